@@ -39,5 +39,85 @@ fn benchmark_symbolic(c: &mut Criterion) {
     });
 }
 
-criterion_group!(benches, benchmark_arithmetic, benchmark_parsing, benchmark_symbolic);
+/// 节点池基准测试：反复求导并展开，比较启用节点池前后的耗时
+fn benchmark_node_pool(c: &mut Criterion) {
+    use yufmath::core::memory::{recycle, NodePoolScope};
+    use yufmath::Yufmath;
+    
+    let yuf = Yufmath::new();
+    let expr = yuf.parse("(x + 1) * (x - 2) * (x + 3) * (x^2 - 4) * x^3").unwrap();
+    for (name, capacity) in [("diff_expand_without_pool", 0), ("diff_expand_with_pool", 4096)] {
+        let _pool = NodePoolScope::new(capacity);
+        c.bench_function(name, |b| {
+            b.iter(|| {
+                recycle(black_box(yuf.diff(&expr, "x").unwrap()));
+                recycle(black_box(yuf.expand(&expr).unwrap()));
+            })
+        });
+    }
+}
+
+/// 内存记账的开销：同一组计算分别在开启与关闭记账时运行
+fn benchmark_memory_accounting(c: &mut Criterion) {
+    use yufmath::{Yufmath, ComputeConfig, MemoryConfig};
+    
+    let expr_text = "(x + 1) * (x - 2) * (x + 3) * (x^2 - 4) * x^3";
+    for (name, track_usage) in [("simplify_expand_without_accounting", false), ("simplify_expand_with_accounting", true)] {
+        let config = ComputeConfig::default().with_memory(MemoryConfig::default().with_usage_tracking(track_usage));
+        let yuf = Yufmath::with_config(config);
+        let expr = yuf.parse(expr_text).unwrap();
+        c.bench_function(name, |b| {
+            b.iter(|| {
+                black_box(yuf.expand(&expr).unwrap());
+                black_box(yuf.simplify(&expr).unwrap());
+            })
+        });
+    }
+}
+
+/// 大表达式作为缓存键：1000 次查询分别逐次遍历整棵树哈希与使用预先计算的结构哈希，
+/// 并经由缓存计算引擎比较两种键
+fn benchmark_hashed_cache_key(c: &mut Criterion) {
+    use std::collections::HashMap;
+    use yufmath::core::{Expression, HashedExpression, Number};
+    use yufmath::engine::{CachedComputeEngine, ComputeEngine};
+    use yufmath::CacheConfig;
+    
+    // 平衡的和式，约 10 万个节点
+    fn balanced_sum(lo: i64, hi: i64) -> Expression {
+        if lo == hi {
+            return Expression::multiply(Expression::number(Number::integer(lo)), Expression::variable("x"));
+        }
+        let mid = (lo + hi) / 2;
+        Expression::add(balanced_sum(lo, mid), balanced_sum(mid + 1, hi))
+    }
+    let expr = balanced_sum(1, 25_000);
+    
+    let plain: HashMap<Expression, usize> = HashMap::from([(expr.clone(), 1)]);
+    c.bench_function("cache_lookup_1000_plain_key", |b| {
+        b.iter(|| (0..1000).filter(|_| plain.contains_key(black_box(&expr))).count())
+    });
+    
+    let key = HashedExpression::new(expr.clone());
+    let hashed: HashMap<HashedExpression, usize> = HashMap::from([(key.clone(), 1)]);
+    c.bench_function("cache_lookup_1000_hashed_key", |b| {
+        b.iter(|| (0..1000).filter(|_| hashed.contains_key(black_box(&key))).count())
+    });
+    
+    // 经由缓存计算引擎的命中路径：每次调用都克隆并哈希整棵树构造键，与复用预先计算哈希的键
+    let engine = CachedComputeEngine::new(CacheConfig::default());
+    let key = HashedExpression::new(expr.clone());
+    engine.simplify_hashed(&key).unwrap();
+    let mut group = c.benchmark_group("cached_engine_simplify_hit");
+    group.sample_size(10);
+    group.bench_function("expression", |b| {
+        b.iter(|| engine.simplify(black_box(&expr)).unwrap())
+    });
+    group.bench_function("hashed_expression", |b| {
+        b.iter(|| engine.simplify_hashed(black_box(&key)).unwrap())
+    });
+    group.finish();
+}
+
+criterion_group!(benches, benchmark_arithmetic, benchmark_parsing, benchmark_symbolic, benchmark_node_pool, benchmark_memory_accounting, benchmark_hashed_cache_key);
 criterion_main!(benches);
\ No newline at end of file
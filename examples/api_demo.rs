@@ -98,6 +98,8 @@ fn config_demo() -> Result<(), Box<dyn std::error::Error>> {
         format_type: FormatType::LaTeX,
         precision: Some(10),
         use_parentheses: true,
+        sort_terms: false,
+        ..FormatOptions::default()
     });
     
     let expr = yuf.parse("sqrt(x^2 + y^2)")?;
@@ -0,0 +1,34 @@
+//! # 用运算符重载构造牛顿迭代
+//!
+//! 用 `+ - * /` 与 `pow` 写出 f(x) = x³ - 2x - 5，符号求导得到迭代式
+//! N(x) = x - f(x)/f'(x)，再数值迭代求根。
+
+use yufmath::{Yufmath, expr};
+use yufmath::core::Expression;
+use std::collections::HashMap;
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let yuf = Yufmath::new();
+    let x = Expression::variable("x");
+
+    // 运算符重载与 expr! 宏得到同一棵表达式树
+    let f = x.clone().pow(3) - 2 * x.clone() - 5;
+    assert_eq!(f, expr!(x^3 - 2*x - 5));
+
+    let df = yuf.diff(&f, "x")?;
+    let step = yuf.simplify(&(&x - &f / &df))?;
+    println!("f(x)  = {}", f);
+    println!("f'(x) = {}", df);
+    println!("N(x)  = {}", step);
+
+    let mut value = 2.0;
+    for i in 1..=6 {
+        let vars = HashMap::from([("x".to_string(), value)]);
+        value = yuf.numerical_evaluate(&step, &vars)?;
+        println!("x{} = {:.15}", i, value);
+    }
+
+    let residual = yuf.numerical_evaluate(&f, &HashMap::from([("x".to_string(), value)]))?;
+    println!("f(x6) = {:e}", residual);
+    Ok(())
+}
@@ -5,7 +5,7 @@ use yufmath::Yufmath;
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("测试增强化简功能...\n");
     
-    let mut yuf = Yufmath::new();
+    let yuf = Yufmath::new();
     
     // 检查增强化简功能是否启用
     println!("增强化简功能状态: {}", yuf.is_enhanced_simplify_enabled());
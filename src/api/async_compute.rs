@@ -5,10 +5,12 @@
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::task::{Context, Poll, Waker};
 use std::thread;
 use std::time::{Duration, Instant};
 use super::{YufmathError, ComputeProgress};
+use crate::tr;
 
 /// 异步计算结果
 pub type AsyncResult<T> = Pin<Box<dyn Future<Output = Result<T, YufmathError>> + Send>>;
@@ -163,14 +165,14 @@ impl<T: Clone> Future for AsyncComputation<T> {
                     if let Some(result) = task.result.take() {
                         Poll::Ready(result)
                     } else {
-                        Poll::Ready(Err(YufmathError::internal("任务已完成但没有结果")))
+                        Poll::Ready(Err(YufmathError::internal(tr!("api.task.no_result"))))
                     }
                 }
                 TaskStatus::Cancelled => {
-                    Poll::Ready(Err(YufmathError::internal("任务已被取消")))
+                    Poll::Ready(Err(YufmathError::internal(tr!("api.task.cancelled"))))
                 }
                 TaskStatus::Error => {
-                    Poll::Ready(Err(YufmathError::internal("任务执行出错")))
+                    Poll::Ready(Err(YufmathError::internal(tr!("api.task.failed"))))
                 }
                 _ => {
                     // 保存 waker 以便任务完成时唤醒
@@ -179,7 +181,7 @@ impl<T: Clone> Future for AsyncComputation<T> {
                 }
             }
         } else {
-            Poll::Ready(Err(YufmathError::internal("无法获取任务锁")))
+            Poll::Ready(Err(YufmathError::internal(tr!("api.task.lock"))))
         }
     }
 }
@@ -190,8 +192,8 @@ pub struct BatchAsyncComputer {
     task_counter: Arc<Mutex<u64>>,
     /// 活跃任务
     active_tasks: Arc<Mutex<Vec<Arc<Mutex<AsyncTask<String>>>>>>,
-    /// 最大并发数
-    max_concurrent: usize,
+    /// 最大并发数，可在运行时调整
+    max_concurrent: AtomicUsize,
 }
 
 impl BatchAsyncComputer {
@@ -200,10 +202,20 @@ impl BatchAsyncComputer {
         Self {
             task_counter: Arc::new(Mutex::new(0)),
             active_tasks: Arc::new(Mutex::new(Vec::new())),
-            max_concurrent,
+            max_concurrent: AtomicUsize::new(max_concurrent),
         }
     }
     
+    /// 最大并发数
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent.load(Ordering::Relaxed)
+    }
+    
+    /// 调整最大并发数，对之后提交的任务生效
+    pub fn set_max_concurrent(&self, max_concurrent: usize) {
+        self.max_concurrent.store(max_concurrent, Ordering::Relaxed);
+    }
+    
     /// 提交批量计算任务
     pub fn submit_batch(&self, expressions: Vec<String>) -> Vec<AsyncComputation<String>> {
         let mut computations = Vec::new();
@@ -257,17 +269,17 @@ impl BatchAsyncComputer {
     ) -> Result<String, YufmathError> {
         // 模拟计算步骤
         let steps = vec![
-            ("解析表达式", 0.2),
-            ("简化表达式", 0.5),
-            ("计算结果", 0.8),
-            ("格式化输出", 1.0),
+            (tr!("api.step.parse"), 0.2),
+            (tr!("api.step.simplify"), 0.5),
+            (tr!("api.step.compute"), 0.8),
+            (tr!("api.step.format_output"), 1.0),
         ];
         
         for (step_name, progress) in steps {
             // 检查是否被取消
             if let Ok(task_guard) = task.lock() {
                 if task_guard.status == TaskStatus::Cancelled {
-                    return Err(YufmathError::internal("计算被取消"));
+                    return Err(YufmathError::internal(tr!("api.task.task_cancelled")));
                 }
             }
             
@@ -284,7 +296,7 @@ impl BatchAsyncComputer {
         }
         
         // 返回模拟结果
-        Ok(format!("计算结果: {}", expression))
+        Ok(tr!("api.task.result", expression = expression))
     }
     
     /// 获取活跃任务数量
@@ -381,6 +393,14 @@ mod tests {
     use super::*;
     use std::time::Duration;
     
+    #[test]
+    fn test_set_max_concurrent() {
+        let computer = BatchAsyncComputer::new(4);
+        assert_eq!(computer.max_concurrent(), 4);
+        computer.set_max_concurrent(16);
+        assert_eq!(computer.max_concurrent(), 16);
+    }
+    
     #[test]
     fn test_async_task_creation() {
         let task = AsyncTask::<String>::new(1);
@@ -1,20 +1,31 @@
 //! # 配置选项定义
 //!
 //! 定义 Yufmath 库的各种配置选项。
+//! 配置可以保存为 TOML 或 JSON 文件，时间长度以秒为单位，缺省的字段取默认值。
+//! 默认有上限的超时、缓存过期时间与内存上限在文件中写 0 表示不限制。
 
+use std::path::Path;
 use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use super::YufmathError;
+use crate::core::CostModel;
+use crate::tr;
 
 /// 并行计算配置
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ParallelConfig {
     /// 是否启用并行计算
     pub enabled: bool,
     /// 线程池大小
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub thread_count: Option<usize>,
-    /// 并行计算的最小表达式复杂度阈值
+    /// 并行计算的最小表达式成本阈值，成本按 `cost_model` 计算
     pub complexity_threshold: usize,
     /// 最大并行任务数
     pub max_parallel_tasks: usize,
+    /// 表达式成本的权重，用于并行阈值和任务耗时预估
+    pub cost_model: CostModel,
 }
 
 impl Default for ParallelConfig {
@@ -24,12 +35,14 @@ impl Default for ParallelConfig {
             thread_count: None, // 使用系统默认
             complexity_threshold: 100,
             max_parallel_tasks: 8,
+            cost_model: CostModel::default(),
         }
     }
 }
 
 /// 缓存配置
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CacheConfig {
     /// 是否启用缓存
     pub enabled: bool,
@@ -40,7 +53,13 @@ pub struct CacheConfig {
     /// 符号缓存大小（符号简化结果）
     pub symbolic_cache_size: usize,
     /// 缓存过期时间
+    #[serde(with = "optional_seconds")]
     pub cache_ttl: Option<Duration>,
+    /// 各层缓存合计的内存上限（字节，按键和值估算），超出时淘汰最久未使用的条目
+    #[serde(with = "optional_limit")]
+    pub max_memory: Option<usize>,
+    /// 表达式成本的权重，用于估算符号缓存条目的计算成本
+    pub cost_model: CostModel,
 }
 
 impl Default for CacheConfig {
@@ -51,21 +70,28 @@ impl Default for CacheConfig {
             exact_cache_size: 500,
             symbolic_cache_size: 200,
             cache_ttl: Some(Duration::from_secs(3600)), // 1小时
+            max_memory: Some(64 * 1024 * 1024), // 64MB
+            cost_model: CostModel::default(),
         }
     }
 }
 
 /// 内存管理配置
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct MemoryConfig {
     /// 最大内存使用量（字节）
+    #[serde(with = "optional_limit")]
     pub max_memory_usage: Option<usize>,
     /// 内存清理阈值
     pub cleanup_threshold: f64,
     /// 是否启用自动垃圾回收
     pub auto_gc: bool,
     /// 垃圾回收间隔
+    #[serde(with = "seconds")]
     pub gc_interval: Duration,
+    /// 是否在计算中估算内存占用；关闭后不统计峰值，也不检查上限
+    pub track_usage: bool,
 }
 
 impl Default for MemoryConfig {
@@ -75,18 +101,21 @@ impl Default for MemoryConfig {
             cleanup_threshold: 0.8, // 80%
             auto_gc: true,
             gc_interval: Duration::from_secs(60), // 1分钟
+            track_usage: true,
         }
     }
 }
 
 /// 计算配置
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct ComputeConfig {
     /// 是否启用进度报告
     pub enable_progress: bool,
     /// 进度更新间隔（毫秒）
     pub progress_interval_ms: u64,
     /// 最大计算时间限制
+    #[serde(with = "optional_seconds")]
     pub max_compute_time: Option<Duration>,
     /// 是否允许用户取消计算
     pub allow_cancellation: bool,
@@ -98,6 +127,13 @@ pub struct ComputeConfig {
     pub cache: CacheConfig,
     /// 内存管理配置
     pub memory: MemoryConfig,
+    /// 概率算法的随机种子，未设置时每个实例随机取种
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub random_seed: Option<u64>,
+    /// 不定积分的结果是否加上积分常数 `C`
+    pub integration_constant: bool,
+    /// 严格模式：调用既不是内置函数、也不是用户函数的函数时报错，默认当作未定义的符号函数放行
+    pub strict_functions: bool,
 }
 
 impl Default for ComputeConfig {
@@ -111,20 +147,26 @@ impl Default for ComputeConfig {
             parallel: ParallelConfig::default(),
             cache: CacheConfig::default(),
             memory: MemoryConfig::default(),
+            random_seed: None,
+            integration_constant: false,
+            strict_functions: false,
         }
     }
 }
 
 /// 精确计算配置
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
 pub struct PrecisionConfig {
     /// 是否强制使用精确计算
     pub force_exact: bool,
     /// 任意精度计算的最大位数限制
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max_precision: Option<usize>,
     /// 是否允许符号表示
     pub allow_symbolic: bool,
     /// 数值近似的阈值
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub approximation_threshold: Option<f64>,
 }
 
@@ -145,6 +187,49 @@ impl ComputeConfig {
         Self::default()
     }
     
+    /// 从配置文件加载，按扩展名选择格式：`.json` 为 JSON，其余按 TOML 解析
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, YufmathError> {
+        let path = path.as_ref();
+        let content = std::fs::read_to_string(path)?;
+        if Self::is_json(path) {
+            Self::from_json(&content)
+        } else {
+            Self::from_toml(&content)
+        }
+    }
+    
+    /// 保存到配置文件，格式规则与 `from_file` 相同
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> Result<(), YufmathError> {
+        let path = path.as_ref();
+        let content = if Self::is_json(path) { self.to_json()? } else { self.to_toml()? };
+        std::fs::write(path, content)?;
+        Ok(())
+    }
+    
+    /// 从 TOML 文本解析配置
+    pub fn from_toml(content: &str) -> Result<Self, YufmathError> {
+        toml::from_str(content).map_err(|e| YufmathError::config(tr!("api.config.parse_toml", error = e)))
+    }
+    
+    /// 序列化为 TOML 文本
+    pub fn to_toml(&self) -> Result<String, YufmathError> {
+        toml::to_string_pretty(self).map_err(|e| YufmathError::config(tr!("api.config.serialize", error = e)))
+    }
+    
+    /// 从 JSON 文本解析配置
+    pub fn from_json(content: &str) -> Result<Self, YufmathError> {
+        serde_json::from_str(content).map_err(|e| YufmathError::config(tr!("api.config.parse_json", error = e)))
+    }
+    
+    /// 序列化为 JSON 文本
+    pub fn to_json(&self) -> Result<String, YufmathError> {
+        serde_json::to_string_pretty(self).map_err(|e| YufmathError::config(tr!("api.config.serialize", error = e)))
+    }
+    
+    fn is_json(path: &Path) -> bool {
+        path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+    }
+    
     /// 设置是否启用进度报告
     pub fn with_progress(mut self, enable: bool) -> Self {
         self.enable_progress = enable;
@@ -192,6 +277,24 @@ impl ComputeConfig {
         self.memory = memory;
         self
     }
+    
+    /// 设置随机种子，使概率算法的结果可复现
+    pub fn with_random_seed(mut self, seed: u64) -> Self {
+        self.random_seed = Some(seed);
+        self
+    }
+    
+    /// 设置不定积分的结果是否加上积分常数 `C`
+    pub fn with_integration_constant(mut self, enabled: bool) -> Self {
+        self.integration_constant = enabled;
+        self
+    }
+    
+    /// 设置是否对未知函数报错
+    pub fn with_strict_functions(mut self, enabled: bool) -> Self {
+        self.strict_functions = enabled;
+        self
+    }
 }
 
 impl PrecisionConfig {
@@ -254,6 +357,12 @@ impl ParallelConfig {
         self.max_parallel_tasks = max_tasks;
         self
     }
+    
+    /// 设置表达式成本的权重
+    pub fn with_cost_model(mut self, cost_model: CostModel) -> Self {
+        self.cost_model = cost_model;
+        self
+    }
 }
 
 impl CacheConfig {
@@ -297,6 +406,24 @@ impl CacheConfig {
         self.cache_ttl = None;
         self
     }
+    
+    /// 设置缓存内存上限
+    pub fn with_max_memory(mut self, max_bytes: usize) -> Self {
+        self.max_memory = Some(max_bytes);
+        self
+    }
+    
+    /// 不限制缓存内存，只按条目数淘汰
+    pub fn without_max_memory(mut self) -> Self {
+        self.max_memory = None;
+        self
+    }
+    
+    /// 设置估算缓存成本用的表达式成本权重
+    pub fn with_cost_model(mut self, cost_model: CostModel) -> Self {
+        self.cost_model = cost_model;
+        self
+    }
 }
 
 impl MemoryConfig {
@@ -334,4 +461,59 @@ impl MemoryConfig {
         self.gc_interval = interval;
         self
     }
-}
\ No newline at end of file
+    
+    /// 设置是否估算计算中的内存占用
+    pub fn with_usage_tracking(mut self, track_usage: bool) -> Self {
+        self.track_usage = track_usage;
+        self
+    }
+    
+    /// 触发一次紧急回收（丢弃缓存）的估算占用；未启用自动回收或没有上限时为 `None`
+    pub fn reclaim_threshold(&self) -> Option<usize> {
+        self.max_memory_usage
+            .filter(|_| self.auto_gc)
+            .map(|max| (max as f64 * self.cleanup_threshold) as usize)
+    }
+}
+
+/// 以秒（可带小数）读写时间长度
+mod seconds {
+    use std::time::Duration;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let secs = f64::deserialize(deserializer)?;
+        Duration::try_from_secs_f64(secs).map_err(serde::de::Error::custom)
+    }
+}
+
+/// 以秒读写可选的时间长度，0 表示不限制
+mod optional_seconds {
+    use std::time::Duration;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(duration: &Option<Duration>, serializer: S) -> Result<S::Ok, S::Error> {
+        super::seconds::serialize(&duration.unwrap_or_default(), serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Duration>, D::Error> {
+        super::seconds::deserialize(deserializer).map(|duration| Some(duration).filter(|d| !d.is_zero()))
+    }
+}
+
+/// 读写可选的数量上限，0 表示不限制
+mod optional_limit {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(limit: &Option<usize>, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(limit.unwrap_or(0) as u64)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<usize>, D::Error> {
+        usize::deserialize(deserializer).map(|limit| Some(limit).filter(|&l| l != 0))
+    }
+}
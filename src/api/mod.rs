@@ -9,8 +9,8 @@ pub mod progress;
 pub mod error;
 pub mod async_compute;
 
-pub use yufmath::Yufmath;
+pub use yufmath::{Yufmath, DiffVariables};
 pub use config::{ComputeConfig, PrecisionConfig, ParallelConfig, CacheConfig, MemoryConfig};
-pub use progress::{ComputeProgress, PerformanceStats, PerformanceMonitor, ProgressCallback, ComputePhase};
-pub use error::YufmathError;
+pub use progress::{ComputeProgress, PerformanceStats, OperationStats, MemoryUsageStats, PerformanceMonitor, ProgressCallback, CancelToken, ComputePhase, PhaseTimings, format_duration};
+pub use error::{YufmathError, FormatError};
 pub use async_compute::{AsyncComputation, BatchAsyncComputer, AsyncConfig, TaskStatus};
\ No newline at end of file
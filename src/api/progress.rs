@@ -2,7 +2,12 @@
 //!
 //! 提供计算进度监控和性能统计功能。
 
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
+use crate::core::memory::MemoryUsage;
+use crate::tr;
 
 /// 计算进度信息
 #[derive(Debug, Clone)]
@@ -46,6 +51,81 @@ pub enum ComputePhase {
     Error,
 }
 
+impl ComputePhase {
+    /// 阶段名称，用于计时输出
+    pub fn label(&self) -> &'static str {
+        match self {
+            ComputePhase::Parsing => tr!("api.phase.parsing"),
+            ComputePhase::Simplification => tr!("api.phase.simplification"),
+            ComputePhase::Computation => tr!("api.phase.computation"),
+            ComputePhase::Formatting => tr!("api.phase.formatting"),
+            ComputePhase::Completed => tr!("api.phase.completed"),
+            ComputePhase::Error => tr!("api.phase.error"),
+        }
+    }
+}
+
+/// 一次计算各阶段的耗时，同一阶段多次进入时累加
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PhaseTimings {
+    /// 按首次进入的顺序排列的阶段与耗时
+    pub phases: Vec<(ComputePhase, Duration)>,
+}
+
+impl PhaseTimings {
+    /// 创建空的计时
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// 累加一个阶段的耗时
+    pub fn record(&mut self, phase: ComputePhase, duration: Duration) {
+        match self.phases.iter_mut().find(|(existing, _)| *existing == phase) {
+            Some((_, total)) => *total += duration,
+            None => self.phases.push((phase, duration)),
+        }
+    }
+    
+    /// 计时存在时测量 `run` 并记入 `phase`；为 None 时直接运行，不读时钟
+    pub fn measure<T>(timings: Option<&mut Self>, phase: ComputePhase, run: impl FnOnce() -> T) -> T {
+        let Some(timings) = timings else {
+            return run();
+        };
+        let start = Instant::now();
+        let result = run();
+        timings.record(phase, start.elapsed());
+        result
+    }
+    
+    /// 各阶段耗时之和
+    pub fn total(&self) -> Duration {
+        self.phases.iter().map(|(_, duration)| *duration).sum()
+    }
+    
+    /// 形如 `耗时 12.3ms（解析 0.5ms，化简 3.1ms，格式化 0.2ms）` 的摘要
+    pub fn summary(&self) -> String {
+        let phases: Vec<String> = self.phases.iter()
+            .map(|(phase, duration)| tr!("api.timing.phase", phase = phase.label(), duration = format_duration(*duration)))
+            .collect();
+        if phases.is_empty() {
+            return tr!("api.timing.total", total = format_duration(self.total()));
+        }
+        tr!("api.timing.summary", total = format_duration(self.total()), phases = phases.join(tr!("api.timing.separator")))
+    }
+}
+
+/// 格式化耗时：不到 1 毫秒显示微秒，超过 1 秒显示秒，其余显示毫秒
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_secs_f64() * 1e6;
+    if micros < 1000.0 {
+        format!("{:.0}µs", micros)
+    } else if micros < 1e6 {
+        format!("{:.1}ms", micros / 1000.0)
+    } else {
+        format!("{:.2}s", micros / 1e6)
+    }
+}
+
 impl ComputeProgress {
     /// 创建新的进度信息
     pub fn new(current_step: impl Into<String>) -> Self {
@@ -136,6 +216,79 @@ impl ComputeProgress {
 /// 返回 false 表示用户请求取消计算
 pub type ProgressCallback = Box<dyn Fn(&ComputeProgress) -> bool + Send + Sync>;
 
+/// 单次计算的取消标志，克隆得到的句柄共享同一标志，可以交给其他线程用来取消这次计算
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken(Arc<AtomicBool>);
+
+impl CancelToken {
+    /// 创建未取消的标志
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// 请求取消，计算在下一个检查点停止
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+    
+    /// 是否已请求取消
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+    
+    /// 两个句柄是否属于同一次计算
+    fn same(&self, other: &CancelToken) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+/// 正在进行的各次计算的取消标志，计算开始时登记，结束时注销
+#[derive(Debug, Default)]
+pub(crate) struct ActiveCalls(std::sync::Mutex<Vec<CancelToken>>);
+
+impl ActiveCalls {
+    /// 登记一次计算，返回的守卫析构时注销
+    pub(crate) fn begin(&self, token: CancelToken) -> ActiveCall<'_> {
+        if let Ok(mut calls) = self.0.lock() {
+            calls.push(token.clone());
+        }
+        ActiveCall { calls: self, token }
+    }
+    
+    /// 取消所有正在进行的计算
+    pub(crate) fn cancel_all(&self) {
+        if let Ok(calls) = self.0.lock() {
+            calls.iter().for_each(CancelToken::cancel);
+        }
+    }
+    
+    /// 是否有正在进行的计算已被取消
+    pub(crate) fn any_cancelled(&self) -> bool {
+        self.0.lock().is_ok_and(|calls| calls.iter().any(CancelToken::is_cancelled))
+    }
+}
+
+/// 一次正在进行的计算，析构时从 [`ActiveCalls`] 中注销
+pub(crate) struct ActiveCall<'a> {
+    calls: &'a ActiveCalls,
+    token: CancelToken,
+}
+
+impl ActiveCall<'_> {
+    /// 这次计算的取消标志
+    pub(crate) fn token(&self) -> &CancelToken {
+        &self.token
+    }
+}
+
+impl Drop for ActiveCall<'_> {
+    fn drop(&mut self) {
+        if let Ok(mut calls) = self.calls.0.lock() {
+            calls.retain(|token| !token.same(&self.token));
+        }
+    }
+}
+
 /// 性能统计
 #[derive(Debug, Default, Clone)]
 pub struct PerformanceStats {
@@ -145,6 +298,10 @@ pub struct PerformanceStats {
     pub avg_compute_time: Duration,
     /// 内存使用量（字节）
     pub memory_usage: usize,
+    /// 单次计算中表达式估算占用的历史峰值（字节）
+    pub peak_memory_usage: usize,
+    /// 因超过内存上限而中止的计算次数
+    pub memory_limit_hits: usize,
     /// 精确计算比例
     pub exact_computation_ratio: f64,
     /// 最近的进度信息
@@ -169,6 +326,50 @@ pub struct PerformanceStats {
     pub gc_count: usize,
     /// 最后一次垃圾回收时间
     pub last_gc_time: Option<Instant>,
+    /// 化简达到迭代上限仍未收敛的次数
+    pub non_converged_simplifications: usize,
+    /// 按操作名（simplify、diff、integrate 等）分组的统计
+    pub operations: BTreeMap<String, OperationStats>,
+    /// 最近一次分阶段计时的结果，只在请求计时的计算后更新
+    pub last_timings: Option<PhaseTimings>,
+}
+
+/// 单个操作类型的统计
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct OperationStats {
+    /// 调用次数
+    pub calls: usize,
+    /// 成功次数
+    pub successes: usize,
+    /// 总耗时
+    pub total_time: Duration,
+    /// 表达式估算占用的峰值（字节）
+    pub peak_memory: usize,
+}
+
+impl OperationStats {
+    /// 平均耗时
+    pub fn avg_time(&self) -> Duration {
+        if self.calls == 0 {
+            Duration::ZERO
+        } else {
+            self.total_time / self.calls as u32
+        }
+    }
+    
+    /// 成功率
+    pub fn success_rate(&self) -> f64 {
+        if self.calls == 0 {
+            0.0
+        } else {
+            self.successes as f64 / self.calls as f64
+        }
+    }
+    
+    /// 失败次数
+    pub fn failures(&self) -> usize {
+        self.calls - self.successes
+    }
 }
 
 impl PerformanceStats {
@@ -229,6 +430,19 @@ impl PerformanceStats {
     }
 }
 
+/// 计算过程中的内存统计（按表达式节点估算）
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MemoryUsageStats {
+    /// 最近一次计算结束时的估算占用（字节）
+    pub current: usize,
+    /// 历史峰值（字节）
+    pub peak: usize,
+    /// 因超过内存上限而中止的计算次数
+    pub limit_hits: usize,
+    /// 各操作的峰值（字节）
+    pub operation_peaks: BTreeMap<String, usize>,
+}
+
 /// 性能监控器
 pub struct PerformanceMonitor {
     stats: PerformanceStats,
@@ -263,7 +477,7 @@ impl PerformanceMonitor {
     /// 更新计算进度
     /// 返回 false 表示用户请求取消计算
     pub fn update_progress(&mut self, progress: ComputeProgress) -> bool {
-        self.stats.last_progress = Some(progress.clone());
+        self.record_progress(progress.clone());
         
         if let Some(ref callback) = self.progress_callback {
             callback(&progress)
@@ -271,7 +485,12 @@ impl PerformanceMonitor {
             true
         }
     }
-    
+
+    /// 只记录最近一次进度，不调用回调
+    pub fn record_progress(&mut self, progress: ComputeProgress) {
+        self.stats.last_progress = Some(progress);
+    }
+
     /// 记录计算开始
     pub fn start_computation(&mut self) -> ComputationTimer {
         ComputationTimer::new()
@@ -317,6 +536,64 @@ impl PerformanceMonitor {
         self.record_memory_sample();
     }
     
+    /// 记录一次 API 操作的耗时与结果，按操作名分组
+    pub fn record_operation(&mut self, operation: &str, duration: Duration, success: bool) {
+        let stats = self.stats.operations.entry(operation.to_string()).or_default();
+        stats.calls += 1;
+        stats.total_time += duration;
+        if success {
+            stats.successes += 1;
+        }
+    }
+    
+    /// 记录一次计算的分阶段耗时
+    pub fn record_phase_timings(&mut self, timings: &PhaseTimings) {
+        self.stats.last_timings = Some(timings.clone());
+    }
+    
+    /// 按操作分组的统计报表，每个操作一行
+    pub fn get_operation_report(&self) -> String {
+        let mut report = format!(
+            "{:<16} {:>8} {:>14} {:>8} {:>12}\n",
+            tr!("performance.operation"), tr!("performance.calls"), tr!("performance.avg_time"), tr!("performance.success_rate"), tr!("performance.peak_memory"),
+        );
+        for (operation, stats) in &self.stats.operations {
+            report.push_str(&format!(
+                "{:<16} {:>8} {:>14} {:>7.1}% {:>12}\n",
+                operation,
+                stats.calls,
+                format!("{:?}", stats.avg_time()),
+                stats.success_rate() * 100.0,
+                format_bytes(stats.peak_memory),
+            ));
+        }
+        report
+    }
+    
+    /// 记录一次操作的内存记账结果，更新当前占用与各级峰值
+    pub fn record_memory(&mut self, operation: &str, usage: MemoryUsage) {
+        let stats = self.stats.operations.entry(operation.to_string()).or_default();
+        stats.peak_memory = stats.peak_memory.max(usage.peak);
+        self.stats.peak_memory_usage = self.stats.peak_memory_usage.max(usage.peak);
+        if usage.limit_exceeded {
+            self.stats.memory_limit_hits += 1;
+        }
+        self.update_memory_usage(usage.current);
+    }
+    
+    /// 内存统计：当前估算占用、历史峰值和各操作的峰值
+    pub fn get_memory_stats(&self) -> MemoryUsageStats {
+        MemoryUsageStats {
+            current: self.stats.memory_usage,
+            peak: self.stats.peak_memory_usage,
+            limit_hits: self.stats.memory_limit_hits,
+            operation_peaks: self.stats.operations.iter()
+                .filter(|(_, stats)| stats.peak_memory > 0)
+                .map(|(operation, stats)| (operation.clone(), stats.peak_memory))
+                .collect(),
+        }
+    }
+    
     /// 记录缓存命中
     pub fn record_cache_hit(&mut self) {
         self.stats.cache_hits += 1;
@@ -329,6 +606,11 @@ impl PerformanceMonitor {
         self.update_cache_hit_rate();
     }
     
+    /// 记录化简达到迭代上限仍未收敛的次数
+    pub fn record_non_converged(&mut self, count: usize) {
+        self.stats.non_converged_simplifications += count;
+    }
+    
     /// 记录并行计算
     pub fn record_parallel_computation(&mut self) {
         self.stats.parallel_computations += 1;
@@ -407,59 +689,45 @@ impl PerformanceMonitor {
     
     /// 获取详细的性能报告
     pub fn get_detailed_report(&self) -> String {
-        format!(
-            "性能统计报告:\n\
-            ================\n\
-            总计算次数: {}\n\
-            成功计算次数: {}\n\
-            失败计算次数: {}\n\
-            成功率: {:.2}%\n\
-            \n\
-            时间统计:\n\
-            --------\n\
-            总计算时间: {:?}\n\
-            平均计算时间: {:?}\n\
-            最快计算时间: {:?}\n\
-            最慢计算时间: {:?}\n\
-            计算吞吐量: {:.2} 计算/秒\n\
-            \n\
-            缓存统计:\n\
-            --------\n\
-            缓存命中次数: {}\n\
-            缓存未命中次数: {}\n\
-            缓存命中率: {:.2}%\n\
-            \n\
-            并行计算:\n\
-            --------\n\
-            并行计算次数: {}\n\
-            并行计算比例: {:.2}%\n\
-            \n\
-            内存统计:\n\
-            --------\n\
-            当前内存使用: {} 字节\n\
-            垃圾回收次数: {}\n\
-            \n\
-            精确计算:\n\
-            --------\n\
-            精确计算比例: {:.2}%\n",
-            self.stats.total_computations,
-            self.stats.successful_computations,
-            self.stats.failed_computations(),
-            self.stats.success_rate() * 100.0,
-            self.stats.total_compute_time,
-            self.stats.avg_compute_time,
-            if self.stats.fastest_computation == Duration::MAX { Duration::ZERO } else { self.stats.fastest_computation },
-            self.stats.slowest_computation,
-            self.stats.throughput(),
-            self.stats.cache_hits,
-            self.stats.cache_misses,
-            self.stats.cache_hit_rate() * 100.0,
-            self.stats.parallel_computations,
-            self.stats.parallel_computation_ratio() * 100.0,
-            self.stats.memory_usage,
-            self.stats.gc_count,
-            self.stats.exact_computation_ratio * 100.0
-        )
+        let stats = &self.stats;
+        let fastest = if stats.fastest_computation == Duration::MAX { Duration::ZERO } else { stats.fastest_computation };
+        let mut report = tr!(
+            "performance.report",
+            total = stats.total_computations,
+            successful = stats.successful_computations,
+            failed = stats.failed_computations(),
+            success_rate = format!("{:.2}", stats.success_rate() * 100.0),
+            total_time = format!("{:?}", stats.total_compute_time),
+            avg_time = format!("{:?}", stats.avg_compute_time),
+            fastest = format!("{:?}", fastest),
+            slowest = format!("{:?}", stats.slowest_computation),
+            throughput = format!("{:.2}", stats.throughput()),
+            cache_hits = stats.cache_hits,
+            cache_misses = stats.cache_misses,
+            cache_hit_rate = format!("{:.2}", stats.cache_hit_rate() * 100.0),
+            parallel = stats.parallel_computations,
+            parallel_ratio = format!("{:.2}", stats.parallel_computation_ratio() * 100.0),
+            memory = stats.memory_usage,
+            peak_memory = stats.peak_memory_usage,
+            limit_hits = stats.memory_limit_hits,
+            gc_count = stats.gc_count,
+            exact_ratio = format!("{:.2}", stats.exact_computation_ratio * 100.0),
+            non_converged = stats.non_converged_simplifications
+        );
+        if !self.stats.operations.is_empty() {
+            report.push_str(tr!("performance.operations_title"));
+            report.push_str(&self.get_operation_report());
+        }
+        report
+    }
+}
+
+/// 把字节数格式化为 B / KB / MB
+fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
     }
 }
 
@@ -2,118 +2,403 @@
 //!
 //! 提供 Yufmath 库的主要入口点和核心功能。
 
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
-use std::sync::atomic::{AtomicBool, Ordering};
-use crate::core::{Expression, Number};
-use crate::parser::{Parser, syntax::ExpressionParser};
-use crate::engine::{ComputeEngine, CachedComputeEngine, EnhancedComputeEngine, RuntimeEnhancedEngine, RuntimeConfig, CacheStats, CacheUsageInfo};
-use crate::formatter::{Formatter, FormatOptions, MultiFormatter};
-use super::{YufmathError, ComputeConfig, PerformanceMonitor, ComputeProgress};
-use super::progress::ProgressCallback;
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Instant;
+use num_bigint::BigInt;
+use crate::core::{cse, BinaryOperator, CseOptions, Expression, HashedExpression, Number, ExprType, MathConstant, signatures};
+use crate::parser::{Parser, ParseError, Statement, syntax::ExpressionParser, latex::LaTeXParser};
+use crate::engine::{conic, equation, exercise, groebner, modular, orthogonal, resultant, vector_calculus};
+use crate::engine::calculus::CalculusEngine;
+use crate::engine::polynomial::{self, PolynomialEngine};
+use crate::engine::simplify::Simplifier;
+use crate::engine::trigonometry;
+use crate::engine::{ComputeEngine, ComputeError, SummationEngine, Solution, DomainAnalysis, Extremum, SeriesSum, LaplaceTransform, TruthTable, Congruence, Distribution, MonomialOrder, Conic, EquationStep, OrthogonalFamily, SimplifyOptions, Assumptions, Property, Optimizer, Optimum, Equivalence, EquivalenceChecker, IdentityResult, VerifiedIntegral, ExerciseKind, TraceEvent, CachedComputeEngine, EnhancedComputeEngine, RuntimeEnhancedEngine, RuntimeConfig, UserFunction, CacheStats, CacheUsageInfo};
+use crate::formatter::{Formatter, FormatOptions, MultiFormatter, LaTeXFormatter, CodeGenerator, CodegenOptions, Language};
+use super::{YufmathError, ComputeConfig, PerformanceMonitor, ComputeProgress, ComputePhase, PhaseTimings};
+use super::progress::{ProgressCallback, CancelToken, ActiveCalls};
+use crate::tr;
 use super::async_compute::{AsyncComputation, BatchAsyncComputer};
 
+/// 实例级进度回调，调用时不持有任何锁
+type SharedProgressCallback = Arc<dyn Fn(&ComputeProgress) -> bool + Send + Sync>;
+
+/// 求导变量：单个变量名，或按顺序依次求导的多个变量名
+///
+/// `&["x", "x", "y"]` 表示先对 x 求两次导再对 y 求导，即 ∂³/∂x²∂y；
+/// 变量名为空字符串时自动选择表达式中唯一的变量，见 [`Yufmath::sole_variable`]。
+pub trait DiffVariables {
+    /// 依次求导的变量名
+    fn variable_names(&self) -> Vec<&str>;
+}
+
+impl DiffVariables for str {
+    fn variable_names(&self) -> Vec<&str> {
+        vec![self]
+    }
+}
+
+impl DiffVariables for String {
+    fn variable_names(&self) -> Vec<&str> {
+        vec![self.as_str()]
+    }
+}
+
+impl<S: AsRef<str>> DiffVariables for [S] {
+    fn variable_names(&self) -> Vec<&str> {
+        self.iter().map(AsRef::as_ref).collect()
+    }
+}
+
+impl<S: AsRef<str>, const N: usize> DiffVariables for [S; N] {
+    fn variable_names(&self) -> Vec<&str> {
+        self.as_slice().variable_names()
+    }
+}
+
+impl<S: AsRef<str>> DiffVariables for Vec<S> {
+    fn variable_names(&self) -> Vec<&str> {
+        self.as_slice().variable_names()
+    }
+}
+
 /// Yufmath 库的主要入口点
+///
+/// 实例是 `Send + Sync` 的，可以放入 `Arc` 在多个线程间共享，
+/// 所有接口都只需要 `&self`，配置也可以用 `update_config` 在运行时修改。
 pub struct Yufmath {
     parser: Box<dyn Parser>,
     engine: Box<dyn ComputeEngine>,
-    formatter: Arc<Mutex<Box<dyn Formatter>>>,
+    formatter: Arc<RwLock<Box<dyn Formatter>>>,
     monitor: Arc<Mutex<PerformanceMonitor>>,
-    config: ComputeConfig,
-    cancelled: Arc<AtomicBool>,
+    progress_callback: Arc<RwLock<Option<SharedProgressCallback>>>,
+    config: RwLock<ComputeConfig>,
+    active_calls: ActiveCalls,
     async_computer: Arc<BatchAsyncComputer>,
 }
 
 impl Yufmath {
     /// 创建新的 Yufmath 实例
     pub fn new() -> Self {
-        let config = ComputeConfig::default();
-        let async_computer = Arc::new(BatchAsyncComputer::new(config.parallel.max_parallel_tasks));
-        
-        Self {
-            parser: Box::new(ExpressionParser::new()),
-            engine: Box::new(RuntimeEnhancedEngine::new()),
-            formatter: Arc::new(Mutex::new(Box::new(MultiFormatter::new()))),
-            monitor: Arc::new(Mutex::new(PerformanceMonitor::new())),
-            config,
-            cancelled: Arc::new(AtomicBool::new(false)),
-            async_computer,
-        }
+        Self::with_config(ComputeConfig::default())
     }
     
     /// 创建带配置的 Yufmath 实例
     pub fn with_config(config: ComputeConfig) -> Self {
         let async_computer = Arc::new(BatchAsyncComputer::new(config.parallel.max_parallel_tasks));
         
-        Self {
+        let yuf = Self {
             parser: Box::new(ExpressionParser::new()),
             engine: Box::new(RuntimeEnhancedEngine::new()),
-            formatter: Arc::new(Mutex::new(Box::new(MultiFormatter::new()))),
+            formatter: Arc::new(RwLock::new(Box::new(MultiFormatter::new()))),
             monitor: Arc::new(Mutex::new(PerformanceMonitor::new())),
-            config,
-            cancelled: Arc::new(AtomicBool::new(false)),
+            progress_callback: Arc::new(RwLock::new(None)),
+            config: RwLock::new(config.clone()),
+            active_calls: ActiveCalls::default(),
             async_computer,
-        }
+        };
+        yuf.apply_config(None, &config);
+        yuf
+    }
+    
+    /// 从 TOML 或 JSON 配置文件创建实例，格式按扩展名判断
+    pub fn from_config_file(path: impl AsRef<std::path::Path>) -> Result<Self, YufmathError> {
+        Ok(Self::with_config(ComputeConfig::from_file(path)?))
     }
     
     /// 解析并计算表达式
+    ///
+    /// 输入可以是 `;` 分隔的多条语句并带 `#`、`//`、`/* */` 注释，如 `a = 2; b = 3; a*b # 乘积`：
+    /// 语句按顺序求值，`名称 = 表达式` 把结果存为会话变量，`f(x) := 表达式` 注册用户函数（见 [`Yufmath::define_function`]），
+    /// 返回最后一条语句的结果。
     pub fn compute(&self, input: &str) -> Result<String, YufmathError> {
-        let expr = self.parser.parse(input)?;
-        
-        // 使用安全计算（如果是运行时增强引擎）
-        let result = if let Some(runtime_engine) = self.engine.as_any().downcast_ref::<RuntimeEnhancedEngine>() {
-            // 使用运行时增强引擎的安全计算
-            runtime_engine.safe_compute(&expr)?
-        } else {
-            // 尝试求值（对于纯数值表达式）
-            let vars = HashMap::new();
-            match self.engine.evaluate(&expr, &vars) {
-                Ok(number) => Expression::Number(number),
-                Err(_) => {
-                    // 如果求值失败，则简化表达式
-                    self.engine.simplify(&expr)?
-                }
-            }
+        let mut results = self.compute_statements(input)?;
+        Ok(results.pop().map(|(_, output)| output).unwrap_or_default())
+    }
+    
+    /// 按顺序计算 `;` 分隔的各条语句，返回每条语句与它的结果；赋值语句的结果是存入变量的值
+    pub fn compute_statements(&self, input: &str) -> Result<Vec<(Statement, String)>, YufmathError> {
+        self.instrumented("compute", || self.run_statements(input, None))
+    }
+    
+    /// 与 [`compute`](Self::compute) 相同，另外返回解析、化简、格式化各阶段的耗时，并记入性能监控器
+    pub fn compute_timed(&self, input: &str) -> Result<(String, PhaseTimings), YufmathError> {
+        let mut timings = PhaseTimings::new();
+        let mut results = self.instrumented("compute", || self.run_statements(input, Some(&mut timings)))?;
+        if let Ok(mut monitor) = self.monitor.lock() {
+            monitor.record_phase_timings(&timings);
+        }
+        Ok((results.pop().map(|(_, output)| output).unwrap_or_default(), timings))
+    }
+    
+    /// 逐条计算语句；给出 `timings` 时在解析、化简、格式化的边界打点，否则不读时钟
+    fn run_statements(&self, input: &str, mut timings: Option<&mut PhaseTimings>) -> Result<Vec<(Statement, String)>, YufmathError> {
+        let statements = PhaseTimings::measure(timings.as_deref_mut(), ComputePhase::Parsing, || self.parse_statements(input))?;
+        let format = |expr: &Expression| -> Result<String, YufmathError> {
+            let formatter = self.formatter.read()
+                .map_err(|_| YufmathError::internal(tr!("api.formatter_lock")))?;
+            Ok(formatter.format(expr))
         };
+        let mut results = Vec::with_capacity(statements.len());
+        for statement in statements {
+            if let (Some(name), Some(parameters)) = (&statement.target, &statement.parameters) {
+                self.define_function(name.clone(), parameters.clone(), statement.expression.clone())?;
+                let body = PhaseTimings::measure(timings.as_deref_mut(), ComputePhase::Formatting, || format(&statement.expression))?;
+                let output = format!("{}({}) := {}", name, parameters.join(", "), body);
+                results.push((statement, output));
+                continue;
+            }
+            let result = PhaseTimings::measure(timings.as_deref_mut(), ComputePhase::Simplification, || self.compute_expression(&statement.expression))?;
+            if let Some(name) = &statement.target {
+                self.set_variable(name.clone(), result.clone())?;
+            }
+            let output = PhaseTimings::measure(timings.as_deref_mut(), ComputePhase::Formatting, || format(&result))?;
+            results.push((statement, output));
+        }
+        Ok(results)
+    }
+    
+    /// 计算单个表达式，代入会话变量
+    fn compute_expression(&self, expr: &Expression) -> Result<Expression, YufmathError> {
+        // 使用安全计算（如果是运行时增强引擎）
+        if let Some(runtime_engine) = self.engine.as_any().downcast_ref::<RuntimeEnhancedEngine>() {
+            return Ok(runtime_engine.safe_compute(expr)?);
+        }
         
-        let formatter = self.formatter.lock()
-            .map_err(|_| YufmathError::internal("无法获取格式化器锁"))?;
-        Ok(formatter.format(&result))
+        // 尝试求值（对于纯数值表达式），失败时简化表达式
+        let vars = HashMap::new();
+        match self.engine.evaluate(expr, &vars) {
+            Ok(number) => Ok(Expression::Number(number)),
+            Err(_) => Ok(self.engine.simplify(expr)?),
+        }
     }
     
-    /// 解析表达式
+    /// 解析表达式；内置函数的参数个数与类型在解析时校验，严格模式下未知函数也会报错
     pub fn parse(&self, input: &str) -> Result<Expression, YufmathError> {
-        Ok(self.parser.parse(input)?)
+        let expr = self.parser.parse(input)?;
+        self.check_function_names(&expr, &[])?;
+        Ok(expr)
+    }
+    
+    /// 解析方程 `左边 = 右边`；单独的 `=` 在表达式中表示赋值，这里把它当作等号，`==`、`<=` 等仍交给解析器
+    pub fn parse_equation(&self, input: &str) -> Result<Expression, YufmathError> {
+        let bytes = input.as_bytes();
+        let split = (0..bytes.len()).find(|&i| bytes[i] == b'='
+            && !matches!(bytes.get(i + 1), Some(b'='))
+            && !matches!(i.checked_sub(1).map(|j| bytes[j]), Some(b'=' | b'<' | b'>' | b'!')));
+        match split {
+            Some(i) => Ok(Expression::binary_op(BinaryOperator::Equal, self.parse(&input[..i])?, self.parse(&input[i + 1..])?)),
+            None => self.parse(input),
+        }
+    }
+    
+    /// 解析 `;` 分隔的多条语句，见 [`Yufmath::compute`]
+    pub fn parse_statements(&self, input: &str) -> Result<Vec<Statement>, YufmathError> {
+        let statements = self.parser.parse_statements(input)?;
+        // 前面语句定义的函数对后面的语句可见，函数体也可以递归调用自身
+        let mut defined = Vec::new();
+        for statement in &statements {
+            if let (Some(name), Some(_)) = (&statement.target, &statement.parameters) {
+                defined.push(name.as_str());
+            }
+            self.check_function_names(&statement.expression, &defined)?;
+        }
+        Ok(statements)
+    }
+    
+    /// 严格模式下拒绝既不是内置函数、也不是用户函数的调用
+    fn check_function_names(&self, expr: &Expression, defined: &[&str]) -> Result<(), YufmathError> {
+        if !self.config.read().unwrap().strict_functions {
+            return Ok(());
+        }
+        let functions = self.get_all_functions()?;
+        match signatures::find_unknown_function(expr, &|name| defined.contains(&name) || functions.contains_key(name)) {
+            Some(name) => Err(ParseError::unknown_function(name).into()),
+            None => Ok(()),
+        }
+    }
+    
+    /// 解析 LaTeX 写法的表达式，支持的构造见 [`LaTeXParser`]
+    pub fn parse_latex(&self, input: &str) -> Result<Expression, YufmathError> {
+        Ok(LaTeXParser::new().parse(input)?)
+    }
+    
+    /// 将表达式格式化为 LaTeX，与当前格式化选项无关
+    pub fn to_latex(&self, expr: &Expression) -> String {
+        LaTeXFormatter::new().format(expr)
     }
     
     /// 简化表达式
     pub fn simplify(&self, expr: &Expression) -> Result<Expression, YufmathError> {
-        Ok(self.engine.simplify(expr)?)
+        self.instrumented("simplify", || Ok(self.engine.simplify(expr)?))
     }
     
-    /// 求导
-    pub fn diff(&self, expr: &Expression, var: &str) -> Result<Expression, YufmathError> {
-        Ok(self.engine.differentiate(expr, var)?)
+    /// 简化表达式并按顺序返回每条生效的规则，用于调试化简结果
+    pub fn simplify_traced(&self, expr: &Expression) -> Result<(Expression, Vec<TraceEvent>), YufmathError> {
+        Ok(self.engine.simplify_traced(expr)?)
+    }
+    
+    /// 求导，结果经过轻量化简（去掉 `*1`、`+0`、`^1` 并折叠数值），如 `x^3+2*x^2+x` 得到 `3*x^2 + 4*x + 1`
+    ///
+    /// `vars` 可以是单个变量名，也可以是依次求导的变量列表，见 [`DiffVariables`]；变量名为空时自动选择。
+    /// 化简遵守计算超时与取消：超过 `max_compute_time` 时返回未化简的导数，被取消时报错。
+    ///
+    /// `floor`、`ceil`、`round`、`trunc` 的导数按几乎处处成立的结果取 `0`：
+    /// 它们在自变量为整数（`round` 为半整数）处跳跃、导数不存在，结果中不表示这一条件。
+    pub fn diff<V: DiffVariables + ?Sized>(&self, expr: &Expression, vars: &V) -> Result<Expression, YufmathError> {
+        let mut names = vars.variable_names();
+        if names.is_empty() {
+            names.push("");
+        }
+        let variables = names.into_iter()
+            .map(|name| self.resolve_variable(expr, name))
+            .collect::<Result<Vec<_>, _>>()?;
+        
+        self.instrumented("diff", || {
+            let call = self.active_calls.begin(CancelToken::new());
+            let start = Instant::now();
+            let limit = self.get_config().max_compute_time;
+            let interrupted = || call.token().is_cancelled() || limit.is_some_and(|limit| start.elapsed() > limit);
+            let mut result = expr.clone();
+            for var in &variables {
+                let derivative = self.engine.differentiate(&result, var)?;
+                result = match Simplifier::simplify_light(&derivative, &interrupted) {
+                    Ok(simplified) => simplified,
+                    Err(ComputeError::Timeout) if call.token().is_cancelled() => return Err(YufmathError::internal(tr!("api.cancelled"))),
+                    Err(ComputeError::Timeout) => derivative,
+                    Err(error) => return Err(error.into()),
+                };
+            }
+            Ok(result)
+        })
+    }
+    
+    /// 对表达式中唯一的变量求导，等同于 `diff(expr, "")`
+    pub fn diff_auto(&self, expr: &Expression) -> Result<Expression, YufmathError> {
+        self.diff(expr, "")
+    }
+    
+    /// 表达式中唯一的变量，省略求导或积分变量时使用
+    ///
+    /// `pi`、`e` 等数学常量不算变量；不含变量或含多个变量时报错，错误信息列出所有变量以便明确指定。
+    pub fn sole_variable(&self, expr: &Expression) -> Result<String, YufmathError> {
+        let mut variables: Vec<String> = expr.get_variables().into_iter()
+            .filter(|name| MathConstant::from_str(name).is_none())
+            .collect();
+        match variables.len() {
+            1 => Ok(variables.remove(0)),
+            0 => Err(ComputeError::domain_error(tr!("api.no_variable")).into()),
+            _ => Err(ComputeError::domain_error(tr!("api.ambiguous_variable", variables = variables.join(", "))).into()),
+        }
+    }
+    
+    /// 空变量名换成表达式中唯一的变量
+    fn resolve_variable(&self, expr: &Expression, var: &str) -> Result<String, YufmathError> {
+        if var.is_empty() {
+            self.sole_variable(expr)
+        } else {
+            Ok(var.to_string())
+        }
+    }
+    
+    /// 求导但不化简，按求导法则直接返回原始导数树，如 `sin(x)` 得到 `cos(x) * 1`
+    pub fn diff_raw(&self, expr: &Expression, var: &str) -> Result<Expression, YufmathError> {
+        Ok(CalculusEngine::new().differentiate(expr, var)?)
     }
     
     /// 求导（别名方法）
-    pub fn differentiate(&self, expr: &Expression, var: &str) -> Result<Expression, YufmathError> {
-        self.diff(expr, var)
+    pub fn differentiate<V: DiffVariables + ?Sized>(&self, expr: &Expression, vars: &V) -> Result<Expression, YufmathError> {
+        self.diff(expr, vars)
     }
     
-    /// 积分
+    /// 不定积分，配置了 `integration_constant` 时结果末尾加上积分常数 `C`；变量名为空时自动选择表达式中唯一的变量
     pub fn integrate(&self, expr: &Expression, var: &str) -> Result<Expression, YufmathError> {
-        Ok(self.engine.integrate(expr, var)?)
+        let var = self.resolve_variable(expr, var)?;
+        self.instrumented("integrate", || {
+            let integral = self.engine.integrate(expr, &var)?;
+            Ok(self.add_integration_constant(integral))
+        })
+    }
+    
+    /// 不定积分并对结果求导回检，与被积函数不恒等时报错，无法判断时标记为未验证
+    pub fn integrate_verified(&self, expr: &Expression, var: &str) -> Result<VerifiedIntegral, YufmathError> {
+        let var = self.resolve_variable(expr, var)?;
+        let integral = self.integrate(expr, &var)?;
+        Ok(EquivalenceChecker::new().verify_integral(expr, integral, &var)?)
     }
     
     /// 计算极限
     pub fn limit(&self, expr: &Expression, var: &str, point: &Expression) -> Result<Expression, YufmathError> {
-        Ok(self.engine.limit(expr, var, point)?)
+        self.instrumented("limit", || Ok(self.engine.limit(expr, var, point)?))
     }
     
     /// 级数展开
     pub fn series(&self, expr: &Expression, var: &str, point: &Expression, order: usize) -> Result<Expression, YufmathError> {
-        Ok(self.engine.series(expr, var, point, order)?)
+        self.instrumented("series", || Ok(self.engine.series(expr, var, point, order)?))
+    }
+    
+    /// 求和，上限为正无穷时计算无穷级数并判断收敛性
+    pub fn sum(&self, expr: &Expression, var: &str, lower: &Expression, upper: &Expression) -> Result<SeriesSum, YufmathError> {
+        self.instrumented("sum", || Ok(self.engine.sum(expr, var, lower, upper)?))
+    }
+    
+    /// 前向差分 `f(var + 1) - f(var)`，多项式的差分次数降低一次
+    pub fn forward_difference(&self, expr: &Expression, var: &str) -> Result<Expression, YufmathError> {
+        Ok(SummationEngine::new().forward_difference(expr, var)?)
+    }
+    
+    /// 检查 `Σ_{k=lower}^{upper} Δf(k) = f(upper + 1) - f(lower)`
+    pub fn verify_difference_sum(&self, f: &Expression, var: &str, lower: i64, upper: i64) -> Result<bool, YufmathError> {
+        Ok(SummationEngine::new().verify_difference_sum(f, var, lower, upper)?)
+    }
+    
+    /// 随机变量的函数 g(X) 的符号期望 E[g(X)]，`var` 是 `g` 中代表随机变量的符号
+    pub fn expectation(&self, distribution: &Distribution, g: &Expression, var: &str) -> Result<Expression, YufmathError> {
+        self.instrumented("expectation", || Ok(distribution.expectation(g, var)?))
+    }
+    
+    /// Laplace 变换 L{f(t)}(s)，同时给出收敛域
+    pub fn laplace(&self, expr: &Expression, t: &str, s: &str) -> Result<LaplaceTransform, YufmathError> {
+        self.instrumented("laplace", || Ok(self.engine.laplace(expr, t, s)?))
+    }
+    
+    /// Laplace 逆变换 L⁻¹{F(s)}(t)
+    pub fn inverse_laplace(&self, expr: &Expression, s: &str, t: &str) -> Result<Expression, YufmathError> {
+        self.instrumented("inverse_laplace", || Ok(self.engine.inverse_laplace(expr, s, t)?))
+    }
+    
+    /// 生成一道练习题，返回（题目, 标准答案）；同一种子总是得到同一道题
+    pub fn generate_exercise(&self, kind: ExerciseKind, seed: u64) -> Result<(Expression, Expression), YufmathError> {
+        Ok(exercise::generate_exercise(kind, seed)?)
+    }
+    
+    /// 批改练习题：答案与标准答案数学上等价即判对，积分题允许相差常数
+    pub fn check_answer(&self, kind: ExerciseKind, problem: &Expression, student_answer: &Expression) -> Result<bool, YufmathError> {
+        Ok(exercise::check_answer(self.engine.as_ref(), kind, problem, student_answer)?)
+    }
+    
+    /// 布尔表达式的真值表，变量超过 20 个时报错
+    pub fn truth_table(&self, expr: &Expression, vars: &[String]) -> Result<TruthTable, YufmathError> {
+        Ok(self.engine.truth_table(expr, vars)?)
+    }
+    
+    /// 解线性同余方程，写成 `3*x ≡ 4 (mod 7)`；无解时返回 `None`
+    pub fn solve_congruence(&self, equation: &str, var: &str) -> Result<Option<Congruence>, YufmathError> {
+        let malformed = || YufmathError::from(ComputeError::domain_error(tr!("api.malformed_congruence", equation = equation)));
+        let (lhs, rest) = equation.split_once('≡').ok_or_else(malformed)?;
+        let (rhs, modulus) = rest.rsplit_once("(mod").ok_or_else(malformed)?;
+        let modulus = modulus.trim().strip_suffix(')').ok_or_else(malformed)?;
+        Ok(modular::solve_congruence(&self.parse(lhs)?, &self.parse(rhs)?, &self.parse(modulus)?, var)?)
+    }
+    
+    /// 转换为析取范式
+    pub fn to_dnf(&self, expr: &Expression) -> Result<Expression, YufmathError> {
+        Ok(self.engine.to_dnf(expr)?)
+    }
+    
+    /// 转换为合取范式
+    pub fn to_cnf(&self, expr: &Expression) -> Result<Expression, YufmathError> {
+        Ok(self.engine.to_cnf(expr)?)
     }
     
     /// 数值计算
@@ -121,34 +406,122 @@ impl Yufmath {
         Ok(self.engine.numerical_evaluate(expr, vars)?)
     }
     
+    /// 在 [from, to] 上等距采样 n 个点（含两端），定义域外或结果非有限的点取值为 None
+    pub fn sample(&self, expr: &Expression, var: &str, from: f64, to: f64, n: usize) -> Result<Vec<(f64, Option<f64>)>, YufmathError> {
+        if n == 0 {
+            return Err(ComputeError::domain_error(tr!("api.sample_count")).into());
+        }
+        if !from.is_finite() || !to.is_finite() {
+            return Err(ComputeError::domain_error(tr!("api.sample_interval")).into());
+        }
+        if let Some(other) = expr.get_variables().into_iter().find(|v| v != var) {
+            return Err(ComputeError::undefined_variable(other).into());
+        }
+        
+        let step = if n > 1 { (to - from) / (n - 1) as f64 } else { 0.0 };
+        let mut vars = HashMap::new();
+        Ok((0..n).map(|i| {
+            // 最后一个点直接取 to，避免累积误差
+            let x = if i + 1 == n && n > 1 { to } else { from + step * i as f64 };
+            vars.insert(var.to_string(), x);
+            let y = self.engine.numerical_evaluate(expr, &vars).ok().filter(|y| y.is_finite());
+            (x, y)
+        }).collect())
+    }
+    
     /// 计算表达式的数值
     pub fn evaluate(&self, expr: &Expression, vars: &HashMap<String, Number>) -> Result<Number, YufmathError> {
         Ok(self.engine.evaluate(expr, vars)?)
     }
     
     /// 设置格式化选项
-    pub fn set_format_options(&mut self, options: FormatOptions) {
-        if let Ok(mut formatter) = self.formatter.lock() {
+    pub fn set_format_options(&self, options: FormatOptions) {
+        if let Ok(mut formatter) = self.formatter.write() {
             formatter.set_options(options);
         }
     }
     
     /// 格式化表达式为字符串
     pub fn format(&self, expr: &Expression) -> String {
-        if let Ok(formatter) = self.formatter.lock() {
+        if let Ok(formatter) = self.formatter.read() {
             formatter.format(expr)
         } else {
             format!("{:?}", expr) // 如果无法获取格式化器，使用调试格式
         }
     }
     
+    /// 生成目标语言的函数源码，参数为表达式中的变量（按字典序）
+    pub fn codegen(&self, expr: &Expression, language: Language, options: &CodegenOptions) -> Result<String, YufmathError> {
+        Ok(CodeGenerator::new(language, options.clone()).generate(expr)?)
+    }
+    
+    /// 提取公共子表达式，返回按依赖顺序排列的定义 `(t1, ...)`、`(t2, ...)` 和用临时变量重写后的表达式
+    pub fn cse(&self, expr: &Expression) -> (Vec<(String, Expression)>, Expression) {
+        self.cse_with(expr, &CseOptions::default())
+    }
+    
+    /// 按给定的复杂度阈值与命名前缀提取公共子表达式
+    pub fn cse_with(&self, expr: &Expression, options: &CseOptions) -> (Vec<(String, Expression)>, Expression) {
+        let (definitions, mut rewritten) = cse(std::slice::from_ref(expr), options);
+        (definitions, rewritten.remove(0))
+    }
+    
     /// 设置是否启用增强化简功能
-    pub fn set_enhanced_simplify(&mut self, enabled: bool) {
+    pub fn set_enhanced_simplify(&self, enabled: bool) {
         if let Some(enhanced_engine) = self.engine.as_any().downcast_ref::<EnhancedComputeEngine>() {
             enhanced_engine.set_auto_simplify(enabled);
         }
     }
     
+    /// 设置化简启用的规则集，如 `SimplifyOptions::algebraic()` 只做代数化简、不合并三角恒等式
+    pub fn set_simplify_options(&self, options: SimplifyOptions) {
+        if let Some(enhanced_engine) = self.enhanced_engine() {
+            enhanced_engine.set_simplify_options(options);
+        }
+    }
+    
+    /// 假设变量具有某种性质，之后的化简据此做更强的变换，如假设 x > 0 后 `sqrt(x^2) = x`
+    pub fn assume(&self, var: &str, property: Property) -> Result<(), YufmathError> {
+        if let Some(enhanced_engine) = self.enhanced_engine() {
+            enhanced_engine.assume(var, property)?;
+        }
+        Ok(())
+    }
+    
+    /// 清空全部变量假设
+    pub fn clear_assumptions(&self) {
+        if let Some(enhanced_engine) = self.enhanced_engine() {
+            enhanced_engine.clear_assumptions();
+        }
+    }
+    
+    /// 当前的变量假设；非增强引擎没有假设
+    fn assumptions(&self) -> Assumptions {
+        self.enhanced_engine().map(EnhancedComputeEngine::assumptions).unwrap_or_default()
+    }
+    
+    /// 推断表达式的类型，已假设为整数或实数的变量按相应数值类型参与推断
+    pub fn infer_type(&self, expr: &Expression) -> ExprType {
+        match self.enhanced_engine() {
+            Some(enhanced_engine) => enhanced_engine.assumptions().infer_type(expr),
+            None => expr.infer_type(),
+        }
+    }
+    
+    /// 设置化简主循环的最多迭代轮数，达到上限仍未收敛时返回当前结果并计入性能统计
+    pub fn set_max_simplify_iterations(&self, max_iterations: usize) {
+        if let Some(enhanced_engine) = self.enhanced_engine() {
+            enhanced_engine.set_max_simplify_iterations(max_iterations);
+        }
+    }
+    
+    /// 直接使用或包在运行时增强引擎里的增强计算引擎
+    fn enhanced_engine(&self) -> Option<&EnhancedComputeEngine> {
+        let engine = self.engine.as_any();
+        engine.downcast_ref::<EnhancedComputeEngine>()
+            .or_else(|| engine.downcast_ref::<RuntimeEnhancedEngine>().map(RuntimeEnhancedEngine::base_engine))
+    }
+    
     /// 检查是否启用了增强化简功能
     pub fn is_enhanced_simplify_enabled(&self) -> bool {
         if let Some(enhanced_engine) = self.engine.as_any().downcast_ref::<EnhancedComputeEngine>() {
@@ -159,38 +532,56 @@ impl Yufmath {
     }
     
     /// 设置进度回调函数
-    pub fn set_progress_callback(&mut self, callback: ProgressCallback) {
-        if let Ok(mut monitor) = self.monitor.lock() {
-            monitor.set_progress_callback(callback);
+    pub fn set_progress_callback(&self, callback: ProgressCallback) {
+        if let Ok(mut slot) = self.progress_callback.write() {
+            *slot = Some(Arc::from(callback));
         }
     }
     
+    /// 取出实例级进度回调，未设置时返回始终继续的回调
+    fn instance_callback(&self) -> SharedProgressCallback {
+        self.progress_callback.read().ok()
+            .and_then(|slot| slot.clone())
+            .unwrap_or_else(|| Arc::new(|_: &ComputeProgress| true))
+    }
+    
     /// 带进度监控的计算表达式
-    pub fn compute_with_progress(&mut self, input: &str) -> Result<String, YufmathError> {
-        self.cancelled.store(false, Ordering::Relaxed);
+    pub fn compute_with_progress(&self, input: &str) -> Result<String, YufmathError> {
+        let callback = self.instance_callback();
+        self.compute_with_callback(input, callback.as_ref())
+    }
+    
+    /// 带进度监控的计算表达式，回调只对本次调用生效
+    pub fn compute_with_callback(&self, input: &str, callback: &dyn Fn(&ComputeProgress) -> bool) -> Result<String, YufmathError> {
+        self.compute_with_token(input, callback, CancelToken::new())
+    }
+    
+    /// 带进度监控的计算表达式，可以在其他线程上用 `token` 的克隆只取消这一次计算
+    pub fn compute_with_token(&self, input: &str, callback: &dyn Fn(&ComputeProgress) -> bool, token: CancelToken) -> Result<String, YufmathError> {
+        let call = self.active_calls.begin(token);
         
         let timer = if let Ok(mut monitor) = self.monitor.lock() {
             monitor.start_computation()
         } else {
-            return Err(YufmathError::internal("无法获取性能监控器"));
+            return Err(YufmathError::internal(tr!("api.monitor_unavailable")));
         };
         
         // 更新进度：开始解析
-        self.update_progress(ComputeProgress::new("解析表达式").with_progress(0.1))?;
+        self.update_progress(ComputeProgress::new(tr!("api.step.parse")).with_progress(0.1), callback, call.token())?;
         
         let expr = self.parser.parse(input)?;
         
         // 更新进度：开始简化
-        self.update_progress(ComputeProgress::new("简化表达式").with_progress(0.5))?;
+        self.update_progress(ComputeProgress::new(tr!("api.step.simplify")).with_progress(0.5), callback, call.token())?;
         
         let simplified = self.engine.simplify(&expr)?;
         
         // 更新进度：格式化输出
-        self.update_progress(ComputeProgress::new("格式化结果").with_progress(0.9))?;
+        self.update_progress(ComputeProgress::new(tr!("api.step.format")).with_progress(0.9), callback, call.token())?;
         
         let result = {
-            let formatter = self.formatter.lock()
-                .map_err(|_| YufmathError::internal("无法获取格式化器锁"))?;
+            let formatter = self.formatter.read()
+                .map_err(|_| YufmathError::internal(tr!("api.formatter_lock")))?;
             formatter.format(&simplified)
         };
         
@@ -200,23 +591,29 @@ impl Yufmath {
         }
         
         // 更新进度：完成
-        self.update_progress(ComputeProgress::new("计算完成").with_progress(1.0))?;
+        self.update_progress(ComputeProgress::new(tr!("api.step.done")).with_progress(1.0), callback, call.token())?;
         
         Ok(result)
     }
     
     /// 带进度监控的简化表达式
-    pub fn simplify_with_progress(&mut self, expr: &Expression) -> Result<Expression, YufmathError> {
-        self.cancelled.store(false, Ordering::Relaxed);
+    pub fn simplify_with_progress(&self, expr: &Expression) -> Result<Expression, YufmathError> {
+        let callback = self.instance_callback();
+        self.simplify_with_callback(expr, callback.as_ref())
+    }
+    
+    /// 带进度监控的简化表达式，回调只对本次调用生效
+    pub fn simplify_with_callback(&self, expr: &Expression, callback: &dyn Fn(&ComputeProgress) -> bool) -> Result<Expression, YufmathError> {
+        let call = self.active_calls.begin(CancelToken::new());
         
         let timer = if let Ok(mut monitor) = self.monitor.lock() {
             monitor.start_computation()
         } else {
-            return Err(YufmathError::internal("无法获取性能监控器"));
+            return Err(YufmathError::internal(tr!("api.monitor_unavailable")));
         };
         
         // 更新进度：开始简化
-        self.update_progress(ComputeProgress::new("分析表达式结构").with_progress(0.2))?;
+        self.update_progress(ComputeProgress::new(tr!("api.step.analyze")).with_progress(0.2), callback, call.token())?;
         
         let result = self.engine.simplify(expr)?;
         
@@ -226,27 +623,33 @@ impl Yufmath {
         }
         
         // 更新进度：完成
-        self.update_progress(ComputeProgress::new("简化完成").with_progress(1.0))?;
+        self.update_progress(ComputeProgress::new(tr!("api.step.simplified")).with_progress(1.0), callback, call.token())?;
         
         Ok(result)
     }
     
     /// 带进度监控的积分计算
-    pub fn integrate_with_progress(&mut self, expr: &Expression, var: &str) -> Result<Expression, YufmathError> {
-        self.cancelled.store(false, Ordering::Relaxed);
+    pub fn integrate_with_progress(&self, expr: &Expression, var: &str) -> Result<Expression, YufmathError> {
+        let callback = self.instance_callback();
+        self.integrate_with_callback(expr, var, callback.as_ref())
+    }
+    
+    /// 带进度监控的积分计算，回调只对本次调用生效
+    pub fn integrate_with_callback(&self, expr: &Expression, var: &str, callback: &dyn Fn(&ComputeProgress) -> bool) -> Result<Expression, YufmathError> {
+        let call = self.active_calls.begin(CancelToken::new());
         
         let timer = if let Ok(mut monitor) = self.monitor.lock() {
             monitor.start_computation()
         } else {
-            return Err(YufmathError::internal("无法获取性能监控器"));
+            return Err(YufmathError::internal(tr!("api.monitor_unavailable")));
         };
         
         // 更新进度：开始积分
-        self.update_progress(ComputeProgress::new("分析被积函数").with_progress(0.1))?;
+        self.update_progress(ComputeProgress::new(tr!("api.step.analyze_integrand")).with_progress(0.1), callback, call.token())?;
         
-        self.update_progress(ComputeProgress::new("应用积分规则").with_progress(0.5))?;
+        self.update_progress(ComputeProgress::new(tr!("api.step.integration_rules")).with_progress(0.5), callback, call.token())?;
         
-        let result = self.engine.integrate(expr, var)?;
+        let result = self.add_integration_constant(self.engine.integrate(expr, var)?);
         
         // 记录计算完成
         if let Ok(mut monitor) = self.monitor.lock() {
@@ -254,24 +657,68 @@ impl Yufmath {
         }
         
         // 更新进度：完成
-        self.update_progress(ComputeProgress::new("积分完成").with_progress(1.0))?;
+        self.update_progress(ComputeProgress::new(tr!("api.step.integrated")).with_progress(1.0), callback, call.token())?;
         
         Ok(result)
     }
     
-    /// 取消当前计算
-    pub fn cancel_computation(&mut self) {
-        self.cancelled.store(true, Ordering::Relaxed);
+    /// 按配置在积分结果末尾加上积分常数，`C` 已被占用时依次改用 `C1`、`C2`……
+    fn add_integration_constant(&self, integral: Expression) -> Expression {
+        if !self.get_config().integration_constant {
+            return integral;
+        }
+        let used = integral.get_variables();
+        let name = std::iter::once("C".to_string())
+            .chain((1..).map(|k| format!("C{}", k)))
+            .find(|name| !used.contains(name))
+            .expect("候选名称无穷多");
+        Expression::add(integral, Expression::variable(name))
+    }
+    
+    /// 取消本实例上所有正在进行的计算
+    ///
+    /// 每次计算有各自的取消标志，之后开始的计算不受影响；只取消其中一次计算时用
+    /// [`compute_with_token`](Self::compute_with_token) 传入的 [`CancelToken`]。
+    pub fn cancel_computation(&self) {
+        self.active_calls.cancel_all();
     }
     
-    /// 检查是否被取消
+    /// 检查是否有正在进行的计算已被取消
     pub fn is_cancelled(&self) -> bool {
-        self.cancelled.load(Ordering::Relaxed)
+        self.active_calls.any_cancelled()
     }
     
     /// 批量计算多个表达式
+    ///
+    /// 只含一条表达式的输入按 [`Expression::canonicalize`] 分组，`2+3` 与 `3+2` 这类等价输入只计算一次，
+    /// 之后直接复用成功的结果；出错的输入不复用，每条各自报错。赋值语句会改变会话变量，
+    /// 遇到时丢弃已有的结果；引用了矩阵或向量变量的乘积不满足交换律，这类输入不参与分组。
     pub fn batch_compute(&self, inputs: &[&str]) -> Vec<Result<String, YufmathError>> {
-        inputs.iter().map(|input| self.compute(input)).collect()
+        let non_commutative: HashSet<String> = self.get_all_variables().unwrap_or_default().into_iter()
+            .filter(|(_, value)| matches!(value, Expression::Matrix(_) | Expression::Vector(_)))
+            .map(|(name, _)| name)
+            .collect();
+        let mut computed: HashMap<HashedExpression, String> = HashMap::new();
+        inputs.iter().map(|input| {
+            let key = match self.parse_statements(input).as_deref() {
+                Ok(statements) if statements.iter().any(|statement| statement.target.is_some()) => {
+                    computed.clear();
+                    None
+                }
+                Ok([statement]) if !statement.expression.get_variables().iter().any(|name| non_commutative.contains(name)) => {
+                    Some(HashedExpression::new(statement.expression.canonicalize()))
+                }
+                _ => None,
+            };
+            if let Some(output) = key.as_ref().and_then(|key| computed.get(key)) {
+                return Ok(output.clone());
+            }
+            let result = self.compute(input);
+            if let (Some(key), Ok(output)) = (key, &result) {
+                computed.insert(key, output.clone());
+            }
+            result
+        }).collect()
     }
     
     /// 批量解析多个表达式
@@ -284,14 +731,97 @@ impl Yufmath {
         expressions.iter().map(|expr| self.simplify(expr)).collect()
     }
     
-    /// 获取配置信息
-    pub fn get_config(&self) -> &ComputeConfig {
-        &self.config
+    /// 获取当前配置的快照
+    pub fn get_config(&self) -> ComputeConfig {
+        self.config.read().unwrap().clone()
+    }
+    
+    /// 在运行时修改配置，变化立即对之后的计算生效
+    ///
+    /// ```rust,ignore
+    /// yuf.update_config(|config| config.max_compute_time = Some(Duration::from_secs(300)));
+    /// ```
+    ///
+    /// 会话变量与缓存内容都会保留；依赖配置的内部组件随之调整。
+    pub fn update_config(&self, update: impl FnOnce(&mut ComputeConfig)) {
+        let (previous, current) = {
+            let mut config = self.config.write().unwrap();
+            let previous = config.clone();
+            update(&mut config);
+            (previous, config.clone())
+        };
+        self.apply_config(Some(&previous), &current);
+    }
+    
+    /// 把配置同步到内部组件，`previous` 为 `None` 时全部同步，否则只同步变化的部分
+    fn apply_config(&self, previous: Option<&ComputeConfig>, config: &ComputeConfig) {
+        let changed = |same: fn(&ComputeConfig, &ComputeConfig) -> bool| previous.is_none_or(|p| !same(p, config));
+        
+        if let Some(seed) = config.random_seed {
+            if changed(|a, b| a.random_seed == b.random_seed) {
+                self.engine.set_random_seed(seed);
+            }
+        }
+        
+        // 超时与精度上限由运行时引擎执行
+        if changed(|a, b| a.max_compute_time == b.max_compute_time && a.precision.max_precision == b.precision.max_precision) {
+            if let Some(runtime_engine) = self.engine.as_any().downcast_ref::<RuntimeEnhancedEngine>() {
+                if let Ok(mut runtime) = runtime_engine.get_runtime_config() {
+                    runtime.enable_time_limit = config.max_compute_time.is_some();
+                    if let Some(limit) = config.max_compute_time {
+                        runtime.max_compute_time_ms = limit.as_millis().try_into().unwrap_or(u64::MAX);
+                    }
+                    runtime.max_integer_digits = config.precision.max_precision
+                        .unwrap_or(RuntimeConfig::default().max_integer_digits);
+                    let _ = runtime_engine.update_runtime_config(runtime);
+                }
+            }
+        }
+        
+        if changed(|a, b| a.cache == b.cache) {
+            if let Some(cached_engine) = self.engine.as_any().downcast_ref::<CachedComputeEngine>() {
+                let _ = cached_engine.update_cache_config(config.cache.clone());
+            } else if let Some(enhanced_engine) = self.enhanced_engine() {
+                let _ = enhanced_engine.update_cache_config(config.cache.clone());
+            }
+        }
+        
+        if changed(|a, b| a.parallel.max_parallel_tasks == b.parallel.max_parallel_tasks) {
+            self.async_computer.set_max_concurrent(config.parallel.max_parallel_tasks);
+        }
+    }
+    
+    /// 计时执行一次 API 操作，并按操作名记录到性能统计
+    fn instrumented<T>(&self, operation: &str, run: impl FnOnce() -> Result<T, YufmathError>) -> Result<T, YufmathError> {
+        let memory = self.config.read().unwrap().memory.clone();
+        if memory.track_usage {
+            crate::core::memory::begin_accounting(memory.max_memory_usage, memory.reclaim_threshold());
+        }
+        let start = Instant::now();
+        let result = run();
+        let elapsed = start.elapsed();
+        let usage = memory.track_usage.then(crate::core::memory::end_accounting);
+        let non_converged = self.enhanced_engine().map_or(0, EnhancedComputeEngine::take_non_converged_simplifications);
+        if let Ok(mut monitor) = self.monitor.lock() {
+            monitor.record_operation(operation, elapsed, result.is_ok());
+            monitor.record_non_converged(non_converged);
+            if let Some(usage) = usage {
+                monitor.record_memory(operation, usage);
+            }
+        }
+        result
     }
     
-    /// 更新配置信息
-    pub fn update_config(&mut self, config: ComputeConfig) {
-        self.config = config;
+    /// 计算过程中的内存统计：当前估算占用、历史峰值和各操作的峰值
+    ///
+    /// 占用按计算中新建的表达式节点和多项式项估算，只在 `memory.track_usage` 开启时统计。
+    pub fn get_memory_stats(&self) -> crate::api::MemoryUsageStats {
+        self.monitor.lock().map(|monitor| monitor.get_memory_stats()).unwrap_or_default()
+    }
+    
+    /// 按操作分组的性能统计报表
+    pub fn get_operation_report(&self) -> String {
+        self.monitor.lock().map(|monitor| monitor.get_operation_report()).unwrap_or_default()
     }
     
     /// 获取性能统计信息
@@ -300,7 +830,7 @@ impl Yufmath {
     }
     
     /// 重置性能统计
-    pub fn reset_performance_stats(&mut self) {
+    pub fn reset_performance_stats(&self) {
         if let Ok(mut monitor) = self.monitor.lock() {
             monitor.reset_stats();
         }
@@ -308,12 +838,108 @@ impl Yufmath {
     
     /// 多项式运算：展开
     pub fn expand(&self, expr: &Expression) -> Result<Expression, YufmathError> {
-        Ok(self.engine.expand(expr)?)
+        self.instrumented("expand", || Ok(self.engine.expand(expr)?))
     }
     
     /// 多项式运算：因式分解
     pub fn factor(&self, expr: &Expression) -> Result<Expression, YufmathError> {
-        Ok(self.engine.factor(expr)?)
+        self.instrumented("factor", || Ok(self.engine.factor(expr)?))
+    }
+    
+    /// 两个多项式关于 `var` 的结式
+    pub fn resultant(&self, p: &Expression, q: &Expression, var: &str) -> Result<Expression, YufmathError> {
+        Ok(resultant::resultant(p, q, var)?)
+    }
+    
+    /// 多项式关于 `var` 的判别式
+    pub fn discriminant(&self, p: &Expression, var: &str) -> Result<Expression, YufmathError> {
+        Ok(resultant::discriminant(p, var)?)
+    }
+    
+    /// 判别圆锥曲线 `expr = 0` 的类型；系数都是有理数时给出标准形、中心或顶点、焦点与离心率
+    pub fn conic_classify(&self, expr: &Expression, vars: &[&str]) -> Result<Conic, YufmathError> {
+        Ok(conic::classify_conic(expr, vars)?)
+    }
+    
+    /// 关于 `vars` 的二次型部分的对称矩阵，交叉项系数平分到对称的两个位置
+    pub fn quadratic_form_matrix(&self, expr: &Expression, vars: &[&str]) -> Result<Expression, YufmathError> {
+        Ok(conic::quadratic_form_matrix(expr, vars)?)
+    }
+    
+    /// 关于 `var` 配方，得到 `a*(x + h)^2 + k`
+    pub fn complete_square(&self, expr: &Expression, var: &str) -> Result<Expression, YufmathError> {
+        Ok(conic::complete_square(expr, &[var])?)
+    }
+    
+    /// 关于多个变量配方；二元且含交叉项时先旋转坐标消去交叉项，新坐标记作 `x'`、`y'`
+    pub fn complete_square_in(&self, expr: &Expression, vars: &[&str]) -> Result<Expression, YufmathError> {
+        Ok(conic::complete_square(expr, vars)?)
+    }
+    
+    /// 正交多项式族 `family` 的第 `n` 个多项式，以 `var` 为变量，如 Legendre 的 `P_2 = (3*x^2 - 1)/2`
+    pub fn orthogonal_polynomial(&self, family: OrthogonalFamily, n: usize, var: &str) -> Result<Expression, YufmathError> {
+        Ok(orthogonal::orthogonal_polynomial(family, n, &Expression::variable(var))?)
+    }
+    
+    /// 方程（或不等式）两边加上 `term`
+    pub fn equation_add(&self, eq: &Expression, term: &Expression) -> Result<EquationStep, YufmathError> {
+        Ok(equation::add(eq, term, &self.assumptions())?)
+    }
+    
+    /// 方程两边减去 `term`
+    pub fn equation_subtract(&self, eq: &Expression, term: &Expression) -> Result<EquationStep, YufmathError> {
+        Ok(equation::subtract(eq, term, &self.assumptions())?)
+    }
+    
+    /// 方程两边乘以 `factor`；因子可能为 0 或符号未知时在 `warnings` 中提示，不等式乘负数时反向
+    pub fn equation_multiply(&self, eq: &Expression, factor: &Expression) -> Result<EquationStep, YufmathError> {
+        Ok(equation::multiply(eq, factor, &self.assumptions())?)
+    }
+    
+    /// 方程两边除以 `divisor`，警告规则同 [`Yufmath::equation_multiply`]
+    pub fn equation_divide(&self, eq: &Expression, divisor: &Expression) -> Result<EquationStep, YufmathError> {
+        Ok(equation::divide(eq, divisor, &self.assumptions())?)
+    }
+    
+    /// 把方程两边的 `var` 代换为 `value`
+    pub fn equation_substitute(&self, eq: &Expression, var: &str, value: &Expression) -> Result<EquationStep, YufmathError> {
+        Ok(equation::substitute(eq, var, value, &self.assumptions())?)
+    }
+    
+    /// 把 `var` 解到左边，如 `y = m*x + b` 得到 `x = (y - b)/m`；变量出现多次时转交 solve
+    pub fn isolate(&self, eq: &Expression, var: &str) -> Result<Expression, YufmathError> {
+        let steps = self.isolate_steps(eq, var)?;
+        Ok(steps.last().map_or_else(|| eq.clone(), |step| step.equation.clone()))
+    }
+    
+    /// 与 [`Yufmath::isolate`] 相同，但返回每一步逆运算及其警告
+    pub fn isolate_steps(&self, eq: &Expression, var: &str) -> Result<Vec<EquationStep>, YufmathError> {
+        Ok(equation::isolate(eq, var, &self.assumptions())?)
+    }
+    
+    /// 多项式组在变量顺序 `vars` 与单项式序 `order` 下的约化 Gröbner 基；字典序下末尾的多项式只含最后的变量
+    pub fn groebner_basis(&self, polys: &[Expression], vars: &[&str], order: MonomialOrder) -> Result<Vec<Expression>, YufmathError> {
+        Ok(groebner::groebner_basis(polys, vars, order)?)
+    }
+    
+    /// 多项式关于 `var` 的系数，按幂升序排列，缺项以 0 占位，如 `3*x^2+x+5` 得到 `[5, 1, 3]`
+    pub fn poly_coeffs(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, YufmathError> {
+        Ok(PolynomialEngine::new().poly_coeffs(expr, var)?)
+    }
+    
+    /// 多项式关于 `var` 的次数
+    pub fn degree(&self, expr: &Expression, var: &str) -> Result<usize, YufmathError> {
+        Ok(PolynomialEngine::new().degree(expr, var)?)
+    }
+    
+    /// 是否为 `var` 的多项式，系数可以含其他变量；`sin(x)`、`1/x`、`x^(1/2)` 都不是 `x` 的多项式
+    pub fn is_polynomial(&self, expr: &Expression, var: &str) -> bool {
+        polynomial::is_polynomial(expr, var)
+    }
+    
+    /// 作为 `var` 的多项式的次数，不是多项式时返回 `None`；次数很高时不展开
+    pub fn polynomial_degree(&self, expr: &Expression, var: &str) -> Option<BigInt> {
+        polynomial::polynomial_degree(expr, var)
     }
     
     /// 多项式运算：收集同类项
@@ -323,7 +949,112 @@ impl Yufmath {
     
     /// 方程求解
     pub fn solve(&self, equation: &Expression, var: &str) -> Result<Vec<Expression>, YufmathError> {
-        Ok(self.engine.solve(equation, var)?)
+        self.instrumented("solve", || Ok(self.engine.solve(equation, var)?))
+    }
+    
+    /// 方程求解（返回根的重数与验证信息）
+    pub fn solve_detailed(&self, equation: &Expression, var: &str) -> Result<Vec<Solution>, YufmathError> {
+        self.instrumented("solve", || Ok(self.engine.solve_detailed(equation, var)?))
+    }
+    
+    /// 定义域分析，返回区间或区间并集
+    pub fn domain(&self, expr: &Expression, var: &str) -> Result<DomainAnalysis, YufmathError> {
+        Ok(self.engine.domain(expr, var)?)
+    }
+    
+    /// 值域估计（基于驻点与端点）
+    pub fn range_estimate(&self, expr: &Expression, var: &str, domain: &Expression) -> Result<DomainAnalysis, YufmathError> {
+        Ok(self.engine.range_estimate(expr, var, domain)?)
+    }
+    
+    /// 求临界点（一阶导为零或不存在的点）
+    pub fn critical_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, YufmathError> {
+        Ok(self.engine.critical_points(expr, var)?)
+    }
+    
+    /// 求局部极值，返回 (点, 类型, 函数值)
+    pub fn local_extrema(&self, expr: &Expression, var: &str) -> Result<Vec<Extremum>, YufmathError> {
+        Ok(self.engine.local_extrema(expr, var)?)
+    }
+    
+    /// 一元函数在自然定义域上的最小值，返回全部最小值点；无下界或取不到时报错
+    pub fn minimize(&self, expr: &Expression, var: &str) -> Result<Vec<Optimum>, YufmathError> {
+        Ok(Optimizer::new().minimize(expr, var)?)
+    }
+    
+    /// 一元函数在自然定义域上的最大值，返回全部最大值点；无上界或取不到时报错
+    pub fn maximize(&self, expr: &Expression, var: &str) -> Result<Vec<Optimum>, YufmathError> {
+        Ok(Optimizer::new().maximize(expr, var)?)
+    }
+    
+    /// 多项式在等式约束下的最小值（拉格朗日乘数法）
+    pub fn minimize_constrained(&self, expr: &Expression, vars: &[&str], constraint: &Expression) -> Result<Vec<Optimum>, YufmathError> {
+        Ok(Optimizer::new().minimize_constrained(expr, vars, constraint)?)
+    }
+    
+    /// 多项式在等式约束下的最大值（拉格朗日乘数法）
+    pub fn maximize_constrained(&self, expr: &Expression, vars: &[&str], constraint: &Expression) -> Result<Vec<Optimum>, YufmathError> {
+        Ok(Optimizer::new().maximize_constrained(expr, vars, constraint)?)
+    }
+    
+    /// 展开和角与倍角，如 `sin(x+y)`、`cos(2x)` 写成单角 sin/cos 的组合
+    pub fn trig_expand(&self, expr: &Expression) -> Result<Expression, YufmathError> {
+        self.instrumented("trig_expand", || Ok(trigonometry::trig_expand(expr)?))
+    }
+    
+    /// 和差化积，如 `sin(x) + sin(y)` 写成 `2 sin((x+y)/2) cos((x-y)/2)`
+    pub fn trig_factor(&self, expr: &Expression) -> Result<Expression, YufmathError> {
+        self.instrumented("trig_factor", || Ok(trigonometry::trig_factor(expr)?))
+    }
+    
+    /// 积化和差，把 sin、cos 的乘积与幂写成线性组合
+    pub fn trig_reduce(&self, expr: &Expression) -> Result<Expression, YufmathError> {
+        self.instrumented("trig_reduce", || Ok(trigonometry::trig_reduce(expr)?))
+    }
+    
+    /// 把三角函数与双曲函数改写为指数形式
+    pub fn trig_to_exp(&self, expr: &Expression) -> Result<Expression, YufmathError> {
+        self.instrumented("trig_to_exp", || Ok(trigonometry::trig_to_exp(expr)?))
+    }
+    
+    /// 把含虚部的指数改写为三角函数
+    pub fn exp_to_trig(&self, expr: &Expression) -> Result<Expression, YufmathError> {
+        self.instrumented("exp_to_trig", || Ok(trigonometry::exp_to_trig(expr)?))
+    }
+    
+    /// 雅可比矩阵，第 i 行是 `exprs[i]` 对各变量的偏导
+    pub fn jacobian(&self, exprs: &[Expression], vars: &[&str]) -> Result<Expression, YufmathError> {
+        self.instrumented("jacobian", || Ok(vector_calculus::jacobian(exprs, vars)?))
+    }
+    
+    /// 向量场的散度
+    pub fn divergence(&self, field: &[Expression], vars: &[&str]) -> Result<Expression, YufmathError> {
+        self.instrumented("divergence", || Ok(vector_calculus::divergence(field, vars)?))
+    }
+    
+    /// 向量场的旋度：三维时为向量，二维时为标量 `∂Q/∂x - ∂P/∂y`
+    pub fn curl(&self, field: &[Expression], vars: &[&str]) -> Result<Expression, YufmathError> {
+        self.instrumented("curl", || Ok(vector_calculus::curl(field, vars)?))
+    }
+    
+    /// 标量场的拉普拉斯算子
+    pub fn laplacian(&self, f: &Expression, vars: &[&str]) -> Result<Expression, YufmathError> {
+        self.instrumented("laplacian", || Ok(vector_calculus::laplacian(f, vars)?))
+    }
+    
+    /// 求拐点
+    pub fn inflection_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, YufmathError> {
+        Ok(self.engine.inflection_points(expr, var)?)
+    }
+    
+    /// 判断两个表达式是否恒等，不恒等时给出反例
+    pub fn are_equivalent(&self, lhs: &Expression, rhs: &Expression) -> Result<Equivalence, YufmathError> {
+        Ok(self.engine.are_equivalent(lhs, rhs)?)
+    }
+    
+    /// 证明 `lhs = rhs` 对 `vars` 的所有取值成立，结论为符号证明、数值支持或带具体取值的反例；`vars` 为空时取全部变量
+    pub fn prove_identity(&self, lhs: &Expression, rhs: &Expression, vars: &[&str]) -> Result<IdentityResult, YufmathError> {
+        Ok(EquivalenceChecker::new().prove_identity(lhs, rhs, vars)?)
     }
     
     /// 方程组求解
@@ -361,6 +1092,16 @@ impl Yufmath {
         Ok(self.engine.lcm(a, b)?)
     }
     
+    /// 数论函数：多个数的最大公约数，空列表返回 0
+    pub fn gcd_list(&self, values: &[Expression]) -> Result<Expression, YufmathError> {
+        Ok(self.engine.gcd_list(values)?)
+    }
+    
+    /// 数论函数：多个数的最小公倍数，空列表返回 1
+    pub fn lcm_list(&self, values: &[Expression]) -> Result<Expression, YufmathError> {
+        Ok(self.engine.lcm_list(values)?)
+    }
+    
     /// 数论函数：素数判断
     pub fn is_prime(&self, n: &Expression) -> Result<bool, YufmathError> {
         Ok(self.engine.is_prime(n)?)
@@ -469,13 +1210,12 @@ impl Yufmath {
         self.async_computer.cleanup_completed();
     }
     
-    /// 获取缓存统计信息
+    /// 获取缓存统计信息：缓存计算引擎的多层缓存，或增强引擎的化简规则缓存
     pub fn get_cache_stats(&self) -> Option<CacheStats> {
-        // 尝试将引擎转换为 CachedComputeEngine
         if let Some(cached_engine) = self.engine.as_any().downcast_ref::<CachedComputeEngine>() {
             cached_engine.get_cache_stats().ok()
         } else {
-            None
+            self.enhanced_engine()?.get_cache_stats().ok()
         }
     }
     
@@ -484,7 +1224,7 @@ impl Yufmath {
         if let Some(cached_engine) = self.engine.as_any().downcast_ref::<CachedComputeEngine>() {
             cached_engine.get_cache_usage().ok()
         } else {
-            None
+            self.enhanced_engine()?.get_cache_usage().ok()
         }
     }
     
@@ -492,8 +1232,10 @@ impl Yufmath {
     pub fn cleanup_cache(&self) -> Result<(), YufmathError> {
         if let Some(cached_engine) = self.engine.as_any().downcast_ref::<CachedComputeEngine>() {
             cached_engine.cleanup_cache().map_err(YufmathError::from)
+        } else if let Some(enhanced_engine) = self.enhanced_engine() {
+            enhanced_engine.cleanup_cache().map_err(YufmathError::from)
         } else {
-            Ok(()) // 如果不是缓存引擎，直接返回成功
+            Ok(()) // 引擎没有缓存，直接返回成功
         }
     }
     
@@ -501,8 +1243,10 @@ impl Yufmath {
     pub fn clear_cache(&self) -> Result<(), YufmathError> {
         if let Some(cached_engine) = self.engine.as_any().downcast_ref::<CachedComputeEngine>() {
             cached_engine.clear_cache().map_err(YufmathError::from)
+        } else if let Some(enhanced_engine) = self.enhanced_engine() {
+            enhanced_engine.clear_cache().map_err(YufmathError::from)
         } else {
-            Ok(()) // 如果不是缓存引擎，直接返回成功
+            Ok(()) // 引擎没有缓存，直接返回成功
         }
     }
     
@@ -511,7 +1255,7 @@ impl Yufmath {
         if let Some(runtime_engine) = self.engine.as_any().downcast_ref::<RuntimeEnhancedEngine>() {
             runtime_engine.set_variable(name, value).map_err(YufmathError::from)
         } else {
-            Err(YufmathError::internal("当前引擎不支持变量管理"))
+            Err(YufmathError::internal(tr!("api.variables_unsupported")))
         }
     }
     
@@ -557,12 +1301,42 @@ impl Yufmath {
         }
     }
     
+    /// 注册符号函数 `name(parameters) = body`，如注册 `f(x) = x^2` 后 `compute("f(3)")` 得到 9
+    ///
+    /// 之后计算、求导、积分等运算中的调用处展开为代入实参后的函数体，求导因此自然遵循链式法则；
+    /// 未注册的函数调用保留原样。`compute` 中的 `f(x) := x^2` 语句也会注册函数。
+    pub fn define_function(&self, name: String, parameters: Vec<String>, body: Expression) -> Result<(), YufmathError> {
+        if let Some(runtime_engine) = self.engine.as_any().downcast_ref::<RuntimeEnhancedEngine>() {
+            runtime_engine.define_function(name, parameters, body).map_err(YufmathError::from)
+        } else {
+            Err(YufmathError::internal(tr!("api.functions_unsupported")))
+        }
+    }
+    
+    /// 获取所有用户函数
+    pub fn get_all_functions(&self) -> Result<HashMap<String, UserFunction>, YufmathError> {
+        if let Some(runtime_engine) = self.engine.as_any().downcast_ref::<RuntimeEnhancedEngine>() {
+            runtime_engine.get_all_functions().map_err(YufmathError::from)
+        } else {
+            Ok(HashMap::new())
+        }
+    }
+    
+    /// 删除用户函数
+    pub fn remove_function(&self, name: &str) -> Result<bool, YufmathError> {
+        if let Some(runtime_engine) = self.engine.as_any().downcast_ref::<RuntimeEnhancedEngine>() {
+            runtime_engine.remove_function(name).map_err(YufmathError::from)
+        } else {
+            Ok(false)
+        }
+    }
+    
     /// 更新运行时配置
     pub fn update_runtime_config(&self, config: RuntimeConfig) -> Result<(), YufmathError> {
         if let Some(runtime_engine) = self.engine.as_any().downcast_ref::<RuntimeEnhancedEngine>() {
             runtime_engine.update_runtime_config(config).map_err(YufmathError::from)
         } else {
-            Err(YufmathError::internal("当前引擎不支持运行时配置"))
+            Err(YufmathError::internal(tr!("api.runtime_config_unsupported")))
         }
     }
     
@@ -571,7 +1345,7 @@ impl Yufmath {
         if let Some(runtime_engine) = self.engine.as_any().downcast_ref::<RuntimeEnhancedEngine>() {
             runtime_engine.get_runtime_config().map_err(YufmathError::from)
         } else {
-            Err(YufmathError::internal("当前引擎不支持运行时配置"))
+            Err(YufmathError::internal(tr!("api.runtime_config_unsupported")))
         }
     }
     
@@ -596,16 +1370,19 @@ impl Yufmath {
     }
     
     /// 内部方法：更新进度
-    fn update_progress(&self, progress: ComputeProgress) -> Result<(), YufmathError> {
-        if !self.config.enable_progress {
+    ///
+    /// 监控器锁只用于记录进度，回调在锁外调用，回调中可以安全地访问本实例。
+    fn update_progress(&self, progress: ComputeProgress, callback: &dyn Fn(&ComputeProgress) -> bool, token: &CancelToken) -> Result<(), YufmathError> {
+        if !self.config.read().unwrap().enable_progress {
             return Ok(());
         }
         
         if let Ok(mut monitor) = self.monitor.lock() {
-            let should_continue = monitor.update_progress(progress);
-            if !should_continue || self.is_cancelled() {
-                return Err(YufmathError::internal("计算被用户取消"));
-            }
+            monitor.record_progress(progress.clone());
+        }
+        
+        if !callback(&progress) || token.is_cancelled() {
+            return Err(YufmathError::internal(tr!("api.cancelled")));
         }
         
         Ok(())
@@ -2,7 +2,13 @@
 //!
 //! 定义命令行工具的参数结构。
 
-use clap::{Parser, Subcommand};
+use std::ffi::OsString;
+use clap::{Command, CommandFactory, FromArgMatches, Parser, Subcommand};
+use crate::engine::ExerciseKind;
+use crate::cli::terminal::ColorConfig;
+use crate::formatter::{MultilineEnv, TermOrder};
+use crate::i18n::{self, Locale};
+use crate::tr;
 
 /// Yufmath 命令行工具
 #[derive(Parser)]
@@ -38,9 +44,105 @@ pub struct CliArgs {
     #[arg(long)]
     pub no_progress: bool,
     
-    /// 计算超时时间（秒）
+    /// 计算超时时间（秒），优先于配置文件中的设置
     #[arg(long)]
     pub timeout: Option<u64>,
+    
+    /// 从 TOML 或 JSON 文件加载计算配置
+    #[arg(long)]
+    pub config: Option<String>,
+    
+    /// 按顺序打印化简过程中生效的规则（可用环境变量 YUFMATH_TRACE_FILTER 只看某条规则）
+    #[arg(long)]
+    pub trace: bool,
+    
+    /// 结束后打印各操作的耗时与内存峰值
+    #[arg(long)]
+    pub stats: bool,
+    
+    /// 在结果后打印耗时，compute 命令按解析、化简、格式化分阶段列出
+    #[arg(long)]
+    pub time: bool,
+    
+    /// 显示前把多项式按次数降序排列，乘法因子按数值、变量、函数的顺序排列
+    #[arg(long)]
+    pub sort_terms: bool,
+    
+    /// 加法项的排列顺序，指定后即重排（隐含 --sort-terms）
+    #[arg(long, value_enum)]
+    pub term_order: Option<TermSort>,
+    
+    /// LaTeX 输出的行宽，估算宽度超出时在加法项之间断行
+    #[arg(long)]
+    pub line_width: Option<usize>,
+    
+    /// LaTeX 断行后使用的环境
+    #[arg(long, value_enum, default_value = "align")]
+    pub multiline_env: LatexEnv,
+    
+    /// 界面语言（zh 或 en），默认按环境变量 YUFMATH_LANG 和系统 locale 选择
+    #[arg(long, global = true, value_parser = parse_locale)]
+    pub lang: Option<Locale>,
+    
+    /// 彩色输出：auto 时仅在输出到终端且未设置 NO_COLOR 时着色
+    #[arg(long, global = true, value_enum, default_value = "auto")]
+    pub color: ColorChoice,
+}
+
+impl CliArgs {
+    /// 解析命令行参数；先按 `--lang`、`YUFMATH_LANG`、系统 locale 的顺序确定界面语言，
+    /// 帮助和参数错误也用该语言输出
+    pub fn parse_localized() -> Self {
+        let args: Vec<OsString> = std::env::args_os().collect();
+        i18n::set_locale(requested_locale(&args).unwrap_or_else(Locale::detect));
+        let matches = localize_command(Self::command(), "yufmath").get_matches_from(args);
+        Self::from_arg_matches(&matches).unwrap_or_else(|e| e.exit())
+    }
+    
+    /// 显示时加法项的顺序；未指定 `--sort-terms` 或 `--term-order` 时为 None，保持原有顺序
+    pub fn term_order(&self) -> Option<TermOrder> {
+        match self.term_order {
+            Some(sort) => Some(sort.term_order()),
+            None => self.sort_terms.then(TermOrder::default),
+        }
+    }
+}
+
+/// 解析 `--lang` 的取值
+fn parse_locale(value: &str) -> Result<Locale, String> {
+    Locale::from_tag(value).ok_or_else(|| tr!("cli.invalid_lang", lang = value))
+}
+
+/// 在完整解析之前从参数中找出 `--lang`，取值无效时返回 None，留给 clap 报错
+fn requested_locale(args: &[OsString]) -> Option<Locale> {
+    let mut args = args.iter().skip(1).filter_map(|arg| arg.to_str()).take_while(|arg| *arg != "--");
+    while let Some(arg) = args.next() {
+        let value = match arg.strip_prefix("--lang") {
+            Some("") => args.next(),
+            Some(rest) => rest.strip_prefix('='),
+            None => continue,
+        };
+        return value.and_then(Locale::from_tag);
+    }
+    None
+}
+
+/// 用当前语言的帮助表替换命令及其参数、子命令的说明；`path` 为以 `.` 连接的子命令路径，
+/// 表中没有的条目保留文档注释里的中文说明
+fn localize_command(command: Command, path: &str) -> Command {
+    let command = match i18n::help(path) {
+        Some(about) => command.about(about),
+        None => command,
+    };
+    command
+        .mut_args(|arg| match i18n::help(&format!("{}.{}", path, arg.get_id())) {
+            Some(help) => arg.help(help),
+            None => arg,
+        })
+        .mut_subcommands(|subcommand| {
+            let path = format!("{}.{}", path, subcommand.get_name());
+            localize_command(subcommand, &path)
+        })
 }
 
 /// 子命令定义
@@ -60,15 +162,18 @@ pub enum Commands {
     Diff {
         /// 要求导的表达式
         expression: String,
-        /// 求导变量
-        variable: String,
+        /// 求导变量，给出多个时依次求导；省略时取表达式中唯一的变量
+        variables: Vec<String>,
     },
     /// 对变量积分
     Integrate {
         /// 要积分的表达式
         expression: String,
-        /// 积分变量
-        variable: String,
+        /// 积分变量，省略时取表达式中唯一的变量
+        variable: Option<String>,
+        /// 在结果末尾加上积分常数 C
+        #[arg(long)]
+        with_constant: bool,
     },
     /// 求解方程
     Solve {
@@ -87,6 +192,16 @@ pub enum Commands {
         /// 要展开的表达式
         expression: String,
     },
+    /// 展开三角函数的和角与倍角
+    TrigExpand {
+        /// 要展开的表达式
+        expression: String,
+    },
+    /// 三角函数和差化积
+    TrigFactor {
+        /// 要变换的表达式
+        expression: String,
+    },
     /// 计算极限
     Limit {
         /// 表达式
@@ -108,6 +223,121 @@ pub enum Commands {
         #[arg(short, long, default_value = "5")]
         order: usize,
     },
+    /// Laplace 变换
+    Laplace {
+        /// 原函数
+        expression: String,
+        /// 原函数的自变量
+        #[arg(default_value = "t")]
+        variable: String,
+        /// 像函数的自变量
+        #[arg(default_value = "s")]
+        transform_variable: String,
+        /// 同时输出收敛域
+        #[arg(long)]
+        region: bool,
+    },
+    /// Laplace 逆变换
+    InverseLaplace {
+        /// 像函数
+        expression: String,
+        /// 像函数的自变量
+        #[arg(default_value = "s")]
+        variable: String,
+        /// 原函数的自变量
+        #[arg(default_value = "t")]
+        time_variable: String,
+    },
+    /// 分析函数的定义域、临界点、极值与拐点
+    Analyze {
+        /// 要分析的表达式
+        expression: String,
+        /// 自变量
+        variable: String,
+    },
+    /// 校验等式是否恒成立
+    Verify {
+        /// 要校验的等式，形如 "lhs = rhs"
+        equation: String,
+    },
+    /// 证明恒等式，输出结论和依据（符号证明、数值检验或反例）
+    Prove {
+        /// 左边
+        lhs: String,
+        /// 右边
+        rhs: String,
+        /// 检验的变量，逗号分隔（默认为两边出现的全部变量）
+        #[arg(long, value_delimiter = ',')]
+        vars: Vec<String>,
+    },
+    /// 导出函数采样数据，供外部工具绘图
+    PlotData {
+        /// 要采样的表达式
+        expression: String,
+        /// 自变量
+        variable: String,
+        /// 区间起点
+        #[arg(long, allow_negative_numbers = true)]
+        from: f64,
+        /// 区间终点
+        #[arg(long, allow_negative_numbers = true)]
+        to: f64,
+        /// 采样点数（含两端）
+        #[arg(long, default_value = "100")]
+        n: usize,
+        /// 将 CSV 写入文件而不是标准输出
+        #[arg(long)]
+        csv: Option<String>,
+        /// 以 JSON 输出到标准输出
+        #[arg(long, conflicts_with = "csv")]
+        json: bool,
+        /// 跳过定义域外的点（默认保留并标为 NaN）
+        #[arg(long)]
+        skip_undefined: bool,
+    },
+    /// 在终端画出函数图像
+    PlotAscii {
+        /// 要绘制的表达式，多条曲线用 `;` 分隔
+        expression: String,
+        /// 自变量
+        variable: String,
+        /// 区间起点
+        #[arg(allow_negative_numbers = true)]
+        from: f64,
+        /// 区间终点
+        #[arg(allow_negative_numbers = true)]
+        to: f64,
+        /// 图像宽度（字符数），默认取终端宽度
+        #[arg(long)]
+        width: Option<usize>,
+        /// 图像高度（行数），默认取终端高度
+        #[arg(long)]
+        height: Option<usize>,
+        /// y 轴下限，与 --y-max 一起指定，默认按函数值自动确定
+        #[arg(long, allow_negative_numbers = true, requires = "y_max")]
+        y_min: Option<f64>,
+        /// y 轴上限
+        #[arg(long, allow_negative_numbers = true, requires = "y_min")]
+        y_max: Option<f64>,
+        /// 用 ASCII 字符代替 Unicode 盲文点阵
+        #[arg(long)]
+        ascii: bool,
+    },
+    /// 生成练习题，先列题目再列答案
+    Quiz {
+        /// 题型
+        #[arg(value_enum)]
+        kind: QuizKind,
+        /// 题目数量
+        #[arg(short, long, default_value = "10")]
+        count: usize,
+        /// 难度（1 到 3）
+        #[arg(short, long, default_value = "1")]
+        difficulty: u8,
+        /// 随机种子，相同种子生成相同的题目；不指定时随机选取并打印出来
+        #[arg(long)]
+        seed: Option<u64>,
+    },
     /// 批处理模式
     Batch {
         /// 输入文件路径
@@ -117,6 +347,17 @@ pub enum Commands {
         #[arg(short, long)]
         output: Option<String>,
     },
+    /// 监视输入文件，每次保存后重新计算并标出有变化的行
+    Watch {
+        /// 输入文件路径，不存在时等待它被创建
+        input: String,
+        /// 持续写入结果的输出文件（可选），LaTeX 格式时为完整文档
+        #[arg(short, long)]
+        output: Option<String>,
+        /// 检查文件变化的间隔（毫秒）
+        #[arg(long, default_value = "500")]
+        interval: u64,
+    },
     /// 启动交互模式
     Interactive,
     /// 启动笔记本模式
@@ -141,4 +382,102 @@ pub enum OutputFormat {
     Latex,
     /// MathML 格式
     Mathml,
-}
\ No newline at end of file
+    /// Maxima 语法
+    Maxima,
+    /// 制表符分隔的 `输入<TAB>结果<TAB>耗时`，用于 batch 命令；其他命令按标准格式输出
+    Tsv,
+}
+
+/// 多行 LaTeX 环境
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum LatexEnv {
+    /// align* 环境
+    Align,
+    /// multline* 环境
+    Multline,
+    /// breqn 宏包的 dmath* 环境
+    Breqn,
+}
+
+impl LatexEnv {
+    /// 对应的格式化环境
+    pub fn multiline_env(self) -> MultilineEnv {
+        match self {
+            LatexEnv::Align => MultilineEnv::Align,
+            LatexEnv::Multline => MultilineEnv::Multline,
+            LatexEnv::Breqn => MultilineEnv::Breqn,
+        }
+    }
+}
+
+/// `--term-order` 的取值
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TermSort {
+    /// 次数降序：x^2 + x + 1
+    DegreeDesc,
+    /// 次数升序：1 + x + x^2
+    DegreeAsc,
+    /// 按变量字典序
+    Lex,
+}
+
+impl TermSort {
+    /// 对应的格式化顺序
+    pub fn term_order(self) -> TermOrder {
+        match self {
+            TermSort::DegreeDesc => TermOrder::DegreeDesc,
+            TermSort::DegreeAsc => TermOrder::DegreeAsc,
+            TermSort::Lex => TermOrder::Lexicographic,
+        }
+    }
+}
+
+/// 彩色输出的开关
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorChoice {
+    /// 总是着色
+    Always,
+    /// 按终端检测，遵守 NO_COLOR 与 FORCE_COLOR
+    Auto,
+    /// 从不着色
+    Never,
+}
+
+impl ColorChoice {
+    /// 对应的颜色配置
+    pub fn color_config(self) -> ColorConfig {
+        match self {
+            ColorChoice::Always => ColorConfig { force_color: true, no_color: false, auto_detect: false },
+            ColorChoice::Auto => ColorConfig::from_env(),
+            ColorChoice::Never => ColorConfig { force_color: false, no_color: true, auto_detect: false },
+        }
+    }
+}
+
+/// 练习题题型
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum QuizKind {
+    /// 求导
+    Diff,
+    /// 不定积分
+    Integrate,
+    /// 化简
+    Simplify,
+    /// 因式分解
+    Factor,
+    /// 解方程
+    Solve,
+}
+
+impl QuizKind {
+    /// 对应的练习题类型
+    pub fn exercise(self, difficulty: u8) -> ExerciseKind {
+        match self {
+            QuizKind::Diff => ExerciseKind::Differentiation { difficulty },
+            QuizKind::Integrate => ExerciseKind::Integration { difficulty },
+            QuizKind::Simplify => ExerciseKind::Simplification { difficulty },
+            QuizKind::Factor => ExerciseKind::Factorization { difficulty },
+            QuizKind::Solve => ExerciseKind::Equation { difficulty },
+        }
+    }
+}
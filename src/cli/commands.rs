@@ -3,70 +3,109 @@
 //! 实现各种命令行命令的处理逻辑。
 
 use super::args::{CliArgs, Commands, OutputFormat};
+use super::plot::{render_plot, Curve, PlotOptions, PlotStyle};
 use crate::notebook::{NotebookFormat, NotebookDeserializer, NotebookUI};
-use crate::api::Yufmath;
+use crate::api::{Yufmath, YufmathError, ComputeConfig};
+use crate::core::{BinaryOperator, Expression};
+use crate::engine::trace::{TraceEvent, TRACE_FILTER_ENV};
+use crate::engine::{ExerciseKind, IdentityResult};
+use crate::engine::random::SeededRng;
+use crate::formatter::{FormatOptions, Formatter, StandardFormatter};
+use crate::parser::ParseError;
+use crate::tr;
+use ansi_term::Colour;
 use std::path::Path;
+use std::time::{Duration, Instant};
 
 /// 运行命令行命令
 pub fn run_command(args: CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let yuf = Yufmath::with_config(load_config(&args)?);
+    if let Some(term_order) = args.term_order() {
+        yuf.set_format_options(FormatOptions { sort_terms: true, term_order, ..FormatOptions::default() });
+    }
     match args.command {
         Some(Commands::Notepad { file, title, terminal }) => {
             run_notepad_command(file, title, terminal)?;
         }
         Some(Commands::Interactive) => {
-            crate::cli::run_interactive()?;
+            crate::cli::run_interactive_with_config(yuf.get_config())?;
+        }
+        Some(Commands::Compute { ref expression }) | Some(Commands::Simplify { ref expression }) if args.trace => {
+            let expr = yuf.parse(expression)?;
+            let (simplified, events) = yuf.simplify_traced(&expr)?;
+            for line in trace_report(&yuf, &events) {
+                println!("{}", line);
+            }
+            let result = yuf.format(&simplified);
+            println!("{}", format_output(&result, &args.format));
         }
         Some(Commands::Compute { expression }) => {
-            let yuf = Yufmath::new();
             let result = yuf.compute(&expression)?;
             println!("{}", format_output(&result, &args.format));
         }
         Some(Commands::Simplify { expression }) => {
-            let yuf = Yufmath::new();
             let expr = yuf.parse(&expression)?;
             let simplified = yuf.simplify(&expr)?;
             let result = yuf.format(&simplified);
             println!("{}", format_output(&result, &args.format));
         }
-        Some(Commands::Diff { expression, variable }) => {
-            let yuf = Yufmath::new();
+        Some(Commands::Diff { expression, variables }) => {
             let expr = yuf.parse(&expression)?;
-            let derivative = yuf.diff(&expr, &variable)?;
+            let derivative = yuf.diff(&expr, &variables)?;
             let result = yuf.format(&derivative);
             println!("{}", format_output(&result, &args.format));
         }
-        Some(Commands::Integrate { expression, variable }) => {
-            let yuf = Yufmath::new();
+        Some(Commands::Integrate { expression, variable, with_constant }) => {
+            if with_constant {
+                yuf.update_config(|config| config.integration_constant = true);
+            }
             let expr = yuf.parse(&expression)?;
-            let integral = yuf.integrate(&expr, &variable)?;
+            let integral = yuf.integrate(&expr, variable.as_deref().unwrap_or(""))?;
             let result = yuf.format(&integral);
             println!("{}", format_output(&result, &args.format));
         }
         Some(Commands::Solve { equation, variable }) => {
-            let yuf = Yufmath::new();
-            let eq = yuf.parse(&equation)?;
-            let solutions = yuf.solve(&eq, &variable)?;
+            let eq = yuf.parse_equation(&equation)?;
+            let solutions = yuf.solve_detailed(&eq, &variable)?;
+            if solutions.is_empty() {
+                println!("{}", tr!("cli.solve.no_solution"));
+            }
             for (i, solution) in solutions.iter().enumerate() {
-                let result = yuf.format(solution);
-                println!("解 {}: {}", i + 1, format_output(&result, &args.format));
+                let result = yuf.format(&solution.root);
+                let mut notes = vec![tr!("cli.solve.multiplicity", multiplicity = solution.multiplicity)];
+                if !solution.is_exact() {
+                    notes.push(tr!("cli.solve.numeric").to_string());
+                }
+                if solution.is_candidate() {
+                    notes.push(tr!("cli.solve.candidate").to_string());
+                }
+                println!("{}", tr!(
+                    "cli.solve.solution",
+                    index = i + 1, variable = variable, root = format_output(&result, &args.format), notes = notes.join(tr!("cli.solve.note_separator"))
+                ));
             }
         }
         Some(Commands::Factor { expression }) => {
-            let yuf = Yufmath::new();
             let expr = yuf.parse(&expression)?;
             let factored = yuf.factor(&expr)?;
             let result = yuf.format(&factored);
             println!("{}", format_output(&result, &args.format));
         }
         Some(Commands::Expand { expression }) => {
-            let yuf = Yufmath::new();
             let expr = yuf.parse(&expression)?;
             let expanded = yuf.expand(&expr)?;
             let result = yuf.format(&expanded);
             println!("{}", format_output(&result, &args.format));
         }
+        Some(Commands::TrigExpand { expression }) => {
+            let expanded = yuf.trig_expand(&yuf.parse(&expression)?)?;
+            println!("{}", format_output(&yuf.format(&expanded), &args.format));
+        }
+        Some(Commands::TrigFactor { expression }) => {
+            let factored = yuf.trig_factor(&yuf.parse(&expression)?)?;
+            println!("{}", format_output(&yuf.format(&factored), &args.format));
+        }
         Some(Commands::Limit { expression, variable, point }) => {
-            let yuf = Yufmath::new();
             let expr = yuf.parse(&expression)?;
             let point_expr = yuf.parse(&point)?;
             let limit = yuf.limit(&expr, &variable, &point_expr)?;
@@ -74,25 +113,97 @@ pub fn run_command(args: CliArgs) -> Result<(), Box<dyn std::error::Error>> {
             println!("{}", format_output(&result, &args.format));
         }
         Some(Commands::Series { expression, variable, point, order }) => {
-            let yuf = Yufmath::new();
             let expr = yuf.parse(&expression)?;
             let point_expr = yuf.parse(&point)?;
             let series = yuf.series(&expr, &variable, &point_expr, order)?;
             let result = yuf.format(&series);
             println!("{}", format_output(&result, &args.format));
         }
+        Some(Commands::Laplace { expression, variable, transform_variable, region }) => {
+            let expr = yuf.parse(&expression)?;
+            for line in laplace_report(&yuf, &expr, &variable, &transform_variable, region)? {
+                println!("{}", line);
+            }
+        }
+        Some(Commands::InverseLaplace { expression, variable, time_variable }) => {
+            let expr = yuf.parse(&expression)?;
+            let original = yuf.inverse_laplace(&expr, &variable, &time_variable)?;
+            let result = yuf.format(&original);
+            println!("{}", format_output(&result, &args.format));
+        }
+        Some(Commands::Analyze { expression, variable }) => {
+            let expr = yuf.parse(&expression)?;
+            for line in analysis_report(&yuf, &expr, &variable)? {
+                println!("{}", line);
+            }
+        }
+        Some(Commands::Verify { equation }) => {
+            for line in verify_report(&yuf, &equation)? {
+                println!("{}", line);
+            }
+        }
+        Some(Commands::Prove { ref lhs, ref rhs, ref vars }) => {
+            for line in prove_report(&yuf, lhs, rhs, vars)? {
+                println!("{}", line);
+            }
+        }
+        Some(Commands::PlotData { ref expression, ref variable, from, to, n, ref csv, json, skip_undefined }) => {
+            let expr = yuf.parse(expression)?;
+            let points = plot_points(&yuf, &expr, variable, from, to, n, skip_undefined)?;
+            write_plot_data(&points, csv.as_deref(), json)?;
+        }
+        Some(Commands::PlotAscii { ref expression, ref variable, from, to, width, height, y_min, y_max, ascii }) => {
+            let options = plot_options(width, height, y_min.zip(y_max), ascii);
+            println!("{}", plot_ascii_report(&yuf, expression, variable, from, to, &options)?);
+        }
+        Some(Commands::Quiz { kind, count, difficulty, seed }) => {
+            for line in quiz_report(&yuf, kind.exercise(difficulty), count, seed)? {
+                println!("{}", line);
+            }
+        }
         Some(Commands::Batch { ref input, ref output }) => {
-            run_batch_command(input, output.as_deref(), &args)?;
+            run_batch_command(&yuf, input, output.as_deref(), &args)?;
+        }
+        Some(Commands::Watch { ref input, ref output, interval }) => {
+            super::watch::run_watch(&yuf, input, output.as_deref(), std::time::Duration::from_millis(interval), &args)?;
         }
         None => {
             // 如果没有提供子命令，启动交互模式
-            crate::cli::run_interactive()?;
+            crate::cli::run_interactive_with_config(yuf.get_config())?;
         }
     }
     
+    if args.stats {
+        for line in stats_report(&yuf) {
+            eprintln!("{}", line);
+        }
+    }
     Ok(())
 }
 
+/// `--stats` 的报告：按操作分组的统计，以及内存的当前估算占用与峰值
+pub fn stats_report(yuf: &Yufmath) -> Vec<String> {
+    let memory = yuf.get_memory_stats();
+    let mut lines: Vec<String> = yuf.get_operation_report().lines().map(String::from).collect();
+    lines.push(tr!("cli.stats.memory", current = memory.current, peak = memory.peak));
+    if memory.limit_hits > 0 {
+        lines.push(tr!("cli.stats.limit_hits", count = memory.limit_hits));
+    }
+    lines
+}
+
+/// 读取 `--config` 指定的配置文件，`--timeout` 覆盖其中的超时设置
+pub fn load_config(args: &CliArgs) -> Result<ComputeConfig, YufmathError> {
+    let mut config = match &args.config {
+        Some(path) => ComputeConfig::from_file(path)?,
+        None => ComputeConfig::default(),
+    };
+    if let Some(seconds) = args.timeout {
+        config.max_compute_time = Some(std::time::Duration::from_secs(seconds));
+    }
+    Ok(config)
+}
+
 /// 运行笔记本命令
 fn run_notepad_command(file: Option<String>, title: Option<String>, terminal: bool) -> Result<(), Box<dyn std::error::Error>> {
     let notebook = if let Some(file_path) = file {
@@ -100,18 +211,18 @@ fn run_notepad_command(file: Option<String>, title: Option<String>, terminal: bo
         
         if path.exists() {
             // 加载现有笔记本
-            println!("正在加载笔记本: {}", file_path);
+            println!("{}", tr!("cli.notepad.loading", file = file_path));
             NotebookDeserializer::load_from_file(path)?
         } else {
             // 创建新笔记本并保存到指定路径
             let notebook_title = title.unwrap_or_else(|| {
                 path.file_stem()
                     .and_then(|s| s.to_str())
-                    .unwrap_or("新笔记本")
+                    .unwrap_or(tr!("cli.notepad.default_title"))
                     .to_string()
             });
             
-            println!("正在创建新笔记本: {} ({})", notebook_title, file_path);
+            println!("{}", tr!("cli.notepad.creating", title = notebook_title, file = file_path));
             let mut notebook = NotebookFormat::create_template(&notebook_title);
             
             // 保存到文件
@@ -120,8 +231,8 @@ fn run_notepad_command(file: Option<String>, title: Option<String>, terminal: bo
         }
     } else {
         // 创建临时笔记本
-        let notebook_title = title.unwrap_or_else(|| "临时笔记本".to_string());
-        println!("正在创建临时笔记本: {}", notebook_title);
+        let notebook_title = title.unwrap_or_else(|| tr!("cli.notepad.temporary_title").to_string());
+        println!("{}", tr!("cli.notepad.creating_temporary", title = notebook_title));
         NotebookFormat::create_template(&notebook_title)
     };
     
@@ -132,15 +243,11 @@ fn run_notepad_command(file: Option<String>, title: Option<String>, terminal: bo
         ui.run()?;
     } else {
         // 默认使用图形界面
-        println!("正在启动图形界面...");
+        println!("{}", tr!("cli.notepad.starting_gui"));
         
         // 检查图形环境
         if !has_display() {
-            eprintln!("警告：未检测到图形环境，将使用终端界面");
-            eprintln!("提示：");
-            eprintln!("  - 确保在图形桌面环境中运行");
-            eprintln!("  - 如果使用 SSH，请使用 -X 或 -Y 参数启用 X11 转发");
-            eprintln!("  - 或者使用 --terminal 参数强制使用终端界面");
+            eprintln!("{}", tr!("cli.notepad.no_display"));
             
             let mut ui = NotebookUI::with_notebook(notebook);
             ui.run()?;
@@ -151,15 +258,15 @@ fn run_notepad_command(file: Option<String>, title: Option<String>, terminal: bo
                 Ok(_) => {
                     match gui_ui.run() {
                         Ok(_) => {
-                            println!("图形界面已关闭");
+                            println!("{}", tr!("cli.notepad.gui_closed"));
                         }
                         Err(e) => {
-                            eprintln!("图形界面运行错误: {}", e);
+                            eprintln!("{}", tr!("cli.notepad.gui_error", error = e));
                         }
                     }
                 }
                 Err(e) => {
-                    eprintln!("图形界面设置失败: {}", e);
+                    eprintln!("{}", tr!("cli.notepad.gui_setup_failed", error = e));
                 }
             }
         }
@@ -169,35 +276,55 @@ fn run_notepad_command(file: Option<String>, title: Option<String>, terminal: bo
 }
 
 /// 运行批处理命令
-fn run_batch_command(input: &str, output: Option<&str>, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+fn run_batch_command(yuf: &Yufmath, input: &str, output: Option<&str>, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
     use std::fs;
     use std::io::Write;
     
-    let input_content = fs::read_to_string(input)?;
+    let input_content = crate::parser::lexer::strip_comments(&fs::read_to_string(input)?);
     let lines: Vec<&str> = input_content.lines().collect();
     
-    let yuf = Yufmath::new();
     let mut results = Vec::new();
+    let tsv = matches!(args.format, OutputFormat::Tsv);
+    if tsv {
+        results.push(tr!("cli.batch.tsv_header").to_string());
+        if !args.quiet {
+            println!("{}", tr!("cli.batch.tsv_header"));
+        }
+    }
     
     for (line_num, line) in lines.iter().enumerate() {
         let line = line.trim();
-        if line.is_empty() || line.starts_with('#') {
-            continue; // 跳过空行和注释
+        if line.is_empty() {
+            continue; // 跳过空行和只有注释的行
+        }
+        
+        // TSV 模式下出错的行也输出一行，错误信息放在结果列
+        if tsv {
+            let start = Instant::now();
+            let output = yuf.compute(line).map_err(|e| e.to_string());
+            let row = batch_tsv_row(line, &output, start.elapsed());
+            if !args.quiet {
+                println!("{}", row);
+            }
+            results.push(row);
+            continue;
         }
         
         match yuf.compute(line) {
             Ok(result) => {
                 let formatted = format_output(&result, &args.format);
-                results.push(format!("输入 {}: {}", line_num + 1, line));
-                results.push(format!("输出 {}: {}", line_num + 1, formatted));
+                let input_line = tr!("cli.batch.input", line = line_num + 1, input = line);
+                let output_line = tr!("cli.batch.output", line = line_num + 1, output = formatted);
                 
                 if !args.quiet {
-                    println!("输入 {}: {}", line_num + 1, line);
-                    println!("输出 {}: {}", line_num + 1, formatted);
+                    println!("{}", input_line);
+                    println!("{}", output_line);
                 }
+                results.push(input_line);
+                results.push(output_line);
             }
             Err(e) => {
-                let error_msg = format!("错误 {}: {}", line_num + 1, e);
+                let error_msg = tr!("cli.batch.error", line = line_num + 1, error = e);
                 results.push(error_msg.clone());
                 
                 if !args.quiet {
@@ -215,17 +342,277 @@ fn run_batch_command(input: &str, output: Option<&str>, args: &CliArgs) -> Resul
         }
         
         if !args.quiet {
-            println!("结果已保存到: {}", output_path);
+            println!("{}", tr!("cli.batch.saved", file = output_path));
         }
     }
     
     Ok(())
 }
 
+/// TSV 字段转义：反斜杠、制表符和换行分别写成 `\\`、`\t`、`\n`、`\r`，保证每行的列数固定
+pub fn tsv_escape(field: &str) -> String {
+    let mut escaped = String::with_capacity(field.len());
+    for ch in field.chars() {
+        match ch {
+            '\\' => escaped.push_str("\\\\"),
+            '\t' => escaped.push_str("\\t"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            _ => escaped.push(ch),
+        }
+    }
+    escaped
+}
+
+/// 批处理 TSV 输出的一行：输入、结果（出错时为错误信息）和以毫秒计的耗时
+pub fn batch_tsv_row(input: &str, output: &Result<String, String>, elapsed: Duration) -> String {
+    let (Ok(result) | Err(result)) = output;
+    format!("{}\t{}\t{:.3}", tsv_escape(input), tsv_escape(result), elapsed.as_secs_f64() * 1000.0)
+}
+
+/// 化简追踪报告，每条规则一行；设置了 YUFMATH_TRACE_FILTER 时只保留匹配的规则
+pub fn trace_report(yuf: &Yufmath, events: &[TraceEvent]) -> Vec<String> {
+    let filter = std::env::var(TRACE_FILTER_ENV).ok().filter(|f| !f.is_empty());
+    events.iter()
+        .filter(|event| filter.as_deref().is_none_or(|f| event.matches_filter(f)))
+        .enumerate()
+        .map(|(i, event)| format!(
+            "[{}] {} @ {}: {} → {} ({:?})",
+            i + 1, event.rule, event.path_string(), yuf.format(&event.before), yuf.format(&event.after), event.elapsed
+        ))
+        .collect()
+}
+
+/// 采样绘图数据，`skip_undefined` 为真时去掉定义域外的点
+pub fn plot_points(yuf: &Yufmath, expr: &crate::core::Expression, var: &str, from: f64, to: f64, n: usize, skip_undefined: bool) -> Result<Vec<(f64, Option<f64>)>, crate::api::YufmathError> {
+    let mut points = yuf.sample(expr, var, from, to, n)?;
+    if skip_undefined {
+        points.retain(|(_, y)| y.is_some());
+    }
+    Ok(points)
+}
+
+/// 绘图数据的 CSV 形式，定义域外的点记为 NaN
+pub fn plot_data_csv(points: &[(f64, Option<f64>)]) -> String {
+    let mut csv = String::from("x,y\n");
+    for (x, y) in points {
+        csv.push_str(&format!("{},{}\n", x, y.unwrap_or(f64::NAN)));
+    }
+    csv
+}
+
+/// 绘图数据的 JSON 形式，定义域外的点记为 null
+pub fn plot_data_json(points: &[(f64, Option<f64>)]) -> String {
+    let points: Vec<_> = points.iter()
+        .map(|(x, y)| serde_json::json!({ "x": x, "y": y }))
+        .collect();
+    serde_json::Value::Array(points).to_string()
+}
+
+/// 输出绘图数据：指定文件时写 CSV 文件，否则按格式打印到标准输出
+pub fn write_plot_data(points: &[(f64, Option<f64>)], csv_file: Option<&str>, json: bool) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(path) = csv_file {
+        std::fs::write(path, plot_data_csv(points))
+            .map_err(|e| tr!("cli.plot.write_failed", file = path, error = e))?;
+        println!("{}", tr!("cli.plot.written", count = points.len(), file = path));
+    } else if json {
+        println!("{}", plot_data_json(points));
+    } else {
+        print!("{}", plot_data_csv(points));
+    }
+    Ok(())
+}
+
+/// 终端绘图的选项：未指定宽高时适配终端大小
+pub fn plot_options(width: Option<usize>, height: Option<usize>, y_range: Option<(f64, f64)>, ascii: bool) -> PlotOptions {
+    PlotOptions {
+        y_range,
+        style: if ascii { PlotStyle::Ascii } else { PlotStyle::Braille },
+        ..PlotOptions::fit_terminal(width, height)
+    }
+}
+
+/// 在终端画出 `;` 分隔的各个表达式
+pub fn plot_ascii_report(yuf: &Yufmath, expressions: &str, var: &str, from: f64, to: f64, options: &PlotOptions) -> Result<String, Box<dyn std::error::Error>> {
+    let exprs: Vec<_> = yuf.parse_statements(expressions)?.into_iter()
+        .map(|statement| statement.expression)
+        .collect();
+    plot_ascii(yuf, &exprs, var, from, to, options)
+}
+
+/// 按绘图宽度采样各条曲线并画成文本图像，定义域外的点留空
+pub fn plot_ascii(yuf: &Yufmath, exprs: &[crate::core::Expression], var: &str, from: f64, to: f64, options: &PlotOptions) -> Result<String, Box<dyn std::error::Error>> {
+    if let Some((low, high)) = options.y_range {
+        if low.partial_cmp(&high) != Some(std::cmp::Ordering::Less) {
+            return Err(tr!("cli.plot.invalid_y_range", min = low, max = high).into());
+        }
+    }
+    // 图例不带颜色，与图像其余部分一致
+    let formatter = StandardFormatter::new();
+    let curves = exprs.iter()
+        .map(|expr| Ok(Curve {
+            label: formatter.format(expr),
+            points: plot_points(yuf, expr, var, from, to, options.sample_count(), false)?,
+        }))
+        .collect::<Result<Vec<_>, YufmathError>>()?;
+    Ok(render_plot(&curves, options))
+}
+
+/// 校验等式是否恒成立：第一行为 true/false，不成立时附带反例
+pub fn verify_report(yuf: &Yufmath, equation: &str) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let (lhs, rhs) = match yuf.parse_equation(equation)? {
+        Expression::BinaryOp { op: BinaryOperator::Equal, left, right } => (*left, *right),
+        _ => return Err(tr!("cli.verify.missing_equals", equation = equation).into()),
+    };
+    let result = yuf.are_equivalent(&lhs, &rhs)?;
+    
+    let mut report = vec![result.equivalent.to_string()];
+    if result.equivalent && !result.proven {
+        report.push(tr!("cli.verify.numeric").to_string());
+    }
+    if let Some(counterexample) = result.counterexample {
+        let point = if counterexample.point.is_empty() {
+            tr!("cli.verify.no_variables").to_string()
+        } else {
+            counterexample.point.iter()
+                .map(|(name, value)| format!("{} = {}", name, yuf.format(value)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        report.push(tr!("cli.verify.counterexample", point = point, lhs = counterexample.lhs, rhs = counterexample.rhs));
+    }
+    Ok(report)
+}
+
+/// 恒等式证明报告：首行为结论，推翻时第二行给出反例
+pub fn prove_report(yuf: &Yufmath, lhs: &str, rhs: &str, vars: &[String]) -> Result<Vec<String>, YufmathError> {
+    let vars: Vec<&str> = vars.iter().map(String::as_str).collect();
+    let result = yuf.prove_identity(&yuf.parse(lhs)?, &yuf.parse(rhs)?, &vars)?;
+    
+    Ok(match result {
+        IdentityResult::Verified => vec![tr!("cli.prove.verified").to_string()],
+        IdentityResult::NumericallySupported { tested_points } => vec![tr!("cli.prove.supported", count = tested_points)],
+        IdentityResult::Refuted { counterexample, lhs_value, rhs_value } => {
+            let mut point: Vec<_> = counterexample.into_iter().collect();
+            point.sort_by(|a, b| a.0.cmp(&b.0));
+            let point = if point.is_empty() {
+                tr!("cli.verify.no_variables").to_string()
+            } else {
+                point.iter()
+                    .map(|(name, value)| format!("{} = {}", name, yuf.format(&crate::core::Expression::Number(value.clone()))))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            };
+            vec![
+                tr!("cli.prove.refuted").to_string(),
+                tr!("cli.verify.counterexample", point = point, lhs = lhs_value, rhs = rhs_value),
+            ]
+        }
+    })
+}
+
+/// 练习卷：先列出全部题目，再列出答案页。第 i 题使用种子 `seed + i`；
+/// 未指定种子时随机选取，并在首行打印以便复现
+pub fn quiz_report(yuf: &Yufmath, kind: ExerciseKind, count: usize, seed: Option<u64>) -> Result<Vec<String>, crate::api::YufmathError> {
+    let seed = seed.unwrap_or_else(|| SeededRng::from_entropy().next_u64());
+    let exercises = (0..count as u64)
+        .map(|i| yuf.generate_exercise(kind, seed.wrapping_add(i)))
+        .collect::<Result<Vec<_>, _>>()?;
+    
+    let mut report = vec![
+        tr!("cli.quiz.title", kind = kind.name(), difficulty = kind.difficulty(), seed = seed),
+        String::new(),
+        tr!("cli.quiz.problems").to_string(),
+    ];
+    report.extend(exercises.iter().enumerate().map(|(i, (problem, _))| format!("{}. {}", i + 1, yuf.format(problem))));
+    report.extend([String::new(), tr!("cli.quiz.answers").to_string()]);
+    report.extend(exercises.iter().enumerate().map(|(i, (_, answer))| format!("{}. {}", i + 1, yuf.format(answer))));
+    Ok(report)
+}
+
+/// Laplace 变换报告：第一行为像函数，可附带收敛域
+pub fn laplace_report(yuf: &Yufmath, expr: &crate::core::Expression, t: &str, s: &str, region: bool) -> Result<Vec<String>, crate::api::YufmathError> {
+    let result = yuf.laplace(expr, t, s)?;
+    let mut report = vec![yuf.format(&result.transform)];
+    if region {
+        report.push(tr!("cli.laplace.region", region = result.region(s).unwrap_or_else(|| tr!("cli.laplace.region_unknown").to_string())));
+    }
+    if !result.evaluated {
+        report.push(tr!("cli.laplace.partial").to_string());
+    }
+    Ok(report)
+}
+
+/// 生成函数分析报告：定义域、临界点、局部极值与拐点
+pub fn analysis_report(yuf: &Yufmath, expr: &crate::core::Expression, var: &str) -> Result<Vec<String>, crate::api::YufmathError> {
+    let points = |points: &[crate::core::Expression]| -> String {
+        if points.is_empty() {
+            tr!("cli.analysis.none").to_string()
+        } else {
+            points.iter().map(|p| format!("{} = {}", var, yuf.format(p))).collect::<Vec<_>>().join(", ")
+        }
+    };
+    
+    let mut report = vec![tr!("cli.analysis.function", function = yuf.format(expr))];
+    
+    let domain = yuf.domain(expr, var)?;
+    let mut line = tr!("cli.analysis.domain", domain = yuf.format(&domain.set));
+    if domain.is_partial() {
+        line.push_str(tr!("cli.analysis.partial"));
+    }
+    report.push(line);
+    
+    // 临界点和拐点依赖方程求解，无法求解时在报告中注明而不中断
+    match yuf.local_extrema(expr, var) {
+        Ok(extrema) => {
+            let critical: Vec<_> = extrema.iter().map(|e| e.point.clone()).collect();
+            report.push(tr!("cli.analysis.critical", points = points(&critical)));
+            for extremum in extrema {
+                report.push(format!(
+                    "  {} = {}: {} {}",
+                    var, yuf.format(&extremum.point), extremum.kind, yuf.format(&extremum.value)
+                ));
+            }
+        }
+        Err(e) => report.push(tr!("cli.analysis.critical_failed", error = e)),
+    }
+    match yuf.inflection_points(expr, var) {
+        Ok(inflections) => report.push(tr!("cli.analysis.inflection", points = points(&inflections))),
+        Err(e) => report.push(tr!("cli.analysis.inflection_failed", error = e)),
+    }
+    
+    Ok(report)
+}
+
+/// 解析错误的位置指示：出错的输入和指向出错位置的 `^`，`color` 为真时 `^` 用红色加粗；
+/// 不是带位置的解析错误或位置超出输入时为空
+///
+/// 两行的标签宽度不同时（如英文的 `Input:` 与 `Position:`）把较短的一行补齐，`^` 正好落在第 `pos` 个字符下方
+pub fn error_context(error: &(dyn std::error::Error + 'static), input: &str, color: bool) -> Vec<String> {
+    let parse_error = match error.downcast_ref::<YufmathError>() {
+        Some(YufmathError::Parse(parse_error)) => Some(parse_error),
+        _ => error.downcast_ref::<ParseError>(),
+    };
+    let Some(pos) = parse_error.and_then(ParseError::position).filter(|&pos| pos < input.chars().count()) else {
+        return Vec::new();
+    };
+    // 终端中非 ASCII 字符（中文标签、全角标点）占两列
+    let width = |text: &str| text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum::<usize>();
+    let input_label = width(&tr!("parse.context.input", input = ""));
+    let position_label = width(&tr!("parse.context.position", marker = ""));
+    let label = input_label.max(position_label);
+    let column = width(&input.chars().take(pos).collect::<String>());
+    let caret = if color { Colour::Red.bold().paint("^").to_string() } else { "^".to_string() };
+    vec![
+        tr!("parse.context.input", input = format!("{}{}", " ".repeat(label - input_label), input)),
+        tr!("parse.context.position", marker = format!("{}{}", " ".repeat(label - position_label + column), caret)),
+    ]
+}
+
 /// 格式化输出
 fn format_output(result: &str, format: &OutputFormat) -> String {
     match format {
-        OutputFormat::Standard => result.to_string(),
+        OutputFormat::Standard | OutputFormat::Maxima | OutputFormat::Tsv => result.to_string(),
         OutputFormat::Latex => {
             // 如果结果已经是 LaTeX 格式，直接返回
             if result.starts_with('$') && result.ends_with('$') {
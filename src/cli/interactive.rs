@@ -3,14 +3,77 @@
 //! 实现 REPL 交互式计算环境。
 
 use rustyline::error::ReadlineError;
-use rustyline::{DefaultEditor, Result as RustylineResult};
+use rustyline::DefaultEditor;
 use std::collections::HashMap;
 use ansi_term::Colour;
-use crate::{Yufmath, Expression};
+use crate::{Yufmath, YufmathError, Expression, ParseError};
 use crate::core::Number;
+use crate::api::{ComputeConfig, ComputePhase, PhaseTimings};
 use crate::formatter::{FormatOptions, FormatType, TerminalFormatter, Formatter};
+use crate::parser::lexer::strip_comments;
+use crate::tr;
+use super::plot::PlotOptions;
 use super::terminal::{ColorConfig, supports_color};
 
+/// 帮助中的一组命令：标题键、(命令, 说明键) 行和命令的颜色
+type HelpSection = (&'static str, &'static [(&'static str, &'static str)], Colour);
+
+/// 帮助中的命令分组
+const HELP_SECTIONS: &[HelpSection] = &[
+    ("interactive.help.basic", &[
+        ("help, ?", "interactive.help.help"),
+        ("quit, exit, q", "interactive.help.quit"),
+        ("clear", "interactive.help.clear"),
+        ("vars, variables", "interactive.help.vars"),
+        ("verbose", "interactive.help.verbose"),
+        ("colors", "interactive.help.colors"),
+        ("approx, approximations", "interactive.help.approx"),
+        ("enhanced, enhanced_simplify", "interactive.help.enhanced"),
+        ("timing on|off", "interactive.help.timing"),
+    ], Colour::Green),
+    ("interactive.help.formatting", &[
+        ("format <type>", "interactive.help.format"),
+        ("precision <n>", "interactive.help.precision"),
+        ("approx_precision <n>", "interactive.help.approx_precision"),
+    ], Colour::Green),
+    ("interactive.help.configuration", &[
+        ("show config", "interactive.help.show_config"),
+        ("set timeout <n>", "interactive.help.set_timeout"),
+        ("set precision <n>", "interactive.help.set_precision"),
+        ("set force_exact on|off", "interactive.help.set_force_exact"),
+        ("set seed <n>", "interactive.help.set_seed"),
+    ], Colour::Green),
+    ("interactive.help.math", &[
+        ("2 + 3", "interactive.help.arithmetic"),
+        ("x^2 + 2*x + 1", "interactive.help.algebra"),
+        ("sin(pi/2)", "interactive.help.trig"),
+        ("diff(x^2, x)", "interactive.help.diff"),
+        ("integrate(x, x)", "interactive.help.integrate"),
+        ("curl([-y, x, 0], [x, y, z])", "interactive.help.vector_calculus"),
+        ("plot(sin(x), -pi, pi)", "interactive.help.plot"),
+    ], Colour::Cyan),
+    ("interactive.help.assignment", &[
+        ("x = 5", "interactive.help.assign"),
+        ("y = x^2 + 1", "interactive.help.use_variable"),
+        ("a = 2;; b = 3; a*b", "interactive.help.statements"),
+        ("x + 1  # ...", "interactive.help.comments"),
+    ], Colour::Cyan),
+];
+
+/// 帮助中的示例：输入和输出
+const HELP_EXAMPLES: &[(&str, &str)] = &[
+    ("2 + 3", "5"),
+    ("x = 10", "x = 10"),
+    ("sqrt(3)", "sqrt(3) ≈ 1.732051"),
+    ("sin(pi/2)", "1"),
+];
+
+/// 续行提示符
+const CONTINUATION_PROMPT: &str = "     ... ";
+
+/// 出现在行尾时表示表达式尚未结束的字符
+const CONTINUATION_OPERATORS: &[char] = &['+', '-', '*', '/', '^', '=', '<', '>', ',', '&', '|', '%', '\\'];
+
 /// 交互式会话状态
 pub struct InteractiveSession {
     /// Yufmath 计算引擎
@@ -27,12 +90,19 @@ pub struct InteractiveSession {
     color_config: ColorConfig,
     /// 是否显示数值近似值
     show_approximations: bool,
+    /// 是否在结果后显示耗时
+    show_timing: bool,
 }
 
 impl InteractiveSession {
     /// 创建新的交互式会话
     pub fn new() -> Self {
-        let mut yufmath = Yufmath::new();
+        Self::with_config(ComputeConfig::default())
+    }
+    
+    /// 使用给定的计算配置创建交互式会话
+    pub fn with_config(config: ComputeConfig) -> Self {
+        let yufmath = Yufmath::with_config(config);
         let mut format_options = FormatOptions::default();
         format_options.format_type = FormatType::Terminal;
         yufmath.set_format_options(format_options.clone());
@@ -54,6 +124,7 @@ impl InteractiveSession {
             color_config,
             // 默认启用近似值显示以便用户看到代数值
             show_approximations: true,
+            show_timing: false,
         }
     }
     
@@ -71,13 +142,33 @@ impl InteractiveSession {
             return Ok(result);
         }
         
-        // 检查是否是变量赋值
-        if let Some((var_name, expression)) = self.parse_assignment(input) {
-            return self.handle_assignment(var_name, expression);
+        // 逐条处理 `;` 分隔的语句，`;;` 结尾的语句不回显；开启计时时在各阶段边界打点
+        let mut timings = self.show_timing.then(PhaseTimings::new);
+        let statements = match PhaseTimings::measure(timings.as_mut(), ComputePhase::Parsing, || self.yufmath.parse_statements(input)) {
+            Ok(statements) => statements,
+            // 只有注释
+            Err(YufmathError::Parse(ParseError::EmptyExpression)) => return Ok(String::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let mut outputs = Vec::new();
+        for statement in statements {
+            let output = match (statement.target, statement.parameters) {
+                (Some(name), Some(parameters)) => PhaseTimings::measure(timings.as_mut(), ComputePhase::Simplification, || {
+                    self.handle_function_definition(name, parameters, statement.expression)
+                })?,
+                (Some(var_name), None) => PhaseTimings::measure(timings.as_mut(), ComputePhase::Simplification, || {
+                    self.handle_assignment(var_name, statement.expression)
+                })?,
+                (None, _) => self.handle_expression(&statement.expression, timings.as_mut())?,
+            };
+            if !statement.quiet {
+                outputs.push(output);
+            }
         }
-        
-        // 处理数学表达式
-        self.handle_expression(input)
+        if let Some(timings) = timings {
+            outputs.push(timings.summary());
+        }
+        Ok(outputs.join("\n"))
     }
     
     /// 处理特殊命令（如 help、quit、set 等）
@@ -94,9 +185,9 @@ impl InteractiveSession {
                 // 清空变量
                 self.variables.clear();
                 if let Err(e) = self.yufmath.clear_variables() {
-                    eprintln!("警告: 清空系统变量时出错: {}", e);
+                    eprintln!("{}", tr!("interactive.clear_failed", error = e));
                 }
-                Ok(Some("变量已清空".to_string()))
+                Ok(Some(tr!("interactive.cleared").to_string()))
             }
             "vars" | "variables" => {
                 Ok(Some(self.show_variables()))
@@ -104,11 +195,11 @@ impl InteractiveSession {
             "verbose" => {
                 self.verbose = !self.verbose;
                 let status = if self.verbose { 
-                    Colour::Green.paint("开启").to_string() 
+                    Colour::Green.paint(tr!("interactive.on")).to_string() 
                 } else { 
-                    Colour::Red.paint("关闭").to_string() 
+                    Colour::Red.paint(tr!("interactive.off")).to_string() 
                 };
-                Ok(Some(format!("详细模式: {}", status)))
+                Ok(Some(tr!("interactive.verbose", status = status)))
             }
             "colors" => {
                 // 切换颜色配置
@@ -124,31 +215,52 @@ impl InteractiveSession {
                 self.terminal_formatter.set_colors_enabled(colors_enabled);
                 
                 let status = if colors_enabled { 
-                    Colour::Green.paint("开启").to_string() 
+                    Colour::Green.paint(tr!("interactive.on")).to_string() 
                 } else { 
-                    Colour::Red.paint("关闭").to_string() 
+                    Colour::Red.paint(tr!("interactive.off")).to_string() 
                 };
-                Ok(Some(format!("颜色输出: {}", status)))
+                Ok(Some(tr!("interactive.colors", status = status)))
             }
             "approx" | "approximations" => {
                 self.show_approximations = !self.show_approximations;
                 self.terminal_formatter.set_approximations_enabled(self.show_approximations);
                 let status = if self.show_approximations { 
-                    Colour::Green.paint("开启").to_string() 
+                    Colour::Green.paint(tr!("interactive.on")).to_string() 
                 } else { 
-                    Colour::Red.paint("关闭").to_string() 
+                    Colour::Red.paint(tr!("interactive.off")).to_string() 
                 };
-                Ok(Some(format!("数值近似值: {}", status)))
+                Ok(Some(tr!("interactive.approximations", status = status)))
             }
             "enhanced" | "enhanced_simplify" => {
                 let current_status = self.yufmath.is_enhanced_simplify_enabled();
                 self.yufmath.set_enhanced_simplify(!current_status);
                 let status = if !current_status { 
-                    Colour::Green.paint("开启").to_string() 
+                    Colour::Green.paint(tr!("interactive.on")).to_string() 
                 } else { 
-                    Colour::Red.paint("关闭").to_string() 
+                    Colour::Red.paint(tr!("interactive.off")).to_string() 
+                };
+                Ok(Some(tr!("interactive.enhanced", status = status)))
+            }
+            input if input == "timing" || input.starts_with("timing ") => {
+                let status = match input.strip_prefix("timing").unwrap().trim() {
+                    "on" => true,
+                    "off" => false,
+                    _ => return Ok(Some(tr!("interactive.timing_usage").to_string())),
                 };
-                Ok(Some(format!("增强化简功能: {}", status)))
+                self.show_timing = status;
+                let status = if status {
+                    Colour::Green.paint(tr!("interactive.on")).to_string()
+                } else {
+                    Colour::Red.paint(tr!("interactive.off")).to_string()
+                };
+                Ok(Some(tr!("interactive.timing", status = status)))
+            }
+            "show config" | "config" => {
+                Ok(Some(self.show_config()))
+            }
+            input if input.starts_with("set ") && !input.contains('=') => {
+                let option = input.strip_prefix("set ").unwrap().trim();
+                Ok(Some(self.set_config_option(option)))
             }
             input if input.starts_with("format ") => {
                 let format_type = input.strip_prefix("format ").unwrap().trim();
@@ -166,160 +278,96 @@ impl InteractiveSession {
         }
     }
     
-    /// 解析变量赋值
-    fn parse_assignment(&self, input: &str) -> Option<(String, String)> {
-        if let Some(eq_pos) = input.find('=') {
-            let var_name = input[..eq_pos].trim().to_string();
-            let expression = input[eq_pos + 1..].trim().to_string();
-            
-            // 检查变量名是否有效（简单的字母数字检查）
-            if var_name.chars().all(|c| c.is_alphanumeric() || c == '_') && !var_name.is_empty() {
-                Some((var_name, expression))
-            } else {
-                None
-            }
-        } else {
-            None
-        }
-    }
-    
     /// 处理变量赋值
-    fn handle_assignment(&mut self, var_name: String, expression: String) -> Result<String, Box<dyn std::error::Error>> {
-        // 首先解析表达式
-        match self.yufmath.parse(&expression) {
-            Ok(expr) => {
-                // 设置变量
-                match self.yufmath.set_variable(var_name.clone(), expr.clone()) {
-                    Ok(()) => {
-                        if self.verbose {
-                            println!("设置变量 {} = {:?}", var_name, expr);
-                        }
-                        
-                        // 尝试计算表达式的值用于显示
-                        match self.yufmath.compute(&expression) {
-                            Ok(result) => {
-                                // 同时更新本地变量存储（用于显示）
-                                if let Ok(Some(var_expr)) = self.yufmath.get_variable(&var_name) {
-                                    if let Expression::Number(num) = var_expr {
-                                        self.variables.insert(var_name.clone(), num);
-                                    }
-                                }
-                                
-                                Ok(format!("{} = {}", var_name, result))
-                            }
-                            Err(_) => {
-                                // 如果无法计算具体值，显示符号形式
-                                Ok(format!("{} = {:?}", var_name, expr))
-                            }
-                        }
-                    }
-                    Err(e) => {
-                        Err(format!("无法设置变量 '{}': {}", var_name, e).into())
-                    }
+    fn handle_assignment(&mut self, var_name: String, expr: Expression) -> Result<String, Box<dyn std::error::Error>> {
+        // 设置变量
+        if let Err(e) = self.yufmath.set_variable(var_name.clone(), expr.clone()) {
+            return Err(tr!("interactive.set_variable_failed", name = var_name, error = e).into());
+        }
+        if self.verbose {
+            println!("{}", tr!("interactive.setting_variable", name = var_name, value = format!("{:?}", expr)));
+        }
+        
+        // 尝试计算表达式的值用于显示
+        match self.yufmath.simplify(&expr) {
+            Ok(result) => {
+                // 同时更新本地变量存储（用于显示）
+                if let Ok(Some(Expression::Number(num))) = self.yufmath.get_variable(&var_name) {
+                    self.variables.insert(var_name.clone(), num);
                 }
+                
+                Ok(format!("{} = {}", var_name, self.yufmath.format(&result)))
             }
-            Err(e) => {
-                Err(format!("无法解析表达式 '{}': {}", expression, e).into())
+            Err(_) => {
+                // 如果无法计算具体值，显示符号形式
+                Ok(format!("{} = {:?}", var_name, expr))
             }
         }
     }
     
-    /// 处理数学表达式
-    fn handle_expression(&mut self, input: &str) -> Result<String, Box<dyn std::error::Error>> {
-        if self.verbose {
-            println!("正在计算: {}", input);
+    /// 处理函数定义 `f(x) := 表达式`
+    fn handle_function_definition(&mut self, name: String, parameters: Vec<String>, body: Expression) -> Result<String, Box<dyn std::error::Error>> {
+        let output = format!("{}({}) := {}", name, parameters.join(", "), self.yufmath.format(&body));
+        self.yufmath.define_function(name, parameters, body)?;
+        Ok(output)
+    }
+    
+    /// 处理数学表达式，给出 `timings` 时分别记录化简与格式化的耗时
+    fn handle_expression(&mut self, expr: &Expression, mut timings: Option<&mut PhaseTimings>) -> Result<String, Box<dyn std::error::Error>> {
+        if let Expression::Function { name, args } = expr {
+            if name == "plot" {
+                return PhaseTimings::measure(timings, ComputePhase::Formatting, || self.plot(args));
+            }
         }
         
-        // 解析表达式
-        let expr = self.yufmath.parse(input)?;
+        if self.verbose {
+            println!("{}", tr!("interactive.computing", input = self.yufmath.format(expr)));
+        }
         
         // 简化表达式
-        let simplified = self.yufmath.simplify(&expr)?;
+        let simplified = PhaseTimings::measure(timings.as_deref_mut(), ComputePhase::Simplification, || self.yufmath.simplify(expr))?;
         
         // 使用终端格式化器格式化结果
-        let result = self.terminal_formatter.format(&simplified);
+        let result = PhaseTimings::measure(timings, ComputePhase::Formatting, || self.terminal_formatter.format(&simplified));
         
         Ok(result)
     }
     
+    /// 内联绘图 `plot(f, g, ..., 起点, 终点)`，自变量取曲线中唯一的变量，没有变量时为 x
+    fn plot(&self, args: &[Expression]) -> Result<String, Box<dyn std::error::Error>> {
+        let [curves @ .., from, to] = args else {
+            return Err(tr!("cli.plot.usage").into());
+        };
+        if curves.is_empty() {
+            return Err(tr!("cli.plot.usage").into());
+        }
+        let bound = |expr: &Expression| self.yufmath.numerical_evaluate(expr, &std::collections::HashMap::new());
+        let (from, to) = (bound(from)?, bound(to)?);
+        let var = self.yufmath.sole_variable(&Expression::Vector(curves.to_vec()))
+            .unwrap_or_else(|_| "x".to_string());
+        super::commands::plot_ascii(&self.yufmath, curves, &var, from, to, &PlotOptions::fit_terminal(None, None))
+    }
+    
     /// 显示帮助信息
     fn show_help(&self) -> String {
-        format!(r#"{}
-
-{}:
-  {}          显示此帮助信息
-  {}    退出程序
-  {}            清空所有变量
-  {}  显示所有变量
-  {}          切换详细模式
-  {}           切换颜色输出
-  {}     切换数值近似值显示
-  {}  切换增强化简功能
-
-{}:
-  {}    设置输出格式 (standard, terminal, latex, mathml)
-  {}    设置数值精度
-  {}  设置近似值显示精度
-
-{}:
-  {}            基本算术运算
-  {}    代数表达式
-  {}        三角函数和数学常量
-  {}     求导 (暂未实现)
-  {}  积分 (暂未实现)
-
-{}:
-  {}            将值赋给变量
-  {}      使用变量的表达式
-
-{}:
-  {} 2 + 3
-  {}
-  
-  {} x = 10
-  {} x = 10
-  
-  {} sqrt(3)
-  {} √(3) ≈ 1.732051
-  
-  {} sin(pi/2)
-  {} sin(π ≈ 3.141593/2 ≈ 1.570796) ≈ 1.000000
-
-输入多行表达式时，以空行结束输入。
-"#,
-            Colour::Cyan.bold().paint("Yufmath 交互式计算器帮助"),
-            Colour::Yellow.bold().paint("基本命令"),
-            Colour::Green.paint("help, ?"),
-            Colour::Green.paint("quit, exit, q"),
-            Colour::Green.paint("clear"),
-            Colour::Green.paint("vars, variables"),
-            Colour::Green.paint("verbose"),
-            Colour::Green.paint("colors"),
-            Colour::Green.paint("approx, approximations"),
-            Colour::Green.paint("enhanced, enhanced_simplify"),
-            Colour::Yellow.bold().paint("格式化命令"),
-            Colour::Green.paint("format <type>"),
-            Colour::Green.paint("precision <n>"),
-            Colour::Green.paint("approx_precision <n>"),
-            Colour::Yellow.bold().paint("数学运算"),
-            Colour::Cyan.paint("2 + 3"),
-            Colour::Cyan.paint("x^2 + 2*x + 1"),
-            Colour::Cyan.paint("sin(pi/2)"),
-            Colour::Cyan.paint("diff(x^2, x)"),
-            Colour::Cyan.paint("integrate(x, x)"),
-            Colour::Yellow.bold().paint("变量赋值"),
-            Colour::Cyan.paint("x = 5"),
-            Colour::Cyan.paint("y = x^2 + 1"),
-            Colour::Yellow.bold().paint("示例"),
-            Colour::Green.bold().paint("yufmath>"),
-            Colour::Cyan.bold().paint("5"),
-            Colour::Green.bold().paint("yufmath>"),
-            Colour::Cyan.bold().paint("x = 10"),
-            Colour::Green.bold().paint("yufmath>"),
-            Colour::Cyan.bold().paint("√(3) ≈ 1.732051"),
-            Colour::Green.bold().paint("yufmath>"),
-            Colour::Cyan.bold().paint("sin(π ≈ 3.141593/2 ≈ 1.570796) ≈ 1.000000"),
-        )
+        let mut help = format!("{}\n", Colour::Cyan.bold().paint(tr!("interactive.help.title")));
+        for (title, rows, colour) in HELP_SECTIONS {
+            help.push_str(&format!("\n{}:\n", Colour::Yellow.bold().paint(tr!(title))));
+            let width = rows.iter().map(|(command, _)| command.chars().count()).max().unwrap_or(0);
+            for (command, description) in *rows {
+                let padding = " ".repeat(width - command.chars().count());
+                help.push_str(&format!("  {}{}  {}\n", colour.paint(*command), padding, tr!(description)));
+            }
+        }
+        
+        let prompt = Colour::Green.bold().paint("yufmath>");
+        help.push_str(&format!("\n{}:\n", Colour::Yellow.bold().paint(tr!("interactive.help.examples"))));
+        for (input, output) in HELP_EXAMPLES {
+            help.push_str(&format!("  {} {}\n  {}\n  \n", prompt, input, Colour::Cyan.bold().paint(*output)));
+        }
+        help.push_str(tr!("interactive.help.multiline"));
+        help.push('\n');
+        help
     }
     
     /// 显示当前变量
@@ -328,9 +376,9 @@ impl InteractiveSession {
         let system_vars = self.yufmath.get_all_variables().unwrap_or_default();
         
         if system_vars.is_empty() && self.variables.is_empty() {
-            "没有定义变量".to_string()
+            tr!("interactive.no_variables").to_string()
         } else {
-            let mut result = "当前变量:\n".to_string();
+            let mut result = format!("{}\n", tr!("interactive.variables"));
             
             // 显示系统变量
             for (name, expr) in &system_vars {
@@ -348,7 +396,7 @@ impl InteractiveSession {
             // 显示本地变量（如果有的话）
             for (name, value) in &self.variables {
                 if !system_vars.contains_key(name) {
-                    result.push_str(&format!("  {} = {:?} (本地)\n", name, value));
+                    result.push_str(&format!("  {} = {:?} ({})\n", name, value, tr!("interactive.local")));
                 }
             }
             
@@ -363,8 +411,9 @@ impl InteractiveSession {
             "terminal" | "term" => FormatType::Terminal,
             "latex" | "tex" => FormatType::LaTeX,
             "mathml" | "xml" => FormatType::MathML,
+            "maxima" | "mac" => FormatType::Maxima,
             _ => {
-                return Ok(Some(Colour::Red.paint("无效的格式类型。可用格式: standard, terminal, latex, mathml").to_string()));
+                return Ok(Some(Colour::Red.paint(tr!("interactive.invalid_format")).to_string()));
             }
         };
         
@@ -372,13 +421,14 @@ impl InteractiveSession {
         self.yufmath.set_format_options(self.format_options.clone());
         
         let format_name = match new_format {
-            FormatType::Standard => Colour::Cyan.paint("标准格式").to_string(),
-            FormatType::Terminal => Colour::Cyan.paint("终端彩色格式").to_string(),
-            FormatType::LaTeX => Colour::Cyan.paint("LaTeX 格式").to_string(),
-            FormatType::MathML => Colour::Cyan.paint("MathML 格式").to_string(),
+            FormatType::Standard => Colour::Cyan.paint(tr!("interactive.format.standard")).to_string(),
+            FormatType::Terminal => Colour::Cyan.paint(tr!("interactive.format.terminal")).to_string(),
+            FormatType::LaTeX => Colour::Cyan.paint(tr!("interactive.format.latex")).to_string(),
+            FormatType::MathML => Colour::Cyan.paint(tr!("interactive.format.mathml")).to_string(),
+            FormatType::Maxima => Colour::Cyan.paint(tr!("interactive.format.maxima")).to_string(),
         };
         
-        Ok(Some(format!("输出格式已设置为: {}", format_name)))
+        Ok(Some(tr!("interactive.format_set", format = format_name)))
     }
     
     /// 设置数值精度
@@ -387,23 +437,84 @@ impl InteractiveSession {
             Ok(precision) => {
                 self.format_options.precision = Some(precision);
                 self.yufmath.set_format_options(self.format_options.clone());
-                Ok(Some(format!("数值精度已设置为: {}", Colour::Cyan.paint(precision.to_string()))))
+                Ok(Some(tr!("interactive.precision_set", precision = Colour::Cyan.paint(precision.to_string()))))
             }
             Err(_) => {
-                Ok(Some(Colour::Red.paint("无效的精度值，请输入正整数").to_string()))
+                Ok(Some(Colour::Red.paint(tr!("interactive.invalid_precision")).to_string()))
             }
         }
     }
     
+    /// 显示当前计算配置
+    fn show_config(&self) -> String {
+        match self.yufmath.get_config().to_toml() {
+            Ok(toml) => format!("{}\n{}", Colour::Cyan.bold().paint(tr!("interactive.config")), toml.trim_end()),
+            Err(e) => Colour::Red.paint(tr!("interactive.config_failed", error = e)).to_string(),
+        }
+    }
+    
+    /// 修改计算配置，如 `set timeout 300`、`set precision 200`，`off` 表示取消限制
+    fn set_config_option(&mut self, option: &str) -> String {
+        let (name, value) = option.split_once(char::is_whitespace).unwrap_or((option, ""));
+        let value = value.trim();
+        let limit = |value: &str| -> Result<Option<u64>, String> {
+            match value {
+                "off" | "none" => Ok(None),
+                _ => value.parse::<u64>().map(Some).map_err(|_| tr!("interactive.invalid_number", value = value)),
+            }
+        };
+        let switch = |value: &str| -> Result<bool, String> {
+            match value {
+                "on" | "true" => Ok(true),
+                "off" | "false" => Ok(false),
+                _ => Err(tr!("interactive.invalid_switch", value = value)),
+            }
+        };
+        
+        let result = match name {
+            "timeout" => limit(value).map(|seconds| {
+                self.yufmath.update_config(|config| config.max_compute_time = seconds.map(std::time::Duration::from_secs));
+                match seconds {
+                    Some(seconds) => tr!("interactive.timeout_set", seconds = seconds),
+                    None => tr!("interactive.timeout_cleared").to_string(),
+                }
+            }),
+            "precision" => limit(value).map(|digits| {
+                self.yufmath.update_config(|config| config.precision.max_precision = digits.map(|d| d as usize));
+                match digits {
+                    Some(digits) => tr!("interactive.max_precision_set", digits = digits),
+                    None => tr!("interactive.max_precision_cleared").to_string(),
+                }
+            }),
+            "force_exact" => switch(value).map(|enabled| {
+                self.yufmath.update_config(|config| config.precision.force_exact = enabled);
+                tr!("interactive.force_exact", status = tr!(if enabled { "interactive.on" } else { "interactive.off" }))
+            }),
+            "seed" => limit(value).map(|seed| {
+                self.yufmath.update_config(|config| config.random_seed = seed);
+                match seed {
+                    Some(seed) => tr!("interactive.seed_set", seed = seed),
+                    None => tr!("interactive.seed_cleared").to_string(),
+                }
+            }),
+            _ => Err(tr!("interactive.unknown_option", name = name)),
+        };
+        
+        match result {
+            Ok(message) => Colour::Green.paint(message).to_string(),
+            Err(message) => Colour::Red.paint(message).to_string(),
+        }
+    }
+    
     /// 设置近似值精度
     fn set_approximation_precision(&mut self, precision_str: &str) -> Result<Option<String>, Box<dyn std::error::Error>> {
         match precision_str.parse::<usize>() {
             Ok(precision) => {
                 self.terminal_formatter.set_approximation_precision(precision);
-                Ok(Some(format!("近似值精度已设置为: {}", Colour::Cyan.paint(precision.to_string()))))
+                Ok(Some(tr!("interactive.approx_precision_set", precision = Colour::Cyan.paint(precision.to_string()))))
             }
             Err(_) => {
-                Ok(Some(Colour::Red.paint("无效的精度值，请输入正整数").to_string()))
+                Ok(Some(Colour::Red.paint(tr!("interactive.invalid_precision")).to_string()))
             }
         }
     }
@@ -411,58 +522,62 @@ impl InteractiveSession {
 
 /// 运行交互式模式
 pub fn run_interactive() -> Result<(), Box<dyn std::error::Error>> {
+    run_interactive_with_config(ComputeConfig::default())
+}
+
+/// 使用给定的计算配置运行交互式模式
+pub fn run_interactive_with_config(config: ComputeConfig) -> Result<(), Box<dyn std::error::Error>> {
     let color_support = supports_color();
     
     if color_support {
         println!("{} {} - {}", 
             Colour::Cyan.bold().paint("Yufmath"),
             Colour::Green.bold().paint(format!("v{}", crate::VERSION)),
-            Colour::White.bold().paint("计算机代数系统"));
+            Colour::White.bold().paint(tr!("interactive.banner")));
         println!("{}", Colour::Black.bold().paint("━".repeat(50)));
-        println!("输入 {} 查看帮助，输入 {} 退出", 
-            Colour::Green.paint("'help'"), 
-            Colour::Red.paint("'quit'"));
+        println!("{}", tr!("interactive.banner_hint",
+            help = Colour::Green.paint("'help'"),
+            quit = Colour::Red.paint("'quit'")));
     } else {
-        println!("Yufmath v{} - 计算机代数系统", crate::VERSION);
+        println!("Yufmath v{} - {}", crate::VERSION, tr!("interactive.banner"));
         println!("{}", "━".repeat(50));
-        println!("输入 'help' 查看帮助，输入 'quit' 退出");
-        println!("注意: 终端不支持颜色输出或颜色已被禁用");
+        println!("{}", tr!("interactive.banner_hint", help = "'help'", quit = "'quit'"));
+        println!("{}", tr!("interactive.no_color"));
     }
     println!();
     
     let mut rl = DefaultEditor::new()?;
-    let mut session = InteractiveSession::new();
+    let mut session = InteractiveSession::with_config(config);
     
     // 尝试加载历史记录
     let history_file = "yufmath_history.txt";
     let _ = rl.load_history(history_file);
     
+    let mut pending = MultilineInput::new();
     loop {
         // 读取用户输入
         // 修复终端显示问题：使用简单的提示符，避免彩色输出导致的显示偏移
-        let prompt = "yufmath> ";
+        let prompt = if pending.is_pending() { CONTINUATION_PROMPT } else { "yufmath> " };
         let readline = rl.readline(prompt);
         
         match readline {
             Ok(line) => {
-                // 添加到历史记录
-                let _ = rl.add_history_entry(line.as_str());
-                
-                // 处理多行输入
-                let input = if line.trim().is_empty() {
+                // 括号未闭合或行尾是运算符时等待续行
+                let Some(input) = pending.push_line(&line) else {
                     continue;
-                } else if line.trim().ends_with('\\') {
-                    // 支持行尾反斜杠续行
-                    handle_multiline_input(&mut rl, line)?
-                } else {
-                    line
                 };
+                if input.trim().is_empty() {
+                    continue;
+                }
+                
+                // 添加到历史记录
+                let _ = rl.add_history_entry(input.as_str());
                 
                 // 检查是否是退出命令
                 if input.trim().to_lowercase() == "quit" 
                     || input.trim().to_lowercase() == "exit" 
                     || input.trim().to_lowercase() == "q" {
-                    println!("{}", Colour::Cyan.bold().paint("再见！"));
+                    println!("{}", Colour::Cyan.bold().paint(tr!("interactive.goodbye")));
                     break;
                 }
                 
@@ -475,12 +590,13 @@ pub fn run_interactive() -> Result<(), Box<dyn std::error::Error>> {
                     }
                     Err(e) => {
                         eprintln!("{} {}", 
-                            Colour::Red.bold().paint("错误:"), 
+                            Colour::Red.bold().paint(tr!("interactive.error")), 
                             Colour::Red.paint(e.to_string()));
                     }
                 }
             }
             Err(ReadlineError::Interrupted) => {
+                pending.clear();
                 println!("{}", Colour::Yellow.bold().paint("^C"));
                 continue;
             }
@@ -489,7 +605,7 @@ pub fn run_interactive() -> Result<(), Box<dyn std::error::Error>> {
                 break;
             }
             Err(err) => {
-                eprintln!("{} {:?}", Colour::Red.bold().paint("错误:"), err);
+                eprintln!("{} {:?}", Colour::Red.bold().paint(tr!("interactive.error")), err);
                 break;
             }
         }
@@ -501,34 +617,71 @@ pub fn run_interactive() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
-/// 处理多行输入
-fn handle_multiline_input(rl: &mut DefaultEditor, first_line: String) -> RustylineResult<String> {
-    let mut input = first_line;
-    
-    // 移除行尾的反斜杠
-    if input.trim().ends_with('\\') {
-        input = input.trim_end_matches('\\').to_string();
+/// 输入是否还不完整：括号未闭合，或去掉注释后行尾是二元运算符、逗号或续行用的反斜杠
+pub fn needs_continuation(input: &str) -> bool {
+    let code = strip_comments(input);
+    let trimmed = code.trim_end();
+    let depth = trimmed.chars().fold(0i32, |depth, ch| match ch {
+        '(' | '[' | '{' => depth + 1,
+        ')' | ']' | '}' => depth - 1,
+        _ => depth,
+    });
+    // 后缀 `!` 是阶乘，不算未完成的运算符
+    depth > 0 || trimmed.ends_with(CONTINUATION_OPERATORS)
+}
+
+/// 多行输入缓冲：输入不完整时等待续行，完整后拼成一条输入交给会话处理
+///
+/// 各行以换行连接，行注释不会吞掉后面的行；续行时输入空行会直接提交已输入的部分。
+#[derive(Debug, Default)]
+pub struct MultilineInput {
+    buffer: String,
+    /// 上一行以续行用的反斜杠结尾；只输入 `\` 时缓冲仍为空，但同样在等待续行
+    continued: bool,
+}
+
+impl MultilineInput {
+    /// 创建空的输入缓冲
+    pub fn new() -> Self {
+        Self::default()
     }
-    
-    loop {
-        // 修复终端显示问题：使用简单的续行提示符
-        let continuation_prompt = "     ... ";
-        let line = rl.readline(continuation_prompt)?;
-        
-        if line.trim().is_empty() {
-            // 空行表示输入结束
-            break;
-        } else if line.trim().ends_with('\\') {
-            // 继续下一行
-            input.push(' ');
-            input.push_str(line.trim_end_matches('\\'));
-        } else {
-            // 最后一行
-            input.push(' ');
-            input.push_str(&line);
-            break;
+
+    /// 追加一行；输入完整时返回整条输入并清空缓冲，否则返回 `None`
+    pub fn push_line(&mut self, line: &str) -> Option<String> {
+        if self.is_pending() && line.trim().is_empty() {
+            self.continued = false;
+            return Some(std::mem::take(&mut self.buffer));
+        }
+        if self.is_pending() {
+            self.buffer.push('\n');
+        }
+        let trimmed = line.trim_end();
+        match trimmed.strip_suffix('\\') {
+            Some(rest) => {
+                self.buffer.push_str(rest);
+                self.continued = true;
+                None
+            }
+            None => {
+                self.continued = false;
+                self.buffer.push_str(line);
+                if needs_continuation(&self.buffer) {
+                    None
+                } else {
+                    Some(std::mem::take(&mut self.buffer))
+                }
+            }
         }
     }
-    
-    Ok(input)
-}
\ No newline at end of file
+
+    /// 是否正在等待续行
+    pub fn is_pending(&self) -> bool {
+        !self.buffer.is_empty() || self.continued
+    }
+
+    /// 放弃已输入的部分
+    pub fn clear(&mut self) {
+        self.buffer.clear();
+        self.continued = false;
+    }
+}
@@ -6,10 +6,12 @@
 pub mod commands;
 pub mod interactive;
 pub mod args;
+pub mod plot;
 pub mod progress;
 pub mod terminal;
+pub mod watch;
 
 pub use args::CliArgs;
 pub use commands::run_command;
-pub use interactive::run_interactive;
-pub use terminal::{init_terminal, supports_color, ColorConfig};
\ No newline at end of file
+pub use interactive::{run_interactive, run_interactive_with_config};
+pub use terminal::{init_terminal, supports_color, stream_supports_color, ColorConfig};
\ No newline at end of file
@@ -0,0 +1,347 @@
+//! # 终端绘图
+//!
+//! 把采样得到的函数值画成纯文本图像：默认用 Unicode 盲文点阵（每个字符 2×4 个点），
+//! 也可以用星号等 ASCII 字符。图像带左侧 y 轴刻度、底边框和 x 区间标注，
+//! 结果是普通字符串，便于在没有图形环境时快速查看函数形状。
+
+/// 绘图字符集
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PlotStyle {
+    /// Unicode 盲文点阵，分辨率为字符网格的 2×4 倍
+    #[default]
+    Braille,
+    /// ASCII 字符，每个字符一个点
+    Ascii,
+}
+
+impl PlotStyle {
+    /// 每个字符横向、纵向的点数
+    fn dots(self) -> (usize, usize) {
+        match self {
+            PlotStyle::Braille => (2, 4),
+            PlotStyle::Ascii => (1, 1),
+        }
+    }
+
+    /// 边框与坐标轴字符
+    fn glyphs(self) -> Glyphs {
+        match self {
+            PlotStyle::Braille => Glyphs { border: '│', tick: '┤', corner: '└', horizontal: '─', vertical: '│', cross: '┼' },
+            PlotStyle::Ascii => Glyphs { border: '|', tick: '+', corner: '+', horizontal: '-', vertical: '|', cross: '+' },
+        }
+    }
+
+    /// 第 `index` 条曲线在图例中的字符；盲文模式下第一条曲线用点阵绘制，其余曲线用 ASCII 标记区分
+    fn legend_marker(self, index: usize) -> char {
+        match (self, index) {
+            (PlotStyle::Braille, 0) => '⣿',
+            _ => MARKERS[index % MARKERS.len()],
+        }
+    }
+}
+
+/// 各条曲线的标记字符，避开坐标轴用到的 `+`、`-`、`|`
+const MARKERS: [char; 6] = ['*', 'o', 'x', '#', '@', '%'];
+
+/// 盲文字符中第 (列, 行) 个点对应的位
+const BRAILLE_BITS: [[u8; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+
+/// 边框与坐标轴字符
+struct Glyphs {
+    border: char,
+    tick: char,
+    corner: char,
+    horizontal: char,
+    vertical: char,
+    cross: char,
+}
+
+/// 绘图选项
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlotOptions {
+    /// 输出的总宽度（字符数），含左侧刻度
+    pub width: usize,
+    /// 输出的总高度（行数），含底边框、x 区间和图例
+    pub height: usize,
+    /// y 轴范围，为 None 时取采样值的最小、最大值
+    pub y_range: Option<(f64, f64)>,
+    /// 字符集
+    pub style: PlotStyle,
+}
+
+impl Default for PlotOptions {
+    fn default() -> Self {
+        Self { width: 80, height: 24, y_range: None, style: PlotStyle::default() }
+    }
+}
+
+impl PlotOptions {
+    /// 按终端大小确定宽高，未给出的一项从 `COLUMNS`、`LINES` 环境变量读取（默认 80×24），
+    /// 高度留出一行给提示符
+    pub fn fit_terminal(width: Option<usize>, height: Option<usize>) -> Self {
+        let (columns, lines) = terminal_size();
+        Self {
+            width: width.unwrap_or(columns),
+            height: height.unwrap_or(lines.saturating_sub(1)),
+            ..Self::default()
+        }
+    }
+
+    /// 为铺满绘图区宽度所需的采样点数
+    pub fn sample_count(&self) -> usize {
+        (self.width * self.style.dots().0).max(2)
+    }
+}
+
+/// 终端的列数和行数
+fn terminal_size() -> (usize, usize) {
+    let read = |name: &str, default: usize| std::env::var(name).ok()
+        .and_then(|value| value.trim().parse().ok())
+        .filter(|&value: &usize| value > 0)
+        .unwrap_or(default);
+    (read("COLUMNS", 80), read("LINES", 24))
+}
+
+/// 一条曲线：图例标签和采样点，定义域外的点取值为 None
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve {
+    pub label: String,
+    pub points: Vec<(f64, Option<f64>)>,
+}
+
+/// 字符网格中的一格
+#[derive(Clone, Copy, Default)]
+struct Cell {
+    /// 盲文点阵的位
+    dots: u8,
+    /// 曲线标记，优先于点阵显示
+    marker: Option<char>,
+}
+
+/// 点坐标到字符网格的映射
+struct Canvas {
+    cells: Vec<Vec<Cell>>,
+    style: PlotStyle,
+    x_range: (f64, f64),
+    y_range: (f64, f64),
+}
+
+impl Canvas {
+    fn new(columns: usize, rows: usize, style: PlotStyle, x_range: (f64, f64), y_range: (f64, f64)) -> Self {
+        Self { cells: vec![vec![Cell::default(); columns]; rows], style, x_range, y_range }
+    }
+
+    /// 横向、纵向的点数
+    fn size(&self) -> (usize, usize) {
+        let (dx, dy) = self.style.dots();
+        (self.cells[0].len() * dx, self.cells.len() * dy)
+    }
+
+    /// x 对应的点列
+    fn column(&self, x: f64) -> usize {
+        let (from, to) = self.x_range;
+        scale(x, from, to, self.size().0)
+    }
+
+    /// y 对应的点行（0 为最上方），超出 y 范围时为 None
+    fn row(&self, y: f64) -> Option<usize> {
+        let (low, high) = self.y_range;
+        (low..=high).contains(&y).then(|| self.size().1 - 1 - scale(y, low, high, self.size().1))
+    }
+
+    /// 点亮第 `curve` 条曲线在 (列, 行) 处的点
+    fn set(&mut self, column: usize, row: usize, curve: usize) {
+        let (dx, dy) = self.style.dots();
+        let cell = &mut self.cells[row / dy][column / dx];
+        match (self.style, curve) {
+            (PlotStyle::Braille, 0) => cell.dots |= BRAILLE_BITS[column % dx][row % dy],
+            _ => cell.marker = Some(MARKERS[curve % MARKERS.len()]),
+        }
+    }
+
+    /// 画一条曲线：相邻两列的点用竖线连起来，定义域外或超出 y 范围的点断开
+    fn draw(&mut self, points: &[(f64, Option<f64>)], curve: usize) {
+        let mut previous: Option<(usize, usize)> = None;
+        for &(x, y) in points {
+            let Some(row) = y.and_then(|y| self.row(y)) else {
+                previous = None;
+                continue;
+            };
+            let column = self.column(x);
+            if let Some((previous_column, previous_row)) = previous.filter(|&(c, _)| column.abs_diff(c) <= 1) {
+                // 前一半竖线画在上一列，后一半画在本列
+                for r in previous_row.min(row)..=previous_row.max(row) {
+                    let c = if r.abs_diff(previous_row) < r.abs_diff(row) { previous_column } else { column };
+                    self.set(c, r, curve);
+                }
+            } else {
+                self.set(column, row, curve);
+            }
+            previous = Some((column, row));
+        }
+    }
+}
+
+/// 把 [low, high] 中的值线性映射到 0..count 的下标
+fn scale(value: f64, low: f64, high: f64, count: usize) -> usize {
+    let t = (value - low) / (high - low);
+    ((t * (count - 1) as f64).round().max(0.0) as usize).min(count - 1)
+}
+
+/// 刻度标注：最多两位小数并去掉末尾的 0
+fn tick_label(value: f64) -> String {
+    let label = format!("{:.2}", value);
+    let label = label.trim_end_matches('0').trim_end_matches('.');
+    if label == "-0" { "0".to_string() } else { label.to_string() }
+}
+
+/// 区间的两端，相等时向两侧各扩展 1
+fn widen(low: f64, high: f64) -> (f64, f64) {
+    if high > low { (low, high) } else { (low - 1.0, high + 1.0) }
+}
+
+/// 把曲线画成文本图像，各行末尾不留空格
+///
+/// x 范围取所有采样点的最小、最大值；y 范围由 `options.y_range` 指定或取采样值的最值。
+/// 多条曲线时用不同字符区分，并在最后一行给出图例。
+pub fn render_plot(curves: &[Curve], options: &PlotOptions) -> String {
+    let points = || curves.iter().flat_map(|curve| curve.points.iter());
+    let bounds = |values: &mut dyn Iterator<Item = f64>| values
+        .fold(None, |range: Option<(f64, f64)>, v| Some(range.map_or((v, v), |(low, high)| (low.min(v), high.max(v)))));
+    let x_range = bounds(&mut points().map(|&(x, _)| x)).map_or((0.0, 1.0), |(low, high)| widen(low, high));
+    let y_range = options.y_range
+        .or_else(|| bounds(&mut points().filter_map(|&(_, y)| y)))
+        .map_or((-1.0, 1.0), |(low, high)| widen(low, high));
+
+    let glyphs = options.style.glyphs();
+    let (top, bottom) = (tick_label(y_range.1), tick_label(y_range.0));
+    let margin = top.chars().count().max(bottom.chars().count());
+    let legend_lines = usize::from(curves.len() > 1);
+    let columns = options.width.saturating_sub(margin + 1).max(1);
+    let rows = options.height.saturating_sub(2 + legend_lines).max(1);
+
+    let mut canvas = Canvas::new(columns, rows, options.style, x_range, y_range);
+    for (index, curve) in curves.iter().enumerate() {
+        canvas.draw(&curve.points, index);
+    }
+
+    // 坐标轴画在 0 所在的格子里，不覆盖曲线
+    let (dx, dy) = options.style.dots();
+    let axis_row = canvas.row(0.0).map(|row| row / dy);
+    let axis_column = (x_range.0..=x_range.1).contains(&0.0).then(|| canvas.column(0.0) / dx);
+
+    let mut lines = Vec::with_capacity(rows + 2 + legend_lines);
+    for (r, row) in canvas.cells.iter().enumerate() {
+        let label = match r {
+            0 => top.as_str(),
+            _ if r + 1 == rows => bottom.as_str(),
+            _ if Some(r) == axis_row => "0",
+            _ => "",
+        };
+        let border = if label.is_empty() { glyphs.border } else { glyphs.tick };
+        let body: String = row.iter().enumerate().map(|(c, cell)| match cell.marker {
+            Some(marker) => marker,
+            None if cell.dots != 0 => char::from_u32(0x2800 + cell.dots as u32).unwrap_or(' '),
+            None => match (Some(r) == axis_row, Some(c) == axis_column) {
+                (true, true) => glyphs.cross,
+                (true, false) => glyphs.horizontal,
+                (false, true) => glyphs.vertical,
+                (false, false) => ' ',
+            },
+        }).collect();
+        lines.push(format!("{:>margin$}{}{}", label, border, body).trim_end().to_string());
+    }
+    lines.push(format!("{:margin$}{}{}", "", glyphs.corner, glyphs.horizontal.to_string().repeat(columns)));
+
+    let (from, to) = (tick_label(x_range.0), tick_label(x_range.1));
+    let gap = columns.saturating_sub(from.chars().count() + to.chars().count()).max(1);
+    lines.push(format!("{:margin$} {}{}{}", "", from, " ".repeat(gap), to));
+
+    if legend_lines > 0 {
+        let legend: Vec<String> = curves.iter().enumerate()
+            .map(|(index, curve)| format!("{} {}", options.style.legend_marker(index), curve.label))
+            .collect();
+        lines.push(format!("{:margin$} {}", "", legend.join("  ")));
+    }
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SNAPSHOT_ASCII: &str = concat!(
+        "4+*         |         *\n",
+        " | *        |        *\n",
+        " |  *       |       *\n",
+        " |   *      |      *\n",
+        " |    **    |    **\n",
+        " |      **  |  **\n",
+        "0+--------*****--------\n",
+        " +---------------------\n",
+        "  -2                  2",
+    );
+
+    const SNAPSHOT_BRAILLE: &str = concat!(
+        " 1┤         │ ⣀⠔⠉⠉⠒⢄\n",
+        " 0┤─────────⢀⠔⠁─────⠑⢄\n",
+        "  │⠑⢄      ⡔⠁\n",
+        "-1┤  ⠑⠤⣀⣀⠔⠉ │\n",
+        "  └───────────────────\n",
+        "   -3.14          3.14",
+    );
+
+    const SNAPSHOT_TWO_CURVES: &str = concat!(
+        " 2+          | o    ***\n",
+        "  |          |  ooo*\n",
+        " 0+----------***---oooo\n",
+        "  |oooo  ****|\n",
+        "  |   *ooo   |\n",
+        "-2+***    o  |\n",
+        "  +--------------------\n",
+        "   -2                 2\n",
+        "   * x  o 1/x",
+    );
+
+    fn curve(label: &str, f: impl Fn(f64) -> Option<f64>, from: f64, to: f64, n: usize) -> Curve {
+        let points = (0..n).map(|i| {
+            let x = from + (to - from) * i as f64 / (n - 1) as f64;
+            (x, f(x))
+        }).collect();
+        Curve { label: label.to_string(), points }
+    }
+
+    #[test]
+    fn test_tick_label() {
+        assert_eq!(tick_label(1.0), "1");
+        assert_eq!(tick_label(-6.28318), "-6.28");
+        assert_eq!(tick_label(0.5), "0.5");
+        assert_eq!(tick_label(-0.001), "0");
+    }
+
+    #[test]
+    fn test_render_ascii_snapshot() {
+        let options = PlotOptions { width: 23, height: 9, y_range: None, style: PlotStyle::Ascii };
+        let plot = render_plot(&[curve("x^2", |x| Some(x * x), -2.0, 2.0, 21)], &options);
+        assert_eq!(plot, SNAPSHOT_ASCII);
+    }
+
+    #[test]
+    fn test_render_braille_snapshot() {
+        let options = PlotOptions { width: 22, height: 6, y_range: None, style: PlotStyle::Braille };
+        let plot = render_plot(&[curve("sin(x)", |x| Some(x.sin()), -3.14, 3.14, 40)], &options);
+        assert_eq!(plot, SNAPSHOT_BRAILLE);
+    }
+
+    #[test]
+    fn test_render_multiple_curves_and_gaps() {
+        let options = PlotOptions { width: 23, height: 9, y_range: Some((-2.0, 2.0)), style: PlotStyle::Ascii };
+        let curves = [
+            curve("x", Some, -2.0, 2.0, 21),
+            curve("1/x", |x| (x.abs() > 1e-9).then(|| 1.0 / x), -2.0, 2.0, 21),
+        ];
+        let plot = render_plot(&curves, &options);
+        assert_eq!(plot, SNAPSHOT_TWO_CURVES);
+    }
+}
+
@@ -4,6 +4,7 @@
 
 use indicatif::{ProgressBar, ProgressStyle};
 use std::time::Duration;
+use crate::tr;
 
 /// 进度条管理器
 pub struct ProgressManager {
@@ -110,7 +111,7 @@ impl Drop for ProgressManager {
 pub fn create_compute_progress(enabled: bool, operation: &str) -> ProgressManager {
     let mut progress = ProgressManager::new(enabled);
     if enabled {
-        progress.start(&format!("正在{}...", operation), None);
+        progress.start(&tr!("cli.progress.running", operation = operation), None);
     }
     progress
 }
@@ -119,7 +120,7 @@ pub fn create_compute_progress(enabled: bool, operation: &str) -> ProgressManage
 pub fn create_batch_progress(enabled: bool, total_lines: u64) -> ProgressManager {
     let mut progress = ProgressManager::new(enabled);
     if enabled {
-        progress.start("正在处理批处理文件", Some(total_lines));
+        progress.start(tr!("cli.progress.batch"), Some(total_lines));
     }
     progress
 }
@@ -51,7 +51,7 @@ fn init_windows_terminal() -> Result<(), Box<dyn std::error::Error>> {
                 // 启用虚拟终端处理和处理输出
                 let new_mode = mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING | ENABLE_PROCESSED_OUTPUT;
                 if SetConsoleMode(stdout_handle, new_mode) == 0 {
-                    eprintln!("警告: 无法为标准输出启用 ANSI 颜色支持");
+                    eprintln!("{}", crate::tr!("cli.terminal.stdout_ansi"));
                 }
             }
         }
@@ -64,7 +64,7 @@ fn init_windows_terminal() -> Result<(), Box<dyn std::error::Error>> {
                 // 启用虚拟终端处理和处理输出
                 let new_mode = mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING | ENABLE_PROCESSED_OUTPUT;
                 if SetConsoleMode(stderr_handle, new_mode) == 0 {
-                    eprintln!("警告: 无法为标准错误输出启用 ANSI 颜色支持");
+                    eprintln!("{}", crate::tr!("cli.terminal.stderr_ansi"));
                 }
             }
         }
@@ -78,8 +78,13 @@ fn init_windows_terminal() -> Result<(), Box<dyn std::error::Error>> {
 /// 这个函数尝试检测当前终端是否支持 ANSI 颜色。
 /// 在某些情况下（如重定向到文件），应该禁用颜色输出。
 pub fn supports_color() -> bool {
+    stream_supports_color(atty::Stream::Stdout)
+}
+
+/// 检查指定的输出流是否支持颜色输出，标准错误输出被重定向时与标准输出分开判断
+pub fn stream_supports_color(stream: atty::Stream) -> bool {
     // 检查是否在交互式终端中运行
-    if !atty::is(atty::Stream::Stdout) {
+    if !atty::is(stream) {
         return false;
     }
     
@@ -103,7 +108,7 @@ pub fn supports_color() -> bool {
     // 在 Windows 上，检查是否成功启用了虚拟终端处理
     #[cfg(windows)]
     {
-        return check_windows_color_support();
+        return check_windows_color_support(stream);
     }
     
     // 在其他系统上，默认支持颜色
@@ -114,8 +119,9 @@ pub fn supports_color() -> bool {
 }
 
 #[cfg(windows)]
-fn check_windows_color_support() -> bool {
+fn check_windows_color_support(stream: atty::Stream) -> bool {
     const STD_OUTPUT_HANDLE: u32 = 0xFFFFFFF5_u32; // -11 as u32
+    const STD_ERROR_HANDLE: u32 = 0xFFFFFFF4_u32;  // -12 as u32
     const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
     
     #[link(name = "kernel32")]
@@ -125,10 +131,13 @@ fn check_windows_color_support() -> bool {
     }
     
     unsafe {
-        let stdout_handle = GetStdHandle(STD_OUTPUT_HANDLE);
-        if !stdout_handle.is_null() {
+        let handle = GetStdHandle(match stream {
+            atty::Stream::Stderr => STD_ERROR_HANDLE,
+            _ => STD_OUTPUT_HANDLE,
+        });
+        if !handle.is_null() {
             let mut mode: u32 = 0;
-            if GetConsoleMode(stdout_handle, &mut mode) != 0 {
+            if GetConsoleMode(handle, &mut mode) != 0 {
                 return (mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0;
             }
         }
@@ -161,6 +170,11 @@ impl Default for ColorConfig {
 impl ColorConfig {
     /// 根据配置和环境确定是否应该使用颜色
     pub fn should_use_color(&self) -> bool {
+        self.should_use_color_on(atty::Stream::Stdout)
+    }
+    
+    /// 根据配置和指定输出流的环境确定是否应该使用颜色
+    pub fn should_use_color_on(&self, stream: atty::Stream) -> bool {
         if self.no_color {
             return false;
         }
@@ -170,7 +184,7 @@ impl ColorConfig {
         }
         
         if self.auto_detect {
-            return supports_color();
+            return stream_supports_color(stream);
         }
         
         false
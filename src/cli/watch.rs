@@ -0,0 +1,188 @@
+//! # 监视模式
+//!
+//! 监视输入文件，每次保存后按批处理的规则逐行重新计算，清屏显示结果并标出与上一次不同的行。
+//! 出错的行只在对应位置标记，不中断监视；文件不存在时等待它被创建。
+
+use super::args::{CliArgs, OutputFormat};
+use crate::api::Yufmath;
+use crate::formatter::terminal::strip_ansi;
+use crate::parser::lexer::strip_comments;
+use crate::tr;
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// 清屏并把光标移到左上角
+const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// 一行输入的计算结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineResult {
+    /// 行号，从 1 开始
+    pub line: usize,
+    /// 去掉首尾空白后的输入
+    pub input: String,
+    /// 计算结果或错误信息
+    pub output: Result<String, String>,
+}
+
+/// 按批处理的规则计算文件内容：去掉注释，跳过空行，每行可以有 `;` 分隔的多条语句
+pub fn evaluate(yuf: &Yufmath, content: &str) -> Vec<LineResult> {
+    strip_comments(content).lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty())
+        .map(|(line, input)| LineResult {
+            line,
+            input: input.to_string(),
+            output: yuf.compute(input).map_err(|e| e.to_string()),
+        })
+        .collect()
+}
+
+/// 标出相对上一次有变化的行
+///
+/// 上一次没有相同输入和相同结果的行才算变化，插入或删除行造成的行号移动不算；
+/// 第一次计算没有可比较的结果，不标记任何行。
+pub fn changed_lines(previous: Option<&[LineResult]>, current: &[LineResult]) -> Vec<bool> {
+    let Some(previous) = previous else {
+        return vec![false; current.len()];
+    };
+    current.iter()
+        .map(|result| !previous.iter().any(|old| old.input == result.input && old.output == result.output))
+        .collect()
+}
+
+/// 屏幕上显示的各行，出错的行以 `!` 开头，有变化的行以 `*` 开头
+pub fn render(results: &[LineResult], changed: &[bool]) -> Vec<String> {
+    results.iter()
+        .zip(changed)
+        .map(|(result, &changed)| match &result.output {
+            Ok(output) => {
+                let marker = if changed { '*' } else { ' ' };
+                format!("{} {:>4} | {} = {}", marker, result.line, result.input, output)
+            }
+            Err(error) => format!("! {:>4} | {}", result.line, tr!("cli.watch.line_error", input = result.input, error = error)),
+        })
+        .collect()
+}
+
+/// 写入输出文件的内容；LaTeX 格式生成可以直接编译预览的完整文档，错误写成注释
+pub fn output_document(yuf: &Yufmath, results: &[LineResult], format: &OutputFormat) -> String {
+    let line_error = |result: &LineResult, error: &str| {
+        tr!("cli.batch.line_error", line = result.line, input = result.input, error = error)
+    };
+    match format {
+        OutputFormat::Latex => {
+            let mut document = String::from("\\documentclass{article}\n\\usepackage{amsmath}\n\\begin{document}\n");
+            for result in results {
+                match &result.output {
+                    Ok(output) => {
+                        let input = yuf.parse(&result.input).map(|expr| yuf.format(&expr)).unwrap_or_else(|_| result.input.clone());
+                        document.push_str(&format!("\\[ {} = {} \\]\n", input, output));
+                    }
+                    Err(error) => document.push_str(&format!("% {}\n", line_error(result, error))),
+                }
+            }
+            document.push_str("\\end{document}\n");
+            document
+        }
+        _ => results.iter()
+            .map(|result| match &result.output {
+                Ok(output) => format!("{} = {}\n", result.input, output),
+                Err(error) => format!("{}\n", line_error(result, error)),
+            })
+            .collect(),
+    }
+}
+
+/// 监视 `input`，每次内容变化后重新计算；按 Ctrl+C 结束
+pub fn run_watch(yuf: &Yufmath, input: &str, output: Option<&str>, interval: Duration, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let stop = interrupt_flag();
+    let path = Path::new(input);
+    let clear = !args.quiet && atty::is(atty::Stream::Stdout);
+    let mut last_content: Option<String> = None;
+    let mut previous: Option<Vec<LineResult>> = None;
+    let mut notice: Option<String> = None;
+    let mut runs = 0;
+
+    if !args.quiet {
+        println!("{}", tr!("cli.watch.watching", file = input, interval = interval.as_millis()));
+    }
+    while !stop.load(Ordering::SeqCst) {
+        match fs::read_to_string(path) {
+            Ok(content) => {
+                notice = None;
+                if last_content.as_deref() != Some(content.as_str()) {
+                    let results = evaluate(yuf, &content);
+                    let changed = changed_lines(previous.as_deref(), &results);
+                    runs += 1;
+                    if !args.quiet {
+                        if clear {
+                            print!("{}", CLEAR_SCREEN);
+                        }
+                        let count = changed.iter().filter(|&&changed| changed).count();
+                        println!("{}", tr!("cli.watch.run", file = input, count = runs, changed = count));
+                        for line in render(&results, &changed) {
+                            println!("{}", line);
+                        }
+                    }
+                    if let Some(output_path) = output {
+                        // 彩色输出时结果带转义码，写入文件前去掉
+                        if let Err(e) = fs::write(output_path, strip_ansi(&output_document(yuf, &results, &args.format))) {
+                            eprintln!("{}", tr!("cli.batch.write_failed", error = e));
+                        }
+                    }
+                    last_content = Some(content);
+                    previous = Some(results);
+                }
+            }
+            Err(e) => {
+                let message = match e.kind() {
+                    ErrorKind::NotFound => tr!("cli.watch.waiting", file = input),
+                    _ => tr!("cli.batch.read_failed", file = input, error = e),
+                };
+                // 文件被删除后重新创建时，即使内容相同也重新计算
+                last_content = None;
+                if !args.quiet && notice.as_ref() != Some(&message) {
+                    eprintln!("{}", message);
+                }
+                notice = Some(message);
+            }
+        }
+        sleep_unless_stopped(&stop, interval);
+    }
+    if !args.quiet {
+        println!("{}", tr!("cli.watch.stopped"));
+    }
+    Ok(())
+}
+
+/// 按 Ctrl+C 时置位的标志；无法注册信号处理时保留默认行为，Ctrl+C 直接结束进程
+fn interrupt_flag() -> Arc<AtomicBool> {
+    let flag = Arc::new(AtomicBool::new(false));
+    if let Ok(runtime) = tokio::runtime::Builder::new_current_thread().enable_all().build() {
+        let setter = Arc::clone(&flag);
+        std::thread::spawn(move || {
+            if runtime.block_on(tokio::signal::ctrl_c()).is_ok() {
+                setter.store(true, Ordering::SeqCst);
+            }
+        });
+    }
+    flag
+}
+
+/// 等待 `interval`，期间收到 Ctrl+C 时立即返回
+fn sleep_unless_stopped(stop: &AtomicBool, interval: Duration) {
+    let deadline = Instant::now() + interval;
+    while !stop.load(Ordering::SeqCst) {
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+        std::thread::sleep((deadline - now).min(Duration::from_millis(50)));
+    }
+}
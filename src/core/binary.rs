@@ -0,0 +1,686 @@
+//! # 表达式的紧凑二进制格式
+//!
+//! 用于缓存持久化和 FFI 传输，体积远小于 JSON，解析也更快。
+//!
+//! 数据以 `YUFB` 魔数、1 字节版本号和 8 字节校验和（正文的 FNV-1a 64，小端）开头，
+//! 正文依次是字符串表、节点表和根节点列表。节点按后序排列，子节点用与当前节点序号之差引用，
+//! 结构相同的子表达式只存一次。整数一律用 LEB128 变长编码，超出 `i64` 的大整数存为补码字节串。
+//! 解码先检查全部节点再构造表达式，损坏的数据返回错误而不会 panic。
+
+use std::collections::HashMap;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use bigdecimal::BigDecimal;
+use num_traits::{ToPrimitive, Zero};
+use crate::engine::ComputeError;
+use super::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator};
+use crate::tr;
+
+/// 当前的格式版本
+pub const FORMAT_VERSION: u8 = 1;
+
+const MAGIC: &[u8; 4] = b"YUFB";
+const HEADER_LEN: usize = MAGIC.len() + 1 + 8;
+/// 解码结果允许的节点总数，防止共享子树被构造出的数据放大成巨大的表达式树
+const MAX_DECODED_NODES: u64 = 1 << 28;
+
+// 以下编码表只能在末尾追加，否则旧数据会被解读成别的运算
+const BINARY_OPERATORS: [BinaryOperator; 20] = [
+    BinaryOperator::Add, BinaryOperator::Subtract, BinaryOperator::Multiply, BinaryOperator::Divide,
+    BinaryOperator::Power, BinaryOperator::Modulo, BinaryOperator::Equal, BinaryOperator::NotEqual,
+    BinaryOperator::Less, BinaryOperator::LessEqual, BinaryOperator::Greater, BinaryOperator::GreaterEqual,
+    BinaryOperator::And, BinaryOperator::Or, BinaryOperator::Union, BinaryOperator::Intersection,
+    BinaryOperator::SetDifference, BinaryOperator::MatrixMultiply, BinaryOperator::CrossProduct, BinaryOperator::DotProduct,
+];
+
+const UNARY_OPERATORS: [UnaryOperator; 31] = [
+    UnaryOperator::Negate, UnaryOperator::Plus, UnaryOperator::Sqrt, UnaryOperator::Abs,
+    UnaryOperator::Sin, UnaryOperator::Cos, UnaryOperator::Tan, UnaryOperator::Asin,
+    UnaryOperator::Acos, UnaryOperator::Atan, UnaryOperator::Sinh, UnaryOperator::Cosh,
+    UnaryOperator::Tanh, UnaryOperator::Asinh, UnaryOperator::Acosh, UnaryOperator::Atanh,
+    UnaryOperator::Ln, UnaryOperator::Log10, UnaryOperator::Log2, UnaryOperator::Exp,
+    UnaryOperator::Factorial, UnaryOperator::Gamma, UnaryOperator::Not, UnaryOperator::Real,
+    UnaryOperator::Imaginary, UnaryOperator::Conjugate, UnaryOperator::Argument, UnaryOperator::Transpose,
+    UnaryOperator::Determinant, UnaryOperator::Inverse, UnaryOperator::Trace,
+];
+
+const CONSTANTS: [MathConstant; 9] = [
+    MathConstant::Pi, MathConstant::E, MathConstant::I, MathConstant::EulerGamma, MathConstant::GoldenRatio,
+    MathConstant::Catalan, MathConstant::PositiveInfinity, MathConstant::NegativeInfinity, MathConstant::Undefined,
+];
+
+const TAG_INT: u8 = 0;
+const TAG_BIG_INT: u8 = 1;
+const TAG_RATIONAL: u8 = 2;
+const TAG_REAL: u8 = 3;
+const TAG_FLOAT: u8 = 4;
+const TAG_COMPLEX: u8 = 5;
+const TAG_NUMBER_CONSTANT: u8 = 6;
+const TAG_SYMBOLIC: u8 = 7;
+const TAG_VARIABLE: u8 = 8;
+const TAG_CONSTANT: u8 = 9;
+const TAG_BINARY: u8 = 10;
+const TAG_UNARY: u8 = 11;
+const TAG_FUNCTION: u8 = 12;
+const TAG_MATRIX: u8 = 13;
+const TAG_VECTOR: u8 = 14;
+const TAG_SET: u8 = 15;
+const TAG_INTERVAL: u8 = 16;
+
+/// 节点表中的一项，子节点和字符串都用序号引用
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Node {
+    Int(i64),
+    BigInt(Vec<u8>),
+    /// 分子、分母（均为整数节点）
+    Rational(u32, u32),
+    /// 整数尾数节点与小数位数
+    Real(u32, i64),
+    Float(u64),
+    /// 实部、虚部（均为数值节点）
+    Complex(u32, u32),
+    NumberConstant(u8),
+    Symbolic(u32),
+    Variable(u32),
+    Constant(u8),
+    Binary(u8, u32, u32),
+    Unary(u8, u32),
+    Function(u32, Vec<u32>),
+    Matrix(Vec<Vec<u32>>),
+    Vector(Vec<u32>),
+    Set(Vec<u32>),
+    /// 端点是否闭合（第 0、1 位）与两个端点
+    Interval(u8, u32, u32),
+}
+
+impl Node {
+    /// 依次访问引用的子节点
+    fn for_each_child(&self, mut f: impl FnMut(u32)) {
+        match self {
+            Node::Int(_) | Node::BigInt(_) | Node::Float(_) | Node::NumberConstant(_)
+            | Node::Variable(_) | Node::Constant(_) => {}
+            Node::Real(id, _) | Node::Symbolic(id) | Node::Unary(_, id) => f(*id),
+            Node::Rational(a, b) | Node::Complex(a, b) | Node::Binary(_, a, b) | Node::Interval(_, a, b) => {
+                f(*a);
+                f(*b);
+            }
+            Node::Function(_, ids) | Node::Vector(ids) | Node::Set(ids) => ids.iter().copied().for_each(f),
+            Node::Matrix(rows) => rows.iter().flatten().copied().for_each(f),
+        }
+    }
+
+    fn is_integer(&self) -> bool {
+        matches!(self, Node::Int(_) | Node::BigInt(_))
+    }
+
+    fn is_number(&self) -> bool {
+        matches!(self, Node::Int(_) | Node::BigInt(_) | Node::Rational(..) | Node::Real(..) | Node::Float(_)
+            | Node::Complex(..) | Node::NumberConstant(_) | Node::Symbolic(_))
+    }
+}
+
+/// 把若干表达式编码进同一份数据，它们之间相同的子表达式也只存一次
+pub fn encode(exprs: &[&Expression]) -> Vec<u8> {
+    let mut encoder = Encoder::default();
+    let roots: Vec<u32> = exprs.iter().map(|expr| encoder.expression(expr)).collect();
+    encoder.finish(&roots)
+}
+
+/// 解码 [`encode`] 生成的数据，按编码时的顺序返回全部表达式
+pub fn decode(bytes: &[u8]) -> Result<Vec<Expression>, ComputeError> {
+    let body = check_header(bytes)?;
+    let mut reader = Reader { data: body, pos: 0 };
+    let strings = reader.strings()?;
+    let nodes = reader.nodes(strings.len())?;
+    let roots = reader.roots(nodes.len())?;
+    if reader.pos != body.len() {
+        return Err(corrupt(tr!("binary.trailing_data")));
+    }
+    Builder::new(&strings, &nodes, &roots)?.build(&roots)
+}
+
+impl Expression {
+    /// 编码为紧凑二进制格式，格式见 [`crate::core::binary`]
+    pub fn to_bytes(&self) -> Vec<u8> {
+        encode(&[self])
+    }
+
+    /// 从紧凑二进制格式解码，数据损坏、版本不支持或不是恰好一个表达式时返回错误
+    pub fn from_bytes(bytes: &[u8]) -> Result<Expression, ComputeError> {
+        let mut exprs = decode(bytes)?;
+        if exprs.len() != 1 {
+            return Err(corrupt(&tr!("binary.expression_count", count = exprs.len())));
+        }
+        Ok(exprs.remove(0))
+    }
+}
+
+fn corrupt(reason: &str) -> ComputeError {
+    ComputeError::domain_error(tr!("binary.corrupt", reason = reason))
+}
+
+fn checksum(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| (hash ^ byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+fn check_header(bytes: &[u8]) -> Result<&[u8], ComputeError> {
+    if bytes.len() < HEADER_LEN || &bytes[..MAGIC.len()] != MAGIC {
+        return Err(corrupt(tr!("binary.missing_header")));
+    }
+    let version = bytes[MAGIC.len()];
+    if version != FORMAT_VERSION {
+        return Err(corrupt(&tr!("binary.unsupported_version", version = version)));
+    }
+    let mut stored = [0u8; 8];
+    stored.copy_from_slice(&bytes[MAGIC.len() + 1..HEADER_LEN]);
+    let body = &bytes[HEADER_LEN..];
+    if u64::from_le_bytes(stored) != checksum(body) {
+        return Err(corrupt(tr!("binary.checksum")));
+    }
+    Ok(body)
+}
+
+/// 编码器：对节点去重并分配序号
+#[derive(Default)]
+struct Encoder {
+    strings: Vec<String>,
+    string_ids: HashMap<String, u32>,
+    nodes: Vec<Node>,
+    node_ids: HashMap<Node, u32>,
+}
+
+impl Encoder {
+    fn intern(&mut self, node: Node) -> u32 {
+        if let Some(&id) = self.node_ids.get(&node) {
+            return id;
+        }
+        let id = self.nodes.len() as u32;
+        self.node_ids.insert(node.clone(), id);
+        self.nodes.push(node);
+        id
+    }
+
+    fn string(&mut self, s: &str) -> u32 {
+        if let Some(&id) = self.string_ids.get(s) {
+            return id;
+        }
+        let id = self.strings.len() as u32;
+        self.string_ids.insert(s.to_string(), id);
+        self.strings.push(s.to_string());
+        id
+    }
+
+    /// 后序遍历用显式栈，长的左结合和式等很深的表达式也不会耗尽调用栈
+    fn expression(&mut self, root: &Expression) -> u32 {
+        let mut pending = vec![(root, false)];
+        let mut ids: Vec<u32> = Vec::new();
+        while let Some((expr, children_done)) = pending.pop() {
+            let children = expr.children();
+            if !children_done && !children.is_empty() {
+                pending.push((expr, true));
+                pending.extend(children.into_iter().rev().map(|child| (child, false)));
+                continue;
+            }
+            let args = ids.split_off(ids.len() - children.len());
+            let id = self.node(expr, args);
+            ids.push(id);
+        }
+        ids[0]
+    }
+
+    /// 由子节点序号构造一个节点
+    fn node(&mut self, expr: &Expression, mut args: Vec<u32>) -> u32 {
+        let node = match expr {
+            Expression::Number(n) => return self.number(n),
+            Expression::Variable(name) => Node::Variable(self.string(name)),
+            Expression::Constant(c) => Node::Constant(code_of(&CONSTANTS, c)),
+            Expression::BinaryOp { op, .. } => Node::Binary(code_of(&BINARY_OPERATORS, op), args[0], args[1]),
+            Expression::UnaryOp { op, .. } => Node::Unary(code_of(&UNARY_OPERATORS, op), args[0]),
+            Expression::Function { name, .. } => Node::Function(self.string(name), args),
+            Expression::Matrix(rows) => Node::Matrix(rows.iter().map(|row| args.drain(..row.len()).collect()).collect()),
+            Expression::Vector(_) => Node::Vector(args),
+            Expression::Set(_) => Node::Set(args),
+            Expression::Interval { start_inclusive, end_inclusive, .. } => {
+                Node::Interval(*start_inclusive as u8 | (*end_inclusive as u8) << 1, args[0], args[1])
+            }
+        };
+        self.intern(node)
+    }
+
+    fn number(&mut self, number: &Number) -> u32 {
+        let node = match number {
+            Number::Integer(i) => return self.integer(i),
+            Number::Rational(r) => Node::Rational(self.integer(r.numer()), self.integer(r.denom())),
+            Number::Real(d) => {
+                let (mantissa, scale) = d.as_bigint_and_exponent();
+                Node::Real(self.integer(&mantissa), scale)
+            }
+            Number::Float(f) => Node::Float(f.to_bits()),
+            Number::Complex { real, imaginary } => Node::Complex(self.number(real), self.number(imaginary)),
+            Number::Constant(c) => Node::NumberConstant(code_of(&CONSTANTS, c)),
+            Number::Symbolic(expr) => Node::Symbolic(self.expression(expr)),
+        };
+        self.intern(node)
+    }
+
+    fn integer(&mut self, i: &BigInt) -> u32 {
+        let node = match i.to_i64() {
+            Some(small) => Node::Int(small),
+            None => Node::BigInt(i.to_signed_bytes_le()),
+        };
+        self.intern(node)
+    }
+
+    fn finish(self, roots: &[u32]) -> Vec<u8> {
+        let mut body = Vec::new();
+        write_varint(&mut body, self.strings.len() as u64);
+        for s in &self.strings {
+            write_bytes(&mut body, s.as_bytes());
+        }
+        write_varint(&mut body, self.nodes.len() as u64);
+        for (index, node) in self.nodes.iter().enumerate() {
+            write_node(&mut body, index as u32, node);
+        }
+        write_varint(&mut body, roots.len() as u64);
+        for &root in roots {
+            write_varint(&mut body, root as u64);
+        }
+
+        let mut bytes = Vec::with_capacity(HEADER_LEN + body.len());
+        bytes.extend_from_slice(MAGIC);
+        bytes.push(FORMAT_VERSION);
+        bytes.extend_from_slice(&checksum(&body).to_le_bytes());
+        bytes.extend_from_slice(&body);
+        bytes
+    }
+}
+
+fn code_of<T: PartialEq>(table: &[T], item: &T) -> u8 {
+    table.iter().position(|entry| entry == item).expect("编码表缺少该项") as u8
+}
+
+fn write_varint(out: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        out.push(value as u8 | 0x80);
+        value >>= 7;
+    }
+    out.push(value as u8);
+}
+
+fn write_signed(out: &mut Vec<u8>, value: i64) {
+    write_varint(out, ((value << 1) ^ (value >> 63)) as u64);
+}
+
+fn write_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    write_varint(out, bytes.len() as u64);
+    out.extend_from_slice(bytes);
+}
+
+fn write_node(out: &mut Vec<u8>, index: u32, node: &Node) {
+    // 子节点总在当前节点之前，存差值使大多数引用只占一个字节
+    let child = |out: &mut Vec<u8>, id: u32| write_varint(out, (index - id) as u64);
+    let children = |out: &mut Vec<u8>, ids: &[u32]| {
+        write_varint(out, ids.len() as u64);
+        ids.iter().for_each(|&id| child(out, id));
+    };
+    match node {
+        Node::Int(value) => {
+            out.push(TAG_INT);
+            write_signed(out, *value);
+        }
+        Node::BigInt(bytes) => {
+            out.push(TAG_BIG_INT);
+            write_bytes(out, bytes);
+        }
+        Node::Rational(numer, denom) => {
+            out.push(TAG_RATIONAL);
+            child(out, *numer);
+            child(out, *denom);
+        }
+        Node::Real(mantissa, scale) => {
+            out.push(TAG_REAL);
+            child(out, *mantissa);
+            write_signed(out, *scale);
+        }
+        Node::Float(bits) => {
+            out.push(TAG_FLOAT);
+            out.extend_from_slice(&bits.to_le_bytes());
+        }
+        Node::Complex(real, imaginary) => {
+            out.push(TAG_COMPLEX);
+            child(out, *real);
+            child(out, *imaginary);
+        }
+        Node::NumberConstant(code) => out.extend_from_slice(&[TAG_NUMBER_CONSTANT, *code]),
+        Node::Symbolic(expr) => {
+            out.push(TAG_SYMBOLIC);
+            child(out, *expr);
+        }
+        Node::Variable(name) => {
+            out.push(TAG_VARIABLE);
+            write_varint(out, *name as u64);
+        }
+        Node::Constant(code) => out.extend_from_slice(&[TAG_CONSTANT, *code]),
+        Node::Binary(op, left, right) => {
+            out.extend_from_slice(&[TAG_BINARY, *op]);
+            child(out, *left);
+            child(out, *right);
+        }
+        Node::Unary(op, operand) => {
+            out.extend_from_slice(&[TAG_UNARY, *op]);
+            child(out, *operand);
+        }
+        Node::Function(name, args) => {
+            out.push(TAG_FUNCTION);
+            write_varint(out, *name as u64);
+            children(out, args);
+        }
+        Node::Matrix(rows) => {
+            out.push(TAG_MATRIX);
+            write_varint(out, rows.len() as u64);
+            rows.iter().for_each(|row| children(out, row));
+        }
+        Node::Vector(elements) => {
+            out.push(TAG_VECTOR);
+            children(out, elements);
+        }
+        Node::Set(elements) => {
+            out.push(TAG_SET);
+            children(out, elements);
+        }
+        Node::Interval(flags, start, end) => {
+            out.extend_from_slice(&[TAG_INTERVAL, *flags]);
+            child(out, *start);
+            child(out, *end);
+        }
+    }
+}
+
+/// 读取正文，所有长度和引用都先与剩余数据比对
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn byte(&mut self) -> Result<u8, ComputeError> {
+        let byte = *self.data.get(self.pos).ok_or_else(|| corrupt(tr!("binary.truncated")))?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&[u8], ComputeError> {
+        if len > self.data.len() - self.pos {
+            return Err(corrupt(tr!("binary.truncated")));
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn varint(&mut self) -> Result<u64, ComputeError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            let bits = (byte & 0x7f) as u64;
+            if shift == 63 && bits > 1 {
+                break;
+            }
+            value |= bits << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(corrupt(tr!("binary.varint_overflow")))
+    }
+
+    fn signed(&mut self) -> Result<i64, ComputeError> {
+        let value = self.varint()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    /// 读取元素个数，每个元素至少占一个字节，超过剩余字节数的个数必然是坏数据
+    fn count(&mut self) -> Result<usize, ComputeError> {
+        let count = self.varint()?;
+        if count > (self.data.len() - self.pos) as u64 {
+            return Err(corrupt(tr!("binary.length_out_of_range")));
+        }
+        Ok(count as usize)
+    }
+
+    fn index(&mut self, len: usize, what: &str) -> Result<u32, ComputeError> {
+        let index = self.varint()?;
+        if index >= len as u64 {
+            return Err(corrupt(&tr!("binary.index_out_of_range", item = what)));
+        }
+        Ok(index as u32)
+    }
+
+    fn code(&mut self, len: usize, what: &str) -> Result<u8, ComputeError> {
+        let code = self.byte()?;
+        if code as usize >= len {
+            return Err(corrupt(&tr!("binary.unknown_code", item = what, code = code)));
+        }
+        Ok(code)
+    }
+
+    fn strings(&mut self) -> Result<Vec<String>, ComputeError> {
+        let count = self.count()?;
+        let mut strings = Vec::with_capacity(count);
+        for _ in 0..count {
+            let len = self.count()?;
+            let text = std::str::from_utf8(self.take(len)?).map_err(|_| corrupt(tr!("binary.invalid_utf8")))?;
+            strings.push(text.to_string());
+        }
+        Ok(strings)
+    }
+
+    fn nodes(&mut self, string_count: usize) -> Result<Vec<Node>, ComputeError> {
+        let count = self.count()?;
+        let mut nodes: Vec<Node> = Vec::with_capacity(count);
+        for index in 0..count {
+            let node = self.node(index, string_count)?;
+            let kind_ok = match &node {
+                Node::Rational(numer, denom) => {
+                    nodes[*numer as usize].is_integer() && nodes[*denom as usize].is_integer()
+                        && nodes[*denom as usize] != Node::Int(0)
+                }
+                Node::Real(mantissa, _) => nodes[*mantissa as usize].is_integer(),
+                Node::Complex(real, imaginary) => nodes[*real as usize].is_number() && nodes[*imaginary as usize].is_number(),
+                _ => true,
+            };
+            if !kind_ok {
+                return Err(corrupt(tr!("binary.number_parts")));
+            }
+            nodes.push(node);
+        }
+        Ok(nodes)
+    }
+
+    fn node(&mut self, index: usize, string_count: usize) -> Result<Node, ComputeError> {
+        let tag = self.byte()?;
+        let node = match tag {
+            TAG_INT => Node::Int(self.signed()?),
+            TAG_BIG_INT => {
+                let len = self.count()?;
+                Node::BigInt(self.take(len)?.to_vec())
+            }
+            TAG_RATIONAL => Node::Rational(self.child(index)?, self.child(index)?),
+            TAG_REAL => Node::Real(self.child(index)?, self.signed()?),
+            TAG_FLOAT => {
+                let mut bits = [0u8; 8];
+                bits.copy_from_slice(self.take(8)?);
+                Node::Float(u64::from_le_bytes(bits))
+            }
+            TAG_COMPLEX => Node::Complex(self.child(index)?, self.child(index)?),
+            TAG_NUMBER_CONSTANT => Node::NumberConstant(self.code(CONSTANTS.len(), tr!("binary.item.constant"))?),
+            TAG_SYMBOLIC => Node::Symbolic(self.child(index)?),
+            TAG_VARIABLE => Node::Variable(self.index(string_count, tr!("binary.item.string"))?),
+            TAG_CONSTANT => Node::Constant(self.code(CONSTANTS.len(), tr!("binary.item.constant"))?),
+            TAG_BINARY => {
+                let op = self.code(BINARY_OPERATORS.len(), tr!("binary.item.binary_operator"))?;
+                Node::Binary(op, self.child(index)?, self.child(index)?)
+            }
+            TAG_UNARY => {
+                let op = self.code(UNARY_OPERATORS.len(), tr!("binary.item.unary_operator"))?;
+                Node::Unary(op, self.child(index)?)
+            }
+            TAG_FUNCTION => {
+                let name = self.index(string_count, tr!("binary.item.string"))?;
+                Node::Function(name, self.children(index)?)
+            }
+            TAG_MATRIX => {
+                let rows = self.count()?;
+                Node::Matrix((0..rows).map(|_| self.children(index)).collect::<Result<_, _>>()?)
+            }
+            TAG_VECTOR => Node::Vector(self.children(index)?),
+            TAG_SET => Node::Set(self.children(index)?),
+            TAG_INTERVAL => {
+                let flags = self.byte()?;
+                if flags > 0b11 {
+                    return Err(corrupt(tr!("binary.interval_flags")));
+                }
+                Node::Interval(flags, self.child(index)?, self.child(index)?)
+            }
+            _ => return Err(corrupt(&tr!("binary.unknown_tag", tag = tag))),
+        };
+        Ok(node)
+    }
+
+    fn child(&mut self, index: usize) -> Result<u32, ComputeError> {
+        let offset = self.varint()?;
+        if offset == 0 || offset > index as u64 {
+            return Err(corrupt(tr!("binary.child_out_of_range")));
+        }
+        Ok((index as u64 - offset) as u32)
+    }
+
+    fn children(&mut self, index: usize) -> Result<Vec<u32>, ComputeError> {
+        let count = self.count()?;
+        (0..count).map(|_| self.child(index)).collect()
+    }
+
+    fn roots(&mut self, node_count: usize) -> Result<Vec<u32>, ComputeError> {
+        let count = self.count()?;
+        (0..count).map(|_| self.index(node_count, tr!("binary.item.node"))).collect()
+    }
+}
+
+/// 按后序构造表达式；只被引用一次的节点直接移交给父节点，不必克隆
+struct Builder<'a> {
+    strings: &'a [String],
+    nodes: &'a [Node],
+    remaining: Vec<u32>,
+    built: Vec<Option<Expression>>,
+}
+
+impl<'a> Builder<'a> {
+    fn new(strings: &'a [String], nodes: &'a [Node], roots: &[u32]) -> Result<Self, ComputeError> {
+        let mut remaining = vec![0u32; nodes.len()];
+        let mut sizes = vec![0u64; nodes.len()];
+        for (index, node) in nodes.iter().enumerate() {
+            let mut size = 1u64;
+            node.for_each_child(|id| {
+                remaining[id as usize] = remaining[id as usize].saturating_add(1);
+                size = size.saturating_add(sizes[id as usize]);
+            });
+            sizes[index] = size.min(MAX_DECODED_NODES + 1);
+        }
+        let mut total = 0u64;
+        for &root in roots {
+            remaining[root as usize] = remaining[root as usize].saturating_add(1);
+            total = total.saturating_add(sizes[root as usize]);
+        }
+        if total > MAX_DECODED_NODES {
+            return Err(corrupt(&tr!("binary.too_many_nodes", limit = MAX_DECODED_NODES)));
+        }
+        Ok(Self { strings, nodes, remaining, built: vec![None; nodes.len()] })
+    }
+
+    fn build(mut self, roots: &[u32]) -> Result<Vec<Expression>, ComputeError> {
+        for index in 0..self.nodes.len() {
+            if self.remaining[index] > 0 {
+                let nodes = self.nodes;
+                let expr = self.node(&nodes[index])?;
+                self.built[index] = Some(expr);
+            }
+        }
+        roots.iter().map(|&root| self.take(root)).collect()
+    }
+
+    fn take(&mut self, id: u32) -> Result<Expression, ComputeError> {
+        let id = id as usize;
+        self.remaining[id] -= 1;
+        let expr = if self.remaining[id] == 0 { self.built[id].take() } else { self.built[id].clone() };
+        expr.ok_or_else(|| ComputeError::internal(tr!("binary.forward_reference")))
+    }
+
+    fn take_number(&mut self, id: u32) -> Result<Number, ComputeError> {
+        match self.take(id)? {
+            Expression::Number(n) => Ok(n),
+            _ => Err(corrupt(tr!("binary.number_parts"))),
+        }
+    }
+
+    fn take_integer(&mut self, id: u32) -> Result<BigInt, ComputeError> {
+        match self.take_number(id)? {
+            Number::Integer(i) => Ok(i),
+            _ => Err(corrupt(tr!("binary.number_parts"))),
+        }
+    }
+
+    fn take_all(&mut self, ids: &[u32]) -> Result<Vec<Expression>, ComputeError> {
+        ids.iter().map(|&id| self.take(id)).collect()
+    }
+
+    fn node(&mut self, node: &Node) -> Result<Expression, ComputeError> {
+        let number = |n: Number| Ok(Expression::Number(n));
+        match node {
+            Node::Int(value) => number(Number::Integer(BigInt::from(*value))),
+            Node::BigInt(bytes) => number(Number::Integer(BigInt::from_signed_bytes_le(bytes))),
+            Node::Rational(numer, denom) => {
+                let (numer, denom) = (self.take_integer(*numer)?, self.take_integer(*denom)?);
+                if denom.is_zero() {
+                    return Err(corrupt(tr!("binary.zero_denominator")));
+                }
+                number(Number::Rational(BigRational::new(numer, denom)))
+            }
+            Node::Real(mantissa, scale) => number(Number::Real(BigDecimal::new(self.take_integer(*mantissa)?, *scale))),
+            Node::Float(bits) => number(Number::Float(f64::from_bits(*bits))),
+            Node::Complex(real, imaginary) => {
+                let (real, imaginary) = (self.take_number(*real)?, self.take_number(*imaginary)?);
+                number(Number::Complex { real: Box::new(real), imaginary: Box::new(imaginary) })
+            }
+            Node::NumberConstant(code) => number(Number::Constant(CONSTANTS[*code as usize].clone())),
+            Node::Symbolic(expr) => number(Number::Symbolic(Box::new(self.take(*expr)?))),
+            Node::Variable(name) => Ok(Expression::Variable(self.strings[*name as usize].clone())),
+            Node::Constant(code) => Ok(Expression::Constant(CONSTANTS[*code as usize].clone())),
+            Node::Binary(op, left, right) => Ok(Expression::BinaryOp {
+                op: BINARY_OPERATORS[*op as usize].clone(),
+                left: Box::new(self.take(*left)?),
+                right: Box::new(self.take(*right)?),
+            }),
+            Node::Unary(op, operand) => Ok(Expression::UnaryOp {
+                op: UNARY_OPERATORS[*op as usize].clone(),
+                operand: Box::new(self.take(*operand)?),
+            }),
+            Node::Function(name, args) => Ok(Expression::Function {
+                name: self.strings[*name as usize].clone(),
+                args: self.take_all(args)?,
+            }),
+            Node::Matrix(rows) => Ok(Expression::Matrix(rows.iter().map(|row| self.take_all(row)).collect::<Result<_, _>>()?)),
+            Node::Vector(elements) => Ok(Expression::Vector(self.take_all(elements)?)),
+            Node::Set(elements) => Ok(Expression::Set(self.take_all(elements)?)),
+            Node::Interval(flags, start, end) => Ok(Expression::Interval {
+                start: Box::new(self.take(*start)?),
+                end: Box::new(self.take(*end)?),
+                start_inclusive: flags & 1 != 0,
+                end_inclusive: flags & 2 != 0,
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "binary_tests.rs"]
+mod binary_tests;
@@ -0,0 +1,126 @@
+//! # 二进制格式测试
+//!
+//! 测试各类表达式的往返一致性、子树共享、体积，以及损坏数据的错误处理。
+
+#[cfg(test)]
+mod tests {
+    use crate::core::binary::{checksum, decode, encode, FORMAT_VERSION};
+    use crate::core::{Expression, Number, MathConstant};
+    use crate::engine::{ComputeEngine, ComputeError};
+    use crate::engine::compute::BasicComputeEngine;
+    use crate::test_utils::parse;
+    use num_bigint::BigInt;
+
+    /// 给正文加上格式头与正确的校验和
+    fn seal(body: &[u8]) -> Vec<u8> {
+        let mut bytes = b"YUFB".to_vec();
+        bytes.push(FORMAT_VERSION);
+        bytes.extend_from_slice(&checksum(body).to_le_bytes());
+        bytes.extend_from_slice(body);
+        bytes
+    }
+
+    fn round_trip(expr: &Expression) {
+        assert_eq!(&Expression::from_bytes(&expr.to_bytes()).unwrap(), expr);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for input in ["x^2 + 3*x - 1/2", "sin(x)*cos(y) + exp(-x)", "[[1, x], [y, 2]]", "f(x, y, z)", "-x!"] {
+            round_trip(&parse(input));
+        }
+        let huge: BigInt = BigInt::from(3).pow(200u32);
+        round_trip(&Expression::number(Number::Integer(-huge.clone())));
+        round_trip(&Expression::number(Number::Rational(num_rational::BigRational::new(huge, BigInt::from(7)))));
+        round_trip(&Expression::number(Number::real("-12.5e-40".parse::<bigdecimal::BigDecimal>().unwrap())));
+        round_trip(&Expression::number(Number::float(-0.25)));
+        round_trip(&Expression::number(Number::complex(Number::rational(1, 2), Number::float(2.5))));
+        round_trip(&Expression::number(Number::Constant(MathConstant::Pi)));
+        round_trip(&Expression::number(Number::Symbolic(Box::new(parse("sqrt(2)")))));
+        round_trip(&Expression::Set(vec![Expression::constant(MathConstant::I), Expression::variable("变量")]));
+        round_trip(&Expression::Vector(Vec::new()));
+        round_trip(&Expression::Interval {
+            start: Box::new(Expression::constant(MathConstant::NegativeInfinity)),
+            end: Box::new(parse("a + 1")),
+            start_inclusive: false,
+            end_inclusive: true,
+        });
+    }
+
+    #[test]
+    fn test_shared_subtrees() {
+        let sub = parse("sin(x + y)^2");
+        let repeated = (0..50).fold(sub.clone(), |acc, _| Expression::add(acc, sub.clone()));
+        // 重复的子树只存一次，每次重复只多一个四字节的加法节点
+        assert!(repeated.to_bytes().len() <= sub.to_bytes().len() + 4 * 50 + 1);
+        round_trip(&repeated);
+
+        let exprs = [parse("x^2 + 1"), parse("2*x"), parse("x^2 + 1")];
+        let bytes = encode(&exprs.iter().collect::<Vec<_>>());
+        assert_eq!(decode(&bytes).unwrap(), exprs);
+    }
+
+    #[test]
+    fn test_smaller_than_json() {
+        let engine = BasicComputeEngine::new();
+        let expanded = engine.expand(&parse("(x + y + z)^20")).unwrap();
+        let bytes = expanded.to_bytes();
+        let json = serde_json::to_vec(&expanded).unwrap();
+        assert!(bytes.len() * 5 <= json.len(), "二进制 {} 字节，JSON {} 字节", bytes.len(), json.len());
+        round_trip(&expanded);
+    }
+
+    #[test]
+    fn test_corrupt_data() {
+        let bytes = parse("x^2 + sin(y) - 3/4").to_bytes();
+        let is_invalid = |data: &[u8]| matches!(Expression::from_bytes(data), Err(ComputeError::DomainError { .. }));
+
+        // 截断在任何位置都报错
+        for len in 0..bytes.len() {
+            assert!(is_invalid(&bytes[..len]));
+        }
+        // 任意一个字节被改动都能被校验和发现
+        for index in 0..bytes.len() {
+            let mut damaged = bytes.clone();
+            damaged[index] ^= 0x5a;
+            assert!(is_invalid(&damaged));
+        }
+        let mut future = bytes.clone();
+        future[4] = FORMAT_VERSION + 1;
+        assert!(is_invalid(&future));
+        assert!(is_invalid(b"not an expression"));
+
+        let mut extra = bytes.clone();
+        extra.push(0);
+        assert!(is_invalid(&extra));
+        // 多个表达式不能当作单个表达式解码
+        let pair = encode(&[&parse("x"), &parse("y")]);
+        assert!(is_invalid(&pair));
+    }
+
+    #[test]
+    fn test_malformed_body() {
+        let bytes = parse("[1/2, f(x, 2.5), y + i]").to_bytes();
+        let body = &bytes[13..];
+        // 校验和正确但正文被篡改时也只会报错，不会 panic
+        for index in 0..body.len() {
+            for mask in [0x01, 0x7f, 0x80, 0xff] {
+                let mut damaged = body.to_vec();
+                damaged[index] ^= mask;
+                let _ = decode(&seal(&damaged));
+            }
+        }
+
+        // 每个节点都引用前一个节点两次，展开后有 2^40 个节点
+        let mut bomb = vec![1, 1, b'x', 41, 8, 0];
+        for _ in 0..40 {
+            bomb.extend_from_slice(&[10, 0, 1, 1]);
+        }
+        bomb.extend_from_slice(&[1, 40]);
+        assert!(matches!(decode(&seal(&bomb)), Err(ComputeError::DomainError { .. })));
+
+        // 分母为零的有理数
+        let zero_denominator = [0, 3, 0, 2, 0, 0, 2, 2, 1, 1, 2];
+        assert!(matches!(decode(&seal(&zero_denominator)), Err(ComputeError::DomainError { .. })));
+    }
+}
@@ -0,0 +1,54 @@
+//! # 表达式的规范形式
+//!
+//! 把加法链与乘法链展开后按固定顺序重新排列，使只差交换律、结合律的表达式
+//! （`2 + 3` 与 `3 + 2`、`(a*b)*c` 与 `c*(b*a)`）得到同一个结果，可作为去重和分组的键。
+//! 只重排，不化简，也不改变减法、除法等不满足交换律的运算。
+
+use super::{BinaryOperator, Expression};
+
+impl Expression {
+    /// 规范形式：递归地把加法与乘法的操作数按固定顺序排列并改写为左结合
+    ///
+    /// 含矩阵或向量字面量的乘积不满足交换律，只展开不重排。
+    pub fn canonicalize(&self) -> Expression {
+        match self {
+            Expression::BinaryOp { op: op @ (BinaryOperator::Add | BinaryOperator::Multiply), .. } => {
+                let mut operands = Vec::new();
+                collect_operands(self, op, &mut operands);
+                let mut operands: Vec<Expression> = operands.into_iter().map(Expression::canonicalize).collect();
+                let commutative = *op == BinaryOperator::Add
+                    || !operands.iter().any(|operand| matches!(operand, Expression::Matrix(_) | Expression::Vector(_)));
+                if commutative {
+                    operands.sort_by_cached_key(|operand| format!("{:?}", operand));
+                }
+                operands.into_iter()
+                    .reduce(|left, right| Expression::binary_op(op.clone(), left, right))
+                    .expect("运算链至少有两个操作数")
+            }
+            Expression::BinaryOp { op, left, right } => Expression::binary_op(op.clone(), left.canonicalize(), right.canonicalize()),
+            Expression::UnaryOp { op, operand } => Expression::UnaryOp { op: op.clone(), operand: Box::new(operand.canonicalize()) },
+            Expression::Function { name, args } => Expression::function(name, args.iter().map(Expression::canonicalize).collect()),
+            Expression::Matrix(rows) => Expression::Matrix(rows.iter().map(|row| row.iter().map(Expression::canonicalize).collect()).collect()),
+            Expression::Vector(elements) => Expression::Vector(elements.iter().map(Expression::canonicalize).collect()),
+            Expression::Set(elements) => Expression::Set(elements.iter().map(Expression::canonicalize).collect()),
+            Expression::Interval { start, end, start_inclusive, end_inclusive } => Expression::Interval {
+                start: Box::new(start.canonicalize()),
+                end: Box::new(end.canonicalize()),
+                start_inclusive: *start_inclusive,
+                end_inclusive: *end_inclusive,
+            },
+            other => other.clone(),
+        }
+    }
+}
+
+/// 展开同一运算符的运算链
+fn collect_operands<'a>(expr: &'a Expression, chain: &BinaryOperator, operands: &mut Vec<&'a Expression>) {
+    match expr {
+        Expression::BinaryOp { op, left, right } if op == chain => {
+            collect_operands(left, chain, operands);
+            collect_operands(right, chain, operands);
+        }
+        _ => operands.push(expr),
+    }
+}
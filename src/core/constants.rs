@@ -6,6 +6,7 @@ use super::Number;
 use num_bigint::BigInt;
 use bigdecimal::BigDecimal;
 use std::collections::HashMap;
+use crate::tr;
 
 /// 数学常量类型
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
@@ -70,7 +71,7 @@ impl MathConstant {
             "gamma" | "γ" => Some(MathConstant::EulerGamma),
             "phi" | "φ" => Some(MathConstant::GoldenRatio),
             "catalan" | "g" => Some(MathConstant::Catalan),
-            "inf" | "infinity" | "∞" => Some(MathConstant::PositiveInfinity),
+            "inf" | "infinity" | "oo" | "∞" => Some(MathConstant::PositiveInfinity),
             "-inf" | "-infinity" | "-∞" => Some(MathConstant::NegativeInfinity),
             "nan" | "undefined" => Some(MathConstant::Undefined),
             _ => None,
@@ -80,15 +81,15 @@ impl MathConstant {
     /// 获取常量的完整名称
     pub fn name(&self) -> &'static str {
         match self {
-            MathConstant::Pi => "圆周率",
-            MathConstant::E => "自然常数",
-            MathConstant::I => "虚数单位",
-            MathConstant::EulerGamma => "欧拉-马歇罗尼常数",
-            MathConstant::GoldenRatio => "黄金比例",
-            MathConstant::Catalan => "卡塔兰常数",
-            MathConstant::PositiveInfinity => "正无穷",
-            MathConstant::NegativeInfinity => "负无穷",
-            MathConstant::Undefined => "未定义",
+            MathConstant::Pi => tr!("constant.pi"),
+            MathConstant::E => tr!("constant.e"),
+            MathConstant::I => tr!("constant.i"),
+            MathConstant::EulerGamma => tr!("constant.euler_gamma"),
+            MathConstant::GoldenRatio => tr!("constant.golden_ratio"),
+            MathConstant::Catalan => tr!("constant.catalan"),
+            MathConstant::PositiveInfinity => tr!("constant.positive_infinity"),
+            MathConstant::NegativeInfinity => tr!("constant.negative_infinity"),
+            MathConstant::Undefined => tr!("constant.undefined"),
         }
     }
     
@@ -174,15 +175,15 @@ impl MathConstant {
     /// 获取常量的数学性质描述
     pub fn properties(&self) -> Vec<&'static str> {
         match self {
-            MathConstant::Pi => vec!["无理数", "超越数", "正数"],
-            MathConstant::E => vec!["无理数", "超越数", "正数"],
-            MathConstant::I => vec!["虚数单位", "复数"],
-            MathConstant::EulerGamma => vec!["实数", "可能是无理数"],
-            MathConstant::GoldenRatio => vec!["无理数", "代数数", "正数"],
-            MathConstant::Catalan => vec!["实数", "可能是无理数", "正数"],
-            MathConstant::PositiveInfinity => vec!["无穷大", "正数"],
-            MathConstant::NegativeInfinity => vec!["无穷大", "负数"],
-            MathConstant::Undefined => vec!["未定义"],
+            MathConstant::Pi => vec![tr!("constant.property.irrational"), tr!("constant.property.transcendental"), tr!("constant.property.positive")],
+            MathConstant::E => vec![tr!("constant.property.irrational"), tr!("constant.property.transcendental"), tr!("constant.property.positive")],
+            MathConstant::I => vec![tr!("constant.property.imaginary_unit"), tr!("constant.property.complex")],
+            MathConstant::EulerGamma => vec![tr!("constant.property.real"), tr!("constant.property.possibly_irrational")],
+            MathConstant::GoldenRatio => vec![tr!("constant.property.irrational"), tr!("constant.property.algebraic"), tr!("constant.property.positive")],
+            MathConstant::Catalan => vec![tr!("constant.property.real"), tr!("constant.property.possibly_irrational"), tr!("constant.property.positive")],
+            MathConstant::PositiveInfinity => vec![tr!("constant.property.infinite"), tr!("constant.property.positive")],
+            MathConstant::NegativeInfinity => vec![tr!("constant.property.infinite"), tr!("constant.property.negative")],
+            MathConstant::Undefined => vec![tr!("constant.property.undefined")],
         }
     }
     
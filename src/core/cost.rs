@@ -0,0 +1,113 @@
+//! # 表达式计算成本模型
+//!
+//! [`Expression::complexity`] 只数节点，幂、函数调用和矩阵与加法一样计 1。
+//! 成本模型给各类节点不同的权重，用于并行阈值、缓存成本和耗时预估，权重可以在配置中调整。
+
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use super::{BinaryOperator, Expression, UnaryOperator};
+
+/// 各类节点的计算成本权重
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CostModel {
+    /// 数值、变量与常量
+    pub atom: u64,
+    /// 加法与减法
+    pub addition: u64,
+    /// 乘法
+    pub multiplication: u64,
+    /// 除法与取模
+    pub division: u64,
+    /// 幂运算
+    pub power: u64,
+    /// 矩阵乘法、叉积、点积、行列式与逆矩阵
+    pub matrix_product: u64,
+    /// 比较、逻辑与集合运算
+    pub other_binary: u64,
+    /// 取负、取正与逻辑非
+    pub sign: u64,
+    /// 函数调用，含写成一元运算的 `sqrt`、`sin` 等
+    pub function: u64,
+    /// 矩阵本身
+    pub matrix: u64,
+    /// 矩阵的每个元素，另加元素自身的成本
+    pub matrix_element: u64,
+    /// 向量、集合与区间本身
+    pub collection: u64,
+    /// 每单位成本的预估耗时（微秒）
+    pub unit_micros: u64,
+}
+
+impl Default for CostModel {
+    fn default() -> Self {
+        Self {
+            atom: 1,
+            addition: 1,
+            multiplication: 2,
+            division: 4,
+            power: 8,
+            matrix_product: 20,
+            other_binary: 1,
+            sign: 1,
+            function: 10,
+            matrix: 10,
+            matrix_element: 2,
+            collection: 1,
+            unit_micros: 1,
+        }
+    }
+}
+
+impl CostModel {
+    /// 二元运算节点自身的权重
+    pub fn binary_weight(&self, op: &BinaryOperator) -> u64 {
+        match op {
+            BinaryOperator::Add | BinaryOperator::Subtract => self.addition,
+            BinaryOperator::Multiply => self.multiplication,
+            BinaryOperator::Divide | BinaryOperator::Modulo => self.division,
+            BinaryOperator::Power => self.power,
+            BinaryOperator::MatrixMultiply | BinaryOperator::CrossProduct | BinaryOperator::DotProduct => self.matrix_product,
+            _ => self.other_binary,
+        }
+    }
+
+    /// 一元运算节点自身的权重，`sqrt`、`sin`、阶乘这类运算按函数调用计
+    pub fn unary_weight(&self, op: &UnaryOperator) -> u64 {
+        match op {
+            UnaryOperator::Negate | UnaryOperator::Plus | UnaryOperator::Not => self.sign,
+            UnaryOperator::Determinant | UnaryOperator::Inverse => self.matrix_product,
+            _ => self.function,
+        }
+    }
+
+    /// 按成本预估计算耗时
+    pub fn estimated_duration(&self, expr: &Expression) -> Duration {
+        Duration::from_micros(expr.cost(self).saturating_mul(self.unit_micros))
+    }
+}
+
+impl Expression {
+    /// 按成本模型计算的表达式成本，各节点权重之和
+    pub fn cost(&self, model: &CostModel) -> u64 {
+        let sum = |elements: &[Expression]| elements.iter().map(|elem| elem.cost(model)).fold(0u64, u64::saturating_add);
+        match self {
+            Expression::Number(_) | Expression::Variable(_) | Expression::Constant(_) => model.atom,
+            Expression::BinaryOp { op, left, right } => {
+                model.binary_weight(op).saturating_add(left.cost(model)).saturating_add(right.cost(model))
+            }
+            Expression::UnaryOp { op, operand } => model.unary_weight(op).saturating_add(operand.cost(model)),
+            Expression::Function { args, .. } => model.function.saturating_add(sum(args)),
+            Expression::Matrix(rows) => {
+                let elements = rows.iter().map(Vec::len).sum::<usize>() as u64;
+                rows.iter()
+                    .map(|row| sum(row))
+                    .fold(model.matrix.saturating_add(model.matrix_element.saturating_mul(elements)), u64::saturating_add)
+            }
+            Expression::Vector(elements) | Expression::Set(elements) => model.collection.saturating_add(sum(elements)),
+            Expression::Interval { start, end, .. } => {
+                model.collection.saturating_add(start.cost(model)).saturating_add(end.cost(model))
+            }
+        }
+    }
+}
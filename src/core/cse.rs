@@ -0,0 +1,151 @@
+//! # 公共子表达式提取
+//!
+//! 找出出现两次以上、复杂度不低于阈值的复合子表达式，按依赖顺序提取为临时变量，
+//! 如 `sin(x+y)^2 + cos(x+y)^2 + sin(x+y)` 提取 `t1 = x + y`、`t2 = sin(t1)`。
+//! 某个子表达式整体重复时不再统计它内部的子表达式，避免只在它内部出现的部分被单独提取。
+//! 约束变量的函数（如 `sum(f, k, a, b)`）只能整体提取，不深入其参数。代码生成也使用这里的实现。
+
+use std::collections::{HashMap, HashSet};
+use super::{Expression, MathConstant, Number, UnaryOperator};
+use super::expression::binds_variable;
+
+/// 公共子表达式提取选项
+#[derive(Debug, Clone, PartialEq)]
+pub struct CseOptions {
+    /// 复杂度（[`Expression::complexity`]）不低于该值的子表达式才提取
+    pub min_complexity: usize,
+    /// 临时变量名前缀
+    pub prefix: String,
+    /// 第一个临时变量的编号
+    pub first_index: usize,
+}
+
+impl Default for CseOptions {
+    fn default() -> Self {
+        Self {
+            min_complexity: 2,
+            prefix: "t".to_string(),
+            first_index: 1,
+        }
+    }
+}
+
+/// 对一组表达式做公共子表达式提取，返回按依赖顺序排列的定义 `(临时变量, 子表达式)` 和重写后的各表达式
+///
+/// 临时变量名避开表达式中已有的变量与数学常量名。
+pub fn cse(exprs: &[Expression], options: &CseOptions) -> (Vec<(String, Expression)>, Vec<Expression>) {
+    let mut counts = HashMap::new();
+    for expr in exprs {
+        count(expr, &mut counts);
+    }
+    let shared: HashSet<Expression> = counts.into_iter()
+        .filter(|(expr, count)| *count > 1 && expr.complexity() >= options.min_complexity)
+        .map(|(expr, _)| expr)
+        .collect();
+
+    let taken: HashSet<String> = exprs.iter().flat_map(Expression::get_variables).collect();
+    let mut extractor = Extractor {
+        shared,
+        temps: HashMap::new(),
+        definitions: Vec::new(),
+        names: (options.first_index..)
+            .map(|i| format!("{}{}", options.prefix, i))
+            .filter(|name| !taken.contains(name) && MathConstant::from_str(name).is_none()),
+    };
+    let rewritten = exprs.iter().map(|expr| extractor.rewrite(expr)).collect();
+    (extractor.definitions, rewritten)
+}
+
+/// 统计复合子表达式的出现次数，重复出现的子表达式不再深入统计
+fn count(expr: &Expression, counts: &mut HashMap<Expression, usize>) {
+    if let Expression::Number(Number::Symbolic(inner)) = expr {
+        return count(inner, counts);
+    }
+    if is_compound(expr) {
+        let count = counts.entry(expr.clone()).or_insert(0);
+        *count += 1;
+        if *count > 1 {
+            return;
+        }
+    }
+    for child in children(expr) {
+        count(child, counts);
+    }
+}
+
+/// 是否值得提取（变量、常量和对它们取负不提取）
+fn is_compound(expr: &Expression) -> bool {
+    match expr {
+        Expression::BinaryOp { .. } | Expression::Function { .. } => true,
+        Expression::UnaryOp { op: UnaryOperator::Negate | UnaryOperator::Plus, operand } => is_compound(operand),
+        Expression::UnaryOp { .. } => true,
+        _ => false,
+    }
+}
+
+/// 可以单独提取的直接子表达式
+fn children(expr: &Expression) -> Vec<&Expression> {
+    match expr {
+        Expression::BinaryOp { left, right, .. } => vec![left, right],
+        Expression::UnaryOp { operand, .. } => vec![operand],
+        Expression::Function { name, args } if !binds_variable(name, args) => args.iter().collect(),
+        Expression::Vector(elements) | Expression::Set(elements) => elements.iter().collect(),
+        Expression::Matrix(rows) => rows.iter().flatten().collect(),
+        Expression::Interval { start, end, .. } => vec![start, end],
+        _ => Vec::new(),
+    }
+}
+
+/// 单次提取过程的状态
+struct Extractor<I: Iterator<Item = String>> {
+    /// 需要提取的子表达式
+    shared: HashSet<Expression>,
+    /// 已提取的子表达式及其临时变量名
+    temps: HashMap<Expression, String>,
+    /// 按依赖顺序排列的定义
+    definitions: Vec<(String, Expression)>,
+    /// 可用的临时变量名
+    names: I,
+}
+
+impl<I: Iterator<Item = String>> Extractor<I> {
+    /// 自底向上重写，子表达式先于包含它的表达式提取
+    fn rewrite(&mut self, expr: &Expression) -> Expression {
+        if let Some(name) = self.temps.get(expr) {
+            return Expression::variable(name.clone());
+        }
+        let rewritten = self.rewrite_children(expr);
+        if !self.shared.contains(expr) {
+            return rewritten;
+        }
+        let name = self.names.next().expect("临时变量名取之不尽");
+        self.temps.insert(expr.clone(), name.clone());
+        self.definitions.push((name.clone(), rewritten));
+        Expression::variable(name)
+    }
+
+    fn rewrite_children(&mut self, expr: &Expression) -> Expression {
+        match expr {
+            Expression::Number(Number::Symbolic(inner)) => Expression::Number(Number::Symbolic(Box::new(self.rewrite(inner)))),
+            Expression::BinaryOp { op, left, right } => Expression::binary_op(op.clone(), self.rewrite(left), self.rewrite(right)),
+            Expression::UnaryOp { op, operand } => Expression::unary_op(op.clone(), self.rewrite(operand)),
+            Expression::Function { name, args } if !binds_variable(name, args) => {
+                Expression::function(name.clone(), args.iter().map(|arg| self.rewrite(arg)).collect())
+            }
+            Expression::Vector(elements) => Expression::Vector(elements.iter().map(|e| self.rewrite(e)).collect()),
+            Expression::Set(elements) => Expression::Set(elements.iter().map(|e| self.rewrite(e)).collect()),
+            Expression::Matrix(rows) => Expression::Matrix(rows.iter().map(|row| row.iter().map(|e| self.rewrite(e)).collect()).collect()),
+            Expression::Interval { start, end, start_inclusive, end_inclusive } => Expression::Interval {
+                start: Box::new(self.rewrite(start)),
+                end: Box::new(self.rewrite(end)),
+                start_inclusive: *start_inclusive,
+                end_inclusive: *end_inclusive,
+            },
+            _ => expr.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+#[path = "cse_tests.rs"]
+mod cse_tests;
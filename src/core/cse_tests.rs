@@ -0,0 +1,93 @@
+//! # 公共子表达式提取测试
+//!
+//! 测试提取顺序、复杂度阈值与命名前缀、临时变量避开已有变量名，
+//! 以及把定义代回后与原表达式在数值抽样上一致。
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::core::{cse, CseOptions, Expression};
+    use crate::engine::calculus::CalculusEngine;
+    use crate::test_utils::parse;
+
+    fn extract(input: &str, options: &CseOptions) -> (Vec<(String, Expression)>, Expression) {
+        let (definitions, mut rewritten) = cse(&[parse(input)], options);
+        (definitions, rewritten.remove(0))
+    }
+
+    /// 从最后一个定义开始依次代回
+    fn inline(definitions: &[(String, Expression)], expr: &Expression) -> Expression {
+        definitions.iter().rev().fold(expr.clone(), |expr, (name, value)| {
+            expr.substitute(&HashMap::from([(name.clone(), value.clone())]))
+        })
+    }
+
+    /// 在若干抽样点上比较数值
+    fn assert_equivalent(original: &Expression, restored: &Expression) {
+        let engine = CalculusEngine::new();
+        for (x, y) in [(0.3, 1.7), (-1.2, 0.4), (2.5, -0.8), (0.9, 0.9)] {
+            let bindings = HashMap::from([("x".to_string(), x), ("y".to_string(), y), ("t1".to_string(), 0.5)]);
+            let expected = engine.numerical_evaluate(original, &bindings).unwrap();
+            let actual = engine.numerical_evaluate(restored, &bindings).unwrap();
+            assert!((expected - actual).abs() <= 1e-12 * (1.0 + expected.abs()), "{} 与 {} 在 ({}, {}) 处不一致", original, restored, x, y);
+        }
+    }
+
+    #[test]
+    fn test_dependency_order() {
+        let input = "sin(x+y)^2 + cos(x+y)^2 + sin(x+y)";
+        let (definitions, rewritten) = extract(input, &CseOptions::default());
+        assert_eq!(definitions, vec![
+            ("t1".to_string(), parse("x + y")),
+            ("t2".to_string(), parse("sin(t1)")),
+        ]);
+        assert_eq!(rewritten, parse("t2^2 + cos(t1)^2 + t2"));
+        assert_equivalent(&parse(input), &inline(&definitions, &rewritten));
+    }
+
+    #[test]
+    fn test_threshold_and_prefix() {
+        let input = "exp(x*y + 1) * (x*y + 1) + cos(x*y + 1)";
+        let (definitions, rewritten) = extract(input, &CseOptions::default());
+        assert_eq!(definitions, vec![("t1".to_string(), parse("x*y + 1"))]);
+        assert_equivalent(&parse(input), &inline(&definitions, &rewritten));
+
+        // 只在整体重复的子表达式内部出现的部分不单独提取
+        let (definitions, _) = extract("sin(x*y) + sin(x*y)", &CseOptions::default());
+        assert_eq!(definitions, vec![("t1".to_string(), parse("sin(x*y)"))]);
+
+        let options = CseOptions { min_complexity: 6, prefix: "c".to_string(), ..CseOptions::default() };
+        let (definitions, rewritten) = extract("(x + y)^2 + (x + y) + sin(x*y + 1) / sin(x*y + 1)", &options);
+        assert_eq!(definitions, vec![("c1".to_string(), parse("sin(x*y + 1)"))]);
+        assert_eq!(rewritten, parse("(x + y)^2 + (x + y) + c1/c1"));
+
+        let (definitions, rewritten) = extract("x + y", &CseOptions::default());
+        assert!(definitions.is_empty());
+        assert_eq!(rewritten, parse("x + y"));
+    }
+
+    #[test]
+    fn test_avoid_existing_names() {
+        let input = "(x + y)*t1 + (x + y)^2";
+        let (definitions, rewritten) = extract(input, &CseOptions::default());
+        assert_eq!(definitions, vec![("t2".to_string(), parse("x + y"))]);
+        assert_equivalent(&parse(input), &inline(&definitions, &rewritten));
+
+        // 约束变量的函数只整体提取
+        let (definitions, _) = extract("sum(k^2 + 1, k, 1, 5) + sum(k^2 + 1, k, 1, 6)", &CseOptions::default());
+        assert!(definitions.is_empty());
+        let (definitions, _) = extract("sum(k^2, k, 1, 5) * sum(k^2, k, 1, 5)", &CseOptions::default());
+        assert_eq!(definitions, vec![("t1".to_string(), parse("sum(k^2, k, 1, 5)"))]);
+    }
+
+    #[test]
+    fn test_multiple_expressions() {
+        let outputs = [parse("2*x*cos(x*y)"), parse("x^2*cos(x*y)")];
+        let options = CseOptions { first_index: 0, ..CseOptions::default() };
+        let (definitions, rewritten) = cse(&outputs, &options);
+        assert_eq!(definitions, vec![("t0".to_string(), parse("cos(x*y)"))]);
+        for (original, rewritten) in outputs.iter().zip(&rewritten) {
+            assert_equivalent(original, &inline(&definitions, rewritten));
+        }
+    }
+}
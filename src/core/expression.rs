@@ -3,9 +3,34 @@
 //! 定义数学表达式的核心数据结构，支持各种数学运算和操作。
 
 use super::{Number, MathConstant, BinaryOperator, UnaryOperator, ExprType, NumericType};
+use super::memory;
+use crate::formatter::{Formatter, StandardFormatter};
+use super::indeterminate::{IndeterminateSemantics, Magnitude};
+use super::signatures;
 use std::fmt::{self, Display};
 use std::collections::HashMap;
 use num_traits::{ToPrimitive, Zero, Signed};
+use crate::tr;
+
+/// 下标访问在表达式树中的函数名：`A[i, j]` 表示为 `index(A, i, j)`
+pub const INDEX_FUNCTION: &str = "index";
+
+/// 切片下标 `:` 在表达式树中的函数名（无参数）
+pub const INDEX_ALL: &str = ":";
+
+/// 约束变量的函数：(函数名, 约束变量的参数位置, 约束变量起作用的参数位置, 最少参数个数)
+///
+/// 如 `sum(f, k, a, b)` 中的 `k` 只在 `f` 里受约束，代换和 alpha 等价判断都不把它当作自由变量。
+/// 参数个数不足时不约束变量，如 `map(f, L)` 中的 `L` 是列表而不是约束变量。
+const BINDERS: &[(&str, usize, &[usize], usize)] = &[
+    ("sum", 1, &[0], 2),
+    ("expectation", 2, &[1], 3),
+    ("resultant", 2, &[0, 1], 3),
+    ("discriminant", 1, &[0], 2),
+    ("table", 1, &[0], 4),
+    ("map", 1, &[0], 3),
+    ("filter", 1, &[0], 3),
+];
 
 /// 数学表达式的核心数据结构
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -67,16 +92,22 @@ impl Expression {
     pub fn binary_op(op: BinaryOperator, left: Expression, right: Expression) -> Self {
         Expression::BinaryOp {
             op,
-            left: Box::new(left),
-            right: Box::new(right),
+            left: memory::boxed(left),
+            right: memory::boxed(right),
         }
     }
     
     /// 创建一元运算表达式
+    ///
+    /// `sin`、`ln`、`exp` 等函数型运算符（见 [`UnaryOperator::function_name`]）规范化为同名的函数调用，
+    /// 与解析器的输出一致
     pub fn unary_op(op: UnaryOperator, operand: Expression) -> Self {
+        if let Some(name) = op.function_name() {
+            return Expression::function(name, vec![operand]);
+        }
         Expression::UnaryOp {
             op,
-            operand: Box::new(operand),
+            operand: memory::boxed(operand),
         }
     }
     
@@ -91,28 +122,141 @@ impl Expression {
     /// 创建矩阵表达式
     pub fn matrix(rows: Vec<Vec<Expression>>) -> Result<Self, String> {
         if rows.is_empty() {
-            return Err("矩阵不能为空".to_string());
+            return Err(tr!("expression.empty_matrix").to_string());
         }
         
         let cols = rows[0].len();
         if cols == 0 {
-            return Err("矩阵行不能为空".to_string());
+            return Err(tr!("expression.empty_matrix_row").to_string());
         }
         
         // 检查所有行的列数是否相同
         for (i, row) in rows.iter().enumerate() {
             if row.len() != cols {
-                return Err(format!("矩阵第{}行的列数({})与第一行的列数({})不匹配", i + 1, row.len(), cols));
+                return Err(tr!("expression.ragged_matrix", row = i + 1, len = row.len(), cols = cols));
             }
         }
         
         Ok(Expression::Matrix(rows))
     }
+
+    /// 创建 n×n 单位矩阵
+    pub fn identity_matrix(n: usize) -> Self {
+        Expression::Matrix((0..n).map(|i| {
+            (0..n).map(|j| Expression::Number(if i == j { Number::one() } else { Number::zero() })).collect()
+        }).collect())
+    }
+
+    /// 创建 m×n 零矩阵
+    pub fn zero_matrix(rows: usize, cols: usize) -> Self {
+        Expression::Matrix(vec![vec![Expression::Number(Number::zero()); cols]; rows])
+    }
+
+    /// 创建下标访问 `target[i, j]`；下标从 1 开始，整行或整列用 [`Expression::index_all`]
+    pub fn index(target: Expression, indices: Vec<Expression>) -> Self {
+        let mut args = Vec::with_capacity(indices.len() + 1);
+        args.push(target);
+        args.extend(indices);
+        Expression::function(INDEX_FUNCTION, args)
+    }
+    
+    /// 切片下标 `:`，取整行或整列
+    pub fn index_all() -> Self {
+        Expression::function(INDEX_ALL, Vec::new())
+    }
+    
+    /// 是否为切片下标 `:`
+    pub fn is_index_all(&self) -> bool {
+        matches!(self, Expression::Function { name, args } if name == INDEX_ALL && args.is_empty())
+    }
+    
+    /// 拆出下标访问的目标与下标
+    pub fn as_index(&self) -> Option<(&Expression, &[Expression])> {
+        match self {
+            Expression::Function { name, args } if name == INDEX_FUNCTION && args.len() >= 2 => {
+                Some((&args[0], &args[1..]))
+            }
+            _ => None,
+        }
+    }
+    
+    /// 直接构造的函数型一元运算（如 `UnaryOp { op: Sin, .. }`）对应的函数调用，
+    /// 化简、求导等只处理函数调用这一种形式
+    pub fn to_function_call(&self) -> Option<Expression> {
+        match self {
+            Expression::UnaryOp { op, operand } => {
+                op.function_name().map(|name| Expression::function(name, vec![operand.as_ref().clone()]))
+            }
+            _ => None,
+        }
+    }
     
+    /// 作为下标访问的目标时是否需要加括号：`(A * B)[1, 2]`
+    pub fn needs_parens_as_index_target(&self) -> bool {
+        !matches!(self, Expression::Variable(_) | Expression::Function { .. } | Expression::Matrix(_) | Expression::Vector(_))
+    }
+
+    /// 打印后最外层二元运算的优先级，原子表达式为 `None`；分数 `1/2` 按除法、复数 `1+2i` 按加法计
+    fn display_precedence(&self) -> Option<u8> {
+        match self {
+            Expression::BinaryOp { op, .. } => Some(op.precedence()),
+            Expression::Number(Number::Rational(r)) if !r.is_integer() => Some(BinaryOperator::Divide.precedence()),
+            Expression::Number(Number::Complex { real, imaginary }) if !real.is_zero() && !imaginary.is_zero() => {
+                Some(BinaryOperator::Add.precedence())
+            }
+            Expression::Number(Number::Symbolic(expr)) => expr.display_precedence(),
+            _ => None,
+        }
+    }
+
+    /// 打印后是否以符号开头：`-x`、`+x`、`!x`、`-3`
+    pub fn has_leading_sign(&self) -> bool {
+        match self {
+            Expression::UnaryOp { op: UnaryOperator::Negate | UnaryOperator::Plus | UnaryOperator::Not, .. } => true,
+            Expression::Number(Number::Symbolic(expr)) => expr.has_leading_sign(),
+            Expression::Number(n) => n.to_string().starts_with('-'),
+            Expression::Constant(MathConstant::NegativeInfinity) => true,
+            _ => false,
+        }
+    }
+
+    /// 作为二元运算 `parent` 的运算数时是否需要加括号，`is_right` 表示右运算数
+    ///
+    /// 优先级低于父运算、或同级而结合方向不符时加括号；幂的底数与指数为复合表达式时一律加括号；
+    /// 带符号的表达式作算术运算的右运算数或幂的底数时加括号：`2 * (-3)`、`a - (-b)`、`x^(-1)`、`(-x)^2`。
+    /// 幂比负号结合更紧，`-x^2` 即 `-(x^2)`，与解析器一致
+    pub fn needs_parens_as_operand(&self, parent: &BinaryOperator, is_right: bool) -> bool {
+        if let Some(precedence) = self.display_precedence() {
+            let parent_precedence = parent.precedence();
+            if *parent == BinaryOperator::Power
+                || precedence < parent_precedence
+                || (precedence == parent_precedence && is_right != parent.is_right_associative())
+            {
+                return true;
+            }
+        }
+        self.has_leading_sign()
+            && parent.precedence() >= BinaryOperator::Add.precedence()
+            && (is_right || *parent == BinaryOperator::Power)
+    }
+
+    /// 作为前缀负号、正号或逻辑非的运算数时是否需要加括号：`-(a + b)`、`-(-x)`，幂不加括号
+    pub fn needs_parens_as_prefix_operand(&self) -> bool {
+        match self.display_precedence() {
+            Some(precedence) => precedence != BinaryOperator::Power.precedence(),
+            None => self.has_leading_sign(),
+        }
+    }
+
+    /// 作为后缀运算（阶乘、转置）的运算数时是否需要加括号：`(n + 1)!`、`(-n)!`
+    pub fn needs_parens_as_postfix_operand(&self) -> bool {
+        self.display_precedence().is_some() || self.has_leading_sign()
+    }
+
     /// 创建向量表达式
     pub fn vector(elements: Vec<Expression>) -> Result<Self, String> {
         if elements.is_empty() {
-            return Err("向量不能为空".to_string());
+            return Err(tr!("expression.empty_vector").to_string());
         }
         Ok(Expression::Vector(elements))
     }
@@ -271,12 +415,58 @@ impl Expression {
             _ => {} // 数值和常量不包含变量
         }
     }
-    
-    /// 推断表达式的类型
+
+    /// 查找所有满足条件的子表达式（包括自身），按先序遍历顺序返回
+    pub fn find<F>(&self, predicate: F) -> Vec<&Expression>
+    where
+        F: Fn(&Expression) -> bool,
+    {
+        let mut matches = Vec::new();
+        self.collect_matches(&predicate, &mut matches);
+        matches
+    }
+
+    /// 检查表达式中是否包含与 `sub` 结构相同的子表达式
+    pub fn contains(&self, sub: &Expression) -> bool {
+        self == sub || self.children().into_iter().any(|child| child.contains(sub))
+    }
+
+    /// 递归收集满足条件的子表达式
+    fn collect_matches<'a, F>(&'a self, predicate: &F, matches: &mut Vec<&'a Expression>)
+    where
+        F: Fn(&Expression) -> bool,
+    {
+        if predicate(self) {
+            matches.push(self);
+        }
+        for child in self.children() {
+            child.collect_matches(predicate, matches);
+        }
+    }
+
+    /// 直接子表达式
+    pub(crate) fn children(&self) -> Vec<&Expression> {
+        match self {
+            Expression::BinaryOp { left, right, .. } => vec![left.as_ref(), right.as_ref()],
+            Expression::UnaryOp { operand, .. } => vec![operand.as_ref()],
+            Expression::Function { args, .. } => args.iter().collect(),
+            Expression::Matrix(rows) => rows.iter().flatten().collect(),
+            Expression::Vector(elements) | Expression::Set(elements) => elements.iter().collect(),
+            Expression::Interval { start, end, .. } => vec![start.as_ref(), end.as_ref()],
+            Expression::Number(_) | Expression::Variable(_) | Expression::Constant(_) => Vec::new(),
+        }
+    }
+
+    /// 推断表达式的类型，变量一律视为符号类型
     pub fn infer_type(&self) -> ExprType {
+        self.infer_type_with(&|_| None)
+    }
+
+    /// 推断表达式的类型，`variable_type` 给出已知变量的数值类型（如来自变量假设表），未知时返回 `None`
+    pub fn infer_type_with(&self, variable_type: &dyn Fn(&str) -> Option<NumericType>) -> ExprType {
         match self {
             Expression::Number(n) => ExprType::Numeric(n.get_numeric_type()),
-            Expression::Variable(_) => ExprType::Symbolic,
+            Expression::Variable(name) => variable_type(name).map_or(ExprType::Symbolic, ExprType::Numeric),
             Expression::Constant(c) => match c {
                 MathConstant::I => ExprType::Numeric(NumericType::Complex),
                 MathConstant::PositiveInfinity | MathConstant::NegativeInfinity => ExprType::Numeric(NumericType::Real),
@@ -284,16 +474,16 @@ impl Expression {
                 _ => ExprType::Numeric(NumericType::Real),
             },
             Expression::BinaryOp { op, left, right } => {
-                let left_type = left.infer_type();
-                let right_type = right.infer_type();
+                let left_type = left.infer_type_with(variable_type);
+                let right_type = right.infer_type_with(variable_type);
                 self.infer_binary_op_type(op, &left_type, &right_type)
             }
             Expression::UnaryOp { op, operand } => {
-                let operand_type = operand.infer_type();
+                let operand_type = operand.infer_type_with(variable_type);
                 self.infer_unary_op_type(op, &operand_type)
             }
             Expression::Function { name, args } => {
-                let arg_types: Vec<ExprType> = args.iter().map(|arg| arg.infer_type()).collect();
+                let arg_types: Vec<ExprType> = args.iter().map(|arg| arg.infer_type_with(variable_type)).collect();
                 self.infer_function_type(name, &arg_types)
             }
             Expression::Matrix(rows) => {
@@ -307,7 +497,7 @@ impl Expression {
                 let mut element_type = ExprType::Unknown;
                 for row in rows {
                     for elem in row {
-                        let elem_type = elem.infer_type();
+                        let elem_type = elem.infer_type_with(variable_type);
                         element_type = self.common_type(&element_type, &elem_type);
                     }
                 }
@@ -321,7 +511,7 @@ impl Expression {
                 
                 let mut element_type = ExprType::Unknown;
                 for elem in elements {
-                    let elem_type = elem.infer_type();
+                    let elem_type = elem.infer_type_with(variable_type);
                     element_type = self.common_type(&element_type, &elem_type);
                 }
                 
@@ -330,15 +520,15 @@ impl Expression {
             Expression::Set(elements) => {
                 let mut element_type = ExprType::Unknown;
                 for elem in elements {
-                    let elem_type = elem.infer_type();
+                    let elem_type = elem.infer_type_with(variable_type);
                     element_type = self.common_type(&element_type, &elem_type);
                 }
                 
                 ExprType::Set(Box::new(element_type))
             }
             Expression::Interval { start, end, .. } => {
-                let start_type = start.infer_type();
-                let end_type = end.infer_type();
+                let start_type = start.infer_type_with(variable_type);
+                let end_type = end.infer_type_with(variable_type);
                 let common_type = self.common_type(&start_type, &end_type);
                 
                 ExprType::Interval(Box::new(common_type))
@@ -446,37 +636,12 @@ impl Expression {
                     _ => operand_type.clone()
                 }
             }
-            UnaryOperator::Sin | UnaryOperator::Cos | UnaryOperator::Tan |
-            UnaryOperator::Asin | UnaryOperator::Acos | UnaryOperator::Atan |
-            UnaryOperator::Sinh | UnaryOperator::Cosh | UnaryOperator::Tanh |
-            UnaryOperator::Asinh | UnaryOperator::Acosh | UnaryOperator::Atanh => {
-                match operand_type {
-                    ExprType::Numeric(NumericType::Complex) => ExprType::Numeric(NumericType::Complex),
-                    ExprType::Numeric(_) => ExprType::Numeric(NumericType::Real),
-                    _ => ExprType::Symbolic
-                }
-            }
-            UnaryOperator::Ln | UnaryOperator::Log10 | UnaryOperator::Log2 => {
-                match operand_type {
-                    ExprType::Numeric(NumericType::Complex) => ExprType::Numeric(NumericType::Complex),
-                    ExprType::Numeric(_) => ExprType::Numeric(NumericType::Real),
-                    _ => ExprType::Symbolic
-                }
-            }
-            UnaryOperator::Exp => {
-                match operand_type {
-                    ExprType::Numeric(NumericType::Complex) => ExprType::Numeric(NumericType::Complex),
-                    ExprType::Numeric(_) => ExprType::Numeric(NumericType::Real),
-                    _ => ExprType::Symbolic
-                }
-            }
             UnaryOperator::Factorial => {
                 match operand_type {
                     ExprType::Numeric(NumericType::Integer) => ExprType::Numeric(NumericType::Integer),
                     _ => ExprType::Symbolic
                 }
             }
-            UnaryOperator::Gamma => ExprType::Numeric(NumericType::Real),
             UnaryOperator::Not => ExprType::Numeric(NumericType::Integer), // 布尔值用整数表示
             UnaryOperator::Real | UnaryOperator::Imaginary => ExprType::Numeric(NumericType::Real),
             UnaryOperator::Conjugate => operand_type.clone(),
@@ -508,6 +673,9 @@ impl Expression {
                     _ => ExprType::Unknown
                 }
             }
+            // 函数型运算符与同名函数调用的类型一致
+            _ => op.function_name()
+                .map_or(ExprType::Unknown, |name| self.infer_function_type(name, std::slice::from_ref(operand_type))),
         }
     }
     
@@ -516,7 +684,8 @@ impl Expression {
         match name {
             "sin" | "cos" | "tan" | "asin" | "acos" | "atan" |
             "sinh" | "cosh" | "tanh" | "asinh" | "acosh" | "atanh" |
-            "ln" | "log" | "exp" | "sqrt" | "abs" => {
+            "ln" | "log" | "log10" | "log2" | "exp" | "sqrt" | "cbrt" | "abs" |
+            "erf" | "erfc" | "Si" | "Ci" | "Ei" | "li" | "sinc" => {
                 if arg_types.len() == 1 {
                     match &arg_types[0] {
                         ExprType::Numeric(NumericType::Complex) => ExprType::Numeric(NumericType::Complex),
@@ -527,6 +696,7 @@ impl Expression {
                     ExprType::Unknown
                 }
             }
+            "gamma" => ExprType::Numeric(NumericType::Real),
             "max" | "min" => {
                 if arg_types.len() >= 2 {
                     let mut result_type = arg_types[0].clone();
@@ -570,26 +740,26 @@ impl Expression {
                         match (&left_type, &right_type) {
                             (ExprType::Matrix(_, n, _), ExprType::Matrix(p, _, _)) => {
                                 if n != p {
-                                    return Err(format!("矩阵乘法维度不匹配：{}×? 和 {}×?", n, p));
+                                    return Err(tr!("expression.matmul_mismatch", left = n, right = p));
                                 }
                             }
-                            _ => return Err("矩阵乘法只能应用于矩阵".to_string())
+                            _ => return Err(tr!("expression.matmul_requires_matrices").to_string())
                         }
                     }
                     BinaryOperator::DotProduct => {
                         match (&left_type, &right_type) {
                             (ExprType::Vector(m, _), ExprType::Vector(n, _)) => {
                                 if m != n {
-                                    return Err(format!("向量点积维度不匹配：{} 和 {}", m, n));
+                                    return Err(tr!("expression.dot_mismatch", left = m, right = n));
                                 }
                             }
-                            _ => return Err("点积只能应用于向量".to_string())
+                            _ => return Err(tr!("expression.dot_requires_vectors").to_string())
                         }
                     }
                     BinaryOperator::CrossProduct => {
                         match (&left_type, &right_type) {
                             (ExprType::Vector(3, _), ExprType::Vector(3, _)) => {}
-                            _ => return Err("叉积只能应用于3维向量".to_string())
+                            _ => return Err(tr!("expression.cross_requires_3d").to_string())
                         }
                     }
                     _ => {} // 其他运算符暂不做特殊验证
@@ -607,16 +777,16 @@ impl Expression {
                         match &operand_type {
                             ExprType::Matrix(m, n, _) => {
                                 if m != n {
-                                    return Err(format!("{}只能应用于方阵", op.name()));
+                                    return Err(tr!("expression.requires_square_matrix", operation = op.name()));
                                 }
                             }
-                            _ => return Err(format!("{}只能应用于矩阵", op.name()))
+                            _ => return Err(tr!("expression.requires_matrix", operation = op.name()))
                         }
                     }
                     UnaryOperator::Factorial => {
                         match &operand_type {
                             ExprType::Numeric(NumericType::Integer) | ExprType::Symbolic => {}
-                            _ => return Err("阶乘只能应用于整数".to_string())
+                            _ => return Err(tr!("expression.factorial_requires_integer").to_string())
                         }
                     }
                     _ => {} // 其他运算符暂不做特殊验证
@@ -632,17 +802,17 @@ impl Expression {
             }
             Expression::Matrix(rows) => {
                 if rows.is_empty() {
-                    return Err("矩阵不能为空".to_string());
+                    return Err(tr!("expression.empty_matrix").to_string());
                 }
                 
                 let cols = rows[0].len();
                 if cols == 0 {
-                    return Err("矩阵行不能为空".to_string());
+                    return Err(tr!("expression.empty_matrix_row").to_string());
                 }
                 
                 for (i, row) in rows.iter().enumerate() {
                     if row.len() != cols {
-                        return Err(format!("矩阵第{}行的列数({})与第一行的列数({})不匹配", i + 1, row.len(), cols));
+                        return Err(tr!("expression.ragged_matrix", row = i + 1, len = row.len(), cols = cols));
                     }
                     
                     for elem in row {
@@ -654,7 +824,7 @@ impl Expression {
             }
             Expression::Vector(elements) => {
                 if elements.is_empty() {
-                    return Err("向量不能为空".to_string());
+                    return Err(tr!("expression.empty_vector").to_string());
                 }
                 
                 for elem in elements {
@@ -704,7 +874,9 @@ impl Expression {
         }
     }
     
-    /// 使用变量值替换表达式中的变量
+    /// 使用变量值替换表达式中的自由变量
+    ///
+    /// `sum(f, k, a, b)` 这类函数中的约束变量 `k` 不被替换；代入的值含有 `k` 时先把 `k` 换成新名字，避免变量捕获。
     pub fn substitute(&self, variables: &HashMap<String, Expression>) -> Expression {
         match self {
             Expression::Variable(name) => {
@@ -723,12 +895,16 @@ impl Expression {
                     operand: Box::new(operand.substitute(variables)),
                 }
             }
-            Expression::Function { name, args } => {
-                Expression::Function {
+            Expression::Function { name, args } => match bound_variable(name, args) {
+                Some((bound, position, scope)) => Expression::Function {
+                    name: name.clone(),
+                    args: substitute_bound(args, bound, position, scope, variables),
+                },
+                None => Expression::Function {
                     name: name.clone(),
                     args: args.iter().map(|arg| arg.substitute(variables)).collect(),
-                }
-            }
+                },
+            },
             Expression::Matrix(rows) => {
                 Expression::Matrix(
                     rows.iter()
@@ -759,6 +935,52 @@ impl Expression {
         }
     }
     
+    /// 把变量 `from` 重命名为 `to`
+    ///
+    /// `to` 已经出现在表达式中时，重命名会把两个不同的变量合并成一个，因此返回错误；
+    /// `from` 不在表达式中时原样返回。
+    pub fn rename_var(&self, from: &str, to: &str) -> Result<Expression, String> {
+        if from == to {
+            return Ok(self.clone());
+        }
+        if to.is_empty() {
+            return Err(tr!("expression.empty_variable_name").to_string());
+        }
+        if MathConstant::from_str(to).is_some() {
+            return Err(tr!("expression.variable_is_constant", name = to));
+        }
+        
+        let variables = self.get_variables();
+        if !variables.iter().any(|v| v == from) {
+            return Ok(self.clone());
+        }
+        if variables.iter().any(|v| v == to) {
+            return Err(tr!("expression.rename_conflict", from = from, to = to));
+        }
+        
+        let mut mapping = HashMap::new();
+        mapping.insert(from.to_string(), Expression::variable(to));
+        Ok(self.substitute(&mapping))
+    }
+    
+    /// 把变量 `from` 重命名为 `to`；`to` 已被占用时改用 [`fresh_variable`] 以 `to` 为前缀生成的新名字
+    ///
+    /// 返回重命名后的表达式和实际使用的名字。
+    pub fn rename_variable(&self, from: &str, to: &str) -> (Expression, String) {
+        let name = if from == to || !self.get_variables().iter().any(|v| v == to) {
+            to.to_string()
+        } else {
+            fresh_variable(to, self)
+        };
+        match self.rename_var(from, &name) {
+            Ok(renamed) => (renamed, name),
+            Err(_) => {
+                let name = fresh_variable(to, self);
+                (self.rename_var(from, &name).unwrap_or_else(|_| self.clone()), name)
+            }
+        }
+    }
+    
     /// 使用数值替换表达式中的变量
     pub fn substitute_numbers(&self, variables: &HashMap<String, Number>) -> Expression {
         let expr_vars: HashMap<String, Expression> = variables
@@ -783,7 +1005,7 @@ impl Expression {
             Expression::Number(n) => Ok(n.clone()),
             
             Expression::Variable(name) => {
-                Err(format!("未定义的变量: {}", name))
+                Err(tr!("expression.undefined_variable", name = name))
             }
             
             Expression::Constant(c) => {
@@ -908,27 +1130,25 @@ impl Expression {
             MathConstant::Catalan => {
                 Number::Symbolic(Box::new(Expression::Constant(MathConstant::Catalan)))
             }
-            MathConstant::PositiveInfinity => {
-                Number::Symbolic(Box::new(Expression::Constant(MathConstant::PositiveInfinity)))
-            }
-            MathConstant::NegativeInfinity => {
-                Number::Symbolic(Box::new(Expression::Constant(MathConstant::NegativeInfinity)))
-            }
-            MathConstant::Undefined => {
-                Number::Symbolic(Box::new(Expression::Constant(MathConstant::Undefined)))
+            // 无穷与未定义值按扩展实数参与运算
+            MathConstant::PositiveInfinity | MathConstant::NegativeInfinity | MathConstant::Undefined => {
+                Number::Constant(constant.clone())
             }
         }
     }
     
     /// 求值二元运算
     fn evaluate_binary_op(&self, op: &BinaryOperator, left: &Number, right: &Number) -> Result<Number, String> {
+        if let Some(value) = IndeterminateSemantics::default().resolve_numbers(op, left, right) {
+            return Ok(value);
+        }
         match op {
             BinaryOperator::Add => Ok(left.clone() + right.clone()),
             BinaryOperator::Subtract => Ok(left.clone() - right.clone()),
             BinaryOperator::Multiply => Ok(left.clone() * right.clone()),
             BinaryOperator::Divide => {
                 if right.is_zero() {
-                    Err("除零错误".to_string())
+                    Err(tr!("compute.division_by_zero").to_string())
                 } else {
                     Ok(left.clone() / right.clone())
                 }
@@ -976,6 +1196,13 @@ impl Expression {
     
     /// 求值一元运算
     fn evaluate_unary_op(&self, op: &UnaryOperator, operand: &Number) -> Result<Number, String> {
+        if Magnitude::of_number(operand) == Magnitude::Undefined {
+            return Ok(Number::Constant(MathConstant::Undefined));
+        }
+        // 函数型运算符与同名函数调用的求值一致
+        if let Some(name) = op.function_name() {
+            return self.evaluate_function(name, std::slice::from_ref(operand));
+        }
         match op {
             UnaryOperator::Negate => Ok(-operand.clone()),
             UnaryOperator::Plus => Ok(operand.clone()),
@@ -986,18 +1213,6 @@ impl Expression {
             UnaryOperator::Factorial => {
                 self.evaluate_factorial(operand)
             }
-            // 对于三角函数和其他复杂函数，返回符号表示以保持精确性
-            UnaryOperator::Sin | UnaryOperator::Cos | UnaryOperator::Tan |
-            UnaryOperator::Asin | UnaryOperator::Acos | UnaryOperator::Atan |
-            UnaryOperator::Sinh | UnaryOperator::Cosh | UnaryOperator::Tanh |
-            UnaryOperator::Asinh | UnaryOperator::Acosh | UnaryOperator::Atanh |
-            UnaryOperator::Ln | UnaryOperator::Log10 | UnaryOperator::Log2 |
-            UnaryOperator::Exp | UnaryOperator::Gamma => {
-                Ok(Number::Symbolic(Box::new(Expression::UnaryOp {
-                    op: op.clone(),
-                    operand: Box::new(Expression::Number(operand.clone())),
-                })))
-            }
             UnaryOperator::Real => {
                 match operand {
                     Number::Complex { real, .. } => Ok(*real.clone()),
@@ -1044,7 +1259,7 @@ impl Expression {
             if exponent.is_positive() {
                 return Ok(Number::zero());
             } else {
-                return Err("0的负数次幂未定义".to_string());
+                return Err(tr!("expression.zero_negative_power").to_string());
             }
         }
         if base.is_one() {
@@ -1072,12 +1287,13 @@ impl Expression {
     /// 求值取模运算
     fn evaluate_modulo(&self, left: &Number, right: &Number) -> Result<Number, String> {
         if right.is_zero() {
-            return Err("模零错误".to_string());
+            return Err(tr!("expression.modulo_by_zero").to_string());
         }
         
         match (left.to_integer(), right.to_integer()) {
             (Some(a), Some(b)) => {
-                Ok(Number::Integer(a % b))
+                // 采用余数非负的数学约定：(-7) % 3 = 2
+                Ok(Number::Integer(num_traits::Euclid::rem_euclid(&a, &b)))
             }
             _ => {
                 // 非整数的取模运算返回符号表示
@@ -1149,7 +1365,7 @@ impl Expression {
     fn evaluate_factorial(&self, operand: &Number) -> Result<Number, String> {
         if let Some(n) = operand.to_integer() {
             if n < num_bigint::BigInt::from(0) {
-                return Err("负数的阶乘未定义".to_string());
+                return Err(tr!("expression.negative_factorial").to_string());
             }
             
             if n > num_bigint::BigInt::from(1000) {
@@ -1169,44 +1385,25 @@ impl Expression {
             
             Ok(Number::Integer(result))
         } else {
-            Err("阶乘只能应用于非负整数".to_string())
+            Err(tr!("expression.factorial_requires_natural").to_string())
         }
     }
     
     /// 求值函数调用（使用表达式参数）
     fn evaluate_function_with_expressions(&self, name: &str, args: &[Expression]) -> Result<Number, String> {
+        if args.iter().any(|arg| Magnitude::of_expression(arg) == Magnitude::Undefined) {
+            return Ok(Number::Constant(MathConstant::Undefined));
+        }
+        signatures::check_arity(name, args.len())?;
         match name {
             // 三角函数
-            "sin" => {
-                if args.len() != 1 {
-                    return Err("sin函数需要一个参数".to_string());
-                }
-                self.evaluate_trigonometric_function_with_expr("sin", &args[0])
-            }
-            "cos" => {
-                if args.len() != 1 {
-                    return Err("cos函数需要一个参数".to_string());
-                }
-                self.evaluate_trigonometric_function_with_expr("cos", &args[0])
-            }
-            "tan" => {
-                if args.len() != 1 {
-                    return Err("tan函数需要一个参数".to_string());
-                }
-                self.evaluate_trigonometric_function_with_expr("tan", &args[0])
-            }
+            "sin" => self.evaluate_trigonometric_function_with_expr("sin", &args[0]),
+            "cos" => self.evaluate_trigonometric_function_with_expr("cos", &args[0]),
+            "tan" => self.evaluate_trigonometric_function_with_expr("tan", &args[0]),
             
             // 指数和对数函数
-            "exp" => {
-                if args.len() != 1 {
-                    return Err("exp函数需要一个参数".to_string());
-                }
-                self.evaluate_exponential_function_with_expr(&args[0])
-            }
-            "ln" | "log" => {
-                if args.len() != 1 {
-                    return Err("ln函数需要一个参数".to_string());
-                }
+            "exp" => self.evaluate_exponential_function_with_expr(&args[0]),
+            "ln" | "log" if args.len() == 1 => {
                 let arg_val = args[0].evaluate_exact()?;
                 self.evaluate_logarithm_function(&arg_val)
             }
@@ -1310,112 +1507,38 @@ impl Expression {
 
     /// 求值函数调用
     fn evaluate_function(&self, name: &str, args: &[Number]) -> Result<Number, String> {
+        if args.iter().any(|arg| Magnitude::of_number(arg) == Magnitude::Undefined) {
+            return Ok(Number::Constant(MathConstant::Undefined));
+        }
+        signatures::check_arity(name, args.len())?;
         match name {
             // 三角函数
-            "sin" => {
-                if args.len() != 1 {
-                    return Err("sin函数需要一个参数".to_string());
-                }
-                self.evaluate_trigonometric_function("sin", &args[0])
-            }
-            "cos" => {
-                if args.len() != 1 {
-                    return Err("cos函数需要一个参数".to_string());
-                }
-                self.evaluate_trigonometric_function("cos", &args[0])
-            }
-            "tan" => {
-                if args.len() != 1 {
-                    return Err("tan函数需要一个参数".to_string());
-                }
-                self.evaluate_trigonometric_function("tan", &args[0])
-            }
+            "sin" => self.evaluate_trigonometric_function("sin", &args[0]),
+            "cos" => self.evaluate_trigonometric_function("cos", &args[0]),
+            "tan" => self.evaluate_trigonometric_function("tan", &args[0]),
             
             // 反三角函数
-            "asin" | "arcsin" => {
-                if args.len() != 1 {
-                    return Err("asin函数需要一个参数".to_string());
-                }
-                self.evaluate_inverse_trigonometric_function("asin", &args[0])
-            }
-            "acos" | "arccos" => {
-                if args.len() != 1 {
-                    return Err("acos函数需要一个参数".to_string());
-                }
-                self.evaluate_inverse_trigonometric_function("acos", &args[0])
-            }
-            "atan" | "arctan" => {
-                if args.len() != 1 {
-                    return Err("atan函数需要一个参数".to_string());
-                }
-                self.evaluate_inverse_trigonometric_function("atan", &args[0])
-            }
+            "asin" | "arcsin" => self.evaluate_inverse_trigonometric_function("asin", &args[0]),
+            "acos" | "arccos" => self.evaluate_inverse_trigonometric_function("acos", &args[0]),
+            "atan" | "arctan" => self.evaluate_inverse_trigonometric_function("atan", &args[0]),
             
             // 指数和对数函数
-            "exp" => {
-                if args.len() != 1 {
-                    return Err("exp函数需要一个参数".to_string());
-                }
-                self.evaluate_exponential_function(&args[0])
-            }
-            "ln" | "log" => {
-                if args.len() != 1 {
-                    return Err("ln函数需要一个参数".to_string());
-                }
-                self.evaluate_logarithm_function(&args[0])
-            }
-            "log10" => {
-                if args.len() != 1 {
-                    return Err("log10函数需要一个参数".to_string());
-                }
-                self.evaluate_log10_function(&args[0])
-            }
-            "log2" => {
-                if args.len() != 1 {
-                    return Err("log2函数需要一个参数".to_string());
-                }
-                self.evaluate_log2_function(&args[0])
-            }
+            "exp" => self.evaluate_exponential_function(&args[0]),
+            "ln" | "log" if args.len() == 1 => self.evaluate_logarithm_function(&args[0]),
+            "log10" => self.evaluate_log10_function(&args[0]),
+            "log2" => self.evaluate_log2_function(&args[0]),
             
             // 幂函数和根函数
-            "sqrt" => {
-                if args.len() != 1 {
-                    return Err("sqrt函数需要一个参数".to_string());
-                }
-                self.evaluate_sqrt_function(&args[0])
-            }
-            "pow" => {
-                if args.len() != 2 {
-                    return Err("pow函数需要两个参数".to_string());
-                }
-                self.evaluate_power_function(&args[0], &args[1])
-            }
+            "sqrt" => self.evaluate_sqrt_function(&args[0]),
+            "pow" => self.evaluate_power_function(&args[0], &args[1]),
             
             // 双曲函数
-            "sinh" => {
-                if args.len() != 1 {
-                    return Err("sinh函数需要一个参数".to_string());
-                }
-                self.evaluate_hyperbolic_function("sinh", &args[0])
-            }
-            "cosh" => {
-                if args.len() != 1 {
-                    return Err("cosh函数需要一个参数".to_string());
-                }
-                self.evaluate_hyperbolic_function("cosh", &args[0])
-            }
-            "tanh" => {
-                if args.len() != 1 {
-                    return Err("tanh函数需要一个参数".to_string());
-                }
-                self.evaluate_hyperbolic_function("tanh", &args[0])
-            }
+            "sinh" => self.evaluate_hyperbolic_function("sinh", &args[0]),
+            "cosh" => self.evaluate_hyperbolic_function("cosh", &args[0]),
+            "tanh" => self.evaluate_hyperbolic_function("tanh", &args[0]),
             
             // 统计函数
             "max" => {
-                if args.is_empty() {
-                    return Err("max函数需要至少一个参数".to_string());
-                }
                 let mut max_val = &args[0];
                 for arg in &args[1..] {
                     if arg.approximate() > max_val.approximate() {
@@ -1425,9 +1548,6 @@ impl Expression {
                 Ok(max_val.clone())
             }
             "min" => {
-                if args.is_empty() {
-                    return Err("min函数需要至少一个参数".to_string());
-                }
                 let mut min_val = &args[0];
                 for arg in &args[1..] {
                     if arg.approximate() < min_val.approximate() {
@@ -1436,12 +1556,7 @@ impl Expression {
                 }
                 Ok(min_val.clone())
             }
-            "abs" => {
-                if args.len() != 1 {
-                    return Err("abs函数需要一个参数".to_string());
-                }
-                args[0].abs().map_err(|e| format!("{}", e))
-            }
+            "abs" => args[0].abs().map_err(|e| format!("{}", e)),
             
             // 对于其他函数，返回符号表示
             _ => {
@@ -1539,17 +1654,7 @@ impl Expression {
         }
         
         // 对于一般情况，返回符号表示
-        let unary_op = match name {
-            "sin" => UnaryOperator::Sin,
-            "cos" => UnaryOperator::Cos,
-            "tan" => UnaryOperator::Tan,
-            _ => return Err(format!("未知的三角函数: {}", name)),
-        };
-        
-        Ok(Number::Symbolic(Box::new(Expression::UnaryOp {
-            op: unary_op,
-            operand: Box::new(Expression::Number(arg.clone())),
-        })))
+        Ok(Number::Symbolic(Box::new(Expression::function(name, vec![Expression::Number(arg.clone())]))))
     }
     
     /// 求值反三角函数
@@ -1587,17 +1692,7 @@ impl Expression {
         }
         
         // 对于一般情况，返回符号表示
-        let unary_op = match name {
-            "asin" => UnaryOperator::Asin,
-            "acos" => UnaryOperator::Acos,
-            "atan" => UnaryOperator::Atan,
-            _ => return Err(format!("未知的反三角函数: {}", name)),
-        };
-        
-        Ok(Number::Symbolic(Box::new(Expression::UnaryOp {
-            op: unary_op,
-            operand: Box::new(Expression::Number(arg.clone())),
-        })))
+        Ok(Number::Symbolic(Box::new(Expression::function(name, vec![Expression::Number(arg.clone())]))))
     }
     
     /// 求值指数函数
@@ -1657,10 +1752,7 @@ impl Expression {
         }
         
         // 对于一般情况，返回符号表示
-        Ok(Number::Symbolic(Box::new(Expression::UnaryOp {
-            op: UnaryOperator::Exp,
-            operand: Box::new(Expression::Number(arg.clone())),
-        })))
+        Ok(Number::Symbolic(Box::new(Expression::function("exp", vec![Expression::Number(arg.clone())]))))
     }
     
     /// 求值对数函数
@@ -1676,7 +1768,7 @@ impl Expression {
                 return Ok(Number::Integer(num_bigint::BigInt::from(1)));
             }
             Number::Integer(n) if n <= &num_bigint::BigInt::from(0) => {
-                return Err("对数函数的参数必须为正数".to_string());
+                return Err(tr!("expression.log_requires_positive").to_string());
             }
             // 检查符号表达式中的常量
             Number::Symbolic(expr) => {
@@ -1688,10 +1780,7 @@ impl Expression {
         }
         
         // 对于一般情况，返回符号表示
-        Ok(Number::Symbolic(Box::new(Expression::UnaryOp {
-            op: UnaryOperator::Ln,
-            operand: Box::new(Expression::Number(arg.clone())),
-        })))
+        Ok(Number::Symbolic(Box::new(Expression::function("ln", vec![Expression::Number(arg.clone())]))))
     }
     
     /// 求值常用对数函数
@@ -1705,16 +1794,13 @@ impl Expression {
                 return Ok(Number::Integer(num_bigint::BigInt::from(1)));
             }
             Number::Integer(n) if n <= &num_bigint::BigInt::from(0) => {
-                return Err("对数函数的参数必须为正数".to_string());
+                return Err(tr!("expression.log_requires_positive").to_string());
             }
             _ => {}
         }
         
         // 对于一般情况，返回符号表示
-        Ok(Number::Symbolic(Box::new(Expression::UnaryOp {
-            op: UnaryOperator::Log10,
-            operand: Box::new(Expression::Number(arg.clone())),
-        })))
+        Ok(Number::Symbolic(Box::new(Expression::function("log10", vec![Expression::Number(arg.clone())]))))
     }
     
     /// 求值二进制对数函数
@@ -1728,16 +1814,13 @@ impl Expression {
                 return Ok(Number::Integer(num_bigint::BigInt::from(1)));
             }
             Number::Integer(n) if n <= &num_bigint::BigInt::from(0) => {
-                return Err("对数函数的参数必须为正数".to_string());
+                return Err(tr!("expression.log_requires_positive").to_string());
             }
             _ => {}
         }
         
         // 对于一般情况，返回符号表示
-        Ok(Number::Symbolic(Box::new(Expression::UnaryOp {
-            op: UnaryOperator::Log2,
-            operand: Box::new(Expression::Number(arg.clone())),
-        })))
+        Ok(Number::Symbolic(Box::new(Expression::function("log2", vec![Expression::Number(arg.clone())]))))
     }
     
     /// 求值平方根函数
@@ -1838,17 +1921,7 @@ impl Expression {
         }
         
         // 对于一般情况，返回符号表示
-        let unary_op = match name {
-            "sinh" => UnaryOperator::Sinh,
-            "cosh" => UnaryOperator::Cosh,
-            "tanh" => UnaryOperator::Tanh,
-            _ => return Err(format!("未知的双曲函数: {}", name)),
-        };
-        
-        Ok(Number::Symbolic(Box::new(Expression::UnaryOp {
-            op: unary_op,
-            operand: Box::new(Expression::Number(arg.clone())),
-        })))
+        Ok(Number::Symbolic(Box::new(Expression::function(name, vec![Expression::Number(arg.clone())]))))
     }
     
     /// 求值三角函数的常量倍数
@@ -1978,113 +2051,161 @@ impl Expression {
     }
 }
 
-impl Display for Expression {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        match self {
-            Expression::Number(n) => write!(f, "{}", n),
-            Expression::Variable(name) => write!(f, "{}", name),
-            Expression::Constant(c) => write!(f, "{}", c.symbol()),
-            Expression::BinaryOp { op, left, right } => {
-                // 根据运算符优先级决定是否需要括号
-                let needs_left_parens = match left.as_ref() {
-                    Expression::BinaryOp { op: left_op, .. } => {
-                        left_op.precedence() < op.precedence() ||
-                        (left_op.precedence() == op.precedence() && op.is_right_associative())
-                    }
-                    _ => false,
-                };
-                
-                let needs_right_parens = match right.as_ref() {
-                    Expression::BinaryOp { op: right_op, .. } => {
-                        right_op.precedence() < op.precedence() ||
-                        (right_op.precedence() == op.precedence() && !op.is_right_associative())
-                    }
-                    _ => false,
-                };
-                
-                if needs_left_parens {
-                    write!(f, "({})", left)?;
-                } else {
-                    write!(f, "{}", left)?;
-                }
-                
-                write!(f, " {} ", op.symbol())?;
-                
-                if needs_right_parens {
-                    write!(f, "({})", right)?;
-                } else {
-                    write!(f, "{}", right)?;
-                }
-                
-                Ok(())
-            }
-            Expression::UnaryOp { op, operand } => {
-                match op {
-                    UnaryOperator::Negate => write!(f, "-{}", operand),
-                    UnaryOperator::Plus => write!(f, "+{}", operand),
-                    UnaryOperator::Factorial => write!(f, "{}!", operand),
-                    UnaryOperator::Transpose => write!(f, "{}^T", operand),
-                    UnaryOperator::Conjugate => write!(f, "{}*", operand),
-                    _ => {
-                        // 函数形式的一元运算符
-                        write!(f, "{}({})", op.symbol(), operand)
-                    }
-                }
-            }
-            Expression::Function { name, args } => {
-                write!(f, "{}(", name)?;
-                for (i, arg) in args.iter().enumerate() {
-                    if i > 0 {
-                        write!(f, ", ")?;
-                    }
-                    write!(f, "{}", arg)?;
-                }
-                write!(f, ")")
-            }
-            Expression::Matrix(rows) => {
-                write!(f, "[")?;
-                for (i, row) in rows.iter().enumerate() {
-                    if i > 0 {
-                        write!(f, ", ")?;
-                    }
-                    write!(f, "[")?;
-                    for (j, elem) in row.iter().enumerate() {
-                        if j > 0 {
-                            write!(f, ", ")?;
+/// 以 `prefix` 为前缀、在 `expr` 中未出现过的变量名：`prefix` 本身可用时直接返回，否则依次尝试 `prefix1`、`prefix2`……
+pub fn fresh_variable(prefix: &str, expr: &Expression) -> String {
+    let taken = expr.get_variables();
+    fresh_name(prefix, |name| taken.iter().any(|v| v == name))
+}
+
+/// 以 `prefix` 为前缀、不被 `taken` 占用也不与数学常量同名的变量名
+fn fresh_name(prefix: &str, taken: impl Fn(&str) -> bool) -> String {
+    let prefix = if prefix.is_empty() { "t" } else { prefix };
+    let usable = |name: &str| !taken(name) && MathConstant::from_str(name).is_none();
+    if usable(prefix) {
+        return prefix.to_string();
+    }
+    (1..).map(|i| format!("{}{}", prefix, i)).find(|name| usable(name)).unwrap()
+}
+
+/// 函数调用是否约束了变量，如 `sum(f, k, a, b)` 中的 `k`
+pub(crate) fn binds_variable(name: &str, args: &[Expression]) -> bool {
+    bound_variable(name, args).is_some()
+}
+
+/// 函数调用中的约束变量：(变量名, 所在参数位置, 起作用的参数位置)
+fn bound_variable<'a>(name: &str, args: &'a [Expression]) -> Option<(&'a str, usize, &'static [usize])> {
+    let (_, position, scope, _) = BINDERS.iter().find(|(binder, _, _, min_args)| *binder == name && args.len() >= *min_args)?;
+    match &args[*position] {
+        Expression::Variable(bound) => Some((bound.as_str(), *position, *scope)),
+        _ => None,
+    }
+}
+
+/// 代换约束变量函数的参数：作用域内不替换约束变量，代入值含有约束变量时先把它换成新名字
+fn substitute_bound(
+    args: &[Expression],
+    bound: &str,
+    position: usize,
+    scope: &[usize],
+    variables: &HashMap<String, Expression>,
+) -> Vec<Expression> {
+    let mut inner = variables.clone();
+    inner.remove(bound);
+    let scoped: Vec<&Expression> = scope.iter().map(|&i| &args[i]).collect();
+    let captured = inner.iter().any(|(name, value)| {
+        scoped.iter().any(|arg| arg.get_variables().contains(name)) && value.get_variables().iter().any(|v| v == bound)
+    });
+
+    let mut bound = bound.to_string();
+    let mut scoped: Vec<Expression> = scoped.into_iter().cloned().collect();
+    if captured {
+        let fresh = fresh_name(&bound, |name| {
+            scoped.iter().any(|arg| arg.get_variables().iter().any(|v| v == name))
+                || inner.values().any(|value| value.get_variables().iter().any(|v| v == name))
+        });
+        let renaming = HashMap::from([(bound.clone(), Expression::variable(fresh.clone()))]);
+        scoped = scoped.iter().map(|arg| arg.substitute(&renaming)).collect();
+        bound = fresh;
+    }
+
+    args.iter().enumerate().map(|(i, arg)| {
+        if i == position {
+            Expression::variable(bound.clone())
+        } else if let Some(k) = scope.iter().position(|&j| j == i) {
+            scoped[k].substitute(&inner)
+        } else {
+            arg.substitute(variables)
+        }
+    }).collect()
+}
+
+/// 判断两个表达式在变量的一致重命名下是否同构，如 `x^2 + x` 与 `y^2 + y`
+///
+/// 自由变量之间须一一对应；`sum(k^2, k, 1, n)` 这类函数中的约束变量只在各自的作用域内对应。
+pub fn alpha_equivalent(a: &Expression, b: &Expression) -> bool {
+    AlphaMatcher::default().matches(a, b)
+}
+
+/// alpha 等价判断的状态：自由变量的双向对应和当前作用域内的约束变量对
+#[derive(Default)]
+struct AlphaMatcher {
+    forward: HashMap<String, String>,
+    backward: HashMap<String, String>,
+    bound: Vec<(String, String)>,
+}
+
+impl AlphaMatcher {
+    fn matches(&mut self, a: &Expression, b: &Expression) -> bool {
+        match (a, b) {
+            (Expression::Variable(x), Expression::Variable(y)) => self.variables(x, y),
+            (Expression::BinaryOp { op, left, right }, Expression::BinaryOp { op: other_op, left: other_left, right: other_right }) => {
+                op == other_op && self.matches(left, other_left) && self.matches(right, other_right)
+            }
+            (Expression::UnaryOp { op, operand }, Expression::UnaryOp { op: other_op, operand: other_operand }) => {
+                op == other_op && self.matches(operand, other_operand)
+            }
+            (Expression::Function { name, args }, Expression::Function { name: other_name, args: other_args }) => {
+                if name != other_name || args.len() != other_args.len() {
+                    return false;
+                }
+                match (bound_variable(name, args), bound_variable(other_name, other_args)) {
+                    (Some((x, position, scope)), Some((y, _, _))) => {
+                        let outside = (0..args.len()).filter(|i| *i != position && !scope.contains(i));
+                        if !outside.into_iter().all(|i| self.matches(&args[i], &other_args[i])) {
+                            return false;
                         }
-                        write!(f, "{}", elem)?;
+                        self.bound.push((x.to_string(), y.to_string()));
+                        let inside = scope.iter().all(|&i| self.matches(&args[i], &other_args[i]));
+                        self.bound.pop();
+                        inside
                     }
-                    write!(f, "]")?;
+                    _ => self.all(args, other_args),
                 }
-                write!(f, "]")
             }
-            Expression::Vector(elements) => {
-                write!(f, "[")?;
-                for (i, elem) in elements.iter().enumerate() {
-                    if i > 0 {
-                        write!(f, ", ")?;
-                    }
-                    write!(f, "{}", elem)?;
-                }
-                write!(f, "]")
+            (Expression::Matrix(rows), Expression::Matrix(other_rows)) => {
+                rows.len() == other_rows.len() && rows.iter().zip(other_rows).all(|(row, other_row)| self.all(row, other_row))
             }
-            Expression::Set(elements) => {
-                write!(f, "{{")?;
-                for (i, elem) in elements.iter().enumerate() {
-                    if i > 0 {
-                        write!(f, ", ")?;
-                    }
-                    write!(f, "{}", elem)?;
-                }
-                write!(f, "}}")
-            }
-            Expression::Interval { start, end, start_inclusive, end_inclusive } => {
-                let left_bracket = if *start_inclusive { "[" } else { "(" };
-                let right_bracket = if *end_inclusive { "]" } else { ")" };
-                write!(f, "{}{}, {}{}", left_bracket, start, end, right_bracket)
+            (Expression::Vector(elements), Expression::Vector(other_elements))
+            | (Expression::Set(elements), Expression::Set(other_elements)) => self.all(elements, other_elements),
+            (
+                Expression::Interval { start, end, start_inclusive, end_inclusive },
+                Expression::Interval { start: other_start, end: other_end, start_inclusive: other_start_inclusive, end_inclusive: other_end_inclusive },
+            ) => {
+                start_inclusive == other_start_inclusive && end_inclusive == other_end_inclusive
+                    && self.matches(start, other_start) && self.matches(end, other_end)
             }
+            _ => a == b,
         }
     }
+
+    fn all(&mut self, a: &[Expression], b: &[Expression]) -> bool {
+        a.len() == b.len() && a.iter().zip(b).all(|(x, y)| self.matches(x, y))
+    }
+
+    /// 约束变量须绑定在同一层作用域；自由变量须保持一一对应
+    fn variables(&mut self, x: &str, y: &str) -> bool {
+        let left = self.bound.iter().rposition(|(a, _)| a == x);
+        let right = self.bound.iter().rposition(|(_, b)| b == y);
+        if left.is_some() || right.is_some() {
+            return left == right;
+        }
+        match (self.forward.get(x), self.backward.get(y)) {
+            (None, None) => {
+                self.forward.insert(x.to_string(), y.to_string());
+                self.backward.insert(y.to_string(), x.to_string());
+                true
+            }
+            (Some(image), Some(preimage)) => image == y && preimage == x,
+            _ => false,
+        }
+    }
+}
+
+/// 委托给 [`StandardFormatter`]，两者输出完全一致
+impl Display for Expression {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&StandardFormatter::new().format(self))
+    }
 }
 
 // 手动实现 Eq trait
@@ -129,6 +129,10 @@ impl ExpressionBuilder {
         if let Some(simplified) = self.try_simplify_unary_op(&op, &operand) {
             return simplified;
         }
+        // 函数型运算符与 Expression::unary_op 一样规范化为函数调用
+        if let Some(name) = op.function_name() {
+            return self.function(name, vec![operand]);
+        }
         
         let expr = Expression::UnaryOp {
             op,
@@ -3,7 +3,7 @@
 //! 测试表达式的构造、验证和类型推断功能。
 
 use super::*;
-use crate::core::{Number, MathConstant, BinaryOperator, UnaryOperator, ExprType, NumericType};
+use crate::core::{Number, MathConstant, BinaryOperator, UnaryOperator, ExprType, NumericType, CostModel};
 use num_bigint::BigInt;
 
 #[cfg(test)]
@@ -83,14 +83,10 @@ mod expression_construction_tests {
             _ => panic!("期望平方根表达式"),
         }
         
-        // 测试三角函数
+        // 测试三角函数：函数型运算符统一以函数调用表示
         let sin_expr = Expression::sin(x.clone());
-        match sin_expr {
-            Expression::UnaryOp { op: UnaryOperator::Sin, operand } => {
-                assert_eq!(*operand, x);
-            }
-            _ => panic!("期望正弦函数表达式"),
-        }
+        assert_eq!(sin_expr, Expression::function("sin", vec![x.clone()]));
+        assert_eq!(Expression::unary_op(UnaryOperator::Gamma, x.clone()), Expression::function("gamma", vec![x.clone()]));
     }
 
     #[test]
@@ -248,6 +244,128 @@ mod expression_properties_tests {
         assert_eq!(dup_var.get_variables(), vec!["x"]);
     }
 
+    #[test]
+    fn test_rename_var() {
+        let x = Expression::variable("x");
+        let y = Expression::variable("y");
+        let z = Expression::variable("z");
+        
+        // x + y 中 x -> z
+        let expr = Expression::add(x.clone(), y.clone());
+        assert_eq!(expr.rename_var("x", "z").unwrap(), Expression::add(z.clone(), y.clone()));
+        
+        // 嵌套在函数和幂中的变量同样被替换
+        let nested = Expression::function("sin", vec![Expression::power(x.clone(), Expression::number(Number::integer(2)))]);
+        let renamed = nested.rename_var("x", "t").unwrap();
+        assert_eq!(renamed.get_variables(), vec!["t"]);
+        
+        // 不存在的变量与同名重命名原样返回
+        assert_eq!(expr.rename_var("w", "z").unwrap(), expr);
+        assert_eq!(expr.rename_var("x", "x").unwrap(), expr);
+    }
+
+    #[test]
+    fn test_rename_var_conflict() {
+        let expr = Expression::add(Expression::variable("x"), Expression::variable("y"));
+        
+        // 目标变量已存在时报错，不会把 x 和 y 合并
+        let err = expr.rename_var("x", "y").unwrap_err();
+        assert!(err.contains("已存在"));
+        
+        // 不能与数学常量同名
+        assert!(expr.rename_var("x", "pi").is_err());
+        assert!(expr.rename_var("x", "").is_err());
+    }
+
+    #[test]
+    fn test_fresh_variable_and_rename_variable() {
+        let x = Expression::variable("x");
+        let expr = Expression::add(x.clone(), Expression::variable("x1"));
+        assert_eq!(fresh_variable("x", &expr), "x2");
+        assert_eq!(fresh_variable("t", &expr), "t");
+        assert_eq!(fresh_variable("pi", &expr), "pi1");
+        
+        // 目标名已被占用时自动换成不冲突的名字，x 和 y 不会合并
+        let expr = Expression::add(x.clone(), Expression::variable("y"));
+        let (renamed, name) = expr.rename_variable("x", "y");
+        assert_eq!(name, "y1");
+        assert_eq!(renamed, Expression::add(Expression::variable("y1"), Expression::variable("y")));
+        assert_eq!(expr.rename_variable("x", "z"), (Expression::add(Expression::variable("z"), Expression::variable("y")), "z".to_string()));
+    }
+    
+    #[test]
+    fn test_alpha_equivalent() {
+        let var = |name: &str| Expression::variable(name);
+        let int = |n: i64| Expression::number(Number::integer(n));
+        let square_plus = |v: &str| Expression::add(Expression::power(var(v), int(2)), var(v));
+        assert!(alpha_equivalent(&square_plus("x"), &square_plus("y")));
+        assert!(!alpha_equivalent(&square_plus("x"), &Expression::add(Expression::power(var("x"), int(2)), var("y"))));
+        
+        // 自由变量须一一对应：x + y 与 y + x 同构，x + y 与 y + y 不同构
+        assert!(alpha_equivalent(&Expression::add(var("x"), var("y")), &Expression::add(var("y"), var("x"))));
+        assert!(!alpha_equivalent(&Expression::add(var("x"), var("y")), &Expression::add(var("y"), var("y"))));
+        assert!(!alpha_equivalent(&Expression::add(var("x"), int(1)), &Expression::add(var("x"), int(2))));
+        
+        // 约束变量只在作用域内对应，不占用自由变量的名字
+        let sum = |term: Expression, k: &str| Expression::function("sum", vec![term, var(k), int(1), var("n")]);
+        assert!(alpha_equivalent(&sum(Expression::power(var("k"), int(2)), "k"), &sum(Expression::power(var("j"), int(2)), "j")));
+        assert!(alpha_equivalent(
+            &Expression::add(sum(var("k"), "k"), var("k")),
+            &Expression::add(sum(var("j"), "j"), var("m")),
+        ));
+        assert!(!alpha_equivalent(&sum(Expression::multiply(var("k"), var("n")), "k"), &sum(Expression::multiply(var("n"), var("n")), "n")));
+    }
+    
+    #[test]
+    fn test_substitute_avoids_capture() {
+        let var = |name: &str| Expression::variable(name);
+        let int = |n: i64| Expression::number(Number::integer(n));
+        let sum = |term: Expression, k: &str, upper: Expression| Expression::function("sum", vec![term, var(k), int(1), upper]);
+        
+        // 约束变量不被代换
+        let squares = sum(Expression::power(var("k"), int(2)), "k", int(3));
+        assert_eq!(squares.substitute(&HashMap::from([("k".to_string(), int(5))])), squares);
+        
+        // 代入值含有约束变量 k 时先把 k 换成新名字，而不是得到 sum(k*k, k, 1, n)
+        let expr = sum(Expression::multiply(var("x"), var("k")), "k", var("n"));
+        let result = expr.substitute(&HashMap::from([("x".to_string(), var("k"))]));
+        assert_eq!(result, sum(Expression::multiply(var("k"), var("k1")), "k1", var("n")));
+        assert!(alpha_equivalent(&result, &sum(Expression::multiply(var("k"), var("j")), "j", var("n"))));
+        
+        // 上下限不在作用域内，照常代换
+        let result = expr.substitute(&HashMap::from([("n".to_string(), var("k"))]));
+        assert_eq!(result, sum(Expression::multiply(var("x"), var("k")), "k", var("k")));
+    }
+    
+    #[test]
+    fn test_find_trig_calls() {
+        // 与解析 sin(x) + cos(x) 得到的结构一致
+        let x = Expression::variable("x");
+        let sin_x = Expression::function("sin", vec![x.clone()]);
+        let cos_x = Expression::function("cos", vec![x.clone()]);
+        let expr = Expression::add(sin_x.clone(), cos_x.clone());
+
+        let trig = expr.find(|e| matches!(e, Expression::Function { name, .. } if matches!(name.as_str(), "sin" | "cos" | "tan")));
+        assert_eq!(trig, vec![&sin_x, &cos_x]);
+
+        // 含 x 的所有子树：整个和式、两个函数调用以及两处 x
+        let with_x = expr.find(|e| e.get_variables().iter().any(|v| v == "x"));
+        assert_eq!(with_x.len(), 5);
+        assert!(expr.find(|e| matches!(e, Expression::Number(_))).is_empty());
+    }
+
+    #[test]
+    fn test_contains() {
+        let x = Expression::variable("x");
+        let expr = Expression::add(Expression::sin(x.clone()), Expression::cos(x.clone()));
+
+        assert!(expr.contains(&expr));
+        assert!(expr.contains(&Expression::sin(x.clone())));
+        assert!(expr.contains(&x));
+        assert!(!expr.contains(&Expression::tan(x.clone())));
+        assert!(!expr.contains(&Expression::variable("y")));
+    }
+
     #[test]
     fn test_complexity() {
         // 简单表达式
@@ -281,6 +399,69 @@ mod expression_properties_tests {
         ]).unwrap();
         assert_eq!(matrix.complexity(), 5); // 1 + (1 + 1 + 1 + 1)
     }
+
+    #[test]
+    fn test_weighted_cost() {
+        let model = CostModel::default();
+        let x = || Expression::variable("x");
+        let one = || Expression::number(Number::integer(1));
+
+        // 节点数相同：x + 1 + x + 1 与 sin(x) + x^1 与 [[x, 1]] + x
+        let additions = Expression::add(Expression::add(Expression::add(x(), one()), x()), one());
+        let functions = Expression::add(Expression::function("sin", vec![x()]), Expression::power(x(), one()));
+        let matrix = Expression::add(Expression::matrix(vec![vec![x(), one()]]).unwrap(), x());
+        assert_eq!(additions.complexity(), 7);
+        assert!(functions.complexity() <= additions.complexity());
+        assert!(matrix.complexity() <= additions.complexity());
+
+        assert_eq!(additions.cost(&model), 7);
+        assert!(functions.cost(&model) >= 2 * additions.cost(&model));
+        assert!(matrix.cost(&model) >= 2 * additions.cost(&model));
+
+        // 写成一元运算的函数与函数调用成本相同
+        let sin = Expression::UnaryOp { op: UnaryOperator::Sin, operand: Box::new(x()) };
+        assert_eq!(sin.cost(&model), Expression::function("sin", vec![x()]).cost(&model));
+
+        // 权重可配置，全部为 1 时与节点数一致
+        let uniform = CostModel {
+            multiplication: 1, division: 1, power: 1, matrix_product: 1, function: 1,
+            matrix: 1, matrix_element: 0, ..CostModel::default()
+        };
+        assert_eq!(functions.cost(&uniform), functions.complexity() as u64);
+        assert_eq!(matrix.cost(&uniform), matrix.complexity() as u64);
+
+        let slow = CostModel { unit_micros: 10, ..CostModel::default() };
+        assert_eq!(slow.estimated_duration(&additions), std::time::Duration::from_micros(70));
+    }
+
+    #[test]
+    fn test_canonicalize() {
+        let x = || Expression::variable("x");
+        let y = || Expression::variable("y");
+        let n = |value: i64| Expression::number(Number::integer(value));
+
+        // 交换律与结合律
+        assert_eq!(Expression::add(n(2), n(3)).canonicalize(), Expression::add(n(3), n(2)).canonicalize());
+        let left = Expression::multiply(Expression::multiply(x(), y()), n(2));
+        let right = Expression::multiply(n(2), Expression::multiply(y(), x()));
+        assert_eq!(left.canonicalize(), right.canonicalize());
+        // 子表达式同样规范化
+        let sin = |arg| Expression::function("sin", vec![arg]);
+        assert_eq!(sin(Expression::add(x(), y())).canonicalize(), sin(Expression::add(y(), x())).canonicalize());
+
+        // 不满足交换律的运算保持原样
+        assert_ne!(Expression::subtract(x(), y()).canonicalize(), Expression::subtract(y(), x()).canonicalize());
+        let a = Expression::matrix(vec![vec![x()]]).unwrap();
+        let b = Expression::matrix(vec![vec![y()]]).unwrap();
+        assert_ne!(
+            Expression::multiply(a.clone(), b.clone()).canonicalize(),
+            Expression::multiply(b, a).canonicalize(),
+        );
+
+        // 规范形式再规范化不变
+        let canonical = right.canonicalize();
+        assert_eq!(canonical.canonicalize(), canonical);
+    }
 }
 
 #[cfg(test)]
@@ -818,6 +999,14 @@ mod expression_evaluation_tests {
             Expression::number(Number::integer(5))
         );
         assert_eq!(mod_expr.evaluate_exact().unwrap(), Number::integer(2));
+
+        // 负数取模的余数非负
+        let mod_expr = Expression::binary_op(
+            BinaryOperator::Modulo,
+            Expression::number(Number::integer(-7)),
+            Expression::number(Number::integer(3))
+        );
+        assert_eq!(mod_expr.evaluate_exact().unwrap(), Number::integer(2));
     }
 
     #[test]
@@ -1000,7 +1189,7 @@ mod expression_display_tests {
         assert_eq!(format!("{}", mul), "x * 2");
         
         let pow = Expression::power(x.clone(), two.clone());
-        assert_eq!(format!("{}", pow), "x ^ 2");
+        assert_eq!(format!("{}", pow), "x^2");
         
         // 运算符优先级和括号
         let complex_expr = Expression::add(
@@ -403,7 +403,7 @@ mod tests {
         let sin_no_args = Expression::function("sin", vec![]);
         let result = sin_no_args.evaluate_exact();
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("sin函数需要一个参数"));
+        assert!(result.unwrap_err().contains("sin 需要 1 个参数"));
         
         let sin_two_args = Expression::function("sin", vec![
             Expression::Number(Number::integer(0)),
@@ -411,13 +411,13 @@ mod tests {
         ]);
         let result = sin_two_args.evaluate_exact();
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("sin函数需要一个参数"));
+        assert!(result.unwrap_err().contains("sin 需要 1 个参数"));
         
         // pow 函数需要两个参数
         let pow_one_arg = Expression::function("pow", vec![Expression::Number(Number::integer(2))]);
         let result = pow_one_arg.evaluate_exact();
         assert!(result.is_err());
-        assert!(result.unwrap_err().contains("pow函数需要两个参数"));
+        assert!(result.unwrap_err().contains("pow 需要 2 个参数"));
     }
 
     #[test]
@@ -430,8 +430,8 @@ mod tests {
         match result {
             Number::Symbolic(expr) => {
                 match expr.as_ref() {
-                    Expression::UnaryOp { op: UnaryOperator::Sin, operand } => {
-                        assert_eq!(operand.as_ref(), &Expression::Number(Number::integer(2)));
+                    Expression::Function { name, args } if name == "sin" => {
+                        assert_eq!(args.as_slice(), [Expression::Number(Number::integer(2))]);
                     }
                     _ => panic!("sin(2) 应该返回 sin 的符号表示"),
                 }
@@ -445,8 +445,8 @@ mod tests {
         match result {
             Number::Symbolic(expr) => {
                 match expr.as_ref() {
-                    Expression::UnaryOp { op: UnaryOperator::Exp, operand } => {
-                        assert_eq!(operand.as_ref(), &Expression::Number(Number::integer(2)));
+                    Expression::Function { name, args } if name == "exp" => {
+                        assert_eq!(args.as_slice(), [Expression::Number(Number::integer(2))]);
                     }
                     _ => panic!("exp(2) 应该返回 exp 的符号表示"),
                 }
@@ -460,8 +460,8 @@ mod tests {
         match result {
             Number::Symbolic(expr) => {
                 match expr.as_ref() {
-                    Expression::UnaryOp { op: UnaryOperator::Ln, operand } => {
-                        assert_eq!(operand.as_ref(), &Expression::Number(Number::integer(2)));
+                    Expression::Function { name, args } if name == "ln" => {
+                        assert_eq!(args.as_slice(), [Expression::Number(Number::integer(2))]);
                     }
                     _ => panic!("ln(2) 应该返回 ln 的符号表示"),
                 }
@@ -570,4 +570,21 @@ mod tests {
             _ => panic!("替换后应该仍然是函数表达式"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_function_signatures() {
+        assert_eq!(signatures::lookup("log").unwrap().hint(), "log(x, [base])");
+        assert_eq!(signatures::lookup("max").unwrap().hint(), "max(x, ...)");
+        assert!(signatures::lookup("foo").is_none());
+        
+        // 求值时的参数个数检查同样来自签名表
+        let call = Expression::function("sin", vec![Expression::variable("x"), Expression::variable("y")]);
+        assert!(call.evaluate(&HashMap::new()).is_err());
+        assert!(signatures::check_arity("max", 0).is_err());
+        assert!(signatures::check_arity("foo", 7).is_ok());
+        
+        let f = Expression::add(Expression::function("sin", vec![Expression::variable("x")]), Expression::function("foo", vec![]));
+        assert_eq!(signatures::find_unknown_function(&f, &|_| false), Some("foo".to_string()));
+        assert_eq!(signatures::find_unknown_function(&f, &|name| name == "foo"), None);
+    }
+}
@@ -0,0 +1,278 @@
+//! # 未定式与扩展实数运算
+//!
+//! 集中定义 `0^0`、`0/0`、`∞-∞`、`0*∞`、`∞/∞`、`1^∞` 六种未定式的语义，
+//! 以及正负无穷参与的算术与比较规则（扩展实数）。化简与求值都查这张表：
+//! 未定式的结果为 `undefined`（`0^0` 可约定为 1），含 `undefined` 的任何运算结果也是 `undefined`，
+//! `∞ + 有限数 = ∞`、`∞ * 负数 = -∞`、`c / ∞ = 0`、`∞ > 任何有限数` 等按扩展实数计算。
+
+use super::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator};
+
+/// 未定式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndeterminateForm {
+    /// `0^0`
+    ZeroPowerZero,
+    /// `0/0`
+    ZeroOverZero,
+    /// `∞ - ∞`，包括 `∞ + (-∞)`
+    InfinityMinusInfinity,
+    /// `0 * ∞`
+    ZeroTimesInfinity,
+    /// `∞ / ∞`
+    InfinityOverInfinity,
+    /// `1^∞`
+    OnePowerInfinity,
+}
+
+impl IndeterminateForm {
+    /// 全部未定式
+    pub const ALL: [IndeterminateForm; 6] = [
+        IndeterminateForm::ZeroPowerZero,
+        IndeterminateForm::ZeroOverZero,
+        IndeterminateForm::InfinityMinusInfinity,
+        IndeterminateForm::ZeroTimesInfinity,
+        IndeterminateForm::InfinityOverInfinity,
+        IndeterminateForm::OnePowerInfinity,
+    ];
+
+    /// 未定式的写法，如 `0^0`
+    pub fn notation(&self) -> &'static str {
+        match self {
+            IndeterminateForm::ZeroPowerZero => "0^0",
+            IndeterminateForm::ZeroOverZero => "0/0",
+            IndeterminateForm::InfinityMinusInfinity => "∞-∞",
+            IndeterminateForm::ZeroTimesInfinity => "0*∞",
+            IndeterminateForm::InfinityOverInfinity => "∞/∞",
+            IndeterminateForm::OnePowerInfinity => "1^∞",
+        }
+    }
+
+    /// 判定 `left op right` 是否构成未定式
+    pub fn classify(op: &BinaryOperator, left: Magnitude, right: Magnitude) -> Option<Self> {
+        use Magnitude::*;
+        match (op, left, right) {
+            (BinaryOperator::Add, PositiveInfinity, NegativeInfinity)
+            | (BinaryOperator::Add, NegativeInfinity, PositiveInfinity)
+            | (BinaryOperator::Subtract, PositiveInfinity, PositiveInfinity)
+            | (BinaryOperator::Subtract, NegativeInfinity, NegativeInfinity) => {
+                Some(IndeterminateForm::InfinityMinusInfinity)
+            }
+            (BinaryOperator::Multiply, Zero, r) if r.is_infinite() => Some(IndeterminateForm::ZeroTimesInfinity),
+            (BinaryOperator::Multiply, l, Zero) if l.is_infinite() => Some(IndeterminateForm::ZeroTimesInfinity),
+            (BinaryOperator::Divide, Zero, Zero) => Some(IndeterminateForm::ZeroOverZero),
+            (BinaryOperator::Divide, l, r) if l.is_infinite() && r.is_infinite() => {
+                Some(IndeterminateForm::InfinityOverInfinity)
+            }
+            (BinaryOperator::Power, Zero, Zero) => Some(IndeterminateForm::ZeroPowerZero),
+            (BinaryOperator::Power, One, r) if r.is_infinite() => Some(IndeterminateForm::OnePowerInfinity),
+            _ => None,
+        }
+    }
+}
+
+/// 运算数在扩展实数运算中的取值类别
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Magnitude {
+    /// 零
+    Zero,
+    /// 一
+    One,
+    /// 其他正的有限实数
+    Positive,
+    /// 负的有限实数
+    Negative,
+    /// 正无穷
+    PositiveInfinity,
+    /// 负无穷
+    NegativeInfinity,
+    /// 未定义值，包括浮点 NaN
+    Undefined,
+    /// 复数、符号值等无法判定的情形
+    Other,
+}
+
+impl Magnitude {
+    /// 表达式的取值类别，`-∞` 写成 `-inf` 时同样识别
+    pub fn of_expression(expr: &Expression) -> Self {
+        match expr {
+            Expression::Number(n) => Self::of_number(n),
+            Expression::Constant(c) => Self::of_constant(c),
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => Self::of_expression(operand).negate(),
+            _ => Magnitude::Other,
+        }
+    }
+
+    /// 数值的取值类别
+    pub fn of_number(number: &Number) -> Self {
+        match number {
+            Number::Float(f) if f.is_nan() => Magnitude::Undefined,
+            Number::Float(f) if *f == f64::INFINITY => Magnitude::PositiveInfinity,
+            Number::Float(f) if *f == f64::NEG_INFINITY => Magnitude::NegativeInfinity,
+            Number::Constant(c) => Self::of_constant(c),
+            Number::Symbolic(expr) => Self::of_expression(expr),
+            Number::Complex { .. } if !number.is_real() => Magnitude::Other,
+            n if n.is_zero() => Magnitude::Zero,
+            n if n.is_one() => Magnitude::One,
+            n if n.is_positive() => Magnitude::Positive,
+            n if n.is_negative() => Magnitude::Negative,
+            _ => Magnitude::Other,
+        }
+    }
+
+    fn of_constant(constant: &MathConstant) -> Self {
+        match constant {
+            MathConstant::PositiveInfinity => Magnitude::PositiveInfinity,
+            MathConstant::NegativeInfinity => Magnitude::NegativeInfinity,
+            MathConstant::Undefined => Magnitude::Undefined,
+            MathConstant::I => Magnitude::Other,
+            _ => Magnitude::Positive,
+        }
+    }
+
+    /// 相反数的取值类别
+    pub fn negate(self) -> Self {
+        match self {
+            Magnitude::One | Magnitude::Positive => Magnitude::Negative,
+            // -(-1) 不一定是 1，只能确定为正
+            Magnitude::Negative => Magnitude::Positive,
+            Magnitude::PositiveInfinity => Magnitude::NegativeInfinity,
+            Magnitude::NegativeInfinity => Magnitude::PositiveInfinity,
+            other => other,
+        }
+    }
+
+    /// 是否为正负无穷
+    pub fn is_infinite(self) -> bool {
+        matches!(self, Magnitude::PositiveInfinity | Magnitude::NegativeInfinity)
+    }
+
+    /// 是否为有限实数
+    pub fn is_finite(self) -> bool {
+        matches!(self, Magnitude::Zero | Magnitude::One | Magnitude::Positive | Magnitude::Negative)
+    }
+
+    /// 非零实数（含无穷）的符号，零与无法判定时为 None
+    fn sign(self) -> Option<i8> {
+        match self {
+            Magnitude::One | Magnitude::Positive | Magnitude::PositiveInfinity => Some(1),
+            Magnitude::Negative | Magnitude::NegativeInfinity => Some(-1),
+            _ => None,
+        }
+    }
+
+    /// 比较时的次序：-∞ < 有限实数 < +∞，有限实数之间不由本表比较
+    fn rank(self) -> Option<i8> {
+        match self {
+            Magnitude::NegativeInfinity => Some(-1),
+            Magnitude::PositiveInfinity => Some(1),
+            m if m.is_finite() => Some(0),
+            _ => None,
+        }
+    }
+}
+
+/// 扩展实数运算的取值约定，默认全部未定式都是 `undefined`、除以零报错
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IndeterminateSemantics {
+    /// 按组合数学习惯取 `0^0 = 1`
+    pub zero_power_zero_is_one: bool,
+    /// 非零数除以零得到带符号的无穷，如 `1/0 = ∞`、`-1/0 = -∞`；关闭时由调用方报除零错误
+    pub division_by_zero_is_infinite: bool,
+}
+
+impl IndeterminateSemantics {
+    /// 创建取值约定
+    pub fn new(zero_power_zero_is_one: bool) -> Self {
+        Self { zero_power_zero_is_one, ..Self::default() }
+    }
+
+    /// 设置非零数除以零是否得到带符号的无穷
+    pub fn with_division_by_zero_infinite(mut self, enabled: bool) -> Self {
+        self.division_by_zero_is_infinite = enabled;
+        self
+    }
+
+    /// 未定式的取值
+    pub fn value(&self, form: IndeterminateForm) -> Number {
+        match form {
+            IndeterminateForm::ZeroPowerZero if self.zero_power_zero_is_one => Number::one(),
+            _ => Number::Constant(MathConstant::Undefined),
+        }
+    }
+
+    /// 查表计算 `left op right`：含 `undefined` 的运算、未定式与无穷参与的运算给出结果，其他情况返回 None
+    pub fn resolve(&self, op: &BinaryOperator, left: Magnitude, right: Magnitude) -> Option<Number> {
+        if left == Magnitude::Undefined || right == Magnitude::Undefined {
+            return Some(Number::Constant(MathConstant::Undefined));
+        }
+        if let Some(form) = IndeterminateForm::classify(op, left, right) {
+            return Some(self.value(form));
+        }
+        if matches!(op, BinaryOperator::Divide) && right == Magnitude::Zero && self.division_by_zero_is_infinite {
+            return left.sign().map(infinity);
+        }
+        let known = |m: Magnitude| m.is_finite() || m.is_infinite();
+        if !(left.is_infinite() || right.is_infinite()) || !known(left) || !known(right) {
+            return None;
+        }
+        let (l, r) = (left.sign(), right.sign());
+        match op {
+            // 未定式已排除，剩下的和与差由无穷的一侧决定
+            BinaryOperator::Add => [left, right].into_iter().find(|m| m.is_infinite()).and_then(Magnitude::sign).map(infinity),
+            BinaryOperator::Subtract if left.is_infinite() => l.map(infinity),
+            BinaryOperator::Subtract => r.map(|sign| infinity(-sign)),
+            BinaryOperator::Multiply => Some(infinity(l? * r?)),
+            BinaryOperator::Divide if left.is_infinite() => Some(infinity(l? * r?)),
+            BinaryOperator::Divide => Some(Number::zero()),
+            // ∞^正数 = ∞，∞^负数 = 0；负无穷的幂与以无穷为指数的幂依赖底数大小，不在表中
+            BinaryOperator::Power if left == Magnitude::PositiveInfinity => match r? {
+                1 => Some(infinity(1)),
+                _ => Some(Number::zero()),
+            },
+            BinaryOperator::Equal | BinaryOperator::NotEqual | BinaryOperator::Less
+            | BinaryOperator::LessEqual | BinaryOperator::Greater | BinaryOperator::GreaterEqual => {
+                let ordering = left.rank()?.cmp(&right.rank()?);
+                let holds = match op {
+                    BinaryOperator::Equal => ordering.is_eq(),
+                    BinaryOperator::NotEqual => ordering.is_ne(),
+                    BinaryOperator::Less => ordering.is_lt(),
+                    BinaryOperator::LessEqual => ordering.is_le(),
+                    BinaryOperator::Greater => ordering.is_gt(),
+                    _ => ordering.is_ge(),
+                };
+                Some(if holds { Number::one() } else { Number::zero() })
+            }
+            _ => None,
+        }
+    }
+
+    /// 对两个数值查表，见 [`IndeterminateSemantics::resolve`]
+    pub fn resolve_numbers(&self, op: &BinaryOperator, left: &Number, right: &Number) -> Option<Number> {
+        self.resolve(op, Magnitude::of_number(left), Magnitude::of_number(right))
+    }
+
+    /// 对两个表达式查表，无穷与 `undefined` 以常量形式返回
+    pub fn resolve_expressions(&self, op: &BinaryOperator, left: &Expression, right: &Expression) -> Option<Expression> {
+        self.resolve(op, Magnitude::of_expression(left), Magnitude::of_expression(right))
+            .map(|value| match value {
+                Number::Constant(c) => Expression::Constant(c),
+                value => Expression::Number(value),
+            })
+    }
+
+    /// 对两个浮点数查表，无穷映射到 `f64::INFINITY`，`undefined` 映射到 NaN
+    pub fn resolve_floats(&self, op: &BinaryOperator, left: f64, right: f64) -> Option<f64> {
+        self.resolve_numbers(op, &Number::Float(left), &Number::Float(right))
+            .map(|value| value.to_f64().unwrap_or(f64::NAN))
+    }
+}
+
+/// 带符号的无穷
+fn infinity(sign: i8) -> Number {
+    Number::Constant(if sign > 0 { MathConstant::PositiveInfinity } else { MathConstant::NegativeInfinity })
+}
+
+/// 表达式是否为 `undefined`
+pub fn is_undefined(expr: &Expression) -> bool {
+    Magnitude::of_expression(expr) == Magnitude::Undefined
+}
@@ -1,10 +1,21 @@
 //! # 内存管理优化
 //!
 //! 提供表达式的内存管理优化，包括引用计数共享、写时复制、哈希优化等功能。
+//!
+//! 节点池按线程回收不再使用的 `Box<Expression>`，供 [`Expression::binary_op`] 等构造函数
+//! 复用其堆内存。回收的节点是独占所有权的普通 Box，复用时整体覆盖其内容，不涉及不安全代码。
+//! 节点池默认关闭，只在 [`NodePoolScope`] 存在期间于创建它的线程上启用；
+//! 只有交还给 [`recycle`] 的表达式才会进入池中（化简器会交还自己的中间结果）。
+//!
+//! 内存记账同样挂在节点分配上：[`begin_accounting`] 之后每个新节点按 `Expression` 的大小
+//! 计入当前线程的账本，用于统计峰值和检查上限；没有线程记账时只多一次原子读取。
 
-use super::{Expression, Number};
+use super::{BinaryOperator, Expression, Number, UnaryOperator};
 use std::rc::Rc;
+use std::sync::Arc;
+use std::cell::RefCell;
 use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::time::{Duration, Instant};
@@ -24,6 +35,10 @@ pub struct MemoryStats {
     pub cow_triggers: usize,
     /// 估计的内存使用量（字节）
     pub estimated_memory_usage: usize,
+    /// 当前线程经构造函数新分配的表达式节点数
+    pub node_allocations: usize,
+    /// 当前线程从节点池复用的表达式节点数
+    pub node_reuses: usize,
     /// 最后更新时间
     pub last_updated: Instant,
 }
@@ -37,6 +52,8 @@ impl Default for MemoryStats {
             cache_misses: 0,
             cow_triggers: 0,
             estimated_memory_usage: 0,
+            node_allocations: 0,
+            node_reuses: 0,
             last_updated: Instant::now(),
         }
     }
@@ -72,6 +89,286 @@ impl Default for MemoryConfig {
     }
 }
 
+/// 节点池统计
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NodePoolStats {
+    /// 新分配的节点数
+    pub allocations: usize,
+    /// 复用的节点数
+    pub reuses: usize,
+    /// 池中空闲的节点数
+    pub free: usize,
+}
+
+/// 表达式节点池
+struct NodePool {
+    // 保存的正是 Box 的堆内存本身，不能展开成 Vec<Expression>
+    #[allow(clippy::vec_box)]
+    free: Vec<Box<Expression>>,
+    capacity: usize,
+    allocations: usize,
+    reuses: usize,
+}
+
+impl NodePool {
+    /// 取一个节点装入 `expr`，池空时新分配
+    fn boxed(&mut self, expr: Expression) -> Box<Expression> {
+        match self.free.pop() {
+            Some(mut node) => {
+                *node = expr;
+                self.reuses += 1;
+                node
+            }
+            None => {
+                self.allocations += 1;
+                Box::new(expr)
+            }
+        }
+    }
+
+    /// 拆开表达式，把子节点的 Box 放回池中；池满后剩余部分直接释放
+    fn recycle(&mut self, expr: Expression) {
+        let mut pending = vec![expr];
+        while let Some(expr) = pending.pop() {
+            match expr {
+                Expression::BinaryOp { left, right, .. } => {
+                    self.reclaim(left, &mut pending);
+                    self.reclaim(right, &mut pending);
+                }
+                Expression::UnaryOp { operand, .. } => self.reclaim(operand, &mut pending),
+                Expression::Interval { start, end, .. } => {
+                    self.reclaim(start, &mut pending);
+                    self.reclaim(end, &mut pending);
+                }
+                Expression::Function { args: elements, .. }
+                | Expression::Vector(elements)
+                | Expression::Set(elements) => pending.extend(elements),
+                Expression::Matrix(rows) => pending.extend(rows.into_iter().flatten()),
+                _ => {}
+            }
+        }
+    }
+
+    fn reclaim(&mut self, mut node: Box<Expression>, pending: &mut Vec<Expression>) {
+        if self.free.len() < self.capacity {
+            // 空字符串不占堆内存，作为占位内容
+            pending.push(std::mem::replace(&mut *node, Expression::Variable(String::new())));
+            self.free.push(node);
+        }
+    }
+}
+
+thread_local! {
+    static NODE_POOL: RefCell<NodePool> = const {
+        RefCell::new(NodePool { free: Vec::new(), capacity: 0, allocations: 0, reuses: 0 })
+    };
+}
+
+/// 为子表达式分配 Box，当前线程启用节点池时优先复用回收的节点
+pub fn boxed(expr: Expression) -> Box<Expression> {
+    account_bytes(NODE_SIZE);
+    NODE_POOL.with(|pool| pool.borrow_mut().boxed(expr))
+}
+
+/// 把不再使用的表达式交还当前线程的节点池；未启用节点池时等同于直接释放
+pub fn recycle(expr: Expression) {
+    let reclaimed = NODE_POOL.with(|pool| {
+        let mut pool = pool.borrow_mut();
+        let before = pool.free.len();
+        pool.recycle(expr);
+        pool.free.len() - before
+    });
+    if reclaimed > 0 && ACCOUNTING_THREADS.load(Ordering::Relaxed) != 0 {
+        account_release(reclaimed);
+    }
+}
+
+/// 设置当前线程节点池最多保留的空闲节点数，返回原来的容量；0 表示关闭并释放池中节点
+fn set_node_pool_capacity(capacity: usize) -> usize {
+    NODE_POOL.with(|pool| {
+        let mut pool = pool.borrow_mut();
+        let previous = std::mem::replace(&mut pool.capacity, capacity);
+        pool.free.truncate(capacity);
+        pool.free.shrink_to(capacity);
+        previous
+    })
+}
+
+/// 在当前线程上启用节点池的作用域，离开作用域时恢复原来的容量
+///
+/// 节点池是线程局部的，作用域不能跨线程传递。
+///
+/// ```
+/// use yufmath::core::memory::{node_pool_stats, NodePoolScope};
+///
+/// {
+///     let _pool = NodePoolScope::new(1024);
+///     // 这里交还给 recycle 的节点会被后续构造复用
+/// }
+/// assert_eq!(node_pool_stats().free, 0);
+/// ```
+#[must_use = "节点池只在作用域存在期间启用"]
+pub struct NodePoolScope {
+    /// 进入作用域前的容量
+    previous: usize,
+    /// 恢复的是创建线程的线程局部状态，不能 Send
+    _thread: std::marker::PhantomData<*const ()>,
+}
+
+impl NodePoolScope {
+    /// 让当前线程的节点池最多保留 `capacity` 个空闲节点
+    pub fn new(capacity: usize) -> Self {
+        Self { previous: set_node_pool_capacity(capacity), _thread: std::marker::PhantomData }
+    }
+}
+
+impl Drop for NodePoolScope {
+    fn drop(&mut self) {
+        set_node_pool_capacity(self.previous);
+    }
+}
+
+/// 当前线程节点池的统计
+pub fn node_pool_stats() -> NodePoolStats {
+    NODE_POOL.with(|pool| {
+        let pool = pool.borrow();
+        NodePoolStats { allocations: pool.allocations, reuses: pool.reuses, free: pool.free.len() }
+    })
+}
+
+/// 一次计算的内存记账结果（按表达式节点估算）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// 结束时仍占用的估算字节数
+    pub current: usize,
+    /// 计算过程中的估算峰值
+    pub peak: usize,
+    /// 是否超过了内存上限
+    pub limit_exceeded: bool,
+}
+
+/// 正在记账的线程数；为 0 时 `boxed` 只多一次原子读取
+static ACCOUNTING_THREADS: AtomicUsize = AtomicUsize::new(0);
+
+/// 每个表达式节点的估算大小
+const NODE_SIZE: usize = std::mem::size_of::<Expression>();
+
+/// 当前线程的内存账本
+struct Ledger {
+    depth: usize,
+    current: usize,
+    peak: usize,
+    limit: Option<usize>,
+    reclaim_at: Option<usize>,
+    reclaim_pending: bool,
+    exceeded: bool,
+}
+
+impl Ledger {
+    fn allocate(&mut self, bytes: usize) {
+        self.current += bytes;
+        self.peak = self.peak.max(self.current);
+        if self.limit.is_some_and(|limit| self.current > limit) {
+            self.exceeded = true;
+        }
+        // 回收只触发一次，之后依靠上限兜底
+        if self.reclaim_at.is_some_and(|threshold| self.current > threshold) {
+            self.reclaim_at = None;
+            self.reclaim_pending = true;
+        }
+    }
+}
+
+thread_local! {
+    static LEDGER: RefCell<Ledger> = const {
+        RefCell::new(Ledger {
+            depth: 0, current: 0, peak: 0, limit: None, reclaim_at: None, reclaim_pending: false, exceeded: false,
+        })
+    };
+}
+
+/// 在当前线程开始内存记账，`limit` 为上限，`reclaim_at` 为触发一次回收的阈值（字节）。
+/// 可以嵌套，只有最外层的设置生效
+pub fn begin_accounting(limit: Option<usize>, reclaim_at: Option<usize>) {
+    LEDGER.with(|ledger| {
+        let mut ledger = ledger.borrow_mut();
+        if ledger.depth == 0 {
+            *ledger = Ledger { depth: 0, current: 0, peak: 0, limit, reclaim_at, reclaim_pending: false, exceeded: false };
+            ACCOUNTING_THREADS.fetch_add(1, Ordering::Relaxed);
+        }
+        ledger.depth += 1;
+    })
+}
+
+/// 结束一层内存记账，返回目前为止的占用与峰值
+pub fn end_accounting() -> MemoryUsage {
+    LEDGER.with(|ledger| {
+        let mut ledger = ledger.borrow_mut();
+        if ledger.depth == 0 {
+            return MemoryUsage::default();
+        }
+        ledger.depth -= 1;
+        if ledger.depth == 0 {
+            ACCOUNTING_THREADS.fetch_sub(1, Ordering::Relaxed);
+        }
+        MemoryUsage { current: ledger.current, peak: ledger.peak, limit_exceeded: ledger.exceeded }
+    })
+}
+
+/// 当前线程记账中的估算占用；未记账时为 `None`
+pub fn accounted_usage() -> Option<MemoryUsage> {
+    LEDGER.with(|ledger| {
+        let ledger = ledger.borrow();
+        (ledger.depth > 0).then_some(MemoryUsage { current: ledger.current, peak: ledger.peak, limit_exceeded: ledger.exceeded })
+    })
+}
+
+/// 把节点以外的中间结构（如多项式的项）按 `bytes` 字节计入当前线程的账本
+pub fn account_bytes(bytes: usize) {
+    if ACCOUNTING_THREADS.load(Ordering::Relaxed) != 0 {
+        LEDGER.with(|ledger| {
+            let mut ledger = ledger.borrow_mut();
+            if ledger.depth > 0 {
+                ledger.allocate(bytes);
+            }
+        })
+    }
+}
+
+/// 估算占用超过上限时返回资源限制错误
+pub fn check_memory_limit() -> Result<(), crate::engine::ComputeError> {
+    if ACCOUNTING_THREADS.load(Ordering::Relaxed) == 0 {
+        return Ok(());
+    }
+    LEDGER.with(|ledger| {
+        let ledger = ledger.borrow();
+        match ledger.limit {
+            Some(limit) if ledger.exceeded => Err(crate::engine::ComputeError::resource_limit(crate::tr!(
+                "memory.limit_exceeded", peak = ledger.peak, limit = limit
+            ))),
+            _ => Ok(()),
+        }
+    })
+}
+
+/// 取走待处理的回收请求：估算占用越过回收阈值后返回一次 `true`
+pub fn take_reclaim_request() -> bool {
+    if ACCOUNTING_THREADS.load(Ordering::Relaxed) == 0 {
+        return false;
+    }
+    LEDGER.with(|ledger| std::mem::take(&mut ledger.borrow_mut().reclaim_pending))
+}
+
+
+fn account_release(nodes: usize) {
+    LEDGER.with(|ledger| {
+        let mut ledger = ledger.borrow_mut();
+        if ledger.depth > 0 {
+            ledger.current = ledger.current.saturating_sub(nodes * NODE_SIZE);
+        }
+    })
+}
+
 /// 共享表达式包装器
 #[derive(Debug, Clone)]
 pub struct SharedExpression {
@@ -186,6 +483,82 @@ impl Hash for SharedExpression {
     }
 }
 
+/// 预先计算结构哈希的表达式，供缓存键使用
+///
+/// 结构哈希在构造时计算一次，与 [`calculate_expression_hash`] 的结果相同；之后每次哈希只写入这个值，
+/// 比较时先比较哈希值和指针，克隆只增加引用计数。大表达式反复查询缓存、去重时不再每次遍历整棵树。
+/// 结构哈希由子表达式的哈希自底向上组合，[`binary_op`](Self::binary_op) 等由已哈希的部分构造时
+/// 只组合两边的哈希值，不再遍历子树。
+#[derive(Debug, Clone)]
+pub struct HashedExpression {
+    /// 表达式，克隆的键共享同一棵树
+    expr: Arc<Expression>,
+    /// 结构哈希
+    hash: u64,
+}
+
+impl HashedExpression {
+    /// 计算结构哈希并包装表达式
+    pub fn new(expr: Expression) -> Self {
+        let hash = calculate_expression_hash(&expr);
+        Self { expr: Arc::new(expr), hash }
+    }
+    
+    /// 由已哈希的两边构造二元运算
+    pub fn binary_op(op: BinaryOperator, left: HashedExpression, right: HashedExpression) -> Self {
+        let hash = binary_op_hash(&op, left.hash, right.hash);
+        Self { expr: Arc::new(Expression::binary_op(op, left.into_inner(), right.into_inner())), hash }
+    }
+    
+    /// 由已哈希的操作数构造一元运算
+    pub fn unary_op(op: UnaryOperator, operand: HashedExpression) -> Self {
+        let hash = unary_op_hash(&op, operand.hash);
+        Self { expr: Arc::new(Expression::unary_op(op, operand.into_inner())), hash }
+    }
+    
+    /// 由已哈希的参数构造函数调用
+    pub fn function(name: impl Into<String>, args: Vec<HashedExpression>) -> Self {
+        let name = name.into();
+        let hash = function_hash(&name, args.iter().map(|arg| arg.hash));
+        Self { expr: Arc::new(Expression::function(name, args.into_iter().map(Self::into_inner).collect())), hash }
+    }
+    
+    /// 获取表达式的引用
+    pub fn expression(&self) -> &Expression {
+        &self.expr
+    }
+    
+    /// 缓存的结构哈希
+    pub fn hash_value(&self) -> u64 {
+        self.hash
+    }
+    
+    /// 取出表达式，其他键仍共享时克隆
+    pub fn into_inner(self) -> Expression {
+        Arc::try_unwrap(self.expr).unwrap_or_else(|expr| (*expr).clone())
+    }
+}
+
+impl From<Expression> for HashedExpression {
+    fn from(expr: Expression) -> Self {
+        Self::new(expr)
+    }
+}
+
+impl PartialEq for HashedExpression {
+    fn eq(&self, other: &Self) -> bool {
+        self.hash == other.hash && (Arc::ptr_eq(&self.expr, &other.expr) || self.expr == other.expr)
+    }
+}
+
+impl Eq for HashedExpression {}
+
+impl Hash for HashedExpression {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_u64(self.hash);
+    }
+}
+
 /// 内存管理器
 pub struct MemoryManager {
     /// 配置
@@ -276,6 +649,9 @@ impl MemoryManager {
         self.stats.active_expressions = self.expression_counter.load(Ordering::Relaxed);
         self.stats.shared_expressions = self.expression_pool.len();
         self.stats.estimated_memory_usage = self.estimate_memory_usage();
+        let pool = node_pool_stats();
+        self.stats.node_allocations = pool.allocations;
+        self.stats.node_reuses = pool.reuses;
         self.stats.last_updated = Instant::now();
         
         // 检查是否需要清理
@@ -326,9 +702,11 @@ impl MemoryManager {
         
         self.last_cleanup = Instant::now();
         
-        println!("内存清理完成: 哈希缓存 {} -> {}, 表达式池 {} -> {}", 
-                initial_hash_cache_size, self.hash_cache.len(),
-                initial_pool_size, self.expression_pool.len());
+        println!("{}", crate::tr!(
+            "memory.cleanup",
+            hash_cache_before = initial_hash_cache_size, hash_cache_after = self.hash_cache.len(),
+            pool_before = initial_pool_size, pool_after = self.expression_pool.len()
+        ));
     }
     
     /// 强制清理所有缓存
@@ -356,85 +734,61 @@ impl Default for MemoryManager {
     }
 }
 
-/// 计算表达式的哈希值
+/// 计算表达式的结构哈希
+///
+/// 叶子直接哈希；复合节点只哈希节点本身的信息（运算符、函数名、长度等）与各子表达式的哈希值，
+/// 因此已知子表达式的哈希时，父节点的哈希不必重新遍历子树。
 pub fn calculate_expression_hash(expr: &Expression) -> u64 {
-    use std::collections::hash_map::DefaultHasher;
-    
-    let mut hasher = DefaultHasher::new();
-    hash_expression(expr, &mut hasher);
-    hasher.finish()
-}
-
-/// 递归计算表达式哈希
-fn hash_expression<H: Hasher>(expr: &Expression, hasher: &mut H) {
     match expr {
-        Expression::Number(n) => {
-            0u8.hash(hasher);
-            hash_number(n, hasher);
-        }
-        Expression::Variable(name) => {
-            1u8.hash(hasher);
-            name.hash(hasher);
-        }
-        Expression::Constant(c) => {
-            2u8.hash(hasher);
-            std::mem::discriminant(c).hash(hasher);
-        }
+        Expression::Number(n) => leaf_hash(0, |hasher| hash_number(n, hasher)),
+        Expression::Variable(name) => leaf_hash(1, |hasher| name.hash(hasher)),
+        Expression::Constant(c) => leaf_hash(2, |hasher| std::mem::discriminant(c).hash(hasher)),
         Expression::BinaryOp { op, left, right } => {
-            3u8.hash(hasher);
-            std::mem::discriminant(op).hash(hasher);
-            hash_expression(left, hasher);
-            hash_expression(right, hasher);
-        }
-        Expression::UnaryOp { op, operand } => {
-            4u8.hash(hasher);
-            std::mem::discriminant(op).hash(hasher);
-            hash_expression(operand, hasher);
-        }
-        Expression::Function { name, args } => {
-            5u8.hash(hasher);
-            name.hash(hasher);
-            args.len().hash(hasher);
-            for arg in args {
-                hash_expression(arg, hasher);
-            }
+            binary_op_hash(op, calculate_expression_hash(left), calculate_expression_hash(right))
         }
+        Expression::UnaryOp { op, operand } => unary_op_hash(op, calculate_expression_hash(operand)),
+        Expression::Function { name, args } => function_hash(name, args.iter().map(calculate_expression_hash)),
         Expression::Matrix(rows) => {
-            6u8.hash(hasher);
-            rows.len().hash(hasher);
-            if !rows.is_empty() {
-                rows[0].len().hash(hasher);
-            }
-            for row in rows {
-                for elem in row {
-                    hash_expression(elem, hasher);
-                }
-            }
-        }
-        Expression::Vector(elements) => {
-            7u8.hash(hasher);
-            elements.len().hash(hasher);
-            for elem in elements {
-                hash_expression(elem, hasher);
-            }
-        }
-        Expression::Set(elements) => {
-            8u8.hash(hasher);
-            elements.len().hash(hasher);
-            for elem in elements {
-                hash_expression(elem, hasher);
-            }
-        }
-        Expression::Interval { start, end, start_inclusive, end_inclusive } => {
-            9u8.hash(hasher);
-            hash_expression(start, hasher);
-            hash_expression(end, hasher);
-            start_inclusive.hash(hasher);
-            end_inclusive.hash(hasher);
+            let columns = rows.first().map_or(0, Vec::len);
+            node_hash(6, (rows.len(), columns), rows.iter().flatten().map(calculate_expression_hash))
         }
+        Expression::Vector(elements) => node_hash(7, elements.len(), elements.iter().map(calculate_expression_hash)),
+        Expression::Set(elements) => node_hash(8, elements.len(), elements.iter().map(calculate_expression_hash)),
+        Expression::Interval { start, end, start_inclusive, end_inclusive } => node_hash(
+            9,
+            (start_inclusive, end_inclusive),
+            [calculate_expression_hash(start), calculate_expression_hash(end)],
+        ),
     }
 }
 
+fn binary_op_hash(op: &BinaryOperator, left: u64, right: u64) -> u64 {
+    node_hash(3, std::mem::discriminant(op), [left, right])
+}
+
+fn unary_op_hash(op: &UnaryOperator, operand: u64) -> u64 {
+    node_hash(4, std::mem::discriminant(op), [operand])
+}
+
+fn function_hash(name: &str, args: impl ExactSizeIterator<Item = u64>) -> u64 {
+    node_hash(5, (name, args.len()), args)
+}
+
+fn leaf_hash(tag: u8, hash: impl FnOnce(&mut DefaultHasher)) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    tag.hash(&mut hasher);
+    hash(&mut hasher);
+    hasher.finish()
+}
+
+/// 组合节点标记、节点本身的信息与各子表达式的哈希
+fn node_hash(tag: u8, header: impl Hash, children: impl IntoIterator<Item = u64>) -> u64 {
+    leaf_hash(tag, |hasher| {
+        header.hash(hasher);
+        children.into_iter().for_each(|child| hasher.write_u64(child));
+    })
+}
+
 /// 计算数值的哈希
 fn hash_number<H: Hasher>(number: &Number, hasher: &mut H) {
     match number {
@@ -461,7 +815,7 @@ fn hash_number<H: Hasher>(number: &Number, hasher: &mut H) {
         }
         Number::Symbolic(expr) => {
             4u8.hash(hasher);
-            hash_expression(expr, hasher);
+            hasher.write_u64(calculate_expression_hash(expr));
         }
         Number::Float(f) => {
             5u8.hash(hasher);
@@ -571,6 +925,22 @@ impl CowExpression {
         self.inner.make_mut()
     }
     
+    /// 独占时就地修改，不触发复制：`f` 返回是否有改变，有改变时标记为已修改。
+    /// 与其他持有者共享时不调用 `f`，返回 `None`
+    pub fn modify_unique<E>(&mut self, f: impl FnOnce(&mut Expression) -> Result<bool, E>) -> Option<Result<bool, E>> {
+        let result = f(self.inner.get_mut()?);
+        if let Ok(true) = result {
+            self.modified = true;
+        }
+        Some(result)
+    }
+    
+    /// 替换为新的表达式并标记为已修改；其他持有者仍共享原表达式
+    pub fn replace(&mut self, new: Expression) {
+        self.inner = SharedExpression::new(new);
+        self.modified = true;
+    }
+    
     /// 检查是否已修改
     pub fn is_modified(&self) -> bool {
         self.modified
@@ -789,4 +1159,39 @@ mod tests {
         println!("清理前: {:?}", stats_before);
         println!("清理后: {:?}", stats_after);
     }
-}
\ No newline at end of file
+    
+    #[test]
+    fn test_hashed_expression() {
+        let build = |n: i64| (1..=n).fold(Expression::variable("x"), |acc, i| {
+            Expression::add(acc, Expression::multiply(Expression::Number(Number::integer(i)), Expression::variable("y")))
+        });
+        let key = HashedExpression::new(build(50));
+        
+        // 缓存的哈希与不缓存时直接计算的结果一致
+        assert_eq!(key.hash_value(), calculate_expression_hash(&build(50)));
+        assert_eq!(key.expression(), &build(50));
+        
+        // 克隆共享同一棵树，独立构造的相同表达式也相等
+        let clone = key.clone();
+        assert_eq!(clone, key);
+        assert_eq!(HashedExpression::from(build(50)), key);
+        assert_ne!(HashedExpression::from(build(49)), key);
+        
+        let mut set = std::collections::HashSet::new();
+        set.insert(key.clone());
+        assert!(set.contains(&HashedExpression::new(build(50))));
+        assert!(!set.insert(clone));
+        assert_eq!(key.into_inner(), build(50));
+        
+        // 由已哈希的部分构造时只组合子表达式的哈希，结果与整棵树重新计算的一致
+        let y = HashedExpression::new(Expression::variable("y"));
+        let composed = HashedExpression::binary_op(
+            BinaryOperator::Add,
+            HashedExpression::new(build(50)),
+            HashedExpression::function("sin", vec![HashedExpression::unary_op(UnaryOperator::Negate, y)]),
+        );
+        let direct = Expression::add(build(50), Expression::function("sin", vec![Expression::negate(Expression::variable("y"))]));
+        assert_eq!(composed.hash_value(), calculate_expression_hash(&direct));
+        assert_eq!(composed, HashedExpression::new(direct));
+    }
+}
@@ -10,19 +10,32 @@ pub mod operators;
 pub mod types;
 pub mod high_precision;
 pub mod memory;
+pub mod binary;
 pub mod expression_builder;
+pub mod indeterminate;
+pub mod signatures;
+pub mod cost;
+pub mod cse;
+pub mod tensor;
+pub mod canonical;
+mod ops;
 
 #[cfg(test)]
 pub mod precision_test;
 
 // 重新导出主要类型
-pub use expression::Expression;
+pub use expression::{Expression, INDEX_FUNCTION, INDEX_ALL, fresh_variable, alpha_equivalent};
 pub use number::Number;
 pub use constants::MathConstant;
 pub use operators::{BinaryOperator, UnaryOperator};
 pub use types::{ExprType, NumericType};
 pub use memory::{
-    SharedExpression, CowExpression, MemoryManager, MemoryMonitor,
-    MemoryStats, MemoryConfig, ExpressionComparator
+    SharedExpression, HashedExpression, CowExpression, MemoryManager, MemoryMonitor,
+    MemoryStats, MemoryConfig, ExpressionComparator, NodePoolStats, NodePoolScope
 };
-pub use expression_builder::{ExpressionBuilder, ExpressionFactory};
\ No newline at end of file
+pub use expression_builder::{ExpressionBuilder, ExpressionFactory};
+pub use indeterminate::{IndeterminateForm, IndeterminateSemantics};
+pub use signatures::{FunctionSignature, ArgumentKind};
+pub use cost::CostModel;
+pub use cse::{cse, CseOptions};
+pub use tensor::Tensor;
\ No newline at end of file
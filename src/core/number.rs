@@ -197,6 +197,43 @@ impl From<BigDecimal> for Number {
     }
 }
 
+impl std::str::FromStr for Number {
+    type Err = crate::parser::ParseError;
+
+    /// 精确解析数值：整数 `"123456789012345678901234567890"`、分数 `"-1/3"`、
+    /// 小数 `"3.14"` 与科学记数法 `"2e10"`、`"1.5e-3"`；后两种按有理数精确表示，值为整数时化为整数
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || crate::parser::ParseError::invalid_number(s);
+        let exact = |value: BigRational| if value.is_integer() { Number::Integer(value.to_integer()) } else { Number::Rational(value) };
+        let s = s.trim();
+        if let Some((numerator, denominator)) = s.split_once('/') {
+            let numerator: BigInt = numerator.trim().parse().map_err(|_| invalid())?;
+            let denominator: BigInt = denominator.trim().parse().map_err(|_| invalid())?;
+            if denominator.is_zero() {
+                return Err(invalid());
+            }
+            return Ok(exact(BigRational::new(numerator, denominator)));
+        }
+        if !s.contains(['.', 'e', 'E']) {
+            return s.parse::<BigInt>().map(Number::Integer).map_err(|_| invalid());
+        }
+        // 小数与科学记数法都是 digits × 10^(-scale)
+        let decimal = s.parse::<BigDecimal>().map_err(|_| invalid())?;
+        let (digits, scale) = decimal.as_bigint_and_exponent();
+        // 指数过大时精确展开会占用大量内存
+        if scale.unsigned_abs() > 100_000 {
+            return Err(invalid());
+        }
+        let power = num_traits::pow(BigInt::from(10), scale.unsigned_abs() as usize);
+        let value = if scale >= 0 {
+            BigRational::new(digits, power)
+        } else {
+            BigRational::from_integer(digits * power)
+        };
+        Ok(exact(value))
+    }
+}
+
 impl Display for Number {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -293,4 +293,32 @@ mod tests {
         assert!(big_int.is_integer());
         assert!(big_int.is_exact());
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_from_str() {
+        let parse = |s: &str| s.parse::<Number>().unwrap();
+
+        // 整数与大整数
+        assert_eq!(parse("42"), Number::integer(42));
+        assert_eq!(parse("-7"), Number::integer(-7));
+        let big: BigInt = "123456789012345678901234567890".parse().unwrap();
+        assert_eq!(parse("123456789012345678901234567890"), Number::Integer(big));
+
+        // 分数约分，分母为 1 时化为整数
+        assert_eq!(parse("1/3"), Number::rational(1, 3));
+        assert_eq!(parse("-2/6"), Number::rational(-1, 3));
+        assert_eq!(parse(" 4 / 2 "), Number::integer(2));
+
+        // 小数与科学记数法按有理数精确表示
+        assert_eq!(parse("3.14"), Number::rational(157, 50));
+        assert_eq!(parse("0.1"), Number::rational(1, 10));
+        assert_eq!(parse("2.0"), Number::integer(2));
+        assert_eq!(parse("2e10"), Number::integer(20_000_000_000i64));
+        assert_eq!(parse("1.5e-3"), Number::rational(3, 2000));
+        assert_eq!(parse("-2.5E2"), Number::integer(-250));
+
+        for invalid in ["", "abc", "1/0", "1/x", "1.2.3", "1e", "1e999999999"] {
+            assert!(invalid.parse::<Number>().is_err(), "{:?} 应当解析失败", invalid);
+        }
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! 定义数学表达式中使用的各种运算符。
 
+use crate::tr;
+
 /// 二元运算符
 #[derive(Debug, Clone, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
 pub enum BinaryOperator {
@@ -110,26 +112,26 @@ impl BinaryOperator {
     /// 获取运算符的名称
     pub fn name(&self) -> &'static str {
         match self {
-            BinaryOperator::Add => "加法",
-            BinaryOperator::Subtract => "减法",
-            BinaryOperator::Multiply => "乘法",
-            BinaryOperator::Divide => "除法",
-            BinaryOperator::Power => "幂运算",
-            BinaryOperator::Modulo => "取模",
-            BinaryOperator::Equal => "等于",
-            BinaryOperator::NotEqual => "不等于",
-            BinaryOperator::Less => "小于",
-            BinaryOperator::LessEqual => "小于等于",
-            BinaryOperator::Greater => "大于",
-            BinaryOperator::GreaterEqual => "大于等于",
-            BinaryOperator::And => "逻辑与",
-            BinaryOperator::Or => "逻辑或",
-            BinaryOperator::Union => "并集",
-            BinaryOperator::Intersection => "交集",
-            BinaryOperator::SetDifference => "差集",
-            BinaryOperator::MatrixMultiply => "矩阵乘法",
-            BinaryOperator::CrossProduct => "叉积",
-            BinaryOperator::DotProduct => "点积",
+            BinaryOperator::Add => tr!("operator.add"),
+            BinaryOperator::Subtract => tr!("operator.subtract"),
+            BinaryOperator::Multiply => tr!("operator.multiply"),
+            BinaryOperator::Divide => tr!("operator.divide"),
+            BinaryOperator::Power => tr!("operator.power"),
+            BinaryOperator::Modulo => tr!("operator.modulo"),
+            BinaryOperator::Equal => tr!("operator.equal"),
+            BinaryOperator::NotEqual => tr!("operator.not_equal"),
+            BinaryOperator::Less => tr!("operator.less"),
+            BinaryOperator::LessEqual => tr!("operator.less_equal"),
+            BinaryOperator::Greater => tr!("operator.greater"),
+            BinaryOperator::GreaterEqual => tr!("operator.greater_equal"),
+            BinaryOperator::And => tr!("operator.and"),
+            BinaryOperator::Or => tr!("operator.or"),
+            BinaryOperator::Union => tr!("operator.union"),
+            BinaryOperator::Intersection => tr!("operator.intersection"),
+            BinaryOperator::SetDifference => tr!("operator.set_difference"),
+            BinaryOperator::MatrixMultiply => tr!("operator.matrix_multiply"),
+            BinaryOperator::CrossProduct => tr!("operator.cross_product"),
+            BinaryOperator::DotProduct => tr!("operator.dot_product"),
         }
     }
 }
@@ -254,40 +256,56 @@ impl UnaryOperator {
         }
     }
     
+    /// 没有专门记法的函数型运算符对应的函数名
+    ///
+    /// 这些运算符统一以同名的函数调用表示，见 [`Expression::unary_op`](crate::core::Expression::unary_op)
+    pub fn function_name(&self) -> Option<&'static str> {
+        match self {
+            UnaryOperator::Sin | UnaryOperator::Cos | UnaryOperator::Tan |
+            UnaryOperator::Asin | UnaryOperator::Acos | UnaryOperator::Atan |
+            UnaryOperator::Sinh | UnaryOperator::Cosh | UnaryOperator::Tanh |
+            UnaryOperator::Asinh | UnaryOperator::Acosh | UnaryOperator::Atanh |
+            UnaryOperator::Ln | UnaryOperator::Log10 | UnaryOperator::Log2 |
+            UnaryOperator::Exp => Some(self.symbol()),
+            UnaryOperator::Gamma => Some("gamma"),
+            _ => None,
+        }
+    }
+    
     /// 获取运算符的名称
     pub fn name(&self) -> &'static str {
         match self {
-            UnaryOperator::Negate => "负号",
-            UnaryOperator::Plus => "正号",
-            UnaryOperator::Sqrt => "平方根",
-            UnaryOperator::Abs => "绝对值",
-            UnaryOperator::Sin => "正弦",
-            UnaryOperator::Cos => "余弦",
-            UnaryOperator::Tan => "正切",
-            UnaryOperator::Asin => "反正弦",
-            UnaryOperator::Acos => "反余弦",
-            UnaryOperator::Atan => "反正切",
-            UnaryOperator::Sinh => "双曲正弦",
-            UnaryOperator::Cosh => "双曲余弦",
-            UnaryOperator::Tanh => "双曲正切",
-            UnaryOperator::Asinh => "反双曲正弦",
-            UnaryOperator::Acosh => "反双曲余弦",
-            UnaryOperator::Atanh => "反双曲正切",
-            UnaryOperator::Ln => "自然对数",
-            UnaryOperator::Log10 => "常用对数",
-            UnaryOperator::Log2 => "二进制对数",
-            UnaryOperator::Exp => "指数函数",
-            UnaryOperator::Factorial => "阶乘",
-            UnaryOperator::Gamma => "伽马函数",
-            UnaryOperator::Not => "逻辑非",
-            UnaryOperator::Real => "实部",
-            UnaryOperator::Imaginary => "虚部",
-            UnaryOperator::Conjugate => "共轭",
-            UnaryOperator::Argument => "幅角",
-            UnaryOperator::Transpose => "转置",
-            UnaryOperator::Determinant => "行列式",
-            UnaryOperator::Inverse => "逆矩阵",
-            UnaryOperator::Trace => "矩阵的迹",
+            UnaryOperator::Negate => tr!("operator.negate"),
+            UnaryOperator::Plus => tr!("operator.plus"),
+            UnaryOperator::Sqrt => tr!("operator.sqrt"),
+            UnaryOperator::Abs => tr!("operator.abs"),
+            UnaryOperator::Sin => tr!("operator.sin"),
+            UnaryOperator::Cos => tr!("operator.cos"),
+            UnaryOperator::Tan => tr!("operator.tan"),
+            UnaryOperator::Asin => tr!("operator.asin"),
+            UnaryOperator::Acos => tr!("operator.acos"),
+            UnaryOperator::Atan => tr!("operator.atan"),
+            UnaryOperator::Sinh => tr!("operator.sinh"),
+            UnaryOperator::Cosh => tr!("operator.cosh"),
+            UnaryOperator::Tanh => tr!("operator.tanh"),
+            UnaryOperator::Asinh => tr!("operator.asinh"),
+            UnaryOperator::Acosh => tr!("operator.acosh"),
+            UnaryOperator::Atanh => tr!("operator.atanh"),
+            UnaryOperator::Ln => tr!("operator.ln"),
+            UnaryOperator::Log10 => tr!("operator.log10"),
+            UnaryOperator::Log2 => tr!("operator.log2"),
+            UnaryOperator::Exp => tr!("operator.exp"),
+            UnaryOperator::Factorial => tr!("operator.factorial"),
+            UnaryOperator::Gamma => tr!("operator.gamma"),
+            UnaryOperator::Not => tr!("operator.not"),
+            UnaryOperator::Real => tr!("operator.real"),
+            UnaryOperator::Imaginary => tr!("operator.imaginary"),
+            UnaryOperator::Conjugate => tr!("operator.conjugate"),
+            UnaryOperator::Argument => tr!("operator.argument"),
+            UnaryOperator::Transpose => tr!("operator.transpose"),
+            UnaryOperator::Determinant => tr!("operator.determinant"),
+            UnaryOperator::Inverse => tr!("operator.inverse"),
+            UnaryOperator::Trace => tr!("operator.trace"),
         }
     }
 }
\ No newline at end of file
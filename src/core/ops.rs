@@ -0,0 +1,159 @@
+//! # 表达式的运算符重载
+//!
+//! 为 [`Expression`] 实现 `+ - * /` 与取负，并支持与 `i32`、`i64`、`f64` 混合运算，
+//! 幂运算用 [`Expression::pow`]：
+//!
+//! ```rust,ignore
+//! let x = Expression::variable("x");
+//! let e = x.clone().pow(2) + 2 * x.clone() + 1;
+//! assert_eq!(e, expr!(x^2 + 2*x + 1));
+//! ```
+//!
+//! 运算符按 Rust 的优先级与左结合性组合，产生的树与解析器解析同样写法的结果一致。
+//! 例外是负数字面量：`x * -3` 中的 `-3` 是负整数，而解析器得到的是 `-(3)`。
+//! 浮点数按最短十进制表示转换，`0.5` 与解析器一样成为有理数 `1/2`。
+
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use super::{Expression, Number, BinaryOperator, UnaryOperator};
+
+impl Expression {
+    /// 幂运算 `self^exponent`
+    pub fn pow(self, exponent: impl Into<Expression>) -> Expression {
+        Expression::power(self, exponent.into())
+    }
+}
+
+impl From<i32> for Expression {
+    fn from(value: i32) -> Self {
+        Expression::Number(Number::from(value))
+    }
+}
+
+impl From<i64> for Expression {
+    fn from(value: i64) -> Self {
+        Expression::Number(Number::from(value))
+    }
+}
+
+impl From<f64> for Expression {
+    /// 有限值按十进制写法转为有理数，与解析器对小数字面量的处理相同
+    fn from(value: f64) -> Self {
+        if !value.is_finite() {
+            return Expression::Number(Number::Float(value));
+        }
+        let text = value.abs().to_string();
+        let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+        let numerator: BigInt = format!("{}{}", integer, fraction).parse().expect("f64 的十进制表示只含数字");
+        let numerator = if value < 0.0 { -numerator } else { numerator };
+        let number = if fraction.is_empty() {
+            Number::Integer(numerator)
+        } else {
+            Number::Rational(BigRational::new(numerator, BigInt::from(10).pow(fraction.len() as u32)))
+        };
+        Expression::Number(number)
+    }
+}
+
+/// 为表达式与表达式、表达式与标量实现二元运算符
+macro_rules! impl_binary_operator {
+    ($trait:ident, $method:ident, $op:expr) => {
+        impl $trait for Expression {
+            type Output = Expression;
+            fn $method(self, rhs: Expression) -> Expression {
+                Expression::binary_op($op, self, rhs)
+            }
+        }
+
+        impl $trait<&Expression> for &Expression {
+            type Output = Expression;
+            fn $method(self, rhs: &Expression) -> Expression {
+                Expression::binary_op($op, self.clone(), rhs.clone())
+            }
+        }
+
+        impl $trait<&Expression> for Expression {
+            type Output = Expression;
+            fn $method(self, rhs: &Expression) -> Expression {
+                Expression::binary_op($op, self, rhs.clone())
+            }
+        }
+
+        impl $trait<Expression> for &Expression {
+            type Output = Expression;
+            fn $method(self, rhs: Expression) -> Expression {
+                Expression::binary_op($op, self.clone(), rhs)
+            }
+        }
+
+        impl_binary_operator!(@scalar $trait, $method, $op, i32, i64, f64);
+    };
+    (@scalar $trait:ident, $method:ident, $op:expr, $($scalar:ty),*) => {$(
+        impl $trait<$scalar> for Expression {
+            type Output = Expression;
+            fn $method(self, rhs: $scalar) -> Expression {
+                Expression::binary_op($op, self, Expression::from(rhs))
+            }
+        }
+
+        impl $trait<$scalar> for &Expression {
+            type Output = Expression;
+            fn $method(self, rhs: $scalar) -> Expression {
+                Expression::binary_op($op, self.clone(), Expression::from(rhs))
+            }
+        }
+
+        impl $trait<Expression> for $scalar {
+            type Output = Expression;
+            fn $method(self, rhs: Expression) -> Expression {
+                Expression::binary_op($op, Expression::from(self), rhs)
+            }
+        }
+
+        impl $trait<&Expression> for $scalar {
+            type Output = Expression;
+            fn $method(self, rhs: &Expression) -> Expression {
+                Expression::binary_op($op, Expression::from(self), rhs.clone())
+            }
+        }
+    )*};
+}
+
+impl_binary_operator!(Add, add, BinaryOperator::Add);
+impl_binary_operator!(Sub, sub, BinaryOperator::Subtract);
+impl_binary_operator!(Mul, mul, BinaryOperator::Multiply);
+impl_binary_operator!(Div, div, BinaryOperator::Divide);
+
+impl Neg for Expression {
+    type Output = Expression;
+    fn neg(self) -> Expression {
+        Expression::unary_op(UnaryOperator::Negate, self)
+    }
+}
+
+impl Neg for &Expression {
+    type Output = Expression;
+    fn neg(self) -> Expression {
+        Expression::unary_op(UnaryOperator::Negate, self.clone())
+    }
+}
+
+/// 用解析器的语法写表达式：`expr!(x^2 + 2*x + 1)`
+///
+/// 宏把记号原样转成字符串交给解析器，每次求值时解析一次；语法错误时 panic，
+/// 适合测试与示例中书写固定的表达式
+#[macro_export]
+macro_rules! expr {
+    ($($tokens:tt)+) => {{
+        use $crate::parser::Parser as _;
+        let source = stringify!($($tokens)+);
+        $crate::parser::syntax::ExpressionParser::new()
+            .parse(source)
+            .unwrap_or_else(|e| panic!("expr! 无法解析 `{}`: {}", source, e))
+    }};
+}
+
+#[cfg(test)]
+#[path = "ops_tests.rs"]
+mod ops_tests;
@@ -0,0 +1,52 @@
+//! # 运算符重载测试
+//!
+//! 测试运算符、`pow` 与 `expr!` 构造的表达式树与解析器的结果一致。
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{Expression, Number};
+    use crate::test_utils::parse;
+    use num_bigint::BigInt;
+    use num_rational::BigRational;
+
+    #[test]
+    fn test_operators_match_parser() {
+        let x = Expression::variable("x");
+        let y = Expression::variable("y");
+
+        assert_eq!(x.clone() * x.clone() + 2 * x.clone() + 1, parse("x*x + 2*x + 1"));
+        assert_eq!(&x - &y / 3, parse("x - y/3"));
+        assert_eq!((&x - &y) / 3i64, parse("(x - y)/3"));
+        assert_eq!(1 - x.clone() - y.clone(), parse("1 - x - y"));
+        assert_eq!(-x.clone() * &y, parse("-x*y"));
+        assert_eq!(-(x.clone() * &y), parse("-(x*y)"));
+        assert_eq!(x.clone().pow(2) + y.clone().pow(x.clone()), parse("x^2 + y^x"));
+        assert_eq!(x.clone().pow(y.clone().pow(2)), parse("x^y^2"));
+    }
+
+    #[test]
+    fn test_float_conversion() {
+        let x = Expression::variable("x");
+        assert_eq!(2.5 * x.clone(), parse("2.5*x"));
+        assert_eq!(x.clone() + 0.1, parse("x + 0.1"));
+        assert_eq!(Expression::from(3.0), parse("3"));
+        assert_eq!(
+            Expression::from(-0.25),
+            Expression::Number(Number::Rational(BigRational::new(BigInt::from(-1), BigInt::from(4))))
+        );
+        assert!(matches!(Expression::from(f64::NAN), Expression::Number(Number::Float(v)) if v.is_nan()));
+    }
+
+    #[test]
+    fn test_expr_macro() {
+        let x = Expression::variable("x");
+        assert_eq!(crate::expr!(x^2 + 2*x + 1), x.clone().pow(2) + 2 * x.clone() + 1);
+        assert_eq!(crate::expr!(sin(x) / (1 + x)), parse("sin(x)/(1 + x)"));
+    }
+
+    #[test]
+    #[should_panic(expected = "expr!")]
+    fn test_expr_macro_syntax_error() {
+        let _ = crate::expr!(x + * 2);
+    }
+}
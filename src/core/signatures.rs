@@ -0,0 +1,244 @@
+//! # 内置函数签名表
+//!
+//! 解析器、计算引擎和自动补全共用的内置函数签名：参数个数范围、各参数的类型期望以及是否可变参数。
+//! 不在表中的函数名当作未定义的符号函数放行，严格模式下由调用方报错。
+
+use super::{Expression, Number, INDEX_FUNCTION, INDEX_ALL};
+use crate::tr;
+
+/// 参数的类型期望
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgumentKind {
+    /// 任意表达式
+    Any,
+    /// 变量名，如 `diff(f, x)` 中的 `x`
+    Variable,
+    /// 整数，如 `root(x, n)` 中的 `n`
+    Integer,
+}
+
+impl ArgumentKind {
+    /// 参数是否明显不符合期望；符号参数要到求值时才能判断，一律放行
+    fn rejects(self, arg: &Expression) -> bool {
+        match self {
+            ArgumentKind::Any => false,
+            // 单个字母的常量名（如 `e`、`i`）也可以当作变量
+            ArgumentKind::Variable => !matches!(arg, Expression::Variable(_) | Expression::Constant(_)),
+            ArgumentKind::Integer => match arg {
+                Expression::Number(Number::Integer(_)) => false,
+                Expression::Number(n) => !n.is_integer(),
+                Expression::Matrix(_) | Expression::Vector(_) | Expression::Set(_) | Expression::Interval { .. } => true,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// 内置函数签名
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FunctionSignature {
+    /// 函数名
+    pub name: &'static str,
+    /// 参数名与类型期望；可变参数函数的最后一项可以重复
+    pub parameters: &'static [(&'static str, ArgumentKind)],
+    /// 必需的参数个数，之后的参数可以省略
+    pub required: usize,
+    /// 最后一个参数是否可以重复任意次
+    pub variadic: bool,
+}
+
+/// 单个任意参数
+const X: &[(&str, ArgumentKind)] = &[("x", ArgumentKind::Any)];
+/// 两个任意参数
+const AB: &[(&str, ArgumentKind)] = &[("a", ArgumentKind::Any), ("b", ArgumentKind::Any)];
+/// 单个矩阵参数
+const MATRIX: &[(&str, ArgumentKind)] = &[("A", ArgumentKind::Any)];
+/// 向量场与变量列表
+const FIELD: &[(&str, ArgumentKind)] = &[("field", ArgumentKind::Any), ("vars", ArgumentKind::Any)];
+/// 正交多项式：次数与可省略的变量
+const ORTHOGONAL: &[(&str, ArgumentKind)] = &[("n", ArgumentKind::Integer), ("x", ArgumentKind::Any)];
+/// 列表参数
+const LIST: &[(&str, ArgumentKind)] = &[("list", ArgumentKind::Any)];
+/// 列表函数：`map(f, L)` 或 `map(body, x, L)`
+const LIST_MAP: &[(&str, ArgumentKind)] = &[("f", ArgumentKind::Any), ("x", ArgumentKind::Any), ("list", ArgumentKind::Any)];
+/// 单个张量参数
+const TENSOR: &[(&str, ArgumentKind)] = &[("T", ArgumentKind::Any)];
+
+/// 参数个数固定
+const fn exact(name: &'static str, parameters: &'static [(&'static str, ArgumentKind)]) -> FunctionSignature {
+    FunctionSignature { name, parameters, required: parameters.len(), variadic: false }
+}
+
+/// 前 `required` 个参数必需，其余可以省略
+const fn optional(name: &'static str, parameters: &'static [(&'static str, ArgumentKind)], required: usize) -> FunctionSignature {
+    FunctionSignature { name, parameters, required, variadic: false }
+}
+
+/// 至少 `required` 个参数，最后一个可以重复
+const fn variadic(name: &'static str, parameters: &'static [(&'static str, ArgumentKind)], required: usize) -> FunctionSignature {
+    FunctionSignature { name, parameters, required, variadic: true }
+}
+
+/// 内置函数签名表
+pub static BUILTIN_FUNCTIONS: &[FunctionSignature] = &[
+    // 三角与反三角函数
+    exact("sin", X), exact("cos", X), exact("tan", X),
+    exact("cot", X), exact("sec", X), exact("csc", X),
+    exact("asin", X), exact("acos", X), exact("atan", X),
+    exact("arcsin", X), exact("arccos", X), exact("arctan", X),
+    // 双曲函数
+    exact("sinh", X), exact("cosh", X), exact("tanh", X),
+    exact("asinh", X), exact("acosh", X), exact("atanh", X),
+    // 指数、对数与根
+    exact("exp", X), exact("ln", X),
+    optional("log", &[("x", ArgumentKind::Any), ("base", ArgumentKind::Any)], 1),
+    exact("log10", X), exact("log2", X),
+    exact("sqrt", X), exact("cbrt", X),
+    exact("root", &[("x", ArgumentKind::Any), ("n", ArgumentKind::Integer)]),
+    exact("pow", &[("x", ArgumentKind::Any), ("y", ArgumentKind::Any)]),
+    // 取整与绝对值
+    exact("abs", X), exact("floor", X), exact("ceil", X), exact("round", X), exact("trunc", X),
+    exact("factorial", &[("n", ArgumentKind::Any)]),
+    // 特殊函数
+    exact("erf", X), exact("erfc", X), exact("Si", X), exact("Ci", X), exact("Ei", X), exact("li", X), exact("sinc", X),
+    // 复数
+    exact("re", &[("z", ArgumentKind::Any)]), exact("im", &[("z", ArgumentKind::Any)]),
+    exact("real", &[("z", ArgumentKind::Any)]), exact("imag", &[("z", ArgumentKind::Any)]),
+    exact("arg", &[("z", ArgumentKind::Any)]),
+    exact("conj", &[("z", ArgumentKind::Any)]), exact("conjugate", &[("z", ArgumentKind::Any)]),
+    // 最值与数论
+    variadic("max", X, 1), variadic("min", X, 1),
+    variadic("gcd", AB, 0), variadic("lcm", AB, 0),
+    exact("mod", AB), exact("Mod", AB), exact("rem", AB), exact("quotient", AB), exact("divmod", AB),
+    exact("congruent", &[("a", ArgumentKind::Any), ("b", ArgumentKind::Any), ("n", ArgumentKind::Any)]),
+    // 微积分与代数
+    exact("diff", &[("f", ArgumentKind::Any), ("x", ArgumentKind::Variable)]),
+    optional("integrate", &[("f", ArgumentKind::Any), ("x", ArgumentKind::Variable), ("a", ArgumentKind::Any), ("b", ArgumentKind::Any)], 2),
+    optional("sum", &[("f", ArgumentKind::Any), ("k", ArgumentKind::Variable), ("a", ArgumentKind::Any), ("b", ArgumentKind::Any)], 2),
+    exact("forward_difference", &[("f", ArgumentKind::Any), ("n", ArgumentKind::Variable)]),
+    exact("limit", &[("f", ArgumentKind::Any), ("x", ArgumentKind::Variable), ("point", ArgumentKind::Any)]),
+    optional("series", &[("f", ArgumentKind::Any), ("x", ArgumentKind::Variable), ("point", ArgumentKind::Any), ("order", ArgumentKind::Integer)], 3),
+    exact("solve", &[("equation", ArgumentKind::Any), ("x", ArgumentKind::Variable)]),
+    exact("simplify", &[("expr", ArgumentKind::Any)]),
+    exact("expand", &[("expr", ArgumentKind::Any)]),
+    exact("factor", &[("expr", ArgumentKind::Any)]),
+    exact("resultant", &[("p", ArgumentKind::Any), ("q", ArgumentKind::Any), ("x", ArgumentKind::Variable)]),
+    exact("discriminant", &[("p", ArgumentKind::Any), ("x", ArgumentKind::Variable)]),
+    optional("chebyshev_t", ORTHOGONAL, 1), optional("chebyshev_u", ORTHOGONAL, 1),
+    optional("legendre_p", ORTHOGONAL, 1), optional("hermite_h", ORTHOGONAL, 1), optional("laguerre_l", ORTHOGONAL, 1),
+    exact("complete_square", &[("f", ArgumentKind::Any), ("vars", ArgumentKind::Any)]),
+    exact("quadratic_form_matrix", &[("f", ArgumentKind::Any), ("vars", ArgumentKind::Any)]),
+    exact("laplace", &[("f", ArgumentKind::Any), ("t", ArgumentKind::Variable), ("s", ArgumentKind::Variable)]),
+    exact("inverse_laplace", &[("F", ArgumentKind::Any), ("s", ArgumentKind::Variable), ("t", ArgumentKind::Variable)]),
+    // 矩阵
+    exact("det", MATRIX), exact("transpose", MATRIX), exact("inverse", MATRIX), exact("inv", MATRIX),
+    exact("trace", MATRIX), exact("tr", MATRIX),
+    exact("eye", &[("n", ArgumentKind::Integer)]),
+    optional("zeros", &[("m", ArgumentKind::Integer), ("n", ArgumentKind::Integer)], 1),
+    variadic(INDEX_FUNCTION, &[("A", ArgumentKind::Any), ("i", ArgumentKind::Any)], 2),
+    exact(INDEX_ALL, &[]),
+    // 向量微积分
+    exact("jacobian", &[("fs", ArgumentKind::Any), ("vars", ArgumentKind::Any)]),
+    exact("div", FIELD), exact("divergence", FIELD), exact("curl", FIELD),
+    exact("laplacian", &[("f", ArgumentKind::Any), ("vars", ArgumentKind::Any)]),
+    // 概率分布
+    optional("normal_pdf", &[("x", ArgumentKind::Any), ("mu", ArgumentKind::Any), ("sigma", ArgumentKind::Any)], 1),
+    optional("normal_cdf", &[("x", ArgumentKind::Any), ("mu", ArgumentKind::Any), ("sigma", ArgumentKind::Any)], 1),
+    exact("binomial_pmf", &[("k", ArgumentKind::Any), ("n", ArgumentKind::Any), ("p", ArgumentKind::Any)]),
+    exact("binomial_cdf", &[("k", ArgumentKind::Any), ("n", ArgumentKind::Any), ("p", ArgumentKind::Any)]),
+    exact("poisson_pmf", &[("k", ArgumentKind::Any), ("lambda", ArgumentKind::Any)]),
+    exact("poisson_cdf", &[("k", ArgumentKind::Any), ("lambda", ArgumentKind::Any)]),
+    optional("normal", &[("mu", ArgumentKind::Any), ("sigma", ArgumentKind::Any)], 0),
+    exact("binomial", &[("n", ArgumentKind::Any), ("p", ArgumentKind::Any)]),
+    exact("poisson", &[("lambda", ArgumentKind::Any)]),
+    optional("expectation", &[("dist", ArgumentKind::Any), ("g", ArgumentKind::Any), ("x", ArgumentKind::Variable)], 2),
+    // 列表
+    optional("range", &[("start", ArgumentKind::Any), ("end", ArgumentKind::Any), ("step", ArgumentKind::Any)], 2),
+    optional("table", &[("f", ArgumentKind::Any), ("k", ArgumentKind::Variable), ("start", ArgumentKind::Any), ("end", ArgumentKind::Any), ("step", ArgumentKind::Any)], 4),
+    optional("map", LIST_MAP, 2), optional("filter", LIST_MAP, 2),
+    exact("sum_list", LIST), exact("sort", LIST), exact("length", LIST),
+    // 张量
+    exact("shape", TENSOR), exact("flatten", TENSOR),
+    exact("reshape", &[("T", ArgumentKind::Any), ("shape", ArgumentKind::Any)]),
+    exact("tensor_sum", &[("T", ArgumentKind::Any), ("axis", ArgumentKind::Integer)]),
+    exact("tensor_add", AB), exact("tensor_subtract", AB), exact("tensor_multiply", AB), exact("tensor_divide", AB),
+];
+
+/// 按名称查找内置函数签名
+pub fn lookup(name: &str) -> Option<&'static FunctionSignature> {
+    BUILTIN_FUNCTIONS.iter().find(|signature| signature.name == name)
+}
+
+/// 校验内置函数调用的参数个数与类型，不在表中的函数不做检查
+pub fn check_call(name: &str, args: &[Expression]) -> Result<(), String> {
+    lookup(name).map_or(Ok(()), |signature| signature.check(args))
+}
+
+/// 只校验内置函数的参数个数，供参数已求值的场合使用
+pub fn check_arity(name: &str, count: usize) -> Result<(), String> {
+    lookup(name).map_or(Ok(()), |signature| signature.check_arity(count))
+}
+
+/// 表达式中第一个既不是内置函数、也不满足 `is_known` 的函数名
+pub fn find_unknown_function(expr: &Expression, is_known: &dyn Fn(&str) -> bool) -> Option<String> {
+    if let Expression::Function { name, .. } = expr {
+        if lookup(name).is_none() && !is_known(name) {
+            return Some(name.clone());
+        }
+    }
+    expr.children().into_iter().find_map(|child| find_unknown_function(child, is_known))
+}
+
+impl FunctionSignature {
+    /// 最多接受的参数个数，可变参数函数为 `None`
+    pub fn max_args(&self) -> Option<usize> {
+        (!self.variadic).then_some(self.parameters.len())
+    }
+
+    /// 是否接受 `count` 个参数
+    pub fn accepts(&self, count: usize) -> bool {
+        count >= self.required && self.max_args().is_none_or(|max| count <= max)
+    }
+
+    /// 第 `index` 个参数的名称与类型期望
+    fn parameter(&self, index: usize) -> Option<(&'static str, ArgumentKind)> {
+        self.parameters.get(index).or_else(|| self.variadic.then(|| self.parameters.last()).flatten()).copied()
+    }
+
+    /// 参数提示，如 `log(x, [base])`、`max(x, ...)`
+    pub fn hint(&self) -> String {
+        let parameters: Vec<String> = self.parameters.iter().enumerate()
+            .map(|(index, (name, _))| if index < self.required { name.to_string() } else { format!("[{}]", name) })
+            .chain(self.variadic.then(|| "...".to_string()))
+            .collect();
+        format!("{}({})", self.name, parameters.join(", "))
+    }
+
+    /// 校验参数个数
+    pub fn check_arity(&self, count: usize) -> Result<(), String> {
+        if self.accepts(count) {
+            return Ok(());
+        }
+        Err(match self.max_args() {
+            Some(max) if max == self.required => tr!("signature.arity_exact", name = self.name, count = max, actual = count),
+            Some(max) => tr!("signature.arity_range", name = self.name, min = self.required, max = max, actual = count),
+            None => tr!("signature.arity_at_least", name = self.name, min = self.required, actual = count),
+        })
+    }
+
+    /// 校验参数个数与明显不符的参数类型
+    pub fn check(&self, args: &[Expression]) -> Result<(), String> {
+        self.check_arity(args.len())?;
+        for (index, arg) in args.iter().enumerate() {
+            let Some((parameter, kind)) = self.parameter(index) else { continue };
+            if kind.rejects(arg) {
+                let key = match kind {
+                    ArgumentKind::Integer => "signature.expected_integer",
+                    _ => "signature.expected_variable",
+                };
+                return Err(tr!(key, name = self.name, parameter = parameter, actual = arg));
+            }
+        }
+        Ok(())
+    }
+}
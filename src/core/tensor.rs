@@ -0,0 +1,184 @@
+//! # 张量（多维数组）
+//!
+//! 以形状和按行优先展平的元素表示 N 维数组。写成表达式时，1 维张量是向量，2 维是矩阵，
+//! 3 维及以上是以低一维张量为元素的向量，如 `[[[1, 2], [3, 4]], [[5, 6], [7, 8]]]` 是 2×2×2 的张量。
+//! 读取时同样接受嵌套的向量，但各层长度必须一致。这里的下标和轴都从 0 开始，
+//! 表达式中的下标 `T[i, j, k]` 与 `tensor_sum(T, axis)` 的轴从 1 开始，由调用方换算。
+
+use super::Expression;
+use crate::tr;
+
+/// 张量：形状与按行优先排列的元素
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tensor {
+    shape: Vec<usize>,
+    elements: Vec<Expression>,
+}
+
+impl Tensor {
+    /// 由形状和按行优先排列的元素构造；有长度为 0 的维或元素个数与形状不符时报错
+    pub fn new(shape: Vec<usize>, elements: Vec<Expression>) -> Result<Self, String> {
+        if shape.contains(&0) {
+            return Err(tr!("tensor.empty_axis", shape = format_shape(&shape)));
+        }
+        let size = shape.iter().try_fold(1usize, |size, &len| size.checked_mul(len));
+        if size != Some(elements.len()) {
+            return Err(tr!("tensor.size_mismatch", shape = format_shape(&shape), count = elements.len()));
+        }
+        Ok(Self { shape, elements })
+    }
+
+    /// 从向量、矩阵或嵌套的向量读取张量，其他表达式看作 0 维张量；各层长度不一致时报错
+    pub fn from_nested(expr: &Expression) -> Result<Self, String> {
+        let mut shape = Vec::new();
+        let mut first = expr.clone();
+        while let Some(items) = items(&first) {
+            shape.push(items.len());
+            match items.into_iter().next() {
+                Some(item) => first = item,
+                None => break,
+            }
+        }
+        let mut elements = Vec::new();
+        collect(expr, &shape, 0, &mut elements)?;
+        Self::new(shape, elements)
+    }
+
+    /// 形状
+    pub fn shape(&self) -> &[usize] {
+        &self.shape
+    }
+
+    /// 维数
+    pub fn rank(&self) -> usize {
+        self.shape.len()
+    }
+
+    /// 按行优先排列的元素
+    pub fn elements(&self) -> &[Expression] {
+        &self.elements
+    }
+
+    /// 取出元素
+    pub fn into_elements(self) -> Vec<Expression> {
+        self.elements
+    }
+
+    /// 写成表达式：0 维为元素本身，1 维为向量，2 维为矩阵，更高维为以低一维张量为元素的向量
+    pub fn to_expression(&self) -> Expression {
+        match self.shape.as_slice() {
+            [] => self.elements[0].clone(),
+            [_] => Expression::Vector(self.elements.clone()),
+            [_, columns] => Expression::Matrix(self.elements.chunks(*columns).map(<[_]>::to_vec).collect()),
+            [_, inner @ ..] => {
+                let stride = self.elements.len() / self.shape[0];
+                Expression::Vector(self.elements.chunks(stride)
+                    .map(|chunk| Tensor { shape: inner.to_vec(), elements: chunk.to_vec() }.to_expression())
+                    .collect())
+            }
+        }
+    }
+
+    /// 按下标取元素；下标个数与维数不符或越界时返回 `None`
+    pub fn get(&self, index: &[usize]) -> Option<&Expression> {
+        if index.len() != self.rank() || index.iter().zip(&self.shape).any(|(&i, &len)| i >= len) {
+            return None;
+        }
+        Some(&self.elements[self.offset(index)])
+    }
+
+    /// 切片：每一维给出下标，`None` 保留整个维度；结果去掉取定下标的维。下标个数与维数不符或越界时返回 `None`
+    pub fn slice(&self, selection: &[Option<usize>]) -> Option<Tensor> {
+        if selection.len() != self.rank() || selection.iter().zip(&self.shape).any(|(i, &len)| i.is_some_and(|i| i >= len)) {
+            return None;
+        }
+        let shape: Vec<usize> = selection.iter().zip(&self.shape).filter(|(i, _)| i.is_none()).map(|(_, &len)| len).collect();
+        let elements = indices(&shape).map(|free| {
+            let mut free = free.into_iter();
+            let index: Vec<usize> = selection.iter().map(|i| i.unwrap_or_else(|| free.next().unwrap())).collect();
+            self.elements[self.offset(&index)].clone()
+        }).collect();
+        Some(Tensor { shape, elements })
+    }
+
+    /// 保持元素顺序改为新形状，元素个数必须不变
+    pub fn reshape(&self, shape: Vec<usize>) -> Result<Tensor, String> {
+        let from = format_shape(&self.shape);
+        let to = format_shape(&shape);
+        Tensor::new(shape, self.elements.clone())
+            .map_err(|_| tr!("tensor.reshape", from = from, to = to))
+    }
+
+    /// 沿第 `axis` 维求和（未化简的和式），结果少一维；轴越界时报错
+    pub fn sum_axis(&self, axis: usize) -> Result<Tensor, String> {
+        if axis >= self.rank() {
+            return Err(tr!("tensor.no_axis", rank = self.rank(), axis = axis + 1));
+        }
+        let mut selection: Vec<Option<usize>> = vec![None; self.rank()];
+        let parts: Vec<Tensor> = (0..self.shape[axis]).map(|i| {
+            selection[axis] = Some(i);
+            self.slice(&selection).unwrap()
+        }).collect();
+        let shape = parts[0].shape.clone();
+        let elements = (0..parts[0].elements.len())
+            .map(|k| parts.iter().map(|part| part.elements[k].clone()).reduce(Expression::add).unwrap())
+            .collect();
+        Ok(Tensor { shape, elements })
+    }
+
+    /// 展平后的位置
+    fn offset(&self, index: &[usize]) -> usize {
+        index.iter().zip(&self.shape).fold(0, |offset, (&i, &len)| offset * len + i)
+    }
+}
+
+/// 形状写成 `2×3×4`
+pub fn format_shape(shape: &[usize]) -> String {
+    if shape.is_empty() {
+        return tr!("tensor.scalar").to_string();
+    }
+    shape.iter().map(usize::to_string).collect::<Vec<_>>().join("×")
+}
+
+/// 数组的下一层：向量的元素或矩阵的各行
+fn items(expr: &Expression) -> Option<Vec<Expression>> {
+    match expr {
+        Expression::Vector(elements) => Some(elements.clone()),
+        Expression::Matrix(rows) => Some(rows.iter().cloned().map(Expression::Vector).collect()),
+        _ => None,
+    }
+}
+
+/// 按第一个分支推出的形状校验第 `level` 层及以下，并按行优先收集元素
+fn collect(expr: &Expression, shape: &[usize], level: usize, elements: &mut Vec<Expression>) -> Result<(), String> {
+    match (items(expr), shape.get(level)) {
+        (None, None) => {
+            elements.push(expr.clone());
+            Ok(())
+        }
+        (Some(items), Some(&len)) if items.len() == len => {
+            items.iter().try_for_each(|item| collect(item, shape, level + 1, elements))
+        }
+        (Some(items), Some(&len)) => {
+            Err(tr!("tensor.ragged", level = level + 1, expected = len, actual = items.len()))
+        }
+        _ => Err(tr!("tensor.mixed_level", level = level + 1)),
+    }
+}
+
+/// 按行优先遍历形状为 `shape` 的所有下标
+fn indices(shape: &[usize]) -> impl Iterator<Item = Vec<usize>> + '_ {
+    let size: usize = shape.iter().product();
+    (0..size).map(move |mut flat| {
+        let mut index = vec![0; shape.len()];
+        for (i, &len) in shape.iter().enumerate().rev() {
+            index[i] = flat % len;
+            flat /= len;
+        }
+        index
+    })
+}
+
+#[cfg(test)]
+#[path = "tensor_tests.rs"]
+mod tensor_tests;
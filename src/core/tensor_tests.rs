@@ -0,0 +1,68 @@
+//! # 张量测试
+//!
+//! 测试从嵌套数组读取与写回表达式、各层长度校验、取元素与切片、变形以及沿轴求和。
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{Expression, Number, Tensor};
+    use crate::test_utils::parse;
+
+    fn integers(values: &[i64]) -> Vec<Expression> {
+        values.iter().map(|&v| Expression::Number(Number::integer(v))).collect()
+    }
+
+    fn cube() -> Tensor {
+        Tensor::new(vec![2, 2, 2], integers(&[1, 2, 3, 4, 5, 6, 7, 8])).unwrap()
+    }
+
+    #[test]
+    fn test_nested_round_trip() {
+        let nested = Expression::Vector(vec![parse("[[1, 2], [3, 4]]"), parse("[[5, 6], [7, 8]]")]);
+        let tensor = Tensor::from_nested(&nested).unwrap();
+        assert_eq!(tensor, cube());
+        assert_eq!(tensor.to_expression(), nested);
+        assert_eq!(tensor.to_expression().to_string(), "[[[1, 2], [3, 4]], [[5, 6], [7, 8]]]");
+
+        // 嵌套向量也能读取，写回时 2 维为矩阵
+        let rows = Expression::Vector(vec![parse("[1, 2]"), parse("[3, 4]")]);
+        assert_eq!(Tensor::from_nested(&rows).unwrap().to_expression(), parse("[[1, 2], [3, 4]]"));
+        let scalar = Tensor::from_nested(&parse("x")).unwrap();
+        assert_eq!(scalar.rank(), 0);
+        assert_eq!(scalar.to_expression(), parse("x"));
+    }
+
+    #[test]
+    fn test_ragged_rejected() {
+        let ragged = Expression::Vector(vec![parse("[[1, 2], [3, 4]]"), parse("[[5, 6], [7, 8], [9, 10]]")]);
+        assert!(Tensor::from_nested(&ragged).unwrap_err().contains("第 2 层"));
+        let mixed = Expression::Vector(vec![parse("[1, 2]"), parse("3")]);
+        assert!(Tensor::from_nested(&mixed).is_err());
+        assert!(Tensor::from_nested(&parse("[]")).is_err());
+        assert!(Tensor::new(vec![2, 3], integers(&[1, 2, 3])).is_err());
+    }
+
+    #[test]
+    fn test_get_and_slice() {
+        let tensor = cube();
+        assert_eq!(tensor.get(&[1, 0, 1]), Some(&Expression::Number(Number::integer(6))));
+        assert_eq!(tensor.get(&[2, 0, 0]), None);
+        assert_eq!(tensor.get(&[0, 0]), None);
+        assert_eq!(tensor.slice(&[Some(1), None, None]).unwrap().to_expression(), parse("[[5, 6], [7, 8]]"));
+        assert_eq!(tensor.slice(&[None, Some(0), Some(1)]).unwrap().to_expression(), parse("[2, 6]"));
+        assert!(tensor.slice(&[None, Some(2), None]).is_none());
+    }
+
+    #[test]
+    fn test_reshape_and_sum() {
+        let tensor = cube();
+        assert_eq!(tensor.reshape(vec![2, 4]).unwrap().to_expression(), parse("[[1, 2, 3, 4], [5, 6, 7, 8]]"));
+        assert_eq!(tensor.reshape(vec![8]).unwrap().to_expression(), parse("[1, 2, 3, 4, 5, 6, 7, 8]"));
+        assert!(tensor.reshape(vec![3, 3]).unwrap_err().contains("2×2×2"));
+
+        let summed = tensor.sum_axis(0).unwrap();
+        assert_eq!(summed.shape(), &[2, 2]);
+        assert_eq!(summed.elements()[0], parse("1 + 5"));
+        assert_eq!(tensor.sum_axis(2).unwrap().elements()[3], parse("7 + 8"));
+        assert!(tensor.sum_axis(3).is_err());
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! 定义表达式的类型信息和数值类型系统。
 
+use crate::tr;
+
 /// 表达式的类型信息
 #[derive(Debug, Clone, PartialEq)]
 pub enum ExprType {
@@ -68,24 +70,24 @@ impl ExprType {
     pub fn to_string(&self) -> String {
         match self {
             ExprType::Numeric(nt) => nt.to_string(),
-            ExprType::Symbolic => "符号".to_string(),
+            ExprType::Symbolic => tr!("type.symbolic").to_string(),
             ExprType::Function(args, ret) => {
                 let arg_types: Vec<String> = args.iter().map(|t| t.to_string()).collect();
                 format!("({}) -> {}", arg_types.join(", "), ret.to_string())
             }
             ExprType::Matrix(rows, cols, elem_type) => {
-                format!("矩阵[{}×{}]<{}>", rows, cols, elem_type.to_string())
+                tr!("type.matrix", rows = rows, cols = cols, element = elem_type.to_string())
             }
             ExprType::Vector(dim, elem_type) => {
-                format!("向量[{}]<{}>", dim, elem_type.to_string())
+                tr!("type.vector", dim = dim, element = elem_type.to_string())
             }
             ExprType::Set(elem_type) => {
-                format!("集合<{}>", elem_type.to_string())
+                tr!("type.set", element = elem_type.to_string())
             }
             ExprType::Interval(elem_type) => {
-                format!("区间<{}>", elem_type.to_string())
+                tr!("type.interval", element = elem_type.to_string())
             }
-            ExprType::Unknown => "未知".to_string(),
+            ExprType::Unknown => tr!("type.unknown").to_string(),
         }
     }
 }
@@ -119,11 +121,11 @@ impl NumericType {
     /// 获取类型的字符串表示
     pub fn to_string(&self) -> String {
         match self {
-            NumericType::Integer => "整数".to_string(),
-            NumericType::Rational => "有理数".to_string(),
-            NumericType::Real => "实数".to_string(),
-            NumericType::Complex => "复数".to_string(),
-            NumericType::Float => "浮点数".to_string(),
+            NumericType::Integer => tr!("type.integer").to_string(),
+            NumericType::Rational => tr!("type.rational").to_string(),
+            NumericType::Real => tr!("type.real").to_string(),
+            NumericType::Complex => tr!("type.complex").to_string(),
+            NumericType::Float => tr!("type.float").to_string(),
         }
     }
     
@@ -0,0 +1,322 @@
+//! # 变量假设
+//!
+//! 记录变量的性质（正负、整数、实数），并据此判断表达式的符号、是否为实数或整数。
+//! 化简器在 `sqrt(x^2)`、`abs(x)`、`ln(e^x)` 等只在一定条件下成立的变换处查询假设。
+//! 判断是保守的：无法确定时一律返回 `false`。
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator, MathConstant, ExprType, NumericType};
+use super::ComputeError;
+use crate::tr;
+
+/// 变量的性质
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Property {
+    /// `x > 0`
+    Positive,
+    /// `x >= 0`
+    NonNegative,
+    /// `x < 0`
+    Negative,
+    /// `x <= 0`
+    NonPositive,
+    /// `x != 0`
+    NonZero,
+    /// `x` 是整数
+    Integer,
+    /// `x` 是实数
+    Real,
+}
+
+impl Property {
+    /// 可能的符号（负、零、正）；不限定符号时为 `None`
+    fn signs(self) -> Option<Signs> {
+        match self {
+            Property::Positive => Some(Signs { negative: false, zero: false, positive: true }),
+            Property::NonNegative => Some(Signs { negative: false, zero: true, positive: true }),
+            Property::Negative => Some(Signs { negative: true, zero: false, positive: false }),
+            Property::NonPositive => Some(Signs { negative: true, zero: true, positive: false }),
+            Property::NonZero => Some(Signs { negative: true, zero: false, positive: true }),
+            Property::Integer | Property::Real => None,
+        }
+    }
+}
+
+impl fmt::Display for Property {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Property::Positive => "> 0",
+            Property::NonNegative => ">= 0",
+            Property::Negative => "< 0",
+            Property::NonPositive => "<= 0",
+            Property::NonZero => "!= 0",
+            Property::Integer => "is integer",
+            Property::Real => "is real",
+        };
+        f.write_str(text)
+    }
+}
+
+impl FromStr for Property {
+    type Err = ComputeError;
+
+    /// 解析 `> 0`、`>= 0`、`< 0`、`<= 0`、`!= 0`、`is integer`、`is real` 或 `positive` 等名称
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let compact: String = s.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase();
+        let name = compact.strip_prefix("is ").unwrap_or(&compact);
+        match name.replace(' ', "").as_str() {
+            ">0" | "positive" => Ok(Property::Positive),
+            ">=0" | "nonnegative" => Ok(Property::NonNegative),
+            "<0" | "negative" => Ok(Property::Negative),
+            "<=0" | "nonpositive" => Ok(Property::NonPositive),
+            "!=0" | "nonzero" => Ok(Property::NonZero),
+            "integer" => Ok(Property::Integer),
+            "real" => Ok(Property::Real),
+            _ => Err(ComputeError::domain_error(tr!("assumptions.unrecognized", assumption = s))),
+        }
+    }
+}
+
+/// 表达式可能取的符号
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Signs {
+    negative: bool,
+    zero: bool,
+    positive: bool,
+}
+
+impl Signs {
+    const ANY: Signs = Signs { negative: true, zero: true, positive: true };
+
+    fn of(number: &Number) -> Signs {
+        Signs { negative: number.is_negative(), zero: number.is_zero(), positive: number.is_positive() }
+    }
+
+    fn intersect(self, other: Signs) -> Signs {
+        Signs {
+            negative: self.negative && other.negative,
+            zero: self.zero && other.zero,
+            positive: self.positive && other.positive,
+        }
+    }
+
+    fn negate(self) -> Signs {
+        Signs { negative: self.positive, zero: self.zero, positive: self.negative }
+    }
+
+    fn add(self, other: Signs) -> Signs {
+        let non_negative = !self.negative && !other.negative;
+        let non_positive = !self.positive && !other.positive;
+        if non_negative || non_positive {
+            Signs {
+                negative: self.negative || other.negative,
+                zero: self.zero && other.zero,
+                positive: self.positive || other.positive,
+            }
+        } else {
+            Signs::ANY
+        }
+    }
+
+    fn multiply(self, other: Signs) -> Signs {
+        Signs {
+            negative: self.negative && other.positive || self.positive && other.negative,
+            zero: self.zero || other.zero,
+            positive: self.positive && other.positive || self.negative && other.negative,
+        }
+    }
+}
+
+/// 变量假设表
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Assumptions {
+    facts: HashMap<String, Vec<Property>>,
+}
+
+impl Assumptions {
+    /// 创建空的假设表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 解析 `x > 0`、`n is integer` 形式的假设
+    pub fn parse(statement: &str) -> Result<(String, Property), ComputeError> {
+        let statement = statement.trim();
+        let split = statement.find(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .ok_or_else(|| ComputeError::domain_error(tr!("assumptions.unrecognized", assumption = statement)))?;
+        let (var, property) = statement.split_at(split);
+        if var.is_empty() {
+            return Err(ComputeError::domain_error(tr!("assumptions.unrecognized", assumption = statement)));
+        }
+        Ok((var.to_string(), property.parse()?))
+    }
+
+    /// 增加一条假设，与已有假设矛盾时报错
+    pub fn assume(&mut self, var: &str, property: Property) -> Result<(), ComputeError> {
+        let properties = self.facts.entry(var.to_string()).or_default();
+        if properties.contains(&property) {
+            return Ok(());
+        }
+        let signs = properties.iter().chain([&property]).filter_map(|p| p.signs()).fold(Signs::ANY, Signs::intersect);
+        if !(signs.negative || signs.zero || signs.positive) {
+            return Err(ComputeError::domain_error(tr!("assumptions.contradiction", variable = var, property = property)));
+        }
+        properties.push(property);
+        Ok(())
+    }
+
+    /// 去掉变量的全部假设
+    pub fn forget(&mut self, var: &str) {
+        self.facts.remove(var);
+    }
+
+    /// 清空假设
+    pub fn clear(&mut self) {
+        self.facts.clear();
+    }
+
+    /// 是否没有任何假设
+    pub fn is_empty(&self) -> bool {
+        self.facts.is_empty()
+    }
+
+    /// 变量的全部假设
+    pub fn properties(&self, var: &str) -> &[Property] {
+        self.facts.get(var).map_or(&[], Vec::as_slice)
+    }
+
+    /// 据假设得到的变量数值类型：整数，或除 `!= 0` 外任一性质蕴含的实数；没有相关假设时为 `None`
+    pub fn variable_type(&self, var: &str) -> Option<NumericType> {
+        let properties = self.properties(var);
+        if properties.contains(&Property::Integer) {
+            Some(NumericType::Integer)
+        } else if properties.iter().any(|p| *p != Property::NonZero) {
+            Some(NumericType::Real)
+        } else {
+            None
+        }
+    }
+
+    /// 结合变量假设推断表达式的类型
+    pub fn infer_type(&self, expr: &Expression) -> ExprType {
+        expr.infer_type_with(&|var| self.variable_type(var))
+    }
+
+    /// 表达式是否一定大于 0
+    pub fn is_positive(&self, expr: &Expression) -> bool {
+        let signs = self.signs(expr);
+        self.is_real(expr) && !signs.negative && !signs.zero
+    }
+
+    /// 表达式是否一定大于等于 0
+    pub fn is_nonnegative(&self, expr: &Expression) -> bool {
+        self.is_real(expr) && !self.signs(expr).negative
+    }
+
+    /// 表达式是否一定小于 0
+    pub fn is_negative(&self, expr: &Expression) -> bool {
+        let signs = self.signs(expr);
+        self.is_real(expr) && !signs.positive && !signs.zero
+    }
+
+    /// 表达式是否一定小于等于 0
+    pub fn is_nonpositive(&self, expr: &Expression) -> bool {
+        self.is_real(expr) && !self.signs(expr).positive
+    }
+
+    /// 表达式是否一定是实数
+    pub fn is_real(&self, expr: &Expression) -> bool {
+        match expr {
+            Expression::Number(n) => n.is_real(),
+            Expression::Constant(c) => matches!(c, MathConstant::Pi | MathConstant::E | MathConstant::EulerGamma | MathConstant::GoldenRatio | MathConstant::Catalan),
+            // 除 `!= 0` 外的性质都蕴含实数
+            Expression::Variable(var) => self.properties(var).iter().any(|p| *p != Property::NonZero),
+            Expression::UnaryOp { op: UnaryOperator::Negate | UnaryOperator::Plus, operand } => self.is_real(operand),
+            Expression::BinaryOp { op, left, right } => match op {
+                BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply => self.is_real(left) && self.is_real(right),
+                BinaryOperator::Divide => self.is_real(left) && self.is_real(right) && !self.signs(right).zero,
+                BinaryOperator::Power => {
+                    self.is_real(left) && (self.is_positive(left) && self.is_real(right)
+                        || self.is_integer(right) && (!self.signs(left).zero || self.is_nonnegative(right)))
+                }
+                _ => false,
+            },
+            Expression::Function { name, args } if args.len() == 1 => match name.as_str() {
+                "sin" | "cos" | "tan" | "atan" | "exp" | "abs" | "sinh" | "cosh" | "tanh" | "floor" | "ceil" => self.is_real(&args[0]),
+                "sqrt" => self.is_nonnegative(&args[0]),
+                "ln" | "log" => self.is_positive(&args[0]),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// 表达式是否一定是整数
+    pub fn is_integer(&self, expr: &Expression) -> bool {
+        match expr {
+            Expression::Number(n) => n.is_integer(),
+            Expression::Variable(var) => self.properties(var).contains(&Property::Integer),
+            Expression::UnaryOp { op: UnaryOperator::Negate | UnaryOperator::Plus, operand } => self.is_integer(operand),
+            Expression::BinaryOp { op: BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply, left, right } => {
+                self.is_integer(left) && self.is_integer(right)
+            }
+            Expression::BinaryOp { op: BinaryOperator::Power, left, right } => {
+                self.is_integer(left) && self.is_integer(right) && self.is_nonnegative(right)
+            }
+            Expression::Function { name, args } if args.len() == 1 && matches!(name.as_str(), "abs" | "floor" | "ceil") => {
+                name != "abs" || self.is_integer(&args[0])
+            }
+            _ => false,
+        }
+    }
+
+    /// 表达式可能取的符号，只对实数表达式有意义
+    fn signs(&self, expr: &Expression) -> Signs {
+        match expr {
+            Expression::Number(n) if n.is_real() => Signs::of(n),
+            Expression::Constant(_) if self.is_real(expr) => Signs { negative: false, zero: false, positive: true },
+            Expression::Variable(var) => self.properties(var).iter().filter_map(|p| p.signs()).fold(Signs::ANY, Signs::intersect),
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => self.signs(operand).negate(),
+            Expression::UnaryOp { op: UnaryOperator::Plus, operand } => self.signs(operand),
+            Expression::BinaryOp { op, left, right } => {
+                let (l, r) = (self.signs(left), self.signs(right));
+                match op {
+                    BinaryOperator::Add => l.add(r),
+                    BinaryOperator::Subtract => l.add(r.negate()),
+                    BinaryOperator::Multiply => l.multiply(r),
+                    BinaryOperator::Divide => l.multiply(Signs { zero: false, ..r }),
+                    BinaryOperator::Power => self.power_signs(left, right, l),
+                    _ => Signs::ANY,
+                }
+            }
+            Expression::Function { name, args } if args.len() == 1 => {
+                let arg = self.signs(&args[0]);
+                match name.as_str() {
+                    "abs" => Signs { negative: false, zero: arg.zero, positive: arg.negative || arg.positive },
+                    "sqrt" => Signs { negative: false, zero: arg.zero, positive: arg.positive },
+                    "exp" | "cosh" => Signs { negative: false, zero: false, positive: true },
+                    _ => Signs::ANY,
+                }
+            }
+            _ => Signs::ANY,
+        }
+    }
+
+    /// 幂的符号：正底数的实数次幂为正，实数的偶数次幂非负
+    fn power_signs(&self, base: &Expression, exponent: &Expression, base_signs: Signs) -> Signs {
+        if !base_signs.negative && self.is_real(exponent) {
+            return Signs { negative: false, ..base_signs };
+        }
+        let even = matches!(exponent, Expression::Number(n) if n.is_integer() && n.is_even());
+        if even && self.is_real(base) {
+            return Signs { negative: false, zero: base_signs.zero, positive: true };
+        }
+        Signs::ANY
+    }
+}
+
+#[cfg(test)]
+#[path = "assumptions_tests.rs"]
+mod assumptions_tests;
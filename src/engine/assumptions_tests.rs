@@ -0,0 +1,87 @@
+//! # 变量假设测试
+//!
+//! 测试假设的解析、矛盾检查，以及据假设判断表达式的符号、实数性与整数性。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::assumptions::{Assumptions, Property};
+    use crate::engine::ComputeError;
+    use crate::core::{ExprType, NumericType};
+    use crate::test_utils::parse;
+
+    #[test]
+    fn test_parse_assumption() {
+        assert_eq!(Assumptions::parse("x > 0").unwrap(), ("x".to_string(), Property::Positive));
+        assert_eq!(Assumptions::parse("y>=0").unwrap(), ("y".to_string(), Property::NonNegative));
+        assert_eq!(Assumptions::parse("n is integer").unwrap(), ("n".to_string(), Property::Integer));
+        assert_eq!(Assumptions::parse("z is real").unwrap(), ("z".to_string(), Property::Real));
+        assert_eq!("negative".parse::<Property>().unwrap(), Property::Negative);
+        assert!(matches!(Assumptions::parse("x ~ 0"), Err(ComputeError::DomainError { .. })));
+        assert!(matches!(Assumptions::parse("> 0"), Err(ComputeError::DomainError { .. })));
+    }
+
+    #[test]
+    fn test_contradiction() {
+        let mut assumptions = Assumptions::new();
+        assumptions.assume("x", Property::Positive).unwrap();
+        assumptions.assume("x", Property::NonZero).unwrap();
+        assert!(matches!(assumptions.assume("x", Property::NonPositive), Err(ComputeError::DomainError { .. })));
+        assert_eq!(assumptions.properties("x"), &[Property::Positive, Property::NonZero]);
+        assumptions.forget("x");
+        assert!(assumptions.is_empty());
+    }
+
+    #[test]
+    fn test_sign_reasoning() {
+        let mut assumptions = Assumptions::new();
+        assumptions.assume("x", Property::Positive).unwrap();
+        assumptions.assume("y", Property::NonPositive).unwrap();
+        assumptions.assume("z", Property::Real).unwrap();
+        assumptions.assume("n", Property::Integer).unwrap();
+
+        assert!(assumptions.is_positive(&parse("x")));
+        assert!(assumptions.is_positive(&parse("2*x + 1")));
+        assert!(assumptions.is_positive(&parse("x^z")));
+        assert!(assumptions.is_nonnegative(&parse("z^2 + x")));
+        assert!(assumptions.is_nonpositive(&parse("x*y")));
+        assert!(assumptions.is_negative(&parse("-x")));
+        assert!(assumptions.is_nonnegative(&parse("-y")));
+        // 符号无法确定
+        assert!(!assumptions.is_nonnegative(&parse("x + y")));
+        assert!(!assumptions.is_nonnegative(&parse("z")));
+        // 没有假设的变量不一定是实数，平方也不一定非负
+        assert!(!assumptions.is_nonnegative(&parse("w^2")));
+
+        assert!(assumptions.is_real(&parse("sin(z) + x*y")));
+        assert!(!assumptions.is_real(&parse("sqrt(z)")));
+        assert!(assumptions.is_real(&parse("sqrt(x)")));
+        assert!(assumptions.is_integer(&parse("2*n + 1")));
+        assert!(!assumptions.is_integer(&parse("n / 2")));
+    }
+
+    #[test]
+    fn test_variable_type() {
+        let mut assumptions = Assumptions::new();
+        assumptions.assume("n", Property::Integer).unwrap();
+        assumptions.assume("m", Property::Integer).unwrap();
+        assumptions.assume("m", Property::Positive).unwrap();
+        assumptions.assume("x", Property::Positive).unwrap();
+        assumptions.assume("w", Property::NonZero).unwrap();
+
+        assert_eq!(assumptions.variable_type("n"), Some(NumericType::Integer));
+        assert_eq!(assumptions.variable_type("m"), Some(NumericType::Integer));
+        assert_eq!(assumptions.variable_type("x"), Some(NumericType::Real));
+        // `!= 0` 不蕴含实数
+        assert_eq!(assumptions.variable_type("w"), None);
+        assert_eq!(assumptions.variable_type("y"), None);
+
+        // 整数变量取模仍是整数
+        let modulo = parse("n % m");
+        assert_eq!(assumptions.infer_type(&modulo), ExprType::Numeric(NumericType::Integer));
+        assert_eq!(modulo.infer_type(), ExprType::Symbolic);
+        assert_eq!(assumptions.infer_type(&parse("n * m + 1")), ExprType::Numeric(NumericType::Integer));
+        assert_eq!(assumptions.infer_type(&parse("n + x")), ExprType::Numeric(NumericType::Real));
+        assert_eq!(assumptions.infer_type(&parse("n % x")), ExprType::Symbolic);
+        assert_eq!(assumptions.infer_type(&parse("n + y")), ExprType::Symbolic);
+    }
+}
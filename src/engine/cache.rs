@@ -7,8 +7,10 @@ use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 use std::time::{Duration, Instant};
 use std::sync::{Arc, RwLock};
-use crate::core::{Expression, Number, BinaryOperator, UnaryOperator};
+use crate::core::{binary, Expression, HashedExpression, Number, BinaryOperator, UnaryOperator};
 use crate::api::CacheConfig;
+use super::ComputeError;
+use crate::tr;
 
 /// 缓存项的元数据
 #[derive(Debug, Clone)]
@@ -23,6 +25,8 @@ pub struct CacheEntry<T> {
     pub access_count: u64,
     /// 计算成本（用于优先级排序）
     pub compute_cost: u32,
+    /// 估算占用的内存（字节），包括键
+    pub size: usize,
 }
 
 impl<T> CacheEntry<T> {
@@ -35,9 +39,16 @@ impl<T> CacheEntry<T> {
             last_accessed: now,
             access_count: 1,
             compute_cost,
+            size: 0,
         }
     }
     
+    /// 设置估算的内存占用
+    pub fn with_size(mut self, size: usize) -> Self {
+        self.size = size;
+        self
+    }
+    
     /// 更新访问信息
     pub fn access(&mut self) {
         self.last_accessed = Instant::now();
@@ -60,6 +71,43 @@ impl<T> CacheEntry<T> {
     }
 }
 
+/// 缓存键和值的内存占用估算
+trait MemoryFootprint {
+    /// 估算占用的字节数，包括堆上的部分
+    fn footprint(&self) -> usize;
+}
+
+impl MemoryFootprint for i64 {
+    fn footprint(&self) -> usize {
+        std::mem::size_of::<i64>()
+    }
+}
+
+impl MemoryFootprint for Number {
+    fn footprint(&self) -> usize {
+        let bigint_bytes = |n: &num_bigint::BigInt| (n.bits() as usize).div_ceil(8);
+        std::mem::size_of::<Number>() + match self {
+            Number::Integer(n) => bigint_bytes(n),
+            Number::Rational(r) => bigint_bytes(r.numer()) + bigint_bytes(r.denom()),
+            Number::Real(r) => (r.digits() as usize).div_ceil(2),
+            Number::Complex { real, imaginary } => real.footprint() + imaginary.footprint(),
+            Number::Symbolic(expr) => expr.footprint(),
+            Number::Constant(_) | Number::Float(_) => 0,
+        }
+    }
+}
+
+impl MemoryFootprint for Expression {
+    fn footprint(&self) -> usize {
+        let own = match self {
+            Expression::Number(n) => n.footprint(),
+            Expression::Variable(name) | Expression::Function { name, .. } => std::mem::size_of::<Expression>() + name.len(),
+            _ => std::mem::size_of::<Expression>(),
+        };
+        own + self.children().into_iter().map(MemoryFootprint::footprint).sum::<usize>()
+    }
+}
+
 /// 快速缓存键（用于小整数运算）
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum FastCacheKey {
@@ -71,6 +119,15 @@ pub enum FastCacheKey {
     Function(String, Vec<i64>),
 }
 
+impl MemoryFootprint for FastCacheKey {
+    fn footprint(&self) -> usize {
+        std::mem::size_of::<Self>() + match self {
+            FastCacheKey::Function(name, args) => name.len() + args.len() * std::mem::size_of::<i64>(),
+            _ => 0,
+        }
+    }
+}
+
 /// 精确缓存键（用于任意精度运算）
 #[derive(Debug, Clone)]
 pub struct ExactCacheKey {
@@ -165,11 +222,19 @@ impl Hash for ExactCacheKey {
     }
 }
 
+impl MemoryFootprint for ExactCacheKey {
+    fn footprint(&self) -> usize {
+        std::mem::size_of::<Self>() + self.operand1.footprint()
+            + self.operand2.as_ref().map_or(0, MemoryFootprint::footprint)
+            + self.operation.len()
+    }
+}
+
 /// 符号缓存键（用于符号简化结果）
 #[derive(Debug, Clone)]
 pub struct SymbolicCacheKey {
-    /// 表达式
-    pub expression: Expression,
+    /// 表达式，结构哈希只在构造键时计算一次
+    pub expression: HashedExpression,
     /// 操作类型
     pub operation: String,
     /// 变量（如果适用）
@@ -188,13 +253,32 @@ impl Eq for SymbolicCacheKey {}
 
 impl Hash for SymbolicCacheKey {
     fn hash<H: Hasher>(&self, state: &mut H) {
-        format!("{:?}", self.expression).hash(state);
+        self.expression.hash(state);
         self.operation.hash(state);
         self.variable.hash(state);
     }
 }
 
+impl MemoryFootprint for SymbolicCacheKey {
+    fn footprint(&self) -> usize {
+        std::mem::size_of::<Self>() + self.expression.expression().footprint()
+            + self.operation.len()
+            + self.variable.as_ref().map_or(0, String::len)
+    }
+}
+
+/// 一层缓存
+type CacheLayer<K, T> = RwLock<HashMap<K, CacheEntry<T>>>;
+
+/// 一层缓存中各条目估算占用的内存之和
+fn layer_memory<K, T>(cache: &HashMap<K, CacheEntry<T>>) -> usize {
+    cache.values().map(|entry| entry.size).sum()
+}
+
 /// 多层缓存系统
+///
+/// 每层的条目数不超过各自的容量，各层合计的估算内存不超过 [`CacheConfig::max_memory`]；
+/// 写入时超出任一上限，就在写入的那一层按最后访问时间淘汰最久未使用的条目。
 #[derive(Debug)]
 pub struct ComputeCache {
     /// 快速缓存：小整数运算结果
@@ -226,6 +310,8 @@ pub struct CacheStats {
     pub symbolic_misses: u64,
     /// 缓存清理次数
     pub cleanup_count: u64,
+    /// 因超出容量或内存上限被淘汰的条目数
+    pub evictions: u64,
     /// 总节省的计算时间（估算）
     pub total_time_saved: Duration,
 }
@@ -318,14 +404,7 @@ impl ComputeCache {
             return;
         }
         
-        let mut cache = self.fast_cache.write().unwrap();
-        
-        // 检查缓存大小限制
-        if cache.len() >= self.config.fast_cache_size {
-            self.cleanup_fast_cache(&mut cache);
-        }
-        
-        cache.insert(key, CacheEntry::new(value, compute_cost));
+        self.insert(&self.fast_cache, self.config.fast_cache_size, key, value, compute_cost);
     }
     
     /// 获取精确缓存中的值
@@ -360,14 +439,7 @@ impl ComputeCache {
             return;
         }
         
-        let mut cache = self.exact_cache.write().unwrap();
-        
-        // 检查缓存大小限制
-        if cache.len() >= self.config.exact_cache_size {
-            self.cleanup_exact_cache(&mut cache);
-        }
-        
-        cache.insert(key, CacheEntry::new(value, compute_cost));
+        self.insert(&self.exact_cache, self.config.exact_cache_size, key, value, compute_cost);
     }
     
     /// 获取符号缓存中的值
@@ -402,89 +474,65 @@ impl ComputeCache {
             return;
         }
         
-        let mut cache = self.symbolic_cache.write().unwrap();
-        
-        // 检查缓存大小限制
-        if cache.len() >= self.config.symbolic_cache_size {
-            self.cleanup_symbolic_cache(&mut cache);
-        }
-        
-        cache.insert(key, CacheEntry::new(value, compute_cost));
+        self.insert(&self.symbolic_cache, self.config.symbolic_cache_size, key, value, compute_cost);
     }
     
-    /// 清理快速缓存
-    fn cleanup_fast_cache(&self, cache: &mut HashMap<FastCacheKey, CacheEntry<i64>>) {
-        let target_size = self.config.fast_cache_size * 3 / 4; // 清理到75%
-        
-        if cache.len() <= target_size {
+    /// 写入一层缓存；条目数达到容量或内存将超出上限时，先按最近最少使用淘汰到上限的 75%
+    ///
+    /// 单个条目就超出内存上限时不缓存。
+    fn insert<K, T>(&self, layer: &CacheLayer<K, T>, capacity: usize, key: K, value: T, compute_cost: u32)
+    where
+        K: Hash + Eq + Clone + MemoryFootprint,
+        T: MemoryFootprint,
+    {
+        let size = key.footprint() + value.footprint();
+        let memory_limit = self.memory_limit(layer);
+        if memory_limit.is_some_and(|limit| size > limit) {
             return;
         }
         
-        // 收集所有项目及其优先级
-        let mut items: Vec<_> = cache.iter()
-            .map(|(key, entry)| (key.clone(), entry.priority()))
-            .collect();
-        
-        // 按优先级排序（低优先级先删除）
-        items.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
-        
-        // 删除低优先级项目
-        let to_remove = cache.len() - target_size;
-        for (key, _) in items.iter().take(to_remove) {
-            cache.remove(key);
+        let mut cache = layer.write().unwrap();
+        cache.remove(&key);
+        let over_capacity = cache.len() >= capacity;
+        let over_memory = memory_limit.is_some_and(|limit| layer_memory(&cache) + size > limit);
+        if over_capacity || over_memory {
+            let max_entries = if over_capacity { capacity * 3 / 4 } else { capacity };
+            let max_memory = memory_limit.map(|limit| if over_memory { (limit - size) * 3 / 4 } else { limit - size });
+            self.evict_lru(&mut cache, max_entries, max_memory);
         }
         
-        self.record_cleanup();
+        cache.insert(key, CacheEntry::new(value, compute_cost).with_size(size));
     }
     
-    /// 清理精确缓存
-    fn cleanup_exact_cache(&self, cache: &mut HashMap<ExactCacheKey, CacheEntry<Number>>) {
-        let target_size = self.config.exact_cache_size * 3 / 4; // 清理到75%
-        
-        if cache.len() <= target_size {
-            return;
-        }
-        
-        // 收集所有项目及其优先级
-        let mut items: Vec<_> = cache.iter()
-            .map(|(key, entry)| (key.clone(), entry.priority()))
-            .collect();
-        
-        // 按优先级排序（低优先级先删除）
-        items.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
-        
-        // 删除低优先级项目
-        let to_remove = cache.len() - target_size;
-        for (key, _) in items.iter().take(to_remove) {
-            cache.remove(key);
-        }
-        
-        self.record_cleanup();
+    /// 内存上限中留给 `layer` 的部分，即总上限减去其他层已占用的内存
+    ///
+    /// 其他层的占用在取得 `layer` 的写锁之前逐层读取，不会同时持有两层的锁。
+    fn memory_limit<K, T>(&self, layer: &CacheLayer<K, T>) -> Option<usize> {
+        let max = self.config.max_memory?;
+        let others = self.memory_usage().saturating_sub(layer_memory(&layer.read().unwrap()));
+        Some(max.saturating_sub(others))
     }
     
-    /// 清理符号缓存
-    fn cleanup_symbolic_cache(&self, cache: &mut HashMap<SymbolicCacheKey, CacheEntry<Expression>>) {
-        let target_size = self.config.symbolic_cache_size * 3 / 4; // 清理到75%
-        
-        if cache.len() <= target_size {
-            return;
-        }
-        
-        // 收集所有项目及其优先级
+    /// 按最后访问时间从旧到新淘汰，直到条目数不超过 `max_entries`、内存不超过 `max_memory`
+    fn evict_lru<K: Hash + Eq + Clone, T>(&self, cache: &mut HashMap<K, CacheEntry<T>>, max_entries: usize, max_memory: Option<usize>) {
+        let mut memory = layer_memory(cache);
         let mut items: Vec<_> = cache.iter()
-            .map(|(key, entry)| (key.clone(), entry.priority()))
+            .map(|(key, entry)| (key.clone(), entry.last_accessed, entry.size))
             .collect();
+        items.sort_by_key(|(_, last_accessed, _)| *last_accessed);
         
-        // 按优先级排序（低优先级先删除）
-        items.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(std::cmp::Ordering::Equal));
-        
-        // 删除低优先级项目
-        let to_remove = cache.len() - target_size;
-        for (key, _) in items.iter().take(to_remove) {
-            cache.remove(key);
+        let mut evicted = 0;
+        for (key, _, size) in items {
+            if cache.len() <= max_entries && max_memory.is_none_or(|max| memory <= max) {
+                break;
+            }
+            cache.remove(&key);
+            memory -= size;
+            evicted += 1;
         }
         
         self.record_cleanup();
+        self.record_evictions(evicted);
     }
     
     /// 清理所有过期项目
@@ -512,6 +560,34 @@ impl ComputeCache {
         }
     }
     
+    /// 当前的缓存配置
+    pub fn config(&self) -> &CacheConfig {
+        &self.config
+    }
+    
+    /// 更新缓存配置：禁用时清空缓存，容量或内存上限缩小时立即淘汰超出的条目
+    pub fn update_config(&mut self, config: CacheConfig) {
+        self.config = config;
+        if !self.config.enabled {
+            self.clear_all();
+            return;
+        }
+        
+        self.shrink(&self.fast_cache, self.config.fast_cache_size);
+        self.shrink(&self.exact_cache, self.config.exact_cache_size);
+        self.shrink(&self.symbolic_cache, self.config.symbolic_cache_size);
+        self.cleanup_expired();
+    }
+    
+    /// 把一层缓存淘汰到容量和留给它的内存以内
+    fn shrink<K: Hash + Eq + Clone, T>(&self, layer: &CacheLayer<K, T>, capacity: usize) {
+        let memory_limit = self.memory_limit(layer);
+        let mut cache = layer.write().unwrap();
+        if cache.len() > capacity || memory_limit.is_some_and(|limit| layer_memory(&cache) > limit) {
+            self.evict_lru(&mut cache, capacity, memory_limit);
+        }
+    }
+    
     /// 清空所有缓存
     pub fn clear_all(&self) {
         self.fast_cache.write().unwrap().clear();
@@ -520,11 +596,71 @@ impl ComputeCache {
         self.record_cleanup();
     }
     
+    /// 以表达式的紧凑二进制格式导出未过期的符号缓存，用于持久化
+    ///
+    /// 每个条目编码为一个函数调用：函数名是运算名，参数依次为输入表达式、结果、计算成本，
+    /// 有变量时再跟上该变量。各条目之间相同的子表达式只存一次。
+    pub fn export_symbolic(&self) -> Vec<u8> {
+        let cache = self.symbolic_cache.read().unwrap();
+        let entries: Vec<Expression> = cache.iter()
+            .filter(|(_, entry)| self.config.cache_ttl.is_none_or(|ttl| !entry.is_expired(ttl)))
+            .map(|(key, entry)| {
+                let mut args = vec![
+                    key.expression.expression().clone(),
+                    entry.value.clone(),
+                    Expression::number(Number::integer(entry.compute_cost)),
+                ];
+                args.extend(key.variable.as_deref().map(Expression::variable));
+                Expression::function(&key.operation, args)
+            })
+            .collect();
+        binary::encode(&entries.iter().collect::<Vec<_>>())
+    }
+    
+    /// 导入 [`export_symbolic`](Self::export_symbolic) 导出的条目，返回导入的条目数
+    pub fn import_symbolic(&self, bytes: &[u8]) -> Result<usize, ComputeError> {
+        let entries = binary::decode(bytes)?;
+        let mut imported = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let Expression::Function { name, mut args } = entry else {
+                return Err(ComputeError::domain_error(tr!("cache.malformed_entry")));
+            };
+            let variable = match args.len() {
+                3 => None,
+                4 => match args.pop() {
+                    Some(Expression::Variable(variable)) => Some(variable),
+                    _ => return Err(ComputeError::domain_error(tr!("cache.malformed_variable"))),
+                },
+                _ => return Err(ComputeError::domain_error(tr!("cache.malformed_entry"))),
+            };
+            let compute_cost = match args.pop() {
+                Some(Expression::Number(Number::Integer(cost))) => u32::try_from(cost)
+                    .map_err(|_| ComputeError::domain_error(tr!("cache.cost_out_of_range")))?,
+                _ => return Err(ComputeError::domain_error(tr!("cache.malformed_cost"))),
+            };
+            let value = args.pop().unwrap();
+            let expression = args.pop().unwrap();
+            imported.push((SymbolicCacheKey { expression: expression.into(), operation: name, variable }, value, compute_cost));
+        }
+        let count = imported.len();
+        for (key, value, compute_cost) in imported {
+            self.put_symbolic(key, value, compute_cost);
+        }
+        Ok(count)
+    }
+    
     /// 获取缓存统计信息
     pub fn get_stats(&self) -> CacheStats {
         self.stats.read().unwrap().clone()
     }
     
+    /// 各层缓存合计的估算内存占用（字节）
+    pub fn memory_usage(&self) -> usize {
+        layer_memory(&self.fast_cache.read().unwrap())
+            + layer_memory(&self.exact_cache.read().unwrap())
+            + layer_memory(&self.symbolic_cache.read().unwrap())
+    }
+    
     /// 获取缓存使用情况
     pub fn get_usage_info(&self) -> CacheUsageInfo {
         let fast_size = self.fast_cache.read().unwrap().len();
@@ -538,6 +674,8 @@ impl ComputeCache {
             exact_cache_capacity: self.config.exact_cache_size,
             symbolic_cache_usage: symbolic_size,
             symbolic_cache_capacity: self.config.symbolic_cache_size,
+            memory_usage: self.memory_usage(),
+            memory_capacity: self.config.max_memory,
         }
     }
     
@@ -590,6 +728,12 @@ impl ComputeCache {
             stats.cleanup_count += 1;
         }
     }
+    
+    fn record_evictions(&self, count: u64) {
+        if let Ok(mut stats) = self.stats.write() {
+            stats.evictions += count;
+        }
+    }
 }
 
 /// 缓存使用情况信息
@@ -607,6 +751,10 @@ pub struct CacheUsageInfo {
     pub symbolic_cache_usage: usize,
     /// 符号缓存容量
     pub symbolic_cache_capacity: usize,
+    /// 估算的内存占用（字节）
+    pub memory_usage: usize,
+    /// 内存上限（字节），不限制时为 `None`
+    pub memory_capacity: Option<usize>,
 }
 
 impl CacheUsageInfo {
@@ -690,6 +838,11 @@ impl CacheManager {
     pub fn set_cleanup_interval(&mut self, interval: Duration) {
         self.cleanup_interval = interval;
     }
+    
+    /// 更新缓存配置
+    pub fn update_config(&mut self, config: CacheConfig) {
+        self.cache.update_config(config);
+    }
 }
 
 #[cfg(test)]
@@ -785,7 +938,7 @@ mod tests {
         let cache = ComputeCache::new(config);
         
         let key = SymbolicCacheKey {
-            expression: Expression::variable("x"),
+            expression: Expression::variable("x").into(),
             operation: "simplify".to_string(),
             variable: None,
         };
@@ -807,6 +960,29 @@ mod tests {
         assert_eq!(stats.symbolic_misses, 1);
     }
     
+    #[test]
+    fn test_symbolic_cache_export_import() {
+        let cache = ComputeCache::new(CacheConfig::default());
+        let expr = Expression::add(Expression::variable("x"), Expression::variable("x"));
+        let doubled = Expression::multiply(Expression::number(Number::integer(2)), Expression::variable("x"));
+        let simplify_key = SymbolicCacheKey { expression: expr.clone().into(), operation: "simplify".to_string(), variable: None };
+        let diff_key = SymbolicCacheKey { expression: expr.into(), operation: "differentiate".to_string(), variable: Some("x".to_string()) };
+        cache.put_symbolic(simplify_key.clone(), doubled.clone(), 10);
+        cache.put_symbolic(diff_key.clone(), Expression::number(Number::integer(2)), 20);
+        
+        let bytes = cache.export_symbolic();
+        let restored = ComputeCache::new(CacheConfig::default());
+        assert_eq!(restored.import_symbolic(&bytes).unwrap(), 2);
+        assert_eq!(restored.get_symbolic(&simplify_key), Some(doubled));
+        assert_eq!(restored.get_symbolic(&diff_key), Some(Expression::number(Number::integer(2))));
+        
+        // 损坏的数据报错，且不会导入任何条目
+        let empty = ComputeCache::new(CacheConfig::default());
+        assert!(empty.import_symbolic(&bytes[..bytes.len() - 1]).is_err());
+        assert!(empty.import_symbolic(&Expression::variable("x").to_bytes()).is_err());
+        assert_eq!(empty.get_usage_info().symbolic_cache_usage, 0);
+    }
+    
     #[test]
     fn test_cache_size_limits() {
         let config = CacheConfig {
@@ -815,6 +991,8 @@ mod tests {
             exact_cache_size: 2,
             symbolic_cache_size: 2,
             cache_ttl: None,
+            max_memory: None,
+            cost_model: Default::default(),
         };
         
         let cache = ComputeCache::new(config);
@@ -830,6 +1008,81 @@ mod tests {
         assert!(usage.fast_cache_usage <= 2);
     }
     
+    #[test]
+    fn test_lru_eviction() {
+        let cache = ComputeCache::new(CacheConfig::default().with_fast_cache_size(4).without_max_memory());
+        let key = |i: i64| FastCacheKey::BinaryOp(i, i, BinaryOperator::Add);
+        for i in 0..4 {
+            cache.put_fast(key(i), 2 * i, 1);
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        // 最早写入的 0 刚被访问过，最久未使用的是 1
+        assert_eq!(cache.get_fast(&key(0)), Some(0));
+        cache.put_fast(key(4), 8, 1);
+        
+        assert_eq!(cache.get_usage_info().fast_cache_usage, 4);
+        assert_eq!(cache.get_fast(&key(1)), None);
+        assert_eq!(cache.get_fast(&key(0)), Some(0));
+        assert_eq!(cache.get_fast(&key(2)), Some(4));
+        assert_eq!(cache.get_fast(&key(4)), Some(8));
+        assert_eq!(cache.get_stats().evictions, 1);
+    }
+    
+    #[test]
+    fn test_memory_limit_eviction() {
+        let key = |i: i64| SymbolicCacheKey {
+            expression: Expression::power(Expression::variable("x"), Expression::number(Number::integer(i))).into(),
+            operation: "simplify".to_string(),
+            variable: None,
+        };
+        let value = |i: i64| Expression::multiply(Expression::number(Number::integer(i)), Expression::variable("x"));
+        let probe = ComputeCache::new(CacheConfig::default());
+        probe.put_symbolic(key(9), value(9), 10);
+        let entry_size = probe.memory_usage();
+        assert!(entry_size > 0);
+        
+        // 内存只够放三条，条目数上限远未达到
+        let cache = ComputeCache::new(CacheConfig::default().with_max_memory(entry_size * 3));
+        for i in 1..=3 {
+            cache.put_symbolic(key(i), value(i), 10);
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(cache.get_stats().evictions, 0);
+        cache.put_symbolic(key(4), value(4), 10);
+        
+        let usage = cache.get_usage_info();
+        assert!(usage.memory_usage <= entry_size * 3);
+        assert_eq!(usage.memory_capacity, Some(entry_size * 3));
+        assert!(cache.get_symbolic(&key(1)).is_none());
+        assert_eq!(cache.get_symbolic(&key(4)), Some(value(4)));
+        assert!(cache.get_stats().evictions >= 1);
+        
+        // 单个条目就超出上限时不缓存
+        let tiny = ComputeCache::new(CacheConfig::default().with_max_memory(entry_size / 2));
+        tiny.put_symbolic(key(9), value(9), 10);
+        assert_eq!(tiny.get_usage_info().symbolic_cache_usage, 0);
+    }
+    
+    #[test]
+    fn test_update_config_shrinks_cache() {
+        let mut cache = ComputeCache::new(CacheConfig::default());
+        for i in 0..8 {
+            cache.put_fast(FastCacheKey::BinaryOp(i, i, BinaryOperator::Add), 2 * i, 1);
+        }
+        assert_eq!(cache.get_usage_info().fast_cache_usage, 8);
+        
+        // 容量缩小后立即淘汰到新容量以内
+        cache.update_config(CacheConfig::default().with_fast_cache_size(4));
+        let usage = cache.get_usage_info();
+        assert_eq!(usage.fast_cache_capacity, 4);
+        assert!(usage.fast_cache_usage <= 4);
+        
+        // 禁用缓存会清空已有条目，之后的写入被忽略
+        cache.update_config(CacheConfig::default().with_enabled(false));
+        cache.put_fast(FastCacheKey::BinaryOp(1, 1, BinaryOperator::Add), 2, 1);
+        assert_eq!(cache.get_usage_info().fast_cache_usage, 0);
+    }
+    
     #[test]
     fn test_cache_stats() {
         let config = CacheConfig::default();
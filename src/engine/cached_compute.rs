@@ -3,13 +3,16 @@
 //! 实现集成多层缓存系统的高性能计算引擎。
 
 use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
 use std::sync::{Arc, Mutex};
-use crate::core::{Expression, Number, MathConstant, BinaryOperator};
-use crate::api::CacheConfig;
+use crate::core::{Expression, HashedExpression, Number, MathConstant, BinaryOperator, CostModel};
+use crate::api::{CacheConfig, YufmathError};
 use super::{ComputeEngine, ComputeError};
 use super::compute::BasicComputeEngine;
 use super::cache::{CacheManager, FastCacheKey, ExactCacheKey, SymbolicCacheKey};
 use num_bigint::BigInt;
+use crate::tr;
 
 /// 带缓存的计算引擎
 pub struct CachedComputeEngine {
@@ -31,7 +34,7 @@ impl CachedComputeEngine {
     /// 获取缓存统计信息
     pub fn get_cache_stats(&self) -> Result<super::cache::CacheStats, ComputeError> {
         self.cache_manager.lock()
-            .map_err(|_| ComputeError::internal("无法获取缓存管理器锁"))?
+            .map_err(|_| ComputeError::internal(tr!("engine.cache_manager_lock")))?
             .cache()
             .get_stats()
             .pipe(Ok)
@@ -40,7 +43,7 @@ impl CachedComputeEngine {
     /// 获取缓存使用情况
     pub fn get_cache_usage(&self) -> Result<super::cache::CacheUsageInfo, ComputeError> {
         self.cache_manager.lock()
-            .map_err(|_| ComputeError::internal("无法获取缓存管理器锁"))?
+            .map_err(|_| ComputeError::internal(tr!("engine.cache_manager_lock")))?
             .cache()
             .get_usage_info()
             .pipe(Ok)
@@ -49,7 +52,7 @@ impl CachedComputeEngine {
     /// 清理缓存
     pub fn cleanup_cache(&self) -> Result<(), ComputeError> {
         self.cache_manager.lock()
-            .map_err(|_| ComputeError::internal("无法获取缓存管理器锁"))?
+            .map_err(|_| ComputeError::internal(tr!("engine.cache_manager_lock")))?
             .force_cleanup();
         Ok(())
     }
@@ -57,12 +60,40 @@ impl CachedComputeEngine {
     /// 清空所有缓存
     pub fn clear_cache(&self) -> Result<(), ComputeError> {
         self.cache_manager.lock()
-            .map_err(|_| ComputeError::internal("无法获取缓存管理器锁"))?
+            .map_err(|_| ComputeError::internal(tr!("engine.cache_manager_lock")))?
             .cache()
             .clear_all();
         Ok(())
     }
     
+    /// 把符号缓存以紧凑二进制格式保存到文件，返回写入的字节数
+    pub fn save_cache(&self, path: impl AsRef<Path>) -> Result<usize, YufmathError> {
+        let bytes = self.cache_manager.lock()
+            .map_err(|_| ComputeError::internal(tr!("engine.cache_manager_lock")))?
+            .cache()
+            .export_symbolic();
+        fs::write(path, &bytes)?;
+        Ok(bytes.len())
+    }
+    
+    /// 从 [`save_cache`](Self::save_cache) 保存的文件恢复符号缓存，返回恢复的条目数
+    pub fn load_cache(&self, path: impl AsRef<Path>) -> Result<usize, YufmathError> {
+        let bytes = fs::read(path)?;
+        let count = self.cache_manager.lock()
+            .map_err(|_| ComputeError::internal(tr!("engine.cache_manager_lock")))?
+            .cache()
+            .import_symbolic(&bytes)?;
+        Ok(count)
+    }
+    
+    /// 更新缓存配置，容量缩小或禁用缓存时立即生效
+    pub fn update_cache_config(&self, config: CacheConfig) -> Result<(), ComputeError> {
+        self.cache_manager.lock()
+            .map_err(|_| ComputeError::internal(tr!("engine.cache_manager_lock")))?
+            .update_config(config);
+        Ok(())
+    }
+    
     /// 尝试从快速缓存获取二元运算结果
     fn try_fast_binary_op(&self, left: &Number, right: &Number, op: &BinaryOperator) -> Option<Number> {
         // 只对小整数使用快速缓存
@@ -143,29 +174,47 @@ impl CachedComputeEngine {
         }
     }
     
-    /// 尝试从符号缓存获取结果
-    fn try_symbolic_cache(&self, expr: &Expression, operation: &str, variable: Option<&str>) -> Option<Expression> {
+    /// 化简已计算结构哈希的表达式。反复化简同一个大表达式时复用这个键，
+    /// 查询缓存不再克隆和重新哈希整棵树
+    pub fn simplify_hashed(&self, expr: &HashedExpression) -> Result<Expression, ComputeError> {
+        self.cached_symbolic(expr, "simplify", None, 1, |expr| self.base_engine.simplify(expr))
+    }
+    
+    /// 查询符号缓存，未命中时计算并按表达式复杂度的 `cost_factor` 倍存入。
+    /// 查询与存入共用同一个键，结构哈希只计算一次
+    fn cached_symbolic<F>(&self, expr: &HashedExpression, operation: &str, variable: Option<&str>, cost_factor: u32, compute: F) -> Result<Expression, ComputeError>
+    where
+        F: FnOnce(&Expression) -> Result<Expression, ComputeError>,
+    {
+        // 执行定期清理
+        self.periodic_cleanup();
+        
         let key = SymbolicCacheKey {
             expression: expr.clone(),
             operation: operation.to_string(),
             variable: variable.map(|s| s.to_string()),
         };
+        if let Some(cached_result) = self.try_symbolic_cache(&key) {
+            return Ok(cached_result);
+        }
         
+        let result = compute(expr.expression())?;
+        let complexity = self.compute_complexity(expr.expression());
+        self.cache_symbolic_result(key, &result, complexity.saturating_mul(cost_factor));
+        Ok(result)
+    }
+    
+    /// 尝试从符号缓存获取结果
+    fn try_symbolic_cache(&self, key: &SymbolicCacheKey) -> Option<Expression> {
         if let Ok(cache_manager) = self.cache_manager.lock() {
-            cache_manager.cache().get_symbolic(&key)
+            cache_manager.cache().get_symbolic(key)
         } else {
             None
         }
     }
     
     /// 将符号运算结果存入缓存
-    fn cache_symbolic_result(&self, expr: &Expression, operation: &str, variable: Option<&str>, result: &Expression, cost: u32) {
-        let key = SymbolicCacheKey {
-            expression: expr.clone(),
-            operation: operation.to_string(),
-            variable: variable.map(|s| s.to_string()),
-        };
-        
+    fn cache_symbolic_result(&self, key: SymbolicCacheKey, result: &Expression, cost: u32) {
         if let Ok(cache_manager) = self.cache_manager.lock() {
             cache_manager.cache().put_symbolic(key, result.clone(), cost);
         }
@@ -178,26 +227,13 @@ impl CachedComputeEngine {
         }
     }
     
-    /// 计算表达式复杂度（用于确定缓存成本）
+    /// 计算表达式复杂度（用于确定缓存成本），按缓存配置中的成本模型加权
     fn compute_complexity(&self, expr: &Expression) -> u32 {
-        match expr {
-            Expression::Number(_) | Expression::Variable(_) | Expression::Constant(_) => 1,
-            Expression::UnaryOp { operand, .. } => 1 + self.compute_complexity(operand),
-            Expression::BinaryOp { left, right, .. } => 1 + self.compute_complexity(left) + self.compute_complexity(right),
-            Expression::Function { args, .. } => 5 + args.iter().map(|arg| self.compute_complexity(arg)).sum::<u32>(),
-            Expression::Matrix(rows) => {
-                10 + rows.iter().flat_map(|row| row.iter()).map(|elem| self.compute_complexity(elem)).sum::<u32>()
-            }
-            Expression::Vector(elements) => {
-                5 + elements.iter().map(|elem| self.compute_complexity(elem)).sum::<u32>()
-            }
-            Expression::Set(elements) => {
-                3 + elements.iter().map(|elem| self.compute_complexity(elem)).sum::<u32>()
-            }
-            Expression::Interval { start, end, .. } => {
-                2 + self.compute_complexity(start) + self.compute_complexity(end)
-            }
-        }
+        let cost = match self.cache_manager.lock() {
+            Ok(cache_manager) => expr.cost(&cache_manager.cache().config().cost_model),
+            Err(_) => expr.cost(&CostModel::default()),
+        };
+        u32::try_from(cost).unwrap_or(u32::MAX)
     }
 }
 
@@ -206,22 +242,12 @@ impl ComputeEngine for CachedComputeEngine {
         self
     }
     fn simplify(&self, expr: &Expression) -> Result<Expression, ComputeError> {
-        // 执行定期清理
-        self.periodic_cleanup();
-        
-        // 尝试从符号缓存获取结果
-        if let Some(cached_result) = self.try_symbolic_cache(expr, "simplify", None) {
-            return Ok(cached_result);
-        }
-        
-        // 执行实际计算
-        let result = self.base_engine.simplify(expr)?;
-        
-        // 缓存结果
-        let complexity = self.compute_complexity(expr);
-        self.cache_symbolic_result(expr, "simplify", None, &result, complexity);
-        
-        Ok(result)
+        self.simplify_hashed(&HashedExpression::new(expr.clone()))
+    }
+    
+    fn simplify_traced(&self, expr: &Expression) -> Result<(Expression, Vec<super::TraceEvent>), ComputeError> {
+        // 追踪需要实际执行每条规则，不使用缓存
+        self.base_engine.simplify_traced(expr)
     }
     
     fn evaluate(&self, expr: &Expression, vars: &HashMap<String, Number>) -> Result<Number, ComputeError> {
@@ -254,98 +280,23 @@ impl ComputeEngine for CachedComputeEngine {
     }
     
     fn differentiate(&self, expr: &Expression, var: &str) -> Result<Expression, ComputeError> {
-        // 执行定期清理
-        self.periodic_cleanup();
-        
-        // 尝试从符号缓存获取结果
-        if let Some(cached_result) = self.try_symbolic_cache(expr, "differentiate", Some(var)) {
-            return Ok(cached_result);
-        }
-        
-        // 执行实际计算
-        let result = self.base_engine.differentiate(expr, var)?;
-        
-        // 缓存结果
-        let complexity = self.compute_complexity(expr);
-        self.cache_symbolic_result(expr, "differentiate", Some(var), &result, complexity * 2);
-        
-        Ok(result)
+        self.cached_symbolic(&HashedExpression::new(expr.clone()), "differentiate", Some(var), 2, |expr| self.base_engine.differentiate(expr, var))
     }
     
     fn integrate(&self, expr: &Expression, var: &str) -> Result<Expression, ComputeError> {
-        // 执行定期清理
-        self.periodic_cleanup();
-        
-        // 尝试从符号缓存获取结果
-        if let Some(cached_result) = self.try_symbolic_cache(expr, "integrate", Some(var)) {
-            return Ok(cached_result);
-        }
-        
-        // 执行实际计算
-        let result = self.base_engine.integrate(expr, var)?;
-        
-        // 缓存结果
-        let complexity = self.compute_complexity(expr);
-        self.cache_symbolic_result(expr, "integrate", Some(var), &result, complexity * 5);
-        
-        Ok(result)
+        self.cached_symbolic(&HashedExpression::new(expr.clone()), "integrate", Some(var), 5, |expr| self.base_engine.integrate(expr, var))
     }
     
     fn expand(&self, expr: &Expression) -> Result<Expression, ComputeError> {
-        // 执行定期清理
-        self.periodic_cleanup();
-        
-        // 尝试从符号缓存获取结果
-        if let Some(cached_result) = self.try_symbolic_cache(expr, "expand", None) {
-            return Ok(cached_result);
-        }
-        
-        // 执行实际计算
-        let result = self.base_engine.expand(expr)?;
-        
-        // 缓存结果
-        let complexity = self.compute_complexity(expr);
-        self.cache_symbolic_result(expr, "expand", None, &result, complexity * 3);
-        
-        Ok(result)
+        self.cached_symbolic(&HashedExpression::new(expr.clone()), "expand", None, 3, |expr| self.base_engine.expand(expr))
     }
     
     fn factor(&self, expr: &Expression) -> Result<Expression, ComputeError> {
-        // 执行定期清理
-        self.periodic_cleanup();
-        
-        // 尝试从符号缓存获取结果
-        if let Some(cached_result) = self.try_symbolic_cache(expr, "factor", None) {
-            return Ok(cached_result);
-        }
-        
-        // 执行实际计算
-        let result = self.base_engine.factor(expr)?;
-        
-        // 缓存结果
-        let complexity = self.compute_complexity(expr);
-        self.cache_symbolic_result(expr, "factor", None, &result, complexity * 4);
-        
-        Ok(result)
+        self.cached_symbolic(&HashedExpression::new(expr.clone()), "factor", None, 4, |expr| self.base_engine.factor(expr))
     }
     
     fn collect(&self, expr: &Expression, var: &str) -> Result<Expression, ComputeError> {
-        // 执行定期清理
-        self.periodic_cleanup();
-        
-        // 尝试从符号缓存获取结果
-        if let Some(cached_result) = self.try_symbolic_cache(expr, "collect", Some(var)) {
-            return Ok(cached_result);
-        }
-        
-        // 执行实际计算
-        let result = self.base_engine.collect(expr, var)?;
-        
-        // 缓存结果
-        let complexity = self.compute_complexity(expr);
-        self.cache_symbolic_result(expr, "collect", Some(var), &result, complexity * 2);
-        
-        Ok(result)
+        self.cached_symbolic(&HashedExpression::new(expr.clone()), "collect", Some(var), 2, |expr| self.base_engine.collect(expr, var))
     }
     
     // 对于其他方法，直接委托给基础引擎（可以根据需要添加缓存）
@@ -358,6 +309,30 @@ impl ComputeEngine for CachedComputeEngine {
         self.base_engine.series(expr, var, point, order)
     }
     
+    fn sum(&self, expr: &Expression, var: &str, lower: &Expression, upper: &Expression) -> Result<super::SeriesSum, ComputeError> {
+        self.base_engine.sum(expr, var, lower, upper)
+    }
+    
+    fn laplace(&self, expr: &Expression, t: &str, s: &str) -> Result<super::LaplaceTransform, ComputeError> {
+        self.base_engine.laplace(expr, t, s)
+    }
+    
+    fn inverse_laplace(&self, expr: &Expression, s: &str, t: &str) -> Result<Expression, ComputeError> {
+        self.base_engine.inverse_laplace(expr, s, t)
+    }
+    
+    fn truth_table(&self, expr: &Expression, vars: &[String]) -> Result<super::TruthTable, ComputeError> {
+        self.base_engine.truth_table(expr, vars)
+    }
+    
+    fn to_dnf(&self, expr: &Expression) -> Result<Expression, ComputeError> {
+        self.base_engine.to_dnf(expr)
+    }
+    
+    fn to_cnf(&self, expr: &Expression) -> Result<Expression, ComputeError> {
+        self.base_engine.to_cnf(expr)
+    }
+    
     fn numerical_evaluate(&self, expr: &Expression, vars: &HashMap<String, f64>) -> Result<f64, ComputeError> {
         self.base_engine.numerical_evaluate(expr, vars)
     }
@@ -386,6 +361,14 @@ impl ComputeEngine for CachedComputeEngine {
         self.base_engine.lcm(a, b)
     }
     
+    fn gcd_list(&self, values: &[Expression]) -> Result<Expression, ComputeError> {
+        self.base_engine.gcd_list(values)
+    }
+    
+    fn lcm_list(&self, values: &[Expression]) -> Result<Expression, ComputeError> {
+        self.base_engine.lcm_list(values)
+    }
+    
     fn is_prime(&self, n: &Expression) -> Result<bool, ComputeError> {
         self.base_engine.is_prime(n)
     }
@@ -394,6 +377,10 @@ impl ComputeEngine for CachedComputeEngine {
         self.base_engine.prime_factors(n)
     }
     
+    fn set_random_seed(&self, seed: u64) {
+        self.base_engine.set_random_seed(seed)
+    }
+    
     fn binomial(&self, n: &Expression, k: &Expression) -> Result<Expression, ComputeError> {
         self.base_engine.binomial(n, k)
     }
@@ -418,6 +405,34 @@ impl ComputeEngine for CachedComputeEngine {
         self.base_engine.solve(equation, var)
     }
     
+    fn solve_detailed(&self, equation: &Expression, var: &str) -> Result<Vec<super::Solution>, ComputeError> {
+        self.base_engine.solve_detailed(equation, var)
+    }
+    
+    fn domain(&self, expr: &Expression, var: &str) -> Result<super::DomainAnalysis, ComputeError> {
+        self.base_engine.domain(expr, var)
+    }
+    
+    fn range_estimate(&self, expr: &Expression, var: &str, domain: &Expression) -> Result<super::DomainAnalysis, ComputeError> {
+        self.base_engine.range_estimate(expr, var, domain)
+    }
+    
+    fn critical_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError> {
+        self.base_engine.critical_points(expr, var)
+    }
+    
+    fn local_extrema(&self, expr: &Expression, var: &str) -> Result<Vec<super::Extremum>, ComputeError> {
+        self.base_engine.local_extrema(expr, var)
+    }
+    
+    fn inflection_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError> {
+        self.base_engine.inflection_points(expr, var)
+    }
+    
+    fn are_equivalent(&self, lhs: &Expression, rhs: &Expression) -> Result<super::Equivalence, ComputeError> {
+        self.base_engine.are_equivalent(lhs, rhs)
+    }
+    
     fn solve_system(&self, equations: &[Expression], vars: &[String]) -> Result<Vec<HashMap<String, Expression>>, ComputeError> {
         self.base_engine.solve_system(equations, vars)
     }
@@ -512,6 +527,18 @@ mod tests {
         assert_eq!(usage.total_usage_rate(), 0.0);
     }
     
+    #[test]
+    fn test_configured_cost_model() {
+        let expr = Expression::function("sin", vec![Expression::variable("x")]);
+        let engine = CachedComputeEngine::new(CacheConfig::default());
+        let default_cost = engine.compute_complexity(&expr);
+        
+        let model = CostModel { function: 1000, ..CostModel::default() };
+        engine.update_cache_config(CacheConfig::default().with_cost_model(model.clone())).unwrap();
+        assert_eq!(u64::from(engine.compute_complexity(&expr)), expr.cost(&model));
+        assert!(engine.compute_complexity(&expr) > default_cost);
+    }
+    
     #[test]
     fn test_fast_cache_integration() {
         let config = CacheConfig::default();
@@ -563,6 +590,33 @@ mod tests {
         assert!(stats.symbolic_hits > 0);
     }
     
+    #[test]
+    fn test_cache_persistence() {
+        let engine = CachedComputeEngine::new(CacheConfig::default());
+        let expr = Expression::BinaryOp {
+            op: BinaryOperator::Add,
+            left: Box::new(Expression::Variable("x".to_string())),
+            right: Box::new(Expression::Variable("x".to_string())),
+        };
+        let simplified = engine.simplify(&expr).unwrap();
+        let derivative = engine.differentiate(&expr, "x").unwrap();
+        
+        let file = tempfile::NamedTempFile::new().unwrap();
+        assert!(engine.save_cache(file.path()).unwrap() > 0);
+        
+        // 新引擎读入缓存后，同样的运算直接命中
+        let restored = CachedComputeEngine::new(CacheConfig::default());
+        assert_eq!(restored.load_cache(file.path()).unwrap(), 2);
+        assert_eq!(restored.simplify(&expr).unwrap(), simplified);
+        assert_eq!(restored.differentiate(&expr, "x").unwrap(), derivative);
+        let stats = restored.get_cache_stats().unwrap();
+        assert_eq!(stats.symbolic_hits, 2);
+        assert_eq!(stats.symbolic_misses, 0);
+        
+        std::fs::write(file.path(), b"YUFB").unwrap();
+        assert!(restored.load_cache(file.path()).is_err());
+    }
+    
     #[test]
     fn test_cache_cleanup() {
         let config = CacheConfig::default();
@@ -0,0 +1,285 @@
+//! # 复数标准形
+//!
+//! 把含虚数单位 `i` 的表达式拆成 `a + b*i`，`a`、`b` 都不含 `i`。拆分只用到 `i^2 = -1`，
+//! 对符号同样成立；除法与负整数次幂只在分母不含变量时按共轭有理化，
+//! 避免在分母可能为零的地方改写表达式。
+//!
+//! 共轭 [`conjugate`] 对四则运算、整数次幂与实系数的初等函数逐项分配，无法确定的部分保留为 `conj(...)`。
+
+use num_traits::ToPrimitive;
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator, MathConstant};
+
+/// 展开整数次幂允许的最大指数
+const MAX_POWER: i64 = 64;
+
+/// 表达式中是否含有虚数单位或复数数值
+pub fn contains_imaginary(expr: &Expression) -> bool {
+    match expr {
+        Expression::Constant(MathConstant::I) => true,
+        Expression::Number(n) => n.is_complex() || matches!(n, Number::Constant(MathConstant::I)),
+        _ => expr.children().into_iter().any(contains_imaginary),
+    }
+}
+
+/// 拆成不含 `i` 的两部分 `(a, b)`，使表达式等于 `a + b*i`
+///
+/// 含 `i` 的子式无法拆开时（如 `exp(i*x)`、`sqrt(i)`）返回 `None`。
+pub fn rectangular(expr: &Expression) -> Option<(Expression, Expression)> {
+    if !contains_imaginary(expr) {
+        return Some((expr.clone(), zero()));
+    }
+    match expr {
+        Expression::Constant(MathConstant::I) | Expression::Number(Number::Constant(MathConstant::I)) => Some((zero(), one())),
+        Expression::Number(Number::Complex { real, imaginary }) => {
+            Some((Expression::Number(real.as_ref().clone()), Expression::Number(imaginary.as_ref().clone())))
+        }
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+            let (a, b) = rectangular(operand)?;
+            Some((negate(a), negate(b)))
+        }
+        Expression::UnaryOp { op: UnaryOperator::Plus, operand } => rectangular(operand),
+        Expression::BinaryOp { op, left, right } => {
+            let (a, b) = rectangular(left)?;
+            match op {
+                BinaryOperator::Add => {
+                    let (c, d) = rectangular(right)?;
+                    Some((add(a, c), add(b, d)))
+                }
+                BinaryOperator::Subtract => {
+                    let (c, d) = rectangular(right)?;
+                    Some((subtract(a, c), subtract(b, d)))
+                }
+                BinaryOperator::Multiply => {
+                    let (c, d) = rectangular(right)?;
+                    Some(multiply_parts((a, b), (c, d)))
+                }
+                BinaryOperator::Divide => {
+                    let (c, d) = rectangular(right)?;
+                    if is_zero(&d) {
+                        return Some((divide(a, c.clone()), divide(b, c)));
+                    }
+                    Some(multiply_parts((a, b), reciprocal(right, (c, d))?))
+                }
+                BinaryOperator::Power => {
+                    let exponent = match right.as_ref() {
+                        Expression::Number(Number::Integer(n)) => n.to_i64().filter(|n| n.abs() <= MAX_POWER)?,
+                        _ => return None,
+                    };
+                    let base = match exponent < 0 {
+                        true => reciprocal(left, (a, b))?,
+                        false => (a, b),
+                    };
+                    Some(power_parts(base, exponent.unsigned_abs()))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// 由两部分组装 `a + b*i`，省略为零的部分；两部分都是数值时合成复数数值
+pub fn from_rectangular(re: Expression, im: Expression) -> Expression {
+    if is_zero(&im) {
+        return re;
+    }
+    if let (Expression::Number(a), Expression::Number(b)) = (&re, &im) {
+        if !a.is_complex() && !b.is_complex() {
+            return Expression::Number(Number::complex(a.clone(), b.clone()));
+        }
+    }
+    // 虚部带负号或负系数时写成 a - b*i
+    let (negative, magnitude) = match im {
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => (true, *operand),
+        Expression::Number(n) if n.is_negative() => (true, Expression::Number(-n)),
+        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } if matches!(left.as_ref(), Expression::Number(n) if n.is_negative()) => {
+            let Expression::Number(n) = *left else { unreachable!() };
+            (true, multiply(Expression::Number(-n), *right))
+        }
+        im => (false, im),
+    };
+    let imaginary = match magnitude {
+        Expression::Number(n) if n.is_one() => Expression::Constant(MathConstant::I),
+        magnitude => Expression::multiply(magnitude, Expression::Constant(MathConstant::I)),
+    };
+    match (is_zero(&re), negative) {
+        (true, false) => imaginary,
+        (true, true) => Expression::negate(imaginary),
+        (false, false) => Expression::add(re, imaginary),
+        (false, true) => Expression::subtract(re, imaginary),
+    }
+}
+
+/// 共轭，`is_real` 判断变量是否为实数
+///
+/// `conj(a ± b)`、`conj(a * b)`、`conj(a / b)`、`conj(a^n)`（`n` 为整数）逐项分配，`conj(i) = -i`，
+/// 实数、实常量与实变量不变，`conj(conj(z)) = z`，`|z|`、`Re`、`Im`、`arg` 的值是实数也不变；
+/// exp、sin、cos、tan 及双曲函数与共轭交换。ln、sqrt、非整数次幂等在分支切割上不满足交换，保留为 `conj(...)`。
+pub fn conjugate(expr: &Expression, is_real: &dyn Fn(&str) -> bool) -> Expression {
+    let conj = |e: &Expression| conjugate(e, is_real);
+    match expr {
+        Expression::Constant(MathConstant::I) | Expression::Number(Number::Constant(MathConstant::I)) => {
+            Expression::negate(Expression::Constant(MathConstant::I))
+        }
+        Expression::Number(Number::Complex { real, imaginary }) => {
+            Expression::Number(Number::complex(real.as_ref().clone(), -imaginary.as_ref().clone()))
+        }
+        Expression::Number(_) | Expression::Constant(_) => expr.clone(),
+        Expression::Variable(name) if is_real(name) => expr.clone(),
+        Expression::BinaryOp { op, left, right } => match op {
+            BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply | BinaryOperator::Divide => {
+                Expression::binary_op(op.clone(), conj(left), conj(right))
+            }
+            BinaryOperator::Power if matches!(right.as_ref(), Expression::Number(Number::Integer(_))) => {
+                Expression::power(conj(left), right.as_ref().clone())
+            }
+            _ => conjugate_call(expr),
+        },
+        Expression::UnaryOp { op, operand } => match op {
+            UnaryOperator::Conjugate => operand.as_ref().clone(),
+            UnaryOperator::Abs | UnaryOperator::Real | UnaryOperator::Imaginary | UnaryOperator::Argument => expr.clone(),
+            UnaryOperator::Negate | UnaryOperator::Plus => Expression::unary_op(op.clone(), conj(operand)),
+            _ => expr.to_function_call().map_or_else(|| conjugate_call(expr), |call| conj(&call)),
+        },
+        Expression::Function { name, args } => match (name.as_str(), args.as_slice()) {
+            ("conj" | "conjugate", [z]) => z.clone(),
+            ("abs" | "re" | "im" | "arg", [_]) => expr.clone(),
+            ("exp" | "sin" | "cos" | "tan" | "sinh" | "cosh" | "tanh", [z]) => Expression::function(name, vec![conj(z)]),
+            _ => conjugate_call(expr),
+        },
+        Expression::Matrix(rows) => Expression::Matrix(rows.iter().map(|row| row.iter().map(conj).collect()).collect()),
+        Expression::Vector(elements) => Expression::Vector(elements.iter().map(conj).collect()),
+        _ => conjugate_call(expr),
+    }
+}
+
+/// 把表达式中每个 `conj(...)` 按 [`conjugate`] 展开，由内向外，`conj(conj(z))` 化为 `z`
+pub fn expand_conjugates(expr: &Expression, is_real: &dyn Fn(&str) -> bool) -> Expression {
+    let expand = |e: &Expression| expand_conjugates(e, is_real);
+    match expr {
+        Expression::UnaryOp { op: UnaryOperator::Conjugate, operand } => conjugate(&expand(operand), is_real),
+        Expression::Function { name, args } if matches!(name.as_str(), "conj" | "conjugate") && args.len() == 1 => {
+            conjugate(&expand(&args[0]), is_real)
+        }
+        Expression::BinaryOp { op, left, right } => Expression::binary_op(op.clone(), expand(left), expand(right)),
+        Expression::UnaryOp { op, operand } => Expression::unary_op(op.clone(), expand(operand)),
+        Expression::Function { name, args } => Expression::function(name, args.iter().map(expand).collect()),
+        Expression::Matrix(rows) => Expression::Matrix(rows.iter().map(|row| row.iter().map(expand).collect()).collect()),
+        Expression::Vector(elements) => Expression::Vector(elements.iter().map(expand).collect()),
+        _ => expr.clone(),
+    }
+}
+
+/// 无法分配时保留的 `conj(expr)`
+fn conjugate_call(expr: &Expression) -> Expression {
+    Expression::function("conj", vec![expr.clone()])
+}
+
+/// `(a + b*i)(c + d*i) = (ac - bd) + (ad + bc)*i`
+fn multiply_parts((a, b): (Expression, Expression), (c, d): (Expression, Expression)) -> (Expression, Expression) {
+    let re = subtract(multiply(a.clone(), c.clone()), multiply(b.clone(), d.clone()));
+    let im = add(multiply(a, d), multiply(b, c));
+    (re, im)
+}
+
+/// 按平方展开非负整数次幂
+fn power_parts(base: (Expression, Expression), exponent: u64) -> (Expression, Expression) {
+    let mut result = (one(), zero());
+    let mut square = base;
+    let mut exponent = exponent;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = multiply_parts(result, square.clone());
+        }
+        exponent >>= 1;
+        if exponent > 0 {
+            square = multiply_parts(square.clone(), square);
+        }
+    }
+    result
+}
+
+/// `1/(c + d*i) = (c - d*i)/(c^2 + d^2)`，`original` 含变量时不改写
+fn reciprocal(original: &Expression, (c, d): (Expression, Expression)) -> Option<(Expression, Expression)> {
+    if !original.is_constant() {
+        return None;
+    }
+    let norm = add(multiply(c.clone(), c.clone()), multiply(d.clone(), d.clone()));
+    Some((divide(c, norm.clone()), divide(negate(d), norm)))
+}
+
+fn zero() -> Expression {
+    Expression::Number(Number::zero())
+}
+
+fn one() -> Expression {
+    Expression::Number(Number::one())
+}
+
+fn is_zero(expr: &Expression) -> bool {
+    matches!(expr, Expression::Number(n) if n.is_zero())
+}
+
+fn is_one(expr: &Expression) -> bool {
+    matches!(expr, Expression::Number(n) if n.is_one())
+}
+
+fn is_minus_one(expr: &Expression) -> bool {
+    matches!(expr, Expression::Number(n) if n.is_negative() && (-n.clone()).is_one())
+}
+
+// 以下构造函数跳过零与一，并把负号提到和式层面写成减法，使拆分出的两部分保持紧凑、便于相消
+
+fn add(a: Expression, b: Expression) -> Expression {
+    match (a, b) {
+        (a, b) if is_zero(&a) => b,
+        (a, b) if is_zero(&b) => a,
+        (a, Expression::UnaryOp { op: UnaryOperator::Negate, operand }) => Expression::subtract(a, *operand),
+        (Expression::UnaryOp { op: UnaryOperator::Negate, operand }, b) => Expression::subtract(b, *operand),
+        (a, b) => Expression::add(a, b),
+    }
+}
+
+fn subtract(a: Expression, b: Expression) -> Expression {
+    match (a, b) {
+        (a, b) if is_zero(&b) => a,
+        (a, b) if is_zero(&a) => negate(b),
+        (a, Expression::UnaryOp { op: UnaryOperator::Negate, operand }) => add(a, *operand),
+        (a, b) => Expression::subtract(a, b),
+    }
+}
+
+fn multiply(a: Expression, b: Expression) -> Expression {
+    if is_zero(&a) || is_zero(&b) {
+        return zero();
+    }
+    match (a, b) {
+        (a, b) if is_one(&a) => b,
+        (a, b) if is_one(&b) => a,
+        (a, b) if is_minus_one(&a) => negate(b),
+        (a, b) if is_minus_one(&b) => negate(a),
+        (Expression::UnaryOp { op: UnaryOperator::Negate, operand }, b) => negate(multiply(*operand, b)),
+        (a, Expression::UnaryOp { op: UnaryOperator::Negate, operand }) => negate(multiply(a, *operand)),
+        (a, b) => Expression::multiply(a, b),
+    }
+}
+
+fn divide(a: Expression, b: Expression) -> Expression {
+    match (is_zero(&a), is_one(&b)) {
+        (true, _) => zero(),
+        (_, true) => a,
+        _ => Expression::divide(a, b),
+    }
+}
+
+fn negate(a: Expression) -> Expression {
+    match a {
+        Expression::Number(n) => Expression::Number(-n),
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => *operand,
+        a => Expression::negate(a),
+    }
+}
+
+#[cfg(test)]
+#[path = "complex_tests.rs"]
+mod complex_tests;
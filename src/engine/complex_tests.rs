@@ -0,0 +1,115 @@
+//! # 复数标准形测试
+//!
+//! 测试 `a + b*i` 的拆分、组装，以及化简后的标准形。
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{Expression, Number, MathConstant};
+    use crate::engine::complex::{conjugate, contains_imaginary, from_rectangular, rectangular};
+    use crate::engine::EnhancedSimplifier;
+    use crate::engine::assumptions::Property;
+    use crate::formatter::{Formatter, StandardFormatter};
+    use crate::test_utils::parse;
+
+    fn simplify(input: &str) -> String {
+        StandardFormatter::new().format(&EnhancedSimplifier::new().enhanced_simplify(&parse(input)).unwrap())
+    }
+
+    #[test]
+    fn test_numeric_standard_form() {
+        assert_eq!(simplify("(2+3*i)+(1-i)"), "3+2i");
+        assert_eq!(simplify("1/i"), "-i");
+        assert_eq!(simplify("(1+i)/(1-i)"), "i");
+        assert_eq!(simplify("(1+i)*(1-i)"), "2");
+        assert_eq!(simplify("(2+i)^2"), "3+4i");
+        assert_eq!(simplify("i^3"), "-i");
+        assert_eq!(simplify("(1+2*i)^(-1)"), "1/5-2/5i");
+    }
+
+    #[test]
+    fn test_symbolic_standard_form() {
+        assert_eq!(simplify("x*i + y*i"), "(x + y) * i");
+        assert_eq!(simplify("(x + y*i)*(x - y*i)"), "x^2 + y^2");
+        assert_eq!(simplify("(1 - i)*x"), "x - x * i");
+        assert_eq!(simplify("x - 3*i*x + 2"), "2 + x - 3 * x * i");
+        assert_eq!(simplify("(x + i)^2"), "-1 + x^2 + 2 * x * i");
+        // 实部与虚部各自化简
+        let product = simplify("(a + b*i)*(c + d*i)");
+        assert!(product.ends_with("(a * d + b * c) * i"), "{}", product);
+        // 分母含变量时不有理化
+        assert_eq!(simplify("1/(x + i)"), "1 / (x + i)");
+        // 整体无法拆开时整理函数参数
+        assert_eq!(simplify("exp((1 + i)*(1 - i)*x)"), "exp(2 * x)");
+    }
+
+    #[test]
+    fn test_rectangular_parts() {
+        assert!(contains_imaginary(&parse("x + 2*i")));
+        assert!(!contains_imaginary(&parse("x + 2")));
+        assert_eq!(rectangular(&parse("x")), Some((parse("x"), Expression::number(Number::zero()))));
+        assert_eq!(rectangular(&parse("exp(i*x)")), None);
+        assert_eq!(rectangular(&parse("x^(1/2)*i")).map(|(re, _)| re), Some(Expression::number(Number::zero())));
+
+        let i = Expression::Constant(MathConstant::I);
+        assert_eq!(from_rectangular(parse("x"), Expression::number(Number::integer(-1))), Expression::subtract(parse("x"), i.clone()));
+        assert_eq!(from_rectangular(Expression::number(Number::zero()), parse("y")), Expression::multiply(parse("y"), i));
+        assert_eq!(
+            from_rectangular(Expression::number(Number::integer(3)), Expression::number(Number::integer(2))),
+            Expression::number(Number::complex(Number::integer(3), Number::integer(2)))
+        );
+    }
+
+    #[test]
+    fn test_conjugate() {
+        let complex_vars = |_: &str| false;
+        let conj = |input: &str| conjugate(&parse(input), &complex_vars);
+        assert_eq!(conj("i"), Expression::negate(Expression::Constant(MathConstant::I)));
+        assert_eq!(conj("3"), parse("3"));
+        assert_eq!(conj("pi"), parse("pi"));
+        assert_eq!(conj("z"), parse("conj(z)"));
+        assert_eq!(conj("conj(z)"), parse("z"));
+        assert_eq!(conj("a + b"), parse("conj(a) + conj(b)"));
+        assert_eq!(conj("a * b"), parse("conj(a) * conj(b)"));
+        assert_eq!(conj("a / b - c"), parse("conj(a) / conj(b) - conj(c)"));
+        assert_eq!(conj("z^3"), parse("conj(z)^3"));
+        assert_eq!(conj("exp(z)"), parse("exp(conj(z))"));
+        assert_eq!(conj("abs(z)"), parse("abs(z)"));
+        // 分支切割上不交换
+        assert_eq!(conj("ln(z)"), parse("conj(ln(z))"));
+        assert_eq!(conj("z^(1/2)"), parse("conj(z^(1/2))"));
+
+        // 实变量不变
+        let real_x = |var: &str| var == "x";
+        assert_eq!(conjugate(&parse("x * z + 2"), &real_x), parse("x * conj(z) + 2"));
+
+        // 化简后得到标准形
+        let mut simplifier = EnhancedSimplifier::new();
+        let format = |expr: &Expression| StandardFormatter::new().format(expr);
+        let conjugated = conjugate(&parse("x + 2*i*x - 3*i"), &real_x);
+        assert_eq!(format(&simplifier.enhanced_simplify(&conjugated).unwrap()), "x + (-2 * x + 3) * i");
+        let conjugated = conjugate(&parse("(3 + 4*i) * (1 - i)"), &real_x);
+        assert_eq!(format(&simplifier.enhanced_simplify(&conjugated).unwrap()), "7-i");
+    }
+
+    #[test]
+    fn test_simplify_conjugate() {
+        // 对合
+        assert_eq!(simplify("conj(conj(z))"), "z");
+        assert_eq!(simplify("conj(conj(a) + b)"), "a + conj(b)");
+        // 数与复常量
+        assert_eq!(simplify("conj(3+4*i)"), "3-4i");
+        assert_eq!(simplify("conj(i)"), "-i");
+        assert_eq!(simplify("conj(2*z + i)"), "2 * conj(z) - i");
+        // 对四则运算分配
+        assert_eq!(simplify("conj(a+b)"), "conj(a) + conj(b)");
+        assert_eq!(simplify("conj(a*b)"), "conj(a) * conj(b)");
+        assert_eq!(simplify("conj(a/b)"), "conj(a) / conj(b)");
+        assert_eq!(simplify("conj(z)"), "conj(z)");
+
+        // 假设为实数的变量共轭不变
+        let mut simplifier = EnhancedSimplifier::new();
+        simplifier.assume("x", Property::Positive).unwrap();
+        let simplified = simplifier.enhanced_simplify(&parse("conj(x*z)")).unwrap();
+        assert_eq!(StandardFormatter::new().format(&simplified), "x * conj(z)");
+    }
+}
@@ -6,11 +6,22 @@ use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
 use crate::core::{Expression, Number, MathConstant};
 use super::{ComputeEngine, ComputeError};
-use super::simplify::Simplifier;
+use super::simplify::{Simplifier, SimplifyOptions};
+use crate::core::indeterminate::{IndeterminateSemantics, Magnitude};
+use super::complex;
 use super::polynomial::PolynomialEngine;
 use super::number_theory::NumberTheoryEngine;
 use super::calculus::CalculusEngine;
 use super::matrix::MatrixEngine;
+use super::solver::{SolverEngine, Solution};
+use super::domain::{DomainAnalyzer, DomainAnalysis};
+use super::extrema::{ExtremaAnalyzer, Extremum};
+use super::summation::{SummationEngine, SeriesSum};
+use super::laplace::{LaplaceEngine, LaplaceTransform};
+use super::logic::{self, TruthTable};
+use super::equivalence::{EquivalenceChecker, Equivalence};
+use super::groebner;
+use crate::tr;
 
 /// 基础计算引擎实现
 pub struct BasicComputeEngine {
@@ -24,6 +35,18 @@ pub struct BasicComputeEngine {
     calculus_engine: CalculusEngine,
     /// 矩阵和向量运算引擎
     matrix_engine: MatrixEngine,
+    /// 方程求解引擎
+    solver_engine: SolverEngine,
+    /// 定义域与值域分析器
+    domain_analyzer: DomainAnalyzer,
+    /// 极值与驻点分析器
+    extrema_analyzer: ExtremaAnalyzer,
+    /// 求和与无穷级数引擎
+    summation_engine: SummationEngine,
+    /// 恒等式检验器
+    equivalence_checker: EquivalenceChecker,
+    /// Laplace 变换引擎
+    laplace_engine: LaplaceEngine,
 }
 
 impl BasicComputeEngine {
@@ -35,20 +58,43 @@ impl BasicComputeEngine {
             number_theory_engine: NumberTheoryEngine::new(),
             calculus_engine: CalculusEngine::new(),
             matrix_engine: MatrixEngine::new(),
+            solver_engine: SolverEngine::new(),
+            domain_analyzer: DomainAnalyzer::new(),
+            extrema_analyzer: ExtremaAnalyzer::new(),
+            summation_engine: SummationEngine::new(),
+            equivalence_checker: EquivalenceChecker::new(),
+            laplace_engine: LaplaceEngine::new(),
         }
     }
     
+    /// 设置化简启用的规则集，求值时的未定式约定也由它决定
+    pub fn set_simplify_options(&self, options: SimplifyOptions) {
+        if let Ok(mut simplifier) = self.simplifier.lock() {
+            simplifier.set_options(options);
+        }
+    }
+    
+    /// 化简器配置决定的扩展实数约定，求值与数值计算共用
+    fn indeterminate_semantics(&self) -> Result<IndeterminateSemantics, ComputeError> {
+        Ok(self.simplifier.lock()
+            .map_err(|_| ComputeError::internal(tr!("engine.simplifier_lock")))?
+            .indeterminate_semantics())
+    }
+    
     /// 计算二元运算
     fn evaluate_binary_op(&self, left: &Number, right: &Number, op: &crate::core::BinaryOperator) -> Result<Number, ComputeError> {
         use crate::core::BinaryOperator;
         
+        if let Some(value) = self.indeterminate_semantics()?.resolve_numbers(op, left, right) {
+            return Ok(value);
+        }
         match op {
             BinaryOperator::Add => left.add(right),
             BinaryOperator::Subtract => left.subtract(right),
             BinaryOperator::Multiply => left.multiply(right),
             BinaryOperator::Divide => left.divide(right),
             BinaryOperator::Power => left.power(right),
-            _ => Err(ComputeError::unsupported_operation(&format!("不支持的二元运算: {:?}", op)))
+            _ => Err(ComputeError::unsupported_operation(tr!("engine.unsupported_binary", operation = format!("{:?}", op))))
         }
     }
     
@@ -56,11 +102,14 @@ impl BasicComputeEngine {
     fn evaluate_unary_op(&self, operand: &Number, op: &crate::core::UnaryOperator) -> Result<Number, ComputeError> {
         use crate::core::UnaryOperator;
         
+        if Magnitude::of_number(operand) == Magnitude::Undefined {
+            return Ok(Number::Constant(MathConstant::Undefined));
+        }
         match op {
             UnaryOperator::Negate => operand.negate(),
             UnaryOperator::Plus => Ok(operand.clone()),
             UnaryOperator::Abs => operand.abs(),
-            _ => Err(ComputeError::unsupported_operation(&format!("不支持的一元运算: {:?}", op)))
+            _ => Err(ComputeError::unsupported_operation(tr!("engine.unsupported_unary", operation = format!("{:?}", op))))
         }
     }
 }
@@ -71,10 +120,16 @@ impl ComputeEngine for BasicComputeEngine {
     }
     fn simplify(&self, expr: &Expression) -> Result<Expression, ComputeError> {
         self.simplifier.lock()
-            .map_err(|_| ComputeError::internal("无法获取简化器锁"))?
+            .map_err(|_| ComputeError::internal(tr!("engine.simplifier_lock")))?
             .simplify(expr)
     }
     
+    fn simplify_traced(&self, expr: &Expression) -> Result<(Expression, Vec<super::TraceEvent>), ComputeError> {
+        self.simplifier.lock()
+            .map_err(|_| ComputeError::internal(tr!("engine.simplifier_lock")))?
+            .simplify_traced(expr)
+    }
+    
     fn evaluate(&self, expr: &Expression, vars: &HashMap<String, Number>) -> Result<Number, ComputeError> {
         match expr {
             Expression::Number(n) => Ok(n.clone()),
@@ -155,7 +210,7 @@ impl ComputeEngine for BasicComputeEngine {
                 if evaluated_elements.len() == 1 && evaluated_elements[0].len() == 1 {
                     Ok(evaluated_elements[0][0].clone())
                 } else {
-                    Err(ComputeError::unsupported_operation("矩阵无法求值为单个数值"))
+                    Err(ComputeError::unsupported_operation(tr!("engine.matrix_not_scalar")))
                 }
             }
             Expression::Vector(elements) => {
@@ -170,7 +225,7 @@ impl ComputeEngine for BasicComputeEngine {
                 if evaluated_elements.len() == 1 {
                     Ok(evaluated_elements[0].clone())
                 } else {
-                    Err(ComputeError::unsupported_operation("向量无法求值为单个数值"))
+                    Err(ComputeError::unsupported_operation(tr!("engine.vector_not_scalar")))
                 }
             }
             _ => {
@@ -179,7 +234,7 @@ impl ComputeEngine for BasicComputeEngine {
                 if simplified != *expr {
                     self.evaluate(&simplified, vars)
                 } else {
-                    Err(ComputeError::unsupported_operation(&format!("无法求值表达式: {:?}", expr)))
+                    Err(ComputeError::unsupported_operation(tr!("engine.unevaluable", expression = format!("{:?}", expr))))
                 }
             }
         }
@@ -201,8 +256,32 @@ impl ComputeEngine for BasicComputeEngine {
         self.calculus_engine.series(expr, var, point, order)
     }
     
+    fn sum(&self, expr: &Expression, var: &str, lower: &Expression, upper: &Expression) -> Result<SeriesSum, ComputeError> {
+        self.summation_engine.sum(expr, var, lower, upper)
+    }
+    
+    fn laplace(&self, expr: &Expression, t: &str, s: &str) -> Result<LaplaceTransform, ComputeError> {
+        self.laplace_engine.laplace(expr, t, s)
+    }
+    
+    fn inverse_laplace(&self, expr: &Expression, s: &str, t: &str) -> Result<Expression, ComputeError> {
+        self.laplace_engine.inverse_laplace(expr, s, t)
+    }
+    
+    fn truth_table(&self, expr: &Expression, vars: &[String]) -> Result<TruthTable, ComputeError> {
+        logic::truth_table(expr, vars)
+    }
+    
+    fn to_dnf(&self, expr: &Expression) -> Result<Expression, ComputeError> {
+        Ok(logic::to_dnf(expr))
+    }
+    
+    fn to_cnf(&self, expr: &Expression) -> Result<Expression, ComputeError> {
+        Ok(logic::to_cnf(expr))
+    }
+    
     fn numerical_evaluate(&self, expr: &Expression, vars: &HashMap<String, f64>) -> Result<f64, ComputeError> {
-        self.calculus_engine.numerical_evaluate(expr, vars)
+        self.calculus_engine.numerical_evaluate_with(expr, vars, self.indeterminate_semantics()?)
     }
     
     fn constant_to_number(&self, constant: &MathConstant) -> Result<Number, ComputeError> {
@@ -215,9 +294,8 @@ impl ComputeEngine for BasicComputeEngine {
             MathConstant::EulerGamma => Ok(Number::Float(0.5772156649015329)),
             MathConstant::GoldenRatio => Ok(Number::Float(1.618033988749895)),
             MathConstant::Catalan => Ok(Number::Float(0.915965594177219)),
-            MathConstant::PositiveInfinity => Ok(Number::Float(f64::INFINITY)),
-            MathConstant::NegativeInfinity => Ok(Number::Float(f64::NEG_INFINITY)),
-            MathConstant::Undefined => Ok(Number::Float(f64::NAN)),
+            MathConstant::PositiveInfinity | MathConstant::NegativeInfinity
+            | MathConstant::Undefined => Ok(Number::Constant(constant.clone())),
         }
     }
     
@@ -257,6 +335,14 @@ impl ComputeEngine for BasicComputeEngine {
         self.number_theory_engine.lcm(a, b)
     }
     
+    fn gcd_list(&self, values: &[Expression]) -> Result<Expression, ComputeError> {
+        self.number_theory_engine.gcd_list(values)
+    }
+    
+    fn lcm_list(&self, values: &[Expression]) -> Result<Expression, ComputeError> {
+        self.number_theory_engine.lcm_list(values)
+    }
+    
     fn is_prime(&self, n: &Expression) -> Result<bool, ComputeError> {
         self.number_theory_engine.is_prime(n)
     }
@@ -265,6 +351,10 @@ impl ComputeEngine for BasicComputeEngine {
         self.number_theory_engine.prime_factors(n)
     }
     
+    fn set_random_seed(&self, seed: u64) {
+        self.number_theory_engine.set_seed(seed);
+    }
+    
     fn binomial(&self, n: &Expression, k: &Expression) -> Result<Expression, ComputeError> {
         self.number_theory_engine.binomial(n, k)
     }
@@ -285,17 +375,44 @@ impl ComputeEngine for BasicComputeEngine {
         self.number_theory_engine.standard_deviation(values)
     }
     
-    // 方程求解功能实现（暂时使用占位符）
+    // 方程求解功能实现
     
-    fn solve(&self, _equation: &Expression, _var: &str) -> Result<Vec<Expression>, ComputeError> {
-        // 占位符实现，将在后续任务中完成
-        todo!("方程求解功能将在后续任务中实现")
+    fn solve(&self, equation: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError> {
+        self.solver_engine.solve(equation, var)
+    }
+    
+    fn solve_detailed(&self, equation: &Expression, var: &str) -> Result<Vec<Solution>, ComputeError> {
+        self.solver_engine.solve_detailed(equation, var)
+    }
+    
+    fn domain(&self, expr: &Expression, var: &str) -> Result<DomainAnalysis, ComputeError> {
+        self.domain_analyzer.domain(expr, var)
+    }
+    
+    fn range_estimate(&self, expr: &Expression, var: &str, domain: &Expression) -> Result<DomainAnalysis, ComputeError> {
+        self.domain_analyzer.range_estimate(expr, var, domain)
+    }
+    
+    fn critical_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError> {
+        self.extrema_analyzer.critical_points(expr, var)
     }
     
-    fn solve_system(&self, _equations: &[Expression], _vars: &[String]) 
+    fn local_extrema(&self, expr: &Expression, var: &str) -> Result<Vec<Extremum>, ComputeError> {
+        self.extrema_analyzer.local_extrema(expr, var)
+    }
+    
+    fn inflection_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError> {
+        self.extrema_analyzer.inflection_points(expr, var)
+    }
+    
+    fn are_equivalent(&self, lhs: &Expression, rhs: &Expression) -> Result<Equivalence, ComputeError> {
+        self.equivalence_checker.are_equivalent(lhs, rhs)
+    }
+    
+    fn solve_system(&self, equations: &[Expression], vars: &[String]) 
         -> Result<Vec<HashMap<String, Expression>>, ComputeError> {
-        // 占位符实现，将在后续任务中完成
-        todo!("方程组求解功能将在后续任务中实现")
+        let vars: Vec<&str> = vars.iter().map(String::as_str).collect();
+        groebner::solve_system(equations, &vars)
     }
     
     // 矩阵运算功能实现（暂时使用占位符）
@@ -318,9 +435,9 @@ impl ComputeEngine for BasicComputeEngine {
     
     // 复数运算功能实现（暂时使用占位符）
     
-    fn complex_conjugate(&self, _expr: &Expression) -> Result<Expression, ComputeError> {
-        // 占位符实现，将在后续任务中完成
-        todo!("复数共轭功能将在后续任务中实现")
+    fn complex_conjugate(&self, expr: &Expression) -> Result<Expression, ComputeError> {
+        // 基础引擎没有变量假设，变量都按复数处理
+        Ok(complex::conjugate(expr, &|_| false))
     }
     
     fn complex_modulus(&self, _expr: &Expression) -> Result<Expression, ComputeError> {
@@ -0,0 +1,703 @@
+//! # 二次型与圆锥曲线
+//!
+//! 把关于若干变量次数不超过 2 的多项式拆成 `xᵀQx + bᵀx + c`：二次部分的对称矩阵 Q、一次项系数与常数项，
+//! 系数可以含其余符号。在此基础上提取二次型矩阵、配方和判别圆锥曲线类型。
+//!
+//! 含交叉项时按 2×2 对称矩阵的特征值旋转坐标：`r = √((A - C)² + B²)`，特征值为 `(A + C ∓ r)/2`，
+//! 转轴角满足 `cos 2θ = (A - C)/r`、`sin 2θ = B/r`，结果都是精确表达式。
+//! 分类依据不变量 det Q、Δ（3×3 增广矩阵的行列式）、tr Q 与 K 的符号；系数含参数、符号不能确定时按参数列出各种情形。
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed};
+use crate::core::{BinaryOperator, Expression, Number};
+use super::ComputeError;
+use super::calculus::CalculusEngine;
+use super::polynomial::{require_polynomial, Polynomial, PolynomialEngine};
+use super::resultant::finish;
+use super::simplify::Simplifier;
+use super::solver::SolverEngine;
+use crate::tr;
+
+/// 圆锥曲线的类型
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConicKind {
+    /// 椭圆（不含圆）
+    Ellipse,
+    /// 圆
+    Circle,
+    /// 双曲线
+    Hyperbola,
+    /// 抛物线
+    Parabola,
+    /// 退化为一点
+    Point,
+    /// 退化为两条相交直线
+    IntersectingLines,
+    /// 退化为两条平行直线
+    ParallelLines,
+    /// 退化为一条（二重）直线
+    CoincidentLines,
+    /// 没有实点，如 `x^2 + y^2 + 1 = 0`
+    Empty,
+    /// 系数含参数、类型随参数变化时的各种情形
+    Cases(Vec<ConicCase>),
+}
+
+impl ConicKind {
+    /// 是否为退化情形
+    pub fn is_degenerate(&self) -> bool {
+        matches!(self, ConicKind::Point | ConicKind::IntersectingLines | ConicKind::ParallelLines | ConicKind::CoincidentLines | ConicKind::Empty)
+    }
+}
+
+/// 按参数分类时的一种情形：各条件同时成立时曲线为 `kind`
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConicCase {
+    /// 参数满足的条件，如 `r > 0`
+    pub conditions: Vec<Expression>,
+    /// 该情形下的类型；按参数分类时不区分圆与椭圆
+    pub kind: ConicKind,
+}
+
+/// 圆锥曲线分类结果，除类型外的参数只在系数都是有理数时给出
+#[derive(Debug, Clone, PartialEq)]
+pub struct Conic {
+    /// 类型
+    pub kind: ConicKind,
+    /// 标准形方程；坐标经过平移或旋转时，新坐标记作 `x'`、`y'`
+    pub standard_form: Option<Expression>,
+    /// 中心（椭圆、圆、双曲线、点、相交直线）
+    pub center: Option<Vec<Expression>>,
+    /// 顶点（抛物线）
+    pub vertex: Option<Vec<Expression>>,
+    /// 焦点，圆的焦点即圆心
+    pub foci: Vec<Vec<Expression>>,
+    /// 离心率
+    pub eccentricity: Option<Expression>,
+}
+
+impl Conic {
+    /// 只有类型、没有其他参数的结果
+    fn kind_only(kind: ConicKind) -> Self {
+        Self { kind, standard_form: None, center: None, vertex: None, foci: Vec::new(), eccentricity: None }
+    }
+}
+
+/// 关于若干变量的二次多项式 `xᵀQx + bᵀx + c`，系数是其余符号的多项式
+struct QuadraticParts {
+    /// 二次部分的对称矩阵，交叉项系数平分到对称的两个位置
+    quadratic: Vec<Vec<Polynomial>>,
+    /// 一次项系数
+    linear: Vec<Polynomial>,
+    /// 常数项
+    constant: Polynomial,
+}
+
+impl QuadraticParts {
+    /// 是否有交叉项
+    fn has_cross_terms(&self) -> bool {
+        self.quadratic.iter().enumerate()
+            .any(|(i, row)| row.iter().enumerate().any(|(j, q)| i != j && !q.is_zero()))
+    }
+}
+
+/// 拆出二次部分、一次项与常数项，关于 `vars` 的总次数超过 2 时报错
+fn quadratic_parts(expr: &Expression, vars: &[&str], operation: &str) -> Result<QuadraticParts, ComputeError> {
+    if vars.is_empty() {
+        return Err(ComputeError::domain_error(tr!("conic.requires_variable", operation = operation)));
+    }
+    let expr = match expr {
+        // 方程 lhs == rhs 按 lhs - rhs 处理
+        Expression::BinaryOp { op: BinaryOperator::Equal, left, right } => Expression::subtract(left.as_ref().clone(), right.as_ref().clone()),
+        expr => expr.clone(),
+    };
+    for var in vars {
+        require_polynomial(&expr, var, operation)?;
+    }
+    let poly = PolynomialEngine::new().polynomial_in(&expr, vars[0], operation)?;
+
+    let n = vars.len();
+    let mut parts = QuadraticParts {
+        quadratic: vec![vec![Polynomial::zero(); n]; n],
+        linear: vec![Polynomial::zero(); n],
+        constant: Polynomial::zero(),
+    };
+    let half = Polynomial::constant(Number::rational(1, 2));
+    for mut term in poly.terms {
+        let powers: Vec<i32> = vars.iter().map(|var| term.variables.remove(*var).unwrap_or(0)).collect();
+        let coefficient = Polynomial::new(vec![term]);
+        let present: Vec<usize> = (0..n).filter(|&i| powers[i] > 0).collect();
+        match (present.as_slice(), powers.iter().sum::<i32>()) {
+            ([], _) => parts.constant = parts.constant.add(&coefficient),
+            (&[i], 1) => parts.linear[i] = parts.linear[i].add(&coefficient),
+            (&[i], 2) => parts.quadratic[i][i] = parts.quadratic[i][i].add(&coefficient),
+            (&[i, j], 2) => {
+                let half = coefficient.multiply(&half);
+                parts.quadratic[i][j] = parts.quadratic[i][j].add(&half);
+                parts.quadratic[j][i] = parts.quadratic[j][i].add(&half);
+            }
+            _ => return Err(ComputeError::domain_error(tr!("conic.degree", operation = operation, variables = vars.join(", ")))),
+        }
+    }
+    for p in parts.quadratic.iter_mut().flatten().chain(parts.linear.iter_mut()).chain(std::iter::once(&mut parts.constant)) {
+        for term in &mut p.terms {
+            term.coefficient = integral(term.coefficient.clone());
+        }
+    }
+    Ok(parts)
+}
+
+/// 二次型 `xᵀQx` 的对称矩阵 Q；一次项与常数项不计入，交叉项系数平分到对称的两个位置
+pub fn quadratic_form_matrix(expr: &Expression, vars: &[&str]) -> Result<Expression, ComputeError> {
+    let parts = quadratic_parts(expr, vars, tr!("conic.quadratic_form"))?;
+    let rows = parts.quadratic.into_iter()
+        .map(|row| row.into_iter().map(finish).collect::<Result<Vec<_>, _>>())
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Expression::Matrix(rows))
+}
+
+/// 配方：一元时为 `a*(x + h)^2 + k`，多元时对每个变量分别配方
+///
+/// 二元且含交叉项时先按特征向量旋转坐标消去交叉项，再在新坐标 `x'`、`y'` 下配方；
+/// `x'` 轴沿较大特征值的特征向量，由 x 轴逆时针旋转 θ 得到。二次项系数含参数时视为非零。
+pub fn complete_square(expr: &Expression, vars: &[&str]) -> Result<Expression, ComputeError> {
+    let parts = quadratic_parts(expr, vars, tr!("conic.complete_square"))?;
+    let constant = finish(parts.constant.clone())?;
+    let (names, squares, linear) = if parts.has_cross_terms() {
+        let [a, h, c] = rational_quadratic(&parts, vars)
+            .ok_or_else(|| ComputeError::unsupported_operation(tr!("conic.cross_term")))?;
+        let rotation = Rotation::new(&a, &h, &c);
+        let (d, e) = (finish(parts.linear[0].clone())?, finish(parts.linear[1].clone())?);
+        let along = |axis: &[Expression; 2]| simplify(&Expression::add(
+            Expression::multiply(d.clone(), axis[0].clone()),
+            Expression::multiply(e.clone(), axis[1].clone()),
+        ));
+        (primed(vars), vec![rotation.larger.clone(), rotation.smaller.clone()], vec![along(&rotation.major)?, along(&rotation.minor)?])
+    } else {
+        let squares = (0..vars.len()).map(|i| finish(parts.quadratic[i][i].clone())).collect::<Result<Vec<_>, _>>()?;
+        let linear = parts.linear.iter().cloned().map(finish).collect::<Result<Vec<_>, _>>()?;
+        (vars.iter().map(|var| var.to_string()).collect(), squares, linear)
+    };
+
+    let mut terms = Vec::new();
+    let mut shift = Vec::new();
+    for ((name, a), b) in names.iter().zip(&squares).zip(&linear) {
+        let var = Expression::variable(name.as_str());
+        if is_zero(a) {
+            if !is_zero(b) {
+                terms.push(scaled(b, var));
+            }
+            continue;
+        }
+        // a*x^2 + b*x = a*(x + b/(2a))^2 - b^2/(4a)
+        let h = simplify(&Expression::divide(b.clone(), Expression::multiply(Expression::number(Number::from(2)), a.clone())))?;
+        let base = if is_zero(&h) { var } else { Expression::add(var, h) };
+        terms.push(scaled(a, Expression::power(base, Expression::number(Number::from(2)))));
+        shift.push(Expression::divide(Expression::power(b.clone(), Expression::number(Number::from(2))), Expression::multiply(Expression::number(Number::from(4)), a.clone())));
+    }
+    let k = simplify(&shift.into_iter().fold(constant, Expression::subtract))?;
+    if !is_zero(&k) || terms.is_empty() {
+        terms.push(k);
+    }
+    Ok(terms.into_iter().reduce(Expression::add).expect("至少有一项"))
+}
+
+/// 判别圆锥曲线 `expr = 0`（也可写成方程 `lhs == rhs`）的类型
+///
+/// 系数都是有理数时给出标准形、中心或顶点、焦点与离心率的精确值；系数含参数时只给出类型，
+/// 不变量的符号不能确定时返回按参数分类的 [`ConicKind::Cases`]。
+pub fn classify_conic(expr: &Expression, vars: &[&str]) -> Result<Conic, ComputeError> {
+    if vars.len() != 2 {
+        return Err(ComputeError::domain_error(tr!("conic.variable_count", count = vars.len())));
+    }
+    let parts = quadratic_parts(expr, vars, tr!("conic.classify"))?;
+    let q = &parts.quadratic;
+    if q.iter().flatten().all(Polynomial::is_zero) {
+        return Err(ComputeError::domain_error(tr!("conic.not_quadratic", expression = expr, variables = vars.join(", "))));
+    }
+
+    // 增广矩阵 [[A, h, d], [h, C, e], [d, e, F]]，其中 h、d、e 是 xy、x、y 系数的一半
+    let half = Polynomial::constant(Number::rational(1, 2));
+    let (a, h, c) = (&q[0][0], &q[0][1], &q[1][1]);
+    let (d, e, f) = (&parts.linear[0].multiply(&half), &parts.linear[1].multiply(&half), &parts.constant);
+    let det_q = a.multiply(c).subtract(&h.multiply(h));
+    let delta = a.multiply(&c.multiply(f).subtract(&e.multiply(e)))
+        .subtract(&h.multiply(&h.multiply(f).subtract(&e.multiply(d))))
+        .add(&d.multiply(&h.multiply(e).subtract(&c.multiply(d))));
+    let trace = a.add(c);
+    let k = a.multiply(f).subtract(&d.multiply(d)).add(&c.multiply(f).subtract(&e.multiply(e)));
+
+    let mut cases = invariant_cases(&det_q, &delta, &trace, &k)?;
+    if cases.len() != 1 {
+        if cases.is_empty() {
+            return Err(ComputeError::unsupported_operation(tr!("conic.unclassified", expression = expr)));
+        }
+        return Ok(Conic::kind_only(ConicKind::Cases(cases)));
+    }
+    let kind = cases.remove(0).kind;
+    let coefficients = [a, h, c, d, e, f].map(rational);
+    match coefficients {
+        [Some(a), Some(h), Some(c), Some(d), Some(e), Some(f)] => Ok(Geometry { a, h, c, d, e, f, vars }.analyze(kind)?),
+        _ => Ok(Conic::kind_only(kind)),
+    }
+}
+
+/// 不变量的符号条件
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Sign {
+    Positive,
+    Negative,
+    Zero,
+    NonZero,
+}
+
+impl Sign {
+    fn admits(self, sign: Ordering) -> bool {
+        match self {
+            Sign::Positive => sign == Ordering::Greater,
+            Sign::Negative => sign == Ordering::Less,
+            Sign::Zero => sign == Ordering::Equal,
+            Sign::NonZero => sign != Ordering::Equal,
+        }
+    }
+}
+
+/// 把不变量按给定的符号分支展开：符号已知时只保留相符的分支且不加条件，否则每个分支附带条件
+fn branches(p: &Polynomial, signs: &[Sign]) -> Result<Vec<(Sign, Option<Expression>)>, ComputeError> {
+    match known_sign(p) {
+        Some(sign) => Ok(signs.iter().filter(|s| s.admits(sign)).map(|&s| (s, None)).collect()),
+        None => signs.iter().map(|&s| Ok((s, Some(condition(p, s)?)))).collect(),
+    }
+}
+
+/// 按不变量的符号列出各种情形
+///
+/// det Q > 0 为椭圆型：Δ = 0 时为一点，tr Q·Δ < 0 时为椭圆，否则无实点；
+/// det Q < 0 为双曲型：Δ ≠ 0 时为双曲线，否则为相交直线；
+/// det Q = 0 为抛物型：Δ ≠ 0 时为抛物线，否则按 K 的符号为平行直线、重合直线或无实点。
+fn invariant_cases(det_q: &Polynomial, delta: &Polynomial, trace: &Polynomial, k: &Polynomial) -> Result<Vec<ConicCase>, ComputeError> {
+    let mut cases = Vec::new();
+    let mut push = |conditions: &[&Option<Expression>], kind| {
+        let conditions: Vec<Expression> = conditions.iter().filter_map(|c| (*c).clone()).collect();
+        // tr Q·Δ 与 Δ 只差常数倍时，`r > 0` 已蕴含 `r != 0`
+        let implied = |c: &Expression| match c {
+            Expression::BinaryOp { op: BinaryOperator::NotEqual, left, .. } => conditions.iter().any(|other| matches!(
+                other, Expression::BinaryOp { op: BinaryOperator::Greater | BinaryOperator::Less, left: l, .. } if l == left
+            )),
+            _ => false,
+        };
+        let conditions = conditions.iter().filter(|c| !implied(c)).cloned().collect();
+        cases.push(ConicCase { conditions, kind });
+    };
+    for (s1, c1) in branches(det_q, &[Sign::Positive, Sign::Negative, Sign::Zero])? {
+        for (s2, c2) in branches(delta, &[Sign::NonZero, Sign::Zero])? {
+            match (s1, s2) {
+                (Sign::Positive, Sign::Zero) => push(&[&c1, &c2], ConicKind::Point),
+                (Sign::Positive, _) => {
+                    for (s3, c3) in branches(&trace.multiply(delta), &[Sign::Negative, Sign::Positive])? {
+                        let kind = if s3 == Sign::Negative { ConicKind::Ellipse } else { ConicKind::Empty };
+                        push(&[&c1, &c2, &c3], kind);
+                    }
+                }
+                (Sign::Negative, Sign::Zero) => push(&[&c1, &c2], ConicKind::IntersectingLines),
+                (Sign::Negative, _) => push(&[&c1, &c2], ConicKind::Hyperbola),
+                (_, Sign::NonZero) => push(&[&c1, &c2], ConicKind::Parabola),
+                _ => {
+                    for (s3, c3) in branches(k, &[Sign::Negative, Sign::Zero, Sign::Positive])? {
+                        let kind = match s3 {
+                            Sign::Negative => ConicKind::ParallelLines,
+                            Sign::Zero => ConicKind::CoincidentLines,
+                            _ => ConicKind::Empty,
+                        };
+                        push(&[&c1, &c2, &c3], kind);
+                    }
+                }
+            }
+        }
+    }
+    Ok(cases)
+}
+
+/// 条件 `p ⋈ 0`，先把首项系数化为 1（为负时翻转不等号），读作 `r > 0` 而不是 `-2*r < 0`
+fn condition(p: &Polynomial, sign: Sign) -> Result<Expression, ComputeError> {
+    let mut sorted = p.clone();
+    sorted.terms.sort_by_key(|term| std::cmp::Reverse(term.degree()));
+    let leading = sorted.terms.first().map(|term| term.coefficient.clone()).filter(Number::is_rational);
+    let (p, flipped) = match leading {
+        Some(leading) => (p.multiply(&Polynomial::constant(Number::one() / leading.clone())), leading.is_negative()),
+        None => (p.clone(), false),
+    };
+    let op = match (sign, flipped) {
+        (Sign::Zero, _) => BinaryOperator::Equal,
+        (Sign::NonZero, _) => BinaryOperator::NotEqual,
+        (Sign::Positive, false) | (Sign::Negative, true) => BinaryOperator::Greater,
+        (Sign::Negative, false) | (Sign::Positive, true) => BinaryOperator::Less,
+    };
+    Ok(Expression::binary_op(op, finish(p)?, Expression::number(Number::zero())))
+}
+
+/// 不含参数时的符号，含参数时为 None
+fn known_sign(p: &Polynomial) -> Option<Ordering> {
+    if p.is_zero() {
+        return Some(Ordering::Equal);
+    }
+    if !p.is_constant() {
+        return None;
+    }
+    let value = &p.terms[0].coefficient;
+    if value.is_rational() {
+        return Some(if value.is_negative() { Ordering::Less } else { Ordering::Greater });
+    }
+    // pi、sqrt(2) 这类符号常数按数值判断
+    approximate(&Expression::number(value.clone())).filter(|v| *v != 0.0).and_then(|v| v.partial_cmp(&0.0))
+}
+
+/// 有理数系数，含参数或无理常数时为 None
+fn rational(p: &Polynomial) -> Option<Number> {
+    if p.is_zero() {
+        return Some(Number::zero());
+    }
+    let term = p.terms.first().filter(|_| p.is_constant())?;
+    term.coefficient.is_rational().then(|| term.coefficient.clone())
+}
+
+/// 二次项系数 [A, h, C] 都是有理数时返回它们，h 为 xy 系数的一半
+fn rational_quadratic(parts: &QuadraticParts, vars: &[&str]) -> Option<[Number; 3]> {
+    if vars.len() != 2 {
+        return None;
+    }
+    let q = &parts.quadratic;
+    Some([rational(&q[0][0])?, rational(&q[0][1])?, rational(&q[1][1])?])
+}
+
+/// 消去交叉项的旋转：两个特征值与对应的单位特征向量
+struct Rotation {
+    /// 较大的特征值 `(A + C + r)/2`
+    larger: Expression,
+    /// 较小的特征值 `(A + C - r)/2`
+    smaller: Expression,
+    /// 较大特征值的单位特征向量 (cos θ, sin θ)
+    major: [Expression; 2],
+    /// 较小特征值的单位特征向量 (-sin θ, cos θ)
+    minor: [Expression; 2],
+}
+
+impl Rotation {
+    /// 二次型 `A x² + 2h xy + C y²` 的特征分解
+    fn new(a: &Number, h: &Number, c: &Number) -> Self {
+        let r_squared = (a.clone() - c.clone()) * (a.clone() - c.clone()) + Number::from(4) * h.clone() * h.clone();
+        if r_squared.is_zero() {
+            // A = C 且没有交叉项：任意方向都是特征向量，不旋转
+            let one = || Expression::number(Number::one());
+            let zero = || Expression::number(Number::zero());
+            return Self { larger: num(a), smaller: num(a), major: [one(), zero()], minor: [zero(), one()] };
+        }
+        let r = Expression::sqrt(num(&r_squared));
+        let trace = num(&(a.clone() + c.clone()));
+        let eigenvalue = |op| simplify_or_keep(Expression::divide(Expression::binary_op(op, trace.clone(), r.clone()), two()));
+        // cos 2θ = (A - C)/r，cos θ = √((1 + cos 2θ)/2)，sin θ 与 h 同号
+        let cos_2theta = Expression::divide(num(&(a.clone() - c.clone())), r.clone());
+        let half_angle = |op| root(Expression::divide(
+            Expression::binary_op(op, Expression::number(Number::one()), cos_2theta.clone()), two(),
+        ));
+        let cos = half_angle(BinaryOperator::Add);
+        let sin = half_angle(BinaryOperator::Subtract);
+        let sin = if h.is_negative() { simplify_or_keep(Expression::negate(sin)) } else { sin };
+        Self {
+            larger: eigenvalue(BinaryOperator::Add),
+            smaller: eigenvalue(BinaryOperator::Subtract),
+            minor: [simplify_or_keep(Expression::negate(sin.clone())), cos.clone()],
+            major: [cos, sin],
+        }
+    }
+}
+
+/// 坐标轴方向：沿原 x 轴、沿原 y 轴（带正负号）或斜向
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    X(bool),
+    Y(bool),
+    Oblique,
+}
+
+impl Direction {
+    fn of(axis: &[Expression; 2]) -> Self {
+        let unit = |e: &Expression| match e {
+            Expression::Number(n) if n.is_one() => Some(true),
+            Expression::Number(n) if *n == Number::neg_one() => Some(false),
+            _ => None,
+        };
+        match (unit(&axis[0]), unit(&axis[1])) {
+            (Some(positive), None) if is_zero(&axis[1]) => Direction::X(positive),
+            (None, Some(positive)) if is_zero(&axis[0]) => Direction::Y(positive),
+            _ => Direction::Oblique,
+        }
+    }
+}
+
+/// 有理系数的圆锥曲线 `A x² + 2h xy + C y² + 2d x + 2e y + F = 0`
+struct Geometry<'a> {
+    a: Number,
+    h: Number,
+    c: Number,
+    d: Number,
+    e: Number,
+    f: Number,
+    vars: &'a [&'a str],
+}
+
+impl Geometry<'_> {
+    /// 按已判定的类型计算标准形与几何参数
+    fn analyze(&self, kind: ConicKind) -> Result<Conic, ComputeError> {
+        match kind {
+            ConicKind::Ellipse | ConicKind::Hyperbola | ConicKind::Point | ConicKind::IntersectingLines => self.central(kind),
+            ConicKind::Parabola => self.parabola(),
+            kind => Ok(Conic::kind_only(kind)),
+        }
+    }
+
+    /// 有心曲线：平移到中心后旋转，标准形为 `X^2/a^2 ± Y^2/b^2 = 1`，X 沿长轴或实轴
+    fn central(&self, kind: ConicKind) -> Result<Conic, ComputeError> {
+        let (a, h, c, d, e, f) = (&self.a, &self.h, &self.c, &self.d, &self.e, &self.f);
+        let det = a.clone() * c.clone() - h.clone() * h.clone();
+        let x0 = (h.clone() * e.clone() - c.clone() * d.clone()) / det.clone();
+        let y0 = (h.clone() * d.clone() - a.clone() * e.clone()) / det;
+        // 平移到中心后的常数项
+        let f0 = num(&(f.clone() + d.clone() * x0.clone() + e.clone() * y0.clone()));
+        let center = vec![num(&x0), num(&y0)];
+        if kind.is_degenerate() {
+            return Ok(Conic { center: Some(center), ..Conic::kind_only(kind) });
+        }
+
+        let ellipse = kind == ConicKind::Ellipse;
+        let rotation = Rotation::new(a, h, c);
+        // 主轴（椭圆的长轴、双曲线的实轴）方向的特征值与 F₀ 异号；椭圆取绝对值较小的那个
+        let larger_is_major = match kind {
+            ConicKind::Ellipse => !(a.clone() + c.clone()).is_positive(),
+            _ => approximate(&f0).is_some_and(|value| value < 0.0),
+        };
+        let ((major_value, major_axis), minor_value) = if larger_is_major {
+            ((&rotation.larger, &rotation.major), &rotation.smaller)
+        } else {
+            ((&rotation.smaller, &rotation.minor), &rotation.larger)
+        };
+        // 主轴方向取第一个非零分量为正，焦点按从左到右（或从下到上）排列
+        let major_axis = &upright(major_axis)?;
+        let a_squared = simplify(&Expression::divide(Expression::negate(f0.clone()), major_value.clone()))?;
+        let b_squared = if ellipse {
+            simplify(&Expression::divide(Expression::negate(f0), minor_value.clone()))?
+        } else {
+            simplify(&Expression::divide(f0, minor_value.clone()))?
+        };
+        if ellipse && h.is_zero() && a == c {
+            return self.circle(center, a_squared);
+        }
+
+        let c_squared = simplify(&Expression::binary_op(
+            if ellipse { BinaryOperator::Subtract } else { BinaryOperator::Add }, a_squared.clone(), b_squared.clone(),
+        ))?;
+        let focal = root(c_squared.clone());
+        let eccentricity = root(Expression::divide(c_squared, a_squared.clone()));
+        let foci = vec![
+            offset(&center, major_axis, &Expression::negate(focal.clone()))?,
+            offset(&center, major_axis, &focal)?,
+        ];
+
+        // 主轴沿坐标轴时写成 (x - x₀)^2/a^2 + (y - y₀)^2/b^2 = 1，斜向时用旋转后的 x'、y'
+        let square = |var: Expression, denominator: &Expression| Expression::divide(Expression::power(var, two()), denominator.clone());
+        let (x, y) = (self.shifted(0, &center[0])?, self.shifted(1, &center[1])?);
+        let (major, minor) = match Direction::of(major_axis) {
+            Direction::X(_) => (x, y),
+            Direction::Y(_) => (y, x),
+            Direction::Oblique => {
+                let names = primed(self.vars);
+                (Expression::variable(names[0].as_str()), Expression::variable(names[1].as_str()))
+            }
+        };
+        let lhs = match (ellipse, Direction::of(major_axis)) {
+            // 长轴沿 y 轴时仍把 x 项写在前面
+            (true, Direction::Y(_)) => Expression::add(square(minor, &b_squared), square(major, &a_squared)),
+            (true, _) => Expression::add(square(major, &a_squared), square(minor, &b_squared)),
+            (false, _) => Expression::subtract(square(major, &a_squared), square(minor, &b_squared)),
+        };
+        Ok(Conic {
+            kind,
+            standard_form: Some(Expression::binary_op(BinaryOperator::Equal, simplify(&lhs)?, Expression::number(Number::one()))),
+            center: Some(center),
+            vertex: None,
+            foci,
+            eccentricity: Some(eccentricity),
+        })
+    }
+
+    /// 圆：标准形 `(x - x₀)^2 + (y - y₀)^2 = r^2`，焦点即圆心，离心率为 0
+    fn circle(&self, center: Vec<Expression>, radius_squared: Expression) -> Result<Conic, ComputeError> {
+        let lhs = Expression::add(
+            Expression::power(self.shifted(0, &center[0])?, two()),
+            Expression::power(self.shifted(1, &center[1])?, two()),
+        );
+        Ok(Conic {
+            kind: ConicKind::Circle,
+            standard_form: Some(Expression::binary_op(BinaryOperator::Equal, lhs, radius_squared)),
+            foci: vec![center.clone()],
+            center: Some(center),
+            vertex: None,
+            eccentricity: Some(Expression::number(Number::zero())),
+        })
+    }
+
+    /// 抛物线：对称轴沿零特征值的特征向量 u 并使开口朝 u 的正方向，标准形为 `Y^2 = 2p X`
+    fn parabola(&self) -> Result<Conic, ComputeError> {
+        let (a, h, c, d, e, f) = (&self.a, &self.h, &self.c, &self.d, &self.e, &self.f);
+        let rotation = Rotation::new(a, h, c);
+        // 非零特征值 λ = A + C，二次项沿其特征向量 v
+        let lambda = num(&(a.clone() + c.clone()));
+        let (u, v) = if (a.clone() + c.clone()).is_positive() { (&rotation.minor, &rotation.major) } else { (&rotation.major, &rotation.minor) };
+        let along = |axis: &[Expression; 2]| simplify(&Expression::multiply(two(), Expression::add(
+            Expression::multiply(num(d), axis[0].clone()),
+            Expression::multiply(num(e), axis[1].clone()),
+        )));
+        // 新坐标下 λY² + e'Y + d'X + F = 0；d'/λ > 0 时翻转 u，使开口朝 X 正方向
+        let (mut d1, e1) = (along(u)?, along(v)?);
+        let mut u = u.clone();
+        if approximate(&Expression::divide(d1.clone(), lambda.clone())).is_some_and(|ratio| ratio > 0.0) {
+            d1 = simplify(&Expression::negate(d1))?;
+            u = [simplify(&Expression::negate(u[0].clone()))?, simplify(&Expression::negate(u[1].clone()))?];
+        }
+        // 配方：λ(Y - Y₀)² + d'(X - X₀) = 0
+        let y0 = simplify(&Expression::negate(Expression::divide(e1.clone(), Expression::multiply(two(), lambda.clone()))))?;
+        let constant = Expression::subtract(num(f), Expression::divide(
+            Expression::power(e1, two()), Expression::multiply(Expression::number(Number::from(4)), lambda.clone()),
+        ));
+        let x0 = simplify(&Expression::negate(Expression::divide(constant, d1.clone())))?;
+        let origin = [Expression::number(Number::zero()), Expression::number(Number::zero())];
+        let vertex = offset(&offset(&origin, &u, &x0)?, v, &y0)?;
+        // Y^2 = 2p X，焦点在顶点沿对称轴 p/2 处
+        let two_p = simplify(&Expression::negate(Expression::divide(d1, lambda)))?;
+        let focus = offset(&vertex, &u, &Expression::divide(two_p.clone(), Expression::number(Number::from(4))))?;
+
+        // 对称轴沿坐标轴时写成 (y - y₀)^2 = 2p(x - x₀) 等形式，斜向时用旋转后的 x'、y'
+        let (x, y) = (self.shifted(0, &vertex[0])?, self.shifted(1, &vertex[1])?);
+        let signed = |positive: bool| if positive { two_p.clone() } else { Expression::negate(two_p.clone()) };
+        let (square, linear) = match Direction::of(&u) {
+            Direction::X(positive) => (y, Expression::multiply(signed(positive), x)),
+            Direction::Y(positive) => (x, Expression::multiply(signed(positive), y)),
+            Direction::Oblique => {
+                let names = primed(self.vars);
+                (Expression::variable(names[1].as_str()), Expression::multiply(two_p.clone(), Expression::variable(names[0].as_str())))
+            }
+        };
+        let standard_form = Expression::binary_op(BinaryOperator::Equal, Expression::power(square, two()), simplify(&linear)?);
+        Ok(Conic {
+            kind: ConicKind::Parabola,
+            standard_form: Some(standard_form),
+            center: None,
+            vertex: Some(vertex),
+            foci: vec![focus],
+            eccentricity: Some(Expression::number(Number::one())),
+        })
+    }
+
+    /// 第 `index` 个变量平移到 `origin` 后的坐标 `x - x₀`
+    fn shifted(&self, index: usize, origin: &Expression) -> Result<Expression, ComputeError> {
+        let var = Expression::variable(self.vars[index]);
+        if is_zero(origin) {
+            return Ok(var);
+        }
+        Ok(Expression::add(var, simplify(&Expression::negate(origin.clone()))?))
+    }
+}
+
+/// 第一个非零分量为负时反向
+fn upright(axis: &[Expression; 2]) -> Result<[Expression; 2], ComputeError> {
+    let leading = if is_zero(&axis[0]) { &axis[1] } else { &axis[0] };
+    if approximate(leading).is_some_and(|value| value < 0.0) {
+        return Ok([simplify(&Expression::negate(axis[0].clone()))?, simplify(&Expression::negate(axis[1].clone()))?]);
+    }
+    Ok(axis.clone())
+}
+
+/// 点 `point + t·axis`
+fn offset(point: &[Expression], axis: &[Expression; 2], t: &Expression) -> Result<Vec<Expression>, ComputeError> {
+    point.iter().zip(axis)
+        .map(|(p, a)| simplify(&Expression::add(p.clone(), Expression::multiply(t.clone(), a.clone()))))
+        .collect()
+}
+
+/// 分母为 1 的有理数化成整数，系数平分与求中心时的除法会产生这种形式
+fn integral(n: Number) -> Number {
+    match n {
+        Number::Rational(r) if r.denom().is_one() => Number::Integer(r.to_integer()),
+        n => n,
+    }
+}
+
+fn num(n: &Number) -> Expression {
+    Expression::number(integral(n.clone()))
+}
+
+fn two() -> Expression {
+    Expression::number(Number::from(2))
+}
+
+/// 带撇号的新坐标名 `x'`、`y'`
+fn primed(vars: &[&str]) -> Vec<String> {
+    vars.iter().map(|var| format!("{}'", var)).collect()
+}
+
+/// `coefficient * var`，系数为 1 时省略
+fn scaled(coefficient: &Expression, var: Expression) -> Expression {
+    match coefficient {
+        Expression::Number(n) if n.is_one() => var,
+        _ => Expression::multiply(coefficient.clone(), var),
+    }
+}
+
+fn is_zero(expr: &Expression) -> bool {
+    matches!(expr, Expression::Number(n) if n.is_zero())
+}
+
+fn simplify(expr: &Expression) -> Result<Expression, ComputeError> {
+    match Simplifier::new().simplify(expr)? {
+        Expression::Number(n) => Ok(num(&n)),
+        expr => Ok(expr),
+    }
+}
+
+/// `√expr`，有理数 p/q 写成 `s√t/q`（pq = s²t，t 无平方因子）
+fn root(expr: Expression) -> Expression {
+    let expr = simplify_or_keep(expr);
+    let (numerator, denominator) = match &expr {
+        Expression::Number(Number::Integer(n)) if !n.is_negative() => (n.clone(), BigInt::one()),
+        Expression::Number(Number::Rational(r)) if !r.is_negative() => (r.numer() * r.denom(), r.denom().clone()),
+        _ => return simplify_or_keep(Expression::sqrt(expr)),
+    };
+    let (outside, inside) = SolverEngine::extract_square_factor(&numerator);
+    // s/q 先约分，得到 √3/2 而不是 2√3/4
+    let ratio = BigRational::new(outside, denominator);
+    if inside.is_one() {
+        return num(&Number::Rational(ratio));
+    }
+    let radical = scaled(&num(&Number::Integer(ratio.numer().clone())), Expression::sqrt(num(&Number::Integer(inside))));
+    if ratio.denom().is_one() {
+        return simplify_or_keep(radical);
+    }
+    simplify_or_keep(Expression::divide(radical, num(&Number::Integer(ratio.denom().clone()))))
+}
+
+/// 化简失败时保留原式，用于只含数的中间量
+fn simplify_or_keep(expr: Expression) -> Expression {
+    simplify(&expr).unwrap_or(expr)
+}
+
+/// 不含变量的表达式的数值，无法计算或非有限时为 None
+fn approximate(expr: &Expression) -> Option<f64> {
+    CalculusEngine::new().numerical_evaluate(expr, &HashMap::new()).ok().filter(|v| v.is_finite())
+}
+
+#[cfg(test)]
+#[path = "conic_tests.rs"]
+mod conic_tests;
@@ -0,0 +1,135 @@
+//! # 二次型与圆锥曲线测试
+//!
+//! 测试二次型矩阵、配方、各类圆锥曲线的标准形与几何参数，以及含参数时的分类。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::conic::{classify_conic, complete_square, quadratic_form_matrix, ConicKind};
+    use crate::engine::simplify::Simplifier;
+    use crate::core::Expression;
+    use crate::test_utils::parse;
+
+    fn simplified(input: &str) -> Expression {
+        Simplifier::new().simplify(&parse(input)).unwrap()
+    }
+
+    fn points(inputs: &[[&str; 2]]) -> Vec<Vec<Expression>> {
+        inputs.iter().map(|p| p.iter().map(|c| simplified(c)).collect()).collect()
+    }
+
+    #[test]
+    fn test_quadratic_form_matrix() {
+        assert_eq!(
+            quadratic_form_matrix(&parse("3*x^2 + 4*x*y - y^2 + x - 7"), &["x", "y"]).unwrap(),
+            simplified("[[3, 2], [2, -1]]")
+        );
+        assert_eq!(
+            quadratic_form_matrix(&parse("a*x^2 + x*y + b*y^2"), &["x", "y"]).unwrap(),
+            simplified("[[a, 1/2], [1/2, b]]")
+        );
+        assert!(quadratic_form_matrix(&parse("x^2*y"), &["x", "y"]).is_err());
+        assert!(quadratic_form_matrix(&parse("sin(x)"), &["x"]).is_err());
+    }
+
+    #[test]
+    fn test_complete_square() {
+        assert_eq!(complete_square(&parse("2*x^2 + 4*x + 5"), &["x"]).unwrap().to_string(), "2 * (x + 1)^2 + 3");
+        assert_eq!(complete_square(&parse("x^2 - 6*x"), &["x"]).unwrap().to_string(), "(x - 3)^2 - 9");
+        assert_eq!(complete_square(&parse("x^2 + 2*x + y^2 - 4*y"), &["x", "y"]).unwrap().to_string(), "(x + 1)^2 + (y - 2)^2 - 5");
+        // 含交叉项时旋转坐标：5x² + 4xy + 2y² 的特征值为 6 与 1
+        assert_eq!(complete_square(&parse("5*x^2 + 4*x*y + 2*y^2 - 6"), &["x", "y"]).unwrap().to_string(), "6 * x'^2 + y'^2 - 6");
+    }
+
+    #[test]
+    fn test_function_calls() {
+        assert_eq!(simplified("complete_square(x^2 + 2*x, x)").to_string(), "(x + 1)^2 - 1");
+        assert_eq!(simplified("quadratic_form_matrix(x^2 + 4*x*y, [x, y])"), simplified("[[1, 2], [2, 0]]"));
+        // 变量参数不是变量或变量列表时保持原样
+        assert!(matches!(simplified("complete_square(x^2, 2)"), Expression::Function { .. }));
+    }
+
+    #[test]
+    fn test_ellipse() {
+        let conic = classify_conic(&parse("x^2 + 4*y^2 - 4"), &["x", "y"]).unwrap();
+        assert_eq!(conic.kind, ConicKind::Ellipse);
+        assert_eq!(conic.standard_form.unwrap().to_string(), "x^2 / 4 + y^2 == 1");
+        assert_eq!(conic.center, Some(points(&[["0", "0"]]).remove(0)));
+        assert_eq!(conic.foci, points(&[["-sqrt(3)", "0"], ["sqrt(3)", "0"]]));
+        assert_eq!(conic.eccentricity, Some(simplified("sqrt(3)/2")));
+
+        // 长轴沿 y 轴且中心平移
+        let conic = classify_conic(&parse("4*(x - 1)^2 + (y + 2)^2 == 4"), &["x", "y"]).unwrap();
+        assert_eq!(conic.kind, ConicKind::Ellipse);
+        assert_eq!(conic.center, Some(points(&[["1", "-2"]]).remove(0)));
+        let foci: Vec<String> = conic.foci.iter().map(|p| p[1].to_string()).collect();
+        assert_eq!(foci, vec!["-2 - sqrt(3)", "-2 + sqrt(3)"]);
+    }
+
+    #[test]
+    fn test_circle_and_hyperbola() {
+        let conic = classify_conic(&parse("x^2 + y^2 - 2*x - 3"), &["x", "y"]).unwrap();
+        assert_eq!(conic.kind, ConicKind::Circle);
+        assert_eq!(conic.standard_form.unwrap().to_string(), "(x - 1)^2 + y^2 == 4");
+        assert_eq!(conic.eccentricity, Some(parse("0")));
+
+        let conic = classify_conic(&parse("x^2/9 - y^2/16 - 1"), &["x", "y"]).unwrap();
+        assert_eq!(conic.kind, ConicKind::Hyperbola);
+        assert_eq!(conic.foci, points(&[["-5", "0"], ["5", "0"]]));
+        assert_eq!(conic.eccentricity, Some(simplified("5/3")));
+
+        // xy = 1 的实轴沿 y = x
+        let conic = classify_conic(&parse("x*y - 1"), &["x", "y"]).unwrap();
+        assert_eq!(conic.kind, ConicKind::Hyperbola);
+        assert_eq!(conic.foci, points(&[["-sqrt(2)", "-sqrt(2)"], ["sqrt(2)", "sqrt(2)"]]));
+        assert_eq!(conic.eccentricity, Some(simplified("sqrt(2)")));
+    }
+
+    #[test]
+    fn test_parabola() {
+        let conic = classify_conic(&parse("y - x^2"), &["x", "y"]).unwrap();
+        assert_eq!(conic.kind, ConicKind::Parabola);
+        assert_eq!(conic.vertex, Some(points(&[["0", "0"]]).remove(0)));
+        assert_eq!(conic.foci, points(&[["0", "1/4"]]));
+        assert_eq!(conic.eccentricity, Some(parse("1")));
+
+        let conic = classify_conic(&parse("y^2 + 8*x - 2*y + 17"), &["x", "y"]).unwrap();
+        assert_eq!(conic.kind, ConicKind::Parabola);
+        assert_eq!(conic.vertex, Some(points(&[["-2", "1"]]).remove(0)));
+        assert_eq!(conic.foci, points(&[["-4", "1"]]));
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let kind = |input: &str| classify_conic(&parse(input), &["x", "y"]).unwrap().kind;
+        assert_eq!(kind("x^2 + y^2"), ConicKind::Point);
+        assert_eq!(kind("x^2 - y^2"), ConicKind::IntersectingLines);
+        assert_eq!(kind("x^2 - 1"), ConicKind::ParallelLines);
+        assert_eq!(kind("(x + y)^2"), ConicKind::CoincidentLines);
+        assert_eq!(kind("x^2 + y^2 + 1"), ConicKind::Empty);
+        assert!(classify_conic(&parse("x + y"), &["x", "y"]).is_err());
+        assert!(classify_conic(&parse("x^2"), &["x"]).is_err());
+    }
+
+    #[test]
+    fn test_parametric_cases() {
+        let conic = classify_conic(&parse("x^2 + y^2 - r"), &["x", "y"]).unwrap();
+        let ConicKind::Cases(cases) = conic.kind else { panic!("应按参数分类") };
+        let summary: Vec<(String, ConicKind)> = cases.into_iter()
+            .map(|case| (case.conditions.iter().map(|c| c.to_string()).collect::<Vec<_>>().join(", "), case.kind))
+            .collect();
+        assert_eq!(summary, vec![
+            ("r > 0".to_string(), ConicKind::Ellipse),
+            ("r < 0".to_string(), ConicKind::Empty),
+            ("r == 0".to_string(), ConicKind::Point),
+        ]);
+
+        let conic = classify_conic(&parse("x^2 - y^2 - a"), &["x", "y"]).unwrap();
+        let ConicKind::Cases(cases) = conic.kind else { panic!("应按参数分类") };
+        assert_eq!(cases.iter().map(|case| case.kind.clone()).collect::<Vec<_>>(), vec![ConicKind::Hyperbola, ConicKind::IntersectingLines]);
+        assert_eq!(cases[1].conditions, vec![simplified("a == 0")]);
+
+        // 参数只出现在一次项时类型不变，但不给出几何参数
+        let conic = classify_conic(&parse("x^2 + y^2 + a*x"), &["x", "y"]).unwrap();
+        assert!(matches!(conic.kind, ConicKind::Cases(_)) || conic.standard_form.is_none());
+    }
+}
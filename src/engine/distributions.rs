@@ -0,0 +1,363 @@
+//! # 概率分布
+//!
+//! 正态、二项与泊松分布的符号密度、分布函数、期望与方差。
+//! `normal_pdf(x, mu, sigma)` 这类函数在化简、求导、积分与数值计算时展开为闭式，正态分布函数用 erf 表示。
+//! 期望 E[g(X)] 对多项式 g 用矩公式给出 Σ / ∫ 的精确值，离散分布的其他 g 交给求和引擎。
+
+use std::collections::HashMap;
+use std::fmt;
+use crate::core::{Expression, Number, MathConstant, UnaryOperator};
+use super::ComputeError;
+use super::calculus::CalculusEngine;
+use super::polynomial::PolynomialEngine;
+use super::resultant;
+use super::simplify::Simplifier;
+use super::summation::SummationEngine;
+use crate::tr;
+
+/// 展开为闭式的分布函数名
+pub const DISTRIBUTION_FUNCTIONS: [&str; 6] = [
+    "normal_pdf", "normal_cdf", "binomial_pmf", "binomial_cdf", "poisson_pmf", "poisson_cdf",
+];
+
+/// 概率分布，参数可以是符号
+#[derive(Debug, Clone, PartialEq)]
+pub enum Distribution {
+    /// 正态分布 N(μ, σ²)
+    Normal { mu: Expression, sigma: Expression },
+    /// 二项分布 B(n, p)
+    Binomial { n: Expression, p: Expression },
+    /// 泊松分布 P(λ)
+    Poisson { lambda: Expression },
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Distribution::Normal { mu, sigma } => write!(f, "normal({}, {})", mu, sigma),
+            Distribution::Binomial { n, p } => write!(f, "binomial({}, {})", n, p),
+            Distribution::Poisson { lambda } => write!(f, "poisson({})", lambda),
+        }
+    }
+}
+
+impl Distribution {
+    /// 正态分布，σ 为具体数值时必须为正
+    pub fn normal(mu: Expression, sigma: Expression) -> Result<Self, ComputeError> {
+        if numeric(&sigma).is_some_and(|s| s <= 0.0) {
+            return Err(ComputeError::domain_error(tr!("distribution.normal_sigma", value = sigma)));
+        }
+        Ok(Distribution::Normal { mu, sigma })
+    }
+
+    /// 二项分布，n 为具体数值时必须是非负整数，p 为具体数值时必须在 [0, 1] 内
+    pub fn binomial(n: Expression, p: Expression) -> Result<Self, ComputeError> {
+        if numeric(&n).is_some_and(|n| n < 0.0 || n.fract() != 0.0) {
+            return Err(ComputeError::domain_error(tr!("distribution.binomial_trials", value = n)));
+        }
+        if numeric(&p).is_some_and(|p| !(0.0..=1.0).contains(&p)) {
+            return Err(ComputeError::domain_error(tr!("distribution.binomial_probability", value = p)));
+        }
+        Ok(Distribution::Binomial { n, p })
+    }
+
+    /// 泊松分布，λ 为具体数值时必须为正
+    pub fn poisson(lambda: Expression) -> Result<Self, ComputeError> {
+        if numeric(&lambda).is_some_and(|l| l <= 0.0) {
+            return Err(ComputeError::domain_error(tr!("distribution.poisson_rate", value = lambda)));
+        }
+        Ok(Distribution::Poisson { lambda })
+    }
+
+    /// 从 `normal(mu, sigma)`、`binomial(n, p)`、`poisson(lambda)` 形式的表达式构造，不是分布时返回 None
+    pub fn from_expression(expr: &Expression) -> Option<Result<Self, ComputeError>> {
+        let Expression::Function { name, args } = expr else {
+            return None;
+        };
+        match (name.as_str(), args.as_slice()) {
+            ("normal", [mu, sigma]) => Some(Self::normal(mu.clone(), sigma.clone())),
+            ("normal", []) => Some(Self::normal(int(0), int(1))),
+            ("binomial", [n, p]) => Some(Self::binomial(n.clone(), p.clone())),
+            ("poisson", [lambda]) => Some(Self::poisson(lambda.clone())),
+            ("normal" | "binomial" | "poisson", _) => {
+                Some(Err(ComputeError::domain_error(tr!("distribution.argument_count", name = name))))
+            }
+            _ => None,
+        }
+    }
+
+    /// 是否为离散分布
+    pub fn is_discrete(&self) -> bool {
+        !matches!(self, Distribution::Normal { .. })
+    }
+
+    /// 在 `x` 处的概率密度，离散分布为概率质量函数
+    pub fn pdf(&self, x: &Expression) -> Expression {
+        match self {
+            // e^{-(x-μ)²/(2σ²)} / (σ√(2π))
+            Distribution::Normal { mu, sigma } => {
+                let deviation = Expression::subtract(x.clone(), mu.clone());
+                let exponent = Expression::negate(Expression::divide(
+                    Expression::power(deviation, int(2)),
+                    Expression::multiply(int(2), Expression::power(sigma.clone(), int(2))),
+                ));
+                Expression::divide(
+                    Expression::function("exp", vec![exponent]),
+                    Expression::multiply(sigma.clone(), sqrt(Expression::multiply(int(2), pi()))),
+                )
+            }
+            // C(n, k) p^k (1-p)^{n-k}
+            Distribution::Binomial { n, p } => {
+                let rest = Expression::subtract(n.clone(), x.clone());
+                let choose = Expression::divide(
+                    factorial(n.clone()),
+                    Expression::multiply(factorial(x.clone()), factorial(rest.clone())),
+                );
+                Expression::multiply(
+                    choose,
+                    Expression::multiply(
+                        Expression::power(p.clone(), x.clone()),
+                        Expression::power(Expression::subtract(int(1), p.clone()), rest),
+                    ),
+                )
+            }
+            // λ^k e^{-λ} / k!
+            Distribution::Poisson { lambda } => Expression::divide(
+                Expression::multiply(
+                    Expression::power(lambda.clone(), x.clone()),
+                    Expression::function("exp", vec![Expression::negate(lambda.clone())]),
+                ),
+                factorial(x.clone()),
+            ),
+        }
+    }
+
+    /// 在 `x` 处的分布函数 P(X ≤ x)
+    ///
+    /// 正态分布用 erf 表示；离散分布在 `x` 为具体数值时逐项求和，否则保留符号求和。
+    pub fn cdf(&self, x: &Expression) -> Result<Expression, ComputeError> {
+        if let Distribution::Normal { mu, sigma } = self {
+            // (1 + erf((x-μ)/(σ√2))) / 2
+            let standardized = Expression::divide(
+                Expression::subtract(x.clone(), mu.clone()),
+                Expression::multiply(sigma.clone(), sqrt(int(2))),
+            );
+            let erf = Expression::function("erf", vec![standardized]);
+            return simplify(Expression::divide(Expression::add(int(1), erf), int(2)));
+        }
+
+        let index = self.fresh_index(x);
+        let term = self.pdf(&Expression::variable(&index));
+        let upper = match numeric(x) {
+            Some(value) if value < 0.0 => return Ok(int(0)),
+            Some(value) => int(value.floor() as i64),
+            None => Expression::function("floor", vec![x.clone()]),
+        };
+        Ok(SummationEngine::new().sum(&term, &index, &int(0), &upper)?.value)
+    }
+
+    /// 期望
+    pub fn mean(&self) -> Expression {
+        match self {
+            Distribution::Normal { mu, .. } => mu.clone(),
+            Distribution::Binomial { n, p } => Expression::multiply(n.clone(), p.clone()),
+            Distribution::Poisson { lambda } => lambda.clone(),
+        }
+    }
+
+    /// 方差
+    pub fn variance(&self) -> Expression {
+        match self {
+            Distribution::Normal { sigma, .. } => Expression::power(sigma.clone(), int(2)),
+            Distribution::Binomial { n, p } => Expression::multiply(
+                Expression::multiply(n.clone(), p.clone()),
+                Expression::subtract(int(1), p.clone()),
+            ),
+            Distribution::Poisson { lambda } => lambda.clone(),
+        }
+    }
+
+    /// E[g(X)]，`var` 是 `g` 中代表随机变量的符号
+    ///
+    /// `g` 关于 `var` 是多项式时按矩逐项求出 Σ g(k)·P(k) 或 ∫ g(x)·f(x) dx 的精确值；
+    /// 否则离散分布交给求和引擎（无法求和时保留符号求和），正态分布报不支持。
+    pub fn expectation(&self, g: &Expression, var: &str) -> Result<Expression, ComputeError> {
+        if let Ok(coefficients) = resultant::coefficients(g, var) {
+            let terms = coefficients.iter().enumerate()
+                .filter(|(_, c)| !c.is_zero())
+                .map(|(k, c)| Expression::multiply(c.to_expression(), self.raw_moment(k)));
+            let value = terms.reduce(Expression::add).unwrap_or_else(|| int(0));
+            // 参数都是多项式时整理成展开式，(x-μ)⁴ 的期望直接得到 3σ⁴
+            return match PolynomialEngine::new().expression_to_polynomial(&value) {
+                Ok(polynomial) => resultant::finish(polynomial),
+                Err(_) => simplify(value),
+            };
+        }
+
+        let (upper, note) = match self {
+            Distribution::Normal { .. } => {
+                return Err(ComputeError::unsupported_operation(tr!(
+                    "distribution.normal_expectation", variable = var, found = g
+                )));
+            }
+            Distribution::Binomial { n, .. } => (n.clone(), tr!("distribution.binomial")),
+            Distribution::Poisson { .. } => (Expression::Constant(MathConstant::PositiveInfinity), tr!("distribution.poisson")),
+        };
+        let index = self.fresh_index(g);
+        let k = Expression::variable(&index);
+        let bindings = HashMap::from([(var.to_string(), k.clone())]);
+        let term = Expression::multiply(g.substitute(&bindings), self.pdf(&k));
+        let sum = SummationEngine::new().sum(&term, &index, &int(0), &upper)
+            .map_err(|e| ComputeError::domain_error(tr!("distribution.expectation_sum", distribution = note, error = e)))?;
+        Ok(sum.value)
+    }
+
+    /// k 阶原点矩 E[X^k]
+    fn raw_moment(&self, k: usize) -> Expression {
+        match self {
+            // Σ_{j 偶} C(k, j) μ^{k-j} σ^j (j-1)!!
+            Distribution::Normal { mu, sigma } => (0..=k).step_by(2)
+                .map(|j| {
+                    let coefficient = binomial_coefficient(k, j) * double_factorial(j.saturating_sub(1));
+                    Expression::multiply(
+                        Expression::Number(Number::from(coefficient)),
+                        Expression::multiply(power(mu, k - j), power(sigma, j)),
+                    )
+                })
+                .reduce(Expression::add)
+                .unwrap_or_else(|| int(1)),
+            // 普通幂按第二类 Stirling 数换成下降阶乘幂：E[X^(i下降)] 对二项分布为 n(n-1)…(n-i+1) p^i，对泊松分布为 λ^i
+            Distribution::Binomial { .. } | Distribution::Poisson { .. } => (0..=k)
+                .filter_map(|i| {
+                    let stirling = stirling_second(k, i);
+                    (stirling != 0).then(|| Expression::multiply(
+                        Expression::Number(Number::from(stirling)),
+                        self.factorial_moment(i),
+                    ))
+                })
+                .reduce(Expression::add)
+                .unwrap_or_else(|| int(0)),
+        }
+    }
+
+    /// 离散分布的 i 阶下降阶乘矩
+    fn factorial_moment(&self, i: usize) -> Expression {
+        match self {
+            Distribution::Binomial { n, p } => (0..i)
+                .map(|j| Expression::subtract(n.clone(), int(j as i64)))
+                .fold(power(p, i), |product, factor| Expression::multiply(factor, product)),
+            Distribution::Poisson { lambda } => power(lambda, i),
+            Distribution::Normal { .. } => unreachable!("正态分布按原点矩计算"),
+        }
+    }
+
+    /// 求和指标名，避开参数与 `expr` 中已有的变量
+    fn fresh_index(&self, expr: &Expression) -> String {
+        let mut used = expr.get_variables();
+        match self {
+            Distribution::Normal { mu, sigma } => used.extend(mu.get_variables().into_iter().chain(sigma.get_variables())),
+            Distribution::Binomial { n, p } => used.extend(n.get_variables().into_iter().chain(p.get_variables())),
+            Distribution::Poisson { lambda } => used.extend(lambda.get_variables()),
+        }
+        ["k", "j", "i"].into_iter().map(String::from)
+            .chain((1..).map(|m| format!("k{}", m)))
+            .find(|name| !used.contains(name))
+            .expect("候选名称无穷多")
+    }
+}
+
+/// 把分布函数调用展开为闭式，名称不是分布函数时返回 None
+///
+/// `normal_pdf(x)`、`normal_cdf(x)` 省略参数时为标准正态分布。
+pub fn expand(name: &str, args: &[Expression]) -> Option<Result<Expression, ComputeError>> {
+    if !DISTRIBUTION_FUNCTIONS.contains(&name) {
+        return None;
+    }
+    let distribution = match (name, args) {
+        ("normal_pdf" | "normal_cdf", [_]) => Distribution::normal(int(0), int(1)),
+        ("normal_pdf" | "normal_cdf", [_, mu, sigma]) => Distribution::normal(mu.clone(), sigma.clone()),
+        ("binomial_pmf" | "binomial_cdf", [_, n, p]) => Distribution::binomial(n.clone(), p.clone()),
+        ("poisson_pmf" | "poisson_cdf", [_, lambda]) => Distribution::poisson(lambda.clone()),
+        _ => return Some(Err(ComputeError::domain_error(tr!("distribution.function_argument_count", name = name)))),
+    };
+    Some(distribution.and_then(|distribution| {
+        if name.ends_with("_cdf") {
+            distribution.cdf(&args[0])
+        } else {
+            simplify(distribution.pdf(&args[0]))
+        }
+    }))
+}
+
+/// 计算 `expectation(dist, g, x)`，`dist` 写作 `normal(mu, sigma)` 等形式；
+/// 省略 `x` 时取 `g` 中唯一的变量。`dist` 不是分布或无法确定变量时返回 None
+pub fn expectation_call(args: &[Expression]) -> Option<Result<Expression, ComputeError>> {
+    let (dist, g, var) = match args {
+        [dist, g, Expression::Variable(var)] => (dist, g, var.clone()),
+        [dist, g] => match g.get_variables().as_slice() {
+            [var] => (dist, g, var.clone()),
+            _ => return None,
+        },
+        _ => return None,
+    };
+    let distribution = Distribution::from_expression(dist)?;
+    Some(distribution.and_then(|distribution| distribution.expectation(g, &var)))
+}
+
+/// 参数的数值，含符号时返回 None
+fn numeric(expr: &Expression) -> Option<f64> {
+    if !expr.get_variables().is_empty() {
+        return None;
+    }
+    CalculusEngine::new().numerical_evaluate(expr, &HashMap::new()).ok()
+}
+
+fn simplify(expr: Expression) -> Result<Expression, ComputeError> {
+    Simplifier::new().simplify(&expr)
+}
+
+fn int(n: i64) -> Expression {
+    Expression::Number(Number::integer(n))
+}
+
+fn pi() -> Expression {
+    Expression::Constant(MathConstant::Pi)
+}
+
+fn sqrt(expr: Expression) -> Expression {
+    Expression::function("sqrt", vec![expr])
+}
+
+fn factorial(expr: Expression) -> Expression {
+    Expression::unary_op(UnaryOperator::Factorial, expr)
+}
+
+fn power(base: &Expression, exponent: usize) -> Expression {
+    Expression::power(base.clone(), int(exponent as i64))
+}
+
+fn binomial_coefficient(n: usize, k: usize) -> i64 {
+    (0..k).fold(1, |c, i| c * (n - i) as i64 / (i + 1) as i64)
+}
+
+/// m!!，约定 0!! = 1
+fn double_factorial(m: usize) -> i64 {
+    (1..=m).rev().step_by(2).map(|i| i as i64).product()
+}
+
+/// 第二类 Stirling 数 S(k, i)
+fn stirling_second(k: usize, i: usize) -> i64 {
+    let mut row = vec![1i64];
+    for n in 1..=k {
+        let mut next = vec![0; n + 1];
+        for (j, value) in next.iter_mut().enumerate().skip(1) {
+            *value = row.get(j - 1).copied().unwrap_or(0) + j as i64 * row.get(j).copied().unwrap_or(0);
+        }
+        row = next;
+    }
+    row.get(i).copied().unwrap_or(0)
+}
+
+#[cfg(test)]
+#[path = "distributions_tests.rs"]
+mod distributions_tests;
@@ -0,0 +1,91 @@
+//! # 概率分布测试
+//!
+//! 测试分布函数的闭式展开、与标准表对照的数值、矩公式给出的期望以及参数检查。
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use crate::engine::distributions::{Distribution, expand};
+    use crate::engine::calculus::CalculusEngine;
+    use crate::engine::simplify::Simplifier;
+    use crate::engine::ComputeError;
+    use crate::core::Expression;
+    use crate::test_utils::parse;
+
+    fn simplified(input: &str) -> Result<Expression, ComputeError> {
+        Simplifier::new().simplify(&parse(input))
+    }
+
+    fn value(input: &str) -> f64 {
+        CalculusEngine::new().numerical_evaluate(&parse(input), &HashMap::new()).unwrap()
+    }
+
+    #[test]
+    fn test_normal_cdf_matches_table() {
+        for (z, phi) in [(0.0, 0.5), (1.0, 0.8413), (1.96, 0.9750), (-1.0, 0.1587), (2.58, 0.9951)] {
+            assert!((value(&format!("normal_cdf({})", z)) - phi).abs() < 1e-4, "Φ({})", z);
+        }
+        // 非标准正态先标准化：X ~ N(100, 15²) 时 P(X ≤ 130) = Φ(2)
+        assert!((value("normal_cdf(130, 100, 15)") - 0.9772).abs() < 1e-4);
+        assert!((value("normal_pdf(0)") - 0.3989).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_discrete_distributions() {
+        assert_eq!(simplified("binomial_pmf(2, 4, 1/2)").unwrap(), simplified("3/8").unwrap());
+        assert_eq!(simplified("binomial_cdf(2, 4, 1/2)").unwrap(), simplified("11/16").unwrap());
+        assert!((value("poisson_pmf(2, 3)") - 0.2240).abs() < 1e-4);
+        assert!((value("poisson_cdf(2, 3)") - 0.4232).abs() < 1e-4);
+        assert_eq!(simplified("poisson_cdf(-1, 3)").unwrap(), parse("0"));
+    }
+
+    #[test]
+    fn test_expectation_and_moments() {
+        let normal = Distribution::normal(parse("mu"), parse("sigma")).unwrap();
+        let binomial = Distribution::binomial(parse("n"), parse("p")).unwrap();
+        let poisson = Distribution::poisson(parse("lambda")).unwrap();
+
+        // 全概率为 1，一阶矩与 mean 一致
+        for (distribution, var) in [(&normal, "x"), (&binomial, "k"), (&poisson, "k")] {
+            assert_eq!(distribution.expectation(&parse("1"), var).unwrap(), parse("1"));
+            let mean = Simplifier::new().simplify(&distribution.mean()).unwrap();
+            assert_eq!(distribution.expectation(&parse(var), var).unwrap(), mean);
+        }
+        assert_eq!(normal.expectation(&parse("(x - mu)^2"), "x").unwrap(), simplified("sigma^2").unwrap());
+        assert_eq!(normal.expectation(&parse("(x - mu)^4"), "x").unwrap().to_string(), "3 * sigma^4");
+        assert_eq!(poisson.expectation(&parse("k^2"), "k").unwrap().to_string(), "lambda^2 + lambda");
+        assert_eq!(binomial.expectation(&parse("k^2"), "k").unwrap().to_string(), "n^2 * p^2 - n * p^2 + n * p");
+
+        // 非多项式的 g 对离散分布逐项求和
+        assert_eq!(simplified("expectation(binomial(4, 1/2), 2^k, k)").unwrap(), simplified("81/16").unwrap());
+        assert_eq!(simplified("expectation(normal(0, 1), x^2)").unwrap(), parse("1"));
+        assert!(normal.expectation(&parse("sin(x)"), "x").is_err());
+    }
+
+    #[test]
+    fn test_symbolic_calculus() {
+        // 对 μ 求导：∂f/∂μ = f · (x - μ) / σ²，是极大似然推导的第一步
+        let pdf = expand("normal_pdf", &[parse("x"), parse("mu"), parse("sigma")]).unwrap().unwrap();
+        let derivative = CalculusEngine::new().differentiate(&pdf, "mu").unwrap();
+        let vars: HashMap<String, f64> = [("x", 1.3), ("mu", 0.4), ("sigma", 1.7)].iter().map(|(n, v)| (n.to_string(), *v)).collect();
+        let calculus = CalculusEngine::new();
+        let expected = calculus.numerical_evaluate(&pdf, &vars).unwrap() * (1.3 - 0.4) / (1.7 * 1.7);
+        assert!((calculus.numerical_evaluate(&derivative, &vars).unwrap() - expected).abs() < 1e-12);
+
+        // 正态密度的原函数是分布函数，全域积分为 1
+        let antiderivative = calculus.integrate(&parse("normal_pdf(x, 1, 2)"), "x").unwrap();
+        let at = |x: f64| calculus.numerical_evaluate(&antiderivative, &HashMap::from([("x".to_string(), x)])).unwrap();
+        assert!((at(40.0) - at(-40.0) - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_invalid_parameters() {
+        for input in ["normal_pdf(x, 0, 0)", "normal_cdf(x, 0, -2)", "binomial_pmf(k, 4, 3/2)", "binomial_pmf(k, 5/2, 1/2)", "poisson_pmf(k, -1)"] {
+            assert!(matches!(simplified(input), Err(ComputeError::DomainError { .. })), "{}", input);
+        }
+        assert!(Distribution::binomial(parse("n"), parse("-1/2")).is_err());
+        assert!(matches!(simplified("normal_pdf(x, 0)"), Err(ComputeError::DomainError { .. })));
+        // 符号参数不做检查
+        assert!(Distribution::normal(parse("mu"), parse("sigma")).is_ok());
+    }
+}
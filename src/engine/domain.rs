@@ -0,0 +1,632 @@
+//! # 定义域与值域分析
+//!
+//! 分析单变量表达式在实数范围内的自然定义域，并通过驻点与端点估计值域。
+//! 定义域约束被归结为若干表达式的符号条件（非零、非负、正），
+//! 用所有临界点切分实轴后逐段数值检验，再合并成区间并集。
+
+use std::collections::HashMap;
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator, MathConstant};
+use super::ComputeError;
+use super::calculus::CalculusEngine;
+use super::simplify::Simplifier;
+use super::solver::SolverEngine;
+use crate::tr;
+
+/// 判断数值是否为零时的容差
+const ZERO_TOLERANCE: f64 = 1e-9;
+
+/// 判断函数值趋于无穷时使用的阈值
+const DIVERGENCE_THRESHOLD: f64 = 1e6;
+
+/// 定义域或值域的分析结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct DomainAnalysis {
+    /// 区间、区间并集（`∪`）或孤立点集合
+    pub set: Expression,
+    /// 是否为部分分析（存在无法分析的成分，结果为保守估计）
+    pub partial: bool,
+    /// 无法分析的成分说明
+    pub notes: Vec<String>,
+}
+
+impl DomainAnalysis {
+    /// 是否为部分分析结果
+    pub fn is_partial(&self) -> bool {
+        self.partial
+    }
+}
+
+/// 定义域约束的符号条件
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Condition {
+    /// 表达式不为零
+    NonZero,
+    /// 表达式大于等于零
+    NonNegative,
+    /// 表达式大于零
+    Positive,
+}
+
+/// 单个定义域约束
+#[derive(Debug, Clone)]
+struct Constraint {
+    expr: Expression,
+    condition: Condition,
+}
+
+/// 区间端点：精确表达式与数值近似
+#[derive(Debug, Clone)]
+struct Endpoint {
+    expr: Expression,
+    value: f64,
+}
+
+impl Endpoint {
+    fn negative_infinity() -> Self {
+        Self { expr: Expression::Constant(MathConstant::NegativeInfinity), value: f64::NEG_INFINITY }
+    }
+
+    fn positive_infinity() -> Self {
+        Self { expr: Expression::Constant(MathConstant::PositiveInfinity), value: f64::INFINITY }
+    }
+}
+
+/// 实数区间
+#[derive(Debug, Clone)]
+struct RealInterval {
+    start: Endpoint,
+    end: Endpoint,
+    start_inclusive: bool,
+    end_inclusive: bool,
+}
+
+/// 定义域与值域分析器
+pub struct DomainAnalyzer {
+    solver: SolverEngine,
+    calculus: CalculusEngine,
+}
+
+impl DomainAnalyzer {
+    /// 创建新的分析器
+    pub fn new() -> Self {
+        Self {
+            solver: SolverEngine::new(),
+            calculus: CalculusEngine::new(),
+        }
+    }
+
+    /// 分析表达式关于 `var` 的自然定义域
+    ///
+    /// 检查分母非零、偶次根号内非负、对数参数为正等约束。无法分析的约束会被忽略，
+    /// 此时结果是真实定义域的超集，并标注为部分分析。
+    pub fn domain(&self, expr: &Expression, var: &str) -> Result<DomainAnalysis, ComputeError> {
+        let mut constraints = Vec::new();
+        let mut notes = Vec::new();
+        self.collect_constraints(expr, var, &mut constraints, &mut notes)?;
+
+        // 求出全部临界点，无法求出临界点的约束视为无法分析
+        let mut points = Vec::new();
+        constraints.retain(|constraint| {
+            match self.real_points(&constraint.expr, var) {
+                Ok(found) => {
+                    points.extend(found);
+                    true
+                }
+                Err(_) => {
+                    notes.push(tr!("domain.constraint_critical_points", constraint = format!("{:?}", constraint.expr)));
+                    false
+                }
+            }
+        });
+        let points = Self::sort_points(points);
+
+        let holds = |x: f64, at_point: bool| {
+            constraints.iter().all(|constraint| self.check_constraint(constraint, var, x, at_point))
+        };
+        let intervals = Self::partition(&points, holds);
+
+        Ok(DomainAnalysis {
+            set: Self::intervals_to_expression(&intervals),
+            partial: !notes.is_empty(),
+            notes,
+        })
+    }
+
+    /// 估计表达式在给定定义域上的值域
+    ///
+    /// 在每个区间上比较驻点处的函数值与端点处的函数值（或极限），取整体的上下界。
+    /// 多个区间时返回各段值域的凸包，并标注为部分分析。
+    pub fn range_estimate(&self, expr: &Expression, var: &str, domain: &Expression) -> Result<DomainAnalysis, ComputeError> {
+        let intervals = Self::expression_to_intervals(domain)?;
+        let mut notes = Vec::new();
+        if intervals.is_empty() {
+            return Ok(DomainAnalysis { set: Expression::Set(Vec::new()), partial: false, notes });
+        }
+        if intervals.len() > 1 {
+            notes.push(tr!("domain.convex_hull").to_string());
+        }
+        if Self::contains_discontinuous_function(expr) {
+            notes.push(tr!("domain.discontinuous").to_string());
+        }
+
+        let mut simplifier = Simplifier::new();
+        let derivative = self.calculus.differentiate(expr, var)
+            .and_then(|d| simplifier.simplify(&d));
+        let stationary = match derivative {
+            Ok(d) => match self.real_points(&d, var) {
+                Ok(points) => points,
+                Err(_) => {
+                    notes.push(tr!("domain.derivative_roots").to_string());
+                    Vec::new()
+                }
+            },
+            Err(_) => {
+                notes.push(tr!("domain.derivative").to_string());
+                Vec::new()
+            }
+        };
+
+        // 候选值：(数值, 精确表达式, 是否取到)
+        let mut candidates: Vec<(f64, Expression, bool)> = Vec::new();
+        for interval in &intervals {
+            for point in &stationary {
+                let inside = (point.value > interval.start.value || (interval.start_inclusive && point.value >= interval.start.value))
+                    && (point.value < interval.end.value || (interval.end_inclusive && point.value <= interval.end.value));
+                if inside {
+                    if let Some(candidate) = self.value_at(&mut simplifier, expr, var, point) {
+                        candidates.push((candidate.0, candidate.1, true));
+                    }
+                }
+            }
+            for (endpoint, inclusive, towards) in [
+                (&interval.start, interval.start_inclusive, 1.0),
+                (&interval.end, interval.end_inclusive, -1.0),
+            ] {
+                if let Some(candidate) = self.endpoint_value(&mut simplifier, expr, var, endpoint, inclusive, towards) {
+                    candidates.push(candidate);
+                }
+            }
+        }
+
+        if candidates.is_empty() {
+            notes.push(tr!("domain.no_values").to_string());
+            let whole = RealInterval {
+                start: Endpoint::negative_infinity(),
+                end: Endpoint::positive_infinity(),
+                start_inclusive: false,
+                end_inclusive: false,
+            };
+            return Ok(DomainAnalysis { set: Self::intervals_to_expression(&[whole]), partial: true, notes });
+        }
+
+        let pick = |better: fn(f64, f64) -> bool| {
+            let best = candidates.iter()
+                .fold(None::<&(f64, Expression, bool)>, |acc, c| match acc {
+                    // 数值相同时优先取能取到的候选
+                    Some(a) if !(better(c.0, a.0) || Self::same_value(c.0, a.0) && c.2 && !a.2) => Some(a),
+                    _ => Some(c),
+                })
+                .unwrap();
+            (Endpoint { expr: best.1.clone(), value: best.0 }, best.2 && best.0.is_finite())
+        };
+        let (start, start_inclusive) = pick(|a, b| a < b && !Self::same_value(a, b));
+        let (end, end_inclusive) = pick(|a, b| a > b && !Self::same_value(a, b));
+
+        let range = RealInterval { start, end, start_inclusive, end_inclusive };
+        Ok(DomainAnalysis {
+            set: Self::intervals_to_expression(&[range]),
+            partial: !notes.is_empty(),
+            notes,
+        })
+    }
+
+    /// 递归收集定义域约束
+    fn collect_constraints(&self, expr: &Expression, var: &str, constraints: &mut Vec<Constraint>, notes: &mut Vec<String>) -> Result<(), ComputeError> {
+        let mut require = |expr: &Expression, condition: Condition| {
+            constraints.push(Constraint { expr: expr.clone(), condition });
+        };
+
+        match expr {
+            Expression::Number(_) | Expression::Variable(_) | Expression::Constant(_) => {}
+
+            Expression::BinaryOp { op, left, right } => {
+                match op {
+                    BinaryOperator::Divide => require(right, Condition::NonZero),
+                    BinaryOperator::Power => {
+                        if let Some(condition) = Self::power_condition(right, var) {
+                            require(left, condition);
+                        }
+                    }
+                    BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply => {}
+                    _ => notes.push(tr!("domain.unsupported_operation", operation = format!("{:?}", op))),
+                }
+                self.collect_constraints(left, var, constraints, notes)?;
+                self.collect_constraints(right, var, constraints, notes)?;
+            }
+
+            Expression::UnaryOp { op, operand } => {
+                if let Some(call) = expr.to_function_call() {
+                    return self.collect_constraints(&call, var, constraints, notes);
+                }
+                match op {
+                    UnaryOperator::Sqrt => require(operand, Condition::NonNegative),
+                    UnaryOperator::Negate | UnaryOperator::Plus | UnaryOperator::Abs => {}
+                    _ => notes.push(tr!("domain.unsupported_operation", operation = format!("{:?}", op))),
+                }
+                self.collect_constraints(operand, var, constraints, notes)?;
+            }
+
+            Expression::Function { name, args } => {
+                match (name.as_str(), args.as_slice()) {
+                    ("sqrt", [arg]) => require(arg, Condition::NonNegative),
+                    ("root", [arg, Expression::Number(n)]) => {
+                        if n.to_integer().is_none_or(|n| &n % 2 == 0.into()) {
+                            require(arg, Condition::NonNegative);
+                        }
+                    }
+                    ("ln" | "log" | "log10" | "log2", [arg]) => require(arg, Condition::Positive),
+                    ("asin" | "acos" | "arcsin" | "arccos", [arg]) => require(&Self::unit_bound(arg), Condition::NonNegative),
+                    ("atanh", [arg]) => require(&Self::unit_bound(arg), Condition::Positive),
+                    ("acosh", [arg]) => require(&Expression::subtract(arg.clone(), Expression::Number(Number::one())), Condition::NonNegative),
+                    ("tan" | "sec" | "cot" | "csc", _) => notes.push(tr!("domain.singularities", name = name)),
+                    ("Ci", [arg]) => require(arg, Condition::Positive),
+                    ("Ei", [arg]) => require(arg, Condition::NonZero),
+                    ("li", [arg]) => {
+                        require(arg, Condition::Positive);
+                        require(&Expression::subtract(arg.clone(), Expression::Number(Number::one())), Condition::NonZero);
+                    }
+                    ("sin" | "cos" | "atan" | "arctan" | "sinh" | "cosh" | "tanh" | "asinh" |
+                     "exp" | "abs" | "cbrt" | "floor" | "ceil" | "round" | "trunc" |
+                     "erf" | "erfc" | "Si" | "sinc", [_]) => {}
+                    _ => notes.push(tr!("domain.unknown_function", name = name)),
+                }
+                for arg in args {
+                    self.collect_constraints(arg, var, constraints, notes)?;
+                }
+            }
+
+            _ => {
+                return Err(ComputeError::UnsupportedOperation {
+                    operation: tr!("domain.non_scalar").to_string(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 幂运算对底数的要求：负指数要求非零，偶数分母的分数指数要求非负，含变量的指数要求为正
+    fn power_condition(exponent: &Expression, var: &str) -> Option<Condition> {
+        if exponent.get_variables().iter().any(|v| v == var) {
+            return Some(Condition::Positive);
+        }
+        let (negative, even_root) = match exponent {
+            Expression::Number(Number::Integer(_)) => (exponent_is_negative(exponent), false),
+            Expression::Number(Number::Rational(r)) => (exponent_is_negative(exponent), r.denom() % 2u32 == 0u32.into()),
+            Expression::Number(Number::Float(f)) => (*f < 0.0, f.fract() != 0.0),
+            Expression::BinaryOp { op: BinaryOperator::Divide, left, right } => {
+                match (left.as_ref(), right.as_ref()) {
+                    (Expression::Number(Number::Integer(p)), Expression::Number(Number::Integer(q))) => {
+                        ((p < &0.into()) != (q < &0.into()), q % 2 == 0.into())
+                    }
+                    _ => (false, false),
+                }
+            }
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+                match Self::power_condition(operand, var) {
+                    Some(Condition::NonNegative) | Some(Condition::Positive) => return Some(Condition::Positive),
+                    _ => return Some(Condition::NonZero),
+                }
+            }
+            _ => (false, false),
+        };
+
+        match (negative, even_root) {
+            (true, true) => Some(Condition::Positive),
+            (false, true) => Some(Condition::NonNegative),
+            (true, false) => Some(Condition::NonZero),
+            (false, false) => None,
+        }
+    }
+
+    /// 构造 1 - u^2，用于 |u| ≤ 1 的约束
+    fn unit_bound(arg: &Expression) -> Expression {
+        Expression::subtract(
+            Expression::Number(Number::one()),
+            Expression::power(arg.clone(), Expression::Number(Number::integer(2))),
+        )
+    }
+
+    /// 求表达式的实零点与极点，按从小到大排列并去重
+    pub(crate) fn zeros_and_poles(&self, expr: &Expression, var: &str) -> Result<Vec<(Expression, f64)>, ComputeError> {
+        let points = Self::sort_points(self.real_points(expr, var)?);
+        Ok(points.into_iter().map(|p| (p.expr, p.value)).collect())
+    }
+
+    /// 求表达式的实零点与极点（仅支持由多项式经乘除、数值次幂、根号构成的表达式）
+    fn real_points(&self, expr: &Expression, var: &str) -> Result<Vec<Endpoint>, ComputeError> {
+        if !expr.get_variables().iter().any(|v| v == var) {
+            return Ok(Vec::new());
+        }
+
+        match expr {
+            Expression::BinaryOp { op: BinaryOperator::Multiply | BinaryOperator::Divide, left, right } => {
+                let mut points = self.real_points(left, var)?;
+                points.extend(self.real_points(right, var)?);
+                Ok(points)
+            }
+            Expression::BinaryOp { op: BinaryOperator::Power, left, right }
+                if matches!(right.as_ref(), Expression::Number(_)) => {
+                self.real_points(left, var)
+            }
+            Expression::UnaryOp { op: UnaryOperator::Negate | UnaryOperator::Sqrt, operand } => self.real_points(operand, var),
+            Expression::Function { name, args } if matches!(name.as_str(), "sqrt" | "cbrt" | "root") && !args.is_empty() => {
+                self.real_points(&args[0], var)
+            }
+            _ => {
+                let roots = match self.solver.real_roots(expr, var) {
+                    Ok(roots) => roots,
+                    Err(err) => {
+                        // 含分式的和差先通分，分子的零点与分母的零点都是临界点
+                        let (numerator, denominator) = Self::to_fraction(expr);
+                        if !denominator.get_variables().iter().any(|v| v == var) {
+                            return Err(err);
+                        }
+                        let mut points = self.real_points(&numerator, var)?;
+                        points.extend(self.real_points(&denominator, var)?);
+                        return Ok(points);
+                    }
+                };
+                Ok(roots.into_iter().map(|(expr, value)| Endpoint { expr, value }).collect())
+            }
+        }
+    }
+
+    /// 把表达式通分为 (分子, 分母)，不改变表达式的值
+    pub(crate) fn to_fraction(expr: &Expression) -> (Expression, Expression) {
+        let one = || Expression::Number(Number::one());
+        match expr {
+            Expression::BinaryOp { op, left, right } => {
+                let (ln, ld) = Self::to_fraction(left);
+                match op {
+                    BinaryOperator::Add | BinaryOperator::Subtract => {
+                        let (rn, rd) = Self::to_fraction(right);
+                        let numerator = Expression::binary_op(
+                            op.clone(),
+                            Expression::multiply(ln, rd.clone()),
+                            Expression::multiply(rn, ld.clone()),
+                        );
+                        (numerator, Expression::multiply(ld, rd))
+                    }
+                    BinaryOperator::Multiply => {
+                        let (rn, rd) = Self::to_fraction(right);
+                        (Expression::multiply(ln, rn), Expression::multiply(ld, rd))
+                    }
+                    BinaryOperator::Divide => {
+                        let (rn, rd) = Self::to_fraction(right);
+                        (Expression::multiply(ln, rd), Expression::multiply(ld, rn))
+                    }
+                    BinaryOperator::Power => match right.as_ref() {
+                        Expression::Number(Number::Integer(k)) if k < &0.into() => {
+                            let k = Expression::Number(Number::Integer(-k));
+                            (Expression::power(ld, k.clone()), Expression::power(ln, k))
+                        }
+                        Expression::Number(Number::Integer(_)) => {
+                            (Expression::power(ln, right.as_ref().clone()), Expression::power(ld, right.as_ref().clone()))
+                        }
+                        _ => (expr.clone(), one()),
+                    },
+                    _ => (expr.clone(), one()),
+                }
+            }
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+                let (n, d) = Self::to_fraction(operand);
+                (Expression::negate(n), d)
+            }
+            _ => (expr.clone(), one()),
+        }
+    }
+
+    /// 按数值排序并去除重复的临界点
+    fn sort_points(mut points: Vec<Endpoint>) -> Vec<Endpoint> {
+        points.sort_by(|a, b| a.value.partial_cmp(&b.value).unwrap_or(std::cmp::Ordering::Equal));
+        points.dedup_by(|a, b| Self::same_value(a.value, b.value));
+        points
+    }
+
+    /// 用临界点切分实轴，逐段检验并合并成区间
+    fn partition(points: &[Endpoint], holds: impl Fn(f64, bool) -> bool) -> Vec<RealInterval> {
+        // 依次为：区间段 0、临界点 0、区间段 1、…、区间段 n
+        let sample = |i: usize| -> f64 {
+            match (i.checked_sub(1).map(|j| points[j].value), points.get(i).map(|p| p.value)) {
+                (None, None) => 0.0,
+                (None, Some(right)) => right - 1.0,
+                (Some(left), None) => left + 1.0,
+                (Some(left), Some(right)) => (left + right) / 2.0,
+            }
+        };
+        let segment_ok: Vec<bool> = (0..=points.len()).map(|i| holds(sample(i), false)).collect();
+        let point_ok: Vec<bool> = points.iter().map(|p| holds(p.value, true)).collect();
+
+        let mut intervals = Vec::new();
+        let mut current: Option<(Endpoint, bool)> = None;
+        for i in 0..=points.len() {
+            // 区间段 i 位于 points[i-1] 与 points[i] 之间
+            if segment_ok[i] {
+                if current.is_none() {
+                    current = Some(match i {
+                        0 => (Endpoint::negative_infinity(), false),
+                        _ => (points[i - 1].clone(), false),
+                    });
+                }
+            } else if let Some((start, start_inclusive)) = current.take() {
+                // 上一个被包含的元素是临界点 points[i-1]
+                let end = points[i - 1].clone();
+                intervals.push(RealInterval { start, end, start_inclusive, end_inclusive: true });
+            }
+
+            let Some(point) = points.get(i) else { break };
+            if point_ok[i] {
+                if current.is_none() {
+                    current = Some((point.clone(), true));
+                }
+            } else if let Some((start, start_inclusive)) = current.take() {
+                intervals.push(RealInterval { start, end: point.clone(), start_inclusive, end_inclusive: false });
+            }
+        }
+        if let Some((start, start_inclusive)) = current {
+            intervals.push(RealInterval { start, end: Endpoint::positive_infinity(), start_inclusive, end_inclusive: false });
+        }
+        intervals
+    }
+
+    /// 在给定点检验约束是否成立
+    fn check_constraint(&self, constraint: &Constraint, var: &str, x: f64, at_point: bool) -> bool {
+        let Some(value) = self.evaluate_at(&constraint.expr, var, x) else {
+            return false;
+        };
+        // 临界点处的数值存在舍入误差，按容差判断
+        let tolerance = if at_point { ZERO_TOLERANCE * (1.0 + x.abs()) } else { 0.0 };
+        match constraint.condition {
+            Condition::NonZero => value.abs() > tolerance,
+            Condition::NonNegative => value >= -tolerance,
+            Condition::Positive => value > tolerance,
+        }
+    }
+
+    /// 数值计算表达式在 var = x 处的值
+    pub(crate) fn evaluate_at(&self, expr: &Expression, var: &str, x: f64) -> Option<f64> {
+        let mut vars = HashMap::new();
+        vars.insert(var.to_string(), x);
+        self.calculus.numerical_evaluate(expr, &vars).ok().filter(|v| !v.is_nan())
+    }
+
+    /// 计算函数在给定点的精确值与数值
+    fn value_at(&self, simplifier: &mut Simplifier, expr: &Expression, var: &str, point: &Endpoint) -> Option<(f64, Expression)> {
+        let value = self.evaluate_at(expr, var, point.value)?;
+        let mut bindings = HashMap::new();
+        bindings.insert(var.to_string(), point.expr.clone());
+        let substituted = expr.substitute(&bindings);
+        let exact = simplifier.simplify(&substituted).unwrap_or(substituted);
+        Some((value, exact))
+    }
+
+    /// 计算端点处的函数值；开端点和无穷端点取极限（数值估计）
+    fn endpoint_value(&self, simplifier: &mut Simplifier, expr: &Expression, var: &str, endpoint: &Endpoint, inclusive: bool, towards: f64) -> Option<(f64, Expression, bool)> {
+        if inclusive && endpoint.value.is_finite() {
+            return self.value_at(simplifier, expr, var, endpoint).map(|(v, e)| (v, e, true));
+        }
+
+        let probes: Vec<f64> = if endpoint.value.is_finite() {
+            [1e-4, 1e-6, 1e-8].iter().map(|h| endpoint.value + towards * h).collect()
+        } else {
+            [1e4, 1e8, 1e12].iter().map(|m| -towards * m).collect()
+        };
+        let values: Vec<f64> = probes.iter().filter_map(|&x| self.evaluate_at(expr, var, x)).collect();
+        let &last = values.last()?;
+
+        let diverging = values.len() == probes.len()
+            && last.abs() > DIVERGENCE_THRESHOLD
+            && values.windows(2).all(|w| w[1].abs() >= w[0].abs());
+        if diverging {
+            let infinity = if last > 0.0 { Endpoint::positive_infinity() } else { Endpoint::negative_infinity() };
+            return Some((infinity.value, infinity.expr, false));
+        }
+
+        // 有限端点处函数连续时直接代入，否则使用数值极限
+        if endpoint.value.is_finite() {
+            if let Some((value, exact)) = self.value_at(simplifier, expr, var, endpoint) {
+                return Some((value, exact, false));
+            }
+        }
+        let limit = if last.abs() < ZERO_TOLERANCE { 0.0 } else { last };
+        let exact = if limit == 0.0 { Expression::Number(Number::zero()) } else { Expression::Number(Number::Float(limit)) };
+        Some((limit, exact, false))
+    }
+
+    /// 表达式是否包含取整等间断函数
+    fn contains_discontinuous_function(expr: &Expression) -> bool {
+        match expr {
+            Expression::Function { name, args } => {
+                matches!(name.as_str(), "floor" | "ceil" | "round" | "trunc")
+                    || args.iter().any(Self::contains_discontinuous_function)
+            }
+            Expression::BinaryOp { left, right, .. } => {
+                Self::contains_discontinuous_function(left) || Self::contains_discontinuous_function(right)
+            }
+            Expression::UnaryOp { operand, .. } => Self::contains_discontinuous_function(operand),
+            _ => false,
+        }
+    }
+
+    /// 在容差范围内比较两个数值，无穷只与自身相等
+    fn same_value(a: f64, b: f64) -> bool {
+        if !a.is_finite() || !b.is_finite() {
+            return a == b;
+        }
+        (a - b).abs() <= ZERO_TOLERANCE * (1.0 + a.abs().max(b.abs()))
+    }
+
+    /// 区间列表转换为表达式：空集、单个区间、孤立点或区间并集
+    fn intervals_to_expression(intervals: &[RealInterval]) -> Expression {
+        intervals.iter()
+            .map(|interval| {
+                if interval.start_inclusive && interval.end_inclusive && Self::same_value(interval.start.value, interval.end.value) {
+                    Expression::Set(vec![interval.start.expr.clone()])
+                } else {
+                    Expression::interval(
+                        interval.start.expr.clone(),
+                        interval.end.expr.clone(),
+                        interval.start_inclusive,
+                        interval.end_inclusive,
+                    )
+                }
+            })
+            .reduce(|acc, next| Expression::binary_op(BinaryOperator::Union, acc, next))
+            .unwrap_or_else(|| Expression::Set(Vec::new()))
+    }
+
+    /// 把区间并集表达式拆回区间列表
+    fn expression_to_intervals(expr: &Expression) -> Result<Vec<RealInterval>, ComputeError> {
+        let endpoint = |expr: &Expression| -> Result<Endpoint, ComputeError> {
+            let value = CalculusEngine::new().numerical_evaluate(expr, &HashMap::new())?;
+            Ok(Endpoint { expr: expr.clone(), value })
+        };
+
+        match expr {
+            Expression::Interval { start, end, start_inclusive, end_inclusive } => Ok(vec![RealInterval {
+                start: endpoint(start)?,
+                end: endpoint(end)?,
+                start_inclusive: *start_inclusive,
+                end_inclusive: *end_inclusive,
+            }]),
+            Expression::Set(points) => points.iter()
+                .map(|p| {
+                    let point = endpoint(p)?;
+                    Ok(RealInterval { start: point.clone(), end: point, start_inclusive: true, end_inclusive: true })
+                })
+                .collect(),
+            Expression::BinaryOp { op: BinaryOperator::Union, left, right } => {
+                let mut intervals = Self::expression_to_intervals(left)?;
+                intervals.extend(Self::expression_to_intervals(right)?);
+                Ok(intervals)
+            }
+            _ => Err(ComputeError::domain_error(tr!("domain.invalid_domain"))),
+        }
+    }
+}
+
+/// 检查数值指数是否为负
+fn exponent_is_negative(exponent: &Expression) -> bool {
+    matches!(exponent, Expression::Number(n) if n.is_negative())
+}
+
+impl Default for DomainAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[path = "domain_tests.rs"]
+mod domain_tests;
@@ -0,0 +1,108 @@
+//! # 定义域与值域分析测试
+//!
+//! 测试分母、根号、对数等约束的定义域分析，以及基于驻点的值域估计。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::domain::DomainAnalyzer;
+    use crate::core::{Expression, Number, MathConstant, BinaryOperator};
+    use crate::test_utils::parse;
+
+    fn int(value: i64) -> Expression {
+        Expression::Number(Number::integer(value))
+    }
+
+    fn pos_inf() -> Expression {
+        Expression::Constant(MathConstant::PositiveInfinity)
+    }
+
+    fn neg_inf() -> Expression {
+        Expression::Constant(MathConstant::NegativeInfinity)
+    }
+
+    fn union(left: Expression, right: Expression) -> Expression {
+        Expression::binary_op(BinaryOperator::Union, left, right)
+    }
+
+    #[test]
+    fn test_domain_denominator_and_sqrt() {
+        let analyzer = DomainAnalyzer::new();
+        let result = analyzer.domain(&parse("1/(x-2) + sqrt(x)"), "x").unwrap();
+
+        let expected = union(
+            Expression::interval(int(0), int(2), true, false),
+            Expression::interval(int(2), pos_inf(), false, false),
+        );
+        assert_eq!(result.set, expected);
+        assert!(!result.is_partial());
+    }
+
+    #[test]
+    fn test_domain_logarithm_and_even_root() {
+        let analyzer = DomainAnalyzer::new();
+
+        let result = analyzer.domain(&parse("ln(x)"), "x").unwrap();
+        assert_eq!(result.set, Expression::interval(int(0), pos_inf(), false, false));
+
+        let result = analyzer.domain(&parse("sqrt(1 - x^2)"), "x").unwrap();
+        assert_eq!(result.set, Expression::interval(int(-1), int(1), true, true));
+    }
+
+    #[test]
+    fn test_domain_multiple_poles() {
+        let analyzer = DomainAnalyzer::new();
+        let result = analyzer.domain(&parse("1/(x^2 - 1)"), "x").unwrap();
+
+        let expected = union(
+            union(
+                Expression::interval(neg_inf(), int(-1), false, false),
+                Expression::interval(int(-1), int(1), false, false),
+            ),
+            Expression::interval(int(1), pos_inf(), false, false),
+        );
+        assert_eq!(result.set, expected);
+    }
+
+    #[test]
+    fn test_domain_isolated_point_and_empty() {
+        let analyzer = DomainAnalyzer::new();
+
+        let result = analyzer.domain(&parse("sqrt(-(x^2))"), "x").unwrap();
+        assert_eq!(result.set, Expression::Set(vec![int(0)]));
+
+        let result = analyzer.domain(&parse("ln(-(x^2) - 1)"), "x").unwrap();
+        assert_eq!(result.set, Expression::Set(vec![]));
+    }
+
+    #[test]
+    fn test_domain_partial_analysis() {
+        let analyzer = DomainAnalyzer::new();
+        let result = analyzer.domain(&parse("tan(x)"), "x").unwrap();
+
+        assert!(result.is_partial());
+        assert!(!result.notes.is_empty());
+        assert_eq!(result.set, Expression::interval(neg_inf(), pos_inf(), false, false));
+    }
+
+    #[test]
+    fn test_range_estimate() {
+        let analyzer = DomainAnalyzer::new();
+        let real_line = Expression::interval(neg_inf(), pos_inf(), false, false);
+
+        let result = analyzer.range_estimate(&parse("x^2"), "x", &real_line).unwrap();
+        assert_eq!(result.set, Expression::interval(int(0), pos_inf(), true, false));
+
+        let domain = Expression::interval(int(-1), int(2), true, true);
+        let result = analyzer.range_estimate(&parse("x^2 - 2*x"), "x", &domain).unwrap();
+        assert_eq!(result.set, Expression::interval(int(-1), int(3), true, true));
+        assert!(!result.is_partial());
+    }
+
+    #[test]
+    fn test_range_estimate_open_endpoint() {
+        let analyzer = DomainAnalyzer::new();
+        let domain = Expression::interval(int(0), pos_inf(), false, false);
+        let result = analyzer.range_estimate(&parse("1/x"), "x", &domain).unwrap();
+        assert_eq!(result.set, Expression::interval(int(0), pos_inf(), false, false));
+    }
+}
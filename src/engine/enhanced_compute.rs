@@ -3,11 +3,17 @@
 //! 集成运行时化简增强功能的计算引擎
 
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, Mutex, MutexGuard};
 use crate::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator};
 use super::{ComputeEngine, ComputeError};
 use super::enhanced_simplify::EnhancedSimplifier;
+use super::simplify::SimplifyOptions;
+use super::assumptions::{Assumptions, Property};
+use super::complex;
 use super::compute::BasicComputeEngine;
+use super::cache::{CacheStats, CacheUsageInfo};
+use crate::api::CacheConfig;
+use crate::tr;
 
 /// 增强计算引擎，支持运行时自动化简
 pub struct EnhancedComputeEngine {
@@ -39,6 +45,79 @@ impl EnhancedComputeEngine {
         }
     }
     
+    /// 设置启用的化简规则集
+    pub fn set_simplify_options(&self, options: SimplifyOptions) {
+        if let Ok(mut simplifier) = self.enhanced_simplifier.lock() {
+            simplifier.set_simplify_options(options);
+        }
+        self.base_engine.set_simplify_options(options);
+    }
+    
+    /// 增加一条变量假设
+    pub fn assume(&self, var: &str, property: Property) -> Result<(), ComputeError> {
+        self.enhanced_simplifier.lock()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhanced_simplifier_lock")))?
+            .assume(var, property)
+    }
+    
+    /// 清空变量假设
+    pub fn clear_assumptions(&self) {
+        if let Ok(mut simplifier) = self.enhanced_simplifier.lock() {
+            simplifier.set_assumptions(Assumptions::new());
+        }
+    }
+    
+    /// 当前的变量假设
+    pub fn assumptions(&self) -> Assumptions {
+        self.enhanced_simplifier.lock().map(|simplifier| simplifier.assumptions().clone()).unwrap_or_default()
+    }
+    
+    /// 设置自动化简的最多迭代轮数
+    pub fn set_max_simplify_iterations(&self, max_iterations: usize) {
+        if let Ok(mut simplifier) = self.enhanced_simplifier.lock() {
+            simplifier.set_max_iterations(max_iterations);
+        }
+    }
+    
+    /// 取出化简达到迭代上限仍未收敛的次数并清零
+    pub fn take_non_converged_simplifications(&self) -> usize {
+        self.enhanced_simplifier.lock().map(|mut simplifier| simplifier.take_non_converged()).unwrap_or(0)
+    }
+    
+    /// 获取化简规则缓存的统计信息
+    pub fn get_cache_stats(&self) -> Result<CacheStats, ComputeError> {
+        Ok(self.lock_simplifier()?.cache().get_stats())
+    }
+    
+    /// 获取化简规则缓存的使用情况
+    pub fn get_cache_usage(&self) -> Result<CacheUsageInfo, ComputeError> {
+        Ok(self.lock_simplifier()?.cache().get_usage_info())
+    }
+    
+    /// 清理化简规则缓存中的过期条目
+    pub fn cleanup_cache(&self) -> Result<(), ComputeError> {
+        self.lock_simplifier()?.cache().cleanup_expired();
+        Ok(())
+    }
+    
+    /// 清空化简规则缓存
+    pub fn clear_cache(&self) -> Result<(), ComputeError> {
+        self.lock_simplifier()?.cache().clear_all();
+        Ok(())
+    }
+    
+    /// 更新化简规则缓存的配置
+    pub fn update_cache_config(&self, config: CacheConfig) -> Result<(), ComputeError> {
+        self.lock_simplifier()?.update_cache_config(config);
+        Ok(())
+    }
+    
+    /// 锁定增强化简器
+    fn lock_simplifier(&self) -> Result<MutexGuard<'_, EnhancedSimplifier>, ComputeError> {
+        self.enhanced_simplifier.lock()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhanced_simplifier_lock")))
+    }
+    
     /// 是否启用了自动化简
     pub fn is_auto_simplify_enabled(&self) -> bool {
         self.auto_simplify_enabled.lock().map(|enabled| *enabled).unwrap_or(false)
@@ -47,11 +126,11 @@ impl EnhancedComputeEngine {
     /// 执行运算后自动化简
     fn auto_simplify_if_enabled(&self, expr: &Expression) -> Result<Expression, ComputeError> {
         let enabled = self.auto_simplify_enabled.lock()
-            .map_err(|_| ComputeError::internal("无法获取自动化简开关锁"))?;
+            .map_err(|_| ComputeError::internal(tr!("engine.auto_simplify_lock")))?;
         
         if *enabled {
             self.enhanced_simplifier.lock()
-                .map_err(|_| ComputeError::internal("无法获取增强化简器锁"))?
+                .map_err(|_| ComputeError::internal(tr!("engine.enhanced_simplifier_lock")))?
                 .enhanced_simplify(expr)
         } else {
             Ok(expr.clone())
@@ -94,14 +173,20 @@ impl ComputeEngine for EnhancedComputeEngine {
     fn simplify(&self, expr: &Expression) -> Result<Expression, ComputeError> {
         // 使用增强化简器
         self.enhanced_simplifier.lock()
-            .map_err(|_| ComputeError::internal("无法获取增强化简器锁"))?
+            .map_err(|_| ComputeError::internal(tr!("engine.enhanced_simplifier_lock")))?
             .enhanced_simplify(expr)
     }
     
+    fn simplify_traced(&self, expr: &Expression) -> Result<(Expression, Vec<super::TraceEvent>), ComputeError> {
+        self.enhanced_simplifier.lock()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhanced_simplifier_lock")))?
+            .enhanced_simplify_traced(expr)
+    }
+    
     fn evaluate(&self, expr: &Expression, vars: &HashMap<String, Number>) -> Result<Number, ComputeError> {
         // 先化简表达式，再求值
         let enabled = self.auto_simplify_enabled.lock()
-            .map_err(|_| ComputeError::internal("无法获取自动化简开关锁"))?;
+            .map_err(|_| ComputeError::internal(tr!("engine.auto_simplify_lock")))?;
         
         let simplified = if *enabled {
             self.simplify(expr)?
@@ -136,10 +221,34 @@ impl ComputeEngine for EnhancedComputeEngine {
         self.auto_simplify_if_enabled(&series_result)
     }
     
+    fn sum(&self, expr: &Expression, var: &str, lower: &Expression, upper: &Expression) -> Result<super::SeriesSum, ComputeError> {
+        self.base_engine.sum(expr, var, lower, upper)
+    }
+    
+    fn laplace(&self, expr: &Expression, t: &str, s: &str) -> Result<super::LaplaceTransform, ComputeError> {
+        self.base_engine.laplace(expr, t, s)
+    }
+    
+    fn inverse_laplace(&self, expr: &Expression, s: &str, t: &str) -> Result<Expression, ComputeError> {
+        self.base_engine.inverse_laplace(expr, s, t)
+    }
+    
+    fn truth_table(&self, expr: &Expression, vars: &[String]) -> Result<super::TruthTable, ComputeError> {
+        self.base_engine.truth_table(expr, vars)
+    }
+    
+    fn to_dnf(&self, expr: &Expression) -> Result<Expression, ComputeError> {
+        self.base_engine.to_dnf(expr)
+    }
+    
+    fn to_cnf(&self, expr: &Expression) -> Result<Expression, ComputeError> {
+        self.base_engine.to_cnf(expr)
+    }
+    
     fn numerical_evaluate(&self, expr: &Expression, vars: &HashMap<String, f64>) -> Result<f64, ComputeError> {
         // 数值计算前先化简
         let enabled = self.auto_simplify_enabled.lock()
-            .map_err(|_| ComputeError::internal("无法获取自动化简开关锁"))?;
+            .map_err(|_| ComputeError::internal(tr!("engine.auto_simplify_lock")))?;
         
         let simplified = if *enabled {
             self.simplify(expr)?
@@ -202,6 +311,16 @@ impl ComputeEngine for EnhancedComputeEngine {
         self.auto_simplify_if_enabled(&lcm_result)
     }
     
+    fn gcd_list(&self, values: &[Expression]) -> Result<Expression, ComputeError> {
+        let gcd_result = self.base_engine.gcd_list(values)?;
+        self.auto_simplify_if_enabled(&gcd_result)
+    }
+    
+    fn lcm_list(&self, values: &[Expression]) -> Result<Expression, ComputeError> {
+        let lcm_result = self.base_engine.lcm_list(values)?;
+        self.auto_simplify_if_enabled(&lcm_result)
+    }
+    
     fn is_prime(&self, n: &Expression) -> Result<bool, ComputeError> {
         self.base_engine.is_prime(n)
     }
@@ -215,6 +334,10 @@ impl ComputeEngine for EnhancedComputeEngine {
         simplified_factors
     }
     
+    fn set_random_seed(&self, seed: u64) {
+        self.base_engine.set_random_seed(seed)
+    }
+    
     fn binomial(&self, n: &Expression, k: &Expression) -> Result<Expression, ComputeError> {
         let binomial_result = self.base_engine.binomial(n, k)?;
         self.auto_simplify_if_enabled(&binomial_result)
@@ -249,6 +372,41 @@ impl ComputeEngine for EnhancedComputeEngine {
         simplified_solutions
     }
     
+    fn solve_detailed(&self, equation: &Expression, var: &str) -> Result<Vec<super::Solution>, ComputeError> {
+        let solutions = self.base_engine.solve_detailed(equation, var)?;
+        // 对每个根进行化简，重数和验证信息保持不变
+        solutions.into_iter()
+            .map(|mut solution| {
+                solution.root = self.auto_simplify_if_enabled(&solution.root)?;
+                Ok(solution)
+            })
+            .collect()
+    }
+    
+    fn domain(&self, expr: &Expression, var: &str) -> Result<super::DomainAnalysis, ComputeError> {
+        self.base_engine.domain(expr, var)
+    }
+    
+    fn range_estimate(&self, expr: &Expression, var: &str, domain: &Expression) -> Result<super::DomainAnalysis, ComputeError> {
+        self.base_engine.range_estimate(expr, var, domain)
+    }
+    
+    fn critical_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError> {
+        self.base_engine.critical_points(expr, var)
+    }
+    
+    fn local_extrema(&self, expr: &Expression, var: &str) -> Result<Vec<super::Extremum>, ComputeError> {
+        self.base_engine.local_extrema(expr, var)
+    }
+    
+    fn inflection_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError> {
+        self.base_engine.inflection_points(expr, var)
+    }
+    
+    fn are_equivalent(&self, lhs: &Expression, rhs: &Expression) -> Result<super::Equivalence, ComputeError> {
+        self.base_engine.are_equivalent(lhs, rhs)
+    }
+    
     fn solve_system(&self, equations: &[Expression], vars: &[String]) -> Result<Vec<HashMap<String, Expression>>, ComputeError> {
         let solutions = self.base_engine.solve_system(equations, vars)?;
         // 对每个解的每个变量值进行化简
@@ -285,7 +443,9 @@ impl ComputeEngine for EnhancedComputeEngine {
     }
     
     fn complex_conjugate(&self, expr: &Expression) -> Result<Expression, ComputeError> {
-        let conjugate = self.base_engine.complex_conjugate(expr)?;
+        // 假设为实数的变量共轭不变
+        let assumptions = self.assumptions();
+        let conjugate = complex::conjugate(expr, &|var| assumptions.variable_type(var).is_some());
         self.auto_simplify_if_enabled(&conjugate)
     }
     
@@ -5,23 +5,51 @@
 //! - 根号表达式化简
 //! - 三角函数化简
 //! - 更多代数化简规则
+//! - 含虚数单位的表达式整理为 `a + b*i`
 
 use crate::core::{Expression, Number, BinaryOperator, UnaryOperator, MathConstant};
+use crate::core::memory;
 use crate::engine::error::ComputeError;
-use crate::engine::simplify::Simplifier;
+use crate::engine::simplify::{Simplifier, SimplifyOptions};
+use crate::engine::assumptions::{Assumptions, Property};
+use crate::engine::complex;
+use crate::engine::trace::TraceEvent;
+use crate::engine::cache::{ComputeCache, SymbolicCacheKey};
+use crate::api::CacheConfig;
 use std::collections::HashMap;
+use std::time::Instant;
 use num_bigint::BigInt;
 use num_rational::BigRational;
 use num_traits::{Zero, One, ToPrimitive};
 
+/// 自动化简默认的最多迭代轮数
+const DEFAULT_MAX_ITERATIONS: usize = 10;
+
+/// 一条作用于整个表达式的化简规则：规则名与改写函数
+type Pass = (&'static str, fn(&mut EnhancedSimplifier, &Expression) -> Result<Expression, ComputeError>);
+
+/// 自动化简每轮在基础化简之后依次应用的规则
+const AUTO_SIMPLIFY_PASSES: &[Pass] = &[
+    ("enhanced.like_terms", EnhancedSimplifier::combine_like_terms),
+    ("enhanced.constant_folding", EnhancedSimplifier::apply_constant_folding),
+    ("enhanced.radicals", EnhancedSimplifier::simplify_radicals),
+    ("enhanced.algebraic", EnhancedSimplifier::apply_advanced_algebraic_rules),
+];
+
 /// 增强化简器
 pub struct EnhancedSimplifier {
     /// 基础简化器
     base_simplifier: Simplifier,
     /// 是否启用自动化简
     auto_simplify: bool,
-    /// 化简规则缓存
-    rule_cache: HashMap<Expression, Expression>,
+    /// 化简规则缓存，容量与开关按 [`CacheConfig`] 的符号缓存设置
+    rule_cache: ComputeCache,
+    /// 自动化简的最多迭代轮数
+    max_iterations: usize,
+    /// 达到迭代上限仍未收敛的次数
+    non_converged: usize,
+    /// 最近一次检测到循环时参与改写的规则
+    last_cycle_rules: Vec<&'static str>,
 }
 
 impl EnhancedSimplifier {
@@ -30,93 +58,261 @@ impl EnhancedSimplifier {
         Self {
             base_simplifier: Simplifier::new(),
             auto_simplify: true,
-            rule_cache: HashMap::new(),
+            rule_cache: ComputeCache::new(CacheConfig::default()),
+            max_iterations: DEFAULT_MAX_ITERATIONS,
+            non_converged: 0,
+            last_cycle_rules: Vec::new(),
         }
     }
     
+    /// 化简规则缓存
+    pub fn cache(&self) -> &ComputeCache {
+        &self.rule_cache
+    }
+    
+    /// 更新化简规则缓存的配置，容量缩小时立即淘汰超出的条目
+    pub fn update_cache_config(&mut self, config: CacheConfig) {
+        self.rule_cache.update_config(config);
+    }
+    
     /// 设置是否启用自动化简
     pub fn set_auto_simplify(&mut self, enabled: bool) {
         self.auto_simplify = enabled;
     }
     
+    /// 设置启用的化简规则集，增强规则按所属的规则集一并开关
+    pub fn set_simplify_options(&mut self, options: SimplifyOptions) {
+        if self.base_simplifier.options() != options {
+            self.base_simplifier.set_options(options);
+            self.rule_cache.clear_all();
+        }
+    }
+    
+    /// 增加一条变量假设，与已有假设矛盾时报错
+    pub fn assume(&mut self, var: &str, property: Property) -> Result<(), ComputeError> {
+        let mut assumptions = self.base_simplifier.assumptions().clone();
+        assumptions.assume(var, property)?;
+        self.set_assumptions(assumptions);
+        Ok(())
+    }
+    
+    /// 替换全部变量假设
+    pub fn set_assumptions(&mut self, assumptions: Assumptions) {
+        if *self.base_simplifier.assumptions() != assumptions {
+            self.base_simplifier.set_assumptions(assumptions);
+            self.rule_cache.clear_all();
+        }
+    }
+    
+    /// 当前的变量假设
+    pub fn assumptions(&self) -> &Assumptions {
+        self.base_simplifier.assumptions()
+    }
+    
+    /// 设置自动化简的最多迭代轮数，至少为 1
+    pub fn set_max_iterations(&mut self, max_iterations: usize) {
+        self.max_iterations = max_iterations.max(1);
+    }
+    
+    /// 自动化简的最多迭代轮数
+    pub fn max_iterations(&self) -> usize {
+        self.max_iterations
+    }
+    
+    /// 取出达到迭代上限仍未收敛的次数并清零
+    pub fn take_non_converged(&mut self) -> usize {
+        std::mem::take(&mut self.non_converged)
+    }
+    
+    /// 最近一次检测到改写循环时参与的规则，按首次生效的顺序排列；只在 debug 构建中记录
+    pub fn last_cycle_rules(&self) -> &[&'static str] {
+        &self.last_cycle_rules
+    }
+    
+    /// 增强化简表达式并记录每条生效的规则
+    pub fn enhanced_simplify_traced(&mut self, expr: &Expression) -> Result<(Expression, Vec<TraceEvent>), ComputeError> {
+        self.base_simplifier.begin_trace();
+        let result = self.enhanced_simplify(expr);
+        let events = self.base_simplifier.end_trace();
+        Ok((result?, events))
+    }
+    
+    /// 增强规则所属的规则集是否启用
+    fn pass_enabled(&self, rule: &str) -> bool {
+        let options = self.base_simplifier.options();
+        match rule {
+            "enhanced.like_terms" => options.like_terms,
+            "enhanced.trigonometric" => options.trigonometric,
+            "enhanced.algebraic" => options.identities && options.powers,
+            _ => true,
+        }
+    }
+    
+    /// 对整个表达式应用一轮增强规则，追踪时记录为一条规则；输入用完后交还节点池
+    fn traced_pass(&mut self, rule: &str, expr: Expression, pass: fn(&mut Self, &Expression) -> Result<Expression, ComputeError>) -> Result<Expression, ComputeError> {
+        if !self.pass_enabled(rule) {
+            return Ok(expr);
+        }
+        let after = if self.base_simplifier.is_tracing() {
+            let start = Instant::now();
+            let after = pass(self, &expr)?;
+            self.base_simplifier.record_rule(rule, &expr, &after, start.elapsed());
+            after
+        } else {
+            pass(self, &expr)?
+        };
+        memory::recycle(expr);
+        Ok(after)
+    }
+    
     /// 增强化简表达式
     pub fn enhanced_simplify(&mut self, expr: &Expression) -> Result<Expression, ComputeError> {
         // 首先应用基础简化
         let mut simplified = self.base_simplifier.simplify(expr)?;
         
         // 应用增强化简规则
-        simplified = self.apply_enhanced_rules(&simplified)?;
+        let enhanced = self.apply_enhanced_rules(&simplified)?;
+        memory::recycle(std::mem::replace(&mut simplified, enhanced));
         
         // 如果启用自动化简，继续应用更多规则
         if self.auto_simplify {
-            simplified = self.apply_auto_simplify_rules(&simplified)?;
+            let automatic = self.apply_auto_simplify_rules(&simplified)?;
+            memory::recycle(std::mem::replace(&mut simplified, automatic));
+        }
+        
+        // 含虚数单位时整理为 a + b*i
+        if complex::contains_imaginary(&simplified) {
+            simplified = self.traced_pass("enhanced.complex_form", simplified, Self::complex_standard_form)?;
         }
         
         Ok(simplified)
     }
     
+    /// 把含 `i` 的表达式整理为 `a + b*i`，实部与虚部各自化简；整体无法拆开时分别整理各个子式
+    fn complex_standard_form(&mut self, expr: &Expression) -> Result<Expression, ComputeError> {
+        if !complex::contains_imaginary(expr) {
+            return Ok(expr.clone());
+        }
+        if let Some((re, im)) = complex::rectangular(expr) {
+            let re = self.enhanced_simplify(&re)?;
+            let im = self.enhanced_simplify(&im)?;
+            return Ok(complex::from_rectangular(re, im));
+        }
+        Ok(match expr {
+            Expression::BinaryOp { op, left, right } => {
+                Expression::binary_op(op.clone(), self.complex_standard_form(left)?, self.complex_standard_form(right)?)
+            }
+            Expression::UnaryOp { op, operand } => Expression::unary_op(op.clone(), self.complex_standard_form(operand)?),
+            Expression::Function { name, args } => Expression::function(name, args.iter()
+                .map(|arg| self.complex_standard_form(arg))
+                .collect::<Result<Vec<_>, _>>()?),
+            _ => expr.clone(),
+        })
+    }
+    
+    /// 把 `conj(...)` 对四则运算逐项分配并求出数与常量的共轭，假设为实数的变量共轭不变
+    fn expand_conjugates(&mut self, expr: &Expression) -> Result<Expression, ComputeError> {
+        let assumptions = self.base_simplifier.assumptions();
+        Ok(complex::expand_conjugates(expr, &|var| assumptions.variable_type(var).is_some()))
+    }
+    
     /// 应用增强化简规则
     fn apply_enhanced_rules(&mut self, expr: &Expression) -> Result<Expression, ComputeError> {
-        // 检查缓存
-        if let Some(cached) = self.rule_cache.get(expr) {
-            return Ok(cached.clone());
+        // 检查缓存（追踪时跳过）
+        let key = SymbolicCacheKey {
+            expression: expr.clone().into(),
+            operation: "enhanced_rules".to_string(),
+            variable: None,
+        };
+        if !self.base_simplifier.is_tracing() {
+            if let Some(cached) = self.rule_cache.get_symbolic(&key) {
+                return Ok(cached);
+            }
         }
         
         let mut result = expr.clone();
         
         // 首先应用常量合并规则
-        result = self.apply_constant_folding(&result)?;
+        result = self.traced_pass("enhanced.constant_folding", result, Self::apply_constant_folding)?;
+        
+        // 展开共轭
+        result = self.traced_pass("enhanced.conjugate", result, Self::expand_conjugates)?;
         
         // 应用根号化简规则
-        result = self.simplify_radicals(&result)?;
+        result = self.traced_pass("enhanced.radicals", result, Self::simplify_radicals)?;
         
         // 应用三角函数化简规则
-        result = self.simplify_trigonometric(&result)?;
+        result = self.traced_pass("enhanced.trigonometric", result, Self::simplify_trigonometric)?;
         
         // 应用更多代数化简规则
-        result = self.apply_advanced_algebraic_rules(&result)?;
+        result = self.traced_pass("enhanced.algebraic", result, Self::apply_advanced_algebraic_rules)?;
         
         // 缓存结果
-        self.rule_cache.insert(expr.clone(), result.clone());
+        let cost = expr.cost(&self.rule_cache.config().cost_model);
+        self.rule_cache.put_symbolic(key, result.clone(), u32::try_from(cost).unwrap_or(u32::MAX));
         
         Ok(result)
     }
     
     /// 应用自动化简规则（多次迭代直到无法进一步化简）
     fn apply_auto_simplify_rules(&mut self, expr: &Expression) -> Result<Expression, ComputeError> {
+        self.simplify_to_fixpoint(expr, AUTO_SIMPLIFY_PASSES)
+    }
+    
+    /// 每轮先做基础化简再依次应用 `passes`，直到不再变化
+    ///
+    /// 规则来回改写（A→B→A）时停在循环处，返回循环中复杂度最小的形态，复杂度相同取字符串较小者，
+    /// 使结果与从循环的哪一处进入无关；达到迭代上限时返回当前结果并计一次未收敛。
+    fn simplify_to_fixpoint(&mut self, expr: &Expression, passes: &[Pass]) -> Result<Expression, ComputeError> {
         let mut current = expr.clone();
-        let mut previous;
-        let max_iterations = 10; // 防止无限循环
-        let mut iteration = 0;
+        let mut history = vec![current.clone()];
+        let mut seen = HashMap::from([(current.clone(), 0)]);
+        // 每轮中改变了表达式的规则，只在 debug 构建中记录
+        let mut changed_rules: Vec<Vec<&'static str>> = Vec::new();
         
         loop {
-            previous = current.clone();
-            
-            // 应用基础简化
-            current = self.base_simplifier.simplify(&current)?;
-            
-            // 应用同类项合并
-            current = self.combine_like_terms(&current)?;
-            
-            // 应用常量折叠
-            current = self.apply_constant_folding(&current)?;
-            
-            // 应用根号化简
-            current = self.simplify_radicals(&current)?;
+            let mut changed = Vec::new();
+            let simplified = self.base_simplifier.simplify(&current)?;
+            if cfg!(debug_assertions) && simplified != current {
+                changed.push("simplify");
+            }
+            memory::recycle(std::mem::replace(&mut current, simplified));
             
-            // 应用代数化简
-            current = self.apply_advanced_algebraic_rules(&current)?;
+            for &(rule, pass) in passes {
+                let before = cfg!(debug_assertions).then(|| current.clone());
+                current = self.traced_pass(rule, current, pass)?;
+                if before.is_some_and(|before| before != current) {
+                    changed.push(rule);
+                }
+            }
+            changed_rules.push(changed);
             
-            iteration += 1;
+            if let Some(&start) = seen.get(&current) {
+                if start + 1 < history.len() {
+                    if cfg!(debug_assertions) {
+                        self.last_cycle_rules.clear();
+                        for rule in changed_rules[start..].iter().flatten() {
+                            if !self.last_cycle_rules.contains(rule) {
+                                self.last_cycle_rules.push(rule);
+                            }
+                        }
+                    }
+                    let cycle = history.split_off(start);
+                    return Ok(cycle.into_iter()
+                        .min_by_key(|form| (form.complexity(), form.to_string()))
+                        .unwrap_or(current));
+                }
+                return Ok(current);
+            }
             
-            // 如果没有变化或达到最大迭代次数，停止
-            if current == previous || iteration >= max_iterations {
-                break;
+            if history.len() >= self.max_iterations {
+                self.non_converged += 1;
+                return Ok(current);
             }
+            seen.insert(current.clone(), history.len());
+            history.push(current.clone());
         }
-        
-        Ok(current)
-    }    
+    }
 
     /// 合并同类项
     fn combine_like_terms(&mut self, expr: &Expression) -> Result<Expression, ComputeError> {
@@ -125,15 +321,18 @@ impl EnhancedSimplifier {
                 let left_simplified = self.combine_like_terms(left)?;
                 let right_simplified = self.combine_like_terms(right)?;
                 
-                match op {
+                let combined = match op {
                     BinaryOperator::Add => {
                         self.combine_addition_terms(&left_simplified, &right_simplified)
                     }
                     BinaryOperator::Subtract => {
                         self.combine_subtraction_terms(&left_simplified, &right_simplified)
                     }
-                    _ => Ok(Expression::binary_op(op.clone(), left_simplified, right_simplified))
-                }
+                    _ => return Ok(Expression::binary_op(op.clone(), left_simplified, right_simplified))
+                };
+                memory::recycle(left_simplified);
+                memory::recycle(right_simplified);
+                combined
             }
             
             Expression::UnaryOp { op, operand } => {
@@ -154,10 +353,10 @@ impl EnhancedSimplifier {
     
     /// 合并加法项
     fn combine_addition_terms(&mut self, left: &Expression, right: &Expression) -> Result<Expression, ComputeError> {
-        // 处理嵌套加法：(a + b) + c -> a + b + c
+        // 处理嵌套加减：(a - b) + c -> a + (-b) + c
         let mut terms = Vec::new();
-        self.collect_addition_terms(left, &mut terms);
-        self.collect_addition_terms(right, &mut terms);
+        self.collect_addition_terms(left, false, &mut terms);
+        self.collect_addition_terms(right, false, &mut terms);
         
         // 合并同类项
         let combined_terms = self.merge_like_terms(terms)?;
@@ -173,108 +372,96 @@ impl EnhancedSimplifier {
         self.combine_addition_terms(left, &negated_right)
     }
     
-    /// 收集加法项
-    fn collect_addition_terms(&self, expr: &Expression, terms: &mut Vec<Expression>) {
+    /// 收集加法项，统一写成加上（可能取负的）项：减法与取负的和式都展开，
+    /// 之后的合并只需处理加法与 `-u` 一种形式
+    fn collect_addition_terms(&self, expr: &Expression, negative: bool, terms: &mut Vec<Expression>) {
         match expr {
             Expression::BinaryOp { op: BinaryOperator::Add, left, right } => {
-                self.collect_addition_terms(left, terms);
-                self.collect_addition_terms(right, terms);
+                self.collect_addition_terms(left, negative, terms);
+                self.collect_addition_terms(right, negative, terms);
             }
-            _ => {
-                terms.push(expr.clone());
+            Expression::BinaryOp { op: BinaryOperator::Subtract, left, right } => {
+                self.collect_addition_terms(left, negative, terms);
+                self.collect_addition_terms(right, !negative, terms);
             }
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+                self.collect_addition_terms(operand, !negative, terms);
+            }
+            _ if negative => terms.push(Expression::negate(expr.clone())),
+            _ => terms.push(expr.clone()),
         }
     }
     
-    /// 合并同类项
+    /// 合并同类项：因子相同（不计顺序）的项把数值系数相加
     fn merge_like_terms(&mut self, terms: Vec<Expression>) -> Result<Vec<Expression>, ComputeError> {
+        let split: Vec<(Number, Vec<Expression>)> = terms.iter().map(|term| self.extract_coefficient_and_base(term)).collect();
         let mut merged = Vec::new();
-        let mut used = vec![false; terms.len()];
+        let mut used = vec![false; split.len()];
         
-        for i in 0..terms.len() {
+        for i in 0..split.len() {
             if used[i] {
                 continue;
             }
-            
-            let mut coefficient = Expression::Number(Number::one());
-            let mut base_term = None;
-            
-            // 提取第一个项的系数和基础项
-            if let Some((coeff, base)) = self.extract_coefficient_and_base(&terms[i]) {
-                coefficient = coeff;
-                base_term = Some(base);
-            } else {
-                // 如果无法提取，直接添加
-                merged.push(terms[i].clone());
-                used[i] = true;
-                continue;
-            }
-            
             used[i] = true;
+            let (mut coefficient, factors) = split[i].clone();
             
             // 查找其他同类项
-            for j in (i + 1)..terms.len() {
-                if used[j] {
-                    continue;
-                }
-                
-                if let Some((other_coeff, other_base)) = self.extract_coefficient_and_base(&terms[j]) {
-                    if let Some(ref base) = base_term {
-                        if base == &other_base {
-                            // 合并系数
-                            coefficient = Expression::add(coefficient, other_coeff);
-                            used[j] = true;
-                        }
-                    }
+            for j in (i + 1)..split.len() {
+                if !used[j] && same_factors(&factors, &split[j].1) {
+                    coefficient = coefficient + split[j].0.clone();
+                    used[j] = true;
                 }
             }
             
-            // 简化系数
-            coefficient = self.base_simplifier.simplify(&coefficient)?;
-            
-            // 构建合并后的项
-            if let Some(base) = base_term {
-                if self.is_zero(&coefficient) {
-                    // 系数为0，跳过这一项
-                    continue;
-                } else if self.is_one(&coefficient) {
-                    // 系数为1，只保留基础项
-                    merged.push(base);
-                } else {
-                    // 系数不为1，保留乘法形式
-                    merged.push(Expression::multiply(coefficient, base));
-                }
+            // 系数为0，跳过这一项
+            if !coefficient.is_zero() {
+                merged.push(self.build_term(coefficient, factors));
             }
         }
         
         Ok(merged)
     }
     
-    /// 提取表达式的系数和基础项
-    /// 例如：3*sqrt(2) -> (3, sqrt(2))，sqrt(2) -> (1, sqrt(2))，5 -> (5, 1)
-    fn extract_coefficient_and_base(&self, expr: &Expression) -> Option<(Expression, Expression)> {
-        match expr {
-            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
-                // 检查左边是否为数值系数
-                if matches!(left.as_ref(), Expression::Number(_)) {
-                    Some((left.as_ref().clone(), right.as_ref().clone()))
-                } else if matches!(right.as_ref(), Expression::Number(_)) {
-                    Some((right.as_ref().clone(), left.as_ref().clone()))
-                } else {
-                    // 都不是数值，将整个表达式作为基础项，系数为1
-                    Some((Expression::Number(Number::one()), expr.clone()))
+    /// 提取项的数值系数与其余因子：嵌套乘积逐层展开，数值因子与取负都并入系数
+    /// 例如：3*sqrt(2) -> (3, [sqrt(2)])，-2*x*y -> (-2, [x, y])，x*(-y) -> (-1, [x, y])，5 -> (5, [])
+    fn extract_coefficient_and_base(&self, expr: &Expression) -> (Number, Vec<Expression>) {
+        fn collect(expr: &Expression, coefficient: &mut Number, factors: &mut Vec<Expression>) {
+            match expr {
+                Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
+                    collect(left, coefficient, factors);
+                    collect(right, coefficient, factors);
                 }
+                Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+                    *coefficient = -coefficient.clone();
+                    collect(operand, coefficient, factors);
+                }
+                Expression::Number(n) => *coefficient = coefficient.clone() * n.clone(),
+                _ => factors.push(expr.clone()),
             }
-            
-            Expression::Number(_) => {
-                // 纯数值，基础项为1
-                Some((expr.clone(), Expression::Number(Number::one())))
-            }
-            
-            _ => {
-                // 其他情况，系数为1，整个表达式为基础项
-                Some((Expression::Number(Number::one()), expr.clone()))
-            }
+        }
+        let mut coefficient = Number::one();
+        let mut factors = Vec::new();
+        collect(expr, &mut coefficient, &mut factors);
+        (coefficient, factors)
+    }
+    
+    /// 由系数与因子重建一项：系数为 ±1 时省去，否则乘在第一个因子前
+    fn build_term(&self, coefficient: Number, factors: Vec<Expression>) -> Expression {
+        let mut factors = factors.into_iter();
+        let Some(first) = factors.next() else {
+            return Expression::Number(coefficient);
+        };
+        let negative_one = Number::integer(-1);
+        let first = if coefficient.is_one() || coefficient == negative_one {
+            first
+        } else {
+            Expression::multiply(Expression::Number(coefficient.clone()), first)
+        };
+        let product = factors.fold(first, Expression::multiply);
+        if coefficient == negative_one {
+            Expression::negate(product)
+        } else {
+            product
         }
     }
     
@@ -1017,6 +1204,21 @@ impl Default for EnhancedSimplifier {
     }
 }
 
+/// 两组因子是否相同，不计顺序
+fn same_factors(a: &[Expression], b: &[Expression]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut used = vec![false; b.len()];
+    a.iter().all(|factor| match (0..b.len()).find(|&j| !used[j] && b[j] == *factor) {
+        Some(j) => {
+            used[j] = true;
+            true
+        }
+        None => false,
+    })
+}
+
 // 包含测试模块
 #[cfg(test)]
 #[path = "enhanced_simplify_tests.rs"]
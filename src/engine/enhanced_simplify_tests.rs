@@ -2,8 +2,9 @@
 //!
 //! 测试运行时化简增强功能，包括根号化简、三角函数化简等。
 
-use super::EnhancedSimplifier;
+use super::{EnhancedSimplifier, Pass};
 use crate::core::{Expression, Number, MathConstant};
+use crate::engine::ComputeError;
 
 #[cfg(test)]
 mod tests {
@@ -437,6 +438,46 @@ mod tests {
         println!("✅ 负数格式化修复验证通过");
     }
     
+    #[test]
+    fn test_sign_normal_form() {
+        use crate::formatter::{Formatter, LaTeXFormatter, StandardFormatter, TerminalFormatter};
+        use crate::parser::{Parser, syntax::ExpressionParser};
+        let parse = |input: &str| ExpressionParser::new().parse(input).unwrap();
+        let mut simplifier = EnhancedSimplifier::new();
+        let mut simplify = |input: &str| simplifier.enhanced_simplify(&parse(input)).unwrap();
+        
+        assert_eq!(simplify("x - x"), Expression::Number(Number::zero()));
+        assert_eq!(simplify("-(-x)"), Expression::variable("x"));
+        assert_eq!(simplify("0 - x + x"), Expression::Number(Number::zero()));
+        // 减法与取负统一成加负项后，跨越加减链的同类项也能合并
+        assert_eq!(simplify("y - x + x"), Expression::variable("y"));
+        assert_eq!(simplify("x + y - x"), Expression::variable("y"));
+        assert_eq!(simplify("-(x - y) + x"), Expression::variable("y"));
+        // 嵌套乘积与取负的因子同样并入系数
+        assert_eq!(simplify("-2*x*y + 2*x*y"), Expression::Number(Number::zero()));
+        assert_eq!(simplify("2*x*y - 2*x*y"), Expression::Number(Number::zero()));
+        assert_eq!(simplify("x^2 - 2*x*y + y^2 - (x-y)^2"), Expression::Number(Number::zero()));
+        assert_eq!(simplify("3*a*b + 4*a*b"), simplify("7*a*b"));
+        assert_eq!(simplify("2 - x*(-y)"), simplify("2 + x*y"));
+        
+        let mut terminal = TerminalFormatter::new();
+        terminal.set_colors_enabled(false);
+        let formatters: [Box<dyn Formatter>; 3] = [Box::new(StandardFormatter::new()), Box::new(terminal), Box::new(LaTeXFormatter::new())];
+        for input in ["x + -3", "x + -2*y", "x - 2*y + y", "x - (-y)", "x + (-1)*y", "a - (b - c)"] {
+            let result = simplify(input);
+            for formatter in &formatters {
+                let output = formatter.format(&result);
+                assert!(!output.contains("+ -"), "{} => {}", input, output);
+            }
+        }
+        let standard = StandardFormatter::new();
+        assert_eq!(standard.format(&simplify("x + -2*y")), "x - 2 * y");
+        assert_eq!(standard.format(&simplify("x - 2*y + y")), "x - y");
+        assert_eq!(standard.format(&parse("x + (-3)")), "x - 3");
+        assert_eq!(standard.format(&parse("x + (-2)*y")), "x - 2 * y");
+        assert_eq!(standard.format(&simplify("3*a*b + 4*a*b")), "7 * a * b");
+    }
+    
     #[test]
     fn test_more_nested_radicals() {
         let mut simplifier = EnhancedSimplifier::new();
@@ -481,4 +522,61 @@ mod tests {
         println!("成功简化的嵌套根式数量: {}/3", simplified_count);
         assert!(simplified_count > 0, "至少应该有一个嵌套根式被简化");
     }
+    /// 测试规则：f(a, b) 改写为 g(b, a)
+    fn f_to_g(_: &mut EnhancedSimplifier, expr: &Expression) -> Result<Expression, ComputeError> {
+        Ok(match expr {
+            Expression::Function { name, args } if name == "f" && args.len() == 2 => {
+                Expression::function("g", vec![args[1].clone(), args[0].clone()])
+            }
+            _ => expr.clone(),
+        })
+    }
+    
+    /// 测试规则：g(a, b) 改写回 f(a, b)
+    fn g_to_f(_: &mut EnhancedSimplifier, expr: &Expression) -> Result<Expression, ComputeError> {
+        Ok(match expr {
+            Expression::Function { name, args } if name == "g" => Expression::function("f", args.clone()),
+            _ => expr.clone(),
+        })
+    }
+    
+    /// 测试规则：每次都套一层 h，永不收敛
+    fn wrap(_: &mut EnhancedSimplifier, expr: &Expression) -> Result<Expression, ComputeError> {
+        Ok(Expression::function("h", vec![expr.clone()]))
+    }
+    
+    #[test]
+    fn test_fixpoint_stops_on_cycle() {
+        // 两条规则每轮把 f(x, y) 变成 f(y, x)，形成周期为 2 的循环
+        let passes: &[Pass] = &[("test.f_to_g", f_to_g), ("test.g_to_f", g_to_f)];
+        let (x, y) = (Expression::variable("x"), Expression::variable("y"));
+        let forward = Expression::function("f", vec![x.clone(), y.clone()]);
+        let backward = Expression::function("f", vec![y, x]);
+        
+        let mut simplifier = EnhancedSimplifier::new();
+        simplifier.set_max_iterations(1000);
+        // 两个形态复杂度相同，无论从哪一处进入循环都取字符串较小的 f(x, y)
+        assert_eq!(simplifier.simplify_to_fixpoint(&forward, passes).unwrap(), forward);
+        assert_eq!(simplifier.simplify_to_fixpoint(&backward, passes).unwrap(), forward);
+        assert_eq!(simplifier.take_non_converged(), 0);
+        if cfg!(debug_assertions) {
+            assert_eq!(simplifier.last_cycle_rules(), ["test.f_to_g", "test.g_to_f"]);
+        }
+    }
+    
+    #[test]
+    fn test_fixpoint_iteration_limit() {
+        let passes: &[Pass] = &[("test.wrap", wrap)];
+        let mut simplifier = EnhancedSimplifier::new();
+        simplifier.set_max_iterations(3);
+        let result = simplifier.simplify_to_fixpoint(&Expression::variable("x"), passes).unwrap();
+        assert_eq!(result.to_string(), "h(h(h(x)))");
+        assert_eq!(simplifier.take_non_converged(), 1);
+        assert_eq!(simplifier.take_non_converged(), 0);
+        
+        // 正常的化简在上限内收敛，不计入
+        simplifier.enhanced_simplify(&Expression::add(Expression::variable("x"), Expression::variable("x"))).unwrap();
+        assert_eq!(simplifier.take_non_converged(), 0);
+        assert!(simplifier.last_cycle_rules().is_empty());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,340 @@
+//! # 方程两边的操作
+//!
+//! 把 `lhs = rhs`（以及 `<`、`<=`、`>`、`>=`、`!=`）当作整体，对两边同时做加减乘除与代换，
+//! 每一步都返回新的关系式、文字说明和需要注意的警告：
+//! - 乘除的因子可能为 0 时提示可能引入增根或丢失解
+//! - 不等式乘除负数时不等号反向，因子符号未知时给出警告
+//!
+//! [`isolate`] 在变量只出现一次时由外向内逐层做逆运算，把变量解到左边；
+//! 变量出现多次时转交 [`SolverEngine::solve`]。
+
+use std::collections::HashMap;
+use num_integer::Integer;
+use crate::core::{BinaryOperator, Expression, MathConstant, Number, UnaryOperator};
+use super::ComputeError;
+use super::assumptions::Assumptions;
+use super::enhanced_simplify::EnhancedSimplifier;
+use super::solver::SolverEngine;
+use crate::tr;
+
+/// 对方程的一步操作
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquationStep {
+    /// 操作后的方程（或不等式）
+    pub equation: Expression,
+    /// 这一步做了什么
+    pub description: String,
+    /// 这一步可能引入增根、丢失解或改变不等号方向的提示
+    pub warnings: Vec<String>,
+}
+
+/// 两边加上 `term`
+pub fn add(equation: &Expression, term: &Expression, assumptions: &Assumptions) -> Result<EquationStep, ComputeError> {
+    let (op, lhs, rhs) = relation(equation)?;
+    step(op, Expression::add(lhs, term.clone()), Expression::add(rhs, term.clone()), tr!("equation.add", term = term), Vec::new(), assumptions)
+}
+
+/// 两边减去 `term`
+pub fn subtract(equation: &Expression, term: &Expression, assumptions: &Assumptions) -> Result<EquationStep, ComputeError> {
+    let (op, lhs, rhs) = relation(equation)?;
+    step(op, Expression::subtract(lhs, term.clone()), Expression::subtract(rhs, term.clone()), tr!("equation.subtract", term = term), Vec::new(), assumptions)
+}
+
+/// 两边乘以 `factor`；乘 0 会丢掉方程的全部信息，直接报错
+pub fn multiply(equation: &Expression, factor: &Expression, assumptions: &Assumptions) -> Result<EquationStep, ComputeError> {
+    times(equation, factor, assumptions, true)
+}
+
+/// 两边乘以 `factor`；`check_zero` 为假时不再提示因子可能为 0，用于乘回原本就不为 0 的除数
+fn times(equation: &Expression, factor: &Expression, assumptions: &Assumptions, check_zero: bool) -> Result<EquationStep, ComputeError> {
+    let (op, lhs, rhs) = relation(equation)?;
+    let factor = simplify(factor)?;
+    if is_zero(&factor) {
+        return Err(ComputeError::domain_error(tr!("equation.multiply_by_zero")));
+    }
+    let mut warnings = Vec::new();
+    if check_zero && !is_nonzero(&factor, assumptions) {
+        warnings.push(tr!("equation.multiply_may_be_zero", factor = factor));
+    }
+    let (op, description) = scale(op, &factor, tr!("equation.multiply", factor = factor), assumptions, &mut warnings);
+    step(op, product(lhs, &factor), product(rhs, &factor), description, warnings, assumptions)
+}
+
+/// 两边除以 `divisor`；除数恒为 0 时报错
+pub fn divide(equation: &Expression, divisor: &Expression, assumptions: &Assumptions) -> Result<EquationStep, ComputeError> {
+    let (op, lhs, rhs) = relation(equation)?;
+    let divisor = simplify(divisor)?;
+    if is_zero(&divisor) {
+        return Err(ComputeError::domain_error(tr!("equation.divide_by_zero")));
+    }
+    let mut warnings = Vec::new();
+    if !is_nonzero(&divisor, assumptions) {
+        warnings.push(tr!("equation.divide_may_be_zero", divisor = divisor));
+    }
+    let (op, description) = scale(op, &divisor, tr!("equation.divide", divisor = divisor), assumptions, &mut warnings);
+    step(op, Expression::divide(lhs, divisor.clone()), Expression::divide(rhs, divisor), description, warnings, assumptions)
+}
+
+/// 把两边的 `var` 代换为 `value`
+pub fn substitute(equation: &Expression, var: &str, value: &Expression, assumptions: &Assumptions) -> Result<EquationStep, ComputeError> {
+    let (op, lhs, rhs) = relation(equation)?;
+    let mut bindings = HashMap::new();
+    bindings.insert(var.to_string(), value.clone());
+    step(op, lhs.substitute(&bindings), rhs.substitute(&bindings), tr!("equation.substitute", variable = var, value = value), Vec::new(), assumptions)
+}
+
+/// 把 `var` 解到左边，返回逐步的逆运算；最后一步形如 `var = ...`
+pub fn isolate(equation: &Expression, var: &str, assumptions: &Assumptions) -> Result<Vec<EquationStep>, ComputeError> {
+    let (mut op, mut lhs, mut rhs) = relation(equation)?;
+    let (left, right) = (occurrences(&lhs, var), occurrences(&rhs, var));
+    if left + right == 0 {
+        return Err(ComputeError::domain_error(tr!("equation.missing_variable", variable = var)));
+    }
+    if left + right > 1 {
+        return by_solver(op, &lhs, &rhs, var, left + right);
+    }
+
+    let mut steps = Vec::new();
+    if left == 0 {
+        op = flip(op);
+        std::mem::swap(&mut lhs, &mut rhs);
+        steps.push(EquationStep { equation: Expression::binary_op(op.clone(), lhs.clone(), rhs.clone()), description: tr!("equation.swap").to_string(), warnings: Vec::new() });
+    }
+    while lhs != Expression::variable(var) {
+        let Some(next) = peel(op.clone(), &lhs, &rhs, var, assumptions)? else {
+            return Err(ComputeError::unsupported_operation(tr!("equation.cannot_invert", expression = lhs, variable = var)));
+        };
+        let Expression::BinaryOp { op: next_op, left, right } = &next.equation else {
+            unreachable!("step 总是返回关系式");
+        };
+        (op, lhs, rhs) = (next_op.clone(), left.as_ref().clone(), right.as_ref().clone());
+        steps.push(next);
+    }
+    Ok(steps)
+}
+
+/// 剥掉左边最外层的一次运算；无法求逆时返回 `None`
+fn peel(op: BinaryOperator, lhs: &Expression, rhs: &Expression, var: &str, assumptions: &Assumptions) -> Result<Option<EquationStep>, ComputeError> {
+    let equation = Expression::binary_op(op.clone(), lhs.clone(), rhs.clone());
+    let contains = |e: &Expression| occurrences(e, var) > 0;
+    let is_equal = op == BinaryOperator::Equal;
+    let result = match lhs {
+        Expression::BinaryOp { op: BinaryOperator::Add, left, right } => {
+            let other = if contains(left) { right } else { left };
+            let next = subtract(&equation, other, assumptions)?;
+            // 化简后 `a + x - a` 总能还原为 x，这里直接取含变量的一侧，避免化简改写结构
+            let kept = if contains(left) { left } else { right };
+            with_lhs(next, kept)
+        }
+        Expression::BinaryOp { op: BinaryOperator::Subtract, left, right } if contains(left) => {
+            with_lhs(add(&equation, right, assumptions)?, left)
+        }
+        Expression::BinaryOp { op: BinaryOperator::Subtract, left, right } => {
+            let next = subtract(&equation, left, assumptions)?;
+            with_lhs(next, &Expression::negate(right.as_ref().clone()))
+        }
+        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
+            let (kept, other) = if contains(left) { (left, right) } else { (right, left) };
+            with_lhs(divide(&equation, other, assumptions)?, kept)
+        }
+        Expression::BinaryOp { op: BinaryOperator::Divide, left, right } if contains(left) => {
+            // 除数本来就不为 0，乘回去只需关心不等号方向
+            with_lhs(times(&equation, right, assumptions, false)?, left)
+        }
+        Expression::BinaryOp { op: BinaryOperator::Divide, left, right } if is_equal => {
+            // a / f = r  ⇒  f = a / r
+            let mut warnings = Vec::new();
+            if !is_nonzero(&simplify(rhs)?, assumptions) {
+                warnings.push(tr!("equation.no_solution_when_zero", value = rhs));
+            }
+            Some(step(op, right.as_ref().clone(), Expression::divide(left.as_ref().clone(), rhs.clone()), tr!("equation.reciprocal", factor = left), warnings, assumptions)?)
+        }
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+            let (op, description) = scale(op, &Expression::number(Number::integer(-1)), tr!("equation.negate").to_string(), assumptions, &mut Vec::new());
+            Some(step(op, operand.as_ref().clone(), Expression::negate(rhs.clone()), description, Vec::new(), assumptions)?)
+        }
+        Expression::BinaryOp { op: BinaryOperator::Power, left, right } if is_equal && contains(left) && !contains(right) => {
+            let exponent = simplify(right)?;
+            let (root, description) = match &exponent {
+                Expression::Number(n) if *n == Number::integer(2) => (Expression::function("sqrt", vec![rhs.clone()]), tr!("equation.square_root").to_string()),
+                _ => (Expression::power(rhs.clone(), Expression::divide(Expression::number(Number::one()), exponent.clone())), tr!("equation.root", exponent = exponent)),
+            };
+            let root = simplify(&root)?;
+            let mut warnings = Vec::new();
+            if is_even(&exponent) {
+                warnings.push(tr!("equation.even_root", solution = simplify(&Expression::negate(root.clone()))?));
+            }
+            Some(step(op, left.as_ref().clone(), root, description, warnings, assumptions)?)
+        }
+        Expression::BinaryOp { op: BinaryOperator::Power, left, right } if is_equal && !contains(left) => {
+            let logarithm = match left.as_ref() {
+                Expression::Constant(MathConstant::E) => Expression::ln(rhs.clone()),
+                base => Expression::divide(Expression::ln(rhs.clone()), Expression::ln(base.clone())),
+            };
+            let warnings = vec![tr!("equation.log_requires_positive", value = rhs)];
+            Some(step(op, right.as_ref().clone(), logarithm, tr!("equation.logarithm").to_string(), warnings, assumptions)?)
+        }
+        _ => match function_call(lhs) {
+            Some((name, argument)) if is_equal => inverse(name, argument, rhs)
+                .map(|(inverse, description, warnings)| step(op, argument.clone(), inverse, description, warnings, assumptions))
+                .transpose()?,
+            _ => None,
+        },
+    };
+    Ok(result)
+}
+
+/// 初等函数的逆运算：(新的右边, 说明, 警告)
+fn inverse(name: &str, argument: &Expression, rhs: &Expression) -> Option<(Expression, String, Vec<String>)> {
+    let principal = |inverse: &str| (
+        Expression::function(inverse, vec![rhs.clone()]),
+        tr!("equation.apply_inverse", function = inverse),
+        vec![tr!("equation.principal_value", function = inverse)],
+    );
+    Some(match name {
+        "sqrt" => (Expression::power(rhs.clone(), Expression::number(Number::integer(2))), tr!("equation.square").to_string(), vec![tr!("equation.square_extraneous", value = rhs)]),
+        "exp" => (Expression::ln(rhs.clone()), tr!("equation.natural_log").to_string(), vec![tr!("equation.log_requires_positive", value = rhs)]),
+        "ln" => (Expression::exp(rhs.clone()), tr!("equation.exponentiate").to_string(), vec![tr!("equation.also_requires_positive", value = argument)]),
+        "sin" => principal("asin"),
+        "cos" => principal("acos"),
+        "tan" => principal("atan"),
+        _ => return None,
+    })
+}
+
+/// 单参数的函数调用，`sqrt` 等也可能以一元运算出现
+fn function_call(expr: &Expression) -> Option<(&str, &Expression)> {
+    match expr {
+        Expression::Function { name, args } if args.len() == 1 => Some((name.as_str(), &args[0])),
+        Expression::UnaryOp { op, operand } => {
+            let name = match op {
+                UnaryOperator::Sqrt => "sqrt",
+                UnaryOperator::Sin => "sin",
+                UnaryOperator::Cos => "cos",
+                UnaryOperator::Tan => "tan",
+                _ => return None,
+            };
+            Some((name, operand))
+        }
+        _ => None,
+    }
+}
+
+/// 变量出现多次时交给 solve：唯一解直接给出，否则把解列在提示里
+fn by_solver(op: BinaryOperator, lhs: &Expression, rhs: &Expression, var: &str, count: usize) -> Result<Vec<EquationStep>, ComputeError> {
+    let hint = tr!("equation.repeated_variable", variable = var, count = count);
+    if op != BinaryOperator::Equal {
+        return Err(ComputeError::unsupported_operation(hint));
+    }
+    let equation = Expression::binary_op(op, lhs.clone(), rhs.clone());
+    let solutions = SolverEngine::new().solve(&equation, var)
+        .map_err(|e| ComputeError::unsupported_operation(tr!("equation.solver_failed", hint = hint, error = e)))?;
+    match solutions.as_slice() {
+        [solution] => Ok(vec![EquationStep {
+            equation: Expression::binary_op(BinaryOperator::Equal, Expression::variable(var), solution.clone()),
+            description: tr!("equation.solved_by_solver", hint = hint),
+            warnings: Vec::new(),
+        }]),
+        [] => Err(ComputeError::domain_error(tr!("equation.solver_no_solution", hint = hint))),
+        _ => {
+            let listed: Vec<String> = solutions.iter().map(|s| s.to_string()).collect();
+            Err(ComputeError::domain_error(tr!("equation.solver_many_solutions", hint = hint, solutions = listed.join(", "))))
+        }
+    }
+}
+
+/// 乘除 `factor` 后的关系符与说明；不等式遇到负数反向，符号未知时记下警告
+fn scale(op: BinaryOperator, factor: &Expression, description: String, assumptions: &Assumptions, warnings: &mut Vec<String>) -> (BinaryOperator, String) {
+    if matches!(op, BinaryOperator::Equal | BinaryOperator::NotEqual) || assumptions.is_positive(factor) {
+        (op, description)
+    } else if assumptions.is_negative(factor) {
+        (flip(op), tr!("equation.flip_inequality", description = description))
+    } else {
+        warnings.push(tr!("equation.unknown_sign", factor = factor));
+        (op, description)
+    }
+}
+
+/// 交换两边后的关系符
+fn flip(op: BinaryOperator) -> BinaryOperator {
+    match op {
+        BinaryOperator::Less => BinaryOperator::Greater,
+        BinaryOperator::LessEqual => BinaryOperator::GreaterEqual,
+        BinaryOperator::Greater => BinaryOperator::Less,
+        BinaryOperator::GreaterEqual => BinaryOperator::LessEqual,
+        op => op,
+    }
+}
+
+/// 两边分别化简后组成一步
+fn step(op: BinaryOperator, lhs: Expression, rhs: Expression, description: String, warnings: Vec<String>, assumptions: &Assumptions) -> Result<EquationStep, ComputeError> {
+    let mut simplifier = EnhancedSimplifier::new();
+    simplifier.set_assumptions(assumptions.clone());
+    Ok(EquationStep {
+        equation: Expression::binary_op(op, simplifier.enhanced_simplify(&lhs)?, simplifier.enhanced_simplify(&rhs)?),
+        description,
+        warnings,
+    })
+}
+
+/// `side * factor`；`side` 是以 `factor` 为分母的商时直接约去，化简器不会主动约掉 `x/a*a`
+fn product(side: Expression, factor: &Expression) -> Expression {
+    match side {
+        Expression::BinaryOp { op: BinaryOperator::Divide, left, right } if right.as_ref() == factor => *left,
+        side => Expression::multiply(side, factor.clone()),
+    }
+}
+
+/// 把这一步的左边换成剥掉一层后的表达式
+fn with_lhs(step: EquationStep, lhs: &Expression) -> Option<EquationStep> {
+    let Expression::BinaryOp { op, right, .. } = step.equation else {
+        unreachable!("step 总是返回关系式");
+    };
+    Some(EquationStep { equation: Expression::BinaryOp { op, left: Box::new(lhs.clone()), right }, ..step })
+}
+
+/// 拆出关系式的两边
+fn relation(equation: &Expression) -> Result<(BinaryOperator, Expression, Expression), ComputeError> {
+    match equation {
+        Expression::BinaryOp { op, left, right } if matches!(op,
+            BinaryOperator::Equal | BinaryOperator::NotEqual
+            | BinaryOperator::Less | BinaryOperator::LessEqual
+            | BinaryOperator::Greater | BinaryOperator::GreaterEqual) => {
+            Ok((op.clone(), left.as_ref().clone(), right.as_ref().clone()))
+        }
+        _ => Err(ComputeError::domain_error(tr!("equation.not_a_relation", expression = equation))),
+    }
+}
+
+/// `var` 在表达式中出现的次数
+fn occurrences(expr: &Expression, var: &str) -> usize {
+    match expr {
+        Expression::Variable(name) => usize::from(name == var),
+        Expression::Number(_) | Expression::Constant(_) => 0,
+        Expression::BinaryOp { left, right, .. } => occurrences(left, var) + occurrences(right, var),
+        Expression::UnaryOp { operand, .. } => occurrences(operand, var),
+        Expression::Function { args, .. } | Expression::Vector(args) | Expression::Set(args) => args.iter().map(|a| occurrences(a, var)).sum(),
+        Expression::Matrix(rows) => rows.iter().flatten().map(|a| occurrences(a, var)).sum(),
+        Expression::Interval { start, end, .. } => occurrences(start, var) + occurrences(end, var),
+    }
+}
+
+fn simplify(expr: &Expression) -> Result<Expression, ComputeError> {
+    EnhancedSimplifier::new().enhanced_simplify(expr)
+}
+
+fn is_zero(expr: &Expression) -> bool {
+    matches!(expr, Expression::Number(n) if n.is_zero())
+}
+
+fn is_nonzero(expr: &Expression, assumptions: &Assumptions) -> bool {
+    assumptions.is_positive(expr) || assumptions.is_negative(expr)
+}
+
+fn is_even(exponent: &Expression) -> bool {
+    matches!(exponent, Expression::Number(Number::Integer(n)) if n.is_even())
+}
+
+#[cfg(test)]
+#[path = "equation_tests.rs"]
+mod equation_tests;
@@ -0,0 +1,105 @@
+//! # 方程两边操作测试
+//!
+//! 测试两边加减乘除与代换、不等式乘负数时反向、因子可能为 0 时的警告，以及逐步隔离变量。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::equation::{add, divide, isolate, multiply, substitute};
+    use crate::engine::assumptions::{Assumptions, Property};
+    use crate::engine::enhanced_simplify::EnhancedSimplifier;
+    use crate::core::{BinaryOperator, Expression};
+    use crate::test_utils::parse;
+
+    fn simplified(input: &str) -> Expression {
+        EnhancedSimplifier::new().enhanced_simplify(&parse(input)).unwrap()
+    }
+
+    fn relation(op: BinaryOperator, lhs: &str, rhs: &str) -> Expression {
+        Expression::binary_op(op, simplified(lhs), simplified(rhs))
+    }
+
+    fn equation(lhs: &str, rhs: &str) -> Expression {
+        Expression::binary_op(BinaryOperator::Equal, parse(lhs), parse(rhs))
+    }
+
+    fn isolated(lhs: &str, rhs: &str, var: &str) -> Expression {
+        isolate(&equation(lhs, rhs), var, &Assumptions::new()).unwrap().pop().unwrap().equation
+    }
+
+    #[test]
+    fn test_both_sides() {
+        let assumptions = Assumptions::new();
+        let step = add(&equation("x - 3", "4"), &parse("3"), &assumptions).unwrap();
+        assert_eq!(step.equation, relation(BinaryOperator::Equal, "x", "7"));
+        assert_eq!(step.description, "两边加上 3");
+
+        let step = multiply(&equation("x/2", "5"), &parse("2"), &assumptions).unwrap();
+        assert_eq!(step.equation, relation(BinaryOperator::Equal, "x", "10"));
+        assert!(step.warnings.is_empty());
+
+        let step = substitute(&equation("x + y", "5"), "y", &parse("2*x"), &assumptions).unwrap();
+        assert_eq!(step.equation, relation(BinaryOperator::Equal, "3*x", "5"));
+
+        assert!(multiply(&equation("x", "1"), &parse("0"), &assumptions).is_err());
+        assert!(divide(&equation("x", "1"), &parse("1 - 1"), &assumptions).is_err());
+        assert!(add(&parse("x + 1"), &parse("1"), &assumptions).is_err());
+    }
+
+    #[test]
+    fn test_zero_and_sign_warnings() {
+        let mut assumptions = Assumptions::new();
+        let step = divide(&equation("a*x", "a"), &parse("a"), &assumptions).unwrap();
+        assert_eq!(step.equation, relation(BinaryOperator::Equal, "x", "1"));
+        assert_eq!(step.warnings.len(), 1);
+
+        // 不等式乘负数时反向，符号未知时警告
+        let inequality = Expression::binary_op(BinaryOperator::Less, parse("-2*x"), parse("6"));
+        let step = divide(&inequality, &parse("-2"), &assumptions).unwrap();
+        assert_eq!(step.equation, relation(BinaryOperator::Greater, "x", "-3"));
+        assert!(step.description.contains("反向"));
+        let step = multiply(&inequality, &parse("k"), &assumptions).unwrap();
+        assert_eq!(step.warnings.len(), 2);
+
+        assumptions.assume("k", Property::Negative).unwrap();
+        let step = multiply(&inequality, &parse("k"), &assumptions).unwrap();
+        assert!(step.warnings.is_empty());
+        assert!(matches!(step.equation, Expression::BinaryOp { op: BinaryOperator::Greater, .. }));
+    }
+
+    #[test]
+    fn test_isolate_linear() {
+        assert_eq!(isolated("2*x + 3", "7", "x"), relation(BinaryOperator::Equal, "x", "2"));
+        assert_eq!(isolated("y", "m*x + b", "x"), relation(BinaryOperator::Equal, "x", "(y - b)/m"));
+        assert_eq!(isolated("5 - x", "1", "x"), relation(BinaryOperator::Equal, "x", "4"));
+        assert_eq!(isolated("6/x", "3", "x"), relation(BinaryOperator::Equal, "x", "2"));
+
+        let steps = isolate(&equation("y", "m*x + b"), "x", &Assumptions::new()).unwrap();
+        let descriptions: Vec<&str> = steps.iter().map(|step| step.description.as_str()).collect();
+        assert_eq!(descriptions, vec!["交换两边", "两边减去 b", "两边除以 m"]);
+
+        // 不等式交换两边时方向随之改变
+        let inequality = Expression::binary_op(BinaryOperator::Less, parse("1"), parse("3 - x"));
+        let steps = isolate(&inequality, "x", &Assumptions::new()).unwrap();
+        assert_eq!(steps.last().unwrap().equation, relation(BinaryOperator::Less, "x", "2"));
+    }
+
+    #[test]
+    fn test_isolate_inverse_functions() {
+        let steps = isolate(&equation("x^2", "9"), "x", &Assumptions::new()).unwrap();
+        assert_eq!(steps[0].equation, relation(BinaryOperator::Equal, "x", "3"));
+        assert!(steps[0].warnings[0].contains("-3"));
+
+        assert_eq!(isolated("sqrt(x - 1)", "2", "x"), relation(BinaryOperator::Equal, "x", "5"));
+        assert_eq!(isolated("exp(2*x)", "y", "x"), relation(BinaryOperator::Equal, "x", "ln(y)/2"));
+        assert_eq!(isolated("ln(x)", "1", "x"), relation(BinaryOperator::Equal, "x", "exp(1)"));
+        assert_eq!(isolated("2^x", "8", "x"), relation(BinaryOperator::Equal, "x", "ln(8)/ln(2)"));
+    }
+
+    #[test]
+    fn test_isolate_repeated_variable() {
+        assert_eq!(isolated("2*x + x", "6", "x"), relation(BinaryOperator::Equal, "x", "2"));
+        let error = isolate(&equation("x^2 + x", "2"), "x", &Assumptions::new()).unwrap_err().to_string();
+        assert!(error.contains("出现了 2 次"));
+        assert!(isolate(&equation("y", "2"), "x", &Assumptions::new()).is_err());
+    }
+}
@@ -0,0 +1,263 @@
+//! # 等式恒等性判断
+//!
+//! 判断两个表达式是否恒等。先化简两边之差，结果为零即证明恒等；
+//! 否则在一组固定采样点上数值比较两边，找到不相等的点即作为反例返回。
+//!
+//! [`EquivalenceChecker::prove_identity`] 给出更详细的结论：符号证明、数值支持（附检验点数）
+//! 或带具体取值的反例，数值检验在固定采样点之后再取随机点，跳过任一边无定义的点。
+
+use std::collections::HashMap;
+use crate::core::{Expression, Number};
+use super::ComputeError;
+use super::calculus::CalculusEngine;
+use super::enhanced_simplify::EnhancedSimplifier;
+use super::random::SeededRng;
+use crate::tr;
+
+/// 采样点（分子，分母），整数在前，使反例尽量简单
+const SAMPLES: [(i64, i64); 12] = [
+    (0, 1), (1, 1), (2, 1), (-1, 1), (3, 1), (-2, 1),
+    (1, 2), (-5, 2), (7, 4), (13, 3), (-1, 3), (11, 5),
+];
+
+/// 数值比较的相对容差
+const TOLERANCE: f64 = 1e-9;
+
+/// 数值判断恒等时至少需要的有效采样点数
+const MIN_VALID_SAMPLES: usize = 3;
+
+/// 证明恒等式时需要通过的定义域内检验点数
+const PROOF_POINTS: usize = 24;
+
+/// 证明恒等式时寻找定义域内检验点的最多尝试次数
+const PROOF_ATTEMPTS: usize = 200;
+
+/// 随机检验点的种子，使结论可以复现
+const PROOF_SEED: u64 = 0x5EED_1D3A;
+
+/// 反例：两边取值不同的代入点
+#[derive(Debug, Clone, PartialEq)]
+pub struct Counterexample {
+    /// 各变量的取值，按变量名排序
+    pub point: Vec<(String, Expression)>,
+    /// 左边的数值
+    pub lhs: f64,
+    /// 右边的数值
+    pub rhs: f64,
+}
+
+/// 恒等性判断结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct Equivalence {
+    /// 两边是否恒等
+    pub equivalent: bool,
+    /// 是否由符号化简证明（否则为数值采样的结论）
+    pub proven: bool,
+    /// 不恒等时的反例
+    pub counterexample: Option<Counterexample>,
+}
+
+/// 恒等式证明结果
+#[derive(Debug, Clone, PartialEq)]
+pub enum IdentityResult {
+    /// 两边之差化简为零
+    Verified,
+    /// 符号化简未能证明，但定义域内的检验点全部通过
+    NumericallySupported {
+        /// 通过的检验点数
+        tested_points: usize,
+    },
+    /// 找到两边取值不同的点
+    Refuted {
+        /// 各变量的取值
+        counterexample: HashMap<String, Number>,
+        /// 左边的数值
+        lhs_value: f64,
+        /// 右边的数值
+        rhs_value: f64,
+    },
+}
+
+/// 积分的求导回检结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct VerifiedIntegral {
+    /// 原函数
+    pub integral: Expression,
+    /// 是否通过回检；原函数的导数在采样点上无法求值时为 false
+    pub verified: bool,
+}
+
+/// 等式恒等性判断器
+pub struct EquivalenceChecker {
+    calculus: CalculusEngine,
+}
+
+impl EquivalenceChecker {
+    /// 创建新的判断器
+    pub fn new() -> Self {
+        Self {
+            calculus: CalculusEngine::new(),
+        }
+    }
+
+    /// 判断 `lhs` 与 `rhs` 是否对所有变量取值恒等
+    pub fn are_equivalent(&self, lhs: &Expression, rhs: &Expression) -> Result<Equivalence, ComputeError> {
+        if Self::difference_is_zero(lhs, rhs)? {
+            return Ok(Equivalence { equivalent: true, proven: true, counterexample: None });
+        }
+
+        let mut variables = lhs.get_variables();
+        variables.extend(rhs.get_variables());
+        variables.sort();
+        variables.dedup();
+
+        // 没有变量时只有一个代入点
+        let rounds = if variables.is_empty() { 1 } else { SAMPLES.len() };
+        let mut valid = 0;
+        for round in 0..rounds {
+            let point = Self::sample_point(&variables, round);
+            let values: HashMap<String, f64> = point.iter()
+                .map(|(name, (num, den))| (name.clone(), *num as f64 / *den as f64))
+                .collect();
+
+            // 两边有一边在该点无定义时跳过
+            let (Some(left), Some(right)) = (self.evaluate(lhs, &values), self.evaluate(rhs, &values)) else {
+                continue;
+            };
+            valid += 1;
+
+            if (left - right).abs() > TOLERANCE * left.abs().max(right.abs()).max(1.0) {
+                let point = point.into_iter()
+                    .map(|(name, (num, den))| {
+                        let value = if den == 1 { Number::integer(num) } else { Number::rational(num, den) };
+                        (name, Expression::Number(value))
+                    })
+                    .collect();
+                return Ok(Equivalence {
+                    equivalent: false,
+                    proven: false,
+                    counterexample: Some(Counterexample { point, lhs: left, rhs: right }),
+                });
+            }
+        }
+
+        if valid < MIN_VALID_SAMPLES.min(rounds) {
+            return Err(ComputeError::domain_error(tr!("equivalence.unevaluable_samples")));
+        }
+        Ok(Equivalence { equivalent: true, proven: false, counterexample: None })
+    }
+
+    /// 证明 `lhs = rhs` 对 `vars` 的所有取值成立，`vars` 为空时取两边出现的全部变量
+    ///
+    /// 化简失败时不报错，改为数值检验；两边出现未列在 `vars` 中的变量时报错。
+    pub fn prove_identity(&self, lhs: &Expression, rhs: &Expression, vars: &[&str]) -> Result<IdentityResult, ComputeError> {
+        let mut variables = lhs.get_variables();
+        variables.extend(rhs.get_variables());
+        variables.sort();
+        variables.dedup();
+        if !vars.is_empty() {
+            if let Some(free) = variables.iter().find(|v| !vars.contains(&v.as_str())) {
+                return Err(ComputeError::domain_error(tr!("equivalence.free_variable", variable = free, variables = vars.join(", "))));
+            }
+            variables = vars.iter().map(|v| v.to_string()).collect();
+        }
+
+        if Self::difference_is_zero(lhs, rhs).unwrap_or(false) {
+            return Ok(IdentityResult::Verified);
+        }
+
+        // 先取固定采样点使反例尽量简单，再取随机点；没有变量时只有一个代入点
+        let (points, attempts) = if variables.is_empty() { (1, 1) } else { (PROOF_POINTS, PROOF_ATTEMPTS) };
+        let mut rng = SeededRng::new(PROOF_SEED);
+        let mut tested_points = 0;
+        for attempt in 0..attempts {
+            if tested_points == points {
+                break;
+            }
+            let point = if attempt < SAMPLES.len() {
+                Self::sample_point(&variables, attempt)
+            } else {
+                variables.iter().map(|name| (name.clone(), Self::random_sample(&mut rng))).collect()
+            };
+            let values: HashMap<String, f64> = point.iter()
+                .map(|(name, (num, den))| (name.clone(), *num as f64 / *den as f64))
+                .collect();
+
+            // 任一边在该点无定义（除零、负数取对数等）时换一个点
+            let (Some(lhs_value), Some(rhs_value)) = (self.evaluate(lhs, &values), self.evaluate(rhs, &values)) else {
+                continue;
+            };
+            tested_points += 1;
+
+            if (lhs_value - rhs_value).abs() > TOLERANCE * lhs_value.abs().max(rhs_value.abs()).max(1.0) {
+                let counterexample = point.into_iter()
+                    .map(|(name, (num, den))| (name, if den == 1 { Number::integer(num) } else { Number::rational(num, den) }))
+                    .collect();
+                return Ok(IdentityResult::Refuted { counterexample, lhs_value, rhs_value });
+            }
+        }
+
+        if tested_points < MIN_VALID_SAMPLES.min(points) {
+            return Err(ComputeError::domain_error(tr!("equivalence.unevaluable_points")));
+        }
+        Ok(IdentityResult::NumericallySupported { tested_points })
+    }
+
+    /// 对原函数 `integral` 关于 `var` 求导，检查是否与被积函数 `integrand` 恒等
+    ///
+    /// 找到反例说明积分有误，报错；无法判断时返回未验证的结果。
+    pub fn verify_integral(&self, integrand: &Expression, integral: Expression, var: &str) -> Result<VerifiedIntegral, ComputeError> {
+        let checked = self.calculus.differentiate(&integral, var)
+            .and_then(|derivative| self.are_equivalent(&derivative, integrand));
+        match checked {
+            Ok(Equivalence { equivalent: true, .. }) => Ok(VerifiedIntegral { integral, verified: true }),
+            Ok(Equivalence { counterexample, .. }) => {
+                let at = counterexample
+                    .map(|c| {
+                        let point: Vec<String> = c.point.iter().map(|(name, value)| format!("{} = {}", name, value)).collect();
+                        tr!("equivalence.counterexample", point = point.join(", "), derivative = c.lhs, integrand = c.rhs)
+                    })
+                    .unwrap_or_default();
+                Err(ComputeError::internal(tr!("equivalence.integral_mismatch", integral = integral, integrand = integrand, counterexample = at)))
+            }
+            Err(_) => Ok(VerifiedIntegral { integral, verified: false }),
+        }
+    }
+
+    /// 第 `round` 个采样点，不同变量错开取值，避免总是落在 x = y 上
+    fn sample_point(variables: &[String], round: usize) -> Vec<(String, (i64, i64))> {
+        variables.iter()
+            .enumerate()
+            .map(|(i, name)| (name.clone(), SAMPLES[(round + 5 * i) % SAMPLES.len()]))
+            .collect()
+    }
+
+    /// 随机检验点坐标：分母 1 到 8、绝对值不超过 10 的既约分数
+    fn random_sample(rng: &mut SeededRng) -> (i64, i64) {
+        let den = (rng.next_u64() % 8 + 1) as i64;
+        let num = (rng.next_u64() % (20 * den as u64 + 1)) as i64 - 10 * den;
+        let gcd = num_integer::gcd(num, den);
+        (num / gcd, den / gcd)
+    }
+
+    /// 两边之差能否化简为零
+    fn difference_is_zero(lhs: &Expression, rhs: &Expression) -> Result<bool, ComputeError> {
+        let difference = EnhancedSimplifier::new().enhanced_simplify(&Expression::subtract(lhs.clone(), rhs.clone()))?;
+        Ok(matches!(&difference, Expression::Number(n) if n.is_zero()))
+    }
+
+    /// 数值求值，无定义或结果非有限时返回 None
+    fn evaluate(&self, expr: &Expression, values: &HashMap<String, f64>) -> Option<f64> {
+        self.calculus.numerical_evaluate(expr, values).ok().filter(|v| v.is_finite())
+    }
+}
+
+impl Default for EquivalenceChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[path = "equivalence_tests.rs"]
+mod equivalence_tests;
@@ -0,0 +1,107 @@
+//! # 等式恒等性判断测试
+//!
+//! 测试符号证明、数值采样判断以及反例的选取。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::equivalence::{EquivalenceChecker, IdentityResult};
+    use crate::core::{Expression, Number};
+    use crate::test_utils::parse;
+
+    #[test]
+    fn test_symbolic_identity() {
+        let result = EquivalenceChecker::new().are_equivalent(&parse("(x+1)^2"), &parse("x^2+2*x+1")).unwrap();
+        assert!(result.equivalent);
+        assert!(result.proven);
+        assert!(result.counterexample.is_none());
+    }
+
+    #[test]
+    fn test_numeric_identity() {
+        // 化简器不认识倍角公式，由数值采样得出结论
+        let result = EquivalenceChecker::new().are_equivalent(&parse("cos(x)^2 - sin(x)^2"), &parse("cos(2*x)")).unwrap();
+        assert!(result.equivalent);
+        assert!(!result.proven);
+
+        // sin² + cos² = 1 由三角规则集化简证明
+        let result = EquivalenceChecker::new().are_equivalent(&parse("sin(x)^2 + cos(x)^2"), &parse("1")).unwrap();
+        assert!(result.equivalent);
+        assert!(result.proven);
+    }
+
+    #[test]
+    fn test_counterexample() {
+        // x = 0 和 x = 1 时两边相等，反例取第一个不相等的采样点
+        let result = EquivalenceChecker::new().are_equivalent(&parse("x^2"), &parse("x")).unwrap();
+        assert!(!result.equivalent);
+        let counterexample = result.counterexample.unwrap();
+        assert_eq!(counterexample.point, vec![("x".to_string(), Expression::Number(Number::integer(2)))]);
+        assert_eq!((counterexample.lhs, counterexample.rhs), (4.0, 2.0));
+
+        let result = EquivalenceChecker::new().are_equivalent(&parse("(x+y)^2"), &parse("x^2+y^2")).unwrap();
+        assert!(!result.equivalent);
+        assert_eq!(result.counterexample.unwrap().point.len(), 2);
+    }
+
+    #[test]
+    fn test_undefined_points_skipped() {
+        // x = 0 处两边都无定义，跳过该点
+        let result = EquivalenceChecker::new().are_equivalent(&parse("x/x"), &parse("1")).unwrap();
+        assert!(result.equivalent);
+        assert!(EquivalenceChecker::new().are_equivalent(&parse("1/x"), &parse("x")).map(|r| !r.equivalent).unwrap());
+    }
+
+    #[test]
+    fn test_verify_integral() {
+        let checker = EquivalenceChecker::new();
+        let verified = checker.verify_integral(&parse("3*x^2 + cos(x)"), parse("x^3 + sin(x)"), "x").unwrap();
+        assert!(verified.verified);
+        assert_eq!(verified.integral, parse("x^3 + sin(x)"));
+        // 积分常数不影响回检
+        assert!(checker.verify_integral(&parse("2*x"), parse("x^2 + C"), "x").unwrap().verified);
+
+        // 人为构造的错误原函数：少了系数 1/2
+        let error = checker.verify_integral(&parse("x"), parse("x^2"), "x").unwrap_err();
+        assert!(error.to_string().contains("不一致"), "{}", error);
+
+        // 导数在采样点上处处无定义时无法判断
+        let unverified = checker.verify_integral(&parse("1"), parse("sqrt(-1 - x^2)"), "x").unwrap();
+        assert!(!unverified.verified);
+    }
+
+    #[test]
+    fn test_prove_identity() {
+        let checker = EquivalenceChecker::new();
+        assert_eq!(checker.prove_identity(&parse("(x+1)^2"), &parse("x^2+2*x+1"), &[]).unwrap(), IdentityResult::Verified);
+        assert_eq!(
+            checker.prove_identity(&parse("cos(x)^2 - sin(x)^2"), &parse("cos(2*x)"), &["x"]).unwrap(),
+            IdentityResult::NumericallySupported { tested_points: 24 }
+        );
+
+        // 反例给出具体取值和两边的值
+        let IdentityResult::Refuted { counterexample, lhs_value, rhs_value } = checker.prove_identity(&parse("x^2"), &parse("x"), &[]).unwrap() else {
+            panic!("x^2 = x 应被推翻");
+        };
+        assert_eq!(counterexample["x"], Number::integer(2));
+        assert_eq!((lhs_value, rhs_value), (4.0, 2.0));
+
+        // 没有变量时比较一次
+        assert!(matches!(checker.prove_identity(&parse("sqrt(2)^2"), &parse("3"), &[]).unwrap(), IdentityResult::Refuted { .. }));
+    }
+
+    #[test]
+    fn test_prove_identity_avoids_domain() {
+        let checker = EquivalenceChecker::new();
+        // x ≤ 0 时右边无定义，只在 x > 0 的点上检验
+        let result = checker.prove_identity(&parse("ln(x^2)"), &parse("2*ln(x)"), &["x"]).unwrap();
+        assert!(matches!(result, IdentityResult::NumericallySupported { tested_points: 24 } | IdentityResult::Verified), "{:?}", result);
+        // 反例不会落在除零的点上
+        let IdentityResult::Refuted { counterexample, .. } = checker.prove_identity(&parse("1/x"), &parse("1/(x-1)"), &["x"]).unwrap() else {
+            panic!("1/x = 1/(x-1) 应被推翻");
+        };
+        assert!(!counterexample["x"].is_zero() && counterexample["x"] != Number::integer(1));
+
+        // 未列出的变量报错
+        assert!(checker.prove_identity(&parse("x + y"), &parse("y + x + 1"), &["x"]).is_err());
+    }
+}
@@ -0,0 +1,436 @@
+//! # 练习题生成与批改
+//!
+//! 按题型和难度生成练习题及标准答案。题目由答案反向构造：积分题先取原函数再求导，
+//! 因式分解与解方程题先取整数根再展开，因此标准答案总是正确的。同一种子总是生成
+//! 同一道题。批改用等价性判断，容忍形式不同但数学上相同的答案。
+
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator};
+use crate::tr;
+use super::{ComputeEngine, ComputeError};
+use super::random::SeededRng;
+
+/// 题目中使用的变量
+pub const EXERCISE_VARIABLE: &str = "x";
+
+/// 最高难度
+pub const MAX_DIFFICULTY: u8 = 3;
+
+/// 练习题类型；难度取 1 到 [`MAX_DIFFICULTY`]，越高表达式越深、涉及的函数种类越多
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExerciseKind {
+    /// 求导：题目为 f(x)，答案为 f'(x)
+    Differentiation { difficulty: u8 },
+    /// 不定积分：题目为被积函数，答案为一个原函数
+    Integration { difficulty: u8 },
+    /// 化简：题目为可化简的表达式，答案为化简结果
+    Simplification { difficulty: u8 },
+    /// 因式分解：题目为展开的多项式，答案为因式分解形式
+    Factorization { difficulty: u8 },
+    /// 解方程：题目为方程，答案为全部实根组成的集合
+    Equation { difficulty: u8 },
+}
+
+impl ExerciseKind {
+    /// 题目难度
+    pub fn difficulty(&self) -> u8 {
+        match *self {
+            ExerciseKind::Differentiation { difficulty }
+            | ExerciseKind::Integration { difficulty }
+            | ExerciseKind::Simplification { difficulty }
+            | ExerciseKind::Factorization { difficulty }
+            | ExerciseKind::Equation { difficulty } => difficulty,
+        }
+    }
+
+    /// 题型名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExerciseKind::Differentiation { .. } => tr!("exercise.differentiation"),
+            ExerciseKind::Integration { .. } => tr!("exercise.integration"),
+            ExerciseKind::Simplification { .. } => tr!("exercise.simplification"),
+            ExerciseKind::Factorization { .. } => tr!("exercise.factorization"),
+            ExerciseKind::Equation { .. } => tr!("exercise.equation"),
+        }
+    }
+}
+
+/// 生成一道练习题，返回（题目, 标准答案）
+pub fn generate_exercise(kind: ExerciseKind, seed: u64) -> Result<(Expression, Expression), ComputeError> {
+    let difficulty = kind.difficulty();
+    if !(1..=MAX_DIFFICULTY).contains(&difficulty) {
+        return Err(ComputeError::domain_error(tr!("exercise.difficulty", max = MAX_DIFFICULTY)));
+    }
+    let mut generator = Generator { rng: SeededRng::new(seed), difficulty };
+    Ok(match kind {
+        ExerciseKind::Differentiation { .. } => {
+            let terms = generator.terms(Generator::differentiable_term);
+            (render(expansion(&terms)), render(derivative(&terms)))
+        }
+        ExerciseKind::Integration { .. } => {
+            let terms = generator.terms(Generator::integrable_term);
+            (render(derivative(&terms)), render(expansion(&terms)))
+        }
+        ExerciseKind::Simplification { .. } => generator.simplification(),
+        ExerciseKind::Factorization { .. } => generator.factorization(),
+        ExerciseKind::Equation { .. } => generator.equation(),
+    })
+}
+
+/// 批改答案。无法判定与标准答案等价的答案（包括无法求值的）一律判错
+pub fn check_answer(engine: &dyn ComputeEngine, kind: ExerciseKind, problem: &Expression, answer: &Expression) -> Result<bool, ComputeError> {
+    let equivalent = |a: &Expression, b: &Expression| {
+        engine.are_equivalent(a, b).map(|result| result.equivalent).unwrap_or(false)
+    };
+    Ok(match kind {
+        ExerciseKind::Differentiation { .. } => {
+            equivalent(answer, &engine.differentiate(problem, EXERCISE_VARIABLE)?)
+        }
+        // 只要求导后等于被积函数，允许与标准答案相差任意常数
+        ExerciseKind::Integration { .. } => {
+            equivalent(&engine.differentiate(answer, EXERCISE_VARIABLE)?, problem)
+        }
+        ExerciseKind::Simplification { .. } => {
+            answer.complexity() < problem.complexity() && equivalent(answer, problem)
+        }
+        ExerciseKind::Factorization { .. } => is_product(answer) && equivalent(answer, problem),
+        ExerciseKind::Equation { .. } => {
+            let expected = engine.solve(problem, EXERCISE_VARIABLE)?;
+            let given = match answer {
+                Expression::Set(roots) | Expression::Vector(roots) => roots.clone(),
+                root => vec![root.clone()],
+            };
+            given.len() == expected.len()
+                && given.iter().all(|root| expected.iter().any(|e| equivalent(root, e)))
+                && expected.iter().all(|e| given.iter().any(|root| equivalent(root, e)))
+        }
+    })
+}
+
+/// 乘积、幂或它们的相反数才算因式分解的形式
+fn is_product(expr: &Expression) -> bool {
+    match expr {
+        Expression::BinaryOp { op: BinaryOperator::Multiply | BinaryOperator::Power, .. } => true,
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => is_product(operand),
+        _ => false,
+    }
+}
+
+/// 单项式：系数 · x^指数 · 因子
+#[derive(Debug, Clone, PartialEq)]
+struct Monomial {
+    coefficient: i64,
+    exponent: i64,
+    factor: Option<Expression>,
+}
+
+impl Monomial {
+    fn new(coefficient: i64, exponent: i64, factor: Option<Expression>) -> Self {
+        Self { coefficient, exponent, factor }
+    }
+
+    /// 以 `c` 为系数的表达式
+    fn with_coefficient(&self, c: i64) -> Expression {
+        let power = (self.exponent != 0).then(|| x_power(self.exponent));
+        match (power, &self.factor) {
+            // c·x^e·(1/d) 写成 c·x^e / d
+            (power, Some(Expression::BinaryOp { op: BinaryOperator::Divide, left, right }))
+                if matches!(left.as_ref(), Expression::Number(n) if n.is_one()) =>
+            {
+                let numerator = match power {
+                    Some(power) => scaled(c, power),
+                    None => number(c),
+                };
+                Expression::divide(numerator, right.as_ref().clone())
+            }
+            (None, None) => number(c),
+            (Some(power), None) => scaled(c, power),
+            (None, Some(factor)) => scaled(c, factor.clone()),
+            (Some(power), Some(factor)) => Expression::multiply(scaled(c, power), factor.clone()),
+        }
+    }
+}
+
+/// 内层参数
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Inner {
+    /// k*x + a
+    Linear(i64, i64),
+    /// x^2
+    Square,
+}
+
+impl Inner {
+    fn expr(self) -> Expression {
+        match self {
+            Inner::Linear(k, a) => render(vec![Monomial::new(k, 1, None), Monomial::new(a, 0, None)]),
+            Inner::Square => x_power(2),
+        }
+    }
+
+    /// 内层导数：（系数, x 的指数）
+    fn derivative(self) -> (i64, i64) {
+        match self {
+            Inner::Linear(k, _) => (k, 0),
+            Inner::Square => (2, 1),
+        }
+    }
+}
+
+/// 题目中的一项（不含系数）
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Term {
+    /// x^n
+    Power(i64),
+    /// f(inner)
+    Function(&'static str, Inner),
+    /// x^m · f(inner)
+    Product(i64, &'static str, Inner),
+}
+
+impl Term {
+    fn monomial(self, coefficient: i64) -> Monomial {
+        match self {
+            Term::Power(n) => Monomial::new(coefficient, n, None),
+            Term::Function(name, inner) => Monomial::new(coefficient, 0, Some(function(name, inner))),
+            Term::Product(m, name, inner) => Monomial::new(coefficient, m, Some(function(name, inner))),
+        }
+    }
+
+    /// 求导，按乘积法则与链式法则展开为单项式
+    fn derivative(self, coefficient: i64) -> Vec<Monomial> {
+        match self {
+            Term::Power(n) => vec![Monomial::new(coefficient * n, n - 1, None)],
+            Term::Function(name, inner) => vec![function_derivative(coefficient, 0, name, inner)],
+            Term::Product(m, name, inner) => vec![
+                Monomial::new(coefficient * m, m - 1, Some(function(name, inner))),
+                function_derivative(coefficient, m, name, inner),
+            ],
+        }
+    }
+}
+
+fn function(name: &str, inner: Inner) -> Expression {
+    Expression::function(name, vec![inner.expr()])
+}
+
+/// `coefficient · x^exponent · f'(inner)`
+fn function_derivative(coefficient: i64, exponent: i64, name: &str, inner: Inner) -> Monomial {
+    let (k, e) = inner.derivative();
+    // x^m · (ln(kx))' = x^(m-1)
+    if name == "ln" && exponent > 0 && matches!(inner, Inner::Linear(_, 0)) {
+        return Monomial::new(coefficient, exponent - 1, None);
+    }
+    let (sign, factor) = match name {
+        "sin" => (1, function("cos", inner)),
+        "cos" => (-1, function("sin", inner)),
+        "exp" => (1, function("exp", inner)),
+        _ => (1, Expression::divide(number(1), inner.expr())),
+    };
+    Monomial::new(sign * coefficient * k, exponent + e, Some(factor))
+}
+
+fn expansion(terms: &[(i64, Term)]) -> Vec<Monomial> {
+    terms.iter().map(|&(c, term)| term.monomial(c)).collect()
+}
+
+fn derivative(terms: &[(i64, Term)]) -> Vec<Monomial> {
+    terms.iter().flat_map(|&(c, term)| term.derivative(c)).collect()
+}
+
+/// 合并同类项后连成和：负系数写成减法，系数为 0 的项略去
+fn render(monomials: Vec<Monomial>) -> Expression {
+    let mut merged: Vec<Monomial> = Vec::new();
+    for monomial in monomials {
+        match merged.iter_mut().find(|m| m.exponent == monomial.exponent && m.factor == monomial.factor) {
+            Some(existing) => existing.coefficient += monomial.coefficient,
+            None => merged.push(monomial),
+        }
+    }
+    let mut iter = merged.into_iter().filter(|m| m.coefficient != 0);
+    let Some(first) = iter.next() else { return number(0) };
+    // 首项系数为 -1 时写成相反数，其余负系数直接写进系数里
+    let head = if first.coefficient == -1 { Expression::negate(first.with_coefficient(1)) } else { first.with_coefficient(first.coefficient) };
+    iter.fold(head, |acc, m| {
+        if m.coefficient < 0 {
+            Expression::subtract(acc, m.with_coefficient(-m.coefficient))
+        } else {
+            Expression::add(acc, m.with_coefficient(m.coefficient))
+        }
+    })
+}
+
+/// 按降幂排列的整系数多项式
+fn polynomial(coefficients: &[i64]) -> Expression {
+    render(coefficients.iter().enumerate().rev().map(|(n, &c)| Monomial::new(c, n as i64, None)).collect())
+}
+
+/// 展开 `leading · Π(x - rᵢ)`，返回升幂系数
+fn expand_roots(leading: i64, roots: &[i64]) -> Vec<i64> {
+    roots.iter().fold(vec![leading], |coefficients, &root| {
+        let mut product = vec![0; coefficients.len() + 1];
+        for (i, &c) in coefficients.iter().enumerate() {
+            product[i + 1] += c;
+            product[i] -= root * c;
+        }
+        product
+    })
+}
+
+fn x() -> Expression {
+    Expression::variable(EXERCISE_VARIABLE)
+}
+
+fn number(value: i64) -> Expression {
+    Expression::Number(Number::integer(value))
+}
+
+/// `x^n`，n = 1 时写成 x
+fn x_power(n: i64) -> Expression {
+    if n == 1 { x() } else { Expression::power(x(), number(n)) }
+}
+
+/// `coefficient * expr`，系数为 1 时省略
+fn scaled(coefficient: i64, expr: Expression) -> Expression {
+    if coefficient == 1 { expr } else { Expression::multiply(number(coefficient), expr) }
+}
+
+/// `x - r`，r 为负时写成 x + |r|
+fn linear_factor(root: i64) -> Expression {
+    polynomial(&[-root, 1])
+}
+
+/// 按种子与难度随机构造题目
+struct Generator {
+    rng: SeededRng,
+    difficulty: u8,
+}
+
+impl Generator {
+    /// `[low, high]` 内的随机整数
+    fn integer(&mut self, low: i64, high: i64) -> i64 {
+        low + (self.rng.next_u64() % (high - low + 1) as u64) as i64
+    }
+
+    /// `[-bound, bound]` 内的非零整数
+    fn nonzero(&mut self, bound: i64) -> i64 {
+        let value = self.integer(1, bound);
+        if self.rng.next_u64().is_multiple_of(2) { value } else { -value }
+    }
+
+    fn pick(&mut self, names: &[&'static str]) -> &'static str {
+        names[(self.rng.next_u64() % names.len() as u64) as usize]
+    }
+
+    /// 难度 + 1 个互不相同的项，各带非零系数
+    fn terms(&mut self, mut pick: impl FnMut(&mut Self) -> Term) -> Vec<(i64, Term)> {
+        let mut terms: Vec<(i64, Term)> = Vec::new();
+        for _ in 0..100 {
+            if terms.len() > self.difficulty as usize {
+                break;
+            }
+            let term = pick(self);
+            if terms.iter().all(|&(_, t)| t != term) {
+                let coefficient = self.nonzero(5);
+                terms.push((coefficient, term));
+            }
+        }
+        terms
+    }
+
+    /// 求导题的项：难度 1 只有幂函数，难度 2 加入 sin/cos/exp，难度 3 再加入 ln、复合与乘积
+    fn differentiable_term(&mut self) -> Term {
+        let k = self.integer(1, 3);
+        match (self.difficulty, self.integer(0, 3)) {
+            (1, _) | (_, 0) => Term::Power(self.integer(1, 5)),
+            (2, _) | (_, 1) => Term::Function(self.pick(&["sin", "cos", "exp"]), Inner::Linear(k, 0)),
+            (_, 2) => match self.pick(&["sin", "cos", "exp", "ln"]) {
+                "ln" => Term::Function("ln", Inner::Linear(k, self.integer(1, 4))),
+                name if self.integer(0, 1) == 0 => Term::Function(name, Inner::Square),
+                name => Term::Function(name, Inner::Linear(k, self.integer(1, 4))),
+            },
+            _ => Term::Product(self.integer(1, 2), self.pick(&["sin", "cos", "exp", "ln"]), Inner::Linear(1, 0)),
+        }
+    }
+
+    /// 积分题原函数的项：只取求导后形式简洁的项
+    fn integrable_term(&mut self) -> Term {
+        match (self.difficulty, self.integer(0, 3)) {
+            (1, _) | (_, 0) => Term::Power(self.integer(1, 4)),
+            (2, _) | (_, 1) => Term::Function(self.pick(&["sin", "cos", "exp"]), Inner::Linear(self.integer(1, 3), 0)),
+            (_, 2) => Term::Function("ln", Inner::Linear(1, self.integer(1, 4))),
+            _ => Term::Product(1, self.pick(&["sin", "cos", "exp"]), Inner::Linear(1, 0)),
+        }
+    }
+
+    /// 化简题：题目为 (P·(x+a))/(x+a)，答案为 P；难度 2 加入 k·sin²x + k·cos²x，
+    /// 难度 3 再加入 (x+d)² - (x² + 2dx)
+    fn simplification(&mut self) -> (Expression, Expression) {
+        let degree = self.difficulty as usize;
+        let mut coefficients: Vec<i64> = (0..=degree).map(|_| self.integer(-5, 5)).collect();
+        coefficients[degree] = self.nonzero(5);
+        let shift = linear_factor(-self.integer(1, 5));
+        let mut problem = Expression::divide(Expression::multiply(polynomial(&coefficients), shift.clone()), shift);
+        if self.difficulty >= 2 {
+            let k = self.integer(1, 4);
+            let square = |name: &str| Some(Expression::power(Expression::function(name, vec![x()]), number(2)));
+            problem = Expression::add(problem, render(vec![Monomial::new(k, 0, square("sin")), Monomial::new(k, 0, square("cos"))]));
+            coefficients[0] += k;
+        }
+        if self.difficulty >= 3 {
+            let d = self.integer(1, 4);
+            problem = Expression::add(problem, Expression::subtract(
+                Expression::power(linear_factor(-d), number(2)),
+                polynomial(&[0, 2 * d, 1]),
+            ));
+            coefficients[0] += d * d;
+        }
+        (problem, polynomial(&coefficients))
+    }
+
+    /// 因式分解题：难度 + 1 个整数根，难度 3 带非 1 的首项系数；重根合并为幂
+    fn factorization(&mut self) -> (Expression, Expression) {
+        let leading = if self.difficulty >= 3 { self.integer(2, 3) } else { 1 };
+        let mut roots: Vec<i64> = (0..=self.difficulty).map(|_| self.integer(-5, 5)).collect();
+        roots.sort_unstable_by(|a, b| b.cmp(a));
+        let problem = polynomial(&expand_roots(leading, &roots));
+
+        let mut factors: Vec<Expression> = Vec::new();
+        if leading != 1 {
+            factors.push(number(leading));
+        }
+        let mut i = 0;
+        while i < roots.len() {
+            let multiplicity = roots[i..].iter().take_while(|&&r| r == roots[i]).count();
+            let factor = linear_factor(roots[i]);
+            factors.push(if multiplicity == 1 { factor } else { Expression::power(factor, number(multiplicity as i64)) });
+            i += multiplicity;
+        }
+        let answer = factors.into_iter().reduce(Expression::multiply).unwrap_or_else(x);
+        (problem, answer)
+    }
+
+    /// 解方程题：难度 1 为一次方程 a·x + b = c，难度 2、3 为两个或三个互异整数根的多项式方程；
+    /// 答案为升序排列的根组成的集合
+    fn equation(&mut self) -> (Expression, Expression) {
+        let (lhs, rhs, mut roots) = if self.difficulty == 1 {
+            let (a, root, c) = (self.nonzero(6), self.integer(-9, 9), self.integer(-10, 10));
+            (polynomial(&[c - a * root, a]), number(c), vec![root])
+        } else {
+            let mut roots: Vec<i64> = Vec::new();
+            while roots.len() < self.difficulty as usize {
+                let root = self.integer(-6, 6);
+                if !roots.contains(&root) {
+                    roots.push(root);
+                }
+            }
+            (polynomial(&expand_roots(1, &roots)), number(0), roots)
+        };
+        roots.sort_unstable();
+        let problem = Expression::binary_op(BinaryOperator::Equal, lhs, rhs);
+        (problem, Expression::set(roots.into_iter().map(number).collect()))
+    }
+}
+
+#[cfg(test)]
+#[path = "exercise_tests.rs"]
+mod exercise_tests;
@@ -0,0 +1,81 @@
+//! # 练习题生成与批改测试
+//!
+//! 测试种子的可复现性、标准答案的正确性以及批改对错误答案的判定。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::exercise::{generate_exercise, check_answer, ExerciseKind};
+    use crate::engine::compute::BasicComputeEngine;
+    use crate::core::{Expression, Number};
+
+    fn all_kinds(difficulty: u8) -> [ExerciseKind; 5] {
+        [
+            ExerciseKind::Differentiation { difficulty },
+            ExerciseKind::Integration { difficulty },
+            ExerciseKind::Simplification { difficulty },
+            ExerciseKind::Factorization { difficulty },
+            ExerciseKind::Equation { difficulty },
+        ]
+    }
+
+    #[test]
+    fn test_same_seed_same_exercise() {
+        for kind in all_kinds(2) {
+            let first = generate_exercise(kind, 42).unwrap();
+            assert_eq!(first, generate_exercise(kind, 42).unwrap());
+            let others: Vec<_> = (43..48).map(|seed| generate_exercise(kind, seed).unwrap()).collect();
+            assert!(others.iter().any(|other| *other != first));
+        }
+    }
+
+    #[test]
+    fn test_standard_answers_pass() {
+        let engine = BasicComputeEngine::new();
+        for difficulty in 1..=3 {
+            for kind in all_kinds(difficulty) {
+                for seed in 0..6 {
+                    let (problem, answer) = generate_exercise(kind, seed).unwrap();
+                    assert!(
+                        check_answer(&engine, kind, &problem, &answer).unwrap(),
+                        "{} 难度 {} 种子 {}: {} => {}", kind.name(), difficulty, seed, problem, answer
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_check_answer() {
+        let engine = BasicComputeEngine::new();
+
+        // 积分答案允许相差常数
+        let kind = ExerciseKind::Integration { difficulty: 2 };
+        let (problem, answer) = generate_exercise(kind, 7).unwrap();
+        let shifted = Expression::add(answer.clone(), Expression::Number(Number::integer(5)));
+        assert!(check_answer(&engine, kind, &problem, &shifted).unwrap());
+        let doubled = Expression::multiply(Expression::Number(Number::integer(2)), answer);
+        assert!(!check_answer(&engine, kind, &problem, &doubled).unwrap());
+
+        // 把题目原样交回不算因式分解
+        let kind = ExerciseKind::Factorization { difficulty: 1 };
+        let (problem, _) = generate_exercise(kind, 3).unwrap();
+        assert!(!check_answer(&engine, kind, &problem, &problem).unwrap());
+
+        // 求导答错
+        let kind = ExerciseKind::Differentiation { difficulty: 1 };
+        let (problem, _) = generate_exercise(kind, 3).unwrap();
+        assert!(!check_answer(&engine, kind, &problem, &problem).unwrap());
+
+        // 解方程漏根
+        let kind = ExerciseKind::Equation { difficulty: 2 };
+        let (problem, answer) = generate_exercise(kind, 3).unwrap();
+        let Expression::Set(roots) = answer else { panic!("解方程的答案应为集合") };
+        assert!(!check_answer(&engine, kind, &problem, &roots[0]).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_difficulty() {
+        assert!(generate_exercise(ExerciseKind::Differentiation { difficulty: 0 }, 1).is_err());
+        assert!(generate_exercise(ExerciseKind::Equation { difficulty: 4 }, 1).is_err());
+    }
+}
@@ -0,0 +1,269 @@
+//! # 极值与驻点分析
+//!
+//! 求单变量函数的临界点（一阶导为零或不存在的点）、局部极值和拐点。
+//! 驻点用二阶导判定，二阶导为零时继续使用更高阶导数；
+//! 不可导点和高阶导数无法判定的点使用一阶导数的变号检验。
+
+use std::collections::HashMap;
+use std::fmt;
+use crate::core::{Expression, Number};
+use crate::tr;
+use super::ComputeError;
+use super::calculus::CalculusEngine;
+use super::domain::DomainAnalyzer;
+use super::simplify::Simplifier;
+
+/// 高阶导数判定时使用的最高阶数
+const MAX_DERIVATIVE_ORDER: usize = 8;
+
+/// 判断导数值为零时的容差
+const ZERO_TOLERANCE: f64 = 1e-9;
+
+/// 临界点的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExtremumKind {
+    /// 局部极大值
+    Maximum,
+    /// 局部极小值
+    Minimum,
+    /// 鞍点（临界点但不是极值点）
+    Saddle,
+    /// 无法判定
+    Undetermined,
+}
+
+impl fmt::Display for ExtremumKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            ExtremumKind::Maximum => tr!("extremum.maximum"),
+            ExtremumKind::Minimum => tr!("extremum.minimum"),
+            ExtremumKind::Saddle => tr!("extremum.saddle"),
+            ExtremumKind::Undetermined => tr!("extremum.undetermined"),
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 局部极值分析结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct Extremum {
+    /// 临界点
+    pub point: Expression,
+    /// 临界点类型
+    pub kind: ExtremumKind,
+    /// 函数在临界点处的值
+    pub value: Expression,
+}
+
+/// 临界点及其数值近似
+struct CriticalPoint {
+    point: Expression,
+    approx: f64,
+    /// 一阶导数在该点是否存在
+    differentiable: bool,
+}
+
+/// 极值与驻点分析器
+pub struct ExtremaAnalyzer {
+    domain: DomainAnalyzer,
+    calculus: CalculusEngine,
+}
+
+impl ExtremaAnalyzer {
+    /// 创建新的分析器
+    pub fn new() -> Self {
+        Self {
+            domain: DomainAnalyzer::new(),
+            calculus: CalculusEngine::new(),
+        }
+    }
+
+    /// 求临界点：一阶导为零或不存在、且函数有定义的点，按从小到大排列
+    pub fn critical_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError> {
+        let mut simplifier = Simplifier::new();
+        let derivative = self.derivative(&mut simplifier, expr, var)?;
+        let points = self.find_critical_points(expr, &derivative, var)?;
+        Ok(points.into_iter().map(|p| p.point).collect())
+    }
+
+    /// 求局部极值，返回每个临界点的类型和函数值
+    pub fn local_extrema(&self, expr: &Expression, var: &str) -> Result<Vec<Extremum>, ComputeError> {
+        let mut simplifier = Simplifier::new();
+        let derivative = self.derivative(&mut simplifier, expr, var)?;
+        let points = self.find_critical_points(expr, &derivative, var)?;
+
+        let mut extrema = Vec::new();
+        for point in points {
+            let kind = if point.differentiable {
+                match self.higher_derivative_test(&mut simplifier, &derivative, var, &point)? {
+                    ExtremumKind::Undetermined => self.sign_change_test(&derivative, var, point.approx),
+                    kind => kind,
+                }
+            } else {
+                self.sign_change_test(&derivative, var, point.approx)
+            };
+            let value = self.value_at(&mut simplifier, expr, var, &point);
+            extrema.push(Extremum { point: point.point, kind, value });
+        }
+        Ok(extrema)
+    }
+
+    /// 求拐点：二阶导为零或不存在、且两侧二阶导变号的点，按从小到大排列
+    pub fn inflection_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError> {
+        let mut simplifier = Simplifier::new();
+        let first = self.derivative(&mut simplifier, expr, var)?;
+        let second = self.derivative(&mut simplifier, &first, var)?;
+
+        let candidates = self.domain.zeros_and_poles(&second, var)?;
+        let approximations: Vec<f64> = candidates.iter().map(|(_, x)| *x).collect();
+        let mut points = Vec::new();
+        for (i, (point, x)) in candidates.into_iter().enumerate() {
+            if !self.is_defined(expr, var, x) {
+                continue;
+            }
+            let h = Self::probe_step(&approximations, i);
+            let left = self.domain.evaluate_at(&second, var, x - h);
+            let right = self.domain.evaluate_at(&second, var, x + h);
+            if let (Some(left), Some(right)) = (left, right) {
+                if left.is_finite() && right.is_finite() && left * right < 0.0 {
+                    points.push(point);
+                }
+            }
+        }
+        Ok(points)
+    }
+
+    /// 求导并化简
+    fn derivative(&self, simplifier: &mut Simplifier, expr: &Expression, var: &str) -> Result<Expression, ComputeError> {
+        let derivative = self.calculus.differentiate(expr, var)?;
+        simplifier.simplify(&derivative)
+    }
+
+    /// 在导数的零点与极点中筛选出临界点
+    fn find_critical_points(&self, expr: &Expression, derivative: &Expression, var: &str) -> Result<Vec<CriticalPoint>, ComputeError> {
+        let mut points = Vec::new();
+        for (point, x) in self.domain.zeros_and_poles(derivative, var)? {
+            if !self.is_defined(expr, var, x) {
+                continue;
+            }
+            match self.domain.evaluate_at(derivative, var, x) {
+                // 导数存在但不为零（例如分子分母的公共零点已约去），不是临界点
+                Some(d) if d.is_finite() && d.abs() > ZERO_TOLERANCE * (1.0 + x.abs()) => {}
+                Some(d) if d.is_finite() => points.push(CriticalPoint { point, approx: x, differentiable: true }),
+                _ => points.push(CriticalPoint { point, approx: x, differentiable: false }),
+            }
+        }
+        Ok(points)
+    }
+
+    /// 高阶导数判定：第一个非零的 k 阶导数，k 为偶数时为极值，k 为奇数时为鞍点
+    fn higher_derivative_test(&self, simplifier: &mut Simplifier, first: &Expression, var: &str, point: &CriticalPoint) -> Result<ExtremumKind, ComputeError> {
+        let mut derivative = first.clone();
+        for order in 2..=MAX_DERIVATIVE_ORDER {
+            derivative = self.derivative(simplifier, &derivative, var)?;
+            let sign = match self.sign_at(simplifier, &derivative, var, point) {
+                Some(sign) => sign,
+                None => break,
+            };
+            if sign == 0 {
+                continue;
+            }
+            return Ok(match (order % 2 == 0, sign > 0) {
+                (true, true) => ExtremumKind::Minimum,
+                (true, false) => ExtremumKind::Maximum,
+                (false, _) => ExtremumKind::Saddle,
+            });
+        }
+        Ok(ExtremumKind::Undetermined)
+    }
+
+    /// 一阶导数变号检验，只有一侧有定义时按单侧单调性判定
+    fn sign_change_test(&self, derivative: &Expression, var: &str, x: f64) -> ExtremumKind {
+        let h = 1e-6 * (1.0 + x.abs());
+        let sign = |value: Option<f64>| {
+            value.filter(|v| v.is_finite() && v.abs() > ZERO_TOLERANCE).map(|v| v.signum())
+        };
+        let left = sign(self.domain.evaluate_at(derivative, var, x - h));
+        let right = sign(self.domain.evaluate_at(derivative, var, x + h));
+
+        match (left, right) {
+            (Some(l), Some(r)) if l > 0.0 && r < 0.0 => ExtremumKind::Maximum,
+            (Some(l), Some(r)) if l < 0.0 && r > 0.0 => ExtremumKind::Minimum,
+            (Some(_), Some(_)) => ExtremumKind::Saddle,
+            (Some(l), None) if l > 0.0 => ExtremumKind::Maximum,
+            (Some(_), None) => ExtremumKind::Minimum,
+            (None, Some(r)) if r > 0.0 => ExtremumKind::Minimum,
+            (None, Some(_)) => ExtremumKind::Maximum,
+            (None, None) => ExtremumKind::Undetermined,
+        }
+    }
+
+    /// 表达式在临界点处的符号，优先使用精确值；无定义时返回 None
+    fn sign_at(&self, simplifier: &mut Simplifier, expr: &Expression, var: &str, point: &CriticalPoint) -> Option<i8> {
+        if let Expression::Number(n) = self.substitute(simplifier, expr, var, &point.point) {
+            if n.is_zero() {
+                return Some(0);
+            }
+            if let Some(value) = n.to_f64() {
+                return Some(if value < 0.0 { -1 } else { 1 });
+            }
+        }
+
+        let value = self.domain.evaluate_at(expr, var, point.approx).filter(|v| v.is_finite())?;
+        Some(if value.abs() <= ZERO_TOLERANCE * (1.0 + point.approx.abs()) {
+            0
+        } else if value < 0.0 {
+            -1
+        } else {
+            1
+        })
+    }
+
+    /// 计算函数在临界点处的值，无法化简为精确值时使用数值近似
+    fn value_at(&self, simplifier: &mut Simplifier, expr: &Expression, var: &str, point: &CriticalPoint) -> Expression {
+        let exact = self.substitute(simplifier, expr, var, &point.point);
+        if exact.get_variables().is_empty() {
+            return exact;
+        }
+        match self.domain.evaluate_at(expr, var, point.approx) {
+            Some(value) => Expression::Number(Number::Float(value)),
+            None => exact,
+        }
+    }
+
+    /// 代入并化简
+    fn substitute(&self, simplifier: &mut Simplifier, expr: &Expression, var: &str, value: &Expression) -> Expression {
+        let mut bindings = HashMap::new();
+        bindings.insert(var.to_string(), value.clone());
+        let substituted = expr.substitute(&bindings);
+        simplifier.simplify(&substituted).unwrap_or(substituted)
+    }
+
+    /// 函数在该点是否有定义
+    fn is_defined(&self, expr: &Expression, var: &str, x: f64) -> bool {
+        self.domain.evaluate_at(expr, var, x).is_some_and(f64::is_finite)
+    }
+
+    /// 变号检验的步长，不超过到相邻候选点距离的一半
+    fn probe_step(points: &[f64], i: usize) -> f64 {
+        let x = points[i];
+        let mut h = 1e-3 * (1.0 + x.abs());
+        if i > 0 {
+            h = h.min((x - points[i - 1]) / 2.0);
+        }
+        if let Some(next) = points.get(i + 1) {
+            h = h.min((next - x) / 2.0);
+        }
+        h
+    }
+}
+
+impl Default for ExtremaAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[path = "extrema_tests.rs"]
+mod extrema_tests;
@@ -0,0 +1,72 @@
+//! # 极值与驻点分析测试
+//!
+//! 测试临界点、二阶及高阶导数判定、不可导点和拐点。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::extrema::{ExtremaAnalyzer, ExtremumKind};
+    use crate::core::{Expression, Number};
+    use crate::test_utils::parse;
+
+    fn int(value: i64) -> Expression {
+        Expression::Number(Number::integer(value))
+    }
+
+    #[test]
+    fn test_cubic_extrema_and_inflection() {
+        let analyzer = ExtremaAnalyzer::new();
+        let expr = parse("x^3 - 3*x");
+
+        assert_eq!(analyzer.critical_points(&expr, "x").unwrap(), vec![int(-1), int(1)]);
+
+        let extrema = analyzer.local_extrema(&expr, "x").unwrap();
+        assert_eq!(extrema.len(), 2);
+        assert_eq!(extrema[0].point, int(-1));
+        assert_eq!(extrema[0].kind, ExtremumKind::Maximum);
+        assert_eq!(extrema[0].value, int(2));
+        assert_eq!(extrema[1].point, int(1));
+        assert_eq!(extrema[1].kind, ExtremumKind::Minimum);
+        assert_eq!(extrema[1].value, int(-2));
+
+        assert_eq!(analyzer.inflection_points(&expr, "x").unwrap(), vec![int(0)]);
+    }
+
+    #[test]
+    fn test_higher_derivative_test() {
+        let analyzer = ExtremaAnalyzer::new();
+
+        // x^4 在 0 处二阶导为零，四阶导为正
+        let extrema = analyzer.local_extrema(&parse("x^4"), "x").unwrap();
+        assert_eq!(extrema.len(), 1);
+        assert_eq!(extrema[0].kind, ExtremumKind::Minimum);
+        assert_eq!(extrema[0].value, int(0));
+        assert!(analyzer.inflection_points(&parse("x^4"), "x").unwrap().is_empty());
+
+        // x^3 在 0 处三阶导非零，是鞍点
+        let extrema = analyzer.local_extrema(&parse("x^3"), "x").unwrap();
+        assert_eq!(extrema.len(), 1);
+        assert_eq!(extrema[0].kind, ExtremumKind::Saddle);
+    }
+
+    #[test]
+    fn test_no_critical_points_outside_domain() {
+        let analyzer = ExtremaAnalyzer::new();
+
+        // 1/x 的导数在 0 处不存在，但函数在 0 处无定义
+        assert!(analyzer.critical_points(&parse("1/x"), "x").unwrap().is_empty());
+        assert!(analyzer.inflection_points(&parse("1/x"), "x").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_rational_function_extrema() {
+        let analyzer = ExtremaAnalyzer::new();
+
+        // x + 1/x 在 x = -1 取极大值 -2，在 x = 1 取极小值 2
+        let extrema = analyzer.local_extrema(&parse("x + 1/x"), "x").unwrap();
+        let summary: Vec<_> = extrema.iter().map(|e| (e.point.clone(), e.kind, e.value.clone())).collect();
+        assert_eq!(summary, vec![
+            (int(-1), ExtremumKind::Maximum, int(-2)),
+            (int(1), ExtremumKind::Minimum, int(2)),
+        ]);
+    }
+}
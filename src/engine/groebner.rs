@@ -0,0 +1,348 @@
+//! # Gröbner 基
+//!
+//! 多元多项式在给定的变量顺序下表示为（指数向量，有理系数）的稀疏表，按单项式序从大到小排列。
+//! 用 Buchberger 算法求约化 Gröbner 基；字典序下基中排在最后的多项式只含靠后的变量，可用于方程组消元。
+//! `solve_system` 据此从最后一个变量起逐个求实根并回代，求出多项式方程组的全部实数解。
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use num_rational::BigRational;
+use num_traits::{One, Zero};
+use crate::core::{Expression, Number, BinaryOperator};
+use super::ComputeError;
+use super::calculus::CalculusEngine;
+use super::polynomial::{Polynomial, PolynomialEngine, PolynomialTerm};
+use super::simplify::Simplifier;
+use super::solver::SolverEngine;
+use crate::tr;
+
+/// 基的最大长度，超过时放弃计算
+const MAX_BASIS_SIZE: usize = 64;
+
+/// 最多约化的 S 多项式个数
+const MAX_REDUCTIONS: usize = 2000;
+
+/// 回代时判断多项式取值为零的容差
+const ZERO_TOLERANCE: f64 = 1e-9;
+
+/// 方程组的一个解：各变量的精确值与数值近似
+type Assignment = (HashMap<String, Expression>, HashMap<String, f64>);
+
+/// 单项式序，变量的先后由调用方给出的变量列表决定
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MonomialOrder {
+    /// 字典序：先比第一个变量的次数，相同再比下一个
+    #[default]
+    Lex,
+    /// 分次字典序：先比总次数，相同时按字典序
+    GrLex,
+    /// 分次反字典序：先比总次数，相同时最后一个不同的变量次数较小者为大
+    GRevLex,
+}
+
+impl MonomialOrder {
+    /// 比较两个指数向量，分量与变量列表一一对应
+    pub fn compare(self, a: &[u32], b: &[u32]) -> Ordering {
+        let total = |m: &[u32]| m.iter().map(|&e| e as u64).sum::<u64>();
+        match self {
+            MonomialOrder::Lex => a.cmp(b),
+            MonomialOrder::GrLex => total(a).cmp(&total(b)).then_with(|| a.cmp(b)),
+            MonomialOrder::GRevLex => total(a).cmp(&total(b)).then_with(|| b.iter().rev().cmp(a.iter().rev())),
+        }
+    }
+}
+
+/// 稀疏多项式，项按单项式序从大到小排列，系数非零
+#[derive(Debug, Clone, PartialEq)]
+struct SparsePoly {
+    terms: Vec<(Vec<u32>, BigRational)>,
+}
+
+impl SparsePoly {
+    fn zero() -> SparsePoly {
+        SparsePoly { terms: Vec::new() }
+    }
+
+    fn leading(&self) -> Option<&(Vec<u32>, BigRational)> {
+        self.terms.first()
+    }
+
+    /// self - c · x^shift · other，按序归并
+    fn sub_scaled(&self, c: &BigRational, shift: &[u32], other: &SparsePoly, order: MonomialOrder) -> SparsePoly {
+        let shifted = other.terms.iter().map(|(m, a)| (multiply(m, shift), a * c));
+        let mut terms = Vec::with_capacity(self.terms.len() + other.terms.len());
+        let mut left = self.terms.iter().cloned().peekable();
+        let mut right = shifted.peekable();
+        loop {
+            let ordering = match (left.peek(), right.peek()) {
+                (Some((a, _)), Some((b, _))) => order.compare(a, b),
+                (Some(_), None) => Ordering::Greater,
+                (None, Some(_)) => Ordering::Less,
+                (None, None) => break,
+            };
+            match ordering {
+                Ordering::Greater => terms.extend(left.next()),
+                Ordering::Less => terms.extend(right.next().map(|(m, a)| (m, -a))),
+                Ordering::Equal => {
+                    let (m, a) = left.next().unwrap();
+                    let (_, b) = right.next().unwrap();
+                    let value = a - b;
+                    if !value.is_zero() {
+                        terms.push((m, value));
+                    }
+                }
+            }
+        }
+        SparsePoly { terms }
+    }
+
+    /// 除以首项系数
+    fn monic(mut self) -> SparsePoly {
+        if let Some((_, lead)) = self.terms.first() {
+            let lead = lead.clone();
+            for (_, c) in &mut self.terms {
+                *c = &*c / &lead;
+            }
+        }
+        self
+    }
+}
+
+fn multiply(a: &[u32], b: &[u32]) -> Vec<u32> {
+    a.iter().zip(b).map(|(x, y)| x + y).collect()
+}
+
+fn lcm(a: &[u32], b: &[u32]) -> Vec<u32> {
+    a.iter().zip(b).map(|(x, y)| *x.max(y)).collect()
+}
+
+/// `b` 整除 `a` 时返回商
+fn quotient(a: &[u32], b: &[u32]) -> Option<Vec<u32>> {
+    a.iter().zip(b).map(|(x, y)| x.checked_sub(*y)).collect()
+}
+
+/// `f` 对 `basis` 的完全约化余式
+fn reduce(f: &SparsePoly, basis: &[SparsePoly], order: MonomialOrder) -> SparsePoly {
+    let mut p = f.clone();
+    let mut remainder = Vec::new();
+    while let Some((m, c)) = p.leading().cloned() {
+        let divisor = basis.iter().find_map(|g| {
+            let (lm, lc) = g.leading()?;
+            quotient(&m, lm).map(|shift| (g, shift, &c / lc))
+        });
+        match divisor {
+            Some((g, shift, factor)) => p = p.sub_scaled(&factor, &shift, g, order),
+            None => {
+                p.terms.remove(0);
+                remainder.push((m, c));
+            }
+        }
+    }
+    SparsePoly { terms: remainder }
+}
+
+/// `f` 与 `g` 的 S 多项式
+fn s_polynomial(f: &SparsePoly, g: &SparsePoly, order: MonomialOrder) -> SparsePoly {
+    let (fm, fc) = f.leading().unwrap();
+    let (gm, gc) = g.leading().unwrap();
+    let l = lcm(fm, gm);
+    SparsePoly::zero()
+        .sub_scaled(&-(BigRational::one() / fc), &quotient(&l, fm).unwrap(), f, order)
+        .sub_scaled(&(BigRational::one() / gc), &quotient(&l, gm).unwrap(), g, order)
+}
+
+/// 多项式组在变量列表 `vars` 与单项式序 `order` 下的约化 Gröbner 基，各多项式首一，按首项从大到小排列
+///
+/// 系数须为有理数，且只能含 `vars` 中的变量；生成的基过大时报不支持，避免计算量失控。
+pub fn groebner_basis(polys: &[Expression], vars: &[&str], order: MonomialOrder) -> Result<Vec<Expression>, ComputeError> {
+    let mut basis: Vec<SparsePoly> = Vec::new();
+    for expr in polys {
+        let poly = to_sparse(expr, vars, order)?;
+        if !poly.terms.is_empty() {
+            basis.push(poly.monic());
+        }
+    }
+
+    // Buchberger 算法；首项互素的一对可以跳过（Buchberger 第一准则）
+    let mut pairs: Vec<(usize, usize)> = (0..basis.len()).flat_map(|j| (0..j).map(move |i| (i, j))).collect();
+    let mut reductions = 0;
+    while let Some((i, j)) = pairs.pop() {
+        let (fm, gm) = (&basis[i].terms[0].0, &basis[j].terms[0].0);
+        if fm.iter().zip(gm).all(|(a, b)| *a == 0 || *b == 0) {
+            continue;
+        }
+        reductions += 1;
+        if reductions > MAX_REDUCTIONS {
+            return Err(ComputeError::unsupported_operation(tr!("groebner.too_many_reductions", limit = MAX_REDUCTIONS)));
+        }
+        let remainder = reduce(&s_polynomial(&basis[i], &basis[j], order), &basis, order);
+        if remainder.terms.is_empty() {
+            continue;
+        }
+        if basis.len() >= MAX_BASIS_SIZE {
+            return Err(ComputeError::unsupported_operation(tr!("groebner.basis_too_large", limit = MAX_BASIS_SIZE)));
+        }
+        let k = basis.len();
+        basis.push(remainder.monic());
+        pairs.extend((0..k).map(|i| (i, k)));
+    }
+
+    // 极小化：去掉首项能被其他首项整除的多项式，首项相同的只留一个
+    let mut minimal: Vec<SparsePoly> = Vec::new();
+    for (k, g) in basis.iter().enumerate() {
+        let lm = &g.terms[0].0;
+        let redundant = basis.iter().enumerate().any(|(l, h)| {
+            l != k && quotient(lm, &h.terms[0].0).is_some() && (h.terms[0].0 != *lm || l < k)
+        });
+        if !redundant {
+            minimal.push(g.clone());
+        }
+    }
+
+    // 约化：每个多项式对其余多项式取余，首项不变
+    let mut reduced: Vec<SparsePoly> = (0..minimal.len()).map(|k| {
+        let others: Vec<SparsePoly> = minimal.iter().enumerate().filter(|(l, _)| *l != k).map(|(_, g)| g.clone()).collect();
+        reduce(&minimal[k], &others, order).monic()
+    }).collect();
+    reduced.sort_by(|f, g| order.compare(&g.terms[0].0, &f.terms[0].0));
+
+    reduced.iter().map(|poly| to_expression(poly, vars)).collect()
+}
+
+/// 表达式转为稀疏多项式
+fn to_sparse(expr: &Expression, vars: &[&str], order: MonomialOrder) -> Result<SparsePoly, ComputeError> {
+    let poly = PolynomialEngine::new().expression_to_polynomial(expr)
+        .map_err(|_| ComputeError::domain_error(tr!("groebner.not_polynomial", expression = expr)))?;
+    let mut terms: Vec<(Vec<u32>, BigRational)> = Vec::new();
+    for term in poly.terms {
+        let coefficient = term.coefficient.to_rational().ok_or_else(|| {
+            ComputeError::unsupported_operation(tr!("groebner.rational_coefficients", expression = expr, coefficient = term.coefficient))
+        })?;
+        let mut monomial = vec![0; vars.len()];
+        for (name, power) in &term.variables {
+            let index = vars.iter().position(|var| var == name).ok_or_else(|| {
+                ComputeError::domain_error(tr!("groebner.extra_variable", expression = expr, variable = name))
+            })?;
+            monomial[index] = u32::try_from(*power)
+                .map_err(|_| ComputeError::domain_error(tr!("groebner.not_polynomial", expression = expr)))?;
+        }
+        match terms.iter_mut().find(|(m, _)| *m == monomial) {
+            Some((_, c)) => *c = &*c + coefficient,
+            None => terms.push((monomial, coefficient)),
+        }
+    }
+    terms.retain(|(_, c)| !c.is_zero());
+    terms.sort_by(|(a, _), (b, _)| order.compare(b, a));
+    Ok(SparsePoly { terms })
+}
+
+/// 稀疏多项式转为表达式并化简
+fn to_expression(poly: &SparsePoly, vars: &[&str]) -> Result<Expression, ComputeError> {
+    let terms = poly.terms.iter().map(|(monomial, c)| {
+        let variables = vars.iter().zip(monomial)
+            .filter(|(_, power)| **power > 0)
+            .map(|(var, power)| (var.to_string(), *power as i32))
+            .collect();
+        PolynomialTerm::new(SolverEngine::rational_to_number(c.clone()), variables)
+    }).collect();
+    Simplifier::new().simplify(&Polynomial { terms }.to_expression())
+}
+
+/// 求多项式方程组的全部实数解，方程可以写成 `lhs = rhs` 或表达式（视为等于 0）
+///
+/// 先求字典序 Gröbner 基，再从最后一个变量起求实根并逐个回代。
+/// 无解时返回空列表；解有无穷多个（如方程个数不足）时报不支持。
+pub fn solve_system(equations: &[Expression], vars: &[&str]) -> Result<Vec<HashMap<String, Expression>>, ComputeError> {
+    let polys: Vec<Expression> = equations.iter().map(|equation| match equation {
+        Expression::BinaryOp { op: BinaryOperator::Equal, left, right } => {
+            Expression::subtract(left.as_ref().clone(), right.as_ref().clone())
+        }
+        _ => equation.clone(),
+    }).collect();
+    let basis = groebner_basis(&polys, vars, MonomialOrder::Lex)?;
+
+    let mut solutions = Vec::new();
+    back_substitute(&basis, vars, vars.len(), &mut HashMap::new(), &mut HashMap::new(), &mut solutions)?;
+
+    // 回代使用数值判断，最后在原方程组上再验证一次
+    let calculus = CalculusEngine::new();
+    solutions.retain(|(_, approx)| polys.iter().all(|p| {
+        calculus.numerical_evaluate(p, approx).is_ok_and(|v| v.abs() <= ZERO_TOLERANCE)
+    }));
+    Ok(solutions.into_iter().map(|(exact, _)| exact).collect())
+}
+
+/// 已求出 `vars[level..]` 时求 `vars[level - 1]`，解完全部变量时记入 `solutions`
+fn back_substitute(
+    basis: &[Expression],
+    vars: &[&str],
+    level: usize,
+    exact: &mut HashMap<String, Expression>,
+    approx: &mut HashMap<String, f64>,
+    solutions: &mut Vec<Assignment>,
+) -> Result<(), ComputeError> {
+    if level == 0 {
+        solutions.push((exact.clone(), approx.clone()));
+        return Ok(());
+    }
+    let var = vars[level - 1];
+    let calculus = CalculusEngine::new();
+    let mut simplifier = Simplifier::new();
+
+    // 代入已求出的变量后只剩 var 的多项式
+    let mut univariate = Vec::new();
+    for poly in basis {
+        if poly.get_variables().iter().any(|v| vars[..level - 1].contains(&v.as_str())) {
+            continue;
+        }
+        let reduced = simplifier.simplify(&poly.substitute(exact))?;
+        if !reduced.get_variables().iter().any(|v| v == var) {
+            // 常数非零说明这一分支无解
+            if calculus.numerical_evaluate(&reduced, approx).map_or(true, |v| v.abs() > ZERO_TOLERANCE) {
+                return Ok(());
+            }
+            continue;
+        }
+        univariate.push(reduced);
+    }
+    let Some(first) = univariate.first() else {
+        return Err(ComputeError::unsupported_operation(tr!("groebner.infinite_solutions", variable = var)));
+    };
+
+    // 代入无理数后系数不再是有理数，这时只能解一次方程
+    let roots = match SolverEngine::new().real_roots(first, var) {
+        Ok(roots) => roots,
+        Err(e) => linear_root(first, var, approx).ok_or(e)?,
+    };
+    for (root, value) in roots {
+        approx.insert(var.to_string(), value);
+        let common = univariate[1..].iter().all(|poly| {
+            calculus.numerical_evaluate(poly, approx).is_ok_and(|v| v.abs() <= ZERO_TOLERANCE * (1.0 + value.abs()))
+        });
+        if common {
+            exact.insert(var.to_string(), root);
+            back_substitute(basis, vars, level - 1, exact, approx, solutions)?;
+            exact.remove(var);
+        }
+        approx.remove(var);
+    }
+    Ok(())
+}
+
+/// 关于 `var` 的一次方程 `c1·var + c0 = 0` 的根 `-c0/c1` 及其数值；不是一次方程时返回 `None`
+fn linear_root(poly: &Expression, var: &str, approx: &HashMap<String, f64>) -> Option<Vec<(Expression, f64)>> {
+    let calculus = CalculusEngine::new();
+    let mut simplifier = Simplifier::new();
+    let c1 = simplifier.simplify(&calculus.differentiate(poly, var).ok()?).ok()?;
+    if c1.get_variables().iter().any(|v| v == var) {
+        return None;
+    }
+    let c0 = simplifier.simplify(&poly.substitute(&HashMap::from([(var.to_string(), Expression::Number(Number::zero()))]))).ok()?;
+    let root = simplifier.simplify(&Expression::divide(Expression::negate(c0), c1)).ok()?;
+    let value = calculus.numerical_evaluate(&root, approx).ok().filter(|v| v.is_finite())?;
+    Some(vec![(root, value)])
+}
+
+#[cfg(test)]
+#[path = "groebner_tests.rs"]
+mod groebner_tests;
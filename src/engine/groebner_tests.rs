@@ -0,0 +1,88 @@
+//! # Gröbner 基测试
+//!
+//! 测试单项式序的比较、约化 Gröbner 基的消元结果、不同单项式序、输入检查与方程组求解。
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Ordering;
+    use crate::engine::groebner::{groebner_basis, solve_system, MonomialOrder};
+    use crate::engine::simplify::Simplifier;
+    use crate::engine::ComputeError;
+    use crate::core::Expression;
+    use crate::test_utils::parse;
+
+    fn simplified(input: &str) -> Expression {
+        Simplifier::new().simplify(&parse(input)).unwrap()
+    }
+
+    fn basis(polys: &[&str], vars: &[&str], order: MonomialOrder) -> Result<Vec<Expression>, ComputeError> {
+        groebner_basis(&polys.iter().map(|p| parse(p)).collect::<Vec<_>>(), vars, order)
+    }
+
+    #[test]
+    fn test_monomial_orders() {
+        // x·y² 与 x²：字典序看 x 的次数，分次序先看总次数
+        assert_eq!(MonomialOrder::Lex.compare(&[1, 2], &[2, 0]), Ordering::Less);
+        assert_eq!(MonomialOrder::GrLex.compare(&[1, 2], &[2, 0]), Ordering::Greater);
+        // x²z 与 xy²：总次数相同，分次字典序比 x，分次反字典序比 z
+        assert_eq!(MonomialOrder::GrLex.compare(&[2, 0, 1], &[1, 2, 0]), Ordering::Greater);
+        assert_eq!(MonomialOrder::GRevLex.compare(&[2, 0, 1], &[1, 2, 0]), Ordering::Less);
+        for order in [MonomialOrder::Lex, MonomialOrder::GrLex, MonomialOrder::GRevLex] {
+            assert_eq!(order.compare(&[1, 1], &[1, 1]), Ordering::Equal);
+        }
+    }
+
+    #[test]
+    fn test_circle_and_line() {
+        // x² + y² = 1 与 x = y：字典序 x > y 下消去 x，得到只含 y 的 y² - 1/2
+        let result = basis(&["x^2 + y^2 - 1", "x - y"], &["x", "y"], MonomialOrder::Lex).unwrap();
+        assert_eq!(result, vec![simplified("x - y"), simplified("y^2 - 1/2")]);
+        let last = result.last().unwrap().to_string();
+        assert!(!last.contains('x'), "{}", last);
+    }
+
+    #[test]
+    fn test_elimination() {
+        // x + y + z = 6, x - y = 0, y·z = 4：末尾的多项式只含 z
+        let result = basis(&["x + y + z - 6", "x - y", "y*z - 4"], &["x", "y", "z"], MonomialOrder::Lex).unwrap();
+        assert_eq!(result.last().unwrap(), &simplified("z^2 - 6*z + 8"));
+        // 无公共零点时基为 {1}
+        assert_eq!(basis(&["x*y - 1", "x"], &["x", "y"], MonomialOrder::Lex).unwrap(), vec![parse("1")]);
+        assert_eq!(basis(&["0"], &["x"], MonomialOrder::Lex).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_graded_orders() {
+        // 同一理想在不同单项式序下生成相同的零点集，分次序下的基不必含单变量多项式
+        for order in [MonomialOrder::GrLex, MonomialOrder::GRevLex] {
+            let result = basis(&["x^2 + y^2 - 1", "x - y"], &["x", "y"], order).unwrap();
+            assert_eq!(result, vec![simplified("y^2 - 1/2"), simplified("x - y")]);
+        }
+        let result = basis(&["x^3 - 2*x*y", "x^2*y - 2*y^2 + x"], &["x", "y"], MonomialOrder::GrLex).unwrap();
+        assert_eq!(result, vec![simplified("x^2"), simplified("x*y"), simplified("y^2 - 1/2*x")]);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert!(matches!(basis(&["sin(x) + y"], &["x", "y"], MonomialOrder::Lex), Err(ComputeError::DomainError { .. })));
+        assert!(matches!(basis(&["x + a"], &["x"], MonomialOrder::Lex), Err(ComputeError::DomainError { .. })));
+    }
+
+    #[test]
+    fn test_solve_system() {
+        let solve = |equations: &[&str], vars: &[&str]| {
+            solve_system(&equations.iter().map(|e| parse(e)).collect::<Vec<_>>(), vars)
+        };
+        // x² + y² = 25 与 x - y = 1
+        let solutions = solve(&["x^2 + y^2 == 25", "x - y - 1"], &["x", "y"]).unwrap();
+        let mut pairs: Vec<(String, String)> = solutions.iter().map(|s| (s["x"].to_string(), s["y"].to_string())).collect();
+        pairs.sort();
+        assert_eq!(pairs, vec![("-3".to_string(), "-4".to_string()), ("4".to_string(), "3".to_string())]);
+
+        // 圆与直线不相交、方程矛盾时无解
+        assert_eq!(solve(&["x^2 + y^2 - 1", "x + y - 5"], &["x", "y"]).unwrap().len(), 0);
+        assert_eq!(solve(&["x*y - 1", "x"], &["x", "y"]).unwrap().len(), 0);
+        // 方程个数不足，解有无穷多个
+        assert!(matches!(solve(&["x + y - 1"], &["x", "y"]), Err(ComputeError::UnsupportedOperation { .. })));
+    }
+}
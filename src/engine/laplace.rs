@@ -0,0 +1,530 @@
+//! # Laplace 变换
+//!
+//! 正变换按线性性拆开后逐项查表：常数、tⁿ、e^{at}、sin/cos/sinh/cosh(ωt)，
+//! 并应用位移定理 e^{at}f(t) ↔ F(s - a)、tⁿf(t) ↔ (-1)ⁿF⁽ⁿ⁾(s) 与导数规则
+//! `diff(f, t)` ↔ sF(s) - f(0)。逆变换先做部分分式分解，再对每一项查表。
+//! 不在表内的部分保留为符号形式 `laplace(f, t, s)` 或 `inverse_laplace(F, s, t)`。
+
+use std::collections::HashMap;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator};
+use super::ComputeError;
+use super::calculus::CalculusEngine;
+use super::equivalence::EquivalenceChecker;
+use super::partial_fractions::{PartialFractionEngine, PartialFraction, Factor};
+use super::simplify::Simplifier;
+use super::solver::SolverEngine;
+use crate::tr;
+
+/// Laplace 正变换的结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct LaplaceTransform {
+    /// 像函数，不在变换表内的部分为符号形式 `laplace(f, t, s)`
+    pub transform: Expression,
+    /// 收敛域 Re(s) > σ 中的 σ，无法确定时为 None
+    pub abscissa: Option<Expression>,
+    /// 是否完全求出（不含符号形式）
+    pub evaluated: bool,
+}
+
+impl LaplaceTransform {
+    /// 收敛域的文本形式，如 `Re(s) > -2`
+    pub fn region(&self, s: &str) -> Option<String> {
+        self.abscissa.as_ref().map(|sigma| format!("Re({}) > {}", s, sigma))
+    }
+
+    fn symbolic(expr: &Expression, t: &str, s: &str) -> Self {
+        Self {
+            transform: Expression::function("laplace", vec![expr.clone(), Expression::variable(t), Expression::variable(s)]),
+            abscissa: None,
+            evaluated: false,
+        }
+    }
+
+    fn map(self, f: impl FnOnce(Expression) -> Expression) -> Self {
+        Self { transform: f(self.transform), ..self }
+    }
+}
+
+/// Laplace 变换引擎
+pub struct LaplaceEngine {
+    calculus: CalculusEngine,
+    equivalence: EquivalenceChecker,
+    partial_fractions: PartialFractionEngine,
+}
+
+impl LaplaceEngine {
+    /// 创建新的 Laplace 变换引擎
+    pub fn new() -> Self {
+        Self {
+            calculus: CalculusEngine::new(),
+            equivalence: EquivalenceChecker::new(),
+            partial_fractions: PartialFractionEngine::new(),
+        }
+    }
+
+    /// 计算 `expr` 关于 `t` 的 Laplace 变换，像函数的变量为 `s`
+    pub fn laplace(&self, expr: &Expression, t: &str, s: &str) -> Result<LaplaceTransform, ComputeError> {
+        if Self::depends_on(expr, s) {
+            return Err(ComputeError::domain_error(tr!("laplace.transform_variable", variable = s)));
+        }
+        let mut simplifier = Simplifier::new();
+        // 每一项在位移前已化简；整体再化简会把 (s + a)ⁿ 展开，这里不再处理
+        self.transform_linear(&mut simplifier, expr, t, s)
+    }
+
+    /// 计算 `expr` 关于 `s` 的 Laplace 逆变换，原函数的变量为 `t`
+    pub fn inverse_laplace(&self, expr: &Expression, s: &str, t: &str) -> Result<Expression, ComputeError> {
+        if Self::depends_on(expr, t) {
+            return Err(ComputeError::domain_error(tr!("laplace.original_variable", variable = t)));
+        }
+        let mut simplifier = Simplifier::new();
+        let result = self.inverse_linear(expr, s, t);
+        Ok(simplifier.simplify(&result).unwrap_or(result))
+    }
+
+    /// 按线性性拆开正变换
+    fn transform_linear(&self, simplifier: &mut Simplifier, expr: &Expression, t: &str, s: &str) -> Result<LaplaceTransform, ComputeError> {
+        match expr {
+            Expression::BinaryOp { op: op @ (BinaryOperator::Add | BinaryOperator::Subtract), left, right } => {
+                let left = self.transform_linear(simplifier, left, t, s)?;
+                let right = self.transform_linear(simplifier, right, t, s)?;
+                let transform = match (op, Self::negated(&right.transform)) {
+                    (BinaryOperator::Add, Some(magnitude)) => Expression::subtract(left.transform, magnitude),
+                    (BinaryOperator::Add, None) => Expression::add(left.transform, right.transform),
+                    (_, Some(magnitude)) => Expression::add(left.transform, magnitude),
+                    (_, None) => Expression::subtract(left.transform, right.transform),
+                };
+                Ok(LaplaceTransform {
+                    transform,
+                    abscissa: Self::max_abscissa(left.abscissa, right.abscissa),
+                    evaluated: left.evaluated && right.evaluated,
+                })
+            }
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+                Ok(self.transform_linear(simplifier, operand, t, s)?.map(Expression::negate))
+            }
+            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } if !Self::depends_on(left, t) => {
+                let factor = left.as_ref().clone();
+                Ok(self.transform_linear(simplifier, right, t, s)?.map(|f| Self::scale(factor, f)))
+            }
+            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } if !Self::depends_on(right, t) => {
+                let factor = right.as_ref().clone();
+                Ok(self.transform_linear(simplifier, left, t, s)?.map(|f| Self::scale(factor, f)))
+            }
+            Expression::BinaryOp { op: BinaryOperator::Divide, left, right } if !Self::depends_on(right, t) => {
+                let result = self.transform_linear(simplifier, left, t, s)?;
+                match right.as_ref() {
+                    Expression::Number(divisor) if !divisor.is_zero() => {
+                        let factor = Expression::Number(Number::one().divide(divisor)?);
+                        Ok(result.map(|f| Self::scale(factor, f)))
+                    }
+                    divisor => Ok(result.map(|f| Expression::divide(f, divisor.clone()))),
+                }
+            }
+            _ => Ok(self.transform_product(simplifier, expr, t, s)?
+                .unwrap_or_else(|| LaplaceTransform::symbolic(expr, t, s))),
+        }
+    }
+
+    /// 变换单个乘积项：常数因子、tⁿ、e^{at} 与至多一个表内函数的乘积
+    fn transform_product(&self, simplifier: &mut Simplifier, expr: &Expression, t: &str, s: &str) -> Result<Option<LaplaceTransform>, ComputeError> {
+        let mut factors = Vec::new();
+        Self::collect_factors(expr, t, &mut factors);
+
+        let mut constants = Vec::new();
+        let mut rates = Vec::new();
+        let mut t_power = 0u32;
+        let mut rest = Vec::new();
+        for factor in factors {
+            if !Self::depends_on(&factor, t) {
+                constants.push(factor);
+            } else if let Some(n) = Self::power_of(&factor, t) {
+                t_power += n;
+            } else if let Some((rate, offset)) = self.exponential_rate(simplifier, &factor, t) {
+                rates.push(rate);
+                if !Self::is_zero(&offset) {
+                    constants.push(Expression::function("exp", vec![offset]));
+                }
+            } else {
+                rest.push(factor);
+            }
+        }
+
+        let mut result = match rest.as_slice() {
+            // L{tⁿ} = n!/s^{n+1}
+            [] => {
+                let transform = Expression::divide(
+                    Expression::Number(Number::Integer(Self::factorial(t_power))),
+                    Self::power_expr(Expression::variable(s), t_power + 1),
+                );
+                t_power = 0;
+                LaplaceTransform { transform, abscissa: Some(Expression::Number(Number::zero())), evaluated: true }
+            }
+            [single] => match self.lookup(simplifier, single, t, s)? {
+                Some(result) => result,
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+
+        // tⁿf(t) ↔ (-1)ⁿ F⁽ⁿ⁾(s)
+        for _ in 0..t_power {
+            if !result.evaluated {
+                return Ok(None);
+            }
+            let Ok(derivative) = self.calculus.differentiate(&result.transform, s) else {
+                return Ok(None);
+            };
+            let derivative = Expression::negate(derivative);
+            result.transform = simplifier.simplify(&derivative).unwrap_or(derivative);
+        }
+
+        result.transform = simplifier.simplify(&result.transform).unwrap_or(result.transform);
+
+        // e^{at}f(t) ↔ F(s - a)
+        if !rates.is_empty() {
+            let rate = rates.into_iter().reduce(Expression::add).unwrap_or_else(|| Expression::Number(Number::zero()));
+            let rate = simplifier.simplify(&rate).unwrap_or(rate);
+            if !Self::is_zero(&rate) {
+                let mut bindings = HashMap::new();
+                bindings.insert(s.to_string(), Self::shifted(s, &rate));
+                result.transform = result.transform.substitute(&bindings);
+                result.abscissa = result.abscissa.map(|sigma| {
+                    let shifted = Expression::add(sigma, rate.clone());
+                    simplifier.simplify(&shifted).unwrap_or(shifted)
+                });
+            }
+        }
+
+        let constant = constants.into_iter().reduce(Expression::multiply)
+            .map(|constant| simplifier.simplify(&constant).unwrap_or(constant));
+        Ok(Some(match constant {
+            Some(constant) if constant != Expression::Number(Number::one()) => {
+                result.map(|f| Self::scale(constant, f))
+            }
+            _ => result,
+        }))
+    }
+
+    /// 查变换表
+    fn lookup(&self, simplifier: &mut Simplifier, expr: &Expression, t: &str, s: &str) -> Result<Option<LaplaceTransform>, ComputeError> {
+        let Expression::Function { name, args } = expr else {
+            return Ok(None);
+        };
+        let s_var = Expression::variable(s);
+        let zero = || Some(Expression::Number(Number::zero()));
+
+        match (name.as_str(), args.as_slice()) {
+            ("sin" | "cos" | "sinh" | "cosh", [arg]) => {
+                let Some((omega, offset)) = self.linear_coefficients(simplifier, arg, t) else {
+                    return Ok(None);
+                };
+                if !Self::is_zero(&offset) {
+                    return Ok(None);
+                }
+                let omega_squared = Expression::power(omega.clone(), Expression::Number(Number::integer(2)));
+                let s_squared = Expression::power(s_var.clone(), Expression::Number(Number::integer(2)));
+                let (denominator, abscissa) = if name.starts_with("sin") && name.len() == 3 || name == "cos" {
+                    (Expression::add(s_squared, omega_squared), zero())
+                } else {
+                    let abscissa = match &omega {
+                        Expression::Number(n) => Some(Expression::Number(n.abs()?)),
+                        _ => None,
+                    };
+                    (Expression::subtract(s_squared, omega_squared), abscissa)
+                };
+                let numerator = if name.starts_with("sin") { omega } else { s_var };
+                Ok(Some(LaplaceTransform {
+                    transform: Expression::divide(numerator, denominator),
+                    abscissa,
+                    evaluated: true,
+                }))
+            }
+            // L{f'(t)} = sF(s) - f(0)
+            ("diff", [inner, Expression::Variable(var)]) if var == t => {
+                let transform = self.transform_linear(simplifier, inner, t, s)?;
+                let mut bindings = HashMap::new();
+                bindings.insert(t.to_string(), Expression::Number(Number::zero()));
+                let initial = inner.substitute(&bindings);
+                let initial = simplifier.simplify(&initial).unwrap_or(initial);
+                Ok(Some(transform.map(|f| Expression::subtract(Expression::multiply(s_var, f), initial))))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// 把乘积拆成因子，除以与 t 无关的量记为乘以其倒数
+    fn collect_factors(expr: &Expression, t: &str, factors: &mut Vec<Expression>) {
+        match expr {
+            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
+                Self::collect_factors(left, t, factors);
+                Self::collect_factors(right, t, factors);
+            }
+            Expression::BinaryOp { op: BinaryOperator::Divide, left, right } if !Self::depends_on(right, t) => {
+                Self::collect_factors(left, t, factors);
+                factors.push(Expression::divide(Expression::Number(Number::one()), right.as_ref().clone()));
+            }
+            _ => factors.push(expr.clone()),
+        }
+    }
+
+    /// `expr` 为 t 或 tⁿ（n 为正整数）时返回 n
+    fn power_of(expr: &Expression, t: &str) -> Option<u32> {
+        match expr {
+            Expression::Variable(name) if name == t => Some(1),
+            Expression::BinaryOp { op: BinaryOperator::Power, left, right } => match (left.as_ref(), right.as_ref()) {
+                (Expression::Variable(name), Expression::Number(n)) if name == t => {
+                    n.to_i64().filter(|n| *n > 0).and_then(|n| u32::try_from(n).ok())
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// `expr` 为 e^{at + b} 时返回 (a, b)
+    fn exponential_rate(&self, simplifier: &mut Simplifier, expr: &Expression, t: &str) -> Option<(Expression, Expression)> {
+        match expr {
+            Expression::Function { name, args } if name == "exp" && args.len() == 1 => {
+                self.linear_coefficients(simplifier, &args[0], t)
+            }
+            _ => None,
+        }
+    }
+
+    /// `expr` 关于 t 是线性函数 at + b 时返回 (a, b)
+    fn linear_coefficients(&self, simplifier: &mut Simplifier, expr: &Expression, t: &str) -> Option<(Expression, Expression)> {
+        let slope = self.calculus.differentiate(expr, t).ok()?;
+        let slope = simplifier.simplify(&slope).ok()?;
+        if Self::depends_on(&slope, t) {
+            return None;
+        }
+        let mut bindings = HashMap::new();
+        bindings.insert(t.to_string(), Expression::Number(Number::zero()));
+        let offset = simplifier.simplify(&expr.substitute(&bindings)).ok()?;
+
+        // 导数为常数不代表线性（如 floor(t)），需要再核对一次
+        let linear = Expression::add(Expression::multiply(slope.clone(), Expression::variable(t)), offset.clone());
+        let equivalent = self.equivalence.are_equivalent(expr, &linear).map(|e| e.equivalent).unwrap_or(false);
+        equivalent.then_some((slope, offset))
+    }
+
+    /// 按线性性拆开逆变换
+    fn inverse_linear(&self, expr: &Expression, s: &str, t: &str) -> Expression {
+        match expr {
+            Expression::BinaryOp { op: op @ (BinaryOperator::Add | BinaryOperator::Subtract), left, right } => {
+                let left = self.inverse_linear(left, s, t);
+                let right = self.inverse_linear(right, s, t);
+                if *op == BinaryOperator::Add {
+                    Expression::add(left, right)
+                } else {
+                    Expression::subtract(left, right)
+                }
+            }
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+                Expression::negate(self.inverse_linear(operand, s, t))
+            }
+            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } if !Self::depends_on(left, s) && !left.is_constant() => {
+                Expression::multiply(left.as_ref().clone(), self.inverse_linear(right, s, t))
+            }
+            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } if !Self::depends_on(right, s) && !right.is_constant() => {
+                Expression::multiply(right.as_ref().clone(), self.inverse_linear(left, s, t))
+            }
+            Expression::BinaryOp { op: BinaryOperator::Divide, left, right } if !Self::depends_on(left, s) && !left.is_constant() => {
+                let reciprocal = Expression::divide(Expression::Number(Number::one()), right.as_ref().clone());
+                Expression::multiply(left.as_ref().clone(), self.inverse_linear(&reciprocal, s, t))
+            }
+            _ => self.inverse_rational(expr, s, t).unwrap_or_else(|| {
+                Expression::function("inverse_laplace", vec![expr.clone(), Expression::variable(s), Expression::variable(t)])
+            }),
+        }
+    }
+
+    /// 有理函数的逆变换：部分分式分解后逐项查表；含多项式部分（冲激函数）时返回 None
+    fn inverse_rational(&self, expr: &Expression, s: &str, t: &str) -> Option<Expression> {
+        let decomposition = self.partial_fractions.decompose(expr, s).ok()?;
+        if decomposition.polynomial.iter().any(|c| !c.is_zero()) {
+            return None;
+        }
+        let terms = decomposition.fractions.iter()
+            .map(|fraction| Self::inverse_fraction(fraction, t))
+            .collect::<Option<Vec<_>>>()?;
+        Some(Self::sum(terms))
+    }
+
+    /// 单项部分分式的逆变换
+    fn inverse_fraction(fraction: &PartialFraction, t: &str) -> Option<Expression> {
+        let t_var = Expression::variable(t);
+        let power = fraction.power;
+        match &fraction.factor {
+            // A/(s - r)^k ↔ A·t^{k-1}·e^{rt}/(k-1)!
+            Factor::Linear(root) => {
+                let coefficient = &fraction.constant / BigRational::from(Self::factorial(power as u32 - 1));
+                let mut parts = Vec::new();
+                if power > 1 {
+                    parts.push(Self::power_expr(t_var.clone(), power as u32 - 1));
+                }
+                if !root.is_zero() {
+                    parts.push(Expression::function("exp", vec![Self::scaled(root, t_var)]));
+                }
+                let body = parts.into_iter().reduce(Expression::multiply).unwrap_or_else(|| Expression::Number(Number::one()));
+                Some(Self::scaled(&coefficient, body))
+            }
+            // (Bs + C)/((s + α)² ± ω²)^k，分子改写为 B(s + α) + (C - Bα)
+            Factor::Quadratic(p, q) => {
+                let alpha = p / BigRational::from(BigInt::from(2));
+                let omega_squared = q - &alpha * &alpha;
+                let hyperbolic = omega_squared.is_negative();
+                let omega = Self::sqrt_rational(&omega_squared.abs());
+                let b = fraction.linear.clone();
+                let c = &fraction.constant - &b * &alpha;
+                let omega_t = Expression::multiply(omega.clone(), t_var.clone());
+                let (sin, cos) = if hyperbolic { ("sinh", "cosh") } else { ("sin", "cos") };
+                let sin_term = Expression::function(sin, vec![omega_t.clone()]);
+                let cos_term = Expression::function(cos, vec![omega_t.clone()]);
+
+                let body = match power {
+                    1 => Expression::add(
+                        Self::scaled(&b, cos_term),
+                        Expression::multiply(Expression::divide(Self::rational(&c), omega.clone()), sin_term),
+                    ),
+                    2 if !hyperbolic => {
+                        // L⁻¹{s/(s²+ω²)²} = t·sin(ωt)/(2ω)，L⁻¹{1/(s²+ω²)²} = (sin(ωt) - ωt·cos(ωt))/(2ω³)
+                        let two = || Expression::Number(Number::integer(2));
+                        let first = Expression::divide(
+                            Expression::multiply(Self::scaled(&b, t_var.clone()), sin_term.clone()),
+                            Expression::multiply(two(), omega.clone()),
+                        );
+                        let second = Expression::divide(
+                            Expression::multiply(Self::rational(&c), Expression::subtract(sin_term, Expression::multiply(omega_t, cos_term))),
+                            Expression::multiply(two(), Self::power_expr(omega, 3)),
+                        );
+                        Expression::add(first, second)
+                    }
+                    _ => return None,
+                };
+
+                if alpha.is_zero() {
+                    Some(body)
+                } else {
+                    Some(Expression::multiply(Expression::function("exp", vec![Self::scaled(&-alpha, t_var)]), body))
+                }
+            }
+        }
+    }
+
+    /// 收敛横坐标取较大者，无法比较时为 None
+    fn max_abscissa(a: Option<Expression>, b: Option<Expression>) -> Option<Expression> {
+        match (a?, b?) {
+            (a, b) if a == b => Some(a),
+            (Expression::Number(x), Expression::Number(y)) => {
+                let (xf, yf) = (x.to_f64()?, y.to_f64()?);
+                Some(Expression::Number(if xf >= yf { x } else { y }))
+            }
+            _ => None,
+        }
+    }
+
+    /// 首项系数为负数时返回相反数，用于把 `a + -b` 写成 `a - b`
+    fn negated(expr: &Expression) -> Option<Expression> {
+        match expr {
+            Expression::Number(n) if n.is_negative() => Some(Expression::Number(-n.clone())),
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => Some(operand.as_ref().clone()),
+            Expression::BinaryOp { op: op @ (BinaryOperator::Multiply | BinaryOperator::Divide), left, right } => {
+                let magnitude = Self::negated(left)?;
+                Some(if magnitude == Expression::Number(Number::one()) && *op == BinaryOperator::Multiply {
+                    right.as_ref().clone()
+                } else {
+                    Expression::BinaryOp { op: op.clone(), left: Box::new(magnitude), right: right.clone() }
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// constant·F，F 为 a/g 时把常数并入分子
+    fn scale(constant: Expression, transform: Expression) -> Expression {
+        match (constant, transform) {
+            (Expression::Number(c), Expression::BinaryOp { op: BinaryOperator::Divide, left, right }) => match *left {
+                Expression::Number(a) => Expression::divide(Expression::Number(&c * &a), *right),
+                left => Expression::multiply(Expression::Number(c), Expression::divide(left, *right)),
+            },
+            (constant, Expression::BinaryOp { op: BinaryOperator::Divide, left, right }) if matches!(left.as_ref(), Expression::Number(n) if n.is_one()) => {
+                Expression::divide(constant, *right)
+            }
+            (constant, transform) => Expression::multiply(constant, transform),
+        }
+    }
+
+    /// s - a，a 为负数时写作 s + |a|
+    fn shifted(s: &str, a: &Expression) -> Expression {
+        match a {
+            Expression::Number(n) if n.is_negative() => Expression::add(Expression::variable(s), Expression::Number(-n.clone())),
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => Expression::add(Expression::variable(s), operand.as_ref().clone()),
+            _ => Expression::subtract(Expression::variable(s), a.clone()),
+        }
+    }
+
+    /// 有理数的平方根，尽量提出平方因子
+    fn sqrt_rational(value: &BigRational) -> Expression {
+        let (outside, inside) = SolverEngine::extract_square_factor(&(value.numer() * value.denom()));
+        let factor = BigRational::new(outside, value.denom().clone());
+        if inside.is_one() {
+            Self::rational(&factor)
+        } else {
+            Self::scaled(&factor, Expression::sqrt(Expression::Number(Number::Integer(inside))))
+        }
+    }
+
+    /// coefficient·expr，系数为 ±1 时省略
+    fn scaled(coefficient: &BigRational, expr: Expression) -> Expression {
+        if coefficient.is_one() {
+            expr
+        } else if (-coefficient).is_one() {
+            Expression::negate(expr)
+        } else {
+            Expression::multiply(Self::rational(coefficient), expr)
+        }
+    }
+
+    fn rational(value: &BigRational) -> Expression {
+        Expression::Number(SolverEngine::rational_to_number(value.clone()))
+    }
+
+    fn power_expr(base: Expression, exponent: u32) -> Expression {
+        if exponent == 1 {
+            base
+        } else {
+            Expression::power(base, Expression::Number(Number::integer(exponent as i64)))
+        }
+    }
+
+    fn factorial(n: u32) -> BigInt {
+        (1..=n).fold(BigInt::one(), |acc, k| acc * BigInt::from(k))
+    }
+
+    fn sum(terms: Vec<Expression>) -> Expression {
+        terms.into_iter().reduce(Expression::add).unwrap_or_else(|| Expression::Number(Number::zero()))
+    }
+
+    fn is_zero(expr: &Expression) -> bool {
+        matches!(expr, Expression::Number(n) if n.is_zero())
+    }
+
+    fn depends_on(expr: &Expression, var: &str) -> bool {
+        expr.get_variables().iter().any(|name| name == var)
+    }
+}
+
+impl Default for LaplaceEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[path = "laplace_tests.rs"]
+mod laplace_tests;
@@ -0,0 +1,83 @@
+//! # Laplace 变换测试
+//!
+//! 测试变换表、位移定理、tⁿ 规则、逆变换与符号形式的保留。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::laplace::LaplaceEngine;
+    use crate::engine::equivalence::EquivalenceChecker;
+    use crate::core::Expression;
+    use crate::test_utils::parse;
+
+    fn assert_equivalent(actual: &Expression, expected: &str) {
+        let equivalence = EquivalenceChecker::new().are_equivalent(actual, &parse(expected)).unwrap();
+        assert!(equivalence.equivalent, "{} 应等价于 {}", actual, expected);
+    }
+
+    #[test]
+    fn test_transform_table() {
+        let engine = LaplaceEngine::new();
+        let cases = [
+            ("1", "1/s"),
+            ("t^3", "6/s^4"),
+            ("exp(3*t)", "1/(s-3)"),
+            ("sin(2*t)", "2/(s^2+4)"),
+            ("5*cos(t) - 2", "5*s/(s^2+1) - 2/s"),
+            ("exp(-t)*sin(t)", "1/((s+1)^2+1)"),
+            ("t*sin(t)", "2*s/(s^2+1)^2"),
+        ];
+        for (input, expected) in cases {
+            let result = engine.laplace(&parse(input), "t", "s").unwrap();
+            assert!(result.evaluated, "{} 应能查表求出", input);
+            assert_equivalent(&result.transform, expected);
+        }
+    }
+
+    #[test]
+    fn test_shift_and_region() {
+        let engine = LaplaceEngine::new();
+        let result = engine.laplace(&parse("t*exp(-2*t)"), "t", "s").unwrap();
+        assert_eq!(result.transform.to_string(), "1 / (s + 2)^2");
+        assert_eq!(result.region("s").as_deref(), Some("Re(s) > -2"));
+    }
+
+    #[test]
+    fn test_unknown_stays_symbolic() {
+        let engine = LaplaceEngine::new();
+        let result = engine.laplace(&parse("ln(t)"), "t", "s").unwrap();
+        assert!(!result.evaluated);
+        assert!(result.abscissa.is_none());
+        assert_eq!(result.transform.to_string(), "laplace(ln(t), t, s)");
+
+        assert!(engine.laplace(&parse("s*t"), "t", "s").is_err());
+    }
+
+    #[test]
+    fn test_inverse_transform() {
+        let engine = LaplaceEngine::new();
+        assert_eq!(engine.inverse_laplace(&parse("1/(s^2+1)"), "s", "t").unwrap().to_string(), "sin(t)");
+        let cases = [
+            ("1/(s+2)^2", "t*exp(-2*t)"),
+            ("1/(s*(s+1))", "1 - exp(-t)"),
+            ("(s+1)/(s^2+2*s+5)", "exp(-t)*cos(2*t)"),
+            ("1/(s^2-4)", "sinh(2*t)/2"),
+            ("1/(s^2+1)^2", "(sin(t) - t*cos(t))/2"),
+        ];
+        for (input, expected) in cases {
+            let result = engine.inverse_laplace(&parse(input), "s", "t").unwrap();
+            assert_equivalent(&result, expected);
+        }
+        let symbolic = engine.inverse_laplace(&parse("exp(-s)/s"), "s", "t").unwrap();
+        assert_eq!(symbolic.to_string(), "inverse_laplace(exp(-s) / s, s, t)");
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let engine = LaplaceEngine::new();
+        for input in ["t^2*exp(t)", "3*exp(-t)*cos(2*t)", "sinh(3*t)"] {
+            let forward = engine.laplace(&parse(input), "t", "s").unwrap();
+            let back = engine.inverse_laplace(&forward.transform, "s", "t").unwrap();
+            assert_equivalent(&back, input);
+        }
+    }
+}
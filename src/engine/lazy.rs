@@ -1,11 +1,15 @@
 //! # 惰性求值系统
 //!
 //! 实现延迟计算和依赖跟踪，提高计算效率。
+//!
+//! [`DependencyGraph::define`] 定义的命名值会被引用它的表达式自动依赖；修改某个值后，
+//! 依赖它的惰性值都被标记为失效，下次 [`DependencyGraph::evaluate`] 时重算。
 
 use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, RwLock};
 use crate::core::Expression;
 use crate::engine::{ComputeEngine, ComputeError};
+use crate::tr;
 
 /// 惰性表达式状态
 #[derive(Debug, Clone, PartialEq)]
@@ -155,13 +159,18 @@ impl LazyExpression {
     
     /// 强制计算表达式
     pub fn force_compute(&self, engine: &dyn ComputeEngine) -> Result<Expression, ComputeError> {
+        self.force_compute_with(engine, &HashMap::new())
+    }
+    
+    /// 强制计算表达式，计算前把 `bindings` 中的变量代入原始表达式
+    pub fn force_compute_with(&self, engine: &dyn ComputeEngine, bindings: &HashMap<String, Expression>) -> Result<Expression, ComputeError> {
         // 检查当前状态
         match self.state() {
             LazyState::Computed(expr) => return Ok(expr),
             LazyState::Failed(err) => return Err(err),
             LazyState::Computing => {
                 return Err(ComputeError::UnsupportedOperation { 
-                    operation: "循环依赖或重复计算".to_string() 
+                    operation: tr!("lazy.cycle_or_recomputation").to_string() 
                 });
             }
             LazyState::Pending => {}
@@ -183,11 +192,12 @@ impl LazyExpression {
         }
         
         // 执行计算
+        let expr = if bindings.is_empty() { self.original.clone() } else { self.original.substitute(bindings) };
         let result = if let Some(ref compute_fn) = self.compute_fn {
-            compute_fn(&self.original, engine)
+            compute_fn(&expr, engine)
         } else {
             // 默认计算：简化表达式
-            engine.simplify(&self.original)
+            engine.simplify(&expr)
         };
         
         // 更新状态
@@ -210,6 +220,15 @@ impl LazyExpression {
         let mut state = self.state.write().unwrap();
         *state = LazyState::Pending;
     }
+    
+    /// 换成新的原始表达式，保留 ID、依赖、计算函数和优先级，状态为未计算
+    fn replaced(&self, expr: Expression) -> Self {
+        Self {
+            original: expr,
+            state: Arc::new(RwLock::new(LazyState::Pending)),
+            ..self.clone()
+        }
+    }
 }
 
 /// 依赖图
@@ -221,6 +240,8 @@ pub struct DependencyGraph {
     dependencies: HashMap<usize, HashSet<usize>>,
     /// 反向依赖关系图（表达式ID -> 依赖它的表达式ID列表）
     dependents: HashMap<usize, HashSet<usize>>,
+    /// 命名值（变量名 -> 定义它的表达式ID）
+    names: HashMap<String, usize>,
     /// 下一个可用的表达式ID
     next_id: usize,
 }
@@ -232,11 +253,12 @@ impl DependencyGraph {
             expressions: HashMap::new(),
             dependencies: HashMap::new(),
             dependents: HashMap::new(),
+            names: HashMap::new(),
             next_id: 1,
         }
     }
     
-    /// 添加惰性表达式
+    /// 添加惰性表达式，自动依赖其中引用的命名值
     pub fn add_expression(&mut self, expr: Expression) -> Arc<LazyExpression> {
         let id = self.next_id;
         self.next_id += 1;
@@ -245,10 +267,122 @@ impl DependencyGraph {
         self.expressions.insert(id, lazy_expr.clone());
         self.dependencies.insert(id, HashSet::new());
         self.dependents.insert(id, HashSet::new());
+        // 新表达式还没有依赖它的表达式，不会形成循环
+        let _ = self.link_names(id);
         
         lazy_expr
     }
     
+    /// 定义命名值 `name`；已定义时改为新表达式并使依赖它的值失效
+    ///
+    /// 此前和此后添加的、引用变量 `name` 的表达式都会依赖它。
+    pub fn define(&mut self, name: &str, expr: Expression) -> Result<Arc<LazyExpression>, ComputeError> {
+        if let Some(&id) = self.names.get(name) {
+            return self.set_expression(id, expr);
+        }
+        if expr.get_variables().iter().any(|v| v == name) {
+            return Err(ComputeError::UnsupportedOperation {
+                operation: tr!("lazy.self_reference", name = name),
+            });
+        }
+        
+        let lazy_expr = self.add_expression(expr);
+        let id = lazy_expr.id();
+        self.names.insert(name.to_string(), id);
+        
+        // 已有的引用 name 的表达式改为依赖它
+        let users: Vec<usize> = self.expressions.iter()
+            .filter(|(&other, expr)| other != id && expr.original().get_variables().iter().any(|v| v == name))
+            .map(|(&other, _)| other)
+            .collect();
+        for user in users {
+            self.add_dependency(user, id)?;
+            self.invalidate(user);
+        }
+        
+        Ok(lazy_expr)
+    }
+    
+    /// 命名值对应的表达式ID
+    pub fn lookup(&self, name: &str) -> Option<usize> {
+        self.names.get(name).copied()
+    }
+    
+    /// 修改表达式，使它和所有直接或间接依赖它的表达式失效，返回新的惰性表达式
+    pub fn set_expression(&mut self, id: usize, expr: Expression) -> Result<Arc<LazyExpression>, ComputeError> {
+        let old = self.expressions.get(&id).ok_or_else(|| ComputeError::UnsupportedOperation {
+            operation: tr!("lazy.missing_expression", id = id),
+        })?;
+        let lazy_expr = Arc::new(old.replaced(expr));
+        let previous = self.expressions.insert(id, lazy_expr.clone());
+        if let Err(err) = self.link_names(id) {
+            // 新表达式会形成循环，恢复原表达式
+            if let Some(previous) = previous {
+                self.expressions.insert(id, previous);
+            }
+            return Err(err);
+        }
+        self.invalidate(id);
+        Ok(lazy_expr)
+    }
+    
+    /// 使表达式和所有直接或间接依赖它的表达式失效，返回失效的表达式ID
+    pub fn invalidate(&self, id: usize) -> Vec<usize> {
+        let mut invalidated = Vec::new();
+        let mut visited = HashSet::new();
+        let mut stack = vec![id];
+        
+        while let Some(current) = stack.pop() {
+            if !visited.insert(current) {
+                continue;
+            }
+            if let Some(expr) = self.expressions.get(&current) {
+                expr.reset();
+                invalidated.push(current);
+            }
+            if let Some(dependents) = self.dependents.get(&current) {
+                stack.extend(dependents.iter().copied());
+            }
+        }
+        
+        invalidated
+    }
+    
+    /// 求值表达式，先按依赖图求值依赖并代入其中的命名值；已计算且未失效时直接返回结果
+    pub fn evaluate(&self, id: usize, engine: &dyn ComputeEngine) -> Result<Expression, ComputeError> {
+        let expr = self.expressions.get(&id).ok_or_else(|| ComputeError::UnsupportedOperation {
+            operation: tr!("lazy.missing_expression", id = id),
+        })?;
+        if let Some(result) = expr.get_result() {
+            return Ok(result);
+        }
+        
+        let mut bindings = HashMap::new();
+        if let Some(deps) = self.dependencies.get(&id) {
+            for &dep in deps {
+                let value = self.evaluate(dep, engine)?;
+                if let Some((name, _)) = self.names.iter().find(|(_, &named)| named == dep) {
+                    bindings.insert(name.clone(), value);
+                }
+            }
+        }
+        expr.force_compute_with(engine, &bindings)
+    }
+    
+    /// 为表达式添加对其中引用的命名值的依赖
+    fn link_names(&mut self, id: usize) -> Result<(), ComputeError> {
+        let Some(expr) = self.expressions.get(&id) else {
+            return Ok(());
+        };
+        let referenced: Vec<usize> = expr.original().get_variables().iter()
+            .filter_map(|v| self.names.get(v).copied())
+            .collect();
+        for dep in referenced {
+            self.add_dependency(id, dep)?;
+        }
+        Ok(())
+    }
+    
     /// 添加带计算函数的惰性表达式
     pub fn add_expression_with_fn<F>(&mut self, expr: Expression, compute_fn: F) -> Arc<LazyExpression>
     where
@@ -270,7 +404,7 @@ impl DependencyGraph {
         // 检查是否会形成循环依赖
         if self.would_create_cycle(expr_id, dep_id) {
             return Err(ComputeError::UnsupportedOperation {
-                operation: "添加依赖会形成循环".to_string(),
+                operation: tr!("lazy.dependency_cycle").to_string(),
             });
         }
         
@@ -364,7 +498,7 @@ impl DependencyGraph {
         // 检查是否存在循环依赖
         if result.len() != self.expressions.len() {
             return Err(ComputeError::UnsupportedOperation {
-                operation: "检测到循环依赖".to_string(),
+                operation: tr!("lazy.cycle_detected").to_string(),
             });
         }
         
@@ -406,7 +540,7 @@ impl DependencyGraph {
             } else {
                 // 如果没有可以计算的表达式，但还有剩余的，说明有循环依赖
                 return Err(ComputeError::UnsupportedOperation {
-                    operation: "检测到循环依赖或无法解析的依赖关系".to_string(),
+                    operation: tr!("lazy.unresolved").to_string(),
                 });
             }
         }
@@ -436,6 +570,7 @@ impl DependencyGraph {
             self.expressions.remove(&id);
             self.dependencies.remove(&id);
             self.dependents.remove(&id);
+            self.names.retain(|_, named| *named != id);
             
             // 从其他表达式的依赖中移除
             for deps in self.dependencies.values_mut() {
@@ -622,4 +757,57 @@ mod tests {
         assert!(lazy_expr.is_computed());
         assert_eq!(lazy_expr.get_result(), Some(result));
     }
+    
+    #[test]
+    fn test_invalidation_propagates() {
+        let engine = BasicComputeEngine::new();
+        let mut graph = DependencyGraph::new();
+        
+        // a = 1, b = a + 1, c = b * 2 通过 define 自动建立依赖
+        let a = graph.define("a", Expression::number(1.into())).unwrap();
+        let b = graph.define("b", Expression::add(Expression::variable("a"), Expression::number(1.into()))).unwrap();
+        let c = graph.add_expression(Expression::multiply(Expression::variable("b"), Expression::number(2.into())));
+        let unrelated = graph.add_expression(Expression::variable("z"));
+        
+        assert_eq!(graph.evaluate(c.id(), &engine).unwrap(), Expression::number(4.into()));
+        graph.evaluate(unrelated.id(), &engine).unwrap();
+        assert!(b.is_computed());
+        
+        // 修改 a 后 b、c 失效，z 不受影响
+        graph.define("a", Expression::number(5.into())).unwrap();
+        let mut invalidated = graph.invalidate(a.id());
+        invalidated.sort();
+        assert_eq!(invalidated, vec![a.id(), b.id(), c.id()]);
+        assert!(!b.is_computed());
+        assert!(!c.is_computed());
+        assert!(unrelated.is_computed());
+        
+        // 下次求值得到新结果
+        assert_eq!(graph.evaluate(b.id(), &engine).unwrap(), Expression::number(6.into()));
+        assert_eq!(graph.evaluate(c.id(), &engine).unwrap(), Expression::number(12.into()));
+    }
+    
+    #[test]
+    fn test_define_links_existing_users() {
+        let engine = BasicComputeEngine::new();
+        let mut graph = DependencyGraph::new();
+        
+        // 先引用后定义
+        let sum = graph.add_expression(Expression::add(Expression::variable("x"), Expression::variable("x")));
+        assert_eq!(graph.evaluate(sum.id(), &engine).unwrap().to_string(), "2 * x");
+        
+        let x = graph.define("x", Expression::number(3.into())).unwrap();
+        assert!(!sum.is_computed());
+        assert_eq!(graph.evaluate(sum.id(), &engine).unwrap(), Expression::number(6.into()));
+        
+        // 修改子表达式
+        let x = graph.set_expression(x.id(), Expression::number(4.into())).unwrap();
+        assert_eq!(graph.lookup("x"), Some(x.id()));
+        assert_eq!(graph.evaluate(sum.id(), &engine).unwrap(), Expression::number(8.into()));
+        
+        // 自引用和循环被拒绝
+        assert!(graph.define("y", Expression::variable("y")).is_err());
+        assert!(graph.set_expression(x.id(), Expression::variable("x")).is_err());
+        assert_eq!(graph.evaluate(x.id(), &engine).unwrap(), Expression::number(4.into()));
+    }
 }
\ No newline at end of file
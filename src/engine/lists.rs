@@ -0,0 +1,188 @@
+//! # 列表函数
+//!
+//! 作用于向量 `[a, b, c]` 的生成与处理函数：`range`、`table`、`map`、`filter`、`sum_list`、`sort`、`length`。
+//!
+//! 只处理最外一层：矩阵 `[[1, 2], [3, 4]]` 看作以各行（行向量）为元素的列表，
+//! `length` 得到行数，`map` 逐行作用，`sum_list` 为各行之和，行向量之间无法比较大小，`sort` 报错。
+//! 结果的元素都是等长向量时重新组成矩阵。
+//! 参数还不是具体的列表或数值（如含未赋值的变量）时保留函数形式，等代入后再求值。
+
+use std::collections::HashMap;
+use num_rational::BigRational;
+use num_traits::{Signed, ToPrimitive, Zero};
+use crate::core::{Expression, Number};
+use super::ComputeError;
+use super::calculus::CalculusEngine;
+use super::simplify::Simplifier;
+use super::solver::SolverEngine;
+use crate::tr;
+
+/// 列表函数名
+pub const LIST_FUNCTIONS: &[&str] = &["range", "table", "map", "filter", "sum_list", "sort", "length"];
+
+/// 生成的列表最多含有的元素个数
+const MAX_LENGTH: usize = 100_000;
+
+/// 化简列表函数调用；参数还不能求值时返回 `None`，由调用方保留函数形式
+pub fn simplify_call(name: &str, args: &[Expression]) -> Option<Result<Expression, ComputeError>> {
+    match (name, args) {
+        ("range", [start, end]) => range(start, end, &Expression::Number(Number::one())),
+        ("range", [start, end, step]) => range(start, end, step),
+        ("table", [body, Expression::Variable(var), start, end]) => table(body, var, start, end, &Expression::Number(Number::one())),
+        ("table", [body, Expression::Variable(var), start, end, step]) => table(body, var, start, end, step),
+        ("map", [Expression::Variable(function), list]) => {
+            let elements = list_argument(list)?;
+            Some(map(&elements, |e| simplify(Expression::function(function, vec![e.clone()]))))
+        }
+        ("map", [body, Expression::Variable(var), list]) => Some(map(&list_argument(list)?, |e| bind(body, var, e))),
+        ("filter", [Expression::Variable(predicate), list]) => {
+            filter(list_argument(list)?, |e| simplify(Expression::function(predicate, vec![e.clone()])))
+        }
+        ("filter", [body, Expression::Variable(var), list]) => filter(list_argument(list)?, |e| bind(body, var, e)),
+        ("sum_list", [list]) => Some(sum(list_argument(list)?)),
+        ("sort", [list]) => Some(sort(list_argument(list)?)),
+        ("length", [list]) => Some(Ok(Expression::Number(Number::integer(list_argument(list)?.len() as i64)))),
+        _ => None,
+    }
+}
+
+/// 列表参数的元素：向量返回其元素，矩阵返回各行，其他（符号等）返回 `None`
+fn list_argument(list: &Expression) -> Option<Vec<Expression>> {
+    match list {
+        Expression::Vector(elements) => Some(elements.clone()),
+        Expression::Matrix(rows) => Some(rows.iter().cloned().map(Expression::Vector).collect()),
+        _ => None,
+    }
+}
+
+/// 元素组成列表，元素都是等长的非空向量时组成矩阵
+fn to_list(elements: Vec<Expression>) -> Expression {
+    let width = match elements.first() {
+        Some(Expression::Vector(row)) if !row.is_empty() => row.len(),
+        _ => return Expression::Vector(elements),
+    };
+    if !elements.iter().all(|e| matches!(e, Expression::Vector(row) if row.len() == width)) {
+        return Expression::Vector(elements);
+    }
+    Expression::Matrix(elements.into_iter().map(|e| match e {
+        Expression::Vector(row) => row,
+        _ => unreachable!(),
+    }).collect())
+}
+
+/// 从 `start` 起以 `step` 为步长、不越过 `end` 的有理数序列
+fn rational_range(start: &Expression, end: &Expression, step: &Expression) -> Option<Result<Vec<BigRational>, ComputeError>> {
+    let rational = |expr: &Expression| match expr {
+        Expression::Number(n) => n.to_rational(),
+        _ => None,
+    };
+    let (start, end, step) = (rational(start)?, rational(end)?, rational(step)?);
+    if step.is_zero() {
+        return Some(Err(ComputeError::domain_error(tr!("lists.zero_step"))));
+    }
+    let span = (&end - &start) / &step;
+    if span.is_negative() {
+        return Some(Ok(Vec::new()));
+    }
+    let count = span.floor().to_integer().to_usize().map(|n| n + 1).filter(|&n| n <= MAX_LENGTH);
+    let Some(count) = count else {
+        return Some(Err(ComputeError::unsupported_operation(tr!("lists.too_long", limit = MAX_LENGTH))));
+    };
+    let mut values = Vec::with_capacity(count);
+    let mut value = start;
+    for _ in 0..count {
+        let next = &value + &step;
+        values.push(value);
+        value = next;
+    }
+    Some(Ok(values))
+}
+
+/// `range(a, b, step)`：a 到 b 的等差数列，包含 b
+fn range(start: &Expression, end: &Expression, step: &Expression) -> Option<Result<Expression, ComputeError>> {
+    let values = rational_range(start, end, step)?;
+    Some(values.map(|values| Expression::Vector(values.into_iter().map(|v| Expression::Number(SolverEngine::rational_to_number(v))).collect())))
+}
+
+/// `table(f, n, a, b, step)`：n 依次取 `range(a, b, step)` 时 f 的值
+fn table(body: &Expression, var: &str, start: &Expression, end: &Expression, step: &Expression) -> Option<Result<Expression, ComputeError>> {
+    let values = rational_range(start, end, step)?;
+    Some(values.and_then(|values| {
+        let elements = values.into_iter()
+            .map(|v| bind(body, var, &Expression::Number(SolverEngine::rational_to_number(v))))
+            .collect::<Result<_, _>>()?;
+        Ok(to_list(elements))
+    }))
+}
+
+/// 逐元素作用 `f`
+fn map(elements: &[Expression], f: impl Fn(&Expression) -> Result<Expression, ComputeError>) -> Result<Expression, ComputeError> {
+    Ok(to_list(elements.iter().map(f).collect::<Result<_, _>>()?))
+}
+
+/// 元素之和，元素都是等长向量时逐分量求和
+fn sum(elements: Vec<Expression>) -> Result<Expression, ComputeError> {
+    let add_all = |terms: Vec<Expression>| {
+        simplify(terms.into_iter().reduce(Expression::add).unwrap_or_else(|| Expression::Number(Number::zero())))
+    };
+    match to_list(elements) {
+        Expression::Matrix(rows) => {
+            let columns = (0..rows[0].len()).map(|j| add_all(rows.iter().map(|row| row[j].clone()).collect()));
+            Ok(Expression::Vector(columns.collect::<Result<_, _>>()?))
+        }
+        Expression::Vector(elements) => add_all(elements),
+        _ => unreachable!(),
+    }
+}
+
+/// 保留谓词化简为真（非零数）的元素；有谓词值不能确定时返回 `None`
+fn filter(
+    elements: Vec<Expression>,
+    predicate: impl Fn(&Expression) -> Result<Expression, ComputeError>,
+) -> Option<Result<Expression, ComputeError>> {
+    let mut kept = Vec::new();
+    for element in elements {
+        match predicate(&element) {
+            Ok(Expression::Number(truth)) => {
+                if !truth.is_zero() {
+                    kept.push(element);
+                }
+            }
+            Ok(_) => return None,
+            Err(e) => return Some(Err(e)),
+        }
+    }
+    Some(Ok(to_list(kept)))
+}
+
+/// 按数值从小到大排序，相等的元素保持原顺序；含符号或非实数的元素无法比较，报错
+fn sort(elements: Vec<Expression>) -> Result<Expression, ComputeError> {
+    let calculus = CalculusEngine::new();
+    let mut keyed = Vec::with_capacity(elements.len());
+    for element in elements {
+        let value = if element.get_variables().is_empty() {
+            calculus.numerical_evaluate(&element, &HashMap::new()).ok().filter(|v| !v.is_nan())
+        } else {
+            None
+        };
+        match value {
+            Some(value) => keyed.push((value, element)),
+            None => return Err(ComputeError::domain_error(tr!("lists.incomparable", element = element))),
+        }
+    }
+    keyed.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+    Ok(Expression::Vector(keyed.into_iter().map(|(_, element)| element).collect()))
+}
+
+/// 把 `value` 代入 `body` 中的 `var` 并化简
+fn bind(body: &Expression, var: &str, value: &Expression) -> Result<Expression, ComputeError> {
+    simplify(body.substitute(&HashMap::from([(var.to_string(), value.clone())])))
+}
+
+fn simplify(expr: Expression) -> Result<Expression, ComputeError> {
+    Simplifier::new().simplify(&expr)
+}
+
+#[cfg(test)]
+#[path = "lists_tests.rs"]
+mod lists_tests;
@@ -0,0 +1,76 @@
+//! # 列表函数测试
+//!
+//! 测试列表生成、逐元素映射与筛选、求和、排序、长度，以及符号参数与矩阵按行看作列表的处理。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::simplify::Simplifier;
+    use crate::engine::ComputeError;
+    use crate::core::Expression;
+    use crate::test_utils::parse;
+
+    fn simplify(input: &str) -> Result<Expression, ComputeError> {
+        Simplifier::new().simplify(&parse(input))
+    }
+
+    fn simplified(input: &str) -> String {
+        simplify(input).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_range_and_table() {
+        assert_eq!(simplify("range(1, 5)").unwrap(), parse("[1, 2, 3, 4, 5]"));
+        assert_eq!(simplify("range(1, 2, 1/2)").unwrap(), simplify("[1, 3/2, 2]").unwrap());
+        assert_eq!(simplify("range(5, 1, -2)").unwrap(), parse("[5, 3, 1]"));
+        assert_eq!(simplify("range(3, 1)").unwrap(), parse("[]"));
+        assert_eq!(simplify("table(n^2, n, 1, 5)").unwrap(), parse("[1, 4, 9, 16, 25]"));
+        assert_eq!(simplified("table(diff(x^n, x), n, 1, 3)"), simplified("[1, 2*x, 3*x^2]"));
+        assert!(matches!(simplify("range(1, 5, 0)"), Err(ComputeError::DomainError { .. })));
+        assert!(simplify("range(1, 10^9)").is_err());
+    }
+
+    #[test]
+    fn test_map_and_filter() {
+        assert_eq!(simplify("map(k^2 + 1, k, [1, 2, 3])").unwrap(), parse("[2, 5, 10]"));
+        assert_eq!(simplify("map(abs, [-1, 2, -3])").unwrap(), parse("[1, 2, 3]"));
+        assert_eq!(simplified("map(f, [a, b])"), "[f(a), f(b)]");
+        assert_eq!(simplify("filter(k > 2, k, range(1, 5))").unwrap(), parse("[3, 4, 5]"));
+        assert_eq!(simplify("filter(k % 2 == 0, k, table(n^2, n, 1, 6))").unwrap(), parse("[4, 16, 36]"));
+        // 谓词值含符号时无法判断，保留原式
+        assert_eq!(simplified("filter(k > a, k, [1, 2])"), "filter(k > a, k, [1, 2])");
+    }
+
+    #[test]
+    fn test_sum_sort_length() {
+        assert_eq!(simplify("sum_list(table(n^2, n, 1, 5))").unwrap(), parse("55"));
+        assert_eq!(simplify("sum_list([])").unwrap(), parse("0"));
+        assert_eq!(simplified("sum_list([x, x, y])"), simplified("2*x + y"));
+        assert_eq!(simplify("sort([3, 1/2, -2, 1])").unwrap(), simplify("[-2, 1/2, 1, 3]").unwrap());
+        assert_eq!(simplified("sort([pi, 3, sqrt(10)])"), simplified("[3, pi, sqrt(10)]"));
+        assert!(matches!(simplify("sort([x, 1])"), Err(ComputeError::DomainError { .. })));
+        assert_eq!(simplify("length(range(1, 10))").unwrap(), parse("10"));
+    }
+
+    #[test]
+    fn test_symbolic_and_matrix_arguments() {
+        // 列表还是符号时保留函数形式
+        assert_eq!(simplified("length(L)"), "length(L)");
+        assert_eq!(simplified("range(1, n)"), "range(1, n)");
+        // 代换时约束变量不被替换，列表参数正常代入
+        let expr = parse("map(k^2, k, L)");
+        let substituted = expr.substitute(&[("k".to_string(), parse("5")), ("L".to_string(), parse("[1, 2]"))].into_iter().collect());
+        assert_eq!(Simplifier::new().simplify(&substituted).unwrap(), parse("[1, 4]"));
+    }
+
+    #[test]
+    fn test_matrix_as_list_of_rows() {
+        assert_eq!(simplify("length([[1, 2], [3, 4], [5, 6]])").unwrap(), parse("3"));
+        assert_eq!(simplify("map(r, r, [[1, 2], [3, 4]])").unwrap(), parse("[[1, 2], [3, 4]]"));
+        assert_eq!(simplify("map(length, [[1, 2, 3], [4, 5, 6]])").unwrap(), parse("[3, 3]"));
+        assert_eq!(simplify("filter(sum_list(r) > 3, r, [[1, 2], [3, 4]])").unwrap(), parse("[[3, 4]]"));
+        assert_eq!(simplify("table([n, n^2], n, 1, 2)").unwrap(), parse("[[1, 1], [2, 4]]"));
+        assert_eq!(simplify("sum_list([[1, 2], [3, 4]])").unwrap(), parse("[4, 6]"));
+        // 行向量之间不能比较大小
+        assert!(matches!(simplify("sort([[1, 2], [3, 4]])"), Err(ComputeError::DomainError { .. })));
+    }
+}
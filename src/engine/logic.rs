@@ -0,0 +1,320 @@
+//! # 逻辑表达式
+//!
+//! 布尔值沿用引擎的约定，用整数 1/0 表示真/假。提供化简规则（常量折叠、幂等、
+//! 互补、吸收、双重否定、德摩根、比较取反）、真值表，以及析取/合取范式转换。
+
+use std::collections::HashMap;
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator};
+use super::ComputeError;
+use crate::tr;
+
+/// 真值表允许的最大变量数
+pub const MAX_TRUTH_TABLE_VARIABLES: usize = 20;
+
+/// 真值表：每行为按变量顺序排列的赋值及表达式的值
+pub type TruthTable = Vec<(Vec<bool>, bool)>;
+
+/// 化简 `left && right`，两边应已化简；没有可用的规则时返回 `None`
+pub fn simplify_and(left: &Expression, right: &Expression) -> Option<Expression> {
+    simplify_junction(BinaryOperator::And, left, right)
+}
+
+/// 化简 `left || right`，两边应已化简；没有可用的规则时返回 `None`
+pub fn simplify_or(left: &Expression, right: &Expression) -> Option<Expression> {
+    simplify_junction(BinaryOperator::Or, left, right)
+}
+
+/// 化简 `!operand`：折叠常量、消去双重否定、比较取反，并用德摩根律把否定推入与/或；
+/// 没有可用的规则时返回 `None`
+pub fn simplify_not(operand: &Expression) -> Option<Expression> {
+    match operand {
+        Expression::Number(n) => Some(truth(n.is_zero())),
+        Expression::UnaryOp { op: UnaryOperator::Not, operand } => Some(operand.as_ref().clone()),
+        Expression::BinaryOp { op, left, right } => match (op, negated_comparison(op)) {
+            (_, Some(negated)) => Some(Expression::binary_op(negated, left.as_ref().clone(), right.as_ref().clone())),
+            (BinaryOperator::And, _) => Some(junction(BinaryOperator::Or, negation(left), negation(right))),
+            (BinaryOperator::Or, _) => Some(junction(BinaryOperator::And, negation(left), negation(right))),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// 化简比较：两边为实数时折叠为 1/0，两边相同时按自反性折叠；其他情况返回 `None`
+pub fn simplify_comparison(op: &BinaryOperator, left: &Expression, right: &Expression) -> Option<Expression> {
+    if left == right {
+        return Some(truth(matches!(op, BinaryOperator::Equal | BinaryOperator::LessEqual | BinaryOperator::GreaterEqual)));
+    }
+    if let (Expression::Number(a), Expression::Number(b)) = (left, right) {
+        if let (Some(a), Some(b)) = (real_value(a), real_value(b)) {
+            return Some(truth(match op {
+                BinaryOperator::Equal => a == b,
+                BinaryOperator::NotEqual => a != b,
+                BinaryOperator::Less => a < b,
+                BinaryOperator::LessEqual => a <= b,
+                BinaryOperator::Greater => a > b,
+                _ => a >= b,
+            }));
+        }
+    }
+    None
+}
+
+/// 比较运算符的否定，如 `<` 的否定为 `>=`；非比较运算符返回 None
+pub fn negated_comparison(op: &BinaryOperator) -> Option<BinaryOperator> {
+    match op {
+        BinaryOperator::Equal => Some(BinaryOperator::NotEqual),
+        BinaryOperator::NotEqual => Some(BinaryOperator::Equal),
+        BinaryOperator::Less => Some(BinaryOperator::GreaterEqual),
+        BinaryOperator::LessEqual => Some(BinaryOperator::Greater),
+        BinaryOperator::Greater => Some(BinaryOperator::LessEqual),
+        BinaryOperator::GreaterEqual => Some(BinaryOperator::Less),
+        _ => None,
+    }
+}
+
+/// 在给定赋值下求布尔表达式的值
+pub fn evaluate(expr: &Expression, assignment: &HashMap<String, bool>) -> Result<bool, ComputeError> {
+    match expr {
+        Expression::Number(n) => Ok(!n.is_zero()),
+        Expression::Variable(name) => assignment.get(name).copied()
+            .ok_or_else(|| ComputeError::undefined_variable(name.clone())),
+        Expression::UnaryOp { op: UnaryOperator::Not, operand } => Ok(!evaluate(operand, assignment)?),
+        Expression::BinaryOp { op: BinaryOperator::And, left, right } => {
+            Ok(evaluate(left, assignment)? && evaluate(right, assignment)?)
+        }
+        Expression::BinaryOp { op: BinaryOperator::Or, left, right } => {
+            Ok(evaluate(left, assignment)? || evaluate(right, assignment)?)
+        }
+        Expression::BinaryOp { op: op @ (BinaryOperator::Equal | BinaryOperator::NotEqual), left, right } => {
+            let equal = evaluate(left, assignment)? == evaluate(right, assignment)?;
+            Ok(equal == (*op == BinaryOperator::Equal))
+        }
+        _ => Err(ComputeError::unsupported_operation(tr!("logic.not_boolean", expression = expr))),
+    }
+}
+
+/// 生成真值表，行按赋值的二进制顺序排列（全假在前）
+pub fn truth_table(expr: &Expression, vars: &[String]) -> Result<TruthTable, ComputeError> {
+    if vars.len() > MAX_TRUTH_TABLE_VARIABLES {
+        return Err(ComputeError::domain_error(tr!(
+            "logic.truth_table_size", count = vars.len(), limit = MAX_TRUTH_TABLE_VARIABLES
+        )));
+    }
+    if let Some(free) = expr.get_variables().into_iter().find(|name| !vars.contains(name)) {
+        return Err(ComputeError::undefined_variable(free));
+    }
+
+    let mut table = Vec::with_capacity(1 << vars.len());
+    for row in 0..1usize << vars.len() {
+        let values: Vec<bool> = (0..vars.len()).map(|i| row >> (vars.len() - 1 - i) & 1 == 1).collect();
+        let assignment = vars.iter().cloned().zip(values.iter().copied()).collect();
+        let value = evaluate(expr, &assignment)?;
+        table.push((values, value));
+    }
+    Ok(table)
+}
+
+/// 转换为析取范式（积之和），并消去矛盾项与被吸收的项
+pub fn to_dnf(expr: &Expression) -> Expression {
+    let terms = normalize(expand(&push_negation(expr, true), BinaryOperator::Or));
+    build(terms, BinaryOperator::Or, BinaryOperator::And)
+}
+
+/// 转换为合取范式（和之积），并消去恒真子句与被吸收的子句
+pub fn to_cnf(expr: &Expression) -> Expression {
+    let clauses = normalize(expand(&push_negation(expr, true), BinaryOperator::And));
+    build(clauses, BinaryOperator::And, BinaryOperator::Or)
+}
+
+/// `!operand` 化简后的结果
+fn negation(operand: &Expression) -> Expression {
+    simplify_not(operand).unwrap_or_else(|| Expression::unary_op(UnaryOperator::Not, operand.clone()))
+}
+
+/// `left op right`（与/或）化简后的结果
+fn junction(op: BinaryOperator, left: Expression, right: Expression) -> Expression {
+    simplify_junction(op.clone(), &left, &right).unwrap_or_else(|| Expression::binary_op(op, left, right))
+}
+
+/// 合并与/或的化简：展平后折叠常量、去重、检查互补并应用吸收律。
+/// 没有删去操作数且原式已是左结合时，重建的结果与原式相同，返回 `None`
+fn simplify_junction(op: BinaryOperator, left: &Expression, right: &Expression) -> Option<Expression> {
+    // 与运算中真值是单位元、假值是零元，或运算相反
+    let identity = op == BinaryOperator::And;
+    let mut operands = Vec::new();
+    flatten(left, &op, &mut operands);
+    flatten(right, &op, &mut operands);
+
+    let mut kept: Vec<Expression> = Vec::new();
+    let count = operands.len();
+    for operand in operands {
+        match &operand {
+            Expression::Number(n) if n.is_zero() == identity => return Some(truth(!identity)),
+            Expression::Number(_) => continue,
+            _ => {}
+        }
+        if kept.iter().any(|k| is_complement(k, &operand)) {
+            return Some(truth(!identity));
+        }
+        if !kept.contains(&operand) {
+            kept.push(operand);
+        }
+    }
+
+    // 吸收律：x && (x || y) = x，x || (x && y) = x
+    let dual = if identity { BinaryOperator::Or } else { BinaryOperator::And };
+    let absorbed: Vec<bool> = kept.iter().enumerate().map(|(i, operand)| {
+        let mut parts = Vec::new();
+        flatten(operand, &dual, &mut parts);
+        parts.len() > 1 && kept.iter().enumerate().any(|(j, other)| j != i && parts.contains(other))
+    }).collect();
+    if kept.len() == count && !absorbed.contains(&true) && is_left_nested(left, &op) && !is_junction(right, &op) {
+        return None;
+    }
+    let kept = kept.into_iter().zip(absorbed).filter(|(_, absorbed)| !absorbed).map(|(operand, _)| operand);
+
+    Some(kept.reduce(|acc, operand| Expression::binary_op(op.clone(), acc, operand))
+        .unwrap_or_else(|| truth(identity)))
+}
+
+fn is_junction(expr: &Expression, op: &BinaryOperator) -> bool {
+    matches!(expr, Expression::BinaryOp { op: inner, .. } if inner == op)
+}
+
+/// 同一运算符的嵌套只沿左侧展开，如 `(a && b) && c`
+fn is_left_nested(expr: &Expression, op: &BinaryOperator) -> bool {
+    match expr {
+        Expression::BinaryOp { op: inner, left, right } if inner == op => !is_junction(right, op) && is_left_nested(left, op),
+        _ => true,
+    }
+}
+
+/// 把同一运算符的嵌套展平成操作数列表
+fn flatten(expr: &Expression, op: &BinaryOperator, out: &mut Vec<Expression>) {
+    match expr {
+        Expression::BinaryOp { op: inner, left, right } if inner == op => {
+            flatten(left, op, out);
+            flatten(right, op, out);
+        }
+        _ => out.push(expr.clone()),
+    }
+}
+
+/// 判断两个表达式是否互为否定
+fn is_complement(a: &Expression, b: &Expression) -> bool {
+    let negates = |x: &Expression, y: &Expression| match x {
+        Expression::UnaryOp { op: UnaryOperator::Not, operand } => operand.as_ref() == y,
+        _ => false,
+    };
+    if negates(a, b) || negates(b, a) {
+        return true;
+    }
+    match (a, b) {
+        (Expression::BinaryOp { op: op_a, left: la, right: ra }, Expression::BinaryOp { op: op_b, left: lb, right: rb }) => {
+            negated_comparison(op_a).as_ref() == Some(op_b) && la == lb && ra == rb
+        }
+        _ => false,
+    }
+}
+
+/// 把否定推到原子上（否定范式）；`positive` 为 false 表示对整个表达式取否定
+fn push_negation(expr: &Expression, positive: bool) -> Expression {
+    match expr {
+        Expression::Number(n) => truth(n.is_zero() != positive),
+        Expression::UnaryOp { op: UnaryOperator::Not, operand } => push_negation(operand, !positive),
+        Expression::BinaryOp { op: op @ (BinaryOperator::And | BinaryOperator::Or), left, right } => {
+            let op = match (op, positive) {
+                (op, true) => op.clone(),
+                (BinaryOperator::And, false) => BinaryOperator::Or,
+                _ => BinaryOperator::And,
+            };
+            Expression::binary_op(op, push_negation(left, positive), push_negation(right, positive))
+        }
+        _ if positive => expr.clone(),
+        _ => negation(expr),
+    }
+}
+
+/// 把否定范式展开为以 `outer` 连接的子句列表，子句内部为文字的列表
+fn expand(expr: &Expression, outer: BinaryOperator) -> Vec<Vec<Expression>> {
+    match expr {
+        // 与外层运算相同：子句列表直接拼接
+        Expression::BinaryOp { op, left, right } if *op == outer => {
+            let mut clauses = expand(left, outer.clone());
+            clauses.extend(expand(right, outer));
+            clauses
+        }
+        // 与内层运算相同：两边子句两两合并（分配律）
+        Expression::BinaryOp { op: BinaryOperator::And | BinaryOperator::Or, left, right } => {
+            let left = expand(left, outer.clone());
+            let right = expand(right, outer);
+            left.iter()
+                .flat_map(|l| right.iter().map(move |r| l.iter().chain(r).cloned().collect()))
+                .collect()
+        }
+        // 常量：对外层运算为零元时子句列表为空，为单位元时为一个空子句
+        Expression::Number(n) => {
+            let absorbing = n.is_zero() == (outer == BinaryOperator::Or);
+            if absorbing { Vec::new() } else { vec![Vec::new()] }
+        }
+        literal => vec![vec![literal.clone()]],
+    }
+}
+
+/// 去重、删除含互补文字的子句，并删除被其他子句吸收的子句（空子句吸收一切）
+fn normalize(clauses: Vec<Vec<Expression>>) -> Vec<Vec<Expression>> {
+    let mut result: Vec<Vec<Expression>> = Vec::new();
+    for clause in clauses {
+        let mut literals: Vec<Expression> = Vec::new();
+        for literal in clause {
+            if !literals.contains(&literal) {
+                literals.push(literal);
+            }
+        }
+        let contradictory = literals.iter().enumerate()
+            .any(|(i, a)| literals[i + 1..].iter().any(|b| is_complement(a, b)));
+        if !contradictory {
+            result.push(literals);
+        }
+    }
+
+    let subsumes = |small: &Vec<Expression>, large: &Vec<Expression>| small.iter().all(|l| large.contains(l));
+    let mut kept: Vec<Vec<Expression>> = Vec::new();
+    for (i, clause) in result.iter().enumerate() {
+        let absorbed = result.iter().enumerate().any(|(j, other)| {
+            j != i && subsumes(other, clause) && (other.len() < clause.len() || j < i)
+        });
+        if !absorbed {
+            kept.push(clause.clone());
+        }
+    }
+    kept
+}
+
+/// 由子句列表构造表达式
+fn build(clauses: Vec<Vec<Expression>>, outer: BinaryOperator, inner: BinaryOperator) -> Expression {
+    // 析取范式的空子句为真、空列表为假，合取范式相反
+    let empty_clause = truth(inner == BinaryOperator::And);
+    clauses.into_iter()
+        .map(|clause| clause.into_iter()
+            .reduce(|acc, literal| Expression::binary_op(inner.clone(), acc, literal))
+            .unwrap_or_else(|| empty_clause.clone()))
+        .reduce(|acc, clause| Expression::binary_op(outer.clone(), acc, clause))
+        .unwrap_or_else(|| truth(outer == BinaryOperator::And))
+}
+
+fn truth(value: bool) -> Expression {
+    Expression::Number(if value { Number::one() } else { Number::zero() })
+}
+
+fn real_value(n: &Number) -> Option<f64> {
+    match n {
+        Number::Integer(_) | Number::Rational(_) | Number::Real(_) | Number::Float(_) => n.to_f64(),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[path = "logic_tests.rs"]
+mod logic_tests;
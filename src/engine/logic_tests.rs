@@ -0,0 +1,76 @@
+//! # 逻辑表达式测试
+//!
+//! 测试逻辑化简规则、真值表与范式转换。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::logic::{self, MAX_TRUTH_TABLE_VARIABLES};
+    use crate::engine::simplify::Simplifier;
+    use crate::core::Expression;
+    use crate::test_utils::parse;
+
+    fn simplify(input: &str) -> String {
+        Simplifier::new().simplify(&parse(input)).unwrap().to_string()
+    }
+
+    fn vars(names: &[&str]) -> Vec<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    /// 两个表达式在所有赋值下取值相同
+    fn assert_same_truth(a: &Expression, b: &Expression, names: &[&str]) {
+        let names = vars(names);
+        assert_eq!(logic::truth_table(a, &names).unwrap(), logic::truth_table(b, &names).unwrap(), "{} 与 {}", a, b);
+    }
+
+    #[test]
+    fn test_simplification_rules() {
+        assert_eq!(simplify("x and (x or y)"), "x");
+        assert_eq!(simplify("x || (y && x)"), "x");
+        assert_eq!(simplify("!!x"), "x");
+        assert_eq!(simplify("x and not x"), "0");
+        assert_eq!(simplify("x or true"), "1");
+        assert_eq!(simplify("x and true and x"), "x");
+        assert_eq!(simplify("not (a < b)"), "a >= b");
+        assert_eq!(simplify("not (x and y)"), "!x || !y");
+        assert_eq!(simplify("3 < 5 and x"), "x");
+        assert_eq!(simplify("a < b or a >= b"), "1");
+    }
+
+    #[test]
+    fn test_keyword_precedence() {
+        assert_eq!(parse("not a < b"), parse("!(a < b)"));
+        assert_eq!(parse("a or b and c"), parse("a || (b && c)"));
+        assert_eq!(parse("!a < b").to_string(), "!a < b");
+    }
+
+    #[test]
+    fn test_truth_table() {
+        let table = logic::truth_table(&parse("x and not y"), &vars(&["x", "y"])).unwrap();
+        assert_eq!(table, vec![
+            (vec![false, false], false),
+            (vec![false, true], false),
+            (vec![true, false], true),
+            (vec![true, true], false),
+        ]);
+
+        assert!(logic::truth_table(&parse("x or z"), &vars(&["x"])).is_err());
+        let many: Vec<String> = (0..=MAX_TRUTH_TABLE_VARIABLES).map(|i| format!("v{}", i)).collect();
+        assert!(logic::truth_table(&parse("v0"), &many).is_err());
+    }
+
+    #[test]
+    fn test_normal_forms() {
+        let expr = parse("(a or b) and not (a and c)");
+        let dnf = logic::to_dnf(&expr);
+        let cnf = logic::to_cnf(&expr);
+        assert_eq!(dnf.to_string(), "a && !c || b && !a || b && !c");
+        assert_eq!(cnf.to_string(), "(a || b) && (!a || !c)");
+        assert_same_truth(&dnf, &expr, &["a", "b", "c"]);
+        assert_same_truth(&cnf, &expr, &["a", "b", "c"]);
+
+        assert_eq!(logic::to_dnf(&parse("x and not x")).to_string(), "0");
+        assert_eq!(logic::to_cnf(&parse("x or not x")).to_string(), "1");
+        assert_eq!(logic::to_dnf(&parse("x or (x and y)")).to_string(), "x");
+    }
+}
@@ -5,6 +5,7 @@
 use crate::core::{Expression, Number};
 use super::{ComputeError, ComputeEngine};
 use num_traits::{Zero};
+use crate::tr;
 
 /// 矩阵和向量运算引擎
 pub struct MatrixEngine {
@@ -33,7 +34,7 @@ impl MatrixEngine {
         // 检查矩阵维度是否匹配
         if rows_a != rows_b || cols_a != cols_b {
             return Err(ComputeError::dimension_mismatch(
-                &format!("矩阵维度不匹配：{}×{} 和 {}×{}", rows_a, cols_a, rows_b, cols_b)
+                tr!("matrix.shape_mismatch", left_rows = rows_a, left_cols = cols_a, right_rows = rows_b, right_cols = cols_b)
             ));
         }
         
@@ -62,7 +63,7 @@ impl MatrixEngine {
         // 检查矩阵乘法的维度要求：A的列数必须等于B的行数
         if cols_a != rows_b {
             return Err(ComputeError::dimension_mismatch(
-                &format!("矩阵乘法维度不匹配：{}×{} 和 {}×{}", rows_a, cols_a, rows_b, cols_b)
+                tr!("matrix.multiply_mismatch", left_rows = rows_a, left_cols = cols_a, right_rows = rows_b, right_cols = cols_b)
             ));
         }
         
@@ -95,7 +96,7 @@ impl MatrixEngine {
         // 检查是否为方阵
         if rows != cols {
             return Err(ComputeError::dimension_mismatch(
-                &format!("行列式只能计算方阵，当前矩阵为 {}×{}", rows, cols)
+                tr!("matrix.determinant_square", rows = rows, cols = cols)
             ));
         }
         
@@ -108,7 +109,7 @@ impl MatrixEngine {
         let n = matrix.len();
         
         match n {
-            0 => Err(ComputeError::domain_error("空矩阵无法计算行列式")),
+            0 => Err(ComputeError::domain_error(tr!("matrix.empty_determinant"))),
             1 => Ok(matrix[0][0].clone()),
             2 => {
                 // 2×2矩阵：ad - bc
@@ -147,7 +148,7 @@ impl MatrixEngine {
     fn get_minor(&self, matrix: &[Vec<Expression>], row: usize, col: usize) -> Result<Vec<Vec<Expression>>, ComputeError> {
         let n = matrix.len();
         if row >= n || col >= n {
-            return Err(ComputeError::domain_error("行或列索引超出范围"));
+            return Err(ComputeError::domain_error(tr!("matrix.index_out_of_range")));
         }
         
         let mut minor = Vec::with_capacity(n - 1);
@@ -175,7 +176,7 @@ impl MatrixEngine {
         // 检查是否为方阵
         if rows != cols {
             return Err(ComputeError::dimension_mismatch(
-                &format!("只有方阵才能求逆，当前矩阵为 {}×{}", rows, cols)
+                tr!("matrix.inverse_square", rows = rows, cols = cols)
             ));
         }
         
@@ -186,7 +187,7 @@ impl MatrixEngine {
         
         // 检查行列式是否为零
         if self.is_zero_expression(&det)? {
-            return Err(ComputeError::domain_error("矩阵不可逆（行列式为零）"));
+            return Err(ComputeError::domain_error(tr!("matrix.singular")));
         }
         
         match n {
@@ -277,7 +278,7 @@ impl MatrixEngine {
         // 检查向量维度是否匹配
         if elements_a.len() != elements_b.len() {
             return Err(ComputeError::dimension_mismatch(
-                &format!("向量维度不匹配：{} 和 {}", elements_a.len(), elements_b.len())
+                tr!("matrix.vector_mismatch", left = elements_a.len(), right = elements_b.len())
             ));
         }
         
@@ -299,7 +300,7 @@ impl MatrixEngine {
         // 检查是否为3维向量
         if elements_a.len() != 3 || elements_b.len() != 3 {
             return Err(ComputeError::dimension_mismatch(
-                "叉积只适用于3维向量"
+                tr!("matrix.cross_requires_3d")
             ));
         }
         
@@ -367,7 +368,7 @@ impl MatrixEngine {
         // 检查是否为方阵
         if rows != cols {
             return Err(ComputeError::dimension_mismatch(
-                &format!("矩阵的迹只能计算方阵，当前矩阵为 {}×{}", rows, cols)
+                tr!("matrix.trace_square", rows = rows, cols = cols)
             ));
         }
         
@@ -385,26 +386,26 @@ impl MatrixEngine {
         match expr {
             Expression::Matrix(elements) => {
                 if elements.is_empty() {
-                    return Err(ComputeError::domain_error("矩阵不能为空"));
+                    return Err(ComputeError::domain_error(tr!("expression.empty_matrix")));
                 }
                 let rows = elements.len();
                 let cols = elements[0].len();
                 if cols == 0 {
-                    return Err(ComputeError::domain_error("矩阵行不能为空"));
+                    return Err(ComputeError::domain_error(tr!("expression.empty_matrix_row")));
                 }
                 
                 // 检查所有行的列数是否相同
                 for (i, row) in elements.iter().enumerate() {
                     if row.len() != cols {
                         return Err(ComputeError::dimension_mismatch(
-                            &format!("矩阵第{}行的列数({})与第一行的列数({})不匹配", i + 1, row.len(), cols)
+                            tr!("expression.ragged_matrix", row = i + 1, len = row.len(), cols = cols)
                         ));
                     }
                 }
                 
                 Ok((rows, cols, elements.clone()))
             }
-            _ => Err(ComputeError::domain_error("表达式不是矩阵类型"))
+            _ => Err(ComputeError::domain_error(tr!("matrix.not_a_matrix")))
         }
     }
     
@@ -413,14 +414,14 @@ impl MatrixEngine {
         match expr {
             Expression::Vector(elements) => {
                 if elements.is_empty() {
-                    return Err(ComputeError::domain_error("向量不能为空"));
+                    return Err(ComputeError::domain_error(tr!("expression.empty_vector")));
                 }
                 Ok(elements.clone())
             }
             // 也可以将单列矩阵视为向量
             Expression::Matrix(elements) => {
                 if elements.is_empty() {
-                    return Err(ComputeError::domain_error("矩阵不能为空"));
+                    return Err(ComputeError::domain_error(tr!("expression.empty_matrix")));
                 }
                 if elements[0].len() == 1 {
                     // 单列矩阵，转换为向量
@@ -432,10 +433,10 @@ impl MatrixEngine {
                     // 单行矩阵，转换为向量
                     Ok(elements[0].clone())
                 } else {
-                    Err(ComputeError::dimension_mismatch("矩阵不能转换为向量（必须是单行或单列矩阵）"))
+                    Err(ComputeError::dimension_mismatch(tr!("matrix.not_a_vector_shape")))
                 }
             }
-            _ => Err(ComputeError::domain_error("表达式不是向量类型"))
+            _ => Err(ComputeError::domain_error(tr!("matrix.not_a_vector")))
         }
     }
     
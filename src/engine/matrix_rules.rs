@@ -0,0 +1,326 @@
+//! # 矩阵符号化简规则
+//!
+//! 识别单位阵、零阵，并展开乘积的转置、逆与行列式。依赖维度的规则只在维度可由
+//! 表达式静态推出且相容时应用；符号矩阵的维度未知，这类规则保守跳过。
+//!
+//! 转置、行列式、逆与迹同时接受运算符形式（`UnaryOperator::Transpose` 等）和解析器产生的
+//! 函数形式（`transpose(A)`、`det(A)`、`inverse(A)`/`inv(A)`、`tr(A)`），化简结果沿用输入的形式。
+//!
+//! 下标访问 `A[i, j]`、`v[i]` 与张量的 `T[i, j, k]` 下标从 1 开始，`:` 取整行或整列。
+
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator, Tensor};
+use crate::core::tensor::format_shape;
+use crate::engine::ComputeError;
+use num_traits::{Signed, ToPrimitive};
+use crate::tr;
+
+/// 矩阵维度：（行数, 列数）
+pub type Shape = (usize, usize);
+
+/// 静态推断表达式的矩阵维度；非矩阵或含维度未知的符号矩阵时返回 None
+pub fn shape(expr: &Expression) -> Option<Shape> {
+    match expr {
+        Expression::Matrix(rows) => rows.first().map(|row| (rows.len(), row.len())),
+        Expression::BinaryOp { op: BinaryOperator::Multiply | BinaryOperator::MatrixMultiply, left, right } => {
+            match (shape(left), shape(right)) {
+                (Some((m, n)), Some((p, q))) => (n == p).then_some((m, q)),
+                (Some(s), None) if is_scalar(right) => Some(s),
+                (None, Some(s)) if is_scalar(left) => Some(s),
+                _ => None,
+            }
+        }
+        Expression::BinaryOp { op: BinaryOperator::Add | BinaryOperator::Subtract, left, right } => {
+            let s = shape(left)?;
+            (shape(right)? == s).then_some(s)
+        }
+        Expression::BinaryOp { op: BinaryOperator::Power, left, right } if is_integer(right) => {
+            square(left).map(|n| (n, n))
+        }
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => shape(operand),
+        _ => match operation(expr)? {
+            (UnaryOperator::Transpose, operand) => shape(operand).map(|(m, n)| (n, m)),
+            (UnaryOperator::Inverse, operand) => square(operand).map(|n| (n, n)),
+            _ => None,
+        },
+    }
+}
+
+/// 若表达式是 n×n 单位矩阵，返回 n
+pub fn identity_size(expr: &Expression) -> Option<usize> {
+    let Expression::Matrix(rows) = expr else { return None };
+    let n = rows.len();
+    let is_identity = n > 0 && rows.iter().enumerate().all(|(i, row)| {
+        row.len() == n && row.iter().enumerate().all(|(j, elem)| match elem {
+            Expression::Number(x) => if i == j { x.is_one() } else { x.is_zero() },
+            _ => false,
+        })
+    });
+    is_identity.then_some(n)
+}
+
+/// 是否为零矩阵
+pub fn is_zero_matrix(expr: &Expression) -> bool {
+    matches!(expr, Expression::Matrix(rows) if !rows.is_empty()
+        && rows.iter().flatten().all(|elem| matches!(elem, Expression::Number(x) if x.is_zero())))
+}
+
+/// 化简矩阵乘积 `left * right`：单位阵消去、零阵吸收，两边维度都已知且相容时才应用
+pub fn simplify_product(left: &Expression, right: &Expression) -> Option<Expression> {
+    let ((m, n), (p, q)) = (shape(left)?, shape(right)?);
+    if n != p {
+        return None;
+    }
+    if identity_size(left).is_some() {
+        Some(right.clone())
+    } else if identity_size(right).is_some() {
+        Some(left.clone())
+    } else if is_zero_matrix(left) || is_zero_matrix(right) {
+        Some(Expression::zero_matrix(m, q))
+    } else {
+        None
+    }
+}
+
+/// 化简 `base ^ exponent`：单位阵的整数次幂仍是单位阵
+pub fn simplify_power(base: &Expression, exponent: &Expression) -> Option<Expression> {
+    (identity_size(base).is_some() && is_integer(exponent)).then(|| base.clone())
+}
+
+/// 化简转置、行列式、逆或迹；`expr` 不是这些运算或没有规则可用时返回 None
+pub fn simplify_operation(expr: &Expression) -> Option<Expression> {
+    let (op, operand) = operation(expr)?;
+    match op {
+        UnaryOperator::Transpose => simplify_transpose(expr, operand),
+        UnaryOperator::Determinant => simplify_determinant(expr, operand),
+        UnaryOperator::Inverse => simplify_inverse(expr, operand),
+        UnaryOperator::Trace => simplify_trace(operand),
+        _ => None,
+    }
+}
+
+/// 化简下标访问 `target[indices]`：对具体矩阵、向量取出元素、整行或整列，并把 `transpose(A)[m, n]`
+/// 改写为 `A[n, m]`。下标为符号时返回 None；下标不是正整数、个数不对或越界时报错。
+///
+/// `i`、`j` 解析为虚数单位，不能用作符号下标，与 `pi`、`e` 等常量一样按非正整数下标报错
+pub fn simplify_index(target: &Expression, indices: &[Expression]) -> Result<Option<Expression>, ComputeError> {
+    if let Some(index) = indices.iter().find(|index| !index.is_index_all() && index.is_constant() && !is_positive_integer(index)) {
+        return Err(ComputeError::domain_error(tr!("matrix.subscript_not_positive", index = index)));
+    }
+    if let (Some((UnaryOperator::Transpose, inner)), [i, j]) = (operation(target), indices) {
+        let swapped = [j.clone(), i.clone()];
+        let rewritten = simplify_index(inner, &swapped)?;
+        return Ok(Some(rewritten.unwrap_or_else(|| Expression::index(inner.clone(), swapped.to_vec()))));
+    }
+    let out_of_bounds = |dimensions: String| {
+        let indices: Vec<String> = indices.iter().map(|i| i.to_string()).collect();
+        ComputeError::dimension_mismatch(tr!("matrix.subscript_out_of_bounds", indices = indices.join(", "), dimensions = dimensions))
+    };
+    match target {
+        // 3 维及以上的张量 `T[i, j, k]`，下标个数等于维数，`:` 保留整个维度
+        Expression::Vector(elements) if indices.len() > 1 && elements.iter().any(|e| matches!(e, Expression::Vector(_) | Expression::Matrix(_))) => {
+            let tensor = Tensor::from_nested(target).map_err(ComputeError::dimension_mismatch)?;
+            if indices.len() != tensor.rank() {
+                return Err(ComputeError::dimension_mismatch(tr!(
+                    "matrix.tensor_subscript_count", rank = tensor.rank(), count = indices.len()
+                )));
+            }
+            let mut selection = Vec::with_capacity(indices.len());
+            for (index, &len) in indices.iter().zip(tensor.shape()) {
+                match subscript(index, len).ok_or_else(|| out_of_bounds(tr!("matrix.tensor_shape", shape = format_shape(tensor.shape()))))?? {
+                    Subscript::All => selection.push(None),
+                    Subscript::At(i) => selection.push(Some(i)),
+                    Subscript::Symbolic => return Ok(None),
+                }
+            }
+            Ok(tensor.slice(&selection).map(|t| t.to_expression()))
+        }
+        Expression::Vector(elements) => {
+            let [index] = indices else {
+                return Err(ComputeError::dimension_mismatch(tr!("matrix.vector_subscript_count", count = indices.len())));
+            };
+            Ok(match subscript(index, elements.len()).ok_or_else(|| out_of_bounds(tr!("matrix.vector_length", len = elements.len())))?? {
+                Subscript::All => Some(target.clone()),
+                Subscript::At(i) => Some(elements[i].clone()),
+                Subscript::Symbolic => None,
+            })
+        }
+        Expression::Matrix(rows) => {
+            let (m, n) = shape(target).unwrap_or((rows.len(), 0));
+            let [row, col] = indices else {
+                return Err(ComputeError::dimension_mismatch(tr!("matrix.matrix_subscript_count", count = indices.len())));
+            };
+            let dimensions = || out_of_bounds(tr!("matrix.matrix_shape", rows = m, cols = n));
+            let row = subscript(row, m).ok_or_else(dimensions)??;
+            let col = subscript(col, n).ok_or_else(dimensions)??;
+            Ok(match (row, col) {
+                (Subscript::At(i), Subscript::At(j)) => Some(rows[i][j].clone()),
+                (Subscript::At(i), Subscript::All) => Some(Expression::Vector(rows[i].clone())),
+                (Subscript::All, Subscript::At(j)) => Some(Expression::Vector(rows.iter().map(|r| r[j].clone()).collect())),
+                (Subscript::All, Subscript::All) => Some(target.clone()),
+                _ => None,
+            })
+        }
+        _ => Ok(None),
+    }
+}
+
+/// 一个维度上的下标
+enum Subscript {
+    /// `:`
+    All,
+    /// 从 0 开始的位置
+    At(usize),
+    /// 符号下标
+    Symbolic,
+}
+
+/// 解析长度为 `len` 的维度上的下标；越界时返回 None，不是正整数时报错
+fn subscript(index: &Expression, len: usize) -> Option<Result<Subscript, ComputeError>> {
+    if index.is_index_all() {
+        return Some(Ok(Subscript::All));
+    }
+    let Expression::Number(number) = index else {
+        return Some(Ok(Subscript::Symbolic));
+    };
+    let position = match number {
+        Number::Integer(i) => i.to_usize().filter(|&i| i >= 1),
+        _ => None,
+    };
+    match position {
+        Some(i) if i <= len => Some(Ok(Subscript::At(i - 1))),
+        Some(_) => None,
+        None => Some(Err(ComputeError::domain_error(tr!("matrix.subscript_not_positive", index = index)))),
+    }
+}
+
+/// 下标是否为正整数
+fn is_positive_integer(index: &Expression) -> bool {
+    matches!(index, Expression::Number(Number::Integer(i)) if i.is_positive())
+}
+
+/// 转置：(A^T)^T = A、I^T = I、零阵转置，以及对任意可乘的两边都成立的 (A*B)^T = B^T*A^T
+fn simplify_transpose(expr: &Expression, operand: &Expression) -> Option<Expression> {
+    if is_scalar(operand) {
+        return Some(operand.clone());
+    }
+    if let Some((UnaryOperator::Transpose, inner)) = operation(operand) {
+        return Some(inner.clone());
+    }
+    if identity_size(operand).is_some() {
+        return Some(operand.clone());
+    }
+    if is_zero_matrix(operand) {
+        let (m, n) = shape(operand)?;
+        return Some(Expression::zero_matrix(n, m));
+    }
+    let (op, a, b) = product(operand)?;
+    if is_scalar(a) {
+        return Some(Expression::binary_op(op, a.clone(), apply(expr, b)));
+    }
+    if let (Some((_, n)), Some((p, _))) = (shape(a), shape(b)) {
+        if n != p {
+            return None;
+        }
+    }
+    Some(Expression::binary_op(op, apply(expr, b), apply(expr, a)))
+}
+
+/// 行列式：det(I) = 1、det(0) = 0，两个因子是同阶方阵时 det(A*B) = det(A)*det(B)
+fn simplify_determinant(expr: &Expression, operand: &Expression) -> Option<Expression> {
+    if identity_size(operand).is_some() {
+        return Some(Expression::Number(Number::one()));
+    }
+    if is_zero_matrix(operand) && square(operand).is_some() {
+        return Some(Expression::Number(Number::zero()));
+    }
+    let (_, a, b) = product(operand)?;
+    same_square(a, b)?;
+    Some(Expression::multiply(apply(expr, a), apply(expr, b)))
+}
+
+/// 逆：(A^-1)^-1 = A、I^-1 = I，两个因子是同阶方阵时 (A*B)^-1 = B^-1*A^-1
+fn simplify_inverse(expr: &Expression, operand: &Expression) -> Option<Expression> {
+    if let Some((UnaryOperator::Inverse, inner)) = operation(operand) {
+        return Some(inner.clone());
+    }
+    if identity_size(operand).is_some() {
+        return Some(operand.clone());
+    }
+    let (op, a, b) = product(operand)?;
+    same_square(a, b)?;
+    Some(Expression::binary_op(op, apply(expr, b), apply(expr, a)))
+}
+
+/// 迹：tr(I_n) = n、tr(0) = 0
+fn simplify_trace(operand: &Expression) -> Option<Expression> {
+    if let Some(n) = identity_size(operand) {
+        return Some(Expression::Number(Number::integer(n as i64)));
+    }
+    square(operand).filter(|_| is_zero_matrix(operand)).map(|_| Expression::Number(Number::zero()))
+}
+
+/// 识别转置、行列式、迹、逆（运算符或函数形式），返回运算和操作数
+fn operation(expr: &Expression) -> Option<(UnaryOperator, &Expression)> {
+    match expr {
+        Expression::UnaryOp { op, operand } => match op {
+            UnaryOperator::Transpose | UnaryOperator::Determinant
+            | UnaryOperator::Inverse | UnaryOperator::Trace => Some((op.clone(), operand.as_ref())),
+            _ => None,
+        },
+        Expression::Function { name, args } if args.len() == 1 => {
+            let op = match name.as_str() {
+                "transpose" => UnaryOperator::Transpose,
+                "det" => UnaryOperator::Determinant,
+                "inverse" | "inv" => UnaryOperator::Inverse,
+                "trace" | "tr" => UnaryOperator::Trace,
+                _ => return None,
+            };
+            Some((op, &args[0]))
+        }
+        _ => None,
+    }
+}
+
+/// 以 `template` 相同的形式对 `operand` 施加同一运算，并继续化简
+fn apply(template: &Expression, operand: &Expression) -> Expression {
+    let expr = match template {
+        Expression::Function { name, .. } => Expression::function(name.clone(), vec![operand.clone()]),
+        Expression::UnaryOp { op, .. } => Expression::unary_op(op.clone(), operand.clone()),
+        _ => return operand.clone(),
+    };
+    simplify_operation(&expr).unwrap_or(expr)
+}
+
+/// 拆出矩阵乘积的两个因子
+fn product(expr: &Expression) -> Option<(BinaryOperator, &Expression, &Expression)> {
+    match expr {
+        Expression::BinaryOp { op: op @ (BinaryOperator::Multiply | BinaryOperator::MatrixMultiply), left, right } => {
+            Some((op.clone(), left.as_ref(), right.as_ref()))
+        }
+        _ => None,
+    }
+}
+
+/// 方阵的阶数
+fn square(expr: &Expression) -> Option<usize> {
+    shape(expr).and_then(|(m, n)| (m == n).then_some(n))
+}
+
+/// 两个表达式是同阶方阵时返回阶数
+fn same_square(a: &Expression, b: &Expression) -> Option<usize> {
+    let n = square(a)?;
+    (square(b)? == n).then_some(n)
+}
+
+/// 可以确定是标量的表达式（数值与数学常量）
+fn is_scalar(expr: &Expression) -> bool {
+    matches!(expr, Expression::Number(_) | Expression::Constant(_))
+}
+
+fn is_integer(expr: &Expression) -> bool {
+    matches!(expr, Expression::Number(Number::Integer(_)))
+}
+
+#[cfg(test)]
+#[path = "matrix_rules_tests.rs"]
+mod matrix_rules_tests;
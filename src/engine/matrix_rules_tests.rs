@@ -0,0 +1,100 @@
+//! # 矩阵化简规则测试
+//!
+//! 测试单位阵、零阵的识别，乘积的转置、逆、行列式展开，以及符号维度时的保守跳过。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::matrix_rules;
+    use crate::engine::simplify::Simplifier;
+    use crate::core::{Expression, UnaryOperator};
+    use crate::parser::{Parser, syntax::ExpressionParser};
+
+    fn simplify(input: &str) -> String {
+        let expr = ExpressionParser::new().parse(input).unwrap();
+        Simplifier::new().simplify(&expr).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_identity_and_zero() {
+        assert_eq!(simplify("[[a, b], [c, d]] * I_2"), "[[a, b], [c, d]]");
+        assert_eq!(simplify("eye(2) * [[a, b, c], [d, e, f]]"), "[[a, b, c], [d, e, f]]");
+        assert_eq!(simplify("[[a, b]] * zeros(2, 3)"), "[[0, 0, 0]]");
+        assert_eq!(simplify("I_3^5"), simplify("I_3"));
+        assert_eq!(simplify("det(I_3)"), "1");
+        assert_eq!(simplify("tr(eye(4))"), "4");
+        assert_eq!(simplify("transpose(zeros(2, 3))"), simplify("zeros(3, 2)"));
+    }
+
+    #[test]
+    fn test_product_rules() {
+        assert_eq!(simplify("transpose(A * B)"), "transpose(B) * transpose(A)");
+        assert_eq!(simplify("transpose(2 * A)"), "2 * transpose(A)");
+        assert_eq!(simplify("transpose(transpose(A))"), "A");
+        assert_eq!(
+            simplify("det([[a, b], [c, d]] * [[x, 0], [0, y]])"),
+            "det([[a, b], [c, d]]) * det([[x, 0], [0, y]])"
+        );
+        assert_eq!(
+            simplify("inverse([[a, b], [c, d]] * I_2 * [[x, 0], [0, y]])"),
+            "inverse([[x, 0], [0, y]]) * inverse([[a, b], [c, d]])"
+        );
+
+        let a = Expression::variable("A");
+        let product = Expression::multiply(Expression::identity_matrix(2), a.clone());
+        let transposed = Expression::unary_op(UnaryOperator::Transpose, Expression::multiply(a.clone(), Expression::variable("B")));
+        let expected = Expression::multiply(
+            Expression::unary_op(UnaryOperator::Transpose, Expression::variable("B")),
+            Expression::unary_op(UnaryOperator::Transpose, a),
+        );
+        assert_eq!(matrix_rules::simplify_operation(&transposed), Some(expected));
+        assert!(matrix_rules::shape(&product).is_none());
+    }
+
+    #[test]
+    fn test_incompatible_or_symbolic_dimensions() {
+        // 维度不相容时不消去单位阵
+        assert_eq!(simplify("[[a, b], [c, d]] * I_3"), "[[a, b], [c, d]] * [[1, 0, 0], [0, 1, 0], [0, 0, 1]]");
+        // 符号矩阵的维度未知，保守跳过
+        assert_eq!(simplify("A * eye(2)"), "A * [[1, 0], [0, 1]]");
+        assert!(simplify("det(A * B)").starts_with("det("));
+        assert!(simplify("inverse(A * B)").starts_with("inverse("));
+        // 非方阵的乘积不能拆开行列式
+        assert!(simplify("det([[a, b]] * [[c], [d]])").starts_with("det("));
+    }
+
+    #[test]
+    fn test_indexing() {
+        assert_eq!(simplify("[[1, 2], [3, 4]][2, 1]"), "3");
+        assert_eq!(simplify("[a, b, c][3]"), "c");
+        assert_eq!(simplify("[[1, 2], [3, 4]][1, :]"), "[1, 2]");
+        assert_eq!(simplify("[[1, 2], [3, 4]][:, 2]"), "[2, 4]");
+        assert_eq!(simplify("transpose(A)[1, 2]"), "A[2, 1]");
+        // 符号下标保持不动
+        assert_eq!(simplify("[[1, 2], [3, 4]][k, 1]"), "[[1, 2], [3, 4]][k, 1]");
+
+        // 代入具体矩阵后取出元素
+        let expr = ExpressionParser::new().parse("A[1, 2] + 1").unwrap();
+        let matrix = ExpressionParser::new().parse("[[5, 6], [7, 8]]").unwrap();
+        let substituted = expr.substitute(&[("A".to_string(), matrix)].into_iter().collect());
+        assert_eq!(Simplifier::new().simplify(&substituted).unwrap().to_string(), "7");
+    }
+
+    #[test]
+    fn test_indexing_errors() {
+        let error = |input: &str| {
+            let expr = ExpressionParser::new().parse(input).unwrap();
+            Simplifier::new().simplify(&expr).unwrap_err().to_string()
+        };
+        let out_of_bounds = error("[[1, 2], [3, 4]][3, 1]");
+        assert!(out_of_bounds.contains("[3, 1]") && out_of_bounds.contains("2×2"), "{}", out_of_bounds);
+        assert!(error("[a, b][0]").contains("从 1 开始"));
+        assert!(error("[[1, 2], [3, 4]][1]").contains("2 个下标"));
+        // i、j 是虚数单位，pi 是常量，都不是正整数下标
+        assert!(error("[[1, 2], [3, 4]][i, 1]").contains("从 1 开始"));
+        assert!(error("[[1, 2], [3, 4]][1, j]").contains("从 1 开始"));
+        assert!(error("[[1, 2], [3, 4]][pi, 1]").contains("从 1 开始"));
+        assert!(error("[a, b][2*i]").contains("从 1 开始"));
+        assert!(error("transpose(A)[i, j]").contains("从 1 开始"));
+        assert!(error("A[1.5, 1]").contains("从 1 开始"));
+    }
+}
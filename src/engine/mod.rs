@@ -7,12 +7,40 @@ pub mod compute;
 pub mod cached_compute;
 pub mod enhanced_compute;
 pub mod simplify;
+pub mod assumptions;
 pub mod enhanced_simplify;
 pub mod calculus;
 pub mod algebra;
 pub mod polynomial;
 pub mod number_theory;
+pub mod solver;
+pub mod domain;
+pub mod extrema;
+pub mod equivalence;
+pub mod exercise;
+pub mod laplace;
+pub mod logic;
+pub mod partial_fractions;
+pub mod random;
+pub mod special_functions;
+pub mod summation;
+pub mod trace;
 pub mod matrix;
+pub mod matrix_rules;
+pub mod modular;
+pub mod resultant;
+pub mod conic;
+pub mod equation;
+pub mod orthogonal;
+pub mod units;
+pub mod groebner;
+pub mod optimization;
+pub mod trigonometry;
+pub mod complex;
+pub mod vector_calculus;
+pub mod distributions;
+pub mod lists;
+pub mod tensors;
 pub mod cache;
 pub mod lazy;
 pub mod parallel;
@@ -32,8 +60,28 @@ pub use enhanced_compute::EnhancedComputeEngine;
 pub use lazy::{LazyExpression, DependencyGraph, LazyState, DependencyGraphStats};
 pub use parallel::{ParallelComputeEngine, TaskScheduler, ComputeTask, SchedulerStats, ExpressionPreprocessor, ParallelizationAnalysis};
 pub use matrix::MatrixEngine;
+pub use solver::{SolverEngine, Solution, SolutionKind};
+pub use domain::{DomainAnalyzer, DomainAnalysis};
+pub use extrema::{ExtremaAnalyzer, Extremum, ExtremumKind};
+pub use equivalence::{EquivalenceChecker, Equivalence, Counterexample, IdentityResult, VerifiedIntegral};
+pub use exercise::ExerciseKind;
+pub use summation::{SummationEngine, SeriesSum, Convergence};
+pub use partial_fractions::PartialFractionEngine;
+pub use laplace::{LaplaceEngine, LaplaceTransform};
+pub use logic::TruthTable;
+pub use modular::Congruence;
+pub use distributions::Distribution;
+pub use groebner::MonomialOrder;
+pub use conic::{Conic, ConicKind, ConicCase};
+pub use equation::EquationStep;
+pub use orthogonal::OrthogonalFamily;
+pub use units::{UnitSystem, Quantity, Dimension};
+pub use optimization::{Optimizer, Optimum};
+pub use trace::TraceEvent;
+pub use simplify::SimplifyOptions;
+pub use assumptions::{Assumptions, Property};
 pub use enhanced_simplify::EnhancedSimplifier;
-pub use runtime_enhancement::{RuntimeEnhancer, VariableManager, ComplexityAnalyzer, RuntimeConfig};
+pub use runtime_enhancement::{RuntimeEnhancer, VariableManager, UserFunction, ComplexityAnalyzer, RuntimeConfig};
 pub use runtime_enhanced_engine::RuntimeEnhancedEngine;
 
 /// 计算引擎 trait
@@ -43,6 +91,9 @@ pub trait ComputeEngine: Send + Sync {
     /// 简化表达式
     fn simplify(&self, expr: &Expression) -> Result<Expression, ComputeError>;
     
+    /// 简化表达式并按顺序记录每条生效的规则
+    fn simplify_traced(&self, expr: &Expression) -> Result<(Expression, Vec<TraceEvent>), ComputeError>;
+    
     /// 计算表达式的值
     fn evaluate(&self, expr: &Expression, vars: &HashMap<String, Number>) 
         -> Result<Number, ComputeError>;
@@ -63,6 +114,27 @@ pub trait ComputeEngine: Send + Sync {
     fn series(&self, expr: &Expression, var: &str, point: &Expression, order: usize) 
         -> Result<Expression, ComputeError>;
     
+    /// 求和，上限可以为正无穷（无穷级数）
+    fn sum(&self, expr: &Expression, var: &str, lower: &Expression, upper: &Expression) 
+        -> Result<SeriesSum, ComputeError>;
+    
+    /// Laplace 变换，不在变换表内的部分保留为符号形式
+    fn laplace(&self, expr: &Expression, t: &str, s: &str) 
+        -> Result<LaplaceTransform, ComputeError>;
+    
+    /// Laplace 逆变换，先做部分分式分解再查表
+    fn inverse_laplace(&self, expr: &Expression, s: &str, t: &str) 
+        -> Result<Expression, ComputeError>;
+    
+    /// 布尔表达式的真值表，赋值按 `vars` 的顺序排列
+    fn truth_table(&self, expr: &Expression, vars: &[String]) -> Result<TruthTable, ComputeError>;
+    
+    /// 转换为析取范式
+    fn to_dnf(&self, expr: &Expression) -> Result<Expression, ComputeError>;
+    
+    /// 转换为合取范式
+    fn to_cnf(&self, expr: &Expression) -> Result<Expression, ComputeError>;
+    
     /// 数值计算
     fn numerical_evaluate(&self, expr: &Expression, vars: &HashMap<String, f64>) 
         -> Result<f64, ComputeError>;
@@ -97,12 +169,21 @@ pub trait ComputeEngine: Send + Sync {
     /// 计算最小公倍数
     fn lcm(&self, a: &Expression, b: &Expression) -> Result<Expression, ComputeError>;
     
+    /// 计算多个数的最大公约数
+    fn gcd_list(&self, values: &[Expression]) -> Result<Expression, ComputeError>;
+    
+    /// 计算多个数的最小公倍数
+    fn lcm_list(&self, values: &[Expression]) -> Result<Expression, ComputeError>;
+    
     /// 判断是否为素数
     fn is_prime(&self, n: &Expression) -> Result<bool, ComputeError>;
     
     /// 质因数分解
     fn prime_factors(&self, n: &Expression) -> Result<Vec<Expression>, ComputeError>;
     
+    /// 设定概率算法（Miller-Rabin、Pollard rho）的随机种子，使结果可复现
+    fn set_random_seed(&self, seed: u64);
+    
     /// 计算二项式系数
     fn binomial(&self, n: &Expression, k: &Expression) -> Result<Expression, ComputeError>;
     
@@ -123,6 +204,27 @@ pub trait ComputeEngine: Send + Sync {
     /// 求解单变量方程
     fn solve(&self, equation: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError>;
     
+    /// 求解单变量方程，返回根的重数与验证信息
+    fn solve_detailed(&self, equation: &Expression, var: &str) -> Result<Vec<Solution>, ComputeError>;
+    
+    /// 分析表达式关于变量的实数定义域
+    fn domain(&self, expr: &Expression, var: &str) -> Result<DomainAnalysis, ComputeError>;
+    
+    /// 估计表达式在给定定义域上的值域
+    fn range_estimate(&self, expr: &Expression, var: &str, domain: &Expression) -> Result<DomainAnalysis, ComputeError>;
+    
+    /// 求临界点（一阶导为零或不存在的点）
+    fn critical_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError>;
+    
+    /// 求局部极值及其类型
+    fn local_extrema(&self, expr: &Expression, var: &str) -> Result<Vec<Extremum>, ComputeError>;
+    
+    /// 求拐点（二阶导变号的点）
+    fn inflection_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError>;
+    
+    /// 判断两个表达式是否恒等，不恒等时给出反例
+    fn are_equivalent(&self, lhs: &Expression, rhs: &Expression) -> Result<Equivalence, ComputeError>;
+    
     /// 求解多变量方程组
     fn solve_system(&self, equations: &[Expression], vars: &[String]) 
         -> Result<Vec<HashMap<String, Expression>>, ComputeError>;
@@ -0,0 +1,176 @@
+//! # 模算术
+//!
+//! `mod(a, n)`（也写作 `Mod(a, n)` 或 `a % n`）在模数为正整数时的代数化简：
+//! 和、差、积逐项归约，嵌套的同模取余合并，整数幂用快速幂，具体整数直接归约到 `[0, n)`，
+//! 含符号的部分保留表达式形式。另提供同余判定 `congruent(a, b, n)` 与线性同余方程求解。
+
+use std::collections::HashMap;
+use std::fmt;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator};
+use super::ComputeError;
+use super::simplify::Simplifier;
+use crate::tr;
+
+/// 线性同余方程的解：`x ≡ residue (mod modulus)`，`residue` 位于 `[0, modulus)`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Congruence {
+    /// 余数
+    pub residue: BigInt,
+    /// 模数
+    pub modulus: BigInt,
+}
+
+impl fmt::Display for Congruence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} (mod {})", self.residue, self.modulus)
+    }
+}
+
+/// 是否为取余函数名
+pub fn is_mod_function(name: &str) -> bool {
+    matches!(name, "mod" | "Mod")
+}
+
+/// 在模 `modulus` 下归约 `dividend`，模数不是正整数时返回 `None`
+///
+/// 结果与 `dividend` 同余：为整数时已落在 `[0, n)`，否则为去掉冗余取余、常数已归约的表达式，
+/// 调用方需自行套回取余
+pub fn reduce(dividend: &Expression, modulus: &Expression) -> Option<Expression> {
+    let n = as_integer(modulus).filter(|n| n.is_positive())?;
+    Some(if n.is_one() { integer(BigInt::zero()) } else { residue(dividend, &n) })
+}
+
+/// 化简取余：整数结果直接返回，否则用 `rebuild` 套回取余
+pub fn simplify_mod(dividend: &Expression, modulus: &Expression, rebuild: impl FnOnce(Expression) -> Expression) -> Option<Expression> {
+    reduce(dividend, modulus).map(|reduced| match reduced {
+        Expression::Number(_) => reduced,
+        _ => rebuild(reduced),
+    })
+}
+
+/// 判定 `a ≡ b (mod n)`，含符号而无法判定时返回 `None`
+pub fn congruent(a: &Expression, b: &Expression, modulus: &Expression) -> Option<bool> {
+    let n = as_integer(modulus).filter(|n| n.is_positive())?;
+    let difference = residue(&Expression::subtract(a.clone(), b.clone()), &n);
+    match as_integer(&difference) {
+        Some(d) => Some(d.is_zero()),
+        None if residue(a, &n) == residue(b, &n) => Some(true),
+        None => None,
+    }
+}
+
+/// `a` 模 `n` 的逆元，不互素时返回 `None`
+pub fn mod_inverse(a: &BigInt, n: &BigInt) -> Option<BigInt> {
+    let extended = a.mod_floor(n).extended_gcd(n);
+    extended.gcd.is_one().then(|| extended.x.mod_floor(n))
+}
+
+/// 解线性同余方程 `a·x ≡ b (mod n)`，无解时返回 `None`
+///
+/// 记 g = gcd(a, n)，有解当且仅当 g | b，此时解为模 n/g 的一个剩余类
+pub fn solve_linear(a: &BigInt, b: &BigInt, n: &BigInt) -> Option<Congruence> {
+    let g = a.mod_floor(n).gcd(n);
+    if !b.mod_floor(&g).is_zero() {
+        return None;
+    }
+    let modulus = n / &g;
+    let inverse = mod_inverse(&(a / &g), &modulus).unwrap_or_else(BigInt::zero);
+    let residue = ((b / &g) * inverse).mod_floor(&modulus);
+    Some(Congruence { residue, modulus })
+}
+
+/// 解同余方程 `lhs ≡ rhs (mod modulus)`，两边须为关于 `var` 的整系数线性式
+pub fn solve_congruence(lhs: &Expression, rhs: &Expression, modulus: &Expression, var: &str) -> Result<Option<Congruence>, ComputeError> {
+    let n = as_integer(modulus)
+        .filter(|n| n.is_positive())
+        .ok_or_else(|| ComputeError::domain_error(tr!("modular.modulus", modulus = modulus)))?;
+    let difference = Expression::subtract(lhs.clone(), rhs.clone());
+    if let Some(other) = difference.get_variables().into_iter().find(|v| v != var) {
+        return Err(ComputeError::undefined_variable(other));
+    }
+
+    // 在几个整数点取值确定 a·x + c 的系数，并检验确为线性
+    let mut simplifier = Simplifier::new();
+    let mut value_at = |x: i64| -> Result<BigInt, ComputeError> {
+        let substituted = difference.substitute(&HashMap::from([(var.to_string(), integer(BigInt::from(x)))]));
+        as_integer(&simplifier.simplify(&substituted)?)
+            .ok_or_else(|| ComputeError::domain_error(tr!("modular.linear", variable = var)))
+    };
+    let c = value_at(0)?;
+    let a = value_at(1)? - &c;
+    if value_at(2)? != &a * 2 + &c || value_at(-1)? != &c - &a {
+        return Err(ComputeError::domain_error(tr!("modular.linear", variable = var)));
+    }
+    Ok(solve_linear(&a, &(-c), &n))
+}
+
+/// 与 `expr` 模 n 同余的代表元
+fn residue(expr: &Expression, n: &BigInt) -> Expression {
+    if let Some(value) = as_integer(expr) {
+        return integer(value.mod_floor(n));
+    }
+    match expr {
+        // 模数是 n 的倍数时，内层取余不影响结果
+        Expression::Function { name, args } if is_mod_function(name) && args.len() == 2 && is_multiple_of(&args[1], n) => {
+            residue(&args[0], n)
+        }
+        Expression::BinaryOp { op: BinaryOperator::Modulo, left, right } if is_multiple_of(right, n) => residue(left, n),
+        Expression::BinaryOp { op: op @ (BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply), left, right } => {
+            combine(op, residue(left, n), residue(right, n), n)
+        }
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => match residue(operand, n) {
+            reduced @ Expression::Number(_) => combine(&BinaryOperator::Subtract, integer(BigInt::zero()), reduced, n),
+            reduced => Expression::negate(reduced),
+        },
+        // 非负整数次幂：底数归约为整数时用快速幂
+        Expression::BinaryOp { op: BinaryOperator::Power, left, right } => match as_integer(right).filter(|k| !k.is_negative()) {
+            Some(k) => match residue(left, n) {
+                Expression::Number(Number::Integer(base)) => integer(base.modpow(&k, n)),
+                base => Expression::power(base, right.as_ref().clone()),
+            },
+            None => expr.clone(),
+        },
+        _ => expr.clone(),
+    }
+}
+
+/// 组合两个已归约的运算数，整数直接计算，0 与 1 按恒等式消去
+fn combine(op: &BinaryOperator, left: Expression, right: Expression, n: &BigInt) -> Expression {
+    match (op, as_integer(&left), as_integer(&right)) {
+        (BinaryOperator::Add, Some(a), Some(b)) => integer((a + b).mod_floor(n)),
+        (BinaryOperator::Subtract, Some(a), Some(b)) => integer((a - b).mod_floor(n)),
+        (BinaryOperator::Multiply, Some(a), Some(b)) => integer((a * b).mod_floor(n)),
+        (BinaryOperator::Add, Some(a), _) if a.is_zero() => right,
+        (BinaryOperator::Add | BinaryOperator::Subtract, _, Some(b)) if b.is_zero() => left,
+        (BinaryOperator::Subtract, Some(a), _) if a.is_zero() => Expression::negate(right),
+        (BinaryOperator::Multiply, Some(a), _) | (BinaryOperator::Multiply, _, Some(a)) if a.is_zero() => integer(a),
+        (BinaryOperator::Multiply, Some(a), _) if a.is_one() => right,
+        (BinaryOperator::Multiply, _, Some(b)) if b.is_one() => left,
+        _ => Expression::binary_op(op.clone(), left, right),
+    }
+}
+
+/// 是否为 n 的整数倍
+fn is_multiple_of(expr: &Expression, n: &BigInt) -> bool {
+    as_integer(expr).is_some_and(|m| !m.is_zero() && m.mod_floor(n).is_zero())
+}
+
+/// 整数值（含分母为 1 的有理数）
+fn as_integer(expr: &Expression) -> Option<BigInt> {
+    match expr {
+        Expression::Number(Number::Integer(i)) => Some(i.clone()),
+        Expression::Number(Number::Rational(r)) if r.is_integer() => Some(r.to_integer()),
+        _ => None,
+    }
+}
+
+fn integer(value: BigInt) -> Expression {
+    Expression::Number(Number::Integer(value))
+}
+
+#[cfg(test)]
+#[path = "modular_tests.rs"]
+mod modular_tests;
@@ -0,0 +1,69 @@
+//! # 模算术测试
+//!
+//! 测试取余的逐项归约、快速幂、嵌套取余合并、同余判定与线性同余方程求解。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::modular::{self, Congruence};
+    use crate::engine::simplify::Simplifier;
+    use crate::test_utils::parse;
+    use num_bigint::BigInt;
+
+    fn simplify(input: &str) -> String {
+        Simplifier::new().simplify(&parse(input)).unwrap().to_string()
+    }
+
+    fn congruence(residue: i64, modulus: i64) -> Option<Congruence> {
+        Some(Congruence { residue: BigInt::from(residue), modulus: BigInt::from(modulus) })
+    }
+
+    #[test]
+    fn test_integer_reduction() {
+        assert_eq!(simplify("Mod(-10, 7)"), "4");
+        assert_eq!(simplify("mod(3^1000, 7)"), "4");
+        assert_eq!(simplify("Mod(2^100 * 3^50 + 11, 13)"), simplify("mod(2^100 * 3^50 + 11, 13)"));
+        assert_eq!(simplify("Mod(x, 1)"), "0");
+        // 模数不是正整数时保持原样
+        assert_eq!(simplify("Mod(x, n)"), "Mod(x, n)");
+    }
+
+    #[test]
+    fn test_symbolic_reduction() {
+        assert_eq!(simplify("Mod(x + 10, 7)"), "Mod(3 + x, 7)");
+        assert_eq!(simplify("Mod(7 * x + 2, 7)"), "2");
+        assert_eq!(simplify("Mod(Mod(x, 7), 7)"), "Mod(x, 7)");
+        assert_eq!(simplify("Mod(Mod(x, 14) + Mod(y, 7), 7)"), "Mod(x + y, 7)");
+        assert_eq!(simplify("Mod(9 * a, 4)"), "Mod(a, 4)");
+        assert_eq!(simplify("(a + 10) % 7"), "(3 + a) % 7");
+        // 内层模数不是外层的倍数时不能去掉
+        assert_eq!(simplify("Mod(Mod(x, 5), 7)"), "Mod(Mod(x, 5), 7)");
+    }
+
+    #[test]
+    fn test_congruent() {
+        assert_eq!(simplify("congruent(17, 3, 7)"), "1");
+        assert_eq!(simplify("congruent(17, 4, 7)"), "0");
+        assert_eq!(simplify("congruent(x + 8, x + 1, 7)"), "1");
+        assert_eq!(modular::congruent(&parse("x"), &parse("y"), &parse("7")), None);
+    }
+
+    #[test]
+    fn test_linear_congruence() {
+        let int = |n: i64| BigInt::from(n);
+        assert_eq!(modular::mod_inverse(&int(3), &int(7)), Some(int(5)));
+        assert_eq!(modular::mod_inverse(&int(2), &int(4)), None);
+
+        // 3x ≡ 4 (mod 7) ⇒ x ≡ 6
+        assert_eq!(modular::solve_linear(&int(3), &int(4), &int(7)), congruence(6, 7));
+        // 6x ≡ 4 (mod 10) ⇒ x ≡ 4 (mod 5)
+        assert_eq!(modular::solve_linear(&int(6), &int(4), &int(10)), congruence(4, 5));
+        // 2x ≡ 1 (mod 4) 无解
+        assert_eq!(modular::solve_linear(&int(2), &int(1), &int(4)), None);
+
+        let solved = modular::solve_congruence(&parse("3 * x + 1"), &parse("5 - 7"), &parse("7"), "x").unwrap();
+        assert_eq!(solved, congruence(6, 7));
+        assert_eq!(solved.unwrap().to_string(), "6 (mod 7)");
+        assert!(modular::solve_congruence(&parse("x^2"), &parse("1"), &parse("8"), "x").is_err());
+        assert!(modular::solve_congruence(&parse("x"), &parse("1"), &parse("0"), "x").is_err());
+    }
+}
@@ -2,20 +2,53 @@
 //!
 //! 实现数论相关的算法，包括最大公约数、最小公倍数、素数判断、
 //! 质因数分解、二项式系数、排列组合等功能。
+//!
+//! 较大整数的素性检验用 Miller-Rabin，分解用 Pollard rho，两者都依赖随机数；
+//! 设定种子后同一输入的随机序列与计算过程可以复现。
 
 use num_bigint::{BigInt, ToBigInt};
 use num_rational::BigRational;
 use num_traits::{Zero, One, Signed};
+use std::sync::Mutex;
 use crate::core::{Expression, Number};
 use super::ComputeError;
+use super::random::SeededRng;
+use crate::tr;
+
+/// 不超过此值的整数直接用试除法判断素性
+const TRIAL_DIVISION_LIMIT: u64 = 1 << 32;
+
+/// 分解时先试除的小素数上界
+const SMALL_FACTOR_LIMIT: u32 = 1000;
+
+/// Miller-Rabin 检验的轮数，合数被误判的概率不超过 4^-24
+const MILLER_RABIN_ROUNDS: usize = 24;
 
 /// 数论和组合数学引擎
-pub struct NumberTheoryEngine;
+pub struct NumberTheoryEngine {
+    /// 概率算法使用的随机数生成器
+    rng: Mutex<SeededRng>,
+}
 
 impl NumberTheoryEngine {
-    /// 创建新的数论引擎
+    /// 创建新的数论引擎，使用随机种子
     pub fn new() -> Self {
-        Self
+        Self { rng: Mutex::new(SeededRng::from_entropy()) }
+    }
+    
+    /// 创建使用固定随机种子的数论引擎
+    pub fn with_seed(seed: u64) -> Self {
+        Self { rng: Mutex::new(SeededRng::new(seed)) }
+    }
+    
+    /// 重新设定随机种子
+    pub fn set_seed(&self, seed: u64) {
+        *self.rng() = SeededRng::new(seed);
+    }
+    
+    fn rng(&self) -> std::sync::MutexGuard<'_, SeededRng> {
+        // 生成器状态总是有效的，锁中毒时继续使用
+        self.rng.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
     }
     
     /// 计算两个整数的最大公约数（欧几里得算法）
@@ -39,7 +72,7 @@ impl NumberTheoryEngine {
                 Ok(Expression::Number(Number::Rational(result)))
             }
             _ => Err(ComputeError::unsupported_operation(
-                "gcd 函数只支持整数和有理数，请确保参数是整数或有理数"
+                tr!("number_theory.gcd_arguments")
             ))
         }
     }
@@ -57,11 +90,29 @@ impl NumberTheoryEngine {
                 Ok(Expression::Number(Number::Integer(result)))
             }
             _ => Err(ComputeError::unsupported_operation(
-                "lcm 函数只支持整数，请确保参数是整数"
+                tr!("number_theory.lcm_arguments")
             ))
         }
     }
     
+    /// 计算多个数的最大公约数，空列表返回 0（最大公约数的单位元），单个元素返回其绝对值
+    pub fn gcd_list(&self, values: &[Expression]) -> Result<Expression, ComputeError> {
+        match values.split_first() {
+            None => Ok(Expression::Number(Number::Integer(BigInt::zero()))),
+            Some((first, rest)) => rest.iter()
+                .try_fold(self.gcd(first, first)?, |acc, value| self.gcd(&acc, value)),
+        }
+    }
+    
+    /// 计算多个整数的最小公倍数，空列表返回 1（最小公倍数的单位元），单个元素返回其绝对值
+    pub fn lcm_list(&self, values: &[Expression]) -> Result<Expression, ComputeError> {
+        match values.split_first() {
+            None => Ok(Expression::Number(Number::Integer(BigInt::one()))),
+            Some((first, rest)) => rest.iter()
+                .try_fold(self.lcm(first, first)?, |acc, value| self.lcm(&acc, value)),
+        }
+    }
+    
     /// 判断一个数是否为素数
     pub fn is_prime(&self, n: &Expression) -> Result<bool, ComputeError> {
         match n {
@@ -69,7 +120,7 @@ impl NumberTheoryEngine {
                 Ok(self.is_prime_bigint(n))
             }
             _ => Err(ComputeError::unsupported_operation(
-                "is_prime 函数只支持整数，请确保参数是正整数"
+                tr!("number_theory.is_prime_argument")
             ))
         }
     }
@@ -80,7 +131,7 @@ impl NumberTheoryEngine {
             Expression::Number(Number::Integer(n)) => {
                 if n <= &BigInt::one() {
                     return Err(ComputeError::domain_error(
-                        "质因数分解要求输入大于1的正整数"
+                        tr!("number_theory.factorization_domain")
                     ));
                 }
                 
@@ -91,7 +142,7 @@ impl NumberTheoryEngine {
                 Ok(result)
             }
             _ => Err(ComputeError::unsupported_operation(
-                "prime_factors 函数只支持整数，请确保参数是大于1的正整数"
+                tr!("number_theory.prime_factors_argument")
             ))
         }
     }
@@ -108,7 +159,7 @@ impl NumberTheoryEngine {
                 Ok(Expression::Number(Number::Integer(result)))
             }
             _ => Err(ComputeError::unsupported_operation(
-                "binomial 函数只支持非负整数，请确保参数是非负整数且 k <= n"
+                tr!("number_theory.binomial_arguments")
             ))
         }
     }
@@ -125,7 +176,7 @@ impl NumberTheoryEngine {
                 Ok(Expression::Number(Number::Integer(result)))
             }
             _ => Err(ComputeError::unsupported_operation(
-                "permutation 函数只支持非负整数，请确保参数是非负整数且 k <= n"
+                tr!("number_theory.permutation_arguments")
             ))
         }
     }
@@ -134,7 +185,7 @@ impl NumberTheoryEngine {
     pub fn mean(&self, values: &[Expression]) -> Result<Expression, ComputeError> {
         if values.is_empty() {
             return Err(ComputeError::domain_error(
-                "无法计算空列表的平均值，请提供至少一个数值"
+                tr!("number_theory.empty_mean")
             ));
         }
         
@@ -147,7 +198,7 @@ impl NumberTheoryEngine {
                     sum = self.add_numbers(&sum, num)?;
                 }
                 _ => return Err(ComputeError::unsupported_operation(
-                    "mean 函数只支持数值，请确保所有参数都是数值"
+                    tr!("number_theory.mean_arguments")
                 ))
             }
         }
@@ -162,7 +213,7 @@ impl NumberTheoryEngine {
     pub fn variance(&self, values: &[Expression]) -> Result<Expression, ComputeError> {
         if values.len() < 2 {
             return Err(ComputeError::domain_error(
-                "计算方差需要至少两个数值"
+                tr!("number_theory.variance_count")
             ));
         }
         
@@ -184,7 +235,7 @@ impl NumberTheoryEngine {
                     sum_squares = self.add_numbers(&sum_squares, &square)?;
                 }
                 _ => return Err(ComputeError::unsupported_operation(
-                    "variance 函数只支持数值，请确保所有参数都是数值"
+                    tr!("number_theory.variance_arguments")
                 ))
             }
         }
@@ -224,7 +275,7 @@ impl NumberTheoryEngine {
         a
     }
     
-    /// 判断 BigInt 是否为素数（试除法）
+    /// 判断 BigInt 是否为素数：小整数试除，大整数用 Miller-Rabin
     fn is_prime_bigint(&self, n: &BigInt) -> bool {
         if n <= &BigInt::one() {
             return false;
@@ -235,6 +286,9 @@ impl NumberTheoryEngine {
         if n % 2 == BigInt::zero() {
             return false;
         }
+        if n > &BigInt::from(TRIAL_DIVISION_LIMIT) {
+            return self.miller_rabin(n);
+        }
         
         // 只检查到 sqrt(n)
         let mut i = BigInt::from(3);
@@ -250,7 +304,35 @@ impl NumberTheoryEngine {
         true
     }
     
-    /// 质因数分解（试除法）
+    /// Miller-Rabin 概率素性检验，`n` 为大于 3 的奇数，底数随机选取
+    fn miller_rabin(&self, n: &BigInt) -> bool {
+        let n_minus_one: BigInt = n - 1;
+        let mut d = n_minus_one.clone();
+        let mut s = 0;
+        while (&d % 2u32).is_zero() {
+            d /= 2u32;
+            s += 1;
+        }
+        
+        let (low, high) = (BigInt::from(2), n - 1);
+        'witness: for _ in 0..MILLER_RABIN_ROUNDS {
+            let a = self.rng().bigint_range(&low, &high);
+            let mut x = a.modpow(&d, n);
+            if x.is_one() || x == n_minus_one {
+                continue;
+            }
+            for _ in 1..s {
+                x = x.modpow(&BigInt::from(2), n);
+                if x == n_minus_one {
+                    continue 'witness;
+                }
+            }
+            return false;
+        }
+        true
+    }
+    
+    /// 质因数分解：先试除小素数，余下部分用 Pollard rho 递归拆分，结果升序
     fn prime_factors_bigint(&self, n: &BigInt) -> Vec<BigInt> {
         let mut factors = Vec::new();
         let mut n = n.clone();
@@ -261,11 +343,11 @@ impl NumberTheoryEngine {
             n /= 2;
         }
         
-        // 处理奇数因子
+        // 试除较小的奇数因子
         let mut i = BigInt::from(3);
-        let sqrt_n = self.integer_sqrt(&n);
+        let limit = self.integer_sqrt(&n).min(BigInt::from(SMALL_FACTOR_LIMIT));
         
-        while i <= sqrt_n && n > BigInt::one() {
+        while i <= limit && n > BigInt::one() {
             while &n % &i == BigInt::zero() {
                 factors.push(i.clone());
                 n /= &i;
@@ -273,14 +355,46 @@ impl NumberTheoryEngine {
             i += 2;
         }
         
-        // 如果 n 仍然大于1，那么它本身就是一个素因子
-        if n > BigInt::one() {
-            factors.push(n);
+        // 余下部分没有小于 SMALL_FACTOR_LIMIT 的因子
+        let mut pending = vec![n];
+        while let Some(m) = pending.pop() {
+            if m.is_one() {
+                continue;
+            }
+            if self.is_prime_bigint(&m) {
+                factors.push(m);
+            } else {
+                let divisor = self.pollard_rho(&m);
+                pending.push(&m / &divisor);
+                pending.push(divisor);
+            }
         }
         
+        factors.sort();
         factors
     }
     
+    /// Pollard rho 求奇合数 `n` 的一个非平凡因子，起点与多项式常数随机选取
+    fn pollard_rho(&self, n: &BigInt) -> BigInt {
+        loop {
+            let (x0, c) = {
+                let mut rng = self.rng();
+                (rng.bigint_range(&BigInt::zero(), n), rng.bigint_range(&BigInt::one(), n))
+            };
+            let step = |x: &BigInt| (x * x + &c) % n;
+            let (mut x, mut y) = (x0.clone(), x0);
+            let mut divisor = BigInt::one();
+            while divisor.is_one() {
+                x = step(&x);
+                y = step(&step(&y));
+                divisor = self.gcd_bigint(&(&x - &y).abs(), n);
+            }
+            if &divisor != n {
+                return divisor;
+            }
+        }
+    }
+    
     /// 计算二项式系数
     fn binomial_coefficient(&self, n: &BigInt, k: &BigInt) -> Result<BigInt, ComputeError> {
         let k = if k > &(n - k) { n - k } else { k.clone() };
@@ -352,7 +466,7 @@ impl NumberTheoryEngine {
                 Ok(Number::Rational(a + b_rational))
             }
             _ => Err(ComputeError::unsupported_operation(
-                "不支持的数值类型组合"
+                tr!("number_theory.unsupported_combination")
             ))
         }
     }
@@ -375,7 +489,7 @@ impl NumberTheoryEngine {
                 Ok(Number::Rational(a - b_rational))
             }
             _ => Err(ComputeError::unsupported_operation(
-                "不支持的数值类型组合"
+                tr!("number_theory.unsupported_combination")
             ))
         }
     }
@@ -398,7 +512,7 @@ impl NumberTheoryEngine {
                 Ok(Number::Rational(a * b_rational))
             }
             _ => Err(ComputeError::unsupported_operation(
-                "不支持的数值类型组合"
+                tr!("number_theory.unsupported_combination")
             ))
         }
     }
@@ -433,7 +547,7 @@ impl NumberTheoryEngine {
                 Ok(Number::Rational(a / b_rational))
             }
             _ => Err(ComputeError::unsupported_operation(
-                "不支持的数值类型组合"
+                tr!("number_theory.unsupported_combination")
             ))
         }
     }
@@ -444,7 +558,7 @@ impl NumberTheoryEngine {
             Number::Integer(n) => {
                 if n < &BigInt::zero() {
                     return Err(ComputeError::domain_error(
-                        "负数没有实数平方根，请使用复数运算"
+                        tr!("number_theory.negative_sqrt")
                     ));
                 }
                 
@@ -475,7 +589,7 @@ impl NumberTheoryEngine {
                 }
             }
             _ => Err(ComputeError::unsupported_operation(
-                "不支持的数值类型"
+                tr!("number_theory.unsupported_type")
             ))
         }
     }
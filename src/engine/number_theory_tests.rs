@@ -227,6 +227,28 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_gcd_lcm_list() {
+        let engine = create_engine();
+        let ints = |values: &[i64]| -> Vec<Expression> {
+            values.iter().map(|&v| Expression::Number(Number::Integer(BigInt::from(v)))).collect()
+        };
+        let int = |v: i64| Expression::Number(Number::Integer(BigInt::from(v)));
+
+        assert_eq!(engine.gcd_list(&ints(&[12, 18, 24])).unwrap(), int(6));
+        assert_eq!(engine.lcm_list(&ints(&[4, 6, 8])).unwrap(), int(24));
+
+        // 空列表返回单位元，单个元素返回其绝对值
+        assert_eq!(engine.gcd_list(&[]).unwrap(), int(0));
+        assert_eq!(engine.lcm_list(&[]).unwrap(), int(1));
+        assert_eq!(engine.gcd_list(&ints(&[-15])).unwrap(), int(15));
+        assert_eq!(engine.lcm_list(&ints(&[-15])).unwrap(), int(15));
+
+        // 含零时 lcm 为 0
+        assert_eq!(engine.lcm_list(&ints(&[3, 0, 5])).unwrap(), int(0));
+        assert!(engine.lcm_list(&[int(2), Expression::variable("x")]).is_err());
+    }
+
     #[test]
     fn test_variance() {
         let engine = create_engine();
@@ -353,4 +375,47 @@ mod tests {
         let expected = Expression::Number(Number::Rational(BigRational::new(BigInt::from(1), BigInt::from(3))));
         assert_eq!(result, expected);
     }
-}
\ No newline at end of file
+
+    fn integer(digits: &str) -> Expression {
+        Expression::Number(Number::Integer(digits.parse::<BigInt>().unwrap()))
+    }
+
+    #[test]
+    fn test_probabilistic_algorithms() {
+        let engine = NumberTheoryEngine::with_seed(1);
+        // 2^61 - 1 是素数，1000000007 * 998244353 与 Carmichael 数 3215031751 = 151·751·28351 是合数
+        assert!(engine.is_prime(&integer("2305843009213693951")).unwrap());
+        assert!(!engine.is_prime(&integer("998244359987710471")).unwrap());
+        assert!(!engine.is_prime(&integer("3215031751")).unwrap());
+
+        let factors = engine.prime_factors(&integer("998244359987710471")).unwrap();
+        assert_eq!(factors, vec![integer("998244353"), integer("1000000007")]);
+        let factors = engine.prime_factors(&integer("3215031751")).unwrap();
+        assert_eq!(factors, vec![integer("151"), integer("751"), integer("28351")]);
+        // 小因子、超出试除范围的因子与平方因子混合
+        let factors = engine.prime_factors(&integer("3027018162027243")).unwrap();
+        assert_eq!(factors, vec![integer("3"), integer("1009"), integer("1000003"), integer("1000003")]);
+    }
+
+    #[test]
+    fn test_seed_reproducibility() {
+        let run = |seed: u64| {
+            let engine = NumberTheoryEngine::with_seed(seed);
+            engine.prime_factors(&integer("998244359987710471")).unwrap();
+            engine.is_prime(&integer("2305843009213693951")).unwrap();
+            // 计算之后生成器的后续序列反映了计算过程中消耗的全部随机数
+            let mut rng = engine.rng.lock().unwrap();
+            (0..8).map(|_| rng.next_u64()).collect::<Vec<_>>()
+        };
+        assert_eq!(run(42), run(42));
+        assert_ne!(run(42), run(43));
+
+        let engine = NumberTheoryEngine::with_seed(5);
+        engine.is_prime(&integer("2305843009213693951")).unwrap();
+        engine.set_seed(42);
+        engine.prime_factors(&integer("998244359987710471")).unwrap();
+        engine.is_prime(&integer("2305843009213693951")).unwrap();
+        let mut rng = engine.rng.lock().unwrap();
+        assert_eq!((0..8).map(|_| rng.next_u64()).collect::<Vec<_>>(), run(42));
+    }
+}
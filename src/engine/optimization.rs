@@ -0,0 +1,327 @@
+//! # 最优化
+//!
+//! 一元函数在自然定义域上的最小值与最大值：比较各局部极值点与闭端点处的函数值，
+//! 再用值域估计判断函数是否无界、确界是否取不到，避免把局部极值当作最值。
+//! 带一个等式约束的多元情形用拉格朗日乘数法，驻点方程组交给 [`groebner::solve_system`] 求解，
+//! 再沿约束取样检验候选点是否为全局最优。
+
+use std::collections::HashMap;
+use std::fmt;
+use crate::core::{fresh_variable, Expression, BinaryOperator, Number};
+use super::ComputeError;
+use super::calculus::CalculusEngine;
+use super::domain::DomainAnalyzer;
+use super::extrema::{ExtremaAnalyzer, ExtremumKind};
+use super::groebner;
+use super::simplify::Simplifier;
+use super::solver::SolverEngine;
+use crate::tr;
+
+/// 比较函数值时的容差
+const ZERO_TOLERANCE: f64 = 1e-9;
+
+/// 取样值超过该阈值时认为函数无界
+const DIVERGENCE_THRESHOLD: f64 = 1e6;
+
+/// 沿约束取样时自由变量的取值
+const SAMPLES: [f64; 8] = [-1e8, -1e4, -100.0, -1.0, 1.0, 100.0, 1e4, 1e8];
+
+/// 最优解：最优点的各坐标与最优值
+#[derive(Debug, Clone, PartialEq)]
+pub struct Optimum {
+    /// 最优点，按变量顺序排列
+    pub point: Vec<(String, Expression)>,
+    /// 最优值
+    pub value: Expression,
+}
+
+impl Optimum {
+    /// 写成列表 `[坐标..., 最优值]`
+    pub fn to_expression(&self) -> Expression {
+        let mut elements: Vec<Expression> = self.point.iter().map(|(_, x)| x.clone()).collect();
+        elements.push(self.value.clone());
+        Expression::Vector(elements)
+    }
+}
+
+impl fmt::Display for Optimum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let coordinates: Vec<String> = self.point.iter().map(|(_, x)| x.to_string()).collect();
+        write!(f, "({}, {})", coordinates.join(", "), self.value)
+    }
+}
+
+/// 求最小值还是最大值
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Goal {
+    Minimize,
+    Maximize,
+}
+
+impl Goal {
+    /// `a` 是否严格优于 `b`
+    fn better(self, a: f64, b: f64) -> bool {
+        let margin = ZERO_TOLERANCE * (1.0 + a.abs().max(b.abs()));
+        match self {
+            Goal::Minimize => a < b - margin,
+            Goal::Maximize => a > b + margin,
+        }
+    }
+
+    /// 对应的局部极值类型
+    fn kind(self) -> ExtremumKind {
+        match self {
+            Goal::Minimize => ExtremumKind::Minimum,
+            Goal::Maximize => ExtremumKind::Maximum,
+        }
+    }
+
+    /// 无界时的说明
+    fn unbounded(self) -> &'static str {
+        match self {
+            Goal::Minimize => tr!("optimization.unbounded_below"),
+            Goal::Maximize => tr!("optimization.unbounded_above"),
+        }
+    }
+
+    /// 最值的名称
+    fn name(self) -> &'static str {
+        match self {
+            Goal::Minimize => tr!("optimization.minimum"),
+            Goal::Maximize => tr!("optimization.maximum"),
+        }
+    }
+}
+
+/// 候选点：坐标、精确函数值与数值
+struct Candidate {
+    point: Vec<(String, Expression)>,
+    value: Expression,
+    approx: f64,
+}
+
+/// 最优化求解器
+pub struct Optimizer {
+    domain: DomainAnalyzer,
+    extrema: ExtremaAnalyzer,
+    calculus: CalculusEngine,
+}
+
+impl Optimizer {
+    /// 创建新的求解器
+    pub fn new() -> Self {
+        Self {
+            domain: DomainAnalyzer::new(),
+            extrema: ExtremaAnalyzer::new(),
+            calculus: CalculusEngine::new(),
+        }
+    }
+
+    /// 一元函数在自然定义域上的最小值，有多个最小值点时全部返回
+    ///
+    /// 函数无下界或下确界取不到时报定义域错误。
+    pub fn minimize(&self, expr: &Expression, var: &str) -> Result<Vec<Optimum>, ComputeError> {
+        self.optimize(expr, var, Goal::Minimize)
+    }
+
+    /// 一元函数在自然定义域上的最大值，有多个最大值点时全部返回
+    pub fn maximize(&self, expr: &Expression, var: &str) -> Result<Vec<Optimum>, ComputeError> {
+        self.optimize(expr, var, Goal::Maximize)
+    }
+
+    /// 多元函数在等式约束 `constraint`（`lhs = rhs` 或视为等于 0 的表达式）下的最小值
+    ///
+    /// 用拉格朗日乘数法求候选点，目前要求目标函数与约束都是多项式；
+    /// 不考虑约束梯度为零的奇异点。
+    pub fn minimize_constrained(&self, expr: &Expression, vars: &[&str], constraint: &Expression) -> Result<Vec<Optimum>, ComputeError> {
+        self.optimize_constrained(expr, vars, constraint, Goal::Minimize)
+    }
+
+    /// 多元函数在等式约束下的最大值
+    pub fn maximize_constrained(&self, expr: &Expression, vars: &[&str], constraint: &Expression) -> Result<Vec<Optimum>, ComputeError> {
+        self.optimize_constrained(expr, vars, constraint, Goal::Maximize)
+    }
+
+    fn optimize(&self, expr: &Expression, var: &str, goal: Goal) -> Result<Vec<Optimum>, ComputeError> {
+        let domain = self.domain.domain(expr, var)?.set;
+        let (bound, bound_value, attained) = self.bound(expr, var, &domain, goal)?;
+        if bound_value.is_infinite() {
+            return Err(ComputeError::domain_error(format!("{} {}", expr, goal.unbounded())));
+        }
+
+        // 候选点：同类局部极值点（及无法判定的临界点）与定义域的闭端点
+        let mut points: Vec<Expression> = self.extrema.local_extrema(expr, var)?.into_iter()
+            .filter(|e| e.kind == goal.kind() || e.kind == ExtremumKind::Undetermined)
+            .map(|e| e.point)
+            .collect();
+        Self::closed_endpoints(&domain, &mut points);
+
+        let mut simplifier = Simplifier::new();
+        let mut candidates = Vec::new();
+        for point in points {
+            let Some(approx) = self.numeric(&point, &HashMap::new()).and_then(|x| self.domain.evaluate_at(expr, var, x)) else {
+                continue;
+            };
+            let bindings = HashMap::from([(var.to_string(), point.clone())]);
+            let value = simplifier.simplify(&expr.substitute(&bindings))?;
+            candidates.push(Candidate { point: vec![(var.to_string(), point)], value, approx });
+        }
+
+        match Self::best(candidates, goal) {
+            Some(best) if !goal.better(bound_value, best[0].approx) => Ok(Self::optima(best)),
+            _ if attained => Err(ComputeError::unsupported_operation(tr!("optimization.attained", expression = expr, goal = goal.name()))),
+            _ => Err(ComputeError::domain_error(tr!("optimization.not_attained", expression = expr, goal = goal.name(), bound = bound))),
+        }
+    }
+
+    /// 值域在目标方向的端点：(端点, 数值, 是否取到)
+    fn bound(&self, expr: &Expression, var: &str, domain: &Expression, goal: Goal) -> Result<(Expression, f64, bool), ComputeError> {
+        let range = self.domain.range_estimate(expr, var, domain)?.set;
+        let (bound, attained) = match range {
+            Expression::Interval { start, start_inclusive, .. } if goal == Goal::Minimize => (*start, start_inclusive),
+            Expression::Interval { end, end_inclusive, .. } => (*end, end_inclusive),
+            Expression::Set(mut values) if values.len() == 1 => (values.remove(0), true),
+            _ => return Err(ComputeError::unsupported_operation(tr!("optimization.range", expression = expr))),
+        };
+        let value = self.calculus.numerical_evaluate(&bound, &HashMap::new())?;
+        Ok((bound, value, attained))
+    }
+
+    /// 收集定义域中有限的闭端点与孤立点
+    fn closed_endpoints(domain: &Expression, points: &mut Vec<Expression>) {
+        let finite = |e: &Expression| !matches!(e, Expression::Constant(c) if c.is_infinite());
+        match domain {
+            Expression::Interval { start, end, start_inclusive, end_inclusive } => {
+                if *start_inclusive && finite(start) {
+                    points.push(start.as_ref().clone());
+                }
+                if *end_inclusive && finite(end) {
+                    points.push(end.as_ref().clone());
+                }
+            }
+            Expression::Set(elements) => points.extend(elements.iter().cloned()),
+            Expression::BinaryOp { op: BinaryOperator::Union, left, right } => {
+                Self::closed_endpoints(left, points);
+                Self::closed_endpoints(right, points);
+            }
+            _ => {}
+        }
+    }
+
+    fn optimize_constrained(&self, expr: &Expression, vars: &[&str], constraint: &Expression, goal: Goal) -> Result<Vec<Optimum>, ComputeError> {
+        let mut simplifier = Simplifier::new();
+        let g = simplifier.simplify(&match constraint {
+            Expression::BinaryOp { op: BinaryOperator::Equal, left, right } => {
+                Expression::subtract(left.as_ref().clone(), right.as_ref().clone())
+            }
+            _ => constraint.clone(),
+        })?;
+
+        // 拉格朗日方程组：∇f = λ∇g，g = 0；λ 排在最前以便先被消去
+        let lambda = fresh_variable("lambda", &Expression::add(expr.clone(), g.clone()));
+        let mut equations = Vec::with_capacity(vars.len() + 1);
+        for var in vars {
+            let df = self.calculus.differentiate(expr, var)?;
+            let dg = self.calculus.differentiate(&g, var)?;
+            let equation = Expression::subtract(df, Expression::multiply(Expression::variable(&lambda), dg));
+            equations.push(simplifier.simplify(&equation)?);
+        }
+        equations.push(g.clone());
+        let mut order = vec![lambda.as_str()];
+        order.extend_from_slice(vars);
+
+        let mut candidates = Vec::new();
+        for solution in groebner::solve_system(&equations, &order)? {
+            let point: Vec<(String, Expression)> = vars.iter().map(|v| (v.to_string(), solution[*v].clone())).collect();
+            let bindings: HashMap<String, Expression> = point.iter().cloned().collect();
+            let value = simplifier.simplify(&expr.substitute(&bindings))?;
+            let Some(approx) = self.numeric(&value, &HashMap::new()) else {
+                continue;
+            };
+            candidates.push(Candidate { point, value, approx });
+        }
+        let best = Self::best(candidates, goal);
+
+        // 以候选点（没有时以原点）为基点，沿约束取样
+        let base: Vec<f64> = match &best {
+            Some(best) => best[0].point.iter().map(|(_, x)| self.numeric(x, &HashMap::new()).unwrap_or(0.0)).collect(),
+            None => vec![0.0; vars.len()],
+        };
+        let samples = self.constraint_samples(expr, &g, vars, &base)?;
+        let beaten = samples.iter().copied()
+            .filter(|s| best.as_ref().is_none_or(|best| goal.better(*s, best[0].approx)))
+            .fold(None, |acc: Option<f64>, s| Some(acc.map_or(s, |a| if goal.better(s, a) { s } else { a })));
+
+        match (best, beaten) {
+            (Some(best), None) => Ok(Self::optima(best)),
+            (None, None) if samples.is_empty() => Err(ComputeError::domain_error(tr!("optimization.infeasible", constraint = g))),
+            (_, Some(s)) if s.abs() > DIVERGENCE_THRESHOLD => {
+                Err(ComputeError::domain_error(tr!("optimization.unbounded", expression = expr, constraint = g, bound = goal.unbounded())))
+            }
+            _ => Err(ComputeError::domain_error(tr!("optimization.constrained_not_attained", expression = expr, constraint = g, goal = goal.name()))),
+        }
+    }
+
+    /// 约束上的取样函数值：依次让一个变量取 `SAMPLES` 中的值、另一个变量由约束解出，其余变量固定在基点
+    fn constraint_samples(&self, expr: &Expression, g: &Expression, vars: &[&str], base: &[f64]) -> Result<Vec<f64>, ComputeError> {
+        let solver = SolverEngine::new();
+        let mut simplifier = Simplifier::new();
+        let mut values = Vec::new();
+        for (j, solved) in vars.iter().enumerate() {
+            for i in (0..vars.len()).filter(|&i| i != j) {
+                for sample in SAMPLES {
+                    let mut bindings = HashMap::new();
+                    for (k, var) in vars.iter().enumerate().filter(|(k, _)| *k != j) {
+                        let x = if k == i { sample } else { base[k] };
+                        // 基点坐标取到 6 位小数，保证代入后仍是有理系数
+                        bindings.insert(var.to_string(), Expression::Number(Number::rational((x * 1e6).round() as i64, 1_000_000)));
+                    }
+                    let equation = simplifier.simplify(&g.substitute(&bindings))?;
+                    let Ok(roots) = solver.real_roots(&equation, solved) else {
+                        continue;
+                    };
+                    let mut approx: HashMap<String, f64> = vars.iter().enumerate()
+                        .filter(|(k, _)| *k != j)
+                        .map(|(k, var)| (var.to_string(), if k == i { sample } else { base[k] }))
+                        .collect();
+                    for (_, root) in roots {
+                        approx.insert(solved.to_string(), root);
+                        values.extend(self.numeric(expr, &approx));
+                    }
+                }
+            }
+        }
+        Ok(values)
+    }
+
+    /// 取出最优的候选点（数值相同的都保留，按坐标去重）
+    fn best(candidates: Vec<Candidate>, goal: Goal) -> Option<Vec<Candidate>> {
+        let best = candidates.iter().map(|c| c.approx).reduce(|a, b| if goal.better(b, a) { b } else { a })?;
+        let mut optima: Vec<Candidate> = Vec::new();
+        for candidate in candidates {
+            if !goal.better(best, candidate.approx) && !optima.iter().any(|o| o.point == candidate.point) {
+                optima.push(candidate);
+            }
+        }
+        Some(optima)
+    }
+
+    fn optima(candidates: Vec<Candidate>) -> Vec<Optimum> {
+        candidates.into_iter().map(|c| Optimum { point: c.point, value: c.value }).collect()
+    }
+
+    /// 数值求值，结果不是有限实数时返回 `None`
+    fn numeric(&self, expr: &Expression, vars: &HashMap<String, f64>) -> Option<f64> {
+        self.calculus.numerical_evaluate(expr, vars).ok().filter(|v| v.is_finite())
+    }
+}
+
+impl Default for Optimizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[path = "optimization_tests.rs"]
+mod optimization_tests;
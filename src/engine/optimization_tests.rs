@@ -0,0 +1,62 @@
+//! # 最优化测试
+//!
+//! 测试一元最值、多个最值点、无界与确界取不到的报告，以及拉格朗日乘数法。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::optimization::{Optimizer, Optimum};
+    use crate::engine::ComputeError;
+    use crate::test_utils::parse;
+
+    fn shown(result: Result<Vec<Optimum>, ComputeError>) -> Vec<String> {
+        result.unwrap().iter().map(|o| o.to_string()).collect()
+    }
+
+    #[test]
+    fn test_univariate_optimum() {
+        let optimizer = Optimizer::new();
+        assert_eq!(shown(optimizer.minimize(&parse("x^2+2*x+3"), "x")), vec!["(-1, 2)"]);
+        assert_eq!(shown(optimizer.maximize(&parse("1/(1+x^2)"), "x")), vec!["(0, 1)"]);
+        // 定义域的闭端点也是候选点
+        assert_eq!(shown(optimizer.minimize(&parse("sqrt(x)"), "x")), vec!["(0, 0)"]);
+        // 多个最小值点全部返回
+        assert_eq!(shown(optimizer.minimize(&parse("x^4-2*x^2"), "x")), vec!["(-1, -1)", "(1, -1)"]);
+    }
+
+    #[test]
+    fn test_unbounded_and_unattained() {
+        let optimizer = Optimizer::new();
+        // 无界
+        assert!(matches!(optimizer.minimize(&parse("x^3"), "x"), Err(ComputeError::DomainError { .. })));
+        assert!(matches!(optimizer.maximize(&parse("x^2+2*x+3"), "x"), Err(ComputeError::DomainError { .. })));
+        // 局部极小值不是最小值
+        assert!(matches!(optimizer.minimize(&parse("x+1/x"), "x"), Err(ComputeError::DomainError { .. })));
+        // 确界取不到
+        assert!(matches!(optimizer.maximize(&parse("x^2/(1+x^2)"), "x"), Err(ComputeError::DomainError { .. })));
+        assert!(matches!(optimizer.minimize(&parse("1/(1+x^2)"), "x"), Err(ComputeError::DomainError { .. })));
+    }
+
+    #[test]
+    fn test_lagrange_multipliers() {
+        let optimizer = Optimizer::new();
+        let optimum = optimizer.maximize_constrained(&parse("x*y"), &["x", "y"], &parse("x+y==10")).unwrap();
+        assert_eq!(optimum.len(), 1);
+        assert_eq!(optimum[0].to_string(), "(5, 5, 25)");
+        assert_eq!(optimum[0].point[0].0, "x");
+
+        assert_eq!(
+            shown(optimizer.minimize_constrained(&parse("x^2+y^2"), &["x", "y"], &parse("x+2*y-5"))),
+            vec!["(1, 2, 5)"]
+        );
+        // 约束上无下界
+        assert!(matches!(
+            optimizer.minimize_constrained(&parse("x*y"), &["x", "y"], &parse("x+y==10")),
+            Err(ComputeError::DomainError { .. })
+        ));
+        // 约束无实数解
+        assert!(matches!(
+            optimizer.minimize_constrained(&parse("x+y"), &["x", "y"], &parse("x^2+y^2==-1")),
+            Err(ComputeError::DomainError { .. })
+        ));
+    }
+}
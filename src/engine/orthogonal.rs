@@ -0,0 +1,177 @@
+//! # 正交多项式
+//!
+//! 用三项递推 `p_{n+1} = (a_n x + b_n) p_n - c_n p_{n-1}` 构造第 n 个经典正交多项式，系数是精确有理数：
+//! - 第一类 Chebyshev `T_n`：`T_{n+1} = 2x T_n - T_{n-1}`，`T_0 = 1`、`T_1 = x`
+//! - 第二类 Chebyshev `U_n`：递推同上，`U_1 = 2x`
+//! - Legendre `P_n`：`(n+1) P_{n+1} = (2n+1) x P_n - n P_{n-1}`
+//! - Hermite（物理学约定）`H_n`：`H_{n+1} = 2x H_n - 2n H_{n-1}`
+//! - Laguerre `L_n`：`(n+1) L_{n+1} = (2n+1-x) L_n - n L_{n-1}`
+//!
+//! 表达式中可写作 `chebyshev_t(n)` 或 `chebyshev_t(n, x)`，省略变量时用 `x`；
+//! 系数有分母时提出公分母，如 `legendre_p(2) = (3*x^2 - 1)/2`。
+
+use std::collections::HashMap;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use crate::core::{Expression, Number};
+use super::ComputeError;
+use super::simplify::Simplifier;
+use crate::tr;
+
+/// 正交多项式函数名
+pub const ORTHOGONAL_POLYNOMIALS: &[&str] = &["chebyshev_t", "chebyshev_u", "legendre_p", "hermite_h", "laguerre_l"];
+
+/// 允许的最高次数
+const MAX_DEGREE: usize = 1000;
+
+/// 正交多项式族
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrthogonalFamily {
+    /// 第一类 Chebyshev 多项式 T_n
+    ChebyshevT,
+    /// 第二类 Chebyshev 多项式 U_n
+    ChebyshevU,
+    /// Legendre 多项式 P_n
+    Legendre,
+    /// Hermite 多项式 H_n（物理学约定，首项系数 2^n）
+    Hermite,
+    /// Laguerre 多项式 L_n
+    Laguerre,
+}
+
+impl OrthogonalFamily {
+    /// 按表达式中的函数名查找
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "chebyshev_t" => Some(Self::ChebyshevT),
+            "chebyshev_u" => Some(Self::ChebyshevU),
+            "legendre_p" => Some(Self::Legendre),
+            "hermite_h" => Some(Self::Hermite),
+            "laguerre_l" => Some(Self::Laguerre),
+            _ => None,
+        }
+    }
+
+    /// p_1 的系数，按幂升序
+    fn first(self) -> Vec<BigRational> {
+        match self {
+            Self::ChebyshevT | Self::Legendre => vec![rational(0, 1), rational(1, 1)],
+            Self::ChebyshevU | Self::Hermite => vec![rational(0, 1), rational(2, 1)],
+            Self::Laguerre => vec![rational(1, 1), rational(-1, 1)],
+        }
+    }
+
+    /// 由 p_n 递推 p_{n+1} 时的 (a_n, b_n, c_n)
+    fn step(self, n: i64) -> (BigRational, BigRational, BigRational) {
+        match self {
+            Self::ChebyshevT | Self::ChebyshevU => (rational(2, 1), rational(0, 1), rational(1, 1)),
+            Self::Legendre => (rational(2 * n + 1, n + 1), rational(0, 1), rational(n, n + 1)),
+            Self::Hermite => (rational(2, 1), rational(0, 1), rational(2 * n, 1)),
+            Self::Laguerre => (rational(-1, n + 1), rational(2 * n + 1, n + 1), rational(n, n + 1)),
+        }
+    }
+
+    /// 第 n 个多项式的系数，按幂升序
+    fn coefficients(self, n: usize) -> Vec<BigRational> {
+        let mut previous = vec![rational(1, 1)];
+        if n == 0 {
+            return previous;
+        }
+        let mut current = self.first();
+        for k in 1..n {
+            let (a, b, c) = self.step(k as i64);
+            let mut next = vec![BigRational::zero(); current.len() + 1];
+            for (i, coefficient) in current.iter().enumerate() {
+                next[i + 1] += &a * coefficient;
+                next[i] += &b * coefficient;
+            }
+            for (i, coefficient) in previous.iter().enumerate() {
+                next[i] -= &c * coefficient;
+            }
+            previous = std::mem::replace(&mut current, next);
+        }
+        current
+    }
+}
+
+/// 第 n 个正交多项式在 `x` 处的表达式；`x` 是变量时按降幂写出，否则代入后化简
+pub fn orthogonal_polynomial(family: OrthogonalFamily, n: usize, x: &Expression) -> Result<Expression, ComputeError> {
+    if n > MAX_DEGREE {
+        return Err(ComputeError::domain_error(tr!("orthogonal.max_degree", limit = MAX_DEGREE, degree = n)));
+    }
+    let coefficients = family.coefficients(n);
+    match x {
+        Expression::Variable(var) => Ok(build(&coefficients, var)),
+        x => {
+            let mut bindings = HashMap::new();
+            bindings.insert("x".to_string(), x.clone());
+            Simplifier::new().simplify(&build(&coefficients, "x").substitute(&bindings))
+        }
+    }
+}
+
+/// 化简正交多项式调用；次数还不是具体的整数时返回 `None`，由调用方保留函数形式
+pub fn simplify_call(name: &str, args: &[Expression]) -> Option<Result<Expression, ComputeError>> {
+    let family = OrthogonalFamily::from_name(name)?;
+    let (n, x) = match args {
+        [n] => (n, Expression::variable("x")),
+        [n, x] => (n, x.clone()),
+        _ => return None,
+    };
+    let Expression::Number(Number::Integer(n)) = n else {
+        return None;
+    };
+    match n.to_usize() {
+        Some(n) => Some(orthogonal_polynomial(family, n, &x)),
+        None => Some(Err(ComputeError::domain_error(tr!("orthogonal.degree", name = name, degree = n)))),
+    }
+}
+
+/// 按降幂写出 `Σ cₖ varᵏ`，有分母时提出公分母
+fn build(coefficients: &[BigRational], var: &str) -> Expression {
+    let denominator = coefficients.iter().fold(BigInt::one(), |lcm, c| lcm.lcm(c.denom()));
+    let mut result: Option<Expression> = None;
+    for (power, coefficient) in coefficients.iter().enumerate().rev() {
+        if coefficient.is_zero() {
+            continue;
+        }
+        let scaled = (coefficient * BigRational::from_integer(denominator.clone())).to_integer();
+        let magnitude = monomial(scaled.abs(), var, power);
+        result = Some(match result {
+            None if scaled.is_negative() => Expression::negate(magnitude),
+            None => magnitude,
+            Some(sum) if scaled.is_negative() => Expression::subtract(sum, magnitude),
+            Some(sum) => Expression::add(sum, magnitude),
+        });
+    }
+    let numerator = result.unwrap_or_else(|| Expression::number(Number::zero()));
+    if denominator.is_one() {
+        numerator
+    } else {
+        Expression::divide(numerator, Expression::Number(Number::Integer(denominator)))
+    }
+}
+
+/// `c * var^power`，系数 1、一次幂与零次幂省略
+fn monomial(coefficient: BigInt, var: &str, power: usize) -> Expression {
+    let coefficient = Expression::Number(Number::Integer(coefficient));
+    let base = match power {
+        0 => return coefficient,
+        1 => Expression::variable(var),
+        _ => Expression::power(Expression::variable(var), Expression::Number(Number::integer(power as i64))),
+    };
+    match &coefficient {
+        Expression::Number(n) if n.is_one() => base,
+        _ => Expression::multiply(coefficient, base),
+    }
+}
+
+fn rational(numerator: i64, denominator: i64) -> BigRational {
+    BigRational::new(BigInt::from(numerator), BigInt::from(denominator))
+}
+
+#[cfg(test)]
+#[path = "orthogonal_tests.rs"]
+mod orthogonal_tests;
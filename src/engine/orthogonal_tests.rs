@@ -0,0 +1,53 @@
+//! # 正交多项式测试
+//!
+//! 测试各族低次多项式的精确形式、公分母的提取、代入与函数调用形式。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::orthogonal::{orthogonal_polynomial, OrthogonalFamily};
+    use crate::engine::simplify::Simplifier;
+    use crate::core::Expression;
+    use crate::test_utils::parse;
+
+    fn simplified(input: &str) -> Expression {
+        Simplifier::new().simplify(&parse(input)).unwrap()
+    }
+
+    fn generate(family: OrthogonalFamily, n: usize) -> Expression {
+        orthogonal_polynomial(family, n, &Expression::variable("x")).unwrap()
+    }
+
+    #[test]
+    fn test_chebyshev() {
+        assert_eq!(generate(OrthogonalFamily::ChebyshevT, 0), parse("1"));
+        assert_eq!(generate(OrthogonalFamily::ChebyshevT, 1), parse("x"));
+        assert_eq!(generate(OrthogonalFamily::ChebyshevT, 2), parse("2*x^2 - 1"));
+        assert_eq!(generate(OrthogonalFamily::ChebyshevT, 5), parse("16*x^5 - 20*x^3 + 5*x"));
+        assert_eq!(generate(OrthogonalFamily::ChebyshevU, 3), parse("8*x^3 - 4*x"));
+    }
+
+    #[test]
+    fn test_legendre() {
+        assert_eq!(generate(OrthogonalFamily::Legendre, 2), parse("(3*x^2 - 1)/2"));
+        assert_eq!(generate(OrthogonalFamily::Legendre, 3), parse("(5*x^3 - 3*x)/2"));
+        assert_eq!(generate(OrthogonalFamily::Legendre, 4), parse("(35*x^4 - 30*x^2 + 3)/8"));
+    }
+
+    #[test]
+    fn test_hermite_and_laguerre() {
+        assert_eq!(generate(OrthogonalFamily::Hermite, 3), parse("8*x^3 - 12*x"));
+        assert_eq!(generate(OrthogonalFamily::Laguerre, 1), parse("-x + 1"));
+        assert_eq!(generate(OrthogonalFamily::Laguerre, 2), parse("(x^2 - 4*x + 2)/2"));
+    }
+
+    #[test]
+    fn test_function_calls() {
+        assert_eq!(simplified("chebyshev_t(2)"), simplified("2*x^2 - 1"));
+        assert_eq!(simplified("legendre_p(2, t)"), simplified("(3*t^2 - 1)/2"));
+        // T_n(cos θ) = cos(nθ) 在 θ = 0 处为 1；代入数值直接求值
+        assert_eq!(simplified("chebyshev_t(7, 1)"), parse("1"));
+        assert_eq!(simplified("legendre_p(2, 1/2)"), simplified("-1/8"));
+        assert!(matches!(simplified("chebyshev_t(n)"), Expression::Function { .. }));
+        assert!(Simplifier::new().simplify(&parse("chebyshev_t(-1)")).is_err());
+    }
+}
@@ -11,6 +11,7 @@ use crate::core::{Expression, Number};
 use crate::engine::{ComputeEngine, ComputeError};
 use crate::engine::lazy::{LazyExpression, DependencyGraph};
 use crate::api::config::ParallelConfig;
+use crate::tr;
 
 /// 并行计算任务
 #[derive(Debug, Clone)]
@@ -92,7 +93,8 @@ impl TaskScheduler {
             id
         };
         
-        let task = ComputeTask::new(task_id, lazy_expr);
+        let estimated_duration = self.config.cost_model.estimated_duration(lazy_expr.original());
+        let task = ComputeTask::new(task_id, lazy_expr).with_estimated_duration(estimated_duration);
         
         {
             let mut pending = self.pending_tasks.lock().unwrap();
@@ -198,7 +200,7 @@ impl ParallelComputeEngine {
             };
             
             Some(pool_builder.build().map_err(|e| ComputeError::UnsupportedOperation {
-                operation: format!("创建线程池失败: {}", e),
+                operation: tr!("parallel.thread_pool", error = e),
             })?)
         } else {
             None
@@ -227,8 +229,8 @@ impl ParallelComputeEngine {
             pool.install(|| {
                 expressions.into_par_iter()
                     .map(|expr| {
-                        // 检查表达式复杂度
-                        if expr.complexity() >= self.config.complexity_threshold {
+                        // 检查表达式成本
+                        if expr.cost(&self.config.cost_model) >= self.config.complexity_threshold as u64 {
                             self.base_engine.simplify(&expr)
                         } else {
                             // 对于简单表达式，直接串行计算可能更快
@@ -356,7 +358,7 @@ impl ParallelComputeEngine {
             if let Some(timeout) = timeout {
                 if start_time.elapsed() > timeout {
                     return Err(ComputeError::UnsupportedOperation {
-                        operation: "任务执行超时".to_string(),
+                        operation: tr!("parallel.timeout").to_string(),
                     });
                 }
             }
@@ -460,7 +462,7 @@ impl ExpressionPreprocessor {
             BinaryOperator::Divide => a.divide(b),
             BinaryOperator::Power => a.power(b),
             _ => Err(ComputeError::UnsupportedOperation {
-                operation: format!("常量运算不支持操作符: {:?}", op),
+                operation: tr!("parallel.unsupported_operator", operation = format!("{:?}", op)),
             }),
         }
     }
@@ -474,7 +476,7 @@ impl ExpressionPreprocessor {
             UnaryOperator::Plus => Ok(n.clone()),
             UnaryOperator::Abs => n.abs(),
             _ => Err(ComputeError::UnsupportedOperation {
-                operation: format!("常量运算不支持操作符: {:?}", op),
+                operation: tr!("parallel.unsupported_operator", operation = format!("{:?}", op)),
             }),
         }
     }
@@ -552,14 +554,16 @@ impl ExpressionPreprocessor {
     /// 分析表达式的并行化潜力
     pub fn analyze_parallelization_potential(&self, expr: &Expression) -> ParallelizationAnalysis {
         let complexity = expr.complexity();
+        let cost = expr.cost(&self.config.cost_model);
         let subexpr_count = self.count_subexpressions(expr);
         let independent_parts = self.find_independent_parts(expr);
         
         ParallelizationAnalysis {
             complexity,
+            cost,
             subexpression_count: subexpr_count,
             independent_parts_count: independent_parts.len(),
-            recommended_parallel: complexity >= self.config.complexity_threshold && independent_parts.len() > 1,
+            recommended_parallel: cost >= self.config.complexity_threshold as u64 && independent_parts.len() > 1,
             estimated_speedup: if independent_parts.len() > 1 {
                 (independent_parts.len() as f64).min(self.config.max_parallel_tasks as f64)
             } else {
@@ -623,6 +627,8 @@ impl ExpressionPreprocessor {
 pub struct ParallelizationAnalysis {
     /// 表达式复杂度
     pub complexity: usize,
+    /// 按配置的成本模型计算的表达式成本
+    pub cost: u64,
     /// 子表达式数量
     pub subexpression_count: usize,
     /// 独立部分数量
@@ -659,6 +665,7 @@ mod tests {
         // 获取任务
         let task = scheduler.get_next_task().unwrap();
         assert_eq!(task.id, task_id);
+        assert_eq!(task.estimated_duration, Some(Duration::from_micros(3)));
         assert_eq!(scheduler.pending_count(), 0);
         assert_eq!(scheduler.running_count(), 1);
         
@@ -735,6 +742,7 @@ mod tests {
         let analysis = preprocessor.analyze_parallelization_potential(&expr);
         
         assert!(analysis.complexity > 0);
+        assert!(analysis.cost > analysis.complexity as u64);
         assert!(analysis.subexpression_count > 1);
         assert!(analysis.independent_parts_count > 0);
     }
@@ -0,0 +1,312 @@
+//! # 部分分式分解
+//!
+//! 把关于单个变量的有理系数有理函数 N/D 拆成多项式部分与
+//! Σ A/(x - r)^k + Σ (Bx + C)/(x² + px + q)^k 之和。
+//! 分母先做无平方分解再提出有理根，剩余的不可约因子只支持二次；
+//! 各项系数通过解线性方程组得到。
+
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, Signed, Zero};
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator};
+use super::ComputeError;
+use super::solver::{SolverEngine, DensePoly};
+use super::polynomial;
+use crate::tr;
+
+/// 分母的首一不可约因子
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Factor {
+    /// x - r
+    Linear(BigRational),
+    /// x² + px + q，没有有理根
+    Quadratic(BigRational, BigRational),
+}
+
+impl Factor {
+    fn coefficients(&self) -> DensePoly {
+        match self {
+            Factor::Linear(root) => vec![-root.clone(), BigRational::one()],
+            Factor::Quadratic(p, q) => vec![q.clone(), p.clone(), BigRational::one()],
+        }
+    }
+}
+
+/// 分解中的一项 (Bx + C) / factor^power，线性因子的 B 总为 0
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct PartialFraction {
+    pub(crate) factor: Factor,
+    pub(crate) power: usize,
+    pub(crate) linear: BigRational,
+    pub(crate) constant: BigRational,
+}
+
+/// 分解结果
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct Decomposition {
+    /// 多项式部分（分子次数不低于分母时的商）
+    pub(crate) polynomial: DensePoly,
+    /// 真分式部分，系数为零的项已去掉
+    pub(crate) fractions: Vec<PartialFraction>,
+}
+
+/// 部分分式分解引擎
+pub struct PartialFractionEngine {
+    solver: SolverEngine,
+}
+
+impl PartialFractionEngine {
+    /// 创建新的分解引擎
+    pub fn new() -> Self {
+        Self {
+            solver: SolverEngine::new(),
+        }
+    }
+
+    /// 将有理函数关于 `var` 分解为部分分式之和
+    pub fn apart(&self, expr: &Expression, var: &str) -> Result<Expression, ComputeError> {
+        let decomposition = self.decompose(expr, var)?;
+        let mut result = if Self::is_zero_poly(&decomposition.polynomial) {
+            None
+        } else {
+            Some(Self::poly_to_expression(&decomposition.polynomial, var))
+        };
+        for fraction in &decomposition.fractions {
+            // 分子系数都不为正时提出负号，写成减法
+            let negative = !fraction.linear.is_positive() && !fraction.constant.is_positive();
+            let (linear, constant) = if negative {
+                (-fraction.linear.clone(), -fraction.constant.clone())
+            } else {
+                (fraction.linear.clone(), fraction.constant.clone())
+            };
+            let numerator = Self::poly_to_expression(&[constant, linear], var);
+            let base = Self::poly_to_expression(&fraction.factor.coefficients(), var);
+            let denominator = if fraction.power == 1 {
+                base
+            } else {
+                Expression::power(base, Expression::Number(Number::integer(fraction.power as i64)))
+            };
+            let term = Expression::divide(numerator, denominator);
+            result = Some(match result {
+                None if negative => Expression::negate(term),
+                None => term,
+                Some(sum) if negative => Expression::subtract(sum, term),
+                Some(sum) => Expression::add(sum, term),
+            });
+        }
+        Ok(result.unwrap_or_else(|| Expression::Number(Number::zero())))
+    }
+
+    /// 分解为结构化的各项
+    pub(crate) fn decompose(&self, expr: &Expression, var: &str) -> Result<Decomposition, ComputeError> {
+        let (numerator, denominator) = Self::split_fraction(expr);
+        let numerator = self.coefficients(&numerator, var)?;
+        let denominator = self.coefficients(&denominator, var)?;
+        if Self::is_zero_poly(&denominator) {
+            return Err(ComputeError::DivisionByZero);
+        }
+
+        let (polynomial, remainder) = SolverEngine::div_rem(&numerator, &denominator);
+        if SolverEngine::degree(&denominator) < 1 || Self::is_zero_poly(&remainder) {
+            return Ok(Decomposition { polynomial, fractions: Vec::new() });
+        }
+
+        let factors = Self::factor_denominator(&denominator)?;
+        let size = SolverEngine::degree(&denominator) as usize;
+
+        // 每个未知系数对应一列：D / f^k（二次因子另有 x·D / f^k）
+        let mut columns: Vec<DensePoly> = Vec::with_capacity(size);
+        for (factor, multiplicity) in &factors {
+            let base = factor.coefficients();
+            let mut power = vec![BigRational::one()];
+            for _ in 0..*multiplicity {
+                power = Self::multiply(&power, &base);
+                let cofactor = SolverEngine::div_exact(&denominator, &power);
+                if matches!(factor, Factor::Quadratic(..)) {
+                    columns.push(Self::multiply(&cofactor, &[BigRational::zero(), BigRational::one()]));
+                }
+                columns.push(cofactor);
+            }
+        }
+
+        let solution = Self::solve_linear(&columns, &remainder, size)
+            .ok_or_else(|| ComputeError::internal(tr!("partial_fractions.unsolvable")))?;
+
+        let mut fractions = Vec::new();
+        let mut unknowns = solution.into_iter();
+        for (factor, multiplicity) in factors {
+            for power in 1..=multiplicity {
+                let linear = match factor {
+                    Factor::Quadratic(..) => unknowns.next().unwrap_or_else(BigRational::zero),
+                    Factor::Linear(_) => BigRational::zero(),
+                };
+                let constant = unknowns.next().unwrap_or_else(BigRational::zero);
+                if !linear.is_zero() || !constant.is_zero() {
+                    fractions.push(PartialFraction { factor: factor.clone(), power, linear, constant });
+                }
+            }
+        }
+
+        Ok(Decomposition { polynomial, fractions })
+    }
+
+    /// 把表达式整理为 (分子, 分母)
+    fn split_fraction(expr: &Expression) -> (Expression, Expression) {
+        let one = || Expression::Number(Number::one());
+        match expr {
+            Expression::BinaryOp { op: BinaryOperator::Divide, left, right } => {
+                let (n1, d1) = Self::split_fraction(left);
+                let (n2, d2) = Self::split_fraction(right);
+                (Expression::multiply(n1, d2), Expression::multiply(d1, n2))
+            }
+            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
+                let (n1, d1) = Self::split_fraction(left);
+                let (n2, d2) = Self::split_fraction(right);
+                (Expression::multiply(n1, n2), Expression::multiply(d1, d2))
+            }
+            Expression::BinaryOp { op: op @ (BinaryOperator::Add | BinaryOperator::Subtract), left, right } => {
+                let (n1, d1) = Self::split_fraction(left);
+                let (n2, d2) = Self::split_fraction(right);
+                let left = Expression::multiply(n1, d2.clone());
+                let right = Expression::multiply(n2, d1.clone());
+                let numerator = if *op == BinaryOperator::Add {
+                    Expression::add(left, right)
+                } else {
+                    Expression::subtract(left, right)
+                };
+                (numerator, Expression::multiply(d1, d2))
+            }
+            Expression::BinaryOp { op: BinaryOperator::Power, left, right } => {
+                match right.as_ref() {
+                    Expression::Number(n) if n.is_negative() && n.to_integer().is_some() => {
+                        (one(), Expression::power(left.as_ref().clone(), Expression::Number(-n.clone())))
+                    }
+                    Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+                        (one(), Expression::power(left.as_ref().clone(), operand.as_ref().clone()))
+                    }
+                    _ => (expr.clone(), one()),
+                }
+            }
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+                let (n, d) = Self::split_fraction(operand);
+                (Expression::negate(n), d)
+            }
+            _ => (expr.clone(), one()),
+        }
+    }
+
+    /// 提取关于 var 的有理系数
+    fn coefficients(&self, expr: &Expression, var: &str) -> Result<DensePoly, ComputeError> {
+        polynomial::require_polynomial(expr, var, tr!("partial_fractions.operation"))?;
+        self.solver.extract_coefficients(expr, var).map_err(|_| {
+            ComputeError::unsupported_operation(tr!("partial_fractions.rational_coefficients", variable = var))
+        })
+    }
+
+    /// 分母分解为首一不可约因子及其重数
+    fn factor_denominator(denominator: &DensePoly) -> Result<Vec<(Factor, usize)>, ComputeError> {
+        let mut factors = Vec::new();
+        for (mut part, multiplicity) in SolverEngine::square_free_decomposition(denominator) {
+            if SolverEngine::degree(&part) >= 1 && part[0].is_zero() {
+                part.remove(0);
+                factors.push((Factor::Linear(BigRational::zero()), multiplicity));
+            }
+            for candidate in SolverEngine::rational_root_candidates(&part) {
+                if SolverEngine::degree(&part) < 1 {
+                    break;
+                }
+                if SolverEngine::eval_dense(&part, &candidate).is_zero() {
+                    part = SolverEngine::div_exact(&part, &vec![-candidate.clone(), BigRational::one()]);
+                    factors.push((Factor::Linear(candidate), multiplicity));
+                }
+            }
+            match SolverEngine::degree(&part) {
+                d if d < 1 => {}
+                1 => factors.push((Factor::Linear(-&part[0] / &part[1]), multiplicity)),
+                2 => factors.push((Factor::Quadratic(&part[1] / &part[2], &part[0] / &part[2]), multiplicity)),
+                _ => return Err(ComputeError::unsupported_operation(tr!("partial_fractions.irreducible_factor"))),
+            }
+        }
+        Ok(factors)
+    }
+
+    /// 以各列为系数矩阵解 M·a = rhs（Gauss 消元），方程组奇异时返回 None
+    fn solve_linear(columns: &[DensePoly], rhs: &DensePoly, size: usize) -> Option<Vec<BigRational>> {
+        let entry = |p: &DensePoly, i: usize| p.get(i).cloned().unwrap_or_else(BigRational::zero);
+        let mut rows: Vec<Vec<BigRational>> = (0..size)
+            .map(|i| columns.iter().map(|column| entry(column, i)).chain(std::iter::once(entry(rhs, i))).collect())
+            .collect();
+
+        for col in 0..size {
+            let pivot = (col..size).find(|&row| !rows[row][col].is_zero())?;
+            rows.swap(col, pivot);
+            let lead = rows[col][col].clone();
+            for value in rows[col].iter_mut() {
+                *value = &*value / &lead;
+            }
+            for row in 0..size {
+                if row != col && !rows[row][col].is_zero() {
+                    let factor = rows[row][col].clone();
+                    let pivot_row = rows[col].clone();
+                    for (value, pivot_value) in rows[row].iter_mut().zip(&pivot_row).skip(col) {
+                        *value = &*value - &factor * pivot_value;
+                    }
+                }
+            }
+        }
+        Some(rows.into_iter().map(|row| row[size].clone()).collect())
+    }
+
+    fn multiply(a: &[BigRational], b: &[BigRational]) -> DensePoly {
+        let mut product = vec![BigRational::zero(); a.len() + b.len() - 1];
+        for (i, x) in a.iter().enumerate() {
+            for (j, y) in b.iter().enumerate() {
+                product[i + j] = &product[i + j] + x * y;
+            }
+        }
+        SolverEngine::trim(product)
+    }
+
+    fn is_zero_poly(p: &DensePoly) -> bool {
+        p.iter().all(|c| c.is_zero())
+    }
+
+    /// 稠密多项式转换为表达式，按降幂排列
+    pub(crate) fn poly_to_expression(coeffs: &[BigRational], var: &str) -> Expression {
+        let mut result: Option<Expression> = None;
+        for (power, coefficient) in coeffs.iter().enumerate().rev() {
+            if coefficient.is_zero() {
+                continue;
+            }
+            let monomial = match power {
+                0 => None,
+                1 => Some(Expression::variable(var)),
+                _ => Some(Expression::power(Expression::variable(var), Expression::Number(Number::integer(power as i64)))),
+            };
+            let magnitude = coefficient.abs();
+            let term = match monomial {
+                None => Expression::Number(SolverEngine::rational_to_number(magnitude)),
+                Some(monomial) if magnitude.is_one() => monomial,
+                Some(monomial) => Expression::multiply(Expression::Number(SolverEngine::rational_to_number(magnitude)), monomial),
+            };
+            result = Some(match result {
+                None if coefficient.is_negative() => Expression::negate(term),
+                None => term,
+                Some(sum) if coefficient.is_negative() => Expression::subtract(sum, term),
+                Some(sum) => Expression::add(sum, term),
+            });
+        }
+        result.unwrap_or_else(|| Expression::Number(Number::Integer(BigInt::zero())))
+    }
+
+}
+
+impl Default for PartialFractionEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[path = "partial_fractions_tests.rs"]
+mod partial_fractions_tests;
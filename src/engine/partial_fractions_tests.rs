@@ -0,0 +1,45 @@
+//! # 部分分式分解测试
+//!
+//! 测试线性因子、重根、二次因子以及假分式的分解。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::partial_fractions::PartialFractionEngine;
+    use crate::engine::equivalence::EquivalenceChecker;
+    use crate::core::Expression;
+    use crate::test_utils::parse;
+
+    fn apart(input: &str) -> Expression {
+        PartialFractionEngine::new().apart(&parse(input), "s").unwrap()
+    }
+
+    #[test]
+    fn test_distinct_linear_factors() {
+        assert_eq!(apart("1/(s*(s+1))").to_string(), "1 / s - 1 / (s + 1)");
+        assert_eq!(apart("(s+3)/((s+1)*(s+2))").to_string(), "2 / (s + 1) - 1 / (s + 2)");
+    }
+
+    #[test]
+    fn test_repeated_and_quadratic_factors() {
+        let cases = ["1/(s+2)^2", "(2*s+1)/(s^2*(s-1))", "1/((s+1)*(s^2+1))", "s/(s^2+2*s+5)^2", "1/(s^2-2)"];
+        for input in cases {
+            let result = apart(input);
+            assert!(EquivalenceChecker::new().are_equivalent(&result, &parse(input)).unwrap().equivalent, "{} => {}", input, result);
+        }
+        assert_eq!(apart("1/(s+2)^2").to_string(), "1 / (s + 2)^2");
+    }
+
+    #[test]
+    fn test_improper_fraction() {
+        // 分子次数不低于分母时先分出多项式部分
+        let result = apart("(s^2+1)/(s-1)");
+        assert_eq!(result.to_string(), "s + 1 + 2 / (s - 1)");
+    }
+
+    #[test]
+    fn test_unsupported_inputs() {
+        let engine = PartialFractionEngine::new();
+        assert!(engine.apart(&parse("1/(s^3+s+1)"), "s").is_err());
+        assert!(engine.apart(&parse("sin(s)/s"), "s").is_err());
+    }
+}
@@ -1,13 +1,19 @@
 //! # 多项式运算系统
 //!
 //! 实现多项式的展开、因式分解、同类项收集等功能。
+//!
+//! [`is_polynomial`]、[`polynomial_degree`]、[`as_polynomial`] 判断表达式是否为某个变量的多项式：
+//! 系数可以含其他变量或任意不含该变量的式子，可以除以不含该变量的式子，如 `(x^2+1)/2`；
+//! `sin(x)`、`1/(x+1)`、`x^(1/2)`、`2^x` 都不是 `x` 的多项式。
 
-use crate::core::{Expression, Number, BinaryOperator, UnaryOperator};
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator, memory};
 use crate::engine::error::ComputeError;
+use crate::engine::simplify::Simplifier;
 use std::collections::HashMap;
 use num_bigint::BigInt;
 use num_rational::BigRational;
 use num_traits::{Zero, Signed};
+use crate::tr;
 
 /// 多项式项，表示为系数 * 变量^指数的形式
 #[derive(Debug, Clone, PartialEq)]
@@ -95,7 +101,7 @@ impl PolynomialTerm {
             let self_power = self.variables.get(var).copied().unwrap_or(0);
             if self_power < *other_power {
                 return Err(ComputeError::UnsupportedOperation {
-                    operation: "多项式除法：被除数的变量次数小于除数".to_string()
+                    operation: tr!("polynomial.division_degree").to_string()
                 });
             }
             
@@ -119,10 +125,16 @@ impl PolynomialTerm {
             return Expression::Number(Number::zero());
         }
         
-        let mut result = Expression::Number(self.coefficient.clone());
+        // 不含多项式变量的非多项式部分（如 sin(y)）以符号系数保存，还原为原表达式
+        let mut result = match &self.coefficient {
+            Number::Symbolic(expr) => expr.as_ref().clone(),
+            coefficient => Expression::Number(coefficient.clone()),
+        };
         
-        // 添加变量部分
-        for (var, power) in &self.variables {
+        // 添加变量部分，按变量名排序使结果确定
+        let mut variables: Vec<_> = self.variables.iter().collect();
+        variables.sort();
+        for (var, power) in variables {
             let var_expr = if *power == 1 {
                 Expression::Variable(var.clone())
             } else {
@@ -273,9 +285,11 @@ impl Polynomial {
                 return degree_cmp;
             }
             
-            // 然后按变量名排序
-            let a_vars: Vec<_> = a.variables.keys().collect();
-            let b_vars: Vec<_> = b.variables.keys().collect();
+            // 然后按变量名与指数排序
+            let mut a_vars: Vec<_> = a.variables.iter().collect();
+            let mut b_vars: Vec<_> = b.variables.iter().collect();
+            a_vars.sort();
+            b_vars.sort();
             a_vars.cmp(&b_vars)
         });
     }
@@ -459,6 +473,7 @@ impl PolynomialEngine {
     
     /// 将表达式转换为多项式（如果可能）
     pub fn expression_to_polynomial(&self, expr: &Expression) -> Result<Polynomial, ComputeError> {
+        memory::check_memory_limit()?;
         match expr {
             Expression::Number(n) => Ok(Polynomial::constant(n.clone())),
             
@@ -471,7 +486,7 @@ impl PolynomialEngine {
                 match op {
                     BinaryOperator::Add => Ok(left_poly.add(&right_poly)),
                     BinaryOperator::Subtract => Ok(left_poly.subtract(&right_poly)),
-                    BinaryOperator::Multiply => Ok(left_poly.multiply(&right_poly)),
+                    BinaryOperator::Multiply => Self::accounted(left_poly.multiply(&right_poly)),
                     BinaryOperator::Power => {
                         // 只支持正整数幂
                         if let Expression::Number(exp) = right.as_ref() {
@@ -479,17 +494,17 @@ impl PolynomialEngine {
                                 if exp_int >= BigInt::zero() {
                                     use num_traits::ToPrimitive;
                                     if let Some(exp_u32) = exp_int.to_u32() {
-                                        return Ok(self.power(&left_poly, exp_u32));
+                                        return self.power(&left_poly, exp_u32);
                                     }
                                 }
                             }
                         }
                         Err(ComputeError::UnsupportedOperation {
-                            operation: "多项式只支持正整数幂".to_string()
+                            operation: tr!("polynomial.positive_power").to_string()
                         })
                     }
                     _ => Err(ComputeError::UnsupportedOperation {
-                        operation: format!("多项式不支持 {:?} 运算", op)
+                        operation: tr!("polynomial.unsupported_operation", operation = format!("{:?}", op))
                     })
                 }
             }
@@ -509,37 +524,93 @@ impl PolynomialEngine {
                     }
                     UnaryOperator::Plus => self.expression_to_polynomial(operand),
                     _ => Err(ComputeError::UnsupportedOperation {
-                        operation: format!("多项式不支持 {:?} 运算", op)
+                        operation: tr!("polynomial.unsupported_operation", operation = format!("{:?}", op))
                     })
                 }
             }
             
             _ => Err(ComputeError::UnsupportedOperation {
-                operation: "表达式无法转换为多项式".to_string()
+                operation: tr!("polynomial.not_convertible").to_string()
             })
         }
     }
     
+    /// 把表达式作为 `var` 的多项式转换为内部表示，不是多项式时返回指出问题子式的错误
+    ///
+    /// 与 [`Self::expression_to_polynomial`] 不同，可以除以不含 `var` 的式子；其余变量照常作为多项式变量，
+    /// 不含 `var` 的非多项式部分（如 `sin(y)`、`pi`、`1/y`）整体作为符号系数。
+    pub fn polynomial_in(&self, expr: &Expression, var: &str, operation: &str) -> Result<Polynomial, ComputeError> {
+        require_polynomial(expr, var, operation)?;
+        self.convert_in(expr, var)
+    }
+    
+    /// [`Self::polynomial_in`] 的转换部分，调用前已确认 `expr` 是 `var` 的多项式
+    fn convert_in(&self, expr: &Expression, var: &str) -> Result<Polynomial, ComputeError> {
+        memory::check_memory_limit()?;
+        let opaque = || Polynomial::constant(Number::Symbolic(Box::new(expr.clone())));
+        match expr {
+            Expression::Number(n) => Ok(Polynomial::constant(n.clone())),
+            Expression::Variable(name) => Ok(Polynomial::variable(name.clone())),
+            Expression::BinaryOp { op, left, right } => match op {
+                BinaryOperator::Add => Ok(self.convert_in(left, var)?.add(&self.convert_in(right, var)?)),
+                BinaryOperator::Subtract => Ok(self.convert_in(left, var)?.subtract(&self.convert_in(right, var)?)),
+                BinaryOperator::Multiply => Self::accounted(self.convert_in(left, var)?.multiply(&self.convert_in(right, var)?)),
+                BinaryOperator::Divide => {
+                    // 分母不含 var，乘以它的倒数
+                    let reciprocal = match right.as_ref() {
+                        Expression::Number(n) if n.is_zero() => return Err(ComputeError::DivisionByZero),
+                        Expression::Number(n) => Number::one() / n.clone(),
+                        denominator => Number::Symbolic(Box::new(Expression::divide(Expression::Number(Number::one()), denominator.clone()))),
+                    };
+                    Ok(self.convert_in(left, var)?.multiply(&Polynomial::constant(reciprocal)))
+                }
+                BinaryOperator::Power => {
+                    use num_traits::ToPrimitive;
+                    let exponent = match right.as_ref() {
+                        Expression::Number(n) => n.to_integer().and_then(|n| n.to_u32()).filter(|&n| n <= i32::MAX as u32),
+                        _ => None,
+                    };
+                    match exponent {
+                        Some(exponent) => self.power(&self.convert_in(left, var)?, exponent),
+                        None if depends_on(left, var) => Err(ComputeError::resource_limit(tr!("polynomial.degree_too_high", expression = expr))),
+                        None => Ok(opaque()),
+                    }
+                }
+                _ => Ok(opaque()),
+            },
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => Ok(Polynomial::zero().subtract(&self.convert_in(operand, var)?)),
+            Expression::UnaryOp { op: UnaryOperator::Plus, operand } => self.convert_in(operand, var),
+            _ => Ok(opaque()),
+        }
+    }
+    
     /// 多项式幂运算
-    fn power(&self, poly: &Polynomial, exponent: u32) -> Polynomial {
+    fn power(&self, poly: &Polynomial, exponent: u32) -> Result<Polynomial, ComputeError> {
         if exponent == 0 {
-            return Polynomial::constant(Number::one());
+            return Ok(Polynomial::constant(Number::one()));
         }
         if exponent == 1 {
-            return poly.clone();
+            return Ok(poly.clone());
         }
         
         // 快速幂算法
-        let half_power = self.power(poly, exponent / 2);
-        let result = half_power.multiply(&half_power);
+        let half_power = self.power(poly, exponent / 2)?;
+        let result = Self::accounted(half_power.multiply(&half_power))?;
         
         if exponent % 2 == 0 {
-            result
+            Ok(result)
         } else {
-            result.multiply(poly)
+            Self::accounted(result.multiply(poly))
         }
     }
     
+    /// 乘积的项数可能成倍增长，把它计入内存账本并检查上限
+    fn accounted(poly: Polynomial) -> Result<Polynomial, ComputeError> {
+        memory::account_bytes(poly.terms.len() * std::mem::size_of::<PolynomialTerm>());
+        memory::check_memory_limit()?;
+        Ok(poly)
+    }
+    
     /// 展开表达式
     pub fn expand(&self, expr: &Expression) -> Result<Expression, ComputeError> {
         let poly = self.expression_to_polynomial(expr)?;
@@ -588,6 +659,36 @@ impl PolynomialEngine {
         Ok(result_poly.to_expression())
     }
     
+    /// 关于 `var` 的系数多项式，按次数从低到高排列，末项为首项系数；零多项式返回空列表
+    pub(crate) fn coefficient_polynomials(&self, expr: &Expression, var: &str) -> Result<Vec<Polynomial>, ComputeError> {
+        let poly = self.polynomial_in(expr, var, tr!("polynomial.coefficients"))?;
+        let mut coefficients = vec![Polynomial::zero(); poly.degree_of(var).max(0) as usize + 1];
+        for mut term in poly.terms {
+            let power = term.variables.remove(var).unwrap_or(0);
+            let slot = &mut coefficients[power as usize];
+            *slot = slot.add(&Polynomial::new(vec![term]));
+        }
+        while coefficients.last().is_some_and(Polynomial::is_zero) {
+            coefficients.pop();
+        }
+        Ok(coefficients)
+    }
+    
+    /// 关于 `var` 的系数，按幂升序排列，缺项以 0 占位；其余变量留在化简后的系数里，零多项式为 `[0]`
+    pub fn poly_coeffs(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError> {
+        let coefficients = self.coefficient_polynomials(expr, var)?;
+        if coefficients.is_empty() {
+            return Ok(vec![Expression::Number(Number::zero())]);
+        }
+        let mut simplifier = Simplifier::new();
+        coefficients.iter().map(|c| simplifier.simplify(&c.to_expression())).collect()
+    }
+    
+    /// 关于 `var` 的次数，零多项式按 0 次计
+    pub fn degree(&self, expr: &Expression, var: &str) -> Result<usize, ComputeError> {
+        Ok(self.coefficient_polynomials(expr, var)?.len().saturating_sub(1))
+    }
+    
     /// 简单因式分解（提取公因子）
     pub fn factor(&self, expr: &Expression) -> Result<Expression, ComputeError> {
         let poly = self.expression_to_polynomial(expr)?;
@@ -706,6 +807,92 @@ impl Default for PolynomialEngine {
     }
 }
 
+/// 展开求准确次数时允许的最高次数上界，更高时只按结构给出次数
+const MAX_EXPANDED_DEGREE: u32 = 64;
+
+/// 是否为 `var` 的多项式，系数含其他变量也算
+pub fn is_polynomial(expr: &Expression, var: &str) -> bool {
+    non_polynomial_part(expr, var).is_none()
+}
+
+/// 使 `expr` 不是 `var` 的多项式的第一个子表达式，如 `x + sin(x)` 中的 `sin(x)`、`1/(x+1)` 本身；是多项式时返回 `None`
+pub fn non_polynomial_part<'a>(expr: &'a Expression, var: &str) -> Option<&'a Expression> {
+    if !depends_on(expr, var) {
+        return None;
+    }
+    match expr {
+        Expression::Variable(_) => None,
+        Expression::BinaryOp { op: BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply, left, right } => {
+            non_polynomial_part(left, var).or_else(|| non_polynomial_part(right, var))
+        }
+        Expression::BinaryOp { op: BinaryOperator::Divide, left, right } if !depends_on(right, var) => non_polynomial_part(left, var),
+        Expression::BinaryOp { op: BinaryOperator::Power, left, right } if natural_exponent(right).is_some() => non_polynomial_part(left, var),
+        Expression::UnaryOp { op: UnaryOperator::Negate | UnaryOperator::Plus, operand } => non_polynomial_part(operand, var),
+        _ => Some(expr),
+    }
+}
+
+/// 作为 `var` 的多项式的次数，零多项式按 0 次计；不是多项式时返回 `None`
+///
+/// 次数不高时展开计算，`x^2 + x - x^2` 得到 1；更高时按结构计算不展开，如 `(x^3 + 1)^1000000` 得到 3000000。
+pub fn polynomial_degree(expr: &Expression, var: &str) -> Option<BigInt> {
+    if !is_polynomial(expr, var) {
+        return None;
+    }
+    let bound = degree_bound(expr, var);
+    if bound <= BigInt::from(MAX_EXPANDED_DEGREE) {
+        if let Ok(poly) = PolynomialEngine::new().convert_in(expr, var) {
+            return Some(BigInt::from(poly.degree_of(var).max(0)));
+        }
+    }
+    Some(bound)
+}
+
+/// 转换为内部多项式表示，见 [`PolynomialEngine::polynomial_in`]；不是多项式或无法展开时返回 `None`
+pub fn as_polynomial(expr: &Expression, var: &str) -> Option<Polynomial> {
+    PolynomialEngine::new().polynomial_in(expr, var, "").ok()
+}
+
+/// 要求 `expr` 是 `var` 的多项式，否则返回指出问题子式的错误，`operation` 是需要多项式的操作名
+pub fn require_polynomial(expr: &Expression, var: &str, operation: &str) -> Result<(), ComputeError> {
+    match non_polynomial_part(expr, var) {
+        Some(part) => Err(ComputeError::not_polynomial(operation, var, part)),
+        None => Ok(()),
+    }
+}
+
+/// 按结构得到的次数上界：和取较大者，积相加，幂相乘；调用前已确认是多项式
+fn degree_bound(expr: &Expression, var: &str) -> BigInt {
+    if !depends_on(expr, var) {
+        return BigInt::zero();
+    }
+    match expr {
+        Expression::Variable(_) => BigInt::from(1),
+        Expression::BinaryOp { op: BinaryOperator::Add | BinaryOperator::Subtract, left, right } => {
+            degree_bound(left, var).max(degree_bound(right, var))
+        }
+        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => degree_bound(left, var) + degree_bound(right, var),
+        Expression::BinaryOp { op: BinaryOperator::Divide, left, .. } => degree_bound(left, var),
+        Expression::BinaryOp { op: BinaryOperator::Power, left, right } => {
+            degree_bound(left, var) * natural_exponent(right).unwrap_or_default()
+        }
+        Expression::UnaryOp { operand, .. } => degree_bound(operand, var),
+        _ => BigInt::zero(),
+    }
+}
+
+/// 非负整数指数
+fn natural_exponent(exponent: &Expression) -> Option<BigInt> {
+    match exponent {
+        Expression::Number(n) => n.to_integer().filter(|n| !n.is_negative()),
+        _ => None,
+    }
+}
+
+fn depends_on(expr: &Expression, var: &str) -> bool {
+    expr.get_variables().iter().any(|v| v == var)
+}
+
 #[cfg(test)]
 #[path = "polynomial_tests.rs"]
 mod polynomial_tests;
\ No newline at end of file
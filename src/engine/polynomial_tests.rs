@@ -452,4 +452,96 @@ fn test_multivariate_polynomial() {
     }
     
     assert!(found_xy && found_x && found_y && found_const);
-}
\ No newline at end of file
+}
+#[test]
+fn test_poly_coeffs_and_degree() {
+    use crate::parser::{Parser, syntax::ExpressionParser};
+    let parse = |input: &str| ExpressionParser::new().parse(input).unwrap();
+    let engine = PolynomialEngine::new();
+    
+    // 3x^2 + x + 5 的系数按幂升序为 [5, 1, 3]
+    let expr = parse("3*x^2+x+5");
+    let expected: Vec<Expression> = [5, 1, 3].iter().map(|&c| Expression::number(Number::integer(c))).collect();
+    assert_eq!(engine.poly_coeffs(&expr, "x").unwrap(), expected);
+    assert_eq!(engine.degree(&expr, "x").unwrap(), 2);
+    
+    // 缺项以 0 占位，其余变量留在系数里
+    let expr = parse("x^3 - y*x");
+    let coeffs = engine.poly_coeffs(&expr, "x").unwrap();
+    assert_eq!(coeffs.len(), 4);
+    assert_eq!(coeffs[0], Expression::number(Number::integer(0)));
+    assert_eq!(coeffs[1].to_string(), "-y");
+    assert_eq!(coeffs[2], Expression::number(Number::integer(0)));
+    assert_eq!(engine.degree(&expr, "y").unwrap(), 1);
+    
+    // 零多项式为 [0]，0 次
+    let zero = parse("x - x");
+    assert_eq!(engine.poly_coeffs(&zero, "x").unwrap(), vec![Expression::number(Number::integer(0))]);
+    assert_eq!(engine.degree(&zero, "x").unwrap(), 0);
+    
+    // 非多项式报错
+    for input in ["sin(x) + 1", "x^(1/2)", "2^x"] {
+        assert!(matches!(engine.poly_coeffs(&parse(input), "x"), Err(ComputeError::DomainError { .. })), "{}", input);
+    }
+}
+
+#[test]
+fn test_is_polynomial() {
+    use crate::parser::{Parser, syntax::ExpressionParser};
+    let parse = |input: &str| ExpressionParser::new().parse(input).unwrap();
+    
+    // 系数可以含其他变量，可以除以不含 x 的式子
+    for input in ["3*x^2 + x + 5", "a*x^2 + sin(y)*x", "(x^2+1)/2", "x/y", "-(x+1)^3", "7", "sin(y)"] {
+        assert!(is_polynomial(&parse(input), "x"), "{}", input);
+    }
+    for input in ["sin(x)", "1/x", "1/(x+1)", "x^(1/2)", "2^x", "x^(-1)", "x^n", "((x^2+1)/x)*2"] {
+        assert!(!is_polynomial(&parse(input), "x"), "{}", input);
+    }
+    
+    // 指出使表达式不是多项式的子式
+    assert_eq!(non_polynomial_part(&parse("x^2 + sin(x)"), "x"), Some(&parse("sin(x)")));
+    assert_eq!(non_polynomial_part(&parse("3 + 1/(x+1)"), "x"), Some(&parse("1/(x+1)")));
+    let error = require_polynomial(&parse("x + sin(x)"), "x", "solve").unwrap_err();
+    assert!(matches!(error, ComputeError::DomainError { ref message } if message.contains("sin(x)")), "{:?}", error);
+}
+
+#[test]
+fn test_polynomial_degree() {
+    use crate::parser::{Parser, syntax::ExpressionParser};
+    let parse = |input: &str| ExpressionParser::new().parse(input).unwrap();
+    let degree = |input: &str| polynomial_degree(&parse(input), "x");
+    
+    assert_eq!(degree("3*x^2 + x + 5"), Some(BigInt::from(2)));
+    assert_eq!(degree("(x^2+1)/2"), Some(BigInt::from(2)));
+    assert_eq!(degree("y^5*x"), Some(BigInt::from(1)));
+    assert_eq!(degree("sin(y)"), Some(BigInt::from(0)));
+    // 展开后相消
+    assert_eq!(degree("x^2 + x - x^2"), Some(BigInt::from(1)));
+    assert_eq!(degree("x - x"), Some(BigInt::from(0)));
+    // 次数很高时不展开
+    assert_eq!(degree("(x^3 + 1)^1000000"), Some(BigInt::from(3_000_000)));
+    let huge: BigInt = "1000000000000000000000000000000".parse().unwrap();
+    assert_eq!(degree("x^1000000000000000000000000000000"), Some(huge));
+    assert_eq!(degree("1/(x+1)"), None);
+}
+
+#[test]
+fn test_as_polynomial() {
+    use crate::parser::{Parser, syntax::ExpressionParser};
+    let parse = |input: &str| ExpressionParser::new().parse(input).unwrap();
+    
+    let poly = as_polynomial(&parse("(x^2+1)/2"), "x").unwrap();
+    assert_eq!(poly.degree_of("x"), 2);
+    assert!(poly.terms.iter().all(|term| term.coefficient == Number::rational(1, 2)));
+    
+    // 不含 x 的非多项式部分作为符号系数保留
+    let poly = as_polynomial(&parse("sin(y)*x"), "x").unwrap();
+    assert_eq!(poly.to_expression().to_string(), "sin(y) * x");
+    
+    assert!(as_polynomial(&parse("x + sin(x)"), "x").is_none());
+    assert!(as_polynomial(&parse("x^1000000000000"), "x").is_none());
+    
+    // 除以不含 x 的式子后仍可提取系数
+    let coeffs = PolynomialEngine::new().poly_coeffs(&parse("(x^2-4)/2"), "x").unwrap();
+    assert_eq!(coeffs.len(), 3);
+}
@@ -0,0 +1,58 @@
+//! # 可设种子的随机数生成器
+//!
+//! 供概率算法（Miller-Rabin 素性检验、Pollard rho 分解）使用。算法为 SplitMix64：
+//! 同一种子总是产生同一序列，便于复现与调试；未指定种子时从系统熵取种。
+
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{SystemTime, UNIX_EPOCH};
+use num_bigint::{BigInt, BigUint, Sign};
+
+/// SplitMix64 伪随机数生成器
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeededRng {
+    state: u64,
+}
+
+impl SeededRng {
+    /// 用给定种子创建
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    /// 用随机种子创建
+    pub fn from_entropy() -> Self {
+        let mut hasher = RandomState::new().build_hasher();
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_nanos()).unwrap_or_default();
+        hasher.write_u128(nanos);
+        Self::new(hasher.finish())
+    }
+
+    /// 下一个 64 位随机数
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// `[low, high)` 内的随机整数，要求 `low < high`
+    pub fn bigint_range(&mut self, low: &BigInt, high: &BigInt) -> BigInt {
+        let span = (high - low).magnitude().clone();
+        // 多取 64 位再取模，使分布的偏差可以忽略
+        let words = span.bits() / 32 + 3;
+        let digits: Vec<u32> = (0..words).map(|_| self.next_u64() as u32).collect();
+        low + BigInt::from_biguint(Sign::Plus, BigUint::new(digits) % span)
+    }
+}
+
+impl Default for SeededRng {
+    fn default() -> Self {
+        Self::from_entropy()
+    }
+}
+
+#[cfg(test)]
+#[path = "random_tests.rs"]
+mod random_tests;
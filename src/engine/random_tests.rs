@@ -0,0 +1,34 @@
+//! # 随机数生成器测试
+//!
+//! 测试种子的可复现性与整数区间采样。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::random::SeededRng;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn test_same_seed_same_sequence() {
+        let mut a = SeededRng::new(2024);
+        let mut b = SeededRng::new(2024);
+        let first: Vec<u64> = (0..16).map(|_| a.next_u64()).collect();
+        let second: Vec<u64> = (0..16).map(|_| b.next_u64()).collect();
+        assert_eq!(first, second);
+
+        let mut c = SeededRng::new(2025);
+        let third: Vec<u64> = (0..16).map(|_| c.next_u64()).collect();
+        assert_ne!(first, third);
+    }
+
+    #[test]
+    fn test_bigint_range() {
+        let mut rng = SeededRng::new(7);
+        let low = BigInt::from(-5);
+        let high = BigInt::parse_bytes(b"100000000000000000000000", 10).unwrap();
+        for _ in 0..200 {
+            let value = rng.bigint_range(&low, &high);
+            assert!(value >= low && value < high);
+        }
+        assert_eq!(rng.bigint_range(&BigInt::from(3), &BigInt::from(4)), BigInt::from(3));
+    }
+}
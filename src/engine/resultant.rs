@@ -0,0 +1,112 @@
+//! # 结式与判别式
+//!
+//! 把多项式看作关于 `var` 的一元多项式，系数是其余变量的多项式，结式为 Sylvester 矩阵的行列式。
+//! 行列式按列子集记忆化地做 Laplace 展开，全程只用环上的加法与乘法，不需要多元多项式的整除。
+
+use std::collections::HashMap;
+use crate::core::{Expression, Number};
+use super::ComputeError;
+use super::polynomial::{Polynomial, PolynomialEngine};
+use super::simplify::Simplifier;
+use crate::tr;
+
+/// Sylvester 矩阵允许的最大阶数，记忆化展开的代价随阶数指数增长
+const MAX_SYLVESTER_ORDER: usize = 16;
+
+/// `p` 与 `q` 关于 `var` 的结式，两者的公共根使结式为零
+pub fn resultant(p: &Expression, q: &Expression, var: &str) -> Result<Expression, ComputeError> {
+    let p = coefficients(p, var)?;
+    let q = coefficients(q, var)?;
+    if p.is_empty() || q.is_empty() {
+        return Ok(Expression::Number(Number::zero()));
+    }
+    finish(determinant(&sylvester(&p, &q))?)
+}
+
+/// `p` 关于 `var` 的判别式：`(-1)^(n(n-1)/2) · Res(p, p') / aₙ`，为零当且仅当有重根
+pub fn discriminant(p: &Expression, var: &str) -> Result<Expression, ComputeError> {
+    let p = coefficients(p, var)?;
+    let n = p.len().saturating_sub(1);
+    if n < 1 {
+        return Err(ComputeError::domain_error(tr!("resultant.discriminant_degree", variable = var)));
+    }
+    let derivative: Vec<Polynomial> = p.iter().enumerate().skip(1)
+        .map(|(k, c)| c.multiply(&Polynomial::constant(Number::from(k as i64))))
+        .collect();
+
+    // Sylvester 矩阵第一列只有 aₙ 与 n·aₙ 两项，整列除以 aₙ 即可精确地消去首项系数
+    let mut matrix = sylvester(&p, &derivative);
+    matrix[0][0] = Polynomial::constant(Number::one());
+    matrix[n - 1][0] = Polynomial::constant(Number::from(n as i64));
+    let mut value = determinant(&matrix)?;
+    if (n * (n - 1) / 2) % 2 == 1 {
+        value = Polynomial::zero().subtract(&value);
+    }
+    finish(value)
+}
+
+/// 按 `var` 的次数从低到高排列的系数，末项为首项系数；零多项式返回空列表
+pub(crate) fn coefficients(expr: &Expression, var: &str) -> Result<Vec<Polynomial>, ComputeError> {
+    PolynomialEngine::new().coefficient_polynomials(expr, var)
+}
+
+/// Sylvester 矩阵：前 n 行为 p 的系数逐行右移，后 m 行为 q 的系数，系数按次数从高到低排列
+fn sylvester(p: &[Polynomial], q: &[Polynomial]) -> Vec<Vec<Polynomial>> {
+    let (m, n) = (p.len() - 1, q.len() - 1);
+    let order = m + n;
+    let shifted_rows = |coefficients: &[Polynomial], count: usize| {
+        (0..count).map(|shift| {
+            let mut row = vec![Polynomial::zero(); order];
+            for (i, c) in coefficients.iter().rev().enumerate() {
+                row[shift + i] = c.clone();
+            }
+            row
+        }).collect::<Vec<_>>()
+    };
+    let mut matrix = shifted_rows(p, n);
+    matrix.extend(shifted_rows(q, m));
+    matrix
+}
+
+/// 行列式，按行展开并以已用列的集合记忆子式
+fn determinant(matrix: &[Vec<Polynomial>]) -> Result<Polynomial, ComputeError> {
+    if matrix.len() > MAX_SYLVESTER_ORDER {
+        return Err(ComputeError::unsupported_operation(tr!(
+            "resultant.sylvester_order", order = matrix.len(), limit = MAX_SYLVESTER_ORDER
+        )));
+    }
+    Ok(minor(matrix, 0, 0, &mut HashMap::new()))
+}
+
+fn minor(matrix: &[Vec<Polynomial>], row: usize, used: u32, memo: &mut HashMap<u32, Polynomial>) -> Polynomial {
+    if row == matrix.len() {
+        return Polynomial::constant(Number::one());
+    }
+    if let Some(value) = memo.get(&used) {
+        return value.clone();
+    }
+    let mut value = Polynomial::zero();
+    let mut negative = false;
+    for (column, entry) in matrix[row].iter().enumerate() {
+        if used & (1 << column) != 0 {
+            continue;
+        }
+        if !entry.is_zero() {
+            let term = entry.multiply(&minor(matrix, row + 1, used | (1 << column), memo));
+            value = if negative { value.subtract(&term) } else { value.add(&term) };
+        }
+        negative = !negative;
+    }
+    memo.insert(used, value.clone());
+    value
+}
+
+/// 转为表达式并化简；同次项中正系数在前，读作 `b^2 - 4*a*c`
+pub(crate) fn finish(mut value: Polynomial) -> Result<Expression, ComputeError> {
+    value.terms.sort_by_key(|term| (std::cmp::Reverse(term.degree()), term.coefficient.is_negative()));
+    Simplifier::new().simplify(&value.to_expression())
+}
+
+#[cfg(test)]
+#[path = "resultant_tests.rs"]
+mod resultant_tests;
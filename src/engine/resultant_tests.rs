@@ -0,0 +1,67 @@
+//! # 结式与判别式测试
+//!
+//! 测试符号系数的结式、判别式的符号约定与非多项式输入的错误。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::resultant::{resultant, discriminant};
+    use crate::engine::simplify::Simplifier;
+    use crate::core::Expression;
+    use crate::test_utils::parse;
+
+    fn simplified(input: &str) -> Expression {
+        Simplifier::new().simplify(&parse(input)).unwrap()
+    }
+
+    #[test]
+    fn test_discriminant() {
+        assert_eq!(discriminant(&parse("a*x^2 + b*x + c"), "x").unwrap(), simplified("b^2 - 4*a*c"));
+        assert_eq!(discriminant(&parse("x^3 + p*x + q"), "x").unwrap(), simplified("-4*p^3 - 27*q^2"));
+        assert_eq!(discriminant(&parse("x^2 - 2*x + 1"), "x").unwrap(), parse("0"));
+        assert_eq!(discriminant(&parse("2*x^2 - 3*x - 5"), "x").unwrap(), parse("49"));
+        assert_eq!(discriminant(&parse("3*x + 1"), "x").unwrap(), parse("1"));
+        assert!(discriminant(&parse("y^2"), "x").is_err());
+    }
+
+    #[test]
+    fn test_general_cubic_discriminant() {
+        assert_eq!(
+            discriminant(&parse("a*x^3 + b*x^2 + c*x + d"), "x").unwrap(),
+            simplified("18*a*b*c*d + b^2*c^2 - 4*a*c^3 - 27*a^2*d^2 - 4*b^3*d")
+        );
+        // 有重根时为 0，三个不同实根时为正，一实两复时为负
+        assert_eq!(discriminant(&parse("(x - 1)^2*(x + 2)"), "x").unwrap(), parse("0"));
+        assert_eq!(discriminant(&parse("(x - 1)*(x - 2)*(x - 3)"), "x").unwrap(), parse("4"));
+        assert_eq!(discriminant(&parse("x^3 + x"), "x").unwrap(), simplified("-4"));
+    }
+
+    #[test]
+    fn test_resultant() {
+        // 有公共根 x = 1
+        assert_eq!(resultant(&parse("x^2 - 1"), &parse("x^2 - 3*x + 2"), "x").unwrap(), parse("0"));
+        assert_eq!(resultant(&parse("x^2 + 1"), &parse("x - 2"), "x").unwrap(), parse("5"));
+        assert_eq!(resultant(&parse("x - a"), &parse("x - b"), "x").unwrap(), simplified("a - b"));
+        // 消去 x：x + y = 1 与 x - y = 3 给出 y 满足的方程
+        assert_eq!(resultant(&parse("x + y - 1"), &parse("x - y - 3"), "x").unwrap(), simplified("-2*y - 2"));
+        assert_eq!(resultant(&parse("3"), &parse("x^2 + 1"), "x").unwrap(), parse("9"));
+        assert!(resultant(&parse("sin(x)"), &parse("x"), "x").is_err());
+    }
+
+    #[test]
+    fn test_resultant_vanishes_iff_common_root() {
+        // 公共根可以是无理数或复数，结式都为 0
+        for (p, q) in [("x^2 - 2", "x^3 - 2*x"), ("x^2 + 1", "x^3 + x + 2*x^2 + 2"), ("(x - 3)^2", "x^2 - 9")] {
+            assert_eq!(resultant(&parse(p), &parse(q), "x").unwrap(), parse("0"), "{} 与 {}", p, q);
+        }
+        // 没有公共根时结式非零，包括根仅差一个符号或只有复根的情形
+        for (p, q, value) in [("x^2 - 2", "x^2 - 3", "1"), ("x - 1", "x + 1", "2"), ("x^2 + 1", "x^2 + 4", "9")] {
+            assert_eq!(resultant(&parse(p), &parse(q), "x").unwrap(), parse(value), "{} 与 {}", p, q);
+        }
+    }
+
+    #[test]
+    fn test_function_form() {
+        assert_eq!(simplified("discriminant(a*x^2 + b*x + c, x)"), simplified("b^2 - 4*a*c"));
+        assert_eq!(simplified("resultant(x^2 + 1, x - 2, x)"), parse("5"));
+    }
+}
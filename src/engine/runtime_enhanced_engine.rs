@@ -3,17 +3,18 @@
 //! 集成运行时增强功能的计算引擎，支持变量管理和复杂度控制
 
 use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
+use std::sync::{Arc, RwLock};
 use crate::core::{Expression, Number, MathConstant};
 use super::{ComputeEngine, ComputeError, EnhancedComputeEngine};
-use super::runtime_enhancement::{RuntimeEnhancer, RuntimeConfig};
+use super::runtime_enhancement::{RuntimeEnhancer, RuntimeConfig, UserFunction};
+use crate::tr;
 
 /// 运行时增强计算引擎
 pub struct RuntimeEnhancedEngine {
     /// 基础增强计算引擎
     base_engine: EnhancedComputeEngine,
     /// 运行时增强器
-    runtime_enhancer: Arc<Mutex<RuntimeEnhancer>>,
+    runtime_enhancer: Arc<RwLock<RuntimeEnhancer>>,
 }
 
 impl RuntimeEnhancedEngine {
@@ -21,7 +22,7 @@ impl RuntimeEnhancedEngine {
     pub fn new() -> Self {
         Self {
             base_engine: EnhancedComputeEngine::new(),
-            runtime_enhancer: Arc::new(Mutex::new(RuntimeEnhancer::new(RuntimeConfig::default()))),
+            runtime_enhancer: Arc::new(RwLock::new(RuntimeEnhancer::new(RuntimeConfig::default()))),
         }
     }
     
@@ -29,19 +30,24 @@ impl RuntimeEnhancedEngine {
     pub fn with_config(config: RuntimeConfig) -> Self {
         Self {
             base_engine: EnhancedComputeEngine::new(),
-            runtime_enhancer: Arc::new(Mutex::new(RuntimeEnhancer::new(config))),
+            runtime_enhancer: Arc::new(RwLock::new(RuntimeEnhancer::new(config))),
         }
     }
     
+    /// 底层的增强计算引擎
+    pub fn base_engine(&self) -> &EnhancedComputeEngine {
+        &self.base_engine
+    }
+    
     /// 设置变量值
     pub fn set_variable(&self, name: String, value: Expression) -> Result<(), ComputeError> {
-        let mut enhancer = self.runtime_enhancer.lock()
-            .map_err(|_| ComputeError::internal("无法获取运行时增强器锁"))?;
+        let mut enhancer = self.runtime_enhancer.write()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
         
         // 检查循环引用
         if enhancer.variable_manager().has_circular_reference(&name) {
             return Err(ComputeError::domain_error(
-                format!("变量 '{}' 存在循环引用", name)
+                tr!("engine.circular_reference", name = name)
             ));
         }
         
@@ -50,24 +56,24 @@ impl RuntimeEnhancedEngine {
     
     /// 获取变量值
     pub fn get_variable(&self, name: &str) -> Result<Option<Expression>, ComputeError> {
-        let enhancer = self.runtime_enhancer.lock()
-            .map_err(|_| ComputeError::internal("无法获取运行时增强器锁"))?;
+        let enhancer = self.runtime_enhancer.read()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
         
         Ok(enhancer.variable_manager().get_variable(name).cloned())
     }
     
     /// 获取所有变量
     pub fn get_all_variables(&self) -> Result<HashMap<String, Expression>, ComputeError> {
-        let enhancer = self.runtime_enhancer.lock()
-            .map_err(|_| ComputeError::internal("无法获取运行时增强器锁"))?;
+        let enhancer = self.runtime_enhancer.read()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
         
         Ok(enhancer.variable_manager().get_all_variables().clone())
     }
     
     /// 清空所有变量
     pub fn clear_variables(&self) -> Result<(), ComputeError> {
-        let mut enhancer = self.runtime_enhancer.lock()
-            .map_err(|_| ComputeError::internal("无法获取运行时增强器锁"))?;
+        let mut enhancer = self.runtime_enhancer.write()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
         
         enhancer.variable_manager_mut().clear();
         Ok(())
@@ -75,16 +81,40 @@ impl RuntimeEnhancedEngine {
     
     /// 删除指定变量
     pub fn remove_variable(&self, name: &str) -> Result<bool, ComputeError> {
-        let mut enhancer = self.runtime_enhancer.lock()
-            .map_err(|_| ComputeError::internal("无法获取运行时增强器锁"))?;
+        let mut enhancer = self.runtime_enhancer.write()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
         
         Ok(enhancer.variable_manager_mut().remove_variable(name))
     }
     
+    /// 注册用户函数
+    pub fn define_function(&self, name: String, parameters: Vec<String>, body: Expression) -> Result<(), ComputeError> {
+        let mut enhancer = self.runtime_enhancer.write()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
+        
+        enhancer.variable_manager_mut().define_function(name, parameters, body)
+    }
+    
+    /// 获取所有用户函数
+    pub fn get_all_functions(&self) -> Result<HashMap<String, UserFunction>, ComputeError> {
+        let enhancer = self.runtime_enhancer.read()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
+        
+        Ok(enhancer.variable_manager().get_all_functions().clone())
+    }
+    
+    /// 删除用户函数
+    pub fn remove_function(&self, name: &str) -> Result<bool, ComputeError> {
+        let mut enhancer = self.runtime_enhancer.write()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
+        
+        Ok(enhancer.variable_manager_mut().remove_function(name))
+    }
+    
     /// 更新运行时配置
     pub fn update_runtime_config(&self, config: RuntimeConfig) -> Result<(), ComputeError> {
-        let mut enhancer = self.runtime_enhancer.lock()
-            .map_err(|_| ComputeError::internal("无法获取运行时增强器锁"))?;
+        let mut enhancer = self.runtime_enhancer.write()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
         
         enhancer.update_config(config);
         Ok(())
@@ -92,27 +122,27 @@ impl RuntimeEnhancedEngine {
     
     /// 获取运行时配置
     pub fn get_runtime_config(&self) -> Result<RuntimeConfig, ComputeError> {
-        let enhancer = self.runtime_enhancer.lock()
-            .map_err(|_| ComputeError::internal("无法获取运行时增强器锁"))?;
+        let enhancer = self.runtime_enhancer.read()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
         
         Ok(enhancer.get_config().clone())
     }
     
     /// 安全计算表达式（带运行时增强）
     pub fn safe_compute(&self, expr: &Expression) -> Result<Expression, ComputeError> {
-        let enhancer = self.runtime_enhancer.lock()
-            .map_err(|_| ComputeError::internal("无法获取运行时增强器锁"))?;
+        let enhancer = self.runtime_enhancer.read()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
         
         enhancer.safe_compute(expr, &self.base_engine)
     }
     
     /// 计算表达式并自动替换变量
     pub fn compute_with_variables(&self, expr: &Expression) -> Result<Expression, ComputeError> {
-        let enhancer = self.runtime_enhancer.lock()
-            .map_err(|_| ComputeError::internal("无法获取运行时增强器锁"))?;
+        let enhancer = self.runtime_enhancer.read()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
         
-        // 替换变量
-        let substituted = enhancer.variable_manager().substitute_variables(expr);
+        // 展开用户函数并替换变量
+        let substituted = enhancer.variable_manager().resolve(expr)?;
         
         // 安全计算
         drop(enhancer); // 释放锁
@@ -121,8 +151,8 @@ impl RuntimeEnhancedEngine {
     
     /// 获取数值变量（用于快速数值计算）
     pub fn get_numeric_variables(&self) -> Result<HashMap<String, Number>, ComputeError> {
-        let enhancer = self.runtime_enhancer.lock()
-            .map_err(|_| ComputeError::internal("无法获取运行时增强器锁"))?;
+        let enhancer = self.runtime_enhancer.read()
+            .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
         
         Ok(enhancer.variable_manager().get_all_numeric_variables().clone())
     }
@@ -138,12 +168,23 @@ impl ComputeEngine for RuntimeEnhancedEngine {
         self.safe_compute(expr)
     }
     
+    fn simplify_traced(&self, expr: &Expression) -> Result<(Expression, Vec<super::TraceEvent>), ComputeError> {
+        // 只替换变量，不经过安全计算的特殊处理，使记录与实际规则一一对应
+        let substituted = {
+            let enhancer = self.runtime_enhancer.read()
+                .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
+            enhancer.variable_manager().resolve(expr)?
+        };
+        
+        self.base_engine.simplify_traced(&substituted)
+    }
+    
     fn evaluate(&self, expr: &Expression, vars: &HashMap<String, Number>) -> Result<Number, ComputeError> {
         // 首先替换内部变量，然后使用传入的变量进行求值
         let substituted = {
-            let enhancer = self.runtime_enhancer.lock()
-                .map_err(|_| ComputeError::internal("无法获取运行时增强器锁"))?;
-            enhancer.variable_manager().substitute_variables(expr)
+            let enhancer = self.runtime_enhancer.read()
+                .map_err(|_| ComputeError::internal(tr!("engine.enhancer_lock")))?;
+            enhancer.variable_manager().resolve(expr)?
         };
         
         self.base_engine.evaluate(&substituted, vars)
@@ -171,6 +212,38 @@ impl ComputeEngine for RuntimeEnhancedEngine {
         self.base_engine.series(&substituted_expr, var, &substituted_point, order)
     }
     
+    fn sum(&self, expr: &Expression, var: &str, lower: &Expression, upper: &Expression) -> Result<super::SeriesSum, ComputeError> {
+        let substituted_expr = self.compute_with_variables(expr)?;
+        let substituted_lower = self.compute_with_variables(lower)?;
+        let substituted_upper = self.compute_with_variables(upper)?;
+        self.base_engine.sum(&substituted_expr, var, &substituted_lower, &substituted_upper)
+    }
+    
+    fn laplace(&self, expr: &Expression, t: &str, s: &str) -> Result<super::LaplaceTransform, ComputeError> {
+        let substituted_expr = self.compute_with_variables(expr)?;
+        self.base_engine.laplace(&substituted_expr, t, s)
+    }
+    
+    fn inverse_laplace(&self, expr: &Expression, s: &str, t: &str) -> Result<Expression, ComputeError> {
+        let substituted_expr = self.compute_with_variables(expr)?;
+        self.base_engine.inverse_laplace(&substituted_expr, s, t)
+    }
+    
+    fn truth_table(&self, expr: &Expression, vars: &[String]) -> Result<super::TruthTable, ComputeError> {
+        let substituted_expr = self.compute_with_variables(expr)?;
+        self.base_engine.truth_table(&substituted_expr, vars)
+    }
+    
+    fn to_dnf(&self, expr: &Expression) -> Result<Expression, ComputeError> {
+        let substituted_expr = self.compute_with_variables(expr)?;
+        self.base_engine.to_dnf(&substituted_expr)
+    }
+    
+    fn to_cnf(&self, expr: &Expression) -> Result<Expression, ComputeError> {
+        let substituted_expr = self.compute_with_variables(expr)?;
+        self.base_engine.to_cnf(&substituted_expr)
+    }
+    
     fn numerical_evaluate(&self, expr: &Expression, vars: &HashMap<String, f64>) -> Result<f64, ComputeError> {
         let substituted = self.compute_with_variables(expr)?;
         self.base_engine.numerical_evaluate(&substituted, vars)
@@ -224,6 +297,20 @@ impl ComputeEngine for RuntimeEnhancedEngine {
         self.base_engine.lcm(&substituted_a, &substituted_b)
     }
     
+    fn gcd_list(&self, values: &[Expression]) -> Result<Expression, ComputeError> {
+        let substituted_values: Result<Vec<_>, _> = values.iter()
+            .map(|v| self.compute_with_variables(v))
+            .collect();
+        self.base_engine.gcd_list(&substituted_values?)
+    }
+    
+    fn lcm_list(&self, values: &[Expression]) -> Result<Expression, ComputeError> {
+        let substituted_values: Result<Vec<_>, _> = values.iter()
+            .map(|v| self.compute_with_variables(v))
+            .collect();
+        self.base_engine.lcm_list(&substituted_values?)
+    }
+    
     fn is_prime(&self, n: &Expression) -> Result<bool, ComputeError> {
         let substituted = self.compute_with_variables(n)?;
         self.base_engine.is_prime(&substituted)
@@ -234,6 +321,10 @@ impl ComputeEngine for RuntimeEnhancedEngine {
         self.base_engine.prime_factors(&substituted)
     }
     
+    fn set_random_seed(&self, seed: u64) {
+        self.base_engine.set_random_seed(seed)
+    }
+    
     fn binomial(&self, n: &Expression, k: &Expression) -> Result<Expression, ComputeError> {
         let substituted_n = self.compute_with_variables(n)?;
         let substituted_k = self.compute_with_variables(k)?;
@@ -272,6 +363,42 @@ impl ComputeEngine for RuntimeEnhancedEngine {
         self.base_engine.solve(&substituted, var)
     }
     
+    fn solve_detailed(&self, equation: &Expression, var: &str) -> Result<Vec<super::Solution>, ComputeError> {
+        let substituted = self.compute_with_variables(equation)?;
+        self.base_engine.solve_detailed(&substituted, var)
+    }
+    
+    fn domain(&self, expr: &Expression, var: &str) -> Result<super::DomainAnalysis, ComputeError> {
+        let substituted = self.compute_with_variables(expr)?;
+        self.base_engine.domain(&substituted, var)
+    }
+    
+    fn range_estimate(&self, expr: &Expression, var: &str, domain: &Expression) -> Result<super::DomainAnalysis, ComputeError> {
+        let substituted = self.compute_with_variables(expr)?;
+        self.base_engine.range_estimate(&substituted, var, domain)
+    }
+    
+    fn critical_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError> {
+        let substituted = self.compute_with_variables(expr)?;
+        self.base_engine.critical_points(&substituted, var)
+    }
+    
+    fn local_extrema(&self, expr: &Expression, var: &str) -> Result<Vec<super::Extremum>, ComputeError> {
+        let substituted = self.compute_with_variables(expr)?;
+        self.base_engine.local_extrema(&substituted, var)
+    }
+    
+    fn inflection_points(&self, expr: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError> {
+        let substituted = self.compute_with_variables(expr)?;
+        self.base_engine.inflection_points(&substituted, var)
+    }
+    
+    fn are_equivalent(&self, lhs: &Expression, rhs: &Expression) -> Result<super::Equivalence, ComputeError> {
+        let substituted_lhs = self.compute_with_variables(lhs)?;
+        let substituted_rhs = self.compute_with_variables(rhs)?;
+        self.base_engine.are_equivalent(&substituted_lhs, &substituted_rhs)
+    }
+    
     fn solve_system(&self, equations: &[Expression], vars: &[String]) -> Result<Vec<HashMap<String, Expression>>, ComputeError> {
         let substituted_equations: Result<Vec<_>, _> = equations.iter()
             .map(|eq| self.compute_with_variables(eq))
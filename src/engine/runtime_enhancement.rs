@@ -8,6 +8,7 @@ use crate::core::{Expression, Number, BinaryOperator, UnaryOperator};
 use super::{ComputeError, ComputeEngine};
 use num_bigint::BigInt;
 use num_traits::Signed;
+use crate::tr;
 
 /// 运行时增强配置
 #[derive(Debug, Clone)]
@@ -85,10 +86,12 @@ impl ComplexityAnalyzer {
                 let operand_complexity = self.calculate_complexity(operand);
                 match op {
                     UnaryOperator::Factorial => operand_complexity * 20 + 50,
-                    UnaryOperator::Exp => operand_complexity * 10 + 20,
                     _ => operand_complexity + 2
                 }
             }
+            Expression::Function { name, args } if name == "exp" && args.len() == 1 => {
+                self.calculate_complexity(&args[0]) * 10 + 20
+            }
             Expression::Function { args, .. } => {
                 args.iter().map(|arg| self.calculate_complexity(arg)).sum::<usize>() + 10
             }
@@ -182,6 +185,31 @@ impl ComplexityAnalyzer {
     }
 }
 
+/// 用户函数展开的最大嵌套层数，超过时视为递归定义
+const MAX_CALL_DEPTH: usize = 64;
+
+/// 用户注册的符号函数 `f(x, y) = 函数体`
+#[derive(Debug, Clone, PartialEq)]
+pub struct UserFunction {
+    /// 形参
+    pub parameters: Vec<String>,
+    /// 函数体
+    pub body: Expression,
+}
+
+impl UserFunction {
+    /// 把实参同时代入函数体中的形参，实参个数须与形参一致
+    pub fn apply(&self, name: &str, args: &[Expression]) -> Result<Expression, ComputeError> {
+        if args.len() != self.parameters.len() {
+            return Err(ComputeError::domain_error(tr!(
+                "runtime.argument_count", name = name, expected = self.parameters.len(), actual = args.len()
+            )));
+        }
+        let bindings: HashMap<String, Expression> = self.parameters.iter().cloned().zip(args.iter().cloned()).collect();
+        Ok(self.body.substitute(&bindings))
+    }
+}
+
 /// 变量管理器
 #[derive(Debug, Clone)]
 pub struct VariableManager {
@@ -189,6 +217,8 @@ pub struct VariableManager {
     variables: HashMap<String, Expression>,
     /// 存储数值变量（用于快速数值计算）
     numeric_variables: HashMap<String, Number>,
+    /// 用户注册的函数
+    functions: HashMap<String, UserFunction>,
 }
 
 impl VariableManager {
@@ -197,6 +227,7 @@ impl VariableManager {
         Self {
             variables: HashMap::new(),
             numeric_variables: HashMap::new(),
+            functions: HashMap::new(),
         }
     }
     
@@ -205,7 +236,7 @@ impl VariableManager {
         // 验证变量名
         if !self.is_valid_variable_name(&name) {
             return Err(ComputeError::domain_error(
-                format!("无效的变量名: {}", name)
+                tr!("runtime.invalid_variable", name = name)
             ));
         }
         
@@ -254,63 +285,82 @@ impl VariableManager {
         removed_expr || removed_num
     }
     
-    /// 替换表达式中的变量
-    pub fn substitute_variables(&self, expr: &Expression) -> Expression {
-        match expr {
-            Expression::Variable(name) => {
-                if let Some(value) = self.get_variable(name) {
-                    // 递归替换，防止变量引用链
-                    self.substitute_variables(value)
-                } else {
-                    expr.clone()
-                }
-            }
-            Expression::BinaryOp { op, left, right } => {
-                Expression::BinaryOp {
-                    op: op.clone(),
-                    left: Box::new(self.substitute_variables(left)),
-                    right: Box::new(self.substitute_variables(right)),
-                }
-            }
-            Expression::UnaryOp { op, operand } => {
-                Expression::UnaryOp {
-                    op: op.clone(),
-                    operand: Box::new(self.substitute_variables(operand)),
-                }
-            }
+    /// 注册用户函数，同名函数被覆盖
+    pub fn define_function(&mut self, name: String, parameters: Vec<String>, body: Expression) -> Result<(), ComputeError> {
+        if let Some(invalid) = std::iter::once(&name).chain(&parameters).find(|name| !self.is_valid_variable_name(name)) {
+            return Err(ComputeError::domain_error(tr!("runtime.invalid_function", name = invalid)));
+        }
+        if let Some(duplicate) = parameters.iter().enumerate().find(|(i, p)| parameters[..*i].contains(p)) {
+            return Err(ComputeError::domain_error(tr!("runtime.duplicate_parameter", name = name, parameter = duplicate.1)));
+        }
+        self.functions.insert(name, UserFunction { parameters, body });
+        Ok(())
+    }
+    
+    /// 获取用户函数
+    pub fn get_function(&self, name: &str) -> Option<&UserFunction> {
+        self.functions.get(name)
+    }
+    
+    /// 获取所有用户函数
+    pub fn get_all_functions(&self) -> &HashMap<String, UserFunction> {
+        &self.functions
+    }
+    
+    /// 删除用户函数
+    pub fn remove_function(&mut self, name: &str) -> bool {
+        self.functions.remove(name).is_some()
+    }
+    
+    /// 把表达式中对用户函数的调用展开为代入实参后的函数体，函数体中的调用也一并展开；未注册的函数保留原样
+    pub fn expand_functions(&self, expr: &Expression) -> Result<Expression, ComputeError> {
+        if self.functions.is_empty() {
+            return Ok(expr.clone());
+        }
+        self.expand_calls(expr, 0)
+    }
+    
+    fn expand_calls(&self, expr: &Expression, depth: usize) -> Result<Expression, ComputeError> {
+        if depth > MAX_CALL_DEPTH {
+            return Err(ComputeError::domain_error(tr!("runtime.call_depth", limit = MAX_CALL_DEPTH)));
+        }
+        let expand = |expr: &Expression| self.expand_calls(expr, depth);
+        let expand_all = |elements: &[Expression]| elements.iter().map(expand).collect::<Result<Vec<_>, _>>();
+        Ok(match expr {
             Expression::Function { name, args } => {
-                Expression::Function {
-                    name: name.clone(),
-                    args: args.iter().map(|arg| self.substitute_variables(arg)).collect(),
-                }
-            }
-            Expression::Matrix(rows) => {
-                Expression::Matrix(
-                    rows.iter()
-                        .map(|row| row.iter().map(|elem| self.substitute_variables(elem)).collect())
-                        .collect()
-                )
-            }
-            Expression::Vector(elements) => {
-                Expression::Vector(
-                    elements.iter().map(|elem| self.substitute_variables(elem)).collect()
-                )
-            }
-            Expression::Set(elements) => {
-                Expression::Set(
-                    elements.iter().map(|elem| self.substitute_variables(elem)).collect()
-                )
-            }
-            Expression::Interval { start, end, start_inclusive, end_inclusive } => {
-                Expression::Interval {
-                    start: Box::new(self.substitute_variables(start)),
-                    end: Box::new(self.substitute_variables(end)),
-                    start_inclusive: *start_inclusive,
-                    end_inclusive: *end_inclusive,
+                let args = expand_all(args)?;
+                match self.get_function(name) {
+                    Some(function) => self.expand_calls(&function.apply(name, &args)?, depth + 1)?,
+                    None => Expression::function(name.clone(), args),
                 }
             }
+            Expression::BinaryOp { op, left, right } => Expression::binary_op(op.clone(), expand(left)?, expand(right)?),
+            Expression::UnaryOp { op, operand } => Expression::unary_op(op.clone(), expand(operand)?),
+            Expression::Matrix(rows) => Expression::Matrix(rows.iter().map(|row| expand_all(row)).collect::<Result<_, _>>()?),
+            Expression::Vector(elements) => Expression::Vector(expand_all(elements)?),
+            Expression::Set(elements) => Expression::Set(expand_all(elements)?),
+            Expression::Interval { start, end, start_inclusive, end_inclusive } => Expression::Interval {
+                start: Box::new(expand(start)?),
+                end: Box::new(expand(end)?),
+                start_inclusive: *start_inclusive,
+                end_inclusive: *end_inclusive,
+            },
             _ => expr.clone(),
-        }
+        })
+    }
+    
+    /// 展开用户函数并替换变量，计算前对输入做的全部代换
+    pub fn resolve(&self, expr: &Expression) -> Result<Expression, ComputeError> {
+        Ok(self.substitute_variables(&self.expand_functions(expr)?))
+    }
+    
+    /// 替换表达式中的变量
+    pub fn substitute_variables(&self, expr: &Expression) -> Expression {
+        // 变量的值可能引用其他变量，先递归代换值；sum 等函数的约束变量由 Expression::substitute 保护
+        let values: HashMap<String, Expression> = expr.get_variables().into_iter()
+            .filter_map(|name| self.get_variable(&name).map(|value| (name, self.substitute_variables(value))))
+            .collect();
+        expr.substitute(&values)
     }
     
     /// 检查变量名是否有效
@@ -419,8 +469,8 @@ impl RuntimeEnhancer {
     
     /// 安全计算表达式
     pub fn safe_compute<E: ComputeEngine>(&self, expr: &Expression, engine: &E) -> Result<Expression, ComputeError> {
-        // 1. 替换变量
-        let substituted = self.variable_manager.substitute_variables(expr);
+        // 1. 展开用户函数并替换变量
+        let substituted = self.variable_manager.resolve(expr)?;
         
         // 2. 检查复杂度
         if self.complexity_analyzer.is_too_complex(&substituted) {
@@ -2,17 +2,86 @@
 //!
 //! 实现代数表达式的简化规则和算法。
 
-use crate::core::{Expression, Number, BinaryOperator, UnaryOperator, MathConstant};
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator, MathConstant, INDEX_FUNCTION, signatures};
+use crate::core::indeterminate::{self, IndeterminateSemantics, Magnitude};
+use crate::core::memory;
+use crate::core::CowExpression;
 use crate::engine::error::ComputeError;
+use crate::engine::assumptions::Assumptions;
+use crate::engine::summation::SummationEngine;
+use crate::engine::calculus::CalculusEngine;
+use crate::engine::special_functions;
+use crate::engine::logic;
+use crate::engine::matrix_rules;
+use crate::engine::{conic, distributions, lists, modular, orthogonal, resultant, tensors, vector_calculus};
+use crate::engine::trace::{Tracer, TraceEvent, rule_suffix};
+use std::borrow::Cow;
 use std::collections::HashMap;
+use std::time::{Duration, Instant};
 use num_bigint::BigInt;
 use num_rational::BigRational;
 use num_traits::ToPrimitive;
+use crate::tr;
+
+/// 化简规则集开关，关闭的规则集不参与化简；常量折叠与交换律排序始终进行
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SimplifyOptions {
+    /// 恒等元与零元：`x + 0 = x`、`x * 1 = x`、`x * 0 = 0`、`x^1 = x`、`x / x = 1`
+    pub identities: bool,
+    /// 同类项：`x + x = 2x`、`2x + 3x = 5x`、`x - x = 0`
+    pub like_terms: bool,
+    /// 幂律：`x * x = x^2`、`x^a * x^b = x^(a+b)`、`(x^a)^b = x^(ab)`、小整数次幂的二项式展开
+    pub powers: bool,
+    /// 三角恒等式：`sin(x)^2 + cos(x)^2 = 1`
+    pub trigonometric: bool,
+    /// 对数与指数互逆：`ln(exp(x)) = x`、`exp(ln(x)) = x`
+    pub logarithmic: bool,
+    /// 按组合数学习惯取 `0^0 = 1`；默认关闭，`0^0` 与其他未定式一样为 `undefined`
+    pub zero_power_zero_is_one: bool,
+    /// 非零数除以零得到带符号的无穷，如 `1/0 = ∞`；默认关闭，除以零报错
+    pub division_by_zero_is_infinite: bool,
+}
+
+impl SimplifyOptions {
+    /// 全部规则集（默认）
+    pub fn all() -> Self {
+        Self { identities: true, like_terms: true, powers: true, trigonometric: true, logarithmic: true, zero_power_zero_is_one: false, division_by_zero_is_infinite: false }
+    }
+    
+    /// 只做代数化简，不用三角与对数恒等式
+    pub fn algebraic() -> Self {
+        Self { trigonometric: false, logarithmic: false, ..Self::all() }
+    }
+    
+    /// 只去掉恒等运算，保留表达式的写法
+    pub fn minimal() -> Self {
+        Self { identities: true, ..Self::none() }
+    }
+    
+    /// 关闭全部规则集，只折叠常量
+    pub fn none() -> Self {
+        Self { identities: false, like_terms: false, powers: false, trigonometric: false, logarithmic: false, zero_power_zero_is_one: false, division_by_zero_is_infinite: false }
+    }
+}
+
+impl Default for SimplifyOptions {
+    fn default() -> Self {
+        Self::all()
+    }
+}
 
 /// 表达式简化器
 pub struct Simplifier {
     /// 简化规则缓存
     cache: HashMap<Expression, Expression>,
+    /// 启用的规则集
+    options: SimplifyOptions,
+    /// 负数的奇次根是否取实数负根（否则保持符号形式）
+    real_odd_roots: bool,
+    /// 变量假设，用于只在一定条件下成立的变换
+    assumptions: Assumptions,
+    /// 规则追踪，仅在 `simplify_traced` 期间存在
+    tracer: Option<Tracer>,
 }
 
 impl Simplifier {
@@ -20,41 +89,304 @@ impl Simplifier {
     pub fn new() -> Self {
         Self {
             cache: HashMap::new(),
+            options: SimplifyOptions::default(),
+            real_odd_roots: true,
+            assumptions: Assumptions::new(),
+            tracer: None,
+        }
+    }
+    
+    /// 创建只启用 `options` 中规则集的简化器
+    pub fn with_options(options: SimplifyOptions) -> Self {
+        Self { options, ..Self::new() }
+    }
+    
+    /// 设置启用的规则集
+    pub fn set_options(&mut self, options: SimplifyOptions) {
+        if self.options != options {
+            self.options = options;
+            self.cache.clear();
+        }
+    }
+    
+    /// 启用的规则集
+    pub fn options(&self) -> SimplifyOptions {
+        self.options
+    }
+    
+    /// 设置负数的奇次根是否取实数负根，如 cbrt(-8) = -2
+    pub fn set_real_odd_roots(&mut self, enabled: bool) {
+        if self.real_odd_roots != enabled {
+            self.real_odd_roots = enabled;
+            self.cache.clear();
         }
     }
     
+    /// 设置变量假设，如假设 x > 0 后 sqrt(x^2) = x
+    pub fn set_assumptions(&mut self, assumptions: Assumptions) {
+        if self.assumptions != assumptions {
+            self.assumptions = assumptions;
+            self.cache.clear();
+        }
+    }
+    
+    /// 当前的变量假设
+    pub fn assumptions(&self) -> &Assumptions {
+        &self.assumptions
+    }
+    
+    /// 未定式与除零的取值约定，由 `SimplifyOptions` 中的对应开关决定
+    pub fn indeterminate_semantics(&self) -> IndeterminateSemantics {
+        IndeterminateSemantics::new(self.options.zero_power_zero_is_one)
+            .with_division_by_zero_infinite(self.options.division_by_zero_is_infinite)
+    }
+    
     /// 简化表达式
     pub fn simplify(&mut self, expr: &Expression) -> Result<Expression, ComputeError> {
-        // 检查缓存
-        if let Some(cached) = self.cache.get(expr) {
-            return Ok(cached.clone());
+        // 检查缓存（追踪时跳过，否则命中缓存的规则不会出现在记录中）
+        if self.tracer.is_none() {
+            if let Some(cached) = self.cache.get(expr) {
+                return Ok(cached.clone());
+            }
+        }
+        
+        let restored = self.simplify_borrowed(expr)?.into_owned();
+        
+        // 缓存结果
+        self.cache.insert(expr.clone(), restored.clone());
+        
+        Ok(restored)
+    }
+    
+    /// 就地化简，返回是否有改变。子表达式自底向上就地改写，只替换规则实际改写的节点，
+    /// 未改变的子树保留原有节点
+    pub fn simplify_in_place(&mut self, expr: &mut Expression) -> Result<bool, ComputeError> {
+        let mut changed = self.simplify_in_place_recursive(expr)?;
+        
+        // 应用常量折叠
+        let folded = self.apply_rule(|| "final.constant_folding".to_string(), || expr.clone(), |s| s.constant_folding(expr))?;
+        if let Some(folded) = folded {
+            memory::recycle(std::mem::replace(expr, folded));
+            changed = true;
         }
         
+        // 将内部的 x^(1/2) 还原为 sqrt(x)
+        Ok(self.restore_square_roots(expr) || changed)
+    }
+    
+    /// 化简写时复制表达式，返回是否有改变。独占时就地化简；与其他持有者共享时先只读化简，
+    /// 结果不变则不触发复制，继续共享同一份表达式
+    pub fn simplify_cow(&mut self, expr: &mut CowExpression) -> Result<bool, ComputeError> {
+        if let Some(changed) = expr.modify_unique(|unique| self.simplify_in_place(unique)) {
+            return changed;
+        }
+        match self.simplify_borrowed(expr.as_ref())? {
+            Cow::Borrowed(_) => Ok(false),
+            Cow::Owned(simplified) => {
+                expr.replace(simplified);
+                Ok(true)
+            }
+        }
+    }
+    
+    /// 化简的主流程，不查缓存；结果与原式相同时借用原式，不做任何复制
+    fn simplify_borrowed<'a>(&mut self, expr: &'a Expression) -> Result<Cow<'a, Expression>, ComputeError> {
         let simplified = self.simplify_recursive(expr)?;
         
         // 应用常量折叠
-        let folded = self.constant_folding(&simplified)?;
+        let folded = match self.apply_rule(|| "final.constant_folding".to_string(), || simplified.clone().into_owned(), |s| s.constant_folding(&simplified))? {
+            Some(folded) => {
+                recycle_owned(simplified);
+                Cow::Owned(folded)
+            }
+            None => simplified,
+        };
         
-        // 缓存结果
-        self.cache.insert(expr.clone(), folded.clone());
+        // 将内部的 x^(1/2) 还原为 sqrt(x)，没有可还原的幂时不复制
+        let mut restored = folded;
+        if restored.find(|e| self.is_square_root_power(e)).is_empty() {
+            return Ok(restored);
+        }
+        self.restore_square_roots(restored.to_mut());
+        Ok(restored)
+    }
+    
+    /// 简化表达式并记录每条生效的规则
+    pub fn simplify_traced(&mut self, expr: &Expression) -> Result<(Expression, Vec<TraceEvent>), ComputeError> {
+        self.begin_trace();
+        let result = self.simplify(expr);
+        let events = self.end_trace();
+        Ok((result?, events))
+    }
+    
+    /// 轻量化简：只去掉 `*1`、`+0`、`^1` 这类恒等运算、折叠数值并把数值系数提到乘积前面，
+    /// 不展开、不合并同类项，代价与表达式大小成正比
+    ///
+    /// 每访问一个节点调用一次 `interrupted`，返回 true 时以 `ComputeError::Timeout` 中止。
+    pub fn simplify_light(expr: &Expression, interrupted: &dyn Fn() -> bool) -> Result<Expression, ComputeError> {
+        if interrupted() {
+            return Err(ComputeError::Timeout);
+        }
+        let light = |e: &Expression| Self::simplify_light(e, interrupted);
+        Ok(match expr {
+            Expression::BinaryOp { op, left, right } => Self::light_binary_op(op, light(left)?, light(right)?),
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => match light(operand)? {
+                Expression::Number(n) => Expression::Number(-n),
+                Expression::UnaryOp { op: UnaryOperator::Negate, operand } => *operand,
+                operand => Expression::negate(operand),
+            },
+            Expression::UnaryOp { op, operand } => Expression::unary_op(op.clone(), light(operand)?),
+            Expression::Function { name, args } => {
+                Expression::function(name.clone(), args.iter().map(light).collect::<Result<Vec<_>, _>>()?)
+            }
+            Expression::Matrix(rows) => Expression::Matrix(rows.iter()
+                .map(|row| row.iter().map(light).collect::<Result<Vec<_>, _>>())
+                .collect::<Result<Vec<_>, _>>()?),
+            Expression::Vector(elements) => Expression::Vector(elements.iter().map(light).collect::<Result<Vec<_>, _>>()?),
+            Expression::Set(elements) => Expression::Set(elements.iter().map(light).collect::<Result<Vec<_>, _>>()?),
+            Expression::Interval { start, end, start_inclusive, end_inclusive } => Expression::Interval {
+                start: Box::new(light(start)?),
+                end: Box::new(light(end)?),
+                start_inclusive: *start_inclusive,
+                end_inclusive: *end_inclusive,
+            },
+            Expression::Number(_) | Expression::Variable(_) | Expression::Constant(_) => expr.clone(),
+        })
+    }
+    
+    /// 轻量化简的二元运算规则，两侧已经化简
+    fn light_binary_op(op: &BinaryOperator, left: Expression, right: Expression) -> Expression {
+        let number = |e: &Expression| match e {
+            Expression::Number(n) if !matches!(n, Number::Symbolic(_)) => Some(n.clone()),
+            _ => None,
+        };
+        let is = |e: &Expression, value: i64| number(e).is_some_and(|n| n == Number::integer(value));
+        // 矩阵与向量乘零仍是同形的零，留给完整化简
+        let is_scalar = |e: &Expression| !matches!(e, Expression::Matrix(_) | Expression::Vector(_));
+        let folded = |n: Number| (!matches!(n, Number::Symbolic(_))).then_some(Expression::Number(n));
         
-        Ok(folded)
+        let simplified = match (op, number(&left), number(&right)) {
+            (BinaryOperator::Add, Some(a), Some(b)) => folded(a + b),
+            (BinaryOperator::Add, _, _) if is(&left, 0) => Some(right.clone()),
+            (BinaryOperator::Add | BinaryOperator::Subtract, _, _) if is(&right, 0) => Some(left.clone()),
+            (BinaryOperator::Subtract, Some(a), Some(b)) => folded(a - b),
+            (BinaryOperator::Subtract, _, _) if is(&left, 0) => Some(Expression::negate(right.clone())),
+            (BinaryOperator::Multiply, Some(a), Some(b)) => folded(a * b),
+            (BinaryOperator::Multiply, _, _) if (is(&left, 0) && is_scalar(&right)) || (is(&right, 0) && is_scalar(&left)) => {
+                Some(Expression::Number(Number::zero()))
+            }
+            (BinaryOperator::Multiply, _, _) if is(&left, 1) => Some(right.clone()),
+            (BinaryOperator::Multiply, _, _) if is(&right, 1) => Some(left.clone()),
+            (BinaryOperator::Multiply, _, _) if is(&left, -1) => Some(Expression::negate(right.clone())),
+            (BinaryOperator::Multiply, _, _) if is(&right, -1) => Some(Expression::negate(left.clone())),
+            // 数值系数提到前面并与内层系数合并：x * 2 = 2 * x，2 * (2 * x) = 4 * x
+            (BinaryOperator::Multiply, None, Some(_)) if is_scalar(&left) => {
+                Some(Self::light_binary_op(op, right.clone(), left.clone()))
+            }
+            (BinaryOperator::Multiply, Some(a), None) => match &right {
+                Expression::BinaryOp { op: BinaryOperator::Multiply, left: inner, right: rest } => number(inner)
+                    .and_then(|b| folded(a * b))
+                    .map(|c| Self::light_binary_op(op, c, rest.as_ref().clone())),
+                _ => None,
+            },
+            (BinaryOperator::Divide, Some(a), Some(b)) if !b.is_zero() => folded(a / b),
+            (BinaryOperator::Divide, _, _) if is(&right, 1) => Some(left.clone()),
+            (BinaryOperator::Power, _, _) if is(&right, 1) => Some(left.clone()),
+            (BinaryOperator::Power, _, _) if is(&right, 0) && !is(&left, 0) => Some(Expression::Number(Number::one())),
+            (BinaryOperator::Power, _, _) if is(&left, 1) => Some(Expression::Number(Number::one())),
+            (BinaryOperator::Power, Some(a), Some(Number::Integer(b))) if !a.is_zero() && b.magnitude() <= &64u32.into() => {
+                a.power(&Number::Integer(b)).ok().and_then(folded)
+            }
+            _ => None,
+        };
+        simplified.unwrap_or_else(|| Expression::binary_op(op.clone(), left, right))
+    }
+    
+    /// 开始记录规则，供组合了本化简器的其他化简器使用
+    pub(crate) fn begin_trace(&mut self) {
+        self.tracer = Some(Tracer::new());
+    }
+    
+    /// 结束记录并取出记录
+    pub(crate) fn end_trace(&mut self) -> Vec<TraceEvent> {
+        self.tracer.take().map(Tracer::into_events).unwrap_or_default()
     }
     
-    /// 常量折叠优化
-    fn constant_folding(&self, expr: &Expression) -> Result<Expression, ComputeError> {
+    /// 是否正在记录规则
+    pub(crate) fn is_tracing(&self) -> bool {
+        self.tracer.is_some()
+    }
+    
+    /// 在当前位置记录一条规则，未在追踪时忽略
+    pub(crate) fn record_rule(&mut self, rule: &str, before: &Expression, after: &Expression, elapsed: Duration) {
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.record(rule.to_string(), before.clone(), after, elapsed);
+        }
+    }
+    
+    /// 应用一条规则；追踪时记录规则名、应用前后的片段与耗时，否则直接应用。
+    /// 规则返回 `None` 表示没有改写
+    fn apply_rule<R, B, F>(&mut self, rule: R, before: B, apply: F) -> Result<Option<Expression>, ComputeError>
+    where
+        R: FnOnce() -> String,
+        B: FnOnce() -> Expression,
+        F: FnOnce(&mut Self) -> Result<Option<Expression>, ComputeError>,
+    {
+        if self.tracer.is_none() {
+            return apply(self);
+        }
+        let start = Instant::now();
+        let after = apply(self)?;
+        let elapsed = start.elapsed();
+        if let (Some(tracer), Some(after)) = (self.tracer.as_mut(), &after) {
+            tracer.record(rule(), before(), after, elapsed);
+        }
+        Ok(after)
+    }
+    
+    /// 化简第 `index` 个子表达式，追踪时维护当前位置
+    fn simplify_child<'a>(&mut self, index: usize, expr: &'a Expression) -> Result<Cow<'a, Expression>, ComputeError> {
+        self.enter(index);
+        let result = self.simplify_recursive(expr);
+        self.leave();
+        result
+    }
+    
+    /// 就地化简第 `index` 个子表达式，追踪时维护当前位置
+    fn simplify_child_in_place(&mut self, index: usize, expr: &mut Expression) -> Result<bool, ComputeError> {
+        self.enter(index);
+        let result = self.simplify_in_place_recursive(expr);
+        self.leave();
+        result
+    }
+    
+    fn enter(&mut self, index: usize) {
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.enter(index);
+        }
+    }
+    
+    fn leave(&mut self) {
+        if let Some(tracer) = self.tracer.as_mut() {
+            tracer.leave();
+        }
+    }
+    
+    /// 常量折叠优化，无法折叠时返回 `None`
+    fn constant_folding(&self, expr: &Expression) -> Result<Option<Expression>, ComputeError> {
         match expr {
-            // 对于单个常量，不进行折叠，保持原形式
-            Expression::Constant(_) => Ok(expr.clone()),
+            // 对于单个数值或常量，不进行折叠，保持原形式
+            Expression::Number(_) | Expression::Constant(_) => Ok(None),
             
-            // 如果表达式是常量，尝试计算其值
+            // 如果表达式是常量，尝试计算其值；计算失败时保持原表达式
             _ if expr.is_constant() => {
                 match self.evaluate_constant_expression(expr) {
-                    Ok(value) => Ok(Expression::Number(value)),
-                    Err(_) => Ok(expr.clone()), // 如果计算失败，保持原表达式
+                    Ok(Number::Float(f)) if f.is_nan() => Ok(Some(Expression::Constant(MathConstant::Undefined))),
+                    Ok(value) => Ok(Some(Expression::Number(value))),
+                    Err(_) => Ok(None),
                 }
             }
-            _ => Ok(expr.clone()),
+            _ => Ok(None),
         }
     }
     
@@ -95,12 +427,12 @@ impl Simplifier {
                             Ok(result)
                         } else {
                             Err(ComputeError::UnsupportedOperation { 
-                                operation: "复杂幂运算".to_string() 
+                                operation: tr!("simplify.complex_power").to_string() 
                             })
                         }
                     }
                     _ => Err(ComputeError::UnsupportedOperation { 
-                        operation: format!("常量折叠中的 {:?} 运算", op) 
+                        operation: tr!("simplify.constant_folding", operation = format!("{:?}", op)) 
                     }),
                 }
             }
@@ -113,297 +445,533 @@ impl Simplifier {
                     UnaryOperator::Plus => Ok(operand_val),
                     UnaryOperator::Abs => Ok(operand_val.abs()?),
                     _ => Err(ComputeError::UnsupportedOperation { 
-                        operation: format!("常量折叠中的 {:?} 运算", op) 
+                        operation: tr!("simplify.constant_folding", operation = format!("{:?}", op)) 
                     }),
                 }
             }
             
             _ => Err(ComputeError::UnsupportedOperation { 
-                operation: "非常量表达式".to_string() 
+                operation: tr!("simplify.non_constant").to_string() 
             }),
         }
     }
     
-    /// 递归简化表达式
-    fn simplify_recursive(&mut self, expr: &Expression) -> Result<Expression, ComputeError> {
+    /// 递归简化表达式：先化简子表达式，只有子表达式改变时才重建本节点，再对本节点应用规则。
+    /// 整棵子树都没有改变时借用原式
+    fn simplify_recursive<'a>(&mut self, expr: &'a Expression) -> Result<Cow<'a, Expression>, ComputeError> {
+        self.check_memory()?;
+        // 直接构造的函数型一元运算按同名函数化简
+        if let Some(call) = expr.to_function_call() {
+            return Ok(Cow::Owned(self.simplify_recursive(&call)?.into_owned()));
+        }
+        let node = match self.simplify_children(expr)? {
+            Some(rebuilt) => Cow::Owned(rebuilt),
+            None => Cow::Borrowed(expr),
+        };
+        match self.rewrite_node(&node)? {
+            Some(rewritten) => {
+                // 规则只借用重建的节点，用完交还节点池
+                recycle_owned(node);
+                Ok(Cow::Owned(rewritten))
+            }
+            None => Ok(node),
+        }
+    }
+    
+    /// 就地递归简化：子表达式先就地化简，再对本节点应用规则，只有规则改写时才替换本节点
+    fn simplify_in_place_recursive(&mut self, expr: &mut Expression) -> Result<bool, ComputeError> {
+        self.check_memory()?;
+        let mut changed = false;
+        if let Some(call) = expr.to_function_call() {
+            memory::recycle(std::mem::replace(expr, call));
+            changed = true;
+        }
         match expr {
-            // 基本表达式不需要简化
-            Expression::Number(_) | Expression::Variable(_) | Expression::Constant(_) => {
-                Ok(expr.clone())
+            Expression::Number(_) | Expression::Variable(_) | Expression::Constant(_) => {}
+            Expression::BinaryOp { left, right, .. } | Expression::Interval { start: left, end: right, .. } => {
+                changed |= self.simplify_child_in_place(0, left)?;
+                changed |= self.simplify_child_in_place(1, right)?;
+            }
+            Expression::UnaryOp { operand, .. } => changed |= self.simplify_child_in_place(0, operand)?,
+            Expression::Function { args: elements, .. } | Expression::Vector(elements) | Expression::Set(elements) => {
+                for (i, elem) in elements.iter_mut().enumerate() {
+                    changed |= self.simplify_child_in_place(i, elem)?;
+                }
             }
-            
-            // 简化二元运算
+            Expression::Matrix(rows) => {
+                for (i, row) in rows.iter_mut().enumerate() {
+                    self.enter(i);
+                    let row_changed = row.iter_mut().enumerate()
+                        .try_fold(false, |changed, (j, elem)| Ok::<_, ComputeError>(self.simplify_child_in_place(j, elem)? || changed));
+                    self.leave();
+                    changed |= row_changed?;
+                }
+            }
+        }
+        if let Some(rewritten) = self.rewrite_node(expr)? {
+            memory::recycle(std::mem::replace(expr, rewritten));
+            changed = true;
+        }
+        Ok(changed)
+    }
+    
+    /// 检查内存限制；估算占用越过回收阈值时丢弃缓存，腾出内存
+    fn check_memory(&mut self) -> Result<(), ComputeError> {
+        memory::check_memory_limit()?;
+        if memory::take_reclaim_request() {
+            self.cache.clear();
+        }
+        Ok(())
+    }
+    
+    /// 化简各子表达式；都没有改变时返回 `None`，否则用化简后的子表达式重建本节点
+    fn simplify_children(&mut self, expr: &Expression) -> Result<Option<Expression>, ComputeError> {
+        Ok(match expr {
+            Expression::Number(_) | Expression::Variable(_) | Expression::Constant(_) => None,
             Expression::BinaryOp { op, left, right } => {
-                let left_simplified = self.simplify_recursive(left)?;
-                let right_simplified = self.simplify_recursive(right)?;
-                self.simplify_binary_op(op, &left_simplified, &right_simplified)
+                let left = self.simplify_child(0, left)?;
+                let right = self.simplify_child(1, right)?;
+                changed([left, right]).map(|[left, right]| Expression::binary_op(op.clone(), left, right))
             }
-            
-            // 简化一元运算
             Expression::UnaryOp { op, operand } => {
-                let operand_simplified = self.simplify_recursive(operand)?;
-                self.simplify_unary_op(op, &operand_simplified)
+                changed([self.simplify_child(0, operand)?]).map(|[operand]| Expression::unary_op(op.clone(), operand))
             }
-            
-            // 简化函数调用
             Expression::Function { name, args } => {
-                let args_simplified: Result<Vec<_>, _> = args.iter()
-                    .map(|arg| self.simplify_recursive(arg))
-                    .collect();
-                let args_simplified = args_simplified?;
-                self.simplify_function(name, &args_simplified)
+                let args = args.iter().enumerate()
+                    .map(|(i, arg)| self.simplify_child(i, arg))
+                    .collect::<Result<Vec<_>, _>>()?;
+                changed_all(args).map(|args| Expression::function(name.clone(), args))
             }
-            
-            // 简化矩阵表达式
             Expression::Matrix(rows) => {
                 let mut simplified_rows = Vec::with_capacity(rows.len());
-                for row in rows {
-                    let mut simplified_row = Vec::with_capacity(row.len());
-                    for elem in row {
-                        let simplified_elem = self.simplify_recursive(elem)?;
-                        simplified_row.push(simplified_elem);
-                    }
-                    simplified_rows.push(simplified_row);
-                }
-                Ok(Expression::Matrix(simplified_rows))
-            }
-            
-            // 简化向量表达式
-            Expression::Vector(elements) => {
-                let mut simplified_elements = Vec::with_capacity(elements.len());
-                for elem in elements {
-                    let simplified_elem = self.simplify_recursive(elem)?;
-                    simplified_elements.push(simplified_elem);
+                for (i, row) in rows.iter().enumerate() {
+                    self.enter(i);
+                    let simplified_row = row.iter().enumerate()
+                        .map(|(j, elem)| self.simplify_child(j, elem))
+                        .collect::<Result<Vec<_>, _>>();
+                    self.leave();
+                    simplified_rows.push(simplified_row?);
                 }
-                Ok(Expression::Vector(simplified_elements))
-            }
-            
-            // 简化集合表达式
-            Expression::Set(elements) => {
-                let mut simplified_elements = Vec::with_capacity(elements.len());
-                for elem in elements {
-                    let simplified_elem = self.simplify_recursive(elem)?;
-                    simplified_elements.push(simplified_elem);
-                }
-                Ok(Expression::Set(simplified_elements))
+                simplified_rows.iter().flatten().any(is_owned)
+                    .then(|| Expression::Matrix(simplified_rows.into_iter().map(into_owned_all).collect()))
+            }
+            Expression::Vector(elements) | Expression::Set(elements) => {
+                let simplified = elements.iter().enumerate()
+                    .map(|(i, elem)| self.simplify_child(i, elem))
+                    .collect::<Result<Vec<_>, _>>()?;
+                changed_all(simplified).map(|simplified| match expr {
+                    Expression::Set(_) => Expression::Set(simplified),
+                    _ => Expression::Vector(simplified),
+                })
             }
-            
-            // 简化区间表达式
             Expression::Interval { start, end, start_inclusive, end_inclusive } => {
-                let simplified_start = self.simplify_recursive(start)?;
-                let simplified_end = self.simplify_recursive(end)?;
-                Ok(Expression::Interval {
-                    start: Box::new(simplified_start),
-                    end: Box::new(simplified_end),
+                let start = self.simplify_child(0, start)?;
+                let end = self.simplify_child(1, end)?;
+                changed([start, end]).map(|[start, end]| Expression::Interval {
+                    start: Box::new(start),
+                    end: Box::new(end),
                     start_inclusive: *start_inclusive,
                     end_inclusive: *end_inclusive,
                 })
             }
+        })
+    }
+    
+    /// 对子表达式已化简的节点应用规则，没有规则改写时返回 `None`
+    fn rewrite_node(&mut self, node: &Expression) -> Result<Option<Expression>, ComputeError> {
+        match node {
+            Expression::BinaryOp { op, left, right } => self.apply_rule(
+                || format!("binary.{}", rule_suffix(&format!("{:?}", op))),
+                || node.clone(),
+                |s| s.simplify_binary_op(op, left, right),
+            ),
+            
+            // sqrt(x) 在内部统一表示为 x^(1/2)，与分数指数共用幂运算规则
+            Expression::UnaryOp { op: UnaryOperator::Sqrt, operand } => self.rewrite_square_root(node, operand),
+            Expression::Function { name, args } if name == "sqrt" && args.len() == 1 => self.rewrite_square_root(node, &args[0]),
+            
+            Expression::UnaryOp { op, operand } => self.apply_rule(
+                || format!("unary.{}", rule_suffix(&format!("{:?}", op))),
+                || node.clone(),
+                |s| s.simplify_unary_op(op, operand),
+            ),
+            Expression::Function { name, args } => self.apply_rule(
+                || format!("func.{}", name),
+                || node.clone(),
+                |s| s.simplify_function(name, args),
+            ),
+            _ => Ok(None),
         }
     }
     
+    /// `sqrt(radicand)` 改写为幂并应用幂运算规则
+    fn rewrite_square_root(&mut self, node: &Expression, radicand: &Expression) -> Result<Option<Expression>, ComputeError> {
+        self.apply_rule(
+            || "sqrt.to_power".to_string(),
+            || node.clone(),
+            |s| Ok(Some(s.simplify_power(radicand, &Self::one_half())?
+                .unwrap_or_else(|| Expression::power(radicand.clone(), Self::one_half())))),
+        )
+    }
+    
     /// 简化二元运算
-    fn simplify_binary_op(&mut self, op: &BinaryOperator, left: &Expression, right: &Expression) -> Result<Expression, ComputeError> {
+    fn simplify_binary_op(&mut self, op: &BinaryOperator, left: &Expression, right: &Expression) -> Result<Option<Expression>, ComputeError> {
+        // 未定式与含 undefined 的运算先查表，避免被 x - x = 0、x / x = 1 等规则吞掉
+        if let Some(value) = self.indeterminate_semantics().resolve_expressions(op, left, right) {
+            return Ok(Some(value));
+        }
+        if let Some(folded) = self.fold_infinity(op, left, right) {
+            return Ok(Some(folded));
+        }
         match op {
             BinaryOperator::Add => self.simplify_addition(left, right),
             BinaryOperator::Subtract => self.simplify_subtraction(left, right),
             BinaryOperator::Multiply => self.simplify_multiplication(left, right),
             BinaryOperator::Divide => self.simplify_division(left, right),
             BinaryOperator::Power => self.simplify_power(left, right),
+            BinaryOperator::Modulo => self.simplify_modulo(left, right),
+            
+            // 逻辑与比较运算
+            BinaryOperator::And => Ok(logic::simplify_and(left, right)),
+            BinaryOperator::Or => Ok(logic::simplify_or(left, right)),
+            BinaryOperator::Equal | BinaryOperator::NotEqual | BinaryOperator::Less
+            | BinaryOperator::LessEqual | BinaryOperator::Greater | BinaryOperator::GreaterEqual => {
+                Ok(logic::simplify_comparison(op, left, right))
+            }
             
             // 矩阵和向量运算的简化
             BinaryOperator::MatrixMultiply => self.simplify_matrix_multiply(left, right),
             BinaryOperator::DotProduct => self.simplify_dot_product(left, right),
             BinaryOperator::CrossProduct => self.simplify_cross_product(left, right),
             
-            _ => Ok(Expression::binary_op(op.clone(), left.clone(), right.clone())),
+            _ => Ok(None),
         }
     }
     
+    /// 无穷与实常量的四则运算直接折叠成 ±∞ 或 0，使外层运算能继续查未定式表：(∞ + 1) - ∞ = undefined
+    fn fold_infinity(&self, op: &BinaryOperator, left: &Expression, right: &Expression) -> Option<Expression> {
+        let arithmetic = matches!(op, BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply
+            | BinaryOperator::Divide | BinaryOperator::Power);
+        let infinite = Magnitude::of_expression(left).is_infinite() || Magnitude::of_expression(right).is_infinite();
+        if !arithmetic || !infinite || !left.is_constant() || !right.is_constant() {
+            return None;
+        }
+        let expr = Expression::binary_op(op.clone(), left.clone(), right.clone());
+        match self.evaluate_constant_expression(&expr).ok()? {
+            Number::Float(f) if f.is_nan() => Some(Expression::Constant(MathConstant::Undefined)),
+            Number::Float(f) if f == f64::INFINITY => Some(Expression::Constant(MathConstant::PositiveInfinity)),
+            Number::Float(f) if f == f64::NEG_INFINITY => Some(Expression::Constant(MathConstant::NegativeInfinity)),
+            Number::Float(0.0) => Some(Expression::Number(Number::zero())),
+            _ => None,
+        }
+    }
+    
+    /// 简化取模运算，采用余数非负的数学约定：(-7) % 3 = 2
+    fn simplify_modulo(&self, left: &Expression, right: &Expression) -> Result<Option<Expression>, ComputeError> {
+        if let (Expression::Number(_), Expression::Number(_)) = (left, right) {
+            let (_, remainder) = self.divide_with_remainder("%", left, right, false)?;
+            return Ok(Some(remainder));
+        }
+        
+        // 规则：模数为正整数时逐项归约 (a + 10) % 7 = (a + 3) % 7
+        let rebuild = |reduced| Expression::binary_op(BinaryOperator::Modulo, reduced, right.clone());
+        if let Some(simplified) = modular::simplify_mod(left, right, rebuild) {
+            return Ok(Some(simplified));
+        }
+        
+        Ok(None)
+    }
+    
     /// 简化加法运算
-    fn simplify_addition(&self, left: &Expression, right: &Expression) -> Result<Expression, ComputeError> {
+    fn simplify_addition(&self, left: &Expression, right: &Expression) -> Result<Option<Expression>, ComputeError> {
         // 规则：0 + x = x
-        if self.is_zero(left) {
-            return Ok(right.clone());
-        }
-        if self.is_zero(right) {
-            return Ok(left.clone());
+        if self.options.identities {
+            if self.is_zero(left) {
+                return Ok(Some(right.clone()));
+            }
+            if self.is_zero(right) {
+                return Ok(Some(left.clone()));
+            }
         }
         
         // 规则：常量折叠
         if let (Expression::Number(a), Expression::Number(b)) = (left, right) {
-            return Ok(Expression::Number(a.clone() + b.clone()));
+            return Ok(Some(Expression::Number(a.clone() + b.clone())));
         }
         
-        // 规则：x + x = 2x
-        if left == right {
-            return Ok(Expression::multiply(
-                Expression::Number(Number::integer(2)),
-                left.clone()
-            ));
+        if self.options.like_terms {
+            // 规则：x + x = 2x
+            if Self::same_term(left, right) {
+                return Ok(Some(Expression::multiply(
+                    Expression::Number(Number::integer(2)),
+                    left.clone()
+                )));
+            }
+            
+            // 规则：合并同类项 (ax + bx = (a+b)x)
+            if let Some(simplified) = self.combine_like_terms_add(left, right) {
+                return Ok(Some(simplified));
+            }
         }
         
-        // 规则：合并同类项 (ax + bx = (a+b)x)
-        if let Some(simplified) = self.combine_like_terms_add(left, right) {
-            return Ok(simplified);
+        // 规则：sin(u)^2 + cos(u)^2 = 1，左边是和式时与其最后一项配对
+        if self.options.trigonometric {
+            if Self::is_pythagorean_pair(left, right) {
+                return Ok(Some(Expression::Number(Number::one())));
+            }
+            if let Expression::BinaryOp { op: BinaryOperator::Add, left: rest, right: last } = left {
+                if Self::is_pythagorean_pair(last, right) {
+                    let one = Expression::Number(Number::one());
+                    return Ok(Some(self.simplify_addition(rest, &one)?.unwrap_or_else(|| Expression::add(rest.as_ref().clone(), one))));
+                }
+            }
         }
         
         // 规则：交换律排序（将常数项放在前面）
         if self.should_swap_for_canonical_form(left, right) {
-            return Ok(Expression::add(right.clone(), left.clone()));
+            return Ok(Some(Expression::add(right.clone(), left.clone())));
         }
         
-        Ok(Expression::add(left.clone(), right.clone()))
+        Ok(None)
     }
     
-    /// 简化减法运算
-    fn simplify_subtraction(&self, left: &Expression, right: &Expression) -> Result<Expression, ComputeError> {
-        // 规则：x - 0 = x
-        if self.is_zero(right) {
-            return Ok(left.clone());
-        }
-        
-        // 规则：0 - x = -x
-        if self.is_zero(left) {
-            return Ok(Expression::negate(right.clone()));
+    /// 是否为 `sin(u)^2` 与 `cos(u)^2`（顺序不限）
+    fn is_pythagorean_pair(a: &Expression, b: &Expression) -> bool {
+        let squared_trig = |expr: &Expression| match expr {
+            Expression::BinaryOp { op: BinaryOperator::Power, left, right }
+                if matches!(right.as_ref(), Expression::Number(n) if n.is_two()) => match left.as_ref() {
+                    Expression::Function { name, args } if (name == "sin" || name == "cos") && args.len() == 1 => {
+                        Some((name.as_str() == "sin", args[0].clone()))
+                    }
+                    _ => None,
+                },
+            _ => None,
+        };
+        match (squared_trig(a), squared_trig(b)) {
+            (Some((a_is_sin, u)), Some((b_is_sin, v))) => a_is_sin != b_is_sin && u == v,
+            _ => false,
         }
-        
-        // 规则：x - x = 0
-        if left == right {
-            return Ok(Expression::Number(Number::zero()));
+    }
+    
+    /// 简化减法运算
+    fn simplify_subtraction(&self, left: &Expression, right: &Expression) -> Result<Option<Expression>, ComputeError> {
+        if self.options.identities {
+            // 规则：x - 0 = x
+            if self.is_zero(right) {
+                return Ok(Some(left.clone()));
+            }
+            
+            // 规则：0 - x = -x
+            if self.is_zero(left) {
+                return Ok(Some(Expression::negate(right.clone())));
+            }
         }
         
         // 规则：常量折叠
         if let (Expression::Number(a), Expression::Number(b)) = (left, right) {
-            return Ok(Expression::Number(a.clone() - b.clone()));
+            return Ok(Some(Expression::Number(a.clone() - b.clone())));
         }
         
-        // 规则：合并同类项 (ax - bx = (a-b)x)
-        if let Some(simplified) = self.combine_like_terms_sub(left, right) {
-            return Ok(simplified);
+        if self.options.like_terms {
+            // 规则：x - x = 0
+            if Self::same_term(left, right) {
+                return Ok(Some(Expression::Number(Number::zero())));
+            }
+            
+            // 规则：合并同类项 (ax - bx = (a-b)x)
+            if let Some(simplified) = self.combine_like_terms_sub(left, right) {
+                return Ok(Some(simplified));
+            }
         }
         
-        Ok(Expression::subtract(left.clone(), right.clone()))
+        Ok(None)
     }
     
     /// 简化乘法运算
-    fn simplify_multiplication(&self, left: &Expression, right: &Expression) -> Result<Expression, ComputeError> {
-        // 规则：0 * x = 0
-        if self.is_zero(left) || self.is_zero(right) {
-            return Ok(Expression::Number(Number::zero()));
-        }
-        
-        // 规则：1 * x = x
-        if self.is_one(left) {
-            return Ok(right.clone());
-        }
-        if self.is_one(right) {
-            return Ok(left.clone());
+    fn simplify_multiplication(&self, left: &Expression, right: &Expression) -> Result<Option<Expression>, ComputeError> {
+        // 规则：A * I = A、A * 0 = 0（矩阵乘积，维度须相容）
+        if let Some(simplified) = matrix_rules::simplify_product(left, right) {
+            return Ok(Some(simplified));
         }
         
-        // 规则：-1 * x = -x
-        if self.is_neg_one(left) {
-            return Ok(Expression::negate(right.clone()));
-        }
-        if self.is_neg_one(right) {
-            return Ok(Expression::negate(left.clone()));
+        if self.options.identities {
+            // 规则：0 * x = 0
+            if self.is_zero(left) || self.is_zero(right) {
+                return Ok(Some(Expression::Number(Number::zero())));
+            }
+            
+            // 规则：1 * x = x
+            if self.is_one(left) {
+                return Ok(Some(right.clone()));
+            }
+            if self.is_one(right) {
+                return Ok(Some(left.clone()));
+            }
+            
+            // 规则：-1 * x = -x
+            if self.is_neg_one(left) {
+                return Ok(Some(Expression::negate(right.clone())));
+            }
+            if self.is_neg_one(right) {
+                return Ok(Some(Expression::negate(left.clone())));
+            }
         }
         
         // 规则：常量折叠
         if let (Expression::Number(a), Expression::Number(b)) = (left, right) {
-            return Ok(Expression::Number(a.clone() * b.clone()));
+            return Ok(Some(Expression::Number(a.clone() * b.clone())));
         }
         
-        // 规则：x * x = x^2
-        if left == right {
-            return Ok(Expression::power(
-                left.clone(),
-                Expression::Number(Number::integer(2))
-            ));
+        // 矩阵乘法不满足交换律，以下规则不适用于已知维度的矩阵
+        if matrix_rules::shape(left).is_some() || matrix_rules::shape(right).is_some() {
+            return Ok(None);
         }
         
-        // 规则：合并同底数 (x^a * x^b = x^(a+b))
-        if let Some(simplified) = self.combine_powers_multiply(left, right) {
-            return Ok(simplified);
+        // 规则：数乘分式恰好约去分母 2 * (x^2/2) = x^2，约不尽时保留乘积形式
+        if matches!(left, Expression::Number(_)) || matches!(right, Expression::Number(_)) {
+            if let Some(flattened) = self.flatten_nested_fraction(left, right, false) {
+                if !Self::contains_division(&flattened) {
+                    return Ok(Some(flattened));
+                }
+            }
+        }
+        
+        if self.options.powers {
+            // 规则：合并同底数 (x^a * x^b = x^(a+b))
+            if let Some(simplified) = self.combine_powers_multiply(left, right) {
+                return Ok(Some(self.collapse_trivial_power(simplified)));
+            }
+            
+            // 规则：x * x = x^2
+            if left == right {
+                return Ok(Some(Expression::power(
+                    left.clone(),
+                    Expression::Number(Number::integer(2))
+                )));
+            }
         }
         
         // 规则：交换律排序（将常数项放在前面）
         if self.should_swap_for_canonical_form(left, right) {
-            return Ok(Expression::multiply(right.clone(), left.clone()));
+            return Ok(Some(Expression::multiply(right.clone(), left.clone())));
         }
         
-        Ok(Expression::multiply(left.clone(), right.clone()))
+        Ok(None)
     }
     
     /// 简化除法运算
-    fn simplify_division(&self, left: &Expression, right: &Expression) -> Result<Expression, ComputeError> {
-        // 规则：0 / x = 0 (x ≠ 0)
-        if self.is_zero(left) && !self.is_zero(right) {
-            return Ok(Expression::Number(Number::zero()));
-        }
-        
-        // 规则：x / 1 = x
-        if self.is_one(right) {
-            return Ok(left.clone());
-        }
-        
-        // 规则：x / x = 1 (x ≠ 0)
-        if left == right && !self.is_zero(left) {
-            return Ok(Expression::Number(Number::one()));
-        }
-        
-        // 规则：x / -1 = -x
-        if self.is_neg_one(right) {
-            return Ok(Expression::negate(left.clone()));
+    fn simplify_division(&self, left: &Expression, right: &Expression) -> Result<Option<Expression>, ComputeError> {
+        if self.options.identities {
+            // 规则：0 / x = 0 (x ≠ 0)
+            if self.is_zero(left) && !self.is_zero(right) {
+                return Ok(Some(Expression::Number(Number::zero())));
+            }
+            
+            // 规则：x / 1 = x
+            if self.is_one(right) {
+                return Ok(Some(left.clone()));
+            }
+            
+            // 规则：x / x = 1 (x ≠ 0)
+            if Self::same_term(left, right) && !self.is_zero(left) {
+                return Ok(Some(Expression::Number(Number::one())));
+            }
+            
+            // 规则：x / -1 = -x
+            if self.is_neg_one(right) {
+                return Ok(Some(Expression::negate(left.clone())));
+            }
         }
         
         // 规则：常量折叠
         if let (Expression::Number(a), Expression::Number(b)) = (left, right) {
             if !b.is_zero() {
-                return Ok(Expression::Number(a.clone() / b.clone()));
+                return Ok(Some(Expression::Number(a.clone() / b.clone())));
+            }
+        }
+        
+        // 规则：约去相同的因子 (u*v)/u = v、u/(u*v) = 1/v (u ≠ 0)
+        if self.options.identities && !self.is_zero(right) && !self.is_zero(left) {
+            if let Some(rest) = Self::remove_factor(left, right) {
+                return Ok(Some(rest));
+            }
+            if let Some(rest) = Self::remove_factor(right, left) {
+                return Ok(Some(Expression::divide(Expression::Number(Number::one()), rest)));
             }
         }
         
+        // 规则：嵌套分式展平 a/(b/c) = a*c/b、(a/b)/c = a/(b*c)
+        if let Some(flattened) = self.flatten_nested_fraction(left, right, true) {
+            return Ok(Some(flattened));
+        }
+        
         // 规则：合并同底数 (x^a / x^b = x^(a-b))
-        if let Some(simplified) = self.combine_powers_divide(left, right) {
-            return Ok(simplified);
+        if self.options.powers {
+            if let Some(simplified) = self.combine_powers_divide(left, right) {
+                return Ok(Some(self.collapse_trivial_power(simplified)));
+            }
         }
         
-        Ok(Expression::divide(left.clone(), right.clone()))
+        Ok(None)
     }
     
     /// 简化幂运算
-    fn simplify_power(&mut self, base: &Expression, exponent: &Expression) -> Result<Expression, ComputeError> {
-        // 规则：x^0 = 1 (x ≠ 0)
-        if self.is_zero(exponent) && !self.is_zero(base) {
-            return Ok(Expression::Number(Number::one()));
+    fn simplify_power(&mut self, base: &Expression, exponent: &Expression) -> Result<Option<Expression>, ComputeError> {
+        // 规则：I^n = I
+        if let Some(simplified) = matrix_rules::simplify_power(base, exponent) {
+            return Ok(Some(simplified));
         }
         
-        // 规则：x^1 = x
-        if self.is_one(exponent) {
-            return Ok(base.clone());
-        }
-        
-        // 规则：0^x = 0 (x > 0)
-        if self.is_zero(base) && self.is_positive(exponent) {
-            return Ok(Expression::Number(Number::zero()));
+        if self.options.identities {
+            // 规则：x^0 = 1 (x ≠ 0)
+            if self.is_zero(exponent) && !self.is_zero(base) {
+                return Ok(Some(Expression::Number(Number::one())));
+            }
+            
+            // 规则：x^1 = x
+            if self.is_one(exponent) {
+                return Ok(Some(base.clone()));
+            }
+            
+            // 规则：0^x = 0 (x > 0)
+            if self.is_zero(base) && self.is_positive(exponent) {
+                return Ok(Some(Expression::Number(Number::zero())));
+            }
+            
+            // 规则：1^x = 1
+            if self.is_one(base) {
+                return Ok(Some(Expression::Number(Number::one())));
+            }
         }
         
-        // 规则：1^x = 1
-        if self.is_one(base) {
-            return Ok(Expression::Number(Number::one()));
+        // 规则：x^(1/2) 对完全平方数直接开方，(x^2)^(1/2) = |x|
+        if self.is_one_half(exponent) {
+            if let Expression::Number(_) = base {
+                if let Ok(root @ Expression::Number(_)) = self.evaluate_square_root(base) {
+                    return Ok(Some(root));
+                }
+            }
+            if let Expression::BinaryOp { op: BinaryOperator::Power, left, right } = base {
+                if matches!(right.as_ref(), Expression::Number(n) if n.is_two()) {
+                    return Ok(Some(self.absolute_value_function(left)?));
+                }
+            }
         }
         
         // 规则：常量折叠（仅对小整数指数）
         if let (Expression::Number(a), Expression::Number(b)) = (base, exponent) {
             if let Some(result) = self.compute_integer_power(a, b) {
-                return Ok(Expression::Number(result));
+                return Ok(Some(Expression::Number(result)));
             }
         }
         
+        if !self.options.powers {
+            return Ok(None);
+        }
+        
         // 规则：二项式展开 (a+b)^n 或 (a-b)^n，当 n 是小正整数时
         if let Some(expanded) = self.try_binomial_expansion(base, exponent)? {
-            return Ok(expanded);
+            return Ok(Some(expanded));
         }
         
         // 规则：(x^a)^b = x^(a*b)
@@ -419,180 +987,239 @@ impl Simplifier {
             } else {
                 new_exp
             };
-            return Ok(Expression::power(inner_base.as_ref().clone(), simplified_exp));
+            return Ok(Some(self.simplify_power(inner_base.as_ref(), &simplified_exp)?
+                .unwrap_or_else(|| Expression::power(inner_base.as_ref().clone(), simplified_exp))));
         }
         
-        Ok(Expression::power(base.clone(), exponent.clone()))
+        Ok(None)
     }
     
     /// 简化一元运算
-    fn simplify_unary_op(&self, op: &UnaryOperator, operand: &Expression) -> Result<Expression, ComputeError> {
+    fn simplify_unary_op(&self, op: &UnaryOperator, operand: &Expression) -> Result<Option<Expression>, ComputeError> {
+        if indeterminate::is_undefined(operand) {
+            return Ok(Some(Expression::Constant(MathConstant::Undefined)));
+        }
         match op {
             UnaryOperator::Negate => self.simplify_negation(operand),
-            UnaryOperator::Plus => Ok(operand.clone()), // +x = x
+            UnaryOperator::Plus => Ok(Some(operand.clone())), // +x = x
+            UnaryOperator::Not => Ok(logic::simplify_not(operand)),
             UnaryOperator::Abs => self.simplify_absolute_value(operand),
+            // 整数的阶乘直接计算
+            UnaryOperator::Factorial => match operand {
+                Expression::Number(_) => Ok(self.evaluate_factorial(operand).ok()),
+                _ => Ok(None),
+            },
             
             // 矩阵专用运算符的简化
-            UnaryOperator::Transpose => self.simplify_transpose(operand),
-            UnaryOperator::Determinant => self.simplify_determinant(operand),
-            UnaryOperator::Inverse => self.simplify_inverse(operand),
-            UnaryOperator::Trace => self.simplify_trace(operand),
+            UnaryOperator::Transpose | UnaryOperator::Determinant
+            | UnaryOperator::Inverse | UnaryOperator::Trace => self.simplify_matrix_operation(op, operand),
             
-            _ => Ok(Expression::unary_op(op.clone(), operand.clone())),
+            _ => Ok(None),
         }
     }
     
     /// 简化负号运算
-    fn simplify_negation(&self, operand: &Expression) -> Result<Expression, ComputeError> {
+    fn simplify_negation(&self, operand: &Expression) -> Result<Option<Expression>, ComputeError> {
         match operand {
             // 规则：-(-x) = x
             Expression::UnaryOp { op: UnaryOperator::Negate, operand: inner } => {
-                Ok(inner.as_ref().clone())
+                Ok(Some(inner.as_ref().clone()))
             }
             
             // 规则：-(a + b) = -a - b
             Expression::BinaryOp { op: BinaryOperator::Add, left, right } => {
-                Ok(Expression::subtract(
+                Ok(Some(Expression::subtract(
                     Expression::negate(left.as_ref().clone()),
                     right.as_ref().clone()
-                ))
+                )))
             }
             
             // 规则：-(a - b) = b - a
             Expression::BinaryOp { op: BinaryOperator::Subtract, left, right } => {
-                Ok(Expression::subtract(
+                Ok(Some(Expression::subtract(
                     right.as_ref().clone(),
                     left.as_ref().clone()
-                ))
+                )))
             }
             
             // 规则：-(a * b) = (-a) * b
             Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
-                Ok(Expression::multiply(
+                Ok(Some(Expression::multiply(
                     Expression::negate(left.as_ref().clone()),
                     right.as_ref().clone()
-                ))
+                )))
             }
             
             // 规则：常量折叠
             Expression::Number(n) => {
-                Ok(Expression::Number(n.neg()))
+                Ok(Some(Expression::Number(n.neg())))
             }
             
-            _ => Ok(Expression::negate(operand.clone())),
+            _ => Ok(None),
         }
     }
     
     /// 简化绝对值运算
-    fn simplify_absolute_value(&self, operand: &Expression) -> Result<Expression, ComputeError> {
+    fn simplify_absolute_value(&self, operand: &Expression) -> Result<Option<Expression>, ComputeError> {
         match operand {
             // 规则：|x| = x 如果 x >= 0
-            _ if self.is_non_negative(operand) => Ok(operand.clone()),
+            _ if self.is_non_negative(operand) => Ok(Some(operand.clone())),
             
             // 规则：|x| = -x 如果 x < 0
-            _ if self.is_negative(operand) => Ok(Expression::negate(operand.clone())),
+            _ if self.is_negative(operand) => Ok(Some(Expression::negate(operand.clone()))),
             
             // 规则：|-x| = |x|
             Expression::UnaryOp { op: UnaryOperator::Negate, operand: inner } => {
-                Ok(Expression::abs(inner.as_ref().clone()))
+                Ok(Some(Expression::abs(inner.as_ref().clone())))
             }
             
             // 规则：|a * b| = |a| * |b|
             Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
-                Ok(Expression::multiply(
+                Ok(Some(Expression::multiply(
                     Expression::abs(left.as_ref().clone()),
                     Expression::abs(right.as_ref().clone())
-                ))
+                )))
             }
             
             // 规则：常量折叠
             Expression::Number(n) => {
-                Ok(Expression::Number(n.abs()?))
+                Ok(Some(Expression::Number(n.abs()?)))
             }
             
-            _ => Ok(Expression::abs(operand.clone())),
+            _ => Ok(None),
         }
     }
     
     /// 简化函数调用
-    fn simplify_function(&self, name: &str, args: &[Expression]) -> Result<Expression, ComputeError> {
+    fn simplify_function(&self, name: &str, args: &[Expression]) -> Result<Option<Expression>, ComputeError> {
+        // 下标访问：具体矩阵、向量直接取出元素，越界时报错
+        if name == INDEX_FUNCTION && args.len() >= 2 {
+            return matrix_rules::simplify_index(&args[0], &args[1..]);
+        }
+        
+        if args.iter().any(indeterminate::is_undefined) {
+            return Ok(Some(Expression::Constant(MathConstant::Undefined)));
+        }
+        
         // 如果参数都是常量，尝试计算函数值
         if args.iter().all(|arg| arg.is_constant()) {
             if let Ok(result) = self.evaluate_function(name, args) {
-                return Ok(result);
+                return Ok(Some(result));
             }
         }
         
         // 应用特定的函数简化规则
         match name {
-            "ln" | "log" => self.simplify_logarithm(args),
+            "ln" => self.simplify_logarithm(args),
+            "log" => Ok(Some(self.simplify_logarithm(args)?.unwrap_or_else(|| Expression::function("ln", args.to_vec())))),
             "sin" => self.simplify_sine(args),
             "cos" => self.simplify_cosine(args),
             "tan" => self.simplify_tangent(args),
             "exp" => self.simplify_exponential(args),
             "sqrt" => self.simplify_square_root(args),
             "abs" => self.simplify_absolute_value_function(args),
-            _ => Ok(Expression::function(name, args.to_vec())),
+            "cbrt" | "root" => self.simplify_nth_root(name, args),
+            "floor" | "ceil" | "round" | "trunc" => self.simplify_rounding(args),
+            "sum" => self.simplify_sum(args),
+            "diff" => self.simplify_derivative(args),
+            name if modular::is_mod_function(name) && args.len() == 2 => {
+                let rebuild = |reduced| Expression::function(name, vec![reduced, args[1].clone()]);
+                Ok(modular::simplify_mod(&args[0], &args[1], rebuild))
+            }
+            "congruent" if args.len() == 3 => Ok(modular::congruent(&args[0], &args[1], &args[2])
+                .map(|holds| Expression::Number(if holds { Number::one() } else { Number::zero() }))),
+            "resultant" if args.len() == 3 => match &args[2] {
+                Expression::Variable(var) => resultant::resultant(&args[0], &args[1], var).map(Some),
+                _ => Ok(None),
+            },
+            "discriminant" if args.len() == 2 => match &args[1] {
+                Expression::Variable(var) => resultant::discriminant(&args[0], var).map(Some),
+                _ => Ok(None),
+            },
+            "forward_difference" if args.len() == 2 => match &args[1] {
+                Expression::Variable(var) => SummationEngine::new().forward_difference(&args[0], var).map(Some),
+                _ => Ok(None),
+            },
+            // 变量可以是单个变量，也可以是变量列表 [x, y]
+            "complete_square" | "quadratic_form_matrix" if args.len() == 2 => {
+                let vars = match &args[1] {
+                    Expression::Variable(var) => Some(vec![var.as_str()]),
+                    list => vector_calculus::variables(list),
+                };
+                match vars {
+                    Some(vars) if name == "complete_square" => conic::complete_square(&args[0], &vars).map(Some),
+                    Some(vars) => conic::quadratic_form_matrix(&args[0], &vars).map(Some),
+                    None => Ok(None),
+                }
+            }
+            name if distributions::DISTRIBUTION_FUNCTIONS.contains(&name) => distributions::expand(name, args).transpose(),
+            name if lists::LIST_FUNCTIONS.contains(&name) => lists::simplify_call(name, args).transpose(),
+            name if tensors::TENSOR_FUNCTIONS.contains(&name) => tensors::simplify_call(name, args).transpose(),
+            name if orthogonal::ORTHOGONAL_POLYNOMIALS.contains(&name) => orthogonal::simplify_call(name, args).transpose(),
+            name if vector_calculus::VECTOR_CALCULUS_FUNCTIONS.contains(&name) => vector_calculus::simplify_call(name, args).transpose(),
+            "expectation" => distributions::expectation_call(args).transpose(),
+            "transpose" | "det" | "inverse" | "inv" | "trace" | "tr" => {
+                Ok(matrix_rules::simplify_operation(&Expression::function(name, args.to_vec())))
+            }
+            _ => Ok(None),
         }
     }
     
     /// 计算函数值（当参数都是常量时）
     fn evaluate_function(&self, name: &str, args: &[Expression]) -> Result<Expression, ComputeError> {
-        if args.is_empty() {
-            return Err(ComputeError::domain_error("函数需要至少一个参数"));
+        // gcd 和 lcm 接受任意个参数，没有参数时返回单位元
+        if name == "gcd" || name == "lcm" {
+            return self.evaluate_gcd_lcm(name, args);
         }
         
+        signatures::check_arity(name, args.len()).map_err(ComputeError::domain_error)?;
         match name {
-            "ln" | "log" => {
-                if args.len() != 1 {
-                    return Err(ComputeError::domain_error("ln 函数需要恰好一个参数"));
-                }
-                self.evaluate_logarithm(&args[0])
-            }
-            "sin" => {
-                if args.len() != 1 {
-                    return Err(ComputeError::domain_error("sin 函数需要恰好一个参数"));
-                }
-                self.evaluate_sine(&args[0])
-            }
-            "cos" => {
-                if args.len() != 1 {
-                    return Err(ComputeError::domain_error("cos 函数需要恰好一个参数"));
+            "ln" | "log" if args.len() == 1 => self.evaluate_logarithm(&args[0]),
+            "sin" => self.evaluate_sine(&args[0]),
+            "cos" => self.evaluate_cosine(&args[0]),
+            "tan" => self.evaluate_tangent(&args[0]),
+            "exp" => self.evaluate_exponential(&args[0]),
+            "sqrt" => self.evaluate_square_root(&args[0]),
+            "abs" => self.evaluate_absolute_value(&args[0]),
+            "cbrt" => self.evaluate_nth_root(&args[0], 3),
+            "root" => {
+                let n = self.root_index(&args[1])
+                    .ok_or_else(|| ComputeError::domain_error(tr!("simplify.root_index")))?;
+                self.evaluate_nth_root(&args[0], n)
+            }
+            "factorial" => self.evaluate_factorial(&args[0]),
+            "floor" | "ceil" | "round" | "trunc" => self.evaluate_rounding(name, &args[0]),
+            "mod" | "rem" | "quotient" | "divmod" => {
+                // rem 采用截断约定，其余采用余数非负的数学约定
+                let (quotient, remainder) = self.divide_with_remainder(name, &args[0], &args[1], name == "rem")?;
+                match name {
+                    "mod" | "rem" => Ok(remainder),
+                    "quotient" => Ok(quotient),
+                    _ => Ok(Expression::Vector(vec![quotient, remainder])),
                 }
-                self.evaluate_cosine(&args[0])
-            }
-            "tan" => {
-                if args.len() != 1 {
-                    return Err(ComputeError::domain_error("tan 函数需要恰好一个参数"));
-                }
-                self.evaluate_tangent(&args[0])
-            }
-            "exp" => {
-                if args.len() != 1 {
-                    return Err(ComputeError::domain_error("exp 函数需要恰好一个参数"));
-                }
-                self.evaluate_exponential(&args[0])
-            }
-            "sqrt" => {
-                if args.len() != 1 {
-                    return Err(ComputeError::domain_error("sqrt 函数需要恰好一个参数"));
-                }
-                self.evaluate_square_root(&args[0])
-            }
-            "abs" => {
-                if args.len() != 1 {
-                    return Err(ComputeError::domain_error("abs 函数需要恰好一个参数"));
-                }
-                self.evaluate_absolute_value(&args[0])
-            }
-            "factorial" => {
-                if args.len() != 1 {
-                    return Err(ComputeError::domain_error("factorial 函数需要恰好一个参数"));
-                }
-                self.evaluate_factorial(&args[0])
             }
+            "erf" | "erfc" | "Si" | "Ci" | "Ei" | "li" | "sinc" => self.evaluate_special_function(name, &args[0]),
             _ => Err(ComputeError::UnsupportedOperation { 
-                operation: format!("未知函数: {}", name) 
+                operation: tr!("simplify.unknown_function", name = name) 
+            }),
+        }
+    }
+    
+    /// 计算特殊函数：零点给出精确值，其余数值参数给出浮点近似
+    fn evaluate_special_function(&self, name: &str, arg: &Expression) -> Result<Expression, ComputeError> {
+        match arg {
+            Expression::Number(n) if n.is_zero() => match name {
+                "erf" | "Si" | "li" => Ok(Expression::Number(Number::integer(0))),
+                "erfc" | "sinc" => Ok(Expression::Number(Number::integer(1))),
+                _ => Err(ComputeError::domain_error(tr!("simplify.undefined_at_zero", name = name))),
+            },
+            Expression::Number(n) => n.to_f64()
+                .and_then(|x| special_functions::evaluate(name, x))
+                .filter(|value| value.is_finite())
+                .map(|value| Expression::Number(Number::Float(value)))
+                .ok_or_else(|| ComputeError::domain_error(tr!("simplify.no_finite_value", name = name, argument = n))),
+            _ => Err(ComputeError::UnsupportedOperation { 
+                operation: tr!("simplify.unevaluable_function", name = name) 
             }),
         }
     }
@@ -618,16 +1245,16 @@ impl Simplifier {
                     if f > 0.0 {
                         Ok(Expression::Number(Number::Float(f.ln())))
                     } else {
-                        Err(ComputeError::domain_error("对数的参数必须为正数"))
+                        Err(ComputeError::domain_error(tr!("simplify.log_requires_positive")))
                     }
                 } else {
                     Err(ComputeError::UnsupportedOperation { 
-                        operation: "复数对数计算".to_string() 
+                        operation: tr!("simplify.complex_log").to_string() 
                     })
                 }
             }
             _ => Err(ComputeError::UnsupportedOperation { 
-                operation: "无法计算此对数表达式".to_string() 
+                operation: tr!("simplify.unevaluable_log").to_string() 
             }),
         }
     }
@@ -679,12 +1306,12 @@ impl Simplifier {
                     Ok(Expression::Number(Number::Float(f.sin())))
                 } else {
                     Err(ComputeError::UnsupportedOperation { 
-                        operation: "复数正弦计算".to_string() 
+                        operation: tr!("simplify.complex_sin").to_string() 
                     })
                 }
             }
             _ => Err(ComputeError::UnsupportedOperation { 
-                operation: "无法计算此正弦表达式".to_string() 
+                operation: tr!("simplify.unevaluable_sin").to_string() 
             }),
         }
     }
@@ -736,12 +1363,12 @@ impl Simplifier {
                     Ok(Expression::Number(Number::Float(f.cos())))
                 } else {
                     Err(ComputeError::UnsupportedOperation { 
-                        operation: "复数余弦计算".to_string() 
+                        operation: tr!("simplify.complex_cos").to_string() 
                     })
                 }
             }
             _ => Err(ComputeError::UnsupportedOperation { 
-                operation: "无法计算此余弦表达式".to_string() 
+                operation: tr!("simplify.unevaluable_cos").to_string() 
             }),
         }
     }
@@ -780,12 +1407,12 @@ impl Simplifier {
                     Ok(Expression::Number(Number::Float(f.tan())))
                 } else {
                     Err(ComputeError::UnsupportedOperation { 
-                        operation: "复数正切计算".to_string() 
+                        operation: tr!("simplify.complex_tan").to_string() 
                     })
                 }
             }
             _ => Err(ComputeError::UnsupportedOperation { 
-                operation: "无法计算此正切表达式".to_string() 
+                operation: tr!("simplify.unevaluable_tan").to_string() 
             }),
         }
     }
@@ -811,12 +1438,12 @@ impl Simplifier {
                     Ok(Expression::Number(Number::Float(f.exp())))
                 } else {
                     Err(ComputeError::UnsupportedOperation { 
-                        operation: "复数指数计算".to_string() 
+                        operation: tr!("simplify.complex_exp").to_string() 
                     })
                 }
             }
             _ => Err(ComputeError::UnsupportedOperation { 
-                operation: "无法计算此指数表达式".to_string() 
+                operation: tr!("simplify.unevaluable_exp").to_string() 
             }),
         }
     }
@@ -835,7 +1462,7 @@ impl Simplifier {
             // 对于整数，检查是否是完全平方数
             Expression::Number(Number::Integer(i)) => {
                 if i < &BigInt::from(0) {
-                    return Err(ComputeError::domain_error("平方根的参数不能为负数"));
+                    return Err(ComputeError::domain_error(tr!("simplify.negative_sqrt")));
                 }
                 
                 // 检查是否是完全平方数
@@ -849,7 +1476,7 @@ impl Simplifier {
             // 对于有理数，尝试简化
             Expression::Number(Number::Rational(r)) => {
                 if r < &BigRational::from(BigInt::from(0)) {
-                    return Err(ComputeError::domain_error("平方根的参数不能为负数"));
+                    return Err(ComputeError::domain_error(tr!("simplify.negative_sqrt")));
                 }
                 
                 // 分别计算分子和分母的平方根
@@ -879,16 +1506,138 @@ impl Simplifier {
         }
     }
     
-    /// 计算绝对值函数
-    fn evaluate_absolute_value(&self, arg: &Expression) -> Result<Expression, ComputeError> {
-        match arg {
-            Expression::Number(n) => {
-                Ok(Expression::Number(n.abs()?))
-            }
-            _ => Err(ComputeError::UnsupportedOperation { 
-                operation: "无法计算此绝对值表达式".to_string() 
-            }),
+    /// 计算绝对值函数
+    fn evaluate_absolute_value(&self, arg: &Expression) -> Result<Expression, ComputeError> {
+        match arg {
+            Expression::Number(n) => {
+                Ok(Expression::Number(n.abs()?))
+            }
+            _ => Err(ComputeError::UnsupportedOperation { 
+                operation: tr!("simplify.unevaluable_abs").to_string() 
+            }),
+        }
+    }
+    
+    /// 计算 n 次根（参数为常量）
+    fn evaluate_nth_root(&self, arg: &Expression, n: u32) -> Result<Expression, ComputeError> {
+        let negative = match arg {
+            Expression::Number(value) if value.is_negative() => true,
+            Expression::Number(_) => false,
+            _ => return Err(ComputeError::UnsupportedOperation { operation: tr!("simplify.non_numeric_root").to_string() }),
+        };
+        
+        if negative && n.is_multiple_of(2) {
+            return Err(ComputeError::domain_error(tr!("simplify.negative_even_root")));
+        }
+        if negative && !self.real_odd_roots {
+            return Err(ComputeError::UnsupportedOperation { operation: tr!("simplify.negative_odd_root").to_string() });
+        }
+        
+        use num_traits::Signed;
+        let root = match arg {
+            Expression::Number(Number::Integer(i)) => {
+                self.exact_nth_root(&i.abs(), n).map(Number::Integer)
+            }
+            Expression::Number(Number::Rational(r)) => {
+                match (self.exact_nth_root(&r.numer().abs(), n), self.exact_nth_root(r.denom(), n)) {
+                    (Some(numer), Some(denom)) => Some(Number::Rational(BigRational::new(numer, denom))),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        
+        // 非完全 n 次幂保持符号形式
+        let root = root.ok_or_else(|| ComputeError::UnsupportedOperation { operation: tr!("simplify.imperfect_root").to_string() })?;
+        Ok(Expression::Number(if negative { -root } else { root }))
+    }
+    
+    /// 计算非负整数的精确 n 次根（如果是完全 n 次幂）
+    fn exact_nth_root(&self, value: &BigInt, n: u32) -> Option<BigInt> {
+        let root = value.nth_root(n);
+        if num_traits::pow(root.clone(), n as usize) == *value {
+            Some(root)
+        } else {
+            None
+        }
+    }
+    
+    /// 提取 root(x, n) 的根指数
+    fn root_index(&self, expr: &Expression) -> Option<u32> {
+        match expr {
+            Expression::Number(n) => n.to_integer()?.to_u32().filter(|&n| n > 0),
+            _ => None,
+        }
+    }
+    
+    /// 计算取整函数（floor、ceil、round、trunc）
+    fn evaluate_rounding(&self, name: &str, arg: &Expression) -> Result<Expression, ComputeError> {
+        let value = match arg {
+            Expression::Number(Number::Integer(i)) => return Ok(Expression::Number(Number::Integer(i.clone()))),
+            Expression::Number(Number::Rational(r)) => r.clone(),
+            Expression::Number(Number::Float(f)) if f.is_finite() => {
+                BigRational::from_float(*f).ok_or_else(|| ComputeError::domain_error(tr!("simplify.unroundable_float")))?
+            }
+            _ => return Err(ComputeError::UnsupportedOperation { operation: tr!("simplify.requires_rational", name = name) }),
+        };
+        
+        // round 采用远离零的舍入方式：round(5/2) = 3，round(-5/2) = -3
+        let rounded = match name {
+            "floor" => value.floor(),
+            "ceil" => value.ceil(),
+            "round" => value.round(),
+            _ => value.trunc(),
+        };
+        Ok(Expression::Number(Number::Integer(rounded.to_integer())))
+    }
+    
+    /// 带余除法，返回 (整数商, 余数)，满足 被除数 = 商 * 除数 + 余数
+    ///
+    /// 默认采用数学约定，余数满足 0 <= r < |b|；`truncated` 为真时商向零截断，余数与被除数同号。
+    fn divide_with_remainder(&self, name: &str, dividend: &Expression, divisor: &Expression, truncated: bool) -> Result<(Expression, Expression), ComputeError> {
+        use num_traits::{Signed, Zero};
+        
+        let exact = |expr: &Expression| match expr {
+            Expression::Number(n @ (Number::Integer(_) | Number::Rational(_))) => n.to_rational(),
+            _ => None,
+        };
+        let (Some(a), Some(b)) = (exact(dividend), exact(divisor)) else {
+            return Err(ComputeError::UnsupportedOperation { operation: tr!("simplify.requires_rational", name = name) });
+        };
+        if b.is_zero() {
+            return Err(ComputeError::DivisionByZero);
+        }
+        
+        let ratio = &a / &b;
+        let quotient = if truncated {
+            ratio.trunc()
+        } else if b.is_positive() {
+            ratio.floor()
+        } else {
+            ratio.ceil()
+        };
+        let remainder = a - &quotient * &b;
+        let remainder = if remainder.is_integer() {
+            Number::Integer(remainder.to_integer())
+        } else {
+            Number::Rational(remainder)
+        };
+        Ok((Expression::Number(Number::Integer(quotient.to_integer())), Expression::Number(remainder)))
+    }
+    
+    /// 对整数列表折叠计算最大公约数或最小公倍数
+    fn evaluate_gcd_lcm(&self, name: &str, args: &[Expression]) -> Result<Expression, ComputeError> {
+        use num_integer::Integer;
+        
+        let mut acc = if name == "gcd" { BigInt::from(0) } else { BigInt::from(1) };
+        for arg in args {
+            let value = match arg {
+                Expression::Number(Number::Integer(i)) => i,
+                _ => return Err(ComputeError::UnsupportedOperation { operation: tr!("simplify.requires_integer", name = name) }),
+            };
+            acc = if name == "gcd" { acc.gcd(value) } else { acc.lcm(value) };
         }
+        Ok(Expression::Number(Number::Integer(acc)))
     }
     
     /// 计算阶乘函数
@@ -903,116 +1652,196 @@ impl Simplifier {
                         }
                         Ok(Expression::Number(Number::integer(result)))
                     } else if i < 0 {
-                        Err(ComputeError::domain_error("阶乘的参数不能为负数"))
+                        Err(ComputeError::domain_error(tr!("simplify.negative_factorial")))
                     } else {
-                        Err(ComputeError::domain_error("阶乘的参数过大"))
+                        Err(ComputeError::domain_error(tr!("simplify.factorial_too_large")))
                     }
                 } else {
-                    Err(ComputeError::domain_error("阶乘的参数必须为整数"))
+                    Err(ComputeError::domain_error(tr!("simplify.factorial_requires_integer")))
                 }
             }
             _ => Err(ComputeError::UnsupportedOperation { 
-                operation: "无法计算此阶乘表达式".to_string() 
+                operation: tr!("simplify.unevaluable_factorial").to_string() 
             }),
         }
     }
     
     /// 简化对数函数（非常量参数）
-    fn simplify_logarithm(&self, args: &[Expression]) -> Result<Expression, ComputeError> {
+    fn simplify_logarithm(&self, args: &[Expression]) -> Result<Option<Expression>, ComputeError> {
         if args.len() != 1 {
-            return Ok(Expression::function("ln", args.to_vec()));
+            return Ok(None);
         }
         
         match &args[0] {
             // ln(e^x) = x
-            Expression::Function { name, args: inner_args } if name == "exp" && inner_args.len() == 1 => {
-                Ok(inner_args[0].clone())
+            Expression::Function { name, args: inner_args } if self.options.logarithmic && name == "exp" && inner_args.len() == 1 => {
+                Ok(Some(inner_args[0].clone()))
+            }
+            // 假设 x 为实数时 ln(e^x) = x
+            Expression::BinaryOp { op: BinaryOperator::Power, left, right }
+                if self.options.logarithmic && matches!(left.as_ref(), Expression::Constant(MathConstant::E)) && self.assumptions.is_real(right) => {
+                Ok(Some(right.as_ref().clone()))
             }
-            _ => Ok(Expression::function("ln", args.to_vec())),
+            _ => Ok(None),
         }
     }
     
     /// 简化正弦函数（非常量参数）
-    fn simplify_sine(&self, args: &[Expression]) -> Result<Expression, ComputeError> {
+    fn simplify_sine(&self, args: &[Expression]) -> Result<Option<Expression>, ComputeError> {
         if args.len() != 1 {
-            return Ok(Expression::function("sin", args.to_vec()));
+            return Ok(None);
         }
         
         // 这里可以添加更多的三角恒等式简化
-        Ok(Expression::function("sin", args.to_vec()))
+        Ok(None)
     }
     
     /// 简化余弦函数（非常量参数）
-    fn simplify_cosine(&self, args: &[Expression]) -> Result<Expression, ComputeError> {
+    fn simplify_cosine(&self, args: &[Expression]) -> Result<Option<Expression>, ComputeError> {
         if args.len() != 1 {
-            return Ok(Expression::function("cos", args.to_vec()));
+            return Ok(None);
         }
         
         // 这里可以添加更多的三角恒等式简化
-        Ok(Expression::function("cos", args.to_vec()))
+        Ok(None)
     }
     
     /// 简化正切函数（非常量参数）
-    fn simplify_tangent(&self, args: &[Expression]) -> Result<Expression, ComputeError> {
+    fn simplify_tangent(&self, args: &[Expression]) -> Result<Option<Expression>, ComputeError> {
         if args.len() != 1 {
-            return Ok(Expression::function("tan", args.to_vec()));
+            return Ok(None);
         }
         
         // 这里可以添加更多的三角恒等式简化
-        Ok(Expression::function("tan", args.to_vec()))
+        Ok(None)
     }
     
     /// 简化指数函数（非常量参数）
-    fn simplify_exponential(&self, args: &[Expression]) -> Result<Expression, ComputeError> {
+    fn simplify_exponential(&self, args: &[Expression]) -> Result<Option<Expression>, ComputeError> {
         if args.len() != 1 {
-            return Ok(Expression::function("exp", args.to_vec()));
+            return Ok(None);
         }
         
         match &args[0] {
             // exp(ln(x)) = x
-            Expression::Function { name, args: inner_args } if name == "ln" && inner_args.len() == 1 => {
-                Ok(inner_args[0].clone())
+            Expression::Function { name, args: inner_args } if self.options.logarithmic && name == "ln" && inner_args.len() == 1 => {
+                Ok(Some(inner_args[0].clone()))
             }
-            _ => Ok(Expression::function("exp", args.to_vec())),
+            _ => Ok(None),
         }
     }
     
     /// 简化平方根函数（非常量参数）
-    fn simplify_square_root(&self, args: &[Expression]) -> Result<Expression, ComputeError> {
+    fn simplify_square_root(&self, args: &[Expression]) -> Result<Option<Expression>, ComputeError> {
         if args.len() != 1 {
-            return Ok(Expression::function("sqrt", args.to_vec()));
+            return Ok(None);
         }
         
         match &args[0] {
-            // sqrt(x^2) = |x|
+            // sqrt(x^2) = |x|，按假设继续去掉绝对值
             Expression::BinaryOp { op: BinaryOperator::Power, left, right } 
                 if matches!(right.as_ref(), Expression::Number(n) if n.is_two()) => {
-                Ok(Expression::function("abs", vec![left.as_ref().clone()]))
+                Ok(Some(self.absolute_value_function(left)?))
+            }
+            _ => Ok(None),
+        }
+    }
+    
+    /// 简化 cbrt 与 root 函数（非常量参数）
+    fn simplify_nth_root(&self, name: &str, args: &[Expression]) -> Result<Option<Expression>, ComputeError> {
+        let (radicand, n) = match (name, args) {
+            ("cbrt", [radicand]) => (radicand, 3),
+            ("root", [radicand, index]) => match self.root_index(index) {
+                Some(n) => (radicand, n),
+                None => return Ok(None),
+            },
+            _ => return Ok(None),
+        };
+        
+        // root(x, 1) = x，root(x, 2) = sqrt(x)
+        if n == 1 {
+            return Ok(Some(radicand.clone()));
+        }
+        if n == 2 {
+            return Ok(Some(self.simplify_square_root(std::slice::from_ref(radicand))?
+                .unwrap_or_else(|| Expression::function("sqrt", vec![radicand.clone()]))));
+        }
+        
+        // root(x^n, n) = x（奇数 n），|x|（偶数 n）
+        if let Expression::BinaryOp { op: BinaryOperator::Power, left, right } = radicand {
+            if self.root_index(right) == Some(n) {
+                if n % 2 == 1 && self.real_odd_roots {
+                    return Ok(Some(left.as_ref().clone()));
+                }
+                if n % 2 == 0 {
+                    return Ok(Some(self.absolute_value_function(left)?));
+                }
             }
-            _ => Ok(Expression::function("sqrt", args.to_vec())),
         }
+        
+        Ok(None)
+    }
+    
+    /// 简化取整函数（非常量参数）
+    fn simplify_rounding(&self, args: &[Expression]) -> Result<Option<Expression>, ComputeError> {
+        // 取整结果已是整数：floor(ceil(x)) = ceil(x)
+        if let [Expression::Function { name: inner, args: inner_args }] = args {
+            if matches!(inner.as_str(), "floor" | "ceil" | "round" | "trunc") && inner_args.len() == 1 {
+                return Ok(Some(args[0].clone()));
+            }
+        }
+        Ok(None)
+    }
+    
+    /// 简化求和 sum(f, n, a, b)，无法求值时保持符号形式
+    fn simplify_sum(&self, args: &[Expression]) -> Result<Option<Expression>, ComputeError> {
+        match args {
+            [term, Expression::Variable(var), lower, upper] => {
+                Ok(Some(SummationEngine::new().sum(term, var, lower, upper)?.value))
+            }
+            _ => Ok(None),
+        }
+    }
+    
+    /// 简化求导 diff(f, x)，无法求导时保持符号形式
+    fn simplify_derivative(&self, args: &[Expression]) -> Result<Option<Expression>, ComputeError> {
+        if let [expr, Expression::Variable(var)] = args {
+            if let Ok(derivative) = CalculusEngine::new().differentiate(expr, var) {
+                return Simplifier::new().simplify(&derivative).map(Some);
+            }
+        }
+        Ok(None)
     }
     
     /// 简化绝对值函数（非常量参数）
-    fn simplify_absolute_value_function(&self, args: &[Expression]) -> Result<Expression, ComputeError> {
+    fn simplify_absolute_value_function(&self, args: &[Expression]) -> Result<Option<Expression>, ComputeError> {
         if args.len() != 1 {
-            return Ok(Expression::function("abs", args.to_vec()));
+            return Ok(None);
         }
         
         match &args[0] {
             // abs(abs(x)) = abs(x)
             Expression::Function { name, args: inner_args } if name == "abs" && inner_args.len() == 1 => {
-                Ok(Expression::function("abs", inner_args.clone()))
+                Ok(Some(Expression::function("abs", inner_args.clone())))
             }
+            // 假设 x >= 0 时 abs(x) = x，x <= 0 时 abs(x) = -x
+            arg if !self.assumptions.is_empty() && self.assumptions.is_nonnegative(arg) => Ok(Some(arg.clone())),
+            arg if !self.assumptions.is_empty() && self.assumptions.is_nonpositive(arg) => Ok(Some(Expression::negate(arg.clone()))),
             // abs(-x) = abs(x)
             Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
-                Ok(Expression::function("abs", vec![operand.as_ref().clone()]))
+                Ok(Some(Expression::function("abs", vec![operand.as_ref().clone()])))
             }
-            _ => Ok(Expression::function("abs", args.to_vec())),
+            _ => Ok(None),
         }
     }
     
-    /// 检查表达式是否为零
+    /// `abs(arg)` 化简后的结果
+    fn absolute_value_function(&self, arg: &Expression) -> Result<Expression, ComputeError> {
+        Ok(self.simplify_absolute_value_function(std::slice::from_ref(arg))?
+            .unwrap_or_else(|| Expression::function("abs", vec![arg.clone()])))
+    }
+    
+        /// 检查表达式是否为零
     fn is_zero(&self, expr: &Expression) -> bool {
         match expr {
             Expression::Number(n) => n.is_zero(),
@@ -1028,6 +1857,16 @@ impl Simplifier {
         }
     }
     
+    /// 检查表达式是否为 1/2
+    fn is_one_half(&self, expr: &Expression) -> bool {
+        matches!(expr, Expression::Number(n) if *n == Number::rational(1, 2))
+    }
+    
+    /// 平方根的内部指数 1/2
+    fn one_half() -> Expression {
+        Expression::Number(Number::rational(1, 2))
+    }
+    
     /// 检查表达式是否为负一
     fn is_neg_one(&self, expr: &Expression) -> bool {
         match expr {
@@ -1077,19 +1916,8 @@ impl Simplifier {
             self.extract_coefficient_and_variable(left),
             self.extract_coefficient_and_variable(right)
         ) {
-            if var_a == var_b {
-                let new_coeff = Expression::add(coeff_a, coeff_b);
-                // 尝试简化系数（仅对常量表达式）
-                let simplified_coeff = if new_coeff.is_constant() {
-                    if let Ok(value) = self.evaluate_constant_expression(&new_coeff) {
-                        Expression::Number(value)
-                    } else {
-                        new_coeff
-                    }
-                } else {
-                    new_coeff
-                };
-                return Some(Expression::multiply(simplified_coeff, var_a));
+            if Self::same_term(&var_a, &var_b) {
+                return Some(self.scaled_term(Expression::add(coeff_a, coeff_b), var_a));
             }
         }
         None
@@ -1102,24 +1930,55 @@ impl Simplifier {
             self.extract_coefficient_and_variable(left),
             self.extract_coefficient_and_variable(right)
         ) {
-            if var_a == var_b {
-                let new_coeff = Expression::subtract(coeff_a, coeff_b);
-                // 尝试简化系数（仅对常量表达式）
-                let simplified_coeff = if new_coeff.is_constant() {
-                    if let Ok(value) = self.evaluate_constant_expression(&new_coeff) {
-                        Expression::Number(value)
-                    } else {
-                        new_coeff
-                    }
-                } else {
-                    new_coeff
-                };
-                return Some(Expression::multiply(simplified_coeff, var_a));
+            if Self::same_term(&var_a, &var_b) {
+                return Some(self.scaled_term(Expression::subtract(coeff_a, coeff_b), var_a));
             }
         }
         None
     }
     
+    /// 两项是否只差加法、乘法的交换律与结合律，如 `(a + b)^2` 与 `(b + a)^2`
+    fn same_term(a: &Expression, b: &Expression) -> bool {
+        a == b || (std::mem::discriminant(a) == std::mem::discriminant(b) && a.canonicalize() == b.canonicalize())
+    }
+    
+    /// 从乘积中去掉一个与 `factor` 相同的因子，返回其余因子之积；乘积中没有该因子时返回 None
+    fn remove_factor(product: &Expression, factor: &Expression) -> Option<Expression> {
+        let mut factors = Vec::new();
+        Self::collect_product_factors(product, &mut factors);
+        if factors.len() < 2 {
+            return None;
+        }
+        let index = factors.iter().position(|candidate| Self::same_term(candidate, factor))?;
+        factors.remove(index);
+        factors.into_iter().cloned().reduce(Expression::multiply)
+    }
+    
+    fn collect_product_factors<'a>(expr: &'a Expression, factors: &mut Vec<&'a Expression>) {
+        match expr {
+            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
+                Self::collect_product_factors(left, factors);
+                Self::collect_product_factors(right, factors);
+            }
+            _ => factors.push(expr),
+        }
+    }
+    
+    /// 合并后的系数乘以变量部分：常量系数先求值，系数为 0 时整项相消，为 1 时省去
+    fn scaled_term(&self, coeff: Expression, var: Expression) -> Expression {
+        let coeff = match coeff.is_constant().then(|| self.evaluate_constant_expression(&coeff)) {
+            Some(Ok(value)) => Expression::Number(value),
+            _ => coeff,
+        };
+        if self.is_zero(&coeff) {
+            Expression::Number(Number::zero())
+        } else if self.is_one(&coeff) {
+            var
+        } else {
+            Expression::multiply(coeff, var)
+        }
+    }
+    
     /// 提取表达式的系数和变量部分
     fn extract_coefficient_and_variable(&self, expr: &Expression) -> Option<(Expression, Expression)> {
         match expr {
@@ -1143,53 +2002,120 @@ impl Simplifier {
             Expression::Number(_) => {
                 Some((expr.clone(), Expression::Number(Number::one())))
             }
-            
+
+            // 取负：-(a*x) = (-a)*x
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+                let (coeff, var) = self.extract_coefficient_and_variable(operand)?;
+                let coeff = match coeff {
+                    Expression::Number(n) => Expression::Number(-n),
+                    other => Expression::negate(other),
+                };
+                Some((coeff, var))
+            }
+
             _ => None,
         }
     }
     
+    /// 将合并后的 x^1 化为 x，x^0 化为 1
+    fn collapse_trivial_power(&self, expr: Expression) -> Expression {
+        if let Expression::BinaryOp { op: BinaryOperator::Power, left, right } = &expr {
+            if self.is_one(right) {
+                return left.as_ref().clone();
+            }
+            if self.is_zero(right) && !self.is_zero(left) {
+                return Expression::Number(Number::one());
+            }
+        }
+        expr
+    }
+    
+    /// 将 x^(1/2) 就地还原为 sqrt(x)，返回是否有改变；追踪时记录为 `final.restore_sqrt`
+    fn restore_square_roots(&mut self, expr: &mut Expression) -> bool {
+        let before = self.tracer.is_some().then(|| expr.clone());
+        let start = Instant::now();
+        let changed = self.restore_square_roots_recursive(expr);
+        if let Some(before) = before {
+            self.record_rule("final.restore_sqrt", &before, expr, start.elapsed());
+        }
+        changed
+    }
+    
+    fn restore_square_roots_recursive(&self, expr: &mut Expression) -> bool {
+        let changed = match expr {
+            Expression::BinaryOp { left, right, .. } | Expression::Interval { start: left, end: right, .. } => {
+                let left_changed = self.restore_square_roots_recursive(left);
+                self.restore_square_roots_recursive(right) || left_changed
+            }
+            Expression::UnaryOp { operand, .. } => self.restore_square_roots_recursive(operand),
+            Expression::Function { args: elements, .. } | Expression::Vector(elements) | Expression::Set(elements) => {
+                self.restore_all(elements.iter_mut())
+            }
+            Expression::Matrix(rows) => self.restore_all(rows.iter_mut().flatten()),
+            Expression::Number(_) | Expression::Variable(_) | Expression::Constant(_) => false,
+        };
+        if !self.is_square_root_power(expr) {
+            return changed;
+        }
+        if let Expression::BinaryOp { left, .. } = std::mem::replace(expr, Expression::Number(Number::zero())) {
+            *expr = Expression::function("sqrt", vec![*left]);
+        }
+        true
+    }
+    
+    /// 逐个还原，每个元素都要访问
+    fn restore_all<'a>(&self, elements: impl Iterator<Item = &'a mut Expression>) -> bool {
+        let mut changed = false;
+        for elem in elements {
+            changed |= self.restore_square_roots_recursive(elem);
+        }
+        changed
+    }
+    
+    /// 是否为 x^(1/2)
+    fn is_square_root_power(&self, expr: &Expression) -> bool {
+        matches!(expr, Expression::BinaryOp { op: BinaryOperator::Power, right, .. } if self.is_one_half(right))
+    }
+    
     /// 合并乘法中的同底数幂
     fn combine_powers_multiply(&self, left: &Expression, right: &Expression) -> Option<Expression> {
         // x^a * x^b = x^(a+b)
         match (left, right) {
             (Expression::BinaryOp { op: BinaryOperator::Power, left: base1, right: exp1 },
-             Expression::BinaryOp { op: BinaryOperator::Power, left: base2, right: exp2 }) => {
-                if base1 == base2 {
-                    let new_exp = Expression::add(exp1.as_ref().clone(), exp2.as_ref().clone());
-                    // 尝试简化指数（仅对常量表达式）
-                    let simplified_exp = if new_exp.is_constant() {
-                        if let Ok(value) = self.evaluate_constant_expression(&new_exp) {
-                            Expression::Number(value)
-                        } else {
-                            new_exp
-                        }
+             Expression::BinaryOp { op: BinaryOperator::Power, left: base2, right: exp2 }) if base1 == base2 => {
+                let new_exp = Expression::add(exp1.as_ref().clone(), exp2.as_ref().clone());
+                // 尝试简化指数（仅对常量表达式）
+                let simplified_exp = if new_exp.is_constant() {
+                    if let Ok(value) = self.evaluate_constant_expression(&new_exp) {
+                        Expression::Number(value)
                     } else {
                         new_exp
-                    };
-                    return Some(Expression::power(base1.as_ref().clone(), simplified_exp));
-                }
+                    }
+                } else {
+                    new_exp
+                };
+                return Some(Expression::power(base1.as_ref().clone(), simplified_exp));
             }
             
-            // x * x^a = x^(1+a)
+            // x * x^a = x^(1+a)；数值底数留给常量折叠，避免 2*sqrt(2) 被合并成 2^(3/2)
             (var, Expression::BinaryOp { op: BinaryOperator::Power, left: base, right: exp }) |
-            (Expression::BinaryOp { op: BinaryOperator::Power, left: base, right: exp }, var) => {
-                if var == base.as_ref() {
-                    let new_exp = Expression::add(
-                        Expression::Number(Number::one()),
-                        exp.as_ref().clone()
-                    );
-                    // 尝试简化指数（仅对常量表达式）
-                    let simplified_exp = if new_exp.is_constant() {
-                        if let Ok(value) = self.evaluate_constant_expression(&new_exp) {
-                            Expression::Number(value)
-                        } else {
-                            new_exp
-                        }
+            (Expression::BinaryOp { op: BinaryOperator::Power, left: base, right: exp }, var)
+                if var == base.as_ref() && !matches!(var, Expression::Number(_)) => {
+                let new_exp = Expression::add(
+                    Expression::Number(Number::one()),
+                    exp.as_ref().clone()
+                );
+                // 尝试简化指数（仅对常量表达式）
+                let simplified_exp = if new_exp.is_constant() {
+                    if let Ok(value) = self.evaluate_constant_expression(&new_exp) {
+                        Expression::Number(value)
                     } else {
                         new_exp
-                    };
-                    return Some(Expression::power(var.clone(), simplified_exp));
-                }
+                    }
+                } else {
+                    new_exp
+                };
+                return Some(Expression::power(var.clone(), simplified_exp));
             }
             
             _ => {}
@@ -1197,68 +2123,159 @@ impl Simplifier {
         None
     }
     
+    /// 展平嵌套分式：`divide` 为真时展平 `left / right`，否则展平 `left * right`；
+    /// 除法视为乘以倒数，分子分母的数值因子合并为一个系数，相同的因子约去
+    fn flatten_nested_fraction(&self, left: &Expression, right: &Expression, divide: bool) -> Option<Expression> {
+        if !Self::contains_division(left) && !Self::contains_division(right) {
+            return None;
+        }
+        
+        let (mut numerator, mut denominator) = (Vec::new(), Vec::new());
+        Self::collect_fraction_factors(left, false, &mut numerator, &mut denominator);
+        Self::collect_fraction_factors(right, divide, &mut numerator, &mut denominator);
+        
+        let mut coefficient = Number::one();
+        numerator.retain(|factor| match factor {
+            Expression::Number(n) => {
+                coefficient = coefficient.clone() * n.clone();
+                false
+            }
+            _ => true,
+        });
+        let mut divisor = Number::one();
+        denominator.retain(|factor| match factor {
+            Expression::Number(n) => {
+                divisor = divisor.clone() * n.clone();
+                false
+            }
+            _ => true,
+        });
+        if divisor.is_zero() {
+            return None;
+        }
+        numerator.retain(|factor| match denominator.iter().position(|d| d == factor) {
+            Some(index) => {
+                denominator.remove(index);
+                false
+            }
+            None => true,
+        });
+        
+        let (numer, denom) = match coefficient / divisor {
+            Number::Rational(r) => (Number::Integer(r.numer().clone()), Number::Integer(r.denom().clone())),
+            other => (other, Number::one()),
+        };
+        let numerator = Self::product_with_coefficient(numer, numerator);
+        if denominator.is_empty() && denom.is_one() {
+            return Some(numerator);
+        }
+        Some(Expression::divide(numerator, Self::product_with_coefficient(denom, denominator)))
+    }
+    
+    /// 乘积链中是否有除法
+    fn contains_division(expr: &Expression) -> bool {
+        match expr {
+            Expression::BinaryOp { op: BinaryOperator::Divide, .. } => true,
+            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
+                Self::contains_division(left) || Self::contains_division(right)
+            }
+            _ => false,
+        }
+    }
+    
+    /// 把乘除链拆成分子因子与分母因子，`invert` 表示整体位于分母
+    fn collect_fraction_factors(expr: &Expression, invert: bool, numerator: &mut Vec<Expression>, denominator: &mut Vec<Expression>) {
+        match expr {
+            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
+                Self::collect_fraction_factors(left, invert, numerator, denominator);
+                Self::collect_fraction_factors(right, invert, numerator, denominator);
+            }
+            Expression::BinaryOp { op: BinaryOperator::Divide, left, right } => {
+                Self::collect_fraction_factors(left, invert, numerator, denominator);
+                Self::collect_fraction_factors(right, !invert, numerator, denominator);
+            }
+            // 负号作为 -1 因子并入系数
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+                let factors = if invert { &mut *denominator } else { &mut *numerator };
+                factors.push(Expression::Number(Number::neg_one()));
+                Self::collect_fraction_factors(operand, invert, numerator, denominator);
+            }
+            _ if invert => denominator.push(expr.clone()),
+            _ => numerator.push(expr.clone()),
+        }
+    }
+    
+    /// 系数乘以各因子之积，系数为 1 时省略、为 -1 时写成负号
+    fn product_with_coefficient(coefficient: Number, factors: Vec<Expression>) -> Expression {
+        if factors.is_empty() {
+            return Expression::Number(coefficient);
+        }
+        let negated = (-coefficient.clone()).is_one();
+        let leading = (!coefficient.is_one() && !negated).then_some(Expression::Number(coefficient));
+        let product = leading.into_iter().chain(factors).reduce(Expression::multiply).unwrap();
+        if negated {
+            Expression::negate(product)
+        } else {
+            product
+        }
+    }
+    
     /// 合并除法中的同底数幂
     fn combine_powers_divide(&self, left: &Expression, right: &Expression) -> Option<Expression> {
         // x^a / x^b = x^(a-b)
         match (left, right) {
             (Expression::BinaryOp { op: BinaryOperator::Power, left: base1, right: exp1 },
-             Expression::BinaryOp { op: BinaryOperator::Power, left: base2, right: exp2 }) => {
-                if base1 == base2 {
-                    let new_exp = Expression::subtract(exp1.as_ref().clone(), exp2.as_ref().clone());
-                    // 尝试简化指数（仅对常量表达式）
-                    let simplified_exp = if new_exp.is_constant() {
-                        if let Ok(value) = self.evaluate_constant_expression(&new_exp) {
-                            Expression::Number(value)
-                        } else {
-                            new_exp
-                        }
+             Expression::BinaryOp { op: BinaryOperator::Power, left: base2, right: exp2 }) if base1 == base2 => {
+                let new_exp = Expression::subtract(exp1.as_ref().clone(), exp2.as_ref().clone());
+                // 尝试简化指数（仅对常量表达式）
+                let simplified_exp = if new_exp.is_constant() {
+                    if let Ok(value) = self.evaluate_constant_expression(&new_exp) {
+                        Expression::Number(value)
                     } else {
                         new_exp
-                    };
-                    return Some(Expression::power(base1.as_ref().clone(), simplified_exp));
-                }
+                    }
+                } else {
+                    new_exp
+                };
+                return Some(Expression::power(base1.as_ref().clone(), simplified_exp));
             }
             
             // x / x^a = x^(1-a)
-            (var, Expression::BinaryOp { op: BinaryOperator::Power, left: base, right: exp }) => {
-                if var == base.as_ref() {
-                    let new_exp = Expression::subtract(
-                        Expression::Number(Number::one()),
-                        exp.as_ref().clone()
-                    );
-                    // 尝试简化指数（仅对常量表达式）
-                    let simplified_exp = if new_exp.is_constant() {
-                        if let Ok(value) = self.evaluate_constant_expression(&new_exp) {
-                            Expression::Number(value)
-                        } else {
-                            new_exp
-                        }
+            (var, Expression::BinaryOp { op: BinaryOperator::Power, left: base, right: exp }) if var == base.as_ref() && !matches!(var, Expression::Number(_)) => {
+                let new_exp = Expression::subtract(
+                    Expression::Number(Number::one()),
+                    exp.as_ref().clone()
+                );
+                // 尝试简化指数（仅对常量表达式）
+                let simplified_exp = if new_exp.is_constant() {
+                    if let Ok(value) = self.evaluate_constant_expression(&new_exp) {
+                        Expression::Number(value)
                     } else {
                         new_exp
-                    };
-                    return Some(Expression::power(var.clone(), simplified_exp));
-                }
+                    }
+                } else {
+                    new_exp
+                };
+                return Some(Expression::power(var.clone(), simplified_exp));
             }
             
             // x^a / x = x^(a-1)
-            (Expression::BinaryOp { op: BinaryOperator::Power, left: base, right: exp }, var) => {
-                if base.as_ref() == var {
-                    let new_exp = Expression::subtract(
-                        exp.as_ref().clone(),
-                        Expression::Number(Number::one())
-                    );
-                    // 尝试简化指数（仅对常量表达式）
-                    let simplified_exp = if new_exp.is_constant() {
-                        if let Ok(value) = self.evaluate_constant_expression(&new_exp) {
-                            Expression::Number(value)
-                        } else {
-                            new_exp
-                        }
+            (Expression::BinaryOp { op: BinaryOperator::Power, left: base, right: exp }, var) if base.as_ref() == var && !matches!(var, Expression::Number(_)) => {
+                let new_exp = Expression::subtract(
+                    exp.as_ref().clone(),
+                    Expression::Number(Number::one())
+                );
+                // 尝试简化指数（仅对常量表达式）
+                let simplified_exp = if new_exp.is_constant() {
+                    if let Ok(value) = self.evaluate_constant_expression(&new_exp) {
+                        Expression::Number(value)
                     } else {
                         new_exp
-                    };
-                    return Some(Expression::power(var.clone(), simplified_exp));
-                }
+                    }
+                } else {
+                    new_exp
+                };
+                return Some(Expression::power(var.clone(), simplified_exp));
             }
             
             _ => {}
@@ -1268,6 +2285,10 @@ impl Simplifier {
     
     /// 计算整数幂（仅对小指数）
     fn compute_integer_power(&self, base: &Number, exponent: &Number) -> Option<Number> {
+        // 符号常量的幂保持 π^2 这样的形式，不展开成乘积
+        if matches!(base, Number::Constant(_) | Number::Symbolic(_)) {
+            return None;
+        }
         if let Some(exp_int) = exponent.to_integer() {
             // 只计算小指数的幂，避免计算爆炸
             if exp_int >= BigInt::from(0) && exp_int <= BigInt::from(10) {
@@ -1392,7 +2413,7 @@ impl Simplifier {
         }
         
         // 简化结果
-        self.simplify_recursive(&result)
+        Ok(self.simplify_recursive(&result)?.into_owned())
     }
     
     /// 计算二项式系数 C(n, k)
@@ -1462,176 +2483,58 @@ impl Simplifier {
     // 矩阵和向量运算的简化方法
     
     /// 简化矩阵乘法
-    fn simplify_matrix_multiply(&self, left: &Expression, right: &Expression) -> Result<Expression, ComputeError> {
-        // 基本简化规则
-        match (left, right) {
-            // 零矩阵乘法：0 * A = 0
-            (Expression::Matrix(left_rows), _) if self.is_zero_matrix(left_rows) => {
-                Ok(left.clone())
-            }
-            (_, Expression::Matrix(right_rows)) if self.is_zero_matrix(right_rows) => {
-                Ok(right.clone())
-            }
-            
-            // 单位矩阵乘法：I * A = A, A * I = A
-            (Expression::Matrix(left_rows), _) if self.is_identity_matrix(left_rows) => {
-                Ok(right.clone())
-            }
-            (_, Expression::Matrix(right_rows)) if self.is_identity_matrix(right_rows) => {
-                Ok(left.clone())
-            }
-            
-            // 其他情况保持原样
-            _ => Ok(Expression::binary_op(BinaryOperator::MatrixMultiply, left.clone(), right.clone()))
-        }
+    fn simplify_matrix_multiply(&self, left: &Expression, right: &Expression) -> Result<Option<Expression>, ComputeError> {
+        Ok(matrix_rules::simplify_product(left, right))
     }
     
     /// 简化向量点积
-    fn simplify_dot_product(&self, left: &Expression, right: &Expression) -> Result<Expression, ComputeError> {
+    fn simplify_dot_product(&self, left: &Expression, right: &Expression) -> Result<Option<Expression>, ComputeError> {
         // 基本简化规则
         match (left, right) {
             // 零向量点积：0 · v = 0
             (Expression::Vector(left_elems), _) if self.is_zero_vector(left_elems) => {
-                Ok(Expression::number(Number::from(0)))
+                Ok(Some(Expression::number(Number::from(0))))
             }
             (_, Expression::Vector(right_elems)) if self.is_zero_vector(right_elems) => {
-                Ok(Expression::number(Number::from(0)))
+                Ok(Some(Expression::number(Number::from(0))))
             }
             
             // 其他情况保持原样
-            _ => Ok(Expression::binary_op(BinaryOperator::DotProduct, left.clone(), right.clone()))
+            _ => Ok(None)
         }
     }
     
     /// 简化向量叉积
-    fn simplify_cross_product(&self, left: &Expression, right: &Expression) -> Result<Expression, ComputeError> {
+    fn simplify_cross_product(&self, left: &Expression, right: &Expression) -> Result<Option<Expression>, ComputeError> {
         // 基本简化规则
         match (left, right) {
             // 零向量叉积：0 × v = 0
             (Expression::Vector(left_elems), _) if self.is_zero_vector(left_elems) => {
-                Ok(left.clone())
+                Ok(Some(left.clone()))
             }
             (_, Expression::Vector(right_elems)) if self.is_zero_vector(right_elems) => {
-                Ok(right.clone())
+                Ok(Some(right.clone()))
             }
             
             // 向量与自身叉积：v × v = 0
             _ if left == right => {
                 if let Expression::Vector(elems) = left {
                     let zero_vector = vec![Expression::number(Number::from(0)); elems.len()];
-                    Ok(Expression::Vector(zero_vector))
+                    Ok(Some(Expression::Vector(zero_vector)))
                 } else {
-                    Ok(Expression::binary_op(BinaryOperator::CrossProduct, left.clone(), right.clone()))
+                    Ok(None)
                 }
             }
             
             // 其他情况保持原样
-            _ => Ok(Expression::binary_op(BinaryOperator::CrossProduct, left.clone(), right.clone()))
-        }
-    }
-    
-    /// 简化矩阵转置
-    fn simplify_transpose(&self, operand: &Expression) -> Result<Expression, ComputeError> {
-        match operand {
-            // 转置的转置：(A^T)^T = A
-            Expression::UnaryOp { op: UnaryOperator::Transpose, operand: inner } => {
-                Ok(inner.as_ref().clone())
-            }
-            
-            // 其他情况保持原样
-            _ => Ok(Expression::unary_op(UnaryOperator::Transpose, operand.clone()))
-        }
-    }
-    
-    /// 简化矩阵行列式
-    fn simplify_determinant(&self, operand: &Expression) -> Result<Expression, ComputeError> {
-        match operand {
-            // 单位矩阵的行列式：det(I) = 1
-            Expression::Matrix(rows) if self.is_identity_matrix(rows) => {
-                Ok(Expression::number(Number::from(1)))
-            }
-            
-            // 零矩阵的行列式：det(0) = 0
-            Expression::Matrix(rows) if self.is_zero_matrix(rows) => {
-                Ok(Expression::number(Number::from(0)))
-            }
-            
-            // 其他情况保持原样
-            _ => Ok(Expression::unary_op(UnaryOperator::Determinant, operand.clone()))
-        }
-    }
-    
-    /// 简化矩阵逆
-    fn simplify_inverse(&self, operand: &Expression) -> Result<Expression, ComputeError> {
-        match operand {
-            // 单位矩阵的逆：I^(-1) = I
-            Expression::Matrix(rows) if self.is_identity_matrix(rows) => {
-                Ok(operand.clone())
-            }
-            
-            // 逆的逆：(A^(-1))^(-1) = A
-            Expression::UnaryOp { op: UnaryOperator::Inverse, operand: inner } => {
-                Ok(inner.as_ref().clone())
-            }
-            
-            // 其他情况保持原样
-            _ => Ok(Expression::unary_op(UnaryOperator::Inverse, operand.clone()))
-        }
-    }
-    
-    /// 简化矩阵的迹
-    fn simplify_trace(&self, operand: &Expression) -> Result<Expression, ComputeError> {
-        match operand {
-            // 零矩阵的迹：tr(0) = 0
-            Expression::Matrix(rows) if self.is_zero_matrix(rows) => {
-                Ok(Expression::number(Number::from(0)))
-            }
-            
-            // n×n单位矩阵的迹：tr(I_n) = n
-            Expression::Matrix(rows) if self.is_identity_matrix(rows) => {
-                Ok(Expression::number(Number::from(rows.len() as i64)))
-            }
-            
-            // 其他情况保持原样
-            _ => Ok(Expression::unary_op(UnaryOperator::Trace, operand.clone()))
+            _ => Ok(None)
         }
     }
     
-    /// 检查是否为零矩阵
-    fn is_zero_matrix(&self, rows: &[Vec<Expression>]) -> bool {
-        rows.iter().all(|row| {
-            row.iter().all(|elem| self.is_zero(elem))
-        })
-    }
-    
-    /// 检查是否为单位矩阵
-    fn is_identity_matrix(&self, rows: &[Vec<Expression>]) -> bool {
-        if rows.is_empty() {
-            return false;
-        }
-        
-        let n = rows.len();
-        if rows.iter().any(|row| row.len() != n) {
-            return false; // 不是方阵
-        }
-        
-        for i in 0..n {
-            for j in 0..n {
-                if i == j {
-                    // 对角线元素应该是1
-                    if !self.is_one(&rows[i][j]) {
-                        return false;
-                    }
-                } else {
-                    // 非对角线元素应该是0
-                    if !self.is_zero(&rows[i][j]) {
-                        return false;
-                    }
-                }
-            }
-        }
-        
-        true
+    /// 简化矩阵转置、行列式、逆与迹，规则见 [`matrix_rules`]
+    fn simplify_matrix_operation(&self, op: &UnaryOperator, operand: &Expression) -> Result<Option<Expression>, ComputeError> {
+        let expr = Expression::unary_op(op.clone(), operand.clone());
+        Ok(matrix_rules::simplify_operation(&expr))
     }
     
     /// 检查是否为零向量
@@ -1640,6 +2543,33 @@ impl Simplifier {
     }
 }
 
+/// 子表达式都借用原式时返回 `None`，否则全部取出（未改变的复制一份）用于重建父节点
+fn changed<const N: usize>(children: [Cow<'_, Expression>; N]) -> Option<[Expression; N]> {
+    children.iter().any(is_owned).then(|| children.map(Cow::into_owned))
+}
+
+/// 同 [`changed`]，用于个数不定的子表达式
+fn changed_all(children: Vec<Cow<'_, Expression>>) -> Option<Vec<Expression>> {
+    children.iter().any(is_owned).then(|| into_owned_all(children))
+}
+
+fn into_owned_all(children: Vec<Cow<'_, Expression>>) -> Vec<Expression> {
+    children.into_iter().map(Cow::into_owned).collect()
+}
+
+/// 用作 `Iterator::any` 的参数，因此接受 `&Cow`
+#[allow(clippy::ptr_arg)]
+fn is_owned(expr: &Cow<'_, Expression>) -> bool {
+    matches!(expr, Cow::Owned(_))
+}
+
+/// 中间结果交还节点池，借用的原式不动
+fn recycle_owned(expr: Cow<'_, Expression>) {
+    if let Cow::Owned(expr) = expr {
+        memory::recycle(expr);
+    }
+}
+
 impl Default for Simplifier {
     fn default() -> Self {
         Self::new()
@@ -286,6 +286,30 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_structural_cancellation() {
+        use crate::parser::Parser;
+        let parse = |input: &str| crate::parser::syntax::ExpressionParser::new().parse(input).unwrap();
+        let simplify = |input: &str| create_simplifier().simplify(&parse(input)).unwrap();
+        let (zero, one) = (Expression::Number(Number::zero()), Expression::Number(Number::one()));
+
+        // 结构相同的子树相减为 0，与书写顺序无关
+        for input in ["x - x", "x + (-x)", "-x + x", "sin(x) - sin(x)", "(a + b) - (b + a)", "x*y - y*x",
+                      "sin(x + y) - sin(y + x)", "2*exp(x) - exp(x)*2", "(x^2 + 1)^3 - (1 + x^2)^3"] {
+            assert_eq!(simplify(input), zero, "{}", input);
+        }
+        assert_eq!(simplify("x - x + y"), parse("y"));
+        assert_eq!(simplify("cos(x)^2 - cos(x)^2 + 1"), one);
+
+        // 相同的非零子树相除为 1（假设分母不为 0）
+        for input in ["x/x", "sin(x)/sin(x)", "(a + b)/(a + b)", "(a + b)/(b + a)", "(x^2 + 1)/(x^2 + 1)",
+                      "(a + b)^2/(a + b)^2"] {
+            assert_eq!(simplify(input), one, "{}", input);
+        }
+        assert_eq!(simplify("(a + b)*(a + b)/(a + b)"), simplify("a + b"));
+        assert_eq!(simplify("sin(x)*y/sin(x)"), parse("y"));
+    }
+
     #[test]
     fn test_power_combination() {
         let mut simplifier = create_simplifier();
@@ -464,4 +488,495 @@ mod tests {
         );
         assert_eq!(result, expected);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_sqrt_and_half_power_unified() {
+        let mut simplifier = create_simplifier();
+        let x = Expression::variable("x");
+        let half = Expression::divide(
+            Expression::Number(Number::integer(1)),
+            Expression::Number(Number::integer(2))
+        );
+
+        // sqrt(x) 与 x^(1/2) 化简到相同结果
+        let from_function = simplifier.simplify(&Expression::function("sqrt", vec![x.clone()])).unwrap();
+        let from_unary = simplifier.simplify(&Expression::sqrt(x.clone())).unwrap();
+        let from_power = simplifier.simplify(&Expression::power(x.clone(), half.clone())).unwrap();
+        assert_eq!(from_function, Expression::function("sqrt", vec![x.clone()]));
+        assert_eq!(from_unary, from_function);
+        assert_eq!(from_power, from_function);
+
+        // sqrt(x) * sqrt(x) = x
+        let sqrt_x = Expression::function("sqrt", vec![x.clone()]);
+        let expr = Expression::multiply(sqrt_x.clone(), sqrt_x.clone());
+        assert_eq!(simplifier.simplify(&expr).unwrap(), x);
+
+        // sqrt(x) * x^(1/2) = x
+        let expr = Expression::multiply(sqrt_x.clone(), Expression::power(x.clone(), half.clone()));
+        assert_eq!(simplifier.simplify(&expr).unwrap(), x);
+
+        // (sqrt(x))^2 = x
+        let expr = Expression::power(Expression::sqrt(x.clone()), Expression::Number(Number::integer(2)));
+        assert_eq!(simplifier.simplify(&expr).unwrap(), x);
+
+        // x / sqrt(x) = sqrt(x)
+        let expr = Expression::divide(x.clone(), sqrt_x.clone());
+        assert_eq!(simplifier.simplify(&expr).unwrap(), sqrt_x);
+    }
+
+    #[test]
+    fn test_sqrt_numeric_and_abs() {
+        let mut simplifier = create_simplifier();
+
+        // sqrt(9) 与 9^(1/2) 都得到 3
+        let nine = Expression::Number(Number::integer(9));
+        let expr = Expression::function("sqrt", vec![nine.clone()]);
+        assert_eq!(simplifier.simplify(&expr).unwrap(), Expression::Number(Number::integer(3)));
+        let expr = Expression::power(nine, Expression::Number(Number::rational(1, 2)));
+        assert_eq!(simplifier.simplify(&expr).unwrap(), Expression::Number(Number::integer(3)));
+
+        // sqrt(2) 保持根式
+        let sqrt2 = Expression::function("sqrt", vec![Expression::Number(Number::integer(2))]);
+        assert_eq!(simplifier.simplify(&sqrt2).unwrap(), sqrt2);
+
+        // sqrt(x^2) = |x|
+        let x = Expression::variable("x");
+        let expr = Expression::sqrt(Expression::power(x.clone(), Expression::Number(Number::integer(2))));
+        assert_eq!(simplifier.simplify(&expr).unwrap(), Expression::function("abs", vec![x]));
+    }
+
+    #[test]
+    fn test_cube_root_and_nth_root() {
+        let mut simplifier = create_simplifier();
+        let int = |value: i64| Expression::Number(Number::integer(value));
+        let x = Expression::variable("x");
+
+        // cbrt(27) = 3，cbrt(-8) = -2
+        let expr = Expression::function("cbrt", vec![int(27)]);
+        assert_eq!(simplifier.simplify(&expr).unwrap(), int(3));
+        let expr = Expression::function("cbrt", vec![int(-8)]);
+        assert_eq!(simplifier.simplify(&expr).unwrap(), int(-2));
+
+        // root(16, 4) = 2，root(8/27, 3) = 2/3
+        let expr = Expression::function("root", vec![int(16), int(4)]);
+        assert_eq!(simplifier.simplify(&expr).unwrap(), int(2));
+        let expr = Expression::function("root", vec![Expression::Number(Number::rational(8, 27)), int(3)]);
+        assert_eq!(simplifier.simplify(&expr).unwrap(), Expression::Number(Number::rational(2, 3)));
+
+        // 非完全立方数与符号参数保持符号形式
+        let expr = Expression::function("cbrt", vec![int(2)]);
+        assert_eq!(simplifier.simplify(&expr).unwrap(), expr);
+        let expr = Expression::function("root", vec![x.clone(), int(3)]);
+        assert_eq!(simplifier.simplify(&expr).unwrap(), expr);
+
+        // root(x^3, 3) = x，root(x^4, 4) = |x|
+        let expr = Expression::function("root", vec![Expression::power(x.clone(), int(3)), int(3)]);
+        assert_eq!(simplifier.simplify(&expr).unwrap(), x);
+        let expr = Expression::function("root", vec![Expression::power(x.clone(), int(4)), int(4)]);
+        assert_eq!(simplifier.simplify(&expr).unwrap(), Expression::function("abs", vec![x.clone()]));
+
+        // 关闭实数奇次根后，负数立方根保持符号形式
+        simplifier.set_real_odd_roots(false);
+        let expr = Expression::function("cbrt", vec![int(-8)]);
+        assert_eq!(simplifier.simplify(&expr).unwrap(), expr);
+    }
+
+    #[test]
+    fn test_rounding_functions() {
+        let mut simplifier = create_simplifier();
+        let int = |value: i64| Expression::Number(Number::integer(value));
+        let seven_halves = Expression::Number(Number::rational(7, 2));
+        let neg_seven_halves = Expression::Number(Number::rational(-7, 2));
+
+        let cases = [
+            ("floor", seven_halves.clone(), 3),
+            ("ceil", seven_halves.clone(), 4),
+            ("round", seven_halves.clone(), 4),
+            ("trunc", seven_halves.clone(), 3),
+            ("floor", neg_seven_halves.clone(), -4),
+            ("ceil", neg_seven_halves.clone(), -3),
+            ("round", neg_seven_halves.clone(), -4),
+            ("trunc", neg_seven_halves.clone(), -3),
+            ("round", Expression::Number(Number::rational(7, 3)), 2),
+            ("floor", Expression::Number(Number::Float(2.75)), 2),
+            ("floor", int(5), 5),
+        ];
+        for (name, arg, expected) in cases {
+            let expr = Expression::function(name, vec![arg]);
+            assert_eq!(simplifier.simplify(&expr).unwrap(), int(expected), "{}", name);
+        }
+
+        // 符号参数保持原样，嵌套取整只保留内层
+        let floor_x = Expression::function("floor", vec![Expression::variable("x")]);
+        assert_eq!(simplifier.simplify(&floor_x).unwrap(), floor_x);
+        let expr = Expression::function("ceil", vec![floor_x.clone()]);
+        assert_eq!(simplifier.simplify(&expr).unwrap(), floor_x);
+    }
+
+    #[test]
+    fn test_modulo_conventions() {
+        let mut simplifier = create_simplifier();
+        let int = |value: i64| Expression::Number(Number::integer(value));
+        let call = |name: &str, a: i64, b: i64| Expression::function(name, vec![int(a), int(b)]);
+
+        // 数学约定：余数非负
+        let expr = Expression::binary_op(BinaryOperator::Modulo, int(-7), int(3));
+        assert_eq!(simplifier.simplify(&expr).unwrap(), int(2));
+        assert_eq!(simplifier.simplify(&call("mod", -7, 3)).unwrap(), int(2));
+        assert_eq!(simplifier.simplify(&call("mod", 7, -3)).unwrap(), int(1));
+        assert_eq!(simplifier.simplify(&call("quotient", -7, 3)).unwrap(), int(-3));
+        assert_eq!(
+            simplifier.simplify(&call("divmod", -7, 3)).unwrap(),
+            Expression::Vector(vec![int(-3), int(2)])
+        );
+
+        // 截断约定：余数与被除数同号
+        assert_eq!(simplifier.simplify(&call("rem", -7, 3)).unwrap(), int(-1));
+        assert_eq!(simplifier.simplify(&call("rem", 7, -3)).unwrap(), int(1));
+
+        // 有理数同样适用，除数为零时保持原样
+        let expr = Expression::function("mod", vec![Expression::Number(Number::rational(7, 2)), int(2)]);
+        assert_eq!(simplifier.simplify(&expr).unwrap(), Expression::Number(Number::rational(3, 2)));
+        assert_eq!(simplifier.simplify(&call("mod", 5, 0)).unwrap(), call("mod", 5, 0));
+    }
+
+    #[test]
+    fn test_simplify_traced() {
+        let mut simplifier = create_simplifier();
+        let x = Expression::variable("x");
+        // (x + x) + 0 * y
+        let expr = Expression::add(
+            Expression::add(x.clone(), x.clone()),
+            Expression::multiply(Expression::Number(Number::zero()), Expression::variable("y")),
+        );
+
+        // 先普通化简一次，追踪时不能被缓存跳过
+        let expected = simplifier.simplify(&expr).unwrap();
+        let (result, events) = simplifier.simplify_traced(&expr).unwrap();
+        assert_eq!(result, expected);
+
+        let rules: Vec<(&str, Vec<usize>)> = events.iter().map(|e| (e.rule.as_str(), e.path.clone())).collect();
+        assert_eq!(rules, vec![
+            ("binary.add", vec![0]),
+            ("binary.multiply", vec![1]),
+            ("binary.add", vec![]),
+        ]);
+        assert_eq!(events[0].before, Expression::add(x.clone(), x.clone()));
+        assert_eq!(events[1].after, Expression::Number(Number::zero()));
+        assert!(events[1].matches_filter("binary") && events[1].matches_filter("binary.multiply"));
+        assert!(!events[1].matches_filter("binary.mul"));
+
+        // 追踪结束后恢复普通化简
+        assert!(!simplifier.is_tracing());
+    }
+
+    #[test]
+    fn test_in_place_simplification_keeps_unchanged_subtrees() {
+        use crate::core::CowExpression;
+        use std::borrow::Cow;
+        let mut simplifier = create_simplifier();
+        let x = Expression::variable("x");
+        let unchanged = Expression::add(
+            Expression::function("sin", vec![Expression::variable("y")]),
+            Expression::variable("z"),
+        );
+        let right_ptr = |expr: &Expression| match expr {
+            Expression::BinaryOp { right, .. } => right.as_ref() as *const Expression,
+            _ => panic!("应为二元运算"),
+        };
+
+        // 只有左边 x + 0 改变，右边子树的节点原样保留
+        let mut expr = Expression::multiply(
+            Expression::add(x.clone(), Expression::Number(Number::zero())),
+            unchanged.clone(),
+        );
+        let before = right_ptr(&expr);
+        assert!(simplifier.simplify_in_place(&mut expr).unwrap());
+        assert_eq!(expr, Expression::multiply(x.clone(), unchanged.clone()));
+        assert_eq!(right_ptr(&expr), before);
+
+        // 已是最简形式时不改变
+        assert!(!simplifier.simplify_in_place(&mut expr).unwrap());
+        assert_eq!(right_ptr(&expr), before);
+
+        // 整棵树都没有改变时借用原式，不做复制
+        assert!(matches!(simplifier.simplify_borrowed(&expr).unwrap(), Cow::Borrowed(e) if std::ptr::eq(e, &expr)));
+
+        // 结果不变时写时复制表达式继续与其他持有者共享
+        let shared = CowExpression::new(expr.clone());
+        let mut cow = shared.clone();
+        assert!(!simplifier.simplify_cow(&mut cow).unwrap());
+        assert_eq!(cow.ref_count(), 2);
+        assert!(std::ptr::eq(cow.as_ref(), shared.as_ref()));
+        assert!(!cow.is_modified());
+
+        // 有改变时才复制，另一个持有者仍是原表达式
+        let original = Expression::multiply(
+            Expression::multiply(x.clone(), Expression::Number(Number::one())),
+            unchanged.clone(),
+        );
+        let shared = CowExpression::new(original.clone());
+        let mut cow = shared.clone();
+        assert!(simplifier.simplify_cow(&mut cow).unwrap());
+        assert_eq!(cow.as_ref(), &Expression::multiply(x.clone(), unchanged.clone()));
+        assert_eq!(shared.as_ref(), &original);
+        assert_eq!((cow.ref_count(), shared.ref_count()), (1, 1));
+
+        // 独占时就地化简，未改变的子树保留原有节点
+        let mut cow = CowExpression::new(original);
+        let before = right_ptr(cow.as_ref());
+        assert!(simplifier.simplify_cow(&mut cow).unwrap());
+        assert_eq!(cow.as_ref(), &Expression::multiply(x, unchanged));
+        assert_eq!(right_ptr(cow.as_ref()), before);
+    }
+
+    #[test]
+    fn test_nested_fractions() {
+        let mut simplifier = create_simplifier();
+        let (x, y) = (Expression::variable("x"), Expression::variable("y"));
+        let (a, b, c, d) = (Expression::variable("a"), Expression::variable("b"), Expression::variable("c"), Expression::variable("d"));
+        let int = |n: i64| Expression::Number(Number::integer(n));
+
+        // (x/2)/(3/y) = x*y/6
+        let expr = Expression::divide(Expression::divide(x.clone(), int(2)), Expression::divide(int(3), y.clone()));
+        let expected = Expression::divide(Expression::multiply(x.clone(), y.clone()), int(6));
+        assert_eq!(simplifier.simplify(&expr).unwrap(), expected);
+
+        // a/(b/c) = a*c/b
+        let expr = Expression::divide(a.clone(), Expression::divide(b.clone(), c.clone()));
+        assert_eq!(simplifier.simplify(&expr).unwrap(), Expression::divide(Expression::multiply(a.clone(), c.clone()), b.clone()));
+
+        // (a/b)/c = a/(b*c)
+        let expr = Expression::divide(Expression::divide(a.clone(), b.clone()), c.clone());
+        assert_eq!(simplifier.simplify(&expr).unwrap(), Expression::divide(a.clone(), Expression::multiply(b.clone(), c.clone())));
+
+        // (a/b)/(c/d) = (a*d)/(b*c)
+        let expr = Expression::divide(Expression::divide(a.clone(), b.clone()), Expression::divide(c.clone(), d.clone()));
+        let expected = Expression::divide(Expression::multiply(a, d), Expression::multiply(b, c));
+        assert_eq!(simplifier.simplify(&expr).unwrap(), expected);
+
+        // 相同因子约去，数值系数约分：(6/x)/(3/x) = 2
+        let expr = Expression::divide(Expression::divide(int(6), x.clone()), Expression::divide(int(3), x.clone()));
+        assert_eq!(simplifier.simplify(&expr).unwrap(), int(2));
+
+        // 数乘分式恰好约去分母：2 * (x^2/2) = x^2，约不尽的 3 * (x^2/2) 保持不变
+        let square = Expression::power(x, int(2));
+        let expr = Expression::multiply(int(2), Expression::divide(square.clone(), int(2)));
+        assert_eq!(simplifier.simplify(&expr).unwrap(), square);
+        let expr = Expression::multiply(int(3), Expression::divide(square, int(2)));
+        assert_eq!(simplifier.simplify(&expr).unwrap(), expr);
+    }
+
+    #[test]
+    fn test_simplify_light() {
+        use crate::engine::ComputeError;
+        use crate::parser::Parser;
+        let parse = |input: &str| crate::parser::syntax::ExpressionParser::new().parse(input).unwrap();
+        let light = |input: &str| Simplifier::simplify_light(&parse(input), &|| false).unwrap();
+
+        assert_eq!(light("2*x^1*1 + 2*1 + 0"), parse("2*x + 2"));
+        assert_eq!(light("x*3 + 2*(2*x)"), parse("3*x + 4*x"));
+        assert_eq!(light("(x + 0)^0 - 0"), parse("1"));
+        assert_eq!(light("-(-(2^3/4))"), Simplifier::new().simplify(&parse("2")).unwrap());
+        // 不展开、不合并同类项
+        assert_eq!(light("(x + 1)^2 + x + x"), parse("(x + 1)^2 + x + x"));
+
+        // 中断时报超时
+        assert!(matches!(Simplifier::simplify_light(&parse("x + 0"), &|| true), Err(ComputeError::Timeout)));
+    }
+
+    #[test]
+    fn test_simplify_options() {
+        use crate::engine::simplify::SimplifyOptions;
+        use crate::parser::Parser;
+        let parse = |input: &str| crate::parser::syntax::ExpressionParser::new().parse(input).unwrap();
+        let simplify = |options: SimplifyOptions, input: &str| Simplifier::with_options(options).simplify(&parse(input)).unwrap();
+
+        // 默认全开，三角规则合并 sin² + cos²，也能与前面的项配对
+        assert_eq!(SimplifyOptions::default(), SimplifyOptions::all());
+        assert_eq!(simplify(SimplifyOptions::all(), "sin(x)^2 + cos(x)^2"), parse("1"));
+        assert_eq!(simplify(SimplifyOptions::all(), "cos(2*y)^2 + sin(2*y)^2"), parse("1"));
+        assert_eq!(simplify(SimplifyOptions::all(), "x + sin(t)^2 + cos(t)^2"), simplify(SimplifyOptions::all(), "x + 1"));
+        assert_eq!(simplify(SimplifyOptions::all(), "sin(x)^2 + cos(y)^2"), parse("sin(x)^2 + cos(y)^2"));
+
+        // 关闭三角规则时保持原样，代数规则照常生效
+        let algebraic = SimplifyOptions::algebraic();
+        assert_eq!(simplify(algebraic, "sin(x)^2 + cos(x)^2"), parse("sin(x)^2 + cos(x)^2"));
+        assert_eq!(simplify(algebraic, "2*x + 3*x"), parse("5*x"));
+        assert_eq!(simplify(algebraic, "ln(exp(x))"), parse("ln(exp(x))"));
+        assert_eq!(simplify(SimplifyOptions::all(), "ln(exp(x))"), parse("x"));
+
+        // 只去恒等运算：不合并同类项，不合并同底数幂
+        let minimal = SimplifyOptions::minimal();
+        assert_eq!(simplify(minimal, "x*1 + 0"), parse("x"));
+        assert_eq!(simplify(minimal, "x + x"), parse("x + x"));
+        assert_eq!(simplify(minimal, "x^2 * x^3"), parse("x^2 * x^3"));
+        assert_eq!(simplify(SimplifyOptions::all(), "x^2 * x^3"), parse("x^5"));
+
+        // 全部关闭时只折叠常量、按交换律排序
+        assert_eq!(simplify(SimplifyOptions::none(), "x*1 + 2*3"), parse("1*x + 6"));
+
+        // 切换规则集后不沿用之前的缓存结果
+        let mut simplifier = Simplifier::new();
+        assert_eq!(simplifier.simplify(&parse("x + x")).unwrap(), parse("2*x"));
+        simplifier.set_options(minimal);
+        assert_eq!(simplifier.simplify(&parse("x + x")).unwrap(), parse("x + x"));
+    }
+
+    #[test]
+    fn test_assumptions() {
+        use crate::engine::assumptions::{Assumptions, Property};
+        use crate::parser::Parser;
+        let parse = |input: &str| crate::parser::syntax::ExpressionParser::new().parse(input).unwrap();
+
+        let mut simplifier = create_simplifier();
+        assert_eq!(simplifier.simplify(&parse("sqrt(x^2)")).unwrap(), parse("abs(x)"));
+        assert_eq!(simplifier.simplify(&parse("ln(e^x)")).unwrap(), parse("ln(e^x)"));
+
+        let mut assumptions = Assumptions::new();
+        assumptions.assume("x", Property::Positive).unwrap();
+        assumptions.assume("y", Property::Negative).unwrap();
+        simplifier.set_assumptions(assumptions);
+        assert_eq!(simplifier.simplify(&parse("sqrt(x^2)")).unwrap(), parse("x"));
+        assert_eq!(simplifier.simplify(&parse("(x^2)^(1/2)")).unwrap(), parse("x"));
+        assert_eq!(simplifier.simplify(&parse("root(x^4, 4)")).unwrap(), parse("x"));
+        assert_eq!(simplifier.simplify(&parse("abs(y)")).unwrap(), parse("-y"));
+        assert_eq!(simplifier.simplify(&parse("ln(e^x)")).unwrap(), parse("x"));
+        // 没有假设的变量保持原样
+        assert_eq!(simplifier.simplify(&parse("sqrt(z^2)")).unwrap(), parse("abs(z)"));
+    }
+
+    #[test]
+    fn test_indeterminate_forms() {
+        use crate::core::indeterminate::{IndeterminateForm, IndeterminateSemantics, Magnitude};
+        use crate::engine::compute::BasicComputeEngine;
+        use crate::engine::ComputeEngine;
+        use crate::engine::simplify::SimplifyOptions;
+        use crate::parser::Parser;
+        use std::collections::HashMap;
+        let parse = |input: &str| crate::parser::syntax::ExpressionParser::new().parse(input).unwrap();
+        let undefined = Expression::Constant(MathConstant::Undefined);
+
+        // (输入, 对应的未定式)；None 表示由 undefined 传播而来
+        let cases: &[(&str, Option<IndeterminateForm>)] = &[
+            ("0^0", Some(IndeterminateForm::ZeroPowerZero)),
+            ("0/0", Some(IndeterminateForm::ZeroOverZero)),
+            ("inf - inf", Some(IndeterminateForm::InfinityMinusInfinity)),
+            ("-inf - (-inf)", Some(IndeterminateForm::InfinityMinusInfinity)),
+            ("inf + (-inf)", Some(IndeterminateForm::InfinityMinusInfinity)),
+            ("-inf + inf", Some(IndeterminateForm::InfinityMinusInfinity)),
+            ("0 * inf", Some(IndeterminateForm::ZeroTimesInfinity)),
+            ("inf * 0", Some(IndeterminateForm::ZeroTimesInfinity)),
+            ("0 * (-inf)", Some(IndeterminateForm::ZeroTimesInfinity)),
+            ("inf / inf", Some(IndeterminateForm::InfinityOverInfinity)),
+            ("-inf / inf", Some(IndeterminateForm::InfinityOverInfinity)),
+            ("inf / (-inf)", Some(IndeterminateForm::InfinityOverInfinity)),
+            ("1^inf", Some(IndeterminateForm::OnePowerInfinity)),
+            ("1^(-inf)", Some(IndeterminateForm::OnePowerInfinity)),
+            ("undefined + 1", None),
+            ("x * undefined", None),
+            ("undefined^0", None),
+            ("-undefined", None),
+            ("sin(undefined)", None),
+        ];
+        for (input, form) in cases {
+            let expr = parse(input);
+            if let (Some(form), Expression::BinaryOp { op, left, right }) = (*form, &expr) {
+                let classified = IndeterminateForm::classify(op, Magnitude::of_expression(left), Magnitude::of_expression(right));
+                assert_eq!(classified, Some(form), "{}", input);
+            }
+            assert_eq!(create_simplifier().simplify(&expr).unwrap(), undefined, "simplify {}", input);
+            if !expr.get_variables().is_empty() {
+                continue;
+            }
+            assert_eq!(expr.evaluate_exact(), Ok(Number::Constant(MathConstant::Undefined)), "evaluate_exact {}", input);
+            assert_eq!(BasicComputeEngine::new().evaluate(&expr, &HashMap::new()).unwrap(),
+                Number::Constant(MathConstant::Undefined), "evaluate {}", input);
+        }
+
+        // 嵌套的无穷先折叠，外层仍能识别未定式
+        let mut simplifier = create_simplifier();
+        assert_eq!(simplifier.simplify(&parse("(inf + 1) - inf")).unwrap(), undefined);
+        assert_eq!(simplifier.simplify(&parse("(2 * inf) * 0")).unwrap(), undefined);
+        assert_eq!(simplifier.simplify(&parse("inf - 1")).unwrap(), Expression::Constant(MathConstant::PositiveInfinity));
+        assert_eq!(simplifier.simplify(&parse("1 / inf")).unwrap(), Expression::Number(Number::zero()));
+        // 不是未定式的组合保持原有结果
+        assert_eq!(simplifier.simplify(&parse("inf + inf")).unwrap(), Expression::Constant(MathConstant::PositiveInfinity));
+        assert_eq!(simplifier.simplify(&parse("x - x")).unwrap(), Expression::Number(Number::zero()));
+        assert_eq!(simplifier.simplify(&parse("x^0")).unwrap(), Expression::Number(Number::one()));
+
+        // 0^0 可约定为 1，其余未定式不受影响
+        simplifier.set_options(SimplifyOptions { zero_power_zero_is_one: true, ..SimplifyOptions::all() });
+        assert_eq!(simplifier.simplify(&parse("0^0")).unwrap(), Expression::Number(Number::one()));
+        assert_eq!(simplifier.simplify(&parse("0/0")).unwrap(), undefined);
+        assert_eq!(IndeterminateSemantics::new(true).value(IndeterminateForm::ZeroPowerZero), Number::one());
+        let engine = BasicComputeEngine::new();
+        engine.set_simplify_options(SimplifyOptions { zero_power_zero_is_one: true, ..SimplifyOptions::all() });
+        let vars = HashMap::from([("x".to_string(), Number::zero())]);
+        assert_eq!(engine.evaluate(&parse("x^x"), &vars).unwrap(), Number::one());
+    }
+
+    #[test]
+    fn test_infinity_arithmetic() {
+        use crate::engine::compute::BasicComputeEngine;
+        use crate::engine::ComputeEngine;
+        use crate::engine::simplify::SimplifyOptions;
+        use crate::parser::Parser;
+        use std::collections::HashMap;
+        let parse = |input: &str| crate::parser::syntax::ExpressionParser::new().parse(input).unwrap();
+        let positive = Number::Constant(MathConstant::PositiveInfinity);
+        let negative = Number::Constant(MathConstant::NegativeInfinity);
+
+        // 化简、精确求值、引擎求值与数值计算给出一致的结果
+        let cases: &[(&str, Number, f64)] = &[
+            ("inf + 5", positive.clone(), f64::INFINITY),
+            ("5 - inf", negative.clone(), f64::NEG_INFINITY),
+            ("-inf - 3", negative.clone(), f64::NEG_INFINITY),
+            ("2 * inf", positive.clone(), f64::INFINITY),
+            ("inf * (-3)", negative.clone(), f64::NEG_INFINITY),
+            ("-inf * (-1/2)", positive.clone(), f64::INFINITY),
+            ("inf / 4", positive.clone(), f64::INFINITY),
+            ("-inf / 4", negative.clone(), f64::NEG_INFINITY),
+            ("5 / inf", Number::zero(), 0.0),
+            ("pi / (-inf)", Number::zero(), 0.0),
+            ("inf^2", positive.clone(), f64::INFINITY),
+            ("inf^(-1)", Number::zero(), 0.0),
+            ("inf > 100", Number::one(), 1.0),
+            ("-inf < -100", Number::one(), 1.0),
+            ("inf <= 3", Number::zero(), 0.0),
+            ("inf == inf", Number::one(), 1.0),
+            ("-inf != inf", Number::one(), 1.0),
+        ];
+        let engine = BasicComputeEngine::new();
+        let vars = HashMap::new();
+        for (input, expected, float) in cases {
+            let expr = parse(input);
+            let symbolic = match expected {
+                Number::Constant(c) => Expression::Constant(c.clone()),
+                n => Expression::Number(n.clone()),
+            };
+            assert_eq!(create_simplifier().simplify(&expr).unwrap(), symbolic, "simplify {}", input);
+            assert_eq!(expr.evaluate_exact().as_ref(), Ok(expected), "evaluate_exact {}", input);
+            assert_eq!(&engine.evaluate(&expr, &vars).unwrap(), expected, "evaluate {}", input);
+            assert_eq!(engine.numerical_evaluate(&expr, &HashMap::new()).unwrap(), *float, "numerical_evaluate {}", input);
+        }
+
+        // oo 与 ∞ 均为正无穷
+        for input in ["oo", "inf", "∞"] {
+            assert_eq!(parse(input), Expression::Constant(MathConstant::PositiveInfinity), "{}", input);
+        }
+
+        // 默认除以零报错（化简保留原式），开启后得到带符号的无穷
+        assert!(engine.evaluate(&parse("1/0"), &vars).is_err());
+        assert_eq!(create_simplifier().simplify(&parse("1/0")).unwrap(), parse("1/0"));
+        let options = SimplifyOptions { division_by_zero_is_infinite: true, ..SimplifyOptions::all() };
+        let mut simplifier = create_simplifier();
+        simplifier.set_options(options.clone());
+        engine.set_simplify_options(options);
+        assert_eq!(simplifier.simplify(&parse("1/0")).unwrap(), Expression::Constant(MathConstant::PositiveInfinity));
+        assert_eq!(simplifier.simplify(&parse("-2/0")).unwrap(), Expression::Constant(MathConstant::NegativeInfinity));
+        assert_eq!(simplifier.simplify(&parse("0/0")).unwrap(), Expression::Constant(MathConstant::Undefined));
+        assert_eq!(engine.evaluate(&parse("-2/0"), &vars).unwrap(), negative);
+        assert_eq!(engine.numerical_evaluate(&parse("3/0"), &HashMap::new()).unwrap(), f64::INFINITY);
+    }
+}
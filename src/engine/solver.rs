@@ -0,0 +1,547 @@
+//! # 方程求解
+//!
+//! 实现单变量多项式方程的求解。先做无平方分解得到各根的重数，
+//! 再依次尝试有理根、二次求根公式，最后退化为数值求根。
+//! 每个根都会代回原方程进行验证。
+
+use num_bigint::BigInt;
+use num_complex::Complex64;
+use num_rational::BigRational;
+use num_traits::{One, Signed, ToPrimitive, Zero};
+use crate::core::{Expression, Number, BinaryOperator, MathConstant};
+use super::ComputeError;
+use super::polynomial::PolynomialEngine;
+use super::simplify::Simplifier;
+use crate::tr;
+
+/// 数值验证时允许的相对误差
+const VERIFY_TOLERANCE: f64 = 1e-9;
+
+/// 有理根搜索时允许分解的最大整数（避免大数试除过慢）
+const MAX_TRIAL_DIVISION: u64 = 1_000_000_000_000;
+
+/// 解的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SolutionKind {
+    /// 精确解（有理数或根式）
+    Exact,
+    /// 数值近似解
+    Numeric,
+}
+
+/// 方程的一个解
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    /// 根表达式
+    pub root: Expression,
+    /// 根的重数
+    pub multiplicity: usize,
+    /// 解的类型
+    pub kind: SolutionKind,
+    /// 将根代回原方程后化简得到的残差
+    pub residual: Expression,
+    /// 是否通过验证（残差化简为 0，或数值上足够接近 0）
+    pub verified: bool,
+}
+
+impl Solution {
+    /// 是否为精确解
+    pub fn is_exact(&self) -> bool {
+        self.kind == SolutionKind::Exact
+    }
+
+    /// 是否为未通过验证的候选解
+    pub fn is_candidate(&self) -> bool {
+        !self.verified
+    }
+}
+
+/// 稠密表示的单变量有理系数多项式，`coeffs[i]` 为 x^i 的系数
+pub(super) type DensePoly = Vec<BigRational>;
+
+/// 求解过程中得到的根（尚未验证）
+struct RawRoot {
+    root: Expression,
+    approx: Complex64,
+    kind: SolutionKind,
+}
+
+/// 方程求解引擎
+pub struct SolverEngine {
+    polynomial_engine: PolynomialEngine,
+}
+
+impl SolverEngine {
+    /// 创建新的求解引擎
+    pub fn new() -> Self {
+        Self {
+            polynomial_engine: PolynomialEngine::new(),
+        }
+    }
+
+    /// 求解单变量方程，返回根的重数与验证信息
+    ///
+    /// 方程可以写成 `lhs = rhs`，也可以直接给出表达式（视为 `expr = 0`）。
+    pub fn solve_detailed(&self, equation: &Expression, var: &str) -> Result<Vec<Solution>, ComputeError> {
+        let expr = Self::to_zero_form(equation);
+        let (coeffs, raw_roots) = self.raw_roots(&expr, var)?;
+
+        let mut simplifier = Simplifier::new();
+        let scale = coeffs.iter()
+            .map(|c| c.to_f64().unwrap_or(0.0).abs())
+            .fold(1.0, f64::max);
+
+        let solutions = raw_roots.into_iter().map(|(raw, multiplicity)| {
+            let residual = self.residual(&mut simplifier, &expr, var, &raw.root);
+            let exactly_zero = matches!(&residual, Expression::Number(n) if n.is_zero());
+            let verified = exactly_zero
+                || Self::eval_dense_complex(&coeffs, raw.approx).norm() <= VERIFY_TOLERANCE * scale;
+            Solution {
+                root: raw.root,
+                multiplicity,
+                kind: raw.kind,
+                residual,
+                verified,
+            }
+        }).collect();
+
+        Ok(solutions)
+    }
+
+    /// 求方程的全部实根（每个根出现一次），同时给出数值近似，按从小到大排列
+    pub fn real_roots(&self, equation: &Expression, var: &str) -> Result<Vec<(Expression, f64)>, ComputeError> {
+        let expr = Self::to_zero_form(equation);
+        let (_, raw_roots) = self.raw_roots(&expr, var)?;
+        Ok(raw_roots.into_iter()
+            .filter(|(raw, _)| raw.approx.im.abs() < VERIFY_TOLERANCE)
+            .map(|(raw, _)| (raw.root, raw.approx.re))
+            .collect())
+    }
+
+    /// 求出 `expr = 0` 的全部根及重数（未验证），实根按大小排在前面
+    fn raw_roots(&self, expr: &Expression, var: &str) -> Result<(DensePoly, Vec<(RawRoot, usize)>), ComputeError> {
+        let coeffs = self.extract_coefficients(expr, var)?;
+
+        if coeffs.len() <= 1 {
+            return if coeffs.first().is_none_or(|c| c.is_zero()) {
+                Err(ComputeError::domain_error(tr!("solver.identity", variable = var)))
+            } else {
+                Ok((coeffs, Vec::new()))
+            };
+        }
+
+        let mut raw_roots: Vec<(RawRoot, usize)> = Vec::new();
+        for (factor, multiplicity) in Self::square_free_decomposition(&coeffs) {
+            for root in self.solve_square_free(factor) {
+                raw_roots.push((root, multiplicity));
+            }
+        }
+
+        // 实根按大小排在前面，复根排在后面
+        raw_roots.sort_by(|(a, _), (b, _)| {
+            let a_real = a.approx.im.abs() < VERIFY_TOLERANCE;
+            let b_real = b.approx.im.abs() < VERIFY_TOLERANCE;
+            b_real.cmp(&a_real)
+                .then(a.approx.re.partial_cmp(&b.approx.re).unwrap_or(std::cmp::Ordering::Equal))
+                .then(a.approx.im.partial_cmp(&b.approx.im).unwrap_or(std::cmp::Ordering::Equal))
+        });
+
+        Ok((coeffs, raw_roots))
+    }
+
+    /// 求解单变量方程，只返回根（每个根出现一次）
+    pub fn solve(&self, equation: &Expression, var: &str) -> Result<Vec<Expression>, ComputeError> {
+        Ok(self.solve_detailed(equation, var)?
+            .into_iter()
+            .map(|solution| solution.root)
+            .collect())
+    }
+
+    /// 将 `lhs = rhs` 转换为 `lhs - rhs`
+    fn to_zero_form(equation: &Expression) -> Expression {
+        match equation {
+            Expression::BinaryOp { op: BinaryOperator::Equal, left, right } => {
+                Expression::subtract(left.as_ref().clone(), right.as_ref().clone())
+            }
+            _ => equation.clone(),
+        }
+    }
+
+    /// 提取关于 var 的稠密系数表
+    pub(super) fn extract_coefficients(&self, expr: &Expression, var: &str) -> Result<DensePoly, ComputeError> {
+        let poly = self.polynomial_engine.polynomial_in(expr, var, "solve")?;
+
+        let degree = poly.degree_of(var).max(0) as usize;
+        let mut coeffs = vec![BigRational::zero(); degree + 1];
+
+        for term in &poly.terms {
+            if term.variables.keys().any(|name| name != var) {
+                return Err(ComputeError::unsupported_operation(
+                    tr!("solver.univariate", variable = var)
+                ));
+            }
+            let coefficient = term.coefficient.to_rational().ok_or_else(|| {
+                ComputeError::unsupported_operation(tr!("solver.rational_coefficients"))
+            })?;
+            let power = term.degree_of(var).max(0) as usize;
+            coeffs[power] = &coeffs[power] + coefficient;
+        }
+
+        Ok(Self::trim(coeffs))
+    }
+
+    /// 无平方分解（Yun 算法），返回 (因子, 重数) 列表
+    pub(super) fn square_free_decomposition(f: &DensePoly) -> Vec<(DensePoly, usize)> {
+        let mut result = Vec::new();
+        let f_prime = Self::derivative(f);
+        let a = Self::gcd(f, &f_prime);
+        let mut b = Self::div_exact(f, &a);
+        let c = Self::div_exact(&f_prime, &a);
+        let mut d = Self::sub(&c, &Self::derivative(&b));
+        let mut multiplicity = 1;
+
+        while Self::degree(&b) > 0 {
+            let a = Self::gcd(&b, &d);
+            if Self::degree(&a) > 0 {
+                result.push((a.clone(), multiplicity));
+            }
+            let next_b = Self::div_exact(&b, &a);
+            let c = Self::div_exact(&d, &a);
+            d = Self::sub(&c, &Self::derivative(&next_b));
+            b = next_b;
+            multiplicity += 1;
+        }
+
+        result
+    }
+
+    /// 求解无平方因子多项式的全部根
+    fn solve_square_free(&self, mut poly: DensePoly) -> Vec<RawRoot> {
+        let mut roots = Vec::new();
+
+        // 零根
+        if poly.first().is_some_and(|c| c.is_zero()) {
+            poly.remove(0);
+            roots.push(Self::rational_root(BigRational::zero()));
+        }
+
+        // 有理根
+        for candidate in Self::rational_root_candidates(&poly) {
+            if Self::degree(&poly) < 1 {
+                break;
+            }
+            if Self::eval_dense(&poly, &candidate).is_zero() {
+                poly = Self::div_exact(&poly, &vec![-candidate.clone(), BigRational::one()]);
+                roots.push(Self::rational_root(candidate));
+            }
+        }
+
+        match Self::degree(&poly) {
+            0 => {}
+            1 => roots.push(Self::rational_root(-&poly[0] / &poly[1])),
+            2 => roots.extend(Self::quadratic_roots(&poly[2], &poly[1], &poly[0])),
+            _ => roots.extend(Self::numeric_roots(&poly)),
+        }
+
+        roots
+    }
+
+    /// 有理根定理给出的候选根 ±p/q
+    pub(super) fn rational_root_candidates(poly: &DensePoly) -> Vec<BigRational> {
+        if Self::degree(poly) < 1 {
+            return Vec::new();
+        }
+
+        // 通分为整系数
+        let denom_lcm = poly.iter().fold(BigInt::one(), |acc, c| {
+            use num_integer::Integer;
+            acc.lcm(c.denom())
+        });
+        let constant = (&poly[0] * BigRational::from(denom_lcm.clone())).to_integer().abs();
+        let leading = (poly.last().unwrap() * BigRational::from(denom_lcm)).to_integer().abs();
+
+        let (Some(ps), Some(qs)) = (Self::divisors(&constant), Self::divisors(&leading)) else {
+            return Vec::new();
+        };
+
+        let mut candidates: Vec<BigRational> = Vec::new();
+        for p in &ps {
+            for q in &qs {
+                let r = BigRational::new(BigInt::from(*p), BigInt::from(*q));
+                for candidate in [r.clone(), -r] {
+                    if !candidates.contains(&candidate) {
+                        candidates.push(candidate);
+                    }
+                }
+            }
+        }
+        candidates
+    }
+
+    /// 正整数的全部正因子；数值过大时返回 None
+    fn divisors(n: &BigInt) -> Option<Vec<u64>> {
+        let n = n.to_u64().filter(|n| *n > 0 && *n <= MAX_TRIAL_DIVISION)?;
+        let mut small = Vec::new();
+        let mut large = Vec::new();
+        let mut i = 1u64;
+        while i * i <= n {
+            if n % i == 0 {
+                small.push(i);
+                if i != n / i {
+                    large.push(n / i);
+                }
+            }
+            i += 1;
+        }
+        small.extend(large.into_iter().rev());
+        Some(small)
+    }
+
+    /// 二次方程 ax² + bx + c = 0 的根式解
+    fn quadratic_roots(a: &BigRational, b: &BigRational, c: &BigRational) -> Vec<RawRoot> {
+        let two_a = a * BigRational::from(BigInt::from(2));
+        let center = -b / &two_a;
+        let discriminant = b * b - BigRational::from(BigInt::from(4)) * a * c;
+
+        // sqrt(n/d) = sqrt(n*d)/d，再提出 n*d 中的平方因子
+        let radicand = (discriminant.numer() * discriminant.denom()).abs();
+        let (outside, inside) = Self::extract_square_factor(&radicand);
+        let half_width = BigRational::new(outside, discriminant.denom().clone()) / two_a.abs();
+        let imaginary = discriminant.is_negative();
+
+        if inside.is_one() && !imaginary {
+            return vec![
+                Self::rational_root(&center + &half_width),
+                Self::rational_root(&center - &half_width),
+            ];
+        }
+
+        let center_f = center.to_f64().unwrap_or(0.0);
+        let width_f = half_width.to_f64().unwrap_or(0.0) * inside.to_f64().unwrap_or(0.0).sqrt();
+
+        let radical = {
+            let sqrt = if inside.is_one() {
+                None
+            } else {
+                Some(Expression::sqrt(Expression::Number(Number::Integer(inside))))
+            };
+            match (sqrt, imaginary) {
+                (Some(s), true) => Expression::multiply(s, Expression::Constant(MathConstant::I)),
+                (Some(s), false) => s,
+                (None, true) => Expression::Constant(MathConstant::I),
+                (None, false) => Expression::Number(Number::one()),
+            }
+        };
+        let offset = if half_width.is_one() {
+            radical
+        } else {
+            Expression::multiply(Expression::Number(Self::rational_to_number(half_width)), radical)
+        };
+
+        let build = |negative: bool| {
+            if center.is_zero() {
+                if negative { Expression::negate(offset.clone()) } else { offset.clone() }
+            } else {
+                let center_expr = Expression::Number(Self::rational_to_number(center.clone()));
+                if negative {
+                    Expression::subtract(center_expr, offset.clone())
+                } else {
+                    Expression::add(center_expr, offset.clone())
+                }
+            }
+        };
+
+        [false, true].into_iter().map(|negative| {
+            let sign = if negative { -1.0 } else { 1.0 };
+            let approx = if imaginary {
+                Complex64::new(center_f, sign * width_f)
+            } else {
+                Complex64::new(center_f + sign * width_f, 0.0)
+            };
+            RawRoot { root: build(negative), approx, kind: SolutionKind::Exact }
+        }).collect()
+    }
+
+    /// 将 n 写成 s² * t（t 无平方因子），返回 (s, t)
+    pub(super) fn extract_square_factor(n: &BigInt) -> (BigInt, BigInt) {
+        let Some(mut rest) = n.to_u64().filter(|n| *n <= MAX_TRIAL_DIVISION) else {
+            return (BigInt::one(), n.clone());
+        };
+        let mut outside = 1u64;
+        let mut p = 2u64;
+        while p * p <= rest {
+            while rest % (p * p) == 0 {
+                rest /= p * p;
+                outside *= p;
+            }
+            p += 1;
+        }
+        (BigInt::from(outside), BigInt::from(rest))
+    }
+
+    /// Durand-Kerner 迭代求全部数值根
+    fn numeric_roots(poly: &DensePoly) -> Vec<RawRoot> {
+        let degree = Self::degree(poly) as usize;
+        let leading = poly[degree].to_f64().unwrap_or(1.0);
+        let monic: Vec<Complex64> = poly.iter()
+            .map(|c| Complex64::new(c.to_f64().unwrap_or(0.0) / leading, 0.0))
+            .collect();
+        let eval = |z: Complex64| monic.iter().rev().fold(Complex64::new(0.0, 0.0), |acc, c| acc * z + c);
+
+        let seed = Complex64::new(0.4, 0.9);
+        let mut roots: Vec<Complex64> = (0..degree).map(|k| seed.powu(k as u32)).collect();
+
+        for _ in 0..500 {
+            let mut max_delta: f64 = 0.0;
+            for i in 0..degree {
+                let denominator = (0..degree)
+                    .filter(|&j| j != i)
+                    .fold(Complex64::new(1.0, 0.0), |acc, j| acc * (roots[i] - roots[j]));
+                let delta = eval(roots[i]) / denominator;
+                roots[i] -= delta;
+                max_delta = max_delta.max(delta.norm());
+            }
+            if max_delta < 1e-15 {
+                break;
+            }
+        }
+
+        roots.into_iter().map(|z| {
+            let root = if z.im.abs() < VERIFY_TOLERANCE {
+                Number::Float(z.re)
+            } else {
+                Number::complex(Number::Float(z.re), Number::Float(z.im))
+            };
+            RawRoot {
+                root: Expression::Number(root),
+                approx: z,
+                kind: SolutionKind::Numeric,
+            }
+        }).collect()
+    }
+
+    /// 将根代回原方程并化简
+    fn residual(&self, simplifier: &mut Simplifier, expr: &Expression, var: &str, root: &Expression) -> Expression {
+        let mut bindings = std::collections::HashMap::new();
+        bindings.insert(var.to_string(), root.clone());
+        let substituted = expr.substitute(&bindings);
+        simplifier.simplify(&substituted).unwrap_or(substituted)
+    }
+
+    fn rational_root(value: BigRational) -> RawRoot {
+        let approx = Complex64::new(value.to_f64().unwrap_or(0.0), 0.0);
+        RawRoot {
+            root: Expression::Number(Self::rational_to_number(value)),
+            approx,
+            kind: SolutionKind::Exact,
+        }
+    }
+
+    /// 有理数转换为 Number，分母为 1 时返回整数
+    pub(super) fn rational_to_number(value: BigRational) -> Number {
+        if value.is_integer() {
+            Number::Integer(value.to_integer())
+        } else {
+            Number::Rational(value)
+        }
+    }
+
+    // ---- 稠密多项式的基本运算 ----
+
+    pub(super) fn trim(mut p: DensePoly) -> DensePoly {
+        while p.len() > 1 && p.last().is_some_and(|c| c.is_zero()) {
+            p.pop();
+        }
+        if p.is_empty() {
+            p.push(BigRational::zero());
+        }
+        p
+    }
+
+    pub(super) fn degree(p: &DensePoly) -> isize {
+        let p = Self::trim(p.clone());
+        if p.len() == 1 && p[0].is_zero() { -1 } else { p.len() as isize - 1 }
+    }
+
+    fn derivative(p: &DensePoly) -> DensePoly {
+        if p.len() <= 1 {
+            return vec![BigRational::zero()];
+        }
+        Self::trim(p.iter().enumerate().skip(1)
+            .map(|(i, c)| c * BigRational::from(BigInt::from(i)))
+            .collect())
+    }
+
+    fn sub(a: &DensePoly, b: &DensePoly) -> DensePoly {
+        let len = a.len().max(b.len());
+        Self::trim((0..len).map(|i| {
+            let x = a.get(i).cloned().unwrap_or_else(BigRational::zero);
+            let y = b.get(i).cloned().unwrap_or_else(BigRational::zero);
+            x - y
+        }).collect())
+    }
+
+    /// 多项式带余除法，返回 (商, 余式)
+    pub(super) fn div_rem(a: &DensePoly, b: &DensePoly) -> (DensePoly, DensePoly) {
+        let b = Self::trim(b.clone());
+        let mut rem = Self::trim(a.clone());
+        let db = Self::degree(&b);
+        if db < 0 {
+            return (vec![BigRational::zero()], rem);
+        }
+        let mut quotient = vec![BigRational::zero(); (Self::degree(&rem) - db).max(0) as usize + 1];
+        while Self::degree(&rem) >= db {
+            let shift = (Self::degree(&rem) - db) as usize;
+            let factor = rem.last().unwrap() / b.last().unwrap();
+            for (i, c) in b.iter().enumerate() {
+                rem[i + shift] = &rem[i + shift] - &factor * c;
+            }
+            quotient[shift] = factor;
+            rem = Self::trim(rem);
+            if Self::degree(&rem) < 0 {
+                break;
+            }
+        }
+        (Self::trim(quotient), rem)
+    }
+
+    pub(super) fn div_exact(a: &DensePoly, b: &DensePoly) -> DensePoly {
+        Self::div_rem(a, b).0
+    }
+
+    /// 首一最大公因式
+    fn gcd(a: &DensePoly, b: &DensePoly) -> DensePoly {
+        let mut x = Self::trim(a.clone());
+        let mut y = Self::trim(b.clone());
+        while Self::degree(&y) >= 0 {
+            let (_, r) = Self::div_rem(&x, &y);
+            x = y;
+            y = r;
+        }
+        match x.last().cloned() {
+            Some(lead) if !lead.is_zero() => x.iter().map(|c| c / &lead).collect(),
+            _ => vec![BigRational::one()],
+        }
+    }
+
+    pub(super) fn eval_dense(p: &DensePoly, x: &BigRational) -> BigRational {
+        p.iter().rev().fold(BigRational::zero(), |acc, c| acc * x + c)
+    }
+
+    fn eval_dense_complex(p: &DensePoly, z: Complex64) -> Complex64 {
+        p.iter().rev().fold(Complex64::new(0.0, 0.0), |acc, c| {
+            acc * z + Complex64::new(c.to_f64().unwrap_or(0.0), 0.0)
+        })
+    }
+}
+
+impl Default for SolverEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// 包含测试模块
+#[cfg(test)]
+#[path = "solver_tests.rs"]
+mod solver_tests;
@@ -0,0 +1,116 @@
+//! # 方程求解测试
+//!
+//! 测试多项式方程求解、根的重数和代回验证。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::solver::{SolverEngine, SolutionKind};
+    use crate::core::{Expression, Number, MathConstant, BinaryOperator};
+    use crate::test_utils::parse;
+
+    fn int(value: i64) -> Expression {
+        Expression::Number(Number::integer(value))
+    }
+
+    #[test]
+    fn test_solve_repeated_roots() {
+        let engine = SolverEngine::new();
+        let solutions = engine.solve_detailed(&parse("(x-1)^2*(x+3)"), "x").unwrap();
+
+        assert_eq!(solutions.len(), 2);
+        assert_eq!(solutions[0].root, int(-3));
+        assert_eq!(solutions[0].multiplicity, 1);
+        assert_eq!(solutions[1].root, int(1));
+        assert_eq!(solutions[1].multiplicity, 2);
+
+        for solution in &solutions {
+            assert_eq!(solution.kind, SolutionKind::Exact);
+            assert_eq!(solution.residual, int(0));
+            assert!(solution.verified);
+            assert!(!solution.is_candidate());
+        }
+    }
+
+    #[test]
+    fn test_solve_equation_form() {
+        let engine = SolverEngine::new();
+        let equation = Expression::binary_op(BinaryOperator::Equal, parse("x^2"), int(4));
+        let roots = engine.solve(&equation, "x").unwrap();
+        assert_eq!(roots, vec![int(-2), int(2)]);
+    }
+
+    #[test]
+    fn test_solve_rational_roots() {
+        let engine = SolverEngine::new();
+        let solutions = engine.solve_detailed(&parse("2*x^2 - 3*x + 1"), "x").unwrap();
+        let roots: Vec<_> = solutions.iter().map(|s| s.root.clone()).collect();
+        assert_eq!(roots, vec![Expression::Number(Number::rational(1, 2)), int(1)]);
+    }
+
+    #[test]
+    fn test_solve_zero_root_multiplicity() {
+        let engine = SolverEngine::new();
+        let solutions = engine.solve_detailed(&parse("x^3 - x^2"), "x").unwrap();
+        assert_eq!(solutions.len(), 2);
+        assert_eq!(solutions[0].root, int(0));
+        assert_eq!(solutions[0].multiplicity, 2);
+        assert_eq!(solutions[1].root, int(1));
+        assert_eq!(solutions[1].multiplicity, 1);
+    }
+
+    #[test]
+    fn test_solve_irrational_quadratic() {
+        let engine = SolverEngine::new();
+        let solutions = engine.solve_detailed(&parse("x^2 - 2"), "x").unwrap();
+        assert_eq!(solutions.len(), 2);
+
+        let sqrt2 = Expression::sqrt(int(2));
+        assert_eq!(solutions[0].root, Expression::negate(sqrt2.clone()));
+        assert_eq!(solutions[1].root, sqrt2);
+        assert!(solutions.iter().all(|s| s.is_exact() && s.verified));
+    }
+
+    #[test]
+    fn test_solve_complex_quadratic() {
+        let engine = SolverEngine::new();
+        let solutions = engine.solve_detailed(&parse("x^2 + 1"), "x").unwrap();
+        assert_eq!(solutions.len(), 2);
+
+        let i = Expression::Constant(MathConstant::I);
+        let roots: Vec<_> = solutions.iter().map(|s| s.root.clone()).collect();
+        assert!(roots.contains(&i));
+        assert!(roots.contains(&Expression::negate(i)));
+        assert!(solutions.iter().all(|s| s.verified));
+    }
+
+    #[test]
+    fn test_solve_numeric_fallback() {
+        let engine = SolverEngine::new();
+        let solutions = engine.solve_detailed(&parse("x^3 - 2"), "x").unwrap();
+        assert_eq!(solutions.len(), 3);
+        assert!(solutions.iter().all(|s| s.kind == SolutionKind::Numeric));
+        assert!(solutions.iter().all(|s| s.verified));
+
+        // 唯一的实根排在最前面
+        match &solutions[0].root {
+            Expression::Number(Number::Float(value)) => {
+                assert!((value - 2f64.cbrt()).abs() < 1e-9);
+            }
+            other => panic!("期望实数根，得到 {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_solve_no_solution_and_identity() {
+        let engine = SolverEngine::new();
+        assert!(engine.solve_detailed(&parse("x - x + 1"), "x").unwrap().is_empty());
+        assert!(engine.solve_detailed(&parse("x - x"), "x").is_err());
+    }
+
+    #[test]
+    fn test_solve_rejects_multivariate() {
+        let engine = SolverEngine::new();
+        assert!(engine.solve_detailed(&parse("x^2 - y"), "x").is_err());
+        assert!(engine.solve_detailed(&parse("sin(x)"), "x").is_err());
+    }
+}
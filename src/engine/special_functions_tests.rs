@@ -0,0 +1,65 @@
+//! # 特殊函数数值测试
+//!
+//! 与已知高精度值比较，覆盖级数与连分式/渐近展开两侧的区间。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::special_functions::{erf, erfc, si, ci, ei, li, sinc};
+
+    fn assert_close(actual: f64, expected: f64) {
+        let error = (actual - expected).abs() / expected.abs().max(1.0);
+        assert!(error < 1e-12, "实际值 {} 与期望值 {} 相差过大", actual, expected);
+    }
+
+    #[test]
+    fn test_erf() {
+        assert_eq!(erf(0.0), 0.0);
+        assert_close(erf(0.5), 0.5204998778130465);
+        assert_close(erf(1.0), 0.8427007929497149);
+        assert_close(erf(-1.0), -0.8427007929497149);
+        assert_close(erf(2.0), 0.9953222650189527);
+        assert_close(erf(3.0), 0.9999779095030014);
+    }
+
+    #[test]
+    fn test_erfc_tail() {
+        // 尾部按相对误差比较
+        let cases = [
+            (1.0, 0.15729920705028513),
+            (2.0, 0.004677734981047266),
+            (3.0, 2.209049699858544e-05),
+            (6.0, 2.1519736712498913e-17),
+        ];
+        for (x, expected) in cases {
+            assert!(((erfc(x) - expected) / expected).abs() < 1e-12, "erfc({})", x);
+        }
+        assert_close(erfc(-1.0), 1.8427007929497148);
+    }
+
+    #[test]
+    fn test_sine_and_cosine_integrals() {
+        assert_eq!(si(0.0), 0.0);
+        assert_close(si(1.0), 0.946083070367183);
+        assert_close(si(-1.0), -0.946083070367183);
+        assert_close(si(10.0), 1.658347594218874);
+        assert_close(ci(1.0), 0.3374039229009681);
+        assert_close(ci(10.0), -0.04545643300445537);
+        assert!(ci(-1.0).is_nan());
+    }
+
+    #[test]
+    fn test_exponential_and_logarithmic_integrals() {
+        assert_close(ei(1.0), 1.8951178163559368);
+        assert_close(ei(-1.0), -0.21938393439552029);
+        assert_close(ei(-3.0), -0.013048381094197037);
+        assert_close(ei(50.0) / 1e20, 1.0585636897131691);
+        assert_close(li(2.0), 1.045163780117493);
+        assert_eq!(ei(0.0), f64::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_sinc() {
+        assert_eq!(sinc(0.0), 1.0);
+        assert_close(sinc(2.0), 2.0_f64.sin() / 2.0);
+    }
+}
@@ -0,0 +1,928 @@
+//! # 求和与无穷级数
+//!
+//! 计算 `sum(f, n, a, b)`。有限求和在项数不多时逐项相加；
+//! 无穷级数依次尝试已知级数模式表、等比级数、裂项相消和线性拆分，
+//! 都无法求出闭式时用 p-级数（有理项的次数差）、交错级数和比值判别法判断收敛性。
+//! 无法判定时保留符号求和形式，发散的级数不会给出有限值。
+//!
+//! 前向差分 `Δf(n) = f(n + 1) - f(n)` 是求和的逆运算：`Σ_{k=a}^{b} Δf(k) = f(b + 1) - f(a)`。
+
+use std::collections::HashMap;
+use std::fmt;
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator, MathConstant};
+use super::ComputeError;
+use super::calculus::CalculusEngine;
+use super::domain::DomainAnalyzer;
+use super::polynomial::{is_polynomial, Polynomial, PolynomialEngine};
+use super::simplify::Simplifier;
+use super::solver::SolverEngine;
+use num_rational::BigRational;
+use num_traits::{One, Pow, ToPrimitive, Zero};
+use crate::tr;
+
+/// 逐项相加的最大项数
+const MAX_TERMS: i64 = 10_000;
+
+/// 比值判别法的采样点
+const RATIO_SAMPLES: [f64; 3] = [1e4, 1e5, 1e6];
+
+/// 比值与 1 的最小距离，更接近 1 时比值判别法不作结论
+const RATIO_MARGIN: f64 = 1e-3;
+
+/// 数值比较的容差
+const TOLERANCE: f64 = 1e-9;
+
+/// 数值验证等比级数时，其他变量的取值
+const SAMPLE_PARAMETER: f64 = 0.5;
+
+/// 已知级数模式中的求和指标
+const INDEX: &str = "n";
+
+/// 已知级数模式中的参数，可匹配任意不含求和指标的表达式
+const PARAMETER: &str = "x";
+
+/// 级数的收敛性
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Convergence {
+    /// 收敛（有限求和总是收敛）
+    Convergent,
+    /// 发散
+    Divergent,
+    /// 无法判断
+    Unknown,
+}
+
+impl fmt::Display for Convergence {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Convergence::Convergent => tr!("summation.convergent"),
+            Convergence::Divergent => tr!("summation.divergent"),
+            Convergence::Unknown => tr!("summation.unknown"),
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// 求和结果
+#[derive(Debug, Clone, PartialEq)]
+pub struct SeriesSum {
+    /// 求和的值；无法求值时为符号求和 `sum(f, n, a, b)`
+    pub value: Expression,
+    /// 收敛性
+    pub convergence: Convergence,
+    /// 是否求出了值（发散到正负无穷也算求出）
+    pub evaluated: bool,
+    /// 收敛条件、判别依据或无法求值的原因
+    pub notes: Vec<String>,
+}
+
+impl SeriesSum {
+    /// 是否求出了值
+    pub fn is_evaluated(&self) -> bool {
+        self.evaluated
+    }
+
+    fn exact(value: Expression) -> Self {
+        // 化简得到的 2/1 这样的有理数化为整数
+        let value = match value {
+            Expression::Number(Number::Rational(r)) if r.is_integer() => Expression::Number(Number::Integer(r.to_integer())),
+            value => value,
+        };
+        Self { value, convergence: Convergence::Convergent, evaluated: true, notes: Vec::new() }
+    }
+
+    fn infinite(positive: bool, note: impl Into<String>) -> Self {
+        let constant = if positive { MathConstant::PositiveInfinity } else { MathConstant::NegativeInfinity };
+        Self {
+            value: Expression::Constant(constant),
+            convergence: Convergence::Divergent,
+            evaluated: true,
+            notes: vec![note.into()],
+        }
+    }
+
+    fn unevaluated(symbolic: Expression, convergence: Convergence, note: impl Into<String>) -> Self {
+        Self { value: symbolic, convergence, evaluated: false, notes: vec![note.into()] }
+    }
+
+    /// 发散到无穷时返回方向（true 为正无穷）
+    fn infinity_sign(&self) -> Option<bool> {
+        match (&self.value, self.convergence) {
+            (Expression::Constant(MathConstant::PositiveInfinity), Convergence::Divergent) => Some(true),
+            (Expression::Constant(MathConstant::NegativeInfinity), Convergence::Divergent) => Some(false),
+            _ => None,
+        }
+    }
+
+    /// 是否求出了有限值
+    fn is_finite_value(&self) -> bool {
+        self.evaluated && self.convergence == Convergence::Convergent
+    }
+}
+
+/// 已知级数：从 `start` 开始对 `term` 求和等于 `value`
+struct KnownSeries {
+    term: Expression,
+    start: i64,
+    value: Expression,
+}
+
+/// 求和引擎
+pub struct SummationEngine {
+    calculus: CalculusEngine,
+    polynomial: PolynomialEngine,
+    domain: DomainAnalyzer,
+    known: Vec<KnownSeries>,
+}
+
+impl SummationEngine {
+    /// 创建新的求和引擎
+    pub fn new() -> Self {
+        let mut simplifier = Simplifier::new();
+        let known = Self::known_series()
+            .into_iter()
+            .map(|(term, start, value)| KnownSeries {
+                term: simplifier.simplify(&term).unwrap_or(term),
+                start,
+                value,
+            })
+            .collect();
+
+        Self {
+            calculus: CalculusEngine::new(),
+            polynomial: PolynomialEngine::new(),
+            domain: DomainAnalyzer::new(),
+            known,
+        }
+    }
+
+    /// 计算 `term` 关于 `var` 从 `lower` 到 `upper` 的和，`upper` 可以为正无穷
+    ///
+    /// 求和项在某个指标处无定义时返回定义域错误。
+    pub fn sum(&self, term: &Expression, var: &str, lower: &Expression, upper: &Expression) -> Result<SeriesSum, ComputeError> {
+        let mut simplifier = Simplifier::new();
+        let term = simplifier.simplify(term)?;
+        let lower = simplifier.simplify(lower)?;
+        let upper = simplifier.simplify(upper)?;
+        let symbolic = Self::symbolic(&term, var, &lower, &upper);
+
+        let a = match &lower {
+            Expression::Number(n @ Number::Integer(_)) => n.to_i64(),
+            _ => None,
+        };
+        let Some(a) = a else {
+            return Ok(SeriesSum::unevaluated(symbolic, Convergence::Unknown, tr!("summation.lower_bound_integer")));
+        };
+
+        match &upper {
+            Expression::Constant(MathConstant::PositiveInfinity) => self.infinite_sum(&mut simplifier, &term, var, a),
+            Expression::Number(b @ Number::Integer(_)) => {
+                let Some(b) = b.to_i64() else {
+                    return Ok(SeriesSum::unevaluated(symbolic, Convergence::Convergent, tr!("summation.too_many_terms")));
+                };
+                if b < a {
+                    return Ok(SeriesSum::exact(Expression::Number(Number::zero())));
+                }
+                if b - a >= MAX_TERMS {
+                    return Ok(SeriesSum::unevaluated(symbolic, Convergence::Convergent, tr!("summation.too_many_terms")));
+                }
+                Ok(SeriesSum::exact(self.partial_sum(&mut simplifier, &term, var, a, b)?))
+            }
+            _ => Ok(SeriesSum::unevaluated(symbolic, Convergence::Unknown, tr!("summation.upper_bound_integer"))),
+        }
+    }
+
+    /// 前向差分 `f(var + 1) - f(var)`；多项式展开后合并同类项，次数降低一次
+    pub fn forward_difference(&self, expr: &Expression, var: &str) -> Result<Expression, ComputeError> {
+        let mut simplifier = Simplifier::new();
+        let difference = Expression::subtract(self.shift(&mut simplifier, expr, var)?, expr.clone());
+        if is_polynomial(expr, var) {
+            return simplifier.simplify(&self.polynomial.expand(&difference)?);
+        }
+        simplifier.simplify(&difference)
+    }
+
+    /// 检查求和与差分的互逆关系 `Σ_{k=a}^{b} Δf(k) = f(b + 1) - f(a)`
+    ///
+    /// 两边化简后相同，或在其他变量取样本值时数值相等即认为成立。
+    pub fn verify_difference_sum(&self, f: &Expression, var: &str, lower: i64, upper: i64) -> Result<bool, ComputeError> {
+        let mut simplifier = Simplifier::new();
+        let difference = self.forward_difference(f, var)?;
+        let total = self.sum(&difference, var, &Self::int(lower), &Self::int(upper))?;
+        if !total.is_finite_value() {
+            return Ok(false);
+        }
+        let expected = Expression::subtract(
+            self.term_at(&mut simplifier, f, var, upper + 1)?,
+            self.term_at(&mut simplifier, f, var, lower)?,
+        );
+        let gap = simplifier.simplify(&Expression::subtract(total.value, expected))?;
+        if Self::is_zero(&gap) {
+            return Ok(true);
+        }
+        let samples = gap.get_variables().into_iter().map(|name| (name, SAMPLE_PARAMETER)).collect();
+        Ok(self.evaluate(&gap, &samples).is_some_and(|value| value.abs() < TOLERANCE))
+    }
+
+    /// 无穷级数求和
+    fn infinite_sum(&self, simplifier: &mut Simplifier, term: &Expression, var: &str, a: i64) -> Result<SeriesSum, ComputeError> {
+        let symbolic = Self::symbolic(term, var, &Self::int(a), &Expression::Constant(MathConstant::PositiveInfinity));
+
+        if !Self::depends_on(term, var) {
+            return Ok(self.constant_series(term, symbolic));
+        }
+        self.check_poles(term, var, a)?;
+
+        if let Some(result) = self.match_known(simplifier, term, var, a)? {
+            return Ok(result);
+        }
+        if let Some(result) = self.geometric(simplifier, term, var, a, &symbolic)? {
+            return Ok(result);
+        }
+        if let Some(result) = self.telescoping(simplifier, term, var, a)? {
+            return Ok(result);
+        }
+        if let Some(result) = self.linear_split(simplifier, term, var, a)? {
+            return Ok(result);
+        }
+        Ok(self.convergence_test(term, var, a, symbolic))
+    }
+
+    /// 常数项级数：只有常数为零时收敛
+    fn constant_series(&self, term: &Expression, symbolic: Expression) -> SeriesSum {
+        match self.evaluate(term, &HashMap::new()) {
+            Some(0.0) => SeriesSum::exact(Expression::Number(Number::zero())),
+            Some(value) => SeriesSum::infinite(value > 0.0, tr!("summation.nonzero_constant_term")),
+            None => SeriesSum::unevaluated(symbolic, Convergence::Unknown, tr!("summation.constant_series")),
+        }
+    }
+
+    /// 检查求和项的分母在求和范围内是否有整数零点
+    fn check_poles(&self, term: &Expression, var: &str, a: i64) -> Result<(), ComputeError> {
+        let (_, denominator) = DomainAnalyzer::to_fraction(term);
+        if !Self::depends_on(&denominator, var) {
+            return Ok(());
+        }
+        for (_, x) in self.domain.zeros_and_poles(&denominator, var).unwrap_or_default() {
+            let k = x.round();
+            if k >= a as f64 && (x - k).abs() < TOLERANCE {
+                return Err(ComputeError::domain_error(tr!("summation.undefined_term", variable = var, value = k)));
+            }
+        }
+        Ok(())
+    }
+
+    /// 匹配已知级数模式表，下限不同时补上或去掉开头的若干项
+    fn match_known(&self, simplifier: &mut Simplifier, term: &Expression, var: &str, a: i64) -> Result<Option<SeriesSum>, ComputeError> {
+        for series in &self.known {
+            let mut bindings = HashMap::new();
+            if !Self::matches(&series.term, term, var, &mut bindings) || (a - series.start).abs() > MAX_TERMS {
+                continue;
+            }
+
+            // 和保持 ln(2)、exp(x) 这样的精确形式，不交给化简器求数值
+            let value = series.value.substitute(&bindings);
+            let value = if a > series.start {
+                let head = self.partial_sum(simplifier, term, var, series.start, a - 1)?;
+                Expression::subtract(value, head)
+            } else if a < series.start {
+                let head = self.partial_sum(simplifier, term, var, a, series.start - 1)?;
+                Expression::add(value, head)
+            } else {
+                value
+            };
+            return Ok(Some(SeriesSum::exact(value)));
+        }
+        Ok(None)
+    }
+
+    /// 等比级数：相邻项之比为常数 r 时，|r| < 1 收敛到 f(a) / (1 - r)
+    fn geometric(&self, simplifier: &mut Simplifier, term: &Expression, var: &str, a: i64, symbolic: &Expression) -> Result<Option<SeriesSum>, ComputeError> {
+        let first = self.term_at(simplifier, term, var, a)?;
+        if Self::is_zero(&first) {
+            return Ok(None);
+        }
+        let second = self.term_at(simplifier, term, var, a + 1)?;
+        let ratio = simplifier.simplify(&Expression::divide(second, first.clone()))?;
+        if !self.has_constant_ratio(term, var, a, &ratio) {
+            return Ok(None);
+        }
+
+        let sum = || -> Result<Expression, ComputeError> {
+            // 首项与公比都是有理数时精确计算，避免留下 1 / (1 - 2^(-1)) 这样未约简的形式
+            if let (Some(f), Some(r)) = (Self::exact_rational(&first), Self::exact_rational(&ratio)) {
+                if !r.is_one() {
+                    return Ok(Expression::Number(SolverEngine::rational_to_number(f / (BigRational::one() - r))));
+                }
+            }
+            let denominator = Expression::subtract(Expression::Number(Number::one()), ratio.clone());
+            Simplifier::new().simplify(&Expression::divide(first.clone(), denominator))
+        };
+
+        // 公比含有其他变量时收敛性取决于参数，只在说明中给出 |r| < 1 时的和
+        if !ratio.get_variables().is_empty() {
+            let note = tr!("summation.geometric_conditional", ratio = ratio, value = sum()?);
+            return Ok(Some(SeriesSum::unevaluated(symbolic.clone(), Convergence::Unknown, note)));
+        }
+
+        let Some(r) = self.evaluate(&ratio, &HashMap::new()) else {
+            return Ok(None);
+        };
+        Ok(Some(if r.abs() < 1.0 {
+            SeriesSum::exact(sum()?)
+        } else if r > 0.0 {
+            match self.evaluate(&first, &HashMap::new()) {
+                Some(value) => SeriesSum::infinite(value > 0.0, tr!("summation.geometric_divergent", ratio = ratio)),
+                None => SeriesSum::unevaluated(symbolic.clone(), Convergence::Divergent, tr!("summation.geometric_divergent", ratio = ratio)),
+            }
+        } else {
+            SeriesSum::unevaluated(symbolic.clone(), Convergence::Divergent, tr!("summation.geometric_oscillating", ratio = ratio))
+        }))
+    }
+
+    /// 数值验证相邻项之比在前若干项上恒等于 ratio
+    fn has_constant_ratio(&self, term: &Expression, var: &str, a: i64, ratio: &Expression) -> bool {
+        let mut vars: HashMap<String, f64> = term.get_variables()
+            .into_iter()
+            .filter(|v| v != var)
+            .map(|v| (v, SAMPLE_PARAMETER))
+            .collect();
+        let Some(r) = self.evaluate(ratio, &vars) else {
+            return false;
+        };
+
+        let mut previous: Option<f64> = None;
+        for k in a..a + 10 {
+            vars.insert(var.to_string(), k as f64);
+            let Some(value) = self.evaluate(term, &vars).filter(|v| v.is_finite() && *v != 0.0) else {
+                return false;
+            };
+            if let Some(previous) = previous {
+                if (value / previous - r).abs() > TOLERANCE * (1.0 + r.abs()) {
+                    return false;
+                }
+            }
+            previous = Some(value);
+        }
+        true
+    }
+
+    /// 裂项相消：g(n) - g(n+1) 的和为 g(a) - lim g(n)
+    fn telescoping(&self, simplifier: &mut Simplifier, term: &Expression, var: &str, a: i64) -> Result<Option<SeriesSum>, ComputeError> {
+        let Expression::BinaryOp { op: BinaryOperator::Subtract, left, right } = term else {
+            return Ok(None);
+        };
+
+        for (g, shifted, forward) in [(left, right, true), (right, left, false)] {
+            if self.shift(simplifier, g, var)? != **shifted {
+                continue;
+            }
+            let Some(limit) = self.limit_at_infinity(g, var) else {
+                continue;
+            };
+            let start = self.term_at(simplifier, g, var, a)?;
+            let value = if forward {
+                Expression::subtract(start, limit)
+            } else {
+                Expression::subtract(limit, start)
+            };
+            return Ok(Some(SeriesSum::exact(simplifier.simplify(&value)?)));
+        }
+        Ok(None)
+    }
+
+    /// 线性拆分：逐项求和或提出与求和指标无关的因子
+    fn linear_split(&self, simplifier: &mut Simplifier, term: &Expression, var: &str, a: i64) -> Result<Option<SeriesSum>, ComputeError> {
+        match term {
+            Expression::BinaryOp { op: op @ (BinaryOperator::Add | BinaryOperator::Subtract), left, right } => {
+                let (Ok(left), Ok(right)) = (
+                    self.infinite_sum(simplifier, left, var, a),
+                    self.infinite_sum(simplifier, right, var, a),
+                ) else {
+                    return Ok(None);
+                };
+                self.combine(simplifier, op, left, right)
+            }
+            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } if !Self::depends_on(left, var) => {
+                self.scale(simplifier, left, right, var, a)
+            }
+            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } if !Self::depends_on(right, var) => {
+                self.scale(simplifier, right, left, var, a)
+            }
+            Expression::BinaryOp { op: BinaryOperator::Divide, left, right } if !Self::depends_on(right, var) => {
+                let factor = Expression::divide(Expression::Number(Number::one()), right.as_ref().clone());
+                self.scale(simplifier, &factor, left, var, a)
+            }
+            Expression::BinaryOp { op: BinaryOperator::Divide, left, right } if !Self::depends_on(left, var) && !Self::is_one(left) => {
+                let rest = Expression::divide(Expression::Number(Number::one()), right.as_ref().clone());
+                self.scale(simplifier, left, &rest, var, a)
+            }
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+                self.scale(simplifier, &Expression::Number(Number::integer(-1)), operand, var, a)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// 合并两个级数的和；有限值与无穷、同向无穷可以合并，其他情形交给收敛判别
+    fn combine(&self, simplifier: &mut Simplifier, op: &BinaryOperator, left: SeriesSum, right: SeriesSum) -> Result<Option<SeriesSum>, ComputeError> {
+        let subtract = matches!(op, BinaryOperator::Subtract);
+        let right_sign = right.infinity_sign().map(|s| s != subtract);
+
+        if left.is_finite_value() && right.is_finite_value() {
+            let value = simplifier.simplify(&Expression::binary_op(op.clone(), left.value, right.value))?;
+            let mut result = SeriesSum::exact(value);
+            result.notes = left.notes.into_iter().chain(right.notes).collect();
+            return Ok(Some(result));
+        }
+
+        let sign = match (left.infinity_sign(), right_sign) {
+            (Some(l), None) if right.is_finite_value() => Some(l),
+            (None, Some(r)) if left.is_finite_value() => Some(r),
+            (Some(l), Some(r)) if l == r => Some(l),
+            _ => None,
+        };
+        Ok(sign.map(|positive| SeriesSum::infinite(positive, tr!("summation.diverges"))))
+    }
+
+    /// 提出常数因子：sum(c * f) = c * sum(f)
+    fn scale(&self, simplifier: &mut Simplifier, factor: &Expression, rest: &Expression, var: &str, a: i64) -> Result<Option<SeriesSum>, ComputeError> {
+        let Ok(inner) = self.infinite_sum(simplifier, rest, var, a) else {
+            return Ok(None);
+        };
+
+        if let Some(positive) = inner.infinity_sign() {
+            return Ok(match self.evaluate(factor, &HashMap::new()) {
+                Some(0.0) => Some(SeriesSum::exact(Expression::Number(Number::zero()))),
+                Some(c) => Some(SeriesSum::infinite(positive == (c > 0.0), tr!("summation.diverges"))),
+                None => {
+                    let upper = Expression::Constant(MathConstant::PositiveInfinity);
+                    let symbolic = Expression::multiply(factor.clone(), Self::symbolic(rest, var, &Self::int(a), &upper));
+                    Some(SeriesSum::unevaluated(symbolic, Convergence::Divergent, tr!("summation.diverges")))
+                }
+            });
+        }
+
+        let value = if inner.evaluated {
+            simplifier.simplify(&Expression::multiply(factor.clone(), inner.value))?
+        } else {
+            Expression::multiply(factor.clone(), inner.value)
+        };
+        Ok(Some(SeriesSum { value, ..inner }))
+    }
+
+    /// 收敛判别：交错级数、p-级数（有理项及含根式、实数次幂的项）和比值判别法
+    fn convergence_test(&self, term: &Expression, var: &str, a: i64, symbolic: Expression) -> SeriesSum {
+        if term.get_variables().iter().any(|v| v != var) {
+            return SeriesSum::unevaluated(symbolic, Convergence::Unknown, tr!("summation.unknown_with_parameters"));
+        }
+
+        if let Some(rest) = Self::strip_alternating(term, var) {
+            if let Some((difference, _)) = self.decay_order(&rest, var) {
+                return if difference > TOLERANCE {
+                    SeriesSum::unevaluated(symbolic, Convergence::Convergent, tr!("summation.leibniz_convergent"))
+                } else {
+                    SeriesSum::unevaluated(symbolic, Convergence::Divergent, tr!("summation.term_not_vanishing"))
+                };
+            }
+        }
+
+        if let Some((difference, leading)) = self.decay_order(term, var) {
+            return if difference > 1.0 + TOLERANCE {
+                SeriesSum::unevaluated(symbolic, Convergence::Convergent, tr!("summation.p_series_convergent"))
+            } else {
+                SeriesSum::infinite(leading > 0.0, tr!("summation.p_series_divergent"))
+            };
+        }
+
+        match self.ratio_limit(term, var) {
+            Some(ratio) if ratio < 1.0 - RATIO_MARGIN => {
+                SeriesSum::unevaluated(symbolic, Convergence::Convergent, tr!("summation.ratio_convergent"))
+            }
+            Some(ratio) if ratio > 1.0 + RATIO_MARGIN => match self.eventual_sign(term, var, a) {
+                Some(positive) => SeriesSum::infinite(positive, tr!("summation.ratio_divergent")),
+                None => SeriesSum::unevaluated(symbolic, Convergence::Divergent, tr!("summation.ratio_divergent")),
+            },
+            _ if self.terms_grow(term, var) => match self.eventual_sign(term, var, a) {
+                Some(positive) => SeriesSum::infinite(positive, tr!("summation.term_not_vanishing")),
+                None => SeriesSum::unevaluated(symbolic, Convergence::Divergent, tr!("summation.term_not_vanishing")),
+            },
+            _ => SeriesSum::unevaluated(symbolic, Convergence::Unknown, tr!("summation.unknown_convergence")),
+        }
+    }
+
+    /// 通项的绝对值在采样点上不减且不小于 1，此时通项不趋于零
+    fn terms_grow(&self, term: &Expression, var: &str) -> bool {
+        let magnitudes: Option<Vec<f64>> = RATIO_SAMPLES.iter()
+            .map(|&n| self.log_magnitude(term, var, n))
+            .collect();
+        match magnitudes {
+            Some(m) => m.windows(2).all(|w| w[1] >= w[0]) && m.last().is_some_and(|&last| last >= 0.0),
+            None => false,
+        }
+    }
+
+    /// 去掉 (-1)^k 形式的交错因子，返回剩余部分
+    fn strip_alternating(term: &Expression, var: &str) -> Option<Expression> {
+        let one = || Expression::Number(Number::one());
+        match term {
+            Expression::BinaryOp { op: BinaryOperator::Power, left, right } => {
+                let is_sign = matches!(left.as_ref(), Expression::Number(n) if *n == Number::integer(-1));
+                (is_sign && Self::depends_on(right, var)).then(one)
+            }
+            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
+                if let Some(rest) = Self::strip_alternating(left, var) {
+                    Some(Expression::multiply(rest, right.as_ref().clone()))
+                } else {
+                    Self::strip_alternating(right, var).map(|rest| Expression::multiply(left.as_ref().clone(), rest))
+                }
+            }
+            Expression::BinaryOp { op: BinaryOperator::Divide, left, right } => {
+                Self::strip_alternating(left, var).map(|rest| Expression::divide(rest, right.as_ref().clone()))
+            }
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => Self::strip_alternating(operand, var),
+            _ => None,
+        }
+    }
+
+    /// 通项趋于零的阶：通项约为 c / n^p 时返回 (p, c)。有理项按多项式次数精确计算，
+    /// 其余情形由 [`Self::power_degree`] 估计，`1/sqrt(n)` 的阶为 1/2
+    fn decay_order(&self, expr: &Expression, var: &str) -> Option<(f64, f64)> {
+        if let Some((difference, leading)) = self.rational_degrees(expr, var) {
+            return Some((f64::from(difference), leading));
+        }
+        self.power_degree(expr, var).map(|(degree, coefficient)| (-degree, coefficient))
+    }
+
+    /// 由常数、求和指标、乘除、常数次幂与开方组成的项约为 c * n^d 时返回 (d, c)，和差只保留次数最高的项，
+    /// 最高次项相消时返回 None
+    fn power_degree(&self, expr: &Expression, var: &str) -> Option<(f64, f64)> {
+        if !Self::depends_on(expr, var) {
+            return self.evaluate(expr, &HashMap::new())
+                .filter(|c| c.is_finite() && *c != 0.0)
+                .map(|c| (0.0, c));
+        }
+        // 负系数只能取整数次幂
+        let power = |(degree, coefficient): (f64, f64), exponent: f64| {
+            (coefficient > 0.0 || exponent.fract() == 0.0).then(|| (degree * exponent, coefficient.powf(exponent)))
+        };
+        match expr {
+            Expression::Variable(v) if v == var => Some((1.0, 1.0)),
+            Expression::BinaryOp { op: op @ (BinaryOperator::Multiply | BinaryOperator::Divide), left, right } => {
+                let (left_degree, left_coefficient) = self.power_degree(left, var)?;
+                let (right_degree, right_coefficient) = self.power_degree(right, var)?;
+                Some(if matches!(op, BinaryOperator::Multiply) {
+                    (left_degree + right_degree, left_coefficient * right_coefficient)
+                } else {
+                    (left_degree - right_degree, left_coefficient / right_coefficient)
+                })
+            }
+            Expression::BinaryOp { op: op @ (BinaryOperator::Add | BinaryOperator::Subtract), left, right } => {
+                let (left_degree, left_coefficient) = self.power_degree(left, var)?;
+                let (right_degree, mut right_coefficient) = self.power_degree(right, var)?;
+                if matches!(op, BinaryOperator::Subtract) {
+                    right_coefficient = -right_coefficient;
+                }
+                if (left_degree - right_degree).abs() > TOLERANCE {
+                    return Some(if left_degree > right_degree {
+                        (left_degree, left_coefficient)
+                    } else {
+                        (right_degree, right_coefficient)
+                    });
+                }
+                let coefficient = left_coefficient + right_coefficient;
+                (coefficient.abs() > TOLERANCE * (left_coefficient.abs() + right_coefficient.abs()))
+                    .then_some((left_degree, coefficient))
+            }
+            Expression::BinaryOp { op: BinaryOperator::Power, left, right } if !Self::depends_on(right, var) => {
+                power(self.power_degree(left, var)?, self.evaluate(right, &HashMap::new())?)
+            }
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+                self.power_degree(operand, var).map(|(degree, coefficient)| (degree, -coefficient))
+            }
+            Expression::UnaryOp { op: UnaryOperator::Sqrt, operand } => power(self.power_degree(operand, var)?, 0.5),
+            Expression::Function { name, args } if name == "sqrt" && args.len() == 1 => {
+                power(self.power_degree(&args[0], var)?, 0.5)
+            }
+            _ => None,
+        }
+    }
+
+    /// 有理项 P(n)/Q(n)：返回 deg Q - deg P 与首项系数之比
+    fn rational_degrees(&self, expr: &Expression, var: &str) -> Option<(i32, f64)> {
+        if expr.get_variables().iter().any(|v| v != var) {
+            return None;
+        }
+        let (numerator, denominator) = DomainAnalyzer::to_fraction(expr);
+        let (num_degree, num_leading) = self.leading_term(&numerator, var)?;
+        let (den_degree, den_leading) = self.leading_term(&denominator, var)?;
+        Some((den_degree - num_degree, num_leading / den_leading))
+    }
+
+    /// 多项式的次数与首项系数
+    fn leading_term(&self, expr: &Expression, var: &str) -> Option<(i32, f64)> {
+        let mut polynomial = self.polynomial.expression_to_polynomial(expr).ok()?;
+        polynomial.simplify();
+        let degree = polynomial.terms.iter()
+            .filter(|t| !t.coefficient.is_zero())
+            .map(|t| t.degree_of(var))
+            .max()?;
+        let leading: f64 = polynomial.terms.iter()
+            .filter(|t| t.degree_of(var) == degree)
+            .filter_map(|t| t.coefficient.to_f64())
+            .sum();
+        (leading != 0.0).then_some((degree, leading))
+    }
+
+    /// 用通项对数的差估计 |f(n+1) / f(n)| 的极限，估计值不稳定时返回 None
+    fn ratio_limit(&self, term: &Expression, var: &str) -> Option<f64> {
+        let mut estimates = Vec::new();
+        for n in RATIO_SAMPLES {
+            let ratio = (self.log_magnitude(term, var, n + 1.0)? - self.log_magnitude(term, var, n)?).exp();
+            if !ratio.is_finite() {
+                return None;
+            }
+            estimates.push(ratio);
+        }
+        let max = estimates.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let min = estimates.iter().cloned().fold(f64::INFINITY, f64::min);
+        let last = *estimates.last()?;
+        (max - min <= 1e-2 * (1.0 + last)).then_some(last)
+    }
+
+    /// 计算 ln|f(n)|，对乘积、幂和阶乘分别取对数以避免溢出
+    fn log_magnitude(&self, expr: &Expression, var: &str, n: f64) -> Option<f64> {
+        let value_at = |e: &Expression| {
+            let mut vars = HashMap::new();
+            vars.insert(var.to_string(), n);
+            self.evaluate(e, &vars)
+        };
+
+        match expr {
+            Expression::Variable(v) if v == var => Some(n.ln()),
+            Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
+                Some(self.log_magnitude(left, var, n)? + self.log_magnitude(right, var, n)?)
+            }
+            Expression::BinaryOp { op: BinaryOperator::Divide, left, right } => {
+                Some(self.log_magnitude(left, var, n)? - self.log_magnitude(right, var, n)?)
+            }
+            Expression::BinaryOp { op: BinaryOperator::Power, left, right } => {
+                let exponent = value_at(right)?;
+                let base = self.log_magnitude(left, var, n)?;
+                // 底数为 ±1 时不论指数多大都是 0
+                Some(if base == 0.0 { 0.0 } else { exponent * base })
+            }
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => self.log_magnitude(operand, var, n),
+            Expression::UnaryOp { op: UnaryOperator::Factorial, operand } => Self::ln_factorial(value_at(operand)?),
+            Expression::Function { name, args } if name == "factorial" && args.len() == 1 => {
+                Self::ln_factorial(value_at(&args[0])?)
+            }
+            Expression::Function { name, args } if name == "exp" && args.len() == 1 => value_at(&args[0]),
+            _ => value_at(expr).filter(|v| v.is_finite() && *v != 0.0).map(|v| v.abs().ln()),
+        }
+    }
+
+    /// ln(m!)，较大的 m 使用 Stirling 公式
+    fn ln_factorial(m: f64) -> Option<f64> {
+        if m < 0.0 || m.fract() != 0.0 {
+            return None;
+        }
+        if m < 20.0 {
+            return Some((2..=m as u64).map(|k| (k as f64).ln()).sum());
+        }
+        Some(m * m.ln() - m + 0.5 * (2.0 * std::f64::consts::PI * m).ln() + 1.0 / (12.0 * m))
+    }
+
+    /// 通项最终的符号，取若干项检查，不一致时返回 None
+    fn eventual_sign(&self, term: &Expression, var: &str, a: i64) -> Option<bool> {
+        let mut vars = HashMap::new();
+        let mut sign = None;
+        for k in a + 10..a + 20 {
+            vars.insert(var.to_string(), k as f64);
+            let value = self.evaluate(term, &vars).filter(|v| *v != 0.0)?;
+            match sign {
+                None => sign = Some(value > 0.0),
+                Some(positive) if positive != (value > 0.0) => return None,
+                _ => {}
+            }
+        }
+        sign
+    }
+
+    /// n → ∞ 时的极限，只处理有理项
+    fn limit_at_infinity(&self, expr: &Expression, var: &str) -> Option<Expression> {
+        if !Self::depends_on(expr, var) {
+            return Some(expr.clone());
+        }
+        let (numerator, denominator) = DomainAnalyzer::to_fraction(expr);
+        let mut polynomial = self.polynomial.expression_to_polynomial(&numerator).ok()?;
+        polynomial.simplify();
+        let num_degree = polynomial.degree_of(var);
+        let mut den_polynomial = self.polynomial.expression_to_polynomial(&denominator).ok()?;
+        den_polynomial.simplify();
+        let den_degree = den_polynomial.degree_of(var);
+
+        if num_degree < den_degree {
+            return Some(Expression::Number(Number::zero()));
+        }
+        if num_degree > den_degree {
+            return None;
+        }
+        let leading = |p: &Polynomial| -> Option<Expression> {
+            let terms: Vec<_> = p.terms.iter().filter(|t| t.degree_of(var) == num_degree).collect();
+            match terms.as_slice() {
+                [term] if term.variables.len() == 1 || num_degree == 0 => Some(Expression::Number(term.coefficient.clone())),
+                _ => None,
+            }
+        };
+        Some(Expression::divide(leading(&polynomial)?, leading(&den_polynomial)?))
+    }
+
+    /// 把求和指标替换为 n + 1 并化简
+    fn shift(&self, simplifier: &mut Simplifier, expr: &Expression, var: &str) -> Result<Expression, ComputeError> {
+        let mut bindings = HashMap::new();
+        bindings.insert(var.to_string(), Expression::add(Expression::variable(var), Expression::Number(Number::one())));
+        simplifier.simplify(&expr.substitute(&bindings))
+    }
+
+    /// 从 a 到 b 逐项相加
+    fn partial_sum(&self, simplifier: &mut Simplifier, term: &Expression, var: &str, a: i64, b: i64) -> Result<Expression, ComputeError> {
+        let mut total = Expression::Number(Number::zero());
+        for k in a..=b {
+            let value = self.term_at(simplifier, term, var, k)?;
+            total = simplifier.simplify(&Expression::add(total, value))?;
+        }
+        Ok(total)
+    }
+
+    /// 求和项在 var = k 处的值，无定义时返回定义域错误
+    fn term_at(&self, simplifier: &mut Simplifier, term: &Expression, var: &str, k: i64) -> Result<Expression, ComputeError> {
+        let undefined = || ComputeError::domain_error(tr!("summation.undefined_term", variable = var, value = k));
+
+        let mut bindings = HashMap::new();
+        bindings.insert(var.to_string(), Self::int(k));
+        let value = simplifier.simplify(&term.substitute(&bindings)).map_err(|e| match e {
+            ComputeError::DivisionByZero => undefined(),
+            e => e,
+        })?;
+
+        if value.get_variables().is_empty() {
+            match self.calculus.numerical_evaluate(&value, &HashMap::new()) {
+                Ok(v) if !v.is_finite() => return Err(undefined()),
+                Err(ComputeError::DivisionByZero) => return Err(undefined()),
+                _ => {}
+            }
+        }
+        Ok(value)
+    }
+
+    /// 在模式上匹配表达式，加法和乘法允许交换左右两边
+    fn matches(pattern: &Expression, expr: &Expression, var: &str, bindings: &mut HashMap<String, Expression>) -> bool {
+        match pattern {
+            Expression::Variable(name) if name == INDEX => matches!(expr, Expression::Variable(v) if v == var),
+            Expression::Variable(name) if name == PARAMETER => {
+                if Self::depends_on(expr, var) {
+                    return false;
+                }
+                match bindings.get(PARAMETER) {
+                    Some(bound) => bound == expr,
+                    None => {
+                        bindings.insert(PARAMETER.to_string(), expr.clone());
+                        true
+                    }
+                }
+            }
+            Expression::BinaryOp { op, left, right } => {
+                let Expression::BinaryOp { op: expr_op, left: expr_left, right: expr_right } = expr else {
+                    return false;
+                };
+                if op != expr_op {
+                    return false;
+                }
+                let saved = bindings.clone();
+                if Self::matches(left, expr_left, var, bindings) && Self::matches(right, expr_right, var, bindings) {
+                    return true;
+                }
+                *bindings = saved.clone();
+                if matches!(op, BinaryOperator::Add | BinaryOperator::Multiply)
+                    && Self::matches(left, expr_right, var, bindings)
+                    && Self::matches(right, expr_left, var, bindings)
+                {
+                    return true;
+                }
+                *bindings = saved;
+                false
+            }
+            Expression::UnaryOp { op, operand } => matches!(
+                expr,
+                Expression::UnaryOp { op: expr_op, operand: expr_operand }
+                    if op == expr_op && Self::matches(operand, expr_operand, var, bindings)
+            ),
+            Expression::Function { name, args } => match expr {
+                Expression::Function { name: expr_name, args: expr_args } if name == expr_name && args.len() == expr_args.len() => {
+                    args.iter().zip(expr_args).all(|(p, e)| Self::matches(p, e, var, bindings))
+                }
+                _ => false,
+            },
+            _ => pattern == expr,
+        }
+    }
+
+    /// 已知级数模式表：(通项, 起始指标, 和)
+    fn known_series() -> Vec<(Expression, i64, Expression)> {
+        let n = || Expression::variable(INDEX);
+        let x = || Expression::variable(PARAMETER);
+        let int = Self::int;
+        let pi = || Expression::Constant(MathConstant::Pi);
+        let sign = |exponent: Expression| Expression::power(int(-1), exponent);
+        let factorial = |operand: Expression| Expression::unary_op(UnaryOperator::Factorial, operand);
+        let reciprocal = |expr: Expression| Expression::divide(int(1), expr);
+        let two_n = || Expression::multiply(int(2), n());
+        let two_n_plus_one = || Expression::add(two_n(), int(1));
+
+        vec![
+            // ζ(2)、ζ(4)、ζ(6)
+            (reciprocal(Expression::power(n(), int(2))), 1, Expression::divide(Expression::power(pi(), int(2)), int(6))),
+            (reciprocal(Expression::power(n(), int(4))), 1, Expression::divide(Expression::power(pi(), int(4)), int(90))),
+            (reciprocal(Expression::power(n(), int(6))), 1, Expression::divide(Expression::power(pi(), int(6)), int(945))),
+            // 裂项相消的典型例子
+            (reciprocal(Expression::multiply(n(), Expression::add(n(), int(1)))), 1, int(1)),
+            // 交错调和级数与莱布尼茨级数
+            (Expression::divide(sign(Expression::add(n(), int(1))), n()), 1, Expression::function("ln", vec![int(2)])),
+            (Expression::divide(sign(n()), n()), 1, Expression::negate(Expression::function("ln", vec![int(2)]))),
+            (Expression::divide(sign(n()), two_n_plus_one()), 0, Expression::divide(pi(), int(4))),
+            // 指数函数与正余弦的幂级数
+            (reciprocal(factorial(n())), 0, Expression::Constant(MathConstant::E)),
+            (Expression::divide(Expression::power(x(), n()), factorial(n())), 0, Expression::function("exp", vec![x()])),
+            (
+                Expression::divide(Expression::multiply(sign(n()), Expression::power(x(), two_n_plus_one())), factorial(two_n_plus_one())),
+                0,
+                Expression::function("sin", vec![x()]),
+            ),
+            (
+                Expression::divide(Expression::multiply(sign(n()), Expression::power(x(), two_n())), factorial(two_n())),
+                0,
+                Expression::function("cos", vec![x()]),
+            ),
+        ]
+    }
+
+    /// 符号求和 sum(term, var, lower, upper)
+    fn symbolic(term: &Expression, var: &str, lower: &Expression, upper: &Expression) -> Expression {
+        Expression::function("sum", vec![term.clone(), Expression::variable(var), lower.clone(), upper.clone()])
+    }
+
+    /// 由数和整数次幂经四则运算组成的常数的精确有理值
+    fn exact_rational(expr: &Expression) -> Option<BigRational> {
+        match expr {
+            Expression::Number(n) => n.to_rational(),
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => Some(-Self::exact_rational(operand)?),
+            Expression::BinaryOp { op, left, right } => {
+                let (l, r) = (Self::exact_rational(left)?, Self::exact_rational(right)?);
+                match op {
+                    BinaryOperator::Add => Some(l + r),
+                    BinaryOperator::Subtract => Some(l - r),
+                    BinaryOperator::Multiply => Some(l * r),
+                    BinaryOperator::Divide => (!r.is_zero()).then(|| l / r),
+                    BinaryOperator::Power => {
+                        let exponent = r.is_integer().then(|| r.to_integer().to_i32()).flatten()
+                            .filter(|e| e.unsigned_abs() <= MAX_TERMS as u32)?;
+                        (!(l.is_zero() && exponent < 0)).then(|| Pow::pow(l, exponent))
+                    }
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    fn evaluate(&self, expr: &Expression, vars: &HashMap<String, f64>) -> Option<f64> {
+        self.calculus.numerical_evaluate(expr, vars).ok().filter(|v| !v.is_nan())
+    }
+
+    fn depends_on(expr: &Expression, var: &str) -> bool {
+        expr.get_variables().iter().any(|v| v == var)
+    }
+
+    fn is_zero(expr: &Expression) -> bool {
+        matches!(expr, Expression::Number(n) if n.is_zero())
+    }
+
+    fn is_one(expr: &Expression) -> bool {
+        matches!(expr, Expression::Number(n) if *n == Number::one())
+    }
+
+    fn int(value: i64) -> Expression {
+        Expression::Number(Number::integer(value))
+    }
+}
+
+impl Default for SummationEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+#[path = "summation_tests.rs"]
+mod summation_tests;
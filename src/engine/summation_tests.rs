@@ -0,0 +1,145 @@
+//! # 求和与无穷级数测试
+//!
+//! 测试有限求和、已知级数模式、等比级数、裂项相消以及各种发散情形。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::summation::{SummationEngine, SeriesSum, Convergence};
+    use crate::core::{Expression, Number, MathConstant};
+    use crate::engine::simplify::Simplifier;
+    use crate::test_utils::parse;
+
+    fn sum(term: &str, lower: i64, upper: &str) -> SeriesSum {
+        let engine = SummationEngine::new();
+        engine.sum(&parse(term), "n", &Expression::Number(Number::integer(lower)), &parse(upper)).unwrap()
+    }
+
+    fn simplified(input: &str) -> Expression {
+        Simplifier::new().simplify(&parse(input)).unwrap()
+    }
+
+    fn pos_inf() -> Expression {
+        Expression::Constant(MathConstant::PositiveInfinity)
+    }
+
+    #[test]
+    fn test_finite_sum() {
+        assert_eq!(sum("n^2", 1, "10").value, parse("385"));
+        assert_eq!(sum("1/n", 1, "4").value, simplified("25/12"));
+        assert_eq!(sum("n", 5, "1").value, parse("0"));
+    }
+
+    #[test]
+    fn test_known_series() {
+        let result = sum("1/n^2", 1, "inf");
+        assert_eq!(result.value, parse("pi^2/6"));
+        assert_eq!(result.convergence, Convergence::Convergent);
+        assert!(result.is_evaluated());
+
+        assert_eq!(sum("x^n/n!", 0, "inf").value, parse("exp(x)"));
+        assert_eq!(sum("(-1)^n*y^(2*n+1)/(2*n+1)!", 0, "inf").value, parse("sin(y)"));
+        assert_eq!(sum("(-1)^(n+1)/n", 1, "inf").value, parse("ln(2)"));
+        assert_eq!(sum("1/(n*(n+1))", 1, "inf").value, parse("1"));
+    }
+
+    #[test]
+    fn test_known_series_shifted_start() {
+        // 从 3 开始：去掉 n = 1, 2 两项
+        assert_eq!(sum("1/n^2", 3, "inf").value, simplified("pi^2/6 - 5/4"));
+    }
+
+    #[test]
+    fn test_geometric_series() {
+        assert_eq!(sum("1/2^n", 0, "inf").value, parse("2"));
+        assert_eq!(sum("2^(-n)", 0, "inf").value, parse("2"));
+        assert_eq!(sum("(-1/2)^n", 1, "inf").value, simplified("-1/3"));
+        assert_eq!(sum("3*(1/3)^n", 1, "inf").value, simplified("3/2"));
+
+        // 公比含参数时收敛性未知，不能无条件给出 1/(1 - x)
+        let result = sum("x^n", 0, "inf");
+        assert_eq!(result.convergence, Convergence::Unknown);
+        assert!(!result.is_evaluated());
+        assert!(result.notes.iter().any(|note| note.contains("|x| < 1")), "{:?}", result.notes);
+    }
+
+    #[test]
+    fn test_telescoping_and_linearity() {
+        assert_eq!(sum("1/n - 1/(n+1)", 1, "inf").value, parse("1"));
+        assert_eq!(sum("1/n^2 + 1/2^n", 1, "inf").value, simplified("pi^2/6 + 1"));
+    }
+
+    #[test]
+    fn test_divergent_series() {
+        let result = sum("1/n", 1, "inf");
+        assert_eq!(result.value, pos_inf());
+        assert_eq!(result.convergence, Convergence::Divergent);
+
+        assert_eq!(sum("-1/n", 1, "inf").value, Expression::Constant(MathConstant::NegativeInfinity));
+        assert_eq!(sum("2^n", 0, "inf").value, pos_inf());
+        assert_eq!(sum("n/(n^2 + 1)", 1, "inf").value, pos_inf());
+        assert_eq!(sum("n!/2^n", 1, "inf").value, pos_inf());
+        assert_eq!(sum("1/n + 1/n^2", 1, "inf").value, pos_inf());
+        assert_eq!(sum("5", 1, "inf").value, pos_inf());
+        // 根式与实数次幂按 p-级数判别
+        assert_eq!(sum("1/sqrt(n)", 1, "inf").value, pos_inf());
+        assert_eq!(sum("n^(-1/2)", 1, "inf").value, pos_inf());
+        assert_eq!(sum("-1/sqrt(n + 1)", 1, "inf").value, Expression::Constant(MathConstant::NegativeInfinity));
+    }
+
+    #[test]
+    fn test_oscillating_series_not_evaluated() {
+        // 部分和振荡，不能给出有限值或无穷
+        for term in ["(-1)^n", "(-1)^n*n/(n+1)"] {
+            let result = sum(term, 0, "inf");
+            assert_eq!(result.convergence, Convergence::Divergent, "{}", term);
+            assert!(!result.is_evaluated(), "{}", term);
+        }
+    }
+
+    #[test]
+    fn test_convergent_without_closed_form() {
+        for term in ["1/n^3", "(-1)^n/(n^2 + n + 1)", "n!/n^n", "1/n^(3/2)", "1/(n*sqrt(n))", "(-1)^n/sqrt(n)"] {
+            let result = sum(term, 1, "inf");
+            assert_eq!(result.convergence, Convergence::Convergent, "{}", term);
+            assert!(!result.is_evaluated(), "{}", term);
+            assert!(matches!(&result.value, Expression::Function { name, .. } if name == "sum"));
+            assert!(result.notes.iter().any(|note| note.contains("无法求值")));
+        }
+    }
+
+    #[test]
+    fn test_unknown_convergence() {
+        let result = sum("sin(n)", 1, "inf");
+        assert_eq!(result.convergence, Convergence::Unknown);
+        assert!(!result.is_evaluated());
+        assert!(result.notes.iter().any(|note| note.contains("无法求值")));
+    }
+
+    #[test]
+    fn test_undefined_term() {
+        let engine = SummationEngine::new();
+        let zero = Expression::Number(Number::integer(0));
+        assert!(engine.sum(&parse("1/n"), "n", &zero, &pos_inf()).is_err());
+        assert!(engine.sum(&parse("1/(n - 3)^2"), "n", &zero, &pos_inf()).is_err());
+    }
+
+    #[test]
+    fn test_forward_difference() {
+        let engine = SummationEngine::new();
+        let difference = |f: &str, var: &str| engine.forward_difference(&parse(f), var).unwrap();
+        assert_eq!(difference("x^2", "x"), simplified("2*x + 1"));
+        // 多项式差分降次，常数的差分为 0
+        assert_eq!(difference("n^3", "n"), simplified("3*n^2 + 3*n + 1"));
+        assert_eq!(difference("a*n + b", "n"), parse("a"));
+        assert_eq!(difference("5", "n"), parse("0"));
+        assert_eq!(simplified("forward_difference(x^2, x)"), simplified("2*x + 1"));
+    }
+
+    #[test]
+    fn test_difference_sum_inverse() {
+        let engine = SummationEngine::new();
+        assert!(engine.verify_difference_sum(&parse("n^2"), "n", 1, 10).unwrap());
+        assert!(engine.verify_difference_sum(&parse("1/n"), "n", 1, 5).unwrap());
+        assert!(engine.verify_difference_sum(&parse("a*n^3 + 2^n"), "n", 0, 6).unwrap());
+    }
+}
@@ -0,0 +1,130 @@
+//! # 张量函数
+//!
+//! 作用于 N 维数组（见 [`Tensor`]）的函数：`shape`、`reshape`、`flatten`、沿轴求和 `tensor_sum`，
+//! 以及逐元素运算 `tensor_add`、`tensor_subtract`、`tensor_multiply`、`tensor_divide`。
+//!
+//! 向量与矩阵就是 1 维和 2 维张量，`reshape` 也用于二者与高维张量之间的互转。
+//! 逐元素运算的两个参数形状必须相同，其中一个不是数组时看作标量，作用到另一个的每个元素上。
+//! 轴从 1 开始编号，与下标一致。参数还不是具体的数组时保留函数形式，等代入后再求值。
+
+use num_traits::ToPrimitive;
+use crate::core::{BinaryOperator, Expression, Number, Tensor};
+use crate::core::tensor::format_shape;
+use super::ComputeError;
+use super::simplify::Simplifier;
+use crate::tr;
+
+/// 张量函数名
+pub const TENSOR_FUNCTIONS: &[&str] = &[
+    "shape", "reshape", "flatten", "tensor_sum",
+    "tensor_add", "tensor_subtract", "tensor_multiply", "tensor_divide",
+];
+
+/// 张量最多含有的元素个数
+const MAX_ELEMENTS: usize = 1_000_000;
+
+/// 化简张量函数调用；参数还不能求值时返回 `None`，由调用方保留函数形式
+pub fn simplify_call(name: &str, args: &[Expression]) -> Option<Result<Expression, ComputeError>> {
+    match (name, args) {
+        ("shape", [tensor]) => Some(tensor_argument(tensor)?.map(|t| {
+            Expression::Vector(t.shape().iter().map(|&len| Expression::Number(Number::integer(len))).collect())
+        })),
+        ("flatten", [tensor]) => Some(tensor_argument(tensor)?.map(|t| Expression::Vector(t.into_elements()))),
+        ("reshape", [tensor, shape]) => {
+            let tensor = tensor_argument(tensor)?;
+            let shape = positive_integers(shape)?;
+            Some(tensor.and_then(|t| reshape(&t, shape?)))
+        }
+        ("tensor_sum", [tensor, axis]) => {
+            let tensor = tensor_argument(tensor)?;
+            let axis = positive_integers(axis)?;
+            Some(tensor.and_then(|t| sum(&t, axis?)))
+        }
+        ("tensor_add", [a, b]) => elementwise(BinaryOperator::Add, a, b),
+        ("tensor_subtract", [a, b]) => elementwise(BinaryOperator::Subtract, a, b),
+        ("tensor_multiply", [a, b]) => elementwise(BinaryOperator::Multiply, a, b),
+        ("tensor_divide", [a, b]) => elementwise(BinaryOperator::Divide, a, b),
+        _ => None,
+    }
+}
+
+/// 数组参数读成张量，各层长度不一致时报错；不是数组时返回 `None`
+fn tensor_argument(expr: &Expression) -> Option<Result<Tensor, ComputeError>> {
+    is_array(expr).then(|| Tensor::from_nested(expr).map_err(ComputeError::dimension_mismatch))
+}
+
+/// 正整数或正整数组成的向量；含符号时返回 `None`，有非正整数时报错
+fn positive_integers(expr: &Expression) -> Option<Result<Vec<usize>, ComputeError>> {
+    let values = match expr {
+        Expression::Vector(values) => values.as_slice(),
+        _ => std::slice::from_ref(expr),
+    };
+    let mut result = Vec::with_capacity(values.len());
+    for value in values {
+        match value {
+            Expression::Number(Number::Integer(i)) if i.to_usize().is_some_and(|i| i >= 1) => result.push(i.to_usize().unwrap()),
+            Expression::Number(_) => {
+                return Some(Err(ComputeError::domain_error(tr!("tensor.positive_integers", value = value))));
+            }
+            _ => return None,
+        }
+    }
+    Some(Ok(result))
+}
+
+/// `reshape(T, [d1, d2, ...])`：保持元素顺序改为新形状
+fn reshape(tensor: &Tensor, shape: Vec<usize>) -> Result<Expression, ComputeError> {
+    if shape.iter().try_fold(1usize, |size, &len| size.checked_mul(len)).is_none_or(|size| size > MAX_ELEMENTS) {
+        return Err(ComputeError::domain_error(tr!("tensor.too_many_elements", shape = format_shape(&shape))));
+    }
+    tensor.reshape(shape).map(|t| t.to_expression()).map_err(ComputeError::dimension_mismatch)
+}
+
+/// `tensor_sum(T, axis)`：沿第 axis 维求和，结果少一维
+fn sum(tensor: &Tensor, axis: Vec<usize>) -> Result<Expression, ComputeError> {
+    let [axis] = axis[..] else {
+        return Err(ComputeError::domain_error(tr!("tensor.single_axis")));
+    };
+    let summed = tensor.sum_axis(axis - 1).map_err(ComputeError::dimension_mismatch)?;
+    let shape = summed.shape().to_vec();
+    let elements = summed.into_elements().into_iter().map(simplify).collect::<Result<_, _>>()?;
+    Ok(Tensor::new(shape, elements).map_err(ComputeError::dimension_mismatch)?.to_expression())
+}
+
+/// 逐元素运算；两个参数都不是数组时返回 `None`
+fn elementwise(op: BinaryOperator, a: &Expression, b: &Expression) -> Option<Result<Expression, ComputeError>> {
+    (is_array(a) || is_array(b)).then(|| combine(op, a, b))
+}
+
+/// 逐元素组合两个张量，标量作用到另一个的每个元素上
+fn combine(op: BinaryOperator, a: &Expression, b: &Expression) -> Result<Expression, ComputeError> {
+    let left = Tensor::from_nested(a).map_err(ComputeError::dimension_mismatch)?;
+    let right = Tensor::from_nested(b).map_err(ComputeError::dimension_mismatch)?;
+    let shape = match (left.rank(), right.rank()) {
+        (0, _) => right.shape().to_vec(),
+        (_, 0) => left.shape().to_vec(),
+        _ if left.shape() == right.shape() => left.shape().to_vec(),
+        _ => {
+            return Err(ComputeError::dimension_mismatch(tr!(
+                "tensor.elementwise_shapes", left = format_shape(left.shape()), right = format_shape(right.shape())
+            )));
+        }
+    };
+    let element = |t: &Tensor, k: usize| t.elements()[if t.rank() == 0 { 0 } else { k }].clone();
+    let elements = (0..shape.iter().product())
+        .map(|k| simplify(Expression::binary_op(op.clone(), element(&left, k), element(&right, k))))
+        .collect::<Result<_, _>>()?;
+    Ok(Tensor::new(shape, elements).map_err(ComputeError::dimension_mismatch)?.to_expression())
+}
+
+fn is_array(expr: &Expression) -> bool {
+    matches!(expr, Expression::Vector(_) | Expression::Matrix(_))
+}
+
+fn simplify(expr: Expression) -> Result<Expression, ComputeError> {
+    Simplifier::new().simplify(&expr)
+}
+
+#[cfg(test)]
+#[path = "tensors_tests.rs"]
+mod tensors_tests;
@@ -0,0 +1,67 @@
+//! # 张量函数测试
+//!
+//! 测试嵌套字面量的解析与长度校验、多维下标、逐元素运算与标量广播、沿轴求和，
+//! 以及 reshape 在张量、矩阵与向量之间的互转。
+
+#[cfg(test)]
+mod tests {
+    use crate::engine::simplify::Simplifier;
+    use crate::engine::ComputeError;
+    use crate::core::Expression;
+    use crate::parser::{Parser, syntax::ExpressionParser};
+    use crate::test_utils::parse;
+
+    const CUBE: &str = "[[[1, 2], [3, 4]], [[5, 6], [7, 8]]]";
+
+    fn simplify(input: &str) -> Result<Expression, ComputeError> {
+        Simplifier::new().simplify(&parse(input))
+    }
+
+    fn simplified(input: &str) -> String {
+        simplify(input).unwrap().to_string()
+    }
+
+    #[test]
+    fn test_literal_and_index() {
+        assert_eq!(parse(CUBE).to_string(), CUBE);
+        assert_eq!(simplified(&format!("shape({})", CUBE)), "[2, 2, 2]");
+        assert!(ExpressionParser::new().parse("[[[1, 2], [3, 4]], [[5, 6], [7]]]").is_err());
+        assert!(ExpressionParser::new().parse("[[[1, 2], [3, 4]], [[5, 6]]]").is_err());
+        assert!(ExpressionParser::new().parse("[[[1, 2], 3]]").is_err());
+
+        assert_eq!(simplified(&format!("{}[2, 1, 2]", CUBE)), "6");
+        assert_eq!(simplify(&format!("{}[2]", CUBE)).unwrap(), parse("[[5, 6], [7, 8]]"));
+        assert_eq!(simplify(&format!("{}[:, 1, 2]", CUBE)).unwrap(), parse("[2, 6]"));
+        assert!(matches!(simplify(&format!("{}[1, 1]", CUBE)), Err(ComputeError::DimensionMismatch { .. })));
+        assert!(matches!(simplify(&format!("{}[1, 3, 1]", CUBE)), Err(ComputeError::DimensionMismatch { .. })));
+        assert_eq!(simplified(&format!("{}[1, k, 1]", CUBE)), format!("{}[1, k, 1]", CUBE));
+    }
+
+    #[test]
+    fn test_elementwise() {
+        assert_eq!(simplify(&format!("tensor_add({0}, {0})", CUBE)).unwrap(), parse("[[[2, 4], [6, 8]], [[10, 12], [14, 16]]]"));
+        assert_eq!(simplify(&format!("tensor_multiply(2, {})", CUBE)).unwrap(), parse("[[[2, 4], [6, 8]], [[10, 12], [14, 16]]]"));
+        assert_eq!(simplify("tensor_multiply([[1, 2], [3, 4]], [[1, 2], [3, 4]])").unwrap(), parse("[[1, 4], [9, 16]]"));
+        assert_eq!(simplified("tensor_subtract([x, y], 1)"), simplified("[x - 1, y - 1]"));
+        assert_eq!(simplify("tensor_divide([2, 4], 2)").unwrap(), parse("[1, 2]"));
+        assert!(matches!(simplify(&format!("tensor_add({}, [[1, 2], [3, 4]])", CUBE)), Err(ComputeError::DimensionMismatch { .. })));
+        assert_eq!(simplified("tensor_add(a, b)"), "tensor_add(a, b)");
+    }
+
+    #[test]
+    fn test_sum_and_reshape() {
+        assert_eq!(simplify(&format!("tensor_sum({}, 1)", CUBE)).unwrap(), parse("[[6, 8], [10, 12]]"));
+        assert_eq!(simplify(&format!("tensor_sum({}, 3)", CUBE)).unwrap(), parse("[[3, 7], [11, 15]]"));
+        assert_eq!(simplified("tensor_sum([1, 2, 3], 1)"), "6");
+        assert!(matches!(simplify(&format!("tensor_sum({}, 4)", CUBE)), Err(ComputeError::DimensionMismatch { .. })));
+        assert!(matches!(simplify(&format!("tensor_sum({}, 0)", CUBE)), Err(ComputeError::DomainError { .. })));
+
+        // 张量、矩阵与向量之间互转
+        assert_eq!(simplify(&format!("reshape({}, [2, 4])", CUBE)).unwrap(), parse("[[1, 2, 3, 4], [5, 6, 7, 8]]"));
+        assert_eq!(simplify(&format!("flatten({})", CUBE)).unwrap(), parse("[1, 2, 3, 4, 5, 6, 7, 8]"));
+        assert_eq!(simplify("reshape([1, 2, 3, 4, 5, 6, 7, 8], [2, 2, 2])").unwrap(), parse(CUBE));
+        assert_eq!(simplify("reshape([[1, 2], [3, 4]], 4)").unwrap(), parse("[1, 2, 3, 4]"));
+        assert!(matches!(simplify(&format!("reshape({}, [3, 3])", CUBE)), Err(ComputeError::DimensionMismatch { .. })));
+        assert_eq!(simplified("reshape([1, 2, 3, 4], [n, 2])"), "reshape([1, 2, 3, 4], [n, 2])");
+    }
+}
@@ -0,0 +1,106 @@
+//! # 化简规则追踪
+//!
+//! 记录化简过程中每条生效的规则，用于调试化简结果。
+//! 只有通过 `simplify_traced` 开启追踪时才会记录，普通化简不产生任何额外开销。
+
+use std::fmt;
+use std::time::Duration;
+use crate::core::Expression;
+use crate::tr;
+
+/// 按规则筛选追踪输出的环境变量
+pub const TRACE_FILTER_ENV: &str = "YUFMATH_TRACE_FILTER";
+
+/// 一条规则的应用记录
+#[derive(Debug, Clone, PartialEq)]
+pub struct TraceEvent {
+    /// 规则标识，形如 `binary.add`、`func.sin`、`enhanced.radicals`
+    pub rule: String,
+    /// 应用位置：从根出发依次进入的子表达式下标，矩阵元素占两级（行、列）
+    pub path: Vec<usize>,
+    /// 应用前的片段（子表达式已化简）
+    pub before: Expression,
+    /// 应用后的片段
+    pub after: Expression,
+    /// 本条规则耗时，不含子表达式的化简
+    pub elapsed: Duration,
+}
+
+impl TraceEvent {
+    /// 位置的文本形式，根为 `根`，其余如 `0.1`
+    pub fn path_string(&self) -> String {
+        if self.path.is_empty() {
+            tr!("trace.root").to_string()
+        } else {
+            self.path.iter().map(|i| i.to_string()).collect::<Vec<_>>().join(".")
+        }
+    }
+
+    /// 规则是否匹配筛选条件：完全相同，或筛选条件是规则的类别前缀（如 `binary`）
+    pub fn matches_filter(&self, filter: &str) -> bool {
+        self.rule == filter
+            || self.rule.strip_prefix(filter).is_some_and(|rest| rest.starts_with('.'))
+    }
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} @ {}: {} → {} ({:?})", self.rule, self.path_string(), self.before, self.after, self.elapsed)
+    }
+}
+
+/// 追踪状态，由化简器在追踪期间持有
+#[derive(Debug, Default)]
+pub(crate) struct Tracer {
+    events: Vec<TraceEvent>,
+    path: Vec<usize>,
+}
+
+impl Tracer {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// 进入第 `index` 个子表达式
+    pub(crate) fn enter(&mut self, index: usize) {
+        self.path.push(index);
+    }
+
+    /// 返回上一级
+    pub(crate) fn leave(&mut self) {
+        self.path.pop();
+    }
+
+    /// 在当前位置记录一条规则，结果未变化时不记录
+    pub(crate) fn record(&mut self, rule: String, before: Expression, after: &Expression, elapsed: Duration) {
+        if &before != after {
+            self.events.push(TraceEvent {
+                rule,
+                path: self.path.clone(),
+                before,
+                after: after.clone(),
+                elapsed,
+            });
+        }
+    }
+
+    pub(crate) fn into_events(self) -> Vec<TraceEvent> {
+        self.events
+    }
+}
+
+/// 把 `MatrixMultiply` 这样的变体名转换为 `matrix_multiply`
+pub(crate) fn rule_suffix(variant: &str) -> String {
+    let mut name = String::with_capacity(variant.len() + 4);
+    for (i, c) in variant.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                name.push('_');
+            }
+            name.extend(c.to_lowercase());
+        } else {
+            name.push(c);
+        }
+    }
+    name
+}
@@ -0,0 +1,619 @@
+//! # 三角变换
+//!
+//! 显式的三角恒等变换入口，不会在 simplify 中自动触发：
+//! - [`trig_expand`]：和角、倍角展开为单角 sin/cos 的多项式
+//! - [`trig_factor`]：和差化积，`sin(a) + sin(b) = 2 sin((a+b)/2) cos((a-b)/2)`
+//! - [`trig_reduce`]：积化和差，`sin(a) cos(b) = (sin(a+b) + sin(a-b))/2`
+//! - [`trig_to_exp`] / [`exp_to_trig`]：三角函数与复指数互化
+//!
+//! 每个变换完成后在一组采样点上按复数求值比较前后两式，不一致时报内部错误。
+
+use std::collections::HashMap;
+use num_complex::Complex64;
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator, MathConstant};
+use super::ComputeError;
+use super::polynomial::PolynomialEngine;
+use super::simplify::Simplifier;
+use crate::tr;
+
+/// 倍角展开允许的最大倍数
+const MAX_MULTIPLE: u32 = 64;
+
+/// 积化和差时展开的最高次幂
+const MAX_REDUCE_POWER: u32 = 16;
+
+/// 采样值，各变量错开取值
+const SAMPLES: [f64; 7] = [0.37, -1.21, 2.03, 0.81, -0.59, 1.47, 2.71];
+
+/// 数值比较的相对容差
+const TOLERANCE: f64 = 1e-8;
+
+/// 展开和角与倍角：`sin(x+y)`、`cos(2x)`、`tan(x-y)` 等写成单角的组合
+pub fn trig_expand(expr: &Expression) -> Result<Expression, ComputeError> {
+    let expanded = expand_angles(expr)?;
+    let result = expand_products(&Simplifier::new().simplify(&expanded)?)?;
+    verify(expr, &result)?;
+    Ok(result)
+}
+
+/// 和差化积：把和式中同名的 `c·sin`、`c·cos` 两两写成乘积
+pub fn trig_factor(expr: &Expression) -> Result<Expression, ComputeError> {
+    let result = Simplifier::new().simplify(&factor_sums(expr)?)?;
+    verify(expr, &result)?;
+    Ok(result)
+}
+
+/// 积化和差：把 sin、cos 的乘积与幂写成单个 sin、cos 的线性组合
+pub fn trig_reduce(expr: &Expression) -> Result<Expression, ComputeError> {
+    let result = expand_products(&reduce_products(expr)?)?;
+    verify(expr, &result)?;
+    Ok(result)
+}
+
+/// 把三角函数与双曲函数改写为指数形式，如 `sin(x) = (exp(i·x) - exp(-i·x)) / (2i)`
+pub fn trig_to_exp(expr: &Expression) -> Result<Expression, ComputeError> {
+    let result = Simplifier::new().simplify(&rewrite(expr, &|name, arg| to_exp(name, arg))?)?;
+    verify(expr, &result)?;
+    Ok(result)
+}
+
+/// 把含虚部的指数改写为三角函数：`exp(a + i·b) = exp(a)·(cos(b) + i·sin(b))`
+pub fn exp_to_trig(expr: &Expression) -> Result<Expression, ComputeError> {
+    let result = Simplifier::new().simplify(&rewrite(expr, &|name, arg| from_exp(name, arg))?)?;
+    verify(expr, &result)?;
+    Ok(result)
+}
+
+/// 识别单参数函数调用，`√`、`|x|` 与同名的 `Function` 视为同一函数
+fn call(expr: &Expression) -> Option<(&str, &Expression)> {
+    match expr {
+        Expression::Function { name, args } if args.len() == 1 => Some((name.as_str(), &args[0])),
+        Expression::UnaryOp { op, operand } => {
+            let name = match op {
+                UnaryOperator::Sqrt => "sqrt",
+                UnaryOperator::Abs => "abs",
+                _ => op.function_name()?,
+            };
+            Some((name, operand))
+        }
+        // e^x 与 exp(x) 相同
+        Expression::BinaryOp { op: BinaryOperator::Power, left, right } if matches!(left.as_ref(), Expression::Constant(MathConstant::E)) => {
+            Some(("exp", right))
+        }
+        _ => None,
+    }
+}
+
+fn function(name: &str, arg: Expression) -> Expression {
+    Expression::function(name, vec![arg])
+}
+
+fn number(value: i64) -> Expression {
+    Expression::Number(Number::integer(value))
+}
+
+/// `sin`、`cos` 按奇偶性去掉角的负号：`cos(-u) = cos(u)`，`sin(-u) = -sin(u)`
+fn signed_call(name: &str, angle: Expression) -> Expression {
+    let negated = match &angle {
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => Some(operand.as_ref().clone()),
+        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => match left.as_ref() {
+            Expression::Number(c) if c.is_negative() => Some(Expression::multiply(Expression::Number(-c.clone()), right.as_ref().clone())),
+            _ => None,
+        },
+        Expression::Number(c) if c.is_negative() => Some(Expression::Number(-c.clone())),
+        Expression::BinaryOp { op: BinaryOperator::Divide, left, right } => match left.as_ref() {
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => Some(Expression::divide(operand.as_ref().clone(), right.as_ref().clone())),
+            _ => None,
+        },
+        _ => None,
+    };
+    match negated {
+        Some(angle) if name == "sin" => Expression::negate(function(name, angle)),
+        Some(angle) => function(name, angle),
+        None => function(name, angle),
+    }
+}
+
+/// 整理角：合并同类项，并把有理系数提到前面
+fn tidy_angle(angle: &Expression, divisor: i64) -> Result<Expression, ComputeError> {
+    let scaled = Expression::multiply(Expression::Number(Number::rational(1, divisor)), angle.clone());
+    let expanded = expand_products(&scaled)?;
+    // 各项系数都带分母时写成 (和)/除数，更接近手写形式
+    if divisor != 1 {
+        let whole = expand_products(angle)?;
+        if !matches!(whole, Expression::Number(_)) && !matches!(&whole, Expression::BinaryOp { op: BinaryOperator::Multiply, left, .. } if matches!(left.as_ref(), Expression::Number(_))) {
+            return Ok(Expression::divide(whole, number(divisor)));
+        }
+    }
+    Ok(expanded)
+}
+
+fn imaginary_unit() -> Expression {
+    Expression::Constant(MathConstant::I)
+}
+
+/// 对子表达式逐个应用 `f`，重建当前节点
+fn map_children(expr: &Expression, f: &mut dyn FnMut(&Expression) -> Result<Expression, ComputeError>) -> Result<Expression, ComputeError> {
+    Ok(match expr {
+        Expression::BinaryOp { op, left, right } => Expression::binary_op(op.clone(), f(left)?, f(right)?),
+        Expression::UnaryOp { op, operand } => Expression::unary_op(op.clone(), f(operand)?),
+        Expression::Function { name, args } => Expression::function(name.clone(), args.iter().map(&mut *f).collect::<Result<_, _>>()?),
+        Expression::Vector(elements) => Expression::Vector(elements.iter().map(&mut *f).collect::<Result<_, _>>()?),
+        Expression::Set(elements) => Expression::Set(elements.iter().map(&mut *f).collect::<Result<_, _>>()?),
+        Expression::Matrix(rows) => Expression::Matrix(rows.iter()
+            .map(|row| row.iter().map(&mut *f).collect::<Result<Vec<_>, _>>())
+            .collect::<Result<_, _>>()?),
+        _ => expr.clone(),
+    })
+}
+
+/// 自底向上改写单参数函数调用；`rule` 返回 `None` 时保留原调用
+fn rewrite(expr: &Expression, rule: &dyn Fn(&str, &Expression) -> Option<Expression>) -> Result<Expression, ComputeError> {
+    if let Some((name, arg)) = call(expr) {
+        let arg = rewrite(arg, rule)?;
+        return Ok(rule(name, &arg).unwrap_or_else(|| match expr {
+            Expression::BinaryOp { .. } => Expression::power(Expression::Constant(MathConstant::E), arg),
+            Expression::UnaryOp { op, .. } => Expression::unary_op(op.clone(), arg),
+            _ => function(name, arg),
+        }));
+    }
+    map_children(expr, &mut |child| rewrite(child, rule))
+}
+
+/// 把和式拆成各项，减去的项包上取负
+fn sum_terms(expr: &Expression, negated: bool, terms: &mut Vec<Expression>) {
+    match expr {
+        Expression::BinaryOp { op: BinaryOperator::Add, left, right } => {
+            sum_terms(left, negated, terms);
+            sum_terms(right, negated, terms);
+        }
+        Expression::BinaryOp { op: BinaryOperator::Subtract, left, right } => {
+            sum_terms(left, negated, terms);
+            sum_terms(right, !negated, terms);
+        }
+        _ if negated => terms.push(Expression::negate(expr.clone())),
+        _ => terms.push(expr.clone()),
+    }
+}
+
+fn rebuild_sum(terms: Vec<Expression>) -> Expression {
+    terms.into_iter().reduce(Expression::add).unwrap_or_else(|| number(0))
+}
+
+/// 整数倍角 `n·u` 中的 `n` 与 `u`
+fn integer_multiple(expr: &Expression) -> Option<(i64, &Expression)> {
+    let Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } = expr else {
+        return None;
+    };
+    let (n, u) = match (left.as_ref(), right.as_ref()) {
+        (Expression::Number(n), u) | (u, Expression::Number(n)) => (n, u),
+        _ => return None,
+    };
+    let n = n.to_integer().and_then(|n| i64::try_from(n).ok())?;
+    (n.abs() >= 2).then_some((n, u))
+}
+
+/// 展开式中的 `sin(angle)` 与 `cos(angle)`
+fn sin_cos(angle: &Expression) -> Result<(Expression, Expression), ComputeError> {
+    match angle {
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+            let (s, c) = sin_cos(operand)?;
+            Ok((Expression::negate(s), c))
+        }
+        Expression::BinaryOp { op: op @ (BinaryOperator::Add | BinaryOperator::Subtract), left, right } => {
+            let (sl, cl) = sin_cos(left)?;
+            let (sr, cr) = sin_cos(right)?;
+            let sin_part = (Expression::multiply(sl.clone(), cr.clone()), Expression::multiply(cl.clone(), sr.clone()));
+            let cos_part = (Expression::multiply(cl, cr), Expression::multiply(sl, sr));
+            Ok(if *op == BinaryOperator::Add {
+                (Expression::add(sin_part.0, sin_part.1), Expression::subtract(cos_part.0, cos_part.1))
+            } else {
+                (Expression::subtract(sin_part.0, sin_part.1), Expression::add(cos_part.0, cos_part.1))
+            })
+        }
+        _ => match integer_multiple(angle) {
+            Some((n, u)) => {
+                if n.unsigned_abs() > u64::from(MAX_MULTIPLE) {
+                    return Err(ComputeError::domain_error(tr!("trigonometry.max_multiple", limit = MAX_MULTIPLE)));
+                }
+                let (s1, c1) = sin_cos(u)?;
+                // 逐次用和角公式，每步展开一次，避免表达式成倍膨胀
+                let (mut s, mut c) = (s1.clone(), c1.clone());
+                for _ in 1..n.abs() {
+                    let next_s = Expression::add(Expression::multiply(s.clone(), c1.clone()), Expression::multiply(c.clone(), s1.clone()));
+                    let next_c = Expression::subtract(Expression::multiply(c, c1.clone()), Expression::multiply(s, s1.clone()));
+                    s = expand_products(&next_s)?;
+                    c = expand_products(&next_c)?;
+                }
+                Ok((if n < 0 { Expression::negate(s) } else { s }, c))
+            }
+            None => Ok((function("sin", angle.clone()), function("cos", angle.clone()))),
+        },
+    }
+}
+
+/// 展开式中的 `tan(angle)`
+fn tan_of(angle: &Expression) -> Result<Expression, ComputeError> {
+    match angle {
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => Ok(Expression::negate(tan_of(operand)?)),
+        Expression::BinaryOp { op: op @ (BinaryOperator::Add | BinaryOperator::Subtract), left, right } => {
+            let (tl, tr) = (tan_of(left)?, tan_of(right)?);
+            let product = Expression::multiply(tl.clone(), tr.clone());
+            Ok(if *op == BinaryOperator::Add {
+                Expression::divide(Expression::add(tl, tr), Expression::subtract(number(1), product))
+            } else {
+                Expression::divide(Expression::subtract(tl, tr), Expression::add(number(1), product))
+            })
+        }
+        _ if integer_multiple(angle).is_some() => {
+            // 倍角的正切用 sin/cos 展开后相除
+            let (s, c) = sin_cos(angle)?;
+            Ok(Expression::divide(s, c))
+        }
+        _ => Ok(function("tan", angle.clone())),
+    }
+}
+
+fn expand_angles(expr: &Expression) -> Result<Expression, ComputeError> {
+    if let Some((name @ ("sin" | "cos" | "tan"), arg)) = call(expr) {
+        let arg = expand_angles(arg)?;
+        return match name {
+            "sin" => Ok(sin_cos(&arg)?.0),
+            "cos" => Ok(sin_cos(&arg)?.1),
+            _ => tan_of(&arg),
+        };
+    }
+    map_children(expr, &mut expand_angles)
+}
+
+/// 把函数调用、常量等看作变量做多项式展开；含除法等无法展开时原样返回
+fn expand_products(expr: &Expression) -> Result<Expression, ComputeError> {
+    let mut atoms: Vec<Expression> = Vec::new();
+    let polynomial = atomize(expr, &mut atoms);
+    let expanded = match PolynomialEngine::new().expression_to_polynomial(&polynomial) {
+        Ok(poly) => poly.to_expression(),
+        Err(ComputeError::ResourceLimit { message }) => return Err(ComputeError::ResourceLimit { message }),
+        Err(_) => return Ok(expr.clone()),
+    };
+    let substitutions: HashMap<String, Expression> = atoms.into_iter().enumerate()
+        .map(|(index, atom)| (atom_name(index), atom))
+        .collect();
+    Simplifier::new().simplify(&expanded.substitute(&substitutions))
+}
+
+fn atom_name(index: usize) -> String {
+    format!("__trig_atom{}", index)
+}
+
+/// 保留加、减、乘、取负和非负整数次幂，其余子式换成占位变量
+fn atomize(expr: &Expression, atoms: &mut Vec<Expression>) -> Expression {
+    match expr {
+        Expression::Number(n) if n.to_rational().is_some() => expr.clone(),
+        Expression::Variable(_) => expr.clone(),
+        Expression::BinaryOp { op: op @ (BinaryOperator::Add | BinaryOperator::Subtract | BinaryOperator::Multiply), left, right } => {
+            Expression::binary_op(op.clone(), atomize(left, atoms), atomize(right, atoms))
+        }
+        Expression::BinaryOp { op: BinaryOperator::Power, left, right }
+            if matches!(right.as_ref(), Expression::Number(n) if n.is_integer() && !n.is_negative()) && call(expr).is_none() =>
+        {
+            Expression::power(atomize(left, atoms), right.as_ref().clone())
+        }
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => Expression::negate(atomize(operand, atoms)),
+        _ => {
+            let index = atoms.iter().position(|atom| atom == expr).unwrap_or_else(|| {
+                atoms.push(expr.clone());
+                atoms.len() - 1
+            });
+            Expression::Variable(atom_name(index))
+        }
+    }
+}
+
+/// `c·f(a)` 形式的项：系数、函数名（sin 或 cos）与角
+fn trig_term(term: &Expression) -> Option<(Number, &'static str, Expression)> {
+    match term {
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
+            trig_term(operand).map(|(c, name, arg)| (-c, name, arg))
+        }
+        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => match (left.as_ref(), right.as_ref()) {
+            (Expression::Number(c), other) | (other, Expression::Number(c)) => {
+                trig_term(other).map(|(k, name, arg)| (c.clone() * k, name, arg))
+            }
+            _ => None,
+        },
+        _ => match call(term)? {
+            ("sin", arg) => Some((Number::one(), "sin", arg.clone())),
+            ("cos", arg) => Some((Number::one(), "cos", arg.clone())),
+            _ => None,
+        },
+    }
+}
+
+fn factor_sums(expr: &Expression) -> Result<Expression, ComputeError> {
+    if !matches!(expr, Expression::BinaryOp { op: BinaryOperator::Add | BinaryOperator::Subtract, .. }) {
+        return map_children(expr, &mut factor_sums);
+    }
+    let mut raw = Vec::new();
+    sum_terms(expr, false, &mut raw);
+    let terms = raw.iter().map(factor_sums).collect::<Result<Vec<_>, _>>()?;
+
+    let parsed: Vec<_> = terms.iter().map(trig_term).collect();
+    let mut used = vec![false; terms.len()];
+    let mut result = Vec::new();
+    for i in 0..terms.len() {
+        if used[i] {
+            continue;
+        }
+        used[i] = true;
+        let Some((ci, fi, a)) = &parsed[i] else {
+            result.push(terms[i].clone());
+            continue;
+        };
+        // 找系数相同或相反的同名项配对
+        let partner = (i + 1..terms.len()).find(|&j| !used[j] && matches!(
+            &parsed[j], Some((cj, fj, _)) if fj == fi && (cj == ci || *cj == -ci.clone())
+        ));
+        let Some(j) = partner else {
+            result.push(terms[i].clone());
+            continue;
+        };
+        used[j] = true;
+        let (cj, _, b) = parsed[j].as_ref().expect("配对项已解析");
+        let half_sum = tidy_angle(&Expression::add(a.clone(), b.clone()), 2)?;
+        let half_difference = tidy_angle(&Expression::subtract(a.clone(), b.clone()), 2)?;
+        let same_sign = cj == ci;
+        let (coefficient, first, second) = match (*fi, same_sign) {
+            ("sin", true) => (2, "sin", "cos"),
+            ("sin", false) => (2, "cos", "sin"),
+            ("cos", true) => (2, "cos", "cos"),
+            _ => (-2, "sin", "sin"),
+        };
+        result.push(Expression::multiply(
+            Expression::multiply(Expression::Number(ci.clone() * Number::integer(coefficient)), signed_call(first, half_sum)),
+            signed_call(second, half_difference),
+        ));
+    }
+    Ok(rebuild_sum(result))
+}
+
+/// 把乘积拆成因子，`f(a)^n` 展开成 n 个相同因子
+fn product_factors(expr: &Expression, factors: &mut Vec<Expression>) {
+    match expr {
+        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
+            product_factors(left, factors);
+            product_factors(right, factors);
+        }
+        Expression::BinaryOp { op: BinaryOperator::Power, left, right } if matches!(call(left), Some(("sin" | "cos", _))) => {
+            match right.as_ref() {
+                Expression::Number(n) if n.is_integer() && n.is_positive() && n.to_integer().is_some_and(|k| k <= MAX_REDUCE_POWER.into()) => {
+                    let count = n.to_f64().unwrap_or(1.0) as usize;
+                    factors.extend((0..count).map(|_| left.as_ref().clone()));
+                }
+                _ => factors.push(expr.clone()),
+            }
+        }
+        _ => factors.push(expr.clone()),
+    }
+}
+
+/// 两个 sin/cos 因子之积写成和：返回 (系数符号, 函数名, 角) 的两项，整体再乘 1/2
+fn product_to_sum(first: (&str, &Expression), second: (&str, &Expression)) -> [(i64, &'static str, Expression); 2] {
+    let (f, a) = first;
+    let (g, b) = second;
+    let sum = Expression::add(a.clone(), b.clone());
+    let difference = Expression::subtract(a.clone(), b.clone());
+    match (f, g) {
+        ("sin", "sin") => [(1, "cos", difference), (-1, "cos", sum)],
+        ("cos", "cos") => [(1, "cos", difference), (1, "cos", sum)],
+        ("sin", _) => [(1, "sin", sum), (1, "sin", difference)],
+        _ => [(1, "sin", sum), (-1, "sin", difference)],
+    }
+}
+
+fn reduce_products(expr: &Expression) -> Result<Expression, ComputeError> {
+    let is_product = matches!(expr, Expression::BinaryOp { op: BinaryOperator::Multiply, .. })
+        || matches!(expr, Expression::BinaryOp { op: BinaryOperator::Power, left, .. } if matches!(call(left), Some(("sin" | "cos", _))));
+    if !is_product {
+        return map_children(expr, &mut reduce_products);
+    }
+    let mut raw = Vec::new();
+    product_factors(expr, &mut raw);
+    let factors = raw.iter().map(reduce_products).collect::<Result<Vec<_>, _>>()?;
+    reduce_factors(factors)
+}
+
+/// 每次取两个 sin/cos 因子化为和，再把其余因子分配到和的各项上，直到每项至多一个 sin/cos
+fn reduce_factors(factors: Vec<Expression>) -> Result<Expression, ComputeError> {
+    let trig: Vec<usize> = factors.iter().enumerate()
+        .filter(|(_, factor)| matches!(call(factor), Some(("sin" | "cos", _))))
+        .map(|(index, _)| index)
+        .take(2)
+        .collect();
+    if trig.len() < 2 {
+        return Ok(factors.into_iter().reduce(Expression::multiply).unwrap_or_else(|| number(1)));
+    }
+    let (Some(first), Some(second)) = (call(&factors[trig[0]]), call(&factors[trig[1]])) else {
+        unreachable!("已按函数名筛选")
+    };
+    let terms = product_to_sum(first, second);
+    let rest: Vec<Expression> = factors.iter().enumerate()
+        .filter(|(index, _)| !trig.contains(index))
+        .map(|(_, factor)| factor.clone())
+        .collect();
+    let mut sum = Vec::new();
+    for (sign, name, angle) in terms {
+        let mut next = rest.clone();
+        next.push(signed_call(name, tidy_angle(&angle, 1)?));
+        let reduced = Expression::multiply(Expression::Number(Number::rational(sign, 2)), reduce_factors(next)?);
+        sum.push(reduced);
+    }
+    Ok(rebuild_sum(sum))
+}
+
+fn to_exp(name: &str, arg: &Expression) -> Option<Expression> {
+    let i = imaginary_unit;
+    let exp = |x: Expression| function("exp", x);
+    let (plus, minus) = match name {
+        "sin" | "cos" | "tan" | "sec" | "csc" | "cot" => {
+            let ix = Expression::multiply(i(), arg.clone());
+            (exp(ix.clone()), exp(Expression::negate(ix)))
+        }
+        "sinh" | "cosh" | "tanh" => (exp(arg.clone()), exp(Expression::negate(arg.clone()))),
+        _ => return None,
+    };
+    let difference = || Expression::subtract(plus.clone(), minus.clone());
+    let sum = || Expression::add(plus.clone(), minus.clone());
+    let two_i = || Expression::multiply(number(2), i());
+    Some(match name {
+        "sin" => Expression::divide(difference(), two_i()),
+        "cos" => Expression::divide(sum(), number(2)),
+        "tan" => Expression::divide(difference(), Expression::multiply(i(), sum())),
+        "csc" => Expression::divide(two_i(), difference()),
+        "sec" => Expression::divide(number(2), sum()),
+        "cot" => Expression::divide(Expression::multiply(i(), sum()), difference()),
+        "sinh" => Expression::divide(difference(), number(2)),
+        "cosh" => Expression::divide(sum(), number(2)),
+        _ => Expression::divide(difference(), sum()),
+    })
+}
+
+fn from_exp(name: &str, arg: &Expression) -> Option<Expression> {
+    if name != "exp" {
+        return None;
+    }
+    let mut terms = Vec::new();
+    sum_terms(arg, false, &mut terms);
+    let (mut real, mut imaginary) = (Vec::new(), Vec::new());
+    for term in terms {
+        match imaginary_coefficient(&term) {
+            Some(coefficient) => imaginary.push(coefficient),
+            None => real.push(term),
+        }
+    }
+    if imaginary.is_empty() {
+        return None;
+    }
+    let angle = rebuild_sum(imaginary);
+    let trig = Expression::add(
+        function("cos", angle.clone()),
+        Expression::multiply(imaginary_unit(), function("sin", angle)),
+    );
+    // 用 e^a 而不是 exp(a)，化简时 a 为数值也保持精确形式
+    Some(if real.is_empty() { trig } else { Expression::multiply(Expression::power(Expression::Constant(MathConstant::E), rebuild_sum(real)), trig) })
+}
+
+/// 纯虚项 `b·i` 中的实系数 `b`；不是纯虚项时返回 `None`
+fn imaginary_coefficient(term: &Expression) -> Option<Expression> {
+    match term {
+        Expression::Constant(MathConstant::I) | Expression::Number(Number::Constant(MathConstant::I)) => Some(number(1)),
+        Expression::Number(Number::Complex { real, imaginary }) if real.is_zero() => Some(Expression::Number(imaginary.as_ref().clone())),
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => imaginary_coefficient(operand).map(Expression::negate),
+        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
+            if let Some(c) = imaginary_coefficient(left) {
+                (!contains_imaginary(right)).then(|| Expression::multiply(c, right.as_ref().clone()))
+            } else if let Some(c) = imaginary_coefficient(right) {
+                (!contains_imaginary(left)).then(|| Expression::multiply(left.as_ref().clone(), c))
+            } else {
+                None
+            }
+        }
+        Expression::BinaryOp { op: BinaryOperator::Divide, left, right } if !contains_imaginary(right) => {
+            imaginary_coefficient(left).map(|c| Expression::divide(c, right.as_ref().clone()))
+        }
+        _ => None,
+    }
+}
+
+fn contains_imaginary(expr: &Expression) -> bool {
+    match expr {
+        Expression::Constant(MathConstant::I) | Expression::Number(Number::Constant(MathConstant::I)) => true,
+        Expression::Number(Number::Complex { imaginary, .. }) => !imaginary.is_zero(),
+        Expression::BinaryOp { left, right, .. } => contains_imaginary(left) || contains_imaginary(right),
+        Expression::UnaryOp { operand, .. } => contains_imaginary(operand),
+        Expression::Function { args, .. } => args.iter().any(contains_imaginary),
+        _ => false,
+    }
+}
+
+/// 在采样点上比较变换前后两式，任何一点明显不等即报错；无法求值的点跳过
+fn verify(original: &Expression, transformed: &Expression) -> Result<(), ComputeError> {
+    let mut variables = original.get_variables();
+    variables.extend(transformed.get_variables());
+    variables.sort();
+    variables.dedup();
+    for round in 0..SAMPLES.len() {
+        let values: HashMap<String, Complex64> = variables.iter().enumerate()
+            .map(|(k, name)| (name.clone(), Complex64::new(SAMPLES[(round + 3 * k) % SAMPLES.len()], 0.0)))
+            .collect();
+        let (Some(left), Some(right)) = (evaluate(original, &values), evaluate(transformed, &values)) else {
+            continue;
+        };
+        if !(left.is_finite() && right.is_finite()) {
+            continue;
+        }
+        if (left - right).norm() > TOLERANCE * left.norm().max(right.norm()).max(1.0) {
+            return Err(ComputeError::internal(tr!("trigonometry.mismatch", transformed = transformed, original = original)));
+        }
+    }
+    Ok(())
+}
+
+fn constant_value(constant: &MathConstant) -> Option<Complex64> {
+    Some(match constant {
+        MathConstant::Pi => Complex64::new(std::f64::consts::PI, 0.0),
+        MathConstant::E => Complex64::new(std::f64::consts::E, 0.0),
+        MathConstant::I => Complex64::i(),
+        MathConstant::EulerGamma => Complex64::new(0.577_215_664_901_532_9, 0.0),
+        MathConstant::GoldenRatio => Complex64::new(1.618_033_988_749_895, 0.0),
+        MathConstant::Catalan => Complex64::new(0.915_965_594_177_219, 0.0),
+        _ => return None,
+    })
+}
+
+/// 按复数求值，遇到无法求值的子式返回 `None`
+pub(crate) fn evaluate(expr: &Expression, values: &HashMap<String, Complex64>) -> Option<Complex64> {
+    if let Some((name, arg)) = call(expr) {
+        let z = evaluate(arg, values)?;
+        return Some(match name {
+            "sin" => z.sin(),
+            "cos" => z.cos(),
+            "tan" => z.tan(),
+            "sec" => z.cos().inv(),
+            "csc" => z.sin().inv(),
+            "cot" => z.tan().inv(),
+            "sinh" => z.sinh(),
+            "cosh" => z.cosh(),
+            "tanh" => z.tanh(),
+            "exp" => z.exp(),
+            "ln" => z.ln(),
+            "sqrt" => z.sqrt(),
+            "abs" => Complex64::new(z.norm(), 0.0),
+            _ => return None,
+        });
+    }
+    match expr {
+        Expression::Number(Number::Complex { real, imaginary }) => Some(Complex64::new(real.to_f64()?, imaginary.to_f64()?)),
+        Expression::Number(Number::Constant(c)) | Expression::Constant(c) => constant_value(c),
+        Expression::Number(n) => n.to_f64().map(|x| Complex64::new(x, 0.0)),
+        Expression::Variable(name) => values.get(name).copied(),
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => evaluate(operand, values).map(|z| -z),
+        Expression::UnaryOp { op: UnaryOperator::Plus, operand } => evaluate(operand, values),
+        Expression::BinaryOp { op, left, right } => {
+            let (l, r) = (evaluate(left, values)?, evaluate(right, values)?);
+            match op {
+                BinaryOperator::Add => Some(l + r),
+                BinaryOperator::Subtract => Some(l - r),
+                BinaryOperator::Multiply => Some(l * r),
+                BinaryOperator::Divide => Some(l / r),
+                BinaryOperator::Power if r.im == 0.0 && r.re.fract() == 0.0 && r.re.abs() < 1e6 => Some(l.powi(r.re as i32)),
+                BinaryOperator::Power => Some(l.powc(r)),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[path = "trigonometry_tests.rs"]
+mod trigonometry_tests;
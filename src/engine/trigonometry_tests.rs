@@ -0,0 +1,91 @@
+//! # 三角变换测试
+//!
+//! 测试和角倍角展开、和差化积、积化和差与指数形式的互化，
+//! 每个变换的结果都在采样点上按复数求值与原式比较。
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use num_complex::Complex64;
+    use crate::engine::trigonometry::{trig_expand, trig_factor, trig_reduce, trig_to_exp, exp_to_trig, evaluate};
+    use crate::engine::ComputeError;
+    use crate::core::Expression;
+    use crate::test_utils::parse;
+
+    type Transform = fn(&Expression) -> Result<Expression, ComputeError>;
+
+    fn shown(transform: Transform, input: &str) -> String {
+        transform(&parse(input)).unwrap().to_string()
+    }
+
+    /// 在若干点上比较变换前后的值
+    fn assert_equivalent(transform: Transform, input: &str) {
+        let original = parse(input);
+        let transformed = transform(&original).unwrap();
+        for (x, y) in [(0.3, 1.7), (-2.2, 0.9), (1.1, -0.4)] {
+            let values = HashMap::from([
+                ("x".to_string(), Complex64::new(x, 0.0)),
+                ("y".to_string(), Complex64::new(y, 0.0)),
+            ]);
+            let (left, right) = (evaluate(&original, &values).unwrap(), evaluate(&transformed, &values).unwrap());
+            assert!((left - right).norm() < 1e-9, "{} => {}: {} ≠ {}", input, transformed, left, right);
+        }
+    }
+
+    #[test]
+    fn test_trig_expand() {
+        assert_eq!(shown(trig_expand, "sin(x+y)"), "sin(x) * cos(y) + cos(x) * sin(y)");
+        assert_eq!(shown(trig_expand, "cos(x-y)"), "cos(x) * cos(y) + sin(x) * sin(y)");
+        assert_eq!(shown(trig_expand, "cos(2*x)"), "cos(x)^2 - sin(x)^2");
+        assert_eq!(shown(trig_expand, "sin(3*x)"), "3 * sin(x) * cos(x)^2 - sin(x)^3");
+        assert_eq!(shown(trig_expand, "tan(x+y)"), "(tan(x) + tan(y)) / (1 - tan(x) * tan(y))");
+        assert_eq!(shown(trig_expand, "sin(-x)"), "-sin(x)");
+        // 单角的式子不变
+        assert_eq!(shown(trig_expand, "sin(x) * cos(y)"), "sin(x) * cos(y)");
+        for input in ["sin(5*x)", "cos(4*x-y)", "tan(2*x)", "3*sin(x)+3*sin(2*x)+cos(x)"] {
+            assert_equivalent(trig_expand, input);
+        }
+        assert!(matches!(trig_expand(&parse("sin(100*x)")), Err(ComputeError::DomainError { .. })));
+    }
+
+    #[test]
+    fn test_trig_factor() {
+        assert_eq!(shown(trig_factor, "sin(x)+sin(y)"), "2 * sin((x + y) / 2) * cos((x - y) / 2)");
+        assert_eq!(shown(trig_factor, "sin(x)-sin(y)"), "2 * cos((x + y) / 2) * sin((x - y) / 2)");
+        assert_eq!(shown(trig_factor, "cos(x)+cos(y)"), "2 * cos((x + y) / 2) * cos((x - y) / 2)");
+        assert_eq!(shown(trig_factor, "cos(3*x)-cos(x)"), "-2 * sin(2 * x) * sin(x)");
+        // 没有可配对的项时不变
+        assert_eq!(shown(trig_factor, "sin(x)+cos(y)"), "sin(x) + cos(y)");
+        for input in ["3*sin(x)+3*sin(2*x)+cos(x)", "cos(x)-cos(y)+sin(x)", "2*cos(x)-2*cos(3*y)"] {
+            assert_equivalent(trig_factor, input);
+        }
+    }
+
+    #[test]
+    fn test_trig_reduce() {
+        assert_eq!(shown(trig_reduce, "sin(x)*cos(y)"), "1/2 * sin(x + y) + 1/2 * sin(x - y)");
+        assert_eq!(shown(trig_reduce, "sin(x)^2"), "1/2 - 1/2 * cos(2 * x)");
+        assert_eq!(shown(trig_reduce, "cos(x)^3"), "3/4 * cos(x) + 1/4 * cos(3 * x)");
+        for input in ["2*sin(x)*sin(y)*cos(x)", "sin(x)^4", "x*cos(x)*cos(2*x)"] {
+            assert_equivalent(trig_reduce, input);
+        }
+    }
+
+    #[test]
+    fn test_exponential_form() {
+        assert_eq!(shown(trig_to_exp, "sin(x)"), "(exp(i * x) - exp(-i * x)) / (2 * i)");
+        assert_eq!(shown(trig_to_exp, "cosh(x)"), "(exp(x) + exp(-x)) / 2");
+        assert_eq!(shown(exp_to_trig, "exp(i*x)"), "cos(x) + i * sin(x)");
+        assert_eq!(shown(exp_to_trig, "e^(2+3*i*x)"), "e^2 * (cos(3 * x) + i * sin(3 * x))");
+        // 实指数不变
+        assert_eq!(shown(exp_to_trig, "exp(x)"), "exp(x)");
+        for input in ["sin(x)*cos(y)", "tan(x)", "sec(x)+cot(y)", "tanh(x)"] {
+            assert_equivalent(trig_to_exp, input);
+        }
+        for input in ["exp(-2*i*x)", "exp(x + i*y) - exp(i*x/3)"] {
+            assert_equivalent(exp_to_trig, input);
+        }
+        // 往返后数值不变
+        assert_equivalent(|expr| exp_to_trig(&trig_to_exp(expr)?), "sin(x)+cos(2*y)");
+    }
+}
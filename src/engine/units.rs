@@ -0,0 +1,333 @@
+//! # 单位与量纲分析
+//!
+//! 轻量的单位系统，独立于化简器：表达式中的 `m`、`s`、`kg` 等符号按单位处理，
+//! 也可以用 [`UnitSystem::declare`] 给变量指定单位。分析时把表达式拆成数值部分与单位部分：
+//! - 乘除与整数次幂合并同名单位，如 `m/s * s = m`
+//! - 加减与比较要求量纲一致，同量纲不同单位时换算到左边的单位，如 `1*km + 500*m = 3/2*km`
+//! - `sin`、`exp`、`ln` 等函数的参数必须无量纲
+//!
+//! 量纲不一致时返回 [`ComputeError::DimensionMismatch`]。
+
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use num_bigint::BigInt;
+use num_rational::BigRational;
+use num_traits::{One, ToPrimitive, Zero};
+use crate::core::{BinaryOperator, Expression, Number, UnaryOperator};
+use super::ComputeError;
+use super::simplify::Simplifier;
+use crate::tr;
+
+/// 基本量纲的符号：长度、质量、时间、电流、温度、物质的量、发光强度
+const BASE_DIMENSIONS: [&str; 7] = ["L", "M", "T", "I", "Θ", "N", "J"];
+
+/// 内置单位：(符号, 换算到国际单位制基本单位的比例 分子/分母, 各基本量纲的指数)
+///
+/// 焦耳写作 `N*m`，`J` 会被解析为虚数单位。
+const UNITS: &[(&str, i64, i64, [i32; 7])] = &[
+    ("m", 1, 1, [1, 0, 0, 0, 0, 0, 0]),
+    ("km", 1000, 1, [1, 0, 0, 0, 0, 0, 0]),
+    ("cm", 1, 100, [1, 0, 0, 0, 0, 0, 0]),
+    ("mm", 1, 1000, [1, 0, 0, 0, 0, 0, 0]),
+    ("kg", 1, 1, [0, 1, 0, 0, 0, 0, 0]),
+    ("g", 1, 1000, [0, 1, 0, 0, 0, 0, 0]),
+    ("s", 1, 1, [0, 0, 1, 0, 0, 0, 0]),
+    ("ms", 1, 1000, [0, 0, 1, 0, 0, 0, 0]),
+    ("min", 60, 1, [0, 0, 1, 0, 0, 0, 0]),
+    ("h", 3600, 1, [0, 0, 1, 0, 0, 0, 0]),
+    ("A", 1, 1, [0, 0, 0, 1, 0, 0, 0]),
+    ("K", 1, 1, [0, 0, 0, 0, 1, 0, 0]),
+    ("mol", 1, 1, [0, 0, 0, 0, 0, 1, 0]),
+    ("cd", 1, 1, [0, 0, 0, 0, 0, 0, 1]),
+    ("N", 1, 1, [1, 1, -2, 0, 0, 0, 0]),
+    ("W", 1, 1, [2, 1, -3, 0, 0, 0, 0]),
+    ("Pa", 1, 1, [-1, 1, -2, 0, 0, 0, 0]),
+    ("Hz", 1, 1, [0, 0, -1, 0, 0, 0, 0]),
+    ("C", 1, 1, [0, 0, 1, 1, 0, 0, 0]),
+    ("V", 1, 1, [2, 1, -3, -1, 0, 0, 0]),
+];
+
+/// 量纲：各基本量纲的指数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Dimension(pub [i32; 7]);
+
+impl Dimension {
+    /// 是否无量纲
+    pub fn is_dimensionless(&self) -> bool {
+        self.0.iter().all(|&e| e == 0)
+    }
+}
+
+impl fmt::Display for Dimension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_dimensionless() {
+            return write!(f, "1");
+        }
+        let factors: Vec<String> = BASE_DIMENSIONS.iter().zip(self.0)
+            .filter(|(_, e)| *e != 0)
+            .map(|(symbol, e)| if e == 1 { symbol.to_string() } else { format!("{}^{}", symbol, e) })
+            .collect();
+        write!(f, "{}", factors.join("·"))
+    }
+}
+
+/// 带单位的量：数值部分乘以各单位的幂
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quantity {
+    /// 数值部分，可以含普通变量
+    pub magnitude: Expression,
+    /// 单位符号及其指数，指数为 0 的单位不出现
+    pub units: BTreeMap<String, i32>,
+}
+
+impl Quantity {
+    /// 量纲
+    pub fn dimension(&self) -> Dimension {
+        dimension_of(&self.units)
+    }
+
+    /// 写成 `数值 * 分子单位 / 分母单位`，数值为 1 时省略
+    pub fn to_expression(&self) -> Expression {
+        let power = |unit: &str, exponent: i32| match exponent {
+            1 => Expression::variable(unit),
+            e => Expression::power(Expression::variable(unit), Expression::number(Number::integer(e))),
+        };
+        let numerator = self.units.iter().filter(|(_, e)| **e > 0).map(|(u, e)| power(u, *e)).reduce(Expression::multiply);
+        let denominator = self.units.iter().filter(|(_, e)| **e < 0).map(|(u, e)| power(u, -e)).reduce(Expression::multiply);
+        let numerator = match (numerator, &self.magnitude) {
+            (Some(units), Expression::Number(n)) if n.is_one() => units,
+            (Some(units), magnitude) => Expression::multiply(magnitude.clone(), units),
+            (None, magnitude) => magnitude.clone(),
+        };
+        match denominator {
+            Some(units) => Expression::divide(numerator, units),
+            None => numerator,
+        }
+    }
+
+    fn dimensionless(magnitude: Expression) -> Self {
+        Self { magnitude, units: BTreeMap::new() }
+    }
+}
+
+impl fmt::Display for Quantity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_expression())
+    }
+}
+
+/// 单位系统：内置单位表加上变量的单位声明
+#[derive(Debug, Clone, Default)]
+pub struct UnitSystem {
+    /// 变量名到单位的映射；声明过的变量不再当作同名单位
+    variables: HashMap<String, BTreeMap<String, i32>>,
+}
+
+impl UnitSystem {
+    /// 创建只含内置单位的单位系统
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 声明变量的单位，如 `v` 的单位是 `m/s`；单位表达式只能由单位的乘除与整数次幂组成
+    pub fn declare(&mut self, var: &str, unit: &Expression) -> Result<(), ComputeError> {
+        let quantity = Self::new().analyze(unit)?;
+        if !matches!(&quantity.magnitude, Expression::Number(n) if n.is_one()) {
+            return Err(ComputeError::domain_error(tr!("units.not_a_unit", unit = unit)));
+        }
+        self.variables.insert(var.to_string(), quantity.units);
+        Ok(())
+    }
+
+    /// 拆出数值部分与单位部分，数值部分经过化简
+    pub fn analyze(&self, expr: &Expression) -> Result<Quantity, ComputeError> {
+        let quantity = self.quantity(expr)?;
+        Ok(Quantity { magnitude: Simplifier::new().simplify(&quantity.magnitude)?, ..quantity })
+    }
+
+    /// 表达式的量纲
+    pub fn dimension(&self, expr: &Expression) -> Result<Dimension, ComputeError> {
+        Ok(self.quantity(expr)?.dimension())
+    }
+
+    /// 做量纲检查并合并单位，如 `5*m + 3*m = 8*m`；方程与不等式两边分别化简
+    pub fn simplify(&self, expr: &Expression) -> Result<Expression, ComputeError> {
+        match expr {
+            Expression::BinaryOp { op, left, right } if is_relation(op) => {
+                let (left, right) = (self.analyze(left)?, self.analyze(right)?);
+                let right = convert(right, &left, op)?;
+                Ok(Expression::binary_op(op.clone(), left.to_expression(), right.to_expression()))
+            }
+            _ => Ok(self.analyze(expr)?.to_expression()),
+        }
+    }
+
+    fn quantity(&self, expr: &Expression) -> Result<Quantity, ComputeError> {
+        match expr {
+            Expression::Number(_) | Expression::Constant(_) => Ok(Quantity::dimensionless(expr.clone())),
+            Expression::Variable(name) => Ok(match self.variables.get(name) {
+                Some(units) => Quantity { magnitude: expr.clone(), units: units.clone() },
+                None if unit(name).is_some() => Quantity {
+                    magnitude: Expression::number(Number::one()),
+                    units: BTreeMap::from([(name.clone(), 1)]),
+                },
+                None => Quantity::dimensionless(expr.clone()),
+            }),
+            Expression::BinaryOp { op, left, right } => {
+                let (left, right) = (self.quantity(left)?, self.quantity(right)?);
+                match op {
+                    BinaryOperator::Add | BinaryOperator::Subtract => {
+                        let right = convert(right, &left, op)?;
+                        Ok(Quantity { magnitude: Expression::binary_op(op.clone(), left.magnitude, right.magnitude), units: left.units })
+                    }
+                    BinaryOperator::Multiply => Ok(Quantity {
+                        magnitude: Expression::multiply(left.magnitude, right.magnitude),
+                        units: combine(left.units, &right.units, 1),
+                    }),
+                    BinaryOperator::Divide => Ok(Quantity {
+                        magnitude: Expression::divide(left.magnitude, right.magnitude),
+                        units: combine(left.units, &right.units, -1),
+                    }),
+                    BinaryOperator::Power => power(left, right),
+                    op if is_relation(op) => Err(ComputeError::unsupported_operation(tr!("units.nested_relation"))),
+                    op => Err(ComputeError::unsupported_operation(tr!("units.unsupported_operation", operation = op.name()))),
+                }
+            }
+            Expression::UnaryOp { op, operand } => {
+                let operand = self.quantity(operand)?;
+                match op {
+                    UnaryOperator::Negate | UnaryOperator::Plus | UnaryOperator::Abs => Ok(Quantity {
+                        magnitude: Expression::unary_op(op.clone(), operand.magnitude),
+                        units: operand.units,
+                    }),
+                    UnaryOperator::Sqrt => power(operand, Quantity::dimensionless(Expression::number(Number::rational(1, 2)))),
+                    op => {
+                        require_dimensionless(&operand, op.function_name().unwrap_or(op.symbol()))?;
+                        Ok(Quantity::dimensionless(Expression::unary_op(op.clone(), operand.magnitude)))
+                    }
+                }
+            }
+            Expression::Function { name, args } => {
+                let args = args.iter().map(|arg| self.quantity(arg)).collect::<Result<Vec<_>, _>>()?;
+                match (name.as_str(), args.as_slice()) {
+                    ("sqrt", [operand]) => power(operand.clone(), Quantity::dimensionless(Expression::number(Number::rational(1, 2)))),
+                    ("abs", [operand]) => Ok(Quantity {
+                        magnitude: Expression::function("abs", vec![operand.magnitude.clone()]),
+                        units: operand.units.clone(),
+                    }),
+                    _ => {
+                        for arg in &args {
+                            require_dimensionless(arg, name)?;
+                        }
+                        Ok(Quantity::dimensionless(Expression::function(name.clone(), args.into_iter().map(|arg| arg.magnitude).collect())))
+                    }
+                }
+            }
+            _ => Err(ComputeError::unsupported_operation(tr!("units.unsupported_expression", expression = expr))),
+        }
+    }
+}
+
+/// 内置单位的换算比例与量纲
+fn unit(symbol: &str) -> Option<(BigRational, Dimension)> {
+    UNITS.iter()
+        .find(|(s, ..)| *s == symbol)
+        .map(|(_, numerator, denominator, dimension)| (BigRational::new(BigInt::from(*numerator), BigInt::from(*denominator)), Dimension(*dimension)))
+}
+
+fn dimension_of(units: &BTreeMap<String, i32>) -> Dimension {
+    let mut total = [0; 7];
+    for (symbol, exponent) in units {
+        let (_, Dimension(dimension)) = unit(symbol).expect("单位都来自内置单位表");
+        for (t, d) in total.iter_mut().zip(dimension) {
+            *t += d * exponent;
+        }
+    }
+    Dimension(total)
+}
+
+/// 单位组合换算到国际单位制基本单位的比例
+fn scale_of(units: &BTreeMap<String, i32>) -> BigRational {
+    units.iter().fold(BigRational::one(), |scale, (symbol, exponent)| {
+        let (factor, _) = unit(symbol).expect("单位都来自内置单位表");
+        scale * factor.pow(*exponent)
+    })
+}
+
+/// 合并单位，`sign` 为 -1 时表示相除
+fn combine(mut units: BTreeMap<String, i32>, other: &BTreeMap<String, i32>, sign: i32) -> BTreeMap<String, i32> {
+    for (symbol, exponent) in other {
+        *units.entry(symbol.clone()).or_insert(0) += sign * exponent;
+    }
+    units.retain(|_, e| *e != 0);
+    units
+}
+
+/// 把 `right` 换算到 `left` 的单位，量纲不同时报错
+fn convert(right: Quantity, left: &Quantity, op: &BinaryOperator) -> Result<Quantity, ComputeError> {
+    if right.dimension() != left.dimension() {
+        return Err(ComputeError::dimension_mismatch(tr!(
+            "units.inconsistent",
+            left = unit_string(&left.units), left_dimension = left.dimension(),
+            right = unit_string(&right.units), right_dimension = right.dimension(), operator = op.symbol()
+        )));
+    }
+    if right.units == left.units {
+        return Ok(right);
+    }
+    let factor = scale_of(&right.units) / scale_of(&left.units);
+    let factor = if factor.is_integer() { Number::Integer(factor.to_integer()) } else { Number::Rational(factor) };
+    Ok(Quantity {
+        magnitude: Expression::multiply(Expression::number(factor), right.magnitude),
+        units: left.units.clone(),
+    })
+}
+
+/// 幂：指数必须无量纲；底数有单位时指数必须是常数，且各单位的指数乘上去仍是整数
+fn power(base: Quantity, exponent: Quantity) -> Result<Quantity, ComputeError> {
+    require_dimensionless(&exponent, tr!("units.exponent"))?;
+    if base.units.is_empty() {
+        return Ok(Quantity::dimensionless(Expression::power(base.magnitude, exponent.magnitude)));
+    }
+    let ratio = match Simplifier::new().simplify(&exponent.magnitude)? {
+        Expression::Number(Number::Integer(n)) => BigRational::from_integer(n),
+        Expression::Number(Number::Rational(r)) => r,
+        other => return Err(ComputeError::dimension_mismatch(tr!("units.constant_exponent", units = unit_string(&base.units), exponent = other))),
+    };
+    let mut units = BTreeMap::new();
+    for (symbol, exponent) in &base.units {
+        let scaled = &ratio * BigRational::from_integer(BigInt::from(*exponent));
+        match scaled.is_integer().then(|| scaled.to_integer().to_i32()).flatten() {
+            Some(e) if e.is_zero() => {}
+            Some(e) => { units.insert(symbol.clone(), e); }
+            None => return Err(ComputeError::dimension_mismatch(tr!("units.fractional_power", units = unit_string(&base.units), exponent = ratio))),
+        }
+    }
+    Ok(Quantity { magnitude: Expression::power(base.magnitude, exponent.magnitude), units })
+}
+
+fn require_dimensionless(quantity: &Quantity, context: &str) -> Result<(), ComputeError> {
+    if quantity.units.is_empty() {
+        Ok(())
+    } else {
+        Err(ComputeError::dimension_mismatch(tr!("units.requires_dimensionless", context = context, units = unit_string(&quantity.units))))
+    }
+}
+
+fn unit_string(units: &BTreeMap<String, i32>) -> String {
+    if units.is_empty() {
+        return "1".to_string();
+    }
+    Quantity { magnitude: Expression::number(Number::one()), units: units.clone() }.to_string()
+}
+
+fn is_relation(op: &BinaryOperator) -> bool {
+    matches!(op,
+        BinaryOperator::Equal | BinaryOperator::NotEqual
+        | BinaryOperator::Less | BinaryOperator::LessEqual
+        | BinaryOperator::Greater | BinaryOperator::GreaterEqual)
+}
+
+#[cfg(test)]
+#[path = "units_tests.rs"]
+mod units_tests;
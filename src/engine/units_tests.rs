@@ -0,0 +1,75 @@
+//! # 单位与量纲分析测试
+//!
+//! 测试同名单位的合并与约去、同量纲单位的换算、变量的单位声明，以及量纲不一致时报错。
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+    use crate::engine::units::{Dimension, UnitSystem};
+    use crate::engine::ComputeError;
+    use crate::core::{Expression, Number};
+    use crate::test_utils::parse;
+
+    fn units(pairs: &[(&str, i32)]) -> BTreeMap<String, i32> {
+        pairs.iter().map(|(u, e)| (u.to_string(), *e)).collect()
+    }
+
+    #[test]
+    fn test_combine_like_units() {
+        let system = UnitSystem::new();
+        let quantity = system.analyze(&parse("5*m + 3*m")).unwrap();
+        assert_eq!(quantity.magnitude, Expression::number(Number::integer(8)));
+        assert_eq!(quantity.units, units(&[("m", 1)]));
+        assert_eq!(system.simplify(&parse("5*m + 3*m")).unwrap().to_string(), parse("8*m").to_string());
+
+        // m/s * s = m
+        let quantity = system.analyze(&parse("3*m/s * (2*s)")).unwrap();
+        assert_eq!(quantity.magnitude, Expression::number(Number::integer(6)));
+        assert_eq!(quantity.units, units(&[("m", 1)]));
+
+        let quantity = system.analyze(&parse("(2*m)^2 / s")).unwrap();
+        assert_eq!(quantity.units, units(&[("m", 2), ("s", -1)]));
+        assert_eq!(system.analyze(&parse("sqrt(9*m^2)")).unwrap().units, units(&[("m", 1)]));
+    }
+
+    #[test]
+    fn test_dimension_mismatch() {
+        let system = UnitSystem::new();
+        assert!(matches!(system.analyze(&parse("m + s")), Err(ComputeError::DimensionMismatch { .. })));
+        assert!(matches!(system.analyze(&parse("sin(2*m)")), Err(ComputeError::DimensionMismatch { .. })));
+        assert!(matches!(system.analyze(&parse("2^s")), Err(ComputeError::DimensionMismatch { .. })));
+        assert!(matches!(system.analyze(&parse("sqrt(m)")), Err(ComputeError::DimensionMismatch { .. })));
+        assert!(matches!(system.simplify(&parse("m == s")), Err(ComputeError::DimensionMismatch { .. })));
+        let message = system.analyze(&parse("m + s")).unwrap_err().to_string();
+        assert!(message.contains("L") && message.contains("T"), "{}", message);
+    }
+
+    #[test]
+    fn test_conversion_and_dimensions() {
+        let system = UnitSystem::new();
+        let quantity = system.analyze(&parse("1*km + 500*m")).unwrap();
+        assert_eq!(quantity.magnitude, Expression::number(Number::rational(3, 2)));
+        assert_eq!(quantity.units, units(&[("km", 1)]));
+
+        assert_eq!(system.dimension(&parse("kg*m/s^2")).unwrap(), system.dimension(&parse("N")).unwrap());
+        assert_eq!(system.dimension(&parse("W")).unwrap(), system.dimension(&parse("N*m/s")).unwrap());
+        assert_eq!(system.dimension(&parse("N*m/s")).unwrap().to_string(), "L^2·M·T^-3");
+        assert_eq!(system.dimension(&parse("3*x")).unwrap(), Dimension::default());
+        assert!(system.analyze(&parse("N + kg*m/s^2")).is_ok());
+    }
+
+    #[test]
+    fn test_declared_variables() {
+        let mut system = UnitSystem::new();
+        system.declare("v", &parse("m/s")).unwrap();
+        system.declare("t", &parse("s")).unwrap();
+        let quantity = system.analyze(&parse("v*t")).unwrap();
+        assert_eq!(quantity.units, units(&[("m", 1)]));
+        assert!(system.analyze(&parse("v + t")).is_err());
+
+        // 声明过的变量不再当作同名单位
+        system.declare("m", &parse("kg")).unwrap();
+        assert_eq!(system.analyze(&parse("m*v")).unwrap().units, units(&[("kg", 1), ("m", 1), ("s", -1)]));
+        assert!(system.declare("w", &parse("2*m")).is_err());
+    }
+}
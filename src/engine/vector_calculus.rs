@@ -0,0 +1,114 @@
+//! # 向量微积分
+//!
+//! 场论中的微分算子，都由对各变量的偏导组合而成，结果逐项化简：
+//! - [`jacobian`]：向量函数的雅可比矩阵，第 i 行是第 i 个分量对各变量的偏导
+//! - [`divergence`]：散度 `∂F₁/∂x₁ + … + ∂Fₙ/∂xₙ`
+//! - [`curl`]：旋度，三维时为向量 `∇ × F`；二维时约定返回标量旋度 `∂Q/∂x - ∂P/∂y`
+//! - [`laplacian`]：拉普拉斯算子 `∂²f/∂x₁² + … + ∂²f/∂xₙ²`
+//!
+//! 表达式中可写作 `jacobian([f, g], [x, y])`、`div([P, Q], [x, y])`、`curl([P, Q, R], [x, y, z])`、
+//! `laplacian(f, [x, y])`；参数还不是具体的向量和变量列表时保留函数形式。
+
+use crate::core::Expression;
+use super::ComputeError;
+use super::calculus::CalculusEngine;
+use super::enhanced_simplify::EnhancedSimplifier;
+use super::simplify::Simplifier;
+use crate::tr;
+
+/// 向量微积分函数名
+pub const VECTOR_CALCULUS_FUNCTIONS: &[&str] = &["jacobian", "div", "divergence", "curl", "laplacian"];
+
+/// 雅可比矩阵：`exprs.len()` 行、`vars.len()` 列
+pub fn jacobian(exprs: &[Expression], vars: &[&str]) -> Result<Expression, ComputeError> {
+    if exprs.is_empty() || vars.is_empty() {
+        return Err(ComputeError::dimension_mismatch(tr!("vector_calculus.jacobian_arguments")));
+    }
+    let rows = exprs.iter()
+        .map(|f| vars.iter().map(|var| partial(f, var)).collect::<Result<Vec<_>, _>>())
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Expression::Matrix(rows))
+}
+
+/// 散度，向量场的分量数必须与变量数相同
+pub fn divergence(field: &[Expression], vars: &[&str]) -> Result<Expression, ComputeError> {
+    check_field(field, vars)?;
+    let terms = field.iter().zip(vars)
+        .map(|(component, var)| partial(component, var))
+        .collect::<Result<Vec<_>, _>>()?;
+    sum(terms)
+}
+
+/// 旋度，只接受三维或二维的向量场；二维时返回标量 `∂Q/∂x - ∂P/∂y`
+pub fn curl(field: &[Expression], vars: &[&str]) -> Result<Expression, ComputeError> {
+    check_field(field, vars)?;
+    // ∂Fᵢ/∂xⱼ - ∂Fⱼ/∂xᵢ
+    let rotation = |i: usize, j: usize| -> Result<Expression, ComputeError> {
+        simplify(&Expression::subtract(partial(&field[i], vars[j])?, partial(&field[j], vars[i])?))
+    };
+    match field.len() {
+        3 => Ok(Expression::Vector(vec![rotation(2, 1)?, rotation(0, 2)?, rotation(1, 0)?])),
+        2 => rotation(1, 0),
+        n => Err(ComputeError::dimension_mismatch(tr!("vector_calculus.curl_dimension", dimension = n))),
+    }
+}
+
+/// 拉普拉斯算子
+pub fn laplacian(f: &Expression, vars: &[&str]) -> Result<Expression, ComputeError> {
+    if vars.is_empty() {
+        return Err(ComputeError::dimension_mismatch(tr!("vector_calculus.laplacian_variables")));
+    }
+    let terms = vars.iter()
+        .map(|var| partial(&partial(f, var)?, var))
+        .collect::<Result<Vec<_>, _>>()?;
+    sum(terms)
+}
+
+/// 化简向量微积分函数调用；参数还不是具体的向量和变量列表时返回 `None`，由调用方保留函数形式
+pub fn simplify_call(name: &str, args: &[Expression]) -> Option<Result<Expression, ComputeError>> {
+    match (name, args) {
+        ("jacobian", [Expression::Vector(exprs), vars]) => Some(jacobian(exprs, &variables(vars)?)),
+        ("div" | "divergence", [Expression::Vector(field), vars]) => Some(divergence(field, &variables(vars)?)),
+        ("curl", [Expression::Vector(field), vars]) => Some(curl(field, &variables(vars)?)),
+        ("laplacian", [f, vars]) if !matches!(f, Expression::Vector(_) | Expression::Matrix(_)) => Some(laplacian(f, &variables(vars)?)),
+        _ => None,
+    }
+}
+
+/// 变量列表参数 `[x, y, z]` 中的变量名，有元素不是变量时返回 `None`
+pub(crate) fn variables(list: &Expression) -> Option<Vec<&str>> {
+    match list {
+        Expression::Vector(elements) => elements.iter()
+            .map(|element| match element {
+                Expression::Variable(name) => Some(name.as_str()),
+                _ => None,
+            })
+            .collect(),
+        _ => None,
+    }
+}
+
+fn check_field(field: &[Expression], vars: &[&str]) -> Result<(), ComputeError> {
+    if field.len() != vars.len() || field.is_empty() {
+        return Err(ComputeError::dimension_mismatch(tr!("vector_calculus.field_mismatch", components = field.len(), variables = vars.len())));
+    }
+    Ok(())
+}
+
+/// 化简后的偏导数；先用轻量化简把数值系数提到前面并折叠，如 `3 * (2 * z)` 得到 `6 * z`
+fn partial(f: &Expression, var: &str) -> Result<Expression, ComputeError> {
+    let derivative = CalculusEngine::new().differentiate(f, var)?;
+    simplify(&Simplifier::simplify_light(&derivative, &|| false)?)
+}
+
+fn sum(terms: Vec<Expression>) -> Result<Expression, ComputeError> {
+    simplify(&terms.into_iter().reduce(Expression::add).expect("至少有一项"))
+}
+
+fn simplify(expr: &Expression) -> Result<Expression, ComputeError> {
+    EnhancedSimplifier::new().enhanced_simplify(expr)
+}
+
+#[cfg(test)]
+#[path = "vector_calculus_tests.rs"]
+mod vector_calculus_tests;
@@ -0,0 +1,74 @@
+//! # 向量微积分测试
+//!
+//! 测试雅可比矩阵、散度、旋度、拉普拉斯算子，以及表达式中的函数调用形式。
+
+#[cfg(test)]
+mod tests {
+    use crate::core::{Expression, Number};
+    use crate::engine::ComputeError;
+    use crate::engine::simplify::Simplifier;
+    use crate::engine::vector_calculus::{curl, divergence, jacobian, laplacian};
+    use crate::test_utils::parse;
+
+    fn parse_all(inputs: &[&str]) -> Vec<Expression> {
+        inputs.iter().map(|input| parse(input)).collect()
+    }
+
+    fn int(value: i64) -> Expression {
+        Expression::Number(Number::integer(value))
+    }
+
+    fn simplified(input: &str) -> Expression {
+        Simplifier::new().simplify(&parse(input)).unwrap()
+    }
+
+    #[test]
+    fn test_jacobian() {
+        let result = jacobian(&parse_all(&["x^2*y", "5*x + sin(y)"]), &["x", "y"]).unwrap();
+        assert_eq!(result, Expression::Matrix(vec![
+            vec![simplified("2*x*y"), simplified("x^2")],
+            vec![int(5), simplified("cos(y)")],
+        ]));
+        // 极坐标变换的雅可比行列式为 r
+        let polar = jacobian(&parse_all(&["r*cos(t)", "r*sin(t)"]), &["r", "t"]).unwrap();
+        assert!(matches!(&polar, Expression::Matrix(rows) if rows.len() == 2 && rows[0].len() == 2));
+        assert!(matches!(jacobian(&[], &["x"]), Err(ComputeError::DimensionMismatch { .. })));
+    }
+
+    #[test]
+    fn test_divergence() {
+        assert_eq!(divergence(&parse_all(&["x", "y", "z"]), &["x", "y", "z"]).unwrap(), int(3));
+        assert_eq!(divergence(&parse_all(&["x^2", "x*y"]), &["x", "y"]).unwrap(), simplified("3*x"));
+        assert!(matches!(divergence(&parse_all(&["x", "y"]), &["x", "y", "z"]), Err(ComputeError::DimensionMismatch { .. })));
+    }
+
+    #[test]
+    fn test_curl() {
+        assert_eq!(curl(&parse_all(&["-y", "x", "0"]), &["x", "y", "z"]).unwrap(), Expression::Vector(vec![int(0), int(0), int(2)]));
+        // 梯度场无旋
+        let gradient = parse_all(&["y*z", "x*z", "x*y"]);
+        assert_eq!(curl(&gradient, &["x", "y", "z"]).unwrap(), Expression::Vector(vec![int(0), int(0), int(0)]));
+        // 二维时为标量旋度
+        assert_eq!(curl(&parse_all(&["-y", "x"]), &["x", "y"]).unwrap(), int(2));
+        assert!(matches!(curl(&parse_all(&["x", "y", "z", "w"]), &["x", "y", "z", "w"]), Err(ComputeError::DimensionMismatch { .. })));
+        assert!(matches!(curl(&parse_all(&["x", "y"]), &["x", "y", "z"]), Err(ComputeError::DimensionMismatch { .. })));
+    }
+
+    #[test]
+    fn test_laplacian() {
+        assert_eq!(laplacian(&parse("x^2 + y^2"), &["x", "y"]).unwrap(), int(4));
+        assert_eq!(laplacian(&parse("x^2*y + z^3"), &["x", "y", "z"]).unwrap(), simplified("2*y + 6*z"));
+        assert!(matches!(laplacian(&parse("x"), &[]), Err(ComputeError::DimensionMismatch { .. })));
+    }
+
+    #[test]
+    fn test_function_calls() {
+        assert_eq!(simplified("curl([-y, x, 0], [x, y, z])"), Expression::Vector(vec![int(0), int(0), int(2)]));
+        assert_eq!(simplified("laplacian(x^2 + y^2, [x, y])"), int(4));
+        assert_eq!(simplified("div([x, y, z], [x, y, z])"), int(3));
+        assert_eq!(simplified("jacobian([x*y], [x, y])"), Expression::Matrix(vec![vec![parse("y"), parse("x")]]));
+        // 参数不是具体的列表时保留函数形式
+        assert_eq!(simplified("curl(F, [x, y, z])"), parse("curl(F, [x, y, z])"));
+        assert!(Simplifier::new().simplify(&parse("div([x, y], [x])")).is_err());
+    }
+}
@@ -1,5 +1,141 @@
 //! # C API 接口
 //!
-//! 提供 C 兼容的 API 接口。
+//! 提供 C 兼容的 API 接口。表达式在调用方与库之间以紧凑二进制格式传递，
+//! 调用方可以缓存或转发这些字节，不必反复解析文本。
+//!
+//! 出错的函数返回空缓冲区或空指针，错误信息可用 `yufmath_last_error` 取得。
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+use std::slice;
+use crate::api::{Yufmath, YufmathError};
+use crate::core::Expression;
+use super::types::YufmathBuffer;
+use crate::tr;
+
+thread_local! {
+    static YUFMATH: Yufmath = Yufmath::new();
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// 记录错误信息，成功时清除
+fn finish<T>(result: Result<T, YufmathError>, on_error: T) -> T {
+    match result {
+        Ok(value) => {
+            LAST_ERROR.with(|last| *last.borrow_mut() = None);
+            value
+        }
+        Err(error) => {
+            let message = CString::new(error.to_string().replace('\0', " ")).unwrap_or_default();
+            LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+            on_error
+        }
+    }
+}
+
+/// 读取调用方传入的二进制表达式
+///
+/// # Safety
+///
+/// `data` 必须指向至少 `len` 个可读字节。
+unsafe fn read_expression(data: *const u8, len: usize) -> Result<Expression, YufmathError> {
+    if data.is_null() {
+        return Err(YufmathError::internal(tr!("ffi.null_expression")));
+    }
+    Ok(Expression::from_bytes(slice::from_raw_parts(data, len))?)
+}
+
+/// 解析文本表达式，返回其二进制编码
+///
+/// # Safety
+///
+/// `input` 必须是以 NUL 结尾的有效 C 字符串。
+#[no_mangle]
+pub unsafe extern "C" fn yufmath_parse(input: *const c_char) -> YufmathBuffer {
+    let result = if input.is_null() {
+        Err(YufmathError::internal(tr!("ffi.null_input")))
+    } else {
+        CStr::from_ptr(input).to_str()
+            .map_err(|_| YufmathError::internal(tr!("ffi.invalid_utf8")))
+            .and_then(|text| YUFMATH.with(|yuf| yuf.parse(text)))
+            .map(|expr| YufmathBuffer::from_vec(expr.to_bytes()))
+    };
+    finish(result, YufmathBuffer::null())
+}
+
+/// 化简二进制编码的表达式，返回结果的二进制编码
+///
+/// # Safety
+///
+/// `data` 必须指向至少 `len` 个可读字节。
+#[no_mangle]
+pub unsafe extern "C" fn yufmath_simplify(data: *const u8, len: usize) -> YufmathBuffer {
+    let result = read_expression(data, len)
+        .and_then(|expr| YUFMATH.with(|yuf| yuf.simplify(&expr)))
+        .map(|expr| YufmathBuffer::from_vec(expr.to_bytes()));
+    finish(result, YufmathBuffer::null())
+}
+
+/// 对二进制编码的表达式关于变量 `var` 求导，返回结果的二进制编码
+///
+/// # Safety
+///
+/// `data` 必须指向至少 `len` 个可读字节，`var` 必须是以 NUL 结尾的有效 C 字符串。
+#[no_mangle]
+pub unsafe extern "C" fn yufmath_diff(data: *const u8, len: usize, var: *const c_char) -> YufmathBuffer {
+    let result = if var.is_null() {
+        Err(YufmathError::internal(tr!("ffi.null_variable")))
+    } else {
+        let var = CStr::from_ptr(var).to_string_lossy();
+        read_expression(data, len)
+            .and_then(|expr| YUFMATH.with(|yuf| yuf.diff(&expr, &*var)))
+            .map(|expr| YufmathBuffer::from_vec(expr.to_bytes()))
+    };
+    finish(result, YufmathBuffer::null())
+}
+
+/// 把二进制编码的表达式格式化为文本，返回的字符串须用 `yufmath_string_free` 释放
+///
+/// # Safety
+///
+/// `data` 必须指向至少 `len` 个可读字节。
+#[no_mangle]
+pub unsafe extern "C" fn yufmath_to_string(data: *const u8, len: usize) -> *mut c_char {
+    let result = read_expression(data, len).map(|expr| {
+        CString::new(expr.to_string().replace('\0', " ")).unwrap_or_default().into_raw()
+    });
+    finish(result, ptr::null_mut())
+}
+
+/// 当前线程最近一次调用失败时的错误信息，没有错误时返回空指针
+///
+/// 返回的指针在本线程下一次调用本库函数前有效，不需要释放。
+#[no_mangle]
+pub extern "C" fn yufmath_last_error() -> *const c_char {
+    LAST_ERROR.with(|last| last.borrow().as_ref().map_or(ptr::null(), |message| message.as_ptr()))
+}
+
+/// 释放库返回的缓冲区
+///
+/// # Safety
+///
+/// `buffer` 必须来自本库的函数且尚未释放。
+#[no_mangle]
+pub unsafe extern "C" fn yufmath_buffer_free(buffer: YufmathBuffer) {
+    if !buffer.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(buffer.data, buffer.len)));
+    }
+}
 
-// 占位符文件，将在后续任务中实现
\ No newline at end of file
+/// 释放 `yufmath_to_string` 返回的字符串
+///
+/// # Safety
+///
+/// `text` 必须来自 `yufmath_to_string` 且尚未释放。
+#[no_mangle]
+pub unsafe extern "C" fn yufmath_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
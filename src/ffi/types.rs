@@ -2,4 +2,34 @@
 //!
 //! 定义 FFI 接口使用的类型。
 
-// 占位符文件，将在后续任务中实现
\ No newline at end of file
+use std::ptr;
+
+/// 由库分配的字节缓冲区，内容是表达式的紧凑二进制格式（见 [`crate::core::binary`]）
+///
+/// 出错时 `data` 为空指针。用完须交给 `yufmath_buffer_free` 释放。
+#[repr(C)]
+#[derive(Debug)]
+pub struct YufmathBuffer {
+    /// 数据首地址
+    pub data: *mut u8,
+    /// 数据长度（字节）
+    pub len: usize,
+}
+
+impl YufmathBuffer {
+    /// 表示出错的空缓冲区
+    pub fn null() -> Self {
+        Self { data: ptr::null_mut(), len: 0 }
+    }
+
+    /// 接管 `bytes` 的内存
+    pub fn from_vec(bytes: Vec<u8>) -> Self {
+        let bytes = Box::into_raw(bytes.into_boxed_slice());
+        Self { data: bytes as *mut u8, len: bytes.len() }
+    }
+
+    /// 是否为表示出错的空缓冲区
+    pub fn is_null(&self) -> bool {
+        self.data.is_null()
+    }
+}
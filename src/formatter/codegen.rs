@@ -0,0 +1,505 @@
+//! # 代码生成
+//!
+//! 把表达式转换为 Rust、C 或 Python 函数源码。函数参数为表达式中的全部变量（按字典序），
+//! 向量和矩阵（按行展开）生成返回数组的函数。重复出现的子表达式由 [`cse`] 提取为局部变量；
+//! 小整数次幂展开为乘法。
+
+use crate::core::{cse, CseOptions, Expression, Number, MathConstant, BinaryOperator, UnaryOperator};
+use crate::api::FormatError;
+use num_traits::ToPrimitive;
+use crate::tr;
+
+/// 目标语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// Rust，参数和返回值为 f64
+    Rust,
+    /// C99，参数和返回值为 double，数组结果通过输出参数返回
+    C,
+    /// Python 3，使用 math 模块
+    Python,
+}
+
+/// 代码生成选项
+#[derive(Debug, Clone)]
+pub struct CodegenOptions {
+    /// 生成的函数名
+    pub function_name: String,
+    /// 是否进行公共子表达式消除
+    pub cse: bool,
+    /// 绝对值不超过该值的整数次幂展开为乘法，更高次幂使用幂函数
+    pub max_expanded_power: u32,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self {
+            function_name: "f".to_string(),
+            cse: true,
+            max_expanded_power: 4,
+        }
+    }
+}
+
+// 运算优先级，数值越大结合越紧
+const PREC_ADD: u8 = 1;
+const PREC_MUL: u8 = 2;
+const PREC_UNARY: u8 = 3;
+const PREC_POWER: u8 = 4;
+const PREC_ATOM: u8 = 5;
+
+/// 生成的代码片段
+struct Code {
+    text: String,
+    prec: u8,
+    /// 是否由数值字面量构成（Rust 中不能直接作为方法接收者）
+    literal: bool,
+}
+
+impl Code {
+    fn new(text: impl Into<String>, prec: u8) -> Self {
+        Self { text: text.into(), prec, literal: false }
+    }
+
+    fn atom(text: impl Into<String>) -> Self {
+        Self::new(text, PREC_ATOM)
+    }
+
+    fn literal(text: String) -> Self {
+        let prec = if text.starts_with('-') { PREC_UNARY } else { PREC_ATOM };
+        Self { text, prec, literal: true }
+    }
+
+    /// 优先级低于 `min_prec` 时加括号
+    fn wrap(&self, min_prec: u8) -> String {
+        if self.prec < min_prec {
+            format!("({})", self.text)
+        } else {
+            self.text.clone()
+        }
+    }
+}
+
+/// 代码生成器
+pub struct CodeGenerator {
+    language: Language,
+    options: CodegenOptions,
+}
+
+impl CodeGenerator {
+    /// 创建代码生成器
+    pub fn new(language: Language, options: CodegenOptions) -> Self {
+        Self { language, options }
+    }
+
+    /// 生成完整的函数源码
+    pub fn generate(&self, expr: &Expression) -> Result<String, FormatError> {
+        let outputs: Vec<Expression> = match expr {
+            Expression::Vector(elements) => elements.clone(),
+            Expression::Matrix(rows) => rows.iter().flatten().cloned().collect(),
+            _ => vec![expr.clone()],
+        };
+        let is_array = matches!(expr, Expression::Vector(_) | Expression::Matrix(_));
+        let params = expr.get_variables();
+
+        let temp_prefix = Self::temp_prefix(&params);
+        let (definitions, outputs) = if self.options.cse {
+            // 局部变量从 0 开始编号，展开幂时绑定的底数接着编号
+            let options = CseOptions { prefix: temp_prefix.clone(), first_index: 0, ..CseOptions::default() };
+            cse(&outputs, &options)
+        } else {
+            (Vec::new(), outputs)
+        };
+
+        let mut emitter = Emitter {
+            language: self.language,
+            max_expanded_power: self.options.max_expanded_power,
+            cse: self.options.cse,
+            statements: Vec::new(),
+            temp_prefix,
+        };
+        for (name, value) in &definitions {
+            let code = emitter.emit(value)?;
+            emitter.statements.push((name.clone(), code.text));
+        }
+        let results = outputs.iter()
+            .map(|output| emitter.emit(output).map(|code| code.text))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(self.render(&params, &emitter.statements, &results, is_array))
+    }
+
+    /// 选择不与参数名冲突的局部变量前缀
+    fn temp_prefix(params: &[String]) -> String {
+        let mut prefix = "t".to_string();
+        let conflicts = |prefix: &str| params.iter().any(|p| {
+            p.strip_prefix(prefix).is_some_and(|rest| !rest.is_empty() && rest.chars().all(|c| c.is_ascii_digit()))
+        });
+        while conflicts(&prefix) {
+            prefix.push('_');
+        }
+        prefix
+    }
+
+    /// 拼装函数源码
+    fn render(&self, params: &[String], statements: &[(String, String)], results: &[String], is_array: bool) -> String {
+        let name = &self.options.function_name;
+        let mut lines = Vec::new();
+
+        match self.language {
+            Language::Rust => {
+                let params: Vec<_> = params.iter().map(|p| format!("{}: f64", p)).collect();
+                let ret = if is_array { format!("[f64; {}]", results.len()) } else { "f64".to_string() };
+                lines.push(format!("pub fn {}({}) -> {} {{", name, params.join(", "), ret));
+                for (temp, code) in statements {
+                    lines.push(format!("    let {}: f64 = {};", temp, code));
+                }
+                if is_array {
+                    lines.push(format!("    [{}]", results.join(", ")));
+                } else {
+                    lines.push(format!("    {}", results[0]));
+                }
+                lines.push("}".to_string());
+            }
+            Language::C => {
+                lines.push("#include <math.h>".to_string());
+                lines.push(String::new());
+                let mut params: Vec<_> = params.iter().map(|p| format!("double {}", p)).collect();
+                if is_array {
+                    params.push(format!("double out[{}]", results.len()));
+                }
+                let params = if params.is_empty() { "void".to_string() } else { params.join(", ") };
+                let ret = if is_array { "void" } else { "double" };
+                lines.push(format!("{} {}({}) {{", ret, name, params));
+                for (temp, code) in statements {
+                    lines.push(format!("    const double {} = {};", temp, code));
+                }
+                if is_array {
+                    for (i, result) in results.iter().enumerate() {
+                        lines.push(format!("    out[{}] = {};", i, result));
+                    }
+                } else {
+                    lines.push(format!("    return {};", results[0]));
+                }
+                lines.push("}".to_string());
+            }
+            Language::Python => {
+                lines.push("import math".to_string());
+                lines.push(String::new());
+                lines.push(String::new());
+                lines.push(format!("def {}({}):", name, params.join(", ")));
+                for (temp, code) in statements {
+                    lines.push(format!("    {} = {}", temp, code));
+                }
+                if is_array {
+                    lines.push(format!("    return [{}]", results.join(", ")));
+                } else {
+                    lines.push(format!("    return {}", results[0]));
+                }
+            }
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+/// 单次生成过程的状态
+struct Emitter {
+    language: Language,
+    max_expanded_power: u32,
+    cse: bool,
+    /// 局部变量定义（名称, 代码），按依赖顺序排列
+    statements: Vec<(String, String)>,
+    temp_prefix: String,
+}
+
+impl Emitter {
+    /// 把代码绑定到新的局部变量
+    fn bind(&mut self, code: Code) -> String {
+        let name = format!("{}{}", self.temp_prefix, self.statements.len());
+        self.statements.push((name.clone(), code.text));
+        name
+    }
+
+    fn emit(&mut self, expr: &Expression) -> Result<Code, FormatError> {
+        match expr {
+            Expression::Number(n) => self.emit_number(n),
+            Expression::Variable(name) => Ok(Code::atom(name.clone())),
+            Expression::Constant(c) => self.emit_constant(c),
+            Expression::BinaryOp { op, left, right } => self.emit_binary(op, left, right),
+            Expression::UnaryOp { op, operand } => match op {
+                UnaryOperator::Negate => {
+                    let operand = self.emit(operand)?;
+                    Ok(Code::new(format!("-{}", operand.wrap(PREC_UNARY + 1)), PREC_UNARY))
+                }
+                UnaryOperator::Plus => self.emit(operand),
+                _ => {
+                    let name = Self::unary_function_name(op)
+                        .ok_or_else(|| Self::unsupported(tr!("codegen.operation", op = format!("{:?}", op))))?;
+                    let operand = self.emit(operand)?;
+                    self.call(name, vec![operand])
+                }
+            },
+            Expression::Function { name, args } => {
+                if name == "root" && args.len() == 2 {
+                    let n = match &args[1] {
+                        Expression::Number(Number::Integer(n)) if n > &0.into() => n.to_u32(),
+                        _ => None,
+                    }.ok_or_else(|| Self::unsupported(tr!("codegen.root_index").to_string()))?;
+                    let base = self.emit(&args[0])?;
+                    return Ok(self.nth_root(base, n));
+                }
+                let args = args.iter().map(|arg| self.emit(arg)).collect::<Result<Vec<_>, _>>()?;
+                self.call(name, args)
+            }
+            _ => Err(Self::unsupported(tr!("codegen.nested").to_string())),
+        }
+    }
+
+    fn emit_number(&mut self, n: &Number) -> Result<Code, FormatError> {
+        match n {
+            Number::Integer(i) => Ok(self.float_literal(i.to_f64().unwrap_or(f64::NAN))),
+            Number::Rational(r) => {
+                let numer = self.float_literal(r.numer().to_f64().unwrap_or(f64::NAN));
+                let denom = self.float_literal(r.denom().to_f64().unwrap_or(f64::NAN));
+                Ok(Code { text: format!("{} / {}", numer.text, denom.text), prec: PREC_MUL, literal: true })
+            }
+            Number::Real(r) => Ok(self.float_literal(r.to_f64().unwrap_or(f64::NAN))),
+            Number::Float(f) => Ok(self.float_literal(*f)),
+            Number::Constant(c) => self.emit_constant(c),
+            Number::Symbolic(inner) => self.emit(inner),
+            Number::Complex { .. } => Err(Self::unsupported(tr!("codegen.complex").to_string())),
+        }
+    }
+
+    /// 按目标语言输出浮点字面量
+    fn float_literal(&self, value: f64) -> Code {
+        if value.is_finite() {
+            return Code::literal(format!("{:?}", value));
+        }
+        let text = match (self.language, value.is_nan(), value > 0.0) {
+            (Language::Rust, true, _) => "f64::NAN",
+            (Language::Rust, false, true) => "f64::INFINITY",
+            (Language::Rust, false, false) => "f64::NEG_INFINITY",
+            (Language::C, true, _) => "NAN",
+            (Language::C, false, true) => "INFINITY",
+            (Language::C, false, false) => "-INFINITY",
+            (Language::Python, true, _) => "math.nan",
+            (Language::Python, false, true) => "math.inf",
+            (Language::Python, false, false) => "-math.inf",
+        };
+        let prec = if text.starts_with('-') { PREC_UNARY } else { PREC_ATOM };
+        Code::new(text, prec)
+    }
+
+    fn emit_constant(&self, c: &MathConstant) -> Result<Code, FormatError> {
+        let text = match (c, self.language) {
+            (MathConstant::Pi, Language::Rust) => "std::f64::consts::PI",
+            (MathConstant::Pi, Language::C) => "M_PI",
+            (MathConstant::Pi, Language::Python) => "math.pi",
+            (MathConstant::E, Language::Rust) => "std::f64::consts::E",
+            (MathConstant::E, Language::C) => "M_E",
+            (MathConstant::E, Language::Python) => "math.e",
+            (MathConstant::EulerGamma, _) => return Ok(self.float_literal(0.5772156649015329)),
+            (MathConstant::GoldenRatio, _) => return Ok(self.float_literal(1.618033988749895)),
+            (MathConstant::Catalan, _) => return Ok(self.float_literal(0.915965594177219)),
+            (MathConstant::PositiveInfinity, _) => return Ok(self.float_literal(f64::INFINITY)),
+            (MathConstant::NegativeInfinity, _) => return Ok(self.float_literal(f64::NEG_INFINITY)),
+            (MathConstant::Undefined, _) => return Ok(self.float_literal(f64::NAN)),
+            (MathConstant::I, _) => return Err(Self::unsupported(tr!("codegen.imaginary_unit").to_string())),
+        };
+        Ok(Code::atom(text))
+    }
+
+    fn emit_binary(&mut self, op: &BinaryOperator, left: &Expression, right: &Expression) -> Result<Code, FormatError> {
+        let (symbol, prec) = match op {
+            BinaryOperator::Add => ("+", PREC_ADD),
+            BinaryOperator::Subtract => ("-", PREC_ADD),
+            BinaryOperator::Multiply => ("*", PREC_MUL),
+            BinaryOperator::Divide => ("/", PREC_MUL),
+            BinaryOperator::Power => return self.emit_power(left, right),
+            BinaryOperator::Modulo => {
+                let left = self.emit(left)?;
+                let right = self.emit(right)?;
+                return Ok(self.euclidean_modulo(left, right));
+            }
+            _ => return Err(Self::unsupported(tr!("codegen.operation", op = format!("{:?}", op)))),
+        };
+        let left = self.emit(left)?;
+        let right = self.emit(right)?;
+        // 右操作数同级时也加括号，保持原表达式的求值顺序
+        Ok(Code::new(format!("{} {} {}", left.wrap(prec), symbol, right.wrap(prec + 1)), prec))
+    }
+
+    fn emit_power(&mut self, base: &Expression, exponent: &Expression) -> Result<Code, FormatError> {
+        if let Expression::Number(Number::Rational(r)) = exponent {
+            if r.numer() == &1.into() && r.denom() == &2.into() {
+                let base = self.emit(base)?;
+                return self.call("sqrt", vec![base]);
+            }
+        }
+
+        let integer_exponent = match exponent {
+            Expression::Number(Number::Integer(n)) => Some(n.clone()),
+            Expression::UnaryOp { op: UnaryOperator::Negate, operand } => match operand.as_ref() {
+                Expression::Number(Number::Integer(n)) => Some(-n),
+                _ => None,
+            },
+            _ => None,
+        };
+        if let Some(n) = integer_exponent {
+            let n = n.to_i64().ok_or_else(|| Self::unsupported(tr!("codegen.large_exponent").to_string()))?;
+            if n == 0 {
+                return Ok(self.float_literal(1.0));
+            }
+            let base = self.emit(base)?;
+            if n.unsigned_abs() <= u64::from(self.max_expanded_power) {
+                return Ok(self.expand_power(base, n));
+            }
+            return Ok(match self.language {
+                Language::Rust => Code::atom(self.rust_method(&base, "powi", &[n.to_string()])),
+                Language::C => Code::atom(format!("pow({}, {}.0)", base.text, n)),
+                Language::Python => Code::new(format!("{} ** {}", base.wrap(PREC_ATOM), Self::python_exponent(n)), PREC_POWER),
+            });
+        }
+
+        let base = self.emit(base)?;
+        let exponent = self.emit(exponent)?;
+        Ok(match self.language {
+            Language::Rust => Code::atom(self.rust_method(&base, "powf", &[exponent.text])),
+            Language::C => Code::atom(format!("pow({}, {})", base.text, exponent.text)),
+            Language::Python => Code::new(format!("{} ** {}", base.wrap(PREC_ATOM), exponent.wrap(PREC_ATOM)), PREC_POWER),
+        })
+    }
+
+    fn python_exponent(n: i64) -> String {
+        if n < 0 { format!("({})", n) } else { n.to_string() }
+    }
+
+    /// 把整数次幂展开为乘法，复合底数先提取为局部变量
+    fn expand_power(&mut self, base: Code, n: i64) -> Code {
+        let base = if base.prec < PREC_ATOM && n.unsigned_abs() > 1 && self.cse {
+            Code::atom(self.bind(base))
+        } else {
+            base
+        };
+        let factor = base.wrap(PREC_MUL + 1);
+        let product = if n.unsigned_abs() == 1 {
+            Code::new(base.text.clone(), base.prec)
+        } else {
+            Code::new(vec![factor; n.unsigned_abs() as usize].join(" * "), PREC_MUL)
+        };
+        if n > 0 {
+            product
+        } else {
+            Code::new(format!("{} / {}", self.float_literal(1.0).text, product.wrap(PREC_MUL + 1)), PREC_MUL)
+        }
+    }
+
+    /// 实数 n 次根，奇次根对负数取负实根
+    fn nth_root(&self, base: Code, n: u32) -> Code {
+        let exponent = format!("1.0 / {}.0", n);
+        match (self.language, n % 2 == 1) {
+            (Language::Rust, true) => {
+                let magnitude = Code::atom(self.rust_method(&base, "abs", &[]));
+                let root = Code::atom(self.rust_method(&magnitude, "powf", &[exponent]));
+                Code::atom(self.rust_method(&root, "copysign", &[base.text]))
+            }
+            (Language::Rust, false) => Code::atom(self.rust_method(&base, "powf", &[exponent])),
+            (Language::C, true) => Code::atom(format!("copysign(pow(fabs({}), {}), {})", base.text, exponent, base.text)),
+            (Language::C, false) => Code::atom(format!("pow({}, {})", base.text, exponent)),
+            (Language::Python, true) => Code::atom(format!("math.copysign(abs({}) ** ({}), {})", base.text, exponent, base.text)),
+            (Language::Python, false) => Code::new(format!("{} ** ({})", base.wrap(PREC_ATOM), exponent), PREC_POWER),
+        }
+    }
+
+    /// 余数非负的取模
+    fn euclidean_modulo(&self, left: Code, right: Code) -> Code {
+        match self.language {
+            Language::Rust => Code::atom(self.rust_method(&left, "rem_euclid", &[right.text])),
+            Language::C => Code::atom(format!("fmod(fmod({0}, {1}) + fabs({1}), fabs({1}))", left.text, right.text)),
+            Language::Python => Code::new(format!("{} % abs({})", left.wrap(PREC_MUL), right.text), PREC_MUL),
+        }
+    }
+
+    /// Rust 的 f64 方法调用；接收者不是变量或函数调用时使用 `f64::name(..)` 形式，避免字面量类型不明确
+    fn rust_method(&self, receiver: &Code, method: &str, args: &[String]) -> String {
+        if receiver.prec == PREC_ATOM && !receiver.literal {
+            format!("{}.{}({})", receiver.text, method, args.join(", "))
+        } else {
+            let mut all_args = vec![receiver.text.clone()];
+            all_args.extend_from_slice(args);
+            format!("f64::{}({})", method, all_args.join(", "))
+        }
+    }
+
+    fn unary_function_name(op: &UnaryOperator) -> Option<&'static str> {
+        Some(match op {
+            UnaryOperator::Sqrt => "sqrt",
+            UnaryOperator::Abs => "abs",
+            UnaryOperator::Sin => "sin",
+            UnaryOperator::Cos => "cos",
+            UnaryOperator::Tan => "tan",
+            UnaryOperator::Asin => "asin",
+            UnaryOperator::Acos => "acos",
+            UnaryOperator::Atan => "atan",
+            UnaryOperator::Sinh => "sinh",
+            UnaryOperator::Cosh => "cosh",
+            UnaryOperator::Tanh => "tanh",
+            UnaryOperator::Asinh => "asinh",
+            UnaryOperator::Acosh => "acosh",
+            UnaryOperator::Atanh => "atanh",
+            UnaryOperator::Ln => "ln",
+            UnaryOperator::Log10 => "log10",
+            UnaryOperator::Log2 => "log2",
+            UnaryOperator::Exp => "exp",
+            _ => return None,
+        })
+    }
+
+    /// 调用单参数数学函数
+    fn call(&self, name: &str, args: Vec<Code>) -> Result<Code, FormatError> {
+        let name = match name {
+            "arcsin" => "asin",
+            "arccos" => "acos",
+            "arctan" => "atan",
+            "log" => "ln",
+            other => other,
+        };
+        let known = matches!(name,
+            "sin" | "cos" | "tan" | "asin" | "acos" | "atan" | "sinh" | "cosh" | "tanh" |
+            "asinh" | "acosh" | "atanh" | "exp" | "ln" | "log10" | "log2" | "sqrt" | "cbrt" |
+            "abs" | "floor" | "ceil" | "round" | "trunc");
+        let [arg] = args.as_slice() else {
+            return Err(Self::unsupported(tr!("codegen.function", name = name)));
+        };
+        if !known {
+            return Err(Self::unsupported(tr!("codegen.function", name = name)));
+        }
+
+        Ok(match self.language {
+            Language::Rust => Code::atom(self.rust_method(arg, name, &[])),
+            Language::C => {
+                let name = match name {
+                    "ln" => "log",
+                    "abs" => "fabs",
+                    other => other,
+                };
+                Code::atom(format!("{}({})", name, arg.text))
+            }
+            Language::Python => match name {
+                "abs" => Code::atom(format!("abs({})", arg.text)),
+                "ln" => Code::atom(format!("math.log({})", arg.text)),
+                "cbrt" => self.nth_root(Code::new(arg.text.clone(), arg.prec), 3),
+                // Python 的 round 采用银行家舍入，这里保持远离零的舍入
+                "round" => Code::atom(format!("math.copysign(math.floor(abs({0}) + 0.5), {0})", arg.text)),
+                other => Code::atom(format!("math.{}({})", other, arg.text)),
+            },
+        })
+    }
+
+    fn unsupported(what: String) -> FormatError {
+        FormatError::FormatFailure { message: tr!("codegen.unsupported", what = what) }
+    }
+}
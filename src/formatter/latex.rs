@@ -1,9 +1,12 @@
 //! # LaTeX 格式化器
 //!
-//! 将表达式格式化为 LaTeX 格式。
+//! 将表达式格式化为 LaTeX 格式。设置 [`FormatOptions::line_width`] 后，
+//! 估算宽度超出行宽的表达式在顶层加法项边界处断行，放进 `align*`、`multline*` 或 `dmath*` 环境；
+//! 分式、矩阵等单个项不会被拆开，超宽的项独占一行。
 
-use crate::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator};
-use super::{Formatter, FormatOptions};
+use crate::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator, INDEX_FUNCTION, INDEX_ALL};
+use std::borrow::Cow;
+use super::{negated_term, ordering, Formatter, FormatOptions, MultilineEnv};
 use num_traits::ToPrimitive;
 
 /// LaTeX 格式化器
@@ -96,11 +99,15 @@ impl LaTeXFormatter {
     
     /// 格式化二元运算表达式
     fn format_binary_op(&self, op: &BinaryOperator, left: &Expression, right: &Expression) -> String {
-        let left_str = self.format(left);
-        let right_str = self.format(right);
+        let left_str = self.format_operand(left, op, false);
+        let right_str = self.format_operand(right, op, true);
         
         match op {
-            BinaryOperator::Add => format!("{} + {}", left_str, right_str),
+            // a + (-b)、a + (-2)*b 写作 a - b、a - 2b
+            BinaryOperator::Add => match negated_term(right) {
+                Some(magnitude) => format!("{} - {}", left_str, self.format_operand(&magnitude, &BinaryOperator::Subtract, true)),
+                None => format!("{} + {}", left_str, right_str),
+            },
             BinaryOperator::Subtract => format!("{} - {}", left_str, right_str),
             BinaryOperator::Multiply => {
                 if self.should_omit_multiply_symbol(left, right) {
@@ -129,6 +136,43 @@ impl LaTeXFormatter {
         }
     }
     
+    /// 格式化二元运算的运算数，按需包上括号
+    ///
+    /// 括号规则与标准格式化器一致，但分子、分母与指数本身由花括号分组，
+    /// 分式作运算数时也不会产生歧义，这些位置只在带符号时才加括号
+    fn format_operand(&self, expr: &Expression, parent: &BinaryOperator, is_right: bool) -> String {
+        let formatted = self.format(expr);
+        if !self.options.use_parentheses {
+            return formatted;
+        }
+        let needs_parens = match parent {
+            BinaryOperator::Divide => false,
+            BinaryOperator::Power if is_right => false,
+            BinaryOperator::Power => expr.needs_parens_as_operand(parent, is_right),
+            _ if Self::is_fraction(expr) => expr.has_leading_sign() && expr.needs_parens_as_operand(parent, is_right),
+            _ => expr.needs_parens_as_operand(parent, is_right),
+        };
+        if needs_parens {
+            format!("\\left({}\\right)", formatted)
+        } else {
+            formatted
+        }
+    }
+    
+    /// 是否排版为 `\\frac`
+    fn is_fraction(expr: &Expression) -> bool {
+        matches!(expr, Expression::BinaryOp { op: BinaryOperator::Divide, .. } | Expression::Number(Number::Rational(_)))
+    }
+    
+    /// 给需要括号的前缀或后缀运算数包上括号
+    fn wrap_unary_operand(&self, operand: &Expression, needs_parens: bool) -> String {
+        if needs_parens && self.options.use_parentheses {
+            format!("\\left({}\\right)", self.format(operand))
+        } else {
+            self.format(operand)
+        }
+    }
+    
     /// 判断是否应该省略乘法符号
     fn should_omit_multiply_symbol(&self, left: &Expression, right: &Expression) -> bool {
         match (left, right) {
@@ -146,8 +190,8 @@ impl LaTeXFormatter {
         let operand_str = self.format(operand);
         
         match op {
-            UnaryOperator::Negate => format!("-{}", operand_str),
-            UnaryOperator::Plus => format!("+{}", operand_str),
+            UnaryOperator::Negate => format!("-{}", self.wrap_unary_operand(operand, operand.needs_parens_as_prefix_operand() && !Self::is_fraction(operand))),
+            UnaryOperator::Plus => format!("+{}", self.wrap_unary_operand(operand, operand.needs_parens_as_prefix_operand() && !Self::is_fraction(operand))),
             UnaryOperator::Sqrt => format!("\\sqrt{{{}}}", operand_str),
             UnaryOperator::Abs => format!("\\left|{}\\right|", operand_str),
             UnaryOperator::Sin => format!("\\sin\\left({}\\right)", operand_str),
@@ -166,16 +210,16 @@ impl LaTeXFormatter {
             UnaryOperator::Log10 => format!("\\log_{{10}}\\left({}\\right)", operand_str),
             UnaryOperator::Log2 => format!("\\log_2\\left({}\\right)", operand_str),
             UnaryOperator::Exp => format!("\\exp\\left({}\\right)", operand_str),
-            UnaryOperator::Factorial => format!("{}!", operand_str),
+            UnaryOperator::Factorial => format!("{}!", self.wrap_unary_operand(operand, operand.needs_parens_as_postfix_operand())),
             UnaryOperator::Gamma => format!("\\Gamma\\left({}\\right)", operand_str),
-            UnaryOperator::Not => format!("\\neg {}", operand_str),
+            UnaryOperator::Not => format!("\\neg {}", self.wrap_unary_operand(operand, operand.needs_parens_as_prefix_operand())),
             UnaryOperator::Real => format!("\\text{{Re}}\\left({}\\right)", operand_str),
             UnaryOperator::Imaginary => format!("\\text{{Im}}\\left({}\\right)", operand_str),
             UnaryOperator::Conjugate => format!("\\overline{{{}}}", operand_str),
             UnaryOperator::Argument => format!("\\arg\\left({}\\right)", operand_str),
-            UnaryOperator::Transpose => format!("{}^T", operand_str),
+            UnaryOperator::Transpose => format!("{}^T", self.wrap_unary_operand(operand, operand.needs_parens_as_postfix_operand())),
             UnaryOperator::Determinant => format!("\\det\\left({}\\right)", operand_str),
-            UnaryOperator::Inverse => format!("{}^{{-1}}", operand_str),
+            UnaryOperator::Inverse => format!("{}^{{-1}}", self.wrap_unary_operand(operand, operand.needs_parens_as_postfix_operand())),
             UnaryOperator::Trace => format!("\\text{{tr}}\\left({}\\right)", operand_str),
         }
     }
@@ -185,6 +229,12 @@ impl LaTeXFormatter {
         let args_str: Vec<String> = args.iter().map(|arg| self.format(arg)).collect();
         
         match name {
+            // 下标访问 A_{i, j}
+            INDEX_FUNCTION if args.len() >= 2 => {
+                let target = if args[0].needs_parens_as_index_target() { format!("\\left({}\\right)", args_str[0]) } else { args_str[0].clone() };
+                format!("{}_{{{}}}", target, args_str[1..].join(", "))
+            }
+            INDEX_ALL if args.is_empty() => INDEX_ALL.to_string(),
             // 平方根函数
             "sqrt" => {
                 if args.len() == 1 {
@@ -193,6 +243,12 @@ impl LaTeXFormatter {
                     format!("\\text{{sqrt}}\\left({}\\right)", args_str.join(", "))
                 }
             }
+            // n 次根函数
+            "cbrt" if args.len() == 1 => format!("\\sqrt[3]{{{}}}", args_str[0]),
+            "root" if args.len() == 2 => format!("\\sqrt[{}]{{{}}}", args_str[1], args_str[0]),
+            // 取整函数
+            "floor" if args.len() == 1 => format!("\\left\\lfloor {} \\right\\rfloor", args_str[0]),
+            "ceil" if args.len() == 1 => format!("\\left\\lceil {} \\right\\rceil", args_str[0]),
             // 三角函数
             "sin" => format!("\\sin\\left({}\\right)", args_str.join(", ")),
             "cos" => format!("\\cos\\left({}\\right)", args_str.join(", ")),
@@ -231,6 +287,10 @@ impl LaTeXFormatter {
                 }
             }
             "gamma" => format!("\\Gamma\\left({}\\right)", args_str.join(", ")),
+            // 特殊函数
+            "erf" | "erfc" | "Si" | "Ci" | "Ei" | "li" | "sinc" => {
+                format!("\\operatorname{{{}}}\\left({}\\right)", name, args_str.join(", "))
+            }
             // 复数函数
             "real" => format!("\\text{{Re}}\\left({}\\right)", args_str.join(", ")),
             "imag" => format!("\\text{{Im}}\\left({}\\right)", args_str.join(", ")),
@@ -266,6 +326,75 @@ impl LaTeXFormatter {
         format!("\\begin{{pmatrix}} {} \\end{{pmatrix}}", elements.join(" \\\\ "))
     }
     
+    /// 按行宽断行；放得下一行或无处可断时返回单行结果
+    fn format_multiline(&self, expr: &Expression, line_width: usize) -> String {
+        let inline = Self {
+            options: FormatOptions { line_width: None, ..self.options.clone() },
+        };
+        let single = inline.format(expr);
+        if estimate_width(&single) <= line_width {
+            return single;
+        }
+        
+        // 关系式只拆右边，左边连同关系符放在首行
+        let (lhs, rhs) = match expr {
+            Expression::BinaryOp { op, left, right } => match relation_symbol(op) {
+                Some(symbol) => (Some((inline.format_operand(left, op, false), symbol)), right.as_ref()),
+                None => (None, expr),
+            },
+            _ => (None, expr),
+        };
+        let env = self.options.multiline_env;
+        let (first_prefix, continuation) = match (env, &lhs) {
+            (MultilineEnv::Align, Some((left, symbol))) => (format!("{} &{} ", left, symbol), "&\\quad "),
+            (MultilineEnv::Align, None) => ("&".to_string(), "&\\quad "),
+            (_, Some((left, symbol))) => (format!("{} {} ", left, symbol), ""),
+            (_, None) => (String::new(), ""),
+        };
+        
+        let mut lines = Vec::new();
+        let mut current = first_prefix;
+        let mut has_term = false;
+        for term in inline.additive_terms(rhs) {
+            let candidate = if has_term { format!("{} {}", current, term) } else { format!("{}{}", current, term) };
+            // 行首的项总是放下，超宽也不拆；只有左边加关系符时可以单独成行
+            if estimate_width(&candidate) <= line_width || (!has_term && (lhs.is_none() || !lines.is_empty())) {
+                current = candidate;
+            } else {
+                lines.push(if has_term { current } else { current.trim_end().to_string() });
+                current = format!("{}{}", continuation, term);
+            }
+            has_term = true;
+        }
+        lines.push(current);
+        if lines.len() == 1 {
+            return single;
+        }
+        
+        match env {
+            MultilineEnv::Align => format!("\\begin{{align*}}\n{}\n\\end{{align*}}", lines.join(" \\\\\n")),
+            MultilineEnv::Multline => format!("\\begin{{multline*}}\n{}\n\\end{{multline*}}", lines.join(" \\\\\n")),
+            MultilineEnv::Breqn => format!("\\begin{{dmath*}}\n{}\n\\end{{dmath*}}", lines.join("\n")),
+        }
+    }
+    
+    /// 把加减链拆成各项，首项原样，其余项带 `+ ` 或 `- ` 前缀；依次用空格连接即为单行结果
+    fn additive_terms(&self, expr: &Expression) -> Vec<String> {
+        let expr = if self.options.sort_terms { ordering::sort_top_level(expr, self.options.term_order) } else { Cow::Borrowed(expr) };
+        match expr.as_ref() {
+            Expression::BinaryOp { op: op @ (BinaryOperator::Add | BinaryOperator::Subtract), left, right } => {
+                let mut terms = self.additive_terms(left);
+                terms.push(match (op, negated_term(right)) {
+                    (BinaryOperator::Add, Some(magnitude)) => format!("- {}", self.format_operand(&magnitude, &BinaryOperator::Subtract, true)),
+                    (BinaryOperator::Add, None) => format!("+ {}", self.format_operand(right, op, true)),
+                    _ => format!("- {}", self.format_operand(right, op, true)),
+                });
+                terms
+            }
+            expr => vec![self.format(expr)],
+        }
+    }
+    
     /// 格式化集合
     fn format_set(&self, set: &[Expression]) -> String {
         let elements: Vec<String> = set.iter().map(|elem| self.format(elem)).collect();
@@ -288,6 +417,14 @@ impl Default for LaTeXFormatter {
 
 impl Formatter for LaTeXFormatter {
     fn format(&self, expr: &Expression) -> String {
+        if let Some(line_width) = self.options.line_width {
+            return self.format_multiline(expr, line_width);
+        }
+        if self.options.sort_terms {
+            if let Cow::Owned(sorted) = ordering::sort_top_level(expr, self.options.term_order) {
+                return self.format(&sorted);
+            }
+        }
         match expr {
             Expression::Number(number) => self.format_number(number),
             Expression::Variable(name) => name.clone(),
@@ -319,4 +456,166 @@ impl Formatter for LaTeXFormatter {
     fn set_options(&mut self, options: FormatOptions) {
         self.options = options;
     }
-}
\ No newline at end of file
+}
+
+/// 断行时可以对齐的关系符
+fn relation_symbol(op: &BinaryOperator) -> Option<&'static str> {
+    match op {
+        BinaryOperator::Equal => Some("="),
+        BinaryOperator::NotEqual => Some("\\neq"),
+        BinaryOperator::Less => Some("<"),
+        BinaryOperator::LessEqual => Some("\\leq"),
+        BinaryOperator::Greater => Some(">"),
+        BinaryOperator::GreaterEqual => Some("\\geq"),
+        _ => None,
+    }
+}
+
+/// 上下标相对正文的宽度比例
+const SCRIPT_WEIGHT: f64 = 0.7;
+/// 二元运算符和关系符连同两侧间距的宽度
+const OPERATOR_WEIGHT: f64 = 2.0;
+/// 按名称排版的算子，宽度为名称长度
+const OPERATOR_NAMES: &[&str] = &[
+    "sin", "cos", "tan", "arcsin", "arccos", "arctan", "sinh", "cosh", "tanh",
+    "ln", "log", "exp", "det", "arg", "max", "min", "bmod", "lim",
+];
+/// 作二元运算符或关系符的命令
+const BINARY_COMMANDS: &[&str] = &[
+    "cdot", "times", "neq", "leq", "geq", "land", "lor", "cup", "cap", "setminus",
+];
+
+/// 估算 LaTeX 片段排版后的宽度，单位约为一个正文字符
+///
+/// 启发式规则：普通字符记 1，空白与对齐符 `&` 不计；`+ - = < >` 等运算符连同间距记 2；
+/// 上下标按 0.7 折算；`\frac` 取分子分母中较宽者；矩阵取各列最宽元素之和；
+/// `\left`、`\right` 只计定界符本身。结果向上取整，同一输入总得到同一结果。
+pub fn estimate_width(latex: &str) -> usize {
+    let chars: Vec<char> = latex.chars().collect();
+    let mut pos = 0;
+    let mut width = 0.0;
+    // 多出的右花括号忽略，继续估算后面的部分
+    while pos < chars.len() {
+        width += group_width(&chars, &mut pos);
+        pos += 1;
+    }
+    width.ceil() as usize
+}
+
+/// 从 `pos` 估算到匹配的右花括号（或结尾）为止，`pos` 停在右花括号上
+fn group_width(chars: &[char], pos: &mut usize) -> f64 {
+    let mut width = 0.0;
+    while *pos < chars.len() {
+        let c = chars[*pos];
+        *pos += 1;
+        width += match c {
+            '}' => {
+                *pos -= 1;
+                return width;
+            }
+            '{' => braced_width(chars, pos),
+            '^' | '_' => SCRIPT_WEIGHT * argument_width(chars, pos),
+            '\\' => command_width(chars, pos),
+            '+' | '-' | '=' | '<' | '>' => OPERATOR_WEIGHT,
+            c if c.is_whitespace() || c == '&' => 0.0,
+            _ => 1.0,
+        };
+    }
+    width
+}
+
+/// 估算 `pos` 处左花括号之后的分组（左花括号已读过），并跳过右花括号
+fn braced_width(chars: &[char], pos: &mut usize) -> f64 {
+    let width = group_width(chars, pos);
+    *pos += 1;
+    width
+}
+
+/// 命令或上下标的一个参数：花括号分组、命令或单个字符
+fn argument_width(chars: &[char], pos: &mut usize) -> f64 {
+    while chars.get(*pos).is_some_and(|c| c.is_whitespace()) {
+        *pos += 1;
+    }
+    match chars.get(*pos) {
+        Some('{') => {
+            *pos += 1;
+            braced_width(chars, pos)
+        }
+        Some('\\') => {
+            *pos += 1;
+            command_width(chars, pos)
+        }
+        Some(_) => {
+            *pos += 1;
+            1.0
+        }
+        None => 0.0,
+    }
+}
+
+/// 估算 `pos` 处反斜杠之后的命令（反斜杠已读过）
+fn command_width(chars: &[char], pos: &mut usize) -> f64 {
+    let start = *pos;
+    while chars.get(*pos).is_some_and(|c| c.is_ascii_alphabetic()) {
+        *pos += 1;
+    }
+    let name: String = chars[start..*pos].iter().collect();
+    if name.is_empty() {
+        // 单字符命令：`\\` 换行、`\,` 等间距不计宽度，`\{` 等转义字符记 1
+        let Some(&c) = chars.get(*pos) else { return 0.0 };
+        *pos += 1;
+        return if c.is_ascii_punctuation() && !matches!(c, '{' | '}' | '|') { 0.0 } else { 1.0 };
+    }
+    match name.as_str() {
+        "frac" => argument_width(chars, pos).max(argument_width(chars, pos)),
+        "sqrt" => {
+            let index = if chars.get(*pos) == Some(&'[') {
+                let end = chars[*pos..].iter().position(|&c| c == ']').map_or(chars.len(), |offset| *pos + offset);
+                let inner: String = chars[*pos + 1..end].iter().collect();
+                *pos = (end + 1).min(chars.len());
+                SCRIPT_WEIGHT * estimate_width(&inner) as f64
+            } else {
+                0.0
+            };
+            index + 1.0 + argument_width(chars, pos)
+        }
+        "left" | "right" => argument_width(chars, pos),
+        "text" | "operatorname" | "mathrm" | "overline" => argument_width(chars, pos),
+        "begin" => environment_width(chars, pos),
+        "end" => {
+            argument_width(chars, pos);
+            0.0
+        }
+        "quad" => 2.0,
+        "qquad" => 4.0,
+        name if OPERATOR_NAMES.contains(&name) => name.len() as f64,
+        name if BINARY_COMMANDS.contains(&name) => OPERATOR_WEIGHT,
+        _ => 1.0,
+    }
+}
+
+/// 估算 `\begin{env}...\end{env}`（`\begin` 已读过）：各列最宽元素之和，列间与两侧定界符各记 1
+fn environment_width(chars: &[char], pos: &mut usize) -> f64 {
+    let rest: String = chars[*pos..].iter().collect();
+    let Some(env_end) = rest.find('}') else {
+        *pos = chars.len();
+        return 0.0;
+    };
+    let end_marker = format!("\\end{}", &rest[..=env_end]);
+    let body_start = env_end + 1;
+    let body_end = rest[body_start..].find(&end_marker).map_or(rest.len(), |offset| body_start + offset);
+    *pos += rest[..(body_end + end_marker.len()).min(rest.len())].chars().count();
+    
+    let mut columns: Vec<usize> = Vec::new();
+    for row in rest[body_start..body_end].split("\\\\") {
+        for (i, cell) in row.split('&').enumerate() {
+            let width = estimate_width(cell);
+            match columns.get_mut(i) {
+                Some(column) => *column = (*column).max(width),
+                None => columns.push(width),
+            }
+        }
+    }
+    let cells: usize = columns.iter().sum();
+    (cells + columns.len().saturating_sub(1) + 2) as f64
+}
@@ -2,8 +2,9 @@
 //!
 //! 将表达式格式化为 MathML 格式。
 
-use crate::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator};
-use super::{Formatter, FormatOptions};
+use crate::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator, INDEX_FUNCTION, INDEX_ALL};
+use std::borrow::Cow;
+use super::{ordering, Formatter, FormatOptions};
 use num_traits::ToPrimitive;
 
 /// MathML 格式化器
@@ -289,6 +290,18 @@ impl MathMLFormatter {
     /// 格式化函数调用
     fn format_function(&self, name: &str, args: &[Expression]) -> String {
         let args_str: Vec<String> = args.iter().map(|arg| self.format(arg)).collect();
+        // 下标访问写成下标
+        if name == INDEX_FUNCTION && args.len() >= 2 {
+            let target = if args[0].needs_parens_as_index_target() {
+                format!("<mrow><mo>(</mo>{}<mo>)</mo></mrow>", args_str[0])
+            } else {
+                args_str[0].clone()
+            };
+            return format!("<msub>{}<mrow>{}</mrow></msub>", target, args_str[1..].join("<mo>,</mo>"));
+        }
+        if name == INDEX_ALL && args.is_empty() {
+            return format!("<mo>{}</mo>", INDEX_ALL);
+        }
         format!(
             "<mrow><mi>{}</mi><mo>(</mo>{}<mo>)</mo></mrow>",
             name,
@@ -354,6 +367,11 @@ impl Default for MathMLFormatter {
 
 impl Formatter for MathMLFormatter {
     fn format(&self, expr: &Expression) -> String {
+        if self.options.sort_terms {
+            if let Cow::Owned(sorted) = ordering::sort_top_level(expr, self.options.term_order) {
+                return self.format(&sorted);
+            }
+        }
         let content = match expr {
             Expression::Number(number) => self.format_number(number),
             Expression::Variable(name) => format!("<mi>{}</mi>", name),
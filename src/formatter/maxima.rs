@@ -0,0 +1,272 @@
+//! # Maxima 格式化器
+//!
+//! 将表达式格式化为 Maxima/wxMaxima 可直接读入的语法：常量带 `%` 前缀（`%pi`、`%e`、`%i`），
+//! 乘法总是写出 `*`，自然对数写作 `log`，矩阵写作 `matrix([1, 2], [3, 4])`。
+
+use crate::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator, INDEX_FUNCTION, INDEX_ALL};
+use std::borrow::Cow;
+use super::{ordering, Formatter, FormatOptions};
+use num_traits::ToPrimitive;
+
+/// Maxima 格式化器
+pub struct MaximaFormatter {
+    options: FormatOptions,
+}
+
+impl MaximaFormatter {
+    /// 创建新的 Maxima 格式化器
+    pub fn new() -> Self {
+        Self {
+            options: FormatOptions::default(),
+        }
+    }
+
+    /// 格式化数值
+    fn format_number(&self, number: &Number) -> String {
+        match number {
+            Number::Integer(i) => i.to_string(),
+            Number::Rational(r) => {
+                if r.denom() == &num_bigint::BigInt::from(1) {
+                    r.numer().to_string()
+                } else {
+                    format!("{}/{}", r.numer(), r.denom())
+                }
+            }
+            Number::Real(r) => {
+                if let Some(precision) = self.options.precision {
+                    format!("{:.prec$}", r.to_f64().unwrap_or(0.0), prec = precision)
+                } else {
+                    r.to_string()
+                }
+            }
+            Number::Complex { real, imaginary } => {
+                let magnitude = if imaginary.is_negative() { -imaginary.as_ref().clone() } else { imaginary.as_ref().clone() };
+                let imag_part = if magnitude.is_one() {
+                    "%i".to_string()
+                } else {
+                    format!("{}*%i", self.format_number(&magnitude))
+                };
+                match (real.is_zero(), imaginary.is_negative()) {
+                    (true, false) => imag_part,
+                    (true, true) => format!("-{}", imag_part),
+                    (false, false) => format!("{}+{}", self.format_number(real), imag_part),
+                    (false, true) => format!("{}-{}", self.format_number(real), imag_part),
+                }
+            }
+            Number::Constant(constant) => self.format_constant(constant),
+            Number::Symbolic(expr) => self.format(expr),
+            // Maxima 以小数点区分浮点数与整数
+            Number::Float(f) => {
+                if let Some(precision) = self.options.precision {
+                    format!("{:.prec$}", f, prec = precision)
+                } else {
+                    format!("{:?}", f)
+                }
+            }
+        }
+    }
+
+    /// 格式化数学常量
+    fn format_constant(&self, constant: &MathConstant) -> String {
+        match constant {
+            MathConstant::Pi => "%pi",
+            MathConstant::E => "%e",
+            MathConstant::I => "%i",
+            MathConstant::EulerGamma => "%gamma",
+            MathConstant::GoldenRatio => "%phi",
+            // Maxima 没有内置的卡塔兰常数
+            MathConstant::Catalan => "catalan",
+            MathConstant::PositiveInfinity => "inf",
+            MathConstant::NegativeInfinity => "minf",
+            MathConstant::Undefined => "und",
+        }.to_string()
+    }
+
+    /// 格式化二元运算的运算数，按需包上括号
+    fn format_operand(&self, expr: &Expression, parent: &BinaryOperator, is_right: bool) -> String {
+        if self.options.use_parentheses && expr.needs_parens_as_operand(parent, is_right) {
+            format!("({})", self.format(expr))
+        } else {
+            self.format(expr)
+        }
+    }
+
+    /// 格式化二元运算表达式
+    fn format_binary_op(&self, op: &BinaryOperator, left: &Expression, right: &Expression) -> String {
+        // 没有中缀写法的运算写成函数调用
+        let function = match op {
+            BinaryOperator::Modulo => Some("mod"),
+            BinaryOperator::Union => Some("union"),
+            BinaryOperator::Intersection => Some("intersection"),
+            BinaryOperator::SetDifference => Some("setdifference"),
+            _ => None,
+        };
+        if let Some(function) = function {
+            return format!("{}({}, {})", function, self.format(left), self.format(right));
+        }
+
+        let left_str = self.format_operand(left, op, false);
+        let right_str = self.format_operand(right, op, true);
+        // 与 Maxima 的 string() 输出一样，乘除与乘方不加空格
+        let op_str = match op {
+            BinaryOperator::Power => return format!("{}^{}", left_str, right_str),
+            BinaryOperator::Multiply => return format!("{}*{}", left_str, right_str),
+            BinaryOperator::Divide => return format!("{}/{}", left_str, right_str),
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Equal => "=",
+            BinaryOperator::NotEqual => "#",
+            BinaryOperator::Less => "<",
+            BinaryOperator::LessEqual => "<=",
+            BinaryOperator::Greater => ">",
+            BinaryOperator::GreaterEqual => ">=",
+            BinaryOperator::And => "and",
+            BinaryOperator::Or => "or",
+            BinaryOperator::MatrixMultiply | BinaryOperator::DotProduct => ".",
+            // 需要加载 vect 包
+            BinaryOperator::CrossProduct => "~",
+            BinaryOperator::Modulo | BinaryOperator::Union | BinaryOperator::Intersection | BinaryOperator::SetDifference => {
+                unreachable!("已按函数调用处理")
+            }
+        };
+        format!("{} {} {}", left_str, op_str, right_str)
+    }
+
+    /// 格式化一元运算表达式
+    fn format_unary_op(&self, op: &UnaryOperator, operand: &Expression) -> String {
+        let wrapped = |needs_parens: bool| {
+            if needs_parens && self.options.use_parentheses {
+                format!("({})", self.format(operand))
+            } else {
+                self.format(operand)
+            }
+        };
+        let function = match op {
+            UnaryOperator::Negate => return format!("-{}", wrapped(operand.needs_parens_as_prefix_operand())),
+            UnaryOperator::Plus => return format!("+{}", wrapped(operand.needs_parens_as_prefix_operand())),
+            UnaryOperator::Not => return format!("not {}", wrapped(operand.needs_parens_as_prefix_operand())),
+            UnaryOperator::Factorial => return format!("{}!", wrapped(operand.needs_parens_as_postfix_operand())),
+            UnaryOperator::Log10 => return format!("log({})/log(10)", self.format(operand)),
+            UnaryOperator::Log2 => return format!("log({})/log(2)", self.format(operand)),
+            UnaryOperator::Sqrt => "sqrt",
+            UnaryOperator::Abs => "abs",
+            UnaryOperator::Sin => "sin",
+            UnaryOperator::Cos => "cos",
+            UnaryOperator::Tan => "tan",
+            UnaryOperator::Asin => "asin",
+            UnaryOperator::Acos => "acos",
+            UnaryOperator::Atan => "atan",
+            UnaryOperator::Sinh => "sinh",
+            UnaryOperator::Cosh => "cosh",
+            UnaryOperator::Tanh => "tanh",
+            UnaryOperator::Asinh => "asinh",
+            UnaryOperator::Acosh => "acosh",
+            UnaryOperator::Atanh => "atanh",
+            UnaryOperator::Ln => "log",
+            UnaryOperator::Exp => "exp",
+            UnaryOperator::Gamma => "gamma",
+            UnaryOperator::Real => "realpart",
+            UnaryOperator::Imaginary => "imagpart",
+            UnaryOperator::Conjugate => "conjugate",
+            UnaryOperator::Argument => "carg",
+            UnaryOperator::Transpose => "transpose",
+            UnaryOperator::Determinant => "determinant",
+            UnaryOperator::Inverse => "invert",
+            UnaryOperator::Trace => "mat_trace",
+        };
+        format!("{}({})", function, self.format(operand))
+    }
+
+    /// 格式化函数调用，函数名换成 Maxima 的对应名称
+    fn format_function(&self, name: &str, args: &[Expression]) -> String {
+        let args_str: Vec<String> = args.iter().map(|arg| self.format(arg)).collect();
+        let power_operand = |arg: &Expression| self.format_operand(arg, &BinaryOperator::Power, false);
+        let quotient_operand = |arg: &Expression| self.format_operand(arg, &BinaryOperator::Divide, true);
+
+        let renamed = match (name, args) {
+            // 下标访问：整行、整列用 row、col
+            (INDEX_FUNCTION, [target, row, all]) if all.is_index_all() && !row.is_index_all() => {
+                return format!("row({}, {})", self.format(target), self.format(row));
+            }
+            (INDEX_FUNCTION, [target, all, column]) if all.is_index_all() && !column.is_index_all() => {
+                return format!("col({}, {})", self.format(target), self.format(column));
+            }
+            (INDEX_FUNCTION, [target, ..]) if args.len() >= 2 => {
+                let target = if target.needs_parens_as_index_target() { format!("({})", args_str[0]) } else { args_str[0].clone() };
+                return format!("{}[{}]", target, args_str[1..].join(", "));
+            }
+            (INDEX_ALL, []) => return "all".to_string(),
+            // 对数只有自然对数，其余换底
+            ("ln", _) => "log",
+            ("log", [x, base]) => return format!("log({})/log({})", self.format(x), quotient_operand(base)),
+            ("log10", [x]) => return format!("log({})/log(10)", self.format(x)),
+            ("log2", [x]) => return format!("log({})/log(2)", self.format(x)),
+            // 根式写成分数次幂
+            ("cbrt", [x]) => return format!("{}^(1/3)", power_operand(x)),
+            ("root", [x, n]) => return format!("{}^(1/{})", power_operand(x), quotient_operand(n)),
+            ("factorial", [n]) => return format!("{}!", if n.needs_parens_as_postfix_operand() { format!("({})", args_str[0]) } else { args_str[0].clone() }),
+            ("arcsin", _) => "asin",
+            ("arccos", _) => "acos",
+            ("arctan", _) => "atan",
+            ("ceil", _) => "ceiling",
+            ("real", _) => "realpart",
+            ("imag", _) => "imagpart",
+            ("conj", _) => "conjugate",
+            ("arg", _) => "carg",
+            ("det", _) => "determinant",
+            ("tr" | "trace", _) => "mat_trace",
+            ("inv" | "inverse", _) => "invert",
+            _ => name,
+        };
+        format!("{}({})", renamed, args_str.join(", "))
+    }
+
+    /// 格式化矩阵
+    fn format_matrix(&self, matrix: &[Vec<Expression>]) -> String {
+        let rows: Vec<String> = matrix.iter().map(|row| format!("[{}]", self.format_list(row))).collect();
+        format!("matrix({})", rows.join(", "))
+    }
+
+    /// 逗号分隔的元素列表
+    fn format_list(&self, elements: &[Expression]) -> String {
+        elements.iter().map(|elem| self.format(elem)).collect::<Vec<_>>().join(", ")
+    }
+}
+
+impl Default for MaximaFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter for MaximaFormatter {
+    fn format(&self, expr: &Expression) -> String {
+        if self.options.sort_terms {
+            if let Cow::Owned(sorted) = ordering::sort_top_level(expr, self.options.term_order) {
+                return self.format(&sorted);
+            }
+        }
+        match expr {
+            Expression::Number(number) => self.format_number(number),
+            Expression::Variable(name) => name.clone(),
+            Expression::Constant(constant) => self.format_constant(constant),
+            Expression::BinaryOp { op, left, right } => self.format_binary_op(op, left, right),
+            Expression::UnaryOp { op, operand } => self.format_unary_op(op, operand),
+            Expression::Function { name, args } => self.format_function(name, args),
+            Expression::Matrix(matrix) => self.format_matrix(matrix),
+            // 向量对应 Maxima 的列表
+            Expression::Vector(vector) => format!("[{}]", self.format_list(vector)),
+            Expression::Set(set) => format!("{{{}}}", self.format_list(set)),
+            // Maxima 没有区间类型，写成关于 x 的条件会引入变量，这里保留区间记号
+            Expression::Interval { start, end, start_inclusive, end_inclusive } => {
+                let start_bracket = if *start_inclusive { "[" } else { "(" };
+                let end_bracket = if *end_inclusive { "]" } else { ")" };
+                format!("{}{}, {}{}", start_bracket, self.format(start), self.format(end), end_bracket)
+            }
+        }
+    }
+
+    fn set_options(&mut self, options: FormatOptions) {
+        self.options = options;
+    }
+}
@@ -1,20 +1,27 @@
 //! # 表达式格式化器
 //!
 //! 本模块负责将内部表达式结构转换为各种输出格式，
-//! 包括标准数学记号、LaTeX、MathML 等格式。
+//! 包括标准数学记号、LaTeX、MathML、Maxima、SymPy 等格式。
 
 pub mod standard;
 pub mod latex;
 pub mod mathml;
 pub mod terminal;
+pub mod maxima;
+pub mod sympy;
+pub mod codegen;
+pub mod ordering;
 
 // 重新导出格式化器
 pub use standard::StandardFormatter;
 pub use latex::LaTeXFormatter;
 pub use mathml::MathMLFormatter;
 pub use terminal::TerminalFormatter;
+pub use maxima::MaximaFormatter;
+pub use sympy::SymPyFormatter;
+pub use codegen::{CodeGenerator, CodegenOptions, Language};
 
-use crate::core::Expression;
+use crate::core::{BinaryOperator, Expression, UnaryOperator};
 
 /// 输出格式类型
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -27,6 +34,8 @@ pub enum FormatType {
     LaTeX,
     /// MathML 格式
     MathML,
+    /// Maxima 语法
+    Maxima,
 }
 
 /// 格式化选项
@@ -38,6 +47,38 @@ pub struct FormatOptions {
     pub precision: Option<usize>,
     /// 是否使用括号
     pub use_parentheses: bool,
+    /// 显示前是否重排加法项与乘法因子，见 [`ordering`]
+    pub sort_terms: bool,
+    /// 重排时加法项的顺序
+    pub term_order: TermOrder,
+    /// LaTeX 输出的行宽（估算的渲染宽度），超出时在加法项边界处断行；`None` 表示不断行
+    pub line_width: Option<usize>,
+    /// 断行后使用的 LaTeX 环境
+    pub multiline_env: MultilineEnv,
+}
+
+/// 显示时加法项的排列顺序，见 [`ordering::DegreeKey::compare`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum TermOrder {
+    /// 次数降序：`x^2 + x + 1`
+    #[default]
+    DegreeDesc,
+    /// 次数升序：`1 + x + x^2`
+    DegreeAsc,
+    /// 按变量字典序，不看总次数：`x^2 + x*y^3 + y`
+    Lexicographic,
+}
+
+/// 多行 LaTeX 输出使用的环境
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+pub enum MultilineEnv {
+    /// `align*`，续行在关系符或首项处对齐
+    #[default]
+    Align,
+    /// `multline*`，首行左对齐、末行右对齐
+    Multline,
+    /// breqn 宏包的 `dmath*`，只在源码中换行，由宏包决定排版断点
+    Breqn,
 }
 
 impl Default for FormatOptions {
@@ -46,6 +87,10 @@ impl Default for FormatOptions {
             format_type: FormatType::Standard,
             precision: None,
             use_parentheses: true,
+            sort_terms: false,
+            term_order: TermOrder::DegreeDesc,
+            line_width: None,
+            multiline_env: MultilineEnv::Align,
         }
     }
 }
@@ -59,6 +104,21 @@ pub trait Formatter: Send + Sync {
     fn set_options(&mut self, options: FormatOptions);
 }
 
+/// 以负号开头的加法项去掉负号后的形式：`-u` 为 `u`，负数取相反数，首个因子为负数的乘积把它变为正数（为 1 时省去）
+///
+/// 化简结果内部统一写成加上负项，格式化器据此把 `a + (-b)`、`a + (-2)*b` 写作 `a - b`、`a - 2*b`。
+pub(crate) fn negated_term(expr: &Expression) -> Option<Expression> {
+    match expr {
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => Some(operand.as_ref().clone()),
+        Expression::Number(n) if n.is_negative() => Some(Expression::Number(-n.clone())),
+        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => Some(match negated_term(left)? {
+            Expression::Number(n) if n.is_one() => right.as_ref().clone(),
+            magnitude => Expression::multiply(magnitude, right.as_ref().clone()),
+        }),
+        _ => None,
+    }
+}
+
 /// 格式化器工厂
 pub struct FormatterFactory;
 
@@ -70,6 +130,7 @@ impl FormatterFactory {
             FormatType::Terminal => Box::new(TerminalFormatter::new()),
             FormatType::LaTeX => Box::new(LaTeXFormatter::new()),
             FormatType::MathML => Box::new(MathMLFormatter::new()),
+            FormatType::Maxima => Box::new(MaximaFormatter::new()),
         }
     }
 }
@@ -0,0 +1,247 @@
+//! # 显示前的项排序
+//!
+//! 加法项按 [`TermOrder`] 排列，默认按多项式习惯次数降序，同次按变量字典序（`x^2`、`x*y`、`y^2`），
+//! 不含变量的常数项放在最后；乘法因子按数值、常量、变量（按名字）、其余（函数等）的顺序排列。
+//! `sin(x)` 这类非多项式项按函数名归入多项式项与常数项之间。
+//! 排序是稳定的，键相同的项保持原有的相对顺序。只改变显示顺序，不做任何化简。
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use crate::core::{Expression, BinaryOperator, UnaryOperator};
+use super::TermOrder;
+
+/// 按默认顺序递归地重排表达式中所有的加法项与乘法因子
+pub fn sort_terms(expr: &Expression) -> Expression {
+    sort_terms_by(expr, TermOrder::default())
+}
+
+/// 按给定的加法项顺序递归地重排表达式中所有的加法项与乘法因子
+pub fn sort_terms_by(expr: &Expression, order: TermOrder) -> Expression {
+    let sort = |expr: &Expression| sort_terms_by(expr, order);
+    let sorted = sort_top_level(expr, order).into_owned();
+    match sorted {
+        Expression::BinaryOp { op, left, right } => Expression::BinaryOp {
+            op,
+            left: Box::new(sort(&left)),
+            right: Box::new(sort(&right)),
+        },
+        Expression::UnaryOp { op, operand } => Expression::UnaryOp { op, operand: Box::new(sort(&operand)) },
+        Expression::Function { name, args } => Expression::Function { name, args: args.iter().map(sort).collect() },
+        Expression::Matrix(rows) => Expression::Matrix(rows.iter().map(|row| row.iter().map(sort).collect()).collect()),
+        Expression::Vector(elements) => Expression::Vector(elements.iter().map(sort).collect()),
+        Expression::Set(elements) => Expression::Set(elements.iter().map(sort).collect()),
+        Expression::Interval { start, end, start_inclusive, end_inclusive } => Expression::Interval {
+            start: Box::new(sort(&start)),
+            end: Box::new(sort(&end)),
+            start_inclusive,
+            end_inclusive,
+        },
+        other => other,
+    }
+}
+
+/// 只重排以 `expr` 为根的和式或乘积，已有序时原样借出
+///
+/// 格式化器在递归的每一层调用它，子表达式在各自被格式化时再排序。
+pub fn sort_top_level(expr: &Expression, order: TermOrder) -> Cow<'_, Expression> {
+    match expr {
+        Expression::BinaryOp { op: BinaryOperator::Add | BinaryOperator::Subtract, .. } => {
+            let mut terms = Vec::new();
+            collect_terms(expr, false, &mut terms);
+            let keys: Vec<DegreeKey> = terms.iter().map(|(term, _)| degree_key(term)).collect();
+            match sorted_order(&keys, |a, b| a.compare(b, order)) {
+                Some(order) => Cow::Owned(rebuild_sum(order.into_iter().map(|i| terms[i]))),
+                None => Cow::Borrowed(expr),
+            }
+        }
+        Expression::BinaryOp { op: BinaryOperator::Multiply, .. } => {
+            let mut factors = Vec::new();
+            collect_factors(expr, &mut factors);
+            // 矩阵乘法不满足交换律
+            if factors.iter().any(|factor| matches!(factor, Expression::Matrix(_) | Expression::Vector(_))) {
+                return Cow::Borrowed(expr);
+            }
+            let keys: Vec<(u8, Option<&str>)> = factors.iter().map(|factor| factor_key(factor)).collect();
+            match sorted_order(&keys, |a, b| a.cmp(b)) {
+                Some(order) => Cow::Owned(order.into_iter().map(|i| factors[i].clone())
+                    .reduce(Expression::multiply)
+                    .expect("乘积至少有两个因子")),
+                None => Cow::Borrowed(expr),
+            }
+        }
+        _ => Cow::Borrowed(expr),
+    }
+}
+
+/// 稳定排序后的下标顺序；已经有序时返回 `None`
+fn sorted_order<K>(keys: &[K], compare: impl Fn(&K, &K) -> Ordering) -> Option<Vec<usize>> {
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    order.sort_by(|&a, &b| compare(&keys[a], &keys[b]));
+    (!order.iter().enumerate().all(|(position, &index)| position == index)).then_some(order)
+}
+
+/// 展开加减链，得到各项及其是否取负；减号右侧整体作为一项
+fn collect_terms<'a>(expr: &'a Expression, negative: bool, terms: &mut Vec<(&'a Expression, bool)>) {
+    match expr {
+        Expression::BinaryOp { op: BinaryOperator::Add, left, right } => {
+            collect_terms(left, negative, terms);
+            collect_terms(right, negative, terms);
+        }
+        Expression::BinaryOp { op: BinaryOperator::Subtract, left, right } => {
+            collect_terms(left, negative, terms);
+            terms.push((right, !negative));
+        }
+        _ => terms.push((expr, negative)),
+    }
+}
+
+fn rebuild_sum<'a>(terms: impl Iterator<Item = (&'a Expression, bool)>) -> Expression {
+    terms.fold(None, |sum, (term, negative)| Some(match (sum, negative) {
+        (None, false) => term.clone(),
+        (None, true) => Expression::negate(term.clone()),
+        (Some(sum), false) => Expression::add(sum, term.clone()),
+        (Some(sum), true) => Expression::subtract(sum, term.clone()),
+    })).expect("和式至少有两项")
+}
+
+fn collect_factors<'a>(expr: &'a Expression, factors: &mut Vec<&'a Expression>) {
+    match expr {
+        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
+            collect_factors(left, factors);
+            collect_factors(right, factors);
+        }
+        _ => factors.push(expr),
+    }
+}
+
+/// 因子的排序键：数值、常量、变量或变量的幂（按变量名）、其余
+fn factor_key(factor: &Expression) -> (u8, Option<&str>) {
+    match factor {
+        Expression::Number(_) => (0, None),
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } if matches!(operand.as_ref(), Expression::Number(_)) => (0, None),
+        Expression::Constant(_) => (1, None),
+        Expression::Variable(name) => (2, Some(name)),
+        Expression::BinaryOp { op: BinaryOperator::Power, left, .. } => match left.as_ref() {
+            Expression::Variable(name) => (2, Some(name)),
+            _ => (3, None),
+        },
+        _ => (3, None),
+    }
+}
+
+/// 加法项的归类，按默认顺序中的先后排列
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TermClass {
+    /// 含变量的单项式，可带数值系数与函数因子，如 `3*x^2`、`x*sin(x)`
+    Monomial,
+    /// 不含变量次数的其它项，如 `sin(x)`、`2^x`
+    Opaque,
+    /// 不含变量的数值与常量
+    Constant,
+}
+
+/// 加法项的次数键：归类、总次数、按变量名排列的各变量指数，非多项式项另按函数名归类
+#[derive(Debug, Clone, PartialEq)]
+pub struct DegreeKey {
+    pub class: TermClass,
+    pub degree: f64,
+    pub powers: Vec<(String, f64)>,
+    /// 非多项式项的函数名，其它项为空
+    pub label: String,
+}
+
+/// 提取加法项的次数键；同一个项总是得到同一个键
+pub fn degree_key(term: &Expression) -> DegreeKey {
+    match term {
+        Expression::Number(_) | Expression::Constant(_) => DegreeKey { class: TermClass::Constant, ..DegreeKey::OPAQUE },
+        Expression::Variable(name) => DegreeKey {
+            class: TermClass::Monomial,
+            degree: 1.0,
+            powers: vec![(name.clone(), 1.0)],
+            label: String::new(),
+        },
+        Expression::UnaryOp { op: UnaryOperator::Negate | UnaryOperator::Plus, operand } => degree_key(operand),
+        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => degree_key(left).times(degree_key(right)),
+        Expression::BinaryOp { op: BinaryOperator::Divide, left, right }
+            if degree_key(right).class == TermClass::Constant => degree_key(left),
+        Expression::BinaryOp { op: BinaryOperator::Power, left, right } => match right.as_ref() {
+            Expression::Number(n) => match n.to_f64() {
+                Some(exponent) => degree_key(left).scaled(exponent),
+                None => DegreeKey::OPAQUE,
+            },
+            _ => DegreeKey::OPAQUE,
+        },
+        Expression::Function { name, .. } => DegreeKey { label: name.clone(), ..DegreeKey::OPAQUE },
+        _ => DegreeKey::OPAQUE,
+    }
+}
+
+impl DegreeKey {
+    const OPAQUE: DegreeKey = DegreeKey { class: TermClass::Opaque, degree: 0.0, powers: Vec::new(), label: String::new() };
+
+    fn times(self, other: DegreeKey) -> DegreeKey {
+        let mut powers = self.powers;
+        for (var, exponent) in other.powers {
+            match powers.binary_search_by(|(name, _)| name.cmp(&var)) {
+                Ok(index) => powers[index].1 += exponent,
+                Err(index) => powers.insert(index, (var, exponent)),
+            }
+        }
+        // 单项式乘以任何因子仍是单项式，常数只乘常数时才是常数
+        let (class, label) = match (self.class, other.class) {
+            (TermClass::Monomial, _) | (_, TermClass::Monomial) => (TermClass::Monomial, String::new()),
+            (TermClass::Constant, TermClass::Constant) => (TermClass::Constant, String::new()),
+            (TermClass::Opaque, _) => (TermClass::Opaque, self.label),
+            (_, TermClass::Opaque) => (TermClass::Opaque, other.label),
+        };
+        DegreeKey { class, degree: self.degree + other.degree, powers, label }
+    }
+
+    fn scaled(self, exponent: f64) -> DegreeKey {
+        DegreeKey {
+            degree: self.degree * exponent,
+            powers: self.powers.into_iter().map(|(var, power)| (var, power * exponent)).collect(),
+            ..self
+        }
+    }
+
+    /// 按给定顺序比较两个加法项的次数键
+    ///
+    /// - `DegreeDesc`：单项式、非多项式项、常数项依次排列，单项式按总次数降序，同次再按字典序
+    /// - `DegreeAsc`：与 `DegreeDesc` 相反
+    /// - `Lexicographic`：单项式按字典序，不看总次数
+    ///
+    /// 字典序逐个比较按名字排列的变量，指数大的在前，所以多变量时先看主变量的次数。
+    /// 非多项式项之间总是按函数名升序。
+    pub fn compare(&self, other: &DegreeKey, order: TermOrder) -> Ordering {
+        let ordering = match order {
+            TermOrder::DegreeDesc => self.class.cmp(&other.class)
+                .then_with(|| other.degree.partial_cmp(&self.degree).unwrap_or(Ordering::Equal))
+                .then_with(|| self.compare_powers(other)),
+            TermOrder::DegreeAsc => other.class.cmp(&self.class)
+                .then_with(|| self.degree.partial_cmp(&other.degree).unwrap_or(Ordering::Equal))
+                .then_with(|| other.compare_powers(self)),
+            TermOrder::Lexicographic => self.class.cmp(&other.class).then_with(|| self.compare_powers(other)),
+        };
+        ordering.then_with(|| self.label.cmp(&other.label))
+    }
+
+    /// 在字典序靠前的变量上指数大的在前
+    fn compare_powers(&self, other: &DegreeKey) -> Ordering {
+        let (mut mine, mut theirs) = (self.powers.iter().peekable(), other.powers.iter().peekable());
+        loop {
+            // 缺少的变量视为指数 0
+            let (a, b) = match (mine.peek(), theirs.peek()) {
+                (None, None) => return Ordering::Equal,
+                (Some((x, _)), Some((y, _))) if x == y => (mine.next().unwrap().1, theirs.next().unwrap().1),
+                (Some((x, _)), Some((y, _))) if x < y => (mine.next().unwrap().1, 0.0),
+                (Some(_), None) => (mine.next().unwrap().1, 0.0),
+                _ => (0.0, theirs.next().unwrap().1),
+            };
+            match b.partial_cmp(&a) {
+                Some(Ordering::Equal) | None => continue,
+                Some(ordering) => return ordering,
+            }
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! # SymPy 格式化器
+//!
+//! 将表达式格式化为 Python 中可直接求值的 SymPy 代码，约定以 `import sympy as sp` 导入：
+//! 乘方写作 `**`，常量写作 `sp.pi`、`sp.E`、`sp.I`，函数带 `sp.` 前缀，未知函数写作 `sp.Function("f")(x)`，
+//! 矩阵写作 `sp.Matrix([[1, 2], [3, 4]])`，下标换成从 0 开始。变量需要事先用 `sp.symbols` 声明。
+
+use crate::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator, INDEX_FUNCTION, INDEX_ALL};
+use super::{Formatter, FormatOptions};
+use num_traits::ToPrimitive;
+
+/// 名称与 SymPy 相同、直接加 `sp.` 前缀的函数
+const SYMPY_FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "cot", "sec", "csc", "asin", "acos", "atan", "atan2", "acot",
+    "sinh", "cosh", "tanh", "coth", "asinh", "acosh", "atanh",
+    "exp", "log", "sqrt", "cbrt", "root", "floor", "ceiling", "sign", "factorial", "binomial",
+    "gamma", "beta", "erf", "erfc", "gcd", "lcm", "re", "im", "conjugate", "arg", "trace",
+    "diff", "limit", "solve", "simplify", "expand", "factor", "collect", "apart", "together",
+    "series", "summation", "product", "det", "isprime", "totient", "fibonacci",
+];
+
+/// SymPy 格式化器
+pub struct SymPyFormatter {
+    options: FormatOptions,
+}
+
+impl SymPyFormatter {
+    /// 创建新的 SymPy 格式化器
+    pub fn new() -> Self {
+        Self {
+            options: FormatOptions::default(),
+        }
+    }
+
+    /// 格式化数值；分数写成 `sp.Rational`，避免 Python 整数除法得到浮点数
+    fn format_number(&self, number: &Number) -> String {
+        match number {
+            Number::Integer(i) => i.to_string(),
+            Number::Rational(r) => {
+                if r.denom() == &num_bigint::BigInt::from(1) {
+                    r.numer().to_string()
+                } else {
+                    format!("sp.Rational({}, {})", r.numer(), r.denom())
+                }
+            }
+            Number::Real(r) => match self.options.precision {
+                Some(precision) => format!("sp.Float({:.prec$})", r.to_f64().unwrap_or(0.0), prec = precision),
+                None => format!("sp.Float(\"{}\")", r),
+            },
+            Number::Complex { real, imaginary } => {
+                let imag_part = format!("{}*sp.I", self.format_number(imaginary));
+                if real.is_zero() {
+                    imag_part
+                } else {
+                    format!("{} + {}", self.format_number(real), imag_part)
+                }
+            }
+            Number::Constant(constant) => self.format_constant(constant),
+            Number::Symbolic(expr) => self.format(expr),
+            Number::Float(f) => match self.options.precision {
+                Some(precision) => format!("{:.prec$}", f, prec = precision),
+                None => format!("{:?}", f),
+            },
+        }
+    }
+
+    /// 格式化数学常量
+    fn format_constant(&self, constant: &MathConstant) -> String {
+        match constant {
+            MathConstant::Pi => "sp.pi",
+            MathConstant::E => "sp.E",
+            MathConstant::I => "sp.I",
+            MathConstant::EulerGamma => "sp.EulerGamma",
+            MathConstant::GoldenRatio => "sp.GoldenRatio",
+            MathConstant::Catalan => "sp.Catalan",
+            MathConstant::PositiveInfinity => "sp.oo",
+            MathConstant::NegativeInfinity => "-sp.oo",
+            MathConstant::Undefined => "sp.nan",
+        }.to_string()
+    }
+
+    /// 格式化二元运算的运算数，按需包上括号
+    ///
+    /// 除法和乘方左边的整数写成 `sp.Integer`，使 `1/2`、`2**(-1)` 得到精确值。
+    fn format_operand(&self, expr: &Expression, parent: &BinaryOperator, is_right: bool) -> String {
+        if !is_right && matches!(parent, BinaryOperator::Divide | BinaryOperator::Power) {
+            if let Expression::Number(Number::Integer(i)) = expr {
+                return format!("sp.Integer({})", i);
+            }
+        }
+        // Python 中 -x**2 是 -(x**2)，负数底数总要加括号
+        let negative_base = matches!(parent, BinaryOperator::Power) && !is_right && self.format(expr).starts_with('-');
+        if (self.options.use_parentheses && expr.needs_parens_as_operand(parent, is_right)) || negative_base {
+            format!("({})", self.format(expr))
+        } else {
+            self.format(expr)
+        }
+    }
+
+    /// 格式化二元运算表达式
+    fn format_binary_op(&self, op: &BinaryOperator, left: &Expression, right: &Expression) -> String {
+        // 没有中缀写法的运算写成函数或方法调用
+        let call = |function: &str| format!("sp.{}({}, {})", function, self.format(left), self.format(right));
+        let method = |name: &str| format!("({}).{}({})", self.format(left), name, self.format(right));
+        let op_str = match op {
+            BinaryOperator::Equal => return call("Eq"),
+            BinaryOperator::NotEqual => return call("Ne"),
+            BinaryOperator::And => return call("And"),
+            BinaryOperator::Or => return call("Or"),
+            BinaryOperator::Modulo => return call("Mod"),
+            BinaryOperator::Union => return call("Union"),
+            BinaryOperator::Intersection => return call("Intersection"),
+            BinaryOperator::SetDifference => return call("Complement"),
+            BinaryOperator::DotProduct => return method("dot"),
+            BinaryOperator::CrossProduct => return method("cross"),
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::Multiply | BinaryOperator::MatrixMultiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Power => "**",
+            BinaryOperator::Less => "<",
+            BinaryOperator::LessEqual => "<=",
+            BinaryOperator::Greater => ">",
+            BinaryOperator::GreaterEqual => ">=",
+        };
+        let left_str = self.format_operand(left, op, false);
+        let right_str = self.format_operand(right, op, true);
+        format!("{} {} {}", left_str, op_str, right_str)
+    }
+
+    /// 格式化一元运算表达式
+    fn format_unary_op(&self, op: &UnaryOperator, operand: &Expression) -> String {
+        let inner = self.format(operand);
+        let wrapped = |needs_parens: bool| if needs_parens { format!("({})", inner) } else { inner.clone() };
+        let function = match op {
+            UnaryOperator::Negate => return format!("-{}", wrapped(operand.needs_parens_as_prefix_operand())),
+            UnaryOperator::Plus => return format!("+{}", wrapped(operand.needs_parens_as_prefix_operand())),
+            UnaryOperator::Transpose => return format!("({}).T", inner),
+            UnaryOperator::Inverse => return format!("({}).inv()", inner),
+            UnaryOperator::Log10 => return format!("sp.log({}, 10)", inner),
+            UnaryOperator::Log2 => return format!("sp.log({}, 2)", inner),
+            UnaryOperator::Not => "Not",
+            UnaryOperator::Factorial => "factorial",
+            UnaryOperator::Sqrt => "sqrt",
+            UnaryOperator::Abs => "Abs",
+            UnaryOperator::Sin => "sin",
+            UnaryOperator::Cos => "cos",
+            UnaryOperator::Tan => "tan",
+            UnaryOperator::Asin => "asin",
+            UnaryOperator::Acos => "acos",
+            UnaryOperator::Atan => "atan",
+            UnaryOperator::Sinh => "sinh",
+            UnaryOperator::Cosh => "cosh",
+            UnaryOperator::Tanh => "tanh",
+            UnaryOperator::Asinh => "asinh",
+            UnaryOperator::Acosh => "acosh",
+            UnaryOperator::Atanh => "atanh",
+            UnaryOperator::Ln => "log",
+            UnaryOperator::Exp => "exp",
+            UnaryOperator::Gamma => "gamma",
+            UnaryOperator::Real => "re",
+            UnaryOperator::Imaginary => "im",
+            UnaryOperator::Conjugate => "conjugate",
+            UnaryOperator::Argument => "arg",
+            UnaryOperator::Determinant => "det",
+            UnaryOperator::Trace => "trace",
+        };
+        format!("sp.{}({})", function, inner)
+    }
+
+    /// 格式化函数调用，函数名换成 SymPy 的对应名称
+    fn format_function(&self, name: &str, args: &[Expression]) -> String {
+        let args_str: Vec<String> = args.iter().map(|arg| self.format(arg)).collect();
+        let renamed = match (name, args) {
+            // 下标从 1 开始换成从 0 开始
+            (INDEX_FUNCTION, [target, indices @ ..]) if !indices.is_empty() => {
+                let target = if target.needs_parens_as_index_target() { format!("({})", args_str[0]) } else { args_str[0].clone() };
+                let indices: Vec<String> = indices.iter().map(|index| self.format_index(index)).collect();
+                return format!("{}[{}]", target, indices.join(", "));
+            }
+            (INDEX_ALL, []) => return ":".to_string(),
+            // 定积分的积分限写成元组
+            ("integrate", [f, x, a, b]) => {
+                return format!("sp.integrate({}, ({}, {}, {}))", self.format(f), self.format(x), self.format(a), self.format(b));
+            }
+            ("log10", [x]) => return format!("sp.log({}, 10)", self.format(x)),
+            ("log2", [x]) => return format!("sp.log({}, 2)", self.format(x)),
+            ("inv" | "inverse", [m]) => return format!("({}).inv()", self.format(m)),
+            ("integrate", _) => "integrate",
+            ("ln", _) => "log",
+            ("abs", _) => "Abs",
+            ("min", _) => "Min",
+            ("max", _) => "Max",
+            ("arcsin", _) => "asin",
+            ("arccos", _) => "acos",
+            ("arctan", _) => "atan",
+            ("ceil", _) => "ceiling",
+            ("real", _) => "re",
+            ("imag", _) => "im",
+            ("conj", _) => "conjugate",
+            ("tr", _) => "trace",
+            (name, _) if SYMPY_FUNCTIONS.contains(&name) => name,
+            _ => return format!("sp.Function(\"{}\")({})", name, args_str.join(", ")),
+        };
+        format!("sp.{}({})", renamed, args_str.join(", "))
+    }
+
+    /// 从 1 开始的下标换成从 0 开始
+    fn format_index(&self, index: &Expression) -> String {
+        match index {
+            Expression::Number(Number::Integer(i)) => (i - num_bigint::BigInt::from(1)).to_string(),
+            index if index.is_index_all() => ":".to_string(),
+            index => format!("{} - 1", self.format(index)),
+        }
+    }
+
+    /// 逗号分隔的元素列表
+    fn format_list(&self, elements: &[Expression]) -> String {
+        elements.iter().map(|elem| self.format(elem)).collect::<Vec<_>>().join(", ")
+    }
+}
+
+impl Default for SymPyFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter for SymPyFormatter {
+    fn format(&self, expr: &Expression) -> String {
+        match expr {
+            Expression::Number(number) => self.format_number(number),
+            Expression::Variable(name) => name.clone(),
+            Expression::Constant(constant) => self.format_constant(constant),
+            Expression::BinaryOp { op, left, right } => self.format_binary_op(op, left, right),
+            Expression::UnaryOp { op, operand } => self.format_unary_op(op, operand),
+            Expression::Function { name, args } => self.format_function(name, args),
+            Expression::Matrix(matrix) => {
+                let rows: Vec<String> = matrix.iter().map(|row| format!("[{}]", self.format_list(row))).collect();
+                format!("sp.Matrix([{}])", rows.join(", "))
+            }
+            // 向量对应列向量
+            Expression::Vector(vector) => format!("sp.Matrix([{}])", self.format_list(vector)),
+            Expression::Set(set) => format!("sp.FiniteSet({})", self.format_list(set)),
+            Expression::Interval { start, end, start_inclusive, end_inclusive } => {
+                let open = |inclusive: bool| if inclusive { "False" } else { "True" };
+                format!("sp.Interval({}, {}, {}, {})", self.format(start), self.format(end), open(*start_inclusive), open(*end_inclusive))
+            }
+        }
+    }
+
+    fn set_options(&mut self, options: FormatOptions) {
+        self.options = options;
+    }
+}
@@ -2,13 +2,21 @@
 //!
 //! 专为终端交互模式设计的格式化器，支持颜色输出和数值近似值显示。
 
-use crate::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator};
-use super::{Formatter, FormatOptions};
+use crate::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator, INDEX_FUNCTION, INDEX_ALL};
+use std::borrow::Cow;
+use super::{negated_term, ordering, Formatter, FormatOptions};
+use crate::engine::special_functions;
 use ansi_term::Colour;
 use num_traits::{ToPrimitive, Zero};
 use std::f64::consts;
 
+/// 各层括号轮换使用的颜色：白、橙、紫
+const BRACKET_COLOURS: [Colour; 3] = [Colour::White, Colour::Fixed(208), Colour::Fixed(141)];
+
 /// 终端格式化器
+///
+/// 去掉转义码后的文本与 [`StandardFormatter`](super::StandardFormatter) 完全相同，
+/// 只为数字、变量、常量、函数名、运算符和括号着色，开启近似值显示时追加 `≈ 近似值`。
 pub struct TerminalFormatter {
     options: FormatOptions,
     /// 是否启用颜色输出
@@ -96,10 +104,10 @@ impl TerminalFormatter {
         }
     }
     
-    /// 为括号着色
-    fn colorize_parentheses(&self, text: &str) -> String {
+    /// 为括号着色，按嵌套深度轮换颜色，配对的括号颜色相同
+    fn colorize_bracket(&self, text: &str, depth: usize) -> String {
         if self.enable_colors {
-            Colour::White.paint(text).to_string()
+            BRACKET_COLOURS[depth % BRACKET_COLOURS.len()].paint(text).to_string()
         } else {
             text.to_string()
         }
@@ -188,7 +196,7 @@ impl TerminalFormatter {
                 }
             }
             BinaryOperator::Power => Some(left.powf(right)),
-            BinaryOperator::Modulo => Some(left % right),
+            BinaryOperator::Modulo => Some(left.rem_euclid(right)),
             _ => None, // 其他运算符暂不支持
         }
     }
@@ -314,6 +322,14 @@ impl TerminalFormatter {
                     None
                 }
             }
+            "erf" | "erfc" | "Si" | "Ci" | "Ei" | "li" | "sinc" => {
+                if args.len() == 1 {
+                    let arg_val = self.calculate_approximation(&args[0])?;
+                    special_functions::evaluate(name, arg_val).filter(|value| value.is_finite())
+                } else {
+                    None
+                }
+            }
             "pow" => {
                 if args.len() == 2 {
                     let base = self.calculate_approximation(&args[0])?;
@@ -342,22 +358,19 @@ impl TerminalFormatter {
         }
     }
     
-    /// 格式化数值并添加近似值
-    fn format_number_with_approximation(&self, number: &Number) -> String {
-        let formatted = self.format_number_basic(number);
-        
-        if self.show_approximations {
-            if let Some(approx) = self.number_to_f64(number) {
-                // 检查是否需要显示近似值
-                if self.should_show_approximation(number, approx) {
-                    let approx_str = format!("{:.prec$}", approx, prec = self.approximation_precision);
-                    let colored_approx = self.colorize_approximation(&format!(" ≈ {}", approx_str));
-                    return format!("{}{}", formatted, colored_approx);
-                }
-            }
+    
+    /// 数值的近似值后缀，不需要显示时为空
+    fn number_approximation(&self, number: &Number) -> String {
+        match self.number_to_f64(number) {
+            Some(approx) if self.show_approximations && self.should_show_approximation(number, approx) => self.approximation_suffix(approx),
+            _ => String::new(),
         }
-        
-        formatted
+    }
+    
+    /// 着色后的近似值后缀 ` ≈ 值`
+    fn approximation_suffix(&self, approx: f64) -> String {
+        let approx_str = format!("{:.prec$}", approx, prec = self.approximation_precision);
+        self.colorize_approximation(&format!(" ≈ {}", approx_str))
     }
     
     /// 判断是否应该显示近似值
@@ -376,407 +389,200 @@ impl TerminalFormatter {
         }
     }
     
-    /// 基础数值格式化（不含近似值）
-    fn format_number_basic(&self, number: &Number) -> String {
-        let text = match number {
-            Number::Integer(i) => i.to_string(),
-            Number::Rational(r) => {
-                if r.denom() == &num_bigint::BigInt::from(1) {
-                    r.numer().to_string()
-                } else {
-                    format!("{}/{}", r.numer(), r.denom())
-                }
-            }
-            Number::Real(r) => {
-                if let Some(precision) = self.options.precision {
-                    format!("{:.prec$}", r.to_f64().unwrap_or(0.0), prec = precision)
-                } else {
-                    r.to_string()
-                }
-            }
-            Number::Complex { real, imaginary } => {
-                let real_str = self.format_number_basic(real);
-                let imag_str = self.format_number_basic(imaginary);
-                
+    /// 格式化数值（不含近似值），文本与标准格式化器相同：精度选项只作用于实数和浮点数，其余沿用 `Number` 的显示
+    fn format_number(&self, number: &Number, depth: usize) -> String {
+        match (number, self.options.precision) {
+            (Number::Real(r), Some(precision)) => self.colorize_number(&format!("{:.prec$}", r.to_f64().unwrap_or(0.0), prec = precision)),
+            (Number::Float(f), Some(precision)) => self.colorize_number(&format!("{:.prec$}", f, prec = precision)),
+            (Number::Complex { real, imaginary }, Some(_)) => {
+                let imag_str = strip_ansi(&self.format_number(imaginary, depth));
+                let imag_part = match imag_str.as_str() {
+                    "1" => self.colorize_constant("i"),
+                    "-1" => format!("{}{}", self.colorize_operator("-"), self.colorize_constant("i")),
+                    imag => format!("{}{}", self.colorize_number(imag), self.colorize_constant("i")),
+                };
                 match (real.is_zero(), imaginary.is_zero()) {
-                    (true, true) => "0".to_string(),
-                    (true, false) => {
-                        if imag_str == "1" {
-                            self.colorize_constant("i")
-                        } else if imag_str == "-1" {
-                            format!("-{}", self.colorize_constant("i"))
-                        } else {
-                            format!("{}{}", self.colorize_number(&imag_str), self.colorize_constant("i"))
-                        }
-                    }
-                    (false, true) => self.colorize_number(&real_str),
-                    (false, false) => {
-                        let imag_part = if imag_str == "1" {
-                            self.colorize_constant("i")
-                        } else if imag_str == "-1" {
-                            format!("-{}", self.colorize_constant("i"))
-                        } else if imag_str.starts_with('-') {
-                            format!("{}{}", self.colorize_number(&imag_str), self.colorize_constant("i"))
-                        } else {
-                            format!("+{}{}", self.colorize_number(&imag_str), self.colorize_constant("i"))
-                        };
-                        format!("{}{}", self.colorize_number(&real_str), imag_part)
-                    }
-                }
-            }
-            Number::Constant(constant) => self.colorize_constant(constant.symbol()),
-            Number::Symbolic(expr) => self.format(expr),
-            Number::Float(f) => {
-                if let Some(precision) = self.options.precision {
-                    format!("{:.prec$}", f, prec = precision)
-                } else {
-                    f.to_string()
+                    (_, true) => self.format_number(real, depth),
+                    (true, false) => imag_part,
+                    (false, false) if imag_str.starts_with('-') => format!("{}{}", self.format_number(real, depth), imag_part),
+                    (false, false) => format!("{}{}{}", self.format_number(real, depth), self.colorize_operator("+"), imag_part),
                 }
             }
-        };
-        
-        // 对于非复数情况，应用数字颜色
-        match number {
-            Number::Complex { .. } => text, // 复数已经在上面处理了颜色
-            Number::Constant(_) => text,    // 常量已经在上面处理了颜色
-            Number::Symbolic(_) => text,    // 符号表达式递归处理
-            _ => self.colorize_number(&text),
+            (Number::Symbolic(expr), _) => self.render(expr, depth),
+            (Number::Constant(constant), _) => self.colorize_constant(constant.symbol()),
+            _ => self.colorize_number(&number.to_string()),
         }
     }
     
-    /// 格式化二元运算符
-    fn format_binary_operator(&self, op: &BinaryOperator) -> String {
-        let symbol = match op {
-            BinaryOperator::Add => "+",
-            BinaryOperator::Subtract => "-",
-            BinaryOperator::Multiply => "×",
-            BinaryOperator::Divide => "÷",
-            BinaryOperator::Power => "^",
-            BinaryOperator::Modulo => "%",
-            BinaryOperator::Equal => "==",
-            BinaryOperator::NotEqual => "≠",
-            BinaryOperator::Less => "<",
-            BinaryOperator::LessEqual => "≤",
-            BinaryOperator::Greater => ">",
-            BinaryOperator::GreaterEqual => "≥",
-            BinaryOperator::And => "∧",
-            BinaryOperator::Or => "∨",
-            BinaryOperator::Union => "∪",
-            BinaryOperator::Intersection => "∩",
-            BinaryOperator::SetDifference => "\\",
-            BinaryOperator::MatrixMultiply => "@",
-            BinaryOperator::CrossProduct => "×",
-            BinaryOperator::DotProduct => "·",
-        };
-        self.colorize_operator(symbol)
+    /// 检查是否需要括号（继承自标准格式化器的逻辑）
+    fn needs_parentheses(&self, expr: &Expression, parent_op: Option<&BinaryOperator>, is_right: bool) -> bool {
+        self.options.use_parentheses && parent_op.is_some_and(|parent| expr.needs_parens_as_operand(parent, is_right))
     }
     
-    /// 格式化一元运算符
-    fn format_unary_operator(&self, op: &UnaryOperator) -> String {
-        let symbol = match op {
-            UnaryOperator::Negate => "-",
-            UnaryOperator::Plus => "+",
-            UnaryOperator::Sqrt => "√",
-            UnaryOperator::Abs => "abs",
-            UnaryOperator::Sin => "sin",
-            UnaryOperator::Cos => "cos",
-            UnaryOperator::Tan => "tan",
-            UnaryOperator::Asin => "arcsin",
-            UnaryOperator::Acos => "arccos",
-            UnaryOperator::Atan => "arctan",
-            UnaryOperator::Sinh => "sinh",
-            UnaryOperator::Cosh => "cosh",
-            UnaryOperator::Tanh => "tanh",
-            UnaryOperator::Asinh => "arcsinh",
-            UnaryOperator::Acosh => "arccosh",
-            UnaryOperator::Atanh => "arctanh",
-            UnaryOperator::Ln => "ln",
-            UnaryOperator::Log10 => "log₁₀",
-            UnaryOperator::Log2 => "log₂",
-            UnaryOperator::Exp => "exp",
-            UnaryOperator::Factorial => "!",
-            UnaryOperator::Gamma => "Γ",
-            UnaryOperator::Not => "¬",
-            UnaryOperator::Real => "Re",
-            UnaryOperator::Imaginary => "Im",
-            UnaryOperator::Conjugate => "*",
-            UnaryOperator::Argument => "arg",
-            UnaryOperator::Transpose => "ᵀ",
-            UnaryOperator::Determinant => "det",
-            UnaryOperator::Inverse => "⁻¹",
-            UnaryOperator::Trace => "tr",
-        };
-        
-        match op {
-            UnaryOperator::Negate | UnaryOperator::Plus => self.colorize_operator(symbol),
-            _ => self.colorize_function(symbol),
-        }
+    /// 用一对括号包住已格式化的内容，括号颜色由嵌套深度决定
+    fn wrap(&self, open: &str, inner: &str, close: &str, depth: usize) -> String {
+        format!("{}{}{}", self.colorize_bracket(open, depth), inner, self.colorize_bracket(close, depth))
     }
     
-    /// 检查是否需要括号（继承自标准格式化器的逻辑）
-    fn needs_parentheses(&self, expr: &Expression, parent_op: Option<&BinaryOperator>, is_right: bool) -> bool {
-        if !self.options.use_parentheses {
-            return false;
-        }
-        
-        match (expr, parent_op) {
-            (Expression::BinaryOp { op, .. }, Some(parent)) => {
-                let expr_precedence = self.get_precedence(op);
-                let parent_precedence = self.get_precedence(parent);
-                
-                if expr_precedence < parent_precedence {
-                    return true;
-                }
-                
-                if expr_precedence == parent_precedence {
-                    match parent {
-                        BinaryOperator::Subtract | BinaryOperator::Divide | BinaryOperator::Power => {
-                            return is_right;
-                        }
-                        _ => false,
-                    }
-                } else {
-                    false
-                }
-            }
-            _ => false,
+    /// 格式化运算数，需要时加括号
+    fn format_operand(&self, expr: &Expression, parent_op: &BinaryOperator, is_right: bool, depth: usize) -> String {
+        if self.needs_parentheses(expr, Some(parent_op), is_right) {
+            self.wrap("(", &self.render(expr, depth + 1), ")", depth)
+        } else {
+            self.render(expr, depth)
         }
     }
     
-    /// 获取运算符优先级
-    fn get_precedence(&self, op: &BinaryOperator) -> u8 {
-        match op {
-            BinaryOperator::Or => 1,
-            BinaryOperator::And => 2,
-            BinaryOperator::Equal | BinaryOperator::NotEqual 
-            | BinaryOperator::Less | BinaryOperator::LessEqual 
-            | BinaryOperator::Greater | BinaryOperator::GreaterEqual => 3,
-            BinaryOperator::Union | BinaryOperator::Intersection | BinaryOperator::SetDifference => 4,
-            BinaryOperator::Add | BinaryOperator::Subtract => 5,
-            BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Modulo 
-            | BinaryOperator::DotProduct | BinaryOperator::CrossProduct => 6,
-            BinaryOperator::Power => 7,
-            BinaryOperator::MatrixMultiply => 8,
-        }
+    /// 以着色后的 `, ` 连接各元素
+    fn join(&self, elements: &[Expression], depth: usize) -> String {
+        let elements: Vec<String> = elements.iter().map(|elem| self.render(elem, depth)).collect();
+        elements.join(&format!("{} ", self.colorize_operator(",")))
     }
     
     /// 格式化二元运算表达式
-    fn format_binary_op(&self, op: &BinaryOperator, left: &Expression, right: &Expression) -> String {
-        let left_str = if self.needs_parentheses(left, Some(op), false) {
-            format!("{}{}{}", 
-                self.colorize_parentheses("("), 
-                self.format(left), 
-                self.colorize_parentheses(")"))
-        } else {
-            self.format(left)
-        };
-        
-        let right_str = if self.needs_parentheses(right, Some(op), true) {
-            format!("{}{}{}", 
-                self.colorize_parentheses("("), 
-                self.format(right), 
-                self.colorize_parentheses(")"))
-        } else {
-            self.format(right)
-        };
-        
-        let op_str = self.format_binary_operator(op);
-        
-        // 特殊处理某些运算符的格式
-        match op {
-            BinaryOperator::Power => {
-                format!("{}{}{}", left_str, op_str, right_str)
-            }
-            BinaryOperator::Multiply => {
-                // 智能乘法符号处理
-                if self.should_omit_multiply_symbol(left, right) {
-                    format!("{}{}", left_str, right_str)
-                } else {
-                    format!("{} {} {}", left_str, op_str, right_str)
-                }
-            }
-            BinaryOperator::Add => {
-                // 特殊处理 a + (-b) -> a - b
-                match right {
-                    Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
-                        let right_str = self.format(operand);
-                        format!("{} {} {}", left_str, self.colorize_operator("-"), right_str)
-                    }
-                    Expression::Number(n) if n.is_negative() => {
-                        // 处理负数：a + (-5) -> a - 5
-                        let positive_n = -n.clone();
-                        let right_str = self.format(&Expression::Number(positive_n));
-                        format!("{} {} {}", left_str, self.colorize_operator("-"), right_str)
-                    }
-                    _ => {
-                        format!("{} {} {}", left_str, op_str, right_str)
-                    }
-                }
+    fn format_binary_op(&self, op: &BinaryOperator, left: &Expression, right: &Expression, depth: usize) -> String {
+        let left_str = self.format_operand(left, op, false, depth);
+        match (op, negated_term(right)) {
+            // 负项写成减法：a + (-b) -> a - b、a + (-5) -> a - 5、a + (-2)*b -> a - 2 * b
+            (BinaryOperator::Add, Some(magnitude)) => {
+                let right_str = self.format_operand(&magnitude, &BinaryOperator::Subtract, true, depth);
+                format!("{} {} {}", left_str, self.colorize_operator("-"), right_str)
+            }
+            (BinaryOperator::Power, _) => {
+                let right_str = self.format_operand(right, op, true, depth);
+                format!("{}{}{}", left_str, self.colorize_operator("^"), right_str)
             }
             _ => {
-                format!("{} {} {}", left_str, op_str, right_str)
+                let right_str = self.format_operand(right, op, true, depth);
+                format!("{} {} {}", left_str, self.colorize_operator(op.symbol()), right_str)
             }
         }
     }
     
-    /// 判断是否应该省略乘法符号
-    fn should_omit_multiply_symbol(&self, left: &Expression, right: &Expression) -> bool {
-        match (left, right) {
-            (Expression::Number(_), Expression::Variable(_)) => true,
-            (Expression::Number(_), Expression::Function { .. }) => true,
-            (Expression::Variable(_), Expression::Variable(_)) => true,
-            (Expression::Variable(_), Expression::Function { .. }) => true,
-            (Expression::Function { .. }, Expression::Variable(_)) => true,
-            _ => false,
-        }
-    }
-    
     /// 格式化一元运算表达式
-    fn format_unary_op(&self, op: &UnaryOperator, operand: &Expression) -> String {
-        let op_str = self.format_unary_operator(op);
-        let operand_str = self.format(operand);
-        
+    fn format_unary_op(&self, op: &UnaryOperator, operand: &Expression, depth: usize) -> String {
         match op {
             // 前缀运算符
             UnaryOperator::Negate | UnaryOperator::Plus | UnaryOperator::Not => {
-                if matches!(operand, Expression::BinaryOp { .. }) && self.options.use_parentheses {
-                    format!("{}{}{}{}", op_str, 
-                        self.colorize_parentheses("("), 
-                        operand_str, 
-                        self.colorize_parentheses(")"))
+                let symbol = self.colorize_operator(op.symbol());
+                if operand.needs_parens_as_prefix_operand() && self.options.use_parentheses {
+                    format!("{}{}", symbol, self.wrap("(", &self.render(operand, depth + 1), ")", depth))
                 } else {
-                    format!("{}{}", op_str, operand_str)
+                    format!("{}{}", symbol, self.render(operand, depth))
                 }
             }
-            // 后缀运算符
-            UnaryOperator::Factorial | UnaryOperator::Transpose => {
-                format!("{}{}", operand_str, op_str)
-            }
-            // 特殊格式
-            UnaryOperator::Sqrt => {
-                format!("{}{}{}{}", op_str, 
-                    self.colorize_parentheses("("), 
-                    operand_str, 
-                    self.colorize_parentheses(")"))
-            }
-            UnaryOperator::Abs => {
-                format!("{}{}{}",
-                    self.colorize_operator("|"),
-                    operand_str,
-                    self.colorize_operator("|"))
-            }
-            UnaryOperator::Conjugate => {
-                format!("{}{}", operand_str, op_str)
-            }
-            // 其他函数形式
-            _ => {
-                format!("{}{}{}{}", op_str,
-                    self.colorize_parentheses("("), 
-                    operand_str, 
-                    self.colorize_parentheses(")"))
+            // 后缀运算符，转置写作 `^T`
+            UnaryOperator::Factorial | UnaryOperator::Transpose | UnaryOperator::Conjugate => {
+                let symbol = self.colorize_operator(if *op == UnaryOperator::Transpose { "^T" } else { op.symbol() });
+                if operand.needs_parens_as_postfix_operand() && self.options.use_parentheses {
+                    format!("{}{}", self.wrap("(", &self.render(operand, depth + 1), ")", depth), symbol)
+                } else {
+                    format!("{}{}", self.render(operand, depth), symbol)
+                }
             }
+            // 函数形式运算符
+            _ => format!("{}{}", self.colorize_function(op.symbol()), self.wrap("(", &self.render(operand, depth + 1), ")", depth)),
         }
     }
     
     /// 格式化函数调用
-    fn format_function(&self, name: &str, args: &[Expression]) -> String {
-        let args_str: Vec<String> = args.iter().map(|arg| self.format(arg)).collect();
-        format!("{}{}{}{}", 
-            self.colorize_function(name),
-            self.colorize_parentheses("("),
-            args_str.join(&format!("{} ", self.colorize_operator(","))),
-            self.colorize_parentheses(")"))
-    }
-}
-
-impl Default for TerminalFormatter {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
-impl Formatter for TerminalFormatter {
-    fn format(&self, expr: &Expression) -> String {
-        let formatted = match expr {
-            Expression::Number(number) => self.format_number_with_approximation(number),
-            Expression::Variable(name) => self.colorize_variable(name),
-            Expression::Constant(constant) => {
-                let symbol = self.colorize_constant(constant.symbol());
-                if self.show_approximations {
-                    if let Some(approx) = self.constant_to_f64(constant) {
-                        let approx_str = format!("{:.prec$}", approx, prec = self.approximation_precision);
-                        let colored_approx = self.colorize_approximation(&format!(" ≈ {}", approx_str));
-                        format!("{}{}", symbol, colored_approx)
-                    } else {
-                        symbol
-                    }
+    fn format_function(&self, name: &str, args: &[Expression], depth: usize) -> String {
+        match name {
+            // 下标访问 A[i, j]
+            INDEX_FUNCTION if args.len() >= 2 => {
+                let target = if args[0].needs_parens_as_index_target() {
+                    self.wrap("(", &self.render(&args[0], depth + 1), ")", depth)
                 } else {
-                    symbol
-                }
+                    self.render(&args[0], depth)
+                };
+                format!("{}{}", target, self.wrap("[", &self.join(&args[1..], depth + 1), "]", depth))
             }
-            Expression::BinaryOp { op, left, right } => {
-                self.format_binary_op(op, left, right)
+            INDEX_ALL if args.is_empty() => self.colorize_operator(INDEX_ALL),
+            _ => format!("{}{}", self.colorize_function(name), self.wrap("(", &self.join(args, depth + 1), ")", depth)),
+        }
+    }
+    
+    /// 格式化表达式，`depth` 为所在的括号嵌套深度
+    fn render(&self, expr: &Expression, depth: usize) -> String {
+        if self.options.sort_terms {
+            if let Cow::Owned(sorted) = ordering::sort_top_level(expr, self.options.term_order) {
+                return self.render(&sorted, depth);
             }
-            Expression::UnaryOp { op, operand } => {
-                self.format_unary_op(op, operand)
+        }
+        let formatted = match expr {
+            Expression::Number(number) => {
+                return format!("{}{}", self.format_number(number, depth), self.number_approximation(number));
             }
-            Expression::Function { name, args } => {
-                self.format_function(name, args)
+            Expression::Constant(constant) => {
+                let approximation = self.constant_to_f64(constant)
+                    .filter(|_| self.show_approximations)
+                    .map(|approx| self.approximation_suffix(approx))
+                    .unwrap_or_default();
+                return format!("{}{}", self.colorize_constant(constant.symbol()), approximation);
             }
+            Expression::Variable(name) => self.colorize_variable(name),
+            Expression::BinaryOp { op, left, right } => self.format_binary_op(op, left, right, depth),
+            Expression::UnaryOp { op, operand } => self.format_unary_op(op, operand, depth),
+            Expression::Function { name, args } => self.format_function(name, args, depth),
             Expression::Matrix(matrix) => {
-                let rows: Vec<String> = matrix.iter().map(|row| {
-                    let elements: Vec<String> = row.iter().map(|elem| self.format(elem)).collect();
-                    format!("{}{}{}", 
-                        self.colorize_parentheses("["),
-                        elements.join(&format!("{} ", self.colorize_operator(","))),
-                        self.colorize_parentheses("]"))
-                }).collect();
-                format!("{}{}{}", 
-                    self.colorize_parentheses("["),
-                    rows.join(&format!("{} ", self.colorize_operator(","))),
-                    self.colorize_parentheses("]"))
-            }
-            Expression::Vector(vector) => {
-                let elements: Vec<String> = vector.iter().map(|elem| self.format(elem)).collect();
-                format!("{}{}{}", 
-                    self.colorize_parentheses("["),
-                    elements.join(&format!("{} ", self.colorize_operator(","))),
-                    self.colorize_parentheses("]"))
-            }
-            Expression::Set(set) => {
-                let elements: Vec<String> = set.iter().map(|elem| self.format(elem)).collect();
-                format!("{}{}{}", 
-                    self.colorize_parentheses("{"),
-                    elements.join(&format!("{} ", self.colorize_operator(","))),
-                    self.colorize_parentheses("}"))
+                let rows: Vec<String> = matrix.iter()
+                    .map(|row| self.wrap("[", &self.join(row, depth + 2), "]", depth + 1))
+                    .collect();
+                self.wrap("[", &rows.join(&format!("{} ", self.colorize_operator(","))), "]", depth)
             }
+            Expression::Vector(vector) => self.wrap("[", &self.join(vector, depth + 1), "]", depth),
+            Expression::Set(set) => self.wrap("{", &self.join(set, depth + 1), "}", depth),
             Expression::Interval { start, end, start_inclusive, end_inclusive } => {
                 let start_bracket = if *start_inclusive { "[" } else { "(" };
                 let end_bracket = if *end_inclusive { "]" } else { ")" };
-                format!("{}{}{} {}{}", 
-                    self.colorize_parentheses(start_bracket),
-                    self.format(start),
-                    self.colorize_operator(","),
-                    self.format(end),
-                    self.colorize_parentheses(end_bracket))
+                let bounds = format!("{}{} {}", self.render(start, depth + 1), self.colorize_operator(","), self.render(end, depth + 1));
+                self.wrap(start_bracket, &bounds, end_bracket, depth)
             }
         };
         
-        // 如果整个表达式可以计算近似值，且不是简单的数字或常量，添加近似值
-        if self.show_approximations && !matches!(expr, Expression::Number(_) | Expression::Constant(_)) {
+        // 如果整个表达式可以计算近似值，添加近似值
+        if self.show_approximations {
             if let Some(approx) = self.calculate_approximation(expr) {
-                let approx_str = format!("{:.prec$}", approx, prec = self.approximation_precision);
-                let colored_approx = self.colorize_approximation(&format!(" ≈ {}", approx_str));
-                format!("{}{}", formatted, colored_approx)
-            } else {
-                formatted
+                return format!("{}{}", formatted, self.approximation_suffix(approx));
             }
-        } else {
-            formatted
         }
+        formatted
+    }
+}
+
+impl Default for TerminalFormatter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Formatter for TerminalFormatter {
+    fn format(&self, expr: &Expression) -> String {
+        self.render(expr, 0)
     }
     
     fn set_options(&mut self, options: FormatOptions) {
         self.options = options;
     }
-}
\ No newline at end of file
+}
+
+/// 去掉文本中的 ANSI 转义序列，得到着色输出的纯文本内容
+pub fn strip_ansi(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(ch) = chars.next() {
+        if ch != '\x1b' {
+            plain.push(ch);
+            continue;
+        }
+        // CSI 序列 `ESC [ 参数 终止字符`，终止字符在 `@` 到 `~` 之间
+        if chars.next() == Some('[') {
+            for ch in chars.by_ref() {
+                if ('@'..='~').contains(&ch) {
+                    break;
+                }
+            }
+        }
+    }
+    plain
+}
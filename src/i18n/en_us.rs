@@ -0,0 +1,970 @@
+//! # 英文消息表
+//!
+//! 键与中文消息表一一对应，占位符保持一致。
+
+/// 按键查找的消息
+pub(super) static MESSAGES: &[(&str, &str)] = &[
+    // 计算错误
+    ("compute.division_by_zero", "division by zero"),
+    ("compute.undefined_variable", "undefined variable: {name}"),
+    ("compute.overflow", "numeric overflow"),
+    ("compute.unsupported_operation", "unsupported operation: {operation}"),
+    ("compute.domain_error", "domain error: {message}"),
+    ("compute.dimension_mismatch", "matrix dimension mismatch: {message}"),
+    ("compute.singular_matrix", "singular matrix, cannot invert"),
+    ("compute.convergence_failure", "numerical method failed to converge: {message}"),
+    ("compute.timeout", "computation timed out"),
+    ("compute.out_of_memory", "out of memory"),
+    ("compute.resource_limit", "resource limit exceeded: {message}"),
+    ("compute.cancelled", "computation cancelled by user"),
+    ("compute.division_by_zero.message", "Division by zero: cannot divide by zero. Check whether the denominator is zero"),
+    ("compute.undefined_variable.message", "Undefined variable '{name}'. Make sure the variable is assigned or defined in the expression"),
+    ("compute.overflow.message", "Numeric overflow: the result is out of range. Try smaller numbers or check the computation"),
+    ("compute.unsupported_operation.message", "Unsupported operation '{operation}'. It may not be implemented yet or may not apply to this data type"),
+    ("compute.domain_error.message", "Domain error: {message}. Check that the function inputs are within the valid range"),
+    ("compute.dimension_mismatch.message", "Matrix dimension mismatch: {message}. Check that the matrix shapes fit the operation"),
+    ("compute.singular_matrix.message", "Singular matrix: the matrix is not invertible (its determinant is zero). Check that the matrix has full rank"),
+    ("compute.convergence_failure.message", "Numerical method failed to converge: {message}. Try adjusting the parameters or using another method"),
+    ("compute.timeout.message", "Timeout: the computation exceeded the time limit. Try simplifying the expression or raising the timeout"),
+    ("compute.out_of_memory.message", "Out of memory: the computation needs more memory than is available. Try simplifying it or freeing memory"),
+    ("compute.resource_limit.message", "Resource limit exceeded: {message}. The computation was aborted and its intermediate results released"),
+    ("compute.cancelled.message", "Cancelled: the computation was cancelled by the user"),
+    ("compute.division_by_zero.suggestions", "Check whether the denominator can be zero\nUse a condition to avoid dividing by zero\nConsider a limit for 0/0 indeterminate forms"),
+    ("compute.undefined_variable.suggestions", "Assign a value to '{name}'\nCheck the spelling of the variable name\nMake sure the variable is defined before it is used"),
+    ("compute.overflow.suggestions", "Use smaller numbers\nCheck for infinite loops or recursion\nConsider arbitrary-precision numbers\nCompute step by step to keep intermediate results small"),
+    ("compute.unsupported_operation.suggestions", "Check whether '{operation}' applies to this data type\nSee the documentation for the supported operations\nConsider an equivalent supported operation"),
+    ("compute.domain_error.suggestions", "Check the valid range of the function inputs\nFor square roots, make sure the input is non-negative\nFor logarithms, make sure the input is positive\nFor inverse trigonometric functions, make sure the input is within [-1, 1]"),
+    ("compute.dimension_mismatch.suggestions", "Check that the matrix shapes fit the operation\nMatrix multiplication needs as many columns in the first matrix as rows in the second\nMatrix addition and subtraction need matrices of the same shape"),
+    ("compute.singular_matrix.suggestions", "Check that the matrix is square\nMake sure the determinant is non-zero\nCheck whether rows or columns are linearly dependent\nConsider a pseudo-inverse or another numerical method"),
+    ("compute.convergence_failure.suggestions", "Raise the iteration limit\nAdjust the convergence tolerance\nCheck that the initial value is suitable\nTry a different numerical method"),
+    ("compute.timeout.suggestions", "Simplify the expression to reduce the work\nRaise the computation time limit\nSplit the computation into simpler steps"),
+    ("compute.out_of_memory.suggestions", "Simplify the computation to use less memory\nFree unneeded variables and caches\nProcess large data in a streaming fashion"),
+    ("compute.resource_limit.suggestions", "Raise the memory.max_memory_usage limit\nSimplify or factor the expression first\nAvoid expanding high powers"),
+    ("compute.cancelled.suggestions", "Start the computation again to continue"),
+
+    // 解析错误
+    ("parse.syntax", "syntax error at position {pos}: {message}"),
+    ("parse.unknown_function", "unknown function: {name}"),
+    ("parse.argument_count", "wrong number of arguments: expected {expected}, got {actual}"),
+    ("parse.invalid_number", "invalid number: {value}"),
+    ("parse.invalid_variable", "invalid variable name: {name}"),
+    ("parse.unmatched_parenthesis", "unmatched parenthesis at position {pos}"),
+    ("parse.unexpected_end_of_input", "unexpected end of input"),
+    ("parse.unexpected_character", "unexpected character '{ch}' at position {pos}"),
+    ("parse.empty_expression", "empty expression"),
+    ("parse.syntax.message", "Syntax error (position {pos}): {message}"),
+    ("parse.unknown_function.message", "Unknown function '{name}'. Did you mean one of: sin, cos, tan, exp, ln, sqrt?"),
+    ("parse.argument_count.message", "Wrong number of function arguments: expected {expected}, but {actual} were given"),
+    ("parse.invalid_number.message", "Invalid number '{value}'. Check that the number is written correctly"),
+    ("parse.invalid_variable.message", "Invalid variable name '{name}'. Variable names start with a letter and contain only letters, digits and underscores"),
+    ("parse.unmatched_parenthesis.message", "Unmatched parenthesis (position {pos}). Check for unclosed parentheses"),
+    ("parse.unexpected_end_of_input.message", "Incomplete expression. Check for a missing operand or operator"),
+    ("parse.unexpected_character.message", "Unexpected character '{ch}' (position {pos}). Check the input"),
+    ("parse.empty_expression.message", "The expression is empty. Enter a valid mathematical expression"),
+    ("parse.unknown_function.did_you_mean", "Did you mean '{name}'?"),
+    ("parse.unknown_function.suggestions", "Check the spelling of the function name\nSupported functions include sin, cos, tan, exp, ln, sqrt and more"),
+    ("parse.invalid_number.suggestions", "Check the number format\nSupported formats: integers (123), decimals (3.14), scientific notation (1.23e-4)\nIf '{value}' is a variable name, make sure it does not start with a digit"),
+    ("parse.unmatched_parenthesis.suggestions", "Check that every '(' has a matching ')'\nCheck that every ')' has a matching '('\nUse your editor's bracket matching to check the pairs"),
+    ("parse.syntax.suggestions", "Check how the operators are used\nMake sure every operator has its operands\nLook for repeated operators such as '++' or '--'"),
+    ("parse.argument_count.suggestions", "This function takes {expected} arguments\nCheck the commas in the function call\nMake sure every argument is a valid expression"),
+    ("parse.suggestions", "Check the syntax of the expression\nSee the documentation for the correct syntax"),
+    ("parse.context.error", "Error: {message}"),
+    ("parse.context.input", "Input: {input}"),
+    ("parse.context.position", "Position: {marker}"),
+    ("parse.context.suggestions", "Suggestions:"),
+    ("parse.empty_number", "empty number"),
+    ("parse.empty_identifier", "empty identifier"),
+    ("parse.unexpected_token", "unexpected token: {token}"),
+    ("parse.unterminated_comment", "block comment is missing its closing */"),
+    ("parse.expected_index_separator", "expected ',' or ']' in index"),
+    ("parse.expected_arguments", "expected '(' to start the argument list"),
+    ("parse.expected_argument_separator", "expected ',' or ')' in the argument list"),
+    ("parse.expected_matrix_end", "expected ']' to end the matrix"),
+    ("parse.expected_vector_end", "expected ']' to end the vector"),
+    ("parse.expected_row_start", "expected '[' to start a matrix row"),
+    ("parse.expected_row_separator", "expected ',' or ']' in a matrix row"),
+    ("parse.latex.missing_command", "missing command name after backslash"),
+    ("parse.latex.expected", "expected '{symbol}'"),
+    ("parse.latex.expected_argument", "expected an argument"),
+    ("parse.latex.expected_name", "expected a name"),
+    ("parse.latex.invalid_name", "names may only contain letters and digits"),
+    ("parse.latex.unsupported_command", "unsupported LaTeX command: \\{name}"),
+    ("parse.latex.log_base_arguments", "a logarithm with a base takes exactly one argument"),
+    ("parse.latex.unsupported_environment", "unsupported LaTeX environment: {environment}"),
+    ("parse.latex.unterminated_environment", "environment {environment} is not closed"),
+    ("signature.arity_exact", "{name} takes {count} argument(s) but {actual} were given"),
+    ("signature.arity_range", "{name} takes {min} to {max} arguments but {actual} were given"),
+    ("signature.arity_at_least", "{name} takes at least {min} argument(s) but {actual} were given"),
+    ("signature.expected_variable", "{name} expects a variable name for {parameter}, got {actual}"),
+    ("signature.expected_integer", "{name} expects an integer for {parameter}, got {actual}"),
+
+    // 顶层错误
+    ("error.parse", "parse error: {error}"),
+    ("error.compute", "computation error: {error}"),
+    ("error.format", "formatting error: {error}"),
+    ("error.io", "I/O error: {error}"),
+    ("error.config", "configuration error: {message}"),
+    ("error.internal", "internal error: {message}"),
+    ("error.unsupported_format", "unsupported format: {format}"),
+    ("error.format_failure", "formatting failed: {message}"),
+    ("error.unsupported_format.message", "Unsupported output format '{format}'. Supported formats: standard, latex, mathml, maxima"),
+    ("error.format_failure.message", "Formatting failed: {message}. Check whether the expression is too complex"),
+    ("error.io.message", "File operation failed: {error}. Check the file path and permissions"),
+    ("error.config.message", "Configuration error: {message}. Check the configuration file or arguments"),
+    ("error.internal.message", "Internal error: {message}. This may be a bug, please report it"),
+    ("error.unsupported_format.suggestions", "Use a supported format: standard, latex, mathml, maxima\nCheck the spelling of the format name"),
+    ("error.format_failure.suggestions", "Try simplifying the expression\nCheck whether the expression contains unsupported elements\nTry a different output format"),
+    ("error.io.suggestions", "Check that the file path is correct\nMake sure you have permission to access the file\nCheck that there is enough disk space"),
+    ("error.config.suggestions", "Check the syntax of the configuration file\nMake sure all required settings are present\nSee the documentation for the configuration format"),
+    ("error.internal.suggestions", "This is an internal error, please report it to the developers\nTry restarting the program\nCheck for program updates"),
+    ("error.report.title", "Error: {message}"),
+    ("error.report.severity", "Severity: {severity}"),
+    ("error.report.suggestions", "Suggested fixes:"),
+    ("error.report.recoverable", "This error can be fixed: adjust as suggested and try again"),
+    ("error.report.unrecoverable", "This error cannot be recovered automatically; you may need to restart the program or contact support"),
+
+    // 接口
+    ("api.formatter_lock", "failed to lock the formatter"),
+    ("api.monitor_unavailable", "failed to access the performance monitor"),
+    ("api.cancelled", "computation cancelled by user"),
+    ("api.malformed_congruence", "a congruence must be written as a ≡ b (mod n), got {equation}"),
+    ("api.sample_count", "the number of sample points must be positive"),
+    ("api.sample_interval", "the sampling interval must have finite endpoints"),
+    ("api.no_variable", "the expression has no variables; specify the variable explicitly"),
+    ("api.ambiguous_variable", "the expression has several variables ({variables}); specify the variable explicitly"),
+    ("api.variables_unsupported", "the current engine does not support variables"),
+    ("api.functions_unsupported", "the current engine does not support user-defined functions"),
+    ("api.runtime_config_unsupported", "the current engine does not support runtime configuration"),
+    ("api.step.parse", "Parsing expression"),
+    ("api.step.simplify", "Simplifying expression"),
+    ("api.step.format", "Formatting result"),
+    ("api.step.done", "Done"),
+    ("api.phase.parsing", "parse"),
+    ("api.phase.simplification", "simplify"),
+    ("api.phase.computation", "compute"),
+    ("api.phase.formatting", "format"),
+    ("api.phase.completed", "done"),
+    ("api.phase.error", "error"),
+    ("api.timing.summary", "took {total} ({phases})"),
+    ("api.timing.total", "took {total}"),
+    ("api.timing.phase", "{phase} {duration}"),
+    ("api.timing.separator", ", "),
+    ("api.step.analyze", "Analyzing expression structure"),
+    ("api.step.simplified", "Simplification done"),
+    ("api.step.analyze_integrand", "Analyzing integrand"),
+    ("api.step.integration_rules", "Applying integration rules"),
+    ("api.step.integrated", "Integration done"),
+    ("api.step.compute", "Computing result"),
+    ("api.step.format_output", "Formatting output"),
+    ("api.task.no_result", "the task finished without a result"),
+    ("api.task.cancelled", "the task was cancelled"),
+    ("api.task.failed", "the task failed"),
+    ("api.task.lock", "failed to lock the task"),
+    ("api.task.task_cancelled", "computation cancelled"),
+    ("api.task.result", "result: {expression}"),
+    ("api.config.parse_toml", "failed to parse TOML configuration: {error}"),
+    ("api.config.parse_json", "failed to parse JSON configuration: {error}"),
+    ("api.config.serialize", "failed to serialize configuration: {error}"),
+
+    // 运算符与常量
+    ("operator.add", "addition"),
+    ("operator.subtract", "subtraction"),
+    ("operator.multiply", "multiplication"),
+    ("operator.divide", "division"),
+    ("operator.power", "exponentiation"),
+    ("operator.modulo", "modulo"),
+    ("operator.equal", "equal to"),
+    ("operator.not_equal", "not equal to"),
+    ("operator.less", "less than"),
+    ("operator.less_equal", "less than or equal to"),
+    ("operator.greater", "greater than"),
+    ("operator.greater_equal", "greater than or equal to"),
+    ("operator.and", "logical and"),
+    ("operator.or", "logical or"),
+    ("operator.union", "union"),
+    ("operator.intersection", "intersection"),
+    ("operator.set_difference", "set difference"),
+    ("operator.matrix_multiply", "matrix multiplication"),
+    ("operator.cross_product", "cross product"),
+    ("operator.dot_product", "dot product"),
+    ("operator.negate", "negation"),
+    ("operator.plus", "unary plus"),
+    ("operator.sqrt", "square root"),
+    ("operator.abs", "absolute value"),
+    ("operator.sin", "sine"),
+    ("operator.cos", "cosine"),
+    ("operator.tan", "tangent"),
+    ("operator.asin", "arcsine"),
+    ("operator.acos", "arccosine"),
+    ("operator.atan", "arctangent"),
+    ("operator.sinh", "hyperbolic sine"),
+    ("operator.cosh", "hyperbolic cosine"),
+    ("operator.tanh", "hyperbolic tangent"),
+    ("operator.asinh", "inverse hyperbolic sine"),
+    ("operator.acosh", "inverse hyperbolic cosine"),
+    ("operator.atanh", "inverse hyperbolic tangent"),
+    ("operator.ln", "natural logarithm"),
+    ("operator.log10", "common logarithm"),
+    ("operator.log2", "binary logarithm"),
+    ("operator.exp", "exponential function"),
+    ("operator.factorial", "factorial"),
+    ("operator.gamma", "gamma function"),
+    ("operator.not", "logical not"),
+    ("operator.real", "real part"),
+    ("operator.imaginary", "imaginary part"),
+    ("operator.conjugate", "conjugate"),
+    ("operator.argument", "argument"),
+    ("operator.transpose", "transpose"),
+    ("operator.determinant", "determinant"),
+    ("operator.inverse", "inverse matrix"),
+    ("operator.trace", "trace"),
+    ("constant.pi", "pi"),
+    ("constant.e", "Euler's number"),
+    ("constant.i", "imaginary unit"),
+    ("constant.euler_gamma", "Euler-Mascheroni constant"),
+    ("constant.golden_ratio", "golden ratio"),
+    ("constant.catalan", "Catalan's constant"),
+    ("constant.positive_infinity", "positive infinity"),
+    ("constant.negative_infinity", "negative infinity"),
+    ("constant.undefined", "undefined"),
+    ("constant.property.irrational", "irrational"),
+    ("constant.property.transcendental", "transcendental"),
+    ("constant.property.positive", "positive"),
+    ("constant.property.imaginary_unit", "imaginary unit"),
+    ("constant.property.complex", "complex"),
+    ("constant.property.real", "real"),
+    ("constant.property.possibly_irrational", "possibly irrational"),
+    ("constant.property.algebraic", "algebraic"),
+    ("constant.property.infinite", "infinite"),
+    ("constant.property.negative", "negative"),
+    ("constant.property.undefined", "undefined"),
+
+    // 表达式
+    ("expression.empty_matrix", "a matrix cannot be empty"),
+    ("expression.empty_matrix_row", "a matrix row cannot be empty"),
+    ("expression.ragged_matrix", "row {row} of the matrix has {len} columns but the first row has {cols}"),
+    ("expression.empty_vector", "a vector cannot be empty"),
+    ("expression.matmul_mismatch", "matrix multiplication dimension mismatch: {left}×? and {right}×?"),
+    ("expression.matmul_requires_matrices", "matrix multiplication only applies to matrices"),
+    ("expression.dot_mismatch", "dot product dimension mismatch: {left} and {right}"),
+    ("expression.dot_requires_vectors", "the dot product only applies to vectors"),
+    ("expression.cross_requires_3d", "the cross product only applies to 3-dimensional vectors"),
+    ("expression.requires_square_matrix", "{operation} only applies to square matrices"),
+    ("expression.requires_matrix", "{operation} only applies to matrices"),
+    ("expression.factorial_requires_integer", "the factorial only applies to integers"),
+    ("expression.empty_variable_name", "a variable name cannot be empty"),
+    ("expression.variable_is_constant", "variable name {name} conflicts with a mathematical constant"),
+    ("expression.rename_conflict", "cannot rename {from} to {to}: variable {to} already exists"),
+    ("expression.undefined_variable", "undefined variable: {name}"),
+    ("expression.zero_negative_power", "0 raised to a negative power is undefined"),
+    ("expression.modulo_by_zero", "modulo by zero"),
+    ("expression.negative_factorial", "the factorial of a negative number is undefined"),
+    ("expression.factorial_requires_natural", "the factorial only applies to non-negative integers"),
+    ("expression.log_requires_positive", "the argument of a logarithm must be positive"),
+
+    // 类型
+    ("type.symbolic", "symbolic"),
+    ("type.matrix", "matrix[{rows}×{cols}]<{element}>"),
+    ("type.vector", "vector[{dim}]<{element}>"),
+    ("type.set", "set<{element}>"),
+    ("type.interval", "interval<{element}>"),
+    ("type.unknown", "unknown"),
+    ("type.integer", "integer"),
+    ("type.rational", "rational"),
+    ("type.real", "real"),
+    ("type.complex", "complex"),
+    ("type.float", "float"),
+
+    // 二进制格式
+    ("binary.corrupt", "invalid binary expression data: {reason}"),
+    ("binary.trailing_data", "unexpected trailing data"),
+    ("binary.expression_count", "expected 1 expression, found {count}"),
+    ("binary.missing_header", "missing format header"),
+    ("binary.unsupported_version", "unsupported format version {version}"),
+    ("binary.checksum", "checksum mismatch"),
+    ("binary.truncated", "data is truncated"),
+    ("binary.varint_overflow", "variable-length integer overflow"),
+    ("binary.length_out_of_range", "length exceeds the data"),
+    ("binary.index_out_of_range", "{item} index out of range"),
+    ("binary.unknown_code", "unknown {item} code {code}"),
+    ("binary.invalid_utf8", "string is not valid UTF-8"),
+    ("binary.number_parts", "number node has parts of the wrong type"),
+    ("binary.item.constant", "constant"),
+    ("binary.item.string", "string"),
+    ("binary.item.binary_operator", "binary operator"),
+    ("binary.item.unary_operator", "unary operator"),
+    ("binary.item.node", "node"),
+    ("binary.interval_flags", "invalid interval flags"),
+    ("binary.unknown_tag", "unknown node type {tag}"),
+    ("binary.child_out_of_range", "child node reference out of range"),
+    ("binary.too_many_nodes", "more than {limit} nodes after expansion"),
+    ("binary.forward_reference", "binary decoding referenced a node that has not been built yet"),
+    ("binary.zero_denominator", "rational number has a zero denominator"),
+
+    // 内存
+    ("memory.limit_exceeded", "the expression is estimated to use {peak} bytes, above the limit of {limit} bytes"),
+    ("memory.cleanup", "memory cleanup finished: hash cache {hash_cache_before} -> {hash_cache_after}, expression pool {pool_before} -> {pool_after}"),
+
+    // 张量
+    ("tensor.empty_axis", "every axis of a tensor must have positive length, got shape {shape}"),
+    ("tensor.size_mismatch", "shape {shape} does not match the {count} elements"),
+    ("tensor.reshape", "cannot reshape a tensor of shape {from} to {to}"),
+    ("tensor.no_axis", "a rank-{rank} tensor has no axis {axis}"),
+    ("tensor.scalar", "scalar"),
+    ("tensor.ragged", "inconsistent lengths at level {level} of the tensor: expected {expected}, got {actual}"),
+    ("tensor.mixed_level", "level {level} of the tensor mixes arrays and scalars"),
+    ("tensor.positive_integers", "tensor dimensions and axes must be positive integers, got {value}"),
+    ("tensor.too_many_elements", "shape {shape} has too many elements"),
+    ("tensor.single_axis", "tensor_sum can only sum along one axis"),
+    ("tensor.elementwise_shapes", "elementwise operations need equal shapes: {left} and {right}"),
+
+    // 化简
+    ("simplify.complex_power", "complex exponentiation"),
+    ("simplify.non_constant", "non-constant expression"),
+    ("simplify.complex_log", "complex logarithm"),
+    ("simplify.unevaluable_log", "cannot evaluate this logarithm"),
+    ("simplify.complex_sin", "complex sine"),
+    ("simplify.unevaluable_sin", "cannot evaluate this sine"),
+    ("simplify.complex_cos", "complex cosine"),
+    ("simplify.unevaluable_cos", "cannot evaluate this cosine"),
+    ("simplify.complex_tan", "complex tangent"),
+    ("simplify.unevaluable_tan", "cannot evaluate this tangent"),
+    ("simplify.complex_exp", "complex exponential"),
+    ("simplify.unevaluable_exp", "cannot evaluate this exponential"),
+    ("simplify.unevaluable_abs", "cannot evaluate this absolute value"),
+    ("simplify.unevaluable_factorial", "cannot evaluate this factorial"),
+    ("simplify.non_numeric_root", "n-th root of a non-number"),
+    ("simplify.negative_odd_root", "odd root of a negative number"),
+    ("simplify.imperfect_root", "root of a number that is not a perfect n-th power"),
+    ("simplify.constant_folding", "{operation} during constant folding"),
+    ("simplify.root_index", "the index of root must be a positive integer"),
+    ("simplify.unknown_function", "unknown function: {name}"),
+    ("simplify.undefined_at_zero", "{name} is undefined at 0"),
+    ("simplify.no_finite_value", "{name}({argument}) has no finite real value"),
+    ("simplify.unevaluable_function", "cannot evaluate this {name} expression"),
+    ("simplify.log_requires_positive", "the argument of a logarithm must be positive"),
+    ("simplify.negative_sqrt", "the argument of a square root cannot be negative"),
+    ("simplify.negative_even_root", "the argument of an even root cannot be negative"),
+    ("simplify.unroundable_float", "the float cannot be rounded"),
+    ("simplify.requires_rational", "{name} of a non-rational number"),
+    ("simplify.requires_integer", "the {name} function only accepts integer arguments"),
+    ("simplify.negative_factorial", "the argument of a factorial cannot be negative"),
+    ("simplify.factorial_too_large", "the argument of the factorial is too large"),
+    ("simplify.factorial_requires_integer", "the argument of a factorial must be an integer"),
+    ("trace.root", "root"),
+
+    // 逐步解方程
+    ("equation.add", "add {term} to both sides"),
+    ("equation.subtract", "subtract {term} from both sides"),
+    ("equation.multiply_by_zero", "multiplying both sides by 0 makes the equation always true and loses all information"),
+    ("equation.multiply_may_be_zero", "{factor} may be 0; multiplying both sides by it may introduce extraneous solutions"),
+    ("equation.multiply", "multiply both sides by {factor}"),
+    ("equation.divide_by_zero", "cannot divide both sides by 0"),
+    ("equation.divide_may_be_zero", "{divisor} may be 0; dividing both sides needs {divisor} != 0, otherwise solutions may be lost"),
+    ("equation.divide", "divide both sides by {divisor}"),
+    ("equation.substitute", "substitute {variable} = {value}"),
+    ("equation.missing_variable", "the equation does not contain the variable {variable}"),
+    ("equation.swap", "swap both sides"),
+    ("equation.cannot_invert", "cannot invert {expression} to isolate {variable}; try solve"),
+    ("equation.no_solution_when_zero", "the equation has no solution when {value} = 0"),
+    ("equation.reciprocal", "take reciprocals of both sides and multiply by {factor}"),
+    ("equation.negate", "multiply both sides by -1"),
+    ("equation.square_root", "take the square root of both sides"),
+    ("equation.root", "take the {exponent}-th root of both sides"),
+    ("equation.even_root", "only the positive even root was taken; {solution} is also a solution"),
+    ("equation.log_requires_positive", "taking logarithms requires {value} > 0"),
+    ("equation.logarithm", "take logarithms of both sides"),
+    ("equation.apply_inverse", "apply {function} to both sides"),
+    ("equation.principal_value", "{function} only gives the principal value; other solutions differ by whole periods"),
+    ("equation.square", "square both sides"),
+    ("equation.square_extraneous", "squaring may introduce extraneous solutions; {value} >= 0 is required"),
+    ("equation.natural_log", "take natural logarithms of both sides"),
+    ("equation.exponentiate", "exponentiate both sides"),
+    ("equation.also_requires_positive", "{value} > 0 is also required"),
+    ("equation.repeated_variable", "{variable} appears {count} times in the equation and cannot be isolated step by step"),
+    ("equation.solver_failed", "{hint}, and solve failed as well: {error}"),
+    ("equation.solved_by_solver", "{hint}; solved with solve"),
+    ("equation.solver_no_solution", "{hint}, and the equation has no solution"),
+    ("equation.solver_many_solutions", "{hint}, and solve found several solutions: {solutions}"),
+    ("equation.flip_inequality", "{description}, flipping the inequality"),
+    ("equation.unknown_sign", "the sign of {factor} is unknown and is treated as positive; the inequality must flip if it is negative"),
+    ("equation.not_a_relation", "{expression} is not an equation or inequality"),
+
+    // 求和
+    ("summation.convergent", "convergent"),
+    ("summation.divergent", "divergent"),
+    ("summation.unknown", "unknown"),
+    ("summation.undefined_term", "the term is undefined at {variable} = {value}"),
+    ("summation.geometric_conditional", "cannot evaluate: the ratio {ratio} contains other variables; the sum is {value} when |{ratio}| < 1 and diverges otherwise"),
+    ("summation.geometric_divergent", "the series diverges: the common ratio {ratio} ≥ 1"),
+    ("summation.geometric_oscillating", "the series diverges: the common ratio {ratio} ≤ -1 and the partial sums oscillate"),
+    ("summation.lower_bound_integer", "cannot evaluate: the lower bound must be an integer"),
+    ("summation.too_many_terms", "cannot evaluate: too many terms"),
+    ("summation.upper_bound_integer", "cannot evaluate: the upper bound must be an integer or positive infinity"),
+    ("summation.nonzero_constant_term", "the series diverges: the term is a nonzero constant"),
+    ("summation.constant_series", "cannot evaluate: a constant series only converges when the constant is zero"),
+    ("summation.diverges", "the series diverges"),
+    ("summation.unknown_with_parameters", "cannot evaluate: not a known series, and the term contains other variables"),
+    ("summation.leibniz_convergent", "cannot evaluate: the alternating series converges (Leibniz test) but has no known closed form"),
+    ("summation.term_not_vanishing", "the series diverges: the term does not tend to zero"),
+    ("summation.p_series_convergent", "cannot evaluate: the series converges (p-series test) but has no known closed form"),
+    ("summation.p_series_divergent", "the series diverges (p-series test)"),
+    ("summation.ratio_convergent", "cannot evaluate: the series converges (ratio test) but has no known closed form"),
+    ("summation.ratio_divergent", "the series diverges (ratio test)"),
+    ("summation.unknown_convergence", "cannot evaluate: the convergence of the series cannot be determined"),
+
+    // 微积分
+    ("calculus.complex_numeric", "numeric evaluation of complex numbers"),
+    ("calculus.imaginary_numeric", "numeric evaluation of the imaginary unit"),
+    ("calculus.non_integer_factorial", "factorial of a non-integer"),
+    ("calculus.product_integral", "integrating general products is not supported yet"),
+    ("calculus.power_integral", "integrating complicated powers is not supported yet"),
+    ("calculus.sqrt_integral", "integrating composite square roots is not supported yet"),
+    ("calculus.numeric_binary", "numeric evaluation of binary operation {operation}"),
+    ("calculus.numeric_unary", "numeric evaluation of unary operation {operation}"),
+    ("calculus.numeric_expression", "numeric evaluation of {expression}"),
+    ("calculus.integrate_expression", "integrating {expression}"),
+    ("calculus.differentiate_expression", "differentiating {expression}"),
+    ("calculus.differentiate_binary", "differentiating binary operation {operation}"),
+    ("calculus.differentiate_unary", "differentiating unary operation {operation}"),
+    ("calculus.integrate_binary", "integrating binary operation {operation}"),
+    ("calculus.integrate_unary", "integrating unary operation {operation}"),
+    ("calculus.numeric_function", "numeric evaluation of function {name}"),
+    ("calculus.multi_argument_integral", "integrating function {name} with several arguments is not supported yet"),
+    ("calculus.integrate_function", "integrating function {name}"),
+    ("calculus.integrate_composite", "integrating function {name} of a complicated argument"),
+    ("calculus.multi_argument_derivative", "differentiating function {name} with several arguments is not supported yet"),
+    ("calculus.differentiate_function", "differentiating function {name}"),
+    ("calculus.limit", "limit lim({variable} -> {point}) {expression}"),
+    ("calculus.series", "series expansion of {expression} at {variable} = {point}"),
+
+    // 矩阵
+    ("matrix.shape_mismatch", "matrix dimension mismatch: {left_rows}×{left_cols} and {right_rows}×{right_cols}"),
+    ("matrix.multiply_mismatch", "matrix multiplication dimension mismatch: {left_rows}×{left_cols} and {right_rows}×{right_cols}"),
+    ("matrix.determinant_square", "the determinant needs a square matrix, got {rows}×{cols}"),
+    ("matrix.empty_determinant", "an empty matrix has no determinant"),
+    ("matrix.index_out_of_range", "row or column index out of range"),
+    ("matrix.inverse_square", "only square matrices can be inverted, got {rows}×{cols}"),
+    ("matrix.singular", "the matrix is not invertible (its determinant is zero)"),
+    ("matrix.vector_mismatch", "vector dimension mismatch: {left} and {right}"),
+    ("matrix.cross_requires_3d", "the cross product only applies to 3-dimensional vectors"),
+    ("matrix.trace_square", "the trace needs a square matrix, got {rows}×{cols}"),
+    ("matrix.not_a_matrix", "the expression is not a matrix"),
+    ("matrix.not_a_vector_shape", "the matrix cannot be converted to a vector (it must have a single row or column)"),
+    ("matrix.not_a_vector", "the expression is not a vector"),
+    ("matrix.subscript_out_of_bounds", "subscript [{indices}] out of bounds: {dimensions}"),
+    ("matrix.tensor_subscript_count", "a rank-{rank} tensor needs {rank} subscripts, got {count}"),
+    ("matrix.tensor_shape", "the tensor has shape {shape}"),
+    ("matrix.vector_subscript_count", "a vector needs 1 subscript, got {count}"),
+    ("matrix.vector_length", "the vector has length {len}"),
+    ("matrix.matrix_subscript_count", "a matrix needs 2 subscripts, got {count}"),
+    ("matrix.matrix_shape", "the matrix is {rows}×{cols}"),
+    ("matrix.subscript_not_positive", "subscripts must be positive integers (starting at 1), got {index}"),
+
+    // 数论与统计
+    ("number_theory.gcd_arguments", "gcd only supports integers and rationals; make sure the arguments are integers or rationals"),
+    ("number_theory.lcm_arguments", "lcm only supports integers; make sure the arguments are integers"),
+    ("number_theory.is_prime_argument", "is_prime only supports integers; make sure the argument is a positive integer"),
+    ("number_theory.factorization_domain", "prime factorization needs a positive integer greater than 1"),
+    ("number_theory.prime_factors_argument", "prime_factors only supports integers; make sure the argument is a positive integer greater than 1"),
+    ("number_theory.binomial_arguments", "binomial only supports non-negative integers; make sure the arguments are non-negative integers with k <= n"),
+    ("number_theory.permutation_arguments", "permutation only supports non-negative integers; make sure the arguments are non-negative integers with k <= n"),
+    ("number_theory.empty_mean", "cannot take the mean of an empty list; provide at least one number"),
+    ("number_theory.mean_arguments", "mean only supports numbers; make sure every argument is a number"),
+    ("number_theory.variance_count", "the variance needs at least two numbers"),
+    ("number_theory.variance_arguments", "variance only supports numbers; make sure every argument is a number"),
+    ("number_theory.unsupported_combination", "unsupported combination of number types"),
+    ("number_theory.negative_sqrt", "negative numbers have no real square root; use complex arithmetic"),
+    ("number_theory.unsupported_type", "unsupported number type"),
+
+    // 计算引擎
+    ("engine.enhancer_lock", "cannot acquire the runtime enhancer lock"),
+    ("engine.circular_reference", "variable '{name}' has a circular reference"),
+    ("engine.simplifier_lock", "cannot acquire the simplifier lock"),
+    ("engine.unsupported_binary", "unsupported binary operation: {operation}"),
+    ("engine.unsupported_unary", "unsupported unary operation: {operation}"),
+    ("engine.matrix_not_scalar", "a matrix cannot be evaluated to a single number"),
+    ("engine.vector_not_scalar", "a vector cannot be evaluated to a single number"),
+    ("engine.unevaluable", "cannot evaluate expression: {expression}"),
+    ("engine.not_polynomial", "{operation} needs a polynomial in {variable}, but found {found}"),
+    ("engine.internal", "internal error: {message}"),
+    ("engine.enhanced_simplifier_lock", "cannot acquire the enhanced simplifier lock"),
+    ("engine.auto_simplify_lock", "cannot acquire the auto-simplify switch lock"),
+    ("engine.cache_manager_lock", "cannot acquire the cache manager lock"),
+
+    // 定义域与值域
+    ("domain.constraint_critical_points", "cannot solve for the critical points of constraint {constraint}"),
+    ("domain.convex_hull", "the domain has several intervals; the result is the convex hull of their ranges"),
+    ("domain.discontinuous", "the expression contains rounding functions and is estimated as if continuous"),
+    ("domain.derivative_roots", "cannot solve for the zeros of the derivative; stationary points are not included"),
+    ("domain.derivative", "cannot differentiate the expression; stationary points are not included"),
+    ("domain.no_values", "cannot evaluate the function"),
+    ("domain.unsupported_operation", "the domain of operation {operation} is not analysed"),
+    ("domain.singularities", "the singularities of {name} are not excluded"),
+    ("domain.unknown_function", "the domain of unknown function {name} is not analysed"),
+    ("domain.non_scalar", "domain analysis of a non-scalar expression"),
+    ("domain.invalid_domain", "a domain must be an interval, a set of points or a union of them"),
+
+    // 概率分布
+    ("distribution.normal_sigma", "the standard deviation of a normal distribution must be positive, got {value}"),
+    ("distribution.binomial_trials", "the number of trials of a binomial distribution must be a non-negative integer, got {value}"),
+    ("distribution.binomial_probability", "the probability of a binomial distribution must be in [0, 1], got {value}"),
+    ("distribution.poisson_rate", "the rate of a Poisson distribution must be positive, got {value}"),
+    ("distribution.argument_count", "wrong number of arguments for distribution {name}"),
+    ("distribution.normal_expectation", "expectations under a normal distribution only support polynomials in {variable}, got {found}"),
+    ("distribution.binomial", "the binomial distribution"),
+    ("distribution.poisson", "the Poisson distribution"),
+    ("distribution.expectation_sum", "the expectation under {distribution} cannot be summed: {error}"),
+    ("distribution.function_argument_count", "wrong number of arguments for {name}"),
+
+    // 单位
+    ("units.not_a_unit", "{unit} is not a unit"),
+    ("units.nested_relation", "comparisons can only appear at the top level"),
+    ("units.unsupported_operation", "unit analysis does not support {operation}"),
+    ("units.unsupported_expression", "unit analysis does not support {expression}"),
+    ("units.inconsistent", "inconsistent dimensions: {left} ({left_dimension}) and {right} ({right_dimension}) cannot be joined with {operator}"),
+    ("units.exponent", "the exponent"),
+    ("units.constant_exponent", "a base with units {units} can only be raised to a constant power, got {exponent}"),
+    ("units.fractional_power", "{units} to the power {exponent} does not have integer unit exponents"),
+    ("units.requires_dimensionless", "the argument of {context} must be dimensionless, got units {units}"),
+
+    // 最值
+    ("optimization.unbounded_below", "is unbounded below"),
+    ("optimization.unbounded_above", "is unbounded above"),
+    ("optimization.minimum", "minimum"),
+    ("optimization.maximum", "maximum"),
+    ("optimization.attained", "cannot find where {expression} attains its {goal}"),
+    ("optimization.not_attained", "{expression} has no {goal}; the bound {bound} is not attained"),
+    ("optimization.range", "cannot estimate the range of {expression}"),
+    ("optimization.infeasible", "the constraint {constraint} = 0 has no real solution"),
+    ("optimization.unbounded", "{expression} {bound} on the constraint {constraint} = 0"),
+    ("optimization.constrained_not_attained", "{expression} has no {goal} on the constraint {constraint} = 0"),
+
+    // 二次型与圆锥曲线
+    ("conic.requires_variable", "{operation} needs at least one variable"),
+    ("conic.degree", "{operation} needs degree at most 2 in {variables}"),
+    ("conic.quadratic_form", "extracting the quadratic form matrix"),
+    ("conic.complete_square", "completing the square"),
+    ("conic.cross_term", "with a cross term, completing the square only supports two variables with rational quadratic coefficients"),
+    ("conic.variable_count", "a conic section needs two variables, got {count}"),
+    ("conic.classify", "conic classification"),
+    ("conic.not_quadratic", "{expression} has no quadratic terms in {variables} and is not a conic section"),
+    ("conic.unclassified", "cannot classify {expression}"),
+
+    // 多项式
+    ("polynomial.division_degree", "polynomial division: the dividend has a lower degree than the divisor"),
+    ("polynomial.positive_power", "polynomials only support positive integer powers"),
+    ("polynomial.unsupported_operation", "polynomials do not support the {operation} operation"),
+    ("polynomial.not_convertible", "the expression cannot be converted to a polynomial"),
+    ("polynomial.degree_too_high", "the degree of {expression} is too high to expand"),
+    ("polynomial.coefficients", "extracting coefficients"),
+    ("orthogonal.max_degree", "the degree of an orthogonal polynomial cannot exceed {limit}, got {degree}"),
+    ("orthogonal.degree", "the degree of {name} must be a non-negative integer, got {degree}"),
+    ("resultant.discriminant_degree", "the discriminant needs a polynomial of degree at least one in {variable}"),
+    ("resultant.sylvester_order", "the Sylvester matrix of the resultant has order {order}, above the limit of {limit}"),
+
+    // 延迟求值
+    ("lazy.cycle_or_recomputation", "circular dependency or repeated evaluation"),
+    ("lazy.self_reference", "the definition of {name} refers to itself"),
+    ("lazy.missing_expression", "expression {id} does not exist"),
+    ("lazy.dependency_cycle", "adding the dependency would create a cycle"),
+    ("lazy.cycle_detected", "circular dependency detected"),
+    ("lazy.unresolved", "circular or unresolvable dependencies detected"),
+
+    // Gröbner 基
+    ("groebner.too_many_reductions", "computing the Gröbner basis took more than {limit} reductions"),
+    ("groebner.basis_too_large", "the Gröbner basis has more than {limit} polynomials"),
+    ("groebner.not_polynomial", "{expression} is not a polynomial"),
+    ("groebner.rational_coefficients", "Gröbner bases only support rational coefficients; the coefficient {coefficient} of {expression} is not rational"),
+    ("groebner.extra_variable", "{expression} contains the variable {variable}, which is not in the variable list"),
+    ("groebner.infinite_solutions", "the system has infinitely many solutions; {variable} can take any value"),
+
+    // 向量微积分
+    ("vector_calculus.jacobian_arguments", "the Jacobian needs at least one function and one variable"),
+    ("vector_calculus.curl_dimension", "the curl is only defined for 2- or 3-dimensional vector fields, got {dimension} dimensions"),
+    ("vector_calculus.laplacian_variables", "the Laplacian needs at least one variable"),
+    ("vector_calculus.field_mismatch", "the vector field has {components} components but there are {variables} variables"),
+
+    // 变量与自定义函数
+    ("runtime.argument_count", "function '{name}' takes {expected} arguments but {actual} were given"),
+    ("runtime.invalid_variable", "invalid variable name: {name}"),
+    ("runtime.invalid_function", "invalid function or parameter name: {name}"),
+    ("runtime.duplicate_parameter", "function '{name}' has the parameter '{parameter}' more than once"),
+    ("runtime.call_depth", "function calls are nested more than {limit} levels deep; the definition may be recursive"),
+
+    // 恒等检验
+    ("equivalence.unevaluable_samples", "neither side can be evaluated at the sample points, so equivalence cannot be decided"),
+    ("equivalence.free_variable", "variable {variable} is not among the test variables {variables}"),
+    ("equivalence.unevaluable_points", "neither side can be evaluated at the test points, so equivalence cannot be decided"),
+    ("equivalence.counterexample", ": at {point} the derivative is {derivative} and the integrand is {integrand}"),
+    ("equivalence.integral_mismatch", "the derivative of the integral {integral} does not match the integrand {integrand}{counterexample}"),
+
+    // 缓存
+    ("cache.malformed_entry", "malformed symbolic cache entry"),
+    ("cache.malformed_variable", "malformed variable in a symbolic cache entry"),
+    ("cache.cost_out_of_range", "the cost of a symbolic cache entry is out of range"),
+    ("cache.malformed_cost", "malformed cost in a symbolic cache entry"),
+
+    // 三角变换
+    ("trigonometry.max_multiple", "multiple-angle expansion supports at most {limit} times the angle"),
+    ("trigonometry.mismatch", "the transformed expression {transformed} differs from the original {original} at the sample points"),
+
+    // 部分分式
+    ("partial_fractions.unsolvable", "the coefficient equations of the partial fractions have no solution"),
+    ("partial_fractions.operation", "partial fraction decomposition"),
+    ("partial_fractions.rational_coefficients", "partial fraction decomposition only supports rational functions of {variable} with rational coefficients"),
+    ("partial_fractions.irreducible_factor", "the denominator has an irreducible factor of degree three or more; partial fraction decomposition does not support it yet"),
+
+    // 并行计算
+    ("parallel.thread_pool", "failed to create the thread pool: {error}"),
+    ("parallel.timeout", "task timed out"),
+    ("parallel.unsupported_operator", "constant arithmetic does not support the operator: {operation}"),
+
+    // 假设
+    ("assumptions.unrecognized", "unrecognized assumption: {assumption}"),
+    ("assumptions.contradiction", "the assumption {variable} {property} contradicts earlier assumptions"),
+
+    // 解方程
+    ("solver.identity", "the equation holds for every {variable}"),
+    ("solver.univariate", "solve currently only supports univariate polynomial equations in {variable}"),
+    ("solver.rational_coefficients", "solve currently only supports polynomial equations with rational coefficients"),
+
+    // 积分变换
+    ("laplace.transform_variable", "the original function cannot contain the transform variable {variable}"),
+    ("laplace.original_variable", "the transform cannot contain the original variable {variable}"),
+
+    // 列表
+    ("lists.zero_step", "the step of range cannot be 0"),
+    ("lists.too_long", "a list can hold at most {limit} elements"),
+    ("lists.incomparable", "sort cannot compare the element {element}; only real numbers can be sorted"),
+
+    // 逻辑
+    ("logic.not_boolean", "cannot evaluate as a boolean expression: {expression}"),
+    ("logic.truth_table_size", "the truth table would have 2^{count} rows; at most {limit} variables are allowed"),
+
+    // 同余
+    ("modular.modulus", "the modulus must be a positive integer, got {modulus}"),
+    ("modular.linear", "the congruence must be linear in {variable} with integer coefficients"),
+
+    // 性能统计
+    ("performance.report", "Performance report:\n===================\nTotal computations: {total}\nSuccessful computations: {successful}\nFailed computations: {failed}\nSuccess rate: {success_rate}%\n\nTiming:\n-------\nTotal compute time: {total_time}\nAverage compute time: {avg_time}\nFastest computation: {fastest}\nSlowest computation: {slowest}\nThroughput: {throughput} computations/s\n\nCache:\n------\nCache hits: {cache_hits}\nCache misses: {cache_misses}\nCache hit rate: {cache_hit_rate}%\n\nParallel computation:\n---------------------\nParallel computations: {parallel}\nParallel ratio: {parallel_ratio}%\n\nMemory:\n-------\nCurrent memory usage: {memory} bytes\nPeak memory: {peak_memory} bytes\nAborted over limit: {limit_hits}\nGarbage collections: {gc_count}\n\nExact computation:\n------------------\nExact computation ratio: {exact_ratio}%\n\nSimplification:\n---------------\nNot converged: {non_converged}\n"),
+    ("performance.operations_title", "\nPer-operation statistics:\n-------------------------\n"),
+    ("performance.operation", "Operation"),
+    ("performance.calls", "Calls"),
+    ("performance.avg_time", "Average time"),
+    ("performance.success_rate", "Success"),
+    ("performance.peak_memory", "Peak memory"),
+
+    // C 接口
+    ("ffi.null_expression", "expression data is a null pointer"),
+    ("ffi.null_input", "input is a null pointer"),
+    ("ffi.invalid_utf8", "input is not valid UTF-8"),
+    ("ffi.null_variable", "variable name is a null pointer"),
+
+    // 代码生成
+    ("codegen.unsupported", "code generation does not support {what}"),
+    ("codegen.operation", "the operation {op}"),
+    ("codegen.function", "the function {name}"),
+    ("codegen.root_index", "root with an index that is not a positive integer"),
+    ("codegen.nested", "sets, intervals or nested vectors and matrices"),
+    ("codegen.complex", "complex numbers"),
+    ("codegen.imaginary_unit", "the imaginary unit"),
+    ("codegen.large_exponent", "integer exponents this large"),
+
+    // 分析与练习
+    ("extremum.maximum", "maximum"),
+    ("extremum.minimum", "minimum"),
+    ("extremum.saddle", "saddle point"),
+    ("extremum.undetermined", "undetermined"),
+    ("exercise.differentiation", "differentiation"),
+    ("exercise.integration", "integration"),
+    ("exercise.simplification", "simplification"),
+    ("exercise.factorization", "factorization"),
+    ("exercise.equation", "equation"),
+    ("exercise.difficulty", "the difficulty must be between 1 and {max}"),
+
+    // 命令行
+    ("cli.terminal_init_failed", "warning: failed to initialize the terminal: {error}"),
+    ("cli.colors_may_not_work", "colored output may not work properly"),
+    ("cli.terminal.stdout_ansi", "warning: failed to enable ANSI colors on standard output"),
+    ("cli.terminal.stderr_ansi", "warning: failed to enable ANSI colors on standard error"),
+    ("cli.banner", "Yufmath v{version} - computer algebra system"),
+    ("cli.verbose_enabled", "verbose mode enabled"),
+    ("cli.init_failed", "error: failed to initialize Yufmath: {error}"),
+    ("cli.error", "error: {error}"),
+    ("cli.done", "done"),
+    ("cli.invalid_lang", "unsupported language '{lang}' (available: zh, en)"),
+    ("cli.progress.running", "{operation}..."),
+    ("cli.progress.compute", "Computing expression"),
+    ("cli.progress.batch", "Processing batch file"),
+    ("cli.verbose.compute", "computing expression: {expression}"),
+    ("cli.verbose.simplify", "simplifying expression: {expression}"),
+    ("cli.verbose.diff", "differentiating {expression} with respect to {variable}"),
+    ("cli.verbose.integrate", "integrating {expression} with respect to {variable}"),
+    ("cli.verbose.solve", "solving {equation} for {variable}"),
+    ("cli.verbose.factor", "factoring {expression}"),
+    ("cli.verbose.expand", "expanding {expression}"),
+    ("cli.verbose.trig_expand", "expanding the angles in {expression}"),
+    ("cli.verbose.trig_factor", "turning the sums in {expression} into products"),
+    ("cli.verbose.limit", "computing the limit of {expression} as {variable} approaches {point}"),
+    ("cli.verbose.series", "expanding {expression} into a series of order {order} at {variable} = {point}"),
+    ("cli.verbose.analyze", "analyzing {expression} in {variable}"),
+    ("cli.verbose.laplace", "computing the Laplace transform of {expression} in {variable}"),
+    ("cli.verbose.inverse_laplace", "computing the inverse Laplace transform of {expression} in {variable}"),
+    ("cli.verbose.plot_data", "sampling {expression} at {n} points for {variable} ∈ [{from}, {to}]"),
+    ("cli.verbose.verify", "verifying {equation}"),
+    ("cli.verbose.prove", "proving {lhs} = {rhs}"),
+    ("cli.verbose.quiz", "generating {count} {kind} exercises"),
+    ("cli.verbose.batch", "processing batch file: {file}"),
+    ("cli.verbose.output_file", "output file: {file}"),
+    ("cli.verbose.batch_line", "processing line {line}: {input}"),
+    ("cli.verbose.interactive", "starting interactive mode..."),
+    ("cli.factor_unimplemented", "factoring is not implemented yet, expression: {expression}"),
+    ("cli.expand_unimplemented", "expansion is not implemented yet, expression: {expression}"),
+    ("cli.solve.no_solution", "no solution"),
+    ("cli.solve.multiplicity", "multiplicity {multiplicity}"),
+    ("cli.solve.numeric", "numeric"),
+    ("cli.solve.candidate", "candidate, not verified"),
+    ("cli.solve.note_separator", ", "),
+    ("cli.solve.solution", "solution {index}: {variable} = {root} ({notes})"),
+    ("cli.stats.memory", "memory (estimated): {current} bytes now, {peak} bytes peak"),
+    ("cli.stats.limit_hits", "aborted at the memory limit: {count} times"),
+    ("cli.batch.read_failed", "failed to read input file '{file}': {error}"),
+    ("cli.batch.progress_line", "line {line}"),
+    ("cli.batch.line_error", "error on line {line}: {input} -> {error}"),
+    ("cli.batch.done", "batch finished, {count} lines processed"),
+    ("cli.batch.create_failed", "failed to create output file '{file}': {error}"),
+    ("cli.batch.write_failed", "failed to write output file: {error}"),
+    ("cli.batch.written", "results written to {file}"),
+    ("cli.batch.input", "input {line}: {input}"),
+    ("cli.batch.output", "output {line}: {output}"),
+    ("cli.batch.error", "error {line}: {error}"),
+    ("cli.batch.saved", "results saved to {file}"),
+    ("cli.batch.tsv_header", "input\tresult\ttime (ms)"),
+    ("cli.watch.watching", "watching '{file}', checking every {interval} ms, press Ctrl+C to stop"),
+    ("cli.watch.waiting", "file '{file}' does not exist, waiting for it to be created..."),
+    ("cli.watch.run", "{file} — run {count}, {changed} lines changed"),
+    ("cli.watch.line_error", "{input} -> error: {error}"),
+    ("cli.watch.stopped", "stopped watching"),
+    ("cli.notepad.loading", "loading notebook: {file}"),
+    ("cli.notepad.default_title", "New notebook"),
+    ("cli.notepad.creating", "creating notebook: {title} ({file})"),
+    ("cli.notepad.temporary_title", "Scratch notebook"),
+    ("cli.notepad.creating_temporary", "creating scratch notebook: {title}"),
+    ("cli.notepad.starting_gui", "starting the graphical interface..."),
+    ("cli.notepad.no_display", "warning: no graphical environment found, using the terminal interface\nhints:\n  - run inside a graphical desktop session\n  - over SSH, enable X11 forwarding with -X or -Y\n  - or pass --terminal to use the terminal interface"),
+    ("cli.notepad.gui_closed", "graphical interface closed"),
+    ("cli.notepad.gui_error", "graphical interface error: {error}"),
+    ("cli.notepad.gui_setup_failed", "failed to set up the graphical interface: {error}"),
+    ("cli.plot.write_failed", "failed to write '{file}': {error}"),
+    ("cli.plot.written", "wrote {count} data points to {file}"),
+    ("cli.plot.invalid_y_range", "invalid y range: the lower bound {min} must be less than the upper bound {max}"),
+    ("cli.plot.usage", "usage: plot(expression, ..., from, to)"),
+    ("cli.verify.missing_equals", "the equation has no '=': {equation}"),
+    ("cli.verify.numeric", "could not be proven by simplification; verified numerically at sample points"),
+    ("cli.verify.no_variables", "no variables"),
+    ("cli.prove.verified", "holds: the difference of the two sides simplifies to 0"),
+    ("cli.prove.supported", "numerically supported: not proven by simplification, but both sides agree at {count} points in the domain"),
+    ("cli.prove.refuted", "does not hold"),
+    ("cli.verify.counterexample", "counterexample: {point} (left side = {lhs}, right side = {rhs})"),
+    ("cli.quiz.title", "{kind} exercises (difficulty {difficulty}, seed {seed})"),
+    ("cli.quiz.problems", "Problems"),
+    ("cli.quiz.answers", "Answers"),
+    ("cli.laplace.region", "region of convergence: {region}"),
+    ("cli.laplace.region_unknown", "unknown"),
+    ("cli.laplace.partial", "some terms are not in the transform table and are kept symbolic"),
+    ("cli.analysis.none", "none"),
+    ("cli.analysis.function", "function: {function}"),
+    ("cli.analysis.domain", "domain: {domain}"),
+    ("cli.analysis.partial", " (partial analysis)"),
+    ("cli.analysis.critical", "critical points: {points}"),
+    ("cli.analysis.critical_failed", "critical points: analysis failed ({error})"),
+    ("cli.analysis.inflection", "inflection points: {points}"),
+    ("cli.analysis.inflection_failed", "inflection points: analysis failed ({error})"),
+    ("cli.usage", "Yufmath v{version} - computer algebra system\n\nUsage:\n  yufmath [options] <command> [args...]\n\nCommands:\n  compute <expr>                evaluate an expression\n  simplify <expr>               simplify an expression\n  diff <expr> [var...]          differentiate an expression\n  integrate <expr> [var]        integrate an expression\n  solve <equation> <var>        solve an equation\n  factor <expr>                 factor an expression\n  expand <expr>                 expand an expression\n  limit <expr> <var> <point>    compute a limit\n  series <expr> <var> <point>   series expansion\n  batch -i <file> [-o <file>]   batch mode\n  watch <file> [-o <file>]      recompute whenever the file is saved\n  interactive                   interactive mode\n  notepad [file] [-t <title>]   notebook mode\n\nOptions:\n  -f, --format <format>         output format [standard, latex, mathml, maxima]\n  -p, --precision <digits>      numeric precision\n  -v, --verbose                 verbose output\n  -q, --quiet                   quiet mode\n      --progress                show a progress bar\n      --no-progress             disable the progress bar\n      --timeout <seconds>       computation timeout\n      --config <file>           load configuration from a TOML/JSON file\n      --lang <language>         interface language [zh, en]\n  -h, --help                    show this help\n      --version                 show version information\n\nExamples:\n  yufmath compute \"2 + 3 * 4\"\n  yufmath simplify \"x^2 + 2*x + 1\"\n  yufmath diff \"x^3 + 2*x^2 + x\" x\n  yufmath integrate \"2*x + 1\" x\n  yufmath --format latex compute \"x^2 + 1\"\n  yufmath batch -i input.txt -o output.txt\n  yufmath --format latex watch input.txt -o out.tex\n  yufmath interactive\n  yufmath notepad\n  yufmath notepad my_notebook.ynb\n  yufmath notepad -t \"My math notes\""),
+
+    // 交互模式
+    ("interactive.banner", "computer algebra system"),
+    ("interactive.banner_hint", "Type {help} for help, {quit} to exit"),
+    ("interactive.no_color", "note: the terminal does not support colors or colors are disabled"),
+    ("interactive.goodbye", "Goodbye!"),
+    ("interactive.error", "error:"),
+    ("interactive.on", "on"),
+    ("interactive.off", "off"),
+    ("interactive.verbose", "verbose mode: {status}"),
+    ("interactive.colors", "colored output: {status}"),
+    ("interactive.approximations", "numeric approximations: {status}"),
+    ("interactive.timing", "timing: {status}"),
+    ("interactive.timing_usage", "usage: timing on|off"),
+    ("interactive.enhanced", "enhanced simplification: {status}"),
+    ("interactive.clear_failed", "warning: failed to clear system variables: {error}"),
+    ("interactive.cleared", "variables cleared"),
+    ("interactive.no_variables", "no variables defined"),
+    ("interactive.variables", "variables:"),
+    ("interactive.local", "local"),
+    ("interactive.setting_variable", "setting variable {name} = {value}"),
+    ("interactive.set_variable_failed", "failed to set variable '{name}': {error}"),
+    ("interactive.computing", "computing: {input}"),
+    ("interactive.invalid_format", "invalid format type. Available formats: standard, terminal, latex, mathml, maxima"),
+    ("interactive.format.standard", "standard format"),
+    ("interactive.format.terminal", "colored terminal format"),
+    ("interactive.format.latex", "LaTeX format"),
+    ("interactive.format.mathml", "MathML format"),
+    ("interactive.format.maxima", "Maxima format"),
+    ("interactive.format_set", "output format set to: {format}"),
+    ("interactive.precision_set", "numeric precision set to: {precision}"),
+    ("interactive.approx_precision_set", "approximation precision set to: {precision}"),
+    ("interactive.invalid_precision", "invalid precision, please enter a positive integer"),
+    ("interactive.config", "current compute configuration:"),
+    ("interactive.config_failed", "failed to show the configuration: {error}"),
+    ("interactive.invalid_number", "invalid number: {value}"),
+    ("interactive.invalid_switch", "please use on or off: {value}"),
+    ("interactive.timeout_set", "compute timeout set to: {seconds} seconds"),
+    ("interactive.timeout_cleared", "compute timeout removed"),
+    ("interactive.max_precision_set", "maximum digits for exact computation set to: {digits}"),
+    ("interactive.max_precision_cleared", "digit limit for exact computation removed"),
+    ("interactive.force_exact", "force exact computation: {status}"),
+    ("interactive.seed_set", "random seed set to: {seed}"),
+    ("interactive.seed_cleared", "fixed random seed removed"),
+    ("interactive.unknown_option", "unknown option: {name} (available: timeout, precision, force_exact, seed)"),
+    ("interactive.help.title", "Yufmath interactive calculator help"),
+    ("interactive.help.basic", "Basic commands"),
+    ("interactive.help.help", "Show this help"),
+    ("interactive.help.quit", "Exit the program"),
+    ("interactive.help.clear", "Clear all variables"),
+    ("interactive.help.vars", "Show all variables"),
+    ("interactive.help.verbose", "Toggle verbose mode"),
+    ("interactive.help.colors", "Toggle colored output"),
+    ("interactive.help.approx", "Toggle numeric approximations"),
+    ("interactive.help.enhanced", "Toggle enhanced simplification"),
+    ("interactive.help.timing", "Show or hide the time taken after each result"),
+    ("interactive.help.formatting", "Formatting commands"),
+    ("interactive.help.format", "Set the output format (standard, terminal, latex, mathml, maxima)"),
+    ("interactive.help.precision", "Set numeric precision"),
+    ("interactive.help.approx_precision", "Set approximation display precision"),
+    ("interactive.help.configuration", "Configuration commands"),
+    ("interactive.help.show_config", "Show the current compute configuration"),
+    ("interactive.help.set_timeout", "Set the compute timeout in seconds, off to remove"),
+    ("interactive.help.set_precision", "Set the maximum digits for exact computation, off to remove"),
+    ("interactive.help.set_force_exact", "Toggle forced exact computation"),
+    ("interactive.help.set_seed", "Set the random seed"),
+    ("interactive.help.math", "Math"),
+    ("interactive.help.arithmetic", "Basic arithmetic"),
+    ("interactive.help.algebra", "Algebraic expressions"),
+    ("interactive.help.trig", "Trigonometric functions and constants"),
+    ("interactive.help.diff", "Differentiation (not yet implemented)"),
+    ("interactive.help.integrate", "Integration (not yet implemented)"),
+    ("interactive.help.vector_calculus", "Curl; div, jacobian and laplacian also work"),
+    ("interactive.help.plot", "Draw a function graph inline; several curves may be listed"),
+    ("interactive.help.assignment", "Variable assignment"),
+    ("interactive.help.assign", "Assign a value to a variable"),
+    ("interactive.help.use_variable", "Expression using a variable"),
+    ("interactive.help.statements", "Several statements separated by semicolons; those ending in ;; are not echoed"),
+    ("interactive.help.comments", "# or // to end of line and /* ... */ are comments"),
+    ("interactive.help.examples", "Examples"),
+    ("interactive.help.multiline", "End a line with \\ to continue it; an empty line ends the input."),
+];
+
+/// 命令行帮助，键为以 `.` 连接的子命令路径和参数名，如 `yufmath.diff.variable`
+pub(super) static HELP: &[(&str, &str)] = &[
+    // 命令行帮助
+    ("yufmath", "A high-performance computer algebra system written in Rust"),
+    ("yufmath.format", "Output format"),
+    ("yufmath.precision", "Numeric precision"),
+    ("yufmath.verbose", "Verbose output"),
+    ("yufmath.quiet", "Quiet mode"),
+    ("yufmath.progress", "Show a progress bar while computing"),
+    ("yufmath.no_progress", "Disable the progress bar"),
+    ("yufmath.timeout", "Compute timeout in seconds, overrides the configuration file"),
+    ("yufmath.config", "Load the compute configuration from a TOML or JSON file"),
+    ("yufmath.trace", "Print the simplification rules in the order they fire (set YUFMATH_TRACE_FILTER to follow a single rule)"),
+    ("yufmath.stats", "Print the time and peak memory of each operation when done"),
+    ("yufmath.time", "Print the time taken after the result, split into parse, simplify and format for compute"),
+    ("yufmath.sort_terms", "Before display, order polynomial terms by descending degree and product factors as numbers, variables, then functions"),
+    ("yufmath.term_order", "Order of sum terms for display; implies --sort-terms"),
+    ("yufmath.line_width", "Line width for LaTeX output; expressions wider than this (estimated) break between terms"),
+    ("yufmath.multiline_env", "LaTeX environment used for line-broken output"),
+    ("yufmath.lang", "Interface language (zh or en); chosen from YUFMATH_LANG and the system locale by default"),
+    ("yufmath.color", "Colored output; with auto, color only when writing to a terminal and NO_COLOR is not set"),
+    ("yufmath.compute", "Compute an expression"),
+    ("yufmath.compute.expression", "Expression to compute"),
+    ("yufmath.simplify", "Simplify an expression"),
+    ("yufmath.simplify.expression", "Expression to simplify"),
+    ("yufmath.diff", "Differentiate with respect to a variable"),
+    ("yufmath.diff.expression", "Expression to differentiate"),
+    ("yufmath.diff.variables", "Variable of differentiation; several variables are applied in order, defaults to the only variable in the expression"),
+    ("yufmath.integrate", "Integrate with respect to a variable"),
+    ("yufmath.integrate.expression", "Expression to integrate"),
+    ("yufmath.integrate.variable", "Variable of integration; defaults to the only variable in the expression"),
+    ("yufmath.integrate.with_constant", "Append the integration constant C to the result"),
+    ("yufmath.solve", "Solve an equation"),
+    ("yufmath.solve.equation", "Equation to solve"),
+    ("yufmath.solve.variable", "Variable to solve for"),
+    ("yufmath.factor", "Factor an expression"),
+    ("yufmath.factor.expression", "Expression to factor"),
+    ("yufmath.expand", "Expand an expression"),
+    ("yufmath.expand.expression", "Expression to expand"),
+    ("yufmath.trig-expand", "Expand trigonometric sums and multiple angles"),
+    ("yufmath.trig-expand.expression", "Expression to expand"),
+    ("yufmath.trig-factor", "Turn sums of sines and cosines into products"),
+    ("yufmath.trig-factor.expression", "Expression to transform"),
+    ("yufmath.limit", "Compute a limit"),
+    ("yufmath.limit.expression", "Expression"),
+    ("yufmath.limit.variable", "Variable"),
+    ("yufmath.limit.point", "Point the variable approaches"),
+    ("yufmath.series", "Series expansion"),
+    ("yufmath.series.expression", "Expression"),
+    ("yufmath.series.variable", "Variable"),
+    ("yufmath.series.point", "Expansion point"),
+    ("yufmath.series.order", "Expansion order"),
+    ("yufmath.laplace", "Laplace transform"),
+    ("yufmath.laplace.expression", "Original function"),
+    ("yufmath.laplace.variable", "Variable of the original function"),
+    ("yufmath.laplace.transform_variable", "Variable of the transform"),
+    ("yufmath.laplace.region", "Also print the region of convergence"),
+    ("yufmath.inverse-laplace", "Inverse Laplace transform"),
+    ("yufmath.inverse-laplace.expression", "Transform"),
+    ("yufmath.inverse-laplace.variable", "Variable of the transform"),
+    ("yufmath.inverse-laplace.time_variable", "Variable of the original function"),
+    ("yufmath.analyze", "Analyze the domain, critical points, extrema and inflection points of a function"),
+    ("yufmath.analyze.expression", "Expression to analyze"),
+    ("yufmath.analyze.variable", "Independent variable"),
+    ("yufmath.verify", "Check whether an equation is an identity"),
+    ("yufmath.verify.equation", "Equation to check, of the form \"lhs = rhs\""),
+    ("yufmath.plot-data", "Export sampled function values for external plotting tools"),
+    ("yufmath.plot-data.expression", "Expression to sample"),
+    ("yufmath.plot-data.variable", "Independent variable"),
+    ("yufmath.plot-data.from", "Start of the interval"),
+    ("yufmath.plot-data.to", "End of the interval"),
+    ("yufmath.plot-data.n", "Number of samples, including both ends"),
+    ("yufmath.plot-data.csv", "Write CSV to a file instead of standard output"),
+    ("yufmath.plot-data.json", "Print JSON to standard output"),
+    ("yufmath.plot-data.skip_undefined", "Skip points outside the domain (kept as NaN by default)"),
+    ("yufmath.plot-ascii", "Draw a function graph in the terminal"),
+    ("yufmath.plot-ascii.expression", "Expressions to draw, separated by `;` for multiple curves"),
+    ("yufmath.plot-ascii.variable", "Independent variable"),
+    ("yufmath.plot-ascii.from", "Start of the interval"),
+    ("yufmath.plot-ascii.to", "End of the interval"),
+    ("yufmath.plot-ascii.width", "Width in characters, defaults to the terminal width"),
+    ("yufmath.plot-ascii.height", "Height in lines, defaults to the terminal height"),
+    ("yufmath.plot-ascii.y_min", "Lower bound of the y axis, given together with --y-max; chosen from the values by default"),
+    ("yufmath.plot-ascii.y_max", "Upper bound of the y axis"),
+    ("yufmath.plot-ascii.ascii", "Use ASCII characters instead of Unicode braille dots"),
+    ("yufmath.quiz", "Generate exercises, listing the problems before the answers"),
+    ("yufmath.quiz.kind", "Kind of exercise"),
+    ("yufmath.quiz.count", "Number of problems"),
+    ("yufmath.quiz.difficulty", "Difficulty (1 to 3)"),
+    ("yufmath.quiz.seed", "Random seed; the same seed generates the same problems. A random seed is chosen and printed when omitted"),
+    ("yufmath.batch", "Batch mode"),
+    ("yufmath.batch.input", "Input file path"),
+    ("yufmath.batch.output", "Output file path (optional)"),
+    ("yufmath.watch", "Watch an input file, recompute after every save and mark the lines that changed"),
+    ("yufmath.watch.input", "Input file path; waits for the file to be created if it does not exist"),
+    ("yufmath.watch.output", "Output file that is kept up to date with the results (optional); a complete document for LaTeX"),
+    ("yufmath.watch.interval", "Interval between checks for changes (milliseconds)"),
+    ("yufmath.interactive", "Start interactive mode"),
+    ("yufmath.notepad", "Start notebook mode"),
+    ("yufmath.notepad.file", "Notebook file path (optional; a new notebook is created when omitted)"),
+    ("yufmath.notepad.title", "Notebook title, used when creating a new notebook"),
+    ("yufmath.notepad.terminal", "Use the terminal interface (the graphical interface is the default)"),
+];
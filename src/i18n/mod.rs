@@ -0,0 +1,225 @@
+//! # 本地化
+//!
+//! 错误消息、命令行帮助和交互提示通过消息表获取，支持 zh-CN（默认）和 en-US。
+//! 消息表在编译期嵌入；缺少译文时回退到中文，debug 构建下会打印警告。
+//!
+//! 库默认使用中文，设置了环境变量 `YUFMATH_LANG` 时使用其指定的语言；
+//! 命令行工具另外参考 `--lang` 参数和系统 locale，见 [`Locale::detect`]。
+
+mod zh_cn;
+mod en_us;
+
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 指定界面语言的环境变量，如 `YUFMATH_LANG=en`
+pub const LANG_ENV: &str = "YUFMATH_LANG";
+
+/// 界面语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// 简体中文
+    #[default]
+    ZhCn,
+    /// 美式英语
+    EnUs,
+}
+
+impl Locale {
+    /// 语言标签，如 `zh-CN`
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh-CN",
+            Locale::EnUs => "en-US",
+        }
+    }
+
+    /// 解析 `en`、`en-US`、`en_US.UTF-8` 这类语言标签，只看主语言部分，不认识时返回 None
+    pub fn from_tag(tag: &str) -> Option<Self> {
+        let primary = tag.trim().split(['-', '_', '.', '@']).next()?;
+        match primary.to_ascii_lowercase().as_str() {
+            "zh" => Some(Locale::ZhCn),
+            "en" => Some(Locale::EnUs),
+            _ => None,
+        }
+    }
+
+    /// 从环境选择语言：先看 `YUFMATH_LANG`，再按 POSIX 的顺序取 `LC_ALL`、`LC_MESSAGES`、`LANG`
+    /// 中第一个非空的值，都不认识时用中文
+    pub fn detect() -> Self {
+        if let Some(locale) = std::env::var(LANG_ENV).ok().and_then(|tag| Self::from_tag(&tag)) {
+            return locale;
+        }
+        ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Self::from_tag(&value))
+            .unwrap_or_default()
+    }
+
+    /// 该语言的消息表
+    fn messages(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::ZhCn => zh_cn::MESSAGES,
+            Locale::EnUs => en_us::MESSAGES,
+        }
+    }
+
+    /// 该语言的命令行帮助表；中文帮助直接写在参数定义的文档注释里
+    fn help(self) -> &'static [(&'static str, &'static str)] {
+        match self {
+            Locale::ZhCn => &[],
+            Locale::EnUs => en_us::HELP,
+        }
+    }
+
+    fn to_u8(self) -> u8 {
+        match self {
+            Locale::ZhCn => 1,
+            Locale::EnUs => 2,
+        }
+    }
+}
+
+/// 当前语言，0 表示尚未确定
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+/// 设置当前界面语言，对整个进程生效
+pub fn set_locale(locale: Locale) {
+    CURRENT.store(locale.to_u8(), Ordering::Relaxed);
+}
+
+/// 当前界面语言；未设置时取 `YUFMATH_LANG`，否则为中文
+pub fn locale() -> Locale {
+    match CURRENT.load(Ordering::Relaxed) {
+        1 => Locale::ZhCn,
+        2 => Locale::EnUs,
+        _ => {
+            let locale = std::env::var(LANG_ENV).ok()
+                .and_then(|tag| Locale::from_tag(&tag))
+                .unwrap_or_default();
+            set_locale(locale);
+            locale
+        }
+    }
+}
+
+/// 在指定语言的消息表中查找，没有该条目时返回 None
+pub fn lookup(locale: Locale, key: &str) -> Option<&'static str> {
+    locale.messages().iter().find(|(k, _)| *k == key).map(|(_, message)| *message)
+}
+
+/// 当前语言下的命令行帮助文本，没有译文时返回 None，由调用方保留原帮助
+pub fn help(key: &str) -> Option<&'static str> {
+    locale().help().iter().find(|(k, _)| *k == key).map(|(_, text)| *text)
+}
+
+/// 当前语言下的消息，缺少译文时回退到中文；中文也没有时返回键本身
+pub fn text(key: &'static str) -> &'static str {
+    let locale = locale();
+    if let Some(message) = lookup(locale, key) {
+        return message;
+    }
+    #[cfg(debug_assertions)]
+    eprintln!("warning: missing {} translation for message '{}'", locale.tag(), key);
+    lookup(Locale::ZhCn, key).unwrap_or(key)
+}
+
+/// 当前语言下的消息，并把其中的 `{name}` 占位符替换为对应参数；没有对应参数的占位符原样保留
+pub fn format_text(key: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    fill(text(key), args)
+}
+
+/// 替换模板中的 `{name}` 占位符，只扫描一遍，参数值中的花括号不会被再次替换
+fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        result.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let value = after.find('}')
+            .and_then(|end| args.iter().find(|(name, _)| *name == &after[..end]).map(|(_, value)| (end, value)));
+        match value {
+            Some((end, value)) => {
+                result.push_str(&value.to_string());
+                rest = &after[end + 1..];
+            }
+            None => {
+                result.push('{');
+                rest = after;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+/// 取当前语言下的消息：`tr!("key")` 返回 `&'static str`，
+/// `tr!("key", name = value, ...)` 替换 `{name}` 占位符后返回 `String`
+#[macro_export]
+macro_rules! tr {
+    ($key:expr) => {
+        $crate::i18n::text($key)
+    };
+    ($key:expr, $($name:ident = $value:expr),+ $(,)?) => {
+        $crate::i18n::format_text($key, &[$((stringify!($name), &$value as &dyn ::std::fmt::Display)),+])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    /// 消息中的占位符名称
+    fn placeholders(message: &str) -> HashSet<&str> {
+        message.split('{').skip(1).filter_map(|part| part.split_once('}').map(|(name, _)| name)).collect()
+    }
+
+    #[test]
+    fn test_tables_are_complete() {
+        for locale in [Locale::ZhCn, Locale::EnUs] {
+            let mut seen = HashSet::new();
+            for (key, _) in locale.messages() {
+                assert!(seen.insert(key), "{} 中的键 {} 重复", locale.tag(), key);
+            }
+        }
+        // 两张表的键一致，占位符一致
+        for (key, zh) in zh_cn::MESSAGES {
+            let en = lookup(Locale::EnUs, key).unwrap_or_else(|| panic!("缺少 en-US 译文：{}", key));
+            assert_eq!(placeholders(zh), placeholders(en), "{}", key);
+        }
+        for (key, _) in en_us::MESSAGES {
+            assert!(lookup(Locale::ZhCn, key).is_some(), "en-US 中多出的键：{}", key);
+        }
+    }
+
+    #[test]
+    fn test_locale_tags() {
+        assert_eq!(Locale::from_tag("en"), Some(Locale::EnUs));
+        assert_eq!(Locale::from_tag("en_US.UTF-8"), Some(Locale::EnUs));
+        assert_eq!(Locale::from_tag("zh-CN"), Some(Locale::ZhCn));
+        assert_eq!(Locale::from_tag("zh_TW.UTF-8"), Some(Locale::ZhCn));
+        assert_eq!(Locale::from_tag("C"), None);
+        assert_eq!(Locale::from_tag(""), None);
+        for locale in [Locale::ZhCn, Locale::EnUs] {
+            assert_eq!(Locale::from_tag(locale.tag()), Some(locale));
+        }
+    }
+
+    #[test]
+    fn test_fill_placeholders() {
+        assert_eq!(fill("未定义变量 '{name}'", &[("name", &"x")]), "未定义变量 'x'");
+        assert_eq!(fill("{a} + {b} = {c}", &[("a", &1), ("b", &2), ("c", &3)]), "1 + 2 = 3");
+        // 参数值里的占位符不再替换，不认识的占位符和孤立的花括号原样保留
+        assert_eq!(fill("{a}{b}", &[("a", &"{b}"), ("b", &"!")]), "{b}!");
+        assert_eq!(fill("{x} {", &[]), "{x} {");
+    }
+
+    #[test]
+    fn test_lookup_by_locale() {
+        assert_eq!(lookup(Locale::ZhCn, "compute.division_by_zero"), Some("除零错误"));
+        assert_eq!(lookup(Locale::EnUs, "compute.division_by_zero"), Some("division by zero"));
+        assert_eq!(lookup(Locale::EnUs, "no.such.key"), None);
+    }
+}
@@ -0,0 +1,866 @@
+//! # 简体中文消息表
+//!
+//! 默认语言，也是其他语言缺少译文时的回退。`{name}` 为占位符，建议类消息每行一条。
+
+/// 按键查找的消息
+pub(super) static MESSAGES: &[(&str, &str)] = &[
+    // 计算错误
+    ("compute.division_by_zero", "除零错误"),
+    ("compute.undefined_variable", "未定义变量：{name}"),
+    ("compute.overflow", "数值溢出"),
+    ("compute.unsupported_operation", "不支持的运算：{operation}"),
+    ("compute.domain_error", "域错误：{message}"),
+    ("compute.dimension_mismatch", "矩阵维度不匹配：{message}"),
+    ("compute.singular_matrix", "奇异矩阵，无法求逆"),
+    ("compute.convergence_failure", "数值方法收敛失败：{message}"),
+    ("compute.timeout", "计算超时"),
+    ("compute.out_of_memory", "内存不足"),
+    ("compute.resource_limit", "超出资源限制：{message}"),
+    ("compute.cancelled", "用户取消计算"),
+    ("compute.division_by_zero.message", "除零错误：不能除以零。请检查分母是否为零"),
+    ("compute.undefined_variable.message", "未定义变量 '{name}'。请确保变量已被赋值或在表达式中定义"),
+    ("compute.overflow.message", "数值溢出：计算结果超出了数值范围。请尝试使用更小的数值或检查计算逻辑"),
+    ("compute.unsupported_operation.message", "不支持的运算 '{operation}'。该运算可能尚未实现或不适用于当前数据类型"),
+    ("compute.domain_error.message", "域错误：{message}。请检查函数的输入值是否在有效范围内"),
+    ("compute.dimension_mismatch.message", "矩阵维度不匹配：{message}。请检查矩阵的行列数是否符合运算要求"),
+    ("compute.singular_matrix.message", "奇异矩阵错误：矩阵不可逆（行列式为零）。请检查矩阵是否为满秩矩阵"),
+    ("compute.convergence_failure.message", "数值方法收敛失败：{message}。请尝试调整参数或使用其他方法"),
+    ("compute.timeout.message", "计算超时：计算时间超过了设定的限制。请尝试简化表达式或增加超时时间"),
+    ("compute.out_of_memory.message", "内存不足：计算需要的内存超过了可用内存。请尝试简化计算或释放内存"),
+    ("compute.resource_limit.message", "超出资源限制：{message}。计算已中止，缓存和中间结果已释放"),
+    ("compute.cancelled.message", "计算已取消：用户主动取消了计算过程"),
+    ("compute.division_by_zero.suggestions", "检查分母表达式是否可能为零\n使用条件判断来避免除零情况\n考虑使用极限来处理 0/0 类型的不定式"),
+    ("compute.undefined_variable.suggestions", "为变量 '{name}' 赋值\n检查变量名是否拼写正确\n确保变量在使用前已经定义"),
+    ("compute.overflow.suggestions", "使用更小的数值进行计算\n检查是否有无限循环或递归\n考虑使用任意精度数值类型\n分步计算以避免中间结果过大"),
+    ("compute.unsupported_operation.suggestions", "检查运算 '{operation}' 是否适用于当前数据类型\n查看文档了解支持的运算列表\n考虑使用等价的支持运算"),
+    ("compute.domain_error.suggestions", "检查函数输入值的有效范围\n对于平方根，确保输入为非负数\n对于对数，确保输入为正数\n对于反三角函数，确保输入在 [-1, 1] 范围内"),
+    ("compute.dimension_mismatch.suggestions", "检查矩阵的行列数是否匹配运算要求\n矩阵乘法要求第一个矩阵的列数等于第二个矩阵的行数\n矩阵加减法要求两个矩阵具有相同的维度"),
+    ("compute.singular_matrix.suggestions", "检查矩阵是否为方阵\n确保矩阵的行列式不为零\n检查矩阵的行或列是否线性相关\n考虑使用伪逆或其他数值方法"),
+    ("compute.convergence_failure.suggestions", "增加迭代次数限制\n调整收敛精度要求\n检查初始值是否合适\n尝试使用不同的数值方法"),
+    ("compute.timeout.suggestions", "简化表达式以减少计算复杂度\n增加计算超时时间限制\n分解复杂计算为多个简单步骤"),
+    ("compute.out_of_memory.suggestions", "简化计算以减少内存使用\n释放不必要的变量和缓存\n使用流式计算处理大数据"),
+    ("compute.resource_limit.suggestions", "调大 memory.max_memory_usage 上限\n先化简或分解表达式再计算\n避免展开高次幂"),
+    ("compute.cancelled.suggestions", "如需继续计算，请重新启动"),
+
+    // 解析错误
+    ("parse.syntax", "语法错误：位置 {pos}，{message}"),
+    ("parse.unknown_function", "未知函数：{name}"),
+    ("parse.argument_count", "参数数量错误：期望 {expected}，实际 {actual}"),
+    ("parse.invalid_number", "无效的数值格式：{value}"),
+    ("parse.invalid_variable", "无效的变量名：{name}"),
+    ("parse.unmatched_parenthesis", "括号不匹配：位置 {pos}"),
+    ("parse.unexpected_end_of_input", "意外的输入结束"),
+    ("parse.unexpected_character", "意外的字符：位置 {pos}，字符 '{ch}'"),
+    ("parse.empty_expression", "空表达式"),
+    ("parse.syntax.message", "语法错误（位置 {pos}）：{message}"),
+    ("parse.unknown_function.message", "未知函数 '{name}'。您是否想要使用：sin, cos, tan, exp, ln, sqrt？"),
+    ("parse.argument_count.message", "函数参数数量错误：期望 {expected} 个参数，但提供了 {actual} 个"),
+    ("parse.invalid_number.message", "无效的数值格式 '{value}'。请检查数值是否正确输入"),
+    ("parse.invalid_variable.message", "无效的变量名 '{name}'。变量名应以字母开头，只包含字母、数字和下划线"),
+    ("parse.unmatched_parenthesis.message", "括号不匹配（位置 {pos}）。请检查是否有未闭合的括号"),
+    ("parse.unexpected_end_of_input.message", "表达式不完整。请检查是否缺少操作数或运算符"),
+    ("parse.unexpected_character.message", "意外的字符 '{ch}' （位置 {pos}）。请检查输入是否正确"),
+    ("parse.empty_expression.message", "表达式为空。请输入一个有效的数学表达式"),
+    ("parse.unknown_function.did_you_mean", "您是否想要使用 '{name}'？"),
+    ("parse.unknown_function.suggestions", "请检查函数名是否正确拼写\n查看支持的函数列表：sin, cos, tan, exp, ln, sqrt 等"),
+    ("parse.invalid_number.suggestions", "请检查数值格式是否正确\n支持的格式：整数（123）、小数（3.14）、科学记数法（1.23e-4）\n如果 '{value}' 是变量名，请确保它不以数字开头"),
+    ("parse.unmatched_parenthesis.suggestions", "检查每个左括号 '(' 是否有对应的右括号 ')'\n检查每个右括号 ')' 是否有对应的左括号 '('\n使用文本编辑器的括号匹配功能来检查括号配对"),
+    ("parse.syntax.suggestions", "检查运算符是否正确使用\n确保每个运算符都有适当的操作数\n检查是否有连续的运算符（如 '++' 或 '--'）"),
+    ("parse.argument_count.suggestions", "该函数需要 {expected} 个参数\n检查函数调用中的逗号分隔符\n确保每个参数都是有效的表达式"),
+    ("parse.suggestions", "请检查表达式的语法是否正确\n参考文档了解正确的语法格式"),
+    ("parse.context.error", "错误：{message}"),
+    ("parse.context.input", "输入：{input}"),
+    ("parse.context.position", "位置：{marker}"),
+    ("parse.context.suggestions", "建议："),
+    ("parse.empty_number", "空数值"),
+    ("parse.empty_identifier", "空标识符"),
+    ("parse.unexpected_token", "意外的标记: {token}"),
+    ("parse.unterminated_comment", "块注释缺少结尾的 */"),
+    ("parse.expected_index_separator", "期望 ',' 或 ']' 在下标中"),
+    ("parse.expected_arguments", "期望 '(' 开始函数参数列表"),
+    ("parse.expected_argument_separator", "期望 ',' 或 ')' 在函数参数列表中"),
+    ("parse.expected_matrix_end", "期望 ']' 结束矩阵"),
+    ("parse.expected_vector_end", "期望 ']' 结束向量"),
+    ("parse.expected_row_start", "期望 '[' 开始矩阵行"),
+    ("parse.expected_row_separator", "期望 ',' 或 ']' 在矩阵行中"),
+    ("parse.latex.missing_command", "反斜杠后缺少命令名"),
+    ("parse.latex.expected", "期望 '{symbol}'"),
+    ("parse.latex.expected_argument", "期望参数"),
+    ("parse.latex.expected_name", "期望名称"),
+    ("parse.latex.invalid_name", "名称只能包含字母和数字"),
+    ("parse.latex.unsupported_command", "不支持的 LaTeX 命令: \\{name}"),
+    ("parse.latex.log_base_arguments", "带底数的对数只接受一个参数"),
+    ("parse.latex.unsupported_environment", "不支持的 LaTeX 环境: {environment}"),
+    ("parse.latex.unterminated_environment", "环境 {environment} 没有正确结束"),
+    ("signature.arity_exact", "{name} 需要 {count} 个参数，但给了 {actual} 个"),
+    ("signature.arity_range", "{name} 需要 {min} 到 {max} 个参数，但给了 {actual} 个"),
+    ("signature.arity_at_least", "{name} 至少需要 {min} 个参数，但给了 {actual} 个"),
+    ("signature.expected_variable", "{name} 的参数 {parameter} 应为变量名，但给了 {actual}"),
+    ("signature.expected_integer", "{name} 的参数 {parameter} 应为整数，但给了 {actual}"),
+
+    // 顶层错误
+    ("error.parse", "解析错误: {error}"),
+    ("error.compute", "计算错误: {error}"),
+    ("error.format", "格式化错误: {error}"),
+    ("error.io", "IO 错误: {error}"),
+    ("error.config", "配置错误: {message}"),
+    ("error.internal", "内部错误: {message}"),
+    ("error.unsupported_format", "不支持的格式：{format}"),
+    ("error.format_failure", "格式化失败：{message}"),
+    ("error.unsupported_format.message", "不支持的输出格式 '{format}'。支持的格式：standard, latex, mathml, maxima"),
+    ("error.format_failure.message", "格式化失败：{message}。请检查表达式是否过于复杂"),
+    ("error.io.message", "文件操作错误：{error}。请检查文件路径和权限"),
+    ("error.config.message", "配置错误：{message}。请检查配置文件或参数设置"),
+    ("error.internal.message", "内部错误：{message}。这可能是程序缺陷，请报告此问题"),
+    ("error.unsupported_format.suggestions", "使用支持的格式：standard, latex, mathml, maxima\n检查格式名称的拼写是否正确"),
+    ("error.format_failure.suggestions", "尝试简化表达式\n检查表达式是否包含不支持的元素\n使用不同的输出格式"),
+    ("error.io.suggestions", "检查文件路径是否正确\n确保有足够的文件访问权限\n检查磁盘空间是否充足"),
+    ("error.config.suggestions", "检查配置文件的语法是否正确\n确保所有必需的配置项都已设置\n参考文档了解正确的配置格式"),
+    ("error.internal.suggestions", "这是程序内部错误，请报告给开发者\n尝试重启程序\n检查是否有可用的程序更新"),
+    ("error.report.title", "错误: {message}"),
+    ("error.report.severity", "严重程度：{severity}"),
+    ("error.report.suggestions", "建议解决方案："),
+    ("error.report.recoverable", "此错误可以修复，请根据建议进行调整后重试"),
+    ("error.report.unrecoverable", "此错误无法自动恢复，可能需要程序重启或联系技术支持"),
+
+    // 接口
+    ("api.formatter_lock", "无法获取格式化器锁"),
+    ("api.monitor_unavailable", "无法获取性能监控器"),
+    ("api.cancelled", "计算被用户取消"),
+    ("api.malformed_congruence", "同余方程应写成 a ≡ b (mod n)，实际为 {equation}"),
+    ("api.sample_count", "采样点数必须大于 0"),
+    ("api.sample_interval", "采样区间端点必须是有限数"),
+    ("api.no_variable", "表达式不含变量，请明确指定变量"),
+    ("api.ambiguous_variable", "表达式含有多个变量 {variables}，请明确指定变量"),
+    ("api.variables_unsupported", "当前引擎不支持变量管理"),
+    ("api.functions_unsupported", "当前引擎不支持自定义函数"),
+    ("api.runtime_config_unsupported", "当前引擎不支持运行时配置"),
+    ("api.step.parse", "解析表达式"),
+    ("api.step.simplify", "简化表达式"),
+    ("api.step.format", "格式化结果"),
+    ("api.step.done", "计算完成"),
+    ("api.phase.parsing", "解析"),
+    ("api.phase.simplification", "化简"),
+    ("api.phase.computation", "计算"),
+    ("api.phase.formatting", "格式化"),
+    ("api.phase.completed", "完成"),
+    ("api.phase.error", "错误"),
+    ("api.timing.summary", "耗时 {total}（{phases}）"),
+    ("api.timing.total", "耗时 {total}"),
+    ("api.timing.phase", "{phase} {duration}"),
+    ("api.timing.separator", "，"),
+    ("api.step.analyze", "分析表达式结构"),
+    ("api.step.simplified", "简化完成"),
+    ("api.step.analyze_integrand", "分析被积函数"),
+    ("api.step.integration_rules", "应用积分规则"),
+    ("api.step.integrated", "积分完成"),
+    ("api.step.compute", "计算结果"),
+    ("api.step.format_output", "格式化输出"),
+    ("api.task.no_result", "任务已完成但没有结果"),
+    ("api.task.cancelled", "任务已被取消"),
+    ("api.task.failed", "任务执行出错"),
+    ("api.task.lock", "无法获取任务锁"),
+    ("api.task.task_cancelled", "计算被取消"),
+    ("api.task.result", "计算结果: {expression}"),
+    ("api.config.parse_toml", "无法解析 TOML 配置: {error}"),
+    ("api.config.parse_json", "无法解析 JSON 配置: {error}"),
+    ("api.config.serialize", "无法序列化配置: {error}"),
+
+    // 运算符与常量
+    ("operator.add", "加法"),
+    ("operator.subtract", "减法"),
+    ("operator.multiply", "乘法"),
+    ("operator.divide", "除法"),
+    ("operator.power", "幂运算"),
+    ("operator.modulo", "取模"),
+    ("operator.equal", "等于"),
+    ("operator.not_equal", "不等于"),
+    ("operator.less", "小于"),
+    ("operator.less_equal", "小于等于"),
+    ("operator.greater", "大于"),
+    ("operator.greater_equal", "大于等于"),
+    ("operator.and", "逻辑与"),
+    ("operator.or", "逻辑或"),
+    ("operator.union", "并集"),
+    ("operator.intersection", "交集"),
+    ("operator.set_difference", "差集"),
+    ("operator.matrix_multiply", "矩阵乘法"),
+    ("operator.cross_product", "叉积"),
+    ("operator.dot_product", "点积"),
+    ("operator.negate", "负号"),
+    ("operator.plus", "正号"),
+    ("operator.sqrt", "平方根"),
+    ("operator.abs", "绝对值"),
+    ("operator.sin", "正弦"),
+    ("operator.cos", "余弦"),
+    ("operator.tan", "正切"),
+    ("operator.asin", "反正弦"),
+    ("operator.acos", "反余弦"),
+    ("operator.atan", "反正切"),
+    ("operator.sinh", "双曲正弦"),
+    ("operator.cosh", "双曲余弦"),
+    ("operator.tanh", "双曲正切"),
+    ("operator.asinh", "反双曲正弦"),
+    ("operator.acosh", "反双曲余弦"),
+    ("operator.atanh", "反双曲正切"),
+    ("operator.ln", "自然对数"),
+    ("operator.log10", "常用对数"),
+    ("operator.log2", "二进制对数"),
+    ("operator.exp", "指数函数"),
+    ("operator.factorial", "阶乘"),
+    ("operator.gamma", "伽马函数"),
+    ("operator.not", "逻辑非"),
+    ("operator.real", "实部"),
+    ("operator.imaginary", "虚部"),
+    ("operator.conjugate", "共轭"),
+    ("operator.argument", "幅角"),
+    ("operator.transpose", "转置"),
+    ("operator.determinant", "行列式"),
+    ("operator.inverse", "逆矩阵"),
+    ("operator.trace", "矩阵的迹"),
+    ("constant.pi", "圆周率"),
+    ("constant.e", "自然常数"),
+    ("constant.i", "虚数单位"),
+    ("constant.euler_gamma", "欧拉-马歇罗尼常数"),
+    ("constant.golden_ratio", "黄金比例"),
+    ("constant.catalan", "卡塔兰常数"),
+    ("constant.positive_infinity", "正无穷"),
+    ("constant.negative_infinity", "负无穷"),
+    ("constant.undefined", "未定义"),
+    ("constant.property.irrational", "无理数"),
+    ("constant.property.transcendental", "超越数"),
+    ("constant.property.positive", "正数"),
+    ("constant.property.imaginary_unit", "虚数单位"),
+    ("constant.property.complex", "复数"),
+    ("constant.property.real", "实数"),
+    ("constant.property.possibly_irrational", "可能是无理数"),
+    ("constant.property.algebraic", "代数数"),
+    ("constant.property.infinite", "无穷大"),
+    ("constant.property.negative", "负数"),
+    ("constant.property.undefined", "未定义"),
+
+    // 表达式
+    ("expression.empty_matrix", "矩阵不能为空"),
+    ("expression.empty_matrix_row", "矩阵行不能为空"),
+    ("expression.ragged_matrix", "矩阵第{row}行的列数({len})与第一行的列数({cols})不匹配"),
+    ("expression.empty_vector", "向量不能为空"),
+    ("expression.matmul_mismatch", "矩阵乘法维度不匹配：{left}×? 和 {right}×?"),
+    ("expression.matmul_requires_matrices", "矩阵乘法只能应用于矩阵"),
+    ("expression.dot_mismatch", "向量点积维度不匹配：{left} 和 {right}"),
+    ("expression.dot_requires_vectors", "点积只能应用于向量"),
+    ("expression.cross_requires_3d", "叉积只能应用于3维向量"),
+    ("expression.requires_square_matrix", "{operation}只能应用于方阵"),
+    ("expression.requires_matrix", "{operation}只能应用于矩阵"),
+    ("expression.factorial_requires_integer", "阶乘只能应用于整数"),
+    ("expression.empty_variable_name", "变量名不能为空"),
+    ("expression.variable_is_constant", "变量名 {name} 与数学常量冲突"),
+    ("expression.rename_conflict", "无法将 {from} 重命名为 {to}：变量 {to} 已存在"),
+    ("expression.undefined_variable", "未定义的变量: {name}"),
+    ("expression.zero_negative_power", "0的负数次幂未定义"),
+    ("expression.modulo_by_zero", "模零错误"),
+    ("expression.negative_factorial", "负数的阶乘未定义"),
+    ("expression.factorial_requires_natural", "阶乘只能应用于非负整数"),
+    ("expression.log_requires_positive", "对数函数的参数必须为正数"),
+
+    // 类型
+    ("type.symbolic", "符号"),
+    ("type.matrix", "矩阵[{rows}×{cols}]<{element}>"),
+    ("type.vector", "向量[{dim}]<{element}>"),
+    ("type.set", "集合<{element}>"),
+    ("type.interval", "区间<{element}>"),
+    ("type.unknown", "未知"),
+    ("type.integer", "整数"),
+    ("type.rational", "有理数"),
+    ("type.real", "实数"),
+    ("type.complex", "复数"),
+    ("type.float", "浮点数"),
+
+    // 二进制格式
+    ("binary.corrupt", "二进制表达式数据无效：{reason}"),
+    ("binary.trailing_data", "末尾有多余数据"),
+    ("binary.expression_count", "应包含 1 个表达式，实际为 {count} 个"),
+    ("binary.missing_header", "缺少格式头"),
+    ("binary.unsupported_version", "不支持的格式版本 {version}"),
+    ("binary.checksum", "校验和不匹配"),
+    ("binary.truncated", "数据被截断"),
+    ("binary.varint_overflow", "变长整数溢出"),
+    ("binary.length_out_of_range", "长度超出数据范围"),
+    ("binary.index_out_of_range", "{item}序号越界"),
+    ("binary.unknown_code", "未知的{item}编码 {code}"),
+    ("binary.invalid_utf8", "字符串不是合法的 UTF-8"),
+    ("binary.number_parts", "数值节点的组成部分类型不符"),
+    ("binary.item.constant", "常量"),
+    ("binary.item.string", "字符串"),
+    ("binary.item.binary_operator", "二元运算符"),
+    ("binary.item.unary_operator", "一元运算符"),
+    ("binary.item.node", "节点"),
+    ("binary.interval_flags", "区间标志无效"),
+    ("binary.unknown_tag", "未知的节点类型 {tag}"),
+    ("binary.child_out_of_range", "子节点引用越界"),
+    ("binary.too_many_nodes", "展开后超过 {limit} 个节点"),
+    ("binary.forward_reference", "二进制解码时引用了尚未构造的节点"),
+    ("binary.zero_denominator", "有理数的分母为零"),
+
+    // 内存
+    ("memory.limit_exceeded", "表达式估算占用 {peak} 字节，超过上限 {limit} 字节"),
+    ("memory.cleanup", "内存清理完成: 哈希缓存 {hash_cache_before} -> {hash_cache_after}, 表达式池 {pool_before} -> {pool_after}"),
+
+    // 张量
+    ("tensor.empty_axis", "张量各维长度必须为正，实际形状为 {shape}"),
+    ("tensor.size_mismatch", "形状 {shape} 与元素个数 {count} 不符"),
+    ("tensor.reshape", "无法把形状为 {from} 的张量变形为 {to}"),
+    ("tensor.no_axis", "{rank} 维张量没有第 {axis} 个轴"),
+    ("tensor.scalar", "标量"),
+    ("tensor.ragged", "张量第 {level} 层长度不一致：应为 {expected}，实际为 {actual}"),
+    ("tensor.mixed_level", "张量第 {level} 层混有数组与标量"),
+    ("tensor.positive_integers", "张量的维度和轴应为正整数，实际为 {value}"),
+    ("tensor.too_many_elements", "形状 {shape} 的元素过多"),
+    ("tensor.single_axis", "tensor_sum 只能沿一个轴求和"),
+    ("tensor.elementwise_shapes", "逐元素运算要求形状相同：{left} 与 {right}"),
+
+    // 化简
+    ("simplify.complex_power", "复杂幂运算"),
+    ("simplify.non_constant", "非常量表达式"),
+    ("simplify.complex_log", "复数对数计算"),
+    ("simplify.unevaluable_log", "无法计算此对数表达式"),
+    ("simplify.complex_sin", "复数正弦计算"),
+    ("simplify.unevaluable_sin", "无法计算此正弦表达式"),
+    ("simplify.complex_cos", "复数余弦计算"),
+    ("simplify.unevaluable_cos", "无法计算此余弦表达式"),
+    ("simplify.complex_tan", "复数正切计算"),
+    ("simplify.unevaluable_tan", "无法计算此正切表达式"),
+    ("simplify.complex_exp", "复数指数计算"),
+    ("simplify.unevaluable_exp", "无法计算此指数表达式"),
+    ("simplify.unevaluable_abs", "无法计算此绝对值表达式"),
+    ("simplify.unevaluable_factorial", "无法计算此阶乘表达式"),
+    ("simplify.non_numeric_root", "非数值的 n 次根"),
+    ("simplify.negative_odd_root", "负数的奇次根"),
+    ("simplify.imperfect_root", "非完全 n 次幂的根"),
+    ("simplify.constant_folding", "常量折叠中的 {operation} 运算"),
+    ("simplify.root_index", "root 的根指数必须是正整数"),
+    ("simplify.unknown_function", "未知函数: {name}"),
+    ("simplify.undefined_at_zero", "{name} 在 0 处无定义"),
+    ("simplify.no_finite_value", "{name}({argument}) 没有有限的实数值"),
+    ("simplify.unevaluable_function", "无法计算此 {name} 表达式"),
+    ("simplify.log_requires_positive", "对数的参数必须为正数"),
+    ("simplify.negative_sqrt", "平方根的参数不能为负数"),
+    ("simplify.negative_even_root", "偶次根的参数不能为负数"),
+    ("simplify.unroundable_float", "无法取整的浮点数"),
+    ("simplify.requires_rational", "对非有理数求 {name}"),
+    ("simplify.requires_integer", "{name} 函数只支持整数参数"),
+    ("simplify.negative_factorial", "阶乘的参数不能为负数"),
+    ("simplify.factorial_too_large", "阶乘的参数过大"),
+    ("simplify.factorial_requires_integer", "阶乘的参数必须为整数"),
+    ("trace.root", "根"),
+
+    // 逐步解方程
+    ("equation.add", "两边加上 {term}"),
+    ("equation.subtract", "两边减去 {term}"),
+    ("equation.multiply_by_zero", "两边乘以 0 后方程恒成立，会丢失全部信息"),
+    ("equation.multiply_may_be_zero", "{factor} 可能为 0，两边同乘可能引入增根"),
+    ("equation.multiply", "两边乘以 {factor}"),
+    ("equation.divide_by_zero", "不能在两边除以 0"),
+    ("equation.divide_may_be_zero", "{divisor} 可能为 0，两边同除需要 {divisor} != 0，否则可能丢失解"),
+    ("equation.divide", "两边除以 {divisor}"),
+    ("equation.substitute", "代入 {variable} = {value}"),
+    ("equation.missing_variable", "方程中不含变量 {variable}"),
+    ("equation.swap", "交换两边"),
+    ("equation.cannot_invert", "无法对 {expression} 做逆运算以隔离 {variable}，可以尝试 solve"),
+    ("equation.no_solution_when_zero", "{value} = 0 时方程无解"),
+    ("equation.reciprocal", "两边取倒数后乘以 {factor}"),
+    ("equation.negate", "两边乘以 -1"),
+    ("equation.square_root", "两边开平方"),
+    ("equation.root", "两边开 {exponent} 次方"),
+    ("equation.even_root", "偶次方根只取了正根，{solution} 也是解"),
+    ("equation.log_requires_positive", "取对数需要 {value} > 0"),
+    ("equation.logarithm", "两边取对数"),
+    ("equation.apply_inverse", "两边取 {function}"),
+    ("equation.principal_value", "{function} 只给出主值，其余解相差整数个周期"),
+    ("equation.square", "两边平方"),
+    ("equation.square_extraneous", "平方可能引入增根，需要 {value} >= 0"),
+    ("equation.natural_log", "两边取自然对数"),
+    ("equation.exponentiate", "两边取指数"),
+    ("equation.also_requires_positive", "还需要 {value} > 0"),
+    ("equation.repeated_variable", "{variable} 在方程中出现了 {count} 次，无法逐步隔离"),
+    ("equation.solver_failed", "{hint}，solve 也无法求解：{error}"),
+    ("equation.solved_by_solver", "{hint}，由 solve 求得"),
+    ("equation.solver_no_solution", "{hint}，且方程无解"),
+    ("equation.solver_many_solutions", "{hint}，solve 给出多个解：{solutions}"),
+    ("equation.flip_inequality", "{description}，不等号反向"),
+    ("equation.unknown_sign", "{factor} 的符号未知，这里按正数处理；为负时不等号需要反向"),
+    ("equation.not_a_relation", "{expression} 不是方程或不等式"),
+
+    // 求和
+    ("summation.convergent", "收敛"),
+    ("summation.divergent", "发散"),
+    ("summation.unknown", "未知"),
+    ("summation.undefined_term", "求和项在 {variable} = {value} 处无定义"),
+    ("summation.geometric_conditional", "无法求值：公比 {ratio} 含有其他变量，当 |{ratio}| < 1 时和为 {value}，否则发散"),
+    ("summation.geometric_divergent", "级数发散：公比 {ratio} ≥ 1"),
+    ("summation.geometric_oscillating", "级数发散：公比 {ratio} ≤ -1，部分和振荡"),
+    ("summation.lower_bound_integer", "无法求值：求和下限必须是整数"),
+    ("summation.too_many_terms", "无法求值：项数过多"),
+    ("summation.upper_bound_integer", "无法求值：求和上限必须是整数或正无穷"),
+    ("summation.nonzero_constant_term", "级数发散：通项为非零常数"),
+    ("summation.constant_series", "无法求值：常数项级数只在常数为零时收敛"),
+    ("summation.diverges", "级数发散"),
+    ("summation.unknown_with_parameters", "无法求值：不是已知级数，且通项含有其他变量"),
+    ("summation.leibniz_convergent", "无法求值：交错级数收敛（莱布尼茨判别法），但没有已知的闭式"),
+    ("summation.term_not_vanishing", "级数发散：通项不趋于零"),
+    ("summation.p_series_convergent", "无法求值：级数收敛（p-级数判别法），但没有已知的闭式"),
+    ("summation.p_series_divergent", "级数发散（p-级数判别法）"),
+    ("summation.ratio_convergent", "无法求值：级数收敛（比值判别法），但没有已知的闭式"),
+    ("summation.ratio_divergent", "级数发散（比值判别法）"),
+    ("summation.unknown_convergence", "无法求值：无法判断级数的收敛性"),
+
+    // 微积分
+    ("calculus.complex_numeric", "复数的数值计算"),
+    ("calculus.imaginary_numeric", "虚数单位的数值计算"),
+    ("calculus.non_integer_factorial", "非整数的阶乘"),
+    ("calculus.product_integral", "一般乘法积分暂不支持"),
+    ("calculus.power_integral", "复杂幂函数积分暂不支持"),
+    ("calculus.sqrt_integral", "复合平方根函数积分暂不支持"),
+    ("calculus.numeric_binary", "数值计算二元运算 {operation}"),
+    ("calculus.numeric_unary", "数值计算一元运算 {operation}"),
+    ("calculus.numeric_expression", "数值计算 {expression} 类型"),
+    ("calculus.integrate_expression", "对 {expression} 类型积分"),
+    ("calculus.differentiate_expression", "对 {expression} 类型求导"),
+    ("calculus.differentiate_binary", "对二元运算 {operation} 求导"),
+    ("calculus.differentiate_unary", "对一元运算 {operation} 求导"),
+    ("calculus.integrate_binary", "对二元运算 {operation} 积分"),
+    ("calculus.integrate_unary", "对一元运算 {operation} 积分"),
+    ("calculus.numeric_function", "数值计算函数 {name}"),
+    ("calculus.multi_argument_integral", "函数 {name} 的多参数积分暂不支持"),
+    ("calculus.integrate_function", "对函数 {name} 积分"),
+    ("calculus.integrate_composite", "对复杂参数函数 {name} 积分"),
+    ("calculus.multi_argument_derivative", "函数 {name} 的多参数求导暂不支持"),
+    ("calculus.differentiate_function", "对函数 {name} 求导"),
+    ("calculus.limit", "计算极限 lim({variable} -> {point}) {expression}"),
+    ("calculus.series", "级数展开 {expression} 在 {variable} = {point} 处"),
+
+    // 矩阵
+    ("matrix.shape_mismatch", "矩阵维度不匹配：{left_rows}×{left_cols} 和 {right_rows}×{right_cols}"),
+    ("matrix.multiply_mismatch", "矩阵乘法维度不匹配：{left_rows}×{left_cols} 和 {right_rows}×{right_cols}"),
+    ("matrix.determinant_square", "行列式只能计算方阵，当前矩阵为 {rows}×{cols}"),
+    ("matrix.empty_determinant", "空矩阵无法计算行列式"),
+    ("matrix.index_out_of_range", "行或列索引超出范围"),
+    ("matrix.inverse_square", "只有方阵才能求逆，当前矩阵为 {rows}×{cols}"),
+    ("matrix.singular", "矩阵不可逆（行列式为零）"),
+    ("matrix.vector_mismatch", "向量维度不匹配：{left} 和 {right}"),
+    ("matrix.cross_requires_3d", "叉积只适用于3维向量"),
+    ("matrix.trace_square", "矩阵的迹只能计算方阵，当前矩阵为 {rows}×{cols}"),
+    ("matrix.not_a_matrix", "表达式不是矩阵类型"),
+    ("matrix.not_a_vector_shape", "矩阵不能转换为向量（必须是单行或单列矩阵）"),
+    ("matrix.not_a_vector", "表达式不是向量类型"),
+    ("matrix.subscript_out_of_bounds", "下标 [{indices}] 越界：{dimensions}"),
+    ("matrix.tensor_subscript_count", "{rank} 维张量需要 {rank} 个下标，实际给出 {count} 个"),
+    ("matrix.tensor_shape", "张量形状为 {shape}"),
+    ("matrix.vector_subscript_count", "向量需要 1 个下标，实际给出 {count} 个"),
+    ("matrix.vector_length", "向量长度为 {len}"),
+    ("matrix.matrix_subscript_count", "矩阵需要 2 个下标，实际给出 {count} 个"),
+    ("matrix.matrix_shape", "矩阵为 {rows}×{cols}"),
+    ("matrix.subscript_not_positive", "下标应为正整数（从 1 开始），实际为 {index}"),
+
+    // 数论与统计
+    ("number_theory.gcd_arguments", "gcd 函数只支持整数和有理数，请确保参数是整数或有理数"),
+    ("number_theory.lcm_arguments", "lcm 函数只支持整数，请确保参数是整数"),
+    ("number_theory.is_prime_argument", "is_prime 函数只支持整数，请确保参数是正整数"),
+    ("number_theory.factorization_domain", "质因数分解要求输入大于1的正整数"),
+    ("number_theory.prime_factors_argument", "prime_factors 函数只支持整数，请确保参数是大于1的正整数"),
+    ("number_theory.binomial_arguments", "binomial 函数只支持非负整数，请确保参数是非负整数且 k <= n"),
+    ("number_theory.permutation_arguments", "permutation 函数只支持非负整数，请确保参数是非负整数且 k <= n"),
+    ("number_theory.empty_mean", "无法计算空列表的平均值，请提供至少一个数值"),
+    ("number_theory.mean_arguments", "mean 函数只支持数值，请确保所有参数都是数值"),
+    ("number_theory.variance_count", "计算方差需要至少两个数值"),
+    ("number_theory.variance_arguments", "variance 函数只支持数值，请确保所有参数都是数值"),
+    ("number_theory.unsupported_combination", "不支持的数值类型组合"),
+    ("number_theory.negative_sqrt", "负数没有实数平方根，请使用复数运算"),
+    ("number_theory.unsupported_type", "不支持的数值类型"),
+
+    // 计算引擎
+    ("engine.enhancer_lock", "无法获取运行时增强器锁"),
+    ("engine.circular_reference", "变量 '{name}' 存在循环引用"),
+    ("engine.simplifier_lock", "无法获取简化器锁"),
+    ("engine.unsupported_binary", "不支持的二元运算: {operation}"),
+    ("engine.unsupported_unary", "不支持的一元运算: {operation}"),
+    ("engine.matrix_not_scalar", "矩阵无法求值为单个数值"),
+    ("engine.vector_not_scalar", "向量无法求值为单个数值"),
+    ("engine.unevaluable", "无法求值表达式: {expression}"),
+    ("engine.not_polynomial", "{operation}要求关于 {variable} 的多项式，但检测到 {found}"),
+    ("engine.internal", "内部错误: {message}"),
+    ("engine.enhanced_simplifier_lock", "无法获取增强化简器锁"),
+    ("engine.auto_simplify_lock", "无法获取自动化简开关锁"),
+    ("engine.cache_manager_lock", "无法获取缓存管理器锁"),
+
+    // 定义域与值域
+    ("domain.constraint_critical_points", "无法求解约束 {constraint} 的临界点"),
+    ("domain.convex_hull", "定义域包含多个区间，结果为各段值域的凸包"),
+    ("domain.discontinuous", "表达式包含取整函数，按连续函数估计"),
+    ("domain.derivative_roots", "无法求解导数的零点，驻点未计入"),
+    ("domain.derivative", "无法对表达式求导，驻点未计入"),
+    ("domain.no_values", "无法计算函数值"),
+    ("domain.unsupported_operation", "不支持分析运算 {operation} 的定义域"),
+    ("domain.singularities", "{name} 的奇点未排除"),
+    ("domain.unknown_function", "未知函数 {name} 的定义域未分析"),
+    ("domain.non_scalar", "非标量表达式的定义域分析"),
+    ("domain.invalid_domain", "定义域必须是区间、点集或它们的并集"),
+
+    // 概率分布
+    ("distribution.normal_sigma", "正态分布的标准差必须为正，得到 {value}"),
+    ("distribution.binomial_trials", "二项分布的试验次数必须是非负整数，得到 {value}"),
+    ("distribution.binomial_probability", "二项分布的概率必须在 [0, 1] 内，得到 {value}"),
+    ("distribution.poisson_rate", "泊松分布的参数必须为正，得到 {value}"),
+    ("distribution.argument_count", "分布 {name} 的参数个数不正确"),
+    ("distribution.normal_expectation", "正态分布的期望只支持关于 {variable} 的多项式，得到 {found}"),
+    ("distribution.binomial", "二项分布"),
+    ("distribution.poisson", "泊松分布"),
+    ("distribution.expectation_sum", "{distribution}的期望无法求和: {error}"),
+    ("distribution.function_argument_count", "{name} 的参数个数不正确"),
+
+    // 单位
+    ("units.not_a_unit", "{unit} 不是单位"),
+    ("units.nested_relation", "比较只能出现在最外层"),
+    ("units.unsupported_operation", "单位分析不支持{operation}"),
+    ("units.unsupported_expression", "单位分析不支持 {expression}"),
+    ("units.inconsistent", "量纲不一致：{left}（{left_dimension}）与 {right}（{right_dimension}）不能用 {operator} 连接"),
+    ("units.exponent", "指数"),
+    ("units.constant_exponent", "带单位的底数 {units} 只能取常数次幂，得到 {exponent}"),
+    ("units.fractional_power", "{units} 的 {exponent} 次幂不是整数次幂的单位"),
+    ("units.requires_dimensionless", "{context} 的参数必须无量纲，得到单位 {units}"),
+
+    // 最值
+    ("optimization.unbounded_below", "无下界"),
+    ("optimization.unbounded_above", "无上界"),
+    ("optimization.minimum", "最小值"),
+    ("optimization.maximum", "最大值"),
+    ("optimization.attained", "无法求出 {expression} 取到{goal}的点"),
+    ("optimization.not_attained", "{expression} 取不到{goal}，确界 {bound} 不能达到"),
+    ("optimization.range", "无法估计 {expression} 的值域"),
+    ("optimization.infeasible", "约束 {constraint} = 0 没有实数解"),
+    ("optimization.unbounded", "{expression} 在约束 {constraint} = 0 上{bound}"),
+    ("optimization.constrained_not_attained", "{expression} 在约束 {constraint} = 0 上取不到{goal}"),
+
+    // 二次型与圆锥曲线
+    ("conic.requires_variable", "{operation}需要至少一个变量"),
+    ("conic.degree", "{operation}要求关于 {variables} 的次数不超过 2"),
+    ("conic.quadratic_form", "提取二次型矩阵"),
+    ("conic.complete_square", "配方"),
+    ("conic.cross_term", "含交叉项时只支持二元、二次项系数为有理数的配方"),
+    ("conic.variable_count", "圆锥曲线需要两个变量，给出了 {count} 个"),
+    ("conic.classify", "圆锥曲线分类"),
+    ("conic.not_quadratic", "{expression} 不含 {variables} 的二次项，不是圆锥曲线"),
+    ("conic.unclassified", "无法判别 {expression} 的类型"),
+
+    // 多项式
+    ("polynomial.division_degree", "多项式除法：被除数的变量次数小于除数"),
+    ("polynomial.positive_power", "多项式只支持正整数幂"),
+    ("polynomial.unsupported_operation", "多项式不支持 {operation} 运算"),
+    ("polynomial.not_convertible", "表达式无法转换为多项式"),
+    ("polynomial.degree_too_high", "{expression} 的次数过高，无法展开"),
+    ("polynomial.coefficients", "提取系数"),
+    ("orthogonal.max_degree", "正交多项式的次数不能超过 {limit}，得到 {degree}"),
+    ("orthogonal.degree", "{name} 的次数必须是非负整数，得到 {degree}"),
+    ("resultant.discriminant_degree", "判别式要求关于 {variable} 至少一次的多项式"),
+    ("resultant.sylvester_order", "结式的 Sylvester 矩阵为 {order} 阶，超过了上限 {limit}"),
+
+    // 延迟求值
+    ("lazy.cycle_or_recomputation", "循环依赖或重复计算"),
+    ("lazy.self_reference", "{name} 的定义引用了自身"),
+    ("lazy.missing_expression", "表达式 {id} 不存在"),
+    ("lazy.dependency_cycle", "添加依赖会形成循环"),
+    ("lazy.cycle_detected", "检测到循环依赖"),
+    ("lazy.unresolved", "检测到循环依赖或无法解析的依赖关系"),
+
+    // Gröbner 基
+    ("groebner.too_many_reductions", "Gröbner 基的计算超过了 {limit} 次约化"),
+    ("groebner.basis_too_large", "Gröbner 基超过了 {limit} 个多项式"),
+    ("groebner.not_polynomial", "{expression} 不是多项式"),
+    ("groebner.rational_coefficients", "Gröbner 基只支持有理系数，{expression} 的系数 {coefficient} 不是有理数"),
+    ("groebner.extra_variable", "{expression} 含有变量列表之外的变量 {variable}"),
+    ("groebner.infinite_solutions", "方程组的解有无穷多个，{variable} 可以任意取值"),
+
+    // 向量微积分
+    ("vector_calculus.jacobian_arguments", "雅可比矩阵需要至少一个函数和一个变量"),
+    ("vector_calculus.curl_dimension", "旋度只对二维或三维向量场有定义，得到 {dimension} 维"),
+    ("vector_calculus.laplacian_variables", "拉普拉斯算子需要至少一个变量"),
+    ("vector_calculus.field_mismatch", "向量场有 {components} 个分量，变量有 {variables} 个"),
+
+    // 变量与自定义函数
+    ("runtime.argument_count", "函数 '{name}' 需要 {expected} 个参数，传入了 {actual} 个"),
+    ("runtime.invalid_variable", "无效的变量名: {name}"),
+    ("runtime.invalid_function", "无效的函数名或参数名: {name}"),
+    ("runtime.duplicate_parameter", "函数 '{name}' 的参数 '{parameter}' 重复"),
+    ("runtime.call_depth", "函数调用嵌套超过 {limit} 层，可能存在递归定义"),
+
+    // 恒等检验
+    ("equivalence.unevaluable_samples", "等式两边在采样点上无法求值，无法判断是否恒等"),
+    ("equivalence.free_variable", "变量 {variable} 不在检验变量 {variables} 中"),
+    ("equivalence.unevaluable_points", "等式两边在检验点上无法求值，无法判断是否恒等"),
+    ("equivalence.counterexample", "：在 {point} 处导数为 {derivative}，被积函数为 {integrand}"),
+    ("equivalence.integral_mismatch", "积分结果 {integral} 求导后与被积函数 {integrand} 不一致{counterexample}"),
+
+    // 缓存
+    ("cache.malformed_entry", "符号缓存条目格式不正确"),
+    ("cache.malformed_variable", "符号缓存条目的变量格式不正确"),
+    ("cache.cost_out_of_range", "符号缓存条目的计算成本超出范围"),
+    ("cache.malformed_cost", "符号缓存条目的计算成本格式不正确"),
+
+    // 三角变换
+    ("trigonometry.max_multiple", "倍角展开最多支持 {limit} 倍"),
+    ("trigonometry.mismatch", "三角变换的结果 {transformed} 与原式 {original} 在采样点上不相等"),
+
+    // 部分分式
+    ("partial_fractions.unsolvable", "部分分式的系数方程组无解"),
+    ("partial_fractions.operation", "部分分式分解"),
+    ("partial_fractions.rational_coefficients", "部分分式分解只支持关于 {variable} 的有理系数有理函数"),
+    ("partial_fractions.irreducible_factor", "分母含有三次及以上的不可约因子，暂不支持部分分式分解"),
+
+    // 并行计算
+    ("parallel.thread_pool", "创建线程池失败: {error}"),
+    ("parallel.timeout", "任务执行超时"),
+    ("parallel.unsupported_operator", "常量运算不支持操作符: {operation}"),
+
+    // 假设
+    ("assumptions.unrecognized", "无法识别的假设: {assumption}"),
+    ("assumptions.contradiction", "假设 {variable} {property} 与已有假设矛盾"),
+
+    // 解方程
+    ("solver.identity", "方程对任意 {variable} 都成立"),
+    ("solver.univariate", "solve 目前只支持关于 {variable} 的单变量多项式方程"),
+    ("solver.rational_coefficients", "solve 目前只支持有理系数的多项式方程"),
+
+    // 积分变换
+    ("laplace.transform_variable", "原函数中不能出现像函数的变量 {variable}"),
+    ("laplace.original_variable", "像函数中不能出现原函数的变量 {variable}"),
+
+    // 列表
+    ("lists.zero_step", "range 的步长不能为 0"),
+    ("lists.too_long", "列表最多含有 {limit} 个元素"),
+    ("lists.incomparable", "sort 无法比较元素 {element}，只能排序实数"),
+
+    // 逻辑
+    ("logic.not_boolean", "无法作为布尔表达式求值: {expression}"),
+    ("logic.truth_table_size", "真值表有 2^{count} 行，变量数不能超过 {limit}"),
+
+    // 同余
+    ("modular.modulus", "模数应为正整数，实际为 {modulus}"),
+    ("modular.linear", "同余式应为关于 {variable} 的整系数线性式"),
+
+    // 性能统计
+    ("performance.report", "性能统计报告:\n================\n总计算次数: {total}\n成功计算次数: {successful}\n失败计算次数: {failed}\n成功率: {success_rate}%\n\n时间统计:\n--------\n总计算时间: {total_time}\n平均计算时间: {avg_time}\n最快计算时间: {fastest}\n最慢计算时间: {slowest}\n计算吞吐量: {throughput} 计算/秒\n\n缓存统计:\n--------\n缓存命中次数: {cache_hits}\n缓存未命中次数: {cache_misses}\n缓存命中率: {cache_hit_rate}%\n\n并行计算:\n--------\n并行计算次数: {parallel}\n并行计算比例: {parallel_ratio}%\n\n内存统计:\n--------\n当前内存使用: {memory} 字节\n内存峰值: {peak_memory} 字节\n超限中止次数: {limit_hits}\n垃圾回收次数: {gc_count}\n\n精确计算:\n--------\n精确计算比例: {exact_ratio}%\n\n化简:\n--------\n未收敛次数: {non_converged}\n"),
+    ("performance.operations_title", "\n分操作统计:\n--------\n"),
+    ("performance.operation", "操作"),
+    ("performance.calls", "调用次数"),
+    ("performance.avg_time", "平均耗时"),
+    ("performance.success_rate", "成功率"),
+    ("performance.peak_memory", "内存峰值"),
+
+    // C 接口
+    ("ffi.null_expression", "表达式数据为空指针"),
+    ("ffi.null_input", "输入为空指针"),
+    ("ffi.invalid_utf8", "输入不是合法的 UTF-8"),
+    ("ffi.null_variable", "变量名为空指针"),
+
+    // 代码生成
+    ("codegen.unsupported", "代码生成不支持{what}"),
+    ("codegen.operation", "运算 {op}"),
+    ("codegen.function", "函数 {name}"),
+    ("codegen.root_index", "根指数不是正整数的 root"),
+    ("codegen.nested", "集合、区间或嵌套的向量与矩阵"),
+    ("codegen.complex", "复数"),
+    ("codegen.imaginary_unit", "虚数单位"),
+    ("codegen.large_exponent", "过大的整数指数"),
+
+    // 分析与练习
+    ("extremum.maximum", "极大值"),
+    ("extremum.minimum", "极小值"),
+    ("extremum.saddle", "鞍点"),
+    ("extremum.undetermined", "不确定"),
+    ("exercise.differentiation", "求导"),
+    ("exercise.integration", "不定积分"),
+    ("exercise.simplification", "化简"),
+    ("exercise.factorization", "因式分解"),
+    ("exercise.equation", "解方程"),
+    ("exercise.difficulty", "难度应在 1 到 {max} 之间"),
+
+    // 命令行
+    ("cli.terminal_init_failed", "警告: 终端初始化失败: {error}"),
+    ("cli.colors_may_not_work", "颜色输出可能无法正常工作"),
+    ("cli.terminal.stdout_ansi", "警告: 无法为标准输出启用 ANSI 颜色支持"),
+    ("cli.terminal.stderr_ansi", "警告: 无法为标准错误输出启用 ANSI 颜色支持"),
+    ("cli.banner", "Yufmath v{version} - 计算机代数系统"),
+    ("cli.verbose_enabled", "详细模式已启用"),
+    ("cli.init_failed", "错误：无法初始化 Yufmath: {error}"),
+    ("cli.error", "错误: {error}"),
+    ("cli.done", "计算完成"),
+    ("cli.invalid_lang", "不支持的语言 '{lang}'（可用: zh, en）"),
+    ("cli.progress.running", "正在{operation}..."),
+    ("cli.progress.compute", "计算表达式"),
+    ("cli.progress.batch", "正在处理批处理文件"),
+    ("cli.verbose.compute", "正在计算表达式: {expression}"),
+    ("cli.verbose.simplify", "正在简化表达式: {expression}"),
+    ("cli.verbose.diff", "正在对表达式 {expression} 关于变量 {variable} 求导"),
+    ("cli.verbose.integrate", "正在对表达式 {expression} 关于变量 {variable} 积分"),
+    ("cli.verbose.solve", "正在求解方程 {equation} 关于变量 {variable}"),
+    ("cli.verbose.factor", "正在对表达式 {expression} 进行因式分解"),
+    ("cli.verbose.expand", "正在展开表达式 {expression}"),
+    ("cli.verbose.trig_expand", "正在展开 {expression} 的和角与倍角"),
+    ("cli.verbose.trig_factor", "正在对 {expression} 和差化积"),
+    ("cli.verbose.limit", "正在计算表达式 {expression} 当 {variable} 趋向 {point} 时的极限"),
+    ("cli.verbose.series", "正在对表达式 {expression} 在 {variable} = {point} 处进行 {order} 阶级数展开"),
+    ("cli.verbose.analyze", "正在分析表达式 {expression} 关于变量 {variable}"),
+    ("cli.verbose.laplace", "正在计算 {expression} 关于 {variable} 的 Laplace 变换"),
+    ("cli.verbose.inverse_laplace", "正在计算 {expression} 关于 {variable} 的 Laplace 逆变换"),
+    ("cli.verbose.plot_data", "正在对 {expression} 在 {variable} ∈ [{from}, {to}] 上采样 {n} 个点"),
+    ("cli.verbose.verify", "正在校验等式 {equation}"),
+    ("cli.verbose.prove", "正在证明 {lhs} = {rhs}"),
+    ("cli.verbose.quiz", "正在生成 {count} 道{kind}题"),
+    ("cli.verbose.batch", "正在处理批处理文件: {file}"),
+    ("cli.verbose.output_file", "输出文件: {file}"),
+    ("cli.verbose.batch_line", "处理第 {line} 行: {input}"),
+    ("cli.verbose.interactive", "启动交互模式..."),
+    ("cli.factor_unimplemented", "因式分解功能暂未实现，表达式: {expression}"),
+    ("cli.expand_unimplemented", "展开功能暂未实现，表达式: {expression}"),
+    ("cli.solve.no_solution", "无解"),
+    ("cli.solve.multiplicity", "重数 {multiplicity}"),
+    ("cli.solve.numeric", "数值解"),
+    ("cli.solve.candidate", "候选，未通过验证"),
+    ("cli.solve.note_separator", "，"),
+    ("cli.solve.solution", "解 {index}: {variable} = {root} ({notes})"),
+    ("cli.stats.memory", "内存（估算）: 当前 {current} 字节，峰值 {peak} 字节"),
+    ("cli.stats.limit_hits", "超过内存上限中止: {count} 次"),
+    ("cli.batch.read_failed", "无法读取输入文件 '{file}': {error}"),
+    ("cli.batch.progress_line", "处理第 {line} 行"),
+    ("cli.batch.line_error", "第 {line} 行错误: {input} -> {error}"),
+    ("cli.batch.done", "批处理完成，处理了 {count} 行"),
+    ("cli.batch.create_failed", "无法创建输出文件 '{file}': {error}"),
+    ("cli.batch.write_failed", "写入输出文件失败: {error}"),
+    ("cli.batch.written", "结果已写入文件: {file}"),
+    ("cli.batch.input", "输入 {line}: {input}"),
+    ("cli.batch.output", "输出 {line}: {output}"),
+    ("cli.batch.error", "错误 {line}: {error}"),
+    ("cli.batch.saved", "结果已保存到: {file}"),
+    ("cli.batch.tsv_header", "输入\t结果\t耗时(ms)"),
+    ("cli.watch.watching", "正在监视 '{file}'，每 {interval} 毫秒检查一次，按 Ctrl+C 退出"),
+    ("cli.watch.waiting", "文件 '{file}' 不存在，等待创建..."),
+    ("cli.watch.run", "{file} — 第 {count} 次计算，{changed} 行有变化"),
+    ("cli.watch.line_error", "{input} -> 错误: {error}"),
+    ("cli.watch.stopped", "已停止监视"),
+    ("cli.notepad.loading", "正在加载笔记本: {file}"),
+    ("cli.notepad.default_title", "新笔记本"),
+    ("cli.notepad.creating", "正在创建新笔记本: {title} ({file})"),
+    ("cli.notepad.temporary_title", "临时笔记本"),
+    ("cli.notepad.creating_temporary", "正在创建临时笔记本: {title}"),
+    ("cli.notepad.starting_gui", "正在启动图形界面..."),
+    ("cli.notepad.no_display", "警告：未检测到图形环境，将使用终端界面\n提示：\n  - 确保在图形桌面环境中运行\n  - 如果使用 SSH，请使用 -X 或 -Y 参数启用 X11 转发\n  - 或者使用 --terminal 参数强制使用终端界面"),
+    ("cli.notepad.gui_closed", "图形界面已关闭"),
+    ("cli.notepad.gui_error", "图形界面运行错误: {error}"),
+    ("cli.notepad.gui_setup_failed", "图形界面设置失败: {error}"),
+    ("cli.plot.write_failed", "无法写入文件 '{file}': {error}"),
+    ("cli.plot.written", "已写入 {count} 个数据点到 {file}"),
+    ("cli.plot.invalid_y_range", "y 轴范围无效：下限 {min} 必须小于上限 {max}"),
+    ("cli.plot.usage", "用法：plot(表达式, ..., 起点, 终点)"),
+    ("cli.verify.missing_equals", "等式缺少等号: {equation}"),
+    ("cli.verify.numeric", "未能通过化简证明，已在采样点上数值验证"),
+    ("cli.verify.no_variables", "无变量"),
+    ("cli.prove.verified", "成立：两边之差化简为 0"),
+    ("cli.prove.supported", "数值上成立：化简未能证明，但 {count} 个定义域内的检验点上两边相等"),
+    ("cli.prove.refuted", "不成立"),
+    ("cli.verify.counterexample", "反例: {point}（左边 = {lhs}，右边 = {rhs}）"),
+    ("cli.quiz.title", "{kind}练习（难度 {difficulty}，种子 {seed}）"),
+    ("cli.quiz.problems", "题目"),
+    ("cli.quiz.answers", "答案"),
+    ("cli.laplace.region", "收敛域: {region}"),
+    ("cli.laplace.region_unknown", "无法确定"),
+    ("cli.laplace.partial", "部分项不在变换表内，保留为符号形式"),
+    ("cli.analysis.none", "无"),
+    ("cli.analysis.function", "函数: {function}"),
+    ("cli.analysis.domain", "定义域: {domain}"),
+    ("cli.analysis.partial", " (部分分析)"),
+    ("cli.analysis.critical", "临界点: {points}"),
+    ("cli.analysis.critical_failed", "临界点: 无法分析（{error}）"),
+    ("cli.analysis.inflection", "拐点: {points}"),
+    ("cli.analysis.inflection_failed", "拐点: 无法分析（{error}）"),
+    ("cli.usage", "Yufmath v{version} - 计算机代数系统\n\n用法:\n  yufmath [选项] <命令> [参数...]\n\n命令:\n  compute <表达式>              计算表达式的值\n  simplify <表达式>             简化表达式\n  diff <表达式> [变量...]       对表达式求导\n  integrate <表达式> [变量]     对表达式积分\n  solve <方程> <变量>           求解方程\n  factor <表达式>               因式分解\n  expand <表达式>               展开表达式\n  limit <表达式> <变量> <点>    计算极限\n  series <表达式> <变量> <点>   级数展开\n  batch -i <文件> [-o <文件>]   批处理模式\n  watch <文件> [-o <文件>]      监视文件，保存后自动重新计算\n  interactive                   交互模式\n  notepad [文件] [-t <标题>]    笔记本模式\n\n选项:\n  -f, --format <格式>           输出格式 [standard, latex, mathml, maxima]\n  -p, --precision <精度>        数值精度\n  -v, --verbose                 详细输出\n  -q, --quiet                   静默模式\n      --progress                显示进度条\n      --no-progress             禁用进度条\n      --timeout <秒>            计算超时时间\n      --config <文件>           从 TOML/JSON 文件加载配置\n      --lang <语言>             界面语言 [zh, en]\n  -h, --help                    显示帮助信息\n      --version                 显示版本信息\n\n示例:\n  yufmath compute \"2 + 3 * 4\"\n  yufmath simplify \"x^2 + 2*x + 1\"\n  yufmath diff \"x^3 + 2*x^2 + x\" x\n  yufmath integrate \"2*x + 1\" x\n  yufmath --format latex compute \"x^2 + 1\"\n  yufmath batch -i input.txt -o output.txt\n  yufmath --format latex watch input.txt -o out.tex\n  yufmath interactive\n  yufmath notepad\n  yufmath notepad my_notebook.ynb\n  yufmath notepad -t \"我的数学笔记\""),
+
+    // 交互模式
+    ("interactive.banner", "计算机代数系统"),
+    ("interactive.banner_hint", "输入 {help} 查看帮助，输入 {quit} 退出"),
+    ("interactive.no_color", "注意: 终端不支持颜色输出或颜色已被禁用"),
+    ("interactive.goodbye", "再见！"),
+    ("interactive.error", "错误:"),
+    ("interactive.on", "开启"),
+    ("interactive.off", "关闭"),
+    ("interactive.verbose", "详细模式: {status}"),
+    ("interactive.colors", "颜色输出: {status}"),
+    ("interactive.approximations", "数值近似值: {status}"),
+    ("interactive.timing", "计时: {status}"),
+    ("interactive.timing_usage", "用法: timing on|off"),
+    ("interactive.enhanced", "增强化简功能: {status}"),
+    ("interactive.clear_failed", "警告: 清空系统变量时出错: {error}"),
+    ("interactive.cleared", "变量已清空"),
+    ("interactive.no_variables", "没有定义变量"),
+    ("interactive.variables", "当前变量:"),
+    ("interactive.local", "本地"),
+    ("interactive.setting_variable", "设置变量 {name} = {value}"),
+    ("interactive.set_variable_failed", "无法设置变量 '{name}': {error}"),
+    ("interactive.computing", "正在计算: {input}"),
+    ("interactive.invalid_format", "无效的格式类型。可用格式: standard, terminal, latex, mathml, maxima"),
+    ("interactive.format.standard", "标准格式"),
+    ("interactive.format.terminal", "终端彩色格式"),
+    ("interactive.format.latex", "LaTeX 格式"),
+    ("interactive.format.mathml", "MathML 格式"),
+    ("interactive.format.maxima", "Maxima 格式"),
+    ("interactive.format_set", "输出格式已设置为: {format}"),
+    ("interactive.precision_set", "数值精度已设置为: {precision}"),
+    ("interactive.approx_precision_set", "近似值精度已设置为: {precision}"),
+    ("interactive.invalid_precision", "无效的精度值，请输入正整数"),
+    ("interactive.config", "当前计算配置:"),
+    ("interactive.config_failed", "无法显示配置: {error}"),
+    ("interactive.invalid_number", "无效的数值: {value}"),
+    ("interactive.invalid_switch", "请使用 on 或 off: {value}"),
+    ("interactive.timeout_set", "计算超时已设置为: {seconds} 秒"),
+    ("interactive.timeout_cleared", "已取消计算超时"),
+    ("interactive.max_precision_set", "精确计算的最大位数已设置为: {digits}"),
+    ("interactive.max_precision_cleared", "已取消精确计算的位数限制"),
+    ("interactive.force_exact", "强制精确计算: {status}"),
+    ("interactive.seed_set", "随机种子已设置为: {seed}"),
+    ("interactive.seed_cleared", "已取消固定随机种子"),
+    ("interactive.unknown_option", "未知的配置项: {name}（可用: timeout, precision, force_exact, seed）"),
+    ("interactive.help.title", "Yufmath 交互式计算器帮助"),
+    ("interactive.help.basic", "基本命令"),
+    ("interactive.help.help", "显示此帮助信息"),
+    ("interactive.help.quit", "退出程序"),
+    ("interactive.help.clear", "清空所有变量"),
+    ("interactive.help.vars", "显示所有变量"),
+    ("interactive.help.verbose", "切换详细模式"),
+    ("interactive.help.colors", "切换颜色输出"),
+    ("interactive.help.approx", "切换数值近似值显示"),
+    ("interactive.help.enhanced", "切换增强化简功能"),
+    ("interactive.help.timing", "开启或关闭每条结果后的耗时显示"),
+    ("interactive.help.formatting", "格式化命令"),
+    ("interactive.help.format", "设置输出格式 (standard, terminal, latex, mathml, maxima)"),
+    ("interactive.help.precision", "设置数值精度"),
+    ("interactive.help.approx_precision", "设置近似值显示精度"),
+    ("interactive.help.configuration", "配置命令"),
+    ("interactive.help.show_config", "显示当前计算配置"),
+    ("interactive.help.set_timeout", "设置计算超时（秒），off 取消"),
+    ("interactive.help.set_precision", "设置精确计算的最大位数，off 取消"),
+    ("interactive.help.set_force_exact", "开关强制精确计算"),
+    ("interactive.help.set_seed", "设定随机种子"),
+    ("interactive.help.math", "数学运算"),
+    ("interactive.help.arithmetic", "基本算术运算"),
+    ("interactive.help.algebra", "代数表达式"),
+    ("interactive.help.trig", "三角函数和数学常量"),
+    ("interactive.help.diff", "求导 (暂未实现)"),
+    ("interactive.help.integrate", "积分 (暂未实现)"),
+    ("interactive.help.vector_calculus", "旋度，另有 div、jacobian、laplacian"),
+    ("interactive.help.plot", "在终端内画出函数图像，可列出多条曲线"),
+    ("interactive.help.assignment", "变量赋值"),
+    ("interactive.help.assign", "将值赋给变量"),
+    ("interactive.help.use_variable", "使用变量的表达式"),
+    ("interactive.help.statements", "分号分隔多条语句，;; 结尾的不回显"),
+    ("interactive.help.comments", "# 或 // 到行尾、/* ... */ 是注释"),
+    ("interactive.help.examples", "示例"),
+    ("interactive.help.multiline", "行尾输入 \\ 可续行，以空行结束输入。"),
+];
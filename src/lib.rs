@@ -169,6 +169,7 @@
 //! ```
 
 pub mod core;
+pub mod i18n;
 pub mod parser;
 pub mod engine;
 pub mod formatter;
@@ -177,9 +178,13 @@ pub mod cli;
 pub mod ffi;
 pub mod notebook;
 
+#[cfg(test)]
+mod test_utils;
+
 // 重新导出主要的公共接口
 pub use api::{
     Yufmath, 
+    DiffVariables,
     YufmathError, 
     ComputeConfig, 
     PrecisionConfig,
@@ -187,9 +192,14 @@ pub use api::{
     CacheConfig,
     MemoryConfig,
     ComputeProgress,
+    CancelToken,
     ComputePhase,
     PerformanceStats, 
+    OperationStats,
+    MemoryUsageStats,
     PerformanceMonitor,
+    PhaseTimings,
+    format_duration,
     ProgressCallback,
     AsyncComputation,
     BatchAsyncComputer,
@@ -199,17 +209,21 @@ pub use api::{
 pub use core::{
     Expression, Number, MathConstant, BinaryOperator, UnaryOperator,
     SharedExpression, CowExpression, MemoryManager, MemoryMonitor,
-    MemoryStats, ExpressionComparator, ExpressionBuilder, ExpressionFactory
+    MemoryStats, NodePoolStats, NodePoolScope, ExpressionComparator, ExpressionBuilder, ExpressionFactory,
+    IndeterminateForm, IndeterminateSemantics, CseOptions, Tensor
 };
 pub use engine::{
-    ComputeEngine, ComputeError,
+    ComputeEngine, ComputeError, Solution, SolutionKind, DomainAnalyzer, DomainAnalysis,
+    ExtremaAnalyzer, Extremum, ExtremumKind, SummationEngine, SeriesSum, Convergence,
+    PartialFractionEngine, LaplaceEngine, LaplaceTransform, TruthTable,
+    EquivalenceChecker, Equivalence, Counterexample, IdentityResult, VerifiedIntegral, ExerciseKind, TraceEvent, Congruence, Distribution, MonomialOrder, Conic, ConicKind, ConicCase, EquationStep, OrthogonalFamily, UnitSystem, Quantity, Dimension, SimplifyOptions, Assumptions, Property, Optimizer, Optimum,
     LazyExpression, DependencyGraph, LazyState, DependencyGraphStats,
     ParallelComputeEngine, TaskScheduler, ComputeTask, SchedulerStats, 
     ExpressionPreprocessor, ParallelizationAnalysis,
-    RuntimeEnhancedEngine, RuntimeEnhancer, VariableManager, ComplexityAnalyzer, RuntimeConfig
+    RuntimeEnhancedEngine, RuntimeEnhancer, VariableManager, UserFunction, ComplexityAnalyzer, RuntimeConfig
 };
-pub use parser::{Parser, ParseError};
-pub use formatter::{Formatter, FormatOptions, FormatType};
+pub use parser::{Parser, ParseError, Statement};
+pub use formatter::{Formatter, FormatOptions, FormatType, MultilineEnv, TermOrder, CodegenOptions, Language};
 pub use notebook::{
     NotebookCell, CellType, CellContent, CellMetadata, CellId,
     Notebook, NotebookManager, NotebookMetadata,
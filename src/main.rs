@@ -2,35 +2,38 @@
 //!
 //! 提供命令行接口来使用 Yufmath 计算机代数系统。
 
-use clap::Parser;
 use std::process;
+use std::time::{Duration, Instant};
 use yufmath::cli::args::{CliArgs, Commands, OutputFormat};
 use yufmath::cli::interactive;
 use yufmath::cli::progress::{create_compute_progress, create_batch_progress};
 use yufmath::cli::terminal::init_terminal;
-use yufmath::formatter::{FormatOptions, FormatType};
-use yufmath::Yufmath;
+use yufmath::cli::watch;
+use yufmath::formatter::{Formatter, FormatOptions, FormatType, FormatterFactory};
+use yufmath::formatter::terminal::strip_ansi;
+use yufmath::{Yufmath, ExerciseKind, format_duration, tr};
 
 fn main() {
     // 初始化终端以支持 ANSI 颜色输出（特别是在 Windows 上）
     if let Err(e) = init_terminal() {
-        eprintln!("警告: 终端初始化失败: {}", e);
-        eprintln!("颜色输出可能无法正常工作");
+        eprintln!("{}", tr!("cli.terminal_init_failed", error = e));
+        eprintln!("{}", tr!("cli.colors_may_not_work"));
     }
     
-    let args = CliArgs::parse();
+    let args = CliArgs::parse_localized();
+    let color = args.color.color_config();
     
     // 设置日志级别
     if args.verbose {
-        println!("Yufmath v{} - 计算机代数系统", yufmath::VERSION);
-        println!("详细模式已启用");
+        println!("{}", tr!("cli.banner", version = yufmath::VERSION));
+        println!("{}", tr!("cli.verbose_enabled"));
     }
     
     // 创建 Yufmath 实例
-    let mut yuf = match create_yufmath_instance(&args) {
+    let yuf = match create_yufmath_instance(&args) {
         Ok(instance) => instance,
         Err(e) => {
-            eprintln!("错误：无法初始化 Yufmath: {}", e);
+            eprintln!("{}", tr!("cli.init_failed", error = e));
             process::exit(1);
         }
     };
@@ -38,15 +41,28 @@ fn main() {
     // 设置格式化选项
     let format_options = FormatOptions {
         format_type: match args.format {
-            OutputFormat::Standard => FormatType::Standard,
+            // 输出到支持颜色的终端时用彩色的终端格式，文本与标准格式相同
+            OutputFormat::Standard if color.should_use_color() => FormatType::Terminal,
+            OutputFormat::Standard | OutputFormat::Tsv => FormatType::Standard,
             OutputFormat::Latex => FormatType::LaTeX,
             OutputFormat::Mathml => FormatType::MathML,
+            OutputFormat::Maxima => FormatType::Maxima,
         },
         precision: args.precision,
         use_parentheses: true,
+        sort_terms: args.term_order().is_some(),
+        term_order: args.term_order().unwrap_or_default(),
+        line_width: args.line_width,
+        multiline_env: args.multiline_env.multiline_env(),
     };
     yuf.set_format_options(format_options);
     
+    // compute 自己输出分阶段计时，交互式与持续运行的命令不计时
+    let started = (args.time && !matches!(
+        args.command,
+        None | Some(Commands::Compute { .. } | Commands::Interactive | Commands::Notepad { .. } | Commands::Watch { .. })
+    )).then(Instant::now);
+    
     // 执行命令
     let result = match &args.command {
         Some(Commands::Compute { expression }) => {
@@ -55,11 +71,11 @@ fn main() {
         Some(Commands::Simplify { expression }) => {
             handle_simplify(&yuf, expression, &args)
         }
-        Some(Commands::Diff { expression, variable }) => {
-            handle_diff(&yuf, expression, variable, &args)
+        Some(Commands::Diff { expression, variables }) => {
+            handle_diff(&yuf, expression, variables, &args)
         }
-        Some(Commands::Integrate { expression, variable }) => {
-            handle_integrate(&yuf, expression, variable, &args)
+        Some(Commands::Integrate { expression, variable, with_constant }) => {
+            handle_integrate(&yuf, expression, variable.as_deref(), *with_constant, &args)
         }
         Some(Commands::Solve { equation, variable }) => {
             handle_solve(&yuf, equation, variable, &args)
@@ -70,22 +86,55 @@ fn main() {
         Some(Commands::Expand { expression }) => {
             handle_expand(&yuf, expression, &args)
         }
+        Some(Commands::TrigExpand { expression }) => {
+            handle_trig_transform(&yuf, expression, false, &args)
+        }
+        Some(Commands::TrigFactor { expression }) => {
+            handle_trig_transform(&yuf, expression, true, &args)
+        }
         Some(Commands::Limit { expression, variable, point }) => {
             handle_limit(&yuf, expression, variable, point, &args)
         }
         Some(Commands::Series { expression, variable, point, order }) => {
             handle_series(&yuf, expression, variable, point, *order, &args)
         }
+        Some(Commands::Laplace { expression, variable, transform_variable, region }) => {
+            handle_laplace(&yuf, expression, variable, transform_variable, *region, &args)
+        }
+        Some(Commands::InverseLaplace { expression, variable, time_variable }) => {
+            handle_inverse_laplace(&yuf, expression, variable, time_variable, &args)
+        }
+        Some(Commands::Analyze { expression, variable }) => {
+            handle_analyze(&yuf, expression, variable, &args)
+        }
+        Some(Commands::Verify { equation }) => {
+            handle_verify(&yuf, equation, &args)
+        }
+        Some(Commands::Prove { lhs, rhs, vars }) => {
+            handle_prove(&yuf, lhs, rhs, vars, &args)
+        }
+        Some(Commands::PlotData { .. }) => {
+            handle_plot_data(&yuf, &args)
+        }
+        Some(Commands::PlotAscii { .. }) => {
+            handle_plot_ascii(&yuf, &args)
+        }
+        Some(Commands::Quiz { kind, count, difficulty, seed }) => {
+            handle_quiz(&yuf, kind.exercise(*difficulty), *count, *seed, &args)
+        }
         Some(Commands::Batch { input, output }) => {
             handle_batch(&yuf, input, output.as_deref(), &args)
         }
+        Some(Commands::Watch { input, output, interval }) => {
+            watch::run_watch(&yuf, input, output.as_deref(), Duration::from_millis(*interval), &args)
+        }
         Some(Commands::Interactive) => {
             handle_interactive(&args)
         }
         Some(Commands::Notepad { file, title, terminal }) => {
             // 使用 CLI 模块的命令处理
             if let Err(e) = yufmath::cli::run_command(args) {
-                eprintln!("错误: {}", e);
+                eprintln!("{}", tr!("cli.error", error = e));
                 process::exit(1);
             }
             return;
@@ -98,16 +147,33 @@ fn main() {
         }
     };
     
+    if let Some(started) = started {
+        eprintln!("{}", tr!("api.timing.total", total = format_duration(started.elapsed())));
+    }
+    
+    // 无论成败都打印统计，超过内存上限时也能看到峰值
+    if args.stats {
+        for line in yufmath::cli::commands::stats_report(&yuf) {
+            eprintln!("{}", line);
+        }
+    }
+    
     // 处理结果
     match result {
         Ok(()) => {
             if args.verbose {
-                println!("计算完成");
+                println!("{}", tr!("cli.done"));
             }
         }
         Err(e) => {
             if !args.quiet {
-                eprintln!("错误: {}", e);
+                eprintln!("{}", tr!("cli.error", error = e));
+                if let Some(input) = parsed_input(&args.command) {
+                    let color = color.should_use_color_on(atty::Stream::Stderr);
+                    for line in yufmath::cli::commands::error_context(e.as_ref(), input, color) {
+                        eprintln!("{}", line);
+                    }
+                }
             }
             process::exit(1);
         }
@@ -115,27 +181,71 @@ fn main() {
 }
 
 /// 创建 Yufmath 实例
-fn create_yufmath_instance(_args: &CliArgs) -> Result<Yufmath, Box<dyn std::error::Error>> {
-    // 目前使用默认配置，后续任务会添加更多配置选项
-    Ok(Yufmath::new())
+fn create_yufmath_instance(args: &CliArgs) -> Result<Yufmath, Box<dyn std::error::Error>> {
+    let config = yufmath::cli::commands::load_config(args)?;
+    Ok(Yufmath::with_config(config))
+}
+
+/// 出错时用来标出解析错误位置的输入，只取只解析一个表达式的命令
+fn parsed_input(command: &Option<Commands>) -> Option<&str> {
+    match command.as_ref()? {
+        Commands::Compute { expression }
+        | Commands::Simplify { expression }
+        | Commands::Diff { expression, .. }
+        | Commands::Integrate { expression, .. }
+        | Commands::TrigExpand { expression }
+        | Commands::TrigFactor { expression }
+        | Commands::Laplace { expression, .. }
+        | Commands::InverseLaplace { expression, .. }
+        | Commands::Analyze { expression, .. }
+        | Commands::PlotData { expression, .. }
+        | Commands::PlotAscii { expression, .. } => Some(expression),
+        _ => None,
+    }
+}
+
+/// 结果显示用的格式化器：输出到支持颜色的终端时为彩色的终端格式化器，否则为标准格式化器；
+/// 按 `--sort-terms` 与 `--term-order` 决定是否重排项
+fn result_formatter(args: &CliArgs) -> Box<dyn Formatter> {
+    let format_type = if args.color.color_config().should_use_color() { FormatType::Terminal } else { FormatType::Standard };
+    let mut formatter = FormatterFactory::create_formatter(format_type.clone());
+    formatter.set_options(FormatOptions {
+        format_type,
+        sort_terms: args.term_order().is_some(),
+        term_order: args.term_order().unwrap_or_default(),
+        ..FormatOptions::default()
+    });
+    formatter
 }
 
 /// 处理计算命令
 fn handle_compute(yuf: &Yufmath, expression: &str, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
     let show_progress = args.progress && !args.no_progress && !args.quiet;
-    let progress = create_compute_progress(show_progress, "计算表达式");
+    let progress = create_compute_progress(show_progress, tr!("cli.progress.compute"));
     
     if args.verbose {
-        println!("正在计算表达式: {}", expression);
+        println!("{}", tr!("cli.verbose.compute", expression = expression));
     }
     
-    let result = yuf.compute(expression)?;
+    let (result, timings) = if args.trace {
+        let (simplified, events) = yuf.simplify_traced(&yuf.parse(expression)?)?;
+        print_trace(yuf, &events);
+        (yuf.format(&simplified), None)
+    } else if args.time {
+        let (result, timings) = yuf.compute_timed(expression)?;
+        (result, Some(timings))
+    } else {
+        (yuf.compute(expression)?, None)
+    };
     
-    progress.finish("计算完成");
+    progress.finish(tr!("cli.done"));
     
     if !args.quiet {
         println!("{}", result);
     }
+    if let Some(timings) = timings {
+        eprintln!("{}", timings.summary());
+    }
     
     Ok(())
 }
@@ -143,15 +253,20 @@ fn handle_compute(yuf: &Yufmath, expression: &str, args: &CliArgs) -> Result<(),
 /// 处理简化命令
 fn handle_simplify(yuf: &Yufmath, expression: &str, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
     if args.verbose {
-        println!("正在简化表达式: {}", expression);
+        println!("{}", tr!("cli.verbose.simplify", expression = expression));
     }
     
     let expr = yuf.parse(expression)?;
-    let simplified = yuf.simplify(&expr)?;
+    let simplified = if args.trace {
+        let (simplified, events) = yuf.simplify_traced(&expr)?;
+        print_trace(yuf, &events);
+        simplified
+    } else {
+        yuf.simplify(&expr)?
+    };
     
     // 使用格式化器来正确显示简化后的表达式
-    use yufmath::formatter::{StandardFormatter, Formatter};
-    let formatter = StandardFormatter::new();
+    let formatter = result_formatter(args);
     let result = formatter.format(&simplified);
     
     if !args.quiet {
@@ -161,18 +276,22 @@ fn handle_simplify(yuf: &Yufmath, expression: &str, args: &CliArgs) -> Result<()
     Ok(())
 }
 
-/// 处理求导命令
-fn handle_diff(yuf: &Yufmath, expression: &str, variable: &str, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+/// 处理求导命令，没有给出变量时对表达式中唯一的变量求导
+fn handle_diff(yuf: &Yufmath, expression: &str, variables: &[String], args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let expr = yuf.parse(expression)?;
+    let variables = match variables {
+        [] => vec![yuf.sole_variable(&expr)?],
+        variables => variables.to_vec(),
+    };
+    
     if args.verbose {
-        println!("正在对表达式 {} 关于变量 {} 求导", expression, variable);
+        println!("{}", tr!("cli.verbose.diff", expression = expression, variable = variables.join(", ")));
     }
     
-    let expr = yuf.parse(expression)?;
-    let derivative = yuf.diff(&expr, variable)?;
+    let derivative = yuf.diff(&expr, &variables)?;
     
     // 使用格式化器来正确显示求导结果
-    use yufmath::formatter::{StandardFormatter, Formatter};
-    let formatter = StandardFormatter::new();
+    let formatter = result_formatter(args);
     let result = formatter.format(&derivative);
     
     if !args.quiet {
@@ -182,18 +301,26 @@ fn handle_diff(yuf: &Yufmath, expression: &str, variable: &str, args: &CliArgs)
     Ok(())
 }
 
-/// 处理积分命令
-fn handle_integrate(yuf: &Yufmath, expression: &str, variable: &str, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+/// 处理积分命令，没有给出变量时对表达式中唯一的变量积分
+fn handle_integrate(yuf: &Yufmath, expression: &str, variable: Option<&str>, with_constant: bool, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let expr = yuf.parse(expression)?;
+    let variable = match variable {
+        Some(variable) => variable.to_string(),
+        None => yuf.sole_variable(&expr)?,
+    };
+    
     if args.verbose {
-        println!("正在对表达式 {} 关于变量 {} 积分", expression, variable);
+        println!("{}", tr!("cli.verbose.integrate", expression = expression, variable = variable));
     }
     
-    let expr = yuf.parse(expression)?;
-    let integral = yuf.integrate(&expr, variable)?;
+    if with_constant {
+        yuf.update_config(|config| config.integration_constant = true);
+    }
+    
+    let integral = yuf.integrate(&expr, &variable)?;
     
     // 使用格式化器来正确显示积分结果
-    use yufmath::formatter::{StandardFormatter, Formatter};
-    let formatter = StandardFormatter::new();
+    let formatter = result_formatter(args);
     let result = formatter.format(&integral);
     
     if !args.quiet {
@@ -206,12 +333,34 @@ fn handle_integrate(yuf: &Yufmath, expression: &str, variable: &str, args: &CliA
 /// 处理求解命令
 fn handle_solve(yuf: &Yufmath, equation: &str, variable: &str, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
     if args.verbose {
-        println!("正在求解方程 {} 关于变量 {}", equation, variable);
+        println!("{}", tr!("cli.verbose.solve", equation = equation, variable = variable));
     }
     
-    // 暂时返回未实现错误，因为求解功能还未完全实现
-    if !args.quiet {
-        println!("求解功能暂未实现，方程: {}, 变量: {}", equation, variable);
+    let expr = yuf.parse_equation(equation)?;
+    let solutions = yuf.solve_detailed(&expr, variable)?;
+    
+    if args.quiet {
+        return Ok(());
+    }
+    
+    if solutions.is_empty() {
+        println!("{}", tr!("cli.solve.no_solution"));
+    }
+    
+    // 使用格式化器来正确显示每个根，并标注重数和验证情况
+    let formatter = result_formatter(args);
+    for (i, solution) in solutions.iter().enumerate() {
+        let mut notes = vec![tr!("cli.solve.multiplicity", multiplicity = solution.multiplicity)];
+        if !solution.is_exact() {
+            notes.push(tr!("cli.solve.numeric").to_string());
+        }
+        if solution.is_candidate() {
+            notes.push(tr!("cli.solve.candidate").to_string());
+        }
+        println!("{}", tr!(
+            "cli.solve.solution",
+            index = i + 1, variable = variable, root = formatter.format(&solution.root), notes = notes.join(tr!("cli.solve.note_separator"))
+        ));
     }
     
     Ok(())
@@ -220,12 +369,12 @@ fn handle_solve(yuf: &Yufmath, equation: &str, variable: &str, args: &CliArgs) -
 /// 处理因式分解命令
 fn handle_factor(yuf: &Yufmath, expression: &str, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
     if args.verbose {
-        println!("正在对表达式 {} 进行因式分解", expression);
+        println!("{}", tr!("cli.verbose.factor", expression = expression));
     }
     
     // 暂时返回未实现错误，因为因式分解功能还未完全实现
     if !args.quiet {
-        println!("因式分解功能暂未实现，表达式: {}", expression);
+        println!("{}", tr!("cli.factor_unimplemented", expression = expression));
     }
     
     Ok(())
@@ -234,12 +383,12 @@ fn handle_factor(yuf: &Yufmath, expression: &str, args: &CliArgs) -> Result<(),
 /// 处理展开命令
 fn handle_expand(yuf: &Yufmath, expression: &str, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
     if args.verbose {
-        println!("正在展开表达式 {}", expression);
+        println!("{}", tr!("cli.verbose.expand", expression = expression));
     }
     
     // 暂时返回未实现错误，因为展开功能还未完全实现
     if !args.quiet {
-        println!("展开功能暂未实现，表达式: {}", expression);
+        println!("{}", tr!("cli.expand_unimplemented", expression = expression));
     }
     
     Ok(())
@@ -248,7 +397,7 @@ fn handle_expand(yuf: &Yufmath, expression: &str, args: &CliArgs) -> Result<(),
 /// 处理极限命令
 fn handle_limit(yuf: &Yufmath, expression: &str, variable: &str, point: &str, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
     if args.verbose {
-        println!("正在计算表达式 {} 当 {} 趋向 {} 时的极限", expression, variable, point);
+        println!("{}", tr!("cli.verbose.limit", expression = expression, variable = variable, point = point));
     }
     
     let expr = yuf.parse(expression)?;
@@ -256,8 +405,7 @@ fn handle_limit(yuf: &Yufmath, expression: &str, variable: &str, point: &str, ar
     let limit_result = yuf.limit(&expr, variable, &point_expr)?;
     
     // 使用格式化器来正确显示极限结果
-    use yufmath::formatter::{StandardFormatter, Formatter};
-    let formatter = StandardFormatter::new();
+    let formatter = result_formatter(args);
     let result = formatter.format(&limit_result);
     
     if !args.quiet {
@@ -270,7 +418,7 @@ fn handle_limit(yuf: &Yufmath, expression: &str, variable: &str, point: &str, ar
 /// 处理级数展开命令
 fn handle_series(yuf: &Yufmath, expression: &str, variable: &str, point: &str, order: usize, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
     if args.verbose {
-        println!("正在对表达式 {} 在 {} = {} 处进行 {} 阶级数展开", expression, variable, point, order);
+        println!("{}", tr!("cli.verbose.series", expression = expression, variable = variable, point = point, order = order));
     }
     
     let expr = yuf.parse(expression)?;
@@ -278,8 +426,7 @@ fn handle_series(yuf: &Yufmath, expression: &str, variable: &str, point: &str, o
     let series_result = yuf.series(&expr, variable, &point_expr, order)?;
     
     // 使用格式化器来正确显示级数展开结果
-    use yufmath::formatter::{StandardFormatter, Formatter};
-    let formatter = StandardFormatter::new();
+    let formatter = result_formatter(args);
     let result = formatter.format(&series_result);
     
     if !args.quiet {
@@ -289,28 +436,190 @@ fn handle_series(yuf: &Yufmath, expression: &str, variable: &str, point: &str, o
     Ok(())
 }
 
+/// 处理函数分析命令
+fn handle_analyze(yuf: &Yufmath, expression: &str, variable: &str, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if args.verbose {
+        println!("{}", tr!("cli.verbose.analyze", expression = expression, variable = variable));
+    }
+    
+    let expr = yuf.parse(expression)?;
+    let report = yufmath::cli::commands::analysis_report(yuf, &expr, variable)?;
+    
+    if !args.quiet {
+        for line in report {
+            println!("{}", line);
+        }
+    }
+    
+    Ok(())
+}
+
+/// 处理三角变换命令：`factor` 为 true 时和差化积，否则展开和角与倍角
+fn handle_trig_transform(yuf: &Yufmath, expression: &str, factor: bool, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if args.verbose {
+        if factor {
+            println!("{}", tr!("cli.verbose.trig_factor", expression = expression));
+        } else {
+            println!("{}", tr!("cli.verbose.trig_expand", expression = expression));
+        }
+    }
+    
+    let expr = yuf.parse(expression)?;
+    let result = if factor { yuf.trig_factor(&expr)? } else { yuf.trig_expand(&expr)? };
+    
+    if !args.quiet {
+        println!("{}", yuf.format(&result));
+    }
+    
+    Ok(())
+}
+
+/// 处理 Laplace 变换命令
+fn handle_laplace(yuf: &Yufmath, expression: &str, variable: &str, transform_variable: &str, region: bool, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if args.verbose {
+        println!("{}", tr!("cli.verbose.laplace", expression = expression, variable = variable));
+    }
+    
+    let expr = yuf.parse(expression)?;
+    let report = yufmath::cli::commands::laplace_report(yuf, &expr, variable, transform_variable, region)?;
+    
+    if !args.quiet {
+        for line in report {
+            println!("{}", line);
+        }
+    }
+    
+    Ok(())
+}
+
+/// 处理 Laplace 逆变换命令
+fn handle_inverse_laplace(yuf: &Yufmath, expression: &str, variable: &str, time_variable: &str, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if args.verbose {
+        println!("{}", tr!("cli.verbose.inverse_laplace", expression = expression, variable = variable));
+    }
+    
+    let expr = yuf.parse(expression)?;
+    let original = yuf.inverse_laplace(&expr, variable, time_variable)?;
+    
+    if !args.quiet {
+        println!("{}", yuf.format(&original));
+    }
+    
+    Ok(())
+}
+
+/// 按顺序打印化简追踪
+fn print_trace(yuf: &Yufmath, events: &[yufmath::TraceEvent]) {
+    for line in yufmath::cli::commands::trace_report(yuf, events) {
+        println!("{}", line);
+    }
+}
+
+/// 处理绘图数据导出命令
+fn handle_plot_data(yuf: &Yufmath, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(Commands::PlotData { expression, variable, from, to, n, csv, json, skip_undefined }) = &args.command else {
+        return Ok(());
+    };
+    
+    if args.verbose {
+        println!("{}", tr!("cli.verbose.plot_data", expression = expression, variable = variable, from = from, to = to, n = n));
+    }
+    
+    let expr = yuf.parse(expression)?;
+    let points = yufmath::cli::commands::plot_points(yuf, &expr, variable, *from, *to, *n, *skip_undefined)?;
+    yufmath::cli::commands::write_plot_data(&points, csv.as_deref(), *json)?;
+    
+    Ok(())
+}
+
+/// 处理终端绘图命令
+fn handle_plot_ascii(yuf: &Yufmath, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    let Some(Commands::PlotAscii { expression, variable, from, to, width, height, y_min, y_max, ascii }) = &args.command else {
+        return Ok(());
+    };
+    
+    let options = yufmath::cli::commands::plot_options(*width, *height, y_min.zip(*y_max), *ascii);
+    if args.verbose {
+        println!("{}", tr!("cli.verbose.plot_data", expression = expression, variable = variable, from = from, to = to, n = options.sample_count()));
+    }
+    
+    println!("{}", yufmath::cli::commands::plot_ascii_report(yuf, expression, variable, *from, *to, &options)?);
+    
+    Ok(())
+}
+
+/// 处理等式校验命令
+fn handle_verify(yuf: &Yufmath, equation: &str, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if args.verbose {
+        println!("{}", tr!("cli.verbose.verify", equation = equation));
+    }
+    
+    let report = yufmath::cli::commands::verify_report(yuf, equation)?;
+    
+    if !args.quiet {
+        for line in report {
+            println!("{}", line);
+        }
+    }
+    
+    Ok(())
+}
+
+/// 处理恒等式证明命令
+fn handle_prove(yuf: &Yufmath, lhs: &str, rhs: &str, vars: &[String], args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if args.verbose {
+        println!("{}", tr!("cli.verbose.prove", lhs = lhs, rhs = rhs));
+    }
+    
+    let report = yufmath::cli::commands::prove_report(yuf, lhs, rhs, vars)?;
+    
+    if !args.quiet {
+        for line in report {
+            println!("{}", line);
+        }
+    }
+    
+    Ok(())
+}
+
+/// 处理练习题命令
+fn handle_quiz(yuf: &Yufmath, kind: ExerciseKind, count: usize, seed: Option<u64>, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
+    if args.verbose {
+        println!("{}", tr!("cli.verbose.quiz", count = count, kind = kind.name()));
+    }
+    
+    let report = yufmath::cli::commands::quiz_report(yuf, kind, count, seed)?;
+    
+    if !args.quiet {
+        for line in report {
+            println!("{}", line);
+        }
+    }
+    
+    Ok(())
+}
+
 /// 处理批处理命令
 fn handle_batch(yuf: &Yufmath, input_file: &str, output_file: Option<&str>, args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
     use std::fs;
     use std::io::Write;
     
     if args.verbose {
-        println!("正在处理批处理文件: {}", input_file);
+        println!("{}", tr!("cli.verbose.batch", file = input_file));
         if let Some(output) = output_file {
-            println!("输出文件: {}", output);
+            println!("{}", tr!("cli.verbose.output_file", file = output));
         }
     }
     
     // 读取输入文件
     let content = fs::read_to_string(input_file)
-        .map_err(|e| format!("无法读取输入文件 '{}': {}", input_file, e))?;
+        .map_err(|e| tr!("cli.batch.read_failed", file = input_file, error = e))?;
+    // 去掉注释后逐行计算，块注释可以跨行
+    let content = yufmath::parser::lexer::strip_comments(&content);
     
     // 计算有效行数（用于进度条）
     let total_lines = content.lines()
-        .filter(|line| {
-            let line = line.trim();
-            !line.is_empty() && !line.starts_with('#') && !line.starts_with("//")
-        })
+        .filter(|line| !line.trim().is_empty())
         .count() as u64;
     
     let show_progress = args.progress && !args.no_progress && !args.quiet;
@@ -320,51 +629,70 @@ fn handle_batch(yuf: &Yufmath, input_file: &str, output_file: Option<&str>, args
     let mut line_number = 0;
     let mut processed_lines = 0;
     
+    // TSV 输出：表头之后每个输入一行，出错的行把错误信息放在结果列，全部写到标准输出
+    let tsv = matches!(args.format, OutputFormat::Tsv);
+    if tsv {
+        let header = tr!("cli.batch.tsv_header").to_string();
+        if !args.quiet && !show_progress {
+            println!("{}", header);
+        }
+        results.push(Ok(header));
+    }
+    
     // 处理每一行
     for line in content.lines() {
         line_number += 1;
         let line = line.trim();
         
-        // 跳过空行和注释行
-        if line.is_empty() || line.starts_with('#') || line.starts_with("//") {
+        // 跳过空行和只有注释的行
+        if line.is_empty() {
             continue;
         }
         
         processed_lines += 1;
-        progress.update(processed_lines, Some(&format!("处理第 {} 行", line_number)));
+        progress.update(processed_lines, Some(&tr!("cli.batch.progress_line", line = line_number)));
         
         if args.verbose {
-            println!("处理第 {} 行: {}", line_number, line);
+            println!("{}", tr!("cli.verbose.batch_line", line = line_number, input = line));
         }
         
         // 尝试计算表达式
+        if tsv {
+            let start = Instant::now();
+            let output = yuf.compute(line).map_err(|e| e.to_string());
+            let row = yufmath::cli::commands::batch_tsv_row(line, &output, start.elapsed());
+            if !args.quiet && !show_progress {
+                println!("{}", row);
+            }
+            results.push(Ok(row));
+            continue;
+        }
         match yuf.compute(line) {
             Ok(result) => {
                 let output_line = format!("{} = {}", line, result);
-                results.push(output_line.clone());
                 if !args.quiet && !show_progress {
                     println!("{}", output_line);
                 }
+                results.push(Ok(output_line));
             }
             Err(e) => {
-                let error_line = format!("第 {} 行错误: {} -> {}", line_number, line, e);
-                results.push(error_line.clone());
+                let error_line = tr!("cli.batch.line_error", line = line_number, input = line, error = e);
                 if !args.quiet && !show_progress {
                     eprintln!("{}", error_line);
                 }
+                results.push(Err(error_line));
             }
         }
     }
     
-    progress.finish(&format!("批处理完成，处理了 {} 行", processed_lines));
+    progress.finish(&tr!("cli.batch.done", count = processed_lines));
     
     // 如果启用了进度条，现在显示结果
     if show_progress && !args.quiet {
         for result in &results {
-            if result.contains("错误") {
-                eprintln!("{}", result);
-            } else {
-                println!("{}", result);
+            match result {
+                Ok(line) => println!("{}", line),
+                Err(line) => eprintln!("{}", line),
             }
         }
     }
@@ -372,15 +700,17 @@ fn handle_batch(yuf: &Yufmath, input_file: &str, output_file: Option<&str>, args
     // 如果指定了输出文件，写入结果
     if let Some(output_path) = output_file {
         let mut output = fs::File::create(output_path)
-            .map_err(|e| format!("无法创建输出文件 '{}': {}", output_path, e))?;
+            .map_err(|e| tr!("cli.batch.create_failed", file = output_path, error = e))?;
         
+        // 写入文件的结果不带颜色
         for result in &results {
-            writeln!(output, "{}", result)
-                .map_err(|e| format!("写入输出文件失败: {}", e))?;
+            let (Ok(line) | Err(line)) = result;
+            writeln!(output, "{}", strip_ansi(line))
+                .map_err(|e| tr!("cli.batch.write_failed", error = e))?;
         }
         
         if args.verbose {
-            println!("结果已写入文件: {}", output_path);
+            println!("{}", tr!("cli.batch.written", file = output_path));
         }
     }
     
@@ -389,54 +719,14 @@ fn handle_batch(yuf: &Yufmath, input_file: &str, output_file: Option<&str>, args
 
 /// 显示帮助信息
 fn show_help() {
-    println!("Yufmath v{} - 计算机代数系统", yufmath::VERSION);
-    println!();
-    println!("用法:");
-    println!("  yufmath [选项] <命令> [参数...]");
-    println!();
-    println!("命令:");
-    println!("  compute <表达式>              计算表达式的值");
-    println!("  simplify <表达式>             简化表达式");
-    println!("  diff <表达式> <变量>          对表达式求导");
-    println!("  integrate <表达式> <变量>     对表达式积分");
-    println!("  solve <方程> <变量>           求解方程");
-    println!("  factor <表达式>               因式分解");
-    println!("  expand <表达式>               展开表达式");
-    println!("  limit <表达式> <变量> <点>    计算极限");
-    println!("  series <表达式> <变量> <点>   级数展开");
-    println!("  batch -i <文件> [-o <文件>]   批处理模式");
-    println!("  interactive                   交互模式");
-    println!("  notepad [文件] [-t <标题>]    笔记本模式");
-    println!();
-    println!("选项:");
-    println!("  -f, --format <格式>           输出格式 [standard, latex, mathml]");
-    println!("  -p, --precision <精度>        数值精度");
-    println!("  -v, --verbose                 详细输出");
-    println!("  -q, --quiet                   静默模式");
-    println!("      --progress                显示进度条");
-    println!("      --no-progress             禁用进度条");
-    println!("      --timeout <秒>            计算超时时间");
-    println!("  -h, --help                    显示帮助信息");
-    println!("      --version                 显示版本信息");
-    println!();
-    println!("示例:");
-    println!("  yufmath compute \"2 + 3 * 4\"");
-    println!("  yufmath simplify \"x^2 + 2*x + 1\"");
-    println!("  yufmath diff \"x^3 + 2*x^2 + x\" x");
-    println!("  yufmath integrate \"2*x + 1\" x");
-    println!("  yufmath --format latex compute \"x^2 + 1\"");
-    println!("  yufmath batch -i input.txt -o output.txt");
-    println!("  yufmath interactive");
-    println!("  yufmath notepad");
-    println!("  yufmath notepad my_notebook.ynb");
-    println!("  yufmath notepad -t \"我的数学笔记\"");
+    println!("{}", tr!("cli.usage", version = yufmath::VERSION));
 }
 
 /// 处理交互模式
 fn handle_interactive(args: &CliArgs) -> Result<(), Box<dyn std::error::Error>> {
     if args.verbose {
-        println!("启动交互模式...");
+        println!("{}", tr!("cli.verbose.interactive"));
     }
     
-    interactive::run_interactive()
+    interactive::run_interactive_with_config(yufmath::cli::commands::load_config(args)?)
 }
\ No newline at end of file
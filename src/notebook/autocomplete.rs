@@ -3,6 +3,7 @@
 //! 为笔记本编辑器提供智能自动补全功能。
 
 use std::collections::HashMap;
+use crate::core::signatures;
 
 /// 自动补全建议
 #[derive(Debug, Clone)]
@@ -126,6 +127,48 @@ impl AutoCompleteEngine {
                 description: "计算平方根".to_string(),
                 example: Some("sqrt(16) = 4".to_string()),
             },
+            FunctionInfo {
+                name: "cbrt".to_string(),
+                parameters: vec!["x".to_string()],
+                return_type: "Number".to_string(),
+                description: "计算立方根".to_string(),
+                example: Some("cbrt(27) = 3".to_string()),
+            },
+            FunctionInfo {
+                name: "root".to_string(),
+                parameters: vec!["x".to_string(), "n".to_string()],
+                return_type: "Number".to_string(),
+                description: "计算 n 次根".to_string(),
+                example: Some("root(16, 4) = 2".to_string()),
+            },
+            FunctionInfo {
+                name: "floor".to_string(),
+                parameters: vec!["x".to_string()],
+                return_type: "Number".to_string(),
+                description: "向下取整".to_string(),
+                example: Some("floor(7/2) = 3".to_string()),
+            },
+            FunctionInfo {
+                name: "ceil".to_string(),
+                parameters: vec!["x".to_string()],
+                return_type: "Number".to_string(),
+                description: "向上取整".to_string(),
+                example: Some("ceil(7/2) = 4".to_string()),
+            },
+            FunctionInfo {
+                name: "round".to_string(),
+                parameters: vec!["x".to_string()],
+                return_type: "Number".to_string(),
+                description: "四舍五入取整".to_string(),
+                example: Some("round(5/2) = 3".to_string()),
+            },
+            FunctionInfo {
+                name: "trunc".to_string(),
+                parameters: vec!["x".to_string()],
+                return_type: "Number".to_string(),
+                description: "向零取整".to_string(),
+                example: Some("trunc(-7/2) = -3".to_string()),
+            },
             FunctionInfo {
                 name: "exp".to_string(),
                 parameters: vec!["x".to_string()],
@@ -154,6 +197,55 @@ impl AutoCompleteEngine {
                 description: "计算绝对值".to_string(),
                 example: Some("abs(-5) = 5".to_string()),
             },
+            FunctionInfo {
+                name: "erf".to_string(),
+                parameters: vec!["x".to_string()],
+                return_type: "Number".to_string(),
+                description: "误差函数".to_string(),
+                example: Some("erf(0) = 0".to_string()),
+            },
+            FunctionInfo {
+                name: "erfc".to_string(),
+                parameters: vec!["x".to_string()],
+                return_type: "Number".to_string(),
+                description: "余误差函数 1 - erf(x)".to_string(),
+                example: Some("erfc(0) = 1".to_string()),
+            },
+            FunctionInfo {
+                name: "Si".to_string(),
+                parameters: vec!["x".to_string()],
+                return_type: "Number".to_string(),
+                description: "正弦积分".to_string(),
+                example: Some("Si(0) = 0".to_string()),
+            },
+            FunctionInfo {
+                name: "Ci".to_string(),
+                parameters: vec!["x".to_string()],
+                return_type: "Number".to_string(),
+                description: "余弦积分（x > 0）".to_string(),
+                example: Some("Ci(1) ≈ 0.3374".to_string()),
+            },
+            FunctionInfo {
+                name: "Ei".to_string(),
+                parameters: vec!["x".to_string()],
+                return_type: "Number".to_string(),
+                description: "指数积分".to_string(),
+                example: Some("Ei(1) ≈ 1.8951".to_string()),
+            },
+            FunctionInfo {
+                name: "li".to_string(),
+                parameters: vec!["x".to_string()],
+                return_type: "Number".to_string(),
+                description: "对数积分（x > 0）".to_string(),
+                example: Some("li(2) ≈ 1.0452".to_string()),
+            },
+            FunctionInfo {
+                name: "sinc".to_string(),
+                parameters: vec!["x".to_string()],
+                return_type: "Number".to_string(),
+                description: "非归一化 sinc 函数 sin(x)/x".to_string(),
+                example: Some("sinc(0) = 1".to_string()),
+            },
             FunctionInfo {
                 name: "diff".to_string(),
                 parameters: vec!["expr".to_string(), "var".to_string()],
@@ -210,6 +302,20 @@ impl AutoCompleteEngine {
                 description: "级数展开".to_string(),
                 example: Some("series(e^x, x, 0, 3) = 1 + x + x^2/2! + x^3/3! + O(x^4)".to_string()),
             },
+            FunctionInfo {
+                name: "sum".to_string(),
+                parameters: vec!["expr".to_string(), "var".to_string(), "lower".to_string(), "upper".to_string()],
+                return_type: "Expression".to_string(),
+                description: "求和，上限可以为 inf（无穷级数）".to_string(),
+                example: Some("sum(1/n^2, n, 1, inf) = π^2/6".to_string()),
+            },
+            FunctionInfo {
+                name: "forward_difference".to_string(),
+                parameters: vec!["expr".to_string(), "var".to_string()],
+                return_type: "Expression".to_string(),
+                description: "前向差分 f(n + 1) - f(n)，求和的逆运算".to_string(),
+                example: Some("forward_difference(x^2, x) = 2*x + 1".to_string()),
+            },
             FunctionInfo {
                 name: "matrix".to_string(),
                 parameters: vec!["elements".to_string()],
@@ -228,19 +334,198 @@ impl AutoCompleteEngine {
                 name: "gcd".to_string(),
                 parameters: vec!["a".to_string(), "b".to_string()],
                 return_type: "Number".to_string(),
-                description: "计算最大公约数".to_string(),
-                example: Some("gcd(48, 18) = 6".to_string()),
+                description: "计算最大公约数（支持多个参数）".to_string(),
+                example: Some("gcd(12, 18, 24) = 6".to_string()),
             },
             FunctionInfo {
                 name: "lcm".to_string(),
                 parameters: vec!["a".to_string(), "b".to_string()],
                 return_type: "Number".to_string(),
-                description: "计算最小公倍数".to_string(),
-                example: Some("lcm(12, 8) = 24".to_string()),
+                description: "计算最小公倍数（支持多个参数）".to_string(),
+                example: Some("lcm(4, 6, 8) = 24".to_string()),
+            },
+            FunctionInfo {
+                name: "mod".to_string(),
+                parameters: vec!["a".to_string(), "b".to_string()],
+                return_type: "Number".to_string(),
+                description: "计算余数（余数非负）".to_string(),
+                example: Some("mod(-7, 3) = 2".to_string()),
+            },
+            FunctionInfo {
+                name: "congruent".to_string(),
+                parameters: vec!["a".to_string(), "b".to_string(), "n".to_string()],
+                return_type: "Number".to_string(),
+                description: "判定 a ≡ b (mod n)".to_string(),
+                example: Some("congruent(17, 3, 7) = 1".to_string()),
+            },
+            FunctionInfo {
+                name: "resultant".to_string(),
+                parameters: vec!["p".to_string(), "q".to_string(), "var".to_string()],
+                return_type: "Expression".to_string(),
+                description: "两个多项式关于 var 的结式".to_string(),
+                example: Some("resultant(x^2 - 1, x - 1, x) = 0".to_string()),
+            },
+            FunctionInfo {
+                name: "discriminant".to_string(),
+                parameters: vec!["p".to_string(), "var".to_string()],
+                return_type: "Expression".to_string(),
+                description: "多项式关于 var 的判别式".to_string(),
+                example: Some("discriminant(a*x^2 + b*x + c, x) = b^2 - 4*a*c".to_string()),
+            },
+            FunctionInfo {
+                name: "chebyshev_t".to_string(),
+                parameters: vec!["n".to_string(), "x".to_string()],
+                return_type: "Expression".to_string(),
+                description: "第一类 Chebyshev 多项式 T_n，省略 x 时以 x 为变量".to_string(),
+                example: Some("chebyshev_t(2) = 2*x^2 - 1".to_string()),
+            },
+            FunctionInfo {
+                name: "chebyshev_u".to_string(),
+                parameters: vec!["n".to_string(), "x".to_string()],
+                return_type: "Expression".to_string(),
+                description: "第二类 Chebyshev 多项式 U_n".to_string(),
+                example: Some("chebyshev_u(2) = 4*x^2 - 1".to_string()),
+            },
+            FunctionInfo {
+                name: "legendre_p".to_string(),
+                parameters: vec!["n".to_string(), "x".to_string()],
+                return_type: "Expression".to_string(),
+                description: "Legendre 多项式 P_n".to_string(),
+                example: Some("legendre_p(2) = (3*x^2 - 1)/2".to_string()),
+            },
+            FunctionInfo {
+                name: "hermite_h".to_string(),
+                parameters: vec!["n".to_string(), "x".to_string()],
+                return_type: "Expression".to_string(),
+                description: "Hermite 多项式 H_n（物理学约定）".to_string(),
+                example: Some("hermite_h(2) = 4*x^2 - 2".to_string()),
+            },
+            FunctionInfo {
+                name: "laguerre_l".to_string(),
+                parameters: vec!["n".to_string(), "x".to_string()],
+                return_type: "Expression".to_string(),
+                description: "Laguerre 多项式 L_n".to_string(),
+                example: Some("laguerre_l(2) = (x^2 - 4*x + 2)/2".to_string()),
+            },
+            FunctionInfo {
+                name: "complete_square".to_string(),
+                parameters: vec!["f".to_string(), "vars".to_string()],
+                return_type: "Expression".to_string(),
+                description: "配方为 a*(x + h)^2 + k；vars 可为变量列表，含交叉项时旋转坐标".to_string(),
+                example: Some("complete_square(2*x^2 + 4*x + 5, x) = 2*(x + 1)^2 + 3".to_string()),
+            },
+            FunctionInfo {
+                name: "quadratic_form_matrix".to_string(),
+                parameters: vec!["f".to_string(), "vars".to_string()],
+                return_type: "Matrix".to_string(),
+                description: "关于变量列表 vars 的二次型对称矩阵".to_string(),
+                example: Some("quadratic_form_matrix(x^2 + 4*x*y, [x, y]) = [[1, 2], [2, 0]]".to_string()),
+            },
+            FunctionInfo {
+                name: "jacobian".to_string(),
+                parameters: vec!["fs".to_string(), "vars".to_string()],
+                return_type: "Expression".to_string(),
+                description: "向量函数 fs 对变量列表 vars 的雅可比矩阵".to_string(),
+                example: Some("jacobian([x*y, x + y], [x, y]) = [[y, x], [1, 1]]".to_string()),
+            },
+            FunctionInfo {
+                name: "div".to_string(),
+                parameters: vec!["field".to_string(), "vars".to_string()],
+                return_type: "Expression".to_string(),
+                description: "向量场的散度".to_string(),
+                example: Some("div([x, y, z], [x, y, z]) = 3".to_string()),
+            },
+            FunctionInfo {
+                name: "curl".to_string(),
+                parameters: vec!["field".to_string(), "vars".to_string()],
+                return_type: "Expression".to_string(),
+                description: "向量场的旋度，二维时为标量".to_string(),
+                example: Some("curl([-y, x, 0], [x, y, z]) = [0, 0, 2]".to_string()),
+            },
+            FunctionInfo {
+                name: "laplacian".to_string(),
+                parameters: vec!["f".to_string(), "vars".to_string()],
+                return_type: "Expression".to_string(),
+                description: "标量场的拉普拉斯算子".to_string(),
+                example: Some("laplacian(x^2 + y^2, [x, y]) = 4".to_string()),
+            },
+            FunctionInfo {
+                name: "normal_pdf".to_string(),
+                parameters: vec!["x".to_string(), "mu".to_string(), "sigma".to_string()],
+                return_type: "Expression".to_string(),
+                description: "正态分布 N(mu, sigma²) 的概率密度".to_string(),
+                example: Some("normal_pdf(0) = 1/sqrt(2*π)".to_string()),
+            },
+            FunctionInfo {
+                name: "normal_cdf".to_string(),
+                parameters: vec!["x".to_string(), "mu".to_string(), "sigma".to_string()],
+                return_type: "Expression".to_string(),
+                description: "正态分布函数，用 erf 表示".to_string(),
+                example: Some("normal_cdf(1.96) ≈ 0.975".to_string()),
+            },
+            FunctionInfo {
+                name: "binomial_pmf".to_string(),
+                parameters: vec!["k".to_string(), "n".to_string(), "p".to_string()],
+                return_type: "Expression".to_string(),
+                description: "二项分布 B(n, p) 取 k 的概率".to_string(),
+                example: Some("binomial_pmf(2, 4, 1/2) = 3/8".to_string()),
+            },
+            FunctionInfo {
+                name: "binomial_cdf".to_string(),
+                parameters: vec!["k".to_string(), "n".to_string(), "p".to_string()],
+                return_type: "Expression".to_string(),
+                description: "二项分布函数 P(X ≤ k)".to_string(),
+                example: Some("binomial_cdf(2, 4, 1/2) = 11/16".to_string()),
+            },
+            FunctionInfo {
+                name: "poisson_pmf".to_string(),
+                parameters: vec!["k".to_string(), "lambda".to_string()],
+                return_type: "Expression".to_string(),
+                description: "泊松分布 P(lambda) 取 k 的概率".to_string(),
+                example: Some("poisson_pmf(2, 3) ≈ 0.224".to_string()),
+            },
+            FunctionInfo {
+                name: "poisson_cdf".to_string(),
+                parameters: vec!["k".to_string(), "lambda".to_string()],
+                return_type: "Expression".to_string(),
+                description: "泊松分布函数 P(X ≤ k)".to_string(),
+                example: Some("poisson_cdf(2, 3) ≈ 0.423".to_string()),
+            },
+            FunctionInfo {
+                name: "expectation".to_string(),
+                parameters: vec!["dist".to_string(), "g".to_string(), "var".to_string()],
+                return_type: "Expression".to_string(),
+                description: "g(var) 在分布 normal/binomial/poisson 下的期望".to_string(),
+                example: Some("expectation(poisson(lambda), k^2, k) = lambda^2 + lambda".to_string()),
+            },
+            FunctionInfo {
+                name: "rem".to_string(),
+                parameters: vec!["a".to_string(), "b".to_string()],
+                return_type: "Number".to_string(),
+                description: "计算余数（截断约定，与被除数同号）".to_string(),
+                example: Some("rem(-7, 3) = -1".to_string()),
+            },
+            FunctionInfo {
+                name: "quotient".to_string(),
+                parameters: vec!["a".to_string(), "b".to_string()],
+                return_type: "Number".to_string(),
+                description: "计算整数商（与 mod 配套）".to_string(),
+                example: Some("quotient(-7, 3) = -3".to_string()),
+            },
+            FunctionInfo {
+                name: "divmod".to_string(),
+                parameters: vec!["a".to_string(), "b".to_string()],
+                return_type: "Vector".to_string(),
+                description: "同时计算整数商与余数".to_string(),
+                example: Some("divmod(-7, 3) = [-3, 2]".to_string()),
             },
         ];
         
-        for func in functions {
+        for mut func in functions {
+            // 参数名以签名表为准，与解析器的校验保持一致
+            if let Some(signature) = signatures::lookup(&func.name) {
+                func.parameters = signature.parameters.iter().map(|(name, _)| name.to_string()).collect();
+            }
             self.functions.insert(func.name.clone(), func);
         }
     }
@@ -475,13 +760,10 @@ impl AutoCompleteEngine {
     /// 获取函数签名帮助
     pub fn get_function_signature(&self, function_name: &str) -> Option<String> {
         self.functions.get(function_name).map(|info| {
-            let params = info.parameters.join(", ");
-            format!("{}({}) -> {}\n\n{}", 
-                function_name, 
-                params, 
-                info.return_type,
-                info.description
-            )
+            let call = signatures::lookup(function_name)
+                .map(|signature| signature.hint())
+                .unwrap_or_else(|| format!("{}({})", function_name, info.parameters.join(", ")));
+            format!("{} -> {}\n\n{}", call, info.return_type, info.description)
         })
     }
     
@@ -12,9 +12,11 @@
 
 use super::{NotebookCell, CellId, ScopeManager, NotebookError, NotebookResult};
 use crate::api::{Yufmath, ComputeProgress, ProgressCallback};
-use crate::core::Expression;
+use crate::core::{Expression, Number};
 use crate::engine::ComputeError;
+use crate::parser::Statement;
 use crate::formatter::{FormatType};
+use crate::tr;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::time::{Duration, Instant, SystemTime};
 use std::sync::{Arc, Mutex, RwLock};
@@ -310,6 +312,17 @@ impl DependencyNode {
     }
 }
 
+/// 单元格定义和引用的变量
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CellVariables {
+    /// 赋值定义的变量和 `f(x) := ...` 定义的函数，按定义顺序
+    pub defined: Vec<String>,
+    /// 引用的外部变量
+    pub referenced: HashSet<String>,
+    /// 调用的函数名，含内置函数；不含同一单元格里先前语句已定义的
+    pub called: HashSet<String>,
+}
+
 /// 依赖图
 pub struct DependencyGraph {
     /// 节点映射
@@ -553,6 +566,24 @@ impl DependencyGraph {
         }
     }
     
+    /// 单元格是否被标记为需要重新执行
+    pub fn needs_execution(&self, cell_id: &CellId) -> bool {
+        self.nodes.get(cell_id).is_some_and(|node| node.needs_execution)
+    }
+    
+    /// 把单元格的依赖替换为 `deps`，移除不再存在的旧依赖
+    pub fn set_dependencies(&mut self, cell_id: CellId, deps: &[CellId]) {
+        self.add_node(cell_id);
+        for old in self.get_dependencies(&cell_id) {
+            if !deps.contains(&old) {
+                self.remove_dependency(&cell_id, &old);
+            }
+        }
+        for &dep_id in deps {
+            self.add_dependency(cell_id, dep_id);
+        }
+    }
+    
     /// 获取直接依赖
     pub fn get_dependencies(&self, cell_id: &CellId) -> Vec<CellId> {
         self.nodes.get(cell_id)
@@ -948,6 +979,8 @@ pub struct QueueStatistics {
 }
 
 /// 执行结果缓存
+///
+/// 以单元格内容和它所依赖绑定的指纹为键，依赖的变量或函数改变后缓存不再命中。
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExecutionCache {
     /// 缓存的结果
@@ -956,6 +989,12 @@ pub struct ExecutionCache {
     cache_file: Option<String>,
     /// 最大缓存大小
     max_size: usize,
+    /// 命中次数
+    #[serde(default)]
+    hits: u64,
+    /// 未命中次数
+    #[serde(default)]
+    misses: u64,
 }
 
 /// 缓存的执行结果
@@ -963,6 +1002,9 @@ pub struct ExecutionCache {
 pub struct CachedResult {
     /// 单元格内容的哈希值
     content_hash: u64,
+    /// 执行时所依赖变量和函数的绑定指纹，见 [`ScopeManager::bindings_fingerprint`]
+    #[serde(default)]
+    dependency_fingerprint: u64,
     /// 执行结果
     result: ExecutionResult,
     /// 缓存时间
@@ -975,10 +1017,11 @@ pub struct CachedResult {
 
 impl CachedResult {
     /// 创建新的缓存结果
-    pub fn new(content_hash: u64, result: ExecutionResult) -> Self {
+    pub fn new(content_hash: u64, dependency_fingerprint: u64, result: ExecutionResult) -> Self {
         let now = SystemTime::now();
         Self {
             content_hash,
+            dependency_fingerprint,
             result,
             cached_at: now,
             access_count: 0,
@@ -1005,6 +1048,8 @@ impl ExecutionCache {
             results: HashMap::new(),
             cache_file: None,
             max_size: 1000, // 默认最多缓存1000个结果
+            hits: 0,
+            misses: 0,
         }
     }
     
@@ -1032,27 +1077,29 @@ impl ExecutionCache {
         hasher.finish()
     }
     
-    /// 获取缓存的结果
-    pub fn get(&mut self, cell_id: &CellId, content: &str) -> Option<ExecutionResult> {
+    /// 获取缓存的结果，内容或依赖指纹与缓存时不同则未命中
+    pub fn get(&mut self, cell_id: &CellId, content: &str, dependency_fingerprint: u64) -> Option<ExecutionResult> {
         let content_hash = Self::compute_hash(content);
         
         if let Some(cached) = self.results.get_mut(cell_id) {
-            if cached.content_hash == content_hash {
+            if cached.content_hash == content_hash && cached.dependency_fingerprint == dependency_fingerprint {
                 cached.mark_accessed();
+                self.hits += 1;
                 return Some(cached.result.clone());
             } else {
-                // 内容已更改，移除旧缓存
+                // 内容或依赖已更改，移除旧缓存
                 self.results.remove(cell_id);
             }
         }
         
+        self.misses += 1;
         None
     }
     
     /// 缓存执行结果
-    pub fn put(&mut self, cell_id: CellId, content: &str, result: ExecutionResult) {
+    pub fn put(&mut self, cell_id: CellId, content: &str, dependency_fingerprint: u64, result: ExecutionResult) {
         let content_hash = Self::compute_hash(content);
-        let cached_result = CachedResult::new(content_hash, result);
+        let cached_result = CachedResult::new(content_hash, dependency_fingerprint, result);
         
         self.results.insert(cell_id, cached_result);
         self.evict_if_needed();
@@ -1076,6 +1123,12 @@ impl ExecutionCache {
         }
     }
     
+    /// 设置命中和未命中次数，用于恢复保存的执行状态
+    pub fn set_hit_counts(&mut self, hits: u64, misses: u64) {
+        self.hits = hits;
+        self.misses = misses;
+    }
+    
     /// 清理过期缓存
     pub fn cleanup_expired(&mut self, max_age: Duration) {
         self.results.retain(|_, cached| !cached.is_expired(max_age));
@@ -1135,7 +1188,13 @@ impl ExecutionCache {
             max_size: self.max_size,
             total_access_count,
             avg_access_count,
-            hit_rate: 0.0, // 需要在使用时统计
+            hits: self.hits,
+            misses: self.misses,
+            hit_rate: if self.hits + self.misses > 0 {
+                self.hits as f64 / (self.hits + self.misses) as f64
+            } else {
+                0.0
+            },
         }
     }
 }
@@ -1153,6 +1212,8 @@ pub struct CacheStatistics {
     pub max_size: usize,
     pub total_access_count: u64,
     pub avg_access_count: f64,
+    pub hits: u64,
+    pub misses: u64,
     pub hit_rate: f64,
 }
 
@@ -1235,9 +1296,6 @@ pub struct ExecutionEngine {
     progress_callback: Option<ProgressCallback>,
     /// 执行统计
     statistics: ExecutionStatistics,
-    /// 缓存统计
-    cache_hits: u64,
-    cache_misses: u64,
 }
 
 impl ExecutionEngine {
@@ -1271,8 +1329,6 @@ impl ExecutionEngine {
             cancel_flag: Arc::new(Mutex::new(false)),
             progress_callback: None,
             statistics: ExecutionStatistics::default(),
-            cache_hits: 0,
-            cache_misses: 0,
         }
     }
     
@@ -1287,7 +1343,17 @@ impl ExecutionEngine {
         self.cache.set_max_size(config.cache_max_size);
         self.config = config;
     }
-    
+
+    /// 使单元格的缓存结果失效，下次执行时重新计算
+    pub fn invalidate_cell_cache(&mut self, cell_id: &CellId) {
+        self.cache.remove(cell_id);
+    }
+
+    /// 使所有单元格的缓存结果失效，命中率统计保留
+    pub fn invalidate_all(&mut self) {
+        self.cache.clear();
+    }
+
     /// 执行单个单元格
     pub fn execute_cell(&mut self, cell: &mut NotebookCell) -> NotebookResult<ExecutionResult> {
         let context = ExecutionContext::new(cell.id);
@@ -1318,20 +1384,22 @@ impl ExecutionEngine {
             return Ok(ExecutionResult::Cancelled);
         }
         
-        // 检查缓存
+        // 设置当前作用域
+        self.scope_manager.set_current_scope(Some(cell.id));
+        
+        // 检查缓存，键包含单元格引用的变量和调用的函数当前的绑定
+        let dependency_fingerprint = if self.config.enable_cache {
+            let variables = self.cell_variables(cell);
+            self.scope_manager.bindings_fingerprint(variables.referenced.iter().chain(&variables.called))
+        } else {
+            0
+        };
         if self.config.enable_cache {
-            let content = cell.get_text();
-            if let Some(cached_result) = self.cache.get(&cell.id, &content) {
-                self.cache_hits += 1;
+            if let Some(cached_result) = self.cache.get(&cell.id, &cell.get_text(), dependency_fingerprint) {
                 return Ok(cached_result);
-            } else {
-                self.cache_misses += 1;
             }
         }
         
-        // 设置当前作用域
-        self.scope_manager.set_current_scope(Some(cell.id));
-        
         // 报告进度
         if let Some(ref callback) = self.progress_callback {
             let progress = ComputeProgress {
@@ -1352,22 +1420,22 @@ impl ExecutionEngine {
             }
         }
         
-        // 解析表达式
-        let expression = match self.parse_cell_content(cell) {
-            Ok(expr) => expr,
+        // 解析语句
+        let statements = match self.parse_cell_content(cell) {
+            Ok(statements) => statements,
             Err(e) => {
                 let execution_time = start_time.elapsed();
                 self.statistics.record_execution(false, execution_time);
                 
                 let result = ExecutionResult::Error {
-                    error: format!("解析错误: {}", e),
+                    error: tr!("error.parse", error = e),
                     error_type: "ParseError".to_string(),
                     execution_time,
                 };
                 
                 // 缓存错误结果
                 if self.config.enable_cache {
-                    self.cache.put(cell.id, &cell.get_text(), result.clone());
+                    self.cache.put(cell.id, &cell.get_text(), dependency_fingerprint, result.clone());
                 }
                 
                 return Ok(result);
@@ -1395,7 +1463,7 @@ impl ExecutionEngine {
         }
         
         // 执行计算
-        let result = match self.compute_expression(&expression, &context) {
+        let result = match self.execute_statements(statements, cell.id, &context) {
             Ok(value) => {
                 let execution_time = start_time.elapsed();
                 self.statistics.record_execution(true, execution_time);
@@ -1429,7 +1497,7 @@ impl ExecutionEngine {
         
         // 缓存结果
         if self.config.enable_cache {
-            self.cache.put(cell.id, &cell.get_text(), result.clone());
+            self.cache.put(cell.id, &cell.get_text(), dependency_fingerprint, result.clone());
         }
         
         // 报告完成
@@ -1468,20 +1536,44 @@ impl ExecutionEngine {
         result
     }
     
-    /// 解析单元格内容
-    fn parse_cell_content(&mut self, cell: &NotebookCell) -> NotebookResult<Expression> {
+    /// 解析单元格内容为 `;` 分隔的语句
+    fn parse_cell_content(&mut self, cell: &NotebookCell) -> NotebookResult<Vec<Statement>> {
         let content = cell.get_text();
         
-        match self.yufmath.parse(&content) {
-            Ok(expr) => Ok(expr),
+        match self.yufmath.parse_statements(&content) {
+            Ok(statements) => Ok(statements),
             Err(e) => Err(NotebookError::Execution(ComputeError::UnsupportedOperation { 
                 operation: format!("解析失败: {}", e) 
             })),
         }
     }
     
-    /// 计算表达式
-    fn compute_expression(&mut self, expr: &Expression, context: &ExecutionContext) -> NotebookResult<String> {
+    /// 按顺序执行单元格中的语句，返回最后一条语句的结果
+    ///
+    /// `名称 = 表达式` 把求得的值存为变量，`f(x) := 表达式` 把函数存入作用域，结果显示为定义本身。
+    fn execute_statements(&mut self, statements: Vec<Statement>, cell_id: CellId, context: &ExecutionContext) -> NotebookResult<String> {
+        let mut output = String::new();
+        for statement in statements {
+            output = match (statement.target, statement.parameters) {
+                (Some(name), Some(parameters)) => {
+                    self.scope_manager.define_function(name.clone(), parameters, statement.expression, cell_id)?;
+                    self.scope_manager.get_function(&name).map(ToString::to_string).unwrap_or_default()
+                }
+                (Some(name), None) => {
+                    let value = self.compute_expression(&statement.expression, context)?;
+                    // 简化实现，实际应该使用格式化器
+                    let formatted = format!("{:?}", value);
+                    self.scope_manager.define_variable(name, Expression::Number(value), cell_id)?;
+                    formatted
+                }
+                (None, _) => format!("{:?}", self.compute_expression(&statement.expression, context)?),
+            };
+        }
+        Ok(output)
+    }
+    
+    /// 计算表达式，先展开用户函数的调用
+    fn compute_expression(&mut self, expr: &Expression, context: &ExecutionContext) -> NotebookResult<Number> {
         // 检查超时
         if context.is_timeout() {
             return Err(NotebookError::Execution(ComputeError::UnsupportedOperation { 
@@ -1496,21 +1588,15 @@ impl ExecutionEngine {
             }));
         }
         
+        let expr = self.scope_manager.expand_functions(expr)?;
+        
         // 导出变量用于计算
         let variables = self.scope_manager.export_for_computation();
         
         // 执行计算
-        match self.yufmath.evaluate(expr, &variables) {
-            Ok(result) => {
-                // 格式化结果
-                let formatted = format!("{:?}", result); // 简化实现，实际应该使用格式化器
-                
-                Ok(formatted)
-            }
-            Err(e) => Err(NotebookError::Execution(ComputeError::UnsupportedOperation { 
-                operation: format!("计算失败: {}", e) 
-            })),
-        }
+        self.yufmath.evaluate(&expr, &variables).map_err(|e| NotebookError::Execution(ComputeError::UnsupportedOperation { 
+            operation: format!("计算失败: {}", e) 
+        }))
     }
     
     /// 批量执行单元格
@@ -1547,16 +1633,8 @@ impl ExecutionEngine {
             return results;
         }
         
-        // 分析依赖关系
-        let dependencies = self.analyze_dependencies_from_cells(cells.values().collect());
-        
-        // 更新依赖图
-        for (cell_id, deps) in &dependencies {
-            self.execution_queue.get_dependency_graph().add_node(*cell_id);
-            for dep_id in deps {
-                self.execution_queue.get_dependency_graph().add_dependency(*cell_id, *dep_id);
-            }
-        }
+        // 分析依赖关系并更新依赖图
+        self.update_dependency_graph(cells.values().collect());
         
         // 获取需要执行的单元格（按拓扑顺序）
         let cells_to_execute = self.execution_queue.get_dependency_graph()
@@ -1731,42 +1809,85 @@ impl ExecutionEngine {
         self.analyze_dependencies_from_cells(cells.iter().collect())
     }
     
+    /// 按单元格当前内容重建它们在依赖图中的依赖，返回分析出的依赖关系
+    pub fn update_dependencies(&mut self, cells: &[NotebookCell]) -> HashMap<CellId, Vec<CellId>> {
+        self.update_dependency_graph(cells.iter().collect())
+    }
+    
+    fn update_dependency_graph(&mut self, cells: Vec<&NotebookCell>) -> HashMap<CellId, Vec<CellId>> {
+        let dependencies = self.analyze_dependencies_from_cells(cells);
+        let graph = self.execution_queue.get_dependency_graph();
+        for (cell_id, deps) in &dependencies {
+            graph.set_dependencies(*cell_id, deps);
+        }
+        dependencies
+    }
+    
+    /// 单元格定义和引用的变量
+    ///
+    /// 定义的是赋值语句和函数定义的左侧；引用的是表达式中的变量，不含同一单元格里先前语句已定义的，
+    /// 也不含函数定义的形参。无法解析时都为空。
+    pub fn cell_variables(&self, cell: &NotebookCell) -> CellVariables {
+        let mut variables = CellVariables::default();
+        if !cell.is_executable() {
+            return variables;
+        }
+        let Ok(statements) = self.yufmath.parse_statements(&cell.get_text()) else {
+            return variables;
+        };
+        
+        for statement in statements {
+            let parameters = statement.parameters.unwrap_or_default();
+            for name in statement.expression.get_variables() {
+                if !variables.defined.contains(&name) && !parameters.contains(&name) {
+                    variables.referenced.insert(name);
+                }
+            }
+            let mut called = HashSet::new();
+            collect_called_functions(&statement.expression, &mut called);
+            variables.called.extend(called.into_iter().filter(|name| !variables.defined.contains(name)));
+            if let Some(target) = statement.target {
+                if !variables.defined.contains(&target) {
+                    variables.defined.push(target);
+                }
+            }
+        }
+        variables
+    }
+    
     /// 从单元格向量分析依赖关系
+    ///
+    /// 单元格依赖定义了它所引用变量或调用函数的单元格；多个单元格定义同一名称时取它之前最近的一个，之前没有时取之后最近的一个。
     fn analyze_dependencies_from_cells(&self, cells: Vec<&NotebookCell>) -> HashMap<CellId, Vec<CellId>> {
-        let mut dependencies = HashMap::new();
+        let variables: Vec<CellVariables> = cells.iter().map(|cell| self.cell_variables(cell)).collect();
         
-        // 收集所有变量定义
-        let mut variable_definitions: HashMap<String, CellId> = HashMap::new();
-        
-        for cell in &cells {
-            if cell.is_executable() {
-                let content = cell.get_text();
-                
-                // 简单的变量定义检测（实际实现需要更复杂的解析）
-                if content.contains('=') && !content.contains("==") {
-                    if let Some(var_name) = content.split('=').next() {
-                        let var_name = var_name.trim().to_string();
-                        variable_definitions.insert(var_name, cell.id);
-                    }
-                }
+        // 变量名 -> 按单元格顺序定义它的单元格下标
+        let mut definitions: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (index, cell_variables) in variables.iter().enumerate() {
+            for name in &cell_variables.defined {
+                definitions.entry(name.as_str()).or_default().push(index);
             }
         }
         
-        // 分析每个单元格的依赖
-        for cell in &cells {
-            if cell.is_executable() {
-                let content = cell.get_text();
-                let mut cell_deps = Vec::new();
-                
-                // 检查使用了哪些变量
-                for (var_name, def_cell_id) in &variable_definitions {
-                    if content.contains(var_name) && *def_cell_id != cell.id {
-                        cell_deps.push(*def_cell_id);
+        let mut dependencies = HashMap::new();
+        for (index, cell) in cells.iter().enumerate() {
+            if !cell.is_executable() {
+                continue;
+            }
+            let mut cell_deps = Vec::new();
+            for name in variables[index].referenced.iter().chain(&variables[index].called) {
+                let Some(definers) = definitions.get(name.as_str()) else {
+                    continue;
+                };
+                let definer = definers.iter().rev().find(|&&other| other < index)
+                    .or_else(|| definers.iter().find(|&&other| other > index));
+                if let Some(&definer) = definer {
+                    if !cell_deps.contains(&cells[definer].id) {
+                        cell_deps.push(cells[definer].id);
                     }
                 }
-                
-                dependencies.insert(cell.id, cell_deps);
             }
+            dependencies.insert(cell.id, cell_deps);
         }
         
         dependencies
@@ -1851,22 +1972,19 @@ impl ExecutionEngine {
             is_running,
             is_cancelled,
             queue_statistics: queue_stats,
-            cache_statistics: cache_stats,
             execution_statistics: self.statistics.clone(),
-            cache_hit_rate: if self.cache_hits + self.cache_misses > 0 {
-                self.cache_hits as f64 / (self.cache_hits + self.cache_misses) as f64
-            } else {
-                0.0
-            },
+            cache_hit_rate: cache_stats.hit_rate,
+            cache_statistics: cache_stats,
         }
     }
     
     /// 保存执行状态到磁盘
     pub fn save_state<P: AsRef<Path>>(&self, path: P) -> NotebookResult<()> {
+        let cache_stats = self.cache.statistics();
         let state = ExecutionEngineState {
             statistics: self.statistics.clone(),
-            cache_hits: self.cache_hits,
-            cache_misses: self.cache_misses,
+            cache_hits: cache_stats.hits,
+            cache_misses: cache_stats.misses,
             config: self.config.clone(),
         };
         
@@ -1894,8 +2012,7 @@ impl ExecutionEngine {
             )))?;
         
         self.statistics = state.statistics;
-        self.cache_hits = state.cache_hits;
-        self.cache_misses = state.cache_misses;
+        self.cache.set_hit_counts(state.cache_hits, state.cache_misses);
         self.update_config(state.config);
         
         Ok(())
@@ -1965,6 +2082,38 @@ impl ExecutionStatistics {
     }
 }
 
+/// 收集表达式中调用的函数名
+fn collect_called_functions(expr: &Expression, names: &mut HashSet<String>) {
+    match expr {
+        Expression::Function { name, args } => {
+            names.insert(name.clone());
+            for arg in args {
+                collect_called_functions(arg, names);
+            }
+        }
+        Expression::BinaryOp { left, right, .. } => {
+            collect_called_functions(left, names);
+            collect_called_functions(right, names);
+        }
+        Expression::UnaryOp { operand, .. } => collect_called_functions(operand, names),
+        Expression::Matrix(rows) => {
+            for expr in rows.iter().flatten() {
+                collect_called_functions(expr, names);
+            }
+        }
+        Expression::Vector(elements) | Expression::Set(elements) => {
+            for expr in elements {
+                collect_called_functions(expr, names);
+            }
+        }
+        Expression::Interval { start, end, .. } => {
+            collect_called_functions(start, names);
+            collect_called_functions(end, names);
+        }
+        Expression::Number(_) | Expression::Variable(_) | Expression::Constant(_) => {}
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2169,7 +2318,7 @@ mod tests {
         let content = "2 + 3";
         
         // 缓存未命中
-        assert!(cache.get(&cell_id, content).is_none());
+        assert!(cache.get(&cell_id, content, 0).is_none());
         
         // 添加到缓存
         let result = ExecutionResult::Success {
@@ -2177,19 +2326,28 @@ mod tests {
             format: FormatType::Standard,
             execution_time: Duration::from_millis(10),
         };
-        cache.put(cell_id, content, result.clone());
+        cache.put(cell_id, content, 0, result.clone());
         
         // 缓存命中
-        let cached = cache.get(&cell_id, content).unwrap();
+        let cached = cache.get(&cell_id, content, 0).unwrap();
         assert_eq!(cached.value(), Some("5"));
         
         // 内容更改后缓存失效
         let new_content = "3 + 4";
-        assert!(cache.get(&cell_id, new_content).is_none());
+        assert!(cache.get(&cell_id, new_content, 0).is_none());
+        
+        // 依赖指纹改变后缓存失效
+        cache.put(cell_id, content, 0, result.clone());
+        assert!(cache.get(&cell_id, content, 1).is_none());
         
         // 测试缓存清理
+        cache.put(cell_id, content, 0, result);
         cache.clear();
-        assert!(cache.get(&cell_id, content).is_none());
+        assert!(cache.get(&cell_id, content, 0).is_none());
+        
+        let stats = cache.statistics();
+        assert_eq!((stats.hits, stats.misses), (1, 4));
+        assert_eq!(stats.hit_rate, 0.2);
     }
     
     #[test]
@@ -2318,6 +2476,96 @@ mod tests {
         assert!(deps.get(&cell3.id).unwrap().contains(&cell2.id));
     }
     
+    #[test]
+    fn test_dependency_analysis_uses_variables() {
+        let engine = ExecutionEngine::new();
+        
+        let vars = engine.cell_variables(&NotebookCell::new_code("t = 2; u = t * x; sin(u)".to_string()));
+        assert_eq!(vars.defined, vec!["t".to_string(), "u".to_string()]);
+        assert_eq!(vars.referenced, HashSet::from(["x".to_string()]));
+        
+        // 按变量名而非子串匹配：abc 不引用 a，== 比较不是定义
+        let a = NotebookCell::new_code("a = 1".to_string());
+        let abc = NotebookCell::new_code("abc + 1".to_string());
+        let compare = NotebookCell::new_code("a == 1".to_string());
+        let b = NotebookCell::new_code("b = a + 1 # 注释里的 c 不算".to_string());
+        let c = NotebookCell::new_code("c = 3".to_string());
+        let deps = engine.analyze_dependencies(&[a.clone(), abc.clone(), compare.clone(), b.clone(), c.clone()]);
+        assert!(deps[&abc.id].is_empty());
+        assert_eq!(deps[&compare.id], vec![a.id]);
+        assert_eq!(deps[&b.id], vec![a.id]);
+        assert!(deps[&c.id].is_empty());
+        
+        // 重复定义时依赖之前最近的定义
+        let a2 = NotebookCell::new_code("a = 2".to_string());
+        let user = NotebookCell::new_code("a * 3".to_string());
+        let deps = engine.analyze_dependencies(&[a.clone(), a2.clone(), user.clone()]);
+        assert_eq!(deps[&user.id], vec![a2.id]);
+    }
+    
+    #[test]
+    fn test_modified_definition_marks_users() {
+        let mut engine = ExecutionEngine::new();
+        
+        let mut a = NotebookCell::new_code("a = 1".to_string());
+        let b = NotebookCell::new_code("a + 1".to_string());
+        let c = NotebookCell::new_code("z = 5".to_string());
+        engine.update_dependencies(&[a.clone(), b.clone(), c.clone()]);
+        
+        let graph = engine.execution_queue.get_dependency_graph();
+        for cell in [&a, &b, &c] {
+            graph.mark_executed(&cell.id);
+        }
+        
+        // 修改 A 后 B 被标记需重算，C 不受影响
+        a.set_text("a = 2".to_string());
+        let to_execute = graph.get_cells_to_execute(&HashSet::from([a.id]));
+        assert_eq!(to_execute, vec![a.id, b.id]);
+        assert!(graph.needs_execution(&b.id));
+        assert!(!graph.needs_execution(&c.id));
+        
+        // 改为不再引用 a 后依赖随之移除
+        let mut b = b;
+        b.set_text("7".to_string());
+        engine.update_dependencies(&[a.clone(), b.clone(), c.clone()]);
+        assert!(engine.execution_queue.get_dependency_graph().get_dependencies(&b.id).is_empty());
+    }
+    
+    #[test]
+    fn test_user_function_definition() {
+        let mut engine = ExecutionEngine::new();
+        let mut run = |text: &str| {
+            let mut cell = NotebookCell::new_code(text.to_string());
+            engine.execute_cell(&mut cell).unwrap()
+        };
+        
+        let definition = run("f(x) := x^2 + 1");
+        assert_eq!(definition.value(), Some("f(x) := x^2 + 1"));
+        assert_eq!(run("f(3)").value(), Some(format!("{:?}", Number::integer(10)).as_str()));
+        
+        // 多个形参同时代入，函数体可以调用其他函数并引用变量
+        run("a = 2");
+        run("g(x, y) := a*f(y) - x");
+        assert_eq!(run("g(1, 2) + f(0)").value(), Some(format!("{:?}", Number::integer(10)).as_str()));
+        // 重新定义后使用新的函数体
+        run("f(x) := 2*x");
+        assert_eq!(run("g(1, 2)").value(), Some(format!("{:?}", Number::integer(7)).as_str()));
+        
+        // 参数个数不符与递归定义报错
+        assert!(matches!(run("f(1, 2)"), ExecutionResult::Error { .. }));
+        run("h(x) := h(x) + 1");
+        assert!(matches!(run("h(1)"), ExecutionResult::Error { .. }));
+        
+        // 调用函数的单元格依赖定义它的单元格，形参不算引用
+        let define = NotebookCell::new_code("k(t) := t + b".to_string());
+        let call = NotebookCell::new_code("k(1)".to_string());
+        let vars = engine.cell_variables(&define);
+        assert_eq!(vars.defined, vec!["k".to_string()]);
+        assert_eq!(vars.referenced, HashSet::from(["b".to_string()]));
+        let deps = engine.analyze_dependencies(&[define.clone(), call.clone()]);
+        assert_eq!(deps[&call.id], vec![define.id]);
+    }
+    
     #[tokio::test]
     async fn test_async_execution() {
         let mut engine = ExecutionEngine::new();
@@ -1,8 +1,13 @@
 //! # 笔记本导出功能
 //!
 //! 提供将笔记本导出为不同格式的功能。
+//!
+//! 除文档格式外还可以导出为可执行脚本：[`ExportFormat::Batch`] 每个代码单元格占一行，
+//! 可由 `yufmath batch` 执行；[`ExportFormat::Python`] 转换为 SymPy 代码。Markdown 与文本单元格保留为注释。
 
-use super::{Notebook, NotebookCell, CellType, NotebookError, NotebookResult};
+use super::{Notebook, NotebookCell, CellType, NotebookError, NotebookResult, MarkdownDocument, ScopeManager};
+use crate::formatter::{Formatter, SymPyFormatter};
+use crate::parser::{Parser, lexer, syntax::ExpressionParser};
 use std::fs;
 use std::path::Path;
 
@@ -19,6 +24,10 @@ pub enum ExportFormat {
     Code,
     /// LaTeX 格式
     Latex,
+    /// Yufmath 批处理脚本
+    Batch,
+    /// 使用 SymPy 的 Python 脚本
+    Python,
 }
 
 impl ExportFormat {
@@ -30,6 +39,8 @@ impl ExportFormat {
             ExportFormat::Markdown => "md",
             ExportFormat::Code => "txt",
             ExportFormat::Latex => "tex",
+            ExportFormat::Batch => "yuf",
+            ExportFormat::Python => "py",
         }
     }
     
@@ -41,6 +52,8 @@ impl ExportFormat {
             ExportFormat::Markdown => "text/markdown",
             ExportFormat::Code => "text/plain",
             ExportFormat::Latex => "application/x-latex",
+            ExportFormat::Batch => "text/plain",
+            ExportFormat::Python => "text/x-python",
         }
     }
     
@@ -52,6 +65,8 @@ impl ExportFormat {
             ExportFormat::Markdown => "Markdown",
             ExportFormat::Code => "代码",
             ExportFormat::Latex => "LaTeX",
+            ExportFormat::Batch => "批处理脚本",
+            ExportFormat::Python => "Python (SymPy)",
         }
     }
 }
@@ -163,6 +178,8 @@ impl NotebookExporter {
             ExportFormat::Markdown => self.export_to_markdown(notebook),
             ExportFormat::Code => self.export_to_code(notebook),
             ExportFormat::Latex => self.export_to_latex(notebook),
+            ExportFormat::Batch => self.export_to_batch(notebook),
+            ExportFormat::Python => self.export_to_python(notebook),
         }
     }
     
@@ -288,7 +305,7 @@ impl NotebookExporter {
             }
             CellType::Markdown => {
                 html.push_str("            <div class=\"cell-content\">\n");
-                html.push_str(&self.render_markdown(&cell.get_text()));
+                html.push_str(&MarkdownDocument::parse(&cell.get_text()).to_html());
                 html.push_str("            </div>\n");
             }
             CellType::Text => {
@@ -347,7 +364,7 @@ impl NotebookExporter {
                     }
                 }
                 CellType::Markdown => {
-                    md.push_str(&cell.get_text());
+                    md.push_str(&MarkdownDocument::parse(&cell.get_text()).to_markdown());
                     md.push_str("\n\n");
                 }
                 CellType::Text => {
@@ -395,6 +412,141 @@ impl NotebookExporter {
         Ok(code)
     }
     
+    /// 导出为批处理脚本：代码单元格去掉注释后各占一行，多行语句以分号连接
+    fn export_to_batch(&self, notebook: &Notebook) -> NotebookResult<String> {
+        let mut script = self.script_header(notebook);
+        
+        for (index, cell) in notebook.cells.iter().enumerate() {
+            match cell.cell_type {
+                CellType::Code => {
+                    let source = lexer::strip_comments(&cell.get_text());
+                    let statements: Vec<&str> = source.lines()
+                        .map(|line| line.trim().trim_end_matches(';').trim_end())
+                        .filter(|line| !line.is_empty())
+                        .collect();
+                    if statements.is_empty() {
+                        continue;
+                    }
+                    if self.options.include_metadata {
+                        script.push_str(&format!("# 单元格 {}\n", index + 1));
+                    }
+                    script.push_str(&statements.join("; "));
+                    script.push('\n');
+                    self.push_output_comment(&mut script, cell);
+                }
+                CellType::Markdown | CellType::Text => self.push_comment(&mut script, &cell.get_text()),
+                CellType::Output => {}
+            }
+        }
+        
+        Ok(script)
+    }
+    
+    /// 导出为 Python 脚本：变量声明为 SymPy 符号，赋值保留，其余语句打印结果
+    fn export_to_python(&self, notebook: &Notebook) -> NotebookResult<String> {
+        let parser = ExpressionParser::new();
+        let formatter = SymPyFormatter::new();
+        
+        // 先转换全部代码单元格，收集需要声明的符号
+        let mut functions = ScopeManager::new();
+        let mut symbols = Vec::new();
+        let mut cells = Vec::new();
+        for (index, cell) in notebook.cells.iter().enumerate() {
+            if cell.cell_type != CellType::Code {
+                cells.push((index, cell, Vec::new()));
+                continue;
+            }
+            // 与批处理一样逐行解析，一行内可以有多条以分号分隔的语句
+            let source = lexer::strip_comments(&cell.get_text());
+            let mut statements = Vec::new();
+            for line in source.lines().filter(|line| !line.trim().is_empty()) {
+                statements.extend(parser.parse_statements(line).map_err(|e| {
+                    NotebookError::Format(format!("单元格 {} 无法转换为 Python: {}", index + 1, e))
+                })?);
+            }
+            let mut lines = Vec::new();
+            for statement in statements {
+                // 函数定义保留为注释，调用处展开为函数体
+                if let (Some(name), Some(parameters)) = (&statement.target, &statement.parameters) {
+                    functions.define_function(name.clone(), parameters.clone(), statement.expression.clone(), cell.id)?;
+                    lines.push(format!("# {}({}) := {}", name, parameters.join(", "), statement.expression));
+                    continue;
+                }
+                let expression = functions.expand_functions(&statement.expression)?;
+                symbols.extend(expression.get_variables());
+                let code = formatter.format(&expression);
+                lines.push(match (statement.target, statement.quiet) {
+                    (Some(target), _) => format!("{} = {}", target, code),
+                    (None, true) => code,
+                    (None, false) => format!("print({})", code),
+                });
+            }
+            cells.push((index, cell, lines));
+        }
+        symbols.sort();
+        symbols.dedup();
+        
+        let mut script = self.script_header(notebook);
+        script.push_str("import sympy as sp\n\n");
+        if !symbols.is_empty() {
+            script.push_str(&format!("{} = sp.symbols(\"{}\")\n\n", symbols.join(", "), symbols.join(" ")));
+        }
+        
+        for (index, cell, lines) in cells {
+            match cell.cell_type {
+                CellType::Code if !lines.is_empty() => {
+                    if self.options.include_metadata {
+                        script.push_str(&format!("# 单元格 {}\n", index + 1));
+                    }
+                    for line in lines {
+                        script.push_str(&line);
+                        script.push('\n');
+                    }
+                    self.push_output_comment(&mut script, cell);
+                }
+                CellType::Markdown | CellType::Text => self.push_comment(&mut script, &cell.get_text()),
+                _ => {}
+            }
+        }
+        
+        Ok(script)
+    }
+    
+    /// 脚本开头的标题与作者注释
+    fn script_header(&self, notebook: &Notebook) -> String {
+        let mut header = String::new();
+        if self.options.include_metadata {
+            header.push_str(&format!("# {}\n", notebook.metadata.title));
+            if let Some(author) = &notebook.metadata.author {
+                header.push_str(&format!("# 作者: {}\n", author));
+            }
+            header.push('\n');
+        }
+        header
+    }
+    
+    /// 把 Markdown 或文本逐行写成 `#` 注释，空行保留为单独的 `#`
+    fn push_comment(&self, script: &mut String, text: &str) {
+        for line in text.lines() {
+            if line.trim().is_empty() {
+                script.push_str("#\n");
+            } else {
+                script.push_str(&format!("# {}\n", line));
+            }
+        }
+        script.push('\n');
+    }
+    
+    /// 按选项把单元格的输出写成注释
+    fn push_output_comment(&self, script: &mut String, cell: &NotebookCell) {
+        if self.options.include_outputs {
+            if let Some(output) = cell.get_output() {
+                script.push_str(&format!("# 输出: {}\n", output.get_text()));
+            }
+        }
+        script.push('\n');
+    }
+    
     /// 导出为 LaTeX
     fn export_to_latex(&self, notebook: &Notebook) -> NotebookResult<String> {
         let mut latex = String::new();
@@ -539,6 +691,16 @@ impl NotebookExporter {
             color: #24292e;
             margin-bottom: 30px;
         }
+        
+        .math-error {
+            color: #cb2431;
+            font-family: 'SFMono-Regular', Consolas, 'Liberation Mono', Menlo, monospace;
+        }
+        
+        .math-error mark {
+            background-color: #ffdce0;
+            color: inherit;
+        }
         "#
     }
     
@@ -565,27 +727,6 @@ impl NotebookExporter {
             .replace('~', "\\textasciitilde{}")
     }
     
-    /// 简单的 Markdown 渲染
-    fn render_markdown(&self, text: &str) -> String {
-        let mut html = String::new();
-        
-        for line in text.lines() {
-            if line.starts_with("# ") {
-                html.push_str(&format!("                <h1>{}</h1>\n", &line[2..]));
-            } else if line.starts_with("## ") {
-                html.push_str(&format!("                <h2>{}</h2>\n", &line[3..]));
-            } else if line.starts_with("### ") {
-                html.push_str(&format!("                <h3>{}</h3>\n", &line[4..]));
-            } else if line.trim().is_empty() {
-                html.push_str("                <br>\n");
-            } else {
-                html.push_str(&format!("                <p>{}</p>\n", self.escape_html(line)));
-            }
-        }
-        
-        html
-    }
-    
     /// 将 Markdown 转换为 LaTeX
     fn convert_markdown_to_latex(&self, text: &str) -> String {
         let mut latex = String::new();
@@ -625,6 +766,40 @@ mod tests {
         
         assert_eq!(ExportFormat::Markdown.extension(), "md");
         assert_eq!(ExportFormat::Pdf.extension(), "pdf");
+        assert_eq!(ExportFormat::Batch.extension(), "yuf");
+        assert_eq!(ExportFormat::Python.extension(), "py");
+    }
+    
+    #[test]
+    fn test_script_export() {
+        let exporter = NotebookExporter::new();
+        let mut notebook = Notebook::with_title("脚本导出".to_string());
+        notebook.add_cell(crate::NotebookCell::new_markdown("## 定义\n\n设 $f = x^2$".to_string()));
+        notebook.add_cell(crate::NotebookCell::new_code("f = x^2 + 1/2 # 抛物线\nh = sin(f);".to_string()));
+        notebook.add_cell(crate::NotebookCell::new_code("diff(f, x)".to_string()));
+        
+        // 批处理脚本：每个代码单元格一行，Markdown 变为注释
+        let batch = exporter.export_to_string(&notebook, ExportFormat::Batch).unwrap();
+        assert_eq!(
+            batch,
+            "# 脚本导出\n\n# ## 定义\n#\n# 设 $f = x^2$\n\n# 单元格 2\nf = x^2 + 1/2; h = sin(f)\n\n# 单元格 3\ndiff(f, x)\n\n"
+        );
+        
+        // Python 脚本：声明符号，转换语法
+        let python = exporter.export_to_string(&notebook, ExportFormat::Python).unwrap();
+        assert!(python.starts_with("# 脚本导出\n\nimport sympy as sp\n\nf, x = sp.symbols(\"f x\")\n\n# ## 定义\n"), "{}", python);
+        assert!(python.contains("f = x ** 2 + sp.Integer(1) / 2\nh = sp.sin(f)\n"), "{}", python);
+        assert!(python.contains("print(sp.diff(f, x))\n"), "{}", python);
+        
+        // 函数定义保留为注释，调用处展开，形参不声明为符号
+        notebook.add_cell(crate::NotebookCell::new_code("p(t) := t^2 + x; p(3)".to_string()));
+        let python = exporter.export_to_string(&notebook, ExportFormat::Python).unwrap();
+        assert!(python.contains("f, x = sp.symbols(\"f x\")\n"), "{}", python);
+        assert!(python.contains("# p(t) := t^2 + x\nprint(sp.Integer(3) ** 2 + x)\n"), "{}", python);
+        
+        // 无法解析的单元格报错
+        notebook.add_cell(crate::NotebookCell::new_code("x + )".to_string()));
+        assert!(exporter.export_to_string(&notebook, ExportFormat::Python).is_err());
     }
     
     #[test]
@@ -670,6 +845,21 @@ mod tests {
         assert!(html.contains("MathJax")); // 默认使用 MathJax
     }
     
+    #[test]
+    fn test_markdown_cell_export() {
+        let exporter = NotebookExporter::new();
+        let mut notebook = Notebook::with_title("Markdown 测试".to_string());
+        notebook.add_cell(crate::NotebookCell::new_markdown("## 推导\n\n- **设** $x^{2}$\n- 错误 $x + )$".to_string()));
+
+        let html = exporter.export_to_html(&notebook).unwrap();
+        assert!(html.contains("<h2>推导</h2>"));
+        assert!(html.contains("<li><strong>设</strong> \\(x^{2}\\)</li>"));
+        assert!(html.contains("<span class=\"math-error\""));
+
+        let md = exporter.export_to_markdown(&notebook).unwrap();
+        assert!(md.contains("## 推导\n\n- **设** $x^{2}$\n- 错误 $x + )$"));
+    }
+
     #[test]
     fn test_latex_export() {
         let exporter = NotebookExporter::new();
@@ -2,9 +2,12 @@
 //!
 //! 基于 FLTK 实现的笔记本交互界面，类似 Jupyter 的文本模式。
 
-use super::{Notebook, NotebookCell, CellId, CellType, ExecutionEngine, NotebookError, NotebookResult, AutoCompleteEngine, CompletionSuggestion};
+use super::{Notebook, NotebookCell, CellId, CellType, ExecutionEngine, NotebookError, NotebookResult, AutoCompleteEngine, CompletionSuggestion, MarkdownDocument};
 use fltk::{prelude::*, *};
+use std::cell::Cell;
 use std::collections::HashMap;
+use std::rc::Rc;
+use crate::api::format_duration;
 
 /// 单元格编辑器组件
 pub struct CellEditor {
@@ -26,6 +29,8 @@ pub struct CellEditor {
     syntax_buffer: Option<text::TextBuffer>,
     /// 自动补全引擎
     autocomplete_engine: AutoCompleteEngine,
+    /// 是否为 Markdown 单元格，与按键回调共享
+    is_markdown: Rc<Cell<bool>>,
 }
 
 impl CellEditor {
@@ -71,9 +76,14 @@ impl CellEditor {
             is_selected: false,
             syntax_buffer: None,
             autocomplete_engine: AutoCompleteEngine::new(),
+            is_markdown: Rc::new(Cell::new(cell.cell_type == CellType::Markdown)),
         };
         
         cell_editor.setup_autocomplete();
+        cell_editor.setup_markdown_toggle();
+        if cell_editor.cell_type == CellType::Markdown && !cell.get_text().is_empty() {
+            cell_editor.set_rendered(true);
+        }
         cell_editor
     }
     
@@ -104,6 +114,10 @@ impl CellEditor {
         if let Some(mut buffer) = self.output_display.buffer() {
             buffer.set_text(text);
         }
+        // 去掉渲染 Markdown 时留下的样式
+        if let Some(style_buffer) = self.output_display.style_buffer() {
+            self.output_display.unset_highlight_data(style_buffer);
+        }
     }
     
     /// 设置选中状态
@@ -124,7 +138,11 @@ impl CellEditor {
     
     /// 设置单元格类型
     pub fn set_cell_type(&mut self, cell_type: CellType) {
+        if cell_type != CellType::Markdown && self.is_rendered() {
+            self.set_rendered(false);
+        }
         self.cell_type = cell_type.clone();
+        self.is_markdown.set(cell_type == CellType::Markdown);
         self.type_label.set_label(&format!("[{}]", cell_type.display_name()));
         self.setup_syntax_highlighting();
         self.type_label.redraw();
@@ -192,6 +210,41 @@ impl CellEditor {
         &mut self.editor
     }
     
+    /// Markdown 单元格是否处于渲染状态
+    pub fn is_rendered(&self) -> bool {
+        self.cell_type == CellType::Markdown && !self.editor.visible()
+    }
+    
+    /// 切换 Markdown 单元格的渲染与编辑状态，其他类型的单元格不受影响
+    pub fn set_rendered(&mut self, rendered: bool) {
+        if self.cell_type == CellType::Markdown {
+            show_markdown(&mut self.editor, &mut self.output_display, rendered);
+        }
+    }
+    
+    /// 编辑器中按 Esc 渲染 Markdown，在渲染结果上按 Enter 回到编辑
+    fn setup_markdown_toggle(&mut self) {
+        let mut display = self.output_display.clone();
+        let is_markdown = self.is_markdown.clone();
+        self.editor.handle(move |editor, event| {
+            if event == enums::Event::KeyDown && app::event_key() == enums::Key::Escape && is_markdown.get() {
+                show_markdown(editor, &mut display, true);
+                return true;
+            }
+            false
+        });
+        
+        let mut editor = self.editor.clone();
+        let is_markdown = self.is_markdown.clone();
+        self.output_display.handle(move |display, event| {
+            if event == enums::Event::KeyDown && app::event_key() == enums::Key::Enter && is_markdown.get() {
+                show_markdown(&mut editor, display, false);
+                return true;
+            }
+            false
+        });
+    }
+    
     /// 设置自动补全
     fn setup_autocomplete(&mut self) {
         // 简化的自动补全设置
@@ -253,6 +306,37 @@ impl CellEditor {
     }
 }
 
+/// 在 Markdown 源码编辑器与渲染结果之间切换；渲染结果显示在输出区域，
+/// 出错的公式与其后的错误信息标红，出错位置以红底白字标出
+fn show_markdown(editor: &mut text::TextEditor, display: &mut text::TextDisplay, rendered: bool) {
+    if rendered {
+        let source = editor.buffer().map(|buffer| buffer.text()).unwrap_or_default();
+        let (text, styles) = MarkdownDocument::parse(&source).to_styled_text();
+        if let Some(mut buffer) = display.buffer() {
+            buffer.set_text(&text);
+        }
+        let mut style_buffer = text::TextBuffer::default();
+        style_buffer.set_text(&styles);
+        display.set_highlight_data_ext(style_buffer, markdown_styles(display.text_font(), display.text_size()));
+        editor.hide();
+        let _ = display.take_focus();
+    } else {
+        editor.show();
+        let _ = editor.take_focus();
+    }
+    display.redraw();
+}
+
+/// 渲染后的 Markdown 样式表，依次对应 [`MarkdownDocument::to_styled_text`] 样式串中的 `A`、`B`、`C`
+fn markdown_styles(font: enums::Font, size: i32) -> Vec<text::StyleTableEntryExt> {
+    let entry = |color, attr, bgcolor| text::StyleTableEntryExt { color, font, size, attr, bgcolor };
+    vec![
+        entry(enums::Color::Foreground, text::TextAttr::None, enums::Color::Background2),
+        entry(enums::Color::Red, text::TextAttr::None, enums::Color::Background2),
+        entry(enums::Color::White, text::TextAttr::BgColor, enums::Color::Red),
+    ]
+}
+
 /// 笔记本 GUI 主窗口
 pub struct NotebookGUI {
     /// 主窗口
@@ -601,7 +685,7 @@ impl NotebookGUI {
                     match &result {
                         super::ExecutionResult::Success { value, execution_time, .. } => {
                             editor.set_output_text(&value);
-                            self.set_status(&format!("执行成功 ({:.2}ms)", execution_time.as_millis()));
+                            self.set_status(&format!("执行成功 ({})", format_duration(*execution_time)));
                         }
                         super::ExecutionResult::Error { error, .. } => {
                             editor.set_output_text(&format!("错误: {}", error));
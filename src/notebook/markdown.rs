@@ -0,0 +1,671 @@
+//! # Markdown 单元格渲染
+//!
+//! 轻量的 Markdown 子集：`#` 标题、有序与无序列表、段落、围栏代码块、`$$` 公式块，
+//! 以及行内的粗体、斜体、行内代码与 `$...$` 公式。
+//!
+//! 公式交给 [`LaTeXParser`] 校验，再由 [`LaTeXFormatter`] 规范化；解析失败的公式保留原文，
+//! 并记下出错位置，渲染时标红。
+
+use crate::formatter::{Formatter, latex::LaTeXFormatter};
+use crate::parser::{Parser, latex::LaTeXParser};
+
+/// 可用反斜杠转义的字符
+const ESCAPABLE: &[char] = &['\\', '*', '_', '`', '$', '#'];
+
+/// 样式串中的普通文本
+pub const STYLE_NORMAL: char = 'A';
+/// 样式串中出错的公式
+pub const STYLE_MATH_ERROR: char = 'B';
+/// 样式串中公式的出错位置
+pub const STYLE_ERROR_POSITION: char = 'C';
+
+/// 带样式渲染时标出错误范围的定界符，取自私用区，不会出现在正文中
+const MARK_ERROR_START: char = '\u{E000}';
+const MARK_POSITION_START: char = '\u{E001}';
+const MARK_POSITION_END: char = '\u{E002}';
+const MARK_ERROR_END: char = '\u{E003}';
+
+/// 文本渲染方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum TextMode {
+    /// 纯文本
+    Plain,
+    /// ANSI 转义序列
+    Ansi,
+    /// 纯文本，出错的公式以私用区字符定界，供 [`MarkdownDocument::to_styled_text`] 转成样式串
+    Marked,
+}
+
+/// 公式解析错误
+#[derive(Debug, Clone, PartialEq)]
+pub struct MathError {
+    /// 出错位置，为公式原文中的字符下标；输入意外结束时为 `None`
+    pub position: Option<usize>,
+    /// 错误信息
+    pub message: String,
+}
+
+/// 行内公式或独立公式
+#[derive(Debug, Clone, PartialEq)]
+pub struct MathSpan {
+    /// 定界符之间的原文
+    pub source: String,
+    /// 是否为独立公式（`$$...$$`）
+    pub display: bool,
+    /// 规范化后的 LaTeX，或解析错误
+    pub result: Result<String, MathError>,
+}
+
+impl MathSpan {
+    /// 解析并规范化公式
+    pub fn new(source: &str, display: bool) -> Self {
+        let source = source.trim().to_string();
+        let result = LaTeXParser::new().parse(&source)
+            .map(|expr| LaTeXFormatter::new().format(&expr))
+            .map_err(|e| MathError {
+                position: e.position().and_then(|byte| source.get(..byte)).map(|s| s.chars().count()),
+                message: e.to_string(),
+            });
+        Self { source, display, result }
+    }
+
+    /// 公式是否解析成功
+    pub fn is_valid(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    /// 规范化后的公式，解析失败时为原文
+    pub fn latex(&self) -> &str {
+        self.result.as_deref().unwrap_or(&self.source)
+    }
+
+    /// 以出错位置把原文拆成三段，出错处在末尾时用空格占位
+    fn split_at_error(&self, position: Option<usize>) -> (String, String, String) {
+        let chars: Vec<char> = self.source.chars().collect();
+        let at = position.unwrap_or(chars.len()).min(chars.len());
+        let before = chars[..at].iter().collect();
+        match chars.get(at) {
+            Some(c) => (before, c.to_string(), chars[at + 1..].iter().collect()),
+            None => (before, " ".to_string(), String::new()),
+        }
+    }
+}
+
+/// 行内元素
+#[derive(Debug, Clone, PartialEq)]
+pub enum Inline {
+    /// 普通文本
+    Text(String),
+    /// 粗体
+    Strong(Vec<Inline>),
+    /// 斜体
+    Emphasis(Vec<Inline>),
+    /// 行内代码
+    Code(String),
+    /// 公式
+    Math(MathSpan),
+}
+
+/// 块级元素
+#[derive(Debug, Clone, PartialEq)]
+pub enum Block {
+    /// 标题，级别为 1 到 6
+    Heading { level: usize, content: Vec<Inline> },
+    /// 段落
+    Paragraph(Vec<Inline>),
+    /// 列表
+    List { ordered: bool, items: Vec<Vec<Inline>> },
+    /// 围栏代码块
+    Code { language: String, code: String },
+    /// 独立公式
+    Math(MathSpan),
+}
+
+/// 解析后的 Markdown 文档
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarkdownDocument {
+    /// 块级元素
+    pub blocks: Vec<Block>,
+}
+
+impl MarkdownDocument {
+    /// 解析 Markdown 文本
+    pub fn parse(text: &str) -> Self {
+        let lines: Vec<&str> = text.lines().collect();
+        let mut blocks = Vec::new();
+        let mut paragraph = Vec::new();
+        let mut i = 0;
+
+        while i < lines.len() {
+            let line = lines[i].trim();
+            i += 1;
+
+            if line.is_empty() {
+                flush_paragraph(&mut paragraph, &mut blocks);
+            } else if let Some(language) = line.strip_prefix("```") {
+                flush_paragraph(&mut paragraph, &mut blocks);
+                let start = i;
+                while i < lines.len() && !lines[i].trim_start().starts_with("```") {
+                    i += 1;
+                }
+                let code = lines[start..i].join("\n");
+                i += 1;
+                blocks.push(Block::Code { language: language.trim().to_string(), code });
+            } else if let Some(rest) = line.strip_prefix("$$") {
+                flush_paragraph(&mut paragraph, &mut blocks);
+                let source = match rest.strip_suffix("$$") {
+                    Some(body) => body.to_string(),
+                    None => {
+                        let mut source = rest.to_string();
+                        while i < lines.len() {
+                            let line = lines[i].trim();
+                            i += 1;
+                            if let Some(body) = line.strip_suffix("$$") {
+                                source.push('\n');
+                                source.push_str(body);
+                                break;
+                            }
+                            source.push('\n');
+                            source.push_str(line);
+                        }
+                        source
+                    }
+                };
+                blocks.push(Block::Math(MathSpan::new(&source, true)));
+            } else if let Some((level, title)) = heading(line) {
+                flush_paragraph(&mut paragraph, &mut blocks);
+                blocks.push(Block::Heading { level, content: parse_inline(title) });
+            } else if let Some((ordered, item)) = list_item(line) {
+                flush_paragraph(&mut paragraph, &mut blocks);
+                match blocks.last_mut() {
+                    Some(Block::List { ordered: kind, items }) if *kind == ordered => items.push(parse_inline(item)),
+                    _ => blocks.push(Block::List { ordered, items: vec![parse_inline(item)] }),
+                }
+            } else {
+                paragraph.push(line);
+            }
+        }
+        flush_paragraph(&mut paragraph, &mut blocks);
+
+        Self { blocks }
+    }
+
+    /// 所有解析失败的公式
+    pub fn math_errors(&self) -> Vec<&MathSpan> {
+        let mut errors = Vec::new();
+        for block in &self.blocks {
+            match block {
+                Block::Heading { content, .. } | Block::Paragraph(content) => collect_math_errors(content, &mut errors),
+                Block::List { items, .. } => items.iter().for_each(|item| collect_math_errors(item, &mut errors)),
+                Block::Math(span) if !span.is_valid() => errors.push(span),
+                Block::Math(_) | Block::Code { .. } => {}
+            }
+        }
+        errors
+    }
+
+    /// 渲染为 HTML，公式写成 `\(...\)` 与 `\[...\]` 交给 MathJax/KaTeX
+    pub fn to_html(&self) -> String {
+        let mut html = String::new();
+        for block in &self.blocks {
+            match block {
+                Block::Heading { level, content } => {
+                    html.push_str(&format!("<h{0}>{1}</h{0}>\n", level, inlines_to_html(content)));
+                }
+                Block::Paragraph(content) => {
+                    html.push_str(&format!("<p>{}</p>\n", inlines_to_html(content)));
+                }
+                Block::List { ordered, items } => {
+                    let tag = if *ordered { "ol" } else { "ul" };
+                    html.push_str(&format!("<{}>\n", tag));
+                    for item in items {
+                        html.push_str(&format!("<li>{}</li>\n", inlines_to_html(item)));
+                    }
+                    html.push_str(&format!("</{}>\n", tag));
+                }
+                Block::Code { language, code } => {
+                    let class = if language.is_empty() { String::new() } else { format!(" class=\"language-{}\"", escape_html(language)) };
+                    html.push_str(&format!("<pre><code{}>{}</code></pre>\n", class, escape_html(code)));
+                }
+                Block::Math(span) => {
+                    html.push_str(&format!("<div class=\"math-display\">{}</div>\n", math_to_html(span)));
+                }
+            }
+        }
+        html
+    }
+
+    /// 重新生成 Markdown，公式替换为规范化后的写法，解析失败的公式保留原文
+    pub fn to_markdown(&self) -> String {
+        let blocks: Vec<String> = self.blocks.iter().map(|block| match block {
+            Block::Heading { level, content } => format!("{} {}", "#".repeat(*level), inlines_to_markdown(content)),
+            Block::Paragraph(content) => inlines_to_markdown(content),
+            Block::List { ordered, items } => items.iter().enumerate().map(|(index, item)| {
+                let marker = if *ordered { format!("{}.", index + 1) } else { "-".to_string() };
+                format!("{} {}", marker, inlines_to_markdown(item))
+            }).collect::<Vec<_>>().join("\n"),
+            Block::Code { language, code } => format!("```{}\n{}\n```", language, code),
+            Block::Math(span) => format!("$$\n{}\n$$", span.latex()),
+        }).collect();
+        blocks.join("\n\n")
+    }
+
+    /// 渲染为带 ANSI 样式的终端文本，公式显示为等宽的规范化 LaTeX，出错的公式标红并反色标出位置
+    pub fn to_terminal(&self) -> String {
+        self.render_text(TextMode::Ansi)
+    }
+
+    /// 渲染为纯文本，出错的公式后附上错误信息
+    pub fn to_plain_text(&self) -> String {
+        self.render_text(TextMode::Plain)
+    }
+
+    /// 渲染为纯文本及与之逐字节对应的样式串，供 GUI 的样式缓冲区使用
+    ///
+    /// 样式串中 [`STYLE_NORMAL`] 为普通文本，[`STYLE_MATH_ERROR`] 为出错的公式及其错误信息，
+    /// [`STYLE_ERROR_POSITION`] 为公式中的出错位置。
+    pub fn to_styled_text(&self) -> (String, String) {
+        let marked = self.render_text(TextMode::Marked);
+        let mut text = String::with_capacity(marked.len());
+        let mut styles = String::with_capacity(marked.len());
+        let mut current = STYLE_NORMAL;
+        for c in marked.chars() {
+            current = match c {
+                MARK_ERROR_START | MARK_POSITION_END => STYLE_MATH_ERROR,
+                MARK_POSITION_START => STYLE_ERROR_POSITION,
+                MARK_ERROR_END => STYLE_NORMAL,
+                _ => {
+                    text.push(c);
+                    styles.extend(std::iter::repeat_n(current, c.len_utf8()));
+                    continue;
+                }
+            };
+        }
+        (text, styles)
+    }
+
+    fn render_text(&self, mode: TextMode) -> String {
+        let ansi = mode == TextMode::Ansi;
+        let blocks: Vec<String> = self.blocks.iter().map(|block| match block {
+            Block::Heading { level, content } => {
+                let title = inlines_to_text(content, mode);
+                if ansi {
+                    style(&title, "1;4", true)
+                } else if *level <= 2 {
+                    let width: usize = plain_inlines(content).chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum();
+                    format!("{}\n{}", title, if *level == 1 { "=" } else { "-" }.repeat(width))
+                } else {
+                    title
+                }
+            }
+            Block::Paragraph(content) => inlines_to_text(content, mode),
+            Block::List { ordered, items } => items.iter().enumerate().map(|(index, item)| {
+                let marker = if *ordered { format!("{}.", index + 1) } else { "•".to_string() };
+                format!("  {} {}", marker, inlines_to_text(item, mode))
+            }).collect::<Vec<_>>().join("\n"),
+            Block::Code { code, .. } => code.lines()
+                .map(|line| format!("    {}", style(line, "36", ansi)))
+                .collect::<Vec<_>>().join("\n"),
+            Block::Math(span) => format!("    {}", math_to_text(span, mode)),
+        }).collect();
+        blocks.join("\n\n")
+    }
+}
+
+/// 把累积的行合并为一个段落
+fn flush_paragraph(paragraph: &mut Vec<&str>, blocks: &mut Vec<Block>) {
+    if !paragraph.is_empty() {
+        blocks.push(Block::Paragraph(parse_inline(&paragraph.join(" "))));
+        paragraph.clear();
+    }
+}
+
+/// `# 标题`，井号后须有空格
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|&c| c == '#').count();
+    if !(1..=6).contains(&level) {
+        return None;
+    }
+    let rest = &line[level..];
+    if rest.is_empty() || rest.starts_with(' ') {
+        Some((level, rest.trim()))
+    } else {
+        None
+    }
+}
+
+/// `- 项`、`* 项`、`+ 项` 或 `1. 项`，返回是否有序与项的内容
+fn list_item(line: &str) -> Option<(bool, &str)> {
+    for marker in ["- ", "* ", "+ "] {
+        if let Some(item) = line.strip_prefix(marker) {
+            return Some((false, item.trim()));
+        }
+    }
+    let digits = line.chars().take_while(|c| c.is_ascii_digit()).count();
+    if digits == 0 {
+        return None;
+    }
+    let rest = &line[digits..];
+    rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") ")).map(|item| (true, item.trim()))
+}
+
+/// 解析行内元素；找不到闭合定界符的标记按普通文本处理
+fn parse_inline(text: &str) -> Vec<Inline> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut inlines = Vec::new();
+    let mut buffer = String::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let parsed = match c {
+            '\\' if next.is_some_and(|n| ESCAPABLE.contains(&n)) => {
+                buffer.push(chars[i + 1]);
+                i += 2;
+                continue;
+            }
+            '`' => find(&chars, i + 1, &['`']).map(|end| {
+                (Inline::Code(chars[i + 1..end].iter().collect()), end + 1)
+            }),
+            '$' => {
+                let display = next == Some('$');
+                let start = if display { i + 2 } else { i + 1 };
+                find_math_end(&chars, start, display).map(|end| {
+                    let source: String = chars[start..end].iter().collect();
+                    (Inline::Math(MathSpan::new(&source, display)), if display { end + 2 } else { end + 1 })
+                })
+            }
+            // 单词内部的下划线（如 snake_case）不是强调
+            '*' | '_' if !(c == '_' && i > 0 && chars[i - 1].is_alphanumeric()) => {
+                let strong = next == Some(c);
+                let delimiter = if strong { vec![c, c] } else { vec![c] };
+                let start = i + delimiter.len();
+                if chars.get(start).is_some_and(|n| !n.is_whitespace()) {
+                    find(&chars, start, &delimiter).filter(|&end| end > start).map(|end| {
+                        let content = parse_inline(&chars[start..end].iter().collect::<String>());
+                        let inline = if strong { Inline::Strong(content) } else { Inline::Emphasis(content) };
+                        (inline, end + delimiter.len())
+                    })
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        match parsed {
+            Some((inline, end)) => {
+                if !buffer.is_empty() {
+                    inlines.push(Inline::Text(std::mem::take(&mut buffer)));
+                }
+                inlines.push(inline);
+                i = end;
+            }
+            None => {
+                buffer.push(c);
+                i += 1;
+            }
+        }
+    }
+    if !buffer.is_empty() {
+        inlines.push(Inline::Text(buffer));
+    }
+    inlines
+}
+
+/// 从 `from` 起查找定界符的位置
+fn find(chars: &[char], from: usize, delimiter: &[char]) -> Option<usize> {
+    (from..chars.len()).find(|&i| chars[i..].starts_with(delimiter))
+}
+
+/// 查找公式的闭合定界符；行内公式要求两端紧贴内容且闭合 `$` 后不是数字，以免把金额当作公式
+fn find_math_end(chars: &[char], start: usize, display: bool) -> Option<usize> {
+    if display {
+        return find(chars, start, &['$', '$']).filter(|&end| end > start);
+    }
+    if chars.get(start).is_none_or(|c| c.is_whitespace()) {
+        return None;
+    }
+    (start + 1..chars.len()).find(|&end| {
+        chars[end] == '$' && chars[end - 1] != '\\' && !chars[end - 1].is_whitespace()
+            && !chars.get(end + 1).is_some_and(|c| c.is_ascii_digit())
+    })
+}
+
+fn collect_math_errors<'a>(inlines: &'a [Inline], errors: &mut Vec<&'a MathSpan>) {
+    for inline in inlines {
+        match inline {
+            Inline::Strong(content) | Inline::Emphasis(content) => collect_math_errors(content, errors),
+            Inline::Math(span) if !span.is_valid() => errors.push(span),
+            _ => {}
+        }
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn inlines_to_html(inlines: &[Inline]) -> String {
+    inlines.iter().map(|inline| match inline {
+        Inline::Text(text) => escape_html(text),
+        Inline::Strong(content) => format!("<strong>{}</strong>", inlines_to_html(content)),
+        Inline::Emphasis(content) => format!("<em>{}</em>", inlines_to_html(content)),
+        Inline::Code(code) => format!("<code>{}</code>", escape_html(code)),
+        Inline::Math(span) => math_to_html(span),
+    }).collect()
+}
+
+fn math_to_html(span: &MathSpan) -> String {
+    match &span.result {
+        Ok(latex) if span.display => format!("\\[{}\\]", escape_html(latex)),
+        Ok(latex) => format!("\\({}\\)", escape_html(latex)),
+        Err(error) => {
+            let (before, at, after) = span.split_at_error(error.position);
+            format!(
+                "<span class=\"math-error\" title=\"{}\">{}<mark>{}</mark>{}</span>",
+                escape_html(&error.message), escape_html(&before), escape_html(&at), escape_html(&after)
+            )
+        }
+    }
+}
+
+fn inlines_to_markdown(inlines: &[Inline]) -> String {
+    let mut markdown = String::new();
+    for inline in inlines {
+        match inline {
+            Inline::Text(text) => {
+                let chars: Vec<char> = text.chars().collect();
+                for (i, &c) in chars.iter().enumerate() {
+                    let needs_escape = match c {
+                        '\\' => chars.get(i + 1).is_none_or(|n| ESCAPABLE.contains(n)),
+                        '_' => i == 0 || !chars[i - 1].is_alphanumeric(),
+                        '*' | '`' | '$' => true,
+                        _ => false,
+                    };
+                    if needs_escape {
+                        markdown.push('\\');
+                    }
+                    markdown.push(c);
+                }
+            }
+            Inline::Strong(content) => markdown.push_str(&format!("**{}**", inlines_to_markdown(content))),
+            Inline::Emphasis(content) => markdown.push_str(&format!("*{}*", inlines_to_markdown(content))),
+            Inline::Code(code) => markdown.push_str(&format!("`{}`", code)),
+            Inline::Math(span) if span.display => markdown.push_str(&format!("$${}$$", span.latex())),
+            Inline::Math(span) => markdown.push_str(&format!("${}$", span.latex())),
+        }
+    }
+    markdown
+}
+
+/// 用 ANSI 转义序列包上样式
+fn style(text: &str, code: &str, ansi: bool) -> String {
+    if ansi {
+        format!("\x1b[{}m{}\x1b[0m", code, text)
+    } else {
+        text.to_string()
+    }
+}
+
+fn inlines_to_text(inlines: &[Inline], mode: TextMode) -> String {
+    let ansi = mode == TextMode::Ansi;
+    inlines.iter().map(|inline| match inline {
+        Inline::Text(text) => text.clone(),
+        Inline::Strong(content) => style(&inlines_to_text(content, mode), "1", ansi),
+        Inline::Emphasis(content) => style(&inlines_to_text(content, mode), "3", ansi),
+        Inline::Code(code) => style(code, "36", ansi),
+        Inline::Math(span) => math_to_text(span, mode),
+    }).collect()
+}
+
+/// 不带样式的文本内容，用于计算标题下划线的宽度
+fn plain_inlines(inlines: &[Inline]) -> String {
+    inlines.iter().map(|inline| match inline {
+        Inline::Text(text) | Inline::Code(text) => text.clone(),
+        Inline::Strong(content) | Inline::Emphasis(content) => plain_inlines(content),
+        Inline::Math(span) => span.latex().to_string(),
+    }).collect()
+}
+
+fn math_to_text(span: &MathSpan, mode: TextMode) -> String {
+    match (&span.result, mode) {
+        (Ok(latex), _) => style(latex, "35", mode == TextMode::Ansi),
+        (Err(error), TextMode::Ansi) => {
+            let (before, at, after) = span.split_at_error(error.position);
+            format!("\x1b[31m{}\x1b[7m{}\x1b[27m{}\x1b[0m", before, at, after)
+        }
+        (Err(error), TextMode::Marked) => {
+            let (before, at, after) = span.split_at_error(error.position);
+            format!(
+                "{MARK_ERROR_START}{}{MARK_POSITION_START}{}{MARK_POSITION_END}{} [{}]{MARK_ERROR_END}",
+                before, at, after.trim_end(), error.message
+            )
+        }
+        (Err(error), TextMode::Plain) => format!("{} [{}]", span.source, error.message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(s: &str) -> Inline {
+        Inline::Text(s.to_string())
+    }
+
+    #[test]
+    fn test_block_structure() {
+        let document = MarkdownDocument::parse(
+            "# 标题\n\n第一行\n第二行\n\n- 甲\n- 乙\n1. 一\n2. 二\n\n```yufmath\nx + 1\n```\n$$\n\\frac{1}{2}\n$$"
+        );
+        assert_eq!(document.blocks.len(), 6);
+        assert_eq!(document.blocks[0], Block::Heading { level: 1, content: vec![text("标题")] });
+        assert_eq!(document.blocks[1], Block::Paragraph(vec![text("第一行 第二行")]));
+        assert_eq!(document.blocks[2], Block::List { ordered: false, items: vec![vec![text("甲")], vec![text("乙")]] });
+        assert_eq!(document.blocks[3], Block::List { ordered: true, items: vec![vec![text("一")], vec![text("二")]] });
+        assert_eq!(document.blocks[4], Block::Code { language: "yufmath".to_string(), code: "x + 1".to_string() });
+        assert!(matches!(&document.blocks[5], Block::Math(span) if span.display && span.is_valid()));
+        // 井号后没有空格的不是标题
+        assert!(matches!(MarkdownDocument::parse("#tag").blocks[0], Block::Paragraph(_)));
+    }
+
+    #[test]
+    fn test_inline_elements() {
+        let document = MarkdownDocument::parse("**粗** 与 *斜* 和 `code`，snake_case 与 \\*星号\\*");
+        assert_eq!(document.blocks[0], Block::Paragraph(vec![
+            Inline::Strong(vec![text("粗")]),
+            text(" 与 "),
+            Inline::Emphasis(vec![text("斜")]),
+            text(" 和 "),
+            Inline::Code("code".to_string()),
+            text("，snake_case 与 *星号*"),
+        ]));
+        // 未闭合的标记按文本处理，金额不是公式
+        assert_eq!(MarkdownDocument::parse("a * b 花了 $5 和 $6").blocks[0],
+            Block::Paragraph(vec![text("a * b 花了 $5 和 $6")]));
+    }
+
+    #[test]
+    fn test_math_normalization() {
+        let span = MathSpan::new("\\frac{x}{2}", false);
+        assert!(span.is_valid());
+        assert_eq!(span.latex(), LaTeXFormatter::new().format(&LaTeXParser::new().parse("x/2").unwrap()));
+
+        let span = MathSpan::new("x + ", false);
+        assert!(!span.is_valid());
+        assert_eq!(span.latex(), "x +");
+
+        let span = MathSpan::new("x + )", false);
+        assert_eq!(span.result.unwrap_err().position, Some(4));
+    }
+
+    #[test]
+    fn test_math_errors() {
+        let document = MarkdownDocument::parse("正确 $x^{2}$，**错误 $x + )$**\n\n$$\\frac{1}$$");
+        let errors = document.math_errors();
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].source, "x + )");
+        assert!(errors[1].display);
+    }
+
+    #[test]
+    fn test_html_rendering() {
+        let html = MarkdownDocument::parse("## 结果 <1>\n\n设 $x^{2}$，则\n\n$$\\sqrt{x}$$\n\n错误 $x + )$").to_html();
+        assert!(html.contains("<h2>结果 &lt;1&gt;</h2>"));
+        assert!(html.contains("\\(x^{2}\\)"));
+        assert!(html.contains("<div class=\"math-display\">\\[\\sqrt{x}\\]</div>"));
+        assert!(html.contains("<span class=\"math-error\""));
+        assert!(html.contains("x + <mark>)</mark>"));
+    }
+
+    #[test]
+    fn test_markdown_round_trip() {
+        let source = "# 标题\n\n**粗** *斜* `code` \\$5 与 $\\frac{x}{2}$\n\n- 甲\n- 乙\n\n```\nx + 1\n```\n\n$$\nx + )\n$$";
+        let markdown = MarkdownDocument::parse(source).to_markdown();
+        let normalized = LaTeXFormatter::new().format(&LaTeXParser::new().parse("\\frac{x}{2}").unwrap());
+        assert!(markdown.contains(&format!("${}$", normalized)));
+        // 解析失败的公式原样保留
+        assert!(markdown.ends_with("$$\nx + )\n$$"));
+        assert_eq!(MarkdownDocument::parse(&markdown).to_markdown(), markdown);
+    }
+
+    #[test]
+    fn test_terminal_rendering() {
+        let document = MarkdownDocument::parse("# 标题\n\n**粗** $x + )$");
+        let terminal = document.to_terminal();
+        assert!(terminal.contains("\x1b[1;4m标题\x1b[0m"));
+        assert!(terminal.contains("\x1b[1m粗\x1b[0m"));
+        assert!(terminal.contains("\x1b[31mx + \x1b[7m)\x1b[27m\x1b[0m"));
+
+        let plain = document.to_plain_text();
+        assert!(plain.starts_with("标题\n===="));
+        assert!(plain.contains("x + ) ["));
+        assert!(!plain.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_styled_text() {
+        let document = MarkdownDocument::parse("公式 $x + )$ 与 $x^{2}$");
+        let (text, styles) = document.to_styled_text();
+        assert_eq!(text, document.to_plain_text());
+        assert_eq!(text.len(), styles.len());
+
+        let start = text.find("x + )").unwrap();
+        assert!(styles[..start].chars().all(|c| c == STYLE_NORMAL));
+        assert_eq!(&styles[start..start + 4], "BBBB");
+        assert_eq!(&styles[start + 4..start + 5], "C");
+        let end = text.find(']').unwrap() + 1;
+        assert!(styles[start + 5..end].chars().all(|c| c == STYLE_MATH_ERROR));
+        assert!(styles[end..].chars().all(|c| c == STYLE_NORMAL));
+
+        // 输入意外结束时，出错位置是公式之后的空格
+        let (text, styles) = MarkdownDocument::parse("$x +$").to_styled_text();
+        assert!(text.starts_with("x +  ["));
+        assert_eq!(&styles[..4], "BBBC");
+    }
+}
@@ -9,6 +9,7 @@
 //! - `ExecutionEngine`: 单元格执行引擎
 //! - `VariableScope`: 变量作用域管理
 //! - `NotebookFormat`: 文件格式处理
+//! - `MarkdownDocument`: Markdown 单元格的解析与渲染
 
 pub mod cell;
 pub mod notebook;
@@ -19,6 +20,7 @@ pub mod ui;
 pub mod gui;
 pub mod autocomplete;
 pub mod export;
+pub mod markdown;
 
 #[cfg(test)]
 mod tests;
@@ -28,13 +30,14 @@ mod gui_tests;
 
 pub use cell::{NotebookCell, CellType, CellContent, CellMetadata, CellId};
 pub use notebook::{Notebook, NotebookManager, NotebookMetadata};
-pub use execution::{ExecutionEngine, ExecutionResult, ExecutionContext, ExecutionQueue};
-pub use scope::{VariableScope, ScopeManager, VariableBinding};
+pub use execution::{ExecutionEngine, ExecutionResult, ExecutionContext, ExecutionQueue, CellVariables};
+pub use scope::{VariableScope, ScopeManager, VariableBinding, FunctionDefinition};
 pub use format::{NotebookFormat, NotebookSerializer, NotebookDeserializer};
 pub use ui::{NotebookUI, UIEvent, UICommand, KeyBinding};
 pub use gui::{NotebookGUI, CellEditor};
 pub use autocomplete::{AutoCompleteEngine, CompletionSuggestion, SuggestionType};
 pub use export::{NotebookExporter, ExportFormat, ExportOptions};
+pub use markdown::{MarkdownDocument, MathSpan, MathError};
 
 use crate::engine::ComputeError;
 use std::collections::HashMap;
@@ -1,13 +1,20 @@
 //! # 变量作用域管理
 //!
 //! 管理笔记本中单元格间的变量共享和作用域。
+//! 除变量外，作用域里也保存单元格中 `f(x) := x^2 + 1` 定义的函数，调用处按 [`ScopeManager::expand_functions`] 展开。
 
 use crate::core::{Expression, Number};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::time::SystemTime;
 use super::{CellId, NotebookError, NotebookResult};
 
+/// 函数调用展开的最大嵌套层数，超过时视为递归定义
+const MAX_CALL_DEPTH: usize = 64;
+
 /// 变量绑定信息
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VariableBinding {
@@ -82,6 +89,51 @@ impl VariableBinding {
     }
 }
 
+/// 用户定义的函数 `f(x, y) := 函数体`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionDefinition {
+    /// 函数名
+    pub name: String,
+    /// 形参
+    pub parameters: Vec<String>,
+    /// 函数体
+    pub body: Expression,
+    /// 定义时间
+    pub defined_at: SystemTime,
+    /// 定义的单元格 ID
+    pub defined_in: CellId,
+}
+
+impl FunctionDefinition {
+    /// 创建新的函数定义
+    pub fn new(name: String, parameters: Vec<String>, body: Expression, defined_in: CellId) -> Self {
+        Self {
+            name,
+            parameters,
+            body,
+            defined_at: SystemTime::now(),
+            defined_in,
+        }
+    }
+    
+    /// 把实参同时代入函数体中的形参，实参个数须与形参一致
+    pub fn apply(&self, args: &[Expression]) -> NotebookResult<Expression> {
+        if args.len() != self.parameters.len() {
+            return Err(NotebookError::Scope(format!(
+                "函数 '{}' 需要 {} 个参数，传入了 {} 个", self.name, self.parameters.len(), args.len()
+            )));
+        }
+        let bindings: HashMap<String, Expression> = self.parameters.iter().cloned().zip(args.iter().cloned()).collect();
+        Ok(self.body.substitute(&bindings))
+    }
+}
+
+impl fmt::Display for FunctionDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}({}) := {}", self.name, self.parameters.join(", "), self.body)
+    }
+}
+
 /// 变量作用域
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VariableScope {
@@ -89,6 +141,9 @@ pub struct VariableScope {
     pub name: String,
     /// 变量绑定映射
     variables: HashMap<String, VariableBinding>,
+    /// 用户定义的函数
+    #[serde(default)]
+    functions: HashMap<String, FunctionDefinition>,
     /// 父作用域（用于嵌套作用域）
     parent: Option<Box<VariableScope>>,
     /// 创建时间
@@ -101,6 +156,7 @@ impl VariableScope {
         Self {
             name,
             variables: HashMap::new(),
+            functions: HashMap::new(),
             parent: None,
             created_at: SystemTime::now(),
         }
@@ -111,6 +167,7 @@ impl VariableScope {
         Self {
             name,
             variables: HashMap::new(),
+            functions: HashMap::new(),
             parent: Some(Box::new(self.clone())),
             created_at: SystemTime::now(),
         }
@@ -154,6 +211,12 @@ impl VariableScope {
         }
     }
     
+    /// 查看变量绑定，不记录使用
+    pub fn lookup_variable(&self, name: &str) -> Option<&VariableBinding> {
+        self.variables.get(name)
+            .or_else(|| self.parent.as_ref().and_then(|parent| parent.lookup_variable(name)))
+    }
+    
     /// 检查变量是否存在
     pub fn has_variable(&self, name: &str) -> bool {
         self.variables.contains_key(name) || 
@@ -188,9 +251,28 @@ impl VariableScope {
         &self.variables
     }
     
-    /// 清除所有变量
+    /// 定义函数，同名的函数被替换
+    pub fn define_function(&mut self, name: String, parameters: Vec<String>, body: Expression, defined_in: CellId) -> NotebookResult<()> {
+        let function = FunctionDefinition::new(name.clone(), parameters, body, defined_in);
+        self.functions.insert(name, function);
+        Ok(())
+    }
+    
+    /// 获取函数定义，本作用域没有时查找父作用域
+    pub fn get_function(&self, name: &str) -> Option<&FunctionDefinition> {
+        self.functions.get(name)
+            .or_else(|| self.parent.as_ref().and_then(|parent| parent.get_function(name)))
+    }
+    
+    /// 获取本地函数（不包括父作用域）
+    pub fn get_local_functions(&self) -> &HashMap<String, FunctionDefinition> {
+        &self.functions
+    }
+    
+    /// 清除所有变量和函数
     pub fn clear(&mut self) {
         self.variables.clear();
+        self.functions.clear();
     }
     
     /// 获取变量统计信息
@@ -290,6 +372,12 @@ impl ScopeManager {
         }
     }
     
+    fn current_scope_ref(&self) -> &VariableScope {
+        self.current_scope
+            .and_then(|cell_id| self.cell_scopes.get(&cell_id))
+            .unwrap_or(&self.global_scope)
+    }
+    
     /// 获取全局作用域
     pub fn get_global_scope(&mut self) -> &mut VariableScope {
         &mut self.global_scope
@@ -363,6 +451,68 @@ impl ScopeManager {
         names
     }
     
+    /// 在当前作用域定义函数
+    pub fn define_function(&mut self, name: String, parameters: Vec<String>, body: Expression, defined_in: CellId) -> NotebookResult<()> {
+        self.get_current_scope().define_function(name, parameters, body, defined_in)
+    }
+    
+    /// 获取函数定义，先查当前单元格作用域，再查全局作用域
+    pub fn get_function(&self, name: &str) -> Option<&FunctionDefinition> {
+        self.current_scope
+            .and_then(|cell_id| self.cell_scopes.get(&cell_id))
+            .and_then(|scope| scope.get_local_functions().get(name))
+            .or_else(|| self.global_scope.get_function(name))
+    }
+    
+    /// 给定名称在当前作用域中绑定的变量值和函数定义的指纹
+    ///
+    /// 任一名称被定义、重新赋值、删除或改变函数定义时指纹随之改变，与名称的顺序无关；只读取绑定，不记录变量使用。
+    pub fn bindings_fingerprint<'a>(&self, names: impl IntoIterator<Item = &'a String>) -> u64 {
+        let names: BTreeSet<&String> = names.into_iter().collect();
+        let scope = self.current_scope_ref();
+        let mut hasher = DefaultHasher::new();
+        for name in names {
+            name.hash(&mut hasher);
+            scope.lookup_variable(name).map(|binding| &binding.value).hash(&mut hasher);
+            self.get_function(name).map(|function| (&function.parameters, &function.body)).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+    
+    /// 把表达式中对用户函数的调用展开为代入实参后的函数体，函数体中的调用也一并展开
+    pub fn expand_functions(&self, expr: &Expression) -> NotebookResult<Expression> {
+        self.expand_calls(expr, 0)
+    }
+    
+    fn expand_calls(&self, expr: &Expression, depth: usize) -> NotebookResult<Expression> {
+        if depth > MAX_CALL_DEPTH {
+            return Err(NotebookError::Scope(format!("函数调用嵌套超过 {} 层，可能存在递归定义", MAX_CALL_DEPTH)));
+        }
+        let expand = |expr: &Expression| self.expand_calls(expr, depth);
+        let expand_all = |elements: &[Expression]| elements.iter().map(expand).collect::<NotebookResult<Vec<_>>>();
+        Ok(match expr {
+            Expression::Function { name, args } => {
+                let args = expand_all(args)?;
+                match self.get_function(name) {
+                    Some(function) => self.expand_calls(&function.apply(&args)?, depth + 1)?,
+                    None => Expression::function(name.clone(), args),
+                }
+            }
+            Expression::BinaryOp { op, left, right } => Expression::binary_op(op.clone(), expand(left)?, expand(right)?),
+            Expression::UnaryOp { op, operand } => Expression::unary_op(op.clone(), expand(operand)?),
+            Expression::Matrix(rows) => Expression::Matrix(rows.iter().map(|row| expand_all(row)).collect::<NotebookResult<_>>()?),
+            Expression::Vector(elements) => Expression::Vector(expand_all(elements)?),
+            Expression::Set(elements) => Expression::Set(expand_all(elements)?),
+            Expression::Interval { start, end, start_inclusive, end_inclusive } => Expression::Interval {
+                start: Box::new(expand(start)?),
+                end: Box::new(expand(end)?),
+                start_inclusive: *start_inclusive,
+                end_inclusive: *end_inclusive,
+            },
+            _ => expr.clone(),
+        })
+    }
+    
     /// 清除单元格作用域
     pub fn clear_cell_scope(&mut self, cell_id: &CellId) {
         self.cell_scopes.remove(cell_id);
@@ -600,4 +750,34 @@ mod tests {
         assert!(vars.contains_key("x"));
         assert!(vars.contains_key("y"));
     }
+    
+    #[test]
+    fn test_bindings_fingerprint() {
+        let mut manager = ScopeManager::new();
+        let cell_id = Uuid::new_v4();
+        let names = vec!["x".to_string(), "f".to_string()];
+        
+        let undefined = manager.bindings_fingerprint(&names);
+        manager.define_global_variable("x".to_string(), Expression::Number(Number::from(1)), cell_id).unwrap();
+        let defined = manager.bindings_fingerprint(&names);
+        assert_ne!(undefined, defined);
+        
+        // 名称顺序不影响指纹，读取不记录使用
+        assert_eq!(manager.bindings_fingerprint(names.iter().rev()), defined);
+        assert_eq!(manager.get_global_scope().get_local_variables()["x"].usage_count, 0);
+        
+        // 同值重新定义指纹不变，改值后改变
+        manager.define_global_variable("x".to_string(), Expression::Number(Number::from(1)), cell_id).unwrap();
+        assert_eq!(manager.bindings_fingerprint(&names), defined);
+        manager.update_variable("x", Expression::Number(Number::from(2)), cell_id).unwrap();
+        let updated = manager.bindings_fingerprint(&names);
+        assert_ne!(updated, defined);
+        
+        // 函数定义也计入指纹
+        manager.define_function("f".to_string(), vec!["t".to_string()], Expression::variable("t"), cell_id).unwrap();
+        assert_ne!(manager.bindings_fingerprint(&names), updated);
+        
+        manager.get_global_scope().clear();
+        assert_eq!(manager.bindings_fingerprint(&names), undefined);
+    }
 }
\ No newline at end of file
@@ -202,7 +202,41 @@ mod integration_tests {
         // cell[2] 不应该有依赖
         assert!(dependencies.get(&cells[2].id).unwrap().is_empty());
     }
-    
+
+    #[test]
+    fn test_cache_invalidated_by_upstream_variable() {
+        let mut engine = ExecutionEngine::new();
+        let mut upstream = crate::NotebookCell::new_code("x = 10".to_string());
+        let mut downstream = crate::NotebookCell::new_code("x + 1".to_string());
+
+        engine.execute_cell(&mut upstream).unwrap();
+        let first = engine.execute_cell(&mut downstream).unwrap();
+
+        // 依赖未变时命中缓存
+        let cached = engine.execute_cell(&mut downstream).unwrap();
+        assert_eq!(cached.value(), first.value());
+
+        // 修改上游变量后下游重新计算
+        upstream.set_text("x = 20".to_string());
+        engine.execute_cell(&mut upstream).unwrap();
+        let recomputed = engine.execute_cell(&mut downstream).unwrap();
+        assert!(recomputed.is_success());
+        assert_ne!(recomputed.value(), first.value());
+        assert!(recomputed.value().unwrap().contains("21"));
+
+        // 上游 2 次未命中，下游 2 次未命中 1 次命中
+        let stats = engine.get_engine_status().cache_statistics;
+        assert_eq!((stats.hits, stats.misses), (1, 4));
+        assert_eq!(engine.get_engine_status().cache_hit_rate, 0.2);
+
+        // 显式失效后不再命中
+        engine.invalidate_cell_cache(&downstream.id);
+        engine.execute_cell(&mut downstream).unwrap();
+        engine.invalidate_all();
+        engine.execute_cell(&mut downstream).unwrap();
+        assert_eq!(engine.get_engine_status().cache_statistics.misses, 6);
+    }
+
     #[test]
     fn test_template_and_backup() {
         // 测试模板创建
@@ -2,9 +2,10 @@
 //!
 //! 提供基于终端的笔记本交互界面。
 
-use super::{Notebook, NotebookCell, CellId, CellType, ExecutionEngine, NotebookError, NotebookResult};
+use super::{Notebook, NotebookCell, CellId, CellType, ExecutionEngine, NotebookError, NotebookResult, MarkdownDocument};
 use std::io::{self, Write};
 use std::time::Duration;
+use crate::api::format_duration;
 
 /// UI 事件类型
 #[derive(Debug, Clone)]
@@ -429,7 +430,11 @@ impl NotebookUI {
     /// 处理导航键
     fn handle_navigation(&mut self, event: &KeyEvent) -> Option<UICommand> {
         if self.edit_mode {
-            return None; // 编辑模式下不处理导航
+            // 编辑模式下只有 Esc 退出编辑，其余按键交给编辑器
+            if event.key == Key::Special(SpecialKey::Escape) {
+                self.leave_edit_mode();
+            }
+            return None;
         }
         
         match &event.key {
@@ -455,7 +460,7 @@ impl NotebookUI {
                 self.edit_mode = true;
             }
             Key::Special(SpecialKey::Escape) => {
-                self.edit_mode = false;
+                self.leave_edit_mode();
             }
             _ => return None,
         }
@@ -463,6 +468,25 @@ impl NotebookUI {
         None
     }
     
+    /// 退出编辑模式，Markdown 单元格随之切回渲染状态，公式有误时在状态栏提示
+    fn leave_edit_mode(&mut self) {
+        self.edit_mode = false;
+        
+        let message = self.current_cell
+            .and_then(|index| self.notebook.as_ref()?.get_cell(index))
+            .filter(|cell| cell.cell_type == CellType::Markdown)
+            .and_then(|cell| {
+                let document = MarkdownDocument::parse(&cell.get_text());
+                let errors = document.math_errors();
+                let first = errors.first()?;
+                Some(format!("{} 处公式无法解析: ${}$ {}", errors.len(), first.source,
+                    first.result.as_ref().err().map(|e| e.message.as_str()).unwrap_or("")))
+            });
+        if let Some(message) = message {
+            self.set_status_message(message);
+        }
+    }
+    
     /// 确保当前单元格可见
     fn ensure_cell_visible(&mut self) {
         if let Some(current) = self.current_cell {
@@ -583,7 +607,7 @@ impl NotebookUI {
                 
                 match result {
                     super::ExecutionResult::Success { execution_time, .. } => {
-                        self.set_status_message(format!("执行成功 ({:.2}ms)", execution_time.as_millis()));
+                        self.set_status_message(format!("执行成功 ({})", format_duration(execution_time)));
                     }
                     super::ExecutionResult::Error { error, .. } => {
                         self.set_status_message(format!("执行错误: {}", error));
@@ -766,6 +790,15 @@ impl NotebookUI {
         let prefix = if is_current { ">" } else { " " };
         let cell_type = cell.cell_type.display_name();
         
+        // Markdown 单元格除正在编辑时外都显示渲染结果
+        if cell.cell_type == CellType::Markdown && !(is_current && self.edit_mode) {
+            println!("{} [{}] {}:", prefix, index + 1, cell_type);
+            for line in MarkdownDocument::parse(&cell.get_text()).to_terminal().lines() {
+                println!("    {}", line);
+            }
+            return Ok(());
+        }
+        
         println!("{} [{}] {}: {}", 
                 prefix, 
                 index + 1, 
@@ -800,7 +833,7 @@ impl NotebookUI {
                 "无单元格".to_string()
             };
             
-            format!("{} 模式 | {} | Ctrl+H 显示帮助", mode, cell_info)
+            format!("{} 模式 | {} | Enter 编辑 Esc 渲染 | Ctrl+H 显示帮助", mode, cell_info)
         };
         
         let width = self.terminal_size.0 as usize;
@@ -881,6 +914,24 @@ mod tests {
         assert_eq!(ui.scroll_offset, 0);
     }
     
+    #[test]
+    fn test_markdown_edit_toggle() {
+        let mut ui = NotebookUI::new();
+        let mut notebook = Notebook::with_title("测试笔记本".to_string());
+        notebook.add_cell(NotebookCell::new_markdown("设 $x + )$".to_string()));
+        ui.set_notebook(notebook);
+        ui.current_cell = Some(0);
+        
+        let press = |key| KeyEvent { key: Key::Special(key), modifiers: KeyModifiers::none() };
+        ui.handle_key_event(&press(SpecialKey::Enter));
+        assert!(ui.edit_mode);
+        
+        // 编辑模式下 Esc 仍然生效，并提示公式错误
+        ui.handle_key_event(&press(SpecialKey::Escape));
+        assert!(!ui.edit_mode);
+        assert!(ui.status_message.as_ref().unwrap().starts_with("1 处公式无法解析"));
+    }
+    
     #[test]
     fn test_status_message() {
         let mut ui = NotebookUI::new();
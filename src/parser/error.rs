@@ -2,48 +2,58 @@
 //!
 //! 定义表达式解析过程中可能出现的各种错误类型。
 
+use std::fmt;
 use thiserror::Error;
+use crate::tr;
+use crate::core::signatures;
 
 /// 解析错误
 #[derive(Debug, Error, Clone, PartialEq)]
 pub enum ParseError {
     /// 语法错误
-    #[error("语法错误：位置 {pos}，{message}")]
     Syntax { pos: usize, message: String },
     
     /// 未知函数
-    #[error("未知函数：{name}")]
     UnknownFunction { name: String },
     
     /// 参数数量错误
-    #[error("参数数量错误：期望 {expected}，实际 {actual}")]
     ArgumentCount { expected: usize, actual: usize },
     
     /// 无效的数值格式
-    #[error("无效的数值格式：{value}")]
     InvalidNumber { value: String },
     
     /// 无效的变量名
-    #[error("无效的变量名：{name}")]
     InvalidVariable { name: String },
     
     /// 括号不匹配
-    #[error("括号不匹配：位置 {pos}")]
     UnmatchedParenthesis { pos: usize },
     
     /// 意外的输入结束
-    #[error("意外的输入结束")]
     UnexpectedEndOfInput,
     
     /// 意外的字符
-    #[error("意外的字符：位置 {pos}，字符 '{ch}'")]
     UnexpectedCharacter { pos: usize, ch: char },
     
     /// 空表达式
-    #[error("空表达式")]
     EmptyExpression,
 }
 
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Syntax { pos, message } => f.write_str(&tr!("parse.syntax", pos = pos, message = message)),
+            ParseError::UnknownFunction { name } => f.write_str(&tr!("parse.unknown_function", name = name)),
+            ParseError::ArgumentCount { expected, actual } => f.write_str(&tr!("parse.argument_count", expected = expected, actual = actual)),
+            ParseError::InvalidNumber { value } => f.write_str(&tr!("parse.invalid_number", value = value)),
+            ParseError::InvalidVariable { name } => f.write_str(&tr!("parse.invalid_variable", name = name)),
+            ParseError::UnmatchedParenthesis { pos } => f.write_str(&tr!("parse.unmatched_parenthesis", pos = pos)),
+            ParseError::UnexpectedEndOfInput => f.write_str(tr!("parse.unexpected_end_of_input")),
+            ParseError::UnexpectedCharacter { pos, ch } => f.write_str(&tr!("parse.unexpected_character", pos = pos, ch = ch)),
+            ParseError::EmptyExpression => f.write_str(tr!("parse.empty_expression")),
+        }
+    }
+}
+
 impl ParseError {
     /// 创建语法错误
     pub fn syntax(pos: usize, message: impl Into<String>) -> Self {
@@ -102,114 +112,59 @@ impl ParseError {
     /// 获取用户友好的错误消息
     pub fn user_friendly_message(&self) -> String {
         match self {
-            ParseError::Syntax { pos, message } => {
-                format!("语法错误（位置 {}）：{}", pos + 1, message)
-            }
-            ParseError::UnknownFunction { name } => {
-                format!("未知函数 '{}'。您是否想要使用：sin, cos, tan, exp, ln, sqrt？", name)
-            }
-            ParseError::ArgumentCount { expected, actual } => {
-                format!("函数参数数量错误：期望 {} 个参数，但提供了 {} 个", expected, actual)
-            }
-            ParseError::InvalidNumber { value } => {
-                format!("无效的数值格式 '{}'。请检查数值是否正确输入", value)
-            }
-            ParseError::InvalidVariable { name } => {
-                format!("无效的变量名 '{}'。变量名应以字母开头，只包含字母、数字和下划线", name)
-            }
-            ParseError::UnmatchedParenthesis { pos } => {
-                format!("括号不匹配（位置 {}）。请检查是否有未闭合的括号", pos + 1)
-            }
-            ParseError::UnexpectedEndOfInput => {
-                "表达式不完整。请检查是否缺少操作数或运算符".to_string()
-            }
-            ParseError::UnexpectedCharacter { pos, ch } => {
-                format!("意外的字符 '{}' （位置 {}）。请检查输入是否正确", ch, pos + 1)
-            }
-            ParseError::EmptyExpression => {
-                "表达式为空。请输入一个有效的数学表达式".to_string()
-            }
+            ParseError::Syntax { pos, message } => tr!("parse.syntax.message", pos = pos + 1, message = message),
+            ParseError::UnknownFunction { name } => tr!("parse.unknown_function.message", name = name),
+            ParseError::ArgumentCount { expected, actual } => tr!("parse.argument_count.message", expected = expected, actual = actual),
+            ParseError::InvalidNumber { value } => tr!("parse.invalid_number.message", value = value),
+            ParseError::InvalidVariable { name } => tr!("parse.invalid_variable.message", name = name),
+            ParseError::UnmatchedParenthesis { pos } => tr!("parse.unmatched_parenthesis.message", pos = pos + 1),
+            ParseError::UnexpectedEndOfInput => tr!("parse.unexpected_end_of_input.message").to_string(),
+            ParseError::UnexpectedCharacter { pos, ch } => tr!("parse.unexpected_character.message", ch = ch, pos = pos + 1),
+            ParseError::EmptyExpression => tr!("parse.empty_expression.message").to_string(),
         }
     }
     
     /// 获取修复建议
     pub fn suggestions(&self) -> Vec<String> {
-        match self {
+        let suggestions = match self {
             ParseError::UnknownFunction { name } => {
-                let mut suggestions = Vec::new();
-                
-                // 基于相似性提供函数建议
-                let common_functions = [
-                    "sin", "cos", "tan", "asin", "acos", "atan",
-                    "sinh", "cosh", "tanh", "exp", "ln", "log",
-                    "sqrt", "abs", "factorial", "gamma"
-                ];
-                
-                for func in &common_functions {
-                    if levenshtein_distance(name, func) <= 2 {
-                        suggestions.push(format!("您是否想要使用 '{}'？", func));
-                    }
-                }
-                
-                if suggestions.is_empty() {
-                    suggestions.push("请检查函数名是否正确拼写".to_string());
-                    suggestions.push("查看支持的函数列表：sin, cos, tan, exp, ln, sqrt 等".to_string());
+                // 基于相似性从内置函数签名表中提供建议
+                let suggestions: Vec<String> = signatures::BUILTIN_FUNCTIONS.iter()
+                    .filter(|signature| levenshtein_distance(name, signature.name) <= 2)
+                    .map(|signature| tr!("parse.unknown_function.did_you_mean", name = signature.hint()))
+                    .collect();
+                if !suggestions.is_empty() {
+                    return suggestions;
                 }
-                
-                suggestions
-            }
-            ParseError::InvalidNumber { value } => {
-                vec![
-                    "请检查数值格式是否正确".to_string(),
-                    "支持的格式：整数（123）、小数（3.14）、科学记数法（1.23e-4）".to_string(),
-                    format!("如果 '{}' 是变量名，请确保它不以数字开头", value),
-                ]
+                tr!("parse.unknown_function.suggestions").to_string()
             }
-            ParseError::UnmatchedParenthesis { .. } => {
-                vec![
-                    "检查每个左括号 '(' 是否有对应的右括号 ')'".to_string(),
-                    "检查每个右括号 ')' 是否有对应的左括号 '('".to_string(),
-                    "使用文本编辑器的括号匹配功能来检查括号配对".to_string(),
-                ]
-            }
-            ParseError::Syntax { .. } => {
-                vec![
-                    "检查运算符是否正确使用".to_string(),
-                    "确保每个运算符都有适当的操作数".to_string(),
-                    "检查是否有连续的运算符（如 '++' 或 '--'）".to_string(),
-                ]
-            }
-            ParseError::ArgumentCount { expected, .. } => {
-                vec![
-                    format!("该函数需要 {} 个参数", expected),
-                    "检查函数调用中的逗号分隔符".to_string(),
-                    "确保每个参数都是有效的表达式".to_string(),
-                ]
-            }
-            _ => {
-                vec![
-                    "请检查表达式的语法是否正确".to_string(),
-                    "参考文档了解正确的语法格式".to_string(),
-                ]
-            }
-        }
+            ParseError::InvalidNumber { value } => tr!("parse.invalid_number.suggestions", value = value),
+            ParseError::UnmatchedParenthesis { .. } => tr!("parse.unmatched_parenthesis.suggestions").to_string(),
+            ParseError::Syntax { .. } => tr!("parse.syntax.suggestions").to_string(),
+            ParseError::ArgumentCount { expected, .. } => tr!("parse.argument_count.suggestions", expected = expected),
+            _ => tr!("parse.suggestions").to_string(),
+        };
+        suggestions.lines().map(String::from).collect()
     }
     
     /// 生成带有位置指示的错误报告
     pub fn format_with_context(&self, input: &str) -> String {
         let mut result = String::new();
-        result.push_str(&format!("错误：{}\n", self.user_friendly_message()));
+        result.push_str(&tr!("parse.context.error", message = self.user_friendly_message()));
+        result.push('\n');
         
         if let Some(pos) = self.position() {
             if pos < input.len() {
-                result.push_str(&format!("输入：{}\n", input));
-                result.push_str(&format!("位置：{}{}\n", " ".repeat(pos + 3), "^"));
+                result.push_str(&tr!("parse.context.input", input = input));
+                result.push('\n');
+                result.push_str(&tr!("parse.context.position", marker = format!("{}^", " ".repeat(pos + 3))));
+                result.push('\n');
             }
         }
         
         let suggestions = self.suggestions();
         if !suggestions.is_empty() {
-            result.push_str("\n建议：\n");
+            result.push_str(&format!("\n{}\n", tr!("parse.context.suggestions")));
             for (i, suggestion) in suggestions.iter().enumerate() {
                 result.push_str(&format!("  {}. {}\n", i + 1, suggestion));
             }
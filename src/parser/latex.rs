@@ -0,0 +1,583 @@
+//! # LaTeX 解析器
+//!
+//! 解析常见的 LaTeX 数学写法，便于从论文或 Markdown 粘贴公式：
+//! `\frac{x}{2}`、`x^{2}`、`\sqrt{2}`、`\sqrt[3]{x}`、`\sin(x)`、`\log_{2}(x)`、`\left|x\right|`、
+//! `\begin{pmatrix} ... \end{pmatrix}` 等。相邻的因子按隐式乘法处理（`2x`、`xy`、`2\sin x`），
+//! 因此普通字母都是单字母变量，多字母名称需写成 `\text{name}` 或下标形式 `x_{1}`。
+//!
+//! 对 [`LaTeXFormatter`](crate::formatter::latex::LaTeXFormatter) 输出的子集，解析结果与
+//! 解析器读入同一表达式的普通写法一致，即 `parse_latex(to_latex(e)) == e`。
+
+use crate::core::{Expression, Number, BinaryOperator, UnaryOperator, signatures};
+use super::{ParseError, Parser, syntax::SyntaxParser};
+use crate::tr;
+
+/// LaTeX 词法单元
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// 数字
+    Number(String),
+    /// 单个字母
+    Letter(char),
+    /// 控制序列，不含反斜杠；`\{`、`\\` 等记为单字符名称
+    Command(String),
+    /// 其余单字符符号
+    Symbol(char),
+    /// 输入结束
+    End,
+}
+
+/// 只影响排版、解析时忽略的命令
+const IGNORED_COMMANDS: &[&str] = &[
+    ",", ";", ":", "!", " ", "quad", "qquad", "displaystyle", "textstyle",
+    "left", "right", "big", "Big", "bigl", "bigr", "Bigl", "Bigr", "bigg", "Bigg",
+];
+
+/// 作为变量名的希腊字母；`\pi`、`\gamma`、`\phi` 按解析器的规则识别为常量
+const GREEK_LETTERS: &[&str] = &[
+    "alpha", "beta", "gamma", "delta", "epsilon", "varepsilon", "zeta", "eta", "theta", "vartheta",
+    "iota", "kappa", "lambda", "mu", "nu", "xi", "pi", "rho", "sigma", "tau", "upsilon", "phi",
+    "varphi", "chi", "psi", "omega", "Delta", "Theta", "Lambda", "Xi", "Pi", "Sigma", "Phi", "Psi", "Omega",
+];
+
+/// 把 LaTeX 字符串切分为词法单元，附带起始位置
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, ParseError> {
+    let chars: Vec<(usize, char)> = input.char_indices().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let (pos, ch) = chars[i];
+        i += 1;
+        match ch {
+            c if c.is_whitespace() || c == '$' => {}
+            '\\' => {
+                let start = i;
+                while i < chars.len() && chars[i].1.is_ascii_alphabetic() {
+                    i += 1;
+                }
+                let name: String = if i > start {
+                    chars[start..i].iter().map(|&(_, c)| c).collect()
+                } else if i < chars.len() {
+                    i += 1;
+                    chars[start].1.to_string()
+                } else {
+                    return Err(ParseError::syntax(pos, tr!("parse.latex.missing_command")));
+                };
+                if IGNORED_COMMANDS.contains(&name.as_str()) {
+                    // `\left.` 与 `\right.` 表示不显示的定界符
+                    if (name == "left" || name == "right") && chars.get(i).is_some_and(|&(_, c)| c == '.') {
+                        i += 1;
+                    }
+                    continue;
+                }
+                tokens.push((pos, Token::Command(name)));
+            }
+            c if c.is_ascii_digit() || (c == '.' && chars.get(i).is_some_and(|&(_, d)| d.is_ascii_digit())) => {
+                let mut number = c.to_string();
+                let mut seen_dot = c == '.';
+                while let Some(&(_, d)) = chars.get(i) {
+                    if d.is_ascii_digit() || (d == '.' && !seen_dot && chars.get(i + 1).is_some_and(|&(_, e)| e.is_ascii_digit())) {
+                        seen_dot |= d == '.';
+                        number.push(d);
+                        i += 1;
+                    } else {
+                        break;
+                    }
+                }
+                tokens.push((pos, Token::Number(number)));
+            }
+            c if c.is_ascii_alphabetic() => tokens.push((pos, Token::Letter(c))),
+            '+' | '-' | '*' | '/' | '^' | '_' | '(' | ')' | '[' | ']' | '{' | '}' | '|' | '!' | '=' | '<' | '>' | ',' | '&' => {
+                tokens.push((pos, Token::Symbol(ch)));
+            }
+            c => return Err(ParseError::unexpected_character(pos, c)),
+        }
+    }
+    tokens.push((input.len(), Token::End));
+    Ok(tokens)
+}
+
+/// LaTeX 语法分析器
+struct LaTeXSyntax {
+    tokens: Vec<(usize, Token)>,
+    index: usize,
+}
+
+impl LaTeXSyntax {
+    fn current(&self) -> &Token {
+        &self.tokens[self.index].1
+    }
+
+    fn position(&self) -> usize {
+        self.tokens[self.index].0
+    }
+
+    fn advance(&mut self) -> Token {
+        let token = self.current().clone();
+        if self.index + 1 < self.tokens.len() {
+            self.index += 1;
+        }
+        token
+    }
+
+    fn is_symbol(&self, symbol: char) -> bool {
+        *self.current() == Token::Symbol(symbol)
+    }
+
+    fn is_command(&self, names: &[&str]) -> bool {
+        matches!(self.current(), Token::Command(name) if names.contains(&name.as_str()))
+    }
+
+    fn error(&self, message: impl Into<String>) -> ParseError {
+        match self.current() {
+            Token::End => ParseError::UnexpectedEndOfInput,
+            _ => ParseError::syntax(self.position(), message),
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), ParseError> {
+        if self.is_symbol(symbol) {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.error(tr!("parse.latex.expected", symbol = symbol)))
+        }
+    }
+
+    fn expect_command(&mut self, name: &str) -> Result<(), ParseError> {
+        if self.is_command(&[name]) {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.error(tr!("parse.latex.expected", symbol = format!("\\{}", name))))
+        }
+    }
+
+    fn parse(&mut self) -> Result<Expression, ParseError> {
+        if *self.current() == Token::End {
+            return Err(ParseError::EmptyExpression);
+        }
+        let expr = self.parse_expression()?;
+        if *self.current() != Token::End {
+            return Err(ParseError::syntax(self.position(), tr!("parse.unexpected_token", token = format!("{:?}", self.current()))));
+        }
+        Ok(expr)
+    }
+
+    fn parse_expression(&mut self) -> Result<Expression, ParseError> {
+        self.parse_logical_or()
+    }
+
+    /// 按运算符表解析左结合的二元运算
+    fn parse_binary_level(
+        &mut self,
+        operand: fn(&mut Self) -> Result<Expression, ParseError>,
+        operator: fn(&Token) -> Option<BinaryOperator>,
+    ) -> Result<Expression, ParseError> {
+        let mut left = operand(self)?;
+        while let Some(op) = operator(self.current()) {
+            self.advance();
+            let right = operand(self)?;
+            left = Expression::binary_op(op, left, right);
+        }
+        Ok(left)
+    }
+
+    fn parse_logical_or(&mut self) -> Result<Expression, ParseError> {
+        self.parse_binary_level(Self::parse_logical_and, |token| match token {
+            Token::Command(name) if name == "lor" || name == "vee" => Some(BinaryOperator::Or),
+            _ => None,
+        })
+    }
+
+    fn parse_logical_and(&mut self) -> Result<Expression, ParseError> {
+        self.parse_binary_level(Self::parse_equality, |token| match token {
+            Token::Command(name) if name == "land" || name == "wedge" => Some(BinaryOperator::And),
+            _ => None,
+        })
+    }
+
+    fn parse_equality(&mut self) -> Result<Expression, ParseError> {
+        self.parse_binary_level(Self::parse_comparison, |token| match token {
+            Token::Symbol('=') => Some(BinaryOperator::Equal),
+            Token::Command(name) if name == "neq" || name == "ne" => Some(BinaryOperator::NotEqual),
+            _ => None,
+        })
+    }
+
+    fn parse_comparison(&mut self) -> Result<Expression, ParseError> {
+        self.parse_binary_level(Self::parse_term, |token| match token {
+            Token::Symbol('<') => Some(BinaryOperator::Less),
+            Token::Symbol('>') => Some(BinaryOperator::Greater),
+            Token::Command(name) => match name.as_str() {
+                "leq" | "le" => Some(BinaryOperator::LessEqual),
+                "geq" | "ge" => Some(BinaryOperator::GreaterEqual),
+                _ => None,
+            },
+            _ => None,
+        })
+    }
+
+    fn parse_term(&mut self) -> Result<Expression, ParseError> {
+        self.parse_binary_level(Self::parse_factor, |token| match token {
+            Token::Symbol('+') => Some(BinaryOperator::Add),
+            Token::Symbol('-') => Some(BinaryOperator::Subtract),
+            _ => None,
+        })
+    }
+
+    /// 乘除与取模；相邻的因子按隐式乘法处理，与显式乘法同级左结合
+    fn parse_factor(&mut self) -> Result<Expression, ParseError> {
+        let mut left = self.parse_unary()?;
+        loop {
+            let op = match self.current() {
+                Token::Symbol('*') => Some(BinaryOperator::Multiply),
+                Token::Symbol('/') => Some(BinaryOperator::Divide),
+                Token::Command(name) => match name.as_str() {
+                    "cdot" | "times" | "ast" => Some(BinaryOperator::Multiply),
+                    "div" => Some(BinaryOperator::Divide),
+                    "bmod" | "mod" => Some(BinaryOperator::Modulo),
+                    _ => None,
+                },
+                _ => None,
+            };
+            if let Some(op) = op {
+                self.advance();
+                let right = self.parse_unary()?;
+                left = Expression::binary_op(op, left, right);
+            } else if self.starts_factor() {
+                let right = self.parse_power()?;
+                left = Expression::binary_op(BinaryOperator::Multiply, left, right);
+            } else {
+                return Ok(left);
+            }
+        }
+    }
+
+    /// 当前标记能否开始一个不带符号的因子
+    fn starts_factor(&self) -> bool {
+        match self.current() {
+            Token::Number(_) | Token::Letter(_) => true,
+            Token::Symbol(c) => matches!(c, '(' | '[' | '{'),
+            Token::Command(name) => !matches!(
+                name.as_str(),
+                "cdot" | "times" | "ast" | "div" | "bmod" | "mod" | "neq" | "ne" | "leq" | "le" | "geq" | "ge"
+                    | "land" | "wedge" | "lor" | "vee" | "neg" | "lnot" | "}" | "\\" | "rfloor" | "rceil" | "end"
+            ),
+            Token::End => false,
+        }
+    }
+
+    fn parse_unary(&mut self) -> Result<Expression, ParseError> {
+        let op = match self.current() {
+            Token::Symbol('-') => UnaryOperator::Negate,
+            Token::Symbol('+') => UnaryOperator::Plus,
+            Token::Command(name) if name == "neg" || name == "lnot" => UnaryOperator::Not,
+            _ => return self.parse_power(),
+        };
+        self.advance();
+        let operand = self.parse_unary()?;
+        Ok(Expression::unary_op(op, operand))
+    }
+
+    /// 幂运算：`x^{...}` 或 `x^2`，右结合由花括号分组体现
+    fn parse_power(&mut self) -> Result<Expression, ParseError> {
+        let base = self.parse_postfix()?;
+        if self.is_symbol('^') {
+            self.advance();
+            let exponent = self.parse_argument()?;
+            return Ok(Expression::power(base, exponent));
+        }
+        Ok(base)
+    }
+
+    /// 后缀阶乘
+    fn parse_postfix(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.parse_primary()?;
+        while self.is_symbol('!') {
+            self.advance();
+            expr = Expression::unary_op(UnaryOperator::Factorial, expr);
+        }
+        Ok(expr)
+    }
+
+    /// 命令参数：花括号分组，或单个字母、数字、命令（`\frac12`、`x^2`）
+    fn parse_argument(&mut self) -> Result<Expression, ParseError> {
+        match self.current().clone() {
+            Token::Symbol('{') => self.parse_group('{', '}'),
+            Token::Number(number) if number.len() > 1 => {
+                // 未加花括号时只取第一个字符：`x^23` 即 `x^{2}3`
+                let (first, rest) = number.split_at(1);
+                let position = self.position();
+                self.tokens[self.index] = (position + 1, Token::Number(rest.to_string()));
+                SyntaxParser::parse_number(first)
+            }
+            Token::Number(_) | Token::Letter(_) | Token::Command(_) => self.parse_primary(),
+            _ => Err(self.error(tr!("parse.latex.expected_argument"))),
+        }
+    }
+
+    /// 以 `open` 开始、`close` 结束的分组
+    fn parse_group(&mut self, open: char, close: char) -> Result<Expression, ParseError> {
+        self.expect_symbol(open)?;
+        let expr = self.parse_expression()?;
+        self.expect_symbol(close)?;
+        Ok(expr)
+    }
+
+    /// 花括号内的原始文本，用于 `\text{name}` 与下标名
+    fn parse_text_group(&mut self) -> Result<String, ParseError> {
+        if !self.is_symbol('{') {
+            return match self.advance() {
+                Token::Letter(c) => Ok(c.to_string()),
+                Token::Number(n) => Ok(n),
+                _ => Err(self.error(tr!("parse.latex.expected_name"))),
+            };
+        }
+        self.advance();
+        let mut text = String::new();
+        loop {
+            match self.advance() {
+                Token::Letter(c) => text.push(c),
+                Token::Number(n) => text.push_str(&n),
+                Token::Symbol('}') if !text.is_empty() => return Ok(text),
+                _ => return Err(ParseError::syntax(self.position(), tr!("parse.latex.invalid_name"))),
+            }
+        }
+    }
+
+    fn parse_primary(&mut self) -> Result<Expression, ParseError> {
+        match self.advance() {
+            Token::Number(number) => SyntaxParser::parse_number(&number),
+            Token::Letter(letter) => {
+                let mut name = letter.to_string();
+                if self.is_symbol('_') {
+                    self.advance();
+                    name = format!("{}_{}", name, self.parse_text_group()?);
+                }
+                Ok(SyntaxParser::identifier(name))
+            }
+            Token::Symbol('(') => self.finish_group(')'),
+            Token::Symbol('[') => self.finish_group(']'),
+            Token::Symbol('{') => self.finish_group('}'),
+            Token::Symbol('|') => {
+                let operand = self.finish_group('|')?;
+                Ok(Expression::function("abs", vec![operand]))
+            }
+            Token::Command(name) => self.parse_command(&name),
+            Token::End => Err(ParseError::UnexpectedEndOfInput),
+            token => Err(ParseError::syntax(self.tokens[self.index.saturating_sub(1)].0, tr!("parse.unexpected_token", token = format!("{:?}", token)))),
+        }
+    }
+
+    /// 开始符号已消费的分组
+    fn finish_group(&mut self, close: char) -> Result<Expression, ParseError> {
+        let expr = self.parse_expression()?;
+        self.expect_symbol(close)?;
+        Ok(expr)
+    }
+
+    fn parse_command(&mut self, name: &str) -> Result<Expression, ParseError> {
+        match name {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.parse_argument()?;
+                let denominator = self.parse_argument()?;
+                Ok(Expression::divide(numerator, denominator))
+            }
+            "sqrt" => {
+                if self.is_symbol('[') {
+                    let index = self.parse_group('[', ']')?;
+                    let radicand = self.parse_argument()?;
+                    Ok(Expression::function("root", vec![radicand, index]))
+                } else {
+                    Ok(Expression::function("sqrt", vec![self.parse_argument()?]))
+                }
+            }
+            "infty" => Ok(SyntaxParser::identifier("inf".to_string())),
+            "Gamma" => self.parse_function_call("gamma"),
+            "operatorname" | "text" | "mathrm" => {
+                let text = self.parse_text_group()?;
+                if self.is_symbol('(') || self.is_symbol('^') {
+                    let function = match text.as_str() {
+                        "Re" => "real",
+                        "Im" => "imag",
+                        other => other,
+                    };
+                    self.parse_function_call(function)
+                } else {
+                    Ok(SyntaxParser::identifier(text))
+                }
+            }
+            "sin" | "cos" | "tan" | "sec" | "csc" | "cot" | "sinh" | "cosh" | "tanh" | "ln" | "exp" | "det" | "arg" => {
+                self.parse_function_call(name)
+            }
+            "arcsin" | "arccos" | "arctan" => self.parse_function_call(&name.replacen("arc", "a", 1)),
+            "max" | "min" => self.parse_function_call(name),
+            "log" => self.parse_logarithm(),
+            "lfloor" => self.parse_delimited("floor", "rfloor"),
+            "lceil" => self.parse_delimited("ceil", "rceil"),
+            "overline" => Ok(Expression::function("conj", vec![self.parse_argument()?])),
+            "{" => {
+                let elements = self.parse_list(|parser| parser.is_command(&["}"]))?;
+                self.expect_command("}")?;
+                Ok(Expression::Set(elements))
+            }
+            "begin" => self.parse_environment(),
+            // 大写希腊字母不参与常量识别，`\Phi` 不是黄金比例
+            greek if GREEK_LETTERS.contains(&greek) && greek.starts_with(char::is_uppercase) => Ok(Expression::variable(greek)),
+            greek if GREEK_LETTERS.contains(&greek) => Ok(SyntaxParser::identifier(greek.to_string())),
+            _ => Err(ParseError::syntax(self.tokens[self.index.saturating_sub(1)].0, tr!("parse.latex.unsupported_command", name = name))),
+        }
+    }
+
+    /// 函数调用：`\sin(x)`、`\sin x`，以及幂写在函数名上的 `\sin^{2}(x)`
+    fn parse_function_call(&mut self, name: &str) -> Result<Expression, ParseError> {
+        let exponent = if self.is_symbol('^') {
+            self.advance();
+            Some(self.parse_argument()?)
+        } else {
+            None
+        };
+        let start = self.position();
+        let args = self.parse_function_arguments()?;
+        signatures::check_call(name, &args).map_err(|message| ParseError::syntax(start, message))?;
+        let call = Expression::function(name, args);
+        Ok(match exponent {
+            Some(exponent) => Expression::power(call, exponent),
+            None => call,
+        })
+    }
+
+    /// 圆括号或 `\{...\}` 中的参数列表；没有括号时取紧随的一个因子
+    fn parse_function_arguments(&mut self) -> Result<Vec<Expression>, ParseError> {
+        if self.is_symbol('(') {
+            self.advance();
+            let args = self.parse_list(|parser| parser.is_symbol(')'))?;
+            self.expect_symbol(')')?;
+            Ok(args)
+        } else if self.is_command(&["{"]) {
+            self.advance();
+            let args = self.parse_list(|parser| parser.is_command(&["}"]))?;
+            self.expect_command("}")?;
+            Ok(args)
+        } else {
+            Ok(vec![self.parse_power()?])
+        }
+    }
+
+    /// 逗号分隔的表达式列表，遇到 `at_end` 为真时停止
+    fn parse_list(&mut self, at_end: fn(&Self) -> bool) -> Result<Vec<Expression>, ParseError> {
+        let mut items = Vec::new();
+        if at_end(self) {
+            return Ok(items);
+        }
+        loop {
+            items.push(self.parse_expression()?);
+            if self.is_symbol(',') {
+                self.advance();
+            } else {
+                return Ok(items);
+            }
+        }
+    }
+
+    /// `\log(x)`、`\log_{b}(x)`；底数 10 与 2 对应 `log10`、`log2`
+    fn parse_logarithm(&mut self) -> Result<Expression, ParseError> {
+        if !self.is_symbol('_') {
+            return self.parse_function_call("log");
+        }
+        self.advance();
+        let base = self.parse_argument()?;
+        let name = match &base {
+            Expression::Number(n) if *n == Number::from(10) => "log10",
+            Expression::Number(n) if *n == Number::from(2) => "log2",
+            _ => "",
+        };
+        if !name.is_empty() {
+            return self.parse_function_call(name);
+        }
+        match self.parse_function_call("log")? {
+            Expression::Function { name, mut args } if args.len() == 1 => {
+                args.push(base);
+                Ok(Expression::function(name, args))
+            }
+            _ => Err(ParseError::syntax(self.position(), tr!("parse.latex.log_base_arguments"))),
+        }
+    }
+
+    /// 成对定界符：`\lfloor x \rfloor`
+    fn parse_delimited(&mut self, function: &str, close: &str) -> Result<Expression, ParseError> {
+        let operand = self.parse_expression()?;
+        self.expect_command(close)?;
+        Ok(Expression::function(function, vec![operand]))
+    }
+
+    /// 矩阵环境：`&` 分隔列，`\\` 分隔行；只有一列时作为向量
+    fn parse_environment(&mut self) -> Result<Expression, ParseError> {
+        let environment = self.parse_text_group()?;
+        if !matches!(environment.as_str(), "matrix" | "pmatrix" | "bmatrix" | "Bmatrix" | "vmatrix" | "Vmatrix") {
+            return Err(ParseError::syntax(self.position(), tr!("parse.latex.unsupported_environment", environment = environment)));
+        }
+        let mut rows = Vec::new();
+        let mut row = Vec::new();
+        loop {
+            row.push(self.parse_expression()?);
+            if self.is_symbol('&') {
+                self.advance();
+            } else if self.is_command(&["\\"]) {
+                self.advance();
+                rows.push(std::mem::take(&mut row));
+                if self.is_command(&["end"]) {
+                    break;
+                }
+            } else {
+                rows.push(std::mem::take(&mut row));
+                break;
+            }
+        }
+        self.expect_command("end")?;
+        if self.parse_text_group()? != environment {
+            return Err(ParseError::syntax(self.position(), tr!("parse.latex.unterminated_environment", environment = environment)));
+        }
+        let matrix = if rows.iter().all(|row| row.len() == 1) && rows.len() > 1 {
+            Expression::Vector(rows.into_iter().flatten().collect())
+        } else {
+            Expression::matrix(rows).map_err(|e| ParseError::syntax(self.position(), e))?
+        };
+        Ok(match environment.as_str() {
+            "vmatrix" => Expression::function("det", vec![matrix]),
+            _ => matrix,
+        })
+    }
+}
+
+/// LaTeX 子集解析器
+pub struct LaTeXParser;
+
+impl LaTeXParser {
+    /// 创建新的 LaTeX 解析器
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for LaTeXParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Parser for LaTeXParser {
+    fn parse(&self, input: &str) -> Result<Expression, ParseError> {
+        LaTeXSyntax { tokens: tokenize(input)?, index: 0 }.parse()
+    }
+
+    fn validate(&self, input: &str) -> Result<(), ParseError> {
+        self.parse(input)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[path = "latex_tests.rs"]
+mod latex_tests;
@@ -0,0 +1,72 @@
+//! # LaTeX 解析器测试
+//!
+//! 测试常见 LaTeX 构造的解析，以及与 LaTeX 格式化器的往返一致性。
+
+#[cfg(test)]
+mod tests {
+    use crate::core::Expression;
+    use crate::formatter::{Formatter, LaTeXFormatter};
+    use crate::parser::{Parser, ParseError, latex::LaTeXParser};
+    use crate::test_utils::parse;
+
+    fn latex(input: &str) -> Expression {
+        LaTeXParser::new().parse(input).unwrap()
+    }
+
+    #[test]
+    fn test_common_constructs() {
+        assert_eq!(latex(r"\frac{x}{2}"), parse("x/2"));
+        assert_eq!(latex(r"\frac12"), parse("1/2"));
+        assert_eq!(latex(r"x^{2} + 2x + 1"), parse("x^2 + 2*x + 1"));
+        assert_eq!(latex(r"x^23"), parse("x^2*3"));
+        assert_eq!(latex(r"\sqrt{2}"), parse("sqrt(2)"));
+        assert_eq!(latex(r"\sqrt[3]{x + 1}"), parse("root(x + 1, 3)"));
+        assert_eq!(latex(r"\sin(x) \cdot \cos x"), parse("sin(x)*cos(x)"));
+        assert_eq!(latex(r"\sin^{2}(x)"), parse("sin(x)^2"));
+        assert_eq!(latex(r"\arctan\left(\frac{y}{x}\right)"), parse("atan(y/x)"));
+        assert_eq!(latex(r"\log_{2}(x) + \log_{b} y + \ln x"), parse("log2(x) + log(y, b) + ln(x)"));
+        assert_eq!(latex(r"\left|x - 1\right| \leq \epsilon"), parse("abs(x - 1) <= epsilon"));
+        assert_eq!(latex(r"2\pi r"), parse("2*pi*r"));
+        assert_eq!(latex(r"x_{1} + x_2 = \text{total}"), parse("x_1 + x_2 == total"));
+        assert_eq!(latex(r"$e^{i \theta}$"), parse("e^(i*theta)"));
+        assert_eq!(latex(r"\operatorname{erf}(x) \div 2"), parse("erf(x)/2"));
+        assert_eq!(latex(r"\begin{pmatrix} a & b \\ c & d \end{pmatrix}"), parse("[[a, b], [c, d]]"));
+        assert_eq!(latex(r"\begin{bmatrix} 1 \\ 2 \end{bmatrix}"), parse("[1, 2]"));
+        assert_eq!(latex(r"\left\lfloor \frac{n}{2} \right\rfloor!"), parse("floor(n/2)!"));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let formatter = LaTeXFormatter::new();
+        let corpus = [
+            // 分数
+            "x/2", "(x + 1)/(x - 1)", "1/(1 + 1/x)", "(a/b)/c", "a/(b/c)", "-x/2", "2*(x/3)",
+            // 根式
+            "sqrt(2)", "sqrt(x^2 + 1)", "root(x, 3)", "sqrt(x)/2", "1/sqrt(x)",
+            // 幂
+            "x^2", "x^(y^z)", "(x^y)^z", "(x + 1)^2", "x^(-1)", "2^(1/2)", "e^(-x^2/2)", "(-x)^2", "-x^2",
+            // 组合
+            "2*x + 3*y", "x*y*z", "x*(y*z)", "a - (b - c)", "a - b + c", "sin(x)^2 + cos(x)^2",
+            "exp(x)*ln(x)", "log(x, 3)", "abs(x - 1)", "n!", "(n + 1)!", "pi*r^2",
+            "x < 1", "x == y + 1", "[[1, 2], [3, 4]]", "[x, y, z]",
+        ];
+        for input in corpus {
+            let expr = parse(input);
+            let rendered = formatter.format(&expr);
+            let reparsed = LaTeXParser::new().parse(&rendered)
+                .unwrap_or_else(|e| panic!("无法解析 {} 的 LaTeX 输出 `{}`: {}", input, rendered, e));
+            assert_eq!(reparsed, expr, "往返失败：{} -> `{}`", input, rendered);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let parser = LaTeXParser::new();
+        assert_eq!(parser.parse(""), Err(ParseError::EmptyExpression));
+        assert_eq!(parser.parse(r"\frac{x}"), Err(ParseError::UnexpectedEndOfInput));
+        assert!(matches!(parser.parse(r"\unknown{x}"), Err(ParseError::Syntax { .. })));
+        assert!(matches!(parser.parse(r"\begin{align} x \end{align}"), Err(ParseError::Syntax { .. })));
+        assert!(matches!(parser.parse(r"x @ y"), Err(ParseError::UnexpectedCharacter { ch: '@', .. })));
+        assert!(parser.validate(r"\left(x + 1\right)").is_ok());
+    }
+}
@@ -3,6 +3,7 @@
 //! 将输入字符串分解为词法单元（tokens）。
 
 use super::ParseError;
+use crate::tr;
 
 /// 词法单元类型
 #[derive(Debug, Clone, PartialEq)]
@@ -23,15 +24,56 @@ pub enum Token {
     RightBracket,
     /// 逗号
     Comma,
+    /// 分号，分隔语句
+    Semicolon,
+    /// 双分号，分隔语句并且不回显前一条语句的结果
+    DoubleSemicolon,
     /// 输入结束
     EndOfInput,
 }
 
+/// 去掉输入中的注释，保留换行使行号不变，用于逐行处理的批处理文件
+///
+/// 与词法分析器的规则相同：`#`、`//` 到行尾，`/* ... */` 可跨行；未闭合的块注释延续到输入结束。
+pub fn strip_comments(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_block = false;
+    while let Some(ch) = chars.next() {
+        if in_block {
+            if ch == '*' && chars.peek() == Some(&'/') {
+                chars.next();
+                in_block = false;
+            } else if ch == '\n' {
+                output.push('\n');
+            }
+            continue;
+        }
+        match (ch, chars.peek()) {
+            ('#', _) | ('/', Some('/')) => {
+                while chars.next_if(|&next| next != '\n').is_some() {}
+            }
+            ('/', Some('*')) => {
+                chars.next();
+                in_block = true;
+                output.push(' ');
+            }
+            _ => output.push(ch),
+        }
+    }
+    output
+}
+
 /// 词法分析器
+///
+/// `#`、`//` 到行尾的行注释和 `/* ... */` 块注释（可跨行）被当作空白跳过。
+#[derive(Clone)]
 pub struct Lexer {
     input: Vec<char>,
     position: usize,
     current_char: Option<char>,
+    /// 最近读出的词法单元的起始位置
+    token_start: usize,
 }
 
 impl Lexer {
@@ -44,24 +86,42 @@ impl Lexer {
             input: chars,
             position: 0,
             current_char,
+            token_start: 0,
         }
     }
     
     /// 获取下一个词法单元
     pub fn next_token(&mut self) -> Result<Token, ParseError> {
         loop {
+            self.token_start = self.position;
             match self.current_char {
                 None => return Ok(Token::EndOfInput),
                 Some(ch) if ch.is_whitespace() => {
                     self.skip_whitespace();
                     continue;
                 }
+                Some('#') => {
+                    self.skip_line_comment();
+                    continue;
+                }
+                Some('/') if self.peek() == Some('/') => {
+                    self.skip_line_comment();
+                    continue;
+                }
+                Some('/') if self.peek() == Some('*') => {
+                    self.skip_block_comment()?;
+                    continue;
+                }
                 Some(ch) if ch.is_ascii_digit() => {
                     return self.read_number();
                 }
                 Some(ch) if ch.is_alphabetic() || ch == '_' => {
                     return self.read_identifier();
                 }
+                Some('∞') => {
+                    self.advance();
+                    return Ok(Token::Identifier("∞".to_string()));
+                }
                 Some('(') => {
                     self.advance();
                     return Ok(Token::LeftParen);
@@ -82,6 +142,14 @@ impl Lexer {
                     self.advance();
                     return Ok(Token::Comma);
                 }
+                Some(';') => {
+                    self.advance();
+                    if let Some(';') = self.current_char {
+                        self.advance();
+                        return Ok(Token::DoubleSemicolon);
+                    }
+                    return Ok(Token::Semicolon);
+                }
                 Some(_) => {
                     return self.read_operator();
                 }
@@ -142,7 +210,7 @@ impl Lexer {
         
         // 验证数值格式
         if number_str.is_empty() {
-            return Err(ParseError::syntax(start_pos, "空数值"));
+            return Err(ParseError::syntax(start_pos, tr!("parse.empty_number")));
         }
         
         // 验证数值是否有效
@@ -174,10 +242,16 @@ impl Lexer {
         }
         
         if identifier.is_empty() {
-            return Err(ParseError::syntax(self.position, "空标识符"));
+            return Err(ParseError::syntax(self.position, tr!("parse.empty_identifier")));
         }
         
-        Ok(Token::Identifier(identifier))
+        // 逻辑关键字与 &&、||、! 等价，其中 not 的优先级低于比较运算
+        match identifier.as_str() {
+            "and" => Ok(Token::Operator("&&".to_string())),
+            "or" => Ok(Token::Operator("||".to_string())),
+            "not" => Ok(Token::Operator("not".to_string())),
+            _ => Ok(Token::Identifier(identifier)),
+        }
     }
     
     /// 读取运算符
@@ -215,6 +289,16 @@ impl Lexer {
                 self.advance();
                 Ok(Token::Operator("%".to_string()))
             }
+            Some(':') => {
+                self.advance();
+                // 检查是否是 :=
+                if let Some('=') = self.current_char {
+                    self.advance();
+                    Ok(Token::Operator(":=".to_string()))
+                } else {
+                    Ok(Token::Operator(":".to_string()))
+                }
+            }
             Some('=') => {
                 self.advance();
                 // 检查是否是 ==
@@ -295,6 +379,36 @@ impl Lexer {
         }
     }
     
+    /// 跳过到行尾的注释，换行符留给空白处理
+    fn skip_line_comment(&mut self) {
+        while let Some(ch) = self.current_char {
+            if ch == '\n' {
+                break;
+            }
+            self.advance();
+        }
+    }
+    
+    /// 跳过 `/* ... */` 块注释，不支持嵌套
+    fn skip_block_comment(&mut self) -> Result<(), ParseError> {
+        let start_pos = self.position;
+        self.advance();
+        self.advance();
+        while let Some(ch) = self.current_char {
+            self.advance();
+            if ch == '*' && self.current_char == Some('/') {
+                self.advance();
+                return Ok(());
+            }
+        }
+        Err(ParseError::syntax(start_pos, tr!("parse.unterminated_comment")))
+    }
+    
+    /// 预览下一个词法单元（不移动位置）
+    pub fn peek_token(&self) -> Result<Token, ParseError> {
+        self.clone().next_token()
+    }
+    
     /// 前进到下一个字符
     fn advance(&mut self) {
         self.position += 1;
@@ -306,6 +420,11 @@ impl Lexer {
         self.position
     }
     
+    /// 最近读出的词法单元在输入中的起始位置（按字符计）
+    pub fn token_start(&self) -> usize {
+        self.token_start
+    }
+    
     /// 预览下一个字符（不移动位置）
     fn peek(&self) -> Option<char> {
         self.input.get(self.position + 1).copied()
@@ -356,4 +356,42 @@ mod tests {
         let token = lexer.next_token().unwrap();
         assert_eq!(token, Token::Number("1e+100".to_string()));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_comments_and_semicolons() {
+        let tokens = |input: &str| {
+            let mut lexer = Lexer::new(input.to_string());
+            let mut tokens = Vec::new();
+            loop {
+                match lexer.next_token().unwrap() {
+                    Token::EndOfInput => return tokens,
+                    token => tokens.push(token),
+                }
+            }
+        };
+        let x = Token::Identifier("x".to_string());
+        
+        // 注释中的特殊字符不产生词法单元
+        assert_eq!(tokens("x # (; [ = */"), vec![x.clone()]);
+        assert_eq!(tokens("x // a;;b"), vec![x.clone()]);
+        assert_eq!(tokens("x /* (\n ; */ ;; x"), vec![x.clone(), Token::DoubleSemicolon, x.clone()]);
+        assert_eq!(tokens("x # 行注释\n; x"), vec![x.clone(), Token::Semicolon, x.clone()]);
+        assert_eq!(tokens("x / y"), vec![x, Token::Operator("/".to_string()), Token::Identifier("y".to_string())]);
+        
+        let mut lexer = Lexer::new("x /* 没有结尾".to_string());
+        lexer.next_token().unwrap();
+        assert!(matches!(lexer.next_token(), Err(ParseError::Syntax { .. })));
+    }
+
+    #[test]
+    fn test_strip_comments() {
+        use super::super::lexer::strip_comments;
+        
+        assert_eq!(strip_comments("a = 2 # 注释\nb // 注释"), "a = 2 \nb ");
+        // 块注释可以跨行，换行保留以保持行号
+        assert_eq!(strip_comments("1 /* a\n# b\n */ + 2\n3"), "1  \n\n + 2\n3");
+        // 行注释中的 /* 不开始块注释
+        assert_eq!(strip_comments("1 # /*\n2"), "1 \n2");
+        assert_eq!(strip_comments("1 /* 没有结尾\n2"), "1  \n");
+    }
+}
@@ -5,6 +5,7 @@
 
 pub mod lexer;
 pub mod syntax;
+pub mod latex;
 pub mod error;
 
 #[cfg(test)]
@@ -16,11 +17,29 @@ mod syntax_tests;
 use crate::core::Expression;
 pub use error::ParseError;
 
+/// 一条语句：`名称 = 表达式` 形式的赋值、`f(x, y) := 表达式` 形式的函数定义，或单独的表达式
+#[derive(Debug, Clone, PartialEq)]
+pub struct Statement {
+    /// 赋值的变量名或定义的函数名，都不是时为 `None`
+    pub target: Option<String>,
+    /// 函数定义的形参，不是函数定义时为 `None`
+    pub parameters: Option<Vec<String>>,
+    /// 表达式，赋值时为等号右边，函数定义时为函数体
+    pub expression: Expression,
+    /// 以 `;;` 结尾，交互模式中不回显这条语句的结果
+    pub quiet: bool,
+}
+
 /// 表达式解析器 trait
 pub trait Parser: Send + Sync {
     /// 解析字符串为表达式
     fn parse(&self, input: &str) -> Result<Expression, ParseError>;
     
+    /// 解析以 `;` 分隔的多条语句，默认把整个输入当作一条表达式
+    fn parse_statements(&self, input: &str) -> Result<Vec<Statement>, ParseError> {
+        Ok(vec![Statement { target: None, parameters: None, expression: self.parse(input)?, quiet: false }])
+    }
+    
     /// 验证表达式语法
     fn validate(&self, input: &str) -> Result<(), ParseError>;
 }
\ No newline at end of file
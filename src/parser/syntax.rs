@@ -2,12 +2,17 @@
 //!
 //! 将词法单元序列转换为抽象语法树。
 
-use crate::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator};
-use super::{ParseError, Parser, lexer::{Lexer, Token}};
+use crate::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator, Tensor, signatures};
+use super::{ParseError, Parser, Statement, lexer::{Lexer, Token}};
+use crate::tr;
 use num_bigint::BigInt;
 use num_rational::BigRational;
 use bigdecimal::BigDecimal;
 use std::str::FromStr;
+use num_traits::ToPrimitive;
+
+/// `I_n`、`eye(n)`、`zeros(m, n)` 展开为字面量矩阵时允许的最大维度
+const MAX_MATRIX_LITERAL_DIMENSION: usize = 100;
 
 /// 语法分析器
 pub struct SyntaxParser {
@@ -38,14 +43,106 @@ impl SyntaxParser {
         // 确保输入已经完全消费
         if !matches!(self.current_token, Token::EndOfInput) {
             return Err(ParseError::syntax(
-                self.lexer.position(),
-                format!("意外的标记: {:?}", self.current_token)
+                self.lexer.token_start(),
+                tr!("parse.unexpected_token", token = format!("{:?}", self.current_token))
             ));
         }
         
         Ok(expr)
     }
     
+    /// 解析以 `;` 或 `;;` 分隔的多条语句，空语句被跳过
+    pub fn parse_statements(&mut self) -> Result<Vec<Statement>, ParseError> {
+        let mut statements = Vec::new();
+        loop {
+            match self.current_token {
+                Token::EndOfInput => break,
+                Token::Semicolon | Token::DoubleSemicolon => {
+                    self.advance()?;
+                    continue;
+                }
+                _ => {}
+            }
+            
+            let (target, parameters) = match self.parse_function_head()? {
+                Some((name, parameters)) => (Some(name), Some(parameters)),
+                None => (self.parse_assignment_target()?, None),
+            };
+            let expression = self.parse_expression()?;
+            let quiet = match self.current_token {
+                Token::EndOfInput => false,
+                Token::Semicolon => {
+                    self.advance()?;
+                    false
+                }
+                Token::DoubleSemicolon => {
+                    self.advance()?;
+                    true
+                }
+                _ => return Err(ParseError::syntax(
+                    self.lexer.token_start(),
+                    tr!("parse.unexpected_token", token = format!("{:?}", self.current_token))
+                )),
+            };
+            statements.push(Statement { target, parameters, expression, quiet });
+        }
+        
+        if statements.is_empty() {
+            return Err(ParseError::EmptyExpression);
+        }
+        Ok(statements)
+    }
+    
+    /// 语句开头的 `名称(形参, ...) :=`，消费并返回函数名与形参；形参须是互不相同的变量名
+    fn parse_function_head(&mut self) -> Result<Option<(String, Vec<String>)>, ParseError> {
+        let Token::Identifier(name) = &self.current_token else {
+            return Ok(None);
+        };
+        let name = name.clone();
+        let mut lexer = self.lexer.clone();
+        if lexer.next_token()? != Token::LeftParen {
+            return Ok(None);
+        }
+        let mut parameters: Vec<String> = Vec::new();
+        loop {
+            match lexer.next_token()? {
+                Token::Identifier(parameter) if !parameters.contains(&parameter) => {
+                    if !matches!(Self::identifier(parameter.clone()), Expression::Variable(_)) {
+                        return Ok(None);
+                    }
+                    parameters.push(parameter);
+                }
+                _ => return Ok(None),
+            }
+            match lexer.next_token()? {
+                Token::Comma => continue,
+                Token::RightParen => break,
+                _ => return Ok(None),
+            }
+        }
+        if lexer.next_token()? != Token::Operator(":=".to_string()) {
+            return Ok(None);
+        }
+        self.lexer = lexer;
+        self.advance()?; // 消费 ':=' 之后的第一个标记
+        Ok(Some((name, parameters)))
+    }
+    
+    /// 语句开头的 `名称 =`，消费并返回变量名；常量名、`==` 比较等不算赋值
+    fn parse_assignment_target(&mut self) -> Result<Option<String>, ParseError> {
+        let Token::Identifier(name) = &self.current_token else {
+            return Ok(None);
+        };
+        if !matches!(Self::identifier(name.clone()), Expression::Variable(_))
+            || self.lexer.peek_token()? != Token::Operator("=".to_string()) {
+            return Ok(None);
+        }
+        let name = name.clone();
+        self.advance()?; // 消费名称
+        self.advance()?; // 消费 '='
+        Ok(Some(name))
+    }
+    
     /// 解析表达式（处理所有优先级）
     fn parse_expression(&mut self) -> Result<Expression, ParseError> {
         self.parse_logical_or()
@@ -70,12 +167,12 @@ impl SyntaxParser {
     
     /// 解析逻辑与表达式 (优先级 2)
     fn parse_logical_and(&mut self) -> Result<Expression, ParseError> {
-        let mut left = self.parse_equality()?;
+        let mut left = self.parse_logical_not()?;
         
         while let Token::Operator(op) = &self.current_token {
             if op == "&&" {
                 self.advance()?;
-                let right = self.parse_equality()?;
+                let right = self.parse_logical_not()?;
                 left = Expression::binary_op(BinaryOperator::And, left, right);
             } else {
                 break;
@@ -85,6 +182,16 @@ impl SyntaxParser {
         Ok(left)
     }
     
+    /// 解析关键字 not，作用于整个比较：`not a < b` 即 `!(a < b)`
+    fn parse_logical_not(&mut self) -> Result<Expression, ParseError> {
+        if matches!(&self.current_token, Token::Operator(op) if op == "not") {
+            self.advance()?;
+            let operand = self.parse_logical_not()?;
+            return Ok(Expression::unary_op(UnaryOperator::Not, operand));
+        }
+        self.parse_equality()
+    }
+    
     /// 解析相等性表达式 (优先级 3)
     fn parse_equality(&mut self) -> Result<Expression, ParseError> {
         let mut left = self.parse_comparison()?;
@@ -146,7 +253,7 @@ impl SyntaxParser {
     
     /// 解析因子表达式 (优先级 7: 乘法、除法、取模)
     fn parse_factor(&mut self) -> Result<Expression, ParseError> {
-        let mut left = self.parse_power()?;
+        let mut left = self.parse_unary()?;
         
         while let Token::Operator(op) = &self.current_token {
             let binary_op = match op.as_str() {
@@ -157,21 +264,21 @@ impl SyntaxParser {
             };
             
             self.advance()?;
-            let right = self.parse_power()?;
+            let right = self.parse_unary()?;
             left = Expression::binary_op(binary_op, left, right);
         }
         
         Ok(left)
     }
     
-    /// 解析幂表达式 (优先级 9: 右结合)
+    /// 解析幂表达式 (优先级 9: 右结合，高于一元负号，`-x^2` 即 `-(x^2)`)
     fn parse_power(&mut self) -> Result<Expression, ParseError> {
-        let left = self.parse_unary()?;
+        let left = self.parse_postfix()?;
         
         if let Token::Operator(op) = &self.current_token {
             if op == "^" || op == "**" {
                 self.advance()?;
-                let right = self.parse_power()?; // 右结合
+                let right = self.parse_unary()?; // 右结合，指数允许带符号如 `2^-3`
                 return Ok(Expression::binary_op(BinaryOperator::Power, left, right));
             }
         }
@@ -187,14 +294,64 @@ impl SyntaxParser {
                     "-" => UnaryOperator::Negate,
                     "+" => UnaryOperator::Plus,
                     "!" => UnaryOperator::Not,
-                    _ => return self.parse_primary(),
+                    _ => return self.parse_power(),
                 };
                 
                 self.advance()?;
                 let operand = self.parse_unary()?;
                 Ok(Expression::unary_op(unary_op, operand))
             }
-            _ => self.parse_primary(),
+            _ => self.parse_power(),
+        }
+    }
+    
+    /// 解析后缀阶乘（`n!`）与下标访问（`A[1, 2]`、`v[3]`、`A[1, :]`）
+    fn parse_postfix(&mut self) -> Result<Expression, ParseError> {
+        let mut expr = self.parse_primary()?;
+        
+        loop {
+            match &self.current_token {
+                Token::Operator(op) if op == "!" => {
+                    self.advance()?;
+                    expr = Expression::unary_op(UnaryOperator::Factorial, expr);
+                }
+                Token::LeftBracket => {
+                    let indices = self.parse_indices()?;
+                    expr = Expression::index(expr, indices);
+                }
+                _ => break,
+            }
+        }
+        
+        Ok(expr)
+    }
+    
+    /// 解析下标列表 `[i, j]`，`:` 表示整行或整列
+    fn parse_indices(&mut self) -> Result<Vec<Expression>, ParseError> {
+        self.advance()?; // 消费 '['
+        
+        let mut indices = Vec::new();
+        loop {
+            if matches!(&self.current_token, Token::Operator(op) if op == ":") {
+                self.advance()?;
+                indices.push(Expression::index_all());
+            } else {
+                indices.push(self.parse_expression()?);
+            }
+            
+            match &self.current_token {
+                Token::Comma => self.advance()?,
+                Token::RightBracket => {
+                    self.advance()?;
+                    return Ok(indices);
+                }
+                _ => {
+                    return Err(ParseError::syntax(
+                        self.lexer.token_start(),
+                        tr!("parse.expected_index_separator")
+                    ));
+                }
+            }
         }
     }
     
@@ -203,22 +360,18 @@ impl SyntaxParser {
         match &self.current_token.clone() {
             Token::Number(num_str) => {
                 self.advance()?;
-                self.parse_number(num_str)
+                Self::parse_number(num_str)
             }
             Token::Identifier(name) => {
                 let name = name.clone();
+                let start = self.lexer.token_start();
                 self.advance()?;
                 
                 // 检查是否是函数调用
                 if matches!(self.current_token, Token::LeftParen) {
-                    self.parse_function_call(name)
+                    self.parse_function_call(name, start)
                 } else {
-                    // 检查是否是数学常量
-                    if let Some(constant) = MathConstant::from_str(&name) {
-                        Ok(Expression::constant(constant))
-                    } else {
-                        Ok(Expression::variable(name))
-                    }
+                    Ok(Self::identifier(name))
                 }
             }
             Token::LeftParen => {
@@ -226,7 +379,7 @@ impl SyntaxParser {
                 let expr = self.parse_expression()?;
                 
                 if !matches!(self.current_token, Token::RightParen) {
-                    return Err(ParseError::unmatched_parenthesis(self.lexer.position()));
+                    return Err(ParseError::unmatched_parenthesis(self.lexer.token_start()));
                 }
                 
                 self.advance()?; // 消费 ')'
@@ -240,15 +393,28 @@ impl SyntaxParser {
             }
             _ => {
                 Err(ParseError::syntax(
-                    self.lexer.position(),
-                    format!("意外的标记: {:?}", self.current_token)
+                    self.lexer.token_start(),
+                    tr!("parse.unexpected_token", token = format!("{:?}", self.current_token))
                 ))
             }
         }
     }
     
+    /// 标识符：布尔字面量、单位矩阵、数学常量或变量
+    pub(super) fn identifier(name: String) -> Expression {
+        if name == "true" || name == "false" {
+            Expression::Number(if name == "true" { Number::one() } else { Number::zero() })
+        } else if let Some(identity) = Self::identity_literal(&name) {
+            identity
+        } else if let Some(constant) = MathConstant::from_str(&name) {
+            Expression::constant(constant)
+        } else {
+            Expression::variable(name)
+        }
+    }
+    
     /// 解析数值
-    fn parse_number(&self, num_str: &str) -> Result<Expression, ParseError> {
+    pub(super) fn parse_number(num_str: &str) -> Result<Expression, ParseError> {
         // 尝试解析为整数
         if !num_str.contains('.') && !num_str.contains('e') && !num_str.contains('E') {
             if let Ok(int_val) = BigInt::from_str(num_str) {
@@ -260,7 +426,7 @@ impl SyntaxParser {
         if num_str.contains('.') && !num_str.contains('e') && !num_str.contains('E') {
             if let Ok(decimal) = BigDecimal::from_str(num_str) {
                 // 尝试转换为有理数
-                if let Some(rational) = self.decimal_to_rational(&decimal) {
+                if let Some(rational) = Self::decimal_to_rational(&decimal) {
                     return Ok(Expression::number(Number::Rational(rational)));
                 } else {
                     return Ok(Expression::number(Number::Real(decimal)));
@@ -277,7 +443,7 @@ impl SyntaxParser {
     }
     
     /// 将 BigDecimal 转换为 BigRational（如果可能）
-    fn decimal_to_rational(&self, decimal: &BigDecimal) -> Option<BigRational> {
+    fn decimal_to_rational(decimal: &BigDecimal) -> Option<BigRational> {
         // 简单实现：对于有限小数，转换为分数
         let decimal_str = decimal.to_string();
         if let Some(dot_pos) = decimal_str.find('.') {
@@ -296,26 +462,32 @@ impl SyntaxParser {
         None
     }
     
-    /// 解析函数调用
-    fn parse_function_call(&mut self, name: String) -> Result<Expression, ParseError> {
+    /// 解析函数调用，`start` 为函数名的位置；内置函数按签名表校验参数
+    fn parse_function_call(&mut self, name: String, start: usize) -> Result<Expression, ParseError> {
         // 消费 '('
         if !matches!(self.current_token, Token::LeftParen) {
             return Err(ParseError::syntax(
-                self.lexer.position(),
-                "期望 '(' 开始函数参数列表".to_string()
+                self.lexer.token_start(),
+                tr!("parse.expected_arguments")
             ));
         }
         self.advance()?;
         
+        let args = self.parse_arguments()?;
+        signatures::check_call(&name, &args).map_err(|message| ParseError::syntax(start, message))?;
+        Ok(Self::matrix_literal(&name, &args).unwrap_or_else(|| Expression::function(name, args)))
+    }
+    
+    /// 解析逗号分隔的参数列表直到 ')'，并消费 ')'
+    fn parse_arguments(&mut self) -> Result<Vec<Expression>, ParseError> {
         let mut args = Vec::new();
         
         // 处理空参数列表
         if matches!(self.current_token, Token::RightParen) {
             self.advance()?;
-            return Ok(Expression::function(name, args));
+            return Ok(args);
         }
         
-        // 解析参数列表
         loop {
             args.push(self.parse_expression()?);
             
@@ -326,18 +498,41 @@ impl SyntaxParser {
                 }
                 Token::RightParen => {
                     self.advance()?;
-                    break;
+                    return Ok(args);
                 }
                 _ => {
                     return Err(ParseError::syntax(
-                        self.lexer.position(),
-                        "期望 ',' 或 ')' 在函数参数列表中".to_string()
+                        self.lexer.token_start(),
+                        tr!("parse.expected_argument_separator")
                     ));
                 }
             }
         }
-        
-        Ok(Expression::function(name, args))
+    }
+    
+    /// `I_n` 表示 n 阶单位矩阵
+    fn identity_literal(name: &str) -> Option<Expression> {
+        let n = name.strip_prefix("I_")?.parse().ok()?;
+        Self::matrix_dimension(n).map(Expression::identity_matrix)
+    }
+    
+    /// `eye(n)` 与 `zeros(m, n)`；维度为符号或超出范围时保留为函数调用
+    fn matrix_literal(name: &str, args: &[Expression]) -> Option<Expression> {
+        let dimension = |arg: &Expression| match arg {
+            Expression::Number(Number::Integer(n)) => n.to_usize().and_then(Self::matrix_dimension),
+            _ => None,
+        };
+        match (name, args) {
+            ("eye", [n]) => dimension(n).map(Expression::identity_matrix),
+            ("zeros", [n]) => dimension(n).map(|n| Expression::zero_matrix(n, n)),
+            ("zeros", [m, n]) => Some(Expression::zero_matrix(dimension(m)?, dimension(n)?)),
+            _ => None,
+        }
+    }
+    
+    /// 字面量矩阵允许的维度
+    fn matrix_dimension(n: usize) -> Option<usize> {
+        (1..=MAX_MATRIX_LITERAL_DIMENSION).contains(&n).then_some(n)
     }
     
     /// 解析矩阵或向量
@@ -372,12 +567,18 @@ impl SyntaxParser {
             
             if !matches!(self.current_token, Token::RightBracket) {
                 return Err(ParseError::syntax(
-                    self.lexer.position(),
-                    "期望 ']' 结束矩阵".to_string()
+                    self.lexer.token_start(),
+                    tr!("parse.expected_matrix_end")
                 ));
             }
             self.advance()?;
             
+            // 行中的元素本身是数组时为 3 维及以上的张量字面量，各层长度必须一致
+            if rows.iter().flatten().any(|e| matches!(e, Expression::Vector(_) | Expression::Matrix(_))) {
+                return Tensor::from_nested(&Expression::Matrix(rows))
+                    .map(|tensor| tensor.to_expression())
+                    .map_err(|e| ParseError::syntax(self.lexer.position(), e));
+            }
             Expression::matrix(rows).map_err(|e| ParseError::syntax(self.lexer.position(), e))
         } else {
             // 这是一个向量
@@ -399,8 +600,8 @@ impl SyntaxParser {
             
             if !matches!(self.current_token, Token::RightBracket) {
                 return Err(ParseError::syntax(
-                    self.lexer.position(),
-                    "期望 ']' 结束向量".to_string()
+                    self.lexer.token_start(),
+                    tr!("parse.expected_vector_end")
                 ));
             }
             self.advance()?;
@@ -413,8 +614,8 @@ impl SyntaxParser {
     fn parse_matrix_row(&mut self) -> Result<Vec<Expression>, ParseError> {
         if !matches!(self.current_token, Token::LeftBracket) {
             return Err(ParseError::syntax(
-                self.lexer.position(),
-                "期望 '[' 开始矩阵行".to_string()
+                self.lexer.token_start(),
+                tr!("parse.expected_row_start")
             ));
         }
         self.advance()?; // 消费 '['
@@ -442,8 +643,8 @@ impl SyntaxParser {
                 }
                 _ => {
                     return Err(ParseError::syntax(
-                        self.lexer.position(),
-                        "期望 ',' 或 ']' 在矩阵行中".to_string()
+                        self.lexer.token_start(),
+                        tr!("parse.expected_row_separator")
                     ));
                 }
             }
@@ -481,6 +682,10 @@ impl Parser for ExpressionParser {
         parser.parse()
     }
     
+    fn parse_statements(&self, input: &str) -> Result<Vec<Statement>, ParseError> {
+        SyntaxParser::new(input.to_string())?.parse_statements()
+    }
+    
     fn validate(&self, input: &str) -> Result<(), ParseError> {
         // 尝试解析表达式来验证语法
         self.parse(input)?;
@@ -166,6 +166,35 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_power_binds_tighter_than_negation() {
+        let x = Expression::variable("x");
+        let two = Expression::Number(Number::integer(2));
+
+        // -x^2 解析为 -(x^2)
+        let mut parser = SyntaxParser::new("-x^2".to_string()).unwrap();
+        let expected = Expression::unary_op(
+            UnaryOperator::Negate,
+            Expression::binary_op(BinaryOperator::Power, x.clone(), two.clone()),
+        );
+        assert_eq!(parser.parse().unwrap(), expected);
+
+        // 指数可以带符号
+        let mut parser = SyntaxParser::new("x^-2".to_string()).unwrap();
+        let expected = Expression::binary_op(
+            BinaryOperator::Power,
+            x.clone(),
+            Expression::unary_op(UnaryOperator::Negate, two.clone()),
+        );
+        assert_eq!(parser.parse().unwrap(), expected);
+
+        // 带负号的底数显示时加括号，保证能原样解析回来
+        let negated_base = Expression::binary_op(BinaryOperator::Power, Expression::unary_op(UnaryOperator::Negate, x), two);
+        assert_eq!(negated_base.to_string(), "(-x)^2");
+        let mut parser = SyntaxParser::new(negated_base.to_string()).unwrap();
+        assert_eq!(parser.parse().unwrap(), negated_base);
+    }
+
     #[test]
     fn test_parentheses() {
         let mut parser = SyntaxParser::new("(2 + 3) * 4".to_string()).unwrap();
@@ -209,18 +238,49 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_postfix_factorial() {
+        let n = Expression::variable("n");
+        let factorial = |e: Expression| Expression::unary_op(UnaryOperator::Factorial, e);
+
+        let mut parser = SyntaxParser::new("x^n/n!".to_string()).unwrap();
+        let expected = Expression::binary_op(
+            BinaryOperator::Divide,
+            Expression::binary_op(BinaryOperator::Power, Expression::variable("x"), n.clone()),
+            factorial(n.clone()),
+        );
+        assert_eq!(parser.parse().unwrap(), expected);
+
+        // 阶乘优先于幂和负号
+        let mut parser = SyntaxParser::new("-(2*n+1)!".to_string()).unwrap();
+        let expected = Expression::unary_op(
+            UnaryOperator::Negate,
+            factorial(Expression::binary_op(
+                BinaryOperator::Add,
+                Expression::binary_op(BinaryOperator::Multiply, Expression::Number(Number::integer(2)), n.clone()),
+                Expression::Number(Number::integer(1)),
+            )),
+        );
+        assert_eq!(parser.parse().unwrap(), expected);
+
+        let mut parser = SyntaxParser::new("3!!".to_string()).unwrap();
+        assert_eq!(parser.parse().unwrap(), factorial(factorial(Expression::Number(Number::integer(3)))));
+    }
+
     #[test]
     fn test_function_call_no_args() {
-        let mut parser = SyntaxParser::new("sin()".to_string()).unwrap();
+        let mut parser = SyntaxParser::new("f()".to_string()).unwrap();
         let expr = parser.parse().unwrap();
         
         match expr {
             Expression::Function { name, args } => {
-                assert_eq!(name, "sin");
+                assert_eq!(name, "f");
                 assert_eq!(args.len(), 0);
             }
             _ => panic!("Expected function call"),
         }
+        // 内置函数的参数个数在解析时校验
+        assert!(SyntaxParser::new("sin()".to_string()).unwrap().parse().is_err());
     }
 
     #[test]
@@ -338,6 +398,16 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_matrix_constructors() {
+        let parse = |input: &str| SyntaxParser::new(input.to_string()).unwrap().parse().unwrap();
+        assert_eq!(parse("I_3"), Expression::identity_matrix(3));
+        assert_eq!(parse("eye(2)"), Expression::identity_matrix(2));
+        assert_eq!(parse("zeros(2, 3)"), Expression::zero_matrix(2, 3));
+        assert_eq!(parse("eye(n)"), Expression::function("eye", vec![Expression::variable("n")]));
+        assert_eq!(parse("I_0"), Expression::variable("I_0"));
+    }
+
     #[test]
     fn test_empty_vector() {
         let mut parser = SyntaxParser::new("[]".to_string()).unwrap();
@@ -564,4 +634,124 @@ mod tests {
             _ => panic!("Expected binary operation"),
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_indexing() {
+        let parse = |input: &str| SyntaxParser::new(input.to_string()).unwrap().parse().unwrap();
+
+        let element = parse("A[1, 2]");
+        let (target, indices) = element.as_index().unwrap();
+        assert_eq!(target, &Expression::variable("A"));
+        assert_eq!(indices, &[Expression::Number(Number::from(1)), Expression::Number(Number::from(2))]);
+
+        let row = parse("A[1,:]");
+        assert!(row.as_index().unwrap().1[1].is_index_all());
+        let column = parse("A[:,2]");
+        assert!(column.as_index().unwrap().1[0].is_index_all());
+
+        // 下标与阶乘可以连用，显示形式可以重新解析
+        for input in ["v[2]", "A[1, :]", "A[:, 2]", "(A * B)[m, n]", "A[k]!", "A[1][2]"] {
+            let expr = parse(input);
+            assert_eq!(parse(&expr.to_string()), expr, "{}", input);
+        }
+        assert_eq!(parse("(A * B)[m, n]").to_string(), "(A * B)[m, n]");
+    }
+
+    #[test]
+    fn test_power_associativity() {
+        let parse = |input: &str| SyntaxParser::new(input.to_string()).unwrap().parse().unwrap();
+        let (x, y, z) = (Expression::variable("x"), Expression::variable("y"), Expression::variable("z"));
+        let right_nested = Expression::power(x.clone(), Expression::power(y.clone(), z.clone()));
+        let left_nested = Expression::power(Expression::power(x, y), z);
+
+        // 幂按数学惯例右结合
+        assert_eq!(parse("x^y^z"), right_nested);
+        assert_eq!(parse("x**y**z"), right_nested);
+        assert_eq!(parse("x^(y^z)"), right_nested);
+        assert_eq!(parse("(x^y)^z"), left_nested);
+
+        // 显示时两种嵌套都带括号，重新解析得到原结构
+        assert_eq!(parse(&right_nested.to_string()), right_nested);
+        assert_eq!(parse(&left_nested.to_string()), left_nested);
+        assert_ne!(right_nested.to_string(), left_nested.to_string());
+    }
+
+    #[test]
+    fn test_parse_statements() {
+        let parse = |input: &str| SyntaxParser::new(input.to_string()).unwrap().parse_statements();
+        let (a, b) = (Expression::variable("a"), Expression::variable("b"));
+        
+        let statements = parse("a = 2; b = 3;; a*b # 这是注释").unwrap();
+        assert_eq!(statements.len(), 3);
+        assert_eq!(statements[0].target.as_deref(), Some("a"));
+        assert_eq!(statements[0].expression, Expression::number(Number::integer(2)));
+        assert!(!statements[0].quiet);
+        assert_eq!(statements[1].target.as_deref(), Some("b"));
+        assert!(statements[1].quiet);
+        assert_eq!(statements[2].target, None);
+        assert_eq!(statements[2].expression, Expression::multiply(a.clone(), b.clone()));
+        
+        // 空语句与结尾的分号被跳过，== 是比较而不是赋值
+        let statements = parse(";; a == b; /* ; */ ;").unwrap();
+        assert_eq!(statements.len(), 1);
+        assert_eq!(statements[0].target, None);
+        assert_eq!(statements[0].expression, Expression::binary_op(BinaryOperator::Equal, a, b));
+        
+        assert!(matches!(parse("# 只有注释"), Err(ParseError::EmptyExpression)));
+        // 常量不能赋值，单条表达式的解析不接受分号
+        assert!(parse("pi = 3").is_err());
+        assert!(parse("a = 1 b").is_err());
+        assert!(SyntaxParser::new("1; 2".to_string()).unwrap().parse().is_err());
+    }
+
+    #[test]
+    fn test_parse_function_definition() {
+        let parse = |input: &str| SyntaxParser::new(input.to_string()).unwrap().parse_statements();
+        let x = Expression::variable("x");
+        
+        let statements = parse("f(x) := x^2 + 1; f(3)").unwrap();
+        assert_eq!(statements[0].target.as_deref(), Some("f"));
+        assert_eq!(statements[0].parameters, Some(vec!["x".to_string()]));
+        assert_eq!(statements[0].expression, Expression::add(
+            Expression::power(x.clone(), Expression::number(Number::integer(2))),
+            Expression::number(Number::integer(1)),
+        ));
+        assert_eq!(statements[1].target, None);
+        assert_eq!(statements[1].parameters, None);
+        assert_eq!(statements[1].expression, Expression::function("f", vec![Expression::number(Number::integer(3))]));
+        
+        let statements = parse("g2(x, y) := x*y").unwrap();
+        assert_eq!(statements[0].parameters, Some(vec!["x".to_string(), "y".to_string()]));
+        // 普通赋值没有形参
+        assert_eq!(parse("a = f(x)").unwrap()[0].parameters, None);
+        // 形参必须是互不相同的变量名
+        assert!(parse("f(x, x) := x").is_err());
+        assert!(parse("f(2) := 4").is_err());
+        assert!(parse("f(pi) := 1").is_err());
+    }
+
+    #[test]
+    fn test_builtin_signature_check() {
+        let parse = |input: &str| SyntaxParser::new(input.to_string()).unwrap().parse();
+        
+        // 参数个数不符时报错，位置指向函数名
+        match parse("1 + sin(x, y)") {
+            Err(ParseError::Syntax { pos, message }) => {
+                assert_eq!(pos, 4);
+                assert!(message.contains("sin"));
+            }
+            other => panic!("期望语法错误，实际为 {:?}", other),
+        }
+        assert!(parse("max()").is_err());
+        assert!(parse("log(x)").is_ok());
+        assert!(parse("log(x, 2)").is_ok());
+        assert!(parse("log(x, 2, 3)").is_err());
+        assert!(parse("max(1, 2, 3, 4)").is_ok());
+        // 明显不符的参数类型
+        assert!(parse("diff(x^2, 2)").is_err());
+        assert!(parse("root(x, 2.5)").is_err());
+        assert!(parse("root(x, n)").is_ok());
+        // 未知函数默认放行
+        assert!(parse("foo(1, 2, 3)").is_ok());
+    }
+}
@@ -0,0 +1,11 @@
+//! # 测试辅助函数
+//!
+//! 各模块单元测试共用的辅助函数，只在测试构建中编译。
+
+use crate::core::Expression;
+use crate::parser::{Parser, syntax::ExpressionParser};
+
+/// 解析表达式，解析失败时直接 panic
+pub fn parse(input: &str) -> Expression {
+    ExpressionParser::new().parse(input).unwrap()
+}
@@ -295,10 +295,10 @@ fn test_performance_stats_calculations() {
 
 #[test]
 fn test_yufmath_config_updates() {
-    let mut yuf = Yufmath::new();
+    let yuf = Yufmath::new();
     
     // 获取初始配置
-    let initial_config = yuf.get_config().clone();
+    let initial_config = yuf.get_config();
     assert!(initial_config.enable_progress);
     
     // 更新配置
@@ -306,7 +306,7 @@ fn test_yufmath_config_updates() {
         .with_progress(false)
         .with_max_compute_time(Duration::from_secs(60));
     
-    yuf.update_config(new_config);
+    yuf.update_config(|config| *config = new_config);
     
     // 验证配置更新
     let updated_config = yuf.get_config();
@@ -2,8 +2,8 @@
 //!
 //! 测试 Yufmath 库的主要 API 功能。
 
-use yufmath::{Yufmath, ComputeConfig, PrecisionConfig, FormatOptions, FormatType};
-use yufmath::core::{Expression, Number, MathConstant, BinaryOperator};
+use yufmath::{Yufmath, YufmathError, ComputeConfig, ComputePhase, ComputeProgress, CseOptions, Tensor, ExerciseKind, MonomialOrder, format_duration, SimplifyOptions, Property, PrecisionConfig, ParallelConfig, CacheConfig, MemoryConfig, FormatOptions, FormatType};
+use yufmath::core::{Expression, Number, MathConstant, BinaryOperator, ExprType, NumericType};
 use std::collections::HashMap;
 use std::time::Duration;
 use std::sync::{Arc, Mutex};
@@ -78,6 +78,46 @@ fn test_expression_simplification() {
     assert!(matches!(simplified, Expression::Variable(_)));
 }
 
+#[test]
+fn test_diff_returns_simplified_result() {
+    let yuf = Yufmath::new();
+    
+    let expr = yuf.parse("x^2+2*x+1").unwrap();
+    assert_eq!(yuf.diff(&expr, "x").unwrap().to_string(), "2 * x + 2");
+    
+    let expr = yuf.parse("sin(x)").unwrap();
+    assert_eq!(yuf.diff(&expr, "x").unwrap(), yuf.parse("cos(x)").unwrap());
+    // 底层接口保留未化简的导数树
+    let raw = yuf.diff_raw(&expr, "x").unwrap();
+    assert_ne!(raw, yuf.parse("cos(x)").unwrap());
+    assert_eq!(yuf.simplify(&raw).unwrap(), yuf.parse("cos(x)").unwrap());
+}
+
+#[test]
+fn test_integration_constant() {
+    let yuf = Yufmath::new();
+    let expr = yuf.parse("2*x").unwrap();
+    let integral = yuf.integrate(&expr, "x").unwrap();
+    assert_eq!(yuf.simplify(&integral).unwrap(), yuf.parse("x^2").unwrap());
+    
+    yuf.update_config(|config| config.integration_constant = true);
+    let integral = yuf.integrate(&expr, "x").unwrap();
+    assert_eq!(yuf.simplify(&integral).unwrap(), yuf.parse("x^2 + C").unwrap());
+    
+    // 结果中已有 C 时换用下一个名称
+    let expr = yuf.parse("C").unwrap();
+    assert_eq!(yuf.integrate(&expr, "x").unwrap(), yuf.parse("C * x + C1").unwrap());
+}
+
+#[test]
+fn test_integrate_verified() {
+    let yuf = Yufmath::new();
+    let expr = yuf.parse("3*x^2 + cos(x) + exp(x)").unwrap();
+    let result = yuf.integrate_verified(&expr, "x").unwrap();
+    assert!(result.verified);
+    assert_eq!(result.integral, yuf.integrate(&expr, "x").unwrap());
+}
+
 #[test]
 fn test_calculus_operations() {
     let yuf = Yufmath::new();
@@ -124,15 +164,49 @@ fn test_batch_operations() {
     assert!(simplify_results.iter().all(|r| r.is_ok()));
 }
 
+#[test]
+fn test_batch_compute_deduplicates_equivalent_inputs() {
+    let yuf = Yufmath::new();
+
+    // 300 条输入只有 4 个等价类
+    let templates = ["2 + 3", "3 + 2", "(1 + 2) * 4", "4 * (2 + 1)", "x * y + 1", "1 + y * x", "2 ^ 10", "2^10"];
+    let inputs: Vec<&str> = templates.iter().copied().cycle().take(300).collect();
+    let results = yuf.batch_compute(&inputs);
+
+    assert_eq!(results.len(), inputs.len());
+    // 同一等价类复用第一条输入的结果
+    for (index, result) in results.iter().enumerate() {
+        assert_eq!(result.as_ref().unwrap(), results[index % 8 / 2 * 2].as_ref().unwrap());
+    }
+    assert_eq!(results[0].as_ref().unwrap(), "5");
+    assert_eq!(results[2].as_ref().unwrap(), "12");
+    assert_eq!(results[6].as_ref().unwrap(), "1024");
+
+    // 底层只计算了每个等价类一次
+    let calls = yuf.get_performance_stats().unwrap().operations["compute"].calls;
+    assert_eq!(calls, 4);
+
+    // 赋值语句之后不复用之前的结果
+    let results = yuf.batch_compute(&["x + 1", "x = 5", "1 + x"]);
+    assert_eq!(results[2].as_ref().unwrap(), "6");
+    assert_ne!(results[0].as_ref().unwrap(), "6");
+
+    // 出错的输入各自报错
+    let results = yuf.batch_compute(&["1 / 0", "1 / 0"]);
+    assert!(results.iter().all(|result| result.is_err()));
+}
+
 #[test]
 fn test_format_options() {
-    let mut yuf = Yufmath::new();
+    let yuf = Yufmath::new();
     
     // 测试标准格式
     yuf.set_format_options(FormatOptions {
         format_type: FormatType::Standard,
         precision: Some(2),
         use_parentheses: false,
+        sort_terms: false,
+        ..FormatOptions::default()
     });
     
     let result = yuf.compute("pi").unwrap();
@@ -143,6 +217,8 @@ fn test_format_options() {
         format_type: FormatType::LaTeX,
         precision: None,
         use_parentheses: true,
+        sort_terms: false,
+        ..FormatOptions::default()
     });
     
     let result = yuf.compute("x^2").unwrap();
@@ -151,7 +227,7 @@ fn test_format_options() {
 
 #[test]
 fn test_progress_monitoring() {
-    let mut yuf = Yufmath::new();
+    let yuf = Yufmath::new();
     
     // 设置进度回调
     let progress_data = Arc::new(Mutex::new(Vec::new()));
@@ -178,22 +254,29 @@ fn test_progress_monitoring() {
 
 #[test]
 fn test_cancellation() {
-    let mut yuf = Yufmath::new();
+    let yuf = Yufmath::new();
     
     // 测试取消功能
     assert!(!yuf.is_cancelled());
     
+    // 没有正在进行的计算时取消不影响之后的计算
     yuf.cancel_computation();
-    assert!(yuf.is_cancelled());
+    assert!(!yuf.is_cancelled());
+    assert_eq!(yuf.compute_with_progress("2 + 3").unwrap(), "5");
     
-    // 重置取消状态（通过新的计算）
-    let _ = yuf.compute("2 + 3");
-    // 在实际实现中，compute 应该重置取消状态
+    // 计算过程中取消
+    let result = yuf.compute_with_callback("2 + 3", &|_: &ComputeProgress| {
+        yuf.cancel_computation();
+        assert!(yuf.is_cancelled());
+        true
+    });
+    assert!(result.is_err());
+    assert!(!yuf.is_cancelled());
 }
 
 #[test]
 fn test_performance_stats() {
-    let mut yuf = Yufmath::new();
+    let yuf = Yufmath::new();
     
     // 执行一些计算
     let _ = yuf.compute("2 + 3");
@@ -217,7 +300,7 @@ fn test_performance_stats() {
 
 #[test]
 fn test_config_updates() {
-    let mut yuf = Yufmath::new();
+    let yuf = Yufmath::new();
     
     // 测试初始配置
     assert!(yuf.get_config().enable_progress);
@@ -227,13 +310,77 @@ fn test_config_updates() {
         .with_progress(false)
         .with_progress_interval(200);
     
-    yuf.update_config(new_config);
+    yuf.update_config(|config| *config = new_config);
     
     // 验证配置更新
     assert!(!yuf.get_config().enable_progress);
     assert_eq!(yuf.get_config().progress_interval_ms, 200);
 }
 
+#[test]
+fn test_session_variables_do_not_capture_bound_variables() {
+    let yuf = Yufmath::new();
+    yuf.set_variable("k".to_string(), yuf.parse("5").unwrap()).unwrap();
+    // 求和指标 k 与会话变量同名时不被代入
+    assert_eq!(yuf.compute("sum(k^2, k, 1, 3)").unwrap(), "14");
+    assert_eq!(yuf.compute("k + 1").unwrap(), "6");
+}
+
+#[test]
+fn test_user_defined_functions() {
+    let yuf = Yufmath::new();
+    yuf.define_function("f".to_string(), vec!["x".to_string()], yuf.parse("x^2").unwrap()).unwrap();
+    assert_eq!(yuf.compute("f(3)").unwrap(), "9");
+    
+    // 求导时调用处展开，复合调用按链式法则求导
+    let x = yuf.parse("x").unwrap();
+    let derivative = yuf.diff(&yuf.parse("f(x)").unwrap(), "x").unwrap();
+    assert_eq!(yuf.evaluate(&derivative, &HashMap::from([("x".to_string(), Number::from(3))])).unwrap(), Number::from(6));
+    let chained = yuf.diff(&yuf.parse("f(sin(x))").unwrap(), "x").unwrap();
+    assert!(yuf.are_equivalent(&chained, &yuf.parse("2*sin(x)*cos(x)").unwrap()).unwrap().equivalent);
+    let integral = yuf.integrate(&yuf.parse("f(x)").unwrap(), "x").unwrap();
+    assert!(yuf.are_equivalent(&integral, &yuf.parse("x^3/3").unwrap()).unwrap().equivalent);
+    
+    // compute 中的定义语句同样注册函数，函数体可以调用其他用户函数
+    assert_eq!(yuf.compute("g(a, b) := f(a) + b; g(2, 1)").unwrap(), "5");
+    assert_eq!(yuf.get_all_functions().unwrap().len(), 2);
+    
+    // 未注册的函数保留原样，参数个数不符与递归定义报错
+    assert_eq!(yuf.compute("h(x)").unwrap(), "h(x)");
+    assert!(yuf.compute("f(1, 2)").is_err());
+    yuf.define_function("r".to_string(), vec!["x".to_string()], yuf.parse("r(x) + 1").unwrap()).unwrap();
+    assert!(yuf.compute("r(1)").is_err());
+    assert!(yuf.remove_function("r").unwrap());
+    assert!(yuf.define_function("f".to_string(), vec!["x".to_string(), "x".to_string()], x).is_err());
+}
+
+#[test]
+fn test_update_config_at_runtime() {
+    let yuf = Yufmath::new();
+    yuf.set_variable("a".to_string(), yuf.parse("3").unwrap()).unwrap();
+    
+    yuf.update_config(|config| {
+        config.max_compute_time = Some(Duration::from_secs(300));
+        config.precision.max_precision = Some(200);
+    });
+    
+    // 快照反映修改，超时与精度上限同步到运行时引擎
+    let config = yuf.get_config();
+    assert_eq!(config.max_compute_time, Some(Duration::from_secs(300)));
+    assert_eq!(config.precision.max_precision, Some(200));
+    let runtime = yuf.get_runtime_config().unwrap();
+    assert!(runtime.enable_time_limit);
+    assert_eq!(runtime.max_compute_time_ms, 300_000);
+    assert_eq!(runtime.max_integer_digits, 200);
+    
+    // 取消超时后不再限时
+    yuf.update_config(|config| config.max_compute_time = None);
+    assert!(!yuf.get_runtime_config().unwrap().enable_time_limit);
+    
+    // 会话变量在配置变化后保留
+    assert_eq!(yuf.get_variable("a").unwrap(), Some(yuf.parse("3").unwrap()));
+}
+
 #[test]
 fn test_precision_config() {
     let precision_config = PrecisionConfig::new()
@@ -413,4 +560,512 @@ fn test_thread_safety() {
     
     // 对于真正的多线程使用，每个线程应该有自己的 Yufmath 实例
     // 或者使用适当的同步机制
-}
\ No newline at end of file
+}
+#[test]
+fn test_sample() {
+    let yuf = Yufmath::new();
+
+    let expr = yuf.parse("x^2").unwrap();
+    let points = yuf.sample(&expr, "x", -1.0, 1.0, 5).unwrap();
+    assert_eq!(points.len(), 5);
+    assert_eq!(points[0], (-1.0, Some(1.0)));
+    assert_eq!(points[2], (0.0, Some(0.0)));
+    assert_eq!(points[4], (1.0, Some(1.0)));
+
+    // 定义域外的点取值为 None
+    let expr = yuf.parse("ln(x)").unwrap();
+    let points = yuf.sample(&expr, "x", -1.0, 1.0, 3).unwrap();
+    assert_eq!(points, vec![(-1.0, None), (0.0, None), (1.0, Some(0.0))]);
+
+    // 含其他自由变量或没有采样点时报错
+    assert!(yuf.sample(&yuf.parse("x + y").unwrap(), "x", 0.0, 1.0, 10).is_err());
+    assert!(yuf.sample(&expr, "x", 0.0, 1.0, 0).is_err());
+}
+
+#[test]
+fn test_config_round_trip() {
+    let config = ComputeConfig::new()
+        .with_max_compute_time(Duration::from_millis(1500))
+        .with_precision(PrecisionConfig::new().with_max_precision(64).with_force_exact(false))
+        .with_parallel(ParallelConfig::new().with_thread_count(4))
+        .with_cache(CacheConfig::new().with_fast_cache_size(42).without_cache_ttl())
+        .with_memory(MemoryConfig::new().without_memory_limit());
+
+    let toml = config.to_toml().unwrap();
+    assert!(toml.contains("max_compute_time = 1.5"));
+    assert!(toml.contains("cache_ttl = 0.0"));
+    assert_eq!(ComputeConfig::from_toml(&toml).unwrap(), config);
+    assert_eq!(ComputeConfig::from_json(&config.to_json().unwrap()).unwrap(), config);
+    assert_eq!(ComputeConfig::from_toml(&ComputeConfig::default().to_toml().unwrap()).unwrap(), ComputeConfig::default());
+}
+
+#[test]
+fn test_config_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+
+    // 缺省的字段取默认值
+    let toml_path = dir.path().join("yufmath.toml");
+    std::fs::write(&toml_path, "max_compute_time = 10\n\n[parallel]\nthread_count = 2\n\n[cache]\nsymbolic_cache_size = 16\n").unwrap();
+    let yuf = Yufmath::from_config_file(&toml_path).unwrap();
+    let config = yuf.get_config();
+    assert_eq!(config.max_compute_time, Some(Duration::from_secs(10)));
+    assert_eq!(config.parallel.thread_count, Some(2));
+    assert_eq!(config.cache.symbolic_cache_size, 16);
+    assert_eq!(config.cache.fast_cache_size, CacheConfig::default().fast_cache_size);
+    assert_eq!(config.precision, PrecisionConfig::default());
+
+    let json_path = dir.path().join("yufmath.json");
+    let saved = ComputeConfig::new().with_precision(PrecisionConfig::new().with_max_precision(128));
+    saved.save_to_file(&json_path).unwrap();
+    assert!(std::fs::read_to_string(&json_path).unwrap().trim_start().starts_with('{'));
+    assert_eq!(ComputeConfig::from_file(&json_path).unwrap(), saved);
+
+    // 缺失文件与格式错误
+    assert!(matches!(Yufmath::from_config_file(dir.path().join("missing.toml")), Err(YufmathError::Io(_))));
+    std::fs::write(&toml_path, "max_compute_time = \"forever\"").unwrap();
+    assert!(matches!(ComputeConfig::from_file(&toml_path), Err(YufmathError::Config { .. })));
+}
+
+#[test]
+fn test_boolean_logic() {
+    let yuf = Yufmath::new();
+
+    let expr = yuf.parse("x and (x or y)").unwrap();
+    assert_eq!(yuf.simplify(&expr).unwrap(), Expression::variable("x"));
+
+    let expr = yuf.parse("x or not x and y").unwrap();
+    let vars = vec!["x".to_string(), "y".to_string()];
+    let table = yuf.truth_table(&expr, &vars).unwrap();
+    assert_eq!(table.iter().filter(|(_, value)| *value).count(), 3);
+    assert_eq!(yuf.to_dnf(&expr).unwrap().to_string(), "x || !x && y");
+    assert_eq!(yuf.to_cnf(&expr).unwrap().to_string(), "x || y");
+}
+
+#[test]
+fn test_operation_stats() {
+    let yuf = Yufmath::new();
+    let x = Expression::variable("x");
+    let square = Expression::power(x.clone(), Expression::Number(Number::integer(2)));
+
+    yuf.simplify(&square).unwrap();
+    yuf.simplify(&Expression::add(x.clone(), x.clone())).unwrap();
+    yuf.diff(&square, "x").unwrap();
+    yuf.integrate(&square, "x").unwrap();
+    yuf.solve(&Expression::subtract(square.clone(), Expression::Number(Number::integer(4))), "x").unwrap();
+    assert!(yuf.solve(&Expression::add(Expression::function("sin", vec![x.clone()]), x.clone()), "x").is_err());
+
+    let stats = yuf.get_performance_stats().unwrap();
+    let simplify = &stats.operations["simplify"];
+    assert_eq!(simplify.calls, 2);
+    assert_eq!(simplify.successes, 2);
+    assert_eq!(simplify.success_rate(), 1.0);
+    assert_eq!(stats.operations["diff"].calls, 1);
+    assert_eq!(stats.operations["integrate"].calls, 1);
+    let solve = &stats.operations["solve"];
+    assert_eq!(solve.calls, 2);
+    assert_eq!(solve.failures(), 1);
+    assert_eq!(solve.success_rate(), 0.5);
+    assert!(!stats.operations.contains_key("factor"));
+
+    let report = yuf.get_operation_report();
+    for name in ["simplify", "diff", "integrate", "solve"] {
+        assert!(report.contains(name), "报告缺少 {}", name);
+    }
+
+    yuf.reset_performance_stats();
+    assert!(yuf.get_performance_stats().unwrap().operations.is_empty());
+}
+
+/// 测试分阶段计时与耗时格式
+#[test]
+fn test_compute_timed() {
+    let yuf = Yufmath::new();
+    let (result, timings) = yuf.compute_timed("a = 2; a + x + x").unwrap();
+    assert_eq!(result, yuf.compute("2 + 2*x").unwrap());
+    let phases: Vec<ComputePhase> = timings.phases.iter().map(|(phase, _)| phase.clone()).collect();
+    assert_eq!(phases, vec![ComputePhase::Parsing, ComputePhase::Simplification, ComputePhase::Formatting]);
+    assert_eq!(timings.total(), timings.phases.iter().map(|(_, duration)| *duration).sum());
+    assert_eq!(yuf.get_performance_stats().unwrap().last_timings, Some(timings));
+    assert!(yuf.compute_timed("1 +").is_err());
+
+    assert_eq!(format_duration(Duration::from_micros(850)), "850µs");
+    assert_eq!(format_duration(Duration::from_micros(12_345)), "12.3ms");
+    assert_eq!(format_duration(Duration::from_millis(1_250)), "1.25s");
+}
+
+/// 测试方程两边的操作与变量隔离
+#[test]
+fn test_equation_operations() {
+    let yuf = Yufmath::new();
+    let equation = |input: &str| yuf.parse_equation(input).unwrap();
+    let simplified = |input: &str| yuf.simplify(&yuf.parse(input).unwrap()).unwrap();
+    let relation = |lhs: &str, rhs: &str| Expression::binary_op(BinaryOperator::Equal, simplified(lhs), simplified(rhs));
+
+    assert_eq!(yuf.isolate(&equation("2*x + 3 = 7"), "x").unwrap(), relation("x", "2"));
+    assert_eq!(yuf.isolate(&equation("y = m*x + b"), "x").unwrap(), relation("x", "(y - b)/m"));
+    let steps = yuf.isolate_steps(&equation("y = m*x + b"), "x").unwrap();
+    assert_eq!(steps.len(), 3);
+    assert!(steps[2].warnings.iter().any(|w| w.contains("可能为 0")));
+
+    // 没有假设时 a 可能为 0，给出警告；假设 a > 0 后两边乘 a 可以约去
+    let a = yuf.parse("a").unwrap();
+    assert_eq!(yuf.equation_multiply(&equation("x/a = 3"), &a).unwrap().warnings.len(), 1);
+    yuf.assume("a", Property::Positive).unwrap();
+    let step = yuf.equation_multiply(&equation("x/a = 3"), &a).unwrap();
+    assert_eq!(step.equation, relation("x", "3*a"));
+    assert!(step.warnings.is_empty());
+
+    let step = yuf.equation_substitute(&equation("x + y = 5"), "y", &yuf.parse("2").unwrap()).unwrap();
+    assert_eq!(step.equation, relation("x + 2", "5"));
+    assert_eq!(yuf.parse_equation("x <= 3").unwrap(), yuf.parse("x <= 3").unwrap());
+}
+
+/// 测试公共子表达式提取
+#[test]
+fn test_cse() {
+    let yuf = Yufmath::new();
+    let expr = yuf.parse("sin(x+y)^2 + cos(x+y)^2 + sin(x+y)").unwrap();
+    let (definitions, rewritten) = yuf.cse(&expr);
+    let names: Vec<&str> = definitions.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["t1", "t2"]);
+    assert_eq!(definitions[1].1, yuf.parse("sin(t1)").unwrap());
+    assert_eq!(rewritten, yuf.parse("t2^2 + cos(t1)^2 + t2").unwrap());
+
+    let options = CseOptions { prefix: "s".to_string(), ..CseOptions::default() };
+    let (definitions, _) = yuf.cse_with(&expr, &options);
+    assert_eq!(definitions[0].0, "s1");
+}
+
+/// 测试多维数组的构造、下标、沿轴求和与变形
+#[test]
+fn test_tensors() {
+    let yuf = Yufmath::new();
+    let cube = "[[[1, 2], [3, 4]], [[5, 6], [7, 8]]]";
+    let tensor = Tensor::from_nested(&yuf.parse(cube).unwrap()).unwrap();
+    assert_eq!(tensor.shape(), &[2, 2, 2]);
+    assert_eq!(yuf.compute(&format!("{}[2, 2, 1]", cube)).unwrap(), "7");
+    assert_eq!(yuf.compute(&format!("tensor_sum({}, 2)", cube)).unwrap(), "[[4, 6], [12, 14]]");
+    assert_eq!(yuf.compute(&format!("reshape({}, [4, 2])", cube)).unwrap(), "[[1, 2], [3, 4], [5, 6], [7, 8]]");
+    assert!(yuf.parse("[[[1, 2], [3, 4]], [[5, 6], [7]]]").is_err());
+}
+
+/// 测试计算中的内存峰值统计与内存上限
+#[test]
+fn test_memory_stats() {
+    let yuf = Yufmath::new();
+    let expr = yuf.parse("(x + y + 1)^6").unwrap();
+    yuf.expand(&expr).unwrap();
+    yuf.simplify(&yuf.parse("x + x + 2*x").unwrap()).unwrap();
+    
+    let stats = yuf.get_memory_stats();
+    assert!(stats.peak > 0);
+    assert!(stats.operation_peaks["expand"] > stats.operation_peaks["simplify"]);
+    assert_eq!(stats.peak, stats.operation_peaks.values().copied().max().unwrap());
+    assert_eq!(stats.limit_hits, 0);
+    assert_eq!(yuf.get_performance_stats().unwrap().peak_memory_usage, stats.peak);
+    
+    // 上限很小时展开中止并返回资源限制错误
+    yuf.update_config(|config| config.memory = MemoryConfig::default().with_max_memory_usage(4096));
+    let error = yuf.expand(&yuf.parse("(x + y + z + 1)^12").unwrap()).unwrap_err();
+    assert!(matches!(error, YufmathError::Compute(yufmath::ComputeError::ResourceLimit { .. })), "{:?}", error);
+    assert_eq!(yuf.get_memory_stats().limit_hits, 1);
+    
+    // 关闭记账后不再统计，也不检查上限
+    yuf.reset_performance_stats();
+    yuf.update_config(|config| config.memory = MemoryConfig::default().with_max_memory_usage(4096).with_usage_tracking(false));
+    yuf.expand(&expr).unwrap();
+    assert_eq!(yuf.get_memory_stats(), yufmath::MemoryUsageStats::default());
+}
+
+#[test]
+fn test_random_seed_config() {
+    let config = ComputeConfig::default().with_random_seed(2024);
+    assert_eq!(ComputeConfig::from_toml(&config.to_toml().unwrap()).unwrap().random_seed, Some(2024));
+    assert!(!ComputeConfig::default().to_toml().unwrap().contains("random_seed"));
+
+    let n = Expression::Number(Number::integer(998244359987710471i64));
+    let first = Yufmath::with_config(config.clone()).prime_factors(&n).unwrap();
+    let second = Yufmath::with_config(config).prime_factors(&n).unwrap();
+    assert_eq!(first, second);
+    assert_eq!(first, vec![Expression::Number(Number::integer(998244353)), Expression::Number(Number::integer(1000000007))]);
+}
+
+/// 测试练习题生成与批改
+#[test]
+fn test_exercises() {
+    let yuf = Yufmath::new();
+    let kind = ExerciseKind::Differentiation { difficulty: 2 };
+    let (problem, answer) = yuf.generate_exercise(kind, 11).unwrap();
+    assert_eq!(yuf.generate_exercise(kind, 11).unwrap(), (problem.clone(), answer.clone()));
+    assert!(yuf.check_answer(kind, &problem, &answer).unwrap());
+    assert!(!yuf.check_answer(kind, &problem, &problem).unwrap());
+
+    // 积分答案可以相差常数
+    let kind = ExerciseKind::Integration { difficulty: 1 };
+    let problem = yuf.parse("2*x").unwrap();
+    assert!(yuf.check_answer(kind, &problem, &yuf.parse("x^2 + 3").unwrap()).unwrap());
+    assert!(!yuf.check_answer(kind, &problem, &yuf.parse("x^2 + x").unwrap()).unwrap());
+
+    assert!(yuf.generate_exercise(ExerciseKind::Factorization { difficulty: 9 }, 0).is_err());
+}
+
+/// 测试线性同余方程求解
+#[test]
+fn test_solve_congruence() {
+    let yuf = Yufmath::new();
+    let solution = yuf.solve_congruence("3*x ≡ 4 (mod 7)", "x").unwrap().unwrap();
+    assert_eq!(solution.to_string(), "6 (mod 7)");
+    assert_eq!(yuf.solve_congruence("2*x ≡ 1 (mod 4)", "x").unwrap(), None);
+    assert!(yuf.solve_congruence("3*x = 4", "x").is_err());
+
+    let reduced = yuf.simplify(&yuf.parse("Mod(3^1000 + y, 7)").unwrap()).unwrap();
+    assert_eq!(reduced, yuf.parse("Mod(4 + y, 7)").unwrap());
+}
+
+/// 测试 LaTeX 解析与往返
+#[test]
+fn test_latex_round_trip() {
+    let yuf = Yufmath::new();
+    let pasted = yuf.parse_latex(r"\frac{-b + \sqrt{b^{2} - 4ac}}{2a}").unwrap();
+    assert_eq!(pasted, yuf.parse("(-b + sqrt(b^2 - 4*a*c))/(2*a)").unwrap());
+
+    let expr = yuf.parse("sqrt(x^2 + 1)/(x - 1)^3").unwrap();
+    assert_eq!(yuf.to_latex(&expr), r"\frac{\sqrt{x^{2} + 1}}{\left(x - 1\right)^{3}}");
+    assert_eq!(yuf.parse_latex(&yuf.to_latex(&expr)).unwrap(), expr);
+    assert!(yuf.parse_latex(r"\frac{1}").is_err());
+}
+
+/// 测试 Gröbner 基
+#[test]
+fn test_groebner_basis() {
+    let yuf = Yufmath::new();
+    let polys = [yuf.parse("x^2 + y^2 - 1").unwrap(), yuf.parse("x - y").unwrap()];
+    let basis = yuf.groebner_basis(&polys, &["x", "y"], MonomialOrder::Lex).unwrap();
+    // 字典序下消去 x，最后一个多项式只含 y
+    assert_eq!(basis.len(), 2);
+    assert_eq!(yuf.simplify(&basis[1]).unwrap(), yuf.simplify(&yuf.parse("y^2 - 1/2").unwrap()).unwrap());
+}
+
+/// 测试多项式系数与次数
+#[test]
+fn test_poly_coeffs_and_degree() {
+    let yuf = Yufmath::new();
+    let expr = yuf.parse("3*x^2+x+5").unwrap();
+    let coeffs: Vec<String> = yuf.poly_coeffs(&expr, "x").unwrap().iter().map(|c| c.to_string()).collect();
+    assert_eq!(coeffs, ["5", "1", "3"]);
+    assert_eq!(yuf.degree(&expr, "x").unwrap(), 2);
+    assert!(yuf.poly_coeffs(&yuf.parse("exp(x)").unwrap(), "x").is_err());
+}
+
+/// 测试多项式判别与次数
+#[test]
+fn test_is_polynomial() {
+    let yuf = Yufmath::new();
+    assert!(yuf.is_polynomial(&yuf.parse("a*x^2 + b*x").unwrap(), "x"));
+    assert!(yuf.is_polynomial(&yuf.parse("(x^2+1)/2").unwrap(), "x"));
+    assert!(!yuf.is_polynomial(&yuf.parse("1/(x+1)").unwrap(), "x"));
+    assert!(!yuf.is_polynomial(&yuf.parse("x^(1/2)").unwrap(), "x"));
+    assert_eq!(yuf.polynomial_degree(&yuf.parse("(x^2+1)/2").unwrap(), "x"), Some(2.into()));
+    assert_eq!(yuf.polynomial_degree(&yuf.parse("sin(x)").unwrap(), "x"), None);
+    
+    // 需要多项式的操作指出问题子式
+    let error = yuf.solve(&yuf.parse("x^2 + sin(x)").unwrap(), "x").unwrap_err();
+    assert!(error.to_string().contains("sin(x)"), "{}", error);
+    assert_eq!(yuf.solve(&yuf.parse("(x^2-4)/2").unwrap(), "x").unwrap().len(), 2);
+}
+
+/// 测试结式与判别式
+#[test]
+fn test_resultant_and_discriminant() {
+    let yuf = Yufmath::new();
+    let quadratic = yuf.parse("a*x^2 + b*x + c").unwrap();
+    assert_eq!(yuf.discriminant(&quadratic, "x").unwrap().to_string(), "b^2 - 4 * a * c");
+    let cubic = yuf.parse("x^3 + p*x + q").unwrap();
+    assert_eq!(yuf.discriminant(&cubic, "x").unwrap().to_string(), "-4 * p^3 - 27 * q^2");
+
+    let p = yuf.parse("x^2 - 1").unwrap();
+    let q = yuf.parse("x^2 - 3*x + 2").unwrap();
+    assert_eq!(yuf.resultant(&p, &q, "x").unwrap(), yuf.parse("0").unwrap());
+    assert_eq!(yuf.compute("discriminant(x^2 - 4*x + 4, x)").unwrap(), "0");
+}
+
+/// 测试化简规则集开关
+#[test]
+fn test_simplify_options() {
+    let yuf = Yufmath::new();
+    let identity = yuf.parse("sin(x)^2 + cos(x)^2").unwrap();
+    assert_eq!(yuf.simplify(&identity).unwrap(), yuf.parse("1").unwrap());
+
+    yuf.set_simplify_options(SimplifyOptions::algebraic());
+    assert_eq!(yuf.simplify(&identity).unwrap(), identity);
+    assert_eq!(yuf.compute("2*x + 3*x").unwrap(), "5 * x");
+
+    yuf.set_simplify_options(SimplifyOptions::default());
+    assert_eq!(yuf.simplify(&identity).unwrap(), yuf.parse("1").unwrap());
+
+    // 未定式默认为 undefined，0^0 可按组合数学习惯取 1
+    assert_eq!(yuf.compute("0^0").unwrap(), "undefined");
+    assert_eq!(yuf.compute("inf - inf").unwrap(), "undefined");
+    yuf.set_simplify_options(SimplifyOptions { zero_power_zero_is_one: true, ..SimplifyOptions::default() });
+    assert_eq!(yuf.compute("0^0").unwrap(), "1");
+    assert_eq!(yuf.compute("0/0").unwrap(), "undefined");
+}
+
+/// 测试多项式方程组求解
+#[test]
+fn test_solve_system() {
+    let yuf = Yufmath::new();
+    let equations = vec![yuf.parse("x + y == 10").unwrap(), yuf.parse("x - y == 2").unwrap()];
+    let solutions = yuf.solve_system(&equations, &["x".to_string(), "y".to_string()]).unwrap();
+    assert_eq!(solutions.len(), 1);
+    assert_eq!(solutions[0]["x"], yuf.parse("6").unwrap());
+    assert_eq!(solutions[0]["y"], yuf.parse("4").unwrap());
+}
+
+/// 测试三角变换
+#[test]
+fn test_trig_transforms() {
+    let yuf = Yufmath::new();
+    let shown = |result: Result<Expression, YufmathError>| result.unwrap().to_string();
+    assert_eq!(shown(yuf.trig_expand(&yuf.parse("cos(2*x)").unwrap())), "cos(x)^2 - sin(x)^2");
+    assert_eq!(shown(yuf.trig_factor(&yuf.parse("sin(x)+sin(y)").unwrap())), "2 * sin((x + y) / 2) * cos((x - y) / 2)");
+    assert_eq!(shown(yuf.trig_reduce(&yuf.parse("sin(x)*cos(y)").unwrap())), "1/2 * sin(x + y) + 1/2 * sin(x - y)");
+    assert_eq!(shown(yuf.trig_to_exp(&yuf.parse("cos(x)").unwrap())), "(exp(i * x) + exp(-i * x)) / 2");
+    assert_eq!(shown(yuf.exp_to_trig(&yuf.parse("exp(i*x)").unwrap())), "cos(x) + i * sin(x)");
+    
+    // simplify 不会自动做这些变换
+    assert_eq!(shown(yuf.simplify(&yuf.parse("sin(x)+sin(y)").unwrap())), "sin(x) + sin(y)");
+    assert_eq!(yuf.get_performance_stats().unwrap().operations["trig_expand"].calls, 1);
+}
+
+/// 测试一元与带约束的最值
+#[test]
+fn test_minimize_maximize() {
+    let yuf = Yufmath::new();
+    let optima = yuf.minimize(&yuf.parse("x^2+2*x+3").unwrap(), "x").unwrap();
+    assert_eq!(optima.len(), 1);
+    assert_eq!(optima[0].to_string(), "(-1, 2)");
+
+    let optima = yuf.maximize_constrained(&yuf.parse("x*y").unwrap(), &["x", "y"], &yuf.parse("x+y==10").unwrap()).unwrap();
+    assert_eq!(optima[0].to_string(), "(5, 5, 25)");
+
+    // 无界、确界取不到时报错
+    assert!(yuf.minimize(&yuf.parse("x^3").unwrap(), "x").is_err());
+    assert!(yuf.maximize(&yuf.parse("x^2/(1+x^2)").unwrap(), "x").is_err());
+    assert!(yuf.minimize_constrained(&yuf.parse("x*y").unwrap(), &["x", "y"], &yuf.parse("x+y==10").unwrap()).is_err());
+}
+
+/// 测试假设影响化简
+#[test]
+fn test_assume() {
+    let yuf = Yufmath::new();
+    assert_eq!(yuf.compute("sqrt(x^2)").unwrap(), "abs(x)");
+
+    yuf.assume("x", Property::Positive).unwrap();
+    assert_eq!(yuf.compute("sqrt(x^2)").unwrap(), "x");
+    assert_eq!(yuf.simplify(&yuf.parse("ln(e^x)").unwrap()).unwrap(), yuf.parse("x").unwrap());
+    // 与已有假设矛盾
+    assert!(yuf.assume("x", "<= 0".parse().unwrap()).is_err());
+
+    yuf.clear_assumptions();
+    assert_eq!(yuf.compute("sqrt(x^2)").unwrap(), "abs(x)");
+}
+
+/// 测试假设参与类型推断
+#[test]
+fn test_infer_type_with_assumptions() {
+    let yuf = Yufmath::new();
+    let modulo = yuf.parse("n % m").unwrap();
+    assert_eq!(yuf.infer_type(&modulo), ExprType::Symbolic);
+
+    yuf.assume("n", Property::Integer).unwrap();
+    yuf.assume("m", Property::Integer).unwrap();
+    assert_eq!(yuf.infer_type(&modulo), ExprType::Numeric(NumericType::Integer));
+    yuf.assume("x", Property::Real).unwrap();
+    assert_eq!(yuf.infer_type(&yuf.parse("n + x").unwrap()), ExprType::Numeric(NumericType::Real));
+
+    yuf.clear_assumptions();
+    assert_eq!(yuf.infer_type(&modulo), ExprType::Symbolic);
+}
+
+/// 测试共轭对含变量的复数表达式逐项分配
+#[test]
+fn test_complex_conjugate_distributes() {
+    let yuf = Yufmath::new();
+    let conjugate = |input: &str| yuf.format(&yuf.complex_conjugate(&yuf.parse(input).unwrap()).unwrap());
+
+    assert_eq!(conjugate("3 + 4*i"), "3-4i");
+    assert_eq!(conjugate("conj(z)"), "z");
+    let product = conjugate("z * w");
+    assert!(product == "conj(z) * conj(w)" || product == "conj(w) * conj(z)", "{}", product);
+
+    yuf.assume("x", Property::Real).unwrap();
+    yuf.assume("y", Property::Real).unwrap();
+    assert_eq!(conjugate("(x + y*i) * (x - y*i)"), "x^2 + y^2");
+    assert_eq!(conjugate("x + y*i"), "x - y * i");
+}
+
+/// 测试向量微积分：雅可比矩阵、散度、旋度与拉普拉斯算子
+#[test]
+fn test_vector_calculus() {
+    let yuf = Yufmath::new();
+    let parse_all = |inputs: &[&str]| inputs.iter().map(|input| yuf.parse(input).unwrap()).collect::<Vec<_>>();
+    let int = |value: i64| Expression::Number(Number::integer(value));
+
+    let curl = yuf.curl(&parse_all(&["-y", "x", "0"]), &["x", "y", "z"]).unwrap();
+    assert_eq!(curl, Expression::Vector(vec![int(0), int(0), int(2)]));
+    assert_eq!(yuf.curl(&parse_all(&["-y", "x"]), &["x", "y"]).unwrap(), int(2));
+    assert_eq!(yuf.laplacian(&yuf.parse("x^2 + y^2").unwrap(), &["x", "y"]).unwrap(), int(4));
+    assert_eq!(yuf.divergence(&parse_all(&["x", "y", "z"]), &["x", "y", "z"]).unwrap(), int(3));
+
+    let jacobian = yuf.jacobian(&parse_all(&["x + y", "x - y"]), &["x", "y"]).unwrap();
+    assert_eq!(jacobian, Expression::Matrix(vec![vec![int(1), int(1)], vec![int(1), int(-1)]]));
+
+    // 分量数与变量数不一致
+    assert!(yuf.divergence(&parse_all(&["x", "y"]), &["x", "y", "z"]).is_err());
+    // 表达式中直接调用
+    assert_eq!(yuf.compute("laplacian(x^2 + y^2, [x, y])").unwrap(), "4");
+}
+
+/// 测试分号分隔的多条语句与注释
+#[test]
+fn test_compute_statements() {
+    let yuf = Yufmath::new();
+
+    // 按顺序求值，返回最后一条语句的结果，赋值存为会话变量
+    assert_eq!(yuf.compute("a = 2; b = 3; a*b # 这是注释").unwrap(), "6");
+    assert_eq!(yuf.get_variable("a").unwrap(), Some(Expression::Number(Number::integer(2))));
+    assert_eq!(yuf.compute("a + b /* 块注释; = ( */ + 1 // 行注释").unwrap(), "6");
+
+    let results = yuf.compute_statements("c = a + 1;; c^2").unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[0].0.target.as_deref(), Some("c"));
+    assert!(results[0].0.quiet);
+    assert_eq!(results[0].1, "3");
+    assert_eq!(results[1].1, "9");
+
+    assert!(yuf.compute("1 +; 2").is_err());
+    assert!(yuf.compute("# 只有注释").is_err());
+}
+
+/// 测试严格模式下的未知函数检查
+#[test]
+fn test_strict_functions() {
+    // 默认把未知函数当作符号函数
+    let yuf = Yufmath::new();
+    assert!(yuf.parse("foo(x) + 1").is_ok());
+    // 内置函数的参数个数总是在解析时校验
+    assert!(yuf.parse("sin(x, y)").is_err());
+
+    let yuf = Yufmath::with_config(ComputeConfig::new().with_strict_functions(true));
+    assert!(yuf.parse("sin(x) + log(x, 2)").is_ok());
+    let error = yuf.parse("1 + foo(x)").unwrap_err().to_string();
+    assert!(error.contains("foo"));
+    // 用户函数，包括同一输入中先定义的函数与递归调用，都是已知的
+    yuf.define_function("f".to_string(), vec!["x".to_string()], yuf.parse("x^2").unwrap()).unwrap();
+    assert!(yuf.parse("f(3)").is_ok());
+    assert_eq!(yuf.compute("g(a) := f(a) + 1; g(2)").unwrap(), "5");
+    assert!(yuf.parse_statements("h(n) := h(n - 1)").is_ok());
+    assert!(yuf.compute("k(2); k(x) := x").is_err());
+}
+
@@ -66,10 +66,13 @@ fn test_fast_cache_for_small_integers() {
     // 结果应该相同
     assert_eq!(results1, results2);
     
-    // 检查缓存使用情况
+    // 检查缓存使用情况：缓存计算引擎把小整数运算存在快速层，
+    // 默认的增强引擎则把整条表达式的化简结果存在符号层
     if let Some(usage) = yuf.get_cache_usage() {
         println!("快速缓存使用率: {:.2}%", usage.fast_cache_usage_rate() * 100.0);
-        assert!(usage.fast_cache_usage > 0);
+        assert!(usage.fast_cache_usage + usage.symbolic_cache_usage > 0);
+        let stats = yuf.get_cache_stats().unwrap();
+        assert!(stats.fast_hits + stats.symbolic_hits > 0);
     }
 }
 
@@ -418,4 +421,29 @@ fn test_cache_memory_efficiency() {
         println!("  缓存清理次数: {}", stats.cleanup_count);
         println!("  估算节省时间: {:?}", stats.total_time_saved);
     }
-}
\ No newline at end of file
+}
+#[test]
+fn test_update_config_shrinks_engine_cache() {
+    let yuf = Yufmath::with_config(ComputeConfig::new().with_cache(CacheConfig::new().with_symbolic_cache_size(100)));
+    for i in 1..=20 {
+        yuf.compute(&format!("x^{} + {} * x - x", i, i)).unwrap();
+    }
+    yuf.compute("x^1 + 1 * x - x").unwrap();
+    
+    let usage = yuf.get_cache_usage().expect("默认引擎应当报告缓存使用情况");
+    assert_eq!(usage.symbolic_cache_capacity, 100);
+    assert!(usage.symbolic_cache_usage > 4);
+    let stats = yuf.get_cache_stats().expect("默认引擎应当报告缓存统计");
+    assert!(stats.symbolic_hits > 0);
+    assert_eq!(stats.evictions, 0);
+    
+    yuf.update_config(|config| config.cache = CacheConfig::new().with_symbolic_cache_size(4));
+    
+    let usage = yuf.get_cache_usage().unwrap();
+    assert_eq!(usage.symbolic_cache_capacity, 4);
+    assert!(usage.symbolic_cache_usage <= 4);
+    assert!(yuf.get_cache_stats().unwrap().evictions > 0);
+    
+    yuf.clear_cache().unwrap();
+    assert_eq!(yuf.get_cache_usage().unwrap().symbolic_cache_usage, 0);
+}
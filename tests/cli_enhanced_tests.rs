@@ -4,7 +4,13 @@
 
 use std::fs;
 use std::process::Command;
+use std::time::{Duration, Instant};
 use tempfile::NamedTempFile;
+use yufmath::{ComputePhase, Yufmath, YufmathError};
+use yufmath::cli::args::OutputFormat;
+use yufmath::cli::commands::{batch_tsv_row, error_context, tsv_escape};
+use yufmath::cli::watch::{changed_lines, evaluate, output_document, render};
+use yufmath::formatter::terminal::strip_ansi;
 
 /// 测试帮助信息显示
 #[test]
@@ -65,7 +71,17 @@ fn test_new_commands_basic() {
         .expect("Failed to execute solve command");
     
     let stdout = String::from_utf8_lossy(&output.stdout);
-    assert!(stdout.contains("求解功能暂未实现") || stdout.contains("错误"));
+    assert!(stdout.contains("解 1: x = -2") && stdout.contains("解 2: x = 2"));
+    
+    // `==` 也表示等号
+    let output = Command::new("cargo")
+        .args(["run", "--", "solve", "x^2 == 4", "x"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute solve command");
+    
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("解 1: x = -2") && stdout.contains("解 2: x = 2"));
     
     // 测试 factor 命令
     let output = Command::new("cargo")
@@ -86,6 +102,241 @@ fn test_new_commands_basic() {
     
     let stdout = String::from_utf8_lossy(&output.stdout);
     assert!(stdout.contains("展开功能暂未实现") || stdout.contains("错误"));
+    
+    // 测试 analyze 命令
+    let output = Command::new("cargo")
+        .args(["run", "--", "analyze", "x^3 - 3*x", "x"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute analyze command");
+    
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("临界点: x = -1, x = 1"));
+    assert!(stdout.contains("x = -1: 极大值 2") && stdout.contains("x = 1: 极小值 -2"));
+    assert!(stdout.contains("拐点: x = 0"));
+}
+
+/// 测试等式校验命令
+#[test]
+fn test_verify_command() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "verify", "(x+1)^2 = x^2+2*x+1"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute verify command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert_eq!(stdout.lines().next(), Some("true"));
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "verify", "x == x"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute verify command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().next(), Some("true"));
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "verify", "x^2 = x"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute verify command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().next(), Some("false"));
+    assert!(stdout.contains("反例: x = 2（左边 = 4，右边 = 2）"));
+}
+
+/// 测试恒等式证明命令
+#[test]
+fn test_prove_command() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "prove", "sin(x)^2+cos(x)^2", "1"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute prove command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert_eq!(stdout.lines().next(), Some("成立：两边之差化简为 0"));
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "prove", "cos(x)^2 - sin(x)^2", "cos(2*x)"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute prove command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("24 个定义域内的检验点"), "{}", stdout);
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "prove", "x^2", "x"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute prove command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.lines().collect::<Vec<_>>(), ["不成立", "反例: x = 2（左边 = 4，右边 = 2）"]);
+}
+
+/// 测试 Laplace 变换与逆变换命令
+#[test]
+fn test_laplace_commands() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "laplace", "t*exp(-2*t)", "t", "s", "--region"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute laplace command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines, ["1 / (s + 2)^2", "收敛域: Re(s) > -2"]);
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "inverse-laplace", "1/(s^2+1)"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute inverse-laplace command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert_eq!(stdout.trim(), "sin(t)");
+}
+
+/// 测试从配置文件加载设置
+#[test]
+fn test_config_option() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let config_path = dir.path().join("yufmath.toml");
+    fs::write(&config_path, "max_compute_time = 30\n").unwrap();
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "--config", config_path.to_str().unwrap(), "compute", "2 + 3"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute command with --config");
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "5");
+
+    fs::write(&config_path, "[parallel]\nthread_count = \"many\"\n").unwrap();
+    let output = Command::new("cargo")
+        .args(["run", "--", "--config", config_path.to_str().unwrap(), "compute", "2 + 3"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute command with --config");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("配置错误"));
+}
+
+/// 测试化简追踪输出
+#[test]
+fn test_trace_flag() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "--trace", "simplify", "x + x + 0*y"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute traced simplify command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert!(lines[0].starts_with("[1] binary.add @ 0: x + x → 2 * x"));
+    assert!(lines[1].starts_with("[2] binary.multiply @ 1:"));
+    assert_eq!(lines.last(), Some(&"2 * x"));
+
+    // 只看某条规则
+    let output = Command::new("cargo")
+        .args(["run", "--", "--trace", "simplify", "x + x + 0*y"])
+        .env("YUFMATH_TRACE_FILTER", "binary.multiply")
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute traced simplify command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("[1] binary.multiply @ 1: 0 * y → 0"));
+}
+
+/// 测试绘图数据导出
+#[test]
+fn test_plot_data_command() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "plot-data", "sin(x)", "x", "--from", "0", "--to", "6.28", "--n", "100"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute plot-data command");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 101);
+    assert_eq!(lines[0], "x,y");
+    assert_eq!(lines[1], "0,0");
+    assert!(lines[100].starts_with("6.28,-0.00318"));
+
+    // 定义域外的点标为 NaN，或用 --skip-undefined 跳过
+    let output_file = NamedTempFile::new().expect("Failed to create output temp file");
+    let output = Command::new("cargo")
+        .args(["run", "--", "plot-data", "ln(x)", "x", "--from", "-1", "--to", "1", "--n", "3",
+            "--csv", output_file.path().to_str().unwrap()])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute plot-data command");
+    assert!(output.status.success());
+    let csv = fs::read_to_string(output_file.path()).unwrap();
+    assert_eq!(csv, "x,y\n-1,NaN\n0,NaN\n1,0\n");
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "plot-data", "ln(x)", "x", "--from", "-1", "--to", "1", "--n", "3", "--skip-undefined", "--json"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute plot-data command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert_eq!(stdout.trim(), r#"[{"x":1.0,"y":0.0}]"#);
+}
+
+/// 测试终端绘图
+#[test]
+fn test_plot_ascii_command() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "plot-ascii", "x^2", "x", "-2", "2", "--ascii", "--width", "23", "--height", "9"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute plot-ascii command");
+
+    assert!(output.status.success());
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 9);
+    assert!(lines[0].starts_with("4+*"));
+    assert!(lines[6].starts_with("0+"));
+    assert_eq!(lines[8].trim(), "-2                  2");
+
+    // y 范围下限不小于上限时报错
+    let output = Command::new("cargo")
+        .args(["run", "--", "plot-ascii", "x", "x", "0", "1", "--y-min", "1", "--y-max", "-1"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute plot-ascii command");
+    assert!(!output.status.success());
+}
+
+/// 测试 --time 在结果后输出耗时
+#[test]
+fn test_time_flag() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "--time", "compute", "x + x"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute compute command");
+
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "2 * x");
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let timing = stderr.lines().last().unwrap();
+    assert!(timing.contains(ComputePhase::Simplification.label()), "{}", timing);
 }
 
 /// 测试批处理功能
@@ -129,6 +380,112 @@ fn test_batch_processing() {
     assert!(output_file.path().exists());
 }
 
+/// 测试批处理的 TSV 输出
+#[test]
+fn test_batch_tsv_output() {
+    let input_file = NamedTempFile::new().expect("Failed to create temp file");
+    fs::write(input_file.path(), "# 注释\n2 + 3\nx +\t1\n2 + * 3\n").expect("Failed to write to temp file");
+    let output_file = NamedTempFile::new().expect("Failed to create output temp file");
+    
+    let output = Command::new("cargo")
+        .args([
+            "run", "--", "--lang", "en", "--format", "tsv", "batch",
+            "-i", input_file.path().to_str().unwrap(),
+            "-o", output_file.path().to_str().unwrap(),
+        ])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute batch command");
+    assert!(output.status.success());
+    
+    // 表头之后每个输入一行，出错的行也在内，每行恰好三列
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let rows: Vec<Vec<&str>> = stdout.lines().map(|line| line.split('\t').collect()).collect();
+    assert_eq!(rows.len(), 4);
+    assert!(rows.iter().all(|row| row.len() == 3));
+    assert_eq!(rows[0], ["input", "result", "time (ms)"]);
+    assert_eq!(rows[1][..2], ["2 + 3", "5"]);
+    assert_eq!(rows[2][..2], ["x +\\t1", "1 + x"]);
+    assert!(rows[3][1].contains("parse error"));
+    assert!(rows.iter().skip(1).all(|row| row[2].parse::<f64>().is_ok()));
+    assert_eq!(fs::read_to_string(output_file.path()).unwrap(), stdout);
+}
+
+/// 测试 TSV 字段转义
+#[test]
+fn test_tsv_escape() {
+    assert_eq!(tsv_escape("x + 1"), "x + 1");
+    assert_eq!(tsv_escape("a\tb\nc\r\\d"), "a\\tb\\nc\\r\\\\d");
+    
+    let row = batch_tsv_row("a\tb", &Ok("line 1\nline 2".to_string()), Duration::from_micros(1500));
+    assert_eq!(row, "a\\tb\tline 1\\nline 2\t1.500");
+    let row = batch_tsv_row("x", &Err("bad\tinput".to_string()), Duration::ZERO);
+    assert_eq!(row.split('\t').collect::<Vec<_>>(), ["x", "bad\\tinput", "0.000"]);
+}
+
+/// 测试监视模式的逐行计算与变化标记
+#[test]
+fn test_watch_evaluation() {
+    let yuf = Yufmath::new();
+    let first = evaluate(&yuf, "# 注释\n2 + 3\n\n5 * 6\n2 +\n");
+    assert_eq!(first.iter().map(|result| result.line).collect::<Vec<_>>(), vec![2, 4, 5]);
+    assert_eq!(first[0].output, Ok("5".to_string()));
+    // 出错的行只影响自己
+    assert!(first[2].output.is_err());
+    assert_eq!(changed_lines(None, &first), vec![false; 3]);
+
+    // 插入一行后行号移动，没变的行不标记
+    let second = evaluate(&yuf, "# 注释\n1 + 1\n2 + 3\n\n5 * 7\n2 + 4\n");
+    assert_eq!(changed_lines(Some(&first), &second), vec![true, false, true, true]);
+
+    let lines = render(&second, &changed_lines(Some(&first), &second));
+    assert!(lines[0].starts_with('*') && lines[0].ends_with("1 + 1 = 2"));
+    assert!(lines[1].starts_with(' '));
+    let errors = render(&first, &[false; 3]);
+    assert!(errors[2].starts_with('!') && errors[2].contains("2 +"));
+
+    let text = output_document(&yuf, &second, &OutputFormat::Standard);
+    assert_eq!(text.lines().next(), Some("1 + 1 = 2"));
+    let latex = output_document(&yuf, &first, &OutputFormat::Latex);
+    assert!(latex.starts_with("\\documentclass") && latex.trim_end().ends_with("\\end{document}"));
+    assert!(latex.lines().any(|line| line.starts_with('%')));
+}
+
+/// 测试监视模式：等待文件创建，修改后重新计算并更新输出文件
+#[test]
+fn test_watch_command() {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let input = dir.path().join("input.txt");
+    let output = dir.path().join("output.txt");
+    let mut child = Command::new(env!("CARGO_BIN_EXE_yufmath"))
+        .args(["--quiet", "watch", input.to_str().unwrap(), "-o", output.to_str().unwrap(), "--interval", "20"])
+        .spawn()
+        .expect("Failed to start watch command");
+
+    let wait_for = |expected: &str| {
+        let deadline = Instant::now() + Duration::from_secs(30);
+        while Instant::now() < deadline {
+            if fs::read_to_string(&output).is_ok_and(|content| content.contains(expected)) {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        false
+    };
+
+    // 输入文件稍后才创建
+    std::thread::sleep(Duration::from_millis(100));
+    fs::write(&input, "2 + 3\n").unwrap();
+    let created = wait_for("2 + 3 = 5");
+    fs::write(&input, "2 + 3\n4 * 4\n").unwrap();
+    let updated = wait_for("4 * 4 = 16");
+
+    let running = child.try_wait().unwrap().is_none();
+    child.kill().ok();
+    child.wait().ok();
+    assert!(created && updated && running);
+}
+
 /// 测试详细模式
 #[test]
 fn test_verbose_mode() {
@@ -185,6 +542,95 @@ fn test_format_options() {
     assert!(output.status.success() || output.status.code() == Some(1));
 }
 
+/// 测试练习题命令
+#[test]
+fn test_quiz_command() {
+    let run = || Command::new("cargo")
+        .args(["run", "--", "quiz", "diff", "--count", "4", "--seed", "7"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute quiz command");
+
+    let output = run();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success());
+    assert!(stdout.starts_with("求导练习（难度 1，种子 7）"));
+    let problems = stdout.split("答案").next().unwrap();
+    assert!(problems.contains("4. ") && !problems.contains("5. "));
+    assert!(stdout.split("答案").nth(1).unwrap().contains("4. "));
+    assert_eq!(stdout, String::from_utf8_lossy(&run().stdout));
+
+    let output = Command::new("cargo")
+        .args(["run", "--", "quiz", "solve", "--difficulty", "5"])
+        .current_dir(".")
+        .output()
+        .expect("Failed to execute quiz command");
+    assert!(!output.status.success());
+}
+
+/// 测试彩色输出选项
+#[test]
+fn test_color_option() {
+    let run = |args: &[&str], no_color: bool| {
+        let mut command = Command::new("cargo");
+        command.args(["run", "--"]).args(args).current_dir(".").env_remove("FORCE_COLOR");
+        if no_color {
+            command.env("NO_COLOR", "1");
+        }
+        command.output().expect("Failed to execute color command")
+    };
+    
+    // 强制着色时去掉转义码后与纯文本输出相同
+    let colored = run(&["--color", "always", "simplify", "x + x + (y - 1)^2"], false);
+    let plain = run(&["--color", "never", "simplify", "x + x + (y - 1)^2"], false);
+    let (colored, plain) = (String::from_utf8_lossy(&colored.stdout), String::from_utf8_lossy(&plain.stdout));
+    assert!(colored.contains('\x1b'));
+    assert!(!plain.contains('\x1b'));
+    assert_eq!(strip_ansi(&colored), plain);
+    
+    // 输出到管道时 auto 不着色，NO_COLOR 也不影响纯文本
+    let piped = run(&["compute", "2 * x"], false);
+    assert_eq!(String::from_utf8_lossy(&piped.stdout).trim(), "2 * x");
+    let piped = run(&["--color", "auto", "compute", "2 * x"], true);
+    assert!(!String::from_utf8_lossy(&piped.stdout).contains('\x1b'));
+    
+    // 解析错误的位置指示
+    let error = run(&["--color", "always", "--lang", "en", "compute", "2 + * 3"], false);
+    let stderr = String::from_utf8_lossy(&error.stderr);
+    assert!(!error.status.success());
+    assert!(stderr.contains("Input:    2 + * 3"));
+    assert!(stderr.contains(&ansi_term::Colour::Red.bold().paint("^").to_string()));
+}
+
+/// 测试解析错误的位置指示
+#[test]
+fn test_error_context() {
+    let yuf = Yufmath::new();
+    // 终端显示宽度，非 ASCII 字符占两列
+    let width = |text: &str| text.chars().map(|c| if c.is_ascii() { 1 } else { 2 }).sum::<usize>();
+    let caret_under = |lines: &[String], input: &str| {
+        let input_column = width(&lines[0][..lines[0].find(input).unwrap()]);
+        width(&lines[1][..lines[1].find('^').unwrap()]) - input_column
+    };
+    
+    let error: Box<dyn std::error::Error> = yuf.parse("2 + * 3").unwrap_err().into();
+    let lines = error_context(error.as_ref(), "2 + * 3", false);
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].contains("2 + * 3"));
+    assert_eq!(caret_under(&lines, "2 + * 3"), 4);
+    
+    // 参数个数不符时报告函数名的位置，^ 落在函数名下方
+    let error = yuf.parse("1 + sin(x, y)").unwrap_err();
+    assert!(matches!(&error, YufmathError::Parse(e) if e.position() == Some(4)), "{:?}", error);
+    let error: Box<dyn std::error::Error> = error.into();
+    let lines = error_context(error.as_ref(), "1 + sin(x, y)", false);
+    assert_eq!(caret_under(&lines, "1 + sin(x, y)"), 4);
+    
+    // 不是解析错误时没有位置指示
+    let error: Box<dyn std::error::Error> = "not a parse error".into();
+    assert!(error_context(error.as_ref(), "2 + * 3", true).is_empty());
+}
+
 /// 测试精度选项
 #[test]
 fn test_precision_option() {
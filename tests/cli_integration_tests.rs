@@ -71,14 +71,53 @@ fn test_simplify_command() {
 #[test]
 fn test_diff_command() {
     let output = Command::new("cargo")
-        .args(&["run", "--bin", "yufmath", "--", "diff", "x^2", "x"])
+        .args(["run", "--bin", "yufmath", "--", "diff", "x^3+2*x^2+x", "x"])
         .current_dir(".")
         .output()
         .expect("执行命令失败");
     
-    // 检查命令是否正确解析
-    let stderr = str::from_utf8(&output.stderr).unwrap();
-    assert!(stderr.contains("not yet implemented") || stderr.contains("todo"));
+    // 直接输出化简后的导数
+    assert!(output.status.success());
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    assert_eq!(stdout.trim(), "3 * x^2 + 4 * x + 1");
+}
+
+/// 测试省略求导变量和依次对多个变量求导
+#[test]
+fn test_diff_command_variables() {
+    let run = |args: &[&str]| Command::new("cargo")
+        .args(["run", "--bin", "yufmath", "--", "--lang", "en", "diff"])
+        .args(args)
+        .current_dir(".")
+        .output()
+        .expect("执行命令失败");
+
+    // 只含一个变量时可以省略
+    let output = run(&["x^3+2*x^2+x"]);
+    assert!(output.status.success());
+    assert_eq!(str::from_utf8(&output.stdout).unwrap().trim(), "3 * x^2 + 4 * x + 1");
+
+    // 多个变量时要求指定，并列出变量
+    let output = run(&["x^2*y"]);
+    assert!(!output.status.success());
+    assert!(str::from_utf8(&output.stderr).unwrap().contains("x, y"));
+
+    let output = run(&["x^2*y", "x", "y"]);
+    assert!(output.status.success());
+    assert_eq!(str::from_utf8(&output.stdout).unwrap().trim(), "2 * x");
+}
+
+/// 测试加法项的显示顺序
+#[test]
+fn test_term_order_option() {
+    let output = Command::new("cargo")
+        .args(["run", "--bin", "yufmath", "--", "--term-order", "degree-asc", "diff", "x^3+2*x^2+x", "x"])
+        .current_dir(".")
+        .output()
+        .expect("执行命令失败");
+
+    assert!(output.status.success());
+    assert_eq!(str::from_utf8(&output.stdout).unwrap().trim(), "1 + 4 * x + 3 * x^2");
 }
 
 /// 测试积分命令
@@ -191,4 +230,50 @@ fn test_interactive_command() {
     // 交互模式目前使用 todo!()，应该会 panic
     let stderr = str::from_utf8(&output.stderr).unwrap();
     assert!(stderr.contains("not yet implemented") || stderr.contains("todo"));
-}
\ No newline at end of file
+}
+/// 测试英文输出
+#[test]
+fn test_lang_option() {
+    let output = Command::new("cargo")
+        .args(["run", "--bin", "yufmath", "--", "--lang", "en", "diff", "--help"])
+        .current_dir(".")
+        .output()
+        .expect("执行命令失败");
+    
+    assert!(output.status.success());
+    let stdout = str::from_utf8(&output.stdout).unwrap();
+    assert!(stdout.contains("Differentiate with respect to a variable"));
+    assert!(stdout.contains("Variable of differentiation"));
+    
+    // 环境变量同样生效，--lang 优先
+    let output = Command::new("cargo")
+        .args(["run", "--bin", "yufmath", "--", "compute", "1/0"])
+        .env("YUFMATH_LANG", "en")
+        .current_dir(".")
+        .output()
+        .expect("执行命令失败");
+    
+    assert!(!output.status.success());
+    assert!(str::from_utf8(&output.stderr).unwrap().contains("division by zero"));
+    
+    let output = Command::new("cargo")
+        .args(["run", "--bin", "yufmath", "--", "--lang=zh", "compute", "1/0"])
+        .env("YUFMATH_LANG", "en")
+        .current_dir(".")
+        .output()
+        .expect("执行命令失败");
+    
+    assert!(str::from_utf8(&output.stderr).unwrap().contains("除零错误"));
+
+    // 引擎内部的错误消息同样翻译
+    let output = Command::new("cargo")
+        .args(["run", "--bin", "yufmath", "--", "--lang", "en", "compute", "[[1,2],[3,4]][5,1]"])
+        .current_dir(".")
+        .output()
+        .expect("执行命令失败");
+
+    assert!(!output.status.success());
+    let stderr = str::from_utf8(&output.stderr).unwrap();
+    assert!(stderr.contains("subscript [5, 1] out of bounds: the matrix is 2×2"));
+    assert!(!stderr.contains("越界"));
+}
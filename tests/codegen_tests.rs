@@ -0,0 +1,155 @@
+//! # 代码生成测试
+//!
+//! 测试 Rust、C、Python 代码生成的结构，并实际编译生成的 Rust 代码，
+//! 在随机输入上与数值计算结果对比。
+
+use std::collections::HashMap;
+use std::process::Command;
+use yufmath::{Yufmath, Expression, CodegenOptions, Language};
+use yufmath::engine::calculus::CalculusEngine;
+
+/// 确定性的伪随机数（线性同余），生成 [-2, 2) 内的输入
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self) -> f64 {
+        self.0 = self.0.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+        ((self.0 >> 11) as f64 / (1u64 << 53) as f64) * 4.0 - 2.0
+    }
+}
+
+/// 编译生成的 Rust 函数并在给定输入上运行，返回全部输出值
+fn run_generated_rust(code: &str, inputs: &[Vec<f64>], outputs_per_call: usize) -> Vec<f64> {
+    let dir = tempfile::tempdir().expect("Failed to create temp dir");
+    let source = dir.path().join("generated.rs");
+    let binary = dir.path().join("generated");
+
+    let calls: Vec<String> = inputs.iter()
+        .map(|point| {
+            let args: Vec<String> = point.iter().map(|v| format!("{:?}", v)).collect();
+            if outputs_per_call == 1 {
+                format!("    println!(\"{{:?}}\", f({}));", args.join(", "))
+            } else {
+                format!("    for v in f({}) {{ println!(\"{{:?}}\", v); }}", args.join(", "))
+            }
+        })
+        .collect();
+    let program = format!("{}\nfn main() {{\n{}\n}}\n", code, calls.join("\n"));
+    std::fs::write(&source, program).expect("Failed to write source");
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    let output = Command::new(rustc)
+        .args(["--edition", "2021", "-O", "-o"])
+        .arg(&binary)
+        .arg(&source)
+        .output()
+        .expect("Failed to run rustc");
+    assert!(output.status.success(), "生成的代码编译失败:\n{}\n{}", code, String::from_utf8_lossy(&output.stderr));
+
+    let output = Command::new(&binary).output().expect("Failed to run generated binary");
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| line.parse().expect("Failed to parse output"))
+        .collect()
+}
+
+/// 比较生成代码与 numerical_evaluate 的结果
+fn assert_matches_numerical(yuf: &Yufmath, outputs: &[Expression], vars: &[&str], seed: u64) {
+    let expr = if outputs.len() == 1 { outputs[0].clone() } else { Expression::Vector(outputs.to_vec()) };
+    let code = yuf.codegen(&expr, Language::Rust, &CodegenOptions::default()).unwrap();
+
+    let mut rng = Lcg(seed);
+    let inputs: Vec<Vec<f64>> = (0..20).map(|_| vars.iter().map(|_| rng.next()).collect()).collect();
+    let actual = run_generated_rust(&code, &inputs, outputs.len());
+
+    let engine = CalculusEngine::new();
+    let mut expected = Vec::new();
+    for point in &inputs {
+        let bindings: HashMap<String, f64> = vars.iter().map(|v| v.to_string()).zip(point.iter().copied()).collect();
+        for output in outputs {
+            expected.push(engine.numerical_evaluate(output, &bindings).unwrap());
+        }
+    }
+
+    assert_eq!(actual.len(), expected.len());
+    for (a, e) in actual.iter().zip(&expected) {
+        assert!((a - e).abs() <= 1e-9 * (1.0 + e.abs()), "生成代码结果 {} 与数值计算 {} 不一致:\n{}", a, e, code);
+    }
+}
+
+#[test]
+fn test_codegen_rust_matches_numerical_evaluation() {
+    let yuf = Yufmath::new();
+    let cases = [
+        "(x + 1)^5 / (x^2 + 1) + exp(-x) * sqrt(x^2 + y^2)",
+        "sin(x*y) + cos(x*y)^2 - ln(y^2 + 1) / (x*y + 3)",
+        "cbrt(x - y) + abs(x) * floor(y) + pi * x^(-2) + root(x, 3)",
+        "atan(x) - tanh(y)^3 + 1/3 * x^7 + round(x) - trunc(y)",
+    ];
+    for (i, case) in cases.iter().enumerate() {
+        let expr = yuf.parse(case).unwrap();
+        assert_matches_numerical(&yuf, &[expr], &["x", "y"], i as u64 + 1);
+    }
+}
+
+#[test]
+fn test_codegen_gradient_vector() {
+    let yuf = Yufmath::new();
+    let expr = yuf.parse("x^2*y + sin(x*y)").unwrap();
+    let gradient = vec![yuf.diff(&expr, "x").unwrap(), yuf.diff(&expr, "y").unwrap()];
+
+    let code = yuf.codegen(&Expression::Vector(gradient.clone()), Language::Rust, &CodegenOptions::default()).unwrap();
+    assert!(code.contains("pub fn f(x: f64, y: f64) -> [f64; 2]"));
+
+    assert_matches_numerical(&yuf, &gradient, &["x", "y"], 42);
+}
+
+#[test]
+fn test_codegen_common_subexpressions() {
+    let yuf = Yufmath::new();
+    let expr = yuf.parse("sin(x + y) * sin(x + y) + (x + y)").unwrap();
+
+    let code = yuf.codegen(&expr, Language::Rust, &CodegenOptions::default()).unwrap();
+    assert!(code.contains("let t0: f64 = x + y;"), "{}", code);
+    assert!(code.contains("let t1: f64 = t0.sin();"), "{}", code);
+    assert!(code.contains("t1 * t1 + t0"), "{}", code);
+
+    // 关闭 CSE 后重复计算
+    let options = CodegenOptions { cse: false, ..CodegenOptions::default() };
+    let code = yuf.codegen(&expr, Language::Rust, &options).unwrap();
+    assert!(!code.contains("let "));
+    assert_eq!(code.matches("f64::sin(x + y)").count(), 2, "{}", code);
+}
+
+#[test]
+fn test_codegen_languages() {
+    let yuf = Yufmath::new();
+    let expr = yuf.parse("x^2 + y^7 + ln(x) + pi").unwrap();
+
+    let rust = yuf.codegen(&expr, Language::Rust, &CodegenOptions::default()).unwrap();
+    assert!(rust.contains("x * x"));
+    assert!(rust.contains("y.powi(7)"));
+    assert!(rust.contains("x.ln()"));
+    assert!(rust.contains("std::f64::consts::PI"));
+
+    let c = yuf.codegen(&expr, Language::C, &CodegenOptions::default()).unwrap();
+    assert!(c.starts_with("#include <math.h>"));
+    assert!(c.contains("double f(double x, double y) {"));
+    assert!(c.contains("pow(y, 7.0)"));
+    assert!(c.contains("log(x)"));
+    assert!(c.contains("M_PI"));
+
+    let options = CodegenOptions { function_name: "g".to_string(), ..CodegenOptions::default() };
+    let python = yuf.codegen(&expr, Language::Python, &options).unwrap();
+    assert!(python.contains("def g(x, y):"));
+    assert!(python.contains("y ** 7"));
+    assert!(python.contains("math.log(x)"));
+    assert!(python.contains("math.pi"));
+}
+
+#[test]
+fn test_codegen_unsupported() {
+    let yuf = Yufmath::new();
+    let expr = yuf.parse("x + i").unwrap();
+    assert!(yuf.codegen(&expr, Language::Rust, &CodegenOptions::default()).is_err());
+}
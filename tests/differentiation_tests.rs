@@ -40,13 +40,8 @@ fn test_differentiate_polynomial() {
     
     let result = yuf.differentiate(&expr, "x").unwrap();
     
-    // 结果应该是乘法表达式（2 * x^1 * 1）
-    match result {
-        Expression::BinaryOp { op: BinaryOperator::Multiply, .. } => {
-            // 验证结构正确
-        }
-        _ => panic!("期望得到乘法表达式"),
-    }
+    // 结果化简为 2 * x，不含 x^1、* 1
+    assert_eq!(result.to_string(), "2 * x");
 }
 
 #[test]
@@ -61,19 +56,9 @@ fn test_differentiate_trigonometric() {
     
     let result = yuf.differentiate(&expr, "x").unwrap();
     
-    // 结果应该是 cos(x) * 1
-    match result {
-        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
-            match (left.as_ref(), right.as_ref()) {
-                (Expression::UnaryOp { op: UnaryOperator::Cos, .. }, 
-                 Expression::Number(Number::Integer(n))) => {
-                    assert_eq!(n, &BigInt::from(1));
-                }
-                _ => panic!("期望得到 cos(x) * 1"),
-            }
-        }
-        _ => panic!("期望得到乘法表达式"),
-    }
+    // 结果化简为 cos(x)，原始的 cos(x) * 1 由 diff_raw 保留
+    assert_eq!(result.to_string(), "cos(x)");
+    assert_eq!(yuf.diff_raw(&expr, "x").unwrap().to_string(), "cos(x) * 1");
 }
 
 #[test]
@@ -119,13 +104,8 @@ fn test_differentiate_product_rule() {
     
     let result = yuf.differentiate(&expr, "x").unwrap();
     
-    // 结果应该是加法表达式（乘法法则：u'v + uv'）
-    match result {
-        Expression::BinaryOp { op: BinaryOperator::Add, .. } => {
-            // 验证结构正确
-        }
-        _ => panic!("期望得到加法表达式（乘法法则）"),
-    }
+    // 乘法法则 u'v + uv'，u' = 1 已约去
+    assert_eq!(result.to_string(), "sin(x) + x * cos(x)");
 }
 
 #[test]
@@ -175,13 +155,8 @@ fn test_differentiate_chain_rule() {
     
     let result = yuf.differentiate(&expr, "x").unwrap();
     
-    // 结果应该是乘法表达式（链式法则）
-    match result {
-        Expression::BinaryOp { op: BinaryOperator::Multiply, .. } => {
-            // 验证结构正确
-        }
-        _ => panic!("期望得到乘法表达式（链式法则）"),
-    }
+    // 链式法则：cos(x^2) · 2x
+    assert_eq!(result.to_string(), "cos(x^2) * (2 * x)");
 }
 
 #[test]
@@ -196,17 +171,92 @@ fn test_differentiate_exponential() {
     
     let result = yuf.differentiate(&expr, "x").unwrap();
     
-    // 结果应该是 e^x * 1
-    match result {
-        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
-            match (left.as_ref(), right.as_ref()) {
-                (Expression::UnaryOp { op: UnaryOperator::Exp, .. }, 
-                 Expression::Number(Number::Integer(n))) => {
-                    assert_eq!(n, &BigInt::from(1));
-                }
-                _ => panic!("期望得到 e^x * 1"),
-            }
-        }
-        _ => panic!("期望得到乘法表达式"),
+    // 结果化简为 exp(x)
+    assert_eq!(result.to_string(), "exp(x)");
+}
+#[test]
+fn test_differentiate_returns_light_simplified_result() {
+    let yuf = Yufmath::new();
+    
+    // 不出现 *1、+0、^1，数值系数已折叠
+    let expr = yuf.parse("x^3+2*x^2+x").unwrap();
+    assert_eq!(yuf.diff(&expr, "x").unwrap(), yuf.parse("3*x^2 + 4*x + 1").unwrap());
+    
+    // 超时时返回未化简的导数而不是报错
+    yuf.update_config(|config| config.max_compute_time = Some(std::time::Duration::ZERO));
+    assert!(yuf.diff(&expr, "x").is_ok());
+}
+
+#[test]
+fn test_unary_operator_and_function_call_agree() {
+    let yuf = Yufmath::new();
+    let inner = yuf.parse("x^2 + 1").unwrap();
+    
+    let cases = [
+        ("sin", UnaryOperator::Sin), ("cos", UnaryOperator::Cos), ("tan", UnaryOperator::Tan),
+        ("atan", UnaryOperator::Atan), ("sinh", UnaryOperator::Sinh), ("ln", UnaryOperator::Ln),
+        ("log10", UnaryOperator::Log10), ("exp", UnaryOperator::Exp),
+    ];
+    for (name, op) in cases {
+        let parsed = yuf.parse(&format!("{}(x^2 + 1)", name)).unwrap();
+        let constructed = Expression::unary_op(op.clone(), inner.clone());
+        // 直接写出的 UnaryOp 结构也按同名函数处理
+        let literal = Expression::UnaryOp { op, operand: Box::new(inner.clone()) };
+        
+        // 构造函数与解析器得到同一种内部表示
+        assert_eq!(constructed, parsed, "{}", name);
+        let derivative = yuf.differentiate(&parsed, "x").unwrap();
+        assert_eq!(yuf.differentiate(&literal, "x").unwrap(), derivative, "{}", name);
+        let simplified = yuf.simplify(&parsed).unwrap();
+        assert_eq!(yuf.simplify(&literal).unwrap(), simplified, "{}", name);
     }
-}
\ No newline at end of file
+    
+    // 特殊值在两种来源下同样化简
+    let zero = Expression::Number(Number::Integer(BigInt::from(0)));
+    let literal = Expression::UnaryOp { op: UnaryOperator::Sin, operand: Box::new(zero.clone()) };
+    assert_eq!(yuf.simplify(&literal).unwrap(), zero);
+}
+
+#[test]
+fn test_differentiate_auto_variable() {
+    let yuf = Yufmath::new();
+    
+    // 只含一个变量时自动选择，pi 与 e 不算变量
+    let expr = yuf.parse("pi * t^2 + e").unwrap();
+    assert_eq!(yuf.sole_variable(&expr).unwrap(), "t");
+    let expected = yuf.diff(&expr, "t").unwrap();
+    assert_eq!(yuf.diff(&expr, "").unwrap(), expected);
+    assert_eq!(yuf.diff_auto(&expr).unwrap(), expected);
+    
+    let integrand = yuf.parse("2*t").unwrap();
+    assert_eq!(yuf.integrate(&integrand, "").unwrap(), yuf.integrate(&integrand, "t").unwrap());
+    
+    // 多个变量时报错并列出变量
+    let expr = yuf.parse("x^2 * y").unwrap();
+    let message = yuf.diff_auto(&expr).unwrap_err().to_string();
+    assert!(message.contains("x, y"), "{}", message);
+    assert!(yuf.integrate(&expr, "").is_err());
+    
+    // 不含变量时同样要求明确指定
+    assert!(yuf.diff_auto(&yuf.parse("pi + 1").unwrap()).is_err());
+}
+
+#[test]
+fn test_differentiate_multiple_variables() {
+    let yuf = Yufmath::new();
+    let expr = yuf.parse("x^3 * y^2").unwrap();
+    
+    // ∂³/∂x²∂y (x³y²) = 12xy，与逐次求导一致
+    let stepwise = yuf.diff(&yuf.diff(&yuf.diff(&expr, "x").unwrap(), "x").unwrap(), "y").unwrap();
+    let mixed = yuf.diff(&expr, &["x", "x", "y"]).unwrap();
+    assert_eq!(mixed, stepwise);
+    
+    let mut values = std::collections::HashMap::new();
+    values.insert("x".to_string(), 2.0);
+    values.insert("y".to_string(), 3.0);
+    assert!((yuf.numerical_evaluate(&mixed, &values).unwrap() - 72.0).abs() < 1e-9);
+    
+    // 变量列表可以是 Vec<String>，单元素列表等同于单个变量
+    let variables = vec!["y".to_string()];
+    assert_eq!(yuf.diff(&expr, &variables).unwrap(), yuf.diff(&expr, "y").unwrap());
+}
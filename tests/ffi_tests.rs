@@ -0,0 +1,60 @@
+//! # FFI 接口测试
+//!
+//! 测试通过 C API 以二进制格式传递表达式。
+
+use std::ffi::{CStr, CString};
+use std::slice;
+use yufmath::core::Expression;
+use yufmath::ffi::*;
+
+fn bytes(buffer: &YufmathBuffer) -> &[u8] {
+    unsafe { slice::from_raw_parts(buffer.data, buffer.len) }
+}
+
+/// 测试解析、化简、求导与格式化的往返
+#[test]
+fn test_binary_round_trip() {
+    let input = CString::new("x^2 + x^2").unwrap();
+    let var = CString::new("x").unwrap();
+    unsafe {
+        let parsed = yufmath_parse(input.as_ptr());
+        assert!(!parsed.is_null());
+        assert_eq!(Expression::from_bytes(bytes(&parsed)).unwrap().to_string(), "x^2 + x^2");
+
+        let simplified = yufmath_simplify(parsed.data, parsed.len);
+        assert!(!simplified.is_null());
+        let text = yufmath_to_string(simplified.data, simplified.len);
+        assert_eq!(CStr::from_ptr(text).to_str().unwrap(), "2 * x^2");
+        yufmath_string_free(text);
+
+        // 同一份编码可以反复传给不同的运算
+        let derivative = yufmath_diff(parsed.data, parsed.len, var.as_ptr());
+        let expected = Expression::from_bytes(bytes(&derivative)).unwrap();
+        assert_eq!(expected.to_string(), "4 * x");
+        assert!(yufmath_last_error().is_null());
+
+        yufmath_buffer_free(parsed);
+        yufmath_buffer_free(simplified);
+        yufmath_buffer_free(derivative);
+    }
+}
+
+/// 测试错误以空结果和错误信息返回
+#[test]
+fn test_errors() {
+    let bad = CString::new("x +* 1").unwrap();
+    let damaged = [b'Y', b'U', b'F', b'B', 1, 0, 0];
+    unsafe {
+        let parsed = yufmath_parse(bad.as_ptr());
+        assert!(parsed.is_null());
+        assert!(!yufmath_last_error().is_null());
+
+        let simplified = yufmath_simplify(damaged.as_ptr(), damaged.len());
+        assert!(simplified.is_null());
+        let message = CStr::from_ptr(yufmath_last_error()).to_string_lossy().into_owned();
+        assert!(message.contains("二进制表达式数据无效"), "{}", message);
+        assert!(yufmath_to_string(std::ptr::null(), 0).is_null());
+
+        yufmath_buffer_free(parsed);
+    }
+}
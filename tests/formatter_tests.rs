@@ -4,9 +4,10 @@
 
 use yufmath::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator};
 use yufmath::formatter::{
-    Formatter, FormatOptions, FormatType, 
-    StandardFormatter, LaTeXFormatter, MathMLFormatter, MultiFormatter
+    Formatter, FormatOptions, FormatType, MultilineEnv, TermOrder,
+    StandardFormatter, LaTeXFormatter, MathMLFormatter, MaximaFormatter, SymPyFormatter, MultiFormatter, TerminalFormatter
 };
+use yufmath::parser::{Parser, syntax::ExpressionParser};
 use num_bigint::BigInt;
 use num_rational::BigRational;
 
@@ -119,6 +120,44 @@ fn test_standard_formatter() {
     }
 }
 
+#[test]
+fn test_display_matches_standard_formatter() {
+    let parse = |input: &str| ExpressionParser::new().parse(input).unwrap();
+    let formatter = StandardFormatter::new();
+    let mut expressions: Vec<Expression> = create_test_expressions().into_iter().map(|(_, expr)| expr).collect();
+    expressions.extend([
+        "2*x + 3", "x + (-5)", "a + (-b)", "a - (-b)", "-(a + b)", "(-x)^2", "-x^2", "x^(y^z)", "(x^y)^z",
+        "1/2 * sin(x + y)", "x*y*z", "A[i, j]", "(A*B)[m, n]", "(x + 1)!", "abs(x - 1)", "sqrt(x)",
+        "[1, 2, 3]", "[[1, x], [y, 2]]", "x == y", "x <= 1 && y > 2", "inf - 1",
+    ].map(parse));
+    expressions.extend([
+        Expression::Number(Number::complex(Number::from(1), Number::from(-2))),
+        Expression::Number(Number::complex(Number::from(0), Number::from(1))),
+        Expression::unary_op(UnaryOperator::Transpose, Expression::variable("A")),
+        Expression::unary_op(UnaryOperator::Sqrt, Expression::variable("x")),
+        Expression::unary_op(UnaryOperator::Abs, Expression::variable("x")),
+        Expression::multiply(Expression::Number(Number::from(2)), Expression::variable("x")),
+        Expression::add(Expression::variable("x"), Expression::Number(Number::from(-1))),
+        Expression::add(Expression::variable("x"), Expression::negate(Expression::variable("y"))),
+        Expression::set(vec![Expression::Number(Number::from(1)), Expression::variable("x")]),
+        Expression::interval(Expression::Number(Number::from(0)), Expression::Constant(MathConstant::PositiveInfinity), true, false),
+    ]);
+    expressions.extend(parenthesization_corpus());
+    for expr in &expressions {
+        assert_eq!(format!("{}", expr), formatter.format(expr), "{:?}", expr);
+    }
+    assert_eq!(parse("2*x*y").to_string(), "2 * x * y");
+    assert_eq!(Expression::unary_op(UnaryOperator::Abs, Expression::variable("x")).to_string(), "abs(x)");
+
+    // Display 的输出可以重新解析
+    for input in ["2*x + 3", "1/2 * sin(x + y)", "x*y*z", "(A*B)[m, n]", "abs(x - 1)", "x^(y^z)"] {
+        let expr = parse(input);
+        assert_eq!(parse(&expr.to_string()), expr, "{}", input);
+    }
+    assert_eq!(Expression::unary_op(UnaryOperator::Transpose, Expression::variable("A")).to_string(), "A^T");
+    assert_eq!(Expression::add(Expression::variable("x"), Expression::Number(Number::from(-1))).to_string(), "x - 1");
+}
+
 #[test]
 fn test_latex_formatter() {
     let formatter = LaTeXFormatter::new();
@@ -192,6 +231,8 @@ fn test_format_options() {
         format_type: FormatType::Standard,
         precision: Some(2),
         use_parentheses: true,
+        sort_terms: false,
+        ..FormatOptions::default()
     };
     formatter.set_options(options);
     
@@ -202,6 +243,134 @@ fn test_format_options() {
     assert!(result.contains("3.14") || result.contains("3,14")); // 考虑不同的小数点格式
 }
 
+#[test]
+fn test_sort_terms() {
+    let parse = |input: &str| ExpressionParser::new().parse(input).unwrap();
+    let mut formatter = StandardFormatter::new();
+    formatter.set_options(FormatOptions { sort_terms: true, ..FormatOptions::default() });
+
+    // 次数降序，常数项在最后
+    assert_eq!(formatter.format(&parse("x + 2 + x^2")), "x^2 + x + 2");
+    // 同次按变量字典序
+    assert_eq!(formatter.format(&parse("y^2 + 1 + x*y + x^2")), "x^2 + x * y + y^2 + 1");
+    assert_eq!(formatter.format(&parse("3 - x + x^3")), "x^3 - x + 3");
+    assert_eq!(formatter.format(&parse("2 - x")), "-x + 2");
+    // 乘法里数值在前、变量按字母、函数在后
+    assert_eq!(formatter.format(&parse("sin(x) * y * x^2 * 3")), "3 * x^2 * y * sin(x)");
+    assert_eq!(formatter.format(&parse("cos(1 + x^2 + x)")), "cos(x^2 + x + 1)");
+    // 无法比较的项保持原有顺序
+    assert_eq!(formatter.format(&parse("x + cos(x) + sin(x) + x^2")), "x^2 + x + cos(x) + sin(x)");
+
+    // 未开启时保持原样
+    assert_eq!(StandardFormatter::new().format(&parse("x + 2 + x^2")), "x + 2 + x^2");
+
+    let mut latex = LaTeXFormatter::new();
+    latex.set_options(FormatOptions { format_type: FormatType::LaTeX, sort_terms: true, ..FormatOptions::default() });
+    assert_eq!(latex.format(&parse("x + 2 + x^2")), "x^{2} + x + 2");
+
+    // 单独使用时递归地重排
+    let sorted = yufmath::formatter::ordering::sort_terms(&parse("y * (1 + x) + x"));
+    assert_eq!(sorted.to_string(), "x + y * (x + 1)");
+}
+
+#[test]
+fn test_term_order() {
+    use yufmath::formatter::ordering::{degree_key, TermClass};
+    let parse = |input: &str| ExpressionParser::new().parse(input).unwrap();
+    let format = |order: TermOrder, input: &str| {
+        let mut formatter = StandardFormatter::new();
+        formatter.set_options(FormatOptions { sort_terms: true, term_order: order, ..FormatOptions::default() });
+        formatter.format(&parse(input))
+    };
+
+    assert_eq!(format(TermOrder::DegreeDesc, "1 + x^2 + x"), "x^2 + x + 1");
+    assert_eq!(format(TermOrder::DegreeAsc, "x^2 + 1 + x"), "1 + x + x^2");
+    assert_eq!(format(TermOrder::DegreeAsc, "3 - x + x^3"), "3 - x + x^3");
+    // 字典序先看主变量的次数，不看总次数
+    assert_eq!(format(TermOrder::DegreeDesc, "y + x^2 + x*y^3"), "x * y^3 + x^2 + y");
+    assert_eq!(format(TermOrder::Lexicographic, "y + x^2 + x*y^3"), "x^2 + x * y^3 + y");
+    // 非多项式项按函数名归类，与原有顺序无关
+    assert_eq!(format(TermOrder::DegreeDesc, "1 + sin(x) + x + cos(x)"), "x + cos(x) + sin(x) + 1");
+    assert_eq!(format(TermOrder::DegreeDesc, "cos(x) + 1 + x + sin(x)"), "x + cos(x) + sin(x) + 1");
+    assert_eq!(format(TermOrder::DegreeAsc, "sin(x) + x + 1 + cos(x)"), "1 + cos(x) + sin(x) + x");
+
+    // 只影响显示，不改变表达式本身
+    let expr = parse("1 + x^2 + x");
+    let mut formatter = StandardFormatter::new();
+    formatter.set_options(FormatOptions { sort_terms: true, term_order: TermOrder::DegreeDesc, ..FormatOptions::default() });
+    formatter.format(&expr);
+    assert_eq!(expr, parse("1 + x^2 + x"));
+
+    assert_eq!(degree_key(&parse("3 * x^2 * y")).degree, 3.0);
+    assert_eq!(degree_key(&parse("2 * sin(x)")).class, TermClass::Opaque);
+    assert_eq!(degree_key(&parse("2 * sin(x)")).label, "sin");
+    assert_eq!(degree_key(&parse("-pi")).class, TermClass::Constant);
+}
+
+#[test]
+fn test_latex_line_width() {
+    use yufmath::formatter::latex::estimate_width;
+    let parse = |input: &str| ExpressionParser::new().parse(input).unwrap();
+    let latex = |width: usize, env: MultilineEnv| {
+        let mut formatter = LaTeXFormatter::new();
+        formatter.set_options(FormatOptions {
+            format_type: FormatType::LaTeX,
+            line_width: Some(width),
+            multiline_env: env,
+            ..FormatOptions::default()
+        });
+        formatter
+    };
+
+    // 宽度估算：命令名不计，分式取较宽的一层，上标折算
+    assert_eq!(estimate_width("x + y"), 4);
+    assert_eq!(estimate_width("\\frac{x + 1}{2}"), 4);
+    assert_eq!(estimate_width("\\sin\\left(x\\right)"), 6);
+    assert_eq!(estimate_width("x^{10}"), 3);
+
+    // 放得下时与单行输出相同
+    let short = parse("x^2 + 1");
+    assert_eq!(latex(80, MultilineEnv::Align).format(&short), LaTeXFormatter::new().format(&short));
+
+    // 在加法项之间断行，每行不超过行宽，结果确定
+    let long = parse("x^8 + 8*x^7*y + 28*x^6*y^2 + 56*x^5*y^3 + 70*x^4*y^4 + 56*x^3*y^5 + 28*x^2*y^6 + 8*x*y^7 + y^8");
+    let output = latex(30, MultilineEnv::Align).format(&long);
+    assert!(output.starts_with("\\begin{align*}\n&x^{8}"));
+    assert!(output.ends_with("\n\\end{align*}"));
+    let lines: Vec<&str> = output.lines().collect();
+    assert!(lines.len() > 3);
+    for line in &lines[1..lines.len() - 1] {
+        assert!(estimate_width(line) <= 30, "{}", line);
+    }
+    assert!(lines[2].starts_with("&\\quad + "));
+    assert_eq!(output, latex(30, MultilineEnv::Align).format(&long));
+
+    // 等式在关系符处对齐
+    let equation = parse("y == x^8 + 8*x^7 + 28*x^6 + 56*x^5 + 70*x^4 + 56*x^3 + 28*x^2 + 8*x + 1");
+    let output = latex(30, MultilineEnv::Align).format(&equation);
+    assert!(output.contains("\ny &= x^{8}"));
+
+    let output = latex(30, MultilineEnv::Multline).format(&equation);
+    assert!(output.starts_with("\\begin{multline*}\ny = x^{8}"));
+    assert!(!output.contains('&'));
+    let output = latex(30, MultilineEnv::Breqn).format(&equation);
+    assert!(output.starts_with("\\begin{dmath*}\ny = x^{8}"));
+    assert!(!output.contains("\\\\"));
+
+    // 超宽的分式和矩阵整体放在一行，不被拆开
+    let fraction = parse("(x^5 + 5*x^4 + 10*x^3 + 10*x^2 + 5*x + 1) / (x^3 - 3*x^2 + 3*x - 1) + x");
+    let output = latex(20, MultilineEnv::Align).format(&fraction);
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert!(lines[1].starts_with("&\\frac{x^{5} + ") && lines[1].ends_with("3x - 1} \\\\"), "{}", output);
+    assert_eq!(lines[2], "&\\quad + x");
+    let matrix = parse("[[x^2 + 1, 2], [3, 4]] + [[1, 2], [3, 4]]");
+    let output = latex(12, MultilineEnv::Align).format(&matrix);
+    assert_eq!(output.matches("\\begin{pmatrix}").count(), 2);
+    assert_eq!(output.matches("\\end{pmatrix}").count(), 2);
+    assert!(output.contains("\\end{pmatrix} \\\\\n&\\quad + \\begin{pmatrix}"), "{}", output);
+}
+
 #[test]
 fn test_complex_number_formatting() {
     let formatters: Vec<Box<dyn Formatter>> = vec![
@@ -349,6 +518,21 @@ fn test_interval_formatting() {
     }
 }
 
+#[test]
+fn test_index_formatting() {
+    let a = Expression::variable("A");
+    let element = Expression::index(a.clone(), vec![Expression::Number(Number::from(1)), Expression::index_all()]);
+    assert_eq!(StandardFormatter::new().format(&element), "A[1, :]");
+    assert_eq!(LaTeXFormatter::new().format(&element), "A_{1, :}");
+    let mathml = MathMLFormatter::new().format(&element);
+    assert!(mathml.contains("<msub>") && mathml.contains("<mi>A</mi>"));
+
+    // 复合表达式作下标对象时加括号
+    let product = Expression::index(Expression::multiply(a, Expression::variable("B")), vec![Expression::variable("k")]);
+    assert!(StandardFormatter::new().format(&product).starts_with('('));
+    assert!(LaTeXFormatter::new().format(&product).starts_with("\\left("));
+}
+
 #[test]
 fn test_precedence_and_parentheses() {
     let formatter = StandardFormatter::new();
@@ -384,6 +568,209 @@ fn test_precedence_and_parentheses() {
     assert!(!result_with_parens.is_empty());
 }
 
+/// 把负数字面量写成负号、`a + (-b)` 写成 `a - b`，消除打印时允许的等价改写
+fn normalize_signs(expr: &Expression) -> Expression {
+    match expr {
+        Expression::Number(n) if n.is_negative() => Expression::negate(Expression::Number(-n.clone())),
+        Expression::BinaryOp { op, left, right } => {
+            let (left, right) = (normalize_signs(left), normalize_signs(right));
+            match (op, right) {
+                (BinaryOperator::Add, right) => match strip_leading_negation(&right) {
+                    Some(magnitude) => Expression::subtract(left, magnitude),
+                    None => Expression::add(left, right),
+                },
+                (op, right) => Expression::binary_op(op.clone(), left, right),
+            }
+        }
+        Expression::UnaryOp { op, operand } => Expression::unary_op(op.clone(), normalize_signs(operand)),
+        _ => expr.clone(),
+    }
+}
+
+/// 去掉项开头的负号：`-u` 为 `u`，首个因子取负的乘积 `(-3)*2` 为 `3*2`
+fn strip_leading_negation(expr: &Expression) -> Option<Expression> {
+    match expr {
+        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => Some(operand.as_ref().clone()),
+        Expression::BinaryOp { op: BinaryOperator::Multiply, left, right } => {
+            strip_leading_negation(left).map(|left| Expression::multiply(left, right.as_ref().clone()))
+        }
+        _ => None,
+    }
+}
+
+/// 由常量、带符号的数、变量、函数调用和各种算术运算组合出的表达式集合
+fn parenthesization_corpus() -> Vec<Expression> {
+    let atoms = vec![
+        Expression::Number(Number::from(2)),
+        Expression::Number(Number::from(-3)),
+        Expression::Constant(MathConstant::Pi),
+        Expression::Constant(MathConstant::E),
+        Expression::variable("a"),
+        Expression::variable("bc"),
+        Expression::function("sin", vec![Expression::variable("y")]),
+        Expression::variable("x"),
+    ];
+    let mut operands = atoms.clone();
+    for atom in &atoms {
+        operands.push(Expression::negate(atom.clone()));
+        operands.push(Expression::unary_op(UnaryOperator::Factorial, atom.clone()));
+    }
+    let ops = [
+        BinaryOperator::Add, BinaryOperator::Subtract, BinaryOperator::Multiply,
+        BinaryOperator::Divide, BinaryOperator::Modulo, BinaryOperator::Power,
+    ];
+    let mut pairs = Vec::new();
+    for op in &ops {
+        for left in &operands {
+            for right in &operands {
+                pairs.push(Expression::binary_op(op.clone(), left.clone(), right.clone()));
+            }
+        }
+    }
+    // 再套一层：复合表达式分别作左、右运算数以及负号、阶乘的运算数
+    let mut corpus = pairs.clone();
+    for (i, inner) in pairs.iter().enumerate().step_by(5) {
+        let op = ops[i % ops.len()].clone();
+        let other = operands[i % operands.len()].clone();
+        corpus.push(Expression::binary_op(op.clone(), inner.clone(), other.clone()));
+        corpus.push(Expression::binary_op(op, other, inner.clone()));
+        corpus.push(Expression::negate(inner.clone()));
+        corpus.push(Expression::unary_op(UnaryOperator::Factorial, inner.clone()));
+        corpus.push(Expression::negate(Expression::negate(inner.clone())));
+    }
+    corpus
+}
+
+#[test]
+fn test_parenthesization_round_trip() {
+    let parser = ExpressionParser::new();
+    let standard = StandardFormatter::new();
+    for expr in parenthesization_corpus() {
+        let expected = normalize_signs(&expr);
+        for printed in [expr.to_string(), standard.format(&expr)] {
+            let reparsed = parser.parse(&printed).unwrap_or_else(|e| panic!("无法重新解析 {}: {}", printed, e));
+            assert_eq!(normalize_signs(&reparsed), expected, "{}", printed);
+        }
+    }
+}
+
+#[test]
+fn test_sign_parenthesization() {
+    let x = Expression::variable("x");
+    let b = Expression::variable("b");
+    let cases = [
+        (Expression::multiply(Expression::Number(Number::from(2)), Expression::Number(Number::from(-3))), "2 * (-3)"),
+        (Expression::subtract(Expression::variable("a"), Expression::negate(b.clone())), "a - (-b)"),
+        (Expression::power(x.clone(), Expression::Number(Number::from(-1))), "x^(-1)"),
+        (Expression::power(Expression::negate(x.clone()), Expression::Number(Number::from(2))), "(-x)^2"),
+        (Expression::negate(Expression::power(x.clone(), Expression::Number(Number::from(2)))), "-x^2"),
+        (Expression::power(x.clone(), Expression::power(Expression::variable("a"), b.clone())), "x^(a^b)"),
+        (Expression::negate(Expression::add(Expression::variable("a"), b.clone())), "-(a + b)"),
+        (Expression::unary_op(UnaryOperator::Factorial, Expression::add(x.clone(), Expression::Number(Number::from(1)))), "(x + 1)!"),
+    ];
+    let standard = StandardFormatter::new();
+    for (expr, expected) in &cases {
+        assert_eq!(expr.to_string(), *expected);
+        assert_eq!(standard.format(expr), *expected);
+    }
+
+    let latex = LaTeXFormatter::new();
+    assert_eq!(latex.format(&cases[0].0), "2 \\cdot \\left(-3\\right)");
+    assert_eq!(latex.format(&cases[2].0), "x^{-1}");
+    assert_eq!(latex.format(&cases[3].0), "\\left(-x\\right)^{2}");
+    assert_eq!(latex.format(&cases[4].0), "-x^{2}");
+    assert_eq!(latex.format(&cases[6].0), "-\\left(a + b\\right)");
+    let product = Expression::multiply(Expression::add(x.clone(), b.clone()), Expression::divide(x, b));
+    assert_eq!(latex.format(&product), "\\left(x + b\\right) \\cdot \\frac{x}{b}");
+}
+
+#[test]
+fn test_power_tower_round_trip() {
+    let parser = ExpressionParser::new();
+    let (x, y, z) = (Expression::variable("x"), Expression::variable("y"), Expression::variable("z"));
+    let right_nested = Expression::power(x.clone(), Expression::power(y.clone(), z.clone()));
+    let left_nested = Expression::power(Expression::power(x, y), z);
+
+    let standard = StandardFormatter::new();
+    let mut terminal = TerminalFormatter::new();
+    terminal.set_colors_enabled(false);
+    assert_eq!(standard.format(&right_nested), "x^(y^z)");
+    assert_eq!(standard.format(&left_nested), "(x^y)^z");
+    for expr in [&right_nested, &left_nested] {
+        for printed in [expr.to_string(), standard.format(expr), terminal.format(expr)] {
+            assert_eq!(&parser.parse(&printed).unwrap(), expr, "{}", printed);
+        }
+    }
+
+    let latex = LaTeXFormatter::new();
+    assert_eq!(latex.format(&right_nested), "x^{y^{z}}");
+    assert_eq!(latex.format(&left_nested), "\\left(x^{y}\\right)^{z}");
+}
+
+#[test]
+fn test_sympy_formatting() {
+    let parser = ExpressionParser::new();
+    let formatter = SymPyFormatter::new();
+    let sympy = |input: &str| formatter.format(&parser.parse(input).unwrap());
+
+    // 乘方写作 **，整数相除保持精确
+    assert_eq!(sympy("x^2 + 2*x + 1"), "x ** 2 + 2 * x + 1");
+    assert_eq!(sympy("1/3 + x/2"), "sp.Integer(1) / 3 + x / 2");
+    assert_eq!(sympy("(-2)^x"), "(-2) ** x");
+    assert_eq!(formatter.format(&Expression::Number(Number::rational(3, 4))), "sp.Rational(3, 4)");
+
+    // 常量与函数带 sp. 前缀，未知函数用 sp.Function
+    assert_eq!(sympy("e^(i*pi) + inf"), "sp.E ** (sp.I * sp.pi) + sp.oo");
+    assert_eq!(sympy("ln(x) + arctan(y) + abs(z)"), "sp.log(x) + sp.atan(y) + sp.Abs(z)");
+    assert_eq!(sympy("f(x, 1)"), "sp.Function(\"f\")(x, 1)");
+    assert_eq!(sympy("integrate(x^2, x, 0, 1)"), "sp.integrate(x ** 2, (x, 0, 1))");
+    assert_eq!(sympy("x % 7 == 1"), "sp.Eq(sp.Mod(x, 7), 1)");
+
+    // 矩阵与下标，下标从 0 开始
+    assert_eq!(sympy("[[1, 2], [3, 4]]"), "sp.Matrix([[1, 2], [3, 4]])");
+    assert_eq!(sympy("A[1, 2] + A[2, :]"), "A[0, 1] + A[1, :]");
+}
+
+#[test]
+fn test_maxima_formatting() {
+    let parser = ExpressionParser::new();
+    let formatter = MaximaFormatter::new();
+    let maxima = |input: &str| formatter.format(&parser.parse(input).unwrap());
+
+    // 常量带 % 前缀
+    assert_eq!(maxima("2*pi*r"), "2*%pi*r");
+    assert_eq!(maxima("e^(i*theta)"), "%e^(%i*theta)");
+    assert_eq!(maxima("gamma + phi"), "%gamma + %phi");
+    assert_eq!(maxima("inf"), "inf");
+    assert_eq!(formatter.format(&Expression::Constant(MathConstant::NegativeInfinity)), "minf");
+
+    // 运算与函数
+    assert_eq!(maxima("sqrt(2)"), "sqrt(2)");
+    assert_eq!(maxima("x^2 + 2*x + 1"), "x^2 + 2*x + 1");
+    assert_eq!(maxima("-x^2"), "-x^2");
+    assert_eq!(maxima("(x + 1)^(1/2)"), "(x + 1)^(1/2)");
+    assert_eq!(maxima("ln(x) + log(x, 2) + log10(y)"), "log(x) + log(x)/log(2) + log(y)/log(10)");
+    assert_eq!(maxima("root(x + 1, 3)"), "(x + 1)^(1/3)");
+    assert_eq!(maxima("x % 7 == 1"), "mod(x, 7) = 1");
+    assert_eq!(maxima("x != y && !p"), "x # y and not p");
+    assert_eq!(maxima("(n + 1)!"), "(n + 1)!");
+    assert_eq!(maxima("arctan(x) + ceil(y)"), "atan(x) + ceiling(y)");
+    assert_eq!(formatter.format(&Expression::Number(Number::complex(Number::from(3), Number::from(-4)))), "3-4*%i");
+
+    // 矩阵与列表
+    assert_eq!(maxima("[[1, 2], [3, 4]]"), "matrix([1, 2], [3, 4])");
+    assert_eq!(maxima("[1, 2, 3]"), "[1, 2, 3]");
+    assert_eq!(maxima("det([[a, b], [c, d]])"), "determinant(matrix([a, b], [c, d]))");
+    assert_eq!(maxima("A[2, :] + A[:, 1]"), "row(A, 2) + col(A, 1)");
+    assert_eq!(maxima("A[1, 2]"), "A[1, 2]");
+
+    // 格式化器工厂
+    let mut multi = MultiFormatter::with_format(FormatType::Maxima);
+    assert_eq!(multi.format(&parser.parse("pi/2").unwrap()), "%pi/2");
+    multi.set_format_type(FormatType::Standard);
+    assert_eq!(multi.format(&parser.parse("pi/2").unwrap()), "π / 2");
+}
+
 #[cfg(test)]
 mod integration_tests {
     use super::*;
@@ -393,13 +780,14 @@ mod integration_tests {
         use yufmath::Yufmath;
         use yufmath::formatter::FormatType;
         
-        let mut yuf = Yufmath::new();
+        let yuf = Yufmath::new();
         
         // 测试不同格式的设置
         let formats = vec![
             FormatType::Standard,
             FormatType::LaTeX,
             FormatType::MathML,
+            FormatType::Maxima,
         ];
         
         for format_type in formats {
@@ -407,6 +795,8 @@ mod integration_tests {
                 format_type: format_type.clone(),
                 precision: Some(3),
                 use_parentheses: true,
+                sort_terms: false,
+                ..FormatOptions::default()
             };
             
             yuf.set_format_options(options);
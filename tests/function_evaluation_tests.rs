@@ -2,7 +2,7 @@
 //!
 //! 测试基本数学函数的计算功能。
 
-use yufmath::Yufmath;
+use yufmath::{Yufmath, Expression, Number, Convergence};
 
 #[test]
 fn test_logarithm_functions() {
@@ -164,4 +164,126 @@ fn test_numerical_functions() {
     if let Ok(val) = result.parse::<f64>() {
         assert!((val - 1.0).abs() < 0.01);
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_root_functions() {
+    let yuf = Yufmath::new();
+    
+    // cbrt(27) = 3
+    let result = yuf.compute("cbrt(27)").unwrap();
+    assert_eq!(result, "3");
+    
+    // cbrt(-8) = -2
+    let result = yuf.compute("cbrt(-8)").unwrap();
+    assert_eq!(result, "-2");
+    
+    // root(81, 4) = 3
+    let result = yuf.compute("root(81, 4)").unwrap();
+    assert_eq!(result, "3");
+}
+
+#[test]
+fn test_rounding_functions() {
+    let yuf = Yufmath::new();
+    
+    // floor(7/2) = 3
+    let result = yuf.compute("floor(7/2)").unwrap();
+    assert_eq!(result, "3");
+    
+    // ceil(7/2) = 4
+    let result = yuf.compute("ceil(7/2)").unwrap();
+    assert_eq!(result, "4");
+    
+    // round(-5/2) = -3
+    let result = yuf.compute("round(-5/2)").unwrap();
+    assert_eq!(result, "-3");
+    
+    // trunc(-7/2) = -3
+    let result = yuf.compute("trunc(-7/2)").unwrap();
+    assert_eq!(result, "-3");
+}
+
+#[test]
+fn test_rounding_function_derivatives() {
+    let yuf = Yufmath::new();
+    
+    // 取整函数的导数取几乎处处成立的 0，不保留跳跃点（整数或半整数处）导数不存在的条件
+    for name in ["floor", "ceil", "round", "trunc"] {
+        let expr = yuf.parse(&format!("{}(x^2 + 3*x)", name)).unwrap();
+        assert_eq!(yuf.diff(&expr, "x").unwrap(), Expression::Number(Number::integer(0)));
+    }
+    let expr = yuf.parse("x * floor(x)").unwrap();
+    assert_eq!(yuf.diff(&expr, "x").unwrap(), yuf.parse("floor(x)").unwrap());
+}
+
+#[test]
+fn test_modulo_and_integer_division() {
+    let yuf = Yufmath::new();
+    
+    // 取模采用余数非负的约定：(-7) % 3 = 2
+    let result = yuf.compute("(-7) % 3").unwrap();
+    assert_eq!(result, "2");
+    
+    // rem 采用截断约定：rem(-7, 3) = -1
+    let result = yuf.compute("rem(-7, 3)").unwrap();
+    assert_eq!(result, "-1");
+    
+    // quotient(-7, 3) = -3
+    let result = yuf.compute("quotient(-7, 3)").unwrap();
+    assert_eq!(result, "-3");
+    
+    // quotient(17, 5) = 3, mod(17, 5) = 2
+    let result = yuf.compute("quotient(17, 5)").unwrap();
+    assert_eq!(result, "3");
+    let result = yuf.compute("mod(17, 5)").unwrap();
+    assert_eq!(result, "2");
+}
+
+#[test]
+fn test_gcd_lcm_multiple_arguments() {
+    let yuf = Yufmath::new();
+    
+    // gcd(12, 18, 24) = 6
+    let result = yuf.compute("gcd(12, 18, 24)").unwrap();
+    assert_eq!(result, "6");
+    
+    // lcm(4, 6, 8) = 24
+    let result = yuf.compute("lcm(4, 6, 8)").unwrap();
+    assert_eq!(result, "24");
+    
+    // 单个参数返回其绝对值
+    let result = yuf.compute("gcd(-9)").unwrap();
+    assert_eq!(result, "9");
+    
+    // API 形式
+    let values: Vec<_> = [12, 18, 24].iter().map(|&v| Expression::Number(Number::integer(v))).collect();
+    assert_eq!(yuf.gcd_list(&values).unwrap(), Expression::Number(Number::integer(6)));
+    assert_eq!(yuf.lcm_list(&[]).unwrap(), Expression::Number(Number::integer(1)));
+}
+
+#[test]
+fn test_infinite_series() {
+    let yuf = Yufmath::new();
+    
+    // 已知级数与等比级数
+    let result = yuf.compute("sum(1/n^2, n, 1, inf)").unwrap();
+    assert_eq!(result, "π^2 / 6");
+    let result = yuf.compute("sum(x^n/n!, n, 0, inf)").unwrap();
+    assert_eq!(result, "exp(x)");
+    let result = yuf.compute("sum(1/2^n, n, 0, inf)").unwrap();
+    assert_eq!(result, "2");
+    
+    // 调和级数发散到正无穷
+    let result = yuf.compute("sum(1/n, n, 1, inf)").unwrap();
+    assert_eq!(result, "∞");
+    
+    // 无法求出闭式时保留符号形式并说明原因
+    let expr = yuf.parse("1/n^3").unwrap();
+    let lower = yuf.parse("1").unwrap();
+    let upper = yuf.parse("inf").unwrap();
+    let result = yuf.sum(&expr, "n", &lower, &upper).unwrap();
+    assert!(!result.is_evaluated());
+    assert_eq!(result.convergence, Convergence::Convergent);
+    assert!(result.notes.iter().any(|note| note.contains("无法求值")));
+}
@@ -101,21 +101,8 @@ fn test_integrate_trigonometric() {
     
     let result = yuf.integrate(&expr, "x").unwrap();
     
-    // 结果应该是 -cos(x)
-    match result {
-        Expression::UnaryOp { op: UnaryOperator::Negate, operand } => {
-            match operand.as_ref() {
-                Expression::UnaryOp { op: UnaryOperator::Cos, operand: inner } => {
-                    match inner.as_ref() {
-                        Expression::Variable(var) => assert_eq!(var, "x"),
-                        _ => panic!("期望得到变量 x"),
-                    }
-                }
-                _ => panic!("期望得到 cos(x)"),
-            }
-        }
-        _ => panic!("期望得到负号表达式"),
-    }
+    // 结果应该是 -cos(x)，函数型运算符统一以函数调用表示
+    assert_eq!(result, Expression::negate(Expression::cos(Expression::Variable("x".to_string()))));
     
     // 测试 cos(x) 的积分
     let expr = Expression::UnaryOp {
@@ -126,15 +113,7 @@ fn test_integrate_trigonometric() {
     let result = yuf.integrate(&expr, "x").unwrap();
     
     // 结果应该是 sin(x)
-    match result {
-        Expression::UnaryOp { op: UnaryOperator::Sin, operand } => {
-            match operand.as_ref() {
-                Expression::Variable(var) => assert_eq!(var, "x"),
-                _ => panic!("期望得到变量 x"),
-            }
-        }
-        _ => panic!("期望得到 sin(x)"),
-    }
+    assert_eq!(result, Expression::sin(Expression::Variable("x".to_string())));
 }
 
 #[test]
@@ -150,15 +129,7 @@ fn test_integrate_exponential() {
     let result = yuf.integrate(&expr, "x").unwrap();
     
     // 结果应该是 e^x
-    match result {
-        Expression::UnaryOp { op: UnaryOperator::Exp, operand } => {
-            match operand.as_ref() {
-                Expression::Variable(var) => assert_eq!(var, "x"),
-                _ => panic!("期望得到变量 x"),
-            }
-        }
-        _ => panic!("期望得到 e^x"),
-    }
+    assert_eq!(result, Expression::exp(Expression::Variable("x".to_string())));
 }
 
 #[test]
@@ -226,4 +197,51 @@ fn test_integrate_other_variable() {
         }
         _ => panic!("期望得到乘法表达式"),
     }
-}
\ No newline at end of file
+}
+#[test]
+fn test_integrate_to_special_functions() {
+    let yuf = Yufmath::new();
+    
+    // 初等方法无法积分时用特殊函数表示
+    let cases = [
+        ("exp(-x^2)", "sqrt(π) / 2 * erf(x)"),
+        ("sin(x)/x", "Si(x)"),
+        ("cos(x)/x", "Ci(x)"),
+        ("exp(x)/x", "Ei(x)"),
+        ("1/ln(x)", "li(x)"),
+        ("sinc(x)", "Si(x)"),
+    ];
+    for (input, expected) in cases {
+        let integrand = yuf.parse(input).unwrap();
+        let integral = yuf.integrate(&integrand, "x").unwrap();
+        assert_eq!(integral.to_string(), expected, "∫{} dx", input);
+        
+        // 结果求导后应与被积函数一致
+        let derivative = yuf.diff(&integral, "x").unwrap();
+        assert!(yuf.are_equivalent(&derivative, &integrand).unwrap().equivalent, "d/dx ∫{} dx", input);
+    }
+    
+    // 系数不为 1 的高斯函数
+    let integrand = yuf.parse("exp(-3*x^2)").unwrap();
+    let integral = yuf.integrate(&integrand, "x").unwrap();
+    let derivative = yuf.diff(&integral, "x").unwrap();
+    assert!(yuf.are_equivalent(&derivative, &integrand).unwrap().equivalent);
+    
+    // e^{x²} 不能用 erf 表示
+    assert!(yuf.integrate(&yuf.parse("exp(x^2)").unwrap(), "x").is_err());
+}
+
+#[test]
+fn test_special_function_values() {
+    let yuf = Yufmath::new();
+    let vars = std::collections::HashMap::new();
+    
+    let value = yuf.numerical_evaluate(&yuf.parse("erf(1)").unwrap(), &vars).unwrap();
+    assert!((value - 0.8427007929497149).abs() < 1e-12);
+    
+    // 零点处化简为精确值
+    assert_eq!(yuf.compute("erf(0)").unwrap(), "0");
+    assert_eq!(yuf.compute("erfc(0)").unwrap(), "1");
+    assert_eq!(yuf.compute("sinc(0)").unwrap(), "1");
+    assert_eq!(yuf.compute("Si(x)").unwrap(), "Si(x)");
+}
@@ -2,6 +2,7 @@
 //!
 //! 测试交互式 REPL 功能的各个方面。
 
+use yufmath::ComputePhase;
 use yufmath::cli::interactive::InteractiveSession;
 
 #[test]
@@ -111,6 +112,28 @@ fn test_precision_commands() {
     assert!(result.contains("无效的精度值"));
 }
 
+#[test]
+fn test_config_commands() {
+    let mut session = InteractiveSession::new();
+    
+    let result = session.process_command("set timeout 300").unwrap();
+    assert!(result.contains("计算超时已设置为: 300 秒"));
+    let result = session.process_command("set precision 200").unwrap();
+    assert!(result.contains("精确计算的最大位数已设置为: 200"));
+    
+    // 修改后的配置在 show config 中可见
+    let result = session.process_command("show config").unwrap();
+    assert!(result.contains("max_compute_time = 300"));
+    assert!(result.contains("max_precision = 200"));
+    
+    let result = session.process_command("set timeout off").unwrap();
+    assert!(result.contains("已取消计算超时"));
+    let result = session.process_command("set timeout soon").unwrap();
+    assert!(result.contains("无效的数值"));
+    let result = session.process_command("set unknown 1").unwrap();
+    assert!(result.contains("未知的配置项"));
+}
+
 #[test]
 fn test_empty_input() {
     let mut session = InteractiveSession::new();
@@ -194,4 +217,88 @@ mod integration_tests {
         let clear_result = session.process_command("clear").unwrap();
         assert_eq!(clear_result, "变量已清空");
     }
-}
\ No newline at end of file
+}
+#[test]
+fn test_multiple_statements() {
+    let mut session = InteractiveSession::new();
+    
+    // 逐条回显，;; 结尾的语句不回显
+    let result = session.process_command("a = 2;; b = 3; a*b # 注释").unwrap();
+    let lines: Vec<&str> = result.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("b = "));
+    assert!(lines[1].contains('6'));
+    
+    // 只有注释的输入没有输出
+    assert_eq!(session.process_command("// 注释").unwrap(), "");
+}
+
+#[test]
+fn test_timing_toggle() {
+    let mut session = InteractiveSession::new();
+    
+    assert_eq!(session.process_command("1 + 1").unwrap(), "2");
+    assert!(session.process_command("timing on").is_ok());
+    let result = session.process_command("a = 2; a + 1").unwrap();
+    let lines: Vec<&str> = result.lines().collect();
+    assert_eq!(lines.len(), 3);
+    assert!(lines[2].contains(ComputePhase::Parsing.label()));
+    // 未知参数只提示用法，不改变开关
+    assert!(session.process_command("timing maybe").unwrap().contains("timing on|off"));
+    assert!(session.process_command("timing off").is_ok());
+    assert_eq!(session.process_command("1 + 1").unwrap(), "2");
+}
+
+#[test]
+fn test_inline_plot() {
+    let mut session = InteractiveSession::new();
+    
+    let result = session.process_command("plot(sin(x), -pi, pi)").unwrap();
+    assert!(result.lines().count() > 3);
+    assert!(result.chars().any(|c| ('\u{2801}'..='\u{28ff}').contains(&c)));
+    assert!(result.lines().last().unwrap().trim().starts_with("-3.14"));
+    
+    // 多条曲线带图例
+    let result = session.process_command("plot(sin(x), cos(x), 0, 1)").unwrap();
+    assert!(result.lines().last().unwrap().contains("o cos(x)"));
+    
+    // 缺少区间时报错
+    assert!(session.process_command("plot(sin(x))").is_err());
+}
+
+#[test]
+fn test_multiline_input() {
+    use yufmath::cli::interactive::{needs_continuation, MultilineInput};
+
+    // 矩阵分两行输入
+    let mut input = MultilineInput::new();
+    assert_eq!(input.push_line("[[1, 2],"), None);
+    assert!(input.is_pending());
+    let matrix = input.push_line(" [3, 4]]").unwrap();
+    assert!(!input.is_pending());
+
+    let mut session = InteractiveSession::new();
+    assert_eq!(session.process_command(&matrix).unwrap(), session.process_command("[[1, 2], [3, 4]]").unwrap());
+
+    // 反斜杠续行，续行时空行直接提交
+    assert_eq!(input.push_line("1 + \\"), None);
+    assert_eq!(input.push_line("2").as_deref(), Some("1 + \n2"));
+    assert_eq!(input.push_line("sin(x"), None);
+    assert_eq!(input.push_line("").as_deref(), Some("sin(x"));
+
+    // 空缓冲时只输入反斜杠也等待续行
+    assert_eq!(input.push_line("\\"), None);
+    assert!(input.is_pending());
+    assert_eq!(input.push_line("x + 1").as_deref(), Some("\nx + 1"));
+    assert!(!input.is_pending());
+    assert_eq!(input.push_line("  \\  "), None);
+    assert_eq!(input.push_line("").as_deref(), Some("  "));
+    assert!(!input.is_pending());
+
+    assert!(needs_continuation("sin(x"));
+    assert!(needs_continuation("x +"));
+    assert!(needs_continuation("x \\"));
+    assert!(!needs_continuation("x"));
+    assert!(!needs_continuation("x!"));
+    assert!(!needs_continuation("2 # ("));
+}
@@ -3,7 +3,7 @@ use yufmath::formatter::{Formatter, LaTeXFormatter};
 
 #[test]
 fn test_sqrt_latex_formatting() {
-    let mut engine = Yufmath::new();
+    let engine = Yufmath::new();
     let formatter = LaTeXFormatter::new();
     
     let result = engine.parse("sqrt(3)").unwrap();
@@ -17,7 +17,7 @@ fn test_sqrt_latex_formatting() {
 
 #[test]
 fn test_trigonometric_functions_latex() {
-    let mut engine = Yufmath::new();
+    let engine = Yufmath::new();
     let formatter = LaTeXFormatter::new();
     
     let test_cases = vec![
@@ -35,7 +35,7 @@ fn test_trigonometric_functions_latex() {
 
 #[test]
 fn test_logarithmic_functions_latex() {
-    let mut engine = Yufmath::new();
+    let engine = Yufmath::new();
     let formatter = LaTeXFormatter::new();
     
     let test_cases = vec![
@@ -54,7 +54,7 @@ fn test_logarithmic_functions_latex() {
 
 #[test]
 fn test_absolute_value_latex() {
-    let mut engine = Yufmath::new();
+    let engine = Yufmath::new();
     let formatter = LaTeXFormatter::new();
     
     let result = engine.parse("abs(x)").unwrap();
@@ -64,7 +64,7 @@ fn test_absolute_value_latex() {
 
 #[test]
 fn test_complex_expressions_latex() {
-    let mut engine = Yufmath::new();
+    let engine = Yufmath::new();
     let formatter = LaTeXFormatter::new();
     
     let test_cases = vec![
@@ -384,4 +384,82 @@ fn test_cow_with_multiple_references() {
     assert!(!cow2.is_modified());
     assert_eq!(cow1.ref_count(), 1); // cow1 现在有自己的副本
     assert_eq!(cow2.ref_count(), 2); // shared + cow2
-}
\ No newline at end of file
+}
+
+/// 大量构造表达式：反复求导并展开，用完即交还节点池
+fn build_many_expressions() -> usize {
+    use yufmath::core::memory::{node_pool_stats, recycle};
+    use yufmath::Yufmath;
+    
+    let yuf = Yufmath::new();
+    let polynomial = yuf.parse("(x + 1) * (x - 2) * (x + 3) * (x^2 - 4) * x^3").unwrap();
+    let before = node_pool_stats().allocations;
+    for i in 0..200 {
+        let expr = Expression::add(polynomial.clone(), Expression::Number(Number::integer(i)));
+        let derivative = yuf.diff(&expr, "x").unwrap();
+        let expanded = yuf.expand(&Expression::multiply(expr.clone(), expr)).unwrap();
+        recycle(derivative);
+        recycle(expanded);
+    }
+    node_pool_stats().allocations - before
+}
+
+#[test]
+fn test_node_pool_reduces_allocations() {
+    use yufmath::core::memory::{node_pool_stats, NodePoolScope};
+    
+    // 构造内存管理器不影响节点池
+    let mut manager = MemoryManager::new();
+    let without_pool = build_many_expressions();
+    assert_eq!(node_pool_stats().free, 0);
+    
+    let pool = NodePoolScope::new(4096);
+    let with_pool = build_many_expressions();
+    println!("节点分配次数: 未启用池 {}，启用池 {}", without_pool, with_pool);
+    assert!(with_pool < without_pool / 2);
+    
+    let stats = manager.get_stats();
+    assert!(stats.node_reuses > 0);
+    assert!(node_pool_stats().free <= 4096);
+    
+    // 离开作用域后池中节点全部释放
+    drop(pool);
+    assert_eq!(node_pool_stats().free, 0);
+}
+
+#[test]
+fn test_memory_accounting() {
+    use yufmath::core::memory::{begin_accounting, end_accounting, accounted_usage, check_memory_limit, take_reclaim_request};
+    
+    let x = || Expression::variable("x");
+    
+    // 未记账时不统计，也不会报超限
+    assert!(accounted_usage().is_none());
+    let _ = Expression::add(x(), x());
+    assert!(check_memory_limit().is_ok());
+    
+    let node = std::mem::size_of::<Expression>();
+    begin_accounting(Some(node * 10), Some(node * 5));
+    let exprs: Vec<Expression> = (0..4).map(|_| Expression::add(x(), x())).collect();
+    let usage = accounted_usage().unwrap();
+    assert_eq!(usage.current, node * 8);
+    assert!(!usage.limit_exceeded);
+    // 越过回收阈值只请求一次回收
+    assert!(take_reclaim_request());
+    assert!(!take_reclaim_request());
+    assert!(check_memory_limit().is_ok());
+    
+    // 嵌套记账不重置账本
+    begin_accounting(None, None);
+    let more: Vec<Expression> = (0..2).map(|_| Expression::multiply(x(), x())).collect();
+    let inner = end_accounting();
+    assert_eq!(inner.peak, node * 12);
+    assert!(inner.limit_exceeded);
+    assert!(matches!(check_memory_limit(), Err(yufmath::ComputeError::ResourceLimit { .. })));
+    
+    let outer = end_accounting();
+    assert_eq!(outer.peak, node * 12);
+    assert!(accounted_usage().is_none());
+    assert!(check_memory_limit().is_ok());
+    drop((exprs, more));
+}
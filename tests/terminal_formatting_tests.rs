@@ -2,8 +2,10 @@
 //!
 //! 测试终端格式化器的功能，包括颜色输出和数值近似值。
 
-use yufmath::formatter::{TerminalFormatter, Formatter};
-use yufmath::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator};
+use yufmath::formatter::{TerminalFormatter, StandardFormatter, Formatter, FormatOptions};
+use yufmath::formatter::terminal::strip_ansi;
+use yufmath::core::{Expression, Number, MathConstant, BinaryOperator, UnaryOperator, INDEX_FUNCTION, INDEX_ALL};
+use yufmath::parser::{Parser, syntax::ExpressionParser};
 use num_bigint::BigInt;
 use num_rational::BigRational;
 
@@ -166,19 +168,19 @@ fn test_precision_setting() {
 }
 
 #[test]
-fn test_special_symbols() {
+fn test_operator_symbols() {
     let mut formatter = TerminalFormatter::new();
     formatter.set_colors_enabled(false);
     formatter.set_approximations_enabled(false);
     
-    // 测试特殊数学符号
+    // 运算符与标准格式化器一致，输出可以直接作为输入
     let multiply_expr = Expression::BinaryOp {
         op: BinaryOperator::Multiply,
         left: Box::new(Expression::Number(Number::Integer(BigInt::from(2)))),
         right: Box::new(Expression::Number(Number::Integer(BigInt::from(3)))),
     };
     let formatted = formatter.format(&multiply_expr);
-    assert!(formatted.contains("×")); // 应该使用×而不是*
+    assert_eq!(formatted, "2 * 3");
     
     let divide_expr = Expression::BinaryOp {
         op: BinaryOperator::Divide,
@@ -186,7 +188,7 @@ fn test_special_symbols() {
         right: Box::new(Expression::Number(Number::Integer(BigInt::from(2)))),
     };
     let formatted = formatter.format(&divide_expr);
-    assert!(formatted.contains("÷")); // 应该使用÷而不是/
+    assert_eq!(formatted, "6 / 2");
 }
 
 #[test]
@@ -205,4 +207,80 @@ fn test_complex_number_formatting() {
     assert!(formatted.contains("3"));
     assert!(formatted.contains("4"));
     assert!(formatted.contains("i"));
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_colored_text_matches_standard() {
+    let parser = ExpressionParser::new();
+    let (x, y) = (Expression::variable("x"), Expression::variable("y"));
+    let mut exprs: Vec<Expression> = [
+        "x^2 + 2*x*y - 3/4", "x + (-5)", "-(a + b)", "(-x)^2", "x^(y^z)", "(x^y)^z", "a - (b - c)",
+        "sin(x)^2 + cos(x)^2", "sqrt(x + 1) / abs(y)", "ln(exp(x)) * log10(100)", "n! + (n + 1)!",
+        "x == y", "x <= 1 && y > 2 || z != 0", "max(1, 2, x)", "e^(i*pi) + inf", "2.5 * x - 0.125",
+    ].iter().map(|input| parser.parse(input).unwrap()).collect();
+    exprs.extend([
+        Expression::Number(Number::Rational(BigRational::new(BigInt::from(-22), BigInt::from(7)))),
+        Expression::Number(Number::Float(-1.5)),
+        Expression::Number(Number::Complex { real: Box::new(Number::integer(3)), imaginary: Box::new(Number::integer(-4)) }),
+        Expression::Number(Number::Complex { real: Box::new(Number::integer(0)), imaginary: Box::new(Number::integer(1)) }),
+        Expression::Number(Number::Constant(MathConstant::Pi)),
+        Expression::Number(Number::Symbolic(Box::new(Expression::add(x.clone(), y.clone())))),
+        Expression::Constant(MathConstant::GoldenRatio),
+        Expression::unary_op(UnaryOperator::Not, Expression::binary_op(BinaryOperator::And, x.clone(), y.clone())),
+        Expression::unary_op(UnaryOperator::Transpose, Expression::multiply(x.clone(), y.clone())),
+        Expression::unary_op(UnaryOperator::Conjugate, x.clone()),
+        Expression::unary_op(UnaryOperator::Gamma, x.clone()),
+        Expression::unary_op(UnaryOperator::Plus, Expression::subtract(x.clone(), y.clone())),
+        Expression::binary_op(BinaryOperator::Union, Expression::set(vec![x.clone(), y.clone()]), Expression::set(vec![])),
+        Expression::function(INDEX_FUNCTION, vec![Expression::add(x.clone(), y.clone()), Expression::function(INDEX_ALL, vec![]), Expression::Number(Number::integer(2))]),
+        Expression::matrix(vec![vec![x.clone(), Expression::Number(Number::integer(1))], vec![y.clone(), Expression::negate(x.clone())]]).unwrap(),
+        Expression::vector(vec![x.clone(), Expression::power(y.clone(), Expression::Number(Number::integer(2)))]).unwrap(),
+        Expression::interval(Expression::Number(Number::integer(0)), Expression::Constant(MathConstant::PositiveInfinity), true, false),
+        Expression::interval(Expression::negate(x.clone()), y.clone(), false, true),
+    ]);
+    
+    let options = [
+        FormatOptions::default(),
+        FormatOptions { precision: Some(2), ..FormatOptions::default() },
+        FormatOptions { sort_terms: true, ..FormatOptions::default() },
+        FormatOptions { use_parentheses: false, ..FormatOptions::default() },
+    ];
+    for options in options {
+        let mut standard = StandardFormatter::new();
+        standard.set_options(options.clone());
+        let mut terminal = TerminalFormatter::new();
+        terminal.set_options(options);
+        for expr in &exprs {
+            terminal.set_colors_enabled(true);
+            let colored = terminal.format(expr);
+            terminal.set_colors_enabled(false);
+            let plain = terminal.format(expr);
+            assert_eq!(strip_ansi(&colored), standard.format(expr), "{:?}", expr);
+            assert_eq!(plain, standard.format(expr), "{:?}", expr);
+        }
+    }
+}
+
+#[test]
+fn test_bracket_colors_by_depth() {
+    let parser = ExpressionParser::new();
+    let formatter = TerminalFormatter::new();
+    let colored = formatter.format(&parser.parse("f(g(x))").unwrap());
+    
+    // 配对的括号颜色相同，相邻两层颜色不同
+    let paint = |text: &str, depth: usize| {
+        let colours = [ansi_term::Colour::White, ansi_term::Colour::Fixed(208)];
+        colours[depth].paint(text).to_string()
+    };
+    assert!(colored.contains(&paint("(", 0)) && colored.contains(&paint(")", 0)));
+    assert!(colored.contains(&paint("(", 1)) && colored.contains(&paint(")", 1)));
+    assert_eq!(strip_ansi(&colored), "f(g(x))");
+}
+
+#[test]
+fn test_strip_ansi() {
+    let red = ansi_term::Colour::Red.bold().paint("^").to_string();
+    assert_eq!(strip_ansi(&format!("a{}b", red)), "a^b");
+    assert_eq!(strip_ansi("x + 1"), "x + 1");
+    assert_eq!(strip_ansi("\x1b[38;5;208m(\x1b[0m"), "(");
+}
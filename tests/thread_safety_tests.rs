@@ -0,0 +1,120 @@
+//! # 线程安全测试
+//!
+//! 测试 Yufmath 实例在多个线程间共享时的行为。
+
+use yufmath::{Yufmath, ComputeProgress, CancelToken};
+use std::sync::{Arc, Barrier};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+const THREADS: usize = 16;
+const ROUNDS: usize = 20;
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn test_yufmath_is_send_sync() {
+    assert_send_sync::<Yufmath>();
+    assert_send_sync::<Arc<Yufmath>>();
+}
+
+#[test]
+fn test_shared_instance_stress() {
+    let yuf = Arc::new(Yufmath::new());
+    let callback_calls = Arc::new(AtomicUsize::new(0));
+
+    // 回调中重新访问实例，若在锁内调用会死锁
+    let observer = Arc::downgrade(&yuf);
+    let counter = Arc::clone(&callback_calls);
+    yuf.set_progress_callback(Box::new(move |_: &ComputeProgress| {
+        counter.fetch_add(1, Ordering::Relaxed);
+        observer.upgrade().is_some_and(|yuf| yuf.get_performance_stats().is_some())
+    }));
+
+    let handles: Vec<_> = (0..THREADS).map(|i| {
+        let yuf = Arc::clone(&yuf);
+        thread::spawn(move || {
+            for j in 0..ROUNDS {
+                let n = (i * ROUNDS + j) as i64;
+                assert_eq!(yuf.compute(&format!("{} + 1", n)).unwrap(), (n + 1).to_string());
+
+                let expr = yuf.parse("x^2 + 3*x").unwrap();
+                let derivative = yuf.diff(&expr, "x").unwrap();
+                assert_eq!(yuf.format(&yuf.simplify(&derivative).unwrap()), yuf.compute("2*x + 3").unwrap());
+
+                let result = if j % 2 == 0 {
+                    yuf.compute_with_progress(&format!("{} * 2", n)).unwrap()
+                } else {
+                    let local = AtomicUsize::new(0);
+                    let result = yuf.compute_with_callback(&format!("{} * 2", n), &|_: &ComputeProgress| {
+                        local.fetch_add(1, Ordering::Relaxed);
+                        true
+                    }).unwrap();
+                    assert_eq!(local.load(Ordering::Relaxed), 4);
+                    result
+                };
+                assert_eq!(result, (n * 2).to_string());
+            }
+        })
+    }).collect();
+
+    for handle in handles {
+        handle.join().expect("工作线程不应 panic");
+    }
+
+    let stats = yuf.get_performance_stats().unwrap();
+    assert_eq!(stats.total_computations, THREADS * ROUNDS);
+    assert_eq!(callback_calls.load(Ordering::Relaxed), THREADS * ROUNDS / 2 * 4);
+}
+
+#[test]
+fn test_callback_cancels_only_its_call() {
+    let yuf = Yufmath::new();
+    assert!(yuf.compute_with_callback("1 + 1", &|_: &ComputeProgress| false).is_err());
+    assert_eq!(yuf.compute_with_progress("1 + 1").unwrap(), "2");
+}
+
+#[test]
+fn test_cancel_is_per_call() {
+    let yuf = Arc::new(Yufmath::new());
+    let started = Arc::new(Barrier::new(2));
+    let resume = Arc::new(Barrier::new(2));
+
+    // 线程 A 的计算在第一个进度点停住，等主线程取消后再继续
+    let slow = {
+        let (yuf, started, resume) = (Arc::clone(&yuf), Arc::clone(&started), Arc::clone(&resume));
+        thread::spawn(move || {
+            let first = AtomicUsize::new(0);
+            yuf.compute_with_callback("1 + 1", &|_: &ComputeProgress| {
+                if first.fetch_add(1, Ordering::Relaxed) == 0 {
+                    started.wait();
+                    resume.wait();
+                }
+                true
+            })
+        })
+    };
+    started.wait();
+    yuf.cancel_computation();
+
+    // 线程 B 之后开始的计算不受影响，也不会清除 A 的取消标志
+    let other = {
+        let yuf = Arc::clone(&yuf);
+        thread::spawn(move || yuf.compute_with_progress("2 + 2"))
+    };
+    assert_eq!(other.join().unwrap().unwrap(), "4");
+    assert!(yuf.is_cancelled());
+    resume.wait();
+    assert!(slow.join().unwrap().is_err());
+
+    // 用 CancelToken 只取消其中一次计算
+    let token = CancelToken::new();
+    let handle = token.clone();
+    let result = yuf.compute_with_token("3 + 3", &|_: &ComputeProgress| {
+        handle.cancel();
+        assert_eq!(yuf.compute("4 + 4").unwrap(), "8");
+        true
+    }, token);
+    assert!(result.is_err());
+    assert!(!yuf.is_cancelled());
+}